@@ -103,6 +103,13 @@ impl<Idx, T> Arena<Idx, T> {
         self.entities.clear();
     }
 
+    /// Removes all entities from the arena that have been allocated after the first `len` entities.
+    ///
+    /// Does nothing if the arena has not allocated more than `len` entities.
+    pub fn truncate(&mut self, len: usize) {
+        self.entities.truncate(len);
+    }
+
     /// Returns an iterator over the shared reference of the arena entities.
     pub fn iter(&self) -> Iter<Idx, T> {
         Iter {
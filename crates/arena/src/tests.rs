@@ -57,6 +57,21 @@ mod arena {
         assert_eq!(arena.get(arena.len()), None);
     }
 
+    #[test]
+    fn truncate_works() {
+        let mut arena = alloc_arena(TEST_ENTITIES);
+        // Truncating to a larger length does nothing.
+        arena.truncate(arena.len() + 1);
+        assert_eq!(arena.len(), TEST_ENTITIES.len());
+        // Truncating removes all entities after the first `len` ones.
+        arena.truncate(2);
+        assert_eq!(arena.len(), 2);
+        assert!(arena.iter().eq(TEST_ENTITIES[..2].iter().enumerate()));
+        assert_eq!(arena.get(2), None);
+        // Indices of removed entities are handed out again.
+        assert_eq!(arena.alloc("e"), 2);
+    }
+
     #[test]
     fn iter_works() {
         let arena = alloc_arena(TEST_ENTITIES);
@@ -0,0 +1,191 @@
+// The subset of the standard WebAssembly C API supported by Wasmi.
+//
+// See https://github.com/WebAssembly/wasm-c-api for the full specification.
+// Functions marked `own` transfer ownership of their objects to the caller
+// or callee respectively. Owned objects must be destroyed via their
+// `wasm_*_delete` function.
+
+#ifndef WASM_H
+#define WASM_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define own
+
+typedef char byte_t;
+typedef float float32_t;
+typedef double float64_t;
+
+// Vectors
+
+#define WASM_DECLARE_VEC(name, ptr_or_none)                                                   \
+    typedef struct wasm_##name##_vec_t {                                                      \
+        size_t size;                                                                          \
+        wasm_##name##_t ptr_or_none* data;                                                    \
+    } wasm_##name##_vec_t;                                                                    \
+                                                                                              \
+    void wasm_##name##_vec_new_empty(own wasm_##name##_vec_t* out);                           \
+    void wasm_##name##_vec_new_uninitialized(own wasm_##name##_vec_t* out, size_t);           \
+    void wasm_##name##_vec_new(                                                               \
+        own wasm_##name##_vec_t* out, size_t, own wasm_##name##_t ptr_or_none const[]);       \
+    void wasm_##name##_vec_copy(own wasm_##name##_vec_t* out, const wasm_##name##_vec_t*);    \
+    void wasm_##name##_vec_delete(own wasm_##name##_vec_t*);
+
+typedef byte_t wasm_byte_t;
+WASM_DECLARE_VEC(byte, )
+
+typedef wasm_byte_vec_t wasm_name_t;
+typedef wasm_name_t wasm_message_t;
+
+// Runtime
+
+typedef struct wasm_config_t wasm_config_t;
+own wasm_config_t* wasm_config_new(void);
+void wasm_config_delete(own wasm_config_t*);
+
+typedef struct wasm_engine_t wasm_engine_t;
+own wasm_engine_t* wasm_engine_new(void);
+own wasm_engine_t* wasm_engine_new_with_config(own wasm_config_t*);
+void wasm_engine_delete(own wasm_engine_t*);
+
+typedef struct wasm_store_t wasm_store_t;
+own wasm_store_t* wasm_store_new(wasm_engine_t*);
+void wasm_store_delete(own wasm_store_t*);
+
+// Types
+
+typedef uint8_t wasm_valkind_t;
+enum wasm_valkind_enum {
+    WASM_I32,
+    WASM_I64,
+    WASM_F32,
+    WASM_F64,
+    WASM_ANYREF = 128,
+    WASM_FUNCREF,
+};
+
+typedef struct wasm_valtype_t wasm_valtype_t;
+own wasm_valtype_t* wasm_valtype_new(wasm_valkind_t);
+wasm_valkind_t wasm_valtype_kind(const wasm_valtype_t*);
+own wasm_valtype_t* wasm_valtype_copy(const wasm_valtype_t*);
+void wasm_valtype_delete(own wasm_valtype_t*);
+WASM_DECLARE_VEC(valtype, *)
+
+typedef struct wasm_functype_t wasm_functype_t;
+own wasm_functype_t* wasm_functype_new(
+    own wasm_valtype_vec_t* params, own wasm_valtype_vec_t* results);
+const wasm_valtype_vec_t* wasm_functype_params(const wasm_functype_t*);
+const wasm_valtype_vec_t* wasm_functype_results(const wasm_functype_t*);
+own wasm_functype_t* wasm_functype_copy(const wasm_functype_t*);
+void wasm_functype_delete(own wasm_functype_t*);
+
+// Values
+
+typedef struct wasm_ref_t wasm_ref_t;
+
+typedef struct wasm_val_t {
+    wasm_valkind_t kind;
+    union {
+        int32_t i32;
+        int64_t i64;
+        float32_t f32;
+        float64_t f64;
+        struct wasm_ref_t* ref;
+    } of;
+} wasm_val_t;
+
+void wasm_val_copy(own wasm_val_t* out, const wasm_val_t*);
+void wasm_val_delete(own wasm_val_t* v);
+WASM_DECLARE_VEC(val, )
+
+// Traps
+
+typedef struct wasm_trap_t wasm_trap_t;
+own wasm_trap_t* wasm_trap_new(wasm_store_t* store, const wasm_message_t*);
+void wasm_trap_message(const wasm_trap_t*, own wasm_message_t* out);
+void wasm_trap_delete(own wasm_trap_t*);
+
+// Modules
+
+typedef struct wasm_module_t wasm_module_t;
+own wasm_module_t* wasm_module_new(wasm_store_t*, const wasm_byte_vec_t* binary);
+bool wasm_module_validate(wasm_store_t*, const wasm_byte_vec_t* binary);
+own wasm_module_t* wasm_module_copy(const wasm_module_t*);
+void wasm_module_delete(own wasm_module_t*);
+
+// External values
+
+typedef struct wasm_extern_t wasm_extern_t;
+typedef struct wasm_func_t wasm_func_t;
+typedef struct wasm_memory_t wasm_memory_t;
+
+typedef uint8_t wasm_externkind_t;
+enum wasm_externkind_enum {
+    WASM_EXTERN_FUNC,
+    WASM_EXTERN_GLOBAL,
+    WASM_EXTERN_TABLE,
+    WASM_EXTERN_MEMORY,
+};
+
+wasm_externkind_t wasm_extern_kind(const wasm_extern_t*);
+wasm_func_t* wasm_extern_as_func(wasm_extern_t*);
+wasm_memory_t* wasm_extern_as_memory(wasm_extern_t*);
+own wasm_extern_t* wasm_extern_copy(const wasm_extern_t*);
+void wasm_extern_delete(own wasm_extern_t*);
+WASM_DECLARE_VEC(extern, *)
+
+// Functions
+
+typedef own wasm_trap_t* (*wasm_func_callback_t)(
+    const wasm_val_vec_t* args, own wasm_val_vec_t* results);
+typedef own wasm_trap_t* (*wasm_func_callback_with_env_t)(
+    void* env, const wasm_val_vec_t* args, own wasm_val_vec_t* results);
+
+own wasm_func_t* wasm_func_new(wasm_store_t*, const wasm_functype_t*, wasm_func_callback_t);
+own wasm_func_t* wasm_func_new_with_env(
+    wasm_store_t*,
+    const wasm_functype_t* type,
+    wasm_func_callback_with_env_t,
+    void* env,
+    void (*finalizer)(void*));
+own wasm_functype_t* wasm_func_type(const wasm_func_t*);
+size_t wasm_func_param_arity(const wasm_func_t*);
+size_t wasm_func_result_arity(const wasm_func_t*);
+own wasm_trap_t* wasm_func_call(
+    const wasm_func_t*, const wasm_val_vec_t* args, wasm_val_vec_t* results);
+wasm_extern_t* wasm_func_as_extern(wasm_func_t*);
+own wasm_func_t* wasm_func_copy(const wasm_func_t*);
+void wasm_func_delete(own wasm_func_t*);
+
+// Linear memories
+
+typedef uint32_t wasm_memory_pages_t;
+
+byte_t* wasm_memory_data(wasm_memory_t*);
+size_t wasm_memory_data_size(const wasm_memory_t*);
+wasm_memory_pages_t wasm_memory_size(const wasm_memory_t*);
+bool wasm_memory_grow(wasm_memory_t*, wasm_memory_pages_t delta);
+wasm_extern_t* wasm_memory_as_extern(wasm_memory_t*);
+own wasm_memory_t* wasm_memory_copy(const wasm_memory_t*);
+void wasm_memory_delete(own wasm_memory_t*);
+
+// Instances
+
+typedef struct wasm_instance_t wasm_instance_t;
+own wasm_instance_t* wasm_instance_new(
+    wasm_store_t*, const wasm_module_t*, const wasm_extern_vec_t* imports, own wasm_trap_t**);
+void wasm_instance_exports(const wasm_instance_t*, own wasm_extern_vec_t* out);
+own wasm_instance_t* wasm_instance_copy(const wasm_instance_t*);
+void wasm_instance_delete(own wasm_instance_t*);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // WASM_H
@@ -0,0 +1,39 @@
+// Wasmi specific extensions of the WebAssembly C API.
+
+#ifndef WASMI_H
+#define WASMI_H
+
+#include "wasm.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// The code of a trap raised by a Wasm execution.
+typedef uint8_t wasmi_trap_code_t;
+enum wasmi_trap_code_enum {
+    WASMI_TRAP_CODE_UNREACHABLE_CODE_REACHED,
+    WASMI_TRAP_CODE_MEMORY_OUT_OF_BOUNDS,
+    WASMI_TRAP_CODE_TABLE_OUT_OF_BOUNDS,
+    WASMI_TRAP_CODE_INDIRECT_CALL_TO_NULL,
+    WASMI_TRAP_CODE_INTEGER_DIVISION_BY_ZERO,
+    WASMI_TRAP_CODE_INTEGER_OVERFLOW,
+    WASMI_TRAP_CODE_BAD_CONVERSION_TO_INTEGER,
+    WASMI_TRAP_CODE_STACK_OVERFLOW,
+    WASMI_TRAP_CODE_BAD_SIGNATURE,
+    WASMI_TRAP_CODE_OUT_OF_FUEL,
+    WASMI_TRAP_CODE_GROWTH_OPERATION_LIMITED,
+    WASMI_TRAP_CODE_WATCHPOINT,
+};
+
+// Writes the trap code of the trap into `out`.
+//
+// Returns `false` and leaves `out` untouched if the trap has no trap code,
+// e.g. if it was created by a host function or represents an instantiation error.
+bool wasmi_trap_code(const wasm_trap_t* trap, wasmi_trap_code_t* out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // WASMI_H
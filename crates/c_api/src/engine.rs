@@ -0,0 +1,49 @@
+use wasmi::{Config, Engine};
+
+/// The configuration of a [`wasm_engine_t`].
+///
+/// Wraps a [`Config`].
+#[derive(Debug, Default, Clone)]
+pub struct wasm_config_t {
+    pub(crate) inner: Config,
+}
+
+/// Creates a new default [`wasm_config_t`].
+#[no_mangle]
+pub extern "C" fn wasm_config_new() -> Box<wasm_config_t> {
+    Box::default()
+}
+
+/// Deletes the [`wasm_config_t`].
+#[no_mangle]
+pub extern "C" fn wasm_config_delete(_config: Box<wasm_config_t>) {}
+
+/// The engine of the C API that compiles and executes Wasm modules.
+///
+/// Wraps an [`Engine`].
+#[derive(Debug, Clone)]
+pub struct wasm_engine_t {
+    pub(crate) inner: Engine,
+}
+
+/// Creates a new [`wasm_engine_t`] with the default configuration.
+#[no_mangle]
+pub extern "C" fn wasm_engine_new() -> Box<wasm_engine_t> {
+    Box::new(wasm_engine_t {
+        inner: Engine::default(),
+    })
+}
+
+/// Creates a new [`wasm_engine_t`] with the given `config`.
+///
+/// Takes ownership of `config`.
+#[no_mangle]
+pub extern "C" fn wasm_engine_new_with_config(config: Box<wasm_config_t>) -> Box<wasm_engine_t> {
+    Box::new(wasm_engine_t {
+        inner: Engine::new(&config.inner),
+    })
+}
+
+/// Deletes the [`wasm_engine_t`].
+#[no_mangle]
+pub extern "C" fn wasm_engine_delete(_engine: Box<wasm_engine_t>) {}
@@ -0,0 +1,69 @@
+use crate::{wasm_func_t, wasm_memory_t, StoreRef};
+use wasmi::Extern;
+
+/// The kind of a [`wasm_extern_t`].
+pub type wasm_externkind_t = u8;
+
+/// The [`wasm_externkind_t`] of functions.
+pub const WASM_EXTERN_FUNC: wasm_externkind_t = 0;
+/// The [`wasm_externkind_t`] of global variables.
+pub const WASM_EXTERN_GLOBAL: wasm_externkind_t = 1;
+/// The [`wasm_externkind_t`] of tables.
+pub const WASM_EXTERN_TABLE: wasm_externkind_t = 2;
+/// The [`wasm_externkind_t`] of linear memories.
+pub const WASM_EXTERN_MEMORY: wasm_externkind_t = 3;
+
+/// An external value of the C API that can be imported or exported by Wasm modules.
+///
+/// # Note
+///
+/// The [`wasm_func_t`] and [`wasm_memory_t`] share their representation with
+/// [`wasm_extern_t`] so that they can be converted into each other by pointer casts.
+#[derive(Debug, Clone)]
+pub struct wasm_extern_t {
+    pub(crate) store: StoreRef,
+    pub(crate) which: Extern,
+}
+
+/// Returns the [`wasm_externkind_t`] of the [`wasm_extern_t`].
+#[no_mangle]
+pub extern "C" fn wasm_extern_kind(e: &wasm_extern_t) -> wasm_externkind_t {
+    match e.which {
+        Extern::Func(_) => WASM_EXTERN_FUNC,
+        Extern::Global(_) => WASM_EXTERN_GLOBAL,
+        Extern::Table(_) => WASM_EXTERN_TABLE,
+        Extern::Memory(_) => WASM_EXTERN_MEMORY,
+    }
+}
+
+/// Returns the [`wasm_extern_t`] as [`wasm_func_t`] if it is a function.
+///
+/// The returned [`wasm_func_t`] is owned by `e`.
+#[no_mangle]
+pub extern "C" fn wasm_extern_as_func(e: &mut wasm_extern_t) -> Option<&mut wasm_func_t> {
+    wasm_func_t::try_from_mut(e)
+}
+
+/// Returns the [`wasm_extern_t`] as [`wasm_memory_t`] if it is a linear memory.
+///
+/// The returned [`wasm_memory_t`] is owned by `e`.
+#[no_mangle]
+pub extern "C" fn wasm_extern_as_memory(e: &mut wasm_extern_t) -> Option<&mut wasm_memory_t> {
+    wasm_memory_t::try_from_mut(e)
+}
+
+/// Returns a copy of the [`wasm_extern_t`].
+///
+/// The copy refers to the same external value.
+#[no_mangle]
+pub extern "C" fn wasm_extern_copy(e: &wasm_extern_t) -> Box<wasm_extern_t> {
+    Box::new(e.clone())
+}
+
+/// Deletes the [`wasm_extern_t`].
+///
+/// # Note
+///
+/// The referenced external value remains owned by its [`wasm_store_t`](crate::wasm_store_t).
+#[no_mangle]
+pub extern "C" fn wasm_extern_delete(_e: Box<wasm_extern_t>) {}
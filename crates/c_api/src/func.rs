@@ -0,0 +1,269 @@
+use crate::{
+    wasm_extern_t,
+    wasm_functype_t,
+    wasm_store_t,
+    wasm_trap_t,
+    wasm_val_t,
+    wasm_val_vec_t,
+    StoreRef,
+};
+use core::{ffi::c_void, panic::AssertUnwindSafe};
+use std::panic;
+use wasmi::{Error, Extern, Func, Value};
+
+/// A function of the C API.
+#[repr(transparent)]
+#[derive(Debug, Clone)]
+pub struct wasm_func_t {
+    inner: wasm_extern_t,
+}
+
+impl wasm_func_t {
+    /// Returns the [`wasm_extern_t`] as [`wasm_func_t`] if it is a function.
+    pub(crate) fn try_from_mut(e: &mut wasm_extern_t) -> Option<&mut Self> {
+        match &e.which {
+            // Safety: `wasm_func_t` is a transparent wrapper around `wasm_extern_t`.
+            Extern::Func(_) => Some(unsafe { &mut *(e as *mut wasm_extern_t as *mut Self) }),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying [`Func`].
+    fn func(&self) -> Func {
+        match self.inner.which {
+            Extern::Func(func) => func,
+            _ => unreachable!("encountered non-function external value"),
+        }
+    }
+}
+
+/// A host function callback of the C API.
+///
+/// Returns `NULL` on success or an owned [`wasm_trap_t`] that is raised by the call.
+pub type wasm_func_callback_t =
+    extern "C" fn(args: &wasm_val_vec_t, results: &mut wasm_val_vec_t) -> Option<Box<wasm_trap_t>>;
+
+/// A host function callback of the C API with an environment pointer.
+///
+/// Returns `NULL` on success or an owned [`wasm_trap_t`] that is raised by the call.
+pub type wasm_func_callback_with_env_t = extern "C" fn(
+    env: *mut c_void,
+    args: &wasm_val_vec_t,
+    results: &mut wasm_val_vec_t,
+) -> Option<Box<wasm_trap_t>>;
+
+/// The environment of a host function created via [`wasm_func_new_with_env`].
+///
+/// Runs its finalizer when dropped together with the [`wasm_store_t`] of the host function.
+struct HostEnv {
+    env: *mut c_void,
+    finalizer: Option<extern "C" fn(*mut c_void)>,
+}
+
+// Safety: the C API leaves synchronization of the environment to its users.
+unsafe impl Send for HostEnv {}
+// Safety: the C API leaves synchronization of the environment to its users.
+unsafe impl Sync for HostEnv {}
+
+impl HostEnv {
+    /// Returns the environment pointer.
+    fn get(&self) -> *mut c_void {
+        self.env
+    }
+}
+
+impl Drop for HostEnv {
+    fn drop(&mut self) {
+        if let Some(finalizer) = self.finalizer {
+            finalizer(self.env);
+        }
+    }
+}
+
+/// Calls the host function `callback` with `params` and writes its results into `results`.
+///
+/// # Errors
+///
+/// - If `callback` returns a [`wasm_trap_t`].
+/// - If `callback` writes results of the wrong kind.
+fn call_host(
+    params: &[Value],
+    results: &mut [Value],
+    callback: impl FnOnce(&wasm_val_vec_t, &mut wasm_val_vec_t) -> Option<Box<wasm_trap_t>>,
+) -> Result<(), Error> {
+    let args = params
+        .iter()
+        .map(wasm_val_t::from_value)
+        .collect::<Result<Vec<_>, _>>()?;
+    let args = wasm_val_vec_t::from(args);
+    let mut out = results
+        .iter()
+        .map(wasm_val_t::from_value)
+        .collect::<Result<Vec<_>, _>>()
+        .map(wasm_val_vec_t::from)?;
+    if let Some(trap) = callback(&args, &mut out) {
+        return Err(trap.error);
+    }
+    for (result, out) in results.iter_mut().zip(out.as_slice()) {
+        let value = out.to_value()?;
+        if value.ty() != result.ty() {
+            return Err(Error::new(format!(
+                "host function returned a result of type {:?} instead of {:?}",
+                value.ty(),
+                result.ty(),
+            )));
+        }
+        *result = value;
+    }
+    Ok(())
+}
+
+/// Creates a new host [`wasm_func_t`] of type `ty` calling `callback`.
+#[no_mangle]
+pub extern "C" fn wasm_func_new(
+    store: &mut wasm_store_t,
+    ty: &wasm_functype_t,
+    callback: wasm_func_callback_t,
+) -> Box<wasm_func_t> {
+    let store_ref = StoreRef::new(store);
+    let func = Func::new(
+        &mut store.inner,
+        ty.ty.clone(),
+        move |_, params, results| call_host(params, results, |args, out| callback(args, out)),
+    );
+    Box::new(wasm_func_t {
+        inner: wasm_extern_t {
+            store: store_ref,
+            which: Extern::Func(func),
+        },
+    })
+}
+
+/// Creates a new host [`wasm_func_t`] of type `ty` calling `callback` with `env`.
+///
+/// The optional `finalizer` is called with `env` when the `store` is deleted.
+#[no_mangle]
+pub extern "C" fn wasm_func_new_with_env(
+    store: &mut wasm_store_t,
+    ty: &wasm_functype_t,
+    callback: wasm_func_callback_with_env_t,
+    env: *mut c_void,
+    finalizer: Option<extern "C" fn(*mut c_void)>,
+) -> Box<wasm_func_t> {
+    let store_ref = StoreRef::new(store);
+    let env = HostEnv { env, finalizer };
+    let func = Func::new(
+        &mut store.inner,
+        ty.ty.clone(),
+        move |_, params, results| {
+            call_host(params, results, |args, out| callback(env.get(), args, out))
+        },
+    );
+    Box::new(wasm_func_t {
+        inner: wasm_extern_t {
+            store: store_ref,
+            which: Extern::Func(func),
+        },
+    })
+}
+
+/// Calls the [`wasm_func_t`] with `args` and writes its results into `results`.
+///
+/// Returns `NULL` on success or an owned [`wasm_trap_t`] if the call failed.
+///
+/// # Note
+///
+/// The `results` must be preallocated with the number of results of the [`wasm_func_t`].
+#[no_mangle]
+pub extern "C" fn wasm_func_call(
+    func: &wasm_func_t,
+    args: &wasm_val_vec_t,
+    results: &mut wasm_val_vec_t,
+) -> Option<Box<wasm_trap_t>> {
+    // Safety: the C API requires that the store of `func` is still alive.
+    let store = unsafe { func.inner.store.context_mut() };
+    let func = func.func();
+    let call = panic::catch_unwind(AssertUnwindSafe(|| -> Result<Vec<Value>, Error> {
+        let params = args
+            .as_slice()
+            .iter()
+            .map(|arg| arg.to_value())
+            .collect::<Result<Vec<_>, _>>()?;
+        let ty = func.ty(&*store);
+        let mut outputs = ty
+            .results()
+            .iter()
+            .copied()
+            .map(Value::default)
+            .collect::<Vec<_>>();
+        func.call(&mut *store, &params, &mut outputs)?;
+        Ok(outputs)
+    }));
+    let outputs = match call {
+        Ok(Ok(outputs)) => outputs,
+        Ok(Err(error)) => return Some(wasm_trap_t::new(error)),
+        Err(payload) => return Some(wasm_trap_t::from_panic(payload)),
+    };
+    if outputs.len() != results.size {
+        return Some(wasm_trap_t::new(Error::new(format!(
+            "expected {} results but found space for {}",
+            outputs.len(),
+            results.size,
+        ))));
+    }
+    for (result, output) in results.as_mut_slice().iter_mut().zip(&outputs) {
+        match wasm_val_t::from_value(output) {
+            Ok(value) => *result = value,
+            Err(error) => return Some(wasm_trap_t::new(error)),
+        }
+    }
+    None
+}
+
+/// Returns the type of the [`wasm_func_t`].
+#[no_mangle]
+pub extern "C" fn wasm_func_type(func: &wasm_func_t) -> Box<wasm_functype_t> {
+    // Safety: the C API requires that the store of `func` is still alive.
+    let store = unsafe { func.inner.store.context() };
+    Box::new(wasm_functype_t::new(func.func().ty(store)))
+}
+
+/// Returns the number of parameters of the [`wasm_func_t`].
+#[no_mangle]
+pub extern "C" fn wasm_func_param_arity(func: &wasm_func_t) -> usize {
+    // Safety: the C API requires that the store of `func` is still alive.
+    let store = unsafe { func.inner.store.context() };
+    func.func().ty(store).params().len()
+}
+
+/// Returns the number of results of the [`wasm_func_t`].
+#[no_mangle]
+pub extern "C" fn wasm_func_result_arity(func: &wasm_func_t) -> usize {
+    // Safety: the C API requires that the store of `func` is still alive.
+    let store = unsafe { func.inner.store.context() };
+    func.func().ty(store).results().len()
+}
+
+/// Returns the [`wasm_func_t`] as [`wasm_extern_t`].
+///
+/// The returned [`wasm_extern_t`] is owned by `func`.
+#[no_mangle]
+pub extern "C" fn wasm_func_as_extern(func: &mut wasm_func_t) -> &mut wasm_extern_t {
+    &mut func.inner
+}
+
+/// Returns a copy of the [`wasm_func_t`].
+///
+/// The copy refers to the same function.
+#[no_mangle]
+pub extern "C" fn wasm_func_copy(func: &wasm_func_t) -> Box<wasm_func_t> {
+    Box::new(func.clone())
+}
+
+/// Deletes the [`wasm_func_t`].
+///
+/// # Note
+///
+/// The referenced function remains owned by its [`wasm_store_t`].
+#[no_mangle]
+pub extern "C" fn wasm_func_delete(_func: Box<wasm_func_t>) {}
@@ -0,0 +1,89 @@
+use crate::{wasm_extern_t, wasm_extern_vec_t, wasm_module_t, wasm_store_t, wasm_trap_t, StoreRef};
+use core::panic::AssertUnwindSafe;
+use std::panic;
+use wasmi::{Extern, Instance};
+
+/// An instantiated Wasm module of the C API.
+#[derive(Debug, Clone)]
+pub struct wasm_instance_t {
+    store: StoreRef,
+    inner: Instance,
+}
+
+/// Instantiates the `module` with `imports` in the order of its imports.
+///
+/// Runs the `start` function of the `module` if any.
+///
+/// Returns `NULL` if the instantiation failed. In this case an owned [`wasm_trap_t`]
+/// describing the failure is written into `trap` unless it is `NULL`.
+///
+/// # Panics
+///
+/// If any of the elements of `imports` is `NULL`.
+#[no_mangle]
+pub extern "C" fn wasm_instance_new(
+    store: &mut wasm_store_t,
+    module: &wasm_module_t,
+    imports: &wasm_extern_vec_t,
+    trap: Option<&mut Option<Box<wasm_trap_t>>>,
+) -> Option<Box<wasm_instance_t>> {
+    let store_ref = StoreRef::new(store);
+    let imports = imports
+        .as_slice()
+        .iter()
+        .map(|import| import.as_ref().expect("encountered null import").which)
+        .collect::<Vec<Extern>>();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        Instance::new(&mut store.inner, &module.inner, &imports)
+    }));
+    let error = match result {
+        Ok(Ok(instance)) => {
+            return Some(Box::new(wasm_instance_t {
+                store: store_ref,
+                inner: instance,
+            }))
+        }
+        Ok(Err(error)) => wasm_trap_t::new(error),
+        Err(payload) => wasm_trap_t::from_panic(payload),
+    };
+    if let Some(trap) = trap {
+        *trap = Some(error);
+    }
+    None
+}
+
+/// Writes the exports of the [`wasm_instance_t`] into `out` in the order of their declaration.
+///
+/// The caller owns the exports and must delete them via `wasm_extern_vec_delete`.
+#[no_mangle]
+pub extern "C" fn wasm_instance_exports(instance: &wasm_instance_t, out: &mut wasm_extern_vec_t) {
+    // Safety: the C API requires that the store of `instance` is still alive.
+    let store = unsafe { instance.store.context() };
+    let exports = instance
+        .inner
+        .exports(store)
+        .map(|export| {
+            Some(Box::new(wasm_extern_t {
+                store: instance.store,
+                which: export.into_extern(),
+            }))
+        })
+        .collect();
+    out.set_buffer(exports);
+}
+
+/// Returns a copy of the [`wasm_instance_t`].
+///
+/// The copy refers to the same instance.
+#[no_mangle]
+pub extern "C" fn wasm_instance_copy(instance: &wasm_instance_t) -> Box<wasm_instance_t> {
+    Box::new(instance.clone())
+}
+
+/// Deletes the [`wasm_instance_t`].
+///
+/// # Note
+///
+/// The referenced instance remains owned by its [`wasm_store_t`].
+#[no_mangle]
+pub extern "C" fn wasm_instance_delete(_instance: Box<wasm_instance_t>) {}
@@ -0,0 +1,51 @@
+//! C bindings for the Wasmi interpreter following the standard [wasm-c-api].
+//!
+//! The declarations of the C API are found in the `include` directory of this crate:
+//!
+//! - `wasm.h`: the subset of the standard `wasm.h` that is supported by Wasmi.
+//! - `wasmi.h`: Wasmi specific extensions such as the inspection of trap codes.
+//!
+//! # Ownership
+//!
+//! All functions follow the ownership conventions of the [wasm-c-api]:
+//! objects returned as `own` must be destroyed via their `wasm_*_delete` function
+//! and `own` parameters are taken over by the callee.
+//!
+//! Objects that belong to a `wasm_store_t` such as `wasm_func_t`, `wasm_memory_t`,
+//! `wasm_instance_t` and `wasm_extern_t` must not be used after their store has been deleted.
+//!
+//! # Errors
+//!
+//! Errors and traps never unwind across the C API. Fallible functions either return
+//! `NULL` or an owned `wasm_trap_t` that can be inspected by the caller.
+//! Misuse of the C API such as passing `NULL` where an object is expected aborts the process.
+//!
+//! [wasm-c-api]: https://github.com/WebAssembly/wasm-c-api
+
+#![allow(non_camel_case_types)]
+
+mod engine;
+mod r#extern;
+mod func;
+mod instance;
+mod memory;
+mod module;
+mod store;
+mod trap;
+mod types;
+mod val;
+mod vec;
+
+pub use self::{
+    engine::*,
+    func::*,
+    instance::*,
+    memory::*,
+    module::*,
+    r#extern::*,
+    store::*,
+    trap::*,
+    types::*,
+    val::*,
+    vec::*,
+};
@@ -0,0 +1,96 @@
+use crate::wasm_extern_t;
+use wasmi::{core::Pages, Extern, Memory};
+
+/// The number of 64 KiB pages of a linear memory.
+pub type wasm_memory_pages_t = u32;
+
+/// A linear memory of the C API.
+#[repr(transparent)]
+#[derive(Debug, Clone)]
+pub struct wasm_memory_t {
+    inner: wasm_extern_t,
+}
+
+impl wasm_memory_t {
+    /// Returns the [`wasm_extern_t`] as [`wasm_memory_t`] if it is a linear memory.
+    pub(crate) fn try_from_mut(e: &mut wasm_extern_t) -> Option<&mut Self> {
+        match &e.which {
+            // Safety: `wasm_memory_t` is a transparent wrapper around `wasm_extern_t`.
+            Extern::Memory(_) => Some(unsafe { &mut *(e as *mut wasm_extern_t as *mut Self) }),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying [`Memory`].
+    fn memory(&self) -> Memory {
+        match self.inner.which {
+            Extern::Memory(memory) => memory,
+            _ => unreachable!("encountered non-memory external value"),
+        }
+    }
+}
+
+/// Returns the [`wasm_memory_t`] as [`wasm_extern_t`].
+///
+/// The returned [`wasm_extern_t`] is owned by `memory`.
+#[no_mangle]
+pub extern "C" fn wasm_memory_as_extern(memory: &mut wasm_memory_t) -> &mut wasm_extern_t {
+    &mut memory.inner
+}
+
+/// Returns a pointer to the first byte of the [`wasm_memory_t`].
+///
+/// # Note
+///
+/// The pointer is invalidated by growing the [`wasm_memory_t`] and by Wasm executions.
+#[no_mangle]
+pub extern "C" fn wasm_memory_data(memory: &mut wasm_memory_t) -> *mut u8 {
+    // Safety: the C API requires that the store of `memory` is still alive.
+    let store = unsafe { memory.inner.store.context_mut() };
+    memory.memory().data_mut(store).as_mut_ptr()
+}
+
+/// Returns the number of bytes of the [`wasm_memory_t`].
+#[no_mangle]
+pub extern "C" fn wasm_memory_data_size(memory: &wasm_memory_t) -> usize {
+    // Safety: the C API requires that the store of `memory` is still alive.
+    let store = unsafe { memory.inner.store.context() };
+    memory.memory().data(store).len()
+}
+
+/// Returns the number of pages of the [`wasm_memory_t`].
+#[no_mangle]
+pub extern "C" fn wasm_memory_size(memory: &wasm_memory_t) -> wasm_memory_pages_t {
+    // Safety: the C API requires that the store of `memory` is still alive.
+    let store = unsafe { memory.inner.store.context() };
+    u32::from(memory.memory().current_pages(store))
+}
+
+/// Grows the [`wasm_memory_t`] by `delta` pages.
+///
+/// Returns `false` and leaves the [`wasm_memory_t`] untouched if growing fails.
+#[no_mangle]
+pub extern "C" fn wasm_memory_grow(memory: &mut wasm_memory_t, delta: wasm_memory_pages_t) -> bool {
+    // Safety: the C API requires that the store of `memory` is still alive.
+    let store = unsafe { memory.inner.store.context_mut() };
+    let Some(delta) = Pages::new(delta) else {
+        return false;
+    };
+    memory.memory().grow(store, delta).is_ok()
+}
+
+/// Returns a copy of the [`wasm_memory_t`].
+///
+/// The copy refers to the same linear memory.
+#[no_mangle]
+pub extern "C" fn wasm_memory_copy(memory: &wasm_memory_t) -> Box<wasm_memory_t> {
+    Box::new(memory.clone())
+}
+
+/// Deletes the [`wasm_memory_t`].
+///
+/// # Note
+///
+/// The referenced linear memory remains owned by its [`wasm_store_t`](crate::wasm_store_t).
+#[no_mangle]
+pub extern "C" fn wasm_memory_delete(_memory: Box<wasm_memory_t>) {}
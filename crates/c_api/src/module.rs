@@ -0,0 +1,50 @@
+use crate::{wasm_byte_vec_t, wasm_store_t};
+use core::panic::AssertUnwindSafe;
+use std::panic;
+use wasmi::Module;
+
+/// A compiled Wasm module of the C API.
+///
+/// Wraps a [`Module`].
+#[derive(Debug, Clone)]
+pub struct wasm_module_t {
+    pub(crate) inner: Module,
+}
+
+/// Creates a new [`wasm_module_t`] from the Wasm `binary` for the engine of `store`.
+///
+/// Returns `NULL` if the `binary` is invalid or cannot be compiled.
+#[no_mangle]
+pub extern "C" fn wasm_module_new(
+    store: &mut wasm_store_t,
+    binary: &wasm_byte_vec_t,
+) -> Option<Box<wasm_module_t>> {
+    let engine = store.inner.engine();
+    let module = panic::catch_unwind(AssertUnwindSafe(|| Module::new(engine, binary.as_slice())));
+    match module {
+        Ok(Ok(module)) => Some(Box::new(wasm_module_t { inner: module })),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the Wasm `binary` is valid for the engine of `store`.
+#[no_mangle]
+pub extern "C" fn wasm_module_validate(store: &mut wasm_store_t, binary: &wasm_byte_vec_t) -> bool {
+    let engine = store.inner.engine();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        Module::validate(engine, binary.as_slice())
+    }));
+    matches!(result, Ok(Ok(())))
+}
+
+/// Returns a copy of the [`wasm_module_t`].
+///
+/// The copy shares the compiled code of the [`wasm_module_t`].
+#[no_mangle]
+pub extern "C" fn wasm_module_copy(module: &wasm_module_t) -> Box<wasm_module_t> {
+    Box::new(module.clone())
+}
+
+/// Deletes the [`wasm_module_t`].
+#[no_mangle]
+pub extern "C" fn wasm_module_delete(_module: Box<wasm_module_t>) {}
@@ -0,0 +1,63 @@
+use crate::wasm_engine_t;
+use core::ptr::NonNull;
+use wasmi::Store;
+
+/// The store of the C API that owns all Wasm objects such as functions and memories.
+///
+/// Wraps a [`Store`] without host state.
+#[derive(Debug)]
+pub struct wasm_store_t {
+    pub(crate) inner: Store<()>,
+}
+
+/// Creates a new [`wasm_store_t`] for the `engine`.
+#[no_mangle]
+pub extern "C" fn wasm_store_new(engine: &wasm_engine_t) -> Box<wasm_store_t> {
+    Box::new(wasm_store_t {
+        inner: Store::new(&engine.inner, ()),
+    })
+}
+
+/// Deletes the [`wasm_store_t`] and all Wasm objects owned by it.
+///
+/// # Note
+///
+/// This also runs the finalizers of all host functions created for the [`wasm_store_t`].
+#[no_mangle]
+pub extern "C" fn wasm_store_delete(_store: Box<wasm_store_t>) {}
+
+/// A reference to the [`Store`] of a [`wasm_store_t`] held by the objects owned by it.
+///
+/// # Note
+///
+/// The C API requires that objects are not used after their [`wasm_store_t`] has been
+/// deleted which is why a [`StoreRef`] may access its [`Store`] at any time. Host functions
+/// may call back into their [`wasm_store_t`] as long as they do not retain any references.
+#[derive(Debug, Copy, Clone)]
+pub struct StoreRef(NonNull<Store<()>>);
+
+impl StoreRef {
+    /// Creates a new [`StoreRef`] to the [`Store`] of `store`.
+    pub(crate) fn new(store: &mut wasm_store_t) -> Self {
+        Self(NonNull::from(&mut store.inner))
+    }
+
+    /// Returns a shared reference to the referenced [`Store`].
+    ///
+    /// # Safety
+    ///
+    /// The referenced [`wasm_store_t`] must not have been deleted.
+    pub(crate) unsafe fn context(&self) -> &Store<()> {
+        self.0.as_ref()
+    }
+
+    /// Returns an exclusive reference to the referenced [`Store`].
+    ///
+    /// # Safety
+    ///
+    /// The referenced [`wasm_store_t`] must not have been deleted.
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn context_mut(&self) -> &mut Store<()> {
+        &mut *self.0.as_ptr()
+    }
+}
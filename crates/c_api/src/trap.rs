@@ -0,0 +1,120 @@
+use crate::{wasm_message_t, wasm_store_t};
+use core::any::Any;
+use wasmi::{core::TrapCode, Error};
+
+/// A trap or error of the C API.
+///
+/// Wraps an [`Error`].
+#[derive(Debug)]
+pub struct wasm_trap_t {
+    pub(crate) error: Error,
+}
+
+impl wasm_trap_t {
+    /// Creates a new owned [`wasm_trap_t`] from the [`Error`].
+    pub(crate) fn new(error: Error) -> Box<Self> {
+        Box::new(Self { error })
+    }
+
+    /// Creates a new owned [`wasm_trap_t`] from the payload of a caught panic.
+    ///
+    /// # Note
+    ///
+    /// Panics must not unwind across the C API and are reported as traps instead.
+    pub(crate) fn from_panic(payload: Box<dyn Any + Send>) -> Box<Self> {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown panic");
+        Self::new(Error::new(format!("panicked: {message}")))
+    }
+}
+
+/// Creates a new [`wasm_trap_t`] with the null terminated `message`.
+///
+/// The `store` parameter exists for conformance with the C API and is unused by Wasmi.
+#[no_mangle]
+pub extern "C" fn wasm_trap_new(
+    _store: &wasm_store_t,
+    message: &wasm_message_t,
+) -> Box<wasm_trap_t> {
+    wasm_trap_t::new(Error::new(message.as_str_lossy()))
+}
+
+/// Writes the null terminated message of the [`wasm_trap_t`] into `out`.
+///
+/// The caller owns the message and must delete it via `wasm_byte_vec_delete`.
+#[no_mangle]
+pub extern "C" fn wasm_trap_message(trap: &wasm_trap_t, out: &mut wasm_message_t) {
+    let mut message = trap.error.to_string().into_bytes();
+    message.push(0);
+    out.set_buffer(message);
+}
+
+/// Deletes the [`wasm_trap_t`].
+#[no_mangle]
+pub extern "C" fn wasm_trap_delete(_trap: Box<wasm_trap_t>) {}
+
+/// The code of a trap raised by a Wasm execution.
+///
+/// Wasmi specific extension of the C API.
+pub type wasmi_trap_code_t = u8;
+
+/// Wasm code executed an `unreachable` instruction.
+pub const WASMI_TRAP_CODE_UNREACHABLE_CODE_REACHED: wasmi_trap_code_t = 0;
+/// Wasm code accessed a linear memory out of bounds.
+pub const WASMI_TRAP_CODE_MEMORY_OUT_OF_BOUNDS: wasmi_trap_code_t = 1;
+/// Wasm code accessed a table out of bounds.
+pub const WASMI_TRAP_CODE_TABLE_OUT_OF_BOUNDS: wasmi_trap_code_t = 2;
+/// Wasm code indirectly called a `null` function reference.
+pub const WASMI_TRAP_CODE_INDIRECT_CALL_TO_NULL: wasmi_trap_code_t = 3;
+/// Wasm code divided an integer by zero.
+pub const WASMI_TRAP_CODE_INTEGER_DIVISION_BY_ZERO: wasmi_trap_code_t = 4;
+/// Wasm code caused an integer overflow.
+pub const WASMI_TRAP_CODE_INTEGER_OVERFLOW: wasmi_trap_code_t = 5;
+/// Wasm code converted a float to an unrepresentable integer.
+pub const WASMI_TRAP_CODE_BAD_CONVERSION_TO_INTEGER: wasmi_trap_code_t = 6;
+/// Wasm code exhausted the call stack.
+pub const WASMI_TRAP_CODE_STACK_OVERFLOW: wasmi_trap_code_t = 7;
+/// Wasm code indirectly called a function with a mismatching signature.
+pub const WASMI_TRAP_CODE_BAD_SIGNATURE: wasmi_trap_code_t = 8;
+/// Wasm code ran out of fuel.
+pub const WASMI_TRAP_CODE_OUT_OF_FUEL: wasmi_trap_code_t = 9;
+/// Wasm code grew a table or linear memory beyond the limits of a resource limiter.
+pub const WASMI_TRAP_CODE_GROWTH_OPERATION_LIMITED: wasmi_trap_code_t = 10;
+/// Wasm code wrote to a range of a linear memory guarded by a write watchpoint.
+pub const WASMI_TRAP_CODE_WATCHPOINT: wasmi_trap_code_t = 11;
+
+/// Converts the [`TrapCode`] into its [`wasmi_trap_code_t`].
+fn into_trap_code(code: TrapCode) -> wasmi_trap_code_t {
+    match code {
+        TrapCode::UnreachableCodeReached => WASMI_TRAP_CODE_UNREACHABLE_CODE_REACHED,
+        TrapCode::MemoryOutOfBounds => WASMI_TRAP_CODE_MEMORY_OUT_OF_BOUNDS,
+        TrapCode::TableOutOfBounds => WASMI_TRAP_CODE_TABLE_OUT_OF_BOUNDS,
+        TrapCode::IndirectCallToNull => WASMI_TRAP_CODE_INDIRECT_CALL_TO_NULL,
+        TrapCode::IntegerDivisionByZero => WASMI_TRAP_CODE_INTEGER_DIVISION_BY_ZERO,
+        TrapCode::IntegerOverflow => WASMI_TRAP_CODE_INTEGER_OVERFLOW,
+        TrapCode::BadConversionToInteger => WASMI_TRAP_CODE_BAD_CONVERSION_TO_INTEGER,
+        TrapCode::StackOverflow => WASMI_TRAP_CODE_STACK_OVERFLOW,
+        TrapCode::BadSignature => WASMI_TRAP_CODE_BAD_SIGNATURE,
+        TrapCode::OutOfFuel => WASMI_TRAP_CODE_OUT_OF_FUEL,
+        TrapCode::GrowthOperationLimited => WASMI_TRAP_CODE_GROWTH_OPERATION_LIMITED,
+        TrapCode::Watchpoint => WASMI_TRAP_CODE_WATCHPOINT,
+    }
+}
+
+/// Writes the [`wasmi_trap_code_t`] of the [`wasm_trap_t`] into `out`.
+///
+/// Returns `false` and leaves `out` untouched if the [`wasm_trap_t`] has no trap code,
+/// e.g. if it was created by a host function or represents an instantiation error.
+///
+/// Wasmi specific extension of the C API.
+#[no_mangle]
+pub extern "C" fn wasmi_trap_code(trap: &wasm_trap_t, out: &mut wasmi_trap_code_t) -> bool {
+    let Some(code) = trap.error.as_trap_code() else {
+        return false;
+    };
+    *out = into_trap_code(code);
+    true
+}
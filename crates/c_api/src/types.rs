@@ -0,0 +1,152 @@
+use crate::wasm_valtype_vec_t;
+use wasmi::{core::ValueType, FuncType};
+
+/// The kind of a [`wasm_valtype_t`].
+pub type wasm_valkind_t = u8;
+
+/// The [`wasm_valkind_t`] of 32-bit integers.
+pub const WASM_I32: wasm_valkind_t = 0;
+/// The [`wasm_valkind_t`] of 64-bit integers.
+pub const WASM_I64: wasm_valkind_t = 1;
+/// The [`wasm_valkind_t`] of 32-bit floats.
+pub const WASM_F32: wasm_valkind_t = 2;
+/// The [`wasm_valkind_t`] of 64-bit floats.
+pub const WASM_F64: wasm_valkind_t = 3;
+/// The [`wasm_valkind_t`] of nullable external references.
+pub const WASM_ANYREF: wasm_valkind_t = 128;
+/// The [`wasm_valkind_t`] of nullable function references.
+pub const WASM_FUNCREF: wasm_valkind_t = 129;
+
+/// Converts the [`ValueType`] into its [`wasm_valkind_t`].
+pub(crate) fn into_valkind(ty: ValueType) -> wasm_valkind_t {
+    match ty {
+        ValueType::I32 => WASM_I32,
+        ValueType::I64 => WASM_I64,
+        ValueType::F32 => WASM_F32,
+        ValueType::F64 => WASM_F64,
+        ValueType::ExternRef => WASM_ANYREF,
+        ValueType::FuncRef => WASM_FUNCREF,
+    }
+}
+
+/// Converts the [`wasm_valkind_t`] into its [`ValueType`] if valid.
+pub(crate) fn from_valkind(kind: wasm_valkind_t) -> Option<ValueType> {
+    let ty = match kind {
+        WASM_I32 => ValueType::I32,
+        WASM_I64 => ValueType::I64,
+        WASM_F32 => ValueType::F32,
+        WASM_F64 => ValueType::F64,
+        WASM_ANYREF => ValueType::ExternRef,
+        WASM_FUNCREF => ValueType::FuncRef,
+        _ => return None,
+    };
+    Some(ty)
+}
+
+/// The type of a Wasm value.
+#[derive(Debug, Clone)]
+pub struct wasm_valtype_t {
+    pub(crate) ty: ValueType,
+}
+
+/// Creates a new [`wasm_valtype_t`] of `kind`.
+///
+/// Returns `NULL` if `kind` is invalid.
+#[no_mangle]
+pub extern "C" fn wasm_valtype_new(kind: wasm_valkind_t) -> Option<Box<wasm_valtype_t>> {
+    let ty = from_valkind(kind)?;
+    Some(Box::new(wasm_valtype_t { ty }))
+}
+
+/// Returns the [`wasm_valkind_t`] of the [`wasm_valtype_t`].
+#[no_mangle]
+pub extern "C" fn wasm_valtype_kind(valtype: &wasm_valtype_t) -> wasm_valkind_t {
+    into_valkind(valtype.ty)
+}
+
+/// Returns a copy of the [`wasm_valtype_t`].
+#[no_mangle]
+pub extern "C" fn wasm_valtype_copy(valtype: &wasm_valtype_t) -> Box<wasm_valtype_t> {
+    Box::new(valtype.clone())
+}
+
+/// Deletes the [`wasm_valtype_t`].
+#[no_mangle]
+pub extern "C" fn wasm_valtype_delete(_valtype: Box<wasm_valtype_t>) {}
+
+/// The type of a Wasm function.
+///
+/// Wraps a [`FuncType`] and caches its parameter and result types for the C API.
+#[derive(Debug, Clone)]
+pub struct wasm_functype_t {
+    pub(crate) ty: FuncType,
+    params: wasm_valtype_vec_t,
+    results: wasm_valtype_vec_t,
+}
+
+impl wasm_functype_t {
+    /// Creates a new [`wasm_functype_t`] from the [`FuncType`].
+    pub(crate) fn new(ty: FuncType) -> Self {
+        let valtypes = |types: &[ValueType]| {
+            wasm_valtype_vec_t::from(
+                types
+                    .iter()
+                    .map(|&ty| Some(Box::new(wasm_valtype_t { ty })))
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let params = valtypes(ty.params());
+        let results = valtypes(ty.results());
+        Self {
+            ty,
+            params,
+            results,
+        }
+    }
+}
+
+/// Creates a new [`wasm_functype_t`] from `params` and `results`.
+///
+/// Takes ownership of the elements of `params` and `results`.
+///
+/// # Panics
+///
+/// If any of the elements of `params` or `results` is `NULL`.
+#[no_mangle]
+pub extern "C" fn wasm_functype_new(
+    params: &mut wasm_valtype_vec_t,
+    results: &mut wasm_valtype_vec_t,
+) -> Box<wasm_functype_t> {
+    let types = |types: &mut wasm_valtype_vec_t| {
+        types
+            .take()
+            .into_iter()
+            .map(|ty| ty.expect("encountered null value type").ty)
+            .collect::<Vec<_>>()
+    };
+    let params = types(params);
+    let results = types(results);
+    Box::new(wasm_functype_t::new(FuncType::new(params, results)))
+}
+
+/// Returns the parameter types of the [`wasm_functype_t`].
+#[no_mangle]
+pub extern "C" fn wasm_functype_params(functype: &wasm_functype_t) -> &wasm_valtype_vec_t {
+    &functype.params
+}
+
+/// Returns the result types of the [`wasm_functype_t`].
+#[no_mangle]
+pub extern "C" fn wasm_functype_results(functype: &wasm_functype_t) -> &wasm_valtype_vec_t {
+    &functype.results
+}
+
+/// Returns a copy of the [`wasm_functype_t`].
+#[no_mangle]
+pub extern "C" fn wasm_functype_copy(functype: &wasm_functype_t) -> Box<wasm_functype_t> {
+    Box::new(functype.clone())
+}
+
+/// Deletes the [`wasm_functype_t`].
+#[no_mangle]
+pub extern "C" fn wasm_functype_delete(_functype: Box<wasm_functype_t>) {}
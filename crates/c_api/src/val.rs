@@ -0,0 +1,137 @@
+use crate::{from_valkind, into_valkind, wasm_valkind_t, WASM_I32};
+use core::{fmt, ptr};
+use wasmi::{
+    core::{ValueType, F32, F64},
+    Error,
+    ExternRef,
+    FuncRef,
+    Value,
+};
+
+/// A reference value of the C API.
+///
+/// # Note
+///
+/// Wasmi only supports `NULL` reference values via the C API.
+#[derive(Debug)]
+pub enum wasm_ref_t {}
+
+/// The payload of a [`wasm_val_t`] as determined by its [`wasm_valkind_t`].
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union wasm_val_union {
+    pub i32: i32,
+    pub i64: i64,
+    pub f32: f32,
+    pub f64: f64,
+    pub r#ref: *mut wasm_ref_t,
+}
+
+/// A Wasm value of the C API.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct wasm_val_t {
+    /// The kind of the value.
+    pub kind: wasm_valkind_t,
+    /// The payload of the value.
+    pub of: wasm_val_union,
+}
+
+impl fmt::Debug for wasm_val_t {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("wasm_val_t");
+        s.field("kind", &self.kind);
+        match self.to_value() {
+            Ok(value) => s.field("of", &value),
+            Err(_) => s.field("of", &"<invalid>"),
+        };
+        s.finish()
+    }
+}
+
+impl Default for wasm_val_t {
+    fn default() -> Self {
+        Self {
+            kind: WASM_I32,
+            of: wasm_val_union { i32: 0 },
+        }
+    }
+}
+
+impl wasm_val_t {
+    /// Creates a new [`wasm_val_t`] from the [`Value`].
+    ///
+    /// # Errors
+    ///
+    /// If `value` is a non-null reference value.
+    pub(crate) fn from_value(value: &Value) -> Result<Self, Error> {
+        let of = match value {
+            Value::I32(value) => wasm_val_union { i32: *value },
+            Value::I64(value) => wasm_val_union { i64: *value },
+            Value::F32(value) => wasm_val_union {
+                f32: f32::from_bits(value.to_bits()),
+            },
+            Value::F64(value) => wasm_val_union {
+                f64: f64::from_bits(value.to_bits()),
+            },
+            Value::FuncRef(value) if value.is_null() => wasm_val_union {
+                r#ref: ptr::null_mut(),
+            },
+            Value::ExternRef(value) if value.is_null() => wasm_val_union {
+                r#ref: ptr::null_mut(),
+            },
+            _ => {
+                return Err(Error::new(
+                    "non-null references are unsupported by the C API",
+                ))
+            }
+        };
+        Ok(Self {
+            kind: into_valkind(value.ty()),
+            of,
+        })
+    }
+
+    /// Converts the [`wasm_val_t`] into a [`Value`].
+    ///
+    /// # Errors
+    ///
+    /// - If the kind of the [`wasm_val_t`] is invalid.
+    /// - If the [`wasm_val_t`] is a non-null reference value.
+    pub(crate) fn to_value(self) -> Result<Value, Error> {
+        let Some(ty) = from_valkind(self.kind) else {
+            return Err(Error::new(format!("invalid value kind: {}", self.kind)));
+        };
+        // Safety: the active field of the union is determined by its kind.
+        let value = unsafe {
+            match ty {
+                ValueType::I32 => Value::I32(self.of.i32),
+                ValueType::I64 => Value::I64(self.of.i64),
+                ValueType::F32 => Value::F32(F32::from_bits(self.of.f32.to_bits())),
+                ValueType::F64 => Value::F64(F64::from_bits(self.of.f64.to_bits())),
+                ValueType::FuncRef | ValueType::ExternRef if !self.of.r#ref.is_null() => {
+                    return Err(Error::new(
+                        "non-null references are unsupported by the C API",
+                    ))
+                }
+                ValueType::FuncRef => Value::FuncRef(FuncRef::null()),
+                ValueType::ExternRef => Value::ExternRef(ExternRef::null()),
+            }
+        };
+        Ok(value)
+    }
+}
+
+/// Copies the [`wasm_val_t`] `source` into `out`.
+#[no_mangle]
+pub extern "C" fn wasm_val_copy(out: &mut wasm_val_t, source: &wasm_val_t) {
+    *out = *source;
+}
+
+/// Deletes the [`wasm_val_t`].
+///
+/// # Note
+///
+/// This is a no-op since Wasmi does not support non-null reference values via the C API.
+#[no_mangle]
+pub extern "C" fn wasm_val_delete(_val: &mut wasm_val_t) {}
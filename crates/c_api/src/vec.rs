@@ -0,0 +1,200 @@
+use crate::{wasm_extern_t, wasm_val_t, wasm_valtype_t};
+use core::{mem, ptr, slice};
+
+/// Declares the `wasm_*_vec_t` types of the C API together with their functions.
+///
+/// # Note
+///
+/// The declared types own their elements and release them on `Drop`.
+/// Out-parameters are overwritten without dropping their previous contents
+/// since they usually point to uninitialized memory of the caller.
+macro_rules! declare_vecs {
+    (
+        $(
+            $( #[$attr:meta] )*
+            struct $name:ident {
+                type element = $elem:ty;
+                fn new = $new:ident;
+                fn new_empty = $new_empty:ident;
+                fn new_uninitialized = $new_uninitialized:ident;
+                fn copy = $copy:ident;
+                fn delete = $delete:ident;
+            }
+        )*
+    ) => {
+        $(
+            $( #[$attr] )*
+            #[repr(C)]
+            #[derive(Debug)]
+            pub struct $name {
+                /// The number of elements.
+                pub size: usize,
+                /// The pointer to the first element or null if `size` is zero.
+                pub data: *mut $elem,
+            }
+
+            impl $name {
+                /// Overwrites `self` with the elements of `buffer` without dropping its contents.
+                pub fn set_buffer(&mut self, buffer: Vec<$elem>) {
+                    let mut buffer = buffer.into_boxed_slice();
+                    self.size = buffer.len();
+                    self.data = buffer.as_mut_ptr();
+                    mem::forget(buffer);
+                }
+
+                /// Returns the elements as shared slice.
+                pub fn as_slice(&self) -> &[$elem] {
+                    if self.size == 0 {
+                        return &[];
+                    }
+                    assert!(!self.data.is_null());
+                    // Safety: `data` points to `size` initialized elements.
+                    unsafe { slice::from_raw_parts(self.data, self.size) }
+                }
+
+                /// Returns the elements as exclusive slice.
+                pub fn as_mut_slice(&mut self) -> &mut [$elem] {
+                    if self.size == 0 {
+                        return &mut [];
+                    }
+                    assert!(!self.data.is_null());
+                    // Safety: `data` points to `size` initialized elements.
+                    unsafe { slice::from_raw_parts_mut(self.data, self.size) }
+                }
+
+                /// Takes the elements out of `self` leaving it empty.
+                pub fn take(&mut self) -> Vec<$elem> {
+                    if self.data.is_null() {
+                        return Vec::new();
+                    }
+                    // Safety: `data` has been allocated as boxed slice of `size` elements.
+                    let buffer = unsafe { Vec::from_raw_parts(self.data, self.size, self.size) };
+                    self.size = 0;
+                    self.data = ptr::null_mut();
+                    buffer
+                }
+            }
+
+            impl Clone for $name {
+                fn clone(&self) -> Self {
+                    Self::from(self.as_slice().to_vec())
+                }
+            }
+
+            impl From<Vec<$elem>> for $name {
+                fn from(buffer: Vec<$elem>) -> Self {
+                    let mut vec = Self {
+                        size: 0,
+                        data: ptr::null_mut(),
+                    };
+                    vec.set_buffer(buffer);
+                    vec
+                }
+            }
+
+            impl Drop for $name {
+                fn drop(&mut self) {
+                    drop(self.take());
+                }
+            }
+
+            #[doc = concat!("Initializes `out` as empty `", stringify!($name), "`.")]
+            #[no_mangle]
+            pub extern "C" fn $new_empty(out: &mut $name) {
+                out.set_buffer(Vec::new());
+            }
+
+            #[doc = concat!("Initializes `out` as `", stringify!($name), "` of `size` default elements.")]
+            #[no_mangle]
+            pub extern "C" fn $new_uninitialized(out: &mut $name, size: usize) {
+                out.set_buffer((0..size).map(|_| <$elem>::default()).collect());
+            }
+
+            #[doc = concat!("Initializes `out` as `", stringify!($name), "` taking over the `size` elements at `data`.")]
+            ///
+            /// # Safety
+            ///
+            /// `data` must point to `size` initialized elements unless `size` is zero.
+            #[no_mangle]
+            pub unsafe extern "C" fn $new(out: &mut $name, size: usize, data: *const $elem) {
+                let buffer = match size {
+                    0 => Vec::new(),
+                    _ => slice::from_raw_parts(data, size)
+                        .iter()
+                        .map(|element| ptr::read(element))
+                        .collect(),
+                };
+                out.set_buffer(buffer);
+            }
+
+            #[doc = concat!("Initializes `out` as deep copy of the `", stringify!($name), "` `source`.")]
+            #[no_mangle]
+            pub extern "C" fn $copy(out: &mut $name, source: &$name) {
+                out.set_buffer(source.as_slice().to_vec());
+            }
+
+            #[doc = concat!("Deletes the elements of the `", stringify!($name), "` leaving it empty.")]
+            #[no_mangle]
+            pub extern "C" fn $delete(vec: &mut $name) {
+                drop(vec.take());
+            }
+        )*
+    };
+}
+
+declare_vecs! {
+    /// A vector of bytes, also used for names and messages.
+    struct wasm_byte_vec_t {
+        type element = u8;
+        fn new = wasm_byte_vec_new;
+        fn new_empty = wasm_byte_vec_new_empty;
+        fn new_uninitialized = wasm_byte_vec_new_uninitialized;
+        fn copy = wasm_byte_vec_copy;
+        fn delete = wasm_byte_vec_delete;
+    }
+
+    /// A vector of owned value types.
+    struct wasm_valtype_vec_t {
+        type element = Option<Box<wasm_valtype_t>>;
+        fn new = wasm_valtype_vec_new;
+        fn new_empty = wasm_valtype_vec_new_empty;
+        fn new_uninitialized = wasm_valtype_vec_new_uninitialized;
+        fn copy = wasm_valtype_vec_copy;
+        fn delete = wasm_valtype_vec_delete;
+    }
+
+    /// A vector of values.
+    struct wasm_val_vec_t {
+        type element = wasm_val_t;
+        fn new = wasm_val_vec_new;
+        fn new_empty = wasm_val_vec_new_empty;
+        fn new_uninitialized = wasm_val_vec_new_uninitialized;
+        fn copy = wasm_val_vec_copy;
+        fn delete = wasm_val_vec_delete;
+    }
+
+    /// A vector of owned external values.
+    struct wasm_extern_vec_t {
+        type element = Option<Box<wasm_extern_t>>;
+        fn new = wasm_extern_vec_new;
+        fn new_empty = wasm_extern_vec_new_empty;
+        fn new_uninitialized = wasm_extern_vec_new_uninitialized;
+        fn copy = wasm_extern_vec_copy;
+        fn delete = wasm_extern_vec_delete;
+    }
+}
+
+/// A name of the C API such as the name of an import or export.
+pub type wasm_name_t = wasm_byte_vec_t;
+
+/// A message of the C API such as the message of a trap.
+pub type wasm_message_t = wasm_name_t;
+
+impl wasm_byte_vec_t {
+    /// Returns the bytes of the [`wasm_byte_vec_t`] without a trailing null terminator.
+    pub(crate) fn as_str_lossy(&self) -> String {
+        let bytes = self.as_slice();
+        let bytes = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
@@ -0,0 +1,147 @@
+//! Tests for the C API called from Rust.
+
+use wasmi_c_api::*;
+
+/// Returns the `message` of the [`wasm_trap_t`] without its null terminator.
+fn trap_message(trap: &wasm_trap_t) -> String {
+    let mut message = wasm_byte_vec_t::from(Vec::new());
+    wasm_trap_message(trap, &mut message);
+    let bytes = message.as_slice();
+    assert_eq!(bytes.last(), Some(&0));
+    String::from_utf8(bytes[..bytes.len() - 1].to_vec()).unwrap()
+}
+
+/// Returns the [`wasm_byte_vec_t`] of the Wasm binary of `wat`.
+fn wasm(wat: &str) -> wasm_byte_vec_t {
+    wasm_byte_vec_t::from(wat::parse_str(wat).unwrap())
+}
+
+/// Returns an `i32` [`wasm_val_t`] with `value`.
+fn val_i32(value: i32) -> wasm_val_t {
+    wasm_val_t {
+        kind: WASM_I32,
+        of: wasm_val_union { i32: value },
+    }
+}
+
+/// Returns the [`wasm_functype_t`] of `(param i32) (result i32)`.
+fn functype_i32_i32() -> Box<wasm_functype_t> {
+    let mut params = wasm_valtype_vec_t::from(vec![wasm_valtype_new(WASM_I32)]);
+    let mut results = wasm_valtype_vec_t::from(vec![wasm_valtype_new(WASM_I32)]);
+    wasm_functype_new(&mut params, &mut results)
+}
+
+/// A host function that traps with a message.
+extern "C" fn host_trap(
+    _args: &wasm_val_vec_t,
+    _results: &mut wasm_val_vec_t,
+) -> Option<Box<wasm_trap_t>> {
+    let engine = wasm_engine_new();
+    let store = wasm_store_new(&engine);
+    let message = wasm_byte_vec_t::from(b"host trap\0".to_vec());
+    Some(wasm_trap_new(&store, &message))
+}
+
+/// A host function that returns a result of the wrong kind.
+extern "C" fn host_wrong_result(
+    _args: &wasm_val_vec_t,
+    results: &mut wasm_val_vec_t,
+) -> Option<Box<wasm_trap_t>> {
+    results.as_mut_slice()[0] = wasm_val_t {
+        kind: WASM_I64,
+        of: wasm_val_union { i64: 1 },
+    };
+    None
+}
+
+#[test]
+fn invalid_module() {
+    let engine = wasm_engine_new();
+    let mut store = wasm_store_new(&engine);
+    let binary = wasm_byte_vec_t::from(b"\0asm invalid".to_vec());
+    assert!(!wasm_module_validate(&mut store, &binary));
+    assert!(wasm_module_new(&mut store, &binary).is_none());
+    let binary = wasm("(module)");
+    assert!(wasm_module_validate(&mut store, &binary));
+    assert!(wasm_module_new(&mut store, &binary).is_some());
+}
+
+#[test]
+fn instantiation_error() {
+    let engine = wasm_engine_new();
+    let mut store = wasm_store_new(&engine);
+    let binary = wasm(r#"(module (import "host" "f" (func)))"#);
+    let module = wasm_module_new(&mut store, &binary).unwrap();
+    let imports = wasm_extern_vec_t::from(Vec::new());
+    let mut trap = None;
+    assert!(wasm_instance_new(&mut store, &module, &imports, Some(&mut trap)).is_none());
+    let trap = trap.unwrap();
+    let mut code = 0;
+    assert!(!wasmi_trap_code(&trap, &mut code));
+    assert!(!trap_message(&trap).is_empty());
+    // Note: the start function traps.
+    let binary = wasm(r#"(module (func $start (unreachable)) (start $start))"#);
+    let module = wasm_module_new(&mut store, &binary).unwrap();
+    let mut trap = None;
+    assert!(wasm_instance_new(&mut store, &module, &imports, Some(&mut trap)).is_none());
+    assert!(wasmi_trap_code(&trap.unwrap(), &mut code));
+    assert_eq!(code, WASMI_TRAP_CODE_UNREACHABLE_CODE_REACHED);
+}
+
+#[test]
+fn host_function_errors() {
+    let engine = wasm_engine_new();
+    let mut store = wasm_store_new(&engine);
+    let ty = functype_i32_i32();
+    let params = wasm_functype_params(&ty).as_slice();
+    assert_eq!(params.len(), 1);
+    assert_eq!(wasm_valtype_kind(params[0].as_ref().unwrap()), WASM_I32);
+    let args = wasm_val_vec_t::from(vec![val_i32(1)]);
+    let mut results = wasm_val_vec_t::from(vec![val_i32(0)]);
+
+    let trapping = wasm_func_new(&mut store, &ty, host_trap);
+    let trap = wasm_func_call(&trapping, &args, &mut results).unwrap();
+    assert_eq!(trap_message(&trap), "host trap");
+
+    let wrong_result = wasm_func_new(&mut store, &ty, host_wrong_result);
+    let trap = wasm_func_call(&wrong_result, &args, &mut results).unwrap();
+    assert!(trap_message(&trap).contains("I64"));
+
+    // Note: the results must be preallocated by the caller.
+    let mut no_results = wasm_val_vec_t::from(Vec::new());
+    let trap = wasm_func_call(&trapping, &args, &mut no_results).unwrap();
+    assert!(!trap_message(&trap).is_empty());
+}
+
+#[test]
+fn call_through_wasm() {
+    let engine = wasm_engine_new();
+    let mut store = wasm_store_new(&engine);
+    let binary = wasm(
+        r#"
+        (module
+            (import "host" "f" (func $f (param i32) (result i32)))
+            (func (export "div") (param i32) (result i32)
+                (i32.div_s (call $f (local.get 0)) (local.get 0))
+            )
+        )
+    "#,
+    );
+    let module = wasm_module_new(&mut store, &binary).unwrap();
+    let ty = functype_i32_i32();
+    let mut host = wasm_func_new(&mut store, &ty, host_wrong_result);
+    let import = wasm_extern_copy(wasm_func_as_extern(&mut host));
+    let imports = wasm_extern_vec_t::from(vec![Some(import)]);
+    let instance = wasm_instance_new(&mut store, &module, &imports, None).unwrap();
+    let mut exports = wasm_extern_vec_t::from(Vec::new());
+    wasm_instance_exports(&instance, &mut exports);
+    let export = exports.as_mut_slice()[0].as_mut().unwrap();
+    assert_eq!(wasm_extern_kind(export), WASM_EXTERN_FUNC);
+    assert!(wasm_extern_as_memory(export).is_none());
+    let div = wasm_extern_as_func(export).unwrap();
+    let args = wasm_val_vec_t::from(vec![val_i32(0)]);
+    let mut results = wasm_val_vec_t::from(vec![val_i32(0)]);
+    // Note: the error of the host function propagates through the Wasm caller.
+    let trap = wasm_func_call(div, &args, &mut results).unwrap();
+    assert!(trap_message(&trap).contains("I64"));
+}
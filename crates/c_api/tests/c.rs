@@ -0,0 +1,109 @@
+//! Compiles and runs the C programs in `tests/c` against the static library of this crate.
+//!
+//! # Note
+//!
+//! The C compiler is taken from the `CC` environment variable and defaults to `cc`.
+
+#![cfg(unix)]
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The Wasm module instantiated by `tests/c/example.c`.
+const EXAMPLE: &str = r#"
+    (module
+        (import "host" "double" (func $double (param i32) (result i32)))
+        (memory $memory 1)
+        (data (i32.const 0) "Hello, Wasmi!")
+        (func (export "run") (param i32) (result i32)
+            (i32.store (i32.const 16) (call $double (local.get 0)))
+            (i32.add (i32.load (i32.const 16)) (i32.const 1))
+        )
+        (func (export "trap")
+            (unreachable)
+        )
+        (export "memory" (memory $memory))
+    )
+"#;
+
+/// Builds the static library of this crate and returns its path.
+///
+/// # Note
+///
+/// Cargo does not build the static library for integration tests.
+fn build_static_lib() -> PathBuf {
+    let mut cargo = Command::new(env!("CARGO"));
+    cargo.args([
+        "build",
+        "--package",
+        "wasmi_c_api",
+        "--lib",
+        "--message-format=json",
+    ]);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    let output = cargo.output().unwrap();
+    assert!(
+        output.status.success(),
+        "failed to build the static library:\n{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .filter(|line| line.contains(r#""reason":"compiler-artifact""#))
+        .flat_map(|line| line.split('"'))
+        .find(|item| item.ends_with("libwasmi_c_api.a"))
+        .map(PathBuf::from)
+        .expect("missing static library in build artifacts")
+}
+
+/// Compiles the C program `tests/c/{name}.c` and runs it with the Wasm module `wat`.
+///
+/// Returns the standard output of the C program.
+///
+/// # Panics
+///
+/// If the C program fails to compile or exits unsuccessfully.
+fn run_c_program(name: &str, wat: &str) -> String {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let source = manifest_dir
+        .join("tests")
+        .join("c")
+        .join(format!("{name}.c"));
+    let exe = out_dir.join(name);
+    let wasm = out_dir.join(format!("{name}.wasm"));
+    std::fs::write(&wasm, wat::parse_str(wat).unwrap()).unwrap();
+    let compiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    let status = Command::new(compiler)
+        .arg(&source)
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(build_static_lib())
+        .args(["-lpthread", "-ldl", "-lm"])
+        .arg("-o")
+        .arg(&exe)
+        .status()
+        .unwrap();
+    assert!(status.success(), "failed to compile {}", source.display());
+    let output = Command::new(&exe).arg(&wasm).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        output.status.success(),
+        "{name} failed:\n{stdout}{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    stdout
+}
+
+#[test]
+fn example() {
+    let stdout = run_c_program("example", EXAMPLE);
+    assert!(stdout.contains("unreachable"), "{stdout}");
+    assert!(stdout.ends_with("ok\n"), "{stdout}");
+}
@@ -0,0 +1,145 @@
+// Instantiates the Wasm module at the path given as first argument and checks
+// that calls to its exports, its linear memory and host functions work as expected.
+//
+// The Wasm module is expected to import `host.double` and to export
+// the functions `run` and `trap` as well as its linear memory `memory`.
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "wasmi.h"
+
+#define CHECK(condition)                                                        \
+    do {                                                                        \
+        if (!(condition)) {                                                     \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,   \
+                    #condition);                                                \
+            exit(1);                                                            \
+        }                                                                       \
+    } while (0)
+
+// The number of calls to `host_double` and whether its finalizer ran.
+typedef struct {
+    int calls;
+    bool finalized;
+} host_env_t;
+
+static own wasm_trap_t* host_double(void* env, const wasm_val_vec_t* args,
+                                    wasm_val_vec_t* results) {
+    host_env_t* host = (host_env_t*)env;
+    host->calls += 1;
+    CHECK(args->size == 1 && args->data[0].kind == WASM_I32);
+    CHECK(results->size == 1);
+    results->data[0].kind = WASM_I32;
+    results->data[0].of.i32 = args->data[0].of.i32 * 2;
+    return NULL;
+}
+
+static void host_finalize(void* env) {
+    ((host_env_t*)env)->finalized = true;
+}
+
+static void read_file(const char* path, wasm_byte_vec_t* out) {
+    FILE* file = fopen(path, "rb");
+    CHECK(file != NULL);
+    fseek(file, 0, SEEK_END);
+    size_t size = (size_t)ftell(file);
+    fseek(file, 0, SEEK_SET);
+    wasm_byte_vec_new_uninitialized(out, size);
+    CHECK(fread(out->data, 1, size, file) == size);
+    fclose(file);
+}
+
+int main(int argc, char** argv) {
+    CHECK(argc == 2);
+    wasm_engine_t* engine = wasm_engine_new();
+    wasm_store_t* store = wasm_store_new(engine);
+
+    wasm_byte_vec_t binary;
+    read_file(argv[1], &binary);
+    wasm_module_t* module = wasm_module_new(store, &binary);
+    wasm_byte_vec_delete(&binary);
+    CHECK(module != NULL);
+
+    // Register the host function `host.double`.
+    host_env_t host = {0, false};
+    wasm_valtype_t* param = wasm_valtype_new(WASM_I32);
+    wasm_valtype_t* result = wasm_valtype_new(WASM_I32);
+    wasm_valtype_vec_t params, results;
+    wasm_valtype_vec_new(&params, 1, &param);
+    wasm_valtype_vec_new(&results, 1, &result);
+    wasm_functype_t* double_type = wasm_functype_new(&params, &results);
+    wasm_func_t* double_func =
+        wasm_func_new_with_env(store, double_type, host_double, &host, host_finalize);
+    wasm_functype_delete(double_type);
+
+    wasm_extern_t* externs[] = {wasm_func_as_extern(double_func)};
+    wasm_extern_vec_t imports = {1, externs};
+    wasm_trap_t* trap = NULL;
+    wasm_instance_t* instance = wasm_instance_new(store, module, &imports, &trap);
+    CHECK(instance != NULL && trap == NULL);
+
+    wasm_extern_vec_t exports;
+    wasm_instance_exports(instance, &exports);
+    CHECK(exports.size == 3);
+    wasm_func_t* run = wasm_extern_as_func(exports.data[0]);
+    wasm_func_t* trapping = wasm_extern_as_func(exports.data[1]);
+    wasm_memory_t* memory = wasm_extern_as_memory(exports.data[2]);
+    CHECK(run != NULL && trapping != NULL && memory != NULL);
+    CHECK(wasm_extern_as_memory(exports.data[0]) == NULL);
+    CHECK(wasm_func_param_arity(run) == 1 && wasm_func_result_arity(run) == 1);
+
+    // Call the exported `run` which calls back into `host.double`.
+    wasm_val_t arg = {.kind = WASM_I32, .of = {.i32 = 21}};
+    wasm_val_t out = {.kind = WASM_I32, .of = {.i32 = 0}};
+    wasm_val_vec_t args_vec = {1, &arg};
+    wasm_val_vec_t results_vec = {1, &out};
+    CHECK(wasm_func_call(run, &args_vec, &results_vec) == NULL);
+    CHECK(out.kind == WASM_I32 && out.of.i32 == 43);
+    CHECK(host.calls == 1);
+
+    // Read the linear memory written by its data segment and by `run`.
+    CHECK(wasm_memory_size(memory) == 1);
+    CHECK(wasm_memory_data_size(memory) == 65536);
+    byte_t* data = wasm_memory_data(memory);
+    CHECK(memcmp(data, "Hello, Wasmi!", 13) == 0);
+    int32_t stored;
+    memcpy(&stored, data + 16, sizeof(stored));
+    CHECK(stored == 42);
+    CHECK(wasm_memory_grow(memory, 1));
+    CHECK(wasm_memory_data_size(memory) == 2 * 65536);
+
+    // Inspect the trap of the exported `trap`.
+    wasm_val_vec_t no_vals = {0, NULL};
+    trap = wasm_func_call(trapping, &no_vals, &no_vals);
+    CHECK(trap != NULL);
+    wasmi_trap_code_t code;
+    CHECK(wasmi_trap_code(trap, &code));
+    CHECK(code == WASMI_TRAP_CODE_UNREACHABLE_CODE_REACHED);
+    wasm_message_t message;
+    wasm_trap_message(trap, &message);
+    CHECK(message.size > 1 && message.data[message.size - 1] == '\0');
+    printf("trap: %s\n", message.data);
+    wasm_byte_vec_delete(&message);
+    wasm_trap_delete(trap);
+
+    // Calls with arguments of the wrong type trap instead of aborting.
+    wasm_val_t bad_arg = {.kind = WASM_I64, .of = {.i64 = 1}};
+    wasm_val_vec_t bad_args_vec = {1, &bad_arg};
+    trap = wasm_func_call(run, &bad_args_vec, &results_vec);
+    CHECK(trap != NULL);
+    CHECK(!wasmi_trap_code(trap, &code));
+    wasm_trap_delete(trap);
+
+    wasm_extern_vec_delete(&exports);
+    wasm_func_delete(double_func);
+    wasm_instance_delete(instance);
+    wasm_module_delete(module);
+    CHECK(!host.finalized);
+    wasm_store_delete(store);
+    CHECK(host.finalized);
+    wasm_engine_delete(engine);
+    printf("ok\n");
+    return 0;
+}
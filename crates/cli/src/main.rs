@@ -44,7 +44,7 @@ fn main() -> Result<()> {
             Ok(())
         }
         Err(error) => {
-            if let Some(exit_code) = error.i32_exit_status() {
+            if let Some(exit_code) = error.exit_status() {
                 // We received an exit code from the WASI program,
                 // therefore we exit with the same exit code after
                 // pretty printing the results.
@@ -0,0 +1,546 @@
+//! Wasm `min`, `max` and `nearest` operations for `f32` and `f64`.
+//!
+//! The portable implementations branch on NaN and zero operands in order to adhere to the Wasm specification.
+//! On `x86_64` and `aarch64` there are instructions with almost the exact Wasm semantics
+//! which are selected at compile time instead.
+//!
+//! # Note
+//!
+//! All implementations produce bit-identical results for the same inputs so that
+//! Wasm execution stays deterministic across platforms. Notably this includes:
+//!
+//! - `min(-0.0, +0.0)` is `-0.0` and `max(-0.0, +0.0)` is `+0.0` for either operand order.
+//! - If an operand of `min` or `max` is NaN it is returned unaltered including its payload.
+//!   If both operands are NaN the left-hand side operand is returned.
+//! - `nearest` rounds ties to even and preserves the sign of zero results.
+
+/// Selects the implementation for the `min`, `max` and `nearest` operations of `$fXX`.
+macro_rules! select_float_ops {
+    ($fXX:ident) => {
+        pub mod $fXX {
+            #[cfg(target_arch = "aarch64")]
+            pub use super::aarch64::$fXX::{max, min, nearest};
+            #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+            pub use super::portable::$fXX::{max, min, nearest};
+            #[cfg(target_arch = "x86_64")]
+            pub use super::x86_64::$fXX::{max, min, nearest};
+        }
+    };
+}
+select_float_ops!(f32);
+select_float_ops!(f64);
+
+/// Portable implementations of the Wasm float operations.
+///
+/// # Note
+///
+/// Serves as fallback on platforms without specialized implementations
+/// and as reference implementation for testing.
+#[cfg_attr(any(target_arch = "x86_64", target_arch = "aarch64"), allow(dead_code))]
+mod portable {
+    use crate::value::fmath;
+
+    macro_rules! impl_float_ops {
+        ($fXX:ident) => {
+            pub mod $fXX {
+                use super::fmath;
+
+                /// Returns the Wasm `min` of `lhs` and `rhs`.
+                #[inline]
+                pub fn min(lhs: $fXX, rhs: $fXX) -> $fXX {
+                    // Note: In other contexts this API is also known as: `nan_min`.
+                    if lhs.is_nan() {
+                        return lhs;
+                    }
+                    if rhs.is_nan() {
+                        return rhs;
+                    }
+                    if lhs == rhs {
+                        // Case: `-0.0` and `+0.0` compare equal but `-0.0` is the minimum.
+                        return <$fXX>::from_bits(lhs.to_bits() | rhs.to_bits());
+                    }
+                    if lhs < rhs {
+                        lhs
+                    } else {
+                        rhs
+                    }
+                }
+
+                /// Returns the Wasm `max` of `lhs` and `rhs`.
+                #[inline]
+                pub fn max(lhs: $fXX, rhs: $fXX) -> $fXX {
+                    // Note: In other contexts this API is also known as: `nan_max`.
+                    if lhs.is_nan() {
+                        return lhs;
+                    }
+                    if rhs.is_nan() {
+                        return rhs;
+                    }
+                    if lhs == rhs {
+                        // Case: `-0.0` and `+0.0` compare equal but `+0.0` is the maximum.
+                        return <$fXX>::from_bits(lhs.to_bits() & rhs.to_bits());
+                    }
+                    if lhs > rhs {
+                        lhs
+                    } else {
+                        rhs
+                    }
+                }
+
+                /// Returns `input` rounded to the nearest integer with ties to even.
+                #[inline]
+                pub fn nearest(input: $fXX) -> $fXX {
+                    let round = fmath::$fXX::round(input);
+                    if fmath::$fXX::abs(fmath::$fXX::fract(input)) != 0.5 {
+                        return round;
+                    }
+                    let rem = round % 2.0;
+                    if rem == 1.0 {
+                        fmath::$fXX::floor(input)
+                    } else if rem == -1.0 {
+                        fmath::$fXX::ceil(input)
+                    } else {
+                        round
+                    }
+                }
+            }
+        };
+    }
+    impl_float_ops!(f32);
+    impl_float_ops!(f64);
+}
+
+/// Implementations of the Wasm float operations using SSE instructions.
+///
+/// # Note
+///
+/// The `minss` and `maxss` instructions return their second operand if both operands
+/// are equal or if any of them is NaN. Evaluating them for both operand orders and
+/// combining the results via bitwise `or` and `and` respectively resolves the ordering of
+/// zeros with differing signs. NaN operands are rare and handled on a separate path.
+///
+/// `roundss` requires SSE4.1 which is not part of the `x86_64` baseline and
+/// therefore must be enabled at compile time, e.g. via `-C target-feature=+sse4.1`.
+/// Otherwise `nearest` adds and subtracts the smallest float that has no fractional digits
+/// which rounds ties to even in the default floating point environment.
+/// This is exact since SSE2 arithmetic does not suffer from double rounding unlike x87.
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use core::arch::x86_64::*;
+
+    macro_rules! impl_float_ops {
+        (
+            $fXX:ident,
+            bits: $uXX:ident,
+            set: $set:ident,
+            get: $get:ident,
+            min: $min:ident,
+            max: $max:ident,
+            or: $or:ident,
+            and: $and:ident,
+            round: $round:ident $(,)?
+        ) => {
+            pub mod $fXX {
+                use super::*;
+
+                /// Returns the Wasm `min` of `lhs` and `rhs`.
+                #[inline]
+                #[allow(unused_unsafe)] // SSE2 intrinsics are safe to call since Rust 1.87.
+                pub fn min(lhs: $fXX, rhs: $fXX) -> $fXX {
+                    if lhs.is_nan() | rhs.is_nan() {
+                        return if lhs.is_nan() { lhs } else { rhs };
+                    }
+                    // SAFETY: SSE2 is part of the `x86_64` baseline.
+                    unsafe {
+                        let lhs = $set(lhs);
+                        let rhs = $set(rhs);
+                        $get($or($min(lhs, rhs), $min(rhs, lhs)))
+                    }
+                }
+
+                /// Returns the Wasm `max` of `lhs` and `rhs`.
+                #[inline]
+                #[allow(unused_unsafe)] // SSE2 intrinsics are safe to call since Rust 1.87.
+                pub fn max(lhs: $fXX, rhs: $fXX) -> $fXX {
+                    if lhs.is_nan() | rhs.is_nan() {
+                        return if lhs.is_nan() { lhs } else { rhs };
+                    }
+                    // SAFETY: SSE2 is part of the `x86_64` baseline.
+                    unsafe {
+                        let lhs = $set(lhs);
+                        let rhs = $set(rhs);
+                        $get($and($max(lhs, rhs), $max(rhs, lhs)))
+                    }
+                }
+
+                /// Returns `input` rounded to the nearest integer with ties to even.
+                #[cfg(target_feature = "sse4.1")]
+                #[inline]
+                #[allow(unused_unsafe)] // SSE4.1 intrinsics are safe to call since Rust 1.87.
+                pub fn nearest(input: $fXX) -> $fXX {
+                    // SAFETY: SSE4.1 is enabled at compile time.
+                    unsafe {
+                        let input = $set(input);
+                        $get($round::<{ _MM_FROUND_TO_NEAREST_INT | _MM_FROUND_NO_EXC }>(
+                            input, input,
+                        ))
+                    }
+                }
+
+                /// Returns `input` rounded to the nearest integer with ties to even.
+                #[cfg(not(target_feature = "sse4.1"))]
+                #[inline]
+                pub fn nearest(input: $fXX) -> $fXX {
+                    /// The bit mask of the sign bit.
+                    const SIGN: $uXX = 1 << (<$uXX>::BITS - 1);
+                    /// The bit mask of the bit that distinguishes quiet from signaling NaNs.
+                    const QUIET: $uXX = 1 << (<$fXX>::MANTISSA_DIGITS - 2);
+                    /// The smallest float of which all representable successors are integers.
+                    const EXACT: $fXX = ((1 as $uXX) << (<$fXX>::MANTISSA_DIGITS - 1)) as $fXX;
+                    let bits = input.to_bits();
+                    let abs = <$fXX>::from_bits(bits & !SIGN);
+                    if abs < EXACT {
+                        let rounded = (abs + EXACT) - EXACT;
+                        return <$fXX>::from_bits(rounded.to_bits() | (bits & SIGN));
+                    }
+                    if input.is_nan() {
+                        return <$fXX>::from_bits(bits | QUIET);
+                    }
+                    input
+                }
+            }
+        };
+    }
+    /// Moves `value` into the lowest lane of a SSE vector.
+    #[inline]
+    #[allow(unused_unsafe)] // SSE2 intrinsics are safe to call since Rust 1.87.
+    fn f32_to_vector(value: f32) -> __m128 {
+        // SAFETY: SSE2 is part of the `x86_64` baseline.
+        //
+        // Note: `_mm_set_ss` would additionally clear the upper lanes that are never used.
+        unsafe { _mm_castsi128_ps(_mm_cvtsi32_si128(value.to_bits() as i32)) }
+    }
+
+    /// Moves `value` into the lowest lane of a SSE vector.
+    #[inline]
+    #[allow(unused_unsafe)] // SSE2 intrinsics are safe to call since Rust 1.87.
+    fn f64_to_vector(value: f64) -> __m128d {
+        // SAFETY: SSE2 is part of the `x86_64` baseline.
+        //
+        // Note: `_mm_set_sd` would additionally clear the upper lanes that are never used.
+        unsafe { _mm_castsi128_pd(_mm_cvtsi64_si128(value.to_bits() as i64)) }
+    }
+
+    impl_float_ops!(
+        f32,
+        bits: u32,
+        set: f32_to_vector,
+        get: _mm_cvtss_f32,
+        min: _mm_min_ss,
+        max: _mm_max_ss,
+        or: _mm_or_ps,
+        and: _mm_and_ps,
+        round: _mm_round_ss,
+    );
+    impl_float_ops!(
+        f64,
+        bits: u64,
+        set: f64_to_vector,
+        get: _mm_cvtsd_f64,
+        min: _mm_min_sd,
+        max: _mm_max_sd,
+        or: _mm_or_pd,
+        and: _mm_and_pd,
+        round: _mm_round_sd,
+    );
+}
+
+/// Implementations of the Wasm float operations using NEON instructions.
+///
+/// # Note
+///
+/// The `fmin` and `fmax` instructions have the exact Wasm semantics for non-NaN operands.
+/// Unlike `fminnm` and `fmaxnm` they propagate NaN operands, however, they quiet signaling NaNs.
+/// Therefore NaN operands are handled on a separate path in order to preserve their payloads.
+/// The `frintn` instruction rounds to nearest with ties to even.
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    macro_rules! impl_float_ops {
+        (
+            $fXX:ident,
+            set: $set:ident,
+            get: $get:ident,
+            min: $min:ident,
+            max: $max:ident,
+            round: $round:ident $(,)?
+        ) => {
+            pub mod $fXX {
+                use core::arch::aarch64::*;
+
+                /// Returns the Wasm `min` of `lhs` and `rhs`.
+                #[inline]
+                #[allow(unused_unsafe)] // NEON intrinsics are safe to call since Rust 1.87.
+                pub fn min(lhs: $fXX, rhs: $fXX) -> $fXX {
+                    if lhs.is_nan() | rhs.is_nan() {
+                        return if lhs.is_nan() { lhs } else { rhs };
+                    }
+                    // SAFETY: NEON is part of the `aarch64` baseline.
+                    unsafe { $get::<0>($min($set(lhs), $set(rhs))) }
+                }
+
+                /// Returns the Wasm `max` of `lhs` and `rhs`.
+                #[inline]
+                #[allow(unused_unsafe)] // NEON intrinsics are safe to call since Rust 1.87.
+                pub fn max(lhs: $fXX, rhs: $fXX) -> $fXX {
+                    if lhs.is_nan() | rhs.is_nan() {
+                        return if lhs.is_nan() { lhs } else { rhs };
+                    }
+                    // SAFETY: NEON is part of the `aarch64` baseline.
+                    unsafe { $get::<0>($max($set(lhs), $set(rhs))) }
+                }
+
+                /// Returns `input` rounded to the nearest integer with ties to even.
+                #[inline]
+                #[allow(unused_unsafe)] // NEON intrinsics are safe to call since Rust 1.87.
+                pub fn nearest(input: $fXX) -> $fXX {
+                    // SAFETY: NEON is part of the `aarch64` baseline.
+                    unsafe { $get::<0>($round($set(input))) }
+                }
+            }
+        };
+    }
+    impl_float_ops!(
+        f32,
+        set: vdup_n_f32,
+        get: vget_lane_f32,
+        min: vmin_f32,
+        max: vmax_f32,
+        round: vrndn_f32,
+    );
+    impl_float_ops!(
+        f64,
+        set: vdup_n_f64,
+        get: vget_lane_f64,
+        min: vmin_f64,
+        max: vmax_f64,
+        round: vrndn_f64,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::portable;
+    use rand::Rng as _;
+
+    macro_rules! impl_tests {
+        ($fXX:ident, $uXX:ident, quiet: $quiet:literal, values: [$($value:expr),* $(,)?]) => {
+            mod $fXX {
+                use super::*;
+                use crate::float::$fXX::{max, min, nearest};
+
+                /// Interesting edge-case inputs for the Wasm float operations.
+                const VALUES: &[$uXX] = &[$($value),*];
+
+                /// Asserts that `op` and its `reference` agree bitwise for `lhs` and `rhs`.
+                fn assert_binop(
+                    op: fn($fXX, $fXX) -> $fXX,
+                    reference: fn($fXX, $fXX) -> $fXX,
+                    lhs: $uXX,
+                    rhs: $uXX,
+                ) {
+                    let lhs = <$fXX>::from_bits(lhs);
+                    let rhs = <$fXX>::from_bits(rhs);
+                    let result = op(lhs, rhs);
+                    assert_eq!(
+                        result.to_bits(),
+                        reference(lhs, rhs).to_bits(),
+                        "{lhs:?}, {rhs:?} => {result:?}",
+                    );
+                }
+
+                /// Asserts that `nearest` and its reference agree bitwise for `input`.
+                fn assert_nearest(input: $uXX) {
+                    let input = <$fXX>::from_bits(input);
+                    let result = nearest(input);
+                    assert_eq!(
+                        result.to_bits(),
+                        portable::$fXX::nearest(input).to_bits(),
+                        "{input:?} => {result:?}",
+                    );
+                }
+
+                #[test]
+                fn matches_reference() {
+                    for &lhs in VALUES {
+                        assert_nearest(lhs);
+                        for &rhs in VALUES {
+                            assert_binop(min, portable::$fXX::min, lhs, rhs);
+                            assert_binop(max, portable::$fXX::max, lhs, rhs);
+                        }
+                    }
+                }
+
+                #[test]
+                fn matches_reference_random() {
+                    let mut rng = rand::thread_rng();
+                    for _ in 0..100_000 {
+                        let lhs = rng.gen::<$uXX>();
+                        let rhs = rng.gen::<$uXX>();
+                        assert_nearest(lhs);
+                        assert_binop(min, portable::$fXX::min, lhs, rhs);
+                        assert_binop(max, portable::$fXX::max, lhs, rhs);
+                    }
+                }
+
+                #[test]
+                fn signed_zeros() {
+                    let pos = 0.0 as $fXX;
+                    let neg = -0.0 as $fXX;
+                    assert_eq!(min(pos, neg).to_bits(), neg.to_bits());
+                    assert_eq!(min(neg, pos).to_bits(), neg.to_bits());
+                    assert_eq!(max(pos, neg).to_bits(), pos.to_bits());
+                    assert_eq!(max(neg, pos).to_bits(), pos.to_bits());
+                    assert_eq!(nearest(-0.5).to_bits(), neg.to_bits());
+                    assert_eq!(nearest(-0.25).to_bits(), neg.to_bits());
+                    assert_eq!(nearest(neg).to_bits(), neg.to_bits());
+                    assert_eq!(nearest(0.5).to_bits(), pos.to_bits());
+                }
+
+                #[test]
+                fn nan_propagation() {
+                    let nans = [
+                        <$fXX>::NAN.to_bits(),
+                        (-<$fXX>::NAN).to_bits(),
+                        <$fXX>::NAN.to_bits() | 0x1234,
+                        // Signaling NaN with payload.
+                        <$fXX>::INFINITY.to_bits() | 1,
+                        (-<$fXX>::INFINITY).to_bits() | 0x4321,
+                    ];
+                    for nan in nans.map(<$fXX>::from_bits) {
+                        for &other in VALUES {
+                            let other = <$fXX>::from_bits(other);
+                            if other.is_nan() {
+                                continue;
+                            }
+                            assert_eq!(min(nan, other).to_bits(), nan.to_bits());
+                            assert_eq!(min(other, nan).to_bits(), nan.to_bits());
+                            assert_eq!(max(nan, other).to_bits(), nan.to_bits());
+                            assert_eq!(max(other, nan).to_bits(), nan.to_bits());
+                        }
+                        // `nearest` returns the quieted NaN input.
+                        assert_eq!(nearest(nan).to_bits(), nan.to_bits() | $quiet);
+                        for other in nans.map(<$fXX>::from_bits) {
+                            assert_eq!(min(nan, other).to_bits(), nan.to_bits());
+                            assert_eq!(max(nan, other).to_bits(), nan.to_bits());
+                        }
+                    }
+                }
+
+                #[test]
+                fn nearest_ties_to_even() {
+                    let cases: &[($fXX, $fXX)] = &[
+                        (0.5, 0.0),
+                        (1.5, 2.0),
+                        (2.5, 2.0),
+                        (3.5, 4.0),
+                        (-1.5, -2.0),
+                        (-2.5, -2.0),
+                        (0.49, 0.0),
+                        (0.51, 1.0),
+                        (-0.51, -1.0),
+                        (<$fXX>::INFINITY, <$fXX>::INFINITY),
+                        (<$fXX>::NEG_INFINITY, <$fXX>::NEG_INFINITY),
+                        (<$fXX>::MAX, <$fXX>::MAX),
+                        (<$fXX>::MIN, <$fXX>::MIN),
+                        (<$fXX>::MIN_POSITIVE, 0.0),
+                        (<$fXX>::from_bits(1), 0.0),
+                    ];
+                    for &(input, expected) in cases {
+                        assert_eq!(nearest(input).to_bits(), expected.to_bits(), "{input:?}");
+                    }
+                }
+
+                #[test]
+                fn subnormals() {
+                    let smallest = <$fXX>::from_bits(1);
+                    let largest = <$fXX>::from_bits((<$fXX>::MIN_POSITIVE.to_bits()) - 1);
+                    assert_eq!(min(smallest, largest).to_bits(), smallest.to_bits());
+                    assert_eq!(max(smallest, largest).to_bits(), largest.to_bits());
+                    assert_eq!(min(-smallest, 0.0).to_bits(), (-smallest).to_bits());
+                    assert_eq!(max(-smallest, -0.0).to_bits(), (-0.0 as $fXX).to_bits());
+                    assert_eq!(nearest(-largest).to_bits(), (-0.0 as $fXX).to_bits());
+                }
+            }
+        };
+    }
+    impl_tests!(
+        f32,
+        u32,
+        quiet: 0x0040_0000,
+        values: [
+            0x0000_0000, // +0.0
+            0x8000_0000, // -0.0
+            0x3F80_0000, // +1.0
+            0xBF80_0000, // -1.0
+            0x3F00_0000, // +0.5
+            0xBF00_0000, // -0.5
+            0x3FC0_0000, // +1.5
+            0xBFC0_0000, // -1.5
+            0x4020_0000, // +2.5
+            0xC020_0000, // -2.5
+            0x3EFF_FFFF, // 0.49999997
+            0x4AFF_FFFF, // 8388607.5
+            0x4B00_0000, // 8388608.0
+            0x4B00_0001, // 8388609.0
+            0x0000_0001, // smallest positive subnormal
+            0x8000_0001, // smallest negative subnormal
+            0x007F_FFFF, // largest positive subnormal
+            0x807F_FFFF, // largest negative subnormal
+            0x0080_0000, // smallest positive normal
+            0x7F7F_FFFF, // largest finite
+            0xFF7F_FFFF, // smallest finite
+            0x7F80_0000, // +inf
+            0xFF80_0000, // -inf
+            0x7FC0_0000, // canonical NaN
+            0xFFC0_0000, // negative canonical NaN
+            0x7FC0_1234, // quiet NaN with payload
+            0x7FA0_0000, // signaling NaN
+            0xFF80_0001, // negative signaling NaN
+        ]
+    );
+    impl_tests!(
+        f64,
+        u64,
+        quiet: 0x0008_0000_0000_0000,
+        values: [
+            0x0000_0000_0000_0000, // +0.0
+            0x8000_0000_0000_0000, // -0.0
+            0x3FF0_0000_0000_0000, // +1.0
+            0xBFF0_0000_0000_0000, // -1.0
+            0x3FE0_0000_0000_0000, // +0.5
+            0xBFE0_0000_0000_0000, // -0.5
+            0x3FF8_0000_0000_0000, // +1.5
+            0xBFF8_0000_0000_0000, // -1.5
+            0x4004_0000_0000_0000, // +2.5
+            0xC004_0000_0000_0000, // -2.5
+            0x3FDF_FFFF_FFFF_FFFF, // 0.49999999999999994
+            0x432F_FFFF_FFFF_FFFF, // 4503599627370495.5
+            0x4330_0000_0000_0000, // 4503599627370496.0
+            0x4330_0000_0000_0001, // 4503599627370497.0
+            0x0000_0000_0000_0001, // smallest positive subnormal
+            0x8000_0000_0000_0001, // smallest negative subnormal
+            0x000F_FFFF_FFFF_FFFF, // largest positive subnormal
+            0x800F_FFFF_FFFF_FFFF, // largest negative subnormal
+            0x0010_0000_0000_0000, // smallest positive normal
+            0x7FEF_FFFF_FFFF_FFFF, // largest finite
+            0xFFEF_FFFF_FFFF_FFFF, // smallest finite
+            0x7FF0_0000_0000_0000, // +inf
+            0xFFF0_0000_0000_0000, // -inf
+            0x7FF8_0000_0000_0000, // canonical NaN
+            0xFFF8_0000_0000_0000, // negative canonical NaN
+            0x7FF8_0000_0000_1234, // quiet NaN with payload
+            0x7FF4_0000_0000_0000, // signaling NaN
+            0xFFF0_0000_0000_0001, // negative signaling NaN
+        ]
+    );
+}
@@ -291,6 +291,12 @@ pub enum TrapCode {
     /// desire on the part of the embedder to trap the interpreter rather than
     /// merely fail the growth operation.
     GrowthOperationLimited,
+
+    /// This trap is raised when a WebAssembly execution attempted to write to
+    /// a range of a linear memory that is guarded by a write watchpoint.
+    ///
+    /// The write is not applied to the linear memory.
+    Watchpoint,
 }
 
 impl TrapCode {
@@ -313,6 +319,7 @@ impl TrapCode {
             Self::BadSignature => "indirect call type mismatch",
             Self::OutOfFuel => "all fuel consumed by WebAssembly",
             Self::GrowthOperationLimited => "growth operation limited",
+            Self::Watchpoint => "write to watched linear memory",
         }
     }
 }
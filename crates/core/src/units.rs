@@ -4,6 +4,9 @@
 pub struct Pages(u32);
 
 impl Pages {
+    /// The `log2` of the default byte size of a linear memory page.
+    pub const DEFAULT_PAGE_SIZE_LOG2: u8 = 16;
+
     /// The maximum amount of pages on the `wasm32` target.
     ///
     /// # Note
@@ -15,6 +18,24 @@ impl Pages {
     pub const fn max() -> Self {
         Self(65536) // 2^16
     }
+
+    /// The maximum amount of pages of a linear memory with pages of `2^page_size_log2` bytes.
+    ///
+    /// # Note
+    ///
+    /// A linear memory can have at most 2^32 bytes. With single byte pages
+    /// the maximum is `u32::MAX` pages since 2^32 cannot be represented.
+    ///
+    /// # Panics
+    ///
+    /// If `page_size_log2` is greater than 16.
+    pub const fn max_for_page_size(page_size_log2: u8) -> Self {
+        assert!(page_size_log2 <= Self::DEFAULT_PAGE_SIZE_LOG2);
+        match page_size_log2 {
+            0 => Self(u32::MAX),
+            n => Self(1 << (32 - n as u32)),
+        }
+    }
 }
 
 impl From<u16> for Pages {
@@ -75,6 +96,33 @@ impl Pages {
     pub fn to_bytes(self) -> Option<usize> {
         Bytes::new(self).map(Into::into)
     }
+
+    /// Creates a new amount of [`Pages`] of `2^page_size_log2` bytes if the amount is within bounds.
+    ///
+    /// Returns `None` if the given `amount` exceeds [`Pages::max_for_page_size`].
+    ///
+    /// # Panics
+    ///
+    /// If `page_size_log2` is greater than 16.
+    pub fn new_for_page_size(amount: u32, page_size_log2: u8) -> Option<Self> {
+        if amount > u32::from(Self::max_for_page_size(page_size_log2)) {
+            return None;
+        }
+        Some(Self(amount))
+    }
+
+    /// Returns the amount of bytes required for the amount of [`Pages`] of `2^page_size_log2` bytes.
+    ///
+    /// Returns `None` if the amount of pages represented by `self` cannot
+    /// be represented as bytes on the executing platform.
+    ///
+    /// # Panics
+    ///
+    /// If `page_size_log2` is greater than 16.
+    pub fn to_bytes_for_page_size(self, page_size_log2: u8) -> Option<usize> {
+        assert!(page_size_log2 <= Self::DEFAULT_PAGE_SIZE_LOG2);
+        Bytes::new_impl(self, 1 << page_size_log2, Bytes::max()).map(Into::into)
+    }
 }
 
 impl From<Pages> for u32 {
@@ -132,6 +180,19 @@ impl Bytes {
         }
     }
 
+    /// Returns the maximum amount of bytes representable on the executing platform.
+    fn max() -> u64 {
+        if cfg!(target_pointer_width = "16") {
+            Self::max16()
+        } else if cfg!(target_pointer_width = "32") {
+            Self::max32()
+        } else if cfg!(target_pointer_width = "64") {
+            Self::max64()
+        } else {
+            0
+        }
+    }
+
     /// Creates [`Bytes`] from the given amount of [`Pages`] as if
     /// on a 16-bit platform if possible.
     ///
@@ -141,7 +202,7 @@ impl Bytes {
     ///
     /// This API exists in isolation for cross-platform testing purposes.
     fn new16(pages: Pages) -> Option<Bytes> {
-        Self::new_impl(pages, Bytes::max16())
+        Self::new_impl(pages, usize::from(Self::per_page()) as u64, Bytes::max16())
     }
 
     /// Creates [`Bytes`] from the given amount of [`Pages`] as if
@@ -153,7 +214,7 @@ impl Bytes {
     ///
     /// This API exists in isolation for cross-platform testing purposes.
     fn new32(pages: Pages) -> Option<Bytes> {
-        Self::new_impl(pages, Bytes::max32())
+        Self::new_impl(pages, usize::from(Self::per_page()) as u64, Bytes::max32())
     }
 
     /// Creates [`Bytes`] from the given amount of [`Pages`] as if
@@ -165,13 +226,12 @@ impl Bytes {
     ///
     /// This API exists in isolation for cross-platform testing purposes.
     fn new64(pages: Pages) -> Option<Bytes> {
-        Self::new_impl(pages, Bytes::max64())
+        Self::new_impl(pages, usize::from(Self::per_page()) as u64, Bytes::max64())
     }
 
     /// Actual underlying implementation of [`Bytes::new`].
-    fn new_impl(pages: Pages, max: u64) -> Option<Bytes> {
+    fn new_impl(pages: Pages, bytes_per_page: u64, max: u64) -> Option<Bytes> {
         let pages = u64::from(u32::from(pages));
-        let bytes_per_page = usize::from(Self::per_page()) as u64;
         let bytes = pages
             .checked_mul(bytes_per_page)
             .filter(|&amount| amount <= max)?;
@@ -313,4 +373,17 @@ mod tests {
         assert!(Bytes::new64(Pages(u32::from(u16::MAX) + 2)).is_none());
         assert!(Bytes::new64(Pages::max()).is_some());
     }
+
+    #[test]
+    fn pages_for_page_size() {
+        assert_eq!(Pages::max_for_page_size(16), Pages::max());
+        assert_eq!(Pages::max_for_page_size(12), Pages(1 << 20));
+        assert_eq!(Pages::max_for_page_size(0), Pages(u32::MAX));
+        assert_eq!(Pages::new_for_page_size(1 << 20, 12), Some(Pages(1 << 20)));
+        assert_eq!(Pages::new_for_page_size((1 << 20) + 1, 12), None);
+        assert_eq!(Pages::new_for_page_size(u32::MAX, 0), Some(Pages(u32::MAX)));
+        assert_eq!(pages(3).to_bytes_for_page_size(12), Some(3 * 4096));
+        assert_eq!(pages(3).to_bytes_for_page_size(0), Some(3));
+        assert_eq!(pages(3).to_bytes_for_page_size(16), pages(3).to_bytes());
+    }
 }
@@ -127,14 +127,15 @@ macro_rules! op {
 
 /// Calculates the effective address of a linear memory access.
 ///
-/// # Errors
+/// # Note
 ///
-/// If the resulting effective address overflows.
-fn effective_address(address: u32, offset: u32) -> Result<usize, TrapCode> {
-    offset
-        .checked_add(address)
-        .map(|address| address as usize)
-        .ok_or(TrapCode::MemoryOutOfBounds)
+/// The effective address is computed in 64-bit and thus cannot overflow for
+/// accesses to 32-bit linear memories. Effective addresses that do not fit into
+/// `usize` saturate to `usize::MAX` which is always out of bounds.
+#[inline(always)]
+fn effective_address(address: u32, offset: u32) -> usize {
+    let address = u64::from(address) + u64::from(offset);
+    usize::try_from(address).unwrap_or(usize::MAX)
 }
 
 impl UntypedValue {
@@ -150,7 +151,7 @@ impl UntypedValue {
         U: LittleEndianConvert + ExtendInto<T>,
     {
         let raw_address = u32::from(address);
-        let address = effective_address(raw_address, offset)?;
+        let address = effective_address(raw_address, offset);
         let mut buffer = <<U as LittleEndianConvert>::Bytes as Default>::default();
         buffer.load_into(memory, address)?;
         let value: Self = <U as LittleEndianConvert>::from_le_bytes(buffer)
@@ -329,7 +330,7 @@ impl UntypedValue {
         U: LittleEndianConvert,
     {
         let raw_address = u32::from(address);
-        let address = effective_address(raw_address, offset)?;
+        let address = effective_address(raw_address, offset);
         let wrapped = T::from(value).wrap_into();
         let buffer = <U as LittleEndianConvert>::into_le_bytes(wrapped);
         buffer.store_from(memory, address)?;
@@ -1500,3 +1501,41 @@ macro_rules! impl_encode_untyped_slice {
     };
 }
 for_each_tuple!(impl_encode_untyped_slice);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_in_bounds() {
+        let memory = [0x01, 0x02, 0x03, 0x04, 0x05];
+        let load = |address: u32, offset: u32| {
+            UntypedValue::i32_load(&memory, UntypedValue::from(address), offset).map(i32::from)
+        };
+        assert_eq!(load(0, 0), Ok(0x0403_0201));
+        assert_eq!(load(0, 1), Ok(0x0504_0302));
+        assert_eq!(load(1, 0), Ok(0x0504_0302));
+        assert_eq!(load(1, 1), Err(TrapCode::MemoryOutOfBounds));
+        assert_eq!(load(2, 0), Err(TrapCode::MemoryOutOfBounds));
+        assert_eq!(load(0, u32::MAX), Err(TrapCode::MemoryOutOfBounds));
+        assert_eq!(load(u32::MAX, u32::MAX), Err(TrapCode::MemoryOutOfBounds));
+    }
+
+    #[test]
+    fn store_in_bounds() {
+        let mut memory = [0x00; 5];
+        let mut store = |address: u32, offset: u32| {
+            UntypedValue::i32_store(
+                &mut memory,
+                UntypedValue::from(address),
+                offset,
+                UntypedValue::from(0x0403_0201_i32),
+            )
+        };
+        assert_eq!(store(1, 0), Ok(()));
+        assert_eq!(store(1, 1), Err(TrapCode::MemoryOutOfBounds));
+        assert_eq!(store(u32::MAX, 1), Err(TrapCode::MemoryOutOfBounds));
+        assert_eq!(store(1, u32::MAX), Err(TrapCode::MemoryOutOfBounds));
+        assert_eq!(memory, [0x00, 0x01, 0x02, 0x03, 0x04]);
+    }
+}
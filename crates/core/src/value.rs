@@ -1,4 +1,5 @@
 use crate::{
+    float,
     nan_preserving_float::{F32, F64},
     TrapCode,
 };
@@ -112,12 +113,8 @@ pub trait LoadInto {
 impl<const N: usize> LoadInto for [u8; N] {
     #[inline]
     fn load_into(&mut self, memory: &[u8], address: usize) -> Result<(), TrapCode> {
-        let slice: &Self = memory
-            .get(address..)
-            .and_then(|slice| slice.get(..N))
-            .and_then(|slice| slice.try_into().ok())
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        *self = *slice;
+        let start = in_bounds_start::<N>(memory.len(), address as u64)?;
+        self.copy_from_slice(&memory[start..start + N]);
         Ok(())
     }
 }
@@ -135,16 +132,31 @@ pub trait StoreFrom {
 impl<const N: usize> StoreFrom for [u8; N] {
     #[inline]
     fn store_from(&self, memory: &mut [u8], address: usize) -> Result<(), TrapCode> {
-        let slice: &mut Self = memory
-            .get_mut(address..)
-            .and_then(|slice| slice.get_mut(..N))
-            .and_then(|slice| slice.try_into().ok())
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        *slice = *self;
+        let start = in_bounds_start::<N>(memory.len(), address as u64)?;
+        memory[start..start + N].copy_from_slice(self);
         Ok(())
     }
 }
 
+/// Returns `address` as `usize` if `N` bytes starting at `address` are within `len` bytes.
+///
+/// # Note
+///
+/// This performs a single saturating unsigned comparison in 64-bit so that
+/// out of bounds accesses near `usize::MAX` cannot wrap around.
+///
+/// # Errors
+///
+/// Traps if the access of `N` bytes at `address` is out of bounds.
+#[inline(always)]
+fn in_bounds_start<const N: usize>(len: usize, address: u64) -> Result<usize, TrapCode> {
+    if address.saturating_add(N as u64) > len as u64 {
+        return Err(TrapCode::MemoryOutOfBounds);
+    }
+    // Note: The cast is lossless since `address` is smaller than `len`.
+    Ok(address as usize)
+}
+
 /// Types that can be converted from and to little endian bytes.
 pub trait LittleEndianConvert {
     /// The little endian bytes representation.
@@ -248,16 +260,10 @@ pub trait Float<T>: ArithmeticOps<T> {
     fn ceil(self) -> T;
     /// Returns the integer part of a number.
     fn trunc(self) -> T;
-    /// Returns the nearest integer to a number. Round half-way cases away from 0.0.
-    fn round(self) -> T;
     /// Returns the nearest integer to a number. Ties are round to even number.
     fn nearest(self) -> T;
     /// Takes the square root of a number.
     fn sqrt(self) -> T;
-    /// Returns `true` if the sign of the number is positive.
-    fn is_sign_positive(self) -> bool;
-    /// Returns `true` if the sign of the number is negative.
-    fn is_sign_negative(self) -> bool;
     /// Returns the division of the two numbers.
     fn div(self, other: T) -> T;
     /// Returns the minimum of the two numbers.
@@ -302,8 +308,18 @@ impl_wrap_into!(F32, F32);
 impl_wrap_into!(F64, F64);
 
 impl WrapInto<F32> for F64 {
+    /// Demotes `self` to a [`F32`].
+    ///
+    /// # Note
+    ///
+    /// NaN inputs always yield the canonical quiet NaN with the sign of `self`
+    /// so that the result does not depend on the NaN handling of the host FPU.
     #[inline]
     fn wrap_into(self) -> F32 {
+        if self.is_nan() {
+            let sign = ((self.to_bits() >> 32) as u32) & F32_SIGN_BIT;
+            return F32::from_bits(sign | F32_CANONICAL_NAN);
+        }
         (f64::from(self) as f32).into()
     }
 }
@@ -419,12 +435,141 @@ impl_extend_into!(F32, F32);
 impl_extend_into!(F64, F64);
 
 impl ExtendInto<F64> for F32 {
+    /// Promotes `self` to a [`F64`].
+    ///
+    /// # Note
+    ///
+    /// NaN inputs always yield the canonical quiet NaN with the sign of `self`
+    /// so that the result does not depend on the NaN handling of the host FPU.
     #[inline]
     fn extend_into(self) -> F64 {
+        if self.is_nan() {
+            let sign = u64::from(self.to_bits() & F32_SIGN_BIT) << 32;
+            return F64::from_bits(sign | F64_CANONICAL_NAN);
+        }
         F64::from(f64::from(f32::from(self)))
     }
 }
 
+/// The sign bit of a [`F32`].
+const F32_SIGN_BIT: u32 = 0x8000_0000;
+
+/// The bits of the positive canonical quiet NaN of a [`F32`].
+const F32_CANONICAL_NAN: u32 = 0x7FC0_0000;
+
+/// The bits of the positive canonical quiet NaN of a [`F64`].
+const F64_CANONICAL_NAN: u64 = 0x7FF8_0000_0000_0000;
+
+/// NaN payloads of [`F64`] values used to test NaN canonicalization.
+#[cfg(test)]
+const F64_NAN_CORPUS: &[u64] = &[
+    0x7FF8_0000_0000_0000, // canonical quiet NaN
+    0x7FF0_0000_0000_0001, // smallest signaling NaN
+    0x7FF4_0000_0000_0000, // signaling NaN with the highest payload bit
+    0x7FF7_FFFF_FFFF_FFFF, // largest signaling NaN
+    0x7FF8_0000_0000_0001, // quiet NaN with the lowest payload bit
+    0x7FFF_FFFF_FFFF_FFFF, // largest quiet NaN
+    0x7FF0_0000_2000_0000, // signaling NaN with a payload truncated by demotion
+    0x7FF0_0000_1FFF_FFFF, // signaling NaN with a payload dropped by demotion
+    0x7FFA_AAAA_AAAA_AAAA, // alternating quiet payload
+    0x7FF5_5555_5555_5555, // alternating signaling payload
+];
+
+/// NaN payloads of [`F32`] values used to test NaN canonicalization.
+#[cfg(test)]
+const F32_NAN_CORPUS: &[u32] = &[
+    0x7FC0_0000, // canonical quiet NaN
+    0x7F80_0001, // smallest signaling NaN
+    0x7FA0_0000, // signaling NaN with the highest payload bit
+    0x7FBF_FFFF, // largest signaling NaN
+    0x7FC0_0001, // quiet NaN with the lowest payload bit
+    0x7FFF_FFFF, // largest quiet NaN
+    0x7FD5_5555, // alternating quiet payload
+    0x7FAA_AAAA, // alternating signaling payload
+];
+
+/// Demotes the `f64` `bits` like a host FPU that propagates NaN payloads, e.g. x86 SSE.
+///
+/// The quiet bit is set and the upper payload bits are retained.
+#[cfg(test)]
+fn demote_propagating_fpu(bits: u64) -> u32 {
+    let sign = ((bits >> 32) as u32) & F32_SIGN_BIT;
+    let payload = ((bits >> 29) as u32) & 0x003F_FFFF;
+    sign | F32_CANONICAL_NAN | payload
+}
+
+/// Promotes the `f32` `bits` like a host FPU that propagates NaN payloads, e.g. x86 SSE.
+///
+/// The quiet bit is set and the payload bits are retained.
+#[cfg(test)]
+fn promote_propagating_fpu(bits: u32) -> u64 {
+    let sign = u64::from(bits & F32_SIGN_BIT) << 32;
+    let payload = u64::from(bits & 0x003F_FFFF) << 29;
+    sign | F64_CANONICAL_NAN | payload
+}
+
+#[test]
+fn demote_nan_is_canonical() {
+    for &bits in F64_NAN_CORPUS {
+        for sign in [0, 1_u64 << 63] {
+            let input = F64::from_bits(sign | bits);
+            assert!(input.is_nan());
+            let result = <F64 as WrapInto<F32>>::wrap_into(input).to_bits();
+            let expected = ((sign >> 32) as u32) | F32_CANONICAL_NAN;
+            assert_eq!(result, expected, "input: {:#018X}", sign | bits);
+            // A host FPU that propagates payloads or one that always produces
+            // the positive default NaN, e.g. ARM in default NaN mode, must not
+            // influence the result.
+            let propagated = demote_propagating_fpu(sign | bits);
+            assert!(f32::from_bits(propagated).is_nan());
+            assert_eq!(result & !0x003F_FFFF, propagated & !0x003F_FFFF);
+            assert_eq!(result & !F32_SIGN_BIT, F32_CANONICAL_NAN);
+        }
+    }
+}
+
+#[test]
+fn promote_nan_is_canonical() {
+    for &bits in F32_NAN_CORPUS {
+        for sign in [0, F32_SIGN_BIT] {
+            let input = F32::from_bits(sign | bits);
+            assert!(input.is_nan());
+            let result = <F32 as ExtendInto<F64>>::extend_into(input).to_bits();
+            let expected = (u64::from(sign) << 32) | F64_CANONICAL_NAN;
+            assert_eq!(result, expected, "input: {:#010X}", sign | bits);
+            let propagated = promote_propagating_fpu(sign | bits);
+            assert!(f64::from_bits(propagated).is_nan());
+            assert_eq!(
+                result & !0x000F_FFFF_FFFF_FFFF,
+                propagated & !0x000F_FFFF_FFFF_FFFF
+            );
+            assert_eq!(result & !(1 << 63), F64_CANONICAL_NAN);
+        }
+    }
+}
+
+#[test]
+fn demote_promote_non_nan_unchanged() {
+    for value in [
+        0.0,
+        -0.0,
+        1.0,
+        -1.5,
+        f64::MIN_POSITIVE,
+        f64::MAX,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        1e-50,
+        f64::from(f32::MAX),
+        f64::from(f32::MIN_POSITIVE),
+    ] {
+        let demoted = <F64 as WrapInto<F32>>::wrap_into(F64::from(value));
+        assert_eq!(demoted.to_bits(), (value as f32).to_bits());
+        let promoted = <F32 as ExtendInto<F64>>::extend_into(demoted);
+        assert_eq!(promoted.to_bits(), f64::from(value as f32).to_bits());
+    }
+}
+
 macro_rules! impl_sign_extend_from {
     ( $( impl SignExtendFrom<$from_type:ty> for $for_type:ty; )* ) => {
         $(
@@ -667,13 +812,13 @@ impl_integer!(i64);
 impl_integer!(u64);
 
 #[cfg(feature = "std")]
-mod fmath {
+pub(crate) mod fmath {
     pub use f32;
     pub use f64;
 }
 
 #[cfg(not(feature = "std"))]
-mod fmath {
+pub(crate) mod fmath {
     pub use super::libm_adapters::{f32, f64};
 }
 
@@ -701,71 +846,24 @@ macro_rules! impl_float {
                 fmath::$fXX::trunc(<$fXX>::from(self)).into()
             }
             #[inline]
-            fn round(self) -> Self {
-                fmath::$fXX::round(<$fXX>::from(self)).into()
-            }
-            #[inline]
             fn nearest(self) -> Self {
-                let round = self.round();
-                if fmath::$fXX::fract(<$fXX>::from(self)).abs() != 0.5 {
-                    return round;
-                }
-                let rem = ::core::ops::Rem::rem(round, 2.0);
-                if rem == 1.0 {
-                    self.floor()
-                } else if rem == -1.0 {
-                    self.ceil()
-                } else {
-                    round
-                }
+                float::$fXX::nearest(<$fXX>::from(self)).into()
             }
             #[inline]
             fn sqrt(self) -> Self {
                 fmath::$fXX::sqrt(<$fXX>::from(self)).into()
             }
             #[inline]
-            fn is_sign_positive(self) -> bool {
-                <$fXX>::is_sign_positive(<$fXX>::from(self)).into()
-            }
-            #[inline]
-            fn is_sign_negative(self) -> bool {
-                <$fXX>::is_sign_negative(<$fXX>::from(self)).into()
-            }
-            #[inline]
             fn div(self, other: Self) -> Self {
                 self / other
             }
             #[inline]
             fn min(self, other: Self) -> Self {
-                // The implementation strictly adheres to the mandated behavior for the Wasm specification.
-                // Note: In other contexts this API is also known as: `nan_min`.
-                match (self.is_nan(), other.is_nan()) {
-                    (true, false) => self,
-                    (false, true) => other,
-                    _ => {
-                        // Case: Both values are NaN; OR both values are non-NaN.
-                        if other.is_sign_negative() {
-                            return other.min(self);
-                        }
-                        self.min(other)
-                    }
-                }
+                float::$fXX::min(<$fXX>::from(self), <$fXX>::from(other)).into()
             }
             #[inline]
             fn max(self, other: Self) -> Self {
-                // The implementation strictly adheres to the mandated behavior for the Wasm specification.
-                // Note: In other contexts this API is also known as: `nan_max`.
-                match (self.is_nan(), other.is_nan()) {
-                    (true, false) => self,
-                    (false, true) => other,
-                    _ => {
-                        // Case: Both values are NaN; OR both values are non-NaN.
-                        if other.is_sign_positive() {
-                            return other.max(self);
-                        }
-                        self.max(other)
-                    }
-                }
+                float::$fXX::max(<$fXX>::from(self), <$fXX>::from(other)).into()
             }
             #[inline]
             fn copysign(self, other: Self) -> Self {
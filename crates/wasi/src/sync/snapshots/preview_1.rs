@@ -77,7 +77,7 @@ macro_rules! impl_add_to_linker_for_funcs {
                             match wasi_common::snapshots::preview_1::wasi_snapshot_preview1::$fname(ctx, &memory, $($arg,)*).await {
                                 Ok(r) => Ok(<$ret>::from(r)),
                                 Err(wiggle::Trap::String(err)) => Err(wasmi::Error::new(err)),
-                                Err(wiggle::Trap::I32Exit(i)) => Err(wasmi::Error::i32_exit(i)),
+                                Err(wiggle::Trap::I32Exit(i)) => Err(wasmi::Error::exit(i)),
                             }
                         };
                         run_in_dummy_executor(result)?
@@ -9,17 +9,23 @@ use self::bench::{
 };
 use bench::bench_config;
 use core::{slice, time::Duration};
-use criterion::{criterion_group, criterion_main, Bencher, Criterion};
+use criterion::{criterion_group, criterion_main, Bencher, Criterion, Throughput};
 use wasmi::{
-    core::TrapCode,
+    core::{TrapCode, UntypedValue},
+    Caller,
     CompilationMode,
     Engine,
+    ExecutionDigest,
     Extern,
     Func,
+    FuncRef,
+    FuncType,
     Linker,
     Memory,
     Module,
     Store,
+    Table,
+    TableType,
     Value,
 };
 use wasmi_core::{Pages, ValueType, F32, F64};
@@ -47,6 +53,8 @@ criterion_group!(
         .warm_up_time(Duration::from_millis(1000));
     targets =
         bench_instantiate_wasm_kernel,
+        bench_instantiate_elem_segment,
+        bench_instantiate_host_table,
         // bench_instantiate_erc20,
         // bench_instantiate_erc721,
         // bench_instantiate_erc1155,
@@ -74,21 +82,32 @@ criterion_group! {
         bench_execute_rev_comp,
         bench_execute_regex_redux,
         bench_execute_count_until,
+        bench_execute_count_until_far,
+        bench_execute_count_until_digest,
         bench_execute_br_table,
         bench_execute_trunc_f2i,
         bench_execute_global_bump,
         bench_execute_global_const,
+        bench_execute_global_stack_pointer,
         bench_execute_factorial,
         bench_execute_recursive_ok,
         bench_execute_recursive_scan,
         bench_execute_recursive_trap,
         bench_execute_host_calls,
+        bench_execute_host_add,
+        bench_execute_host_batch,
         bench_execute_fuse,
+        bench_execute_copy_locals,
         bench_execute_divrem,
+        bench_execute_float_ops,
         bench_execute_fibonacci,
         bench_execute_recursive_is_even,
+        bench_execute_call_indirect,
+        bench_execute_call_cross_instance,
+        bench_execute_call_tiny,
         bench_execute_memory_sum,
         bench_execute_memory_fill,
+        bench_execute_memory_copy,
         bench_execute_vec_add,
 }
 
@@ -244,6 +263,71 @@ fn bench_instantiate_wasm_kernel(c: &mut Criterion) {
     });
 }
 
+fn bench_instantiate_elem_segment(c: &mut Criterion) {
+    /// The number of elements initialized by the active element segment.
+    const LEN: usize = 100_000;
+    let funcs = (0..LEN)
+        .map(|i| ["$f0", "$f1", "$f2", "$f3"][i % 4])
+        .collect::<Vec<_>>()
+        .join(" ");
+    let wat = format!(
+        r#"
+        (module
+            (table {LEN} funcref)
+            (func $f0)
+            (func $f1)
+            (func $f2)
+            (func $f3)
+            (elem (i32.const 0) func {funcs})
+        )
+    "#
+    );
+    let wasm = wat2wasm(wat.as_bytes());
+    let mut bench = |bench_id: &str, lazy: bool| {
+        let mut config = bench_config();
+        config.lazy_table_init(lazy);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let linker = <Linker<()>>::new(&engine);
+        c.bench_function(bench_id, |b| {
+            b.iter(|| {
+                let mut store = Store::new(&engine, ());
+                let _instance = linker.instantiate(&mut store, &module).unwrap();
+            })
+        });
+    };
+    bench("instantiate/elem_segment/eager", false);
+    bench("instantiate/elem_segment/lazy", true);
+}
+
+fn bench_instantiate_host_table(c: &mut Criterion) {
+    /// The number of elements of the table built by the host.
+    const LEN: u32 = 100_000;
+    let engine = Engine::new(&bench_config());
+    let mut store = Store::new(&engine, ());
+    let funcs = [
+        Func::wrap(&mut store, || 0_i32),
+        Func::wrap(&mut store, || 1_i32),
+    ];
+    let values = (0..LEN)
+        .map(|i| Value::from(FuncRef::new(funcs[i as usize % 2])))
+        .collect::<Vec<_>>();
+    let ty = TableType::new(ValueType::FuncRef, LEN, None);
+    let table = Table::new(&mut store, ty, Value::from(FuncRef::null())).unwrap();
+    c.bench_function("instantiate/host_table/set", |b| {
+        b.iter(|| {
+            for (index, value) in (0..LEN).zip(&values) {
+                table.set(&mut store, index, value.clone()).unwrap();
+            }
+        })
+    });
+    c.bench_function("instantiate/host_table/set_many", |b| {
+        b.iter(|| {
+            table.set_many(&mut store, 0, &values).unwrap();
+        })
+    });
+}
+
 #[allow(dead_code)]
 fn bench_instantiate_contract(c: &mut Criterion, name: &str, path: &str) {
     let bench_id = format!("instantiate/{name}");
@@ -560,6 +644,108 @@ fn bench_execute_count_until(c: &mut Criterion) {
     });
 }
 
+fn bench_execute_count_until_far(c: &mut Criterion) {
+    const COUNT_UNTIL: i32 = 100_000;
+    // A `br_table` with this many targets pads the loop body so that
+    // the loop back-edge can no longer be encoded as 16-bit branch offset.
+    const LEN_PADDING: usize = 40_000;
+    c.bench_function("execute/count_until_far", |b| {
+        let wat = format!(
+            r#"
+            (module
+                (func (export "count_until") (param $limit i32) (result i32)
+                    (local $counter i32)
+                    (local $pad i32)
+                    (loop $continue
+                        (block (br_table {} 0 (local.get $pad)))
+                        (br_if $continue
+                            (i32.ne
+                                (local.tee $counter
+                                    (i32.add (local.get $counter) (i32.const 1))
+                                )
+                                (local.get $limit)
+                            )
+                        )
+                    )
+                    (return (local.get $counter))
+                )
+            )
+            "#,
+            "0 ".repeat(LEN_PADDING),
+        );
+        let (mut store, instance) = load_instance_from_wat(wat.as_bytes());
+        let count_until = instance
+            .get_export(&store, "count_until")
+            .and_then(Extern::into_func)
+            .unwrap()
+            .typed::<i32, i32>(&store)
+            .unwrap();
+
+        b.iter(|| {
+            let result = count_until.call(&mut store, COUNT_UNTIL).unwrap();
+            assert_eq!(result, COUNT_UNTIL);
+        })
+    });
+}
+
+fn bench_execute_count_until_digest(c: &mut Criterion) {
+    const COUNT_UNTIL: i32 = 1_000_000;
+    let digests = [
+        ("none", ExecutionDigest::None),
+        ("primes", ExecutionDigest::InstructionPrimes),
+        ("custom", ExecutionDigest::Custom(|_instr, _registers| 1)),
+    ];
+    for (digest_id, digest) in digests {
+        let bench_id = format!("execute/count_until/digest/{digest_id}");
+        c.bench_function(&bench_id, |b| {
+            let mut config = bench_config();
+            config.execution_digest(digest);
+            let engine = Engine::new(&config);
+            let wasm = wat2wasm(include_bytes!("wat/count_until.wat"));
+            let module = Module::new(&engine, &wasm[..]).unwrap();
+            let mut store = Store::new(&engine, ());
+            let instance = Linker::new(&engine)
+                .instantiate(&mut store, &module)
+                .unwrap()
+                .start(&mut store)
+                .unwrap();
+            let count_until = instance
+                .get_typed_func::<i32, i32>(&store, "count_until")
+                .unwrap();
+
+            b.iter(|| {
+                let result = count_until.call(&mut store, COUNT_UNTIL).unwrap();
+                assert_eq!(result, COUNT_UNTIL);
+            })
+        });
+    }
+}
+
+fn bench_execute_call_tiny(c: &mut Criterion) {
+    const N: i32 = 100_000;
+    for optimization_level in [0, 2] {
+        let bench_id = format!("execute/call/tiny/opt{optimization_level}");
+        c.bench_function(&bench_id, |b| {
+            let mut config = bench_config();
+            config.optimization_level(optimization_level);
+            let engine = Engine::new(&config);
+            let wasm = wat2wasm(include_bytes!("wat/tiny_calls.wat"));
+            let module = Module::new(&engine, &wasm[..]).unwrap();
+            let mut store = Store::new(&engine, ());
+            let instance = Linker::new(&engine)
+                .instantiate(&mut store, &module)
+                .unwrap()
+                .start(&mut store)
+                .unwrap();
+            let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+
+            b.iter(|| {
+                run.call(&mut store, N).unwrap();
+            })
+        });
+    }
+}
+
 fn bench_execute_br_table(c: &mut Criterion) {
     const REPETITIONS: usize = 20_000;
     c.bench_function("execute/br_table", |b| {
@@ -775,6 +961,30 @@ fn bench_execute_global_const(c: &mut Criterion) {
     });
 }
 
+fn bench_execute_global_stack_pointer(c: &mut Criterion) {
+    const CALLS: i32 = 50_000;
+    const SUM: i32 = CALLS / 2 * (CALLS + 1);
+    c.bench_function("execute/global/stack_pointer", |b| {
+        let (mut store, instance) =
+            load_instance_from_wat(include_bytes!("wat/global_stack_pointer.wat"));
+        let call = instance
+            .get_export(&store, "call")
+            .and_then(Extern::into_func)
+            .unwrap();
+        let mut result = Value::I32(0);
+
+        b.iter(|| {
+            call.call(
+                &mut store,
+                &[Value::I32(CALLS)],
+                slice::from_mut(&mut result),
+            )
+            .unwrap();
+            assert_eq!(result.i32(), Some(SUM));
+        })
+    });
+}
+
 fn bench_execute_factorial(c: &mut Criterion) {
     const REPETITIONS: usize = 1_000;
     const INPUT: i64 = 25;
@@ -895,6 +1105,50 @@ fn bench_execute_recursive_is_even(c: &mut Criterion) {
     });
 }
 
+fn bench_execute_call_indirect(c: &mut Criterion) {
+    const REPETITIONS: i32 = 1_000_000;
+    c.bench_function("execute/call_indirect", |b| {
+        let (mut store, instance) = load_instance_from_wat(include_bytes!("wat/call_indirect.wat"));
+        let bench_call = instance
+            .get_typed_func::<i32, i32>(&store, "call_indirect")
+            .unwrap();
+        b.iter(|| {
+            let result = bench_call.call(&mut store, REPETITIONS).unwrap();
+            assert_eq!(result, REPETITIONS);
+        });
+    });
+}
+
+fn bench_execute_call_cross_instance(c: &mut Criterion) {
+    const REPETITIONS: i32 = 1_000_000;
+    c.bench_function("execute/call/cross_instance", |b| {
+        let engine = Engine::new(&bench_config());
+        let callee = wat2wasm(include_bytes!("wat/cross_instance_callee.wat"));
+        let caller = wat2wasm(include_bytes!("wat/cross_instance_caller.wat"));
+        let callee = Module::new(&engine, &callee[..]).unwrap();
+        let caller = Module::new(&engine, &caller[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        let callee = linker
+            .instantiate(&mut store, &callee)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let load_add = callee.get_func(&store, "load_add").unwrap();
+        linker.define("callee", "load_add", load_add).unwrap();
+        let caller = linker
+            .instantiate(&mut store, &caller)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let bench_call = caller.get_typed_func::<i32, i32>(&store, "call").unwrap();
+        b.iter(|| {
+            let result = bench_call.call(&mut store, REPETITIONS).unwrap();
+            assert_eq!(result, REPETITIONS);
+        });
+    });
+}
+
 /// How often the `host_call` should be called per Wasm invocation.
 const HOST_CALLS_REPETITIONS: i64 = 1000;
 
@@ -931,6 +1185,98 @@ fn bench_execute_host_calls(c: &mut Criterion) {
     });
 }
 
+/// How often the `add` host function should be called per Wasm invocation.
+const HOST_ADD_REPETITIONS: i32 = 1000;
+
+fn bench_execute_host_add(c: &mut Criterion) {
+    /// Raw host function adding its two `i32` parameters.
+    ///
+    /// # Safety
+    ///
+    /// Must be called with a buffer holding 2 `i32` parameters.
+    unsafe fn raw_add(params_results: *mut UntypedValue, _len: usize) -> Result<(), TrapCode> {
+        let lhs = i32::from(unsafe { *params_results });
+        let rhs = i32::from(unsafe { *params_results.add(1) });
+        unsafe { *params_results = UntypedValue::from(lhs.wrapping_add(rhs)) };
+        Ok(())
+    }
+
+    let mut bench_add = |bench_id: &str, define: fn(&mut Linker<()>)| {
+        c.bench_function(bench_id, |b| {
+            let wasm = wat2wasm(include_bytes!("wat/host_add.wat"));
+            let engine = Engine::default();
+            let module = Module::new(&engine, &wasm[..]).unwrap();
+            let mut linker = <Linker<()>>::new(&engine);
+            let mut store = Store::new(&engine, ());
+            define(&mut linker);
+            let call = linker
+                .instantiate(&mut store, &module)
+                .unwrap()
+                .ensure_no_start(&mut store)
+                .unwrap()
+                .get_typed_func::<i32, i32>(&store, "call")
+                .unwrap();
+            let expected = (1..=HOST_ADD_REPETITIONS).sum::<i32>();
+            b.iter(|| {
+                let result = call.call(&mut store, HOST_ADD_REPETITIONS).unwrap();
+                assert_eq!(result, expected);
+            })
+        });
+    };
+    bench_add("execute/call/host/add/wrap", |linker| {
+        linker
+            .func_wrap("benchmark", "add", |lhs: i32, rhs: i32| lhs.wrapping_add(rhs))
+            .unwrap();
+    });
+    bench_add("execute/call/host/add/raw", |linker| {
+        let ty = FuncType::new([ValueType::I32; 2], [ValueType::I32]);
+        // Safety: `raw_add` upholds the safety contract for its function type.
+        unsafe { linker.func_wrap_raw("benchmark", "add", ty, raw_add) }.unwrap();
+    });
+}
+
+/// How many sample points are logged via host calls per Wasm invocation.
+const HOST_BATCH_REPETITIONS: i32 = 1000;
+
+fn bench_execute_host_batch(c: &mut Criterion) {
+    let mut bench_batch = |bench_id: &str, func_name: &str| {
+        c.bench_function(bench_id, |b| {
+            let wasm = wat2wasm(include_bytes!("wat/host_batch.wat"));
+            let mut config = bench_config();
+            config.builtin_batch_call(true);
+            let engine = Engine::new(&config);
+            let module = Module::new(&engine, &wasm[..]).unwrap();
+            let mut linker = <Linker<i64>>::new(&engine);
+            let mut store = Store::new(&engine, 0);
+            linker.define_builtins().unwrap();
+            linker
+                .func_wrap(
+                    "benchmark",
+                    "sample",
+                    |mut caller: Caller<i64>, value: i32| {
+                        *caller.data_mut() += i64::from(value);
+                    },
+                )
+                .unwrap();
+            let call = linker
+                .instantiate(&mut store, &module)
+                .unwrap()
+                .ensure_no_start(&mut store)
+                .unwrap()
+                .get_typed_func::<i32, ()>(&store, func_name)
+                .unwrap();
+            let expected = (1..=i64::from(HOST_BATCH_REPETITIONS)).sum::<i64>();
+            b.iter(|| {
+                *store.data_mut() = 0;
+                call.call(&mut store, HOST_BATCH_REPETITIONS).unwrap();
+                assert_eq!(*store.data(), expected);
+            })
+        });
+    };
+    bench_batch("execute/call/host/batch/individual", "individual");
+    bench_batch("execute/call/host/batch/batched", "batched");
+}
+
 fn bench_execute_fuse(c: &mut Criterion) {
     let (mut store, instance) = load_instance_from_wat(include_bytes!("wat/fuse.wat"));
     let mut bench_fuse = |bench_id: &str, func_name: &str, input: i32| {
@@ -949,6 +1295,21 @@ fn bench_execute_fuse(c: &mut Criterion) {
     bench_fuse("execute/fuse", "test", 1_000_000);
 }
 
+fn bench_execute_copy_locals(c: &mut Criterion) {
+    let (mut store, instance) = load_instance_from_wat(include_bytes!("wat/copy_locals.wat"));
+    c.bench_function("execute/copy_locals", |b| {
+        let test = instance
+            .get_export(&store, "test")
+            .and_then(Extern::into_func)
+            .unwrap()
+            .typed::<i32, i32>(&store)
+            .unwrap();
+        b.iter(|| {
+            assert_eq!(test.call(&mut store, 100_000).unwrap(), 0);
+        });
+    });
+}
+
 fn bench_execute_divrem(c: &mut Criterion) {
     let (mut store, instance) = load_instance_from_wat(include_bytes!("wat/divrem.wat"));
     let mut bench_fuse = |bench_id: &str, func_name: &str, input: i32| {
@@ -967,6 +1328,21 @@ fn bench_execute_divrem(c: &mut Criterion) {
     bench_fuse("execute/divrem", "test", 250_000);
 }
 
+fn bench_execute_float_ops(c: &mut Criterion) {
+    let (mut store, instance) = load_instance_from_wat(include_bytes!("wat/float_ops.wat"));
+    c.bench_function("execute/float_ops", |b| {
+        let test = instance
+            .get_export(&store, "test")
+            .and_then(Extern::into_func)
+            .unwrap()
+            .typed::<i32, i32>(&store)
+            .unwrap();
+        b.iter(|| {
+            assert_eq!(test.call(&mut store, 250_000).unwrap(), 0);
+        });
+    });
+}
+
 fn bench_execute_fibonacci(c: &mut Criterion) {
     const fn fib(n: i64) -> i64 {
         if n <= 1 {
@@ -1069,6 +1445,45 @@ fn bench_execute_memory_fill(c: &mut Criterion) {
     });
 }
 
+fn bench_execute_memory_copy(c: &mut Criterion) {
+    /// The number of bytes copied per iteration.
+    const LEN: usize = 16 * 1024 * 1024;
+    let mut g = c.benchmark_group("execute/memory/copy_bytes");
+    g.throughput(Throughput::Bytes(LEN as u64));
+    let mut bench_copy = |name: &str, dst: usize, src: usize| {
+        g.bench_function(name, |b| {
+            let (mut store, instance) =
+                load_instance_from_wat(include_bytes!("wat/memory-copy.wat"));
+            let copy = instance
+                .get_typed_func::<(u32, u32, u32), ()>(&store, "copy_bytes")
+                .unwrap();
+            let mem = instance
+                .get_export(&store, "mem")
+                .and_then(Extern::into_memory)
+                .unwrap();
+            // Grow the linear memory to hold two disjoint regions of `LEN` bytes.
+            mem.grow(&mut store, Pages::new(512).unwrap()).unwrap();
+            for (n, byte) in mem.data_mut(&mut store)[src..(src + LEN)]
+                .iter_mut()
+                .enumerate()
+            {
+                *byte = (n % 256) as u8;
+            }
+            b.iter(|| {
+                copy.call(&mut store, (dst as u32, src as u32, LEN as u32))
+                    .unwrap();
+            });
+            assert!(mem.data(&store)[dst..(dst + LEN)]
+                .iter()
+                .enumerate()
+                .all(|(n, byte)| *byte == (n % 256) as u8));
+        });
+    };
+    bench_copy("disjoint", LEN, 0);
+    bench_copy("overlap/forward", 0x100, 0);
+    bench_copy("overlap/backward", 0, 0x100);
+}
+
 fn bench_execute_vec_add(c: &mut Criterion) {
     fn test_for<A, B>(
         b: &mut Bencher,
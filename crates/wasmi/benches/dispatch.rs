@@ -0,0 +1,305 @@
+//! Microbenchmarks for the execution of isolated classes of Wasmi IR instructions.
+//!
+//! Each benchmark executes a loop whose body repeats a snippet of Wasm instructions
+//! of a single instruction class [`UNROLL`] times so that the loop overhead is negligible.
+//! The `e2e/*` benchmarks execute a realistic mix of instructions.
+//!
+//! # Instruction Mix Report
+//!
+//! The throughput of all benchmarks is reported in executed instructions per second.
+//! The number of executed instructions is taken from the fuel consumed by a single
+//! run of a benchmark with fuel metering enabled since Wasmi charges the base fuel
+//! costs for every executed Wasmi IR instruction. The benchmarks themselves are
+//! executed without fuel metering.
+//!
+//! Note that the number is an approximation since instructions that copy data,
+//! such as `memory.copy`, are additionally charged for the number of copied bytes.
+//!
+//! # Comparing Two Branches
+//!
+//! Criterion stores the results of a benchmark run as named baseline which can
+//! then be compared against the results of another branch:
+//!
+//! ```text
+//! git checkout main
+//! cargo bench --bench dispatch -- --save-baseline main
+//! git checkout my-branch
+//! cargo bench --bench dispatch -- --baseline main
+//! ```
+//!
+//! The second run reports the change in time and instructions per second for
+//! every benchmark. Append a filter such as `dispatch/call/` in order to only
+//! run the benchmarks of a single instruction class.
+//!
+//! # Call Chains
+//!
+//! The `dispatch/call/forward-chain` benchmark executes call chains in which every
+//! caller returns the results of its callee right after the call. It is translated
+//! with optimizations enabled so that those results are returned directly to the
+//! outermost caller without being copied in between.
+//!
+//! # Spectre Mitigations
+//!
+//! The `dispatch/spectre/*` benchmarks execute the instruction classes affected by
+//! [`Config::spectre_mitigations`] with the mitigations enabled. Compare them with
+//! their `dispatch/*` counterparts in order to quantify the cost of the mitigations.
+
+use core::time::Duration;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use wasmi::{Config, Engine, Linker, Module, Store, TypedFunc};
+
+criterion_group!(
+    name = bench_dispatch;
+    config = Criterion::default()
+        .sample_size(10)
+        .measurement_time(Duration::from_millis(2000))
+        .warm_up_time(Duration::from_millis(1000));
+    targets = bench_dispatch_classes,
+);
+criterion_main!(bench_dispatch);
+
+/// The number of times the snippet of an instruction class is repeated per loop iteration.
+const UNROLL: usize = 16;
+
+/// A benchmark executing the `run` function of type `(i32) -> i32` of its Wasm module.
+struct Case {
+    /// The name of the benchmark.
+    name: &'static str,
+    /// The Wasm module of the benchmark.
+    wasm: Vec<u8>,
+    /// The input given to the `run` function.
+    input: i32,
+    /// Whether the [`Case`] is affected by [`Config::spectre_mitigations`].
+    spectre: bool,
+    /// The [`Config::optimization_level`] used to translate the [`Case`].
+    optimization_level: u8,
+}
+
+impl Case {
+    /// Creates a [`Case`] that executes `snippet` [`UNROLL`] times in a loop of `input` iterations.
+    ///
+    /// The `snippet` may use the `i32` locals `$a`, `$b`, `$c` and `$zero` which are
+    /// initialized to `1`, `2`, `16` and `0` respectively, the function `$id` with its
+    /// type `$id`, the imported host function `$host`, the linear memory and the table.
+    fn snippet(name: &'static str, input: i32, snippet: &str) -> Self {
+        let body = snippet.repeat(UNROLL);
+        let wat = format!(
+            r#"
+            (module
+                (import "env" "host" (func $host (param i32) (result i32)))
+                (type $id (func (param i32) (result i32)))
+                (memory 1)
+                (table 2 funcref)
+                (elem (i32.const 0) $id)
+                (func $id (param i32) (result i32)
+                    (local.get 0)
+                )
+                (func (export "run") (param $n i32) (result i32)
+                    (local $a i32) (local $b i32) (local $c i32) (local $zero i32)
+                    (local.set $a (i32.const 1))
+                    (local.set $b (i32.const 2))
+                    (local.set $c (i32.const 16))
+                    (block $exit
+                        (loop $continue
+                            (br_if $exit (i32.eqz (local.get $n)))
+                            {body}
+                            (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                            (br $continue)
+                        )
+                    )
+                    (i32.add (local.get $a) (local.get $b))
+                )
+            )
+            "#
+        );
+        Self::wat(name, input, &wat)
+    }
+
+    /// Creates a [`Case`] from the `wat` source.
+    fn wat(name: &'static str, input: i32, wat: &str) -> Self {
+        let wasm = wat::parse_str(wat).unwrap();
+        Self {
+            name,
+            wasm,
+            input,
+            spectre: false,
+            optimization_level: 0,
+        }
+    }
+
+    /// Marks the [`Case`] as affected by [`Config::spectre_mitigations`].
+    fn spectre(mut self) -> Self {
+        self.spectre = true;
+        self
+    }
+
+    /// Translates the [`Case`] with [`Config::optimization_level`] `1`.
+    fn optimized(mut self) -> Self {
+        self.optimization_level = 1;
+        self
+    }
+
+    /// Instantiates the [`Case`] and returns its [`Store`] and `run` function.
+    ///
+    /// Fuel metering is enabled if `fuel` is `true`.
+    /// Spectre mitigations are enabled if `spectre` is `true`.
+    fn instantiate(&self, fuel: bool, spectre: bool) -> (Store<()>, TypedFunc<i32, i32>) {
+        let mut config = Config::default();
+        config.consume_fuel(fuel);
+        config.spectre_mitigations(spectre);
+        config.optimization_level(self.optimization_level);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &self.wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        if fuel {
+            store.add_fuel(u64::MAX).unwrap();
+        }
+        let mut linker = <Linker<()>>::new(&engine);
+        linker.func_wrap("env", "host", |input: i32| input).unwrap();
+        let run = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap()
+            .get_typed_func::<i32, i32>(&store, "run")
+            .unwrap();
+        (store, run)
+    }
+
+    /// Returns the number of instructions executed by a single run of the [`Case`].
+    ///
+    /// This uses the fuel consumed with fuel metering enabled as instruction counter.
+    fn executed_instrs(&self) -> u64 {
+        let (mut store, run) = self.instantiate(true, false);
+        run.call(&mut store, self.input).unwrap();
+        store.fuel_consumed().unwrap()
+    }
+}
+
+/// Returns all [`Case`] of the benchmark suite.
+fn cases() -> Vec<Case> {
+    vec![
+        Case::snippet(
+            "alu/reg-reg",
+            10_000,
+            "(local.set $a (i32.add (local.get $a) (local.get $b)))
+             (local.set $b (i32.xor (local.get $b) (local.get $a)))",
+        ),
+        Case::snippet(
+            "alu/imm16",
+            10_000,
+            "(local.set $a (i32.add (local.get $a) (i32.const 7)))
+             (local.set $b (i32.mul (local.get $b) (i32.const 3)))",
+        ),
+        Case::snippet(
+            "load/offset16",
+            10_000,
+            "(local.set $a (i32.load offset=8 (local.get $c)))",
+        )
+        .spectre(),
+        Case::snippet(
+            "store/at",
+            10_000,
+            "(i32.store (i32.const 64) (local.get $a))",
+        )
+        .spectre(),
+        Case::snippet("branch/taken", 10_000, "(block (br_if 0 (local.get $b)))"),
+        Case::snippet(
+            "branch/not-taken",
+            10_000,
+            "(block (br_if 0 (local.get $zero)))",
+        ),
+        Case::snippet(
+            "call/internal",
+            1_000,
+            "(local.set $a (call $id (local.get $a)))",
+        ),
+        Case::snippet(
+            "call/imported",
+            1_000,
+            "(local.set $a (call $host (local.get $a)))",
+        ),
+        Case::snippet(
+            "call/indirect",
+            1_000,
+            "(local.set $a (call_indirect (type $id) (local.get $a) (i32.const 0)))",
+        )
+        .spectre(),
+        Case::wat(
+            "call/forward-chain",
+            1_000,
+            r#"
+            (module
+                (func $c (param i32) (result i32)
+                    (i32.add (local.get 0) (i32.const 1))
+                )
+                (func $b (param i32) (result i32)
+                    (call $c (local.get 0))
+                )
+                (func $a (param i32) (result i32)
+                    (call $b (local.get 0))
+                )
+                (func (export "run") (param $n i32) (result i32)
+                    (local $a i32)
+                    (block $exit
+                        (loop $continue
+                            (br_if $exit (i32.eqz (local.get $n)))
+                            (local.set $a (call $a (local.get $a)))
+                            (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                            (br $continue)
+                        )
+                    )
+                    (local.get $a)
+                )
+            )
+            "#,
+        )
+        .optimized(),
+        Case::snippet(
+            "memory.copy/16",
+            1_000,
+            "(memory.copy (i32.const 8192) (i32.const 0) (i32.const 16))",
+        ),
+        Case::snippet(
+            "memory.copy/256",
+            1_000,
+            "(memory.copy (i32.const 8192) (i32.const 0) (i32.const 256))",
+        ),
+        Case::snippet(
+            "memory.copy/4096",
+            100,
+            "(memory.copy (i32.const 8192) (i32.const 0) (i32.const 4096))",
+        ),
+        Case::snippet(
+            "table/get-set-size",
+            1_000,
+            "(table.set (i32.const 1) (table.get (i32.const 0)))
+             (local.set $a (i32.add (local.get $a) (table.size)))",
+        )
+        .spectre(),
+        Case::wat(
+            "e2e/sort_checksum",
+            4,
+            include_str!("wat/sort_checksum.wat"),
+        )
+        .spectre(),
+    ]
+}
+
+fn bench_dispatch_classes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dispatch");
+    for case in cases() {
+        group.throughput(Throughput::Elements(case.executed_instrs()));
+        let (mut store, run) = case.instantiate(false, false);
+        group.bench_function(case.name, |b| {
+            b.iter(|| run.call(&mut store, case.input).unwrap());
+        });
+        if case.spectre {
+            let (mut store, run) = case.instantiate(false, true);
+            group.bench_function(format!("spectre/{}", case.name), |b| {
+                b.iter(|| run.call(&mut store, case.input).unwrap());
+            });
+        }
+    }
+    group.finish();
+}
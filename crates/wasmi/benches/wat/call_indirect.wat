@@ -0,0 +1,29 @@
+(module
+    (type $binop (func (param i32 i32) (result i32)))
+    (table 2 funcref)
+    (elem (i32.const 0) func $add $sub)
+    (func $add (param i32 i32) (result i32)
+        (i32.add (local.get 0) (local.get 1))
+    )
+    (func $sub (param i32 i32) (result i32)
+        (i32.sub (local.get 0) (local.get 1))
+    )
+    (func (export "call_indirect") (param $n i32) (result i32)
+        (local $acc i32)
+        (block $exit
+            (loop $continue
+                (br_if $exit (i32.eqz (local.get $n)))
+                (local.set $acc
+                    (call_indirect (type $binop)
+                        (local.get $acc)
+                        (i32.const 1)
+                        (i32.const 0)
+                    )
+                )
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br $continue)
+            )
+        )
+        (local.get $acc)
+    )
+)
@@ -0,0 +1,65 @@
+(module
+  (func (export "test") (param $n i32) (result i32)
+    (local i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32 i32)
+    ;; Locals 1 to 16 are called `a` and locals 17 to 32 are called `b`.
+    ;; Initialize a[i] = i
+    (local.set 1 (i32.const 0))
+    (local.set 2 (i32.const 1))
+    (local.set 3 (i32.const 2))
+    (local.set 4 (i32.const 3))
+    (local.set 5 (i32.const 4))
+    (local.set 6 (i32.const 5))
+    (local.set 7 (i32.const 6))
+    (local.set 8 (i32.const 7))
+    (local.set 9 (i32.const 8))
+    (local.set 10 (i32.const 9))
+    (local.set 11 (i32.const 10))
+    (local.set 12 (i32.const 11))
+    (local.set 13 (i32.const 12))
+    (local.set 14 (i32.const 13))
+    (local.set 15 (i32.const 14))
+    (local.set 16 (i32.const 15))
+    (loop $continue
+        ;; b[i] = a[i]
+        (local.set 17 (local.get 1))
+        (local.set 18 (local.get 2))
+        (local.set 19 (local.get 3))
+        (local.set 20 (local.get 4))
+        (local.set 21 (local.get 5))
+        (local.set 22 (local.get 6))
+        (local.set 23 (local.get 7))
+        (local.set 24 (local.get 8))
+        (local.set 25 (local.get 9))
+        (local.set 26 (local.get 10))
+        (local.set 27 (local.get 11))
+        (local.set 28 (local.get 12))
+        (local.set 29 (local.get 13))
+        (local.set 30 (local.get 14))
+        (local.set 31 (local.get 15))
+        (local.set 32 (local.get 16))
+        ;; a[i] = b[(i + 1) % 16]
+        (local.set 1 (local.get 18))
+        (local.set 2 (local.get 19))
+        (local.set 3 (local.get 20))
+        (local.set 4 (local.get 21))
+        (local.set 5 (local.get 22))
+        (local.set 6 (local.get 23))
+        (local.set 7 (local.get 24))
+        (local.set 8 (local.get 25))
+        (local.set 9 (local.get 26))
+        (local.set 10 (local.get 27))
+        (local.set 11 (local.get 28))
+        (local.set 12 (local.get 29))
+        (local.set 13 (local.get 30))
+        (local.set 14 (local.get 31))
+        (local.set 15 (local.get 32))
+        (local.set 16 (local.get 17))
+        ;; n -= 1
+        (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+        ;; continue if $n != 0
+        (br_if $continue (local.get $n))
+    )
+    ;; a[0] is equal to the original input modulo 16
+    (return (local.get 1))
+  )
+)
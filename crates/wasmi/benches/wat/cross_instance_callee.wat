@@ -0,0 +1,7 @@
+(module
+    (memory 1)
+    (data (i32.const 0) "\01\00\00\00")
+    (func (export "load_add") (param i32) (result i32)
+        (i32.add (local.get 0) (i32.load (i32.const 0)))
+    )
+)
@@ -0,0 +1,27 @@
+(module
+    (type $unop (func (param i32) (result i32)))
+    (import "callee" "load_add" (func $load_add (type $unop)))
+    (memory 1)
+    (table 1 funcref)
+    (elem (i32.const 0) func $load_add)
+    (func (export "call") (param $n i32) (result i32)
+        (local $acc i32)
+        (block $exit
+            (loop $continue
+                (br_if $exit (i32.eqz (local.get $n)))
+                ;; Alternate between both instances by accessing
+                ;; the linear memory before and after each call.
+                (i32.store (i32.const 0) (local.get $acc))
+                (local.set $acc
+                    (call_indirect (type $unop)
+                        (i32.load (i32.const 0))
+                        (i32.const 0)
+                    )
+                )
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br $continue)
+            )
+        )
+        (local.get $acc)
+    )
+)
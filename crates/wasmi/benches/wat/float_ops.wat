@@ -0,0 +1,53 @@
+(module
+  (func (export "test") (param $n i32) (result i32)
+    (local $x32 f32)
+    (local $y32 f32)
+    (local $x64 f64)
+    (local $y64 f64)
+    (loop $continue
+        ;; n -= 1
+        (local.set $n
+            (i32.sub
+                (local.get $n)
+                (i32.const 1)
+            )
+        )
+        ;; x = pseudo random value with varying sign derived from n
+        (local.set $x64
+            (f64.div
+                (f64.convert_i32_s
+                    (i32.mul (local.get $n) (i32.const 0x9E3779B1))
+                )
+                (f64.const 65536.0)
+            )
+        )
+        (local.set $x32 (f32.demote_f64 (local.get $x64)))
+        ;; y = nearest(clamp(x, y - 1000, y + 1000))
+        (local.set $y32
+            (f32.nearest
+                (f32.max
+                    (f32.min
+                        (local.get $x32)
+                        (f32.add (local.get $y32) (f32.const 1000.0))
+                    )
+                    (f32.sub (local.get $y32) (f32.const 1000.0))
+                )
+            )
+        )
+        (local.set $y64
+            (f64.nearest
+                (f64.max
+                    (f64.min
+                        (local.get $x64)
+                        (f64.add (local.get $y64) (f64.const 1000.0))
+                    )
+                    (f64.sub (local.get $y64) (f64.const 1000.0))
+                )
+            )
+        )
+        ;; continue if $n != 0
+        (br_if $continue (local.get $n))
+    )
+    (return (local.get $n))
+  )
+)
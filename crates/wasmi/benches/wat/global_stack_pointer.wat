@@ -0,0 +1,39 @@
+;; Exports a function `call` that takes an input `n`.
+;; The exported function mimics the prologue and epilogue of `n` calls of code
+;; compiled from C that allocates its stack frame via the `__stack_pointer`
+;; global variable and updates another global variable within its body.
+;; Returns the sum of the values stored to the stack frames.
+(module
+    (memory 1)
+    (global $__stack_pointer (mut i32) (i32.const 65536))
+    (global $sum (mut i32) (i32.const 0))
+    (func (export "call") (param $n i32) (result i32)
+        (local $frame i32)
+        (global.set $sum (i32.const 0))
+        (block $break
+            (loop $continue
+                (br_if $break (i32.eqz (local.get $n)))
+                ;; prologue: allocate a 16 bytes stack frame
+                (global.set $__stack_pointer
+                    (local.tee $frame
+                        (i32.sub (global.get $__stack_pointer) (i32.const 16))
+                    )
+                )
+                (i32.store offset=8 (local.get $frame) (local.get $n))
+                (global.set $sum
+                    (i32.add
+                        (global.get $sum)
+                        (i32.load offset=8 (global.get $__stack_pointer))
+                    )
+                )
+                ;; epilogue: deallocate the stack frame
+                (global.set $__stack_pointer
+                    (i32.add (global.get $__stack_pointer) (i32.const 16))
+                )
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br $continue)
+            )
+        )
+        (global.get $sum)
+    )
+)
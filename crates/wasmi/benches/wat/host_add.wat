@@ -0,0 +1,22 @@
+;; The below `.wat` file exports a function `call` that takes a `n` of type `i32`.
+;; It will iterate `n` times and call the imported function `add` every time
+;; in order to sum up all numbers from `n` down to `1`.
+;;
+;; This benchmarks tests the overhead of tiny host calls.
+;;
+;; After successful execution the `call` function will return the sum.
+(module
+    (import "benchmark" "add" (func $add (param i32 i32) (result i32)))
+    (func (export "call") (param $n i32) (result i32)
+        (local $acc i32)
+        (block $exit
+            (loop $continue
+                (br_if $exit (i32.eqz (local.get $n)))
+                (local.set $acc (call $add (local.get $acc) (local.get $n)))
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br $continue)
+            )
+        )
+        (local.get $acc)
+    )
+)
@@ -0,0 +1,40 @@
+;; The below `.wat` file exports the functions `individual` and `batched` that take a `n` of type `i32`.
+;; Both of them log the sample points `n` down to `1` via the imported function `sample`.
+;;
+;; - `individual` calls `sample` once per sample point.
+;; - `batched` writes a record per sample point into its linear memory and
+;;   dispatches all of them at once via the `wasmi.batch_call` builtin function.
+;;
+;; This benchmarks tests the overhead of tiny host calls with and without batching.
+(module
+    (import "benchmark" "sample" (func $sample (param i32)))
+    (import "wasmi" "batch_call" (func $batch_call (param i32 i32 i32)))
+    (memory 1)
+    (func (export "individual") (param $n i32)
+        (block $exit
+            (loop $continue
+                (br_if $exit (i32.eqz (local.get $n)))
+                (call $sample (local.get $n))
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br $continue)
+            )
+        )
+    )
+    (func (export "batched") (param $n i32)
+        (local $len i32)
+        (local $ptr i32)
+        (local.set $len (local.get $n))
+        (block $exit
+            (loop $continue
+                (br_if $exit (i32.eqz (local.get $n)))
+                ;; Each record consists of the import index of `sample` and its parameter.
+                (i32.store (local.get $ptr) (i32.const 0))
+                (i64.store offset=4 (local.get $ptr) (i64.extend_i32_u (local.get $n)))
+                (local.set $ptr (i32.add (local.get $ptr) (i32.const 12)))
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br $continue)
+            )
+        )
+        (call $batch_call (i32.const 0) (local.get $len) (local.get $ptr))
+    )
+)
@@ -0,0 +1,16 @@
+;; Exports a function `copy_bytes` that copies `len` bytes
+;; of the linear memory from `src` to `dst` via `memory.copy`.
+;;
+;; # Note
+;;
+;; The source and destination ranges are allowed to overlap.
+(module
+    (memory (export "mem") 1)
+    (func (export "copy_bytes") (param $dst i32) (param $src i32) (param $len i32)
+        (memory.copy
+            (local.get $dst)
+            (local.get $src)
+            (local.get $len)
+        )
+    )
+)
@@ -0,0 +1,101 @@
+;; Exports a function `run` that `n` times fills an array of 256 `i32` values
+;; with pseudo random numbers, sorts them via insertion sort and folds them
+;; into a checksum.
+;;
+;; This benchmark mixes arithmetic, loads, stores, branches and calls
+;; similar to what real world Wasm programs execute.
+;;
+;; After successful execution `run` returns the final checksum.
+(module
+    (memory 1)
+    (global $seed (mut i32) (i32.const 42))
+    (func $next_random (result i32)
+        ;; Linear congruential generator with the constants of Numerical Recipes.
+        (global.set $seed
+            (i32.add
+                (i32.mul (global.get $seed) (i32.const 1664525))
+                (i32.const 1013904223)
+            )
+        )
+        (i32.shr_u (global.get $seed) (i32.const 8))
+    )
+    (func $fill (param $len i32)
+        (local $i i32)
+        (block $exit
+            (loop $continue
+                (br_if $exit (i32.ge_u (local.get $i) (local.get $len)))
+                (i32.store
+                    (i32.shl (local.get $i) (i32.const 2))
+                    (call $next_random)
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $continue)
+            )
+        )
+    )
+    (func $sort (param $len i32)
+        (local $i i32)
+        (local $j i32)
+        (local $key i32)
+        (local $prev i32)
+        (local.set $i (i32.const 1))
+        (block $exit
+            (loop $outer
+                (br_if $exit (i32.ge_u (local.get $i) (local.get $len)))
+                (local.set $key (i32.load (i32.shl (local.get $i) (i32.const 2))))
+                (local.set $j (local.get $i))
+                (block $placed
+                    (loop $inner
+                        (br_if $placed (i32.eqz (local.get $j)))
+                        (local.set $prev
+                            (i32.load offset=0
+                                (i32.shl (i32.sub (local.get $j) (i32.const 1)) (i32.const 2))
+                            )
+                        )
+                        (br_if $placed (i32.le_u (local.get $prev) (local.get $key)))
+                        (i32.store (i32.shl (local.get $j) (i32.const 2)) (local.get $prev))
+                        (local.set $j (i32.sub (local.get $j) (i32.const 1)))
+                        (br $inner)
+                    )
+                )
+                (i32.store (i32.shl (local.get $j) (i32.const 2)) (local.get $key))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $outer)
+            )
+        )
+    )
+    (func $checksum (param $len i32) (result i32)
+        (local $i i32)
+        (local $sum i32)
+        (block $exit
+            (loop $continue
+                (br_if $exit (i32.ge_u (local.get $i) (local.get $len)))
+                (local.set $sum
+                    (i32.xor
+                        (i32.rotl (local.get $sum) (i32.const 5))
+                        (i32.load (i32.shl (local.get $i) (i32.const 2)))
+                    )
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $continue)
+            )
+        )
+        (local.get $sum)
+    )
+    (func (export "run") (param $n i32) (result i32)
+        (local $checksum i32)
+        (block $exit
+            (loop $continue
+                (br_if $exit (i32.eqz (local.get $n)))
+                (call $fill (i32.const 256))
+                (call $sort (i32.const 256))
+                (local.set $checksum
+                    (i32.add (local.get $checksum) (call $checksum (i32.const 256)))
+                )
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br $continue)
+            )
+        )
+        (local.get $checksum)
+    )
+)
@@ -0,0 +1,27 @@
+;; Exports a function `run` that takes an input `n`.
+;; The exported function hashes the numbers below `n` via calls to tiny helper functions.
+(module
+    (func $inc (param $x i32) (result i32)
+        (i32.add (local.get $x) (i32.const 1))
+    )
+    (func $mix (param $hash i32) (param $x i32) (result i32)
+        (i32.xor (i32.mul (local.get $hash) (i32.const 31)) (local.get $x))
+    )
+    (func $rotl (param $x i32) (result i32)
+        (i32.rotl (local.get $x) (i32.const 5))
+    )
+    (func (export "run") (param $n i32) (result i32)
+        (local $i i32)
+        (local $hash i32)
+        (block $break
+            (loop $continue
+                (br_if $break (i32.eq (local.get $i) (local.get $n)))
+                (local.set $hash (call $mix (local.get $hash) (local.get $i)))
+                (local.set $hash (call $rotl (local.get $hash)))
+                (local.set $i (call $inc (local.get $i)))
+                (br $continue)
+            )
+        )
+        (local.get $hash)
+    )
+)
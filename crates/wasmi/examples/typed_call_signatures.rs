@@ -0,0 +1,89 @@
+//! Calls host functions of 256 distinct signatures via [`TypedFunc::call`].
+//!
+//! This is used to measure the binary size impact of monomorphizing
+//! [`TypedFunc::call`] for many signatures:
+//!
+//! ```text
+//! cargo build --release --example typed_call_signatures
+//! ls -l target/release/examples/typed_call_signatures
+//! ```
+//!
+//! [`TypedFunc::call`]: wasmi::TypedFunc::call
+
+use wasmi::{
+    core::{F32, F64},
+    Engine, Error, Func, Store,
+};
+
+/// Wasm number types with a zero value.
+trait Zero {
+    fn zero() -> Self;
+}
+
+impl Zero for i32 {
+    fn zero() -> Self {
+        0
+    }
+}
+
+impl Zero for i64 {
+    fn zero() -> Self {
+        0
+    }
+}
+
+impl Zero for F32 {
+    fn zero() -> Self {
+        F32::from(0.0)
+    }
+}
+
+impl Zero for F64 {
+    fn zero() -> Self {
+        F64::from(0.0)
+    }
+}
+
+/// Calls `macro` with `prefix` extended by each of the Wasm number types.
+macro_rules! for_each_type {
+    ($macro:ident $(, $prefix:ty)*) => {
+        $macro!($($prefix,)* i32);
+        $macro!($($prefix,)* i64);
+        $macro!($($prefix,)* F32);
+        $macro!($($prefix,)* F64);
+    };
+}
+
+fn main() -> Result<(), Error> {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut len_calls = 0;
+    macro_rules! call {
+        ($p0:ty, $p1:ty, $p2:ty, $r:ty) => {{
+            let func = Func::wrap(&mut store, |_: $p0, _: $p1, _: $p2| <$r>::zero());
+            let result = func
+                .typed::<($p0, $p1, $p2), $r>(&store)?
+                .call(&mut store, (<$p0>::zero(), <$p1>::zero(), <$p2>::zero()))?;
+            assert_eq!(result, <$r>::zero());
+            len_calls += 1;
+        }};
+    }
+    macro_rules! call_3 {
+        ($p0:ty, $p1:ty, $p2:ty) => {
+            for_each_type!(call, $p0, $p1, $p2)
+        };
+    }
+    macro_rules! call_2 {
+        ($p0:ty, $p1:ty) => {
+            for_each_type!(call_3, $p0, $p1)
+        };
+    }
+    macro_rules! call_1 {
+        ($p0:ty) => {
+            for_each_type!(call_2, $p0)
+        };
+    }
+    for_each_type!(call_1);
+    println!("called {len_calls} distinct signatures");
+    Ok(())
+}
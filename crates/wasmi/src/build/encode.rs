@@ -0,0 +1,219 @@
+//! Encoding of the Wasm binary format for the [`ModuleBuilder`].
+//!
+//! [`ModuleBuilder`]: super::ModuleBuilder
+
+use crate::{core::ValueType, FuncType, Mutability, Value};
+use alloc::vec::Vec;
+
+/// The Wasm binary magic number followed by the supported Wasm binary version.
+const PREAMBLE: [u8; 8] = [0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00];
+
+/// Identifiers of the Wasm binary sections encoded by the [`ModuleBuilder`].
+///
+/// [`ModuleBuilder`]: super::ModuleBuilder
+#[derive(Debug, Copy, Clone)]
+#[repr(u8)]
+pub enum SectionId {
+    Type = 1,
+    Import = 2,
+    Function = 3,
+    Table = 4,
+    Memory = 5,
+    Global = 6,
+    Export = 7,
+    Start = 8,
+    Code = 10,
+}
+
+/// The kind of an imported or exported Wasm entity.
+#[derive(Debug, Copy, Clone)]
+#[repr(u8)]
+pub enum ExternKind {
+    Func = 0x00,
+    Table = 0x01,
+    Memory = 0x02,
+    Global = 0x03,
+}
+
+/// A growable buffer of Wasm binary encoded bytes.
+#[derive(Debug, Default)]
+pub struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    /// Creates a new [`Encoder`] starting with the Wasm binary preamble.
+    pub fn module() -> Self {
+        Self {
+            bytes: PREAMBLE.to_vec(),
+        }
+    }
+
+    /// Returns the encoded bytes.
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Encodes a single raw `byte`.
+    pub fn byte(&mut self, byte: u8) {
+        self.bytes.push(byte);
+    }
+
+    /// Encodes the raw `bytes` as is.
+    pub fn raw(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Encodes `value` as unsigned LEB128.
+    pub fn u32(&mut self, mut value: u32) {
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.byte(byte);
+                return;
+            }
+            self.byte(byte | 0x80);
+        }
+    }
+
+    /// Encodes `value` as signed LEB128.
+    pub fn i64(&mut self, mut value: i64) {
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            let sign_bit = byte & 0x40 != 0;
+            if (value == 0 && !sign_bit) || (value == -1 && sign_bit) {
+                self.byte(byte);
+                return;
+            }
+            self.byte(byte | 0x80);
+        }
+    }
+
+    /// Encodes the length of a vector or byte sequence.
+    pub fn length(&mut self, len: usize) {
+        let len =
+            u32::try_from(len).unwrap_or_else(|_| panic!("encoded length out of bounds: {len}"));
+        self.u32(len);
+    }
+
+    /// Encodes `bytes` prefixed by their length.
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        self.length(bytes.len());
+        self.raw(bytes);
+    }
+
+    /// Encodes the `name` prefixed by its length.
+    pub fn name(&mut self, name: &str) {
+        self.bytes(name.as_bytes());
+    }
+
+    /// Encodes the section `id` with `items` if `items` is not empty.
+    ///
+    /// Each item is encoded via `f`.
+    pub fn section<T>(&mut self, id: SectionId, items: &[T], mut f: impl FnMut(&mut Self, &T)) {
+        if items.is_empty() {
+            return;
+        }
+        let mut section = Self::default();
+        section.length(items.len());
+        for item in items {
+            f(&mut section, item);
+        }
+        self.byte(id as u8);
+        self.bytes(&section.bytes);
+    }
+
+    /// Encodes the [`ValueType`].
+    pub fn value_type(&mut self, ty: ValueType) {
+        self.byte(match ty {
+            ValueType::I32 => 0x7F,
+            ValueType::I64 => 0x7E,
+            ValueType::F32 => 0x7D,
+            ValueType::F64 => 0x7C,
+            ValueType::FuncRef => 0x70,
+            ValueType::ExternRef => 0x6F,
+        });
+    }
+
+    /// Encodes the [`FuncType`].
+    pub fn func_type(&mut self, ty: &FuncType) {
+        self.byte(0x60);
+        self.length(ty.params().len());
+        for param in ty.params() {
+            self.value_type(*param);
+        }
+        self.length(ty.results().len());
+        for result in ty.results() {
+            self.value_type(*result);
+        }
+    }
+
+    /// Encodes the limits of a table or linear memory.
+    pub fn limits(&mut self, min: u32, max: Option<u32>) {
+        match max {
+            None => {
+                self.byte(0x00);
+                self.u32(min);
+            }
+            Some(max) => {
+                self.byte(0x01);
+                self.u32(min);
+                self.u32(max);
+            }
+        }
+    }
+
+    /// Encodes the type of a global variable.
+    pub fn global_type(&mut self, ty: ValueType, mutability: Mutability) {
+        self.value_type(ty);
+        self.byte(match mutability {
+            Mutability::Const => 0x00,
+            Mutability::Var => 0x01,
+        });
+    }
+
+    /// Encodes a constant initializer expression yielding `value`.
+    ///
+    /// # Panics
+    ///
+    /// If `value` is a non-null reference.
+    pub fn const_expr(&mut self, value: &Value) {
+        match value {
+            Value::I32(value) => {
+                self.byte(0x41);
+                self.i64(i64::from(*value));
+            }
+            Value::I64(value) => {
+                self.byte(0x42);
+                self.i64(*value);
+            }
+            Value::F32(value) => {
+                self.byte(0x43);
+                self.raw(&value.to_bits().to_le_bytes());
+            }
+            Value::F64(value) => {
+                self.byte(0x44);
+                self.raw(&value.to_bits().to_le_bytes());
+            }
+            Value::FuncRef(value) => {
+                assert!(
+                    value.is_null(),
+                    "global initializers must not be non-null references"
+                );
+                self.byte(0xD0);
+                self.value_type(ValueType::FuncRef);
+            }
+            Value::ExternRef(value) => {
+                assert!(
+                    value.is_null(),
+                    "global initializers must not be non-null references"
+                );
+                self.byte(0xD0);
+                self.value_type(ValueType::ExternRef);
+            }
+        }
+        self.byte(0x0B);
+    }
+}
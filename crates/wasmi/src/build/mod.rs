@@ -0,0 +1,456 @@
+//! Programmatic construction of Wasm modules without Wasm bytes.
+//!
+//! The [`ModuleBuilder`] defines the entities of a Wasm [`Module`] one by one.
+//! Function bodies are either given as Wasm function bodies or as hand-written
+//! Wasmi bytecode via [`IrFunc`]. The latter skips the Wasmi translator and instead
+//! is validated to uphold all invariants that the Wasmi executor relies upon.
+//!
+//! # Example
+//!
+//! ```
+//! # use wasmi::{build::{IrFunc, ModuleBuilder}, core::ValueType, ir::{Instruction, Register}, *};
+//! # fn main() -> Result<(), wasmi::Error> {
+//! let engine = Engine::default();
+//! let mut builder = ModuleBuilder::new(&engine);
+//! let ty = builder.push_type(FuncType::new([ValueType::I32; 2], [ValueType::I32]));
+//! let add = builder.push_ir_func(
+//!     ty,
+//!     IrFunc::new(
+//!         3,
+//!         [],
+//!         [
+//!             Instruction::i32_add(Register::from_i16(2), Register::from_i16(0), Register::from_i16(1)),
+//!             Instruction::return_reg(Register::from_i16(2)),
+//!         ],
+//!     ),
+//! );
+//! builder.export_func("add", add);
+//! let module = builder.finish()?;
+//! let mut store = Store::new(&engine, ());
+//! let instance = Linker::new(&engine).instantiate(&mut store, &module)?.start(&mut store)?;
+//! let add = instance.get_typed_func::<(i32, i32), i32>(&store, "add")?;
+//! assert_eq!(add.call(&mut store, (1, 2))?, 3);
+//! # Ok(())
+//! # }
+//! ```
+
+mod encode;
+mod validate;
+
+pub(crate) use self::encode::Encoder;
+use self::{
+    encode::{ExternKind, SectionId},
+    validate::validate_ir_func,
+};
+use crate::{
+    core::{UntypedValue, ValueType},
+    engine::{bytecode::Instruction, CompiledFuncEntity},
+    module,
+    module::ModuleHeader,
+    Engine,
+    Error,
+    FuncType,
+    MemoryType,
+    Module,
+    Mutability,
+    TableType,
+    Value,
+};
+use alloc::{boxed::Box, vec::Vec};
+
+/// A function body made of hand-written Wasmi bytecode.
+///
+/// # Registers
+///
+/// - The function parameters occupy the cells `0..len_params` upon entry.
+/// - The remaining cells up to `len_cells` are initialized to zero upon entry.
+/// - The n-th function local constant is accessed via `Register::from_i16(-1 - n)`.
+///
+/// # Note
+///
+/// Instructions for fuel metering are not inserted automatically.
+/// When fuel metering is enabled it is up to the author to consume fuel.
+#[derive(Debug, Clone)]
+pub struct IrFunc {
+    /// The number of cells of the function frame.
+    len_cells: u16,
+    /// The function local constant values.
+    consts: Vec<UntypedValue>,
+    /// The instruction words of the function body.
+    instrs: Vec<Instruction>,
+}
+
+impl IrFunc {
+    /// Creates a new [`IrFunc`] with `len_cells`, function local `consts` and `instrs`.
+    pub fn new<C, I>(len_cells: u16, consts: C, instrs: I) -> Self
+    where
+        C: IntoIterator<Item = UntypedValue>,
+        I: IntoIterator<Item = Instruction>,
+    {
+        Self {
+            len_cells,
+            consts: consts.into_iter().collect(),
+            instrs: instrs.into_iter().collect(),
+        }
+    }
+
+    /// Validates the [`IrFunc`] and converts it into a [`CompiledFuncEntity`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`IrFunc`] fails to validate for the internal function at `func_idx`.
+    pub(crate) fn into_func_entity(
+        self,
+        engine: &Engine,
+        header: &ModuleHeader,
+        len_data_segments: u32,
+        func_idx: module::FuncIdx,
+    ) -> Result<CompiledFuncEntity, Error> {
+        validate_ir_func(engine, header, len_data_segments, func_idx, &self)?;
+        // Note: validation guarantees that all registers fit into `u16`.
+        let len_registers = self.len_cells + self.consts.len() as u16;
+        // Note: the function local constant at index `n` is addressed by
+        //       register `-1 - n` which requires them to be stored in reverse.
+        Ok(CompiledFuncEntity::new(
+            len_registers,
+            self.instrs,
+            self.consts.into_iter().rev(),
+        ))
+    }
+}
+
+/// The body of a function defined by the [`ModuleBuilder`].
+#[derive(Debug)]
+enum FuncBody {
+    /// A Wasm function body including its local declarations and final `end`.
+    Wasm(Box<[u8]>),
+    /// A hand-written Wasmi bytecode function body.
+    Ir(IrFunc),
+}
+
+/// The type of an import of the [`ModuleBuilder`].
+#[derive(Debug)]
+enum ImportDesc {
+    Func(u32),
+    Table(TableType),
+    Memory(MemoryType),
+    Global(ValueType, Mutability),
+}
+
+/// An import of the [`ModuleBuilder`].
+#[derive(Debug)]
+struct Import {
+    module: Box<str>,
+    name: Box<str>,
+    desc: ImportDesc,
+}
+
+/// Constructs a Wasm [`Module`] programmatically.
+///
+/// # Note
+///
+/// - Entities of the same kind are indexed in the order they are pushed or imported.
+/// - Imports of a kind must be declared before any definition of the same kind.
+#[derive(Debug)]
+pub struct ModuleBuilder {
+    engine: Engine,
+    types: Vec<FuncType>,
+    imports: Vec<Import>,
+    len_imported_funcs: u32,
+    len_imported_tables: u32,
+    len_imported_memories: u32,
+    len_imported_globals: u32,
+    funcs: Vec<(u32, FuncBody)>,
+    tables: Vec<TableType>,
+    memories: Vec<MemoryType>,
+    globals: Vec<(Value, Mutability)>,
+    exports: Vec<(Box<str>, ExternKind, u32)>,
+    start: Option<u32>,
+}
+
+/// Returns the next index of a kind with `len_imports` imports and `len_defs` definitions.
+fn next_index(len_imports: u32, len_defs: usize) -> u32 {
+    let len_defs = u32::try_from(len_defs).unwrap_or_else(|_| panic!("too many definitions"));
+    len_imports + len_defs
+}
+
+impl ModuleBuilder {
+    /// Creates a new [`ModuleBuilder`] for modules used with the `engine`.
+    pub fn new(engine: &Engine) -> Self {
+        Self {
+            engine: engine.clone(),
+            types: Vec::new(),
+            imports: Vec::new(),
+            len_imported_funcs: 0,
+            len_imported_tables: 0,
+            len_imported_memories: 0,
+            len_imported_globals: 0,
+            funcs: Vec::new(),
+            tables: Vec::new(),
+            memories: Vec::new(),
+            globals: Vec::new(),
+            exports: Vec::new(),
+            start: None,
+        }
+    }
+
+    /// Pushes the function type `ty` and returns its type index.
+    pub fn push_type(&mut self, ty: FuncType) -> u32 {
+        let index = next_index(0, self.types.len());
+        self.types.push(ty);
+        index
+    }
+
+    /// Pushes a new import named `module::name` of `desc`.
+    fn push_import(&mut self, module: &str, name: &str, desc: ImportDesc) {
+        self.imports.push(Import {
+            module: module.into(),
+            name: name.into(),
+            desc,
+        });
+    }
+
+    /// Imports a function of type index `ty` and returns its function index.
+    ///
+    /// # Panics
+    ///
+    /// If a function has already been defined.
+    pub fn import_func(&mut self, module: &str, name: &str, ty: u32) -> u32 {
+        assert!(
+            self.funcs.is_empty(),
+            "functions must be imported before they are defined"
+        );
+        self.push_import(module, name, ImportDesc::Func(ty));
+        self.len_imported_funcs += 1;
+        self.len_imported_funcs - 1
+    }
+
+    /// Imports a table of type `ty` and returns its table index.
+    ///
+    /// # Panics
+    ///
+    /// If a table has already been defined.
+    pub fn import_table(&mut self, module: &str, name: &str, ty: TableType) -> u32 {
+        assert!(
+            self.tables.is_empty(),
+            "tables must be imported before they are defined"
+        );
+        self.push_import(module, name, ImportDesc::Table(ty));
+        self.len_imported_tables += 1;
+        self.len_imported_tables - 1
+    }
+
+    /// Imports a linear memory of type `ty` and returns its memory index.
+    ///
+    /// # Panics
+    ///
+    /// If a linear memory has already been defined.
+    pub fn import_memory(&mut self, module: &str, name: &str, ty: MemoryType) -> u32 {
+        assert!(
+            self.memories.is_empty(),
+            "linear memories must be imported before they are defined"
+        );
+        self.push_import(module, name, ImportDesc::Memory(ty));
+        self.len_imported_memories += 1;
+        self.len_imported_memories - 1
+    }
+
+    /// Imports a global variable of type `ty` and `mutability` and returns its global index.
+    ///
+    /// # Panics
+    ///
+    /// If a global variable has already been defined.
+    pub fn import_global(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: ValueType,
+        mutability: Mutability,
+    ) -> u32 {
+        assert!(
+            self.globals.is_empty(),
+            "global variables must be imported before they are defined"
+        );
+        self.push_import(module, name, ImportDesc::Global(ty, mutability));
+        self.len_imported_globals += 1;
+        self.len_imported_globals - 1
+    }
+
+    /// Pushes a function of type index `ty` with the Wasm function `body` and returns its function index.
+    ///
+    /// # Note
+    ///
+    /// The `body` includes the local variable declarations and the final `end` of the function.
+    pub fn push_wasm_func(&mut self, ty: u32, body: &[u8]) -> u32 {
+        let index = next_index(self.len_imported_funcs, self.funcs.len());
+        self.funcs.push((ty, FuncBody::Wasm(body.into())));
+        index
+    }
+
+    /// Pushes a function of type index `ty` with the Wasmi bytecode `body` and returns its function index.
+    ///
+    /// # Note
+    ///
+    /// The `body` is validated upon [`ModuleBuilder::finish`].
+    pub fn push_ir_func(&mut self, ty: u32, body: IrFunc) -> u32 {
+        let index = next_index(self.len_imported_funcs, self.funcs.len());
+        self.funcs.push((ty, FuncBody::Ir(body)));
+        index
+    }
+
+    /// Pushes a table of type `ty` and returns its table index.
+    pub fn push_table(&mut self, ty: TableType) -> u32 {
+        let index = next_index(self.len_imported_tables, self.tables.len());
+        self.tables.push(ty);
+        index
+    }
+
+    /// Pushes a linear memory of type `ty` and returns its memory index.
+    pub fn push_memory(&mut self, ty: MemoryType) -> u32 {
+        let index = next_index(self.len_imported_memories, self.memories.len());
+        self.memories.push(ty);
+        index
+    }
+
+    /// Pushes a global variable initialized to `init` and returns its global index.
+    ///
+    /// # Panics
+    ///
+    /// If `init` is a non-null reference.
+    pub fn push_global(&mut self, init: Value, mutability: Mutability) -> u32 {
+        let is_null = match &init {
+            Value::FuncRef(value) => value.is_null(),
+            Value::ExternRef(value) => value.is_null(),
+            _ => true,
+        };
+        assert!(
+            is_null,
+            "global variables must not be initialized to non-null references"
+        );
+        let index = next_index(self.len_imported_globals, self.globals.len());
+        self.globals.push((init, mutability));
+        index
+    }
+
+    /// Exports the function at `index` under `name`.
+    pub fn export_func(&mut self, name: &str, index: u32) {
+        self.exports.push((name.into(), ExternKind::Func, index));
+    }
+
+    /// Exports the table at `index` under `name`.
+    pub fn export_table(&mut self, name: &str, index: u32) {
+        self.exports.push((name.into(), ExternKind::Table, index));
+    }
+
+    /// Exports the linear memory at `index` under `name`.
+    pub fn export_memory(&mut self, name: &str, index: u32) {
+        self.exports.push((name.into(), ExternKind::Memory, index));
+    }
+
+    /// Exports the global variable at `index` under `name`.
+    pub fn export_global(&mut self, name: &str, index: u32) {
+        self.exports.push((name.into(), ExternKind::Global, index));
+    }
+
+    /// Sets the start function to the function at `index`.
+    pub fn set_start(&mut self, index: u32) {
+        self.start = Some(index);
+    }
+
+    /// Finishes construction and returns the validated and compiled [`Module`].
+    ///
+    /// # Errors
+    ///
+    /// - If the constructed Wasm module is invalid.
+    /// - If any Wasm function body fails to validate or translate.
+    /// - If any [`IrFunc`] fails to validate.
+    pub fn finish(self) -> Result<Module, Error> {
+        let wasm = self.encode();
+        let ir_funcs = self
+            .funcs
+            .into_iter()
+            .map(|(_, body)| match body {
+                FuncBody::Wasm(_) => None,
+                FuncBody::Ir(func) => Some(func),
+            })
+            .collect();
+        Module::new_with_ir_funcs(&self.engine, &wasm[..], ir_funcs)
+    }
+
+    /// Encodes the Wasm binary of the constructed module.
+    ///
+    /// # Note
+    ///
+    /// The function bodies of [`IrFunc`] are encoded as empty placeholders.
+    fn encode(&self) -> Vec<u8> {
+        let mut wasm = Encoder::module();
+        wasm.section(SectionId::Type, &self.types, Encoder::func_type);
+        wasm.section(SectionId::Import, &self.imports, |wasm, import| {
+            wasm.name(&import.module);
+            wasm.name(&import.name);
+            match import.desc {
+                ImportDesc::Func(ty) => {
+                    wasm.byte(ExternKind::Func as u8);
+                    wasm.u32(ty);
+                }
+                ImportDesc::Table(ty) => {
+                    wasm.byte(ExternKind::Table as u8);
+                    wasm.value_type(ty.element());
+                    wasm.limits(ty.minimum(), ty.maximum());
+                }
+                ImportDesc::Memory(ty) => {
+                    wasm.byte(ExternKind::Memory as u8);
+                    encode_memory_type(wasm, &ty);
+                }
+                ImportDesc::Global(ty, mutability) => {
+                    wasm.byte(ExternKind::Global as u8);
+                    wasm.global_type(ty, mutability);
+                }
+            }
+        });
+        wasm.section(SectionId::Function, &self.funcs, |wasm, (ty, _)| {
+            wasm.u32(*ty)
+        });
+        wasm.section(SectionId::Table, &self.tables, |wasm, ty| {
+            wasm.value_type(ty.element());
+            wasm.limits(ty.minimum(), ty.maximum());
+        });
+        wasm.section(SectionId::Memory, &self.memories, encode_memory_type);
+        wasm.section(
+            SectionId::Global,
+            &self.globals,
+            |wasm, (init, mutability)| {
+                wasm.global_type(init.ty(), *mutability);
+                wasm.const_expr(init);
+            },
+        );
+        wasm.section(
+            SectionId::Export,
+            &self.exports,
+            |wasm, (name, kind, index)| {
+                wasm.name(name);
+                wasm.byte(*kind as u8);
+                wasm.u32(*index);
+            },
+        );
+        if let Some(start) = self.start {
+            let mut section = Encoder::default();
+            section.u32(start);
+            wasm.byte(SectionId::Start as u8);
+            wasm.bytes(&section.finish());
+        }
+        wasm.section(SectionId::Code, &self.funcs, |wasm, (_, body)| match body {
+            FuncBody::Wasm(body) => wasm.bytes(body),
+            // Note: an empty placeholder without local variable declarations.
+            FuncBody::Ir(_) => wasm.bytes(&[0x00, 0x0B]),
+        });
+        wasm.finish()
+    }
+}
+
+/// Encodes the linear memory type `ty`.
+fn encode_memory_type(wasm: &mut Encoder, ty: &MemoryType) {
+    let min = u32::from(ty.initial_pages());
+    let max = ty.maximum_pages().map(u32::from);
+    wasm.limits(min, max);
+}
@@ -0,0 +1,1278 @@
+//! Validation of hand-written Wasmi bytecode.
+//!
+//! # Note
+//!
+//! The Wasmi executor relies on the Wasmi translator to only ever emit bytecode
+//! that accesses registers within the frame of its function, that refers to existing
+//! entities of its Wasm module and that calls and returns according to the involved
+//! function signatures. Since hand-written bytecode does not pass through the Wasmi
+//! translator it has to be validated against these invariants before execution.
+//!
+//! Value types of registers are not validated since the Wasmi executor operates on
+//! untyped values. Ill-typed bytecode may compute garbage or trap but cannot access
+//! memory outside of its function frame or its Wasm module.
+
+use super::IrFunc;
+use crate::{
+    engine::{
+        bytecode::{
+            verify_instr_starts,
+            BytecodeError,
+            BytecodeErrorKind,
+            DataSegmentIdx,
+            ElementSegmentIdx,
+            FuncIdx,
+            GlobalIdx,
+            Instruction,
+            Register,
+            RegisterSpan,
+            RegisterSpanIter,
+            SignatureIdx,
+            TableIdx,
+        },
+        CompiledFunc,
+    },
+    module::{self, FuncTypeIdx, ModuleHeader},
+    Engine,
+    FuncType,
+};
+use alloc::vec::Vec;
+
+/// The maximum number of function local constants or cells addressable by [`Register`].
+const MAX_LEN_REGISTERS: usize = 1 << 15;
+
+/// Validates the [`IrFunc`] of the internal function at `func_idx` of a Wasm module.
+///
+/// # Note
+///
+/// - The `header` describes the Wasm module of the function.
+/// - The `len_data_segments` is the number of data segments of the Wasm module.
+///
+/// # Errors
+///
+/// If `func` violates any of the invariants the Wasmi executor relies upon.
+pub fn validate_ir_func(
+    engine: &Engine,
+    header: &ModuleHeader,
+    len_data_segments: u32,
+    func_idx: module::FuncIdx,
+    func: &IrFunc,
+) -> Result<(), BytecodeError> {
+    let func_type = engine.resolve_func_type(header.get_type_of_func(func_idx), FuncType::clone);
+    let validator = IrFuncValidator {
+        engine,
+        header,
+        len_data_segments,
+        func_type,
+        len_consts: func.consts.len(),
+        len_cells: usize::from(func.len_cells),
+    };
+    validator
+        .validate_frame()
+        .map_err(|kind| BytecodeError::new(0, kind))?;
+    validator.validate_instrs(&func.instrs)
+}
+
+/// Validates a hand-written [`IrFunc`] in the context of its Wasm module.
+struct IrFuncValidator<'a> {
+    /// The [`Engine`] used to resolve function types.
+    engine: &'a Engine,
+    /// The header of the Wasm module of the validated function.
+    header: &'a ModuleHeader,
+    /// The number of data segments of the Wasm module.
+    len_data_segments: u32,
+    /// The [`FuncType`] of the validated function.
+    func_type: FuncType,
+    /// The number of function local constant values of the validated function.
+    len_consts: usize,
+    /// The number of cells of the validated function.
+    len_cells: usize,
+}
+
+impl IrFuncValidator<'_> {
+    /// Validates that the function frame can hold its constants, cells and parameters.
+    fn validate_frame(&self) -> Result<(), BytecodeErrorKind> {
+        let len_registers = self.len_consts + self.len_cells;
+        if self.len_consts > MAX_LEN_REGISTERS
+            || self.len_cells > MAX_LEN_REGISTERS
+            || u16::try_from(len_registers).is_err()
+            || self.func_type.params().len() > self.len_cells
+        {
+            return Err(BytecodeErrorKind::InvalidFrame);
+        }
+        Ok(())
+    }
+
+    /// Validates all instructions of `instrs`.
+    fn validate_instrs(&self, instrs: &[Instruction]) -> Result<(), BytecodeError> {
+        let mut is_instr = Vec::new();
+        verify_instr_starts(instrs, &mut is_instr)?;
+        let mut last = None;
+        let mut pos = 0;
+        while pos < instrs.len() {
+            let end = (pos + 1..instrs.len())
+                .find(|&next| is_instr[next])
+                .unwrap_or(instrs.len());
+            let params = &instrs[pos + 1..end];
+            self.validate_instr(&instrs[pos], params)
+                .map_err(|kind| BytecodeError::new(pos, kind))?;
+            if let Instruction::ReturnForward { values } = instrs[pos] {
+                self.forwarded_call(values, last.map(|last| &instrs[last]))
+                    .map_err(|kind| BytecodeError::new(pos, kind))?;
+            }
+            for (offset, param) in params.iter().enumerate() {
+                self.validate_param(param)
+                    .map_err(|kind| BytecodeError::new(pos + 1 + offset, kind))?;
+            }
+            last = Some(pos);
+            pos = end;
+        }
+        match last {
+            Some(pos) if is_terminal(&instrs[pos]) => Ok(()),
+            last => Err(BytecodeError::new(
+                last.unwrap_or(0),
+                BytecodeErrorKind::UnterminatedBody,
+            )),
+        }
+    }
+
+    /// Validates the `instr` followed by its parameter words `params`.
+    fn validate_instr(
+        &self,
+        instr: &Instruction,
+        params: &[Instruction],
+    ) -> Result<(), BytecodeErrorKind> {
+        if uses_default_memory(instr) {
+            self.memory()?;
+        }
+        match *instr {
+            Instruction::Trap(_)
+            | Instruction::I32StoreAtImm16(_)
+            | Instruction::I32Store8AtImm(_)
+            | Instruction::I32Store16AtImm(_)
+            | Instruction::I64StoreAtImm16(_)
+            | Instruction::I64Store8AtImm(_)
+            | Instruction::I64Store16AtImm(_)
+            | Instruction::I64Store32AtImm16(_) => Ok(()),
+            Instruction::ConsumeFuel(_) => {
+                if !self.engine.config().get_fuel_checks() {
+                    return Err(BytecodeErrorKind::UnsupportedInstr);
+                }
+                Ok(())
+            }
+            Instruction::ElemDrop(segment) => self.element_segment(segment),
+            Instruction::DataDrop(segment) => self.data_segment(segment),
+            Instruction::Return => self.returns(0),
+            Instruction::ReturnReg { value } => {
+                self.returns(1)?;
+                self.read(value)
+            }
+            Instruction::ReturnReg2 { values } => {
+                self.returns(2)?;
+                self.read_all(&values)
+            }
+            Instruction::ReturnReg3 { values } => {
+                self.returns(3)?;
+                self.read_all(&values)
+            }
+            Instruction::ReturnMany { values } => {
+                self.returns(3 + len_register_list(params))?;
+                self.read_all(&values)
+            }
+            Instruction::ReturnImm32 { .. }
+            | Instruction::ReturnI64Imm32 { .. }
+            | Instruction::ReturnF64Imm32 { .. } => self.returns(1),
+            Instruction::ReturnSpan { values } | Instruction::ReturnForward { values } => {
+                self.read_span_iter(values)?;
+                self.returns(values.len())
+            }
+            Instruction::ReturnNez { condition } => {
+                self.returns(0)?;
+                self.read(condition)
+            }
+            Instruction::ReturnNezReg { condition, value } => {
+                self.returns(1)?;
+                self.read(condition)?;
+                self.read(value)
+            }
+            Instruction::ReturnNezReg2 { condition, values } => {
+                self.returns(2)?;
+                self.read(condition)?;
+                self.read_all(&values)
+            }
+            Instruction::ReturnNezMany { condition, values } => {
+                self.returns(2 + len_register_list(params))?;
+                self.read(condition)?;
+                self.read_all(&values)
+            }
+            Instruction::ReturnNezImm32 { condition, .. }
+            | Instruction::ReturnNezI64Imm32 { condition, .. }
+            | Instruction::ReturnNezF64Imm32 { condition, .. } => {
+                self.returns(1)?;
+                self.read(condition)
+            }
+            Instruction::ReturnNezSpan { condition, values } => {
+                self.read(condition)?;
+                self.read_span_iter(values)?;
+                self.returns(values.len())
+            }
+            Instruction::Branch { .. } => Ok(()),
+            Instruction::BranchCmpFallback { .. }
+            | Instruction::BranchI32EqFallback(_)
+            | Instruction::BranchI32NeFallback(_)
+            | Instruction::BranchI32LtSFallback(_)
+            | Instruction::BranchI32LtUFallback(_)
+            | Instruction::BranchI32LeSFallback(_)
+            | Instruction::BranchI32LeUFallback(_)
+            | Instruction::BranchI32GtSFallback(_)
+            | Instruction::BranchI32GtUFallback(_)
+            | Instruction::BranchI32GeSFallback(_)
+            | Instruction::BranchI32GeUFallback(_) => Err(BytecodeErrorKind::UnsupportedInstr),
+            Instruction::BranchI32And(instr)
+            | Instruction::BranchI32Or(instr)
+            | Instruction::BranchI32Xor(instr)
+            | Instruction::BranchI32AndEqz(instr)
+            | Instruction::BranchI32OrEqz(instr)
+            | Instruction::BranchI32XorEqz(instr)
+            | Instruction::BranchI32Eq(instr)
+            | Instruction::BranchI32Ne(instr)
+            | Instruction::BranchI32LtS(instr)
+            | Instruction::BranchI32LtU(instr)
+            | Instruction::BranchI32LeS(instr)
+            | Instruction::BranchI32LeU(instr)
+            | Instruction::BranchI32GtS(instr)
+            | Instruction::BranchI32GtU(instr)
+            | Instruction::BranchI32GeS(instr)
+            | Instruction::BranchI32GeU(instr)
+            | Instruction::BranchI64Eq(instr)
+            | Instruction::BranchI64Ne(instr)
+            | Instruction::BranchI64LtS(instr)
+            | Instruction::BranchI64LtU(instr)
+            | Instruction::BranchI64LeS(instr)
+            | Instruction::BranchI64LeU(instr)
+            | Instruction::BranchI64GtS(instr)
+            | Instruction::BranchI64GtU(instr)
+            | Instruction::BranchI64GeS(instr)
+            | Instruction::BranchI64GeU(instr)
+            | Instruction::BranchF32Eq(instr)
+            | Instruction::BranchF32Ne(instr)
+            | Instruction::BranchF32Lt(instr)
+            | Instruction::BranchF32Le(instr)
+            | Instruction::BranchF32Gt(instr)
+            | Instruction::BranchF32Ge(instr)
+            | Instruction::BranchF32NotLt(instr)
+            | Instruction::BranchF32NotLe(instr)
+            | Instruction::BranchF64Eq(instr)
+            | Instruction::BranchF64Ne(instr)
+            | Instruction::BranchF64Lt(instr)
+            | Instruction::BranchF64Le(instr)
+            | Instruction::BranchF64Gt(instr)
+            | Instruction::BranchF64Ge(instr)
+            | Instruction::BranchF64NotLt(instr)
+            | Instruction::BranchF64NotLe(instr) => {
+                self.read(instr.lhs)?;
+                self.read(instr.rhs)
+            }
+            Instruction::BranchI32AndImm(instr)
+            | Instruction::BranchI32OrImm(instr)
+            | Instruction::BranchI32XorImm(instr)
+            | Instruction::BranchI32AndEqzImm(instr)
+            | Instruction::BranchI32OrEqzImm(instr)
+            | Instruction::BranchI32XorEqzImm(instr)
+            | Instruction::BranchI32EqImm(instr)
+            | Instruction::BranchI32NeImm(instr)
+            | Instruction::BranchI32LtSImm(instr)
+            | Instruction::BranchI32LeSImm(instr)
+            | Instruction::BranchI32GtSImm(instr)
+            | Instruction::BranchI32GeSImm(instr) => self.read(instr.lhs),
+            Instruction::BranchI32LtUImm(instr)
+            | Instruction::BranchI32LeUImm(instr)
+            | Instruction::BranchI32GtUImm(instr)
+            | Instruction::BranchI32GeUImm(instr) => self.read(instr.lhs),
+            Instruction::BranchI64EqImm(instr)
+            | Instruction::BranchI64NeImm(instr)
+            | Instruction::BranchI64LtSImm(instr)
+            | Instruction::BranchI64LeSImm(instr)
+            | Instruction::BranchI64GtSImm(instr)
+            | Instruction::BranchI64GeSImm(instr) => self.read(instr.lhs),
+            Instruction::BranchI64LtUImm(instr)
+            | Instruction::BranchI64LeUImm(instr)
+            | Instruction::BranchI64GtUImm(instr)
+            | Instruction::BranchI64GeUImm(instr) => self.read(instr.lhs),
+            Instruction::BranchTable { index, .. }
+            | Instruction::BranchTableSparse { index, .. } => self.read(index),
+            Instruction::Copy { result, value } => {
+                self.write(result)?;
+                self.read(value)
+            }
+            Instruction::Copy2 { results, values } => {
+                self.write_span(results, 2)?;
+                self.read_all(&values)
+            }
+            Instruction::I64MulWideS { results, lhs, rhs }
+            | Instruction::I64MulWideU { results, lhs, rhs } => {
+                self.write_span(results, 2)?;
+                self.read_all(&[lhs, rhs])
+            }
+            Instruction::CopyMany { results, values }
+            | Instruction::CopyManyNonOverlapping { results, values } => {
+                self.write_span(results, 2 + len_register_list(params))?;
+                self.read_all(&values)
+            }
+            Instruction::CopyImm32 { result, .. } => self.write(result),
+            Instruction::CopyI64Imm32 { result, .. } => self.write(result),
+            Instruction::CopyF64Imm32 { result, .. } => self.write(result),
+            Instruction::CopySpan {
+                results,
+                len,
+                values,
+            }
+            | Instruction::CopySpanNonOverlapping {
+                results,
+                len,
+                values,
+            } => {
+                self.write_span(results, usize::from(len))?;
+                self.read_span(values, usize::from(len))
+            }
+            Instruction::ReturnCallInternal0 { func } => {
+                self.return_call(self.internal_func_type_of(func)?, 0)
+            }
+            Instruction::ReturnCallInternal { func } => {
+                let len_params = len_register_list(params);
+                self.return_call(self.internal_func_type_of(func)?, len_params)
+            }
+            Instruction::ReturnCallImported0 { func } => {
+                self.return_call(self.func_type_of(func)?, 0)
+            }
+            Instruction::ReturnCallImported { func } => {
+                let len_params = len_register_list(params);
+                self.return_call(self.func_type_of(func)?, len_params)
+            }
+            Instruction::ReturnCallIndirect0 { func_type } => {
+                self.return_call(self.signature(func_type)?, 0)
+            }
+            Instruction::ReturnCallIndirect { func_type } => {
+                let len_params = len_register_list(params);
+                self.return_call(self.signature(func_type)?, len_params)
+            }
+            Instruction::CallInternal0 { results, func } => {
+                self.call(results, self.internal_func_type_of(func)?, 0)
+            }
+            Instruction::CallInternal { results, func } => {
+                let len_params = len_register_list(params);
+                self.call(results, self.internal_func_type_of(func)?, len_params)
+            }
+            Instruction::CallImported0 { results, func } => {
+                self.call(results, self.func_type_of(func)?, 0)
+            }
+            Instruction::CallImported { results, func } => {
+                let len_params = len_register_list(params);
+                self.call(results, self.func_type_of(func)?, len_params)
+            }
+            Instruction::CallIndirect0 { results, func_type } => {
+                self.call(results, self.signature(func_type)?, 0)
+            }
+            Instruction::CallIndirect { results, func_type } => {
+                let len_params = len_register_list(params);
+                self.call(results, self.signature(func_type)?, len_params)
+            }
+            Instruction::Select {
+                result,
+                condition,
+                lhs,
+            } => {
+                self.write(result)?;
+                self.read(condition)?;
+                self.read(lhs)
+            }
+            Instruction::SelectRev {
+                result,
+                condition,
+                rhs,
+            } => {
+                self.write(result)?;
+                self.read(condition)?;
+                self.read(rhs)
+            }
+            Instruction::SelectImm32 {
+                result_or_condition,
+                ..
+            }
+            | Instruction::SelectI64Imm32 {
+                result_or_condition,
+                ..
+            }
+            | Instruction::SelectF64Imm32 {
+                result_or_condition,
+                ..
+            } => self.write(result_or_condition),
+            Instruction::RefFunc { result, func } => {
+                self.func(func)?;
+                self.write(result)
+            }
+            Instruction::TableGet { result, index } => {
+                self.write(result)?;
+                self.read(index)
+            }
+            Instruction::TableGetImm { result, .. } => self.write(result),
+            Instruction::TableSize { result, table } => {
+                self.table(table)?;
+                self.write(result)
+            }
+            Instruction::TableSet { index, value } => {
+                self.read(index)?;
+                self.read(value)
+            }
+            Instruction::TableSetAt { value, .. } => self.read(value),
+            Instruction::TableCopy { dst, src, len }
+            | Instruction::TableInit { dst, src, len }
+            | Instruction::MemoryCopy { dst, src, len }
+            | Instruction::MemoryInit { dst, src, len } => {
+                self.read(dst)?;
+                self.read(src)?;
+                self.read(len)
+            }
+            Instruction::TableCopyTo { src, len, .. }
+            | Instruction::TableInitTo { src, len, .. }
+            | Instruction::MemoryCopyTo { src, len, .. }
+            | Instruction::MemoryInitTo { src, len, .. } => {
+                self.read(src)?;
+                self.read(len)
+            }
+            Instruction::TableCopyFrom { dst, len, .. }
+            | Instruction::TableInitFrom { dst, len, .. }
+            | Instruction::MemoryCopyFrom { dst, len, .. }
+            | Instruction::MemoryInitFrom { dst, len, .. } => {
+                self.read(dst)?;
+                self.read(len)
+            }
+            Instruction::TableCopyFromTo { len, .. }
+            | Instruction::TableInitFromTo { len, .. }
+            | Instruction::MemoryCopyFromTo { len, .. }
+            | Instruction::MemoryInitFromTo { len, .. } => self.read(len),
+            Instruction::TableCopyExact { dst, src, .. }
+            | Instruction::TableInitExact { dst, src, .. }
+            | Instruction::MemoryCopyExact { dst, src, .. }
+            | Instruction::MemoryInitExact { dst, src, .. } => {
+                self.read(dst)?;
+                self.read(src)
+            }
+            Instruction::TableCopyToExact { src, .. }
+            | Instruction::TableInitToExact { src, .. }
+            | Instruction::MemoryCopyToExact { src, .. }
+            | Instruction::MemoryInitToExact { src, .. } => self.read(src),
+            Instruction::TableCopyFromExact { dst, .. }
+            | Instruction::TableInitFromExact { dst, .. }
+            | Instruction::MemoryCopyFromExact { dst, .. }
+            | Instruction::MemoryInitFromExact { dst, .. } => self.read(dst),
+            Instruction::TableCopyFromToExact { .. }
+            | Instruction::TableInitFromToExact { .. }
+            | Instruction::MemoryCopyFromToExact { .. }
+            | Instruction::MemoryInitFromToExact { .. } => Ok(()),
+            Instruction::TableFill { dst, len, value } => {
+                self.read(dst)?;
+                self.read(len)?;
+                self.read(value)
+            }
+            Instruction::TableFillAt { len, value, .. } => {
+                self.read(len)?;
+                self.read(value)
+            }
+            Instruction::TableFillExact { dst, value, .. } => {
+                self.read(dst)?;
+                self.read(value)
+            }
+            Instruction::TableFillAtExact { value, .. } => self.read(value),
+            Instruction::TableGrow {
+                result,
+                delta,
+                value,
+            } => {
+                self.write(result)?;
+                self.read(delta)?;
+                self.read(value)
+            }
+            Instruction::TableGrowImm { result, value, .. } => {
+                self.write(result)?;
+                self.read(value)
+            }
+            Instruction::MemorySize { result } => self.write(result),
+            Instruction::MemoryGrow { result, delta } => {
+                self.write(result)?;
+                self.read(delta)
+            }
+            Instruction::MemoryGrowBy { result, .. } => self.write(result),
+            Instruction::MemoryFill { dst, value, len } => {
+                self.read(dst)?;
+                self.read(value)?;
+                self.read(len)
+            }
+            Instruction::MemoryFillAt { value, len, .. } => {
+                self.read(value)?;
+                self.read(len)
+            }
+            Instruction::MemoryFillImm { dst, len, .. } => {
+                self.read(dst)?;
+                self.read(len)
+            }
+            Instruction::MemoryFillExact { dst, value, .. } => {
+                self.read(dst)?;
+                self.read(value)
+            }
+            Instruction::MemoryFillAtImm { len, .. } => self.read(len),
+            Instruction::MemoryFillAtExact { value, .. } => self.read(value),
+            Instruction::MemoryFillImmExact { dst, .. } => self.read(dst),
+            Instruction::MemoryFillAtImmExact { .. } => Ok(()),
+            Instruction::GlobalGet { result, global } => {
+                self.global(global)?;
+                self.write(result)
+            }
+            Instruction::GlobalSet { global, input } => {
+                self.global_mut(global)?;
+                self.read(input)
+            }
+            Instruction::GlobalSetI32Imm16 { global, .. }
+            | Instruction::GlobalSetI64Imm16 { global, .. } => self.global_mut(global),
+            Instruction::I32Load(instr)
+            | Instruction::I64Load(instr)
+            | Instruction::F32Load(instr)
+            | Instruction::F64Load(instr)
+            | Instruction::I32Load8s(instr)
+            | Instruction::I32Load8u(instr)
+            | Instruction::I32Load16s(instr)
+            | Instruction::I32Load16u(instr)
+            | Instruction::I64Load8s(instr)
+            | Instruction::I64Load8u(instr)
+            | Instruction::I64Load16s(instr)
+            | Instruction::I64Load16u(instr)
+            | Instruction::I64Load32s(instr)
+            | Instruction::I64Load32u(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.ptr)
+            }
+            Instruction::I32LoadAt(instr)
+            | Instruction::I64LoadAt(instr)
+            | Instruction::F32LoadAt(instr)
+            | Instruction::F64LoadAt(instr)
+            | Instruction::I32Load8sAt(instr)
+            | Instruction::I32Load8uAt(instr)
+            | Instruction::I32Load16sAt(instr)
+            | Instruction::I32Load16uAt(instr)
+            | Instruction::I64Load8sAt(instr)
+            | Instruction::I64Load8uAt(instr)
+            | Instruction::I64Load16sAt(instr)
+            | Instruction::I64Load16uAt(instr)
+            | Instruction::I64Load32sAt(instr)
+            | Instruction::I64Load32uAt(instr) => self.write(instr.result),
+            Instruction::I32LoadOffset16(instr)
+            | Instruction::I64LoadOffset16(instr)
+            | Instruction::F32LoadOffset16(instr)
+            | Instruction::F64LoadOffset16(instr)
+            | Instruction::I32Load8sOffset16(instr)
+            | Instruction::I32Load8uOffset16(instr)
+            | Instruction::I32Load16sOffset16(instr)
+            | Instruction::I32Load16uOffset16(instr)
+            | Instruction::I64Load8sOffset16(instr)
+            | Instruction::I64Load8uOffset16(instr)
+            | Instruction::I64Load16sOffset16(instr)
+            | Instruction::I64Load16uOffset16(instr)
+            | Instruction::I64Load32sOffset16(instr)
+            | Instruction::I64Load32uOffset16(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.ptr)
+            }
+            Instruction::I32Store(instr)
+            | Instruction::I32Store8(instr)
+            | Instruction::I32Store16(instr)
+            | Instruction::I64Store(instr)
+            | Instruction::I64Store8(instr)
+            | Instruction::I64Store16(instr)
+            | Instruction::I64Store32(instr)
+            | Instruction::F32Store(instr)
+            | Instruction::F64Store(instr) => self.read(instr.ptr),
+            Instruction::I32StoreOffset16(instr)
+            | Instruction::I32Store8Offset16(instr)
+            | Instruction::I32Store16Offset16(instr)
+            | Instruction::I64StoreOffset16(instr)
+            | Instruction::I64Store8Offset16(instr)
+            | Instruction::I64Store16Offset16(instr)
+            | Instruction::I64Store32Offset16(instr)
+            | Instruction::F32StoreOffset16(instr)
+            | Instruction::F64StoreOffset16(instr) => {
+                self.read(instr.ptr)?;
+                self.read(instr.value)
+            }
+            Instruction::I32StoreOffset16Imm16(instr)
+            | Instruction::I64Store32Offset16Imm16(instr) => self.read(instr.ptr),
+            Instruction::I32Store8Offset16Imm(instr) | Instruction::I64Store8Offset16Imm(instr) => {
+                self.read(instr.ptr)
+            }
+            Instruction::I32Store16Offset16Imm(instr)
+            | Instruction::I64Store16Offset16Imm(instr) => self.read(instr.ptr),
+            Instruction::I64StoreOffset16Imm16(instr) => self.read(instr.ptr),
+            Instruction::I32StoreAt(instr)
+            | Instruction::I32Store8At(instr)
+            | Instruction::I32Store16At(instr)
+            | Instruction::I64StoreAt(instr)
+            | Instruction::I64Store8At(instr)
+            | Instruction::I64Store16At(instr)
+            | Instruction::I64Store32At(instr)
+            | Instruction::F32StoreAt(instr)
+            | Instruction::F64StoreAt(instr) => self.read(instr.value),
+            Instruction::I32Eq(instr)
+            | Instruction::I64Eq(instr)
+            | Instruction::I32Ne(instr)
+            | Instruction::I64Ne(instr)
+            | Instruction::I32LtS(instr)
+            | Instruction::I32LtU(instr)
+            | Instruction::I64LtS(instr)
+            | Instruction::I64LtU(instr)
+            | Instruction::I32GtS(instr)
+            | Instruction::I32GtU(instr)
+            | Instruction::I64GtS(instr)
+            | Instruction::I64GtU(instr)
+            | Instruction::I32LeS(instr)
+            | Instruction::I32LeU(instr)
+            | Instruction::I64LeS(instr)
+            | Instruction::I64LeU(instr)
+            | Instruction::I32GeS(instr)
+            | Instruction::I32GeU(instr)
+            | Instruction::I64GeS(instr)
+            | Instruction::I64GeU(instr)
+            | Instruction::F32Eq(instr)
+            | Instruction::F64Eq(instr)
+            | Instruction::F32Ne(instr)
+            | Instruction::F64Ne(instr)
+            | Instruction::F32Lt(instr)
+            | Instruction::F64Lt(instr)
+            | Instruction::F32Le(instr)
+            | Instruction::F64Le(instr)
+            | Instruction::F32Gt(instr)
+            | Instruction::F64Gt(instr)
+            | Instruction::F32Ge(instr)
+            | Instruction::F64Ge(instr)
+            | Instruction::I32Add(instr)
+            | Instruction::I64Add(instr)
+            | Instruction::I32Sub(instr)
+            | Instruction::I64Sub(instr)
+            | Instruction::I32Mul(instr)
+            | Instruction::I64Mul(instr)
+            | Instruction::I32DivS(instr)
+            | Instruction::I64DivS(instr)
+            | Instruction::I32DivU(instr)
+            | Instruction::I64DivU(instr)
+            | Instruction::I32RemS(instr)
+            | Instruction::I64RemS(instr)
+            | Instruction::I32RemU(instr)
+            | Instruction::I64RemU(instr)
+            | Instruction::I32CheckedAdd(instr)
+            | Instruction::I32CheckedSub(instr)
+            | Instruction::I32CheckedMul(instr)
+            | Instruction::I64CheckedAdd(instr)
+            | Instruction::I64CheckedSub(instr)
+            | Instruction::I64CheckedMul(instr)
+            | Instruction::I32And(instr)
+            | Instruction::I32AndEqz(instr)
+            | Instruction::I64And(instr)
+            | Instruction::I32Or(instr)
+            | Instruction::I32OrEqz(instr)
+            | Instruction::I64Or(instr)
+            | Instruction::I32Xor(instr)
+            | Instruction::I32XorEqz(instr)
+            | Instruction::I64Xor(instr)
+            | Instruction::I32Shl(instr)
+            | Instruction::I64Shl(instr)
+            | Instruction::I32ShrU(instr)
+            | Instruction::I64ShrU(instr)
+            | Instruction::I32ShrS(instr)
+            | Instruction::I64ShrS(instr)
+            | Instruction::I32Rotl(instr)
+            | Instruction::I64Rotl(instr)
+            | Instruction::I32Rotr(instr)
+            | Instruction::I64Rotr(instr)
+            | Instruction::F32Add(instr)
+            | Instruction::F64Add(instr)
+            | Instruction::F32Sub(instr)
+            | Instruction::F64Sub(instr)
+            | Instruction::F32Mul(instr)
+            | Instruction::F64Mul(instr)
+            | Instruction::F32Div(instr)
+            | Instruction::F64Div(instr)
+            | Instruction::F32Min(instr)
+            | Instruction::F64Min(instr)
+            | Instruction::F32Max(instr)
+            | Instruction::F64Max(instr)
+            | Instruction::F32Copysign(instr)
+            | Instruction::F64Copysign(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.lhs)?;
+                self.read(instr.rhs)
+            }
+            Instruction::I32EqImm16(instr)
+            | Instruction::I32NeImm16(instr)
+            | Instruction::I32LtSImm16(instr)
+            | Instruction::I32GtSImm16(instr)
+            | Instruction::I32LeSImm16(instr)
+            | Instruction::I32GeSImm16(instr)
+            | Instruction::I32AddImm16(instr)
+            | Instruction::I32SubImm16(instr)
+            | Instruction::I32SubImm16Rev(instr)
+            | Instruction::I32MulImm16(instr)
+            | Instruction::I32DivSImm16Rev(instr)
+            | Instruction::I32RemSImm16Rev(instr)
+            | Instruction::I32AndEqzImm16(instr)
+            | Instruction::I32AndImm16(instr)
+            | Instruction::I32OrEqzImm16(instr)
+            | Instruction::I32OrImm16(instr)
+            | Instruction::I32XorEqzImm16(instr)
+            | Instruction::I32XorImm16(instr)
+            | Instruction::I32ShlImm(instr)
+            | Instruction::I32ShlImm16Rev(instr)
+            | Instruction::I32ShrUImm(instr)
+            | Instruction::I32ShrUImm16Rev(instr)
+            | Instruction::I32ShrSImm(instr)
+            | Instruction::I32ShrSImm16Rev(instr)
+            | Instruction::I32RotlImm(instr)
+            | Instruction::I32RotlImm16Rev(instr)
+            | Instruction::I32RotrImm(instr)
+            | Instruction::I32RotrImm16Rev(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.reg_in)
+            }
+            Instruction::I64EqImm16(instr)
+            | Instruction::I64NeImm16(instr)
+            | Instruction::I64LtSImm16(instr)
+            | Instruction::I64GtSImm16(instr)
+            | Instruction::I64LeSImm16(instr)
+            | Instruction::I64GeSImm16(instr)
+            | Instruction::I64AddImm16(instr)
+            | Instruction::I64SubImm16(instr)
+            | Instruction::I64SubImm16Rev(instr)
+            | Instruction::I64MulImm16(instr)
+            | Instruction::I64DivSImm16Rev(instr)
+            | Instruction::I64RemSImm16Rev(instr)
+            | Instruction::I64AndImm16(instr)
+            | Instruction::I64OrImm16(instr)
+            | Instruction::I64XorImm16(instr)
+            | Instruction::I64ShlImm(instr)
+            | Instruction::I64ShlImm16Rev(instr)
+            | Instruction::I64ShrUImm(instr)
+            | Instruction::I64ShrUImm16Rev(instr)
+            | Instruction::I64ShrSImm(instr)
+            | Instruction::I64ShrSImm16Rev(instr)
+            | Instruction::I64RotlImm(instr)
+            | Instruction::I64RotlImm16Rev(instr)
+            | Instruction::I64RotrImm(instr)
+            | Instruction::I64RotrImm16Rev(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.reg_in)
+            }
+            Instruction::I32LtUImm16(instr)
+            | Instruction::I32GtUImm16(instr)
+            | Instruction::I32LeUImm16(instr)
+            | Instruction::I32GeUImm16(instr)
+            | Instruction::I32DivUImm16Rev(instr)
+            | Instruction::I32RemUImm16Rev(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.reg_in)
+            }
+            Instruction::I64LtUImm16(instr)
+            | Instruction::I64GtUImm16(instr)
+            | Instruction::I64LeUImm16(instr)
+            | Instruction::I64GeUImm16(instr)
+            | Instruction::I64DivUImm16Rev(instr)
+            | Instruction::I64RemUImm16Rev(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.reg_in)
+            }
+            Instruction::I32DivSImm16(instr) | Instruction::I32RemSImm16(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.reg_in)
+            }
+            Instruction::I64DivSImm16(instr) | Instruction::I64RemSImm16(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.reg_in)
+            }
+            Instruction::I32DivUImm16(instr) | Instruction::I32RemUImm16(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.reg_in)
+            }
+            Instruction::I64DivUImm16(instr) | Instruction::I64RemUImm16(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.reg_in)
+            }
+            Instruction::F32CopysignImm(instr) | Instruction::F64CopysignImm(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.reg_in)
+            }
+            Instruction::I32Clz(instr)
+            | Instruction::I64Clz(instr)
+            | Instruction::I32Ctz(instr)
+            | Instruction::I64Ctz(instr)
+            | Instruction::I32Popcnt(instr)
+            | Instruction::I64Popcnt(instr)
+            | Instruction::F32Abs(instr)
+            | Instruction::F64Abs(instr)
+            | Instruction::F32Neg(instr)
+            | Instruction::F64Neg(instr)
+            | Instruction::F32Ceil(instr)
+            | Instruction::F64Ceil(instr)
+            | Instruction::F32Floor(instr)
+            | Instruction::F64Floor(instr)
+            | Instruction::F32Trunc(instr)
+            | Instruction::F64Trunc(instr)
+            | Instruction::F32Nearest(instr)
+            | Instruction::F64Nearest(instr)
+            | Instruction::F32Sqrt(instr)
+            | Instruction::F64Sqrt(instr)
+            | Instruction::I32WrapI64(instr)
+            | Instruction::I64ExtendI32S(instr)
+            | Instruction::I64ExtendI32U(instr)
+            | Instruction::I32TruncF32S(instr)
+            | Instruction::I32TruncF32U(instr)
+            | Instruction::I32TruncF64S(instr)
+            | Instruction::I32TruncF64U(instr)
+            | Instruction::I64TruncF32S(instr)
+            | Instruction::I64TruncF32U(instr)
+            | Instruction::I64TruncF64S(instr)
+            | Instruction::I64TruncF64U(instr)
+            | Instruction::I32TruncSatF32S(instr)
+            | Instruction::I32TruncSatF32U(instr)
+            | Instruction::I32TruncSatF64S(instr)
+            | Instruction::I32TruncSatF64U(instr)
+            | Instruction::I64TruncSatF32S(instr)
+            | Instruction::I64TruncSatF32U(instr)
+            | Instruction::I64TruncSatF64S(instr)
+            | Instruction::I64TruncSatF64U(instr)
+            | Instruction::I32Extend8S(instr)
+            | Instruction::I32Extend16S(instr)
+            | Instruction::I64Extend8S(instr)
+            | Instruction::I64Extend16S(instr)
+            | Instruction::I64Extend32S(instr)
+            | Instruction::F32DemoteF64(instr)
+            | Instruction::F64PromoteF32(instr)
+            | Instruction::F32ConvertI32S(instr)
+            | Instruction::F32ConvertI32U(instr)
+            | Instruction::F32ConvertI64S(instr)
+            | Instruction::F32ConvertI64U(instr)
+            | Instruction::F64ConvertI32S(instr)
+            | Instruction::F64ConvertI32U(instr)
+            | Instruction::F64ConvertI64S(instr)
+            | Instruction::F64ConvertI64U(instr) => {
+                self.write(instr.result)?;
+                self.read(instr.input)
+            }
+            Instruction::TableIdx(_)
+            | Instruction::DataSegmentIdx(_)
+            | Instruction::ElementSegmentIdx(_)
+            | Instruction::Const32(_)
+            | Instruction::I64Const32(_)
+            | Instruction::F64Const32(_)
+            | Instruction::Register(_)
+            | Instruction::Register2(_)
+            | Instruction::Register3(_)
+            | Instruction::RegisterList(_)
+            | Instruction::CallIndirectParams(_)
+            | Instruction::CallIndirectParamsImm16(_) => Err(BytecodeErrorKind::UnexpectedParam),
+        }
+    }
+
+    /// Validates the parameter word `param` of an instruction.
+    fn validate_param(&self, param: &Instruction) -> Result<(), BytecodeErrorKind> {
+        match *param {
+            Instruction::Register(register) => self.read(register),
+            Instruction::Register2(registers) => self.read_all(&registers),
+            Instruction::Register3(registers) | Instruction::RegisterList(registers) => {
+                self.read_all(&registers)
+            }
+            Instruction::TableIdx(table) => self.table(table),
+            Instruction::DataSegmentIdx(segment) => self.data_segment(segment),
+            Instruction::ElementSegmentIdx(segment) => self.element_segment(segment),
+            Instruction::CallIndirectParams(params) => {
+                self.table(params.table)?;
+                self.read(params.index)
+            }
+            Instruction::CallIndirectParamsImm16(params) => self.table(params.table),
+            Instruction::SelectImm32 {
+                result_or_condition,
+                ..
+            }
+            | Instruction::SelectI64Imm32 {
+                result_or_condition,
+                ..
+            }
+            | Instruction::SelectF64Imm32 {
+                result_or_condition,
+                ..
+            } => self.read(result_or_condition),
+            _ => Ok(()),
+        }
+    }
+
+    /// Validates that `register` can be read.
+    fn read(&self, register: Register) -> Result<(), BytecodeErrorKind> {
+        let index = register.to_i16();
+        let in_bounds = match usize::try_from(index) {
+            Ok(cell) => cell < self.len_cells,
+            Err(_) => usize::from(index.unsigned_abs()) <= self.len_consts,
+        };
+        if !in_bounds {
+            return Err(BytecodeErrorKind::RegisterOutOfBounds);
+        }
+        Ok(())
+    }
+
+    /// Validates that `register` can be written.
+    fn write(&self, register: Register) -> Result<(), BytecodeErrorKind> {
+        if register.is_const() {
+            return Err(BytecodeErrorKind::ConstResult);
+        }
+        self.read(register)
+    }
+
+    /// Validates that all `registers` can be read.
+    fn read_all(&self, registers: &[Register]) -> Result<(), BytecodeErrorKind> {
+        registers
+            .iter()
+            .try_for_each(|register| self.read(*register))
+    }
+
+    /// Validates that all `len` registers of `span` can be read.
+    fn read_span(&self, span: RegisterSpan, len: usize) -> Result<(), BytecodeErrorKind> {
+        let Some(last) = len.checked_sub(1) else {
+            return Ok(());
+        };
+        let last = i16::try_from(last)
+            .ok()
+            .and_then(|last| span.head().to_i16().checked_add(last))
+            .ok_or(BytecodeErrorKind::RegisterOutOfBounds)?;
+        self.read(span.head())?;
+        self.read(Register::from_i16(last))
+    }
+
+    /// Validates that all `len` registers of `span` can be written.
+    fn write_span(&self, span: RegisterSpan, len: usize) -> Result<(), BytecodeErrorKind> {
+        if len != 0 && span.head().is_const() {
+            return Err(BytecodeErrorKind::ConstResult);
+        }
+        self.read_span(span, len)
+    }
+
+    /// Validates that all registers of `values` can be read.
+    ///
+    /// # Note
+    ///
+    /// A [`RegisterSpanIter`] may have been constructed with its end before its start
+    /// which would make the Wasmi executor iterate over all registers.
+    fn read_span_iter(&self, values: RegisterSpanIter) -> Result<(), BytecodeErrorKind> {
+        let span = values.span();
+        let end = span
+            .head()
+            .to_i16()
+            .checked_add_unsigned(values.len_as_u16())
+            .map(Register::from_i16);
+        if end.map(|end| RegisterSpanIter::from_raw_parts(span.head(), end)) != Some(values) {
+            return Err(BytecodeErrorKind::RegisterOutOfBounds);
+        }
+        self.read_span(span, values.len())
+    }
+
+    /// Validates that the function returns `len_results` values.
+    fn returns(&self, len_results: usize) -> Result<(), BytecodeErrorKind> {
+        if self.func_type.results().len() != len_results {
+            return Err(BytecodeErrorKind::SignatureMismatch);
+        }
+        Ok(())
+    }
+
+    /// Validates a call to `callee` with `len_params` parameters storing its results in `results`.
+    fn call(
+        &self,
+        results: RegisterSpan,
+        callee: FuncType,
+        len_params: usize,
+    ) -> Result<(), BytecodeErrorKind> {
+        if callee.params().len() != len_params {
+            return Err(BytecodeErrorKind::SignatureMismatch);
+        }
+        self.write_span(results, callee.results().len())
+    }
+
+    /// Validates a tail call to `callee` with `len_params` parameters.
+    fn return_call(&self, callee: FuncType, len_params: usize) -> Result<(), BytecodeErrorKind> {
+        if callee.params().len() != len_params || callee.results() != self.func_type.results() {
+            return Err(BytecodeErrorKind::SignatureMismatch);
+        }
+        Ok(())
+    }
+
+    /// Validates that `values` are exactly the results of the preceding `call` instruction.
+    ///
+    /// # Note
+    ///
+    /// This is required for [`Instruction::ReturnForward`] since calls returning to it
+    /// forward their results to the caller of the function without checking its `values`.
+    fn forwarded_call(
+        &self,
+        values: RegisterSpanIter,
+        call: Option<&Instruction>,
+    ) -> Result<(), BytecodeErrorKind> {
+        let (results, callee) = match call {
+            Some(
+                Instruction::CallInternal0 { results, func }
+                | Instruction::CallInternal { results, func },
+            ) => (*results, self.internal_func_type_of(*func)?),
+            Some(
+                Instruction::CallImported0 { results, func }
+                | Instruction::CallImported { results, func },
+            ) => (*results, self.func_type_of(*func)?),
+            Some(
+                Instruction::CallIndirect0 { results, func_type }
+                | Instruction::CallIndirect { results, func_type },
+            ) => (*results, self.signature(*func_type)?),
+            _ => return Err(BytecodeErrorKind::SignatureMismatch),
+        };
+        if results != values.span() || callee.results().len() != values.len() {
+            return Err(BytecodeErrorKind::SignatureMismatch);
+        }
+        Ok(())
+    }
+
+    /// Returns the [`FuncType`] of the function at `func`.
+    fn func_type_of(&self, func: FuncIdx) -> Result<FuncType, BytecodeErrorKind> {
+        let index = func.to_u32();
+        if index as usize >= self.header.len_funcs() {
+            return Err(BytecodeErrorKind::IndexOutOfBounds);
+        }
+        let func_type = self.header.get_type_of_func(module::FuncIdx::from(index));
+        Ok(self.engine.resolve_func_type(func_type, FuncType::clone))
+    }
+
+    /// Returns the [`FuncType`] of the [`CompiledFunc`] if it belongs to the Wasm module.
+    fn internal_func_type_of(&self, func: CompiledFunc) -> Result<FuncType, BytecodeErrorKind> {
+        let index = self
+            .header
+            .get_func_index(func)
+            .ok_or(BytecodeErrorKind::IndexOutOfBounds)?;
+        let func_type = self.header.get_type_of_func(index);
+        Ok(self.engine.resolve_func_type(func_type, FuncType::clone))
+    }
+
+    /// Returns the [`FuncType`] at `func_type`.
+    fn signature(&self, func_type: SignatureIdx) -> Result<FuncType, BytecodeErrorKind> {
+        let index = func_type.to_u32();
+        if index as usize >= self.header.len_func_types() {
+            return Err(BytecodeErrorKind::IndexOutOfBounds);
+        }
+        let func_type = self.header.get_func_type(FuncTypeIdx::from(index));
+        Ok(self.engine.resolve_func_type(func_type, FuncType::clone))
+    }
+
+    /// Validates that the function at `func` exists.
+    fn func(&self, func: FuncIdx) -> Result<(), BytecodeErrorKind> {
+        check_index(func.to_u32(), self.header.len_funcs())
+    }
+
+    /// Validates that the global variable at `global` exists.
+    fn global(&self, global: GlobalIdx) -> Result<(), BytecodeErrorKind> {
+        check_index(global.to_u32(), self.header.len_globals())
+    }
+
+    /// Validates that the global variable at `global` exists and is mutable.
+    fn global_mut(&self, global: GlobalIdx) -> Result<(), BytecodeErrorKind> {
+        self.global(global)?;
+        let global_type = self
+            .header
+            .get_type_of_global(module::GlobalIdx::from(global.to_u32()));
+        if !global_type.mutability().is_mut() {
+            return Err(BytecodeErrorKind::ImmutableGlobal);
+        }
+        Ok(())
+    }
+
+    /// Validates that the table at `table` exists.
+    fn table(&self, table: TableIdx) -> Result<(), BytecodeErrorKind> {
+        check_index(table.to_u32(), self.header.len_tables())
+    }
+
+    /// Validates that the default linear memory exists.
+    fn memory(&self) -> Result<(), BytecodeErrorKind> {
+        check_index(0, self.header.len_memories())
+    }
+
+    /// Validates that the data segment at `segment` exists.
+    fn data_segment(&self, segment: DataSegmentIdx) -> Result<(), BytecodeErrorKind> {
+        check_index(segment.to_u32(), self.len_data_segments as usize)
+    }
+
+    /// Validates that the element segment at `segment` exists.
+    fn element_segment(&self, segment: ElementSegmentIdx) -> Result<(), BytecodeErrorKind> {
+        check_index(segment.to_u32(), self.header.len_element_segments())
+    }
+}
+
+/// Validates that `index` is less than `len`.
+fn check_index(index: u32, len: usize) -> Result<(), BytecodeErrorKind> {
+    if index as usize >= len {
+        return Err(BytecodeErrorKind::IndexOutOfBounds);
+    }
+    Ok(())
+}
+
+/// Returns the number of [`Register`] in the register list parameter words `params`.
+fn len_register_list(params: &[Instruction]) -> usize {
+    params
+        .iter()
+        .map(|param| match param {
+            Instruction::Register(_) => 1,
+            Instruction::Register2(_) => 2,
+            Instruction::Register3(_) | Instruction::RegisterList(_) => 3,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Returns `true` if execution never continues with the instruction after `instr`.
+fn is_terminal(instr: &Instruction) -> bool {
+    matches!(
+        instr,
+        Instruction::Trap(_)
+            | Instruction::Return
+            | Instruction::ReturnReg { .. }
+            | Instruction::ReturnReg2 { .. }
+            | Instruction::ReturnReg3 { .. }
+            | Instruction::ReturnImm32 { .. }
+            | Instruction::ReturnI64Imm32 { .. }
+            | Instruction::ReturnF64Imm32 { .. }
+            | Instruction::ReturnSpan { .. }
+            | Instruction::ReturnMany { .. }
+            | Instruction::ReturnForward { .. }
+            | Instruction::Branch { .. }
+            | Instruction::BranchTable { .. }
+            | Instruction::BranchTableSparse { .. }
+            | Instruction::ReturnCallInternal0 { .. }
+            | Instruction::ReturnCallInternal { .. }
+            | Instruction::ReturnCallImported0 { .. }
+            | Instruction::ReturnCallImported { .. }
+            | Instruction::ReturnCallIndirect0 { .. }
+            | Instruction::ReturnCallIndirect { .. }
+    )
+}
+
+/// Returns `true` if `instr` accesses the default linear memory.
+fn uses_default_memory(instr: &Instruction) -> bool {
+    matches!(
+        instr,
+        Instruction::MemorySize { .. }
+            | Instruction::MemoryGrow { .. }
+            | Instruction::MemoryGrowBy { .. }
+            | Instruction::MemoryCopy { .. }
+            | Instruction::MemoryCopyTo { .. }
+            | Instruction::MemoryCopyFrom { .. }
+            | Instruction::MemoryCopyFromTo { .. }
+            | Instruction::MemoryCopyExact { .. }
+            | Instruction::MemoryCopyToExact { .. }
+            | Instruction::MemoryCopyFromExact { .. }
+            | Instruction::MemoryCopyFromToExact { .. }
+            | Instruction::MemoryFill { .. }
+            | Instruction::MemoryFillAt { .. }
+            | Instruction::MemoryFillImm { .. }
+            | Instruction::MemoryFillExact { .. }
+            | Instruction::MemoryFillAtImm { .. }
+            | Instruction::MemoryFillAtExact { .. }
+            | Instruction::MemoryFillImmExact { .. }
+            | Instruction::MemoryFillAtImmExact { .. }
+            | Instruction::MemoryInit { .. }
+            | Instruction::MemoryInitTo { .. }
+            | Instruction::MemoryInitFrom { .. }
+            | Instruction::MemoryInitFromTo { .. }
+            | Instruction::MemoryInitExact { .. }
+            | Instruction::MemoryInitToExact { .. }
+            | Instruction::MemoryInitFromExact { .. }
+            | Instruction::MemoryInitFromToExact { .. }
+            | Instruction::I32Load(_)
+            | Instruction::I32LoadAt(_)
+            | Instruction::I32LoadOffset16(_)
+            | Instruction::I64Load(_)
+            | Instruction::I64LoadAt(_)
+            | Instruction::I64LoadOffset16(_)
+            | Instruction::F32Load(_)
+            | Instruction::F32LoadAt(_)
+            | Instruction::F32LoadOffset16(_)
+            | Instruction::F64Load(_)
+            | Instruction::F64LoadAt(_)
+            | Instruction::F64LoadOffset16(_)
+            | Instruction::I32Load8s(_)
+            | Instruction::I32Load8sAt(_)
+            | Instruction::I32Load8sOffset16(_)
+            | Instruction::I32Load8u(_)
+            | Instruction::I32Load8uAt(_)
+            | Instruction::I32Load8uOffset16(_)
+            | Instruction::I32Load16s(_)
+            | Instruction::I32Load16sAt(_)
+            | Instruction::I32Load16sOffset16(_)
+            | Instruction::I32Load16u(_)
+            | Instruction::I32Load16uAt(_)
+            | Instruction::I32Load16uOffset16(_)
+            | Instruction::I64Load8s(_)
+            | Instruction::I64Load8sAt(_)
+            | Instruction::I64Load8sOffset16(_)
+            | Instruction::I64Load8u(_)
+            | Instruction::I64Load8uAt(_)
+            | Instruction::I64Load8uOffset16(_)
+            | Instruction::I64Load16s(_)
+            | Instruction::I64Load16sAt(_)
+            | Instruction::I64Load16sOffset16(_)
+            | Instruction::I64Load16u(_)
+            | Instruction::I64Load16uAt(_)
+            | Instruction::I64Load16uOffset16(_)
+            | Instruction::I64Load32s(_)
+            | Instruction::I64Load32sAt(_)
+            | Instruction::I64Load32sOffset16(_)
+            | Instruction::I64Load32u(_)
+            | Instruction::I64Load32uAt(_)
+            | Instruction::I64Load32uOffset16(_)
+            | Instruction::I32Store(_)
+            | Instruction::I32StoreOffset16(_)
+            | Instruction::I32StoreOffset16Imm16(_)
+            | Instruction::I32StoreAt(_)
+            | Instruction::I32StoreAtImm16(_)
+            | Instruction::I32Store8(_)
+            | Instruction::I32Store8Offset16(_)
+            | Instruction::I32Store8Offset16Imm(_)
+            | Instruction::I32Store8At(_)
+            | Instruction::I32Store8AtImm(_)
+            | Instruction::I32Store16(_)
+            | Instruction::I32Store16Offset16(_)
+            | Instruction::I32Store16Offset16Imm(_)
+            | Instruction::I32Store16At(_)
+            | Instruction::I32Store16AtImm(_)
+            | Instruction::I64Store(_)
+            | Instruction::I64StoreOffset16(_)
+            | Instruction::I64StoreOffset16Imm16(_)
+            | Instruction::I64StoreAt(_)
+            | Instruction::I64StoreAtImm16(_)
+            | Instruction::I64Store8(_)
+            | Instruction::I64Store8Offset16(_)
+            | Instruction::I64Store8Offset16Imm(_)
+            | Instruction::I64Store8At(_)
+            | Instruction::I64Store8AtImm(_)
+            | Instruction::I64Store16(_)
+            | Instruction::I64Store16Offset16(_)
+            | Instruction::I64Store16Offset16Imm(_)
+            | Instruction::I64Store16At(_)
+            | Instruction::I64Store16AtImm(_)
+            | Instruction::I64Store32(_)
+            | Instruction::I64Store32Offset16(_)
+            | Instruction::I64Store32Offset16Imm16(_)
+            | Instruction::I64Store32At(_)
+            | Instruction::I64Store32AtImm16(_)
+            | Instruction::F32Store(_)
+            | Instruction::F32StoreOffset16(_)
+            | Instruction::F32StoreAt(_)
+            | Instruction::F64Store(_)
+            | Instruction::F64StoreOffset16(_)
+            | Instruction::F64StoreAt(_)
+    )
+}
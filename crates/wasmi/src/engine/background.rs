@@ -0,0 +1,177 @@
+use crate::{Engine, Error, Module};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    mem::replace,
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+/// A compilation job spawned by [`Engine::compile_in_background_with`].
+pub type CompilationJob = Box<dyn FnOnce() + Send + 'static>;
+
+/// The status of a background compilation queried via [`CompilationHandle::poll`].
+#[derive(Debug)]
+pub enum CompilationStatus {
+    /// The compilation is still in progress.
+    ///
+    /// Holds the ratio of translated function bodies in the range `0.0..=1.0`.
+    Pending(f32),
+    /// The compilation finished successfully.
+    Done(Module),
+    /// The compilation failed.
+    Failed(Error),
+}
+
+/// A handle to a [`Module`] compiled in the background.
+///
+/// # Note
+///
+/// Dropping the [`CompilationHandle`] cancels the background compilation.
+/// The compilation checks for cancellation before translating each function body.
+#[derive(Debug)]
+pub struct CompilationHandle {
+    /// The state shared with the background compilation.
+    shared: Arc<Shared>,
+}
+
+/// The state shared between a [`CompilationHandle`] and its background compilation.
+#[derive(Debug, Default)]
+struct Shared {
+    /// The result of the compilation once it finished.
+    state: Mutex<State>,
+    /// Notified once the compilation finished.
+    finished: Condvar,
+    /// The number of translated function bodies.
+    translated: AtomicU32,
+    /// The total number of function bodies.
+    total: AtomicU32,
+    /// Is `true` if the [`CompilationHandle`] has been dropped.
+    cancelled: AtomicBool,
+}
+
+/// The state of a background compilation.
+#[derive(Debug, Default)]
+enum State {
+    /// The compilation is still in progress.
+    #[default]
+    Pending,
+    /// The compilation finished with its result.
+    Finished(Result<Module, Error>),
+    /// The result of the compilation has already been taken.
+    Taken,
+}
+
+impl Shared {
+    /// Locks the [`State`] of the compilation.
+    ///
+    /// # Note
+    ///
+    /// The [`State`] is never left inconsistent, therefore a poisoned lock is recovered.
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the ratio of translated function bodies in the range `0.0..=1.0`.
+    fn progress(&self) -> f32 {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 {
+            return 0.0;
+        }
+        let translated = self.translated.load(Ordering::Relaxed).min(total);
+        translated as f32 / total as f32
+    }
+
+    /// Compiles `wasm` using `engine` and stores the result.
+    fn compile(self: Arc<Self>, engine: &Engine, wasm: &[u8]) {
+        let observer = self.clone();
+        let result = Module::new_with_progress(engine, wasm, move |translated, total| {
+            observer.total.store(total, Ordering::Relaxed);
+            observer.translated.store(translated, Ordering::Relaxed);
+            !observer.cancelled.load(Ordering::Relaxed)
+        });
+        *self.lock() = State::Finished(result);
+        self.finished.notify_all();
+    }
+}
+
+impl CompilationHandle {
+    /// Returns the [`CompilationStatus`] of the background compilation without blocking.
+    ///
+    /// # Panics
+    ///
+    /// If the result of the compilation has already been returned.
+    pub fn poll(&mut self) -> CompilationStatus {
+        let mut state = self.shared.lock();
+        match *state {
+            State::Pending => CompilationStatus::Pending(self.shared.progress()),
+            State::Finished(_) => match replace(&mut *state, State::Taken) {
+                State::Finished(Ok(module)) => CompilationStatus::Done(module),
+                State::Finished(Err(error)) => CompilationStatus::Failed(error),
+                _ => unreachable!(),
+            },
+            State::Taken => panic!("polled background compilation after it returned its result"),
+        }
+    }
+
+    /// Blocks until the background compilation finished and returns its result.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm binary fails to parse, validate or translate.
+    ///
+    /// # Panics
+    ///
+    /// If the result of the compilation has already been returned by [`CompilationHandle::poll`].
+    pub fn wait(self) -> Result<Module, Error> {
+        let mut state = self.shared.lock();
+        while matches!(*state, State::Pending) {
+            state = self
+                .shared
+                .finished
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        match replace(&mut *state, State::Taken) {
+            State::Finished(result) => result,
+            _ => panic!("waited for background compilation after it returned its result"),
+        }
+    }
+}
+
+impl Drop for CompilationHandle {
+    fn drop(&mut self) {
+        self.shared.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Engine {
+    /// Compiles the Wasm binary `wasm` into a [`Module`] on a new background thread.
+    ///
+    /// # Note
+    ///
+    /// - The resulting [`Module`] is the same as if compiled via [`Module::new`].
+    /// - Use [`Engine::compile_in_background_with`] in order to compile on a thread pool.
+    pub fn compile_in_background(&self, wasm: Vec<u8>) -> CompilationHandle {
+        self.compile_in_background_with(wasm, |job| {
+            std::thread::spawn(job);
+        })
+    }
+
+    /// Compiles the Wasm binary `wasm` into a [`Module`] via the [`CompilationJob`] given to `spawn`.
+    ///
+    /// # Note
+    ///
+    /// - The resulting [`Module`] is the same as if compiled via [`Module::new`].
+    /// - `spawn` is expected to run the [`CompilationJob`] on another thread, e.g. of a thread pool.
+    /// - The [`CompilationJob`] returns promptly if the [`CompilationHandle`] has been dropped.
+    pub fn compile_in_background_with<S>(&self, wasm: Vec<u8>, spawn: S) -> CompilationHandle
+    where
+        S: FnOnce(CompilationJob),
+    {
+        let shared = Arc::new(Shared::default());
+        let engine = self.clone();
+        let job = shared.clone();
+        spawn(Box::new(move || job.compile(&engine, &wasm[..])));
+        CompilationHandle { shared }
+    }
+}
@@ -5,6 +5,7 @@ use super::{
     BinInstrImm,
     BinInstrImm16,
     BranchBinOpInstr,
+    BranchBinOpInstrFallback,
     BranchBinOpInstrImm16,
     BranchOffset,
     CallIndirectParams,
@@ -221,6 +222,8 @@ constructor_for_branch_binop! {
     fn branch_f32_le() -> Self::BranchF32Le;
     fn branch_f32_gt() -> Self::BranchF32Gt;
     fn branch_f32_ge() -> Self::BranchF32Ge;
+    fn branch_f32_not_lt() -> Self::BranchF32NotLt;
+    fn branch_f32_not_le() -> Self::BranchF32NotLe;
 
     fn branch_f64_eq() -> Self::BranchF64Eq;
     fn branch_f64_ne() -> Self::BranchF64Ne;
@@ -228,6 +231,33 @@ constructor_for_branch_binop! {
     fn branch_f64_le() -> Self::BranchF64Le;
     fn branch_f64_gt() -> Self::BranchF64Gt;
     fn branch_f64_ge() -> Self::BranchF64Ge;
+    fn branch_f64_not_lt() -> Self::BranchF64NotLt;
+    fn branch_f64_not_le() -> Self::BranchF64NotLe;
+}
+
+macro_rules! constructor_for_branch_binop_fallback {
+    ( $( fn $name:ident() -> Self::$op_code:ident; )* ) => {
+        impl Instruction {
+            $(
+                #[doc = concat!("Creates a new [`Instruction::", stringify!($op_code), "`].")]
+                pub fn $name(lhs: Register, rhs: Register, offset: Register) -> Self {
+                    Self::$op_code(BranchBinOpInstrFallback::new(lhs, rhs, offset))
+                }
+            )*
+        }
+    }
+}
+constructor_for_branch_binop_fallback! {
+    fn branch_i32_eq_fallback() -> Self::BranchI32EqFallback;
+    fn branch_i32_ne_fallback() -> Self::BranchI32NeFallback;
+    fn branch_i32_lt_s_fallback() -> Self::BranchI32LtSFallback;
+    fn branch_i32_lt_u_fallback() -> Self::BranchI32LtUFallback;
+    fn branch_i32_le_s_fallback() -> Self::BranchI32LeSFallback;
+    fn branch_i32_le_u_fallback() -> Self::BranchI32LeUFallback;
+    fn branch_i32_gt_s_fallback() -> Self::BranchI32GtSFallback;
+    fn branch_i32_gt_u_fallback() -> Self::BranchI32GtUFallback;
+    fn branch_i32_ge_s_fallback() -> Self::BranchI32GeSFallback;
+    fn branch_i32_ge_u_fallback() -> Self::BranchI32GeUFallback;
 }
 
 macro_rules! constructor_for_branch_binop_imm {
@@ -363,6 +393,11 @@ impl Instruction {
         Self::ReturnSpan { values }
     }
 
+    /// Creates a new [`Instruction::ReturnForward`] from the given `values`.
+    pub fn return_forward(values: RegisterSpanIter) -> Self {
+        Self::ReturnForward { values }
+    }
+
     /// Creates a new [`Instruction::ReturnMany`] for the given [`Register`] indices.
     pub fn return_many(
         reg0: impl Into<Register>,
@@ -475,6 +510,14 @@ impl Instruction {
         }
     }
 
+    /// Creates a new [`Instruction::BranchTableSparse`] for the given `index` and `len_cases`.
+    pub fn branch_table_sparse(index: Register, len_cases: impl Into<Const32<u32>>) -> Self {
+        Self::BranchTableSparse {
+            index,
+            len_cases: len_cases.into(),
+        }
+    }
+
     /// Creates a new [`Instruction::Copy`].
     pub fn copy(result: impl Into<Register>, value: impl Into<Register>) -> Self {
         Self::Copy {
@@ -1321,6 +1364,16 @@ impl Instruction {
         }
     }
 
+    /// Creates a new [`Instruction::I64MulWideS`] for the given `results`, `lhs` and `rhs`.
+    pub fn i64_mul_wide_s(results: RegisterSpan, lhs: Register, rhs: Register) -> Self {
+        Self::I64MulWideS { results, lhs, rhs }
+    }
+
+    /// Creates a new [`Instruction::I64MulWideU`] for the given `results`, `lhs` and `rhs`.
+    pub fn i64_mul_wide_u(results: RegisterSpan, lhs: Register, rhs: Register) -> Self {
+        Self::I64MulWideU { results, lhs, rhs }
+    }
+
     constructor_for! {
         // Load
 
@@ -1596,6 +1649,13 @@ impl Instruction {
         fn i64_rem_u(binary) -> Self::I64RemU;
         fn i64_rem_u_imm16_rev(binary_u64imm16_rev) -> Self::I64RemUImm16Rev;
 
+        fn i32_checked_add(binary) -> Self::I32CheckedAdd;
+        fn i32_checked_sub(binary) -> Self::I32CheckedSub;
+        fn i32_checked_mul(binary) -> Self::I32CheckedMul;
+        fn i64_checked_add(binary) -> Self::I64CheckedAdd;
+        fn i64_checked_sub(binary) -> Self::I64CheckedSub;
+        fn i64_checked_mul(binary) -> Self::I64CheckedMul;
+
         fn i32_rem_s(binary) -> Self::I32RemS;
         fn i32_rem_s_imm16_rev(binary_i32imm16_rev) -> Self::I32RemSImm16Rev;
 
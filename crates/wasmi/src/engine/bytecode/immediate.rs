@@ -33,6 +33,16 @@ impl<T> Const16<T> {
             marker: PhantomData,
         }
     }
+
+    /// Creates a new typed [`Const16`] value from `value` without checking its invariants.
+    ///
+    /// # Note
+    ///
+    /// This is a test-only API to construct malformed Wasmi bytecode.
+    #[cfg(test)]
+    pub fn from_i16_unchecked(value: i16) -> Self {
+        Self::new(AnyConst16::from(value))
+    }
 }
 
 impl<T> Clone for Const16<T> {
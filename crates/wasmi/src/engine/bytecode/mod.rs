@@ -2,19 +2,20 @@ mod construct;
 mod immediate;
 mod provider;
 mod utils;
+mod verify;
 
 #[cfg(test)]
 mod tests;
 
-pub(crate) use self::{
+pub use self::{
     immediate::{AnyConst16, AnyConst32, Const16, Const32},
-    provider::{Provider, ProviderSliceStack, UntypedProvider},
     utils::{
         BinInstr,
         BinInstrImm,
         BinInstrImm16,
         BlockFuel,
         BranchBinOpInstr,
+        BranchBinOpInstrFallback,
         BranchBinOpInstrImm,
         BranchBinOpInstrImm16,
         BranchComparator,
@@ -40,6 +41,11 @@ pub(crate) use self::{
         TableIdx,
         UnaryInstr,
     },
+    verify::{BytecodeError, BytecodeErrorKind},
+};
+pub(crate) use self::{
+    provider::{Provider, ProviderSliceStack, UntypedProvider},
+    verify::{verify_instr_starts, verify_instrs, Encoding},
 };
 use crate::{engine::CompiledFunc, Error};
 use core::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
@@ -256,6 +262,23 @@ pub enum Instruction {
         /// The first three returned values.
         values: [Register; 3],
     },
+    /// A Wasm `return` instruction.
+    ///
+    /// # Note
+    ///
+    /// Returns the results of the call instruction immediately preceding it
+    /// as stored in the [`RegisterSpanIter`].
+    ///
+    /// # Execution
+    ///
+    /// Executes the same as [`Instruction::ReturnSpan`]. However, a called Wasm function
+    /// that returns to this instruction instead returns its results directly to the
+    /// caller of this function and returns from this function as well. This way the
+    /// results are copied only once and this instruction is skipped.
+    ReturnForward {
+        /// The results of the call instruction immediately preceding this instruction.
+        values: RegisterSpanIter,
+    },
 
     /// A conditional `return` instruction.
     ///
@@ -372,7 +395,9 @@ pub enum Instruction {
     ///
     /// This instruction fits in a single instruction word but arguably executes slower than
     /// cmp+branch instructions with a 16-bit encoded branch offset. It only ever gets encoded
-    /// and used whenever a branch offset of a cmp+branch instruction cannot be 16-bit encoded.
+    /// and used whenever a branch offset of a cmp+branch instruction cannot be 16-bit encoded
+    /// and there is no dedicated fallback instruction such as [`Instruction::BranchI32EqFallback`]
+    /// for its comparator.
     BranchCmpFallback {
         /// The left-hand side value for the comparison.
         lhs: Register,
@@ -392,6 +417,26 @@ pub enum Instruction {
         /// and 32-bit branch offset fields.
         params: Register,
     },
+    /// Variant of [`Instruction::BranchI32Eq`] with 32-bit encoded branch offset.
+    BranchI32EqFallback(BranchBinOpInstrFallback),
+    /// Variant of [`Instruction::BranchI32Ne`] with 32-bit encoded branch offset.
+    BranchI32NeFallback(BranchBinOpInstrFallback),
+    /// Variant of [`Instruction::BranchI32LtS`] with 32-bit encoded branch offset.
+    BranchI32LtSFallback(BranchBinOpInstrFallback),
+    /// Variant of [`Instruction::BranchI32LtU`] with 32-bit encoded branch offset.
+    BranchI32LtUFallback(BranchBinOpInstrFallback),
+    /// Variant of [`Instruction::BranchI32LeS`] with 32-bit encoded branch offset.
+    BranchI32LeSFallback(BranchBinOpInstrFallback),
+    /// Variant of [`Instruction::BranchI32LeU`] with 32-bit encoded branch offset.
+    BranchI32LeUFallback(BranchBinOpInstrFallback),
+    /// Variant of [`Instruction::BranchI32GtS`] with 32-bit encoded branch offset.
+    BranchI32GtSFallback(BranchBinOpInstrFallback),
+    /// Variant of [`Instruction::BranchI32GtU`] with 32-bit encoded branch offset.
+    BranchI32GtUFallback(BranchBinOpInstrFallback),
+    /// Variant of [`Instruction::BranchI32GeS`] with 32-bit encoded branch offset.
+    BranchI32GeSFallback(BranchBinOpInstrFallback),
+    /// Variant of [`Instruction::BranchI32GeU`] with 32-bit encoded branch offset.
+    BranchI32GeUFallback(BranchBinOpInstrFallback),
     /// A fused [`Instruction::I32And`] and Wasm branch instruction.
     BranchI32And(BranchBinOpInstr),
     /// A fused [`Instruction::I32And`] and Wasm branch instruction.
@@ -619,6 +664,20 @@ pub enum Instruction {
     BranchF32Gt(BranchBinOpInstr),
     /// A fused [`Instruction::F32Ge`] and Wasm branch instruction.
     BranchF32Ge(BranchBinOpInstr),
+    /// A fused negated [`Instruction::F32Lt`] and Wasm branch instruction.
+    ///
+    /// # Note
+    ///
+    /// Branches if `lhs` is not less than `rhs` which includes unordered `NaN` operands.
+    /// This is different from [`Instruction::BranchF32Ge`] which does not branch for `NaN`.
+    BranchF32NotLt(BranchBinOpInstr),
+    /// A fused negated [`Instruction::F32Le`] and Wasm branch instruction.
+    ///
+    /// # Note
+    ///
+    /// Branches if `lhs` is not less than or equal to `rhs` which includes unordered `NaN` operands.
+    /// This is different from [`Instruction::BranchF32Gt`] which does not branch for `NaN`.
+    BranchF32NotLe(BranchBinOpInstr),
 
     /// A fused [`Instruction::F64Eq`] and Wasm branch instruction.
     BranchF64Eq(BranchBinOpInstr),
@@ -633,6 +692,20 @@ pub enum Instruction {
     BranchF64Gt(BranchBinOpInstr),
     /// A fused [`Instruction::F64Ge`] and Wasm branch instruction.
     BranchF64Ge(BranchBinOpInstr),
+    /// A fused negated [`Instruction::F64Lt`] and Wasm branch instruction.
+    ///
+    /// # Note
+    ///
+    /// Branches if `lhs` is not less than `rhs` which includes unordered `NaN` operands.
+    /// This is different from [`Instruction::BranchF64Ge`] which does not branch for `NaN`.
+    BranchF64NotLt(BranchBinOpInstr),
+    /// A fused negated [`Instruction::F64Le`] and Wasm branch instruction.
+    ///
+    /// # Note
+    ///
+    /// Branches if `lhs` is not less than or equal to `rhs` which includes unordered `NaN` operands.
+    /// This is different from [`Instruction::BranchF64Gt`] which does not branch for `NaN`.
+    BranchF64NotLe(BranchBinOpInstr),
 
     /// A Wasm `br_table` instruction.
     ///
@@ -654,6 +727,34 @@ pub enum Instruction {
         /// The number of branch table targets including the default target.
         len_targets: Const32<u32>,
     },
+    /// A Wasm `br_table` instruction with mostly default targets.
+    ///
+    /// # Note
+    ///
+    /// Only the cases that do not branch to the default target are encoded.
+    /// An `index` that is not one of the cases branches to the default target.
+    ///
+    /// # Encoding
+    ///
+    /// 1. Must be followed `len_cases` times by [`Instruction::Const32`] holding
+    ///    the case indices in strictly ascending order.
+    /// 1. May be followed by one of the copy instructions.
+    /// 1. Must be followed `len_cases + 1` times by the branch targets of the cases
+    ///    in the same order and then by the default target, each any of:
+    ///
+    /// - [`Instruction::Branch`]
+    /// - [`Instruction::Return`]
+    /// - [`Instruction::ReturnReg`]
+    /// - [`Instruction::ReturnImm32`]
+    /// - [`Instruction::ReturnI64Imm32`]
+    /// - [`Instruction::ReturnF64Imm32`]
+    /// - [`Instruction::ReturnSpan`]
+    BranchTableSparse {
+        /// The register holding the index of the instruction.
+        index: Register,
+        /// The number of encoded cases excluding the default target.
+        len_cases: Const32<u32>,
+    },
 
     /// Copies `value` to `result`.
     ///
@@ -2581,6 +2682,34 @@ pub enum Instruction {
     ///
     /// Optimized variant of [`Instruction::I64Mul`] for 16-bit constant values.
     I64MulImm16(BinInstrImm16<i64>),
+    /// Signed `i64` widening multiply instruction: `results = lhs * rhs`
+    ///
+    /// # Note
+    ///
+    /// - Computes the full 128-bit product of the signed `lhs` and `rhs` operands.
+    /// - Stores the low 64 bits to `results[0]` and the high 64 bits to `results[1]`.
+    I64MulWideS {
+        /// The registers holding the low and high halves of the product.
+        results: RegisterSpan,
+        /// The register holding the left-hand side operand.
+        lhs: Register,
+        /// The register holding the right-hand side operand.
+        rhs: Register,
+    },
+    /// Unsigned `i64` widening multiply instruction: `results = lhs * rhs`
+    ///
+    /// # Note
+    ///
+    /// - Computes the full 128-bit product of the unsigned `lhs` and `rhs` operands.
+    /// - Stores the low 64 bits to `results[0]` and the high 64 bits to `results[1]`.
+    I64MulWideU {
+        /// The registers holding the low and high halves of the product.
+        results: RegisterSpan,
+        /// The register holding the left-hand side operand.
+        lhs: Register,
+        /// The register holding the right-hand side operand.
+        rhs: Register,
+    },
 
     /// `i32` singed-division instruction: `r0 = r1 / r2`
     I32DivS(BinInstr),
@@ -2728,6 +2857,55 @@ pub enum Instruction {
     /// - Required instruction since unsigned-remainder is not commutative.
     I64RemUImm16Rev(BinInstrImm16<u64>),
 
+    /// `i32` overflow-checked signed add instruction: `r0 = r1 + r2`
+    ///
+    /// # Note
+    ///
+    /// Traps with [`TrapCode::IntegerOverflow`] if the signed addition overflows.
+    ///
+    /// [`TrapCode::IntegerOverflow`]: crate::core::TrapCode::IntegerOverflow
+    I32CheckedAdd(BinInstr),
+    /// `i32` overflow-checked signed subtract instruction: `r0 = r1 - r2`
+    ///
+    /// # Note
+    ///
+    /// Traps with [`TrapCode::IntegerOverflow`] if the signed subtraction overflows.
+    ///
+    /// [`TrapCode::IntegerOverflow`]: crate::core::TrapCode::IntegerOverflow
+    I32CheckedSub(BinInstr),
+    /// `i32` overflow-checked signed multiply instruction: `r0 = r1 * r2`
+    ///
+    /// # Note
+    ///
+    /// Traps with [`TrapCode::IntegerOverflow`] if the signed multiplication overflows.
+    ///
+    /// [`TrapCode::IntegerOverflow`]: crate::core::TrapCode::IntegerOverflow
+    I32CheckedMul(BinInstr),
+    /// `i64` overflow-checked signed add instruction: `r0 = r1 + r2`
+    ///
+    /// # Note
+    ///
+    /// Traps with [`TrapCode::IntegerOverflow`] if the signed addition overflows.
+    ///
+    /// [`TrapCode::IntegerOverflow`]: crate::core::TrapCode::IntegerOverflow
+    I64CheckedAdd(BinInstr),
+    /// `i64` overflow-checked signed subtract instruction: `r0 = r1 - r2`
+    ///
+    /// # Note
+    ///
+    /// Traps with [`TrapCode::IntegerOverflow`] if the signed subtraction overflows.
+    ///
+    /// [`TrapCode::IntegerOverflow`]: crate::core::TrapCode::IntegerOverflow
+    I64CheckedSub(BinInstr),
+    /// `i64` overflow-checked signed multiply instruction: `r0 = r1 * r2`
+    ///
+    /// # Note
+    ///
+    /// Traps with [`TrapCode::IntegerOverflow`] if the signed multiplication overflows.
+    ///
+    /// [`TrapCode::IntegerOverflow`]: crate::core::TrapCode::IntegerOverflow
+    I64CheckedMul(BinInstr),
+
     /// `i32` bitwise-and instruction: `r0 = r1 & r2`
     I32And(BinInstr),
     /// Fused Wasm `i32.and` + `i32.eqz` [`Instruction`].
@@ -3156,6 +3334,10 @@ pub enum Instruction {
 
 impl Instruction {
     /// Convenience method to create a new [`Instruction::ConsumeFuel`].
+    ///
+    /// # Errors
+    ///
+    /// If `amount` does not fit into a [`BlockFuel`].
     pub fn consume_fuel(amount: u64) -> Result<Self, Error> {
         let block_fuel = BlockFuel::try_from(amount)?;
         Ok(Self::ConsumeFuel(block_fuel))
@@ -3167,7 +3349,7 @@ impl Instruction {
     ///
     /// - If `self` is not a [`Instruction::ConsumeFuel`] instruction.
     /// - If the new fuel consumption overflows the internal `u64` value.
-    pub fn bump_fuel_consumption(&mut self, delta: u64) -> Result<(), Error> {
+    pub(crate) fn bump_fuel_consumption(&mut self, delta: u64) -> Result<(), Error> {
         match self {
             Self::ConsumeFuel(block_fuel) => block_fuel.bump_by(delta),
             instr => panic!("expected Instruction::ConsumeFuel but found: {instr:?}"),
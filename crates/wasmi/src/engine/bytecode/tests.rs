@@ -9,6 +9,8 @@ fn bytecode_size() {
     assert_eq!(size_of::<BinInstrImm16<i32>>(), 6);
     assert_eq!(size_of::<BinInstrImm16<i64>>(), 6);
     assert_eq!(size_of::<Instruction>(), 8);
+    // The discriminant of `Instruction` leaves a niche for enclosing types.
+    assert_eq!(size_of::<Option<Instruction>>(), 8);
 }
 
 #[test]
@@ -49,3 +51,189 @@ fn has_overlapping_copy_spans_works() {
     assert!(has_overlapping_copy_spans(span(4), span(1), 4));
     assert!(has_overlapping_copy_spans(span(4), span(0), 5));
 }
+
+#[test]
+fn comparator_offset_param_roundtrip() {
+    let cmps = [
+        BranchComparator::I32Eq,
+        BranchComparator::I32GeU,
+        BranchComparator::I64LtS,
+        BranchComparator::F64Ge,
+        BranchComparator::F32NotLt,
+        BranchComparator::F64NotLe,
+    ];
+    let offsets = [0, 1, -1, i32::from(i16::MAX) + 1, i32::MIN, i32::MAX];
+    for cmp in cmps {
+        for offset in offsets {
+            let param = ComparatorOffsetParam::new(cmp, BranchOffset::from(offset));
+            let decoded = ComparatorOffsetParam::from_untyped(param.into()).unwrap();
+            assert_eq!(decoded, param);
+        }
+    }
+    assert!(ComparatorOffsetParam::from_u64(u64::MAX).is_none());
+}
+
+/// Asserts that verifying `instrs` fails with `kind` at instruction word `instr`.
+fn assert_verify_error(instrs: &[Instruction], instr: u32, kind: BytecodeErrorKind) {
+    let error = verify_instrs(instrs).unwrap_err();
+    assert_eq!(error.kind(), kind);
+    assert_eq!(error.instr(), instr);
+}
+
+#[test]
+fn verify_valid_encodings() {
+    let r = Register::from_i16;
+    verify_instrs(&[
+        Instruction::branch_table(r(0), 2_u32),
+        Instruction::copy(1, 0),
+        Instruction::branch(BranchOffset::from(2)),
+        Instruction::Return,
+        Instruction::select(r(0), r(1), r(2)),
+        Instruction::const32(42_i32),
+        Instruction::table_get(r(0), r(1)),
+        Instruction::table_idx(0),
+        Instruction::return_many(0, 1, 2),
+        Instruction::register_list(3, 4, 5),
+        Instruction::register2(6, 7),
+        Instruction::branch(BranchOffset::from(-10)),
+        Instruction::branch_table_sparse(r(0), 1_u32),
+        Instruction::const32(3_u32),
+        Instruction::copy(1, 0),
+        Instruction::branch(BranchOffset::from(-4)),
+        Instruction::Return,
+    ])
+    .unwrap();
+}
+
+#[test]
+fn verify_unexpected_param() {
+    assert_verify_error(
+        &[Instruction::register(0), Instruction::Return],
+        0,
+        BytecodeErrorKind::UnexpectedParam,
+    );
+}
+
+#[test]
+fn verify_missing_param() {
+    let r = Register::from_i16;
+    assert_verify_error(
+        &[Instruction::table_get(r(0), r(1)), Instruction::Return],
+        1,
+        BytecodeErrorKind::MissingParam,
+    );
+    assert_verify_error(
+        &[Instruction::table_get(r(0), r(1))],
+        1,
+        BytecodeErrorKind::MissingParam,
+    );
+}
+
+#[test]
+fn verify_dangling_register_list() {
+    assert_verify_error(
+        &[
+            Instruction::return_many(0, 1, 2),
+            Instruction::register_list(3, 4, 5),
+            Instruction::Return,
+        ],
+        2,
+        BytecodeErrorKind::DanglingRegisterList,
+    );
+}
+
+#[test]
+fn verify_branch_out_of_bounds() {
+    assert_verify_error(
+        &[
+            Instruction::branch(BranchOffset::from(2)),
+            Instruction::Return,
+        ],
+        0,
+        BytecodeErrorKind::BranchOutOfBounds,
+    );
+    assert_verify_error(
+        &[
+            Instruction::Return,
+            Instruction::branch(BranchOffset::from(-2)),
+        ],
+        1,
+        BytecodeErrorKind::BranchOutOfBounds,
+    );
+}
+
+#[test]
+fn verify_misaligned_branch() {
+    let r = Register::from_i16;
+    assert_verify_error(
+        &[
+            Instruction::branch(BranchOffset::from(2)),
+            Instruction::table_get(r(0), r(1)),
+            Instruction::table_idx(0),
+            Instruction::Return,
+        ],
+        0,
+        BytecodeErrorKind::MisalignedBranch,
+    );
+}
+
+#[test]
+fn verify_empty_branch_table() {
+    assert_verify_error(
+        &[
+            Instruction::branch_table(Register::from_i16(0), 0_u32),
+            Instruction::Return,
+        ],
+        0,
+        BytecodeErrorKind::EmptyBranchTable,
+    );
+}
+
+#[test]
+fn verify_invalid_branch_table_target() {
+    let r = Register::from_i16;
+    assert_verify_error(
+        &[
+            Instruction::branch_table(r(0), 2_u32),
+            Instruction::Return,
+            Instruction::i32_add(r(0), r(1), r(2)),
+            Instruction::Return,
+        ],
+        2,
+        BytecodeErrorKind::InvalidBranchTableTarget,
+    );
+}
+
+#[test]
+fn verify_unsorted_branch_table_cases() {
+    let r = Register::from_i16;
+    assert_verify_error(
+        &[
+            Instruction::branch_table_sparse(r(0), 2_u32),
+            Instruction::const32(5_u32),
+            Instruction::const32(5_u32),
+            Instruction::Return,
+            Instruction::Return,
+            Instruction::Return,
+        ],
+        2,
+        BytecodeErrorKind::UnsortedBranchTableCases,
+    );
+}
+
+#[test]
+fn verify_zero_const16() {
+    let r = Register::from_i16;
+    assert_verify_error(
+        &[
+            Instruction::I32DivSImm16(BinInstrImm16::new(
+                r(0),
+                r(1),
+                Const16::from_i16_unchecked(0),
+            )),
+            Instruction::Return,
+        ],
+        0,
+        BytecodeErrorKind::ZeroConst16,
+    );
+}
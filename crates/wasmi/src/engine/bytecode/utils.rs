@@ -573,6 +573,29 @@ impl BranchBinOpInstr {
     }
 }
 
+/// A generic fused comparison and conditional branch [`Instruction`] with 32-bit branch offset.
+///
+/// # Note
+///
+/// The 32-bit [`BranchOffset`] is stored as function local constant value
+/// since it cannot be encoded in a single instruction word along with its operands.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BranchBinOpInstrFallback {
+    /// The left-hand side operand to the conditional operator.
+    pub lhs: Register,
+    /// The right-hand side operand to the conditional operator.
+    pub rhs: Register,
+    /// The register storing the 32-bit encoded branch offset.
+    pub offset: Register,
+}
+
+impl BranchBinOpInstrFallback {
+    /// Creates a new [`BranchBinOpInstrFallback`].
+    pub fn new(lhs: Register, rhs: Register, offset: Register) -> Self {
+        Self { lhs, rhs, offset }
+    }
+}
+
 /// A generic fused comparison and conditional branch [`Instruction`] with 16-bit immediate value.
 pub type BranchBinOpInstrImm16<T> = BranchBinOpInstrImm<Const16<T>>;
 
@@ -871,6 +894,11 @@ pub enum BranchComparator {
     F64Le = 35,
     F64Gt = 36,
     F64Ge = 37,
+
+    F32NotLt = 38,
+    F32NotLe = 39,
+    F64NotLt = 40,
+    F64NotLe = 41,
 }
 
 /// Encodes the conditional branch comparator and 32-bit offset of the [`Instruction::BranchCmpFallback`].
@@ -914,8 +942,8 @@ impl ComparatorOffsetParam {
     /// Converts the [`ComparatorOffsetParam`] into an `u64` value.
     pub fn as_u64(&self) -> u64 {
         let hi = self.cmp as u64;
-        let lo = self.offset.to_i32() as u64;
-        hi << 32 & lo
+        let lo = u64::from(self.offset.to_i32() as u32);
+        hi << 32 | lo
     }
 }
 
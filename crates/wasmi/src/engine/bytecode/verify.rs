@@ -0,0 +1,669 @@
+//! Verifies the encoding invariants of translated Wasmi bytecode.
+//!
+//! # Note
+//!
+//! The Wasmi executor relies on the translator to always emit instruction
+//! words in valid sequences. The [`Encoding`] of every [`Instruction`] describes
+//! which parameter words must follow it and [`verify_instrs`] checks that a
+//! sequence of instruction words upholds these invariants.
+
+use super::{BranchOffset, BranchOffset16, Instruction};
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Display},
+    mem,
+};
+
+/// The encoding of an [`Instruction`] in terms of its trailing parameter words.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Encoding {
+    /// A parameter word that must only ever follow another [`Instruction`].
+    Param,
+    /// A single instruction word without any trailing parameter words.
+    Single,
+    /// Followed by an [`Instruction::Const32`] encoding a load `offset`.
+    Const32,
+    /// Followed by an [`Instruction::Register`].
+    Register,
+    /// Followed by either of
+    ///
+    /// - [`Instruction::Register`]
+    /// - [`Instruction::Const32`]
+    /// - [`Instruction::I64Const32`]
+    /// - [`Instruction::F64Const32`]
+    SelectOperand,
+    /// Encoded as pair of two instruction words of the same variant.
+    Pair,
+    /// Followed by an [`Instruction::TableIdx`].
+    TableIdx,
+    /// Followed by two [`Instruction::TableIdx`].
+    TableIdx2,
+    /// Followed by an [`Instruction::TableIdx`] and an [`Instruction::ElementSegmentIdx`].
+    TableElementIdx,
+    /// Followed by an [`Instruction::DataSegmentIdx`].
+    DataSegmentIdx,
+    /// Followed by zero or more [`Instruction::RegisterList`] and then one of
+    ///
+    /// - [`Instruction::Register`]
+    /// - [`Instruction::Register2`]
+    /// - [`Instruction::Register3`]
+    RegisterList,
+    /// Followed by [`Instruction::CallIndirectParams`] or [`Instruction::CallIndirectParamsImm16`].
+    CallIndirectParams,
+    /// Followed by [`Encoding::CallIndirectParams`] and then by [`Encoding::RegisterList`].
+    CallIndirectParamsRegisterList,
+    /// Followed by an optional copy instruction and then `len_targets` branch targets.
+    ///
+    /// # Note
+    ///
+    /// Both the copy instruction and the branch targets are executed as
+    /// instructions on their own and are thus not parameter words.
+    BranchTable,
+    /// Followed by `len_cases` times `Const32`, an optional copy and then `len_cases + 1` branch targets.
+    ///
+    /// # Note
+    ///
+    /// Only the case indices are parameter words, see [`Encoding::BranchTable`].
+    BranchTableSparse,
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            Self::Param => "parameter word of its preceding instruction",
+            Self::Single => "single instruction word",
+            Self::Const32 => "followed by `Const32`",
+            Self::Register => "followed by `Register`",
+            Self::SelectOperand => {
+                "followed by one of `Register`, `Const32`, `I64Const32` or `F64Const32`"
+            }
+            Self::Pair => "pair of two instruction words of the same variant",
+            Self::TableIdx => "followed by `TableIdx`",
+            Self::TableIdx2 => "followed by `TableIdx` and `TableIdx`",
+            Self::TableElementIdx => "followed by `TableIdx` and `ElementSegmentIdx`",
+            Self::DataSegmentIdx => "followed by `DataSegmentIdx`",
+            Self::RegisterList => {
+                "followed by zero or more `RegisterList` and one of `Register`, `Register2` or `Register3`"
+            }
+            Self::CallIndirectParams => {
+                "followed by `CallIndirectParams` or `CallIndirectParamsImm16`"
+            }
+            Self::CallIndirectParamsRegisterList => {
+                "followed by `CallIndirectParams` or `CallIndirectParamsImm16`, \
+                zero or more `RegisterList` and one of `Register`, `Register2` or `Register3`"
+            }
+            Self::BranchTable => "followed by an optional copy and `len_targets` branch targets",
+            Self::BranchTableSparse => {
+                "followed by `len_cases` times `Const32`, an optional copy and `len_cases + 1` branch targets"
+            }
+        };
+        f.write_str(description)
+    }
+}
+
+impl Instruction {
+    /// Returns the [`Encoding`] of the [`Instruction`].
+    ///
+    /// # Note
+    ///
+    /// This must be kept in sync with the `# Encoding` sections of the
+    /// [`Instruction`] variants as well as with the Wasmi executor.
+    pub fn encoding(&self) -> Encoding {
+        match self {
+            Self::TableIdx(_)
+            | Self::DataSegmentIdx(_)
+            | Self::ElementSegmentIdx(_)
+            | Self::Const32(_)
+            | Self::I64Const32(_)
+            | Self::F64Const32(_)
+            | Self::Register(_)
+            | Self::Register2(_)
+            | Self::Register3(_)
+            | Self::RegisterList(_)
+            | Self::CallIndirectParams(_)
+            | Self::CallIndirectParamsImm16(_) => Encoding::Param,
+            Self::I32Load(_)
+            | Self::I64Load(_)
+            | Self::F32Load(_)
+            | Self::F64Load(_)
+            | Self::I32Load8s(_)
+            | Self::I32Load8u(_)
+            | Self::I32Load16s(_)
+            | Self::I32Load16u(_)
+            | Self::I64Load8s(_)
+            | Self::I64Load8u(_)
+            | Self::I64Load16s(_)
+            | Self::I64Load16u(_)
+            | Self::I64Load32s(_)
+            | Self::I64Load32u(_) => Encoding::Const32,
+            Self::I32Store(_)
+            | Self::I32Store8(_)
+            | Self::I32Store16(_)
+            | Self::I64Store(_)
+            | Self::I64Store8(_)
+            | Self::I64Store16(_)
+            | Self::I64Store32(_)
+            | Self::F32Store(_)
+            | Self::F64Store(_) => Encoding::Register,
+            Self::Select { .. } | Self::SelectRev { .. } => Encoding::SelectOperand,
+            Self::SelectImm32 { .. }
+            | Self::SelectI64Imm32 { .. }
+            | Self::SelectF64Imm32 { .. } => Encoding::Pair,
+            Self::TableGet { .. }
+            | Self::TableGetImm { .. }
+            | Self::TableSet { .. }
+            | Self::TableSetAt { .. }
+            | Self::TableFill { .. }
+            | Self::TableFillAt { .. }
+            | Self::TableFillExact { .. }
+            | Self::TableFillAtExact { .. }
+            | Self::TableGrow { .. }
+            | Self::TableGrowImm { .. } => Encoding::TableIdx,
+            Self::TableCopy { .. }
+            | Self::TableCopyTo { .. }
+            | Self::TableCopyFrom { .. }
+            | Self::TableCopyFromTo { .. }
+            | Self::TableCopyExact { .. }
+            | Self::TableCopyToExact { .. }
+            | Self::TableCopyFromExact { .. }
+            | Self::TableCopyFromToExact { .. } => Encoding::TableIdx2,
+            Self::TableInit { .. }
+            | Self::TableInitTo { .. }
+            | Self::TableInitFrom { .. }
+            | Self::TableInitFromTo { .. }
+            | Self::TableInitExact { .. }
+            | Self::TableInitToExact { .. }
+            | Self::TableInitFromExact { .. }
+            | Self::TableInitFromToExact { .. } => Encoding::TableElementIdx,
+            Self::MemoryInit { .. }
+            | Self::MemoryInitTo { .. }
+            | Self::MemoryInitFrom { .. }
+            | Self::MemoryInitFromTo { .. }
+            | Self::MemoryInitExact { .. }
+            | Self::MemoryInitToExact { .. }
+            | Self::MemoryInitFromExact { .. }
+            | Self::MemoryInitFromToExact { .. } => Encoding::DataSegmentIdx,
+            Self::ReturnMany { .. }
+            | Self::ReturnNezMany { .. }
+            | Self::CopyMany { .. }
+            | Self::CopyManyNonOverlapping { .. }
+            | Self::ReturnCallInternal { .. }
+            | Self::ReturnCallImported { .. }
+            | Self::CallInternal { .. }
+            | Self::CallImported { .. } => Encoding::RegisterList,
+            Self::ReturnCallIndirect0 { .. } | Self::CallIndirect0 { .. } => {
+                Encoding::CallIndirectParams
+            }
+            Self::ReturnCallIndirect { .. } | Self::CallIndirect { .. } => {
+                Encoding::CallIndirectParamsRegisterList
+            }
+            Self::BranchTable { .. } => Encoding::BranchTable,
+            Self::BranchTableSparse { .. } => Encoding::BranchTableSparse,
+            _ => Encoding::Single,
+        }
+    }
+
+    /// Returns the branch offset of the [`Instruction`] if it is a branch with a static offset.
+    pub(crate) fn branch_offset(&self) -> Option<BranchOffset> {
+        let offset16 = |offset: BranchOffset16| Some(BranchOffset::from(offset));
+        match *self {
+            Self::Branch { offset } => Some(offset),
+            Self::BranchI32And(instr)
+            | Self::BranchI32Or(instr)
+            | Self::BranchI32Xor(instr)
+            | Self::BranchI32AndEqz(instr)
+            | Self::BranchI32OrEqz(instr)
+            | Self::BranchI32XorEqz(instr)
+            | Self::BranchI32Eq(instr)
+            | Self::BranchI32Ne(instr)
+            | Self::BranchI32LtS(instr)
+            | Self::BranchI32LtU(instr)
+            | Self::BranchI32LeS(instr)
+            | Self::BranchI32LeU(instr)
+            | Self::BranchI32GtS(instr)
+            | Self::BranchI32GtU(instr)
+            | Self::BranchI32GeS(instr)
+            | Self::BranchI32GeU(instr)
+            | Self::BranchI64Eq(instr)
+            | Self::BranchI64Ne(instr)
+            | Self::BranchI64LtS(instr)
+            | Self::BranchI64LtU(instr)
+            | Self::BranchI64LeS(instr)
+            | Self::BranchI64LeU(instr)
+            | Self::BranchI64GtS(instr)
+            | Self::BranchI64GtU(instr)
+            | Self::BranchI64GeS(instr)
+            | Self::BranchI64GeU(instr)
+            | Self::BranchF32Eq(instr)
+            | Self::BranchF32Ne(instr)
+            | Self::BranchF32Lt(instr)
+            | Self::BranchF32Le(instr)
+            | Self::BranchF32Gt(instr)
+            | Self::BranchF32Ge(instr)
+            | Self::BranchF32NotLt(instr)
+            | Self::BranchF32NotLe(instr)
+            | Self::BranchF64Eq(instr)
+            | Self::BranchF64Ne(instr)
+            | Self::BranchF64Lt(instr)
+            | Self::BranchF64Le(instr)
+            | Self::BranchF64Gt(instr)
+            | Self::BranchF64Ge(instr)
+            | Self::BranchF64NotLt(instr)
+            | Self::BranchF64NotLe(instr) => offset16(instr.offset),
+            Self::BranchI32AndImm(instr)
+            | Self::BranchI32OrImm(instr)
+            | Self::BranchI32XorImm(instr)
+            | Self::BranchI32AndEqzImm(instr)
+            | Self::BranchI32OrEqzImm(instr)
+            | Self::BranchI32XorEqzImm(instr)
+            | Self::BranchI32EqImm(instr)
+            | Self::BranchI32NeImm(instr)
+            | Self::BranchI32LtSImm(instr)
+            | Self::BranchI32LeSImm(instr)
+            | Self::BranchI32GtSImm(instr)
+            | Self::BranchI32GeSImm(instr) => offset16(instr.offset),
+            Self::BranchI32LtUImm(instr)
+            | Self::BranchI32LeUImm(instr)
+            | Self::BranchI32GtUImm(instr)
+            | Self::BranchI32GeUImm(instr) => offset16(instr.offset),
+            Self::BranchI64EqImm(instr)
+            | Self::BranchI64NeImm(instr)
+            | Self::BranchI64LtSImm(instr)
+            | Self::BranchI64LeSImm(instr)
+            | Self::BranchI64GtSImm(instr)
+            | Self::BranchI64GeSImm(instr) => offset16(instr.offset),
+            Self::BranchI64LtUImm(instr)
+            | Self::BranchI64LeUImm(instr)
+            | Self::BranchI64GtUImm(instr)
+            | Self::BranchI64GeUImm(instr) => offset16(instr.offset),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the [`Instruction`] has a 16-bit immediate that must be non-zero but is zero.
+    fn has_zero_non_zero_const16(&self) -> bool {
+        match self {
+            Self::I32DivSImm16(instr) | Self::I32RemSImm16(instr) => instr.imm_in.is_zero(),
+            Self::I32DivUImm16(instr) | Self::I32RemUImm16(instr) => instr.imm_in.is_zero(),
+            Self::I64DivSImm16(instr) | Self::I64RemSImm16(instr) => instr.imm_in.is_zero(),
+            Self::I64DivUImm16(instr) | Self::I64RemUImm16(instr) => instr.imm_in.is_zero(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the [`Instruction`] may be the optional copy of a branch table.
+    pub(crate) fn is_branch_table_copy(&self) -> bool {
+        matches!(
+            self,
+            Self::Copy { .. }
+                | Self::Copy2 { .. }
+                | Self::CopyImm32 { .. }
+                | Self::CopyI64Imm32 { .. }
+                | Self::CopyF64Imm32 { .. }
+                | Self::CopySpan { .. }
+                | Self::CopySpanNonOverlapping { .. }
+                | Self::CopyMany { .. }
+                | Self::CopyManyNonOverlapping { .. }
+        )
+    }
+
+    /// Returns `true` if the [`Instruction`] is a valid branch table target.
+    fn is_branch_table_target(&self) -> bool {
+        matches!(
+            self,
+            Self::Branch { .. }
+                | Self::Return
+                | Self::ReturnReg { .. }
+                | Self::ReturnImm32 { .. }
+                | Self::ReturnI64Imm32 { .. }
+                | Self::ReturnF64Imm32 { .. }
+                | Self::ReturnSpan { .. }
+        )
+    }
+}
+
+/// An error that may occur upon verifying Wasmi bytecode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BytecodeError {
+    /// The index of the offending instruction word within its function.
+    instr: u32,
+    /// The kind of the [`BytecodeError`].
+    kind: BytecodeErrorKind,
+}
+
+impl BytecodeError {
+    /// Creates a new [`BytecodeError`] for the instruction word at `instr`.
+    pub(crate) fn new(instr: usize, kind: BytecodeErrorKind) -> Self {
+        let instr = u32::try_from(instr).unwrap_or(u32::MAX);
+        Self { instr, kind }
+    }
+
+    /// Returns the index of the offending instruction word within its function.
+    pub fn instr(&self) -> u32 {
+        self.instr
+    }
+
+    /// Returns the [`BytecodeErrorKind`] of the [`BytecodeError`].
+    pub fn kind(&self) -> BytecodeErrorKind {
+        self.kind
+    }
+}
+
+impl Display for BytecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid bytecode at instruction {}: {}",
+            self.instr, self.kind
+        )
+    }
+}
+
+/// The kind of a [`BytecodeError`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BytecodeErrorKind {
+    /// A parameter word was found where an instruction was expected.
+    UnexpectedParam,
+    /// An instruction is missing one of its required parameter words.
+    MissingParam,
+    /// A register list is not terminated by a register parameter word.
+    DanglingRegisterList,
+    /// A branch targets an instruction outside of its function.
+    BranchOutOfBounds,
+    /// A branch targets a parameter word instead of an instruction.
+    MisalignedBranch,
+    /// A branch table has no targets.
+    EmptyBranchTable,
+    /// A branch table target is not one of the allowed target instructions.
+    InvalidBranchTableTarget,
+    /// The case indices of a sparse branch table are not in strictly ascending order.
+    UnsortedBranchTableCases,
+    /// A 16-bit immediate that must be non-zero is zero.
+    ZeroConst16,
+    /// The frame of a function cannot hold its constants, cells or parameters.
+    ///
+    /// # Note
+    ///
+    /// This is reported for the first instruction word of the function.
+    InvalidFrame,
+    /// A register refers to a value outside of its function frame.
+    RegisterOutOfBounds,
+    /// A result register refers to a function local constant value.
+    ConstResult,
+    /// An index refers to an entity that does not exist in the module.
+    IndexOutOfBounds,
+    /// A global variable is written to but is immutable.
+    ImmutableGlobal,
+    /// A call or return does not match the signature of its callee or function.
+    SignatureMismatch,
+    /// An instruction that is only supported in translated bytecode.
+    UnsupportedInstr,
+    /// Execution may continue past the last instruction of a function.
+    UnterminatedBody,
+}
+
+impl Display for BytecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            Self::UnexpectedParam => "found parameter word where an instruction was expected",
+            Self::MissingParam => "instruction is missing a required parameter word",
+            Self::DanglingRegisterList => "register list is not terminated",
+            Self::BranchOutOfBounds => "branch target is out of bounds",
+            Self::MisalignedBranch => "branch target is not an instruction",
+            Self::EmptyBranchTable => "branch table has no targets",
+            Self::InvalidBranchTableTarget => "branch table target is invalid",
+            Self::UnsortedBranchTableCases => "branch table cases are not strictly ascending",
+            Self::ZeroConst16 => "non-zero 16-bit immediate is zero",
+            Self::InvalidFrame => "function frame cannot hold its constants, cells or parameters",
+            Self::RegisterOutOfBounds => "register is out of bounds",
+            Self::ConstResult => "result register refers to a function local constant",
+            Self::IndexOutOfBounds => "index is out of bounds",
+            Self::ImmutableGlobal => "immutable global variable is written to",
+            Self::SignatureMismatch => "call or return does not match its signature",
+            Self::UnsupportedInstr => "instruction is only supported in translated bytecode",
+            Self::UnterminatedBody => "execution may continue past the last instruction",
+        };
+        f.write_str(message)
+    }
+}
+
+/// Verifies that `instrs` upholds the encoding invariants of Wasmi bytecode.
+///
+/// # Errors
+///
+/// If `instrs` violates any of the encoding invariants.
+pub fn verify_instrs(instrs: &[Instruction]) -> Result<(), BytecodeError> {
+    verify_instr_starts(instrs, &mut Vec::new())
+}
+
+/// Verifies that `instrs` upholds the encoding invariants of Wasmi bytecode.
+///
+/// Upon success `is_instr` stores `true` for all instruction words of `instrs`
+/// that begin an instruction and `false` for all parameter words.
+///
+/// # Errors
+///
+/// If `instrs` violates any of the encoding invariants.
+pub fn verify_instr_starts(
+    instrs: &[Instruction],
+    is_instr: &mut Vec<bool>,
+) -> Result<(), BytecodeError> {
+    is_instr.clear();
+    is_instr.resize(instrs.len(), false);
+    let mut pos = 0;
+    while let Some(instr) = instrs.get(pos) {
+        is_instr[pos] = true;
+        pos += verify_instr(instrs, pos, instr)?;
+    }
+    for (pos, instr) in instrs.iter().enumerate() {
+        if !is_instr[pos] {
+            continue;
+        }
+        if let Some(offset) = instr.branch_offset() {
+            verify_branch(is_instr, pos, offset)?;
+        }
+    }
+    Ok(())
+}
+
+/// Verifies the `instr` at `pos` and returns the number of its instruction words.
+fn verify_instr(
+    instrs: &[Instruction],
+    pos: usize,
+    instr: &Instruction,
+) -> Result<usize, BytecodeError> {
+    if instr.has_zero_non_zero_const16() {
+        return Err(BytecodeError::new(pos, BytecodeErrorKind::ZeroConst16));
+    }
+    let param = |offset: usize| instrs.get(pos + offset);
+    let expect = |offset: usize, is_valid: fn(&Instruction) -> bool| match param(offset) {
+        Some(param) if is_valid(param) => Ok(()),
+        _ => Err(BytecodeError::new(
+            pos + offset,
+            BytecodeErrorKind::MissingParam,
+        )),
+    };
+    let len = match instr.encoding() {
+        Encoding::Param => return Err(BytecodeError::new(pos, BytecodeErrorKind::UnexpectedParam)),
+        Encoding::Single => 1,
+        Encoding::Const32 => {
+            expect(1, |param| matches!(param, Instruction::Const32(_)))?;
+            2
+        }
+        Encoding::Register => {
+            expect(1, |param| matches!(param, Instruction::Register(_)))?;
+            2
+        }
+        Encoding::SelectOperand => {
+            expect(1, |param| {
+                matches!(
+                    param,
+                    Instruction::Register(_)
+                        | Instruction::Const32(_)
+                        | Instruction::I64Const32(_)
+                        | Instruction::F64Const32(_)
+                )
+            })?;
+            2
+        }
+        Encoding::Pair => {
+            match param(1) {
+                Some(param) if mem::discriminant(param) == mem::discriminant(instr) => {}
+                _ => return Err(BytecodeError::new(pos + 1, BytecodeErrorKind::MissingParam)),
+            }
+            2
+        }
+        Encoding::TableIdx => {
+            expect(1, |param| matches!(param, Instruction::TableIdx(_)))?;
+            2
+        }
+        Encoding::TableIdx2 => {
+            expect(1, |param| matches!(param, Instruction::TableIdx(_)))?;
+            expect(2, |param| matches!(param, Instruction::TableIdx(_)))?;
+            3
+        }
+        Encoding::TableElementIdx => {
+            expect(1, |param| matches!(param, Instruction::TableIdx(_)))?;
+            expect(2, |param| {
+                matches!(param, Instruction::ElementSegmentIdx(_))
+            })?;
+            3
+        }
+        Encoding::DataSegmentIdx => {
+            expect(1, |param| matches!(param, Instruction::DataSegmentIdx(_)))?;
+            2
+        }
+        Encoding::RegisterList => 1 + verify_register_list(instrs, pos + 1)?,
+        Encoding::CallIndirectParams => {
+            expect(1, is_call_indirect_params)?;
+            2
+        }
+        Encoding::CallIndirectParamsRegisterList => {
+            expect(1, is_call_indirect_params)?;
+            2 + verify_register_list(instrs, pos + 2)?
+        }
+        Encoding::BranchTable => {
+            verify_branch_table(instrs, pos)?;
+            1
+        }
+        Encoding::BranchTableSparse => 1 + verify_branch_table_sparse(instrs, pos)?,
+    };
+    Ok(len)
+}
+
+/// Returns `true` if `instr` encodes the parameters of an indirect call.
+fn is_call_indirect_params(instr: &Instruction) -> bool {
+    matches!(
+        instr,
+        Instruction::CallIndirectParams(_) | Instruction::CallIndirectParamsImm16(_)
+    )
+}
+
+/// Verifies the register list starting at `pos` and returns the number of its instruction words.
+fn verify_register_list(instrs: &[Instruction], pos: usize) -> Result<usize, BytecodeError> {
+    let mut cursor = pos;
+    loop {
+        match instrs.get(cursor) {
+            Some(Instruction::RegisterList(_)) => cursor += 1,
+            Some(
+                Instruction::Register(_) | Instruction::Register2(_) | Instruction::Register3(_),
+            ) => return Ok(cursor - pos + 1),
+            _ if cursor == pos => {
+                return Err(BytecodeError::new(cursor, BytecodeErrorKind::MissingParam))
+            }
+            _ => {
+                return Err(BytecodeError::new(
+                    cursor,
+                    BytecodeErrorKind::DanglingRegisterList,
+                ))
+            }
+        }
+    }
+}
+
+/// Verifies the optional copy and the targets of the [`Instruction::BranchTable`] at `pos`.
+fn verify_branch_table(instrs: &[Instruction], pos: usize) -> Result<(), BytecodeError> {
+    let Instruction::BranchTable { len_targets, .. } = instrs[pos] else {
+        unreachable!(
+            "expected a branch table at {pos} but found: {:?}",
+            instrs[pos]
+        )
+    };
+    let len_targets = u32::from(len_targets) as usize;
+    if len_targets == 0 {
+        return Err(BytecodeError::new(pos, BytecodeErrorKind::EmptyBranchTable));
+    }
+    verify_branch_table_targets(instrs, pos + 1, len_targets)
+}
+
+/// Verifies the [`Instruction::BranchTableSparse`] at `pos`.
+///
+/// Returns the number of its case indices which are its parameter words.
+fn verify_branch_table_sparse(instrs: &[Instruction], pos: usize) -> Result<usize, BytecodeError> {
+    let Instruction::BranchTableSparse { len_cases, .. } = instrs[pos] else {
+        unreachable!(
+            "expected a sparse branch table at {pos} but found: {:?}",
+            instrs[pos]
+        )
+    };
+    let len_cases = u32::from(len_cases) as usize;
+    let mut prev_case = None;
+    for param in pos + 1..pos + 1 + len_cases {
+        let Some(Instruction::Const32(case)) = instrs.get(param) else {
+            return Err(BytecodeError::new(param, BytecodeErrorKind::MissingParam));
+        };
+        let case = u32::from(*case);
+        if prev_case.is_some_and(|prev_case| prev_case >= case) {
+            return Err(BytecodeError::new(
+                param,
+                BytecodeErrorKind::UnsortedBranchTableCases,
+            ));
+        }
+        prev_case = Some(case);
+    }
+    verify_branch_table_targets(instrs, pos + 1 + len_cases, len_cases + 1)?;
+    Ok(len_cases)
+}
+
+/// Verifies the optional copy at `pos` followed by `len_targets` branch table targets.
+fn verify_branch_table_targets(
+    instrs: &[Instruction],
+    pos: usize,
+    len_targets: usize,
+) -> Result<(), BytecodeError> {
+    let mut first_target = pos;
+    if let Some(copy) = instrs
+        .get(first_target)
+        .filter(|instr| instr.is_branch_table_copy())
+    {
+        first_target += verify_instr(instrs, first_target, copy)?;
+    }
+    for target in first_target..first_target + len_targets {
+        match instrs.get(target) {
+            Some(instr) if instr.is_branch_table_target() => {}
+            _ => {
+                return Err(BytecodeError::new(
+                    target,
+                    BytecodeErrorKind::InvalidBranchTableTarget,
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Verifies that the branch at `pos` with `offset` targets the start of an instruction.
+fn verify_branch(is_instr: &[bool], pos: usize, offset: BranchOffset) -> Result<(), BytecodeError> {
+    let target = i64::try_from(pos)
+        .ok()
+        .and_then(|pos| pos.checked_add(i64::from(offset.to_i32())))
+        .and_then(|target| usize::try_from(target).ok());
+    match target.and_then(|target| is_instr.get(target)) {
+        Some(true) => Ok(()),
+        Some(false) => Err(BytecodeError::new(pos, BytecodeErrorKind::MisalignedBranch)),
+        None => Err(BytecodeError::new(
+            pos,
+            BytecodeErrorKind::BranchOutOfBounds,
+        )),
+    }
+}
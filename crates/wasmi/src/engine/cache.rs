@@ -1,5 +1,12 @@
 use crate::{
-    engine::bytecode::{DataSegmentIdx, ElementSegmentIdx, FuncIdx, GlobalIdx, TableIdx},
+    engine::bytecode::{
+        DataSegmentIdx,
+        ElementSegmentIdx,
+        FuncIdx,
+        GlobalIdx,
+        Instruction,
+        TableIdx,
+    },
     instance::InstanceEntity,
     memory::DataSegment,
     module::DEFAULT_MEMORY_INDEX,
@@ -13,7 +20,8 @@ use crate::{
     StoreInner,
     Table,
 };
-use core::ptr::NonNull;
+use alloc::vec::Vec;
+use core::{mem, ptr::NonNull};
 use wasmi_core::UntypedValue;
 
 /// A cache for frequently used entities of an [`Instance`].
@@ -21,9 +29,19 @@ use wasmi_core::UntypedValue;
 #[repr(C)]
 pub struct InstanceCache {
     /// The bytes of a default linear memory of the currently used [`Instance`].
+    ///
+    /// # Note
+    ///
+    /// This caches the raw base pointer and length of the linear memory so that
+    /// loads and stores can bounds check without resolving the memory entity.
+    /// It must be reset whenever the linear memory might have grown, e.g. after
+    /// `memory.grow` or calls to host functions.
+    ///
+    /// This caches an empty slice for linear memories with write watchpoints so that
+    /// all of their accesses fail the bounds check and take the slow path instead.
     default_memory_bytes: Option<NonNull<[u8]>>,
-    /// The last accessed global variable value of the currently used [`Instance`].
-    last_global: Option<(GlobalIdx, NonNull<UntypedValue>)>,
+    /// The recently accessed global variable values of the currently used [`Instance`].
+    globals: GlobalCache,
     /// The current instance in use.
     instance: Instance,
     /// The default linear memory of the currently used [`Instance`].
@@ -32,6 +50,39 @@ pub struct InstanceCache {
     last_table: Option<(TableIdx, Table)>,
     /// The last accessed function of the currently used [`Instance`].
     last_func: Option<(FuncIdx, Func)>,
+    /// The cached entities of the previously used [`Instance`] if any.
+    ///
+    /// # Note
+    ///
+    /// Together with the currently used [`Instance`] this forms a two entry LRU cache.
+    /// Calls and returns that alternate between two instances swap the cached entities
+    /// instead of loading them again from the [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    parked: Option<ParkedInstanceCache>,
+}
+
+/// The cached entities of an [`Instance`] that is not currently in use.
+///
+/// # Note
+///
+/// Whenever the cached linear memory bytes or global variable of the currently
+/// used [`Instance`] are reset the ones of the [`ParkedInstanceCache`] are reset
+/// as well since both instances might share the same linear memory.
+#[derive(Debug)]
+struct ParkedInstanceCache {
+    /// The bytes of the default linear memory of the parked [`Instance`].
+    default_memory_bytes: Option<NonNull<[u8]>>,
+    /// The recently accessed global variable values of the parked [`Instance`].
+    globals: GlobalCache,
+    /// The parked instance.
+    instance: Instance,
+    /// The default linear memory of the parked [`Instance`].
+    default_memory: Option<Memory>,
+    /// The last accessed table of the parked [`Instance`].
+    last_table: Option<(TableIdx, Table)>,
+    /// The last accessed function of the parked [`Instance`].
+    last_func: Option<(FuncIdx, Func)>,
 }
 
 impl From<&'_ Instance> for InstanceCache {
@@ -41,8 +92,9 @@ impl From<&'_ Instance> for InstanceCache {
             default_memory: None,
             last_table: None,
             last_func: None,
-            last_global: None,
+            globals: GlobalCache::default(),
             default_memory_bytes: None,
+            parked: None,
         }
     }
 }
@@ -55,15 +107,32 @@ impl InstanceCache {
     }
 
     /// Updates the cached [`Instance`].
+    ///
+    /// # Note
+    ///
+    /// - Parks the cached entities of the currently used [`Instance`].
+    /// - Restores the cached entities of `instance` if they have been parked before.
     #[cold]
     #[inline]
     fn set_instance(&mut self, instance: &Instance) {
+        let parked = ParkedInstanceCache {
+            default_memory_bytes: self.default_memory_bytes.take(),
+            globals: mem::take(&mut self.globals),
+            instance: self.instance,
+            default_memory: self.default_memory.take(),
+            last_table: self.last_table.take(),
+            last_func: self.last_func.take(),
+        };
         self.instance = *instance;
-        self.default_memory = None;
-        self.last_table = None;
-        self.last_func = None;
-        self.last_global = None;
-        self.default_memory_bytes = None;
+        if let Some(restored) = self.parked.replace(parked) {
+            if restored.instance == *instance {
+                self.default_memory_bytes = restored.default_memory_bytes;
+                self.globals = restored.globals;
+                self.default_memory = restored.default_memory;
+                self.last_table = restored.last_table;
+                self.last_func = restored.last_func;
+            }
+        }
     }
 
     /// Updates the currently used instance resetting all cached entities.
@@ -109,7 +178,11 @@ impl InstanceCache {
             })
     }
 
-    /// Loads the [`DataSegment`] at `index` of the currently used [`Instance`].
+    /// Returns all necessary data required to execute a `memory.init` instruction.
+    ///
+    /// # Note
+    ///
+    /// The returned linear memory bytes are empty for linear memories with write watchpoints.
     ///
     /// # Panics
     ///
@@ -123,7 +196,11 @@ impl InstanceCache {
         let seg = self.get_data_segment(ctx, segment.to_u32());
         let mem = self.default_memory(ctx);
         let (memory, segment, fuel) = ctx.resolve_memory_init_triplet(mem, &seg);
-        (memory.data_mut(), segment.bytes(), fuel)
+        let bytes = match memory.is_watched() {
+            true => <&mut [u8]>::default(),
+            false => memory.data_mut(),
+        };
+        (bytes, segment.bytes(), fuel)
     }
 
     /// Returns all necessary data required to execute a `table.init` instruction.
@@ -202,8 +279,12 @@ impl InstanceCache {
     #[inline]
     fn load_default_memory_bytes(&mut self, ctx: &mut StoreInner) -> &mut NonNull<[u8]> {
         let memory = *self.default_memory(ctx);
-        self.default_memory_bytes
-            .insert(ctx.resolve_memory_mut(&memory).data_mut().into())
+        let entity = ctx.resolve_memory_mut(&memory);
+        let bytes = match entity.is_watched() {
+            true => <&mut [u8]>::default(),
+            false => entity.data_mut(),
+        };
+        self.default_memory_bytes.insert(bytes.into())
     }
 
     /// Clears the cached default memory instance.
@@ -214,10 +295,16 @@ impl InstanceCache {
     ///   occurred that might have invalidated the cached memory.
     /// - It is equally important to reset cached default memory bytes
     ///   when calling a host function since it might call `memory.grow`.
+    /// - The cached default memory bytes of the parked [`Instance`] are cleared
+    ///   as well since it might share the same linear memory.
     #[inline]
     pub fn reset_default_memory_bytes(&mut self) {
         self.default_memory_bytes = None;
-        self.last_global = None;
+        self.globals.clear();
+        if let Some(parked) = &mut self.parked {
+            parked.default_memory_bytes = None;
+            parked.globals.clear();
+        }
     }
 
     /// Clears the cached default memory instance and global variable.
@@ -234,7 +321,6 @@ impl InstanceCache {
     #[inline]
     pub fn reset(&mut self) {
         self.reset_default_memory_bytes();
-        self.last_global = None;
     }
 
     /// Returns the [`Table`] at the `index` of the currently used [`Instance`].
@@ -325,7 +411,7 @@ impl InstanceCache {
                     self.instance
                 )
             });
-        self.last_global = Some((index, global));
+        self.globals.insert(index, global);
         global
     }
 
@@ -341,9 +427,9 @@ impl InstanceCache {
         ctx: &'ctx mut StoreInner,
         global_index: GlobalIdx,
     ) -> &'ctx mut UntypedValue {
-        let mut ptr = match self.last_global {
-            Some((index, global)) if index == global_index => global,
-            _ => self.load_global_at(ctx, global_index),
+        let mut ptr = match self.globals.get(global_index) {
+            Some(global) => global,
+            None => self.load_global_at(ctx, global_index),
         };
         // SAFETY: This deref is safe since we only hold this pointer
         //         as long as we are sure that nothing else can manipulate
@@ -378,3 +464,142 @@ impl InstanceCache {
         *self.get_global_mut(ctx, global_index) = new_value;
     }
 }
+
+/// The number of entries of a [`GlobalCache`].
+///
+/// # Note
+///
+/// Must be a power of two.
+const GLOBAL_CACHE_LEN: usize = 4;
+
+/// A direct-mapped cache of pointers to the values of global variables of an [`Instance`].
+///
+/// # Note
+///
+/// - The cache is indexed by the index of the global variable within the [`Instance`].
+///   Therefore code alternating between a few global variables, such as the stack pointer
+///   and other globals of code compiled from C, does not evict its cached pointers.
+/// - Immutable global variables with a constant initializer never reach the cache
+///   since their `global.get` is replaced with the constant upon translation.
+/// - The cached pointers are only valid for as long as no new global variables are
+///   allocated in the [`Store`]. Therefore the cache is cleared upon host function calls.
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Default)]
+struct GlobalCache {
+    /// The cached pointers to the values of global variables.
+    entries: [Option<(GlobalIdx, NonNull<UntypedValue>)>; GLOBAL_CACHE_LEN],
+}
+
+impl GlobalCache {
+    /// Returns the slot in the cache for the global variable at `index`.
+    #[inline(always)]
+    fn slot(index: GlobalIdx) -> usize {
+        index.to_u32() as usize & (GLOBAL_CACHE_LEN - 1)
+    }
+
+    /// Returns the cached pointer to the value of the global variable at `index` if any.
+    #[inline(always)]
+    fn get(&self, index: GlobalIdx) -> Option<NonNull<UntypedValue>> {
+        match self.entries[Self::slot(index)] {
+            Some((cached, global)) if cached == index => Some(global),
+            _ => None,
+        }
+    }
+
+    /// Caches the pointer to the value of the global variable at `index`.
+    ///
+    /// This evicts any entry that was previously stored in the same slot.
+    #[inline]
+    fn insert(&mut self, index: GlobalIdx, global: NonNull<UntypedValue>) {
+        self.entries[Self::slot(index)] = Some((index, global));
+    }
+
+    /// Removes all entries from the cache.
+    #[inline]
+    fn clear(&mut self) {
+        self.entries = Default::default();
+    }
+}
+
+/// The number of entries of an [`IndirectCallCache`].
+///
+/// # Note
+///
+/// Must be a power of two.
+const INDIRECT_CALL_CACHE_LEN: usize = 256;
+
+/// A cached resolution of a `call_indirect` call site.
+#[derive(Debug, Copy, Clone)]
+struct IndirectCallEntry {
+    /// The address of the `call_indirect` call site instruction.
+    site: usize,
+    /// The [`Table`] that has been used to resolve the callee.
+    table: Table,
+    /// The index into the [`Table`] that has been used to resolve the callee.
+    index: u32,
+    /// The generation of the [`Table`] at the time the callee has been resolved.
+    generation: u64,
+    /// The resolved and signature checked callee.
+    func: Func,
+}
+
+/// A monomorphic inline cache for `call_indirect` call sites.
+///
+/// # Note
+///
+/// - The cache is direct-mapped and indexed by the address of the call site.
+/// - An entry is only ever valid for the same [`Table`], index and table generation.
+///   Since every mutation of a [`Table`] bumps its generation the cache never yields
+///   stale callees.
+/// - Entries are only inserted after a successful signature check so that cache hits
+///   may skip the signature check. This is sound since the expected signature of a
+///   `call_indirect` call site is fixed by the call site itself.
+#[derive(Debug, Default)]
+pub struct IndirectCallCache {
+    /// The cache entries, lazily allocated upon the first insertion.
+    entries: Vec<Option<IndirectCallEntry>>,
+}
+
+impl IndirectCallCache {
+    /// Returns the slot in the cache for the call `site`.
+    #[inline]
+    fn slot(site: usize) -> usize {
+        (site / mem::size_of::<Instruction>()) & (INDIRECT_CALL_CACHE_LEN - 1)
+    }
+
+    /// Returns the cached callee for the call `site` if any.
+    ///
+    /// Returns `None` if there is no valid entry for `table`, `index` and `generation`.
+    #[inline]
+    pub fn get(&self, site: usize, table: &Table, index: u32, generation: u64) -> Option<Func> {
+        let entry = self.entries.get(Self::slot(site))?.as_ref()?;
+        let is_hit = entry.site == site
+            && entry.index == index
+            && entry.generation == generation
+            && Table::eq(&entry.table, table);
+        is_hit.then_some(entry.func)
+    }
+
+    /// Removes all entries from the cache.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Caches the signature checked `func` for the call `site`.
+    ///
+    /// This evicts any entry that was previously stored in the same slot.
+    #[cold]
+    pub fn insert(&mut self, site: usize, table: Table, index: u32, generation: u64, func: Func) {
+        if self.entries.is_empty() {
+            self.entries.resize(INDIRECT_CALL_CACHE_LEN, None);
+        }
+        self.entries[Self::slot(site)] = Some(IndirectCallEntry {
+            site,
+            table,
+            index,
+            generation,
+            func,
+        });
+    }
+}
@@ -5,15 +5,28 @@
 //! This is the data structure specialized to handle compiled
 //! register machine based bytecode functions.
 
-use super::{FuncTranslationDriver, FuncTranslator, TranslationError, ValidatingFuncTranslator};
+use super::{
+    FuelBreakdown,
+    FuelCosts,
+    FuncTranslationDriver,
+    FuncTranslator,
+    RegisterTypes,
+    ValidatingFuncTranslator,
+};
 use crate::{
     core::UntypedValue,
-    engine::bytecode::Instruction,
+    engine::bytecode::{verify_instrs, Instruction},
     module::{FuncIdx, ModuleHeader},
-    store::{Fuel, FuelError},
+    store::Fuel,
     Error,
+    TrapOrigin,
+};
+use alloc::{
+    boxed::Box,
+    collections::BTreeSet,
+    sync::{Arc, Weak},
+    vec::Vec,
 };
-use alloc::boxed::Box;
 use core::{
     cell::UnsafeCell,
     fmt,
@@ -21,10 +34,9 @@ use core::{
     mem,
     ops,
     slice,
-    sync::atomic::{AtomicU8, Ordering},
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
 };
 use wasmi_arena::{Arena, ArenaIndex};
-use wasmi_core::TrapCode;
 use wasmparser::{FuncToValidate, ValidatorResources};
 
 /// A reference to a compiled function stored in the [`CodeMap`] of an [`Engine`](crate::Engine).
@@ -65,6 +77,8 @@ enum InternalFuncEntity {
     Compiled(CompiledFuncEntity),
     /// An internal function that has not yet been compiled.
     Uncompiled(UncompiledFuncEntity),
+    /// An internal function that failed to compile lazily.
+    Failed(FailedFuncEntity),
 }
 
 impl From<CompiledFuncEntity> for InternalFuncEntity {
@@ -85,6 +99,37 @@ impl InternalFuncEntity {
         Self::from(CompiledFuncEntity::uninit())
     }
 
+    /// Returns the [`UncompiledFuncEntity`] of `self`.
+    ///
+    /// # Panics
+    ///
+    /// If the `func` unexpectedly is not in the uncompiled state.
+    fn uncompiled(&mut self) -> &mut UncompiledFuncEntity {
+        match self {
+            InternalFuncEntity::Uncompiled(func) => func,
+            func => {
+                unreachable!("expected func to be uncompiled: {func:?}")
+            }
+        }
+    }
+
+    /// Charges fuel for the compilation of the uncompiled [`FuncEntity`].
+    ///
+    /// # Panics
+    ///
+    /// If the `func` unexpectedly has already been compiled.
+    ///
+    /// # Errors
+    ///
+    /// If `fuel` ran out of fuel in case fuel consumption is enabled.
+    fn charge_compilation_fuel(&mut self, fuel: Option<&mut Fuel>) -> Result<(), Error> {
+        let len_bytes = self.uncompiled().bytes.as_slice().len() as u64;
+        if let Some(fuel) = fuel {
+            fuel.consume_compilation_fuel(len_bytes)?;
+        }
+        Ok(())
+    }
+
     /// Compile the uncompiled [`FuncEntity`].
     ///
     /// # Panics
@@ -94,23 +139,12 @@ impl InternalFuncEntity {
     ///
     /// # Errors
     ///
-    /// - If function translation failed.
-    /// - If `ctx` ran out of fuel in case fuel consumption is enabled.
-    fn compile(&mut self, fuel: Option<&mut Fuel>) -> Result<(), Error> {
-        let uncompiled = match self {
-            InternalFuncEntity::Uncompiled(func) => func,
-            InternalFuncEntity::Compiled(func) => {
-                unreachable!("expected func to be uncompiled: {func:?}")
-            }
-        };
+    /// If function validation or translation failed.
+    fn compile(&mut self) -> Result<(), Error> {
+        let uncompiled = self.uncompiled();
         let func_idx = uncompiled.func_idx;
+        let offset = uncompiled.offset;
         let bytes = mem::take(&mut uncompiled.bytes);
-        if let Some(fuel) = fuel {
-            match fuel.consume_fuel(|costs| costs.fuel_for_bytes(bytes.as_slice().len() as u64)) {
-                Err(FuelError::OutOfFuel) => return Err(Error::from(TrapCode::OutOfFuel)),
-                Ok(_) | Err(FuelError::FuelMeteringDisabled) => {}
-            }
-        }
         let module = uncompiled.module.clone();
         let Some(engine) = module.engine().upgrade() else {
             panic!(
@@ -124,21 +158,19 @@ impl InternalFuncEntity {
                 let translator = FuncTranslator::new(func_idx, module, allocs.0)?;
                 let validator = func_to_validate.into_validator(allocs.1);
                 let translator = ValidatingFuncTranslator::new(validator, translator)?;
-                let allocs = FuncTranslationDriver::new(0, &bytes[..], translator)?.translate(
-                    |compiled_func| {
+                let allocs = FuncTranslationDriver::new(offset, &bytes[..], translator)?
+                    .translate(|compiled_func| {
                         *self = InternalFuncEntity::Compiled(compiled_func);
-                    },
-                )?;
+                    })?;
                 engine.recycle_allocs(allocs.translation, allocs.validation);
             }
             None => {
                 let allocs = engine.get_translation_allocs();
                 let translator = FuncTranslator::new(func_idx, module, allocs)?;
-                let allocs = FuncTranslationDriver::new(0, &bytes[..], translator)?.translate(
-                    |compiled_func| {
+                let allocs = FuncTranslationDriver::new(offset, &bytes[..], translator)?
+                    .translate(|compiled_func| {
                         *self = InternalFuncEntity::Compiled(compiled_func);
-                    },
-                )?;
+                    })?;
                 engine.recycle_translation_allocs(allocs);
             }
         };
@@ -146,10 +178,33 @@ impl InternalFuncEntity {
     }
 }
 
+/// An internal function entity that failed to compile lazily.
+///
+/// # Note
+///
+/// The compilation error is shared so that all subsequent
+/// calls to the function report the same error.
+#[derive(Debug, Clone)]
+pub struct FailedFuncEntity {
+    /// The index of the function within its module.
+    func_idx: FuncIdx,
+    /// The error that caused the lazy compilation to fail.
+    error: Arc<Error>,
+}
+
+impl FailedFuncEntity {
+    /// Returns an [`Error`] describing the failed lazy compilation.
+    fn to_error(&self) -> Error {
+        Error::lazy_compilation_failed(self.func_idx.into_u32(), self.error.clone())
+    }
+}
+
 /// An internal uncompiled function entity.
 pub struct UncompiledFuncEntity {
     /// The index of the function within the `module`.
     func_idx: FuncIdx,
+    /// The offset of the function body within the Wasm binary.
+    offset: usize,
     /// The Wasm binary bytes.
     bytes: SmallByteSlice,
     /// The Wasm module of the Wasm function.
@@ -167,23 +222,40 @@ impl UncompiledFuncEntity {
     /// Creates a new [`UncompiledFuncEntity`].
     pub fn new(
         func_idx: FuncIdx,
+        offset: usize,
         bytes: impl Into<SmallByteSlice>,
         module: ModuleHeader,
         func_to_validate: impl Into<Option<FuncToValidate<ValidatorResources>>>,
     ) -> Self {
         Self {
             func_idx,
+            offset,
             bytes: bytes.into(),
             module,
             func_to_validate: func_to_validate.into(),
         }
     }
+
+    /// Returns the start time of the compilation recorded by tracing.
+    ///
+    /// Returns `None` if the [`Engine`] must not read the wall clock
+    /// due to [`Config::deterministic_only`] or no longer exists.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Config::deterministic_only`]: crate::Config::deterministic_only
+    #[cfg(feature = "tracing")]
+    fn start_tracing_timer(&self) -> Option<std::time::Instant> {
+        let engine = self.module.engine().upgrade()?;
+        let deterministic = engine.config().get_deterministic_only();
+        (!deterministic).then(std::time::Instant::now)
+    }
 }
 
 impl fmt::Debug for UncompiledFuncEntity {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("UncompiledFuncEntity")
             .field("func_idx", &self.func_idx)
+            .field("offset", &self.offset)
             .field("bytes", &self.bytes)
             .field("module", &self.module)
             .field("validate", &self.func_to_validate.is_some())
@@ -254,8 +326,23 @@ impl<'a> From<&'a [u8]> for SmallByteSlice {
     }
 }
 
+/// Information about a compiled [`CompiledFunc`].
+///
+/// Returned by [`Engine::func_info`].
+///
+/// [`Engine::func_info`]: crate::Engine::func_info
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FuncInfo {
+    /// The index of the function within its Wasm module if translated from Wasm.
+    pub func_index: Option<u32>,
+    /// The number of Wasmi bytecode instructions of the function.
+    pub len_instrs: usize,
+    /// The number of registers of the function.
+    pub len_registers: u16,
+}
+
 /// Meta information about a [`CompiledFunc`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CompiledFuncEntity {
     /// The sequence of [`Instruction`] of the [`CompiledFuncEntity`].
     instrs: Box<[Instruction]>,
@@ -268,8 +355,62 @@ pub struct CompiledFuncEntity {
     len_registers: u16,
     /// The constant values local to the [`CompiledFunc`].
     consts: Box<[UntypedValue]>,
+    /// The address map of the [`CompiledFunc`].
+    ///
+    /// # Note
+    ///
+    /// This is empty unless [`Config::generate_address_map`] is enabled.
+    /// Read [`CompiledFuncEntity::address_map`] for more information.
+    ///
+    /// [`Config::generate_address_map`]: crate::Config::generate_address_map
+    address_map: Box<[(u32, u32)]>,
+    /// The register types of the [`CompiledFunc`] if any.
+    ///
+    /// # Note
+    ///
+    /// This is `None` unless [`Config::generate_register_types`] is enabled.
+    ///
+    /// [`Config::generate_register_types`]: crate::Config::generate_register_types
+    register_types: Option<Box<RegisterTypes>>,
+    /// The trap sites of the [`CompiledFunc`] if it contains any [`Instruction::Trap`].
+    ///
+    /// # Note
+    ///
+    /// This is generated regardless of [`Config::generate_address_map`].
+    /// Read [`CompiledFuncEntity::trap_origin`] for more information.
+    ///
+    /// [`Config::generate_address_map`]: crate::Config::generate_address_map
+    trap_sites: Option<Box<TrapSites>>,
+    /// The fuel sites of the [`CompiledFunc`] if it has been translated with fuel metering.
+    ///
+    /// # Note
+    ///
+    /// Read [`CompiledFuncEntity::reprice_fuel`] for more information.
+    fuel_sites: Option<Box<FuelSites>>,
+    /// The index of the Wasm function within its Wasm module if translated from Wasm.
+    ///
+    /// # Note
+    ///
+    /// This is `None` for functions built from Wasmi bytecode via [`build::IrFunc`].
+    ///
+    /// [`build::IrFunc`]: crate::build::IrFunc
+    func_index: Option<u32>,
+}
+
+/// The [`FuelBreakdown`] of all [`Instruction::ConsumeFuel`] of a [`CompiledFuncEntity`].
+#[derive(Debug, Clone)]
+struct FuelSites {
+    /// The [`FuelCosts`] for which the block fuel of all [`Instruction::ConsumeFuel`] is computed.
+    costs: FuelCosts,
+    /// The `(instr, breakdown)` pairs of all [`Instruction::ConsumeFuel`] sorted by `instr`.
+    sites: Box<[(u32, FuelBreakdown)]>,
 }
 
+/// The Wasm origins of all [`Instruction::Trap`] of a [`CompiledFuncEntity`].
+///
+/// Holds the `(instr, offset)` pairs of all [`Instruction::Trap`] sorted by `instr`.
+type TrapSites = [(u32, u32)];
+
 impl CompiledFuncEntity {
     /// Create a new initialized [`CompiledFuncEntity`].
     ///
@@ -292,15 +433,74 @@ impl CompiledFuncEntity {
             instrs,
             len_registers,
             consts,
+            address_map: [].into(),
+            register_types: None,
+            trap_sites: None,
+            fuel_sites: None,
+            func_index: None,
         }
     }
 
+    /// Sets the address map of the [`CompiledFuncEntity`].
+    ///
+    /// Read [`CompiledFuncEntity::address_map`] for more information.
+    pub fn with_address_map<A>(mut self, address_map: A) -> Self
+    where
+        A: IntoIterator<Item = (u32, u32)>,
+    {
+        self.address_map = address_map.into_iter().collect();
+        self
+    }
+
+    /// Sets the index of the Wasm function within its Wasm module the [`CompiledFuncEntity`] is translated from.
+    pub fn with_func_index(mut self, func_index: u32) -> Self {
+        self.func_index = Some(func_index);
+        self
+    }
+
+    /// Sets the trap sites of the [`CompiledFuncEntity`].
+    ///
+    /// The `sites` are the `(instr, offset)` pairs of all [`Instruction::Trap`] sorted by `instr`.
+    /// Read [`CompiledFuncEntity::trap_origin`] for more information.
+    pub fn with_trap_sites<S>(mut self, sites: S) -> Self
+    where
+        S: IntoIterator<Item = (u32, u32)>,
+    {
+        let sites: Box<TrapSites> = sites.into_iter().collect();
+        self.trap_sites = (!sites.is_empty()).then_some(sites);
+        self
+    }
+
+    /// Sets the fuel sites of the [`CompiledFuncEntity`] translated for the given [`FuelCosts`].
+    ///
+    /// The `sites` are the `(instr, breakdown)` pairs of all [`Instruction::ConsumeFuel`] sorted by `instr`.
+    /// Read [`CompiledFuncEntity::reprice_fuel`] for more information.
+    pub fn with_fuel_sites<S>(mut self, costs: FuelCosts, sites: S) -> Self
+    where
+        S: IntoIterator<Item = (u32, FuelBreakdown)>,
+    {
+        let sites: Box<[(u32, FuelBreakdown)]> = sites.into_iter().collect();
+        self.fuel_sites = Some(Box::new(FuelSites { costs, sites }));
+        self
+    }
+
+    /// Sets the [`RegisterTypes`] of the [`CompiledFuncEntity`].
+    pub fn with_register_types(mut self, register_types: RegisterTypes) -> Self {
+        self.register_types = Some(Box::new(register_types));
+        self
+    }
+
     /// Create a new uninitialized [`CompiledFuncEntity`].
     fn uninit() -> Self {
         Self {
             instrs: [].into(),
             len_registers: 0,
             consts: [].into(),
+            address_map: [].into(),
+            register_types: None,
+            trap_sites: None,
+            fuel_sites: None,
+            func_index: None,
         }
     }
 
@@ -335,12 +535,229 @@ impl CompiledFuncEntity {
     pub fn consts(&self) -> &[UntypedValue] {
         &self.consts
     }
+
+    /// Returns the address map of the [`CompiledFunc`].
+    ///
+    /// # Note
+    ///
+    /// The address map is a table of `(instr, offset)` entries sorted by `instr`.
+    /// All [`Instruction`] words with index `instr` up to the `instr` of the next entry
+    /// have been translated from the Wasm operator at `offset` in the Wasm binary.
+    pub fn address_map(&self) -> &[(u32, u32)] {
+        &self.address_map
+    }
+
+    /// Returns the [`RegisterTypes`] of the [`CompiledFunc`] if any.
+    pub fn register_types(&self) -> Option<&RegisterTypes> {
+        self.register_types.as_deref()
+    }
+
+    /// Returns the Wasm binary offset of the [`Instruction`] at `instr` if any.
+    ///
+    /// Returns `None` if `instr` is out of bounds or the address map is empty.
+    pub fn wasm_offset(&self, instr: usize) -> Option<u32> {
+        if instr >= self.instrs.len() {
+            return None;
+        }
+        let index = self
+            .address_map
+            .partition_point(|&(start, _)| start as usize <= instr);
+        let (_, offset) = self.address_map.get(index.checked_sub(1)?)?;
+        Some(*offset)
+    }
+
+    /// Returns the [`TrapOrigin`] of the [`Instruction::Trap`] at `instr` if any.
+    ///
+    /// Returns `None` if there is no [`Instruction::Trap`] at `instr`.
+    pub fn trap_origin(&self, instr: usize) -> Option<TrapOrigin> {
+        let trap_sites = self.trap_sites.as_deref()?;
+        let func_index = self.func_index?;
+        let instr = u32::try_from(instr).ok()?;
+        let index = trap_sites
+            .binary_search_by_key(&instr, |&(instr, _)| instr)
+            .ok()?;
+        let (_, wasm_offset) = trap_sites[index];
+        Some(TrapOrigin::new(func_index, wasm_offset))
+    }
+
+    /// Returns the [`FuncInfo`] of the [`CompiledFuncEntity`].
+    pub fn info(&self) -> FuncInfo {
+        FuncInfo {
+            func_index: self.func_index,
+            len_instrs: self.instrs.len(),
+            len_registers: self.len_registers,
+        }
+    }
+
+    /// Returns the [`FuelBreakdown`] of the [`Instruction::ConsumeFuel`] at `instr` if any.
+    ///
+    /// Returns `None` if there is no recorded [`Instruction::ConsumeFuel`] at `instr`.
+    pub fn fuel_breakdown(&self, instr: usize) -> Option<&FuelBreakdown> {
+        let fuel_sites = self.fuel_sites.as_deref()?;
+        let instr = u32::try_from(instr).ok()?;
+        let index = fuel_sites
+            .sites
+            .binary_search_by_key(&instr, |(instr, _)| *instr)
+            .ok()?;
+        Some(&fuel_sites.sites[index].1)
+    }
+
+    /// Returns `true` if the [`CompiledFuncEntity`] needs to be repriced for `costs`.
+    ///
+    /// This is the case if the [`CompiledFuncEntity`] has been translated with fuel metering
+    /// for other [`FuelCosts`] than `costs`.
+    pub fn needs_repricing(&self, costs: &FuelCosts) -> bool {
+        self.fuel_sites
+            .as_deref()
+            .is_some_and(|fuel_sites| fuel_sites.costs != *costs)
+    }
+
+    /// Returns `Ok` if the [`CompiledFuncEntity`] can be repriced for `costs`.
+    ///
+    /// # Errors
+    ///
+    /// If the block fuel of any [`Instruction::ConsumeFuel`] is out of bounds for `costs`.
+    pub fn check_repricing(&self, costs: &FuelCosts) -> Result<(), Error> {
+        if !self.needs_repricing(costs) {
+            return Ok(());
+        }
+        let Some(fuel_sites) = self.fuel_sites.as_deref() else {
+            return Ok(());
+        };
+        for (_, breakdown) in &fuel_sites.sites[..] {
+            breakdown.block_fuel(costs)?;
+        }
+        Ok(())
+    }
+
+    /// Rewrites the block fuel of all [`Instruction::ConsumeFuel`] for `costs`.
+    ///
+    /// # Note
+    ///
+    /// - The block fuel is recomputed from the [`FuelBreakdown`] recorded during translation.
+    ///   Thus the [`CompiledFuncEntity`] consumes the same fuel as if it was translated for `costs`.
+    /// - The [`Instruction`]s are rewritten in place so that [`InstructionPtr`]s into them stay valid.
+    /// - Block fuel that is out of bounds for `costs` saturates.
+    ///   Use [`CompiledFuncEntity::check_repricing`] to detect this beforehand.
+    /// - Does nothing if the [`CompiledFuncEntity`] has been translated without fuel metering.
+    pub fn reprice_fuel(&mut self, costs: &FuelCosts) {
+        if !self.needs_repricing(costs) {
+            return;
+        }
+        let Some(fuel_sites) = self.fuel_sites.as_deref_mut() else {
+            return;
+        };
+        for (instr, breakdown) in &fuel_sites.sites[..] {
+            let block_fuel = breakdown.block_fuel_saturating(costs);
+            self.instrs[*instr as usize] = Instruction::ConsumeFuel(block_fuel);
+        }
+        fuel_sites.costs = *costs;
+    }
+
+    /// Returns an iterator over the internal functions directly called by the [`CompiledFuncEntity`].
+    ///
+    /// # Note
+    ///
+    /// Functions that are called indirectly via tables are not included.
+    fn callees(&self) -> impl Iterator<Item = CompiledFunc> + '_ {
+        self.instrs.iter().filter_map(|instr| match *instr {
+            Instruction::CallInternal0 { func, .. }
+            | Instruction::CallInternal { func, .. }
+            | Instruction::ReturnCallInternal0 { func }
+            | Instruction::ReturnCallInternal { func } => Some(func),
+            _ => None,
+        })
+    }
+
+    /// Returns a copy of the [`CompiledFuncEntity`] with all called internal functions mapped by `f`.
+    fn map_funcs(&self, mut f: impl FnMut(CompiledFunc) -> CompiledFunc) -> Self {
+        let instrs = self
+            .instrs
+            .iter()
+            .map(|instr| match *instr {
+                Instruction::CallInternal0 { results, func } => Instruction::CallInternal0 {
+                    results,
+                    func: f(func),
+                },
+                Instruction::CallInternal { results, func } => Instruction::CallInternal {
+                    results,
+                    func: f(func),
+                },
+                Instruction::ReturnCallInternal0 { func } => {
+                    Instruction::ReturnCallInternal0 { func: f(func) }
+                }
+                Instruction::ReturnCallInternal { func } => {
+                    Instruction::ReturnCallInternal { func: f(func) }
+                }
+                instr => instr,
+            })
+            .collect();
+        Self {
+            instrs,
+            len_registers: self.len_registers,
+            consts: self.consts.clone(),
+            address_map: self.address_map.clone(),
+            register_types: self.register_types.clone(),
+            trap_sites: self.trap_sites.clone(),
+            fuel_sites: self.fuel_sites.clone(),
+            func_index: self.func_index,
+        }
+    }
+
+    /// Returns the index of the [`Instruction`] pointed to by `ip` if it is within `self`.
+    fn instr_index(&self, ip: &InstructionPtr) -> Option<usize> {
+        let start = self.instrs.as_ptr() as usize;
+        let delta = ip.addr().checked_sub(start)?;
+        let index = delta / mem::size_of::<Instruction>();
+        (index < self.instrs.len()).then_some(index)
+    }
 }
 
 /// Datastructure to efficiently store information about compiled functions.
 #[derive(Debug, Default)]
 pub struct CodeMap {
     funcs: Arena<CompiledFunc, FuncEntity>,
+    /// The owners of the allocated [`CompiledFunc`] and the functions they own.
+    ///
+    /// # Note
+    ///
+    /// Functions of owners that no longer exist are reclaimed by [`CodeMap::purge_unused`].
+    owners: Vec<(Weak<()>, Vec<CompiledFunc>)>,
+    /// Reclaimed [`CompiledFunc`] slots that are reused by [`CodeMap::alloc_func`].
+    free: Vec<CompiledFunc>,
+    /// The number of functions that have been compiled lazily so far.
+    lazy_compilations: AtomicUsize,
+}
+
+/// Keeps the [`CompiledFunc`]s allocated for it alive.
+///
+/// # Note
+///
+/// Each [`Module`] owns a [`CodeOwner`] that is shared with all its instances.
+/// Once all of them have been dropped the [`CompiledFunc`]s of the [`Module`]
+/// are reclaimed by the next call to [`Engine::purge_unused`].
+///
+/// [`Module`]: crate::Module
+/// [`Engine::purge_unused`]: crate::Engine::purge_unused
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwner {
+    inner: Arc<()>,
+}
+
+/// The memory used by the functions of a [`CodeMap`] in bytes.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CodeSize {
+    /// The bytes used by the [`Instruction`] words or Wasm function bodies.
+    pub code: usize,
+    /// The bytes used by function local constant values.
+    pub consts: usize,
+}
+
+impl ops::AddAssign for CodeSize {
+    fn add_assign(&mut self, rhs: Self) {
+        self.code += rhs.code;
+        self.consts += rhs.consts;
+    }
 }
 
 /// Atomicly accessible [`CompilationPhase`].
@@ -457,6 +874,18 @@ impl AtomicCompilationPhase {
         self.change_phase_mut(CompilationPhase::Uninitialized, CompilationPhase::Compiled)
     }
 
+    /// Sets [`AtomicCompilationPhase`] to [`CompilationPhase::CompilationFailed`].
+    ///
+    /// # Errors
+    ///
+    /// If the current [`CompilationPhase`] is not [`CompilationPhase::Uninitialized`].
+    pub fn init_compilation_failed(&mut self) -> Result<(), CompilationPhaseError> {
+        self.change_phase_mut(
+            CompilationPhase::Uninitialized,
+            CompilationPhase::CompilationFailed,
+        )
+    }
+
     /// Sets [`AtomicCompilationPhase`] to [`CompilationPhase::Uncompiled`].
     ///
     /// # Errors
@@ -478,6 +907,20 @@ impl AtomicCompilationPhase {
         self.change_phase(CompilationPhase::Uncompiled, CompilationPhase::Compiling)
     }
 
+    /// Resets [`AtomicCompilationPhase`] to [`CompilationPhase::Uncompiled`].
+    ///
+    /// # Note
+    ///
+    /// This is used to abort a compilation attempt that did not fail
+    /// due to the compiled function itself, e.g. when running out of fuel.
+    ///
+    /// # Errors
+    ///
+    /// If the current [`CompilationPhase`] is not [`CompilationPhase::Compiling`].
+    pub fn set_uncompiled(&self) -> Result<(), CompilationPhaseError> {
+        self.change_phase(CompilationPhase::Compiling, CompilationPhase::Uncompiled)
+    }
+
     /// Sets [`AtomicCompilationPhase`] to [`CompilationPhase::CompilationFailed`].
     ///
     /// # Errors
@@ -490,7 +933,7 @@ impl AtomicCompilationPhase {
         )
     }
 
-    /// Sets [`AtomicCompilationPhase`] to [`CompilationPhase::CompilationFailed`].
+    /// Sets [`AtomicCompilationPhase`] to [`CompilationPhase::Compiled`].
     ///
     /// # Errors
     ///
@@ -522,6 +965,17 @@ pub enum CompilationPhase {
 }
 
 /// A function entity of a [`CodeMap`].
+///
+/// # Note
+///
+/// The `phase` acts as a per-function once-cell for lazy compilation:
+///
+/// - Only the single thread that successfully changed the `phase` from
+///   [`CompilationPhase::Uncompiled`] to [`CompilationPhase::Compiling`]
+///   may mutate `func` until it changes the `phase` again.
+/// - In the phases [`CompilationPhase::Compiled`] and [`CompilationPhase::CompilationFailed`]
+///   `func` is never mutated again and may be read by all threads.
+/// - All other mutations require exclusive `&mut` access to the [`FuncEntity`].
 #[derive(Debug)]
 struct FuncEntity {
     /// Synchronization for the `func` field.
@@ -530,6 +984,14 @@ struct FuncEntity {
     func: UnsafeCell<InternalFuncEntity>,
 }
 
+/// It is safe to share a [`FuncEntity`] between threads.
+///
+/// All shared accesses to `func` are synchronized via `phase` as described
+/// in the docs of [`FuncEntity`]. Phase changes that publish `func` to other
+/// threads use release semantics and all loads of the `phase` that precede
+/// reads of `func` use acquire semantics.
+unsafe impl Sync for FuncEntity {}
+
 impl FuncEntity {
     /// Create a new uninitialized [`FuncEntity`].
     pub fn uninit() -> Self {
@@ -560,6 +1022,27 @@ impl FuncEntity {
         )
     }
 
+    /// Initializes the [`CompiledFunc`] to a state of failed lazy compilation.
+    ///
+    /// # Panics
+    ///
+    /// If `func` has already been initialized [`CompiledFunc`].
+    pub fn init_failed(&mut self, entity: FailedFuncEntity) {
+        assert!(
+            self.phase.is_uninit(),
+            "function ({:?}) must be uninitialized but found: {:?}",
+            self.func,
+            self.phase
+        );
+        *self.func.get_mut() = InternalFuncEntity::Failed(entity);
+        assert!(
+            self.phase.init_compilation_failed().is_ok(),
+            "function ({:?}) must be initializing but found: {:?}",
+            self.func,
+            self.phase
+        )
+    }
+
     /// Initializes the [`CompiledFunc`] to an uncompiled state.
     ///
     /// # Panics
@@ -568,6 +1051,7 @@ impl FuncEntity {
     pub fn init_uncompiled(
         &mut self,
         func_idx: FuncIdx,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
@@ -579,7 +1063,8 @@ impl FuncEntity {
             self.phase
         );
         *self.func.get_mut() =
-            UncompiledFuncEntity::new(func_idx, bytes, module.clone(), func_to_validate).into();
+            UncompiledFuncEntity::new(func_idx, offset, bytes, module.clone(), func_to_validate)
+                .into();
         assert!(
             self.phase.init_uncompiled().is_ok(),
             "function ({:?}) must be initializing but found: {:?}",
@@ -588,6 +1073,60 @@ impl FuncEntity {
         )
     }
 
+    /// Returns the index of the Wasm function within its Wasm module if any.
+    ///
+    /// Returns `None` if the [`FuncEntity`] is uninitialized.
+    fn func_index(&mut self) -> Option<u32> {
+        if self.phase.is_uninit() {
+            return None;
+        }
+        match self.func.get_mut() {
+            InternalFuncEntity::Compiled(func) => func.func_index,
+            InternalFuncEntity::Uncompiled(func) => Some(func.func_idx.into_u32()),
+            InternalFuncEntity::Failed(func) => Some(func.func_idx.into_u32()),
+        }
+    }
+
+    /// Returns the [`CodeSize`] of the [`FuncEntity`].
+    ///
+    /// # Note
+    ///
+    /// - Uncompiled functions account for the Wasm function body kept for their compilation.
+    /// - Uncompiled functions are briefly claimed via their `phase` in order to be measured.
+    ///   This busy waits if another thread is currently compiling the [`FuncEntity`].
+    fn code_size(&self) -> CodeSize {
+        loop {
+            if let Some(func) = self.get_compiled() {
+                return CodeSize {
+                    code: mem::size_of_val(func.instrs()),
+                    consts: mem::size_of_val(func.consts()),
+                };
+            }
+            if matches!(
+                self.phase.get(),
+                CompilationPhase::Uninitialized | CompilationPhase::CompilationFailed
+            ) {
+                // Case: The function holds no code or only its compilation error.
+                return CodeSize::default();
+            }
+            let Ok(_) = self.phase.set_compiling() else {
+                // Case: Another thread is currently compiling the function so we have to wait.
+                hint::spin_loop();
+                continue;
+            };
+            // SAFETY: Since we changed the phase to `Compiling` no other thread
+            //         accesses `self.func` until we change the phase back.
+            let code = match unsafe { &*self.func.get() } {
+                InternalFuncEntity::Uncompiled(func) => func.bytes.as_slice().len(),
+                func => unreachable!("expected func to be uncompiled: {func:?}"),
+            };
+            self.phase
+                .set_uncompiled()
+                .expect("unexpectedly failed to release uncompiled function");
+            return CodeSize { code, consts: 0 };
+        }
+    }
+
     /// Returns the [`CompiledFuncEntity`] if possible.
     ///
     /// Returns `None` if the [`FuncEntity`] has not yet been compiled.
@@ -599,7 +1138,10 @@ impl FuncEntity {
             //         A `CompiledFuncEntity` cannot be mutated after it has been compiled.
             match unsafe { &*self.func.get() } {
                 InternalFuncEntity::Compiled(func) => return Some(func),
-                InternalFuncEntity::Uncompiled(_func) => {
+                #[cfg(feature = "checked-executor")]
+                func => unreachable!("expected func to be compiled: {func:?}"),
+                #[cfg(not(feature = "checked-executor"))]
+                InternalFuncEntity::Uncompiled(_) | InternalFuncEntity::Failed(_) => {
                     // SAFETY: Since the function is in compiled state we are guaranteed
                     //         that it is an `InternalFuncEntity::Compiled` variant.
                     unsafe { hint::unreachable_unchecked() }
@@ -609,6 +1151,34 @@ impl FuncEntity {
         None
     }
 
+    /// Returns the [`CompiledFuncEntity`] if possible.
+    ///
+    /// Returns `None` if the [`FuncEntity`] has not yet been compiled.
+    fn get_compiled_mut(&mut self) -> Option<&mut CompiledFuncEntity> {
+        if !self.phase.is_compiled() {
+            return None;
+        }
+        match self.func.get_mut() {
+            InternalFuncEntity::Compiled(func) => Some(func),
+            func => unreachable!("expected func to be compiled: {func:?}"),
+        }
+    }
+
+    /// Returns the [`FailedFuncEntity`] if the lazy compilation of the [`FuncEntity`] failed.
+    ///
+    /// Returns `None` otherwise.
+    fn get_failed(&self) -> Option<&FailedFuncEntity> {
+        if !matches!(self.phase.get(), CompilationPhase::CompilationFailed) {
+            return None;
+        }
+        // SAFETY: Since the phase is `CompilationFailed` we are guaranteed that
+        //         `self.func` is immutably initialized with a `FailedFuncEntity`.
+        match unsafe { &*self.func.get() } {
+            InternalFuncEntity::Failed(func) => Some(func),
+            func => unreachable!("expected func to have failed compilation: {func:?}"),
+        }
+    }
+
     /// Compile the [`FuncEntity`] if necessary and return the resulting [`CompiledFuncEntity`].
     ///
     /// # Note
@@ -616,6 +1186,11 @@ impl FuncEntity {
     /// This will either compile the [`FuncEntity`] or busy wait until
     /// another thread is done compiling the [`FuncEntity`].
     ///
+    /// Each [`FuncEntity`] is compiled at most once even if many threads
+    /// call this method concurrently. No lock other than the `phase` of
+    /// the [`FuncEntity`] itself is acquired for the compilation, thus
+    /// threads may concurrently execute or compile other functions.
+    ///
     /// # Errors
     ///
     /// - If translation or Wasm validation of the [`FuncEntity`] failed.
@@ -624,18 +1199,33 @@ impl FuncEntity {
     pub fn compile_and_get(
         &self,
         mut fuel: Option<&mut Fuel>,
+        compilations: &AtomicUsize,
     ) -> Result<&CompiledFuncEntity, Error> {
         loop {
             if let Some(func) = self.get_compiled() {
                 // Case: The function has been compiled and can be returned.
                 return Ok(func);
             }
+            if matches!(self.phase.get(), CompilationPhase::Uninitialized) {
+                // Case: The function has never been initialized or has been purged.
+                panic!(
+                    "tried to compile an uninitialized function: {:?}",
+                    self.func
+                )
+            }
             if matches!(self.phase.get(), CompilationPhase::CompilationFailed) {
                 // Case: Another thread failed to compile the function.
-                return Err(Error::from(TranslationError::LazyCompilationFailed));
+                //
+                // SAFETY: Since the phase is `CompilationFailed` we are guaranteed that
+                //         `self.func` is immutably initialized with a `FailedFuncEntity`.
+                match unsafe { &*self.func.get() } {
+                    InternalFuncEntity::Failed(func) => return Err(func.to_error()),
+                    func => unreachable!("expected func to have failed compilation: {func:?}"),
+                }
             }
             let Ok(_) = self.phase.set_compiling() else {
                 // Case: Another thread is currently compiling the function so we have to wait.
+                hint::spin_loop();
                 continue;
             };
             // At this point we are now in charge of driving the function translation.
@@ -645,14 +1235,40 @@ impl FuncEntity {
             let func = unsafe { &mut *self.func.get() };
             // Note: We need to use `take` because Rust doesn't know that this part of
             //       the loop is only executed once.
-            let fuel = fuel.take();
-            match func.compile(fuel) {
+            if let Err(error) = func.charge_compilation_fuel(fuel.take()) {
+                // Note: Running out of fuel is not a compilation failure and therefore
+                //       we need to allow for future compilation attempts.
+                self.phase
+                    .set_uncompiled()
+                    .expect("unexpectedly failed to abort function compilation");
+                return Err(error);
+            }
+            let func_idx = func.uncompiled().func_idx;
+            #[cfg(feature = "tracing")]
+            let started = func.uncompiled().start_tracing_timer();
+            match func.compile() {
                 Ok(()) => {
                     self.phase
                         .set_compiled()
                         .expect("unexpectedly failed to finish function compilation");
+                    compilations.fetch_add(1, Ordering::Relaxed);
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(
+                        func_index = func_idx.into_u32(),
+                        duration_ns = started
+                            .map(|started| started.elapsed())
+                            .unwrap_or_default()
+                            .as_nanos() as u64,
+                        "compiled function lazily",
+                    );
                 }
                 Err(error) => {
+                    let failed = FailedFuncEntity {
+                        func_idx,
+                        error: Arc::new(error),
+                    };
+                    let error = failed.to_error();
+                    *func = InternalFuncEntity::Failed(failed);
                     self.phase
                         .set_compilation_failed()
                         .expect("unexpectedly failed to mark function compilation failed");
@@ -668,10 +1284,96 @@ impl CodeMap {
     ///
     /// # Note
     ///
-    /// The uninitialized [`CompiledFunc`] must be initialized using
-    /// [`CodeMap::init_func`] before it is executed.
-    pub fn alloc_func(&mut self) -> CompiledFunc {
-        self.funcs.alloc(FuncEntity::uninit())
+    /// - The uninitialized [`CompiledFunc`] must be initialized using
+    ///   [`CodeMap::init_func`] before it is executed.
+    /// - The [`CompiledFunc`] is kept alive as long as `owner` exists.
+    pub fn alloc_func(&mut self, owner: &CodeOwner) -> CompiledFunc {
+        let func = match self.free.pop() {
+            Some(func) => func,
+            None => self.funcs.alloc(FuncEntity::uninit()),
+        };
+        match self.owners.last_mut() {
+            Some((last, funcs)) if Weak::ptr_eq(last, &Arc::downgrade(&owner.inner)) => {
+                funcs.push(func);
+            }
+            _ => self.owners.push((Arc::downgrade(&owner.inner), vec![func])),
+        }
+        func
+    }
+
+    /// Reclaims all [`CompiledFunc`]s whose [`CodeOwner`] no longer exists.
+    ///
+    /// Returns the number of reclaimed [`CompiledFunc`]s.
+    pub fn purge_unused(&mut self) -> usize {
+        let mut purged = 0;
+        self.owners.retain(|(owner, funcs)| {
+            if owner.strong_count() != 0 {
+                return true;
+            }
+            for &func in funcs {
+                self.funcs[func] = FuncEntity::uninit();
+                self.free.push(func);
+            }
+            purged += funcs.len();
+            false
+        });
+        purged
+    }
+
+    /// Returns the number of allocated [`CompiledFunc`]s that have not been reclaimed.
+    pub fn len_funcs(&self) -> usize {
+        self.funcs.len() - self.free.len()
+    }
+
+    /// Returns the total [`CodeSize`] of all allocated [`CompiledFunc`]s.
+    pub fn code_size(&self) -> CodeSize {
+        let mut size = CodeSize::default();
+        for (_, func) in self.funcs.iter() {
+            size += func.code_size();
+        }
+        size
+    }
+
+    /// Returns the total [`CodeSize`] of all `funcs`.
+    ///
+    /// # Panics
+    ///
+    /// If any of the `funcs` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn code_size_of(&self, funcs: &[CompiledFunc]) -> CodeSize {
+        let mut size = CodeSize::default();
+        for &func in funcs {
+            let Some(entity) = self.funcs.get(func) else {
+                panic!("encountered invalid function index: {func:?}")
+            };
+            size += entity.code_size();
+        }
+        size
+    }
+
+    /// Rewrites the block fuel of all compiled [`CompiledFunc`]s for `costs`.
+    ///
+    /// # Note
+    ///
+    /// - Uncompiled [`CompiledFunc`]s are translated with the [`FuelCosts`] at the time
+    ///   of their compilation and thus need no repricing.
+    /// - Read [`CompiledFuncEntity::reprice_fuel`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// If the block fuel of any [`Instruction::ConsumeFuel`] is out of bounds for `costs`.
+    /// In this case no [`CompiledFunc`] is repriced.
+    pub fn reprice_fuel(&mut self, costs: &FuelCosts) -> Result<(), Error> {
+        for (_, func) in self.funcs.iter() {
+            if let Some(func) = func.get_compiled() {
+                func.check_repricing(costs)?;
+            }
+        }
+        for (_, func) in self.funcs.iter_mut() {
+            if let Some(func) = func.get_compiled_mut() {
+                func.reprice_fuel(costs);
+            }
+        }
+        Ok(())
     }
 
     /// Initializes the [`CompiledFunc`] with its [`CompiledFuncEntity`].
@@ -687,6 +1389,51 @@ impl CodeMap {
         func.init_compiled(entity);
     }
 
+    /// Replaces the [`CompiledFunc`] with the already compiled `entity`.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    #[cfg(all(test, feature = "checked-executor"))]
+    pub fn replace_func(&mut self, func: CompiledFunc, entity: CompiledFuncEntity) {
+        let Some(func) = self.funcs.get_mut(func) else {
+            panic!("encountered invalid function index for replacement: {func:?}")
+        };
+        *func = FuncEntity::uninit();
+        func.init_compiled(entity);
+    }
+
+    /// Replaces the [`CompiledFunc`] with the externally translated `entity`.
+    ///
+    /// # Note
+    ///
+    /// The `entity` inherits the index of the Wasm function within its Wasm module from `func`.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    pub fn install_func(&mut self, func: CompiledFunc, mut entity: CompiledFuncEntity) {
+        let Some(func) = self.funcs.get_mut(func) else {
+            panic!("encountered invalid function index for installation: {func:?}")
+        };
+        entity.func_index = func.func_index();
+        *func = FuncEntity::uninit();
+        func.init_compiled(entity);
+    }
+
+    /// Initializes the [`CompiledFunc`] to a state of failed lazy compilation.
+    ///
+    /// # Panics
+    ///
+    /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    /// - If `func` refers to an already initialized [`CompiledFunc`].
+    pub fn init_failed_func(&mut self, func: CompiledFunc, entity: FailedFuncEntity) {
+        let Some(func) = self.funcs.get_mut(func) else {
+            panic!("encountered invalid function index for initialization: {func:?}")
+        };
+        func.init_failed(entity);
+    }
+
     /// Initializes the [`CompiledFunc`] for lazy translation.
     ///
     /// # Panics
@@ -697,6 +1444,7 @@ impl CodeMap {
         &mut self,
         func: CompiledFunc,
         func_idx: FuncIdx,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
@@ -704,7 +1452,7 @@ impl CodeMap {
         let Some(func) = self.funcs.get_mut(func) else {
             panic!("encountered invalid function index for initialization: {func:?}")
         };
-        func.init_uncompiled(func_idx, bytes, module, func_to_validate);
+        func.init_uncompiled(func_idx, offset, bytes, module, func_to_validate);
     }
 
     /// Returns the [`InternalFuncEntity`] of the [`CompiledFunc`].
@@ -724,9 +1472,151 @@ impl CodeMap {
         };
         match func.get_compiled() {
             Some(func) => Ok(func),
-            None => func.compile_and_get(fuel),
+            None => func.compile_and_get(fuel, &self.lazy_compilations),
+        }
+    }
+
+    /// Returns the [`CompiledFuncEntity`] of `compiled_func` if it has already been compiled.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`CodeMap::get`] this never compiles `compiled_func`.
+    ///
+    /// # Panics
+    ///
+    /// If `compiled_func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    #[track_caller]
+    pub fn get_compiled(&self, compiled_func: CompiledFunc) -> Option<&CompiledFuncEntity> {
+        let Some(func) = self.funcs.get(compiled_func) else {
+            panic!("invalid compiled func: {compiled_func:?}")
+        };
+        func.get_compiled()
+    }
+
+    /// Returns `true` if `compiled_func` has already been compiled.
+    ///
+    /// # Panics
+    ///
+    /// If `compiled_func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
+    #[track_caller]
+    pub fn is_compiled(&self, compiled_func: CompiledFunc) -> bool {
+        self.get_compiled(compiled_func).is_some()
+    }
+
+    /// Returns the number of functions that have been compiled lazily by the [`CodeMap`].
+    pub fn lazy_compilations(&self) -> usize {
+        self.lazy_compilations.load(Ordering::Relaxed)
+    }
+
+    /// Compiles all `funcs` that have not yet been compiled.
+    ///
+    /// If `transitive` is `true` all internal functions that are directly called
+    /// by the compiled functions are compiled as well, transitively.
+    ///
+    /// # Note
+    ///
+    /// Each function is compiled at most once even if other threads concurrently
+    /// execute or compile the same functions.
+    ///
+    /// # Errors
+    ///
+    /// If the lazy compilation of any of the functions failed.
+    pub fn compile_funcs(
+        &self,
+        funcs: impl IntoIterator<Item = CompiledFunc>,
+        transitive: bool,
+    ) -> Result<(), Error> {
+        let mut queue = funcs.into_iter().collect::<Vec<_>>();
+        let mut visited = BTreeSet::new();
+        while let Some(func) = queue.pop() {
+            if !visited.insert(func) {
+                continue;
+            }
+            let compiled = self.get(None, func)?;
+            if transitive {
+                queue.extend(compiled.callees());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of `func` with all called internal functions mapped by `f`.
+    ///
+    /// # Note
+    ///
+    /// - Compiles `func` first if it has not yet been compiled.
+    /// - The copy is meant to initialize a function of another [`CodeMap`]
+    ///   via [`CodeMap::init_func`] or [`CodeMap::init_failed_func`].
+    ///
+    /// # Errors
+    ///
+    /// If the lazy compilation of `func` failed.
+    pub fn copy_func(
+        &self,
+        func: CompiledFunc,
+        f: impl FnMut(CompiledFunc) -> CompiledFunc,
+    ) -> Result<CompiledFuncEntity, FailedFuncEntity> {
+        let Some(entity) = self.funcs.get(func) else {
+            panic!("invalid compiled func: {func:?}")
+        };
+        // Note: Without fuel metering compilation can only fail due to the function itself.
+        match entity.compile_and_get(None, &self.lazy_compilations) {
+            Ok(compiled) => Ok(compiled.map_funcs(f)),
+            Err(_) => match entity.get_failed() {
+                Some(failed) => Err(failed.clone()),
+                None => unreachable!("expected func to have failed compilation: {func:?}"),
+            },
         }
     }
+
+    /// Verifies that the Wasmi bytecode of `func` upholds its encoding invariants.
+    ///
+    /// # Note
+    ///
+    /// Compiles `func` first if it has not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// - If translation or Wasm validation of `func` failed.
+    /// - If the Wasmi bytecode of `func` violates its encoding invariants.
+    pub fn verify_function(&self, func: CompiledFunc) -> Result<(), Error> {
+        let func = self.get(None, func)?;
+        verify_instrs(func.instrs())?;
+        Ok(())
+    }
+
+    /// Returns the Wasm binary offset of the [`Instruction`] pointed to by `ip` if any.
+    ///
+    /// # Note
+    ///
+    /// This searches all compiled functions with an address map for the one that
+    /// contains `ip` and thus must only be used in cold paths, e.g. upon traps.
+    ///
+    /// Returns `None` if no compiled function with an address map contains `ip`.
+    #[cold]
+    pub fn wasm_offset(&self, ip: &InstructionPtr) -> Option<u32> {
+        self.funcs.iter().find_map(|(_, func)| {
+            let func = func.get_compiled()?;
+            if func.address_map().is_empty() {
+                return None;
+            }
+            func.wasm_offset(func.instr_index(ip)?)
+        })
+    }
+
+    /// Returns the [`TrapOrigin`] of the [`Instruction::Trap`] pointed to by `ip` if any.
+    ///
+    /// # Note
+    ///
+    /// This searches all compiled functions for the one that contains `ip`
+    /// and thus must only be used in cold paths, e.g. upon traps.
+    #[cold]
+    pub fn trap_origin(&self, ip: &InstructionPtr) -> Option<TrapOrigin> {
+        self.funcs.iter().find_map(|(_, func)| {
+            let func = func.get_compiled()?;
+            func.trap_origin(func.instr_index(ip)?)
+        })
+    }
 }
 
 /// The instruction pointer to the instruction of a function on the call stack.
@@ -734,6 +1624,12 @@ impl CodeMap {
 pub struct InstructionPtr {
     /// The pointer to the instruction.
     ptr: *const Instruction,
+    /// The pointer to the first instruction of the function.
+    #[cfg(feature = "checked-executor")]
+    start: *const Instruction,
+    /// The pointer one past the last instruction of the function.
+    #[cfg(feature = "checked-executor")]
+    end: *const Instruction,
 }
 
 /// It is safe to send an [`InstructionPtr`] to another thread.
@@ -747,10 +1643,18 @@ pub struct InstructionPtr {
 unsafe impl Send for InstructionPtr {}
 
 impl InstructionPtr {
-    /// Creates a new [`InstructionPtr`] for `instr`.
+    /// Creates a new [`InstructionPtr`] pointing to the first instruction of `instrs`.
     #[inline]
-    pub fn new(ptr: *const Instruction) -> Self {
-        Self { ptr }
+    pub fn new(instrs: &[Instruction]) -> Self {
+        #[cfg(feature = "checked-executor")]
+        let bounds = instrs.as_ptr_range();
+        Self {
+            ptr: instrs.as_ptr(),
+            #[cfg(feature = "checked-executor")]
+            start: bounds.start,
+            #[cfg(feature = "checked-executor")]
+            end: bounds.end,
+        }
     }
 
     /// Offset the [`InstructionPtr`] by the given value.
@@ -761,6 +1665,7 @@ impl InstructionPtr {
     /// offset values so that the [`InstructionPtr`] never points out of valid
     /// bounds of the instructions of the same compiled Wasm function.
     #[inline(always)]
+    #[cfg(not(feature = "checked-executor"))]
     pub fn offset(&mut self, by: isize) {
         // SAFETY: Within Wasm bytecode execution we are guaranteed by
         //         Wasm validation and Wasmi codegen to never run out
@@ -768,7 +1673,19 @@ impl InstructionPtr {
         self.ptr = unsafe { self.ptr.offset(by) };
     }
 
+    /// Offset the [`InstructionPtr`] by the given value.
+    ///
+    /// # Panics
+    ///
+    /// If the [`InstructionPtr`] points out of bounds of the
+    /// instructions of its compiled Wasm function afterwards.
+    #[cfg(feature = "checked-executor")]
+    pub fn offset(&mut self, by: isize) {
+        self.set_checked(self.ptr.wrapping_offset(by))
+    }
+
     #[inline(always)]
+    #[cfg(not(feature = "checked-executor"))]
     pub fn add(&mut self, delta: usize) {
         // SAFETY: Within Wasm bytecode execution we are guaranteed by
         //         Wasm validation and Wasmi codegen to never run out
@@ -776,6 +1693,38 @@ impl InstructionPtr {
         self.ptr = unsafe { self.ptr.add(delta) };
     }
 
+    /// Advances the [`InstructionPtr`] by `delta`.
+    ///
+    /// # Panics
+    ///
+    /// If the [`InstructionPtr`] points out of bounds of the
+    /// instructions of its compiled Wasm function afterwards.
+    #[cfg(feature = "checked-executor")]
+    pub fn add(&mut self, delta: usize) {
+        self.set_checked(self.ptr.wrapping_add(delta))
+    }
+
+    /// Sets the [`InstructionPtr`] to `ptr`.
+    ///
+    /// # Panics
+    ///
+    /// If `ptr` points out of bounds of the instructions of the compiled Wasm function.
+    #[cfg(feature = "checked-executor")]
+    fn set_checked(&mut self, ptr: *const Instruction) {
+        assert!(
+            (self.start..self.end).contains(&ptr),
+            "instruction pointer out of bounds of its function: {ptr:?} not in {:?}",
+            self.start..self.end,
+        );
+        self.ptr = ptr;
+    }
+
+    /// Returns the address of the currently pointed at [`Instruction`].
+    #[inline(always)]
+    pub fn addr(&self) -> usize {
+        self.ptr as usize
+    }
+
     /// Returns a shared reference to the currently pointed at [`Instruction`].
     ///
     /// # Safety
@@ -1,4 +1,4 @@
-use super::StackLimits;
+use super::{ExecutionDigest, ModuleLimits, StackLimits};
 use core::{mem::size_of, num::NonZeroU64};
 use wasmi_core::UntypedValue;
 use wasmparser::WasmFeatures;
@@ -6,13 +6,21 @@ use wasmparser::WasmFeatures;
 /// The default amount of stacks kept in the cache at most.
 const DEFAULT_CACHED_STACKS: usize = 2;
 
+/// The value stack height in bytes of [`Config::small`].
+const SMALL_VALUE_STACK_HEIGHT: usize = 16 * 1024;
+
+/// The maximum recursion depth of [`Config::small`].
+const SMALL_MAX_RECURSION_DEPTH: usize = 128;
+
 /// Configuration for an [`Engine`].
 ///
 /// [`Engine`]: [`crate::Engine`]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Config {
     /// The limits set on the value stack and call stack.
     stack_limits: StackLimits,
+    /// The limits set on the complexity of parsed and translated Wasm modules.
+    module_limits: ModuleLimits,
     /// The amount of Wasm stacks to keep in cache at most.
     cached_stacks: usize,
     /// Is `true` if the `mutable-global` Wasm proposal is enabled.
@@ -31,23 +39,79 @@ pub struct Config {
     tail_call: bool,
     /// Is `true` if the [`extended-const`] Wasm proposal is enabled.
     extended_const: bool,
+    /// Is `true` if the [`custom-page-sizes`] Wasm proposal is enabled.
+    custom_page_sizes: bool,
     /// Is `true` if Wasm instructions on `f32` and `f64` types are allowed.
     floats: bool,
     /// Is `true` if Wasmi executions shall consume fuel.
     consume_fuel: bool,
+    /// Is `true` if translated functions contain yield checks even without fuel metering.
+    cooperative_yield: bool,
+    /// Is `true` if `memory.grow` traps when running out of fuel instead of returning `-1`.
+    memory_grow_traps_on_out_of_fuel: bool,
+    /// Is `true` if lazily compiled Wasm functions charge fuel for their compilation.
+    charge_compilation_fuel: bool,
+    /// Is `true` if [`Linker::define_builtins`] defines the `wasmi.yield` builtin function.
+    ///
+    /// [`Linker::define_builtins`]: crate::Linker::define_builtins
+    #[cfg(feature = "resumable")]
+    builtin_yield: bool,
+    /// Is `true` if [`Linker::define_builtins`] defines the `wasmi.batch_call` builtin function.
+    ///
+    /// [`Linker::define_builtins`]: crate::Linker::define_builtins
+    builtin_batch_call: bool,
+    /// Is `true` if `funcref` tables are initialized lazily by active element segments.
+    lazy_table_init: bool,
+    /// Is `true` if linear memory and table accesses are hardened against speculative execution.
+    spectre_mitigations: bool,
+    /// The level of optimizations applied to the translated Wasmi bytecode.
+    optimization_level: u8,
+    /// The maximum number of registers of a translated Wasm function.
+    max_registers_per_function: u32,
+    /// Is `true` if translated functions record their Wasm bytecode offsets.
+    generate_address_map: bool,
+    /// Is `true` if translated functions record the types of their registers.
+    generate_register_types: bool,
     /// The configured fuel costs of all Wasmi bytecode instructions.
     fuel_costs: FuelCosts,
     /// The mode of Wasm to Wasmi bytecode compilation.
     compilation_mode: CompilationMode,
-    /// Is `true` if Wasmi executions shall generate a runtime signature.
-    update_runtime_signature: bool,
+    /// The strategy to compute the runtime signature of Wasmi executions.
+    execution_digest: ExecutionDigest,
+    /// Is `true` if the [`Engine`] must not depend on ambient state such as the wall clock.
+    ///
+    /// [`Engine`]: crate::Engine
+    deterministic_only: bool,
+    /// Is `true` if executed instructions are counted per [`InstructionCategory`].
+    ///
+    /// [`InstructionCategory`]: crate::InstructionCategory
+    #[cfg(feature = "metrics")]
+    profiling: bool,
+    /// Is `true` if the executed Wasm function of each thread is tracked for sampling.
+    #[cfg(feature = "metrics")]
+    sampling: bool,
 }
 
 /// Type storing all kinds of fuel costs of instructions.
-#[derive(Debug, Copy, Clone)]
+///
+/// # Note
+///
+/// The fuel costs are used for the translation of Wasm functions and can be
+/// changed for already translated functions via [`Engine::reprice_fuel`].
+///
+/// [`Engine::reprice_fuel`]: crate::Engine::reprice_fuel
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct FuelCosts {
     /// The base fuel costs for all instructions.
     base: u64,
+    /// The fuel costs for all entity related instructions.
+    entity: u64,
+    /// The fuel costs for all load instructions.
+    load: u64,
+    /// The fuel costs for all store instructions.
+    store: u64,
+    /// The fuel costs for all call instructions.
+    call: u64,
     /// The register copies that can be performed per unit of fuel.
     copies_per_fuel: NonZeroU64,
     /// The bytes that can be copied per unit of fuel.
@@ -62,26 +126,78 @@ impl FuelCosts {
 
     /// Returns the base fuel costs for all Wasmi IR entity related instructions.
     pub fn entity(&self) -> u64 {
-        // Note: For simplicity we currently simply use base costs.
-        self.base
+        self.entity
     }
 
     /// Returns the base fuel costs for all Wasmi IR load instructions.
     pub fn load(&self) -> u64 {
-        // Note: For simplicity we currently simply use base costs.
-        self.base
+        self.load
     }
 
     /// Returns the base fuel costs for all Wasmi IR store instructions.
     pub fn store(&self) -> u64 {
-        // Note: For simplicity we currently simply use base costs.
-        self.base
+        self.store
     }
 
     /// Returns the base fuel costs for all Wasmi IR call instructions.
     pub fn call(&self) -> u64 {
-        // Note: For simplicity we currently simply use base costs.
-        self.base
+        self.call
+    }
+
+    /// Sets the base fuel costs for all Wasmi IR instructions.
+    ///
+    /// Defaults to 1.
+    pub fn set_base(&mut self, fuel: u64) -> &mut Self {
+        self.base = fuel;
+        self
+    }
+
+    /// Sets the base fuel costs for all Wasmi IR entity related instructions.
+    ///
+    /// Defaults to 1.
+    pub fn set_entity(&mut self, fuel: u64) -> &mut Self {
+        self.entity = fuel;
+        self
+    }
+
+    /// Sets the base fuel costs for all Wasmi IR load instructions.
+    ///
+    /// Defaults to 1.
+    pub fn set_load(&mut self, fuel: u64) -> &mut Self {
+        self.load = fuel;
+        self
+    }
+
+    /// Sets the base fuel costs for all Wasmi IR store instructions.
+    ///
+    /// Defaults to 1.
+    pub fn set_store(&mut self, fuel: u64) -> &mut Self {
+        self.store = fuel;
+        self
+    }
+
+    /// Sets the base fuel costs for all Wasmi IR call instructions.
+    ///
+    /// Defaults to 1.
+    pub fn set_call(&mut self, fuel: u64) -> &mut Self {
+        self.call = fuel;
+        self
+    }
+
+    /// Sets the number of register copies performed per unit of fuel.
+    ///
+    /// Defaults to 8.
+    pub fn set_copies_per_fuel(&mut self, copies: NonZeroU64) -> &mut Self {
+        self.copies_per_fuel = copies;
+        self
+    }
+
+    /// Sets the number of byte copies performed per unit of fuel.
+    ///
+    /// Defaults to 64.
+    pub fn set_bytes_per_fuel(&mut self, bytes: NonZeroU64) -> &mut Self {
+        self.bytes_per_fuel = bytes;
+        self
     }
 
     /// Returns the number of register copies performed per unit of fuel.
@@ -140,6 +256,10 @@ impl Default for FuelCosts {
         let registers_per_fuel = bytes_per_fuel / bytes_per_register;
         Self {
             base: 1,
+            entity: 1,
+            load: 1,
+            store: 1,
+            call: 1,
             copies_per_fuel: NonZeroU64::new(registers_per_fuel)
                 .unwrap_or_else(|| panic!("invalid zero value for copies_per_fuel value")),
             bytes_per_fuel: NonZeroU64::new(bytes_per_fuel)
@@ -149,7 +269,7 @@ impl Default for FuelCosts {
 }
 
 /// The chosen mode of Wasm to Wasmi bytecode compilation.
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub enum CompilationMode {
     /// The Wasm code is compiled eagerly to Wasmi bytecode.
     #[default]
@@ -169,6 +289,7 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             stack_limits: StackLimits::default(),
+            module_limits: ModuleLimits::default(),
             cached_stacks: DEFAULT_CACHED_STACKS,
             mutable_global: true,
             sign_extension: true,
@@ -178,16 +299,61 @@ impl Default for Config {
             reference_types: true,
             tail_call: false,
             extended_const: false,
+            custom_page_sizes: false,
             floats: true,
             consume_fuel: false,
+            cooperative_yield: false,
+            memory_grow_traps_on_out_of_fuel: true,
+            charge_compilation_fuel: true,
+            #[cfg(feature = "resumable")]
+            builtin_yield: false,
+            builtin_batch_call: false,
+            lazy_table_init: false,
+            spectre_mitigations: false,
+            optimization_level: 0,
+            max_registers_per_function: u32::MAX,
+            generate_address_map: false,
+            generate_register_types: false,
             fuel_costs: FuelCosts::default(),
             compilation_mode: CompilationMode::default(),
-            update_runtime_signature: false,
+            execution_digest: ExecutionDigest::None,
+            deterministic_only: false,
+            #[cfg(feature = "metrics")]
+            profiling: false,
+            #[cfg(feature = "metrics")]
+            sampling: false,
         }
     }
 }
 
 impl Config {
+    /// Returns a [`Config`] for tiny targets with a small and predictable memory footprint.
+    ///
+    /// The returned [`Config`] differs from [`Config::default`] as follows:
+    ///
+    /// - The Wasm stacks have a fixed capacity of 16 KiB of values and 128 nested calls
+    ///   as described by [`StackLimits::fixed`]. Exceeding them results in a
+    ///   [`TrapCode::StackOverflow`] instead of reallocating the stacks.
+    /// - At most a single Wasm stack is cached for reuse.
+    /// - Wasm functions are compiled eagerly via [`CompilationMode::Eager`] so that
+    ///   no Wasm bytecode needs to be kept around for lazy compilation.
+    ///
+    /// # Note
+    ///
+    /// Disable the `resumable` crate feature in order to also remove the
+    /// resumable call machinery from the compiled binary.
+    ///
+    /// [`TrapCode::StackOverflow`]: crate::core::TrapCode::StackOverflow
+    pub fn small() -> Self {
+        let value_stack_height = SMALL_VALUE_STACK_HEIGHT / size_of::<UntypedValue>();
+        Self {
+            stack_limits: StackLimits::fixed(value_stack_height, SMALL_MAX_RECURSION_DEPTH),
+            cached_stacks: 1,
+            compilation_mode: CompilationMode::Eager,
+            ..Self::default()
+        }
+    }
+
     /// Sets the [`StackLimits`] for the [`Config`].
     pub fn set_stack_limits(&mut self, stack_limits: StackLimits) -> &mut Self {
         self.stack_limits = stack_limits;
@@ -199,6 +365,19 @@ impl Config {
         self.stack_limits
     }
 
+    /// Sets the [`ModuleLimits`] for the [`Config`].
+    ///
+    /// Parsing or translating a Wasm module that exceeds any of the limits fails with an error.
+    pub fn set_module_limits(&mut self, module_limits: ModuleLimits) -> &mut Self {
+        self.module_limits = module_limits;
+        self
+    }
+
+    /// Returns the [`ModuleLimits`] of the [`Config`].
+    pub(crate) fn module_limits(&self) -> &ModuleLimits {
+        &self.module_limits
+    }
+
     /// Sets the maximum amount of cached stacks for reuse for the [`Config`].
     ///
     /// # Note
@@ -311,6 +490,29 @@ impl Config {
         self
     }
 
+    /// Enable or disable the [`custom-page-sizes`] Wasm proposal for the [`Config`].
+    ///
+    /// # Note
+    ///
+    /// - Disabled by default.
+    /// - If enabled, host defined [`Memory`] instances may use pages other
+    ///   than 64 KiB as configured via [`MemoryTypeBuilder::page_size_log2`].
+    ///
+    /// [`custom-page-sizes`]: https://github.com/WebAssembly/custom-page-sizes
+    /// [`Memory`]: crate::Memory
+    /// [`MemoryTypeBuilder::page_size_log2`]: crate::MemoryTypeBuilder::page_size_log2
+    pub fn wasm_custom_page_sizes(&mut self, enable: bool) -> &mut Self {
+        self.custom_page_sizes = enable;
+        self
+    }
+
+    /// Returns `true` if the [`custom-page-sizes`] Wasm proposal is enabled.
+    ///
+    /// [`custom-page-sizes`]: https://github.com/WebAssembly/custom-page-sizes
+    pub(crate) fn get_custom_page_sizes(&self) -> bool {
+        self.custom_page_sizes
+    }
+
     /// Enable or disable Wasm floating point (`f32` and `f64`) instructions and types.
     ///
     /// Enabled by default.
@@ -348,13 +550,461 @@ impl Config {
         self.consume_fuel
     }
 
+    /// Enables or disables yield checks in translated functions.
+    ///
+    /// # Note
+    ///
+    /// - The yield callback of a [`Store`] is invoked at the same sites that
+    ///   consume fuel. With fuel metering enabled via [`Config::consume_fuel`]
+    ///   those sites already exist and this setting has no effect.
+    /// - If enabled without fuel metering, Wasmi translates functions with the
+    ///   same check sites as for fuel metering but without charging any fuel.
+    ///   Executions then count the fuel that would have been consumed instead.
+    /// - Use [`Store::set_yield_callback`] to install a yield callback.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::set_yield_callback`]: crate::Store::set_yield_callback
+    pub fn cooperative_yield(&mut self, enable: bool) -> &mut Self {
+        self.cooperative_yield = enable;
+        self
+    }
+
+    /// Returns `true` if translated functions contain check sites for fuel metering or yielding.
+    pub(crate) fn get_fuel_checks(&self) -> bool {
+        self.consume_fuel || self.cooperative_yield
+    }
+
+    /// Configures whether `memory.grow` traps if there is not enough fuel to grow the linear memory.
+    ///
+    /// # Note
+    ///
+    /// The operations of `memory.grow` are performed in the following order:
+    ///
+    /// 1. The [`ResourceLimiter`] and the maximum limits of the linear memory are checked.
+    ///    If either denies the growth `memory.grow` returns `-1` without consuming fuel.
+    /// 2. The fuel for the new pages is consumed. If there is not enough fuel `memory.grow`
+    ///    traps with [`TrapCode::OutOfFuel`] if `enable` is `true` or returns `-1`
+    ///    otherwise. In both cases no fuel is consumed for the new pages.
+    /// 3. The linear memory is grown. If its allocation fails the fuel consumed
+    ///    for the new pages is refunded and `memory.grow` returns `-1`.
+    ///
+    /// Enabled by default.
+    ///
+    /// [`ResourceLimiter`]: crate::ResourceLimiter
+    /// [`TrapCode::OutOfFuel`]: crate::core::TrapCode::OutOfFuel
+    pub fn memory_grow_traps_on_out_of_fuel(&mut self, enable: bool) -> &mut Self {
+        self.memory_grow_traps_on_out_of_fuel = enable;
+        self
+    }
+
+    /// Returns `true` if `memory.grow` traps if there is not enough fuel to grow the linear memory.
+    pub(crate) fn get_memory_grow_traps_on_out_of_fuel(&self) -> bool {
+        self.memory_grow_traps_on_out_of_fuel
+    }
+
+    /// Configures whether lazily compiled Wasm functions charge fuel for their compilation.
+    ///
+    /// # Note
+    ///
+    /// - With [`CompilationMode::Lazy`] and [`CompilationMode::LazyTranslation`] a Wasm
+    ///   function is compiled upon its first call. If enabled, this charges fuel proportional
+    ///   to the size of its Wasm bytecode as configured via [`FuelCosts::set_bytes_per_fuel`].
+    /// - Compilation fuel is drawn from the remaining fuel of the [`Store`] but it is
+    ///   reported separately via [`Store::compilation_fuel_consumed`] and is not
+    ///   included in [`Store::fuel_consumed`]. This way [`Store::fuel_consumed`] is
+    ///   the same for all [`CompilationMode`]s.
+    /// - This has no effect unless fuel metering is enabled via [`Config::consume_fuel`].
+    ///
+    /// Enabled by default.
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Store::compilation_fuel_consumed`]: crate::Store::compilation_fuel_consumed
+    /// [`Store::fuel_consumed`]: crate::Store::fuel_consumed
+    pub fn charge_compilation_fuel(&mut self, enable: bool) -> &mut Self {
+        self.charge_compilation_fuel = enable;
+        self
+    }
+
+    /// Returns `true` if lazily compiled Wasm functions charge fuel for their compilation.
+    pub(crate) fn get_charge_compilation_fuel(&self) -> bool {
+        self.charge_compilation_fuel
+    }
+
+    /// Enables or disables the `wasmi.yield` builtin function for guest controlled yields.
+    ///
+    /// # Note
+    ///
+    /// - If enabled, [`Linker::define_builtins`] defines the `yield` function of type
+    ///   `[i32] -> []` under the reserved `"wasmi"` module name.
+    /// - Calls to `wasmi.yield` suspend resumable calls, e.g. via [`Func::call_resumable`],
+    ///   with a [`GuestYield`] carrying the `i32` parameter as user tag.
+    ///   Resuming the invocation with no inputs continues after the yield point.
+    /// - Non-resumable calls, e.g. via [`Func::call`], fail with the [`GuestYield`] instead.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Linker::define_builtins`]: crate::Linker::define_builtins
+    /// [`Func::call_resumable`]: crate::Func::call_resumable
+    /// [`Func::call`]: crate::Func::call
+    /// [`GuestYield`]: crate::GuestYield
+    #[cfg(feature = "resumable")]
+    pub fn builtin_yield(&mut self, enable: bool) -> &mut Self {
+        self.builtin_yield = enable;
+        self
+    }
+
+    /// Returns `true` if the `wasmi.yield` builtin function is enabled.
+    #[cfg(feature = "resumable")]
+    pub(crate) fn get_builtin_yield(&self) -> bool {
+        self.builtin_yield
+    }
+
+    /// Enables or disables the `wasmi.batch_call` builtin function for batched host calls.
+    ///
+    /// # Note
+    ///
+    /// - If enabled, [`Linker::define_builtins`] defines the `batch_call` function of type
+    ///   `[i32 i32 i32] -> []` under the reserved `"wasmi"` module name.
+    /// - Its parameters are the address of the records, their number and the address
+    ///   of the results within the default linear memory of the calling instance.
+    /// - Each record consists of the little-endian `u32` index of an imported host function
+    ///   followed by its parameters, each encoded as little-endian 8 byte value.
+    ///   Host functions with reference typed parameters or results are not supported.
+    /// - The results of all records are written consecutively to the results address,
+    ///   each encoded as little-endian 8 byte value.
+    /// - All records are validated before any host function is called. Then each record
+    ///   is dispatched in order, consuming the fuel of a call per dispatched host function.
+    ///   The batch is aborted upon the first error of a called host function.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Linker::define_builtins`]: crate::Linker::define_builtins
+    pub fn builtin_batch_call(&mut self, enable: bool) -> &mut Self {
+        self.builtin_batch_call = enable;
+        self
+    }
+
+    /// Returns `true` if the `wasmi.batch_call` builtin function is enabled.
+    pub(crate) fn get_builtin_batch_call(&self) -> bool {
+        self.builtin_batch_call
+    }
+
+    /// Enables or disables lazy initialization of `funcref` tables by active element segments.
+    ///
+    /// # Note
+    ///
+    /// - If enabled, instantiation only records the active element segments of
+    ///   `funcref` tables instead of writing all of their elements to the tables.
+    ///   Each table element is then initialized upon its first access.
+    /// - This speeds up instantiation of Wasm modules with large tables, e.g. used
+    ///   for vtable dispatch, at the cost of a slightly slower first access.
+    /// - The observable behavior is the same as with eager table initialization.
+    ///
+    /// Disabled by default.
+    pub fn lazy_table_init(&mut self, enable: bool) -> &mut Self {
+        self.lazy_table_init = enable;
+        self
+    }
+
+    /// Returns `true` if `funcref` tables are initialized lazily by active element segments.
+    pub(crate) fn get_lazy_table_init(&self) -> bool {
+        self.lazy_table_init
+    }
+
+    /// Enables or disables hardening of linear memory and table accesses against Spectre attacks.
+    ///
+    /// # Note
+    ///
+    /// - If enabled, the index of every Wasm load, store, `table.get`, `table.set` and
+    ///   `call_indirect` is masked by the result of its bounds check after the bounds check.
+    ///   The mask is derived via data dependency instead of control flow. Therefore accesses
+    ///   that are speculatively executed past a mispredicted bounds check access index `0`
+    ///   instead of out of bounds host memory.
+    /// - This is the interpreter equivalent of the Spectre mitigations of JIT compilers
+    ///   such as Cranelift. It does not alter the observable behavior of Wasm executions.
+    /// - Bulk memory and table operations, such as `memory.copy`, are not affected.
+    /// - The `dispatch` benchmark suite quantifies the costs of this hardening.
+    ///
+    /// Disabled by default.
+    pub fn spectre_mitigations(&mut self, enable: bool) -> &mut Self {
+        self.spectre_mitigations = enable;
+        self
+    }
+
+    /// Returns `true` if linear memory and table accesses are hardened against Spectre attacks.
+    pub(crate) fn get_spectre_mitigations(&self) -> bool {
+        self.spectre_mitigations
+    }
+
+    /// Sets the level of optimizations applied to the translated Wasmi bytecode.
+    ///
+    /// # Note
+    ///
+    /// - Level `0` applies no optimizations beyond those of the translation itself.
+    /// - Level `1` additionally runs a light optimization pass over the Wasmi
+    ///   bytecode of every translated function that folds copied constants into
+    ///   the immediate variants of binary instructions, removes copies that are
+    ///   overwritten before their next use and removes unreachable instructions.
+    ///   It also forwards the results of calls that are returned right after the call
+    ///   and fuses doubly negated `i32.eqz` conditions into branches and `select`.
+    /// - Level `2` additionally inlines calls to tiny internal functions that neither call
+    ///   other functions nor grow a linear memory if the function is defined before its
+    ///   caller and the [`CompilationMode`] is [`CompilationMode::Eager`].
+    ///   Inlined calls consume the same amount of fuel as the calls they replace.
+    /// - Higher levels currently behave the same as level `2`.
+    /// - The optimizations do not alter the observable behavior of Wasm executions
+    ///   except that fuel costs are not adjusted for removed instructions and that
+    ///   inlined calls do not count towards the limits of the call stack.
+    ///
+    /// Defaults to level `0`.
+    pub fn optimization_level(&mut self, level: u8) -> &mut Self {
+        self.optimization_level = level;
+        self
+    }
+
+    /// Returns the level of optimizations applied to the translated Wasmi bytecode.
+    pub(crate) fn get_optimization_level(&self) -> u8 {
+        self.optimization_level
+    }
+
+    /// Sets the maximum size in bytes of the body of a translated Wasm function.
+    ///
+    /// # Note
+    ///
+    /// - This sets [`ModuleLimits::max_function_body_bytes`] of the [`ModuleLimits`].
+    /// - The size includes the declaration of the local variables of the function.
+    /// - Translating a function with a larger body fails with an error instead of
+    ///   allocating the potentially large translation data structures for it.
+    /// - With lazy translation this is checked when the function is translated.
+    pub fn max_function_body_size(&mut self, limit: u32) -> &mut Self {
+        self.module_limits.max_function_body_bytes = limit;
+        self
+    }
+
+    /// Sets the maximum number of local variables of a translated Wasm function.
+    ///
+    /// # Note
+    ///
+    /// - This sets [`ModuleLimits::max_locals`] of the [`ModuleLimits`].
+    /// - Function parameters do not count as local variables.
+    /// - Translating a function with more local variables fails with an error.
+    /// - With lazy translation this is checked when the function is translated.
+    pub fn max_locals(&mut self, limit: u32) -> &mut Self {
+        self.module_limits.max_locals = limit;
+        self
+    }
+
+    /// Sets the maximum length in bytes of import and export names of a Wasm module.
+    ///
+    /// # Note
+    ///
+    /// - This sets [`ModuleLimits::max_name_length`] of the [`ModuleLimits`].
+    /// - The limit applies to each import module name, import field name and export name.
+    /// - Parsing a Wasm module with a longer name fails with an error
+    ///   stating the length of the offending name.
+    pub fn max_name_length(&mut self, limit: u32) -> &mut Self {
+        self.module_limits.max_name_length = limit;
+        self
+    }
+
+    /// Sets the maximum number of registers of a translated Wasm function.
+    ///
+    /// # Note
+    ///
+    /// - The registers of a function make up its call frame on the value stack.
+    /// - Translating a function that requires more registers fails with an error
+    ///   naming the function index and its required number of registers.
+    /// - With lazy translation this is checked when the function is translated.
+    /// - The number of registers of a function is queried via [`Engine::len_registers`].
+    ///
+    /// Defaults to no limit.
+    ///
+    /// [`Engine::len_registers`]: crate::Engine::len_registers
+    pub fn max_registers_per_function(&mut self, limit: u32) -> &mut Self {
+        self.max_registers_per_function = limit;
+        self
+    }
+
+    /// Returns the maximum number of registers of a translated Wasm function.
+    pub(crate) fn get_max_registers_per_function(&self) -> u32 {
+        self.max_registers_per_function
+    }
+
+    /// Enables or disables the generation of address maps for translated functions.
+    ///
+    /// # Note
+    ///
+    /// - If enabled, the translation of every function records a sorted table that maps
+    ///   its Wasmi bytecode instructions back to the offsets of the Wasm operators in the
+    ///   Wasm binary they have been translated from. This allows to map traps back to
+    ///   source locations, e.g. via DWARF debug information.
+    /// - The address map of a function is queried via [`Engine::address_map`] and the
+    ///   Wasm binary offset of a trap via [`Error::trap_wasm_offset`].
+    /// - The memory overhead is proportional to the size of the translated code.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine::address_map`]: crate::Engine::address_map
+    /// [`Error::trap_wasm_offset`]: crate::Error::trap_wasm_offset
+    pub fn generate_address_map(&mut self, enable: bool) -> &mut Self {
+        self.generate_address_map = enable;
+        self
+    }
+
+    /// Returns `true` if address maps are generated for translated functions.
+    pub(crate) fn get_generate_address_map(&self) -> bool {
+        self.generate_address_map
+    }
+
+    /// Enables or disables the generation of register type tables for translated functions.
+    ///
+    /// # Note
+    ///
+    /// - If enabled, the translation of every function records the types of its
+    ///   parameter and local registers as well as the types of the result registers
+    ///   of its call instructions. This allows to inspect registers as typed values.
+    /// - The register types of a function are queried via [`Engine::register_types`].
+    /// - Only the translated function metadata grows; execution is unaffected.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine::register_types`]: crate::Engine::register_types
+    pub fn generate_register_types(&mut self, enable: bool) -> &mut Self {
+        self.generate_register_types = enable;
+        self
+    }
+
+    /// Returns `true` if register type tables are generated for translated functions.
+    pub(crate) fn get_generate_register_types(&self) -> bool {
+        self.generate_register_types
+    }
+
+    /// Enables or disables the computation of the runtime signature of Wasmi executions.
+    ///
+    /// This is a shorthand for [`ExecutionDigest::InstructionPrimes`] if `enable`
+    /// is `true` and [`ExecutionDigest::None`] otherwise.
+    ///
+    /// Disabled by default.
     pub fn update_runtime_signature(&mut self, enable: bool) -> &mut Self {
-        self.update_runtime_signature = enable;
+        self.execution_digest = match enable {
+            true => ExecutionDigest::InstructionPrimes,
+            false => ExecutionDigest::None,
+        };
+        self
+    }
+
+    /// Sets the [`ExecutionDigest`] strategy used to compute the runtime signature.
+    ///
+    /// Defaults to [`ExecutionDigest::None`].
+    pub fn execution_digest(&mut self, digest: ExecutionDigest) -> &mut Self {
+        self.execution_digest = digest;
+        self
+    }
+
+    /// Returns the [`ExecutionDigest`] strategy of the [`Config`].
+    pub(crate) fn get_execution_digest(&self) -> ExecutionDigest {
+        self.execution_digest
+    }
+
+    /// Restricts the [`Engine`] to behavior that does not depend on ambient state.
+    ///
+    /// # Note
+    ///
+    /// - Internal maps of Wasmi, such as the deduplicated function types of the [`Engine`]
+    ///   and the definitions of a [`Linker`], are ordered maps regardless of this setting.
+    ///   Therefore their iteration order, which shows up in error messages and compiled
+    ///   Wasmi bytecode, never depends on randomly seeded hashers.
+    /// - If enabled, the call metrics of a [`Store`] and the durations recorded by
+    ///   tracing spans are zero instead of reading the wall clock.
+    /// - If enabled, [`Engine::new`] rejects configurations whose behavior depends on
+    ///   ambient state, such as sampling the executed Wasm functions of each thread.
+    /// - Two [`Engine`]s compile the same Wasm module to identical Wasmi bytecode
+    ///   if both use the same [`Config`] as checked by [`Engine::same_config_fingerprint`].
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Engine::new`]: crate::Engine::new
+    /// [`Engine::same_config_fingerprint`]: crate::Engine::same_config_fingerprint
+    /// [`Linker`]: crate::Linker
+    /// [`Store`]: crate::Store
+    pub fn deterministic_only(&mut self, enable: bool) -> &mut Self {
+        self.deterministic_only = enable;
+        self
+    }
+
+    /// Returns `true` if the [`Engine`] must not depend on ambient state.
+    ///
+    /// [`Engine`]: crate::Engine
+    #[cfg(any(feature = "metrics", feature = "tracing"))]
+    pub(crate) fn get_deterministic_only(&self) -> bool {
+        self.deterministic_only
+    }
+
+    /// Enables or disables counting executed instructions per [`InstructionCategory`].
+    ///
+    /// # Note
+    ///
+    /// - The counts are queried via [`Store::instruction_category_counts`].
+    /// - Conditional branches are counted as taken or not taken upon their execution.
+    /// - Every executed instruction is classified which slows down Wasm executions.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`InstructionCategory`]: crate::InstructionCategory
+    /// [`Store::instruction_category_counts`]: crate::Store::instruction_category_counts
+    #[cfg(feature = "metrics")]
+    pub fn profiling(&mut self, enable: bool) -> &mut Self {
+        self.profiling = enable;
+        self
+    }
+
+    /// Returns `true` if executed instructions are counted per [`InstructionCategory`].
+    ///
+    /// [`InstructionCategory`]: crate::InstructionCategory
+    #[cfg(feature = "metrics")]
+    pub(crate) fn get_profiling(&self) -> bool {
+        self.profiling
+    }
+
+    /// Enables or disables tracking the Wasm function executed by each thread.
+    ///
+    /// # Note
+    ///
+    /// - The tracked Wasm functions are queried via [`Engine::current_func_of`]
+    ///   and periodically sampled via [`Engine::set_sampler`].
+    /// - Only calls and returns update the tracked Wasm function which is cheap.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`Engine::current_func_of`]: crate::Engine::current_func_of
+    /// [`Engine::set_sampler`]: crate::Engine::set_sampler
+    #[cfg(feature = "metrics")]
+    pub fn sampling(&mut self, enable: bool) -> &mut Self {
+        self.sampling = enable;
         self
     }
 
-    pub(crate) fn get_update_runtime_signature(&self) -> bool {
-        self.update_runtime_signature
+    /// Returns `true` if the Wasm function executed by each thread is tracked.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn get_sampling(&self) -> bool {
+        self.sampling
+    }
+
+    /// Sets the [`FuelCosts`] used for the translation of Wasm functions and for fuel metering.
+    ///
+    /// # Note
+    ///
+    /// The [`FuelCosts`] of an [`Engine`] can be changed after its creation
+    /// via [`Engine::reprice_fuel`].
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Engine::reprice_fuel`]: crate::Engine::reprice_fuel
+    pub fn set_fuel_costs(&mut self, costs: FuelCosts) -> &mut Self {
+        self.fuel_costs = costs;
+        self
     }
 
     /// Returns the configured [`FuelCosts`].
@@ -0,0 +1,59 @@
+use super::bytecode::{Instruction, Register};
+use core::ptr;
+use wasmi_core::UntypedValue;
+
+/// Provides read access to the [`Register`] values of the executed function frame.
+///
+/// This is handed to [`ExecutionDigest::Custom`] digest functions.
+pub trait RegisterReader {
+    /// Returns the value of `register` of the executed function frame.
+    ///
+    /// # Note
+    ///
+    /// The `register` must be valid for the executed [`Instruction`].
+    /// Reading registers that are not in use by the executed function
+    /// yields unspecified values and reading registers outside of the
+    /// value stack yields zero.
+    fn read_register(&self, register: Register) -> UntypedValue;
+}
+
+/// A user provided digest function used by [`ExecutionDigest::Custom`].
+///
+/// It is called for every executed [`Instruction`] and returns the value
+/// that is mixed into the runtime signature of the [`Store`].
+///
+/// [`Store`]: crate::Store
+pub type DigestFn = fn(&Instruction, &dyn RegisterReader) -> u64;
+
+/// The strategy to compute the runtime signature of Wasmi executions.
+///
+/// The runtime signature can be queried via [`Store::runtime_signature`].
+///
+/// [`Store::runtime_signature`]: crate::Store::runtime_signature
+#[derive(Debug, Default, Copy, Clone)]
+pub enum ExecutionDigest {
+    /// No runtime signature is computed.
+    ///
+    /// This does not impose any overhead on the executed instructions.
+    #[default]
+    None,
+    /// Every executed [`Instruction`] is mixed into the runtime signature
+    /// as a unique 64-bit prime alongside the input operands of some
+    /// unary and binary instructions.
+    InstructionPrimes,
+    /// Every executed [`Instruction`] is mixed into the runtime signature
+    /// via the value returned by the [`DigestFn`].
+    Custom(DigestFn),
+}
+
+impl PartialEq for ExecutionDigest {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::None, Self::None) | (Self::InstructionPrimes, Self::InstructionPrimes) => true,
+            (Self::Custom(lhs), Self::Custom(rhs)) => ptr::fn_addr_eq(*lhs, *rhs),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ExecutionDigest {}
@@ -0,0 +1,204 @@
+use super::{TypedResumableCall, TypedResumableInvocation};
+use crate::{AsContextMut, Error, Func, Value, WasmResults};
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use core::mem::replace;
+
+/// A host function call that interrupted a resumable function invocation.
+///
+/// Recorded by the [`ResumableDriver`] whenever the driven invocation pauses.
+#[derive(Debug, Clone)]
+pub struct HostInterruption {
+    /// The host function that returned the host error.
+    func: Func,
+    /// The parameters that the host function has been called with.
+    params: Box<[Value]>,
+}
+
+impl HostInterruption {
+    /// Returns the host [`Func`] that interrupted the invocation.
+    pub fn func(&self) -> Func {
+        self.func
+    }
+
+    /// Returns the parameters that the host [`Func`] has been called with.
+    pub fn params(&self) -> &[Value] {
+        &self.params
+    }
+}
+
+/// The state of a [`ResumableDriver`] returned by [`ResumableDriver::run_until_stalled`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DriverState {
+    /// The invocation has finished and its results are available.
+    Finished,
+    /// The invocation is paused and there are no more queued inputs to resume it with.
+    Stalled,
+    /// The invocation is paused and the maximum number of steps has been reached.
+    StepLimitReached,
+    /// The invocation trapped during a previous step and can no longer be driven.
+    Trapped,
+}
+
+/// The invocation driven by a [`ResumableDriver`].
+#[derive(Debug)]
+enum DriverCall<Results> {
+    /// The invocation is paused by a host function.
+    Paused(TypedResumableInvocation<Results>),
+    /// The invocation has finished with its results.
+    Finished(Results),
+    /// The invocation trapped and can no longer be resumed.
+    Trapped,
+}
+
+/// Deterministically drives a resumable function invocation from a queue of host function results.
+///
+/// # Note
+///
+/// - Every step resumes the paused invocation with the next queued inputs
+///   as the results of the interrupting host function.
+/// - Every host function interruption is recorded into a log.
+/// - This is primarily meant for testing embedders of resumable function invocations.
+///
+/// # Example
+///
+/// ```
+/// # use wasmi::{DriverState, Engine, Error, Func, Linker, Module, ResumableDriver, Store, Value};
+/// # let wasm = wat::parse_str(r#"
+/// #     (module
+/// #         (import "env" "ask" (func $ask (param i32) (result i32)))
+/// #         (func (export "run") (result i32)
+/// #             (i32.add (call $ask (i32.const 1)) (call $ask (i32.const 2)))
+/// #         )
+/// #     )
+/// # "#).unwrap();
+/// let engine = Engine::default();
+/// let mut store = Store::new(&engine, ());
+/// let module = Module::new(&engine, &wasm[..])?;
+/// let mut linker = <Linker<()>>::new(&engine);
+/// // The host function always pauses the invocation.
+/// let ask = Func::wrap(&mut store, |_: i32| -> Result<i32, Error> { Err(Error::new("ask")) });
+/// linker.define("env", "ask", ask)?;
+/// let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+/// let run = instance.get_typed_func::<(), i32>(&store, "run")?;
+/// let mut driver = ResumableDriver::new(run.call_resumable(&mut store, ())?);
+/// driver.push_inputs([Value::I32(10)]);
+/// assert_eq!(driver.run_until_stalled(&mut store, 10)?, DriverState::Stalled);
+/// driver.push_inputs([Value::I32(20)]);
+/// assert_eq!(driver.run_until_stalled(&mut store, 10)?, DriverState::Finished);
+/// assert_eq!(driver.results(), Some(&30));
+/// let params: Vec<_> = driver.log().iter().map(|i| i.params()[0].i32()).collect();
+/// assert_eq!(params, [Some(1), Some(2)]);
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug)]
+pub struct ResumableDriver<Results> {
+    /// The driven invocation.
+    call: DriverCall<Results>,
+    /// The queued inputs used to resume the paused invocation.
+    queue: VecDeque<Box<[Value]>>,
+    /// The log of all host function interruptions in order of their occurrence.
+    log: Vec<HostInterruption>,
+}
+
+impl<Results> ResumableDriver<Results> {
+    /// Creates a new [`ResumableDriver`] for the resumable function invocation `call`.
+    ///
+    /// If `call` is paused its host function interruption is recorded.
+    pub fn new(call: TypedResumableCall<Results>) -> Self {
+        let mut driver = Self {
+            call: DriverCall::Trapped,
+            queue: VecDeque::new(),
+            log: Vec::new(),
+        };
+        driver.update(call);
+        driver
+    }
+
+    /// Updates the driven invocation to `call` and records its host function interruption if any.
+    fn update(&mut self, call: TypedResumableCall<Results>) {
+        self.call = match call {
+            TypedResumableCall::Finished(results) => DriverCall::Finished(results),
+            TypedResumableCall::Resumable(invocation) => {
+                self.log.push(HostInterruption {
+                    func: invocation.host_func(),
+                    params: invocation.host_params().into(),
+                });
+                DriverCall::Paused(invocation)
+            }
+        };
+    }
+
+    /// Queues `inputs` to resume the paused invocation with in a future step.
+    pub fn push_inputs(&mut self, inputs: impl Into<Box<[Value]>>) -> &mut Self {
+        self.queue.push_back(inputs.into());
+        self
+    }
+
+    /// Returns the log of all host function interruptions in order of their occurrence.
+    pub fn log(&self) -> &[HostInterruption] {
+        &self.log
+    }
+
+    /// Returns the paused invocation if any.
+    pub fn invocation(&self) -> Option<&TypedResumableInvocation<Results>> {
+        match &self.call {
+            DriverCall::Paused(invocation) => Some(invocation),
+            _ => None,
+        }
+    }
+
+    /// Returns the results of the invocation if it has finished.
+    pub fn results(&self) -> Option<&Results> {
+        match &self.call {
+            DriverCall::Finished(results) => Some(results),
+            _ => None,
+        }
+    }
+
+    /// Consumes `self` and returns the results of the invocation if it has finished.
+    pub fn into_results(self) -> Option<Results> {
+        match self.call {
+            DriverCall::Finished(results) => Some(results),
+            _ => None,
+        }
+    }
+
+    /// Resumes the paused invocation with queued inputs until it finishes or stalls.
+    ///
+    /// Resumes the invocation at most `max_steps` times.
+    ///
+    /// # Errors
+    ///
+    /// If resuming the invocation returned an [`Error`].
+    /// Afterwards the invocation can no longer be driven and [`DriverState::Trapped`] is returned.
+    pub fn run_until_stalled<T>(
+        &mut self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        max_steps: usize,
+    ) -> Result<DriverState, Error>
+    where
+        Results: WasmResults,
+    {
+        for _ in 0..max_steps {
+            if !matches!(self.call, DriverCall::Paused(_)) {
+                break;
+            }
+            let Some(inputs) = self.queue.pop_front() else {
+                return Ok(DriverState::Stalled);
+            };
+            let DriverCall::Paused(invocation) = replace(&mut self.call, DriverCall::Trapped)
+            else {
+                unreachable!("the invocation must be paused")
+            };
+            let call = invocation.resume(ctx.as_context_mut(), &inputs)?;
+            self.update(call);
+        }
+        let state = match self.call {
+            DriverCall::Finished(_) => DriverState::Finished,
+            DriverCall::Trapped => DriverState::Trapped,
+            DriverCall::Paused(_) if self.queue.is_empty() => DriverState::Stalled,
+            DriverCall::Paused(_) => DriverState::StepLimitReached,
+        };
+        Ok(state)
+    }
+}
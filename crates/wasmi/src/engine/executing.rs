@@ -0,0 +1,51 @@
+//! Tracks the [`Engine`]s that execute Wasm on the current thread.
+//!
+//! # Note
+//!
+//! Wasm executions hold a shared lock on the resources of their [`Engine`].
+//! Therefore [`Engine`] APIs that require exclusive access to those resources would
+//! deadlock if called by a host function during such an execution on the same thread.
+//! This is used to detect such calls and report them as errors instead.
+//!
+//! [`Engine`]: crate::Engine
+
+use super::EngineIdx;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+std::thread_local! {
+    /// The [`EngineIdx`] of all Wasm executions of the current thread from outermost to innermost.
+    static EXECUTING: RefCell<Vec<EngineIdx>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Records a Wasm execution of an [`Engine`] on the current thread until dropped.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug)]
+pub struct ExecutingGuard {
+    /// The [`EngineIdx`] of the executing [`Engine`].
+    ///
+    /// [`Engine`]: crate::Engine
+    engine: EngineIdx,
+}
+
+impl ExecutingGuard {
+    /// Records that the current thread starts a Wasm execution of the `engine`.
+    pub fn enter(engine: EngineIdx) -> Self {
+        EXECUTING.with(|executing| executing.borrow_mut().push(engine));
+        Self { engine }
+    }
+}
+
+impl Drop for ExecutingGuard {
+    fn drop(&mut self) {
+        // Note: nested Wasm executions always finish before their outer executions.
+        let engine = EXECUTING.with(|executing| executing.borrow_mut().pop());
+        debug_assert_eq!(engine, Some(self.engine));
+    }
+}
+
+/// Returns `true` if the current thread executes Wasm of the `engine`.
+pub fn is_executing(engine: EngineIdx) -> bool {
+    EXECUTING.with(|executing| executing.borrow().contains(&engine))
+}
@@ -1,7 +1,10 @@
 use super::{Executor, UntypedValueExt};
 use crate::{
     core::{TrapCode, UntypedValue},
-    engine::bytecode::{BinInstr, BinInstrImm, BinInstrImm16, Sign},
+    engine::{
+        bytecode::{BinInstr, BinInstrImm, BinInstrImm16, Register, RegisterSpan, Sign},
+        intrinsics,
+    },
     Error,
 };
 use core::num::{NonZeroI32, NonZeroI64, NonZeroU32, NonZeroU64};
@@ -20,7 +23,7 @@ macro_rules! impl_binary {
         )*
     };
 }
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_binary! {
         (Instruction::I32Add, execute_i32_add, UntypedValue::i32_add),
         (Instruction::I32Sub, execute_i32_sub, UntypedValue::i32_sub),
@@ -80,7 +83,7 @@ macro_rules! impl_binary_imm16 {
         )*
     };
 }
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_binary_imm16! {
         (i32, Instruction::I32AddImm16, execute_i32_add_imm16, UntypedValue::i32_add),
         (i32, Instruction::I32SubImm16, execute_i32_sub_imm16, UntypedValue::i32_sub),
@@ -125,7 +128,7 @@ macro_rules! impl_binary_imm16_rev {
         )*
     };
 }
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_binary_imm16_rev! {
         (i32, Instruction::I32SubImm16Rev, execute_i32_sub_imm16_rev, UntypedValue::i32_sub),
         (i64, Instruction::I64SubImm16Rev, execute_i64_sub_imm16_rev, UntypedValue::i64_sub),
@@ -155,7 +158,7 @@ macro_rules! impl_fallible_binary {
         )*
     };
 }
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_fallible_binary! {
         (Instruction::I32DivS, execute_i32_div_s, UntypedValue::i32_div_s),
         (Instruction::I32DivU, execute_i32_div_u, UntypedValue::i32_div_u),
@@ -166,6 +169,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         (Instruction::I64DivU, execute_i64_div_u, UntypedValue::i64_div_u),
         (Instruction::I64RemS, execute_i64_rem_s, UntypedValue::i64_rem_s),
         (Instruction::I64RemU, execute_i64_rem_u, UntypedValue::i64_rem_u),
+
+        (Instruction::I32CheckedAdd, execute_i32_checked_add, intrinsics::i32_checked_add),
+        (Instruction::I32CheckedSub, execute_i32_checked_sub, intrinsics::i32_checked_sub),
+        (Instruction::I32CheckedMul, execute_i32_checked_mul, intrinsics::i32_checked_mul),
+
+        (Instruction::I64CheckedAdd, execute_i64_checked_add, intrinsics::i64_checked_add),
+        (Instruction::I64CheckedSub, execute_i64_checked_sub, intrinsics::i64_checked_sub),
+        (Instruction::I64CheckedMul, execute_i64_checked_mul, intrinsics::i64_checked_mul),
     }
 }
 
@@ -247,7 +258,7 @@ macro_rules! impl_divrem_s_imm16 {
         )*
     };
 }
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_divrem_s_imm16! {
         (NonZeroI32, Instruction::I32DivSImm16, execute_i32_div_s_imm16, <UntypedValue as DivRemExt>::i32_div_s),
         (NonZeroI32, Instruction::I32RemSImm16, execute_i32_rem_s_imm16, <UntypedValue as DivRemExt>::i32_rem_s),
@@ -268,7 +279,7 @@ macro_rules! impl_divrem_u_imm16 {
         )*
     };
 }
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_divrem_u_imm16! {
         (NonZeroU32, Instruction::I32DivUImm16, execute_i32_div_u_imm16, <UntypedValue as DivRemExt>::i32_div_u),
         (NonZeroU32, Instruction::I32RemUImm16, execute_i32_rem_u_imm16, <UntypedValue as DivRemExt>::i32_rem_u),
@@ -289,7 +300,7 @@ macro_rules! impl_fallible_binary_imm16_rev {
         )*
     };
 }
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_fallible_binary_imm16_rev! {
         (i32, Instruction::I32DivSImm16Rev, execute_i32_div_s_imm16_rev, UntypedValue::i32_div_s),
         (u32, Instruction::I32DivUImm16Rev, execute_i32_div_u_imm16_rev, UntypedValue::i32_div_u),
@@ -303,7 +314,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 }
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Executes an [`Instruction::F32CopysignImm`].
     #[inline(always)]
     pub fn execute_f32_copysign_imm(&mut self, instr: BinInstrImm<Sign>) {
@@ -322,3 +333,31 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.next_instr()
     }
 }
+
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
+    /// Executes an [`Instruction::I64MulWideS`].
+    #[inline(always)]
+    pub fn execute_i64_mul_wide_s(&mut self, results: RegisterSpan, lhs: Register, rhs: Register) {
+        let lhs = i128::from(i64::from(self.get_register(lhs)));
+        let rhs = i128::from(i64::from(self.get_register(rhs)));
+        self.execute_i64_mul_wide(results, (lhs * rhs) as u128)
+    }
+
+    /// Executes an [`Instruction::I64MulWideU`].
+    #[inline(always)]
+    pub fn execute_i64_mul_wide_u(&mut self, results: RegisterSpan, lhs: Register, rhs: Register) {
+        let lhs = u128::from(u64::from(self.get_register(lhs)));
+        let rhs = u128::from(u64::from(self.get_register(rhs)));
+        self.execute_i64_mul_wide(results, lhs * rhs)
+    }
+
+    /// Stores the low and high 64 bits of `product` to `results[0]` and `results[1]`.
+    #[inline(always)]
+    fn execute_i64_mul_wide(&mut self, results: RegisterSpan, product: u128) {
+        let result_lo = results.head();
+        let result_hi = result_lo.next();
+        self.set_register(result_lo, UntypedValue::from(product as u64));
+        self.set_register(result_hi, UntypedValue::from((product >> 64) as u64));
+        self.next_instr()
+    }
+}
@@ -1,6 +1,7 @@
 use super::Executor;
 use crate::engine::bytecode::{
     BranchBinOpInstr,
+    BranchBinOpInstrFallback,
     BranchBinOpInstrImm16,
     BranchComparator,
     BranchOffset,
@@ -14,7 +15,7 @@ use crate::engine::bytecode::{
 use core::cmp;
 use wasmi_core::UntypedValue;
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Branches and adjusts the value stack.
     ///
     /// # Note
@@ -54,6 +55,47 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.ip.add(normalized_index as usize);
     }
 
+    #[inline(always)]
+    pub fn execute_branch_table_sparse(&mut self, index: Register, len_cases: Const32<u32>) {
+        let index: u32 = self.get_register_as(index);
+        let len_cases = u32::from(len_cases) as usize;
+        // The case indices are the parameter words following the instruction.
+        self.ip.add(1);
+        // A normalized index of `len_cases` refers to the default target.
+        let normalized_index = self.find_sparse_case(index, len_cases);
+        // Check if the next instruction is a copy instruction and execute it if so.
+        self.ip.add(len_cases);
+        self.execute_optional_copy_instr();
+        // Update `pc`:
+        self.ip.add(normalized_index);
+    }
+
+    /// Returns the position of `index` within the `len_cases` case indices at `ip`.
+    ///
+    /// Returns `len_cases` if `index` is not one of the case indices.
+    ///
+    /// # Note
+    ///
+    /// The case indices are sorted which allows for a binary search.
+    fn find_sparse_case(&self, index: u32, len_cases: usize) -> usize {
+        let mut lo = 0;
+        let mut hi = len_cases;
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let mut ip = self.ip;
+            ip.add(mid);
+            let Instruction::Const32(case) = *ip.get() else {
+                unreachable!("expected a `Const32` case index but found: {:?}", ip.get())
+            };
+            match u32::from(case).cmp(&index) {
+                cmp::Ordering::Less => lo = mid + 1,
+                cmp::Ordering::Greater => hi = mid,
+                cmp::Ordering::Equal => return mid,
+            }
+        }
+        len_cases
+    }
+
     /// Executes an optional copy instruction at `ip`.
     ///
     /// Does nothing if there is no `copy` instruction at `ip`.
@@ -109,12 +151,26 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     {
         let lhs: T = self.get_register_as(lhs);
         let rhs: T = self.get_register_as(rhs);
-        if f(lhs, rhs) {
+        let taken = f(lhs, rhs);
+        self.count_branch(taken);
+        if taken {
             return self.branch_to(offset.into());
         }
         self.next_instr()
     }
 
+    /// Executes a generic fused compare and branch instruction with 32-bit branch offset.
+    fn execute_branch_binop_fallback<T>(
+        &mut self,
+        instr: BranchBinOpInstrFallback,
+        f: fn(T, T) -> bool,
+    ) where
+        T: From<UntypedValue>,
+    {
+        let offset: i32 = self.get_register_as(instr.offset);
+        self.execute_branch_binop_raw::<T>(instr.lhs, instr.rhs, BranchOffset::from(offset), f)
+    }
+
     /// Executes a generic fused compare and branch instruction with immediate `rhs` operand.
     fn execute_branch_binop_imm<T>(&mut self, instr: BranchBinOpInstrImm16<T>, f: fn(T, T) -> bool)
     where
@@ -122,7 +178,9 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     {
         let lhs: T = self.get_register_as(instr.lhs);
         let rhs = T::from(instr.rhs);
-        if f(lhs, rhs) {
+        let taken = f(lhs, rhs);
+        self.count_branch(taken);
+        if taken {
             return self.branch_to16(instr.offset);
         }
         self.next_instr()
@@ -171,6 +229,25 @@ where
     a >= b
 }
 
+/// Returns `true` if `a` is not less than `b` which includes unordered `NaN` operands.
+fn cmp_not_lt<T>(a: T, b: T) -> bool
+where
+    T: PartialOrd,
+{
+    !matches!(a.partial_cmp(&b), Some(cmp::Ordering::Less))
+}
+
+/// Returns `true` if `a` is not less than or equal to `b` which includes unordered `NaN` operands.
+fn cmp_not_le<T>(a: T, b: T) -> bool
+where
+    T: PartialOrd,
+{
+    !matches!(
+        a.partial_cmp(&b),
+        Some(cmp::Ordering::Less | cmp::Ordering::Equal)
+    )
+}
+
 fn cmp_i32_and(a: i32, b: i32) -> bool {
     (a & b) != 0
 }
@@ -197,7 +274,7 @@ fn cmp_i32_xor_eqz(a: i32, b: i32) -> bool {
 
 macro_rules! impl_execute_branch_binop {
     ( $( ($ty:ty, Instruction::$op_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
-        impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+        impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
             $(
                 #[doc = concat!("Executes an [`Instruction::", stringify!($op_name), "`].")]
                 #[inline(always)]
@@ -243,6 +320,8 @@ impl_execute_branch_binop! {
     (f32, Instruction::BranchF32Le, execute_branch_f32_le, cmp_le),
     (f32, Instruction::BranchF32Gt, execute_branch_f32_gt, cmp_gt),
     (f32, Instruction::BranchF32Ge, execute_branch_f32_ge, cmp_ge),
+    (f32, Instruction::BranchF32NotLt, execute_branch_f32_not_lt, cmp_not_lt),
+    (f32, Instruction::BranchF32NotLe, execute_branch_f32_not_le, cmp_not_le),
 
     (f64, Instruction::BranchF64Eq, execute_branch_f64_eq, cmp_eq),
     (f64, Instruction::BranchF64Ne, execute_branch_f64_ne, cmp_ne),
@@ -250,11 +329,13 @@ impl_execute_branch_binop! {
     (f64, Instruction::BranchF64Le, execute_branch_f64_le, cmp_le),
     (f64, Instruction::BranchF64Gt, execute_branch_f64_gt, cmp_gt),
     (f64, Instruction::BranchF64Ge, execute_branch_f64_ge, cmp_ge),
+    (f64, Instruction::BranchF64NotLt, execute_branch_f64_not_lt, cmp_not_lt),
+    (f64, Instruction::BranchF64NotLe, execute_branch_f64_not_le, cmp_not_le),
 }
 
 macro_rules! impl_execute_branch_binop_imm {
     ( $( ($ty:ty, Instruction::$op_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
-        impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+        impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
             $(
                 #[doc = concat!("Executes an [`Instruction::", stringify!($op_name), "`].")]
                 #[inline(always)]
@@ -295,7 +376,33 @@ impl_execute_branch_binop_imm! {
     (u64, Instruction::BranchI64GeUImm, execute_branch_i64_ge_u_imm, cmp_ge),
 }
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+macro_rules! impl_execute_branch_binop_fallback {
+    ( $( ($ty:ty, Instruction::$op_name:ident, $fn_name:ident, $op:expr) ),* $(,)? ) => {
+        impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
+            $(
+                #[doc = concat!("Executes an [`Instruction::", stringify!($op_name), "`].")]
+                #[inline(always)]
+                pub fn $fn_name(&mut self, instr: BranchBinOpInstrFallback) {
+                    self.execute_branch_binop_fallback::<$ty>(instr, $op)
+                }
+            )*
+        }
+    }
+}
+impl_execute_branch_binop_fallback! {
+    (i32, Instruction::BranchI32EqFallback, execute_branch_i32_eq_fallback, cmp_eq),
+    (i32, Instruction::BranchI32NeFallback, execute_branch_i32_ne_fallback, cmp_ne),
+    (i32, Instruction::BranchI32LtSFallback, execute_branch_i32_lt_s_fallback, cmp_lt),
+    (u32, Instruction::BranchI32LtUFallback, execute_branch_i32_lt_u_fallback, cmp_lt),
+    (i32, Instruction::BranchI32LeSFallback, execute_branch_i32_le_s_fallback, cmp_le),
+    (u32, Instruction::BranchI32LeUFallback, execute_branch_i32_le_u_fallback, cmp_le),
+    (i32, Instruction::BranchI32GtSFallback, execute_branch_i32_gt_s_fallback, cmp_gt),
+    (u32, Instruction::BranchI32GtUFallback, execute_branch_i32_gt_u_fallback, cmp_gt),
+    (i32, Instruction::BranchI32GeSFallback, execute_branch_i32_ge_s_fallback, cmp_ge),
+    (u32, Instruction::BranchI32GeUFallback, execute_branch_i32_ge_u_fallback, cmp_ge),
+}
+
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Executes an [`Instruction::BranchCmpFallback`].
     pub fn execute_branch_cmp_fallback(&mut self, lhs: Register, rhs: Register, params: Register) {
         use BranchComparator as C;
@@ -343,6 +450,10 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             C::F64Le => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_le),
             C::F64Gt => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_gt),
             C::F64Ge => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_ge),
+            C::F32NotLt => self.execute_branch_binop_raw::<f32>(lhs, rhs, offset, cmp_not_lt),
+            C::F32NotLe => self.execute_branch_binop_raw::<f32>(lhs, rhs, offset, cmp_not_le),
+            C::F64NotLt => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_not_lt),
+            C::F64NotLe => self.execute_branch_binop_raw::<f64>(lhs, rhs, offset, cmp_not_le),
         };
     }
 }
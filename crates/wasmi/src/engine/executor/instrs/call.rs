@@ -1,17 +1,18 @@
-use super::Executor;
+use super::{spectre::guard_table_index, Executor};
 use crate::{
     core::TrapCode,
     engine::{
         bytecode::{FuncIdx, Instruction, Register, RegisterSpan, SignatureIdx, TableIdx},
         code_map::InstructionPtr,
-        executor::stack::{CallFrame, FrameRegisters, Stack},
+        executor::stack::{CallFrame, FrameRegisters, FrameValueStackOffset, Stack},
         CompiledFunc,
         CompiledFuncEntity,
     },
-    func::FuncEntity,
+    func::{FuncEntity, RawHostFunc},
     Error,
     Func,
     FuncRef,
+    Instance,
 };
 use core::slice;
 
@@ -38,7 +39,12 @@ pub enum CallOutcome {
     Call {
         results: RegisterSpan,
         host_func: Func,
-        call_kind: CallKind,
+        /// The [`Instance`] of the calling Wasm function.
+        ///
+        /// # Note
+        ///
+        /// For tail calls the calling [`CallFrame`] has already been popped.
+        instance: Instance,
     },
 }
 
@@ -51,7 +57,7 @@ pub enum CallKind {
     Tail,
 }
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Updates the [`InstructionPtr`] of the caller [`CallFrame`] before dispatching a call.
     ///
     /// # Note
@@ -70,6 +76,25 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         caller.update_instr_ptr(ip);
     }
 
+    /// Forwards the results of the `callee` if its caller returns them right after the call.
+    ///
+    /// # Note
+    ///
+    /// This is the case if the caller resumes at an [`Instruction::ReturnForward`].
+    /// The `callee` then returns its results to where the caller returns its results and
+    /// also returns from the caller. This way the results are copied only once.
+    #[inline(always)]
+    fn forward_results(&mut self, callee: &mut CallFrame) {
+        let caller = self
+            .call_stack
+            .peek()
+            .expect("caller call frame must be on the stack");
+        if let Instruction::ReturnForward { values } = caller.instr_ptr().get() {
+            debug_assert_eq!(values.span(), callee.results());
+            callee.forward_to(caller);
+        }
+    }
+
     /// Fetches the [`Instruction::CallIndirectParams`] parameter for a call [`Instruction`].
     ///
     /// # Note
@@ -101,15 +126,20 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Creates a [`CallFrame`] for calling the [`CompiledFunc`].
+    ///
+    /// The [`CallFrame`] uses the `instance` of the callee if any or otherwise the [`Instance`]
+    /// of the caller, e.g. for calls to [`CompiledFunc`] of the same Wasm module.
     #[inline(always)]
     fn dispatch_compiled_func(
         &mut self,
         results: RegisterSpan,
-        func: &CompiledFuncEntity,
+        func: CompiledFunc,
+        entity: &CompiledFuncEntity,
+        instance: Option<Instance>,
     ) -> Result<CallFrame, Error> {
-        let instrs = func.instrs();
-        let instr_ptr = InstructionPtr::new(instrs.as_ptr());
-        let (base_ptr, frame_ptr) = self.value_stack.alloc_call_frame(func)?;
+        let instrs = entity.instrs();
+        let instr_ptr = InstructionPtr::new(instrs);
+        let (base_ptr, frame_ptr) = self.value_stack.alloc_call_frame(entity)?;
         // We have to reinstantiate the `self.sp` [`FrameRegisters`] since we just called
         // [`ValueStack::alloc_call_frame`] which might invalidate all live [`FrameRegisters`].
         let caller = self
@@ -118,8 +148,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             .expect("need to have a caller on the call stack");
         // Safety: We use the base offset of a live call frame on the call stack.
         self.sp = unsafe { self.value_stack.stack_ptr_at(caller.base_offset()) };
-        let instance = caller.instance();
-        let frame = CallFrame::new(instr_ptr, frame_ptr, base_ptr, results, *instance);
+        let instance = instance.unwrap_or_else(|| *caller.instance());
+        let frame = CallFrame::new(func, instr_ptr, frame_ptr, base_ptr, results, instance);
         Ok(frame)
     }
 
@@ -164,6 +194,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Prepares a [`CompiledFunc`] call with optional [`CallParams`].
+    ///
+    /// The `instance` is the [`Instance`] of the callee if it may differ from the caller's.
     #[inline(always)]
     fn prepare_compiled_func_call(
         &mut self,
@@ -171,9 +203,12 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         func: CompiledFunc,
         params: CallParams,
         call_kind: CallKind,
+        instance: Option<Instance>,
     ) -> Result<(), Error> {
-        let func = self.code_map.get(Some(self.ctx.fuel_mut()), func)?;
-        let mut called = self.dispatch_compiled_func(results, func)?;
+        let entity = self
+            .code_map
+            .get(Self::metered(self.ctx.fuel_mut()), func)?;
+        let mut called = self.dispatch_compiled_func(results, func, entity, instance)?;
         if let CallParams::Some = params {
             let called_sp = self.frame_stack_ptr(&called);
             self.ip = self.copy_call_params(called_sp);
@@ -182,6 +217,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             CallKind::Nested => {
                 // We need to update the instruction pointer of the caller call frame.
                 self.update_instr_ptr_at(1);
+                self.forward_results(&mut called);
             }
             CallKind::Tail => {
                 // In case of a tail call we have to remove the caller call frame after
@@ -221,7 +257,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         params: CallParams,
     ) -> Result<(), Error> {
         let results = self.caller_results();
-        self.prepare_compiled_func_call(results, func, params, CallKind::Tail)
+        self.prepare_compiled_func_call(results, func, params, CallKind::Tail, None)
     }
 
     /// Returns the `results` [`RegisterSpan`] of the top-most [`CallFrame`] on the [`CallStack`].
@@ -246,7 +282,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         results: RegisterSpan,
         func: CompiledFunc,
     ) -> Result<(), Error> {
-        self.prepare_compiled_func_call(results, func, CallParams::None, CallKind::Nested)
+        self.prepare_compiled_func_call(results, func, CallParams::None, CallKind::Nested, None)
     }
 
     /// Executes an [`Instruction::CallInternal`].
@@ -256,7 +292,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         results: RegisterSpan,
         func: CompiledFunc,
     ) -> Result<(), Error> {
-        self.prepare_compiled_func_call(results, func, CallParams::Some, CallKind::Nested)
+        self.prepare_compiled_func_call(results, func, CallParams::Some, CallKind::Nested, None)
     }
 
     /// Executes an [`Instruction::ReturnCallImported0`].
@@ -307,7 +343,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// Executes an imported or indirect (tail) call instruction.
     fn execute_call_imported_impl(
         &mut self,
-        results: RegisterSpan,
+        mut results: RegisterSpan,
         func: &Func,
         params: CallParams,
         call_kind: CallKind,
@@ -315,11 +351,17 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         match self.ctx.resolve_func(func) {
             FuncEntity::Wasm(func) => {
                 let instance = *func.instance();
-                self.prepare_compiled_func_call(results, func.func_body(), params, call_kind)?;
-                self.cache.update_instance(&instance);
+                self.prepare_compiled_func_call(
+                    results,
+                    func.func_body(),
+                    params,
+                    call_kind,
+                    Some(instance),
+                )?;
                 Ok(CallOutcome::Continue)
             }
             FuncEntity::Host(host_func) => {
+                let raw = host_func.raw();
                 let (input_types, output_types) = self
                     .func_types
                     .resolve_func_type(host_func.ty_dedup())
@@ -334,6 +376,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     .call_stack
                     .peek()
                     .expect("need to have a caller on the call stack");
+                let instance = *caller.instance();
                 // Safety: We use the base offset of a live call frame on the call stack.
                 self.sp = unsafe { self.value_stack.stack_ptr_at(caller.base_offset()) };
                 let offset = self.value_stack.extend_zeros(max_inout);
@@ -344,19 +387,116 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                         self.ip = new_ip;
                     }
                 }
-                if matches!(call_kind, CallKind::Nested) {
-                    self.update_instr_ptr_at(1);
+                match call_kind {
+                    CallKind::Nested => {
+                        self.update_instr_ptr_at(1);
+                        if let Some(raw) = raw {
+                            // Safety: We use the offset of the values that we just reserved.
+                            let buffer = unsafe { self.value_stack.stack_ptr_at(offset) };
+                            return self.execute_raw_host_func(
+                                raw,
+                                results,
+                                buffer,
+                                len_results,
+                                max_inout,
+                            );
+                        }
+                    }
+                    CallKind::Tail => {
+                        // In case of a tail call we have to remove the caller call frame before
+                        // the host function is called so that its results are returned to the
+                        // caller's caller and nested executions of the host function do not
+                        // keep the caller call frame alive.
+                        //
+                        // This moves the parameters of the host function call down to where the
+                        // caller call frame started and invalidates `self.sp` which is fine since
+                        // the Wasm execution is left for the host function call.
+                        let returned = self
+                            .call_stack
+                            .pop()
+                            .expect("need to have a caller on the call stack");
+                        self.value_stack
+                            .drain(returned.frame_offset(), FrameValueStackOffset::from(offset));
+                        if returned.forwarded() != 0 {
+                            results = self.forwarding_caller_results();
+                        }
+                    }
                 }
                 self.cache.reset();
                 Ok(CallOutcome::Call {
                     results,
                     host_func: *func,
-                    call_kind,
+                    instance,
                 })
             }
         }
     }
 
+    /// Returns the [`RegisterSpan`] where the top-most [`CallFrame`] expects the forwarded results.
+    ///
+    /// # Note
+    ///
+    /// This is used if a callee that forwards its results to where its caller returns
+    /// them has been popped before its results are available, e.g. upon a tail call to
+    /// a host function. The caller then resumes at its [`Instruction::ReturnForward`]
+    /// and returns the results itself.
+    fn forwarding_caller_results(&self) -> RegisterSpan {
+        let caller = self
+            .call_stack
+            .peek()
+            .expect("forwarding caller call frame must be on the stack");
+        match caller.instr_ptr().get() {
+            Instruction::ReturnForward { values } => values.span(),
+            unexpected => {
+                unreachable!("expected `Instruction::ReturnForward` but found {unexpected:?}")
+            }
+        }
+    }
+
+    /// Calls the raw host function `func` with the `max_inout` values at `buffer`.
+    ///
+    /// Upon success the results of the call are copied into the `results` of the caller.
+    ///
+    /// # Note
+    ///
+    /// Raw host functions do not have access to the [`Store`] and therefore are
+    /// called without leaving the execution of the calling Wasm function.
+    ///
+    /// # Errors
+    ///
+    /// If the raw host function returned a [`TrapCode`].
+    ///
+    /// [`Store`]: crate::Store
+    fn execute_raw_host_func(
+        &mut self,
+        func: RawHostFunc,
+        results: RegisterSpan,
+        mut buffer: FrameRegisters,
+        len_results: usize,
+        max_inout: usize,
+    ) -> Result<CallOutcome, Error> {
+        self.ctx.count_host_call();
+        // Safety: `buffer` points to the `max_inout` values that have just been
+        //         reserved on the value stack for the parameters and results of the call.
+        //         The raw host function upholds the safety contract of its definition.
+        let result = unsafe { func(buffer.as_mut_ptr(), max_inout) };
+        if let Err(trap_code) = result {
+            self.value_stack.drop(max_inout);
+            return Err(Error::from(trap_code));
+        }
+        let values = RegisterSpan::new(Register::from_i16(0)).iter(len_results);
+        for (result, value) in results.iter(len_results).zip(values) {
+            // Safety: Wasm validation and translation guarantee valid `results` registers
+            //         of the caller and `value` is within the bounds of `buffer`.
+            unsafe { self.sp.set(result, buffer.get(value)) };
+        }
+        self.value_stack.drop(max_inout);
+        // Note: The execution continues with the instruction following the call
+        //       the same as if the caller was resumed after a host function call.
+        self.ip.add(1);
+        Ok(CallOutcome::Continue)
+    }
+
     /// Executes an [`Instruction::CallIndirect0`].
     #[inline(never)]
     pub fn execute_return_call_indirect_0(
@@ -439,15 +579,27 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         params: CallParams,
         call_kind: CallKind,
     ) -> Result<CallOutcome, Error> {
+        // Note: `self.ip` points to the call site's unique parameter instruction at this point.
+        let site = self.ip.addr();
         let table = self.cache.get_table(self.ctx, table);
-        let funcref = self
+        let table_entity = self.ctx.resolve_table(&table);
+        let generation = table_entity.generation();
+        if let Some(func) = self
             .ctx
-            .resolve_table(&table)
-            .get_untyped(index)
+            .indirect_call_cache()
+            .get(site, &table, index, generation)
+        {
+            // The cached callee already passed the signature check for this call site.
+            return self.execute_call_imported_impl(results, &func, params, call_kind);
+        }
+        let mitigations = self.spectre_mitigations;
+        let table_entity = self.ctx.resolve_table_mut(&table);
+        let funcref = guard_table_index(mitigations, index, table_entity.size())
+            .and_then(|index| table_entity.get_untyped_or_init(index))
             .map(FuncRef::from)
             .ok_or(TrapCode::TableOutOfBounds)?;
-        let func = funcref.func().ok_or(TrapCode::IndirectCallToNull)?;
-        let actual_signature = self.ctx.resolve_func(func).ty_dedup();
+        let func = *funcref.func().ok_or(TrapCode::IndirectCallToNull)?;
+        let actual_signature = self.ctx.resolve_func(&func).ty_dedup();
         let expected_signature = self
             .ctx
             .resolve_instance(self.cache.instance())
@@ -456,8 +608,15 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 panic!("missing signature for call_indirect at index: {func_type:?}")
             });
         if actual_signature != expected_signature {
-            return Err(Error::from(TrapCode::BadSignature));
+            let expected = self.ctx.resolve_func_type(expected_signature);
+            let actual = self.ctx.resolve_func_type(actual_signature);
+            return Err(
+                Error::from(TrapCode::BadSignature).with_signature_mismatch(expected, actual)
+            );
         }
-        self.execute_call_imported_impl(results, func, params, call_kind)
+        self.ctx
+            .indirect_call_cache_mut()
+            .insert(site, table, index, generation, func);
+        self.execute_call_imported_impl(results, &func, params, call_kind)
     }
 }
@@ -28,7 +28,7 @@ macro_rules! impl_fallible_conversion_impls {
     };
 }
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_conversion_impls! {
         (Instruction::I32WrapI64, execute_i32_wrap_i64, UntypedValue::i32_wrap_i64),
         (Instruction::I64ExtendI32S, execute_i64_extend_i32_s, UntypedValue::i64_extend_i32_s),
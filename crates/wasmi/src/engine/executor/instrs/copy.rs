@@ -6,7 +6,7 @@ use crate::{
 use core::slice;
 use smallvec::SmallVec;
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Executes a generic `copy` [`Instruction`].
     fn execute_copy_impl<T>(
         &mut self,
@@ -65,18 +65,22 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     ///
     /// # Note
     ///
-    /// - This instruction assumes that `results` and `values` _do_ overlap
-    ///   and thus requires a costly temporary buffer to avoid overwriting
-    ///   intermediate copy results.
+    /// - This instruction assumes that `results` and `values` _do_ overlap.
+    ///   Like `memmove` it copies in the direction that reads every value
+    ///   before it is overwritten and thus requires no temporary buffer
+    ///   regardless of `len`.
     /// - If `results` and `values` do _not_ overlap [`Instruction::CopySpanNonOverlapping`] is used.
     #[inline(always)]
     pub fn execute_copy_span(&mut self, results: RegisterSpan, values: RegisterSpan, len: u16) {
-        let results = results.iter_u16(len);
-        let values = values.iter_u16(len);
-        let mut tmp = <SmallVec<[UntypedValue; 8]>>::default();
-        tmp.extend(values.into_iter().map(|value| self.get_register(value)));
-        for (result, value) in results.into_iter().zip(tmp) {
-            self.set_register(result, value);
+        let copies = results.iter_u16(len).zip(values.iter_u16(len));
+        if results.head() <= values.head() {
+            for (result, value) in copies {
+                self.set_register(result, self.get_register(value));
+            }
+        } else {
+            for (result, value) in copies.rev() {
+                self.set_register(result, self.get_register(value));
+            }
         }
         self.next_instr();
     }
@@ -5,7 +5,7 @@ use wasmi_core::UntypedValue;
 #[cfg(doc)]
 use crate::engine::bytecode::Instruction;
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Executes an [`Instruction::GlobalGet`].
     #[inline(always)]
     pub fn execute_global_get(&mut self, result: Register, global: GlobalIdx) {
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+use core::ops::Range;
 use wasmi_core::Pages;
 
 use super::Executor;
@@ -12,7 +14,7 @@ use crate::{
     Error,
 };
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Returns the [`Instruction::DataSegmentIdx`] parameter for an [`Instruction`].
     fn fetch_data_segment_index(&self, offset: usize) -> DataSegmentIdx {
         let mut addr: InstructionPtr = self.ip;
@@ -34,6 +36,11 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes an [`Instruction::MemorySize`].
+    ///
+    /// # Note
+    ///
+    /// The size is always read from the linear memory entity and never cached
+    /// since host functions or instances sharing the linear memory might grow it.
     #[inline(always)]
     pub fn execute_memory_size(&mut self, result: Register) {
         let memory = self.cache.default_memory(self.ctx);
@@ -78,7 +85,9 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             self.execute_memory_size(result);
             return Ok(());
         }
-        let delta = match Pages::new(delta) {
+        let memory = self.cache.default_memory(self.ctx);
+        let (memory, fuel) = self.ctx.resolve_memory_and_fuel_mut(memory);
+        let delta = match Pages::new_for_page_size(delta, memory.ty().page_size_log2()) {
             Some(pages) => pages,
             None => {
                 // Cannot grow memory so we push the expected error value.
@@ -86,10 +95,8 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 return self.try_next_instr();
             }
         };
-        let memory = self.cache.default_memory(self.ctx);
-        let (memory, fuel) = self.ctx.resolve_memory_and_fuel_mut(memory);
         let return_value = memory
-            .grow(delta, Some(fuel), resource_limiter)
+            .grow(delta, Self::metered(fuel), resource_limiter)
             .map(u32::from);
         let return_value = match return_value {
             Ok(return_value) => {
@@ -99,7 +106,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 self.cache.reset_default_memory_bytes();
                 return_value
             }
-            Err(EntityGrowError::InvalidGrow) => EntityGrowError::ERROR_CODE,
+            Err(EntityGrowError::InvalidGrow(_)) => EntityGrowError::ERROR_CODE,
             Err(EntityGrowError::TrapCode(trap_code)) => return Err(Error::from(trap_code)),
         };
         self.set_register(result, return_value);
@@ -219,26 +226,64 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a generic `memory.copy` instruction.
+    ///
+    /// # Note
+    ///
+    /// Both byte ranges are validated before any byte is copied so that
+    /// out of bounds copies trap without altering the linear memory.
+    /// Overlapping ranges are handled by [`slice::copy_within`].
     fn execute_memory_copy_impl(
         &mut self,
         dst_index: u32,
         src_index: u32,
         len: u32,
     ) -> Result<(), Error> {
-        let src_index = src_index as usize;
-        let dst_index = dst_index as usize;
-        let default_memory = self.cache.default_memory(self.ctx);
-        let (memory, fuel) = self.ctx.resolve_memory_and_fuel_mut(default_memory);
-        let data = memory.data_mut();
-        // These accesses just perform the bounds checks required by the Wasm spec.
-        data.get(src_index..)
-            .and_then(|memory| memory.get(..len as usize))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        data.get(dst_index..)
-            .and_then(|memory| memory.get(..len as usize))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
-        data.copy_within(src_index..src_index.wrapping_add(len as usize), dst_index);
+        let size = self.cache.default_memory_bytes(self.ctx).len();
+        let (src, dst) = match (
+            memory_range(size, src_index, len),
+            memory_range(size, dst_index, len),
+        ) {
+            (Ok(src), Ok(dst)) => (src, dst),
+            _ => return self.execute_memory_copy_watched(dst_index, src_index, len),
+        };
+        if FUEL {
+            self.ctx
+                .fuel_mut()
+                .consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
+        }
+        self.cache
+            .default_memory_bytes(self.ctx)
+            .copy_within(src, dst.start);
+        self.try_next_instr()
+    }
+
+    /// Retries a failed `memory.copy` instruction respecting the write watchpoints.
+    ///
+    /// # Note
+    ///
+    /// The cached bytes of linear memories with write watchpoints are empty
+    /// so that their accesses end up here. For all other linear memories
+    /// this just traps again with the original out of bounds error.
+    #[cold]
+    #[inline(never)]
+    fn execute_memory_copy_watched(
+        &mut self,
+        dst_index: u32,
+        src_index: u32,
+        len: u32,
+    ) -> Result<(), Error> {
+        let memory = *self.cache.default_memory(self.ctx);
+        let bytes = self.ctx.resolve_memory(&memory).data();
+        let src = memory_range(bytes.len(), src_index, len)?;
+        memory_range(bytes.len(), dst_index, len)?;
+        let copied = bytes[src].to_vec();
+        if FUEL {
+            self.ctx
+                .fuel_mut()
+                .consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
+        }
+        self.ctx
+            .write_watched(&memory, u64::from(dst_index), &copied)?;
         self.try_next_instr()
     }
 
@@ -351,18 +396,45 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a generic `memory.fill` instruction.
+    ///
+    /// # Note
+    ///
+    /// The byte range is validated before any byte is written so that
+    /// out of bounds fills trap without altering the linear memory.
     fn execute_memory_fill_impl(&mut self, dst: u32, value: u8, len: u32) -> Result<(), Error> {
-        let dst = dst as usize;
-        let len = len as usize;
-        let default_memory = self.cache.default_memory(self.ctx);
-        let (memory, fuel) = self.ctx.resolve_memory_and_fuel_mut(default_memory);
-        let memory = memory
-            .data_mut()
-            .get_mut(dst..)
-            .and_then(|memory| memory.get_mut(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
-        fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
-        memory.fill(value);
+        let size = self.cache.default_memory_bytes(self.ctx).len();
+        let Ok(range) = memory_range(size, dst, len) else {
+            return self.execute_memory_fill_watched(dst, value, len);
+        };
+        if FUEL {
+            self.ctx
+                .fuel_mut()
+                .consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
+        }
+        self.cache.default_memory_bytes(self.ctx)[range].fill(value);
+        self.try_next_instr()
+    }
+
+    /// Retries a failed `memory.fill` instruction respecting the write watchpoints.
+    ///
+    /// # Note
+    ///
+    /// The cached bytes of linear memories with write watchpoints are empty
+    /// so that their accesses end up here. For all other linear memories
+    /// this just traps again with the original out of bounds error.
+    #[cold]
+    #[inline(never)]
+    fn execute_memory_fill_watched(&mut self, dst: u32, value: u8, len: u32) -> Result<(), Error> {
+        let memory = *self.cache.default_memory(self.ctx);
+        let size = self.ctx.resolve_memory(&memory).data().len();
+        memory_range(size, dst, len)?;
+        if FUEL {
+            self.ctx
+                .fuel_mut()
+                .consume_fuel_if(|costs| costs.fuel_for_bytes(u64::from(len)))?;
+        }
+        let filled = vec![value; len as usize];
+        self.ctx.write_watched(&memory, u64::from(dst), &filled)?;
         self.try_next_instr()
     }
 
@@ -485,16 +557,78 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let len = len as usize;
         let data_index: DataSegmentIdx = self.fetch_data_segment_index(1);
         let (memory, data, fuel) = self.cache.get_memory_init_triplet(self.ctx, data_index);
-        let memory = memory
+        let Some(memory) = memory
             .get_mut(dst_index..)
             .and_then(|memory| memory.get_mut(..len))
-            .ok_or(TrapCode::MemoryOutOfBounds)?;
+        else {
+            return self.execute_memory_init_watched(dst, src, len, data_index);
+        };
         let data = data
             .get(src_index..)
             .and_then(|data| data.get(..len))
             .ok_or(TrapCode::MemoryOutOfBounds)?;
-        fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
+        if FUEL {
+            fuel.consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
+        }
         memory.copy_from_slice(data);
         self.try_next_instr_at(2)
     }
+
+    /// Retries a failed `memory.init` instruction respecting the write watchpoints.
+    ///
+    /// # Note
+    ///
+    /// The linear memory bytes of [`InstanceCache::get_memory_init_triplet`] are empty
+    /// for linear memories with write watchpoints so that their accesses end up here.
+    /// For all other linear memories this just traps again with the original out of bounds error.
+    ///
+    /// [`InstanceCache::get_memory_init_triplet`]: crate::engine::cache::InstanceCache::get_memory_init_triplet
+    #[cold]
+    #[inline(never)]
+    fn execute_memory_init_watched(
+        &mut self,
+        dst: u32,
+        src: u32,
+        len: usize,
+        data_index: DataSegmentIdx,
+    ) -> Result<(), Error> {
+        let memory = *self.cache.default_memory(self.ctx);
+        let size = self.ctx.resolve_memory(&memory).data().len();
+        let src_index = src as usize;
+        (dst as usize)
+            .checked_add(len)
+            .filter(|&end| end <= size)
+            .ok_or(TrapCode::MemoryOutOfBounds)?;
+        let segment = self.cache.get_data_segment(self.ctx, data_index.to_u32());
+        let data: Vec<u8> = self
+            .ctx
+            .resolve_data_segment_mut(&segment)
+            .bytes()
+            .get(src_index..)
+            .and_then(|data| data.get(..len))
+            .ok_or(TrapCode::MemoryOutOfBounds)?
+            .to_vec();
+        if FUEL {
+            self.ctx
+                .fuel_mut()
+                .consume_fuel_if(|costs| costs.fuel_for_bytes(len as u64))?;
+        }
+        self.ctx.write_watched(&memory, u64::from(dst), &data)?;
+        self.try_next_instr_at(2)
+    }
+}
+
+/// Returns the range of `len` bytes starting at `offset` within a linear memory of `size` bytes.
+///
+/// # Errors
+///
+/// If the range is out of bounds of the linear memory.
+#[inline(always)]
+fn memory_range(size: usize, offset: u32, len: u32) -> Result<Range<usize>, TrapCode> {
+    let start = offset as usize;
+    let end = start
+        .checked_add(len as usize)
+        .filter(|&end| end <= size)
+        .ok_or(TrapCode::MemoryOutOfBounds)?;
+    Ok(start..end)
 }
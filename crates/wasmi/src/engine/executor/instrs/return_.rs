@@ -17,16 +17,21 @@ pub enum ReturnOutcome {
     Host,
 }
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Returns the execution to the caller.
     ///
     /// Any return values are expected to already have been transferred
     /// from the returning callee to the caller.
+    ///
+    /// # Note
+    ///
+    /// If the results of the callee are forwarded the execution returns from
+    /// its callers returning together with it as well.
     #[inline(always)]
     fn return_impl(&mut self) -> ReturnOutcome {
         let returned = self
             .call_stack
-            .pop()
+            .pop_returned()
             .expect("the executing call frame is always on the stack");
         self.value_stack.truncate(returned.frame_offset());
         match self.call_stack.peek() {
@@ -38,6 +43,10 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                     self.cache,
                     caller,
                 );
+                #[cfg(feature = "metrics")]
+                if let Some(tracker) = &self.tracker {
+                    tracker.enter(caller.func());
+                }
                 ReturnOutcome::Wasm
             }
             None => ReturnOutcome::Host,
@@ -56,7 +65,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     fn return_caller_results(&mut self) -> (FrameRegisters, RegisterSpan) {
         let (callee, caller) = self
             .call_stack
-            .peek_2()
+            .peek_returned()
             .expect("the callee must exist on the call stack");
         match caller {
             Some(caller) => {
@@ -65,6 +74,9 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
                 // In this case we transfer the single return `value` to the `results`
                 // register span of the caller's call frame.
                 //
+                // Note: If the results of the callee are forwarded the caller is the
+                //       call frame to which the outermost forwarding caller returns.
+                //
                 // Safety: The caller call frame is still live on the value stack
                 //         and therefore it is safe to acquire its value stack pointer.
                 let caller_sp = unsafe { self.value_stack.stack_ptr_at(caller.base_offset()) };
@@ -169,6 +181,17 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         self.return_impl()
     }
 
+    /// Execute an [`Instruction::ReturnForward`] returning the results of the preceding call.
+    ///
+    /// # Note
+    ///
+    /// This is only executed if the results of the preceding call have not
+    /// been forwarded, e.g. if the callee was a host function.
+    #[inline(always)]
+    pub fn execute_return_forward(&mut self, values: RegisterSpanIter) -> ReturnOutcome {
+        self.execute_return_span(values)
+    }
+
     /// Execute an [`Instruction::ReturnMany`] returning many values.
     #[inline(always)]
     pub fn execute_return_many(&mut self, values: [Register; 3]) -> ReturnOutcome {
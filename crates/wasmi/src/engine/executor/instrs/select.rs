@@ -23,7 +23,7 @@ macro_rules! fetch_select_imm_param {
     }};
 }
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Returns the parameter of [`Instruction::Select`] or [`Instruction::SelectRev`] as [`UntypedValue`].
     fn fetch_select_param(&self) -> UntypedValue {
         let mut addr: InstructionPtr = self.ip;
@@ -40,6 +40,12 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a `select` instruction generically.
+    ///
+    /// # Note
+    ///
+    /// The `result` register may alias the `condition` or either operand register.
+    /// Therefore the `condition` and the selected operand are both read before
+    /// `result` is written.
     fn execute_select_impl<L, R>(
         &mut self,
         result: Register,
@@ -0,0 +1,77 @@
+//! Hardening of linear memory and table accesses against speculative execution.
+//!
+//! # Note
+//!
+//! These utilities are only in effect if [`Config::spectre_mitigations`] is enabled.
+//!
+//! [`Config::spectre_mitigations`]: crate::Config::spectre_mitigations
+
+use crate::core::UntypedValue;
+use core::hint::black_box;
+
+/// Returns `index` if `width` elements starting at `index` are within bounds of `len` elements.
+///
+/// Returns `None` otherwise.
+///
+/// # Note
+///
+/// The returned index is masked by the result of the bounds check via data dependency
+/// instead of control flow. Therefore accesses that are speculatively executed past a
+/// mispredicted bounds check access index `0` instead of an out of bounds index.
+#[inline(always)]
+fn spectre_guard(index: u64, width: u64, len: u64) -> Option<u64> {
+    let in_bounds = index.saturating_add(width) <= len;
+    // Note: `black_box` prevents the compiler from removing the mask after the bounds check.
+    let mask = black_box(u64::from(in_bounds).wrapping_neg());
+    if !in_bounds {
+        return None;
+    }
+    Some(index & mask)
+}
+
+/// Returns the `address` and `offset` of an access of `width` bytes to a linear memory of `len` bytes.
+///
+/// If `enabled` the returned address is the effective address guarded by [`spectre_guard`]
+/// and `None` is returned if the access is out of bounds.
+#[inline(always)]
+pub fn guard_address(
+    enabled: bool,
+    len: usize,
+    address: UntypedValue,
+    offset: u32,
+    width: u64,
+) -> Option<(UntypedValue, u32)> {
+    if !enabled {
+        return Some((address, offset));
+    }
+    let address = u64::from(u32::from(address)) + u64::from(offset);
+    let address = spectre_guard(address, width, len as u64)?;
+    // Note: The cast is lossless since `address` is in bounds of a 32-bit linear memory.
+    Some((UntypedValue::from(address as u32), 0))
+}
+
+/// Returns the effective `address` of an access of `width` bytes to a linear memory of `len` bytes.
+///
+/// If `enabled` the returned address is guarded by [`spectre_guard`]
+/// and `None` is returned if the access is out of bounds.
+#[inline(always)]
+pub fn guard_effective_address(enabled: bool, len: usize, address: u64, width: u64) -> Option<u64> {
+    if !enabled {
+        return Some(address);
+    }
+    spectre_guard(address, width, len as u64)
+}
+
+/// Returns the `index` of an access to a table with `len` elements.
+///
+/// If `enabled` the returned index is guarded by [`spectre_guard`]
+/// and `None` is returned if the access is out of bounds.
+#[inline(always)]
+pub fn guard_table_index(enabled: bool, index: u32, len: u32) -> Option<u32> {
+    if !enabled {
+        return Some(index);
+    }
+    let index = spectre_guard(u64::from(index), 1, u64::from(len))?;
+    // Note: The cast is lossless since `index` is in bounds of `len`.
+    Some(index as u32)
+}
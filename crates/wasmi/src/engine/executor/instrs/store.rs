@@ -1,4 +1,7 @@
-use super::Executor;
+use super::{
+    spectre::{guard_address, guard_effective_address},
+    Executor,
+};
 use crate::{
     core::{TrapCode, UntypedValue},
     engine::{
@@ -16,7 +19,7 @@ type WasmStoreOp = fn(
     value: UntypedValue,
 ) -> Result<(), TrapCode>;
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Returns the [`Instruction::Register`] parameter for an [`Instruction`].
     fn fetch_store_value(&self, offset: usize) -> Register {
         let mut addr: InstructionPtr = self.ip;
@@ -37,25 +40,71 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     /// - `{i32, i64}.store8`
     /// - `{i32, i64}.store16`
     /// - `i64.store32`
+    ///
+    /// The `width` is the number of bytes stored by `store_wrap`.
     #[inline(always)]
     fn execute_store_wrap(
         &mut self,
         address: UntypedValue,
         offset: u32,
         value: UntypedValue,
+        width: u64,
         store_wrap: WasmStoreOp,
     ) -> Result<(), Error> {
+        let mitigations = self.spectre_mitigations;
         let memory = self.cache.default_memory_bytes(self.ctx);
-        store_wrap(memory, address, offset, value)?;
+        let stored = match guard_address(mitigations, memory.len(), address, offset, width) {
+            Some((address, offset)) => store_wrap(memory, address, offset, value),
+            None => Err(TrapCode::MemoryOutOfBounds),
+        };
+        if stored.is_err() {
+            self.execute_store_wrap_watched(address, offset, value, width, store_wrap)?;
+        }
         Ok(())
     }
 
-    fn execute_store(&mut self, instr: StoreInstr, store_op: WasmStoreOp) -> Result<(), Error> {
+    /// Retries a failed Wasm `store` operation respecting the write watchpoints.
+    ///
+    /// # Note
+    ///
+    /// The cached bytes of linear memories with write watchpoints are empty
+    /// so that their accesses end up here. For all other linear memories
+    /// this just traps again with the original out of bounds error.
+    #[cold]
+    #[inline(never)]
+    fn execute_store_wrap_watched(
+        &mut self,
+        address: UntypedValue,
+        offset: u32,
+        value: UntypedValue,
+        width: u64,
+        store_wrap: WasmStoreOp,
+    ) -> Result<(), TrapCode> {
+        // Stores the wrapped value into a scratch buffer to find out about the written bytes.
+        let mut buffer = [0x00_u8; 8];
+        let bytes = &mut buffer[..width as usize];
+        store_wrap(bytes, UntypedValue::from(0_u32), 0, value)
+            .unwrap_or_else(|_| unreachable!("Wasm stores write at most 8 bytes"));
+        let address = u64::from(u32::from(address)) + u64::from(offset);
+        let memory = *self.cache.default_memory(self.ctx);
+        let len = self.ctx.resolve_memory(&memory).data().len();
+        let address = guard_effective_address(self.spectre_mitigations, len, address, width)
+            .ok_or(TrapCode::MemoryOutOfBounds)?;
+        self.ctx.write_watched(&memory, address, bytes)
+    }
+
+    fn execute_store(
+        &mut self,
+        instr: StoreInstr,
+        width: u64,
+        store_op: WasmStoreOp,
+    ) -> Result<(), Error> {
         let value = self.fetch_store_value(1);
         self.execute_store_wrap(
             self.get_register(instr.ptr),
             u32::from(instr.offset),
             self.get_register(value),
+            width,
             store_op,
         )?;
         self.try_next_instr_at(2)
@@ -64,12 +113,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     fn execute_store_offset16(
         &mut self,
         instr: StoreOffset16Instr<Register>,
+        width: u64,
         store_op: WasmStoreOp,
     ) -> Result<(), Error> {
         self.execute_store_wrap(
             self.get_register(instr.ptr),
             u32::from(instr.offset),
             self.get_register(instr.value),
+            width,
             store_op,
         )?;
         self.try_next_instr()
@@ -78,6 +129,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     fn execute_store_offset16_imm16<T, V>(
         &mut self,
         instr: StoreOffset16Instr<V>,
+        width: u64,
         store_op: WasmStoreOp,
     ) -> Result<(), Error>
     where
@@ -87,6 +139,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             self.get_register(instr.ptr),
             u32::from(instr.offset),
             T::from(instr.value).into(),
+            width,
             store_op,
         )?;
         self.try_next_instr()
@@ -95,12 +148,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     fn execute_store_at(
         &mut self,
         instr: StoreAtInstr<Register>,
+        width: u64,
         store_op: WasmStoreOp,
     ) -> Result<(), Error> {
         self.execute_store_wrap(
             UntypedValue::from(0u32),
             u32::from(instr.address),
             self.get_register(instr.value),
+            width,
             store_op,
         )?;
         self.try_next_instr()
@@ -109,6 +164,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     fn execute_store_at_imm16<T, V>(
         &mut self,
         instr: StoreAtInstr<V>,
+        width: u64,
         store_op: WasmStoreOp,
     ) -> Result<(), Error>
     where
@@ -118,6 +174,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             UntypedValue::from(0u32),
             u32::from(instr.address),
             T::from(instr.value).into(),
+            width,
             store_op,
         )?;
         self.try_next_instr()
@@ -133,14 +190,15 @@ macro_rules! impl_execute_istore {
             (Instruction::$var_store_off16_imm16:ident, $fn_store_off16_imm16:ident),
             (Instruction::$var_store_at:ident, $fn_store_at:ident),
             (Instruction::$var_store_at_imm16:ident, $fn_store_at_imm16:ident),
-            $impl_fn:expr $(,)?
+            $impl_fn:expr,
+            $width:literal $(,)?
         )
     ),* $(,)? ) => {
         $(
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store), "`].")]
             #[inline(always)]
             pub fn $fn_store(&mut self, instr: StoreInstr) -> Result<(), Error> {
-                self.execute_store(instr, $impl_fn)
+                self.execute_store(instr, $width, $impl_fn)
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16), "`].")]
@@ -149,7 +207,7 @@ macro_rules! impl_execute_istore {
                 &mut self,
                 instr: StoreOffset16Instr<Register>,
             ) -> Result<(), Error> {
-                self.execute_store_offset16(instr, $impl_fn)
+                self.execute_store_offset16(instr, $width, $impl_fn)
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16_imm16), "`].")]
@@ -158,13 +216,13 @@ macro_rules! impl_execute_istore {
                 &mut self,
                 instr: StoreOffset16Instr<$from_ty>,
             ) -> Result<(), Error> {
-                self.execute_store_offset16_imm16::<$to_ty, _>(instr, $impl_fn)
+                self.execute_store_offset16_imm16::<$to_ty, _>(instr, $width, $impl_fn)
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at), "`].")]
             #[inline(always)]
             pub fn $fn_store_at(&mut self, instr: StoreAtInstr<Register>) -> Result<(), Error> {
-                self.execute_store_at(instr, $impl_fn)
+                self.execute_store_at(instr, $width, $impl_fn)
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at_imm16), "`].")]
@@ -173,12 +231,12 @@ macro_rules! impl_execute_istore {
                 &mut self,
                 instr: StoreAtInstr<$from_ty>,
             ) -> Result<(), Error> {
-                self.execute_store_at_imm16::<$to_ty, _>(instr, $impl_fn)
+                self.execute_store_at_imm16::<$to_ty, _>(instr, $width, $impl_fn)
             }
         )*
     };
 }
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_execute_istore! {
         (
             (Const16<i32> => i32),
@@ -188,6 +246,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             (Instruction::I32StoreAt, execute_i32_store_at),
             (Instruction::I32StoreAtImm16, execute_i32_store_at_imm16),
             UntypedValue::i32_store,
+            4,
         ),
         (
             (Const16<i64> => i64),
@@ -197,6 +256,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             (Instruction::I64StoreAt, execute_i64_store_at),
             (Instruction::I64StoreAtImm16, execute_i64_store_at_imm16),
             UntypedValue::i64_store,
+            8,
         ),
         (
             (i8 => i8),
@@ -206,6 +266,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             (Instruction::I32Store8At, execute_i32_store8_at),
             (Instruction::I32Store8AtImm, execute_i32_store8_at_imm),
             UntypedValue::i32_store8,
+            1,
         ),
         (
             (i16 => i16),
@@ -215,6 +276,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             (Instruction::I32Store16At, execute_i32_store16_at),
             (Instruction::I32Store16AtImm, execute_i32_store16_at_imm),
             UntypedValue::i32_store16,
+            2,
         ),
         (
             (i8 => i8),
@@ -224,6 +286,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             (Instruction::I64Store8At, execute_i64_store8_at),
             (Instruction::I64Store8AtImm, execute_i64_store8_at_imm),
             UntypedValue::i64_store8,
+            1,
         ),
         (
             (i16 => i16),
@@ -233,6 +296,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             (Instruction::I64Store16At, execute_i64_store16_at),
             (Instruction::I64Store16AtImm, execute_i64_store16_at_imm),
             UntypedValue::i64_store16,
+            2,
         ),
         (
             (Const16<i32> => i32),
@@ -242,6 +306,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             (Instruction::I64Store32At, execute_i64_store32_at),
             (Instruction::I64Store32AtImm16, execute_i64_store32_at_imm16),
             UntypedValue::i64_store32,
+            4,
         ),
     }
 }
@@ -252,14 +317,15 @@ macro_rules! impl_execute_fstore {
             (Instruction::$var_store:ident, $fn_store:ident),
             (Instruction::$var_store_off16:ident, $fn_store_off16:ident),
             (Instruction::$var_store_at:ident, $fn_store_at:ident),
-            $impl_fn:expr $(,)?
+            $impl_fn:expr,
+            $width:literal $(,)?
         )
     ),* $(,)? ) => {
         $(
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store), "`].")]
             #[inline(always)]
             pub fn $fn_store(&mut self, instr: StoreInstr) -> Result<(), Error> {
-                self.execute_store(instr, $impl_fn)
+                self.execute_store(instr, $width, $impl_fn)
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_off16), "`].")]
@@ -268,31 +334,33 @@ macro_rules! impl_execute_fstore {
                 &mut self,
                 instr: StoreOffset16Instr<Register>,
             ) -> Result<(), Error> {
-                self.execute_store_offset16(instr, $impl_fn)
+                self.execute_store_offset16(instr, $width, $impl_fn)
             }
 
             #[doc = concat!("Executes an [`Instruction::", stringify!($var_store_at), "`].")]
             #[inline(always)]
             pub fn $fn_store_at(&mut self, instr: StoreAtInstr<Register>) -> Result<(), Error> {
-                self.execute_store_at(instr, $impl_fn)
+                self.execute_store_at(instr, $width, $impl_fn)
             }
         )*
     }
 }
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_execute_fstore! {
         (
             (Instruction::F32Store, execute_f32_store),
             (Instruction::F32StoreOffset16, execute_f32_store_offset16),
             (Instruction::F32StoreAt, execute_f32_store_at),
             UntypedValue::f32_store,
+            4,
         ),
         (
             (Instruction::F64Store, execute_f64_store),
             (Instruction::F64StoreOffset16, execute_f64_store_offset16),
             (Instruction::F64StoreAt, execute_f64_store_at),
             UntypedValue::f64_store,
+            8,
         ),
     }
 }
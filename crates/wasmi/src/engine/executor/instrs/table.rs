@@ -1,4 +1,4 @@
-use super::Executor;
+use super::{spectre::guard_table_index, Executor};
 use crate::{
     core::TrapCode,
     engine::{
@@ -11,7 +11,7 @@ use crate::{
     Error,
 };
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     /// Returns the [`Instruction::TableIdx`] parameter for an [`Instruction`].
     fn fetch_table_index(&self, offset: usize) -> TableIdx {
         let mut addr: InstructionPtr = self.ip;
@@ -53,10 +53,10 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     fn execute_table_get_impl(&mut self, result: Register, index: u32) -> Result<(), Error> {
         let table_index = self.fetch_table_index(1);
         let table = self.cache.get_table(self.ctx, table_index);
-        let value = self
-            .ctx
-            .resolve_table(&table)
-            .get_untyped(index)
+        let mitigations = self.spectre_mitigations;
+        let table = self.ctx.resolve_table_mut(&table);
+        let value = guard_table_index(mitigations, index, table.size())
+            .and_then(|index| table.get_untyped_or_init(index))
             .ok_or(TrapCode::TableOutOfBounds)?;
         self.set_register(result, value);
         self.try_next_instr_at(2)
@@ -70,6 +70,11 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
     }
 
     /// Executes a generic `table.size` instruction.
+    ///
+    /// # Note
+    ///
+    /// The size is always read from the table entity and never cached
+    /// since host functions or instances sharing the table might grow it.
     fn execute_table_size_impl(&mut self, result: Register, table_index: TableIdx) {
         let table = self.cache.get_table(self.ctx, table_index);
         let size = self.ctx.resolve_table(&table).size();
@@ -99,8 +104,11 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let table_index = self.fetch_table_index(1);
         let table = self.cache.get_table(self.ctx, table_index);
         let value = self.get_register(value);
-        self.ctx
-            .resolve_table_mut(&table)
+        let mitigations = self.spectre_mitigations;
+        let table = self.ctx.resolve_table_mut(&table);
+        let index =
+            guard_table_index(mitigations, index, table.size()).ok_or(TrapCode::TableOutOfBounds)?;
+        table
             .set_untyped(index, value)
             .map_err(|_| TrapCode::TableOutOfBounds)?;
         self.try_next_instr_at(2)
@@ -231,7 +239,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             // Case: copy within the same table
             let table = self.cache.get_table(self.ctx, dst_table_index);
             let (table, fuel) = self.ctx.resolve_table_and_fuel_mut(&table);
-            table.copy_within(dst_index, src_index, len, Some(fuel))?;
+            table.copy_within(dst_index, src_index, len, Self::metered(fuel))?;
         } else {
             // Case: copy between two different tables
             let dst_table = self.cache.get_table(self.ctx, dst_table_index);
@@ -239,7 +247,14 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             // Copy from one table to another table:
             let (dst_table, src_table, fuel) =
                 self.ctx.resolve_table_pair_and_fuel(&dst_table, &src_table);
-            TableEntity::copy(dst_table, dst_index, src_table, src_index, len, Some(fuel))?;
+            TableEntity::copy(
+                dst_table,
+                dst_index,
+                src_table,
+                src_index,
+                len,
+                Self::metered(fuel),
+            )?;
         }
         self.try_next_instr_at(3)
     }
@@ -373,7 +388,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
             element,
             src_index,
             len,
-            Some(fuel),
+            Self::metered(fuel),
             |func_index| {
                 instance
                     .get_func(func_index)
@@ -446,7 +461,7 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let value = self.get_register(value);
         let table = self.cache.get_table(self.ctx, table_index);
         let (table, fuel) = self.ctx.resolve_table_and_fuel_mut(&table);
-        table.fill_untyped(dst, value, len, Some(fuel))?;
+        table.fill_untyped(dst, value, len, Self::metered(fuel))?;
         self.try_next_instr_at(2)
     }
 
@@ -493,10 +508,10 @@ impl<'ctx, 'engine> Executor<'ctx, 'engine> {
         let table = self.cache.get_table(self.ctx, table_index);
         let value = self.get_register(value);
         let (table, fuel) = self.ctx.resolve_table_and_fuel_mut(&table);
-        let return_value = table.grow_untyped(delta, value, Some(fuel), resource_limiter);
+        let return_value = table.grow_untyped(delta, value, Self::metered(fuel), resource_limiter);
         let return_value = match return_value {
             Ok(return_value) => return_value,
-            Err(EntityGrowError::InvalidGrow) => EntityGrowError::ERROR_CODE,
+            Err(EntityGrowError::InvalidGrow(_)) => EntityGrowError::ERROR_CODE,
             Err(EntityGrowError::TrapCode(trap_code)) => return Err(Error::from(trap_code)),
         };
         self.set_register(result, return_value);
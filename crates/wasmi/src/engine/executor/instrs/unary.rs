@@ -16,7 +16,7 @@ macro_rules! impl_unary_impls {
     };
 }
 
-impl<'ctx, 'engine> Executor<'ctx, 'engine> {
+impl<'ctx, 'engine, const FUEL: bool, const DIGEST: u8> Executor<'ctx, 'engine, FUEL, DIGEST> {
     impl_unary_impls! {
         (Instruction::I32Clz, execute_i32_clz, UntypedValue::i32_clz),
         (Instruction::I32Ctz, execute_i32_ctz, UntypedValue::i32_ctz),
@@ -1,7 +1,7 @@
 pub(crate) use self::stack::Stack;
 use self::{
-    instrs::{execute_instrs, CallKind, WasmOutcome},
-    stack::CallFrame,
+    instrs::{execute_instrs, WasmOutcome},
+    stack::{CallFrame, FrameRegisters},
     trap::TaggedTrap,
 };
 use crate::{
@@ -9,13 +9,12 @@ use crate::{
         bytecode::{Register, RegisterSpan},
         cache::InstanceCache,
         code_map::InstructionPtr,
+        CallLimits,
         CallParams,
         CallResults,
         EngineInner,
         EngineResources,
         FuncParams,
-        ResumableCallBase,
-        ResumableInvocation,
     },
     func::HostFuncEntity,
     AsContext,
@@ -25,8 +24,23 @@ use crate::{
     FuncEntity,
     Instance,
     StoreContextMut,
+    YieldDecision,
 };
 
+#[cfg(feature = "std")]
+use crate::engine::executing::ExecutingGuard;
+#[cfg(feature = "metrics")]
+use crate::engine::sampler::CurrentFunc;
+#[cfg(feature = "resumable")]
+use crate::{
+    engine::{HostYield, ResumableCallBase, ResumableInvocation, ResumeMode},
+    errors::ErrorKind,
+    value::WithType,
+    Value,
+};
+#[cfg(feature = "resumable")]
+use alloc::boxed::Box;
+
 #[cfg(doc)]
 use crate::{engine::StackLimits, Store};
 
@@ -34,31 +48,127 @@ mod instrs;
 mod stack;
 mod trap;
 
+/// Returns the start time of a host initiated call if the [`Store`] records metrics.
+///
+/// # Note
+///
+/// The start time is `Some(None)` if the [`Engine`] must not read the wall clock
+/// due to [`Config::deterministic_only`].
+///
+/// [`Engine`]: crate::Engine
+/// [`Config::deterministic_only`]: crate::Config::deterministic_only
+#[cfg(feature = "metrics")]
+fn start_call_metrics<T>(ctx: &StoreContextMut<T>) -> Option<Option<std::time::Instant>> {
+    if !ctx.store.inner.metrics().is_enabled() {
+        return None;
+    }
+    let deterministic = ctx.store.engine().config().get_deterministic_only();
+    Some((!deterministic).then(std::time::Instant::now))
+}
+
+/// Records the host initiated call of `func` started at `started` if any.
+///
+/// # Note
+///
+/// Calls without a start time are recorded with a duration of zero.
+#[cfg(feature = "metrics")]
+fn finish_call_metrics<T>(
+    ctx: &mut StoreContextMut<T>,
+    func: &Func,
+    started: Option<Option<std::time::Instant>>,
+    trapped: bool,
+) {
+    if let Some(started) = started {
+        let duration = started.map(|started| started.elapsed()).unwrap_or_default();
+        ctx.store.inner.record_call_metrics(func, duration, trapped);
+    }
+}
+
+/// Enters the tracing span of the host initiated call of `func`.
+///
+/// # Note
+///
+/// The `func_index` field of Wasm functions that have not yet been compiled
+/// is recorded once they have been compiled lazily.
+#[cfg(feature = "tracing")]
+fn enter_call_span<T>(
+    ctx: &StoreContextMut<T>,
+    res: &EngineResources,
+    func: &Func,
+) -> tracing::span::EnteredSpan {
+    let (host, func_index) = match ctx.store.inner.resolve_func(func) {
+        FuncEntity::Wasm(func) => {
+            let func_index = res
+                .code_map
+                .get_compiled(func.func_body())
+                .and_then(|func| func.info().func_index);
+            (false, func_index)
+        }
+        FuncEntity::Host(_) => (true, None),
+    };
+    tracing::info_span!("call", host, func_index).entered()
+}
+
+/// Emits a tracing event for the `error` of a host initiated call.
+#[cfg(feature = "tracing")]
+fn trace_call_error(error: &Error) {
+    match error.as_trap_code() {
+        Some(trap_code) => tracing::info!(?trap_code, "call trapped"),
+        None => tracing::info!(%error, "call failed"),
+    }
+}
+
 impl EngineInner {
     /// Executes the given [`Func`] with the given `params` and returns the `results`.
     ///
     /// Uses the [`StoreContextMut`] for context information about the Wasm [`Store`].
+    /// The stack usage of the execution is additionally restricted to the `limits` if any.
     ///
     /// # Errors
     ///
     /// If the Wasm execution traps or runs out of resources.
     pub fn execute_func<T, Results>(
         &self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         func: &Func,
         params: impl CallParams,
         results: Results,
+        limits: Option<CallLimits>,
     ) -> Result<<Results as CallResults>::Results, Error>
     where
         Results: CallResults,
     {
+        ctx.store.inner.enter_execution()?;
+        #[cfg(feature = "metrics")]
+        let started = start_call_metrics(&ctx);
         let res = self.res.read();
+        #[cfg(feature = "std")]
+        let _executing = ExecutingGuard::enter(res.func_types.engine_idx());
+        #[cfg(feature = "tracing")]
+        let _span = enter_call_span(&ctx, &res, func);
         let mut stack = self.stacks.lock().reuse_or_new();
+        let previous = limits.map(|limits| stack.restrict_limits(limits));
         let results = EngineExecutor::new(&res, &mut stack)
-            .execute_root_func(ctx, func, params, results)
+            .execute_root_func(ctx.as_context_mut(), func, params, results)
             .map_err(TaggedTrap::into_error);
+        ctx.store.inner.exit_execution();
+        #[cfg(feature = "metrics")]
+        finish_call_metrics(&mut ctx, func, started, results.is_err());
+        if let Some(previous) = previous {
+            stack.restore_limits(previous);
+        }
+        let usage = stack.usage();
+        ctx.store.inner.record_stack_usage(usage);
         self.stacks.lock().recycle(stack);
-        results
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &results {
+            trace_call_error(error);
+        }
+        results.map_err(|error| {
+            error
+                .with_stack_usage(usage)
+                .with_trap_messages(self.trap_messages())
+        })
     }
 
     /// Executes the given [`Func`] resumably with the given `params` and returns the `results`.
@@ -68,6 +178,7 @@ impl EngineInner {
     /// # Errors
     ///
     /// If the Wasm execution traps or runs out of resources.
+    #[cfg(feature = "resumable")]
     pub(crate) fn execute_func_resumable<T, Results>(
         &self,
         mut ctx: StoreContextMut<T>,
@@ -78,7 +189,14 @@ impl EngineInner {
     where
         Results: CallResults,
     {
+        ctx.store.inner.enter_execution()?;
+        #[cfg(feature = "metrics")]
+        let started = start_call_metrics(&ctx);
         let res = self.res.read();
+        #[cfg(feature = "std")]
+        let _executing = ExecutingGuard::enter(res.func_types.engine_idx());
+        #[cfg(feature = "tracing")]
+        let _span = enter_call_span(&ctx, &res, func);
         let mut stack = self.stacks.lock().reuse_or_new();
         let results = EngineExecutor::new(&res, &mut stack).execute_root_func(
             ctx.as_context_mut(),
@@ -86,6 +204,16 @@ impl EngineInner {
             params,
             results,
         );
+        ctx.store.inner.exit_execution();
+        #[cfg(feature = "metrics")]
+        finish_call_metrics(
+            &mut ctx,
+            func,
+            started,
+            matches!(results, Err(TaggedTrap::Wasm(_))),
+        );
+        let usage = stack.usage();
+        ctx.store.inner.record_stack_usage(usage);
         match results {
             Ok(results) => {
                 self.stacks.lock().recycle(stack);
@@ -93,18 +221,26 @@ impl EngineInner {
             }
             Err(TaggedTrap::Wasm(error)) => {
                 self.stacks.lock().recycle(stack);
-                Err(error)
+                #[cfg(feature = "tracing")]
+                trace_call_error(&error);
+                Err(error
+                    .with_stack_usage(usage)
+                    .with_trap_messages(self.trap_messages()))
             }
             Err(TaggedTrap::Host {
                 host_func,
                 host_error,
+                host_params,
                 caller_results,
+                caller_instance,
             }) => Ok(ResumableCallBase::Resumable(ResumableInvocation::new(
                 ctx.as_context().store.engine().clone(),
                 *func,
                 host_func,
                 host_error,
+                host_params,
                 caller_results,
+                caller_instance,
                 stack,
             ))),
         }
@@ -117,26 +253,39 @@ impl EngineInner {
     /// # Errors
     ///
     /// If the Wasm execution traps or runs out of resources.
+    #[cfg(feature = "resumable")]
     pub(crate) fn resume_func<T, Results>(
         &self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         mut invocation: ResumableInvocation,
+        mode: ResumeMode,
         params: impl CallParams,
         results: Results,
     ) -> Result<ResumableCallBase<<Results as CallResults>::Results>, Error>
     where
         Results: CallResults,
     {
+        ctx.store.inner.enter_execution()?;
         let res = self.res.read();
+        #[cfg(feature = "std")]
+        let _executing = ExecutingGuard::enter(res.func_types.engine_idx());
+        #[cfg(feature = "tracing")]
+        let _span = enter_call_span(&ctx, &res, &invocation.func());
         let host_func = invocation.host_func();
         let caller_results = invocation.caller_results();
+        let caller_instance = invocation.caller_instance();
         let results = EngineExecutor::new(&res, &mut invocation.stack).resume_func(
-            ctx,
+            ctx.as_context_mut(),
             host_func,
+            mode,
             params,
             caller_results,
+            &caller_instance,
             results,
         );
+        ctx.store.inner.exit_execution();
+        let usage = invocation.stack.usage();
+        ctx.store.inner.record_stack_usage(usage);
         match results {
             Ok(results) => {
                 self.stacks.lock().recycle(invocation.take_stack());
@@ -144,14 +293,26 @@ impl EngineInner {
             }
             Err(TaggedTrap::Wasm(error)) => {
                 self.stacks.lock().recycle(invocation.take_stack());
-                Err(error)
+                #[cfg(feature = "tracing")]
+                trace_call_error(&error);
+                Err(error
+                    .with_stack_usage(usage)
+                    .with_trap_messages(self.trap_messages()))
             }
             Err(TaggedTrap::Host {
                 host_func,
                 host_error,
+                host_params,
                 caller_results,
+                caller_instance,
             }) => {
-                invocation.update(host_func, host_error, caller_results);
+                invocation.update(
+                    host_func,
+                    host_error,
+                    host_params,
+                    caller_results,
+                    caller_instance,
+                );
                 Ok(ResumableCallBase::Resumable(invocation))
             }
         }
@@ -197,17 +358,29 @@ impl<'engine> EngineExecutor<'engine> {
         self.stack.reset();
         match ctx.as_context().store.inner.resolve_func(func) {
             FuncEntity::Wasm(wasm_func) => {
+                // Note: Callers reject functions of other engines via `StoreInner::check_func_engine`
+                //       since their `CompiledFunc` would resolve to an unrelated function here.
+                debug_assert!(
+                    wasm_func
+                        .ty_dedup()
+                        .is_owned_by(self.res.func_types.engine_idx()),
+                    "encountered Wasm function of another engine"
+                );
                 // We reserve space on the stack to write the results of the root function execution.
                 let len_results = results.len_results();
                 self.stack.values.reserve(len_results)?;
                 self.stack.values.extend_zeros(len_results);
                 let instance = *wasm_func.instance();
-                let compiled_func = wasm_func.func_body();
+                let func_body = wasm_func.func_body();
                 let ctx = ctx.as_context_mut();
                 let compiled_func = self
                     .res
                     .code_map
-                    .get(Some(ctx.store.inner.fuel_mut()), compiled_func)?;
+                    .get(Some(ctx.store.inner.fuel_mut()), func_body)?;
+                #[cfg(feature = "tracing")]
+                if let Some(func_index) = compiled_func.info().func_index {
+                    tracing::Span::current().record("func_index", func_index);
+                }
                 let (base_ptr, frame_ptr) = self.stack.values.alloc_call_frame(compiled_func)?;
                 // Safety: We use the `base_ptr` that we just received upon allocating the new
                 //         call frame which is guaranteed to be valid for this particular operation
@@ -216,7 +389,8 @@ impl<'engine> EngineExecutor<'engine> {
                 //         be exactly the length of the expected function arguments.
                 unsafe { self.stack.values.fill_at(base_ptr, params.call_params()) };
                 self.stack.calls.push(CallFrame::new(
-                    InstructionPtr::new(compiled_func.instrs().as_ptr()),
+                    func_body,
+                    InstructionPtr::new(compiled_func.instrs()),
                     frame_ptr,
                     base_ptr,
                     RegisterSpan::new(Register::from_i16(0)),
@@ -244,7 +418,8 @@ impl<'engine> EngineExecutor<'engine> {
                     *value = param;
                 }
                 let host_func = *host_func;
-                self.dispatch_host_func(ctx.as_context_mut(), host_func, HostFuncCaller::Root)?;
+                self.dispatch_host_func(ctx.as_context_mut(), host_func, HostFuncCaller::Root)
+                    .map_err(|error| error.error)?;
             }
         };
         let results = self.write_results_back(results);
@@ -260,12 +435,25 @@ impl<'engine> EngineExecutor<'engine> {
     /// - If the given `params` do not match the expected parameters of `func`.
     /// - If the given `results` do not match the the length of the expected results of `func`.
     /// - When encountering a Wasm or host trap during the execution of `func`.
+    ///
+    /// # Note
+    ///
+    /// The `caller_results` are relative to the top-most [`CallFrame`] of the paused
+    /// [`Stack`] which might have been reallocated since the pause. Therefore the caller
+    /// registers are re-derived from the live [`CallFrame`] before writing back the results.
+    ///
+    /// The `caller_instance` is the [`Instance`] of the Wasm caller of `host_func` which is
+    /// no longer on the [`Stack`] if `host_func` was called via tail call.
+    #[allow(clippy::too_many_arguments)]
+    #[cfg(feature = "resumable")]
     pub fn resume_func<T, Results>(
         &mut self,
         mut ctx: StoreContextMut<T>,
-        _host_func: Func,
+        host_func: Func,
+        mode: ResumeMode,
         params: impl CallParams,
         caller_results: RegisterSpan,
+        caller_instance: &Instance,
         results: Results,
     ) -> Result<<Results as CallResults>::Results, TaggedTrap>
     where
@@ -276,9 +464,31 @@ impl<'engine> EngineExecutor<'engine> {
             .calls
             .peek()
             .expect("must have caller call frame on stack upon function resumption");
-        let mut caller_sp = unsafe { self.stack.values.stack_ptr_at(caller.base_offset()) };
+        if let ResumeMode::Reenter { continuation } = mode {
+            self.reenter_host_func(
+                &mut ctx,
+                &host_func,
+                continuation,
+                params,
+                caller_results,
+                caller_instance,
+            )?;
+            self.execute_func(ctx.as_context_mut())?;
+            let results = self.write_results_back(results);
+            return Ok(results);
+        }
         let call_params = params.call_params();
         let len_params = call_params.len();
+        debug_assert!(
+            caller_results.iter(len_params).all(|result| {
+                usize::try_from(result.to_i16()).is_ok_and(|index| {
+                    usize::from(caller.base_offset()) + index < self.stack.values.as_slice().len()
+                })
+            }),
+            "caller results {caller_results:?} out of bounds for the live caller frame"
+        );
+        // Safety: We use the base offset of a live call frame on the call stack.
+        let mut caller_sp = unsafe { self.stack.values.stack_ptr_at(caller.base_offset()) };
         for (result, param) in caller_results.iter(len_params).zip(call_params) {
             unsafe { caller_sp.set(result, param) };
         }
@@ -287,13 +497,42 @@ impl<'engine> EngineExecutor<'engine> {
         Ok(results)
     }
 
+    /// Returns the [`FrameRegisters`] of the Wasm caller of a host function.
+    ///
+    /// # Note
+    ///
+    /// This is the top-most [`CallFrame`] or the root results of the execution
+    /// if the root function tail called the host function.
+    fn caller_stack_ptr(&mut self) -> FrameRegisters {
+        match self.stack.calls.peek() {
+            // Safety: We use the base offset of a live call frame on the call stack.
+            Some(caller) => unsafe { self.stack.values.stack_ptr_at(caller.base_offset()) },
+            None => self.stack.values.root_stack_ptr(),
+        }
+    }
+
     /// Executes the top most Wasm function on the [`Stack`] until the [`Stack`] is empty.
     ///
     /// # Errors
     ///
     /// When encountering a Wasm or host trap during execution.
     #[inline(never)]
-    fn execute_func<T>(&mut self, mut ctx: StoreContextMut<T>) -> Result<(), TaggedTrap> {
+    fn execute_func<T>(&mut self, ctx: StoreContextMut<T>) -> Result<(), TaggedTrap> {
+        #[cfg(feature = "metrics")]
+        if ctx.engine().config().get_sampling() {
+            // Note: the thread is idle for sampling after returning to the host
+            //       or continues executing the Wasm function of an outer execution.
+            let current = CurrentFunc::of_current_thread();
+            let outer = current.replace(CurrentFunc::IDLE);
+            let result = self.execute_func_impl(ctx);
+            current.replace(outer);
+            return result;
+        }
+        self.execute_func_impl(ctx)
+    }
+
+    /// Implementation of [`EngineExecutor::execute_func`].
+    fn execute_func_impl<T>(&mut self, mut ctx: StoreContextMut<T>) -> Result<(), TaggedTrap> {
         let mut cache = self
             .stack
             .calls
@@ -311,27 +550,115 @@ impl<'engine> EngineExecutor<'engine> {
                 WasmOutcome::Call {
                     results,
                     ref host_func,
-                    call_kind,
+                    instance,
                 } => {
-                    let instance = *self
-                        .stack
-                        .calls
-                        .peek()
-                        .expect("caller must be on the stack")
-                        .instance();
-                    self.execute_host_func(&mut ctx, results, host_func, &instance, call_kind)?;
+                    self.execute_host_func(&mut ctx, results, host_func, &instance)?;
+                    if self.stack.calls.peek().is_none() {
+                        // In this case the root function tail called the host function.
+                        // Therefore the host function has returned from the entire execution.
+                        return Ok(());
+                    }
+                }
+                WasmOutcome::Yield => {
+                    if let YieldDecision::Abort(trap_code) = ctx.store.invoke_yield_callback() {
+                        return Err(TaggedTrap::from(trap_code));
+                    }
                 }
             }
         }
     }
 
+    /// Re-enters the paused host function `func` with `params` as its parameters.
+    ///
+    /// The `continuation` token is queryable by the host function throughout the call.
+    ///
+    /// # Errors
+    ///
+    /// If the host function returned an error. In this case the error is tagged
+    /// so that the function invocation can be resumed again.
+    #[cfg(feature = "resumable")]
+    fn reenter_host_func<T>(
+        &mut self,
+        ctx: &mut StoreContextMut<'_, T>,
+        func: &Func,
+        continuation: Option<u64>,
+        params: impl CallParams,
+        results: RegisterSpan,
+        instance: &Instance,
+    ) -> Result<(), TaggedTrap> {
+        let func_entity = match ctx.as_context().store.inner.resolve_func(func) {
+            FuncEntity::Wasm(wasm_func) => {
+                unreachable!("expected a host function but found: {wasm_func:?}")
+            }
+            FuncEntity::Host(host_func) => *host_func,
+        };
+        let (input_types, output_types) = self
+            .res
+            .func_types
+            .resolve_func_type(func_entity.ty_dedup())
+            .params_results();
+        // The host function call buffer has been dropped when the host function
+        // returned its error, therefore we need to set it up again.
+        let len_params = input_types.len();
+        let max_inout = len_params.max(output_types.len());
+        self.stack.values.reserve(max_inout)?;
+        self.stack.values.extend_zeros(max_inout);
+        let values = self.stack.values.as_slice_mut();
+        let offset = values.len() - max_inout;
+        let values = &mut values[offset..][..len_params];
+        for (value, param) in values.iter_mut().zip(params.call_params()) {
+            *value = param;
+        }
+        let result = self.dispatch_host_func(
+            ctx.as_context_mut(),
+            func_entity,
+            HostFuncCaller::reenter(results, instance, continuation),
+        );
+        result.map_err(|error| self.tag_host_error(&func_entity, *func, error, results, instance))
+    }
+
+    /// Tags the `error` returned by the host function `func` so that its invocation can be resumed.
+    ///
+    /// The parameters of the failed host function call are kept for inspection by the embedder.
+    ///
+    /// # Note
+    ///
+    /// - If `error` is a [`HostYield`] its yielded values must match the result types of `func`.
+    ///   Otherwise the mismatch is returned as Wasm error that cannot be resumed.
+    /// - If `error` is an [`ErrorKind::Exit`] or [`ErrorKind::HostTrap`] it is returned
+    ///   as Wasm error that cannot be resumed. This also applies if `error` wraps them.
+    #[cfg(feature = "resumable")]
+    fn tag_host_error(
+        &self,
+        entity: &HostFuncEntity,
+        func: Func,
+        error: HostCallError,
+        results: RegisterSpan,
+        instance: &Instance,
+    ) -> TaggedTrap {
+        let HostCallError { error, params } = error;
+        if let ErrorKind::Exit(_) | ErrorKind::HostTrap(_) = error.root().kind() {
+            return TaggedTrap::Wasm(error);
+        }
+        if let Some(host_yield) = error.downcast_ref::<HostYield>() {
+            if let Err(mismatch) = self
+                .res
+                .func_types
+                .resolve_func_type(entity.ty_dedup())
+                .match_results(host_yield.values(), true)
+            {
+                return TaggedTrap::Wasm(mismatch.into());
+            }
+        }
+        TaggedTrap::host(func, error, params, results, *instance)
+    }
+
     fn execute_host_func<T>(
         &mut self,
         ctx: &mut StoreContextMut<'_, T>,
         results: RegisterSpan,
         func: &Func,
         instance: &Instance,
-        call_kind: CallKind,
     ) -> Result<(), TaggedTrap> {
         let func_entity = match ctx.as_context().store.inner.resolve_func(func) {
             FuncEntity::Wasm(wasm_func) => {
@@ -344,23 +671,25 @@ impl<'engine> EngineExecutor<'engine> {
             func_entity,
             HostFuncCaller::wasm(results, instance),
         );
-        if matches!(call_kind, CallKind::Tail) {
-            self.stack.calls.pop();
-        }
+        // Note: For tail calls the caller call frame has already been popped
+        //       so that the results are returned to the caller's caller.
+        #[cfg(feature = "resumable")]
         if self.stack.calls.peek().is_some() {
             // Case: There is a frame on the call stack.
             //
             // This is the default case and we can easily make host function
             // errors return a resumable call handle.
-            result.map_err(|error| TaggedTrap::host(*func, error, results))?;
-        } else {
-            // Case: No frame is on the call stack. (edge case)
-            //
-            // This can happen if the host function was called by a tail call.
-            // In this case we treat host function errors the same as if we called
-            // the host function as root and do not allow to resume the call.
-            result.map_err(TaggedTrap::Wasm)?;
+            result.map_err(|error| {
+                self.tag_host_error(&func_entity, *func, error, results, instance)
+            })?;
+            return Ok(());
         }
+        // Case: No frame is on the call stack or calls are not resumable.
+        //
+        // The former can happen if the root function tail called the host function.
+        // In this case we treat host function errors the same as if we called
+        // the host function as root and do not allow to resume the call.
+        result.map_err(|error| TaggedTrap::Wasm(error.error))?;
         Ok(())
     }
 }
@@ -376,13 +705,35 @@ enum HostFuncCaller<'a> {
         results: RegisterSpan,
         /// The instance to be used throughout the host function call.
         instance: &'a Instance,
+        /// The continuation token if the host function is re-entered after it yielded.
+        #[cfg(feature = "resumable")]
+        continuation: Option<u64>,
     },
 }
 
 impl<'a> HostFuncCaller<'a> {
     /// Creates a [`HostFuncCaller::Wasm`].
     pub fn wasm(results: RegisterSpan, instance: &'a Instance) -> Self {
-        Self::Wasm { results, instance }
+        Self::Wasm {
+            results,
+            instance,
+            #[cfg(feature = "resumable")]
+            continuation: None,
+        }
+    }
+
+    /// Creates a [`HostFuncCaller::Wasm`] re-entering a host function with its `continuation` token.
+    #[cfg(feature = "resumable")]
+    pub fn reenter(
+        results: RegisterSpan,
+        instance: &'a Instance,
+        continuation: Option<u64>,
+    ) -> Self {
+        Self::Wasm {
+            results,
+            instance,
+            continuation,
+        }
     }
 
     /// Returns the [`RegisterSpan`] if `self` is a Wasm caller, otherwise returns `None`.
@@ -400,16 +751,44 @@ impl<'a> HostFuncCaller<'a> {
             HostFuncCaller::Wasm { instance, .. } => Some(instance),
         }
     }
+
+    /// Returns the continuation token of a re-entered host function if any.
+    #[cfg(feature = "resumable")]
+    pub fn continuation(&self) -> Option<u64> {
+        match *self {
+            HostFuncCaller::Root => None,
+            HostFuncCaller::Wasm { continuation, .. } => continuation,
+        }
+    }
+}
+
+/// An error returned by a host function together with the parameters it has been called with.
+#[derive(Debug)]
+struct HostCallError {
+    /// The error returned by the host function.
+    error: Error,
+    /// The parameters of the host function call.
+    ///
+    /// # Note
+    ///
+    /// This is empty if the host function has not been called by Wasm
+    /// since only those host function calls can be resumed.
+    #[cfg(feature = "resumable")]
+    params: Box<[Value]>,
 }
 
 impl<'engine> EngineExecutor<'engine> {
     /// Dispatches a host function call and returns its result.
+    ///
+    /// # Errors
+    ///
+    /// If the host function returned an error.
     fn dispatch_host_func<T>(
         &mut self,
-        ctx: StoreContextMut<T>,
+        mut ctx: StoreContextMut<T>,
         host_func: HostFuncEntity,
         caller: HostFuncCaller,
-    ) -> Result<(), Error> {
+    ) -> Result<(), HostCallError> {
         // The host function signature is required for properly
         // adjusting, inspecting and manipulating the value stack.
         let (input_types, output_types) = self
@@ -429,6 +808,7 @@ impl<'engine> EngineExecutor<'engine> {
             len_outputs,
         );
         // Now we are ready to perform the host function call.
+        ctx.store.inner.enter_host_call();
         // Note: We need to clone the host function due to some borrowing issues.
         //       This should not be a big deal since host functions usually are cheap to clone.
         let trampoline = ctx
@@ -436,25 +816,51 @@ impl<'engine> EngineExecutor<'engine> {
             .store
             .resolve_trampoline(host_func.trampoline())
             .clone();
-        trampoline
-            .call(ctx, caller.instance(), params_results)
-            .map_err(|error| {
-                // Note: We drop the values that have been temporarily added to
-                //       the stack to act as parameter and result buffer for the
-                //       called host function. Since the host function failed we
-                //       need to clean up the temporary buffer values here.
-                //       This is required for resumable calls to work properly.
-                self.stack.values.drop(max_inout);
-                error
-            })?;
+        // Note: The continuation token is only visible to the re-entered host function itself.
+        //       Therefore it is stacked so that host functions called by Wasm during the
+        //       re-entered host function, e.g. via nested Wasm calls, do not observe it.
+        #[cfg(feature = "resumable")]
+        let outer_continuation = ctx
+            .store
+            .inner
+            .replace_host_continuation(caller.continuation());
+        let result = trampoline.call(ctx.as_context_mut(), caller.instance(), params_results);
+        #[cfg(feature = "resumable")]
+        ctx.store
+            .inner
+            .replace_host_continuation(outer_continuation);
+        ctx.store.inner.exit_host_call();
+        result.map_err(|error| {
+            // Note: Host functions leave their parameters untouched upon failure
+            //       which allows us to keep them for resumable calls.
+            #[cfg(feature = "resumable")]
+            let params = match caller {
+                HostFuncCaller::Root => Box::default(),
+                HostFuncCaller::Wasm { .. } => {
+                    let values = self.stack.values.as_slice_mut();
+                    let params = &values[values.len() - max_inout..][..len_inputs];
+                    params
+                        .iter()
+                        .zip(input_types)
+                        .map(|(param, ty)| param.with_type(*ty))
+                        .collect()
+                }
+            };
+            // Note: We drop the values that have been temporarily added to
+            //       the stack to act as parameter and result buffer for the
+            //       called host function. Since the host function failed we
+            //       need to clean up the temporary buffer values here.
+            //       This is required for resumable calls to work properly.
+            self.stack.values.drop(max_inout);
+            HostCallError {
+                error,
+                #[cfg(feature = "resumable")]
+                params,
+            }
+        })?;
         if let Some(results) = caller.results() {
             // Now the results need to be written back to where the caller expects them.
-            let caller_offset = self
-                .stack
-                .calls
-                .peek()
-                .expect("caller must be on the stack")
-                .base_offset();
+            //
             // # Safety (1)
             //
             // We can safely acquire the stack pointer to the caller's and callee's (host)
@@ -463,7 +869,7 @@ impl<'engine> EngineExecutor<'engine> {
             // In the following we make sure to not access registers out of bounds of each
             // call frame since we rely on Wasm validation and proper Wasm translation to
             // provide us with valid result registers.
-            let mut caller_sp = unsafe { self.stack.values.stack_ptr_at(caller_offset) };
+            let mut caller_sp = self.caller_stack_ptr();
             // # Safety: See Safety (1) above.
             let callee_sp = unsafe { self.stack.values.stack_ptr_last_n(max_inout) };
             let results = results.iter(len_outputs);
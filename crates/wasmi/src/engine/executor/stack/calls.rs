@@ -1,6 +1,6 @@
 use super::{err_stack_overflow, BaseValueStackOffset, FrameValueStackOffset};
 use crate::{
-    engine::{bytecode::RegisterSpan, code_map::InstructionPtr},
+    engine::{bytecode::RegisterSpan, code_map::InstructionPtr, CompiledFunc},
     Instance,
 };
 use alloc::vec::Vec;
@@ -11,7 +11,6 @@ use crate::{
     engine::bytecode::Instruction,
     engine::bytecode::Register,
     engine::executor::stack::ValueStack,
-    engine::CompiledFunc,
     Global,
     Memory,
     Table,
@@ -28,6 +27,8 @@ pub struct CallStack {
     ///
     /// A [`TrapCode::StackOverflow`] is raised if the recursion limit is exceeded.
     recursion_limit: usize,
+    /// The maximum number of [`CallFrame`] on the [`CallStack`] since the last reset.
+    peak_len: usize,
 }
 
 impl CallStack {
@@ -39,6 +40,20 @@ impl CallStack {
         Self {
             calls: Vec::new(),
             recursion_limit,
+            peak_len: 0,
+        }
+    }
+
+    /// Creates a new [`CallStack`] using the given recursion limit that never reallocates.
+    ///
+    /// # Note
+    ///
+    /// This preallocates enough space for `recursion_limit` nested calls.
+    pub fn preallocated(recursion_limit: usize) -> Self {
+        Self {
+            calls: Vec::with_capacity(recursion_limit),
+            recursion_limit,
+            peak_len: 0,
         }
     }
 
@@ -52,6 +67,32 @@ impl CallStack {
     /// provide a clean slate for all executions.
     pub fn reset(&mut self) {
         self.calls.clear();
+        self.peak_len = 0;
+    }
+
+    /// Lowers the recursion limit of the [`CallStack`] to at most `limit`.
+    ///
+    /// Returns the previous recursion limit which can be restored via [`CallStack::set_recursion_limit`].
+    pub fn restrict_recursion_limit(&mut self, limit: usize) -> usize {
+        let previous = self.recursion_limit;
+        self.recursion_limit = previous.min(limit);
+        previous
+    }
+
+    /// Sets the recursion limit of the [`CallStack`] to `limit`.
+    ///
+    /// # Note
+    ///
+    /// This is used to restore the recursion limit that has been lowered
+    /// via [`CallStack::restrict_recursion_limit`].
+    pub fn set_recursion_limit(&mut self, limit: usize) {
+        debug_assert!(self.len() <= limit);
+        self.recursion_limit = limit;
+    }
+
+    /// Returns the maximum number of [`CallFrame`] on the [`CallStack`] since the last reset.
+    pub fn peak_len(&self) -> usize {
+        self.peak_len
     }
 
     /// Returns the number of [`CallFrame`] on the [`CallStack`].
@@ -70,10 +111,28 @@ impl CallStack {
         if self.len() == self.recursion_limit {
             return Err(err_stack_overflow());
         }
+        if self.len() == self.calls.capacity() {
+            self.grow();
+        }
         self.calls.push(call);
+        if self.len() > self.peak_len {
+            self.peak_len = self.len();
+        }
         Ok(())
     }
 
+    /// Grows the capacity of the [`CallStack`] without exceeding its recursion limit.
+    ///
+    /// # Note
+    ///
+    /// This roughly doubles the capacity and never allocates space
+    /// for more [`CallFrame`] than the recursion limit allows.
+    #[cold]
+    fn grow(&mut self) {
+        let additional = self.len().max(4).min(self.recursion_limit - self.len());
+        self.calls.reserve_exact(additional);
+    }
+
     /// Pops the last [`CallFrame`] from the [`CallStack`] if any.
     #[inline]
     pub fn pop(&mut self) -> Option<CallFrame> {
@@ -92,18 +151,39 @@ impl CallStack {
         self.calls.last_mut()
     }
 
-    /// Peeks the two top-most [`CallFrame`] on the [`CallStack`] if any.
+    /// Pops the top-most [`CallFrame`] and all callers returning together with it.
+    ///
+    /// Returns the outermost popped [`CallFrame`] if any.
     ///
     /// # Note
     ///
-    /// - The top-most [`CallFrame`] on the [`CallStack`] is referred to as the `callee`.
-    /// - The second top-most [`CallFrame`] on the [`CallStack`] is referred to as the `caller`.
+    /// Read [`CallFrame::forwarded`] for more information.
+    #[inline]
+    pub fn pop_returned(&mut self) -> Option<CallFrame> {
+        let returned = self.calls.pop()?;
+        let forwarded = returned.forwarded();
+        if forwarded == 0 {
+            return Some(returned);
+        }
+        let len = self.len() - forwarded;
+        let outermost = self.calls[len];
+        self.calls.truncate(len);
+        Some(outermost)
+    }
+
+    /// Peeks the top-most [`CallFrame`] and the [`CallFrame`] it returns to if any.
     ///
-    /// So this function returns a pair of `(callee, caller?)`.
-    pub fn peek_2(&self) -> Option<(&CallFrame, Option<&CallFrame>)> {
+    /// # Note
+    ///
+    /// The [`CallFrame`] returned to is the caller of the top-most [`CallFrame`]
+    /// unless its results are forwarded. Read [`CallFrame::forwarded`].
+    pub fn peek_returned(&self) -> Option<(&CallFrame, Option<&CallFrame>)> {
         let (callee, remaining) = self.calls.split_last()?;
-        let caller = remaining.last();
-        Some((callee, caller))
+        let target = remaining
+            .len()
+            .checked_sub(callee.forwarded() + 1)
+            .map(|index| &remaining[index]);
+        Some((callee, target))
     }
 }
 
@@ -118,6 +198,14 @@ pub struct CallFrame {
     frame_ptr: FrameValueStackOffset,
     /// Span of registers were the caller expects them in its [`CallFrame`].
     results: RegisterSpan,
+    /// The number of callers that return together with the [`CallFrame`].
+    ///
+    /// # Note
+    ///
+    /// This is non-zero if the callers return the results of the [`CallFrame`] via
+    /// [`Instruction::ReturnForward`]. In this case the `results` refer to the
+    /// [`CallFrame`] to which the outermost of those callers returns.
+    forwarded: u32,
     /// The instance in which the function has been defined.
     ///
     /// # Note
@@ -125,11 +213,16 @@ pub struct CallFrame {
     /// The [`Instance`] is used to inspect and manipulate data that is
     /// non-local to the function such as [`Memory`], [`Global`] and [`Table`].
     instance: Instance,
+    /// The called [`CompiledFunc`].
+    #[cfg(feature = "metrics")]
+    func: CompiledFunc,
 }
 
 impl CallFrame {
     /// Creates a new [`CallFrame`].
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
     pub fn new(
+        func: CompiledFunc,
         instr_ptr: InstructionPtr,
         frame_ptr: FrameValueStackOffset,
         base_ptr: BaseValueStackOffset,
@@ -141,10 +234,34 @@ impl CallFrame {
             base_ptr,
             frame_ptr,
             results,
+            forwarded: 0,
             instance,
+            #[cfg(feature = "metrics")]
+            func,
         }
     }
 
+    /// Forwards the results of the [`CallFrame`] to where its `caller` returns its results.
+    ///
+    /// # Note
+    ///
+    /// This is used if the `caller` returns the results of the [`CallFrame`]
+    /// via [`Instruction::ReturnForward`] right after the call.
+    pub fn forward_to(&mut self, caller: &CallFrame) {
+        self.results = caller.results;
+        self.forwarded = caller.forwarded + 1;
+    }
+
+    /// Takes over the results of the `caller` that is replaced by the [`CallFrame`].
+    ///
+    /// # Note
+    ///
+    /// This is used for the implementation of tail calls.
+    pub fn take_over_results_of(&mut self, caller: &CallFrame) {
+        self.results = caller.results;
+        self.forwarded = caller.forwarded;
+    }
+
     /// Moves the [`ValueStack`] offsets of the [`CallFrame`] down by `delta`.
     ///
     /// # Note
@@ -188,13 +305,27 @@ impl CallFrame {
     /// # Note
     ///
     /// The registers yielded by the returned [`RegisterSpan`]
-    /// refer to the [`CallFrame`] of the caller of this [`CallFrame`].
+    /// refer to the [`CallFrame`] of the caller of this [`CallFrame`]
+    /// unless its results are forwarded. Read [`CallFrame::forwarded`].
     pub fn results(&self) -> RegisterSpan {
         self.results
     }
 
+    /// Returns the number of callers that return together with the [`CallFrame`].
+    ///
+    /// Read [`CallFrame::forward_to`] for more information.
+    pub fn forwarded(&self) -> usize {
+        self.forwarded as usize
+    }
+
     /// Returns the [`Instance`] of the [`CallFrame`].
     pub fn instance(&self) -> &Instance {
         &self.instance
     }
+
+    /// Returns the called [`CompiledFunc`] of the [`CallFrame`].
+    #[cfg(feature = "metrics")]
+    pub fn func(&self) -> CompiledFunc {
+        self.func
+    }
 }
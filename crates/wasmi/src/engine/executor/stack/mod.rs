@@ -5,7 +5,13 @@ pub use self::{
     calls::{CallFrame, CallStack},
     values::{BaseValueStackOffset, FrameRegisters, FrameValueStackOffset, ValueStack},
 };
-use crate::{core::TrapCode, StackLimits};
+use crate::{
+    core::{TrapCode, UntypedValue},
+    CallLimits,
+    StackLimits,
+    StackUsage,
+};
+use core::mem;
 
 /// Returns a [`TrapCode`] signalling a stack overflow.
 #[cold]
@@ -13,6 +19,15 @@ fn err_stack_overflow() -> TrapCode {
     TrapCode::StackOverflow
 }
 
+/// The limits of a [`Stack`] before they have been lowered via [`Stack::restrict_limits`].
+#[derive(Debug, Copy, Clone)]
+pub struct PreviousLimits {
+    /// The previous recursion limit of the [`CallStack`].
+    recursion_limit: usize,
+    /// The previous maximum length of the [`ValueStack`].
+    max_values: usize,
+}
+
 /// Data structure that combines both value stack and call stack.
 #[derive(Debug, Default)]
 pub struct Stack {
@@ -37,8 +52,15 @@ impl Stack {
     /// Creates a new [`Stack`] given the [`Config`].
     ///
     /// [`Config`]: [`crate::Config`]
+    ///
+    /// # Note
+    ///
+    /// Both stacks are preallocated entirely if the [`StackLimits`] are fixed.
     pub fn new(limits: StackLimits) -> Self {
-        let calls = CallStack::new(limits.maximum_recursion_depth);
+        let calls = match limits.is_fixed() {
+            true => CallStack::preallocated(limits.maximum_recursion_depth),
+            false => CallStack::new(limits.maximum_recursion_depth),
+        };
         let values = ValueStack::new(
             limits.initial_value_stack_height,
             limits.maximum_value_stack_height,
@@ -52,6 +74,35 @@ impl Stack {
         self.calls.reset();
     }
 
+    /// Restricts the limits of the [`Stack`] to the given [`CallLimits`].
+    ///
+    /// Returns the previous limits which must be restored via [`Stack::restore_limits`]
+    /// before the [`Stack`] is reused for another execution.
+    pub fn restrict_limits(&mut self, limits: CallLimits) -> PreviousLimits {
+        PreviousLimits {
+            recursion_limit: self
+                .calls
+                .restrict_recursion_limit(limits.max_additional_frames),
+            max_values: self
+                .values
+                .restrict_max_len(limits.max_additional_value_stack),
+        }
+    }
+
+    /// Restores the `previous` limits of the [`Stack`] lowered via [`Stack::restrict_limits`].
+    pub fn restore_limits(&mut self, previous: PreviousLimits) {
+        self.calls.set_recursion_limit(previous.recursion_limit);
+        self.values.set_max_len(previous.max_values);
+    }
+
+    /// Returns the peak [`StackUsage`] of the [`Stack`] since the last reset.
+    pub fn usage(&self) -> StackUsage {
+        StackUsage {
+            peak_value_stack_bytes: self.values.peak_len() * mem::size_of::<UntypedValue>(),
+            peak_call_depth: self.calls.peak_len(),
+        }
+    }
+
     /// Create an empty [`Stack`].
     ///
     /// # Note
@@ -94,6 +145,7 @@ impl Stack {
         let caller = call_stack.pop().expect("caller call frame must exist");
         debug_assert_eq!(callee.results(), caller.results());
         debug_assert!(caller.base_offset() <= callee.base_offset());
+        callee.take_over_results_of(&caller);
         // Safety:
         //
         // We only drain cells of the second top-most call frame on the value stack.
@@ -1,10 +1,10 @@
 use super::err_stack_overflow;
 use crate::{
     core::UntypedValue,
-    engine::{bytecode::Register, CompiledFuncEntity},
+    engine::{bytecode::Register, CompiledFuncEntity, RegisterReader},
 };
 use alloc::vec::Vec;
-use core::{fmt, fmt::Debug, iter, mem, ptr};
+use core::{fmt, fmt::Debug, mem, ptr};
 use wasmi_core::TrapCode;
 
 #[cfg(doc)]
@@ -19,6 +19,8 @@ pub struct ValueStack {
     sp: usize,
     /// Maximal possible `sp` value.
     max_sp: usize,
+    /// Maximal reserved `sp` value since the last reset.
+    peak_sp: usize,
 }
 
 impl ValueStack {
@@ -34,6 +36,7 @@ impl Debug for ValueStack {
         f.debug_struct("ValueStack")
             .field("sp", &self.sp)
             .field("max_sp", &self.max_sp)
+            .field("peak_sp", &self.peak_sp)
             .field("entries", &&self.values[..self.sp])
             .finish()
     }
@@ -79,6 +82,7 @@ impl ValueStack {
             values: vec![UntypedValue::default(); initial_len],
             sp: 0,
             max_sp: maximum_len,
+            peak_sp: 0,
         }
     }
 
@@ -93,6 +97,7 @@ impl ValueStack {
             values: Vec::new(),
             sp: 0,
             max_sp: 0,
+            peak_sp: 0,
         }
     }
 
@@ -106,17 +111,60 @@ impl ValueStack {
     /// provide a clean slate for all executions.
     pub fn reset(&mut self) {
         self.sp = 0;
+        self.peak_sp = 0;
+    }
+
+    /// Lowers the maximum length of the [`ValueStack`] to at most `max_len`.
+    ///
+    /// Returns the previous maximum length which can be restored via [`ValueStack::set_max_len`].
+    pub fn restrict_max_len(&mut self, max_len: usize) -> usize {
+        let previous = self.max_sp;
+        self.max_sp = previous.min(max_len);
+        previous
+    }
+
+    /// Sets the maximum length of the [`ValueStack`] to `max_len`.
+    ///
+    /// # Note
+    ///
+    /// This is used to restore the maximum length that has been lowered
+    /// via [`ValueStack::restrict_max_len`].
+    pub fn set_max_len(&mut self, max_len: usize) {
+        debug_assert!(self.sp <= max_len);
+        self.max_sp = max_len;
+    }
+
+    /// Returns the maximum number of reserved cells on the [`ValueStack`] since the last reset.
+    pub fn peak_len(&self) -> usize {
+        self.peak_sp
     }
 
     /// Returns the root [`FrameRegisters`] pointing to the first value on the [`ValueStack`].
     pub fn root_stack_ptr(&mut self) -> FrameRegisters {
-        FrameRegisters::new(self.values.as_mut_ptr())
+        FrameRegisters::new(self.values.as_mut_ptr(), &mut self.values[..])
     }
 
     /// Returns the [`FrameRegisters`] at the given `offset`.
     pub unsafe fn stack_ptr_at(&mut self, offset: impl Into<ValueStackOffset>) -> FrameRegisters {
         let ptr = self.values.as_mut_ptr().add(offset.into().0);
-        FrameRegisters::new(ptr)
+        FrameRegisters::new(ptr, &mut self.values[..])
+    }
+
+    /// Returns a [`FrameRegistersReader`] for the [`CallFrame`] registers at `sp`.
+    ///
+    /// # Panics
+    ///
+    /// If `sp` does not point into this [`ValueStack`].
+    pub fn frame_reader(&self, sp: &FrameRegisters) -> FrameRegistersReader<'_> {
+        let base = (sp.ptr as usize)
+            .checked_sub(self.values.as_ptr() as usize)
+            .map(|offset| offset / mem::size_of::<UntypedValue>())
+            .filter(|&base| base <= self.values.len())
+            .unwrap_or_else(|| panic!("frame registers do not point into the value stack"));
+        FrameRegistersReader {
+            values: &self.values[..],
+            base,
+        }
     }
 
     /// Returns the [`FrameRegisters`] at the given `offset` from the back.
@@ -159,12 +207,19 @@ impl ValueStack {
             .checked_add(additional)
             .filter(|&new_len| new_len <= self.max_sp)
             .ok_or_else(err_stack_overflow)?;
+        if new_len > self.peak_sp {
+            self.peak_sp = new_len;
+        }
         if new_len > self.capacity() {
             // Note: By extending with the new length we effectively double
             // the current value stack length and add the additional flat amount
             // on top. This avoids too many frequent reallocations.
-            self.values
-                .extend(iter::repeat(UntypedValue::default()).take(new_len));
+            //
+            // The growth is capped at the maximum size limit so that the
+            // value stack never allocates more memory than it may use.
+            let capacity = self.capacity().saturating_add(new_len).min(self.max_sp);
+            self.values.reserve_exact(capacity - self.capacity());
+            self.values.resize(capacity, UntypedValue::default());
         }
         Ok(())
     }
@@ -395,6 +450,9 @@ impl From<BaseValueStackOffset> for usize {
 pub struct FrameRegisters {
     /// The underlying raw pointer to a [`CallFrame`] on the [`ValueStack`].
     ptr: *mut UntypedValue,
+    /// The range of all values of the [`ValueStack`] that may be accessed.
+    #[cfg(feature = "checked-executor")]
+    bounds: core::ops::Range<*mut UntypedValue>,
 }
 
 impl Debug for FrameRegisters {
@@ -404,9 +462,19 @@ impl Debug for FrameRegisters {
 }
 
 impl FrameRegisters {
-    /// Creates a new [`FrameRegisters`].
-    fn new(ptr: *mut UntypedValue) -> Self {
-        Self { ptr }
+    /// Creates a new [`FrameRegisters`] at `ptr` pointing into the `values` of a [`ValueStack`].
+    #[cfg_attr(not(feature = "checked-executor"), allow(unused_variables))]
+    fn new(ptr: *mut UntypedValue, values: &mut [UntypedValue]) -> Self {
+        Self {
+            ptr,
+            #[cfg(feature = "checked-executor")]
+            bounds: values.as_mut_ptr_range(),
+        }
+    }
+
+    /// Returns the underlying raw pointer to the value at [`Register`] index 0.
+    pub fn as_mut_ptr(&mut self) -> *mut UntypedValue {
+        self.ptr
     }
 
     /// Returns the [`UntypedValue`] at the given [`Register`].
@@ -430,7 +498,50 @@ impl FrameRegisters {
     }
 
     /// Returns the underlying pointer offset by the [`Register`] index.
+    #[cfg(not(feature = "checked-executor"))]
     unsafe fn register_offset(&self, register: Register) -> *mut UntypedValue {
         unsafe { self.ptr.offset(register.to_i16() as isize) }
     }
+
+    /// Returns the underlying pointer offset by the [`Register`] index.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Register`] accesses the underlying [`ValueStack`] out of bounds.
+    #[cfg(feature = "checked-executor")]
+    unsafe fn register_offset(&self, register: Register) -> *mut UntypedValue {
+        let ptr = self.ptr.wrapping_offset(isize::from(register.to_i16()));
+        assert!(
+            self.bounds.contains(&ptr),
+            "register {register:?} accesses the value stack out of bounds"
+        );
+        ptr
+    }
+}
+
+/// Bounds checked read-only accessor to the [`Register`] values of a [`CallFrame`].
+///
+/// # Note
+///
+/// This is handed to user provided [`ExecutionDigest::Custom`] digest functions
+/// which may read arbitrary [`Register`] values.
+///
+/// [`ExecutionDigest::Custom`]: crate::ExecutionDigest::Custom
+#[derive(Debug)]
+pub struct FrameRegistersReader<'a> {
+    /// All values of the [`ValueStack`].
+    values: &'a [UntypedValue],
+    /// The index of the [`CallFrame`] registers within `values`.
+    base: usize,
+}
+
+impl RegisterReader for FrameRegistersReader<'_> {
+    fn read_register(&self, register: Register) -> UntypedValue {
+        let offset = isize::from(register.to_i16());
+        self.base
+            .checked_add_signed(offset)
+            .and_then(|index| self.values.get(index))
+            .copied()
+            .unwrap_or_default()
+    }
 }
@@ -1,4 +1,8 @@
-use crate::{core::TrapCode, engine::bytecode::RegisterSpan, Error, Func};
+use crate::{core::TrapCode, Error};
+#[cfg(feature = "resumable")]
+use crate::{engine::bytecode::RegisterSpan, Func, Instance, Value};
+#[cfg(feature = "resumable")]
+use alloc::boxed::Box;
 
 /// Either a Wasm trap or a host trap with its originating host [`Func`].
 #[derive(Debug)]
@@ -6,20 +10,32 @@ pub enum TaggedTrap {
     /// The trap is originating from Wasm.
     Wasm(Error),
     /// The trap is originating from a host function.
+    #[cfg(feature = "resumable")]
     Host {
         host_error: Error,
         host_func: Func,
+        host_params: Box<[Value]>,
         caller_results: RegisterSpan,
+        caller_instance: Instance,
     },
 }
 
 impl TaggedTrap {
     /// Creates a [`TaggedTrap`] from a host error.
-    pub fn host(host_func: Func, host_error: Error, caller_results: RegisterSpan) -> Self {
+    #[cfg(feature = "resumable")]
+    pub fn host(
+        host_func: Func,
+        host_error: Error,
+        host_params: Box<[Value]>,
+        caller_results: RegisterSpan,
+        caller_instance: Instance,
+    ) -> Self {
         Self::Host {
             host_func,
             host_error,
+            host_params,
             caller_results,
+            caller_instance,
         }
     }
 
@@ -27,6 +43,7 @@ impl TaggedTrap {
     pub fn into_error(self) -> Error {
         match self {
             TaggedTrap::Wasm(error) => error,
+            #[cfg(feature = "resumable")]
             TaggedTrap::Host { host_error, .. } => host_error,
         }
     }
@@ -0,0 +1,122 @@
+//! Data structures to reprice the fuel of already translated functions.
+//!
+//! # Note
+//!
+//! The translator records the [`FuelUsage`] charged to every [`Instruction::ConsumeFuel`]
+//! in a [`FuelBreakdown`] so that its block fuel can be recomputed for other [`FuelCosts`]
+//! without translating the function again. Read [`Engine::reprice_fuel`] for more information.
+//!
+//! [`Instruction::ConsumeFuel`]: crate::engine::bytecode::Instruction::ConsumeFuel
+//! [`Engine::reprice_fuel`]: crate::Engine::reprice_fuel
+
+use super::{bytecode::BlockFuel, FuelCosts};
+use crate::Error;
+use alloc::vec::Vec;
+
+/// A class of fuel charged for a translated Wasmi bytecode instruction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FuelUsage {
+    /// Charges [`FuelCosts::base`].
+    Base,
+    /// Charges [`FuelCosts::entity`].
+    Entity,
+    /// Charges [`FuelCosts::load`].
+    Load,
+    /// Charges [`FuelCosts::store`].
+    Store,
+    /// Charges [`FuelCosts::call`].
+    Call,
+    /// Charges [`FuelCosts::fuel_for_copies`] for the given number of register copies.
+    Copies(u64),
+}
+
+impl FuelUsage {
+    /// Returns the fuel charged by the [`FuelUsage`] for the given [`FuelCosts`].
+    pub fn fuel(self, costs: &FuelCosts) -> u64 {
+        match self {
+            Self::Base => costs.base(),
+            Self::Entity => costs.entity(),
+            Self::Load => costs.load(),
+            Self::Store => costs.store(),
+            Self::Call => costs.call(),
+            Self::Copies(len_copies) => costs.fuel_for_copies(len_copies),
+        }
+    }
+}
+
+/// The [`FuelUsage`] charged to a single [`Instruction::ConsumeFuel`].
+///
+/// [`Instruction::ConsumeFuel`]: crate::engine::bytecode::Instruction::ConsumeFuel
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FuelBreakdown {
+    /// The number of [`FuelUsage::Base`] charges.
+    base: u32,
+    /// The number of [`FuelUsage::Entity`] charges.
+    entity: u32,
+    /// The number of [`FuelUsage::Load`] charges.
+    load: u32,
+    /// The number of [`FuelUsage::Store`] charges.
+    store: u32,
+    /// The number of [`FuelUsage::Call`] charges.
+    call: u32,
+    /// The number of register copies of all [`FuelUsage::Copies`] charges.
+    ///
+    /// # Note
+    ///
+    /// Those are stored individually since [`FuelCosts::fuel_for_copies`]
+    /// rounds down the fuel charged for every [`FuelUsage::Copies`].
+    copies: Vec<u64>,
+}
+
+impl From<FuelUsage> for FuelBreakdown {
+    fn from(usage: FuelUsage) -> Self {
+        let mut breakdown = Self::default();
+        breakdown.record(usage);
+        breakdown
+    }
+}
+
+impl FuelBreakdown {
+    /// Records a [`FuelUsage`] charge.
+    pub fn record(&mut self, usage: FuelUsage) {
+        match usage {
+            FuelUsage::Base => self.base += 1,
+            FuelUsage::Entity => self.entity += 1,
+            FuelUsage::Load => self.load += 1,
+            FuelUsage::Store => self.store += 1,
+            FuelUsage::Call => self.call += 1,
+            FuelUsage::Copies(len_copies) => self.copies.push(len_copies),
+        }
+    }
+
+    /// Returns the [`BlockFuel`] of all recorded charges for the given [`FuelCosts`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`BlockFuel`] is out of bounds.
+    pub fn block_fuel(&self, costs: &FuelCosts) -> Result<BlockFuel, Error> {
+        let mut block_fuel = BlockFuel::try_from(0)?;
+        for (count, fuel) in [
+            (self.base, costs.base()),
+            (self.entity, costs.entity()),
+            (self.load, costs.load()),
+            (self.store, costs.store()),
+            (self.call, costs.call()),
+        ] {
+            block_fuel.bump_by(u64::from(count).saturating_mul(fuel))?;
+        }
+        for &len_copies in &self.copies {
+            block_fuel.bump_by(costs.fuel_for_copies(len_copies))?;
+        }
+        Ok(block_fuel)
+    }
+    /// Returns the [`BlockFuel`] of all recorded charges for the given [`FuelCosts`].
+    ///
+    /// Returns the maximum [`BlockFuel`] if it is out of bounds.
+    pub fn block_fuel_saturating(&self, costs: &FuelCosts) -> BlockFuel {
+        self.block_fuel(costs).unwrap_or_else(|_| {
+            BlockFuel::try_from(u64::from(u32::MAX))
+                .unwrap_or_else(|_| unreachable!("u32::MAX is a valid block fuel"))
+        })
+    }
+}
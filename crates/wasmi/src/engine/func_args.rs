@@ -78,7 +78,7 @@ impl<'a> FuncParams<'a> {
     ///
     /// If the length of hte `params_results` slice does not match the maximum
     /// of the `len_params` and `Len_results`.
-    pub(super) fn new(
+    pub(crate) fn new(
         params_results: &'a mut [UntypedValue],
         len_params: usize,
         len_results: usize,
@@ -128,6 +128,16 @@ impl<'a> FuncParams<'a> {
         Ok(results)
     }
 
+    /// Consumes `self` to return the raw buffer of the parameters and results.
+    ///
+    /// # Note
+    ///
+    /// The buffer holds the parameters before and is expected to hold
+    /// the results after the host function invocation.
+    pub(crate) fn into_raw(self) -> (&'a mut [UntypedValue], FuncFinished) {
+        (self.params_results, FuncFinished {})
+    }
+
     /// Consumes `self` to return the [`FuncResults`] out of it.
     fn into_func_results(self) -> FuncResults<'a> {
         FuncResults::new(&mut self.params_results[..self.len_results])
@@ -48,6 +48,11 @@ impl DedupFuncType {
     pub(super) fn into_inner(self) -> GuardedEntity<EngineIdx, DedupFuncTypeIdx> {
         self.0
     }
+
+    /// Returns `true` if the [`DedupFuncType`] is owned by the engine at `engine_idx`.
+    pub(crate) fn is_owned_by(&self, engine_idx: EngineIdx) -> bool {
+        self.0.entity_index(engine_idx).is_some()
+    }
 }
 
 /// A [`FuncType`] registry that efficiently deduplicate stored function types.
@@ -60,6 +65,12 @@ impl DedupFuncType {
 /// deduplicated [`FuncType`] instances is as fast as comparing integer values.
 /// Also with respect to Wasmi bytecode deduplicated [`FuncType`] entities
 /// require a lot less space to be stored.
+///
+/// # Note
+///
+/// Indices are assigned to function types in the order in which they are first
+/// allocated and do not depend on any hasher seeds. Therefore the same sequence
+/// of allocations always yields the same indices in a fresh registry.
 #[derive(Debug)]
 pub struct FuncTypeRegistry {
     /// A unique identifier for the associated engine.
@@ -86,6 +97,11 @@ impl FuncTypeRegistry {
         }
     }
 
+    /// Returns the [`EngineIdx`] of the associated engine.
+    pub(crate) fn engine_idx(&self) -> EngineIdx {
+        self.engine_idx
+    }
+
     /// Unpacks the entity and checks if it is owned by the engine.
     ///
     /// # Panics
@@ -103,6 +119,11 @@ impl FuncTypeRegistry {
         })
     }
 
+    /// Returns the number of deduplicated function types in the registry.
+    pub(crate) fn len(&self) -> usize {
+        self.func_types.len()
+    }
+
     /// Allocates a new function type to the engine.
     pub(crate) fn alloc_func_type(&mut self, func_type: FuncType) -> DedupFuncType {
         DedupFuncType::from_inner(Guarded::new(
@@ -111,6 +132,15 @@ impl FuncTypeRegistry {
         ))
     }
 
+    /// Returns the index of the deduplicated function type within the registry.
+    ///
+    /// # Panics
+    ///
+    /// If the deduplicated function type is not owned by the engine.
+    pub(crate) fn func_type_index(&self, func_type: &DedupFuncType) -> u32 {
+        self.unwrap_index(func_type.into_inner()).0
+    }
+
     /// Resolves a deduplicated function type into a [`FuncType`] entity.
     ///
     /// # Panics
@@ -0,0 +1,122 @@
+use super::bytecode::{Instruction, Register};
+use crate::{
+    core::{TrapCode, UntypedValue, ValueType},
+    FuncType,
+};
+
+/// The reserved module name under which Wasmi intrinsic functions are imported.
+pub const INTRINSICS_MODULE: &str = "wasmi_intrinsics";
+
+/// The reserved module name under which Wasmi builtin functions are imported.
+pub const BUILTINS_MODULE: &str = "wasmi";
+
+macro_rules! define_intrinsics {
+    (
+        $(
+            $( #[doc = $doc:literal] )*
+            $name:ident($value_type:ident, $field:literal, $checked_op:ident, $make_instr:ident)
+        ),* $(,)?
+    ) => {
+        /// An intrinsic function provided by Wasmi under the [`INTRINSICS_MODULE`] namespace.
+        ///
+        /// Calls to imported intrinsic functions are translated to dedicated
+        /// Wasmi [`Instruction`] instead of host function calls if the imported
+        /// function signature matches the signature of the intrinsic.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+        pub enum Intrinsic {
+            $(
+                $( #[doc = $doc] )*
+                $name,
+            )*
+        }
+
+        impl Intrinsic {
+            /// All Wasmi intrinsic functions.
+            pub const ALL: &'static [Self] = &[$( Self::$name ),*];
+
+            /// Returns the name of the [`Intrinsic`] within the [`INTRINSICS_MODULE`] namespace.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $( Self::$name => $field, )*
+                }
+            }
+
+            /// Returns the [`ValueType`] of the parameters and result of the [`Intrinsic`].
+            fn value_type(self) -> ValueType {
+                match self {
+                    $( Self::$name => ValueType::$value_type, )*
+                }
+            }
+
+            /// Evaluates the [`Intrinsic`] for the given `lhs` and `rhs` operands.
+            ///
+            /// # Errors
+            ///
+            /// If the evaluation overflows.
+            pub fn eval(self, lhs: UntypedValue, rhs: UntypedValue) -> Result<UntypedValue, TrapCode> {
+                match self {
+                    $( Self::$name => $checked_op(lhs, rhs), )*
+                }
+            }
+
+            /// Returns the constructor of the Wasmi [`Instruction`] substituting calls to the [`Intrinsic`].
+            pub fn make_instr(self) -> fn(result: Register, lhs: Register, rhs: Register) -> Instruction {
+                match self {
+                    $( Self::$name => Instruction::$make_instr, )*
+                }
+            }
+        }
+    };
+}
+define_intrinsics! {
+    /// `i32.checked_add`: signed `i32` addition trapping on overflow.
+    I32CheckedAdd(I32, "i32.checked_add", i32_checked_add, i32_checked_add),
+    /// `i32.checked_sub`: signed `i32` subtraction trapping on overflow.
+    I32CheckedSub(I32, "i32.checked_sub", i32_checked_sub, i32_checked_sub),
+    /// `i32.checked_mul`: signed `i32` multiplication trapping on overflow.
+    I32CheckedMul(I32, "i32.checked_mul", i32_checked_mul, i32_checked_mul),
+    /// `i64.checked_add`: signed `i64` addition trapping on overflow.
+    I64CheckedAdd(I64, "i64.checked_add", i64_checked_add, i64_checked_add),
+    /// `i64.checked_sub`: signed `i64` subtraction trapping on overflow.
+    I64CheckedSub(I64, "i64.checked_sub", i64_checked_sub, i64_checked_sub),
+    /// `i64.checked_mul`: signed `i64` multiplication trapping on overflow.
+    I64CheckedMul(I64, "i64.checked_mul", i64_checked_mul, i64_checked_mul),
+}
+
+impl Intrinsic {
+    /// Returns the [`Intrinsic`] with the given `name` within the [`INTRINSICS_MODULE`] namespace.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|intrinsic| intrinsic.name() == name)
+    }
+
+    /// Returns the [`FuncType`] of the [`Intrinsic`].
+    pub fn func_type(self) -> FuncType {
+        let ty = self.value_type();
+        FuncType::new([ty, ty], [ty])
+    }
+}
+
+macro_rules! impl_checked_ops {
+    ( $( fn $name:ident($ty:ty) = $op:ident; )* ) => {
+        $(
+            #[doc = concat!("Executes `", stringify!($ty), "::", stringify!($op), "` and traps on overflow.")]
+            pub fn $name(lhs: UntypedValue, rhs: UntypedValue) -> Result<UntypedValue, TrapCode> {
+                <$ty>::from(lhs)
+                    .$op(<$ty>::from(rhs))
+                    .map(UntypedValue::from)
+                    .ok_or(TrapCode::IntegerOverflow)
+            }
+        )*
+    };
+}
+impl_checked_ops! {
+    fn i32_checked_add(i32) = checked_add;
+    fn i32_checked_sub(i32) = checked_sub;
+    fn i32_checked_mul(i32) = checked_mul;
+    fn i64_checked_add(i64) = checked_add;
+    fn i64_checked_sub(i64) = checked_sub;
+    fn i64_checked_mul(i64) = checked_mul;
+}
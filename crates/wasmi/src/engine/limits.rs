@@ -1,3 +1,4 @@
+use super::TranslationError;
 use crate::core::UntypedValue;
 use core::{
     fmt::{self, Display},
@@ -14,7 +15,7 @@ const DEFAULT_MAX_VALUE_STACK_HEIGHT: usize = 1024 * DEFAULT_MIN_VALUE_STACK_HEI
 const DEFAULT_MAX_RECURSION_DEPTH: usize = 1024;
 
 /// The configured limits of the Wasm stack.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct StackLimits {
     /// The initial value stack height that the Wasm stack prepares.
     pub initial_value_stack_height: usize,
@@ -24,6 +25,44 @@ pub struct StackLimits {
     pub maximum_recursion_depth: usize,
 }
 
+/// The peak usage of the Wasm stack.
+///
+/// Returned by [`Store::stack_usage`] and [`Error::stack_usage`].
+///
+/// [`Store::stack_usage`]: crate::Store::stack_usage
+/// [`Error::stack_usage`]: crate::Error::stack_usage
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct StackUsage {
+    /// The maximum number of bytes in use by the value stack.
+    pub peak_value_stack_bytes: usize,
+    /// The maximum number of nested Wasm calls.
+    pub peak_call_depth: usize,
+}
+
+impl StackUsage {
+    /// Merges `other` into `self` keeping the respective maximum usages.
+    pub(crate) fn merge(&mut self, other: StackUsage) {
+        self.peak_value_stack_bytes = self
+            .peak_value_stack_bytes
+            .max(other.peak_value_stack_bytes);
+        self.peak_call_depth = self.peak_call_depth.max(other.peak_call_depth);
+    }
+}
+
+/// The limits of the Wasm stack usage of a single call on top of the usage of its callers.
+///
+/// Used via [`Func::call_with_limits`] in order to restrict re-entrant calls, such as
+/// untrusted callbacks called by host functions, more than the configured [`StackLimits`].
+///
+/// [`Func::call_with_limits`]: crate::Func::call_with_limits
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CallLimits {
+    /// The maximum number of nested calls including the called function itself.
+    pub max_additional_frames: usize,
+    /// The maximum value stack height in use by the call.
+    pub max_additional_value_stack: usize,
+}
+
 /// An error that may occur when configuring [`StackLimits`].
 #[derive(Debug)]
 pub enum LimitsError {
@@ -61,6 +100,33 @@ impl StackLimits {
             maximum_recursion_depth,
         })
     }
+
+    /// Creates a new fixed-capacity [`StackLimits`] configuration.
+    ///
+    /// The value stack starts out with its maximum height of `value_stack_height`
+    /// cells and the call stack preallocates `maximum_recursion_depth` frames.
+    /// This way Wasm execution never reallocates its stacks and instead fails
+    /// with a [`TrapCode::StackOverflow`] when exceeding either of them.
+    ///
+    /// # Panics
+    ///
+    /// Upon creating the Wasm stacks if `value_stack_height` is zero.
+    ///
+    /// [`TrapCode::StackOverflow`]: crate::core::TrapCode::StackOverflow
+    pub fn fixed(value_stack_height: usize, maximum_recursion_depth: usize) -> Self {
+        Self {
+            initial_value_stack_height: value_stack_height,
+            maximum_value_stack_height: value_stack_height,
+            maximum_recursion_depth,
+        }
+    }
+
+    /// Returns `true` if the [`StackLimits`] describe fixed-capacity stacks.
+    ///
+    /// This is the case if the initial and maximum value stack heights are equal.
+    pub fn is_fixed(&self) -> bool {
+        self.initial_value_stack_height == self.maximum_value_stack_height
+    }
 }
 
 impl Default for StackLimits {
@@ -75,3 +141,175 @@ impl Default for StackLimits {
         }
     }
 }
+
+/// Default value for the maximum number of entries in the sections of a Wasm module.
+const DEFAULT_MAX_SECTION_ENTRIES: u32 = 1_000_000;
+
+/// Default value for the maximum number of imports of a Wasm module.
+const DEFAULT_MAX_IMPORTS: u32 = 100_000;
+
+/// Default value for the maximum number of tables or linear memories of a Wasm module.
+const DEFAULT_MAX_TABLES_OR_MEMORIES: u32 = 100;
+
+/// Default value for the maximum number of element or data segments of a Wasm module.
+const DEFAULT_MAX_SEGMENTS: u32 = 100_000;
+
+/// Default value for the maximum number of targets of a Wasm `br_table` operator.
+const DEFAULT_MAX_BR_TABLE_TARGETS: u32 = 128 * 1024;
+
+/// Default value for the maximum number of local variables of a Wasm function.
+const DEFAULT_MAX_LOCALS: u32 = 50_000;
+
+/// Default value for the maximum size of a Wasm function body in bytes.
+const DEFAULT_MAX_FUNCTION_BODY_BYTES: u32 = 8 * 1024 * 1024;
+
+/// Default value for the maximum length of an import or export name in bytes.
+const DEFAULT_MAX_NAME_LENGTH: u32 = 64 * 1024;
+
+/// The configured limits on the complexity of parsed and translated Wasm modules.
+///
+/// Each limit is checked before Wasmi allocates resources for the respective entities
+/// so that untrusted Wasm modules cannot blow up parsing or translation time and memory.
+/// Exceeding a limit results in a [`TranslationError::LimitExceeded`] error.
+///
+/// # Note
+///
+/// - Except for [`ModuleLimits::max_imports`] the limits on module entities
+///   only count entities defined by the Wasm module and exclude imported ones.
+/// - With lazy translation the function local limits are checked when
+///   the function is translated.
+/// - The default limits are finite but generous enough for real world Wasm modules.
+///
+/// [`TranslationError::LimitExceeded`]: crate::engine::TranslationError::LimitExceeded
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ModuleLimits {
+    /// The maximum number of function types of a Wasm module.
+    pub max_types: u32,
+    /// The maximum number of functions defined by a Wasm module.
+    pub max_functions: u32,
+    /// The maximum number of imports of a Wasm module.
+    pub max_imports: u32,
+    /// The maximum number of global variables defined by a Wasm module.
+    pub max_globals: u32,
+    /// The maximum number of tables defined by a Wasm module.
+    pub max_tables: u32,
+    /// The maximum number of linear memories defined by a Wasm module.
+    pub max_memories: u32,
+    /// The maximum number of element segments of a Wasm module.
+    pub max_element_segments: u32,
+    /// The maximum number of data segments of a Wasm module.
+    pub max_data_segments: u32,
+    /// The maximum number of targets of a single Wasm `br_table` operator excluding its default target.
+    pub max_br_table_targets: u32,
+    /// The maximum number of local variables of a Wasm function excluding its parameters.
+    pub max_locals: u32,
+    /// The maximum size of a Wasm function body in bytes including its local variable declarations.
+    pub max_function_body_bytes: u32,
+    /// The maximum length in bytes of a single import module, import field or export name.
+    pub max_name_length: u32,
+}
+
+impl Default for ModuleLimits {
+    fn default() -> Self {
+        Self {
+            max_types: DEFAULT_MAX_SECTION_ENTRIES,
+            max_functions: DEFAULT_MAX_SECTION_ENTRIES,
+            max_imports: DEFAULT_MAX_IMPORTS,
+            max_globals: DEFAULT_MAX_SECTION_ENTRIES,
+            max_tables: DEFAULT_MAX_TABLES_OR_MEMORIES,
+            max_memories: DEFAULT_MAX_TABLES_OR_MEMORIES,
+            max_element_segments: DEFAULT_MAX_SEGMENTS,
+            max_data_segments: DEFAULT_MAX_SEGMENTS,
+            max_br_table_targets: DEFAULT_MAX_BR_TABLE_TARGETS,
+            max_locals: DEFAULT_MAX_LOCALS,
+            max_function_body_bytes: DEFAULT_MAX_FUNCTION_BODY_BYTES,
+            max_name_length: DEFAULT_MAX_NAME_LENGTH,
+        }
+    }
+}
+
+impl ModuleLimits {
+    /// Returns the configured limit for `which`.
+    pub fn get(&self, which: ModuleLimit) -> u32 {
+        match which {
+            ModuleLimit::Types => self.max_types,
+            ModuleLimit::Functions => self.max_functions,
+            ModuleLimit::Imports => self.max_imports,
+            ModuleLimit::Globals => self.max_globals,
+            ModuleLimit::Tables => self.max_tables,
+            ModuleLimit::Memories => self.max_memories,
+            ModuleLimit::ElementSegments => self.max_element_segments,
+            ModuleLimit::DataSegments => self.max_data_segments,
+            ModuleLimit::BrTableTargets => self.max_br_table_targets,
+            ModuleLimit::Locals => self.max_locals,
+            ModuleLimit::FunctionBodyBytes => self.max_function_body_bytes,
+            ModuleLimit::NameLength => self.max_name_length,
+        }
+    }
+
+    /// Ensures that `found` does not exceed the configured limit for `which`.
+    ///
+    /// # Errors
+    ///
+    /// If `found` exceeds the configured limit for `which`.
+    pub(crate) fn ensure(&self, which: ModuleLimit, found: u32) -> Result<(), TranslationError> {
+        let limit = self.get(which);
+        if found > limit {
+            return Err(TranslationError::LimitExceeded {
+                which,
+                limit,
+                found,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The kind of a limit of the [`ModuleLimits`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ModuleLimit {
+    /// See [`ModuleLimits::max_types`].
+    Types,
+    /// See [`ModuleLimits::max_functions`].
+    Functions,
+    /// See [`ModuleLimits::max_imports`].
+    Imports,
+    /// See [`ModuleLimits::max_globals`].
+    Globals,
+    /// See [`ModuleLimits::max_tables`].
+    Tables,
+    /// See [`ModuleLimits::max_memories`].
+    Memories,
+    /// See [`ModuleLimits::max_element_segments`].
+    ElementSegments,
+    /// See [`ModuleLimits::max_data_segments`].
+    DataSegments,
+    /// See [`ModuleLimits::max_br_table_targets`].
+    BrTableTargets,
+    /// See [`ModuleLimits::max_locals`].
+    Locals,
+    /// See [`ModuleLimits::max_function_body_bytes`].
+    FunctionBodyBytes,
+    /// See [`ModuleLimits::max_name_length`].
+    NameLength,
+}
+
+impl Display for ModuleLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Types => "types",
+            Self::Functions => "functions",
+            Self::Imports => "imports",
+            Self::Globals => "globals",
+            Self::Tables => "tables",
+            Self::Memories => "memories",
+            Self::ElementSegments => "element segments",
+            Self::DataSegments => "data segments",
+            Self::BrTableTargets => "br_table targets",
+            Self::Locals => "locals",
+            Self::FunctionBodyBytes => "function body bytes",
+            Self::NameLength => "name bytes",
+        };
+        f.write_str(name)
+    }
+}
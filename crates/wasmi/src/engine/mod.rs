@@ -1,15 +1,28 @@
 //! The Wasmi interpreter.
 
+#[cfg(feature = "std")]
+mod background;
 mod block_type;
 pub mod bytecode;
 mod cache;
 mod code_map;
 mod config;
+mod digest;
+#[cfg(feature = "resumable")]
+mod driver;
+#[cfg(feature = "std")]
+mod executing;
 mod executor;
+mod fuel;
 mod func_args;
 mod func_types;
+mod intrinsics;
 mod limits;
+mod register_types;
+#[cfg(feature = "resumable")]
 mod resumable;
+#[cfg(feature = "metrics")]
+mod sampler;
 mod traits;
 mod translator;
 
@@ -21,10 +34,12 @@ use self::bytecode::RegisterSpan;
 
 pub(crate) use self::{
     block_type::BlockType,
-    config::FuelCosts,
+    cache::IndirectCallCache,
+    code_map::{CodeOwner, CompiledFuncEntity},
     executor::Stack,
+    fuel::{FuelBreakdown, FuelUsage},
     func_args::{FuncFinished, FuncParams, FuncResults},
-    func_types::DedupFuncType,
+    intrinsics::{Intrinsic, BUILTINS_MODULE, INTRINSICS_MODULE},
     translator::{
         FuncTranslationDriver,
         FuncTranslator,
@@ -34,41 +49,62 @@ pub(crate) use self::{
         WasmTranslator,
     },
 };
+#[cfg(feature = "std")]
+pub use self::background::{CompilationHandle, CompilationJob, CompilationStatus};
+#[cfg(feature = "resumable")]
+pub use self::{
+    driver::{DriverState, HostInterruption, ResumableDriver},
+    resumable::{
+        GuestYield,
+        HostYield,
+        ResumableCall,
+        ResumableInvocation,
+        TypedResumableCall,
+        TypedResumableInvocation,
+    },
+};
+#[cfg(feature = "resumable")]
+use self::resumable::{ResumableCallBase, ResumeMode};
 pub use self::{
-    code_map::CompiledFunc,
-    config::{CompilationMode, Config},
-    limits::StackLimits,
-    resumable::{ResumableCall, ResumableInvocation, TypedResumableCall, TypedResumableInvocation},
+    bytecode::{BytecodeError, BytecodeErrorKind},
+    code_map::{CompiledFunc, FuncInfo},
+    config::{CompilationMode, Config, FuelCosts},
+    digest::{DigestFn, ExecutionDigest, RegisterReader},
+    func_types::DedupFuncType,
+    limits::{CallLimits, ModuleLimit, ModuleLimits, StackLimits, StackUsage},
+    register_types::{CallSiteTypes, RegisterTypes},
     traits::{CallParams, CallResults},
     translator::{Instr, TranslationError},
 };
-use self::{
-    code_map::{CodeMap, CompiledFuncEntity},
-    func_types::FuncTypeRegistry,
-    resumable::ResumableCallBase,
-};
+use self::{code_map::CodeMap, func_types::FuncTypeRegistry};
 use crate::{
-    module::{FuncIdx, ModuleHeader},
+    build::Encoder,
+    core::TrapCode,
+    error::TrapMessages,
+    module::{CompiledFuncBytes, FuncIdx, FuncTypeIdx, ModuleHeader, TranslationContext},
     Error,
     Func,
     FuncType,
+    Module,
     StoreContextMut,
 };
 use alloc::{
+    boxed::Box,
+    string::String,
     sync::{Arc, Weak},
-    vec::Vec,
+    vec::{self, Vec},
 };
 use core::sync::atomic::{AtomicU32, Ordering};
-use spin::{Mutex, RwLock};
+use spin::{Mutex, RwLock, RwLockWriteGuard};
 use wasmi_arena::{ArenaIndex, GuardedEntity};
+use wasmi_core::{UntypedValue, ValueType};
 use wasmparser::{FuncToValidate, FuncValidatorAllocations, ValidatorResources};
 
+#[cfg(feature = "std")]
+use crate::errors::FuncError;
 #[cfg(test)]
 use self::bytecode::Instruction;
 
-#[cfg(test)]
-use wasmi_core::UntypedValue;
-
 #[cfg(doc)]
 use crate::Store;
 
@@ -113,6 +149,17 @@ type Guarded<Idx> = GuardedEntity<EngineIdx, Idx>;
 /// - The current Wasmi engine implements a bytecode interpreter.
 /// - This structure is intentionally cheap to copy.
 ///   Most of its API has a `&self` receiver, so can be shared easily.
+///
+/// # Concurrency
+///
+/// An [`Engine`] can be shared between threads that each execute Wasm
+/// on their own [`Store`](crate::Store) concurrently.
+///
+/// Lazily compiled functions are compiled at most once: the first thread
+/// calling such a function compiles it while other threads calling the same
+/// function wait until its compilation has finished. No engine-wide lock is
+/// acquired exclusively for lazy compilation, so other threads may concurrently
+/// execute already compiled functions or compile other functions.
 #[derive(Debug, Clone)]
 pub struct Engine {
     inner: Arc<EngineInner>,
@@ -148,7 +195,17 @@ impl Engine {
     /// # Note
     ///
     /// Users should ues [`Engine::default`] to construct a default [`Engine`].
+    ///
+    /// # Panics
+    ///
+    /// If [`Config::deterministic_only`] is enabled together with sampling
+    /// since the sampled Wasm functions depend on the wall clock.
     pub fn new(config: &Config) -> Self {
+        #[cfg(feature = "metrics")]
+        assert!(
+            !(config.get_deterministic_only() && config.get_sampling()),
+            "sampling cannot be enabled together with `Config::deterministic_only`",
+        );
         Self {
             inner: Arc::new(EngineInner::new(config)),
         }
@@ -166,16 +223,108 @@ impl Engine {
         self.inner.config()
     }
 
+    /// Returns the current [`FuelCosts`] of the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// Those are the [`FuelCosts`] of the [`Config`] unless changed via [`Engine::reprice_fuel`].
+    pub fn fuel_costs(&self) -> FuelCosts {
+        self.inner.fuel_costs()
+    }
+
+    /// Returns the unique [`EngineIdx`] of the [`Engine`].
+    pub(crate) fn engine_idx(&self) -> EngineIdx {
+        self.inner.engine_idx()
+    }
+
+    /// Overrides the message of `trap_code` shown by errors of traps raised by the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// - This is useful to localize or customize the trap messages shown to end users.
+    /// - The override is used by the [`Display`] implementation of [`Error`]s of traps
+    ///   raised by Wasm executions of the [`Engine`] after this call.
+    ///   Errors created before this call keep the messages of their creation.
+    /// - The [`TrapCode`] of the [`Error`] is not affected, i.e. [`Error::as_trap_code`]
+    ///   still returns `trap_code`.
+    /// - Use [`TrapCode::trap_message`] to query the default message of `trap_code`.
+    ///
+    /// [`Display`]: core::fmt::Display
+    pub fn set_trap_message_override(&self, trap_code: TrapCode, message: impl Into<String>) {
+        self.inner
+            .set_trap_message_override(trap_code, message.into().into_boxed_str())
+    }
+
+    /// Reprices the fuel consumed by all Wasm functions of the [`Engine`] for `costs`.
+    ///
+    /// # Note
+    ///
+    /// - The block fuel of already compiled functions is recomputed from the fuel charges
+    ///   recorded during their translation. Thus they consume the same fuel as if they had
+    ///   been translated with `costs` without the need to compile them again.
+    /// - Functions compiled after this call, including lazily compiled ones, use `costs`.
+    /// - [`Store`]s created after this call use `costs` for fuel charged during execution,
+    ///   e.g. for `memory.copy`. Existing [`Store`]s keep their [`FuelCosts`] for those.
+    /// - Compiled functions are rewritten in place. Therefore repricing waits for all Wasm
+    ///   executions of the [`Engine`] on other threads to finish and Wasm executions that
+    ///   start in the meantime wait until the repricing has finished.
+    /// - Suspended resumable calls continue with the repriced fuel.
+    /// - The [`Config`] of the [`Engine`] is not affected.
+    ///
+    /// # Errors
+    ///
+    /// - If the block fuel of any compiled function is out of bounds for `costs`.
+    ///   In this case no function is repriced.
+    /// - If called by a host function during a Wasm execution of the [`Engine`] since the
+    ///   calling execution could not continue with its code rewritten underneath it.
+    ///   Without the `std` crate feature such calls cannot be detected and deadlock instead.
+    pub fn reprice_fuel(&self, costs: &FuelCosts) -> Result<(), Error> {
+        self.inner.reprice_fuel(costs)
+    }
+
     /// Returns `true` if both [`Engine`] references `a` and `b` refer to the same [`Engine`].
     pub fn same(a: &Engine, b: &Engine) -> bool {
         Arc::ptr_eq(&a.inner, &b.inner)
     }
 
+    /// Returns `true` if both [`Engine`] references `a` and `b` use the same [`Config`]
+    /// and the same current [`FuelCosts`].
+    ///
+    /// # Note
+    ///
+    /// - Two such [`Engine`]s compile the same Wasm module to identical Wasmi bytecode
+    ///   since internal maps of Wasmi are ordered maps instead of maps using randomly
+    ///   seeded hashers. Use [`Config::deterministic_only`] to also remove the remaining
+    ///   dependencies on ambient state such as the wall clock of call metrics.
+    /// - The current [`FuelCosts`] are compared since they may differ from the
+    ///   [`Config`] after [`Engine::reprice_fuel`].
+    /// - This does not imply [`Engine::same`] since both [`Engine`]s may still be
+    ///   distinct instances with their own function types and compiled functions.
+    pub fn same_config_fingerprint(a: &Engine, b: &Engine) -> bool {
+        a.config() == b.config() && a.fuel_costs() == b.fuel_costs()
+    }
+
     /// Allocates a new function type to the [`Engine`].
     pub(super) fn alloc_func_type(&self, func_type: FuncType) -> DedupFuncType {
         self.inner.alloc_func_type(func_type)
     }
 
+    /// Returns the index of the deduplicated function type within the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// Indices are assigned in the order in which function types are first
+    /// registered with the [`Engine`], e.g. in the order of the type section
+    /// of compiled Wasm modules. Thus the same function types compiled in the
+    /// same order yield the same indices in fresh [`Engine`]s.
+    ///
+    /// # Panics
+    ///
+    /// If the deduplicated function type is not owned by the [`Engine`].
+    pub fn func_type_index(&self, func_type: &DedupFuncType) -> u32 {
+        self.inner.func_type_index(func_type)
+    }
+
     /// Resolves a deduplicated function type into a [`FuncType`] entity.
     ///
     /// # Panics
@@ -189,11 +338,57 @@ impl Engine {
         self.inner.resolve_func_type(func_type, f)
     }
 
-    /// Allocates a new uninitialized [`CompiledFunc`] to the [`Engine`].
+    /// Allocates a new uninitialized [`CompiledFunc`] owned by `owner` to the [`Engine`].
     ///
     /// Returns a [`CompiledFunc`] reference to allow accessing the allocated [`CompiledFunc`].
-    pub(super) fn alloc_func(&self) -> CompiledFunc {
-        self.inner.alloc_func()
+    pub(super) fn alloc_func(&self, owner: &CodeOwner) -> CompiledFunc {
+        self.inner.alloc_func(owner)
+    }
+
+    /// Returns statistics about the memory used by the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// - Engine resident code is kept until [`Engine::purge_unused`] reclaims
+    ///   it after its [`Module`] and all of its instances have been dropped.
+    /// - Deduplicated function types are never reclaimed.
+    /// - Functions that are currently compiled lazily on other threads are measured
+    ///   once their compilation has finished.
+    ///
+    /// [`Module`]: crate::Module
+    pub fn memory_usage(&self) -> EngineMemoryUsage {
+        self.inner.memory_usage()
+    }
+
+    /// Reclaims the code of all [`Module`]s that are no longer in use.
+    ///
+    /// Returns the number of reclaimed functions.
+    ///
+    /// # Note
+    ///
+    /// - A [`Module`] is no longer in use once it and all [`Instance`]s created
+    ///   from it have been dropped. Instances are dropped with their [`Store`].
+    /// - Reclaimed function slots are reused for functions of new [`Module`]s.
+    /// - Reclaiming code waits for all Wasm executions of the [`Engine`] on
+    ///   other threads to finish since they might still execute reclaimed code.
+    ///
+    /// # Errors
+    ///
+    /// If called by a host function during a Wasm execution of the [`Engine`].
+    /// Without the `std` crate feature such calls cannot be detected and deadlock instead.
+    ///
+    /// [`Module`]: crate::Module
+    /// [`Instance`]: crate::Instance
+    /// [`Store`]: crate::Store
+    pub fn purge_unused(&self) -> Result<usize, Error> {
+        self.inner.purge_unused()
+    }
+
+    /// Returns the memory used by the [`CompiledFunc`]s in `funcs` in bytes.
+    ///
+    /// This includes the function local constant values of `funcs`.
+    pub(crate) fn code_size_of(&self, funcs: &[CompiledFunc]) -> usize {
+        self.inner.code_size_of(funcs)
     }
 
     /// Translates the Wasm function using the [`Engine`].
@@ -214,7 +409,7 @@ impl Engine {
     ///
     /// - If function translation fails.
     /// - If function validation fails.
-    pub(crate) fn translate_func(
+    pub(crate) fn translate_wasm_func(
         &self,
         func_index: FuncIdx,
         compiled_func: CompiledFunc,
@@ -243,7 +438,8 @@ impl Engine {
             }
             (CompilationMode::LazyTranslation, Some(func_to_validate)) => {
                 let allocs = self.inner.get_validation_allocs();
-                let translator = LazyFuncTranslator::new(func_index, compiled_func, module, None);
+                let translator =
+                    LazyFuncTranslator::new(func_index, compiled_func, offset, module, None);
                 let validator = func_to_validate.into_validator(allocs);
                 let translator = ValidatingFuncTranslator::new(validator, translator)?;
                 let allocs = FuncTranslationDriver::new(offset, bytes, translator)?
@@ -251,8 +447,13 @@ impl Engine {
                 self.inner.recycle_validation_allocs(allocs.validation);
             }
             (CompilationMode::Lazy | CompilationMode::LazyTranslation, func_to_validate) => {
-                let translator =
-                    LazyFuncTranslator::new(func_index, compiled_func, module, func_to_validate);
+                let translator = LazyFuncTranslator::new(
+                    func_index,
+                    compiled_func,
+                    offset,
+                    module,
+                    func_to_validate,
+                );
                 FuncTranslationDriver::new(offset, bytes, translator)?
                     .translate(|func_entity| self.inner.init_func(compiled_func, func_entity))?;
             }
@@ -260,6 +461,86 @@ impl Engine {
         Ok(())
     }
 
+    /// Initializes the uninitialized [`CompiledFunc`] with the already compiled `func_entity`.
+    ///
+    /// # Panics
+    ///
+    /// - If `compiled_func` is an invalid [`CompiledFunc`] reference for this [`Engine`].
+    /// - If `compiled_func` refers to an already initialized [`CompiledFunc`].
+    pub(crate) fn init_func(&self, compiled_func: CompiledFunc, func_entity: CompiledFuncEntity) {
+        self.inner.init_func(compiled_func, func_entity)
+    }
+
+    /// Applies `f` to the [`CompiledFuncEntity`] of `func` if it has already been compiled.
+    ///
+    /// Returns `None` without compiling `func` if it has not yet been compiled.
+    ///
+    /// # Panics
+    ///
+    /// If `func` is an invalid [`CompiledFunc`] reference for this [`Engine`].
+    pub(crate) fn with_compiled_func<R>(
+        &self,
+        func: CompiledFunc,
+        f: impl FnOnce(&CompiledFuncEntity) -> R,
+    ) -> Option<R> {
+        self.inner.with_compiled_func(func, f)
+    }
+
+    /// Initializes the uninitialized `compiled_func` with a copy of `origin_func` of the `origin` [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// - All internal functions called by `origin_func` are mapped by `f`.
+    /// - Compiles `origin_func` first if it has not yet been compiled.
+    ///   If its lazy compilation failed `compiled_func` reports the same failure.
+    ///
+    /// # Panics
+    ///
+    /// - If `compiled_func` is an invalid [`CompiledFunc`] reference for this [`Engine`].
+    /// - If `compiled_func` refers to an already initialized [`CompiledFunc`].
+    /// - If `origin_func` is an invalid [`CompiledFunc`] reference for the `origin` [`Engine`].
+    pub(crate) fn init_func_copy(
+        &self,
+        compiled_func: CompiledFunc,
+        origin: &Engine,
+        origin_func: CompiledFunc,
+        f: impl FnMut(CompiledFunc) -> CompiledFunc,
+    ) {
+        // Note: The lock of `origin` is released before `self` is locked
+        //       since both might refer to the same `Engine`.
+        let copy = origin.inner.res.read().code_map.copy_func(origin_func, f);
+        let code_map = &mut self.inner.res.write().code_map;
+        match copy {
+            Ok(mut func_entity) => {
+                func_entity.reprice_fuel(&self.inner.fuel_costs());
+                code_map.init_func(compiled_func, func_entity)
+            }
+            Err(failed) => code_map.init_failed_func(compiled_func, failed),
+        }
+    }
+
+    /// Replaces the [`CompiledFunc`] with the compiled `func_entity`.
+    ///
+    /// # Note
+    ///
+    /// This API is intended for unit testing purposes, e.g. to execute corrupted bytecode.
+    ///
+    /// # Panics
+    ///
+    /// If `compiled_func` is an invalid [`CompiledFunc`] reference for this [`Engine`].
+    #[cfg(all(test, feature = "checked-executor"))]
+    pub(crate) fn replace_func(
+        &self,
+        compiled_func: CompiledFunc,
+        func_entity: CompiledFuncEntity,
+    ) {
+        self.inner
+            .res
+            .write()
+            .code_map
+            .replace_func(compiled_func, func_entity)
+    }
+
     /// Returns reusable [`FuncTranslatorAllocations`] from the [`Engine`].
     pub(crate) fn get_translation_allocs(&self) -> FuncTranslatorAllocations {
         self.inner.get_translation_allocs()
@@ -299,12 +580,343 @@ impl Engine {
         &self,
         func_idx: FuncIdx,
         func: CompiledFunc,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) {
         self.inner
-            .init_lazy_func(func_idx, func, bytes, module, func_to_validate)
+            .init_lazy_func(func_idx, func, offset, bytes, module, func_to_validate)
+    }
+
+    /// Verifies the Wasmi bytecode of the [`CompiledFunc`].
+    ///
+    /// # Note
+    ///
+    /// Compiles the [`CompiledFunc`] first if it has not yet been compiled.
+    ///
+    /// # Errors
+    ///
+    /// - If the `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    /// - If the Wasmi bytecode of `func` violates its encoding invariants.
+    pub(crate) fn verify_func(&self, func: CompiledFunc) -> Result<(), Error> {
+        self.inner.verify_func(func)
+    }
+
+    /// Resolves the address map of the [`CompiledFunc`] and applies `f` to it.
+    ///
+    /// # Note
+    ///
+    /// - The address map is a table of `(instr, offset)` entries sorted by `instr`.
+    ///   All Wasmi bytecode instructions of `func` with index `instr` up to the
+    ///   `instr` of the next entry have been translated from the Wasm operator
+    ///   at `offset` in the Wasm binary.
+    /// - The address map is empty unless [`Config::generate_address_map`] is enabled.
+    /// - Compiles the [`CompiledFunc`] first if it has not yet been compiled.
+    /// - Use [`Module::get_compiled_func`] to query the [`CompiledFunc`] of a function.
+    ///
+    /// # Errors
+    ///
+    /// If the `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    ///
+    /// # Panics
+    ///
+    /// If the [`CompiledFunc`] is invalid for the [`Engine`].
+    ///
+    /// [`Module::get_compiled_func`]: crate::Module::get_compiled_func
+    pub fn address_map<F, R>(&self, func: CompiledFunc, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&[(u32, u32)]) -> R,
+    {
+        self.inner.address_map(func, f)
+    }
+
+    /// Resolves the [`RegisterTypes`] of the [`CompiledFunc`] and applies `f` to it.
+    ///
+    /// # Note
+    ///
+    /// - The [`RegisterTypes`] are empty unless [`Config::generate_register_types`] is enabled.
+    /// - Compiles the [`CompiledFunc`] first if it has not yet been compiled.
+    /// - Use [`Module::get_compiled_func`] to query the [`CompiledFunc`] of a function.
+    ///
+    /// # Errors
+    ///
+    /// If the `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    ///
+    /// # Panics
+    ///
+    /// If the [`CompiledFunc`] is invalid for the [`Engine`].
+    ///
+    /// [`Module::get_compiled_func`]: crate::Module::get_compiled_func
+    pub fn register_types<F, R>(&self, func: CompiledFunc, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&RegisterTypes) -> R,
+    {
+        self.inner.register_types(func, f)
+    }
+
+    /// Returns the number of registers of the [`CompiledFunc`].
+    ///
+    /// # Note
+    ///
+    /// - The registers of a function make up its call frame on the value stack.
+    /// - Compiles the [`CompiledFunc`] first if it has not yet been compiled.
+    /// - Use [`Module::get_compiled_func`] to query the [`CompiledFunc`] of a function.
+    ///
+    /// # Errors
+    ///
+    /// If the `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    ///
+    /// # Panics
+    ///
+    /// If the [`CompiledFunc`] is invalid for the [`Engine`].
+    ///
+    /// [`Module::get_compiled_func`]: crate::Module::get_compiled_func
+    pub fn len_registers(&self, func: CompiledFunc) -> Result<u16, Error> {
+        self.inner.len_registers(func)
+    }
+
+    /// Returns the [`FuncInfo`] of the [`CompiledFunc`] if it has been compiled.
+    ///
+    /// # Note
+    ///
+    /// - Unlike [`Engine::len_registers`] this never compiles the [`CompiledFunc`]
+    ///   and thus is cheap enough to symbolicate the samples of [`Engine::set_sampler`].
+    /// - Returns `None` if the [`CompiledFunc`] has not yet been compiled or
+    ///   has been reclaimed since its [`Module`] no longer exists.
+    ///
+    /// # Panics
+    ///
+    /// If the [`CompiledFunc`] is invalid for the [`Engine`].
+    ///
+    /// [`Module`]: crate::Module
+    pub fn func_info(&self, func: CompiledFunc) -> Option<FuncInfo> {
+        self.inner.func_info(func)
+    }
+
+    /// Returns the compilation state of all internal functions of `module`.
+    ///
+    /// Yields `(func_index, is_compiled)` pairs in the order of the function indices.
+    ///
+    /// # Note
+    ///
+    /// - The `func_index` refers to the function index space of `module`
+    ///   which includes its imported functions.
+    /// - Functions that failed their lazy compilation are reported as not compiled.
+    /// - Use [`Module::compile_functions`] to compile functions ahead of their first call.
+    /// - This does not wait for Wasm executions of the [`Engine`] and thus may also be
+    ///   called by host functions.
+    ///
+    /// # Panics
+    ///
+    /// If `module` has not been created with the [`Engine`].
+    ///
+    /// [`Module::compile_functions`]: crate::Module::compile_functions
+    pub fn compiled_functions(&self, module: &Module) -> impl Iterator<Item = (u32, bool)> {
+        assert!(
+            Engine::same(self, module.engine()),
+            "module has been created with another engine"
+        );
+        self.inner.compiled_funcs(module.internal_compiled_funcs())
+    }
+
+    /// Returns the number of functions that have been compiled lazily by the [`Engine`] so far.
+    ///
+    /// # Note
+    ///
+    /// - This includes functions compiled ahead of their first call via
+    ///   [`Module::compile_functions`] or [`Instance::precompile_exports`].
+    /// - This does not include functions compiled eagerly upon [`Module`] creation.
+    ///
+    /// [`Module::compile_functions`]: crate::Module::compile_functions
+    /// [`Instance::precompile_exports`]: crate::Instance::precompile_exports
+    pub fn lazy_compilations(&self) -> usize {
+        self.inner.lazy_compilations()
+    }
+
+    /// Compiles all `funcs` that have not yet been compiled.
+    ///
+    /// If `transitive` is `true` all internal functions directly called by
+    /// the compiled functions are compiled as well, transitively.
+    ///
+    /// # Errors
+    ///
+    /// If the lazy compilation of any of the functions failed.
+    pub(crate) fn compile_funcs(
+        &self,
+        funcs: impl IntoIterator<Item = CompiledFunc>,
+        transitive: bool,
+    ) -> Result<(), Error> {
+        self.inner.compile_funcs(funcs, transitive)
+    }
+
+    /// Translates a single Wasm function of type `func_type` with the Wasm module resources of `ctx`.
+    ///
+    /// # Note
+    ///
+    /// - The `locals` are the local variable declarations of the function as `(amount, type)` pairs.
+    /// - The `body` are the Wasm encoded operators of the function including its final `end`.
+    /// - The function is always validated and translated eagerly regardless of the [`CompilationMode`].
+    /// - Install the returned [`CompiledFuncBytes`] via [`Engine::install_func`].
+    ///   This allows embedders to cache translated Wasm functions individually.
+    ///
+    /// # Errors
+    ///
+    /// - If `func_type` is not a function type of `ctx`.
+    /// - If the function fails to validate or translate.
+    ///
+    /// # Panics
+    ///
+    /// If `ctx` has been created for another [`Engine`].
+    pub fn translate_func(
+        &self,
+        func_type: &FuncType,
+        locals: &[(u32, ValueType)],
+        body: &[u8],
+        ctx: &TranslationContext,
+    ) -> Result<CompiledFuncBytes, Error> {
+        let header = ctx.header();
+        assert!(
+            header
+                .engine()
+                .upgrade()
+                .is_some_and(|engine| Engine::same(self, &engine)),
+            "cannot translate function with a translation context of another engine"
+        );
+        let Some(type_index) = ctx.func_type_index(func_type) else {
+            return Err(Error::from(TranslationError::UnknownFuncType));
+        };
+        let dedup_func_type = *header.get_func_type(FuncTypeIdx::from(type_index));
+        // Note: the translated function is not part of `ctx` and thus indexed after its functions.
+        let func_index = FuncIdx::from(ctx.len_funcs());
+        let mut encoder = Encoder::default();
+        encoder.length(locals.len());
+        for (amount, ty) in locals {
+            encoder.u32(*amount);
+            encoder.value_type(*ty);
+        }
+        encoder.raw(body);
+        let bytes = encoder.finish();
+        let func_to_validate = FuncToValidate::new(
+            func_index.into_u32(),
+            type_index,
+            ctx.resources().clone(),
+            &self.config().wasm_features(),
+        );
+        let (translation_allocs, validation_allocs) = self.inner.get_allocs();
+        let validator = func_to_validate.into_validator(validation_allocs);
+        let translator = FuncTranslator::new_with_type(
+            func_index,
+            dedup_func_type,
+            header.clone(),
+            translation_allocs,
+        )?;
+        let translator = ValidatingFuncTranslator::new(validator, translator)?;
+        let mut translated = None;
+        let allocs = FuncTranslationDriver::new(0, &bytes, translator)?
+            .translate(|func_entity| translated = Some(func_entity))?;
+        self.inner
+            .recycle_allocs(allocs.translation, allocs.validation);
+        let func_entity = translated.expect("missing translated function after translation");
+        Ok(CompiledFuncBytes::new(self, func_entity))
+    }
+
+    /// Installs the function translated via [`Engine::translate_func`] as `func`.
+    ///
+    /// # Note
+    ///
+    /// - This replaces the current code of `func` regardless of its compilation state.
+    /// - The installed function keeps the index of `func` within its Wasm module.
+    /// - The installed function is repriced to the current [`FuelCosts`] of the [`Engine`].
+    /// - Calls to `func` that have already been inlined into other functions are not affected.
+    /// - The code of `func` is replaced while no Wasm execution of the [`Engine`] is running.
+    ///   Therefore this waits for all Wasm executions of the [`Engine`] on other threads to finish.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that
+    ///
+    /// - `bytes` has been translated with a [`TranslationContext`] that matches
+    ///   the Wasm module of `func` since calls to other functions are resolved with it,
+    /// - `bytes` has been translated for the same function type as the one of `func`,
+    /// - `func` has not yet been executed by any [`Store`] since its previous code
+    ///   may still be referenced by suspended executions and execution caches,
+    /// - this is not called during a Wasm execution of the [`Engine`] on the same thread,
+    ///   e.g. by a host function, since this deadlocks without the `std` crate feature.
+    ///
+    /// # Errors
+    ///
+    /// If called during a Wasm execution of the [`Engine`] on the same thread.
+    /// In this case `func` keeps its current code.
+    ///
+    /// # Panics
+    ///
+    /// - If `bytes` has been translated by another [`Engine`].
+    /// - If `func` is an invalid [`CompiledFunc`] reference for this [`Engine`].
+    pub unsafe fn install_func(
+        &self,
+        func: CompiledFunc,
+        bytes: CompiledFuncBytes,
+    ) -> Result<(), Error> {
+        assert!(
+            Engine::same(self, bytes.engine()),
+            "cannot install function translated by another engine"
+        );
+        let mut func_entity = bytes.into_entity();
+        let mut res = self.inner.res_mut()?;
+        func_entity.reprice_fuel(&self.inner.fuel_costs());
+        res.code_map.install_func(func, func_entity);
+        Ok(())
+    }
+
+    /// Returns the [`CompiledFunc`] of the [`Engine`] currently executed by `thread` if any.
+    ///
+    /// # Note
+    ///
+    /// - Always returns `None` unless [`Config::sampling`] is enabled.
+    /// - Returns `None` while `thread` executes no Wasm function of the [`Engine`].
+    /// - Host functions called from Wasm are attributed to their calling Wasm function.
+    #[cfg(feature = "metrics")]
+    pub fn current_func_of(&self, thread: std::thread::ThreadId) -> Option<CompiledFunc> {
+        sampler::current_func_of(self.inner.engine_idx(), thread)
+    }
+
+    /// Installs a sampler that calls `callback` every `interval` for each thread
+    /// currently executing a Wasm function of the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// - The `callback` runs on a dedicated background thread and is called with
+    ///   the [`CompiledFunc`] that is executed at the time of sampling.
+    ///   Use [`Engine::func_info`] to symbolicate the sampled [`CompiledFunc`].
+    /// - Threads are only sampled if [`Config::sampling`] is enabled.
+    /// - Replaces and stops the previously installed sampler if any.
+    /// - The sampler is stopped once the [`Engine`] is dropped.
+    ///   Therefore `callback` must not own the [`Engine`] itself.
+    ///
+    /// # Panics
+    ///
+    /// If called from within the `callback` of a sampler of the same [`Engine`].
+    #[cfg(feature = "metrics")]
+    pub fn set_sampler<F>(&self, interval: core::time::Duration, callback: F)
+    where
+        F: FnMut(CompiledFunc) + Send + 'static,
+    {
+        self.inner.set_sampler(Some((interval, Box::new(callback))))
+    }
+
+    /// Stops and removes the sampler installed via [`Engine::set_sampler`] if any.
+    ///
+    /// # Note
+    ///
+    /// Upon return the `callback` of the sampler is no longer called.
+    ///
+    /// # Panics
+    ///
+    /// If called from within the `callback` of a sampler of the same [`Engine`].
+    #[cfg(feature = "metrics")]
+    pub fn clear_sampler(&self) {
+        self.inner.set_sampler(None)
     }
 
     /// Resolves the [`CompiledFunc`] to the underlying Wasmi bytecode instructions.
@@ -389,7 +1001,67 @@ impl Engine {
     where
         Results: CallResults,
     {
-        self.inner.execute_func(ctx, func, params, results)
+        self.inner.execute_func(ctx, func, params, results, None)
+    }
+
+    /// Executes the given [`Func`] with parameters `params` restricted to the [`CallLimits`].
+    ///
+    /// Stores the execution result into `results` upon a successful execution.
+    ///
+    /// # Note
+    ///
+    /// The same as [`Engine::execute_func`] except that the stack usage of the
+    /// execution is restricted to the `limits` in addition to the [`StackLimits`].
+    ///
+    /// # Errors
+    ///
+    /// - If the length of `params` or `results` do not match the signature of `func`.
+    /// - When encountering a Wasm or host trap during the execution of `func`.
+    /// - If the execution exceeds the `limits`.
+    #[inline]
+    pub(crate) fn execute_func_with_limits<T, Results>(
+        &self,
+        ctx: StoreContextMut<T>,
+        func: &Func,
+        params: impl CallParams,
+        results: Results,
+        limits: CallLimits,
+    ) -> Result<<Results as CallResults>::Results, Error>
+    where
+        Results: CallResults,
+    {
+        self.inner
+            .execute_func(ctx, func, params, results, Some(limits))
+    }
+
+    /// Executes the given [`Func`] with the untyped `params` and writes its untyped `results`.
+    ///
+    /// # Note
+    ///
+    /// - Assumes that the `params` and `results` are well typed.
+    ///   Type checks are done when creating a new [`TypedFunc`] instance via [`Func::typed`].
+    /// - This is the shared entry point of all [`TypedFunc::call`] signatures.
+    ///   It is intentionally not generic over the signature so that the stack setup,
+    ///   engine entry and copy-back of the results are only monomorphized once per
+    ///   [`Store`] type instead of once per signature.
+    ///
+    /// # Errors
+    ///
+    /// - If the length of `params` or `results` do not match the signature of `func`.
+    /// - When encountering a Wasm or host trap during the execution of `func`.
+    ///
+    /// [`TypedFunc`]: [`crate::TypedFunc`]
+    /// [`TypedFunc::call`]: [`crate::TypedFunc::call`]
+    /// [`Store`]: [`crate::Store`]
+    #[inline(never)]
+    pub(crate) fn execute_func_untyped<T>(
+        &self,
+        ctx: StoreContextMut<T>,
+        func: &Func,
+        params: &[UntypedValue],
+        results: &mut [UntypedValue],
+    ) -> Result<(), Error> {
+        self.inner.execute_func(ctx, func, params, results, None)
     }
 
     /// Executes the given [`Func`] resumably with parameters `params` and returns.
@@ -415,6 +1087,7 @@ impl Engine {
     ///
     /// [`TypedFunc`]: [`crate::TypedFunc`]
     #[inline]
+    #[cfg(feature = "resumable")]
     pub(crate) fn execute_func_resumable<T, Results>(
         &self,
         ctx: StoreContextMut<T>,
@@ -452,25 +1125,50 @@ impl Engine {
     ///
     /// [`TypedFunc`]: [`crate::TypedFunc`]
     #[inline]
+    #[cfg(feature = "resumable")]
     pub(crate) fn resume_func<T, Results>(
         &self,
         ctx: StoreContextMut<T>,
         invocation: ResumableInvocation,
+        mode: ResumeMode,
         params: impl CallParams,
         results: Results,
     ) -> Result<ResumableCallBase<<Results as CallResults>::Results>, Error>
     where
         Results: CallResults,
     {
-        self.inner.resume_func(ctx, invocation, params, results)
+        self.inner
+            .resume_func(ctx, invocation, mode, params, results)
     }
 
     /// Recycles the given [`Stack`] for reuse in the [`Engine`].
+    #[cfg(feature = "resumable")]
     pub(crate) fn recycle_stack(&self, stack: Stack) {
         self.inner.recycle_stack(stack)
     }
 }
 
+/// Statistics about the memory used by an [`Engine`].
+///
+/// Returned by [`Engine::memory_usage`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct EngineMemoryUsage {
+    /// The bytes used by the code of all functions.
+    ///
+    /// # Note
+    ///
+    /// This is the size of the Wasmi bytecode of compiled functions
+    /// and the size of the Wasm function bodies of functions that
+    /// are still waiting for their lazy compilation.
+    pub code_bytes: usize,
+    /// The bytes used by the function local constant values of all compiled functions.
+    pub const_pool_bytes: usize,
+    /// The number of deduplicated function types.
+    pub func_types: usize,
+    /// The number of functions that have not been reclaimed.
+    pub total_funcs: usize,
+}
+
 /// The internal state of the Wasmi [`Engine`].
 #[derive(Debug)]
 pub struct EngineInner {
@@ -478,6 +1176,13 @@ pub struct EngineInner {
     config: Config,
     /// Engine resources shared across multiple engine executors.
     res: RwLock<EngineResources>,
+    /// The current [`FuelCosts`] of the engine.
+    ///
+    /// # Note
+    ///
+    /// This is only changed while holding the write lock of `res`.
+    /// Read [`Engine::reprice_fuel`] for more information.
+    fuel_costs: RwLock<FuelCosts>,
     /// Reusable allocation stacks.
     allocs: Mutex<ReusableAllocationStack>,
     /// Reusable engine stacks for Wasm execution.
@@ -486,6 +1191,16 @@ pub struct EngineInner {
     /// operate on. Therefore a Wasm engine is required to provide stacks and
     /// ideally recycles old ones since creation of a new stack is rather expensive.
     stacks: Mutex<EngineStacks>,
+    /// The trap messages overridden via [`Engine::set_trap_message_override`] if any.
+    ///
+    /// # Note
+    ///
+    /// Errors raised by Wasm executions share a snapshot of the overridden messages
+    /// which is replaced instead of mutated upon [`Engine::set_trap_message_override`].
+    trap_messages: RwLock<Option<Arc<TrapMessages>>>,
+    /// The sampler installed via [`Engine::set_sampler`] if any.
+    #[cfg(feature = "metrics")]
+    sampler: Mutex<Option<sampler::Sampler>>,
 }
 
 /// Stacks to hold and distribute reusable allocations.
@@ -496,6 +1211,12 @@ pub struct ReusableAllocationStack {
     translation: Vec<FuncTranslatorAllocations>,
     /// Allocations required by Wasm function validators.
     validation: Vec<FuncValidatorAllocations>,
+    /// The number of [`FuncTranslatorAllocations`] that had to be newly created.
+    ///
+    /// # Note
+    ///
+    /// This stays at 1 for sequential translations that reuse their allocations.
+    len_created_translation: usize,
 }
 
 impl Default for ReusableAllocationStack {
@@ -504,6 +1225,7 @@ impl Default for ReusableAllocationStack {
             max_height: 1,
             translation: Vec::new(),
             validation: Vec::new(),
+            len_created_translation: 0,
         }
     }
 }
@@ -516,6 +1238,7 @@ impl core::fmt::Debug for ReusableAllocationStack {
             //       We should derive Debug as soon as FuncValidatorAllocations has a Debug impl in future
             //       wasmparser versions.
             .field("validation", &self.validation.len())
+            .field("len_created_translation", &self.len_created_translation)
             .finish()
     }
 }
@@ -525,7 +1248,10 @@ impl ReusableAllocationStack {
     pub fn get_translation_allocs(&mut self) -> FuncTranslatorAllocations {
         match self.translation.pop() {
             Some(allocs) => allocs,
-            None => FuncTranslatorAllocations::default(),
+            None => {
+                self.len_created_translation += 1;
+                FuncTranslatorAllocations::default()
+            }
         }
     }
 
@@ -601,8 +1327,12 @@ impl EngineInner {
         Self {
             config: *config,
             res: RwLock::new(EngineResources::new()),
+            fuel_costs: RwLock::new(*config.fuel_costs()),
             allocs: Mutex::new(ReusableAllocationStack::default()),
             stacks: Mutex::new(EngineStacks::new(config)),
+            trap_messages: RwLock::new(None),
+            #[cfg(feature = "metrics")]
+            sampler: Mutex::new(None),
         }
     }
 
@@ -611,11 +1341,42 @@ impl EngineInner {
         &self.config
     }
 
+    /// Returns the current [`FuelCosts`] of the [`EngineInner`].
+    fn fuel_costs(&self) -> FuelCosts {
+        *self.fuel_costs.read()
+    }
+
+    /// Reprices the fuel consumed by all Wasm functions of the [`EngineInner`] for `costs`.
+    fn reprice_fuel(&self, costs: &FuelCosts) -> Result<(), Error> {
+        let mut res = self.res_mut()?;
+        res.code_map.reprice_fuel(costs)?;
+        *self.fuel_costs.write() = *costs;
+        Ok(())
+    }
+
+    /// Overrides the message of `trap_code` in the trap messages of the [`EngineInner`].
+    fn set_trap_message_override(&self, trap_code: TrapCode, message: Box<str>) {
+        let mut trap_messages = self.trap_messages.write();
+        let mut messages = trap_messages.as_deref().cloned().unwrap_or_default();
+        messages.set(trap_code, message);
+        *trap_messages = Some(Arc::new(messages));
+    }
+
+    /// Returns the current snapshot of the overridden trap messages if any.
+    pub(crate) fn trap_messages(&self) -> Option<Arc<TrapMessages>> {
+        self.trap_messages.read().clone()
+    }
+
     /// Allocates a new function type to the [`EngineInner`].
     fn alloc_func_type(&self, func_type: FuncType) -> DedupFuncType {
         self.res.write().func_types.alloc_func_type(func_type)
     }
 
+    /// Returns the index of the deduplicated function type within the [`EngineInner`].
+    fn func_type_index(&self, func_type: &DedupFuncType) -> u32 {
+        self.res.read().func_types.func_type_index(func_type)
+    }
+
     /// Resolves a deduplicated function type into a [`FuncType`] entity.
     ///
     /// # Panics
@@ -629,11 +1390,54 @@ impl EngineInner {
         f(self.res.read().func_types.resolve_func_type(func_type))
     }
 
-    /// Allocates a new uninitialized [`CompiledFunc`] to the [`EngineInner`].
+    /// Allocates a new uninitialized [`CompiledFunc`] owned by `owner` to the [`EngineInner`].
     ///
     /// Returns a [`CompiledFunc`] reference to allow accessing the allocated [`CompiledFunc`].
-    fn alloc_func(&self) -> CompiledFunc {
-        self.res.write().code_map.alloc_func()
+    fn alloc_func(&self, owner: &CodeOwner) -> CompiledFunc {
+        self.res.write().code_map.alloc_func(owner)
+    }
+
+    /// Exclusively locks the [`EngineResources`] of the [`EngineInner`].
+    ///
+    /// # Note
+    ///
+    /// This waits until all Wasm executions of the [`EngineInner`] on other threads have finished.
+    ///
+    /// # Errors
+    ///
+    /// If the current thread executes Wasm of the [`EngineInner`] since
+    /// waiting for this execution to finish would deadlock.
+    fn res_mut(&self) -> Result<RwLockWriteGuard<'_, EngineResources>, Error> {
+        #[cfg(feature = "std")]
+        if executing::is_executing(self.engine_idx()) {
+            return Err(Error::from(FuncError::EngineAlreadyExecuting));
+        }
+        Ok(self.res.write())
+    }
+
+    /// Returns statistics about the memory used by the [`EngineInner`].
+    fn memory_usage(&self) -> EngineMemoryUsage {
+        let res = self.res.read();
+        let code_size = res.code_map.code_size();
+        EngineMemoryUsage {
+            code_bytes: code_size.code,
+            const_pool_bytes: code_size.consts,
+            func_types: res.func_types.len(),
+            total_funcs: res.code_map.len_funcs(),
+        }
+    }
+
+    /// Reclaims the code of all [`Module`](crate::Module)s that are no longer in use.
+    ///
+    /// Returns the number of reclaimed functions.
+    fn purge_unused(&self) -> Result<usize, Error> {
+        Ok(self.res_mut()?.code_map.purge_unused())
+    }
+
+    /// Returns the memory used by the [`CompiledFunc`]s in `funcs` in bytes.
+    fn code_size_of(&self, funcs: &[CompiledFunc]) -> usize {
+        let code_size = self.res.read().code_map.code_size_of(funcs);
+        code_size.code + code_size.consts
     }
 
     /// Returns reusable [`FuncTranslatorAllocations`] from the [`Engine`].
@@ -697,11 +1501,12 @@ impl EngineInner {
     ///
     /// - If `func` is an invalid [`CompiledFunc`] reference for this [`CodeMap`].
     /// - If `func` refers to an already initialized [`CompiledFunc`].
-    fn init_func(&self, compiled_func: CompiledFunc, func_entity: CompiledFuncEntity) {
-        self.res
-            .write()
-            .code_map
-            .init_func(compiled_func, func_entity)
+    fn init_func(&self, compiled_func: CompiledFunc, mut func_entity: CompiledFuncEntity) {
+        let mut res = self.res.write();
+        // Note: The function might have been translated for outdated fuel costs
+        //       if it was translated concurrently to `Engine::reprice_fuel`.
+        func_entity.reprice_fuel(&self.fuel_costs());
+        res.code_map.init_func(compiled_func, func_entity)
     }
 
     /// Initializes the uninitialized [`CompiledFunc`] for the [`Engine`].
@@ -719,14 +1524,137 @@ impl EngineInner {
         &self,
         func_idx: FuncIdx,
         func: CompiledFunc,
+        offset: usize,
         bytes: &[u8],
         module: &ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) {
+        self.res.write().code_map.init_lazy_func(
+            func,
+            func_idx,
+            offset,
+            bytes,
+            module,
+            func_to_validate,
+        )
+    }
+
+    /// Verifies the Wasmi bytecode of the [`CompiledFunc`].
+    ///
+    /// # Errors
+    ///
+    /// - If the `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    /// - If the Wasmi bytecode of `func` violates its encoding invariants.
+    fn verify_func(&self, func: CompiledFunc) -> Result<(), Error> {
+        self.res.read().code_map.verify_function(func)
+    }
+
+    /// Applies `f` to the [`CompiledFuncEntity`] of `func` if it has already been compiled.
+    fn with_compiled_func<R>(
+        &self,
+        func: CompiledFunc,
+        f: impl FnOnce(&CompiledFuncEntity) -> R,
+    ) -> Option<R> {
+        self.res.read().code_map.get_compiled(func).map(f)
+    }
+
+    /// Resolves the address map of the [`CompiledFunc`] and applies `f` to it.
+    ///
+    /// # Errors
+    ///
+    /// If the `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    fn address_map<F, R>(&self, func: CompiledFunc, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&[(u32, u32)]) -> R,
+    {
+        Ok(f(self.res.read().code_map.get(None, func)?.address_map()))
+    }
+
+    /// Returns the number of registers of the [`CompiledFunc`].
+    ///
+    /// # Errors
+    ///
+    /// If the `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    fn len_registers(&self, func: CompiledFunc) -> Result<u16, Error> {
+        Ok(self.res.read().code_map.get(None, func)?.len_registers())
+    }
+
+    /// Returns the [`FuncInfo`] of the [`CompiledFunc`] if it has been compiled.
+    fn func_info(&self, func: CompiledFunc) -> Option<FuncInfo> {
         self.res
-            .write()
+            .read()
             .code_map
-            .init_lazy_func(func, func_idx, bytes, module, func_to_validate)
+            .get_compiled(func)
+            .map(CompiledFuncEntity::info)
+    }
+
+    /// Returns the `(func_index, is_compiled)` pairs of all `funcs`.
+    fn compiled_funcs(
+        &self,
+        funcs: impl Iterator<Item = (u32, CompiledFunc)>,
+    ) -> vec::IntoIter<(u32, bool)> {
+        let res = self.res.read();
+        funcs
+            .map(|(func_index, func)| (func_index, res.code_map.is_compiled(func)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns the number of functions that have been compiled lazily so far.
+    fn lazy_compilations(&self) -> usize {
+        self.res.read().code_map.lazy_compilations()
+    }
+
+    /// Compiles all `funcs` that have not yet been compiled.
+    ///
+    /// # Note
+    ///
+    /// This only acquires a shared lock on the [`EngineResources`]
+    /// and therefore does not block concurrent Wasm executions.
+    ///
+    /// # Errors
+    ///
+    /// If the lazy compilation of any of the functions failed.
+    fn compile_funcs(
+        &self,
+        funcs: impl IntoIterator<Item = CompiledFunc>,
+        transitive: bool,
+    ) -> Result<(), Error> {
+        self.res.read().code_map.compile_funcs(funcs, transitive)
+    }
+
+    /// Returns the [`EngineIdx`] of the [`EngineInner`].
+    fn engine_idx(&self) -> EngineIdx {
+        self.res.read().func_types.engine_idx()
+    }
+
+    /// Replaces the sampler of the [`EngineInner`] with a new one for `sampler` if any.
+    #[cfg(feature = "metrics")]
+    fn set_sampler(&self, sampler: Option<(core::time::Duration, sampler::SamplerCallback)>) {
+        let new = sampler.map(|(interval, callback)| {
+            sampler::Sampler::spawn(self.engine_idx(), interval, callback)
+        });
+        let old = core::mem::replace(&mut *self.sampler.lock(), new);
+        // Note: the old sampler is stopped without holding the lock
+        //       since this waits for its thread to finish.
+        drop(old);
+    }
+
+    /// Resolves the [`RegisterTypes`] of the [`CompiledFunc`] and applies `f` to it.
+    ///
+    /// # Errors
+    ///
+    /// If the `func` fails Wasm to Wasmi bytecode translation after it was lazily initialized.
+    fn register_types<F, R>(&self, func: CompiledFunc, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&RegisterTypes) -> R,
+    {
+        let res = self.res.read();
+        let func = res.code_map.get(None, func)?;
+        match func.register_types() {
+            Some(register_types) => Ok(f(register_types)),
+            None => Ok(f(&RegisterTypes::default())),
+        }
     }
 
     /// Resolves the [`InternalFuncEntity`] for [`CompiledFunc`] and applies `f` to it.
@@ -787,6 +1715,7 @@ impl EngineInner {
     }
 
     /// Recycles the given [`Stack`].
+    #[cfg(feature = "resumable")]
     fn recycle_stack(&self, stack: Stack) {
         self.stacks.lock().recycle(stack)
     }
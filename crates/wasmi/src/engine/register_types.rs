@@ -0,0 +1,148 @@
+use super::{
+    bytecode::{Register, RegisterSpan},
+    RegisterReader,
+};
+use crate::{value::WithType, Value};
+use alloc::boxed::Box;
+use wasmi_core::ValueType;
+
+/// The types of the result registers of a call instruction of a compiled function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallSiteTypes {
+    /// The index of the call instruction within its compiled function.
+    instr: u32,
+    /// The registers storing the results of the call.
+    results: RegisterSpan,
+    /// The types of the results of the call.
+    types: Box<[ValueType]>,
+}
+
+impl CallSiteTypes {
+    /// Creates new [`CallSiteTypes`] for the call instruction at `instr`.
+    pub(crate) fn new<T>(instr: u32, results: RegisterSpan, types: T) -> Self
+    where
+        T: IntoIterator<Item = ValueType>,
+    {
+        Self {
+            instr,
+            results,
+            types: types.into_iter().collect(),
+        }
+    }
+
+    /// Returns the index of the call instruction within its compiled function.
+    pub fn instr(&self) -> u32 {
+        self.instr
+    }
+
+    /// Returns the registers storing the results of the call.
+    pub fn results(&self) -> RegisterSpan {
+        self.results
+    }
+
+    /// Returns the types of the results of the call.
+    pub fn types(&self) -> &[ValueType] {
+        &self.types
+    }
+}
+
+/// The types of the registers of a compiled function.
+///
+/// # Note
+///
+/// - The registers of function parameters and locals keep their type throughout
+///   the execution of the function and are typed by [`RegisterTypes::entry`].
+/// - All other registers are reused for values of different types. Only the result
+///   registers of calls are typed and only right after the call returned.
+/// - Generated for translated functions if [`Config::generate_register_types`] is enabled.
+///
+/// [`Config::generate_register_types`]: crate::Config::generate_register_types
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RegisterTypes {
+    /// The types of the registers at function entry.
+    entry: Box<[ValueType]>,
+    /// The result types of the call instructions sorted by instruction index.
+    call_sites: Box<[CallSiteTypes]>,
+}
+
+impl RegisterTypes {
+    /// Creates new [`RegisterTypes`] from the types of the registers at function `entry`.
+    ///
+    /// # Panics
+    ///
+    /// If `call_sites` are not sorted by their instruction index.
+    pub(crate) fn new<E, C>(entry: E, call_sites: C) -> Self
+    where
+        E: IntoIterator<Item = ValueType>,
+        C: IntoIterator<Item = CallSiteTypes>,
+    {
+        let call_sites: Box<[CallSiteTypes]> = call_sites.into_iter().collect();
+        assert!(
+            call_sites.windows(2).all(|w| w[0].instr < w[1].instr),
+            "call sites must be sorted by instruction index"
+        );
+        Self {
+            entry: entry.into_iter().collect(),
+            call_sites,
+        }
+    }
+
+    /// Returns the types of the registers at function entry.
+    ///
+    /// # Note
+    ///
+    /// The type at index `n` belongs to the register with index `n`.
+    /// These are the function parameters followed by the function locals.
+    pub fn entry(&self) -> &[ValueType] {
+        &self.entry
+    }
+
+    /// Returns the result types of all call instructions sorted by instruction index.
+    pub fn call_sites(&self) -> &[CallSiteTypes] {
+        &self.call_sites
+    }
+
+    /// Returns the result types of the call instruction at `instr` if any.
+    pub fn call_site(&self, instr: u32) -> Option<&CallSiteTypes> {
+        let index = self
+            .call_sites
+            .binary_search_by_key(&instr, CallSiteTypes::instr)
+            .ok()?;
+        Some(&self.call_sites[index])
+    }
+
+    /// Returns the type of the parameter or local `register` if any.
+    pub fn local_type(&self, register: Register) -> Option<ValueType> {
+        let index = usize::try_from(register.to_i16()).ok()?;
+        self.entry.get(index).copied()
+    }
+
+    /// Reads the typed value of the parameter or local `register` via `reader`.
+    ///
+    /// Returns `None` if `register` is not a parameter or local of the function.
+    pub fn read_local(&self, reader: &dyn RegisterReader, register: Register) -> Option<Value> {
+        let ty = self.local_type(register)?;
+        Some(reader.read_register(register).with_type(ty))
+    }
+
+    /// Reads the typed results of the call instruction at `instr` via `reader`.
+    ///
+    /// Returns `None` if there is no call instruction at `instr`.
+    ///
+    /// # Note
+    ///
+    /// The returned values are only meaningful right after the call returned.
+    pub fn read_call_results<'a>(
+        &'a self,
+        reader: &'a dyn RegisterReader,
+        instr: u32,
+    ) -> Option<impl Iterator<Item = Value> + 'a> {
+        let call_site = self.call_site(instr)?;
+        let values = call_site
+            .results
+            .iter(call_site.types.len())
+            .zip(call_site.types())
+            .map(|(register, ty)| reader.read_register(register).with_type(*ty));
+        Some(values)
+    }
+}
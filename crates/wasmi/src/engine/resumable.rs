@@ -1,15 +1,133 @@
 use super::{bytecode::RegisterSpan, Func};
 use crate::{
+    core::HostError,
     engine::Stack,
     func::CallResultsTuple,
     AsContextMut,
     Engine,
     Error,
+    Instance,
     Value,
+    WasmParams,
     WasmResults,
+    WasmTypeList,
 };
+use alloc::boxed::Box;
 use core::{fmt, marker::PhantomData, mem::replace, ops::Deref};
 
+/// A host error that pauses a resumable function invocation and yields values to the embedder.
+///
+/// # Note
+///
+/// - A host function returns a [`HostYield`] as its [`Error`] in order to yield.
+///   The embedder may then either resume the invocation with the results of the
+///   host function via [`ResumableInvocation::resume`] or re-enter the still active
+///   host function with new inputs via [`ResumableInvocation::resume_with`].
+/// - The yielded values must match the result types of the yielding host function.
+///   Otherwise the function invocation traps and can no longer be resumed.
+/// - The continuation token is opaque to Wasmi and can be queried by the re-entered
+///   host function via [`Caller::continuation`] to restore its state.
+///
+/// [`Caller::continuation`]: crate::Caller::continuation
+#[derive(Debug)]
+pub struct HostYield {
+    /// The opaque continuation token provided by the host function.
+    token: u64,
+    /// The values yielded to the embedder.
+    values: Box<[Value]>,
+}
+
+impl HostYield {
+    /// Creates a new [`HostYield`] with the continuation `token` yielding `values`.
+    pub fn new(token: u64, values: impl Into<Box<[Value]>>) -> Self {
+        Self {
+            token,
+            values: values.into(),
+        }
+    }
+
+    /// Returns the opaque continuation token of the [`HostYield`].
+    pub fn token(&self) -> u64 {
+        self.token
+    }
+
+    /// Returns the values yielded to the embedder.
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+}
+
+impl fmt::Display for HostYield {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "host function yielded {} values with continuation token {}",
+            self.values.len(),
+            self.token
+        )
+    }
+}
+
+impl HostError for HostYield {}
+
+/// A host error that suspends a resumable function invocation upon a guest controlled yield.
+///
+/// # Note
+///
+/// - This is returned by the `wasmi.yield` builtin function of type `[i32] -> []`
+///   that is defined via [`Linker::define_builtins`] if enabled via [`Config::builtin_yield`].
+/// - The embedder continues after the yield point via [`ResumableInvocation::resume`]
+///   without inputs since the `wasmi.yield` builtin function has no results.
+/// - Non-resumable calls, e.g. via [`Func::call`], fail with this error since they
+///   cannot be suspended.
+///
+/// [`Linker::define_builtins`]: crate::Linker::define_builtins
+/// [`Config::builtin_yield`]: crate::Config::builtin_yield
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GuestYield {
+    /// The user tag provided by the guest.
+    tag: i32,
+}
+
+impl GuestYield {
+    /// The name of the builtin yield function within the `"wasmi"` module.
+    pub(crate) const NAME: &'static str = "yield";
+
+    /// Creates a new [`GuestYield`] with the user `tag` provided by the guest.
+    pub(crate) fn new(tag: i32) -> Self {
+        Self { tag }
+    }
+
+    /// Returns the user tag provided by the guest.
+    pub fn tag(&self) -> i32 {
+        self.tag
+    }
+}
+
+impl fmt::Display for GuestYield {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "guest yielded with tag {} which requires a resumable call, e.g. via `Func::call_resumable`",
+            self.tag
+        )
+    }
+}
+
+impl HostError for GuestYield {}
+
+/// Determines how a [`ResumableInvocation`] is resumed.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum ResumeMode {
+    /// The inputs are the results of the host function that returned the host error.
+    Results,
+    /// The host function that returned the host error is re-entered with the inputs as parameters.
+    Reenter {
+        /// The continuation token queryable by the re-entered host function.
+        continuation: Option<u64>,
+    },
+}
+
 /// Returned by [`Engine`] methods for calling a function in a resumable way.
 ///
 /// # Note
@@ -83,12 +201,33 @@ pub struct ResumableInvocation {
     /// actual host error. This is therefore guaranteed to never
     /// be a Wasm trap.
     host_error: Error,
+    /// The parameters that the `host_func` has been called with
+    /// when it returned the `host_error`.
+    host_params: Box<[Value]>,
     /// The registers where to put provided host function results upon resumption.
     ///
     /// # Note
     ///
-    /// This is only needed for the register-machine Wasmi engine backend.
+    /// - This is only needed for the register-machine Wasmi engine backend.
+    /// - The registers are relative to the top-most [`CallFrame`] of the paused `stack`
+    ///   which is the Wasm caller of `host_func`. No pointers into the value stack are
+    ///   kept across pauses since the value stack might be reallocated in between.
+    ///   Instead the caller registers are re-derived from the live [`CallFrame`] upon
+    ///   every resumption.
+    /// - If `host_func` was called via tail call its caller [`CallFrame`] has already
+    ///   been popped and the registers belong to the [`CallFrame`] below it.
+    ///
+    /// [`CallFrame`]: crate::engine::executor::stack::CallFrame
     caller_results: RegisterSpan,
+    /// The [`Instance`] of the Wasm caller of `host_func`.
+    ///
+    /// # Note
+    ///
+    /// This is the [`Instance`] used when re-entering `host_func` since
+    /// the [`CallFrame`] of its caller might have already been popped.
+    ///
+    /// [`CallFrame`]: crate::engine::executor::stack::CallFrame
+    caller_instance: Instance,
     /// The value and call stack in use by the [`ResumableInvocation`].
     ///
     /// # Note
@@ -118,12 +257,15 @@ unsafe impl Sync for ResumableInvocation {}
 
 impl ResumableInvocation {
     /// Creates a new [`ResumableInvocation`].
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         engine: Engine,
         func: Func,
         host_func: Func,
         host_error: Error,
+        host_params: Box<[Value]>,
         caller_results: RegisterSpan,
+        caller_instance: Instance,
         stack: Stack,
     ) -> Self {
         Self {
@@ -131,17 +273,26 @@ impl ResumableInvocation {
             func,
             host_func,
             host_error,
+            host_params,
             caller_results,
+            caller_instance,
             stack,
         }
     }
 
+    /// Returns the [`Func`] that has been called to start the [`ResumableInvocation`].
+    #[cfg(feature = "tracing")]
+    pub(super) fn func(&self) -> Func {
+        self.func
+    }
+
     /// Replaces the internal stack with an empty one that has no heap allocations.
     pub(super) fn take_stack(&mut self) -> Stack {
         replace(&mut self.stack, Stack::empty())
     }
 
-    /// Updates the [`ResumableInvocation`] with the new `host_func`, `host_error` and `caller_results`.
+    /// Updates the [`ResumableInvocation`] with the new `host_func`, `host_error`, `host_params`,
+    /// `caller_results` and `caller_instance`.
     ///
     /// # Note
     ///
@@ -150,11 +301,15 @@ impl ResumableInvocation {
         &mut self,
         host_func: Func,
         host_error: Error,
+        host_params: Box<[Value]>,
         caller_results: RegisterSpan,
+        caller_instance: Instance,
     ) {
         self.host_func = host_func;
         self.host_error = host_error;
+        self.host_params = host_params;
         self.caller_results = caller_results;
+        self.caller_instance = caller_instance;
     }
 }
 
@@ -178,6 +333,17 @@ impl ResumableInvocation {
         self.host_func
     }
 
+    /// Returns the parameters that the host [`Func`] has been called with
+    /// when it returned the host error.
+    ///
+    /// # Note
+    ///
+    /// After resuming via [`ResumableInvocation::resume_with`] these are
+    /// the inputs that the host function has been re-entered with.
+    pub fn host_params(&self) -> &[Value] {
+        &self.host_params
+    }
+
     /// Returns a shared reference to the encountered host error.
     ///
     /// # Note
@@ -187,6 +353,27 @@ impl ResumableInvocation {
         &self.host_error
     }
 
+    /// Returns the [`HostYield`] if the host function yielded.
+    ///
+    /// Returns `None` if the host function returned any other host error.
+    pub fn host_yield(&self) -> Option<&HostYield> {
+        self.host_error.downcast_ref::<HostYield>()
+    }
+
+    /// Returns the [`GuestYield`] if the guest yielded via the `wasmi.yield` builtin function.
+    ///
+    /// Returns `None` if the host function returned any other host error.
+    pub fn guest_yield(&self) -> Option<&GuestYield> {
+        self.host_error.downcast_ref::<GuestYield>()
+    }
+
+    /// Returns the [`ResumeMode`] for re-entering the host function.
+    fn reenter_mode(&self) -> ResumeMode {
+        ResumeMode::Reenter {
+            continuation: self.host_yield().map(HostYield::token),
+        }
+    }
+
     /// Returns the caller results [`RegisterSpan`].
     ///
     /// # Note
@@ -196,6 +383,11 @@ impl ResumableInvocation {
         self.caller_results
     }
 
+    /// Returns the [`Instance`] of the Wasm caller of the host [`Func`].
+    pub(crate) fn caller_instance(&self) -> Instance {
+        self.caller_instance
+    }
+
     /// Resumes the call to the [`Func`] with the given inputs.
     ///
     /// The result is written back into the `outputs` buffer upon success.
@@ -229,10 +421,56 @@ impl ResumableInvocation {
             })?;
         self.engine
             .clone()
-            .resume_func(ctx.as_context_mut(), self, inputs, outputs)
+            .resume_func(
+                ctx.as_context_mut(),
+                self,
+                ResumeMode::Results,
+                inputs,
+                outputs,
+            )
             .map_err(Into::into)
             .map(ResumableCall::new)
     }
+
+    /// Resumes the call to the [`Func`] by re-entering the host function with the given inputs.
+    ///
+    /// The re-entered host function receives `inputs` as its parameters and may query
+    /// the continuation token of its [`HostYield`] via [`Caller::continuation`].
+    /// It may then either return its results to the Wasm caller or yield again.
+    ///
+    /// The result is written back into the `outputs` buffer upon success.
+    ///
+    /// # Errors
+    ///
+    /// - If the function resumption returned a Wasm [`Error`].
+    /// - If the types or the number of values in `inputs` does not match
+    ///   the types and number of parameters of the host function.
+    /// - If the number of output values does not match the expected number of
+    ///   outputs required by the called function.
+    ///
+    /// [`Caller::continuation`]: crate::Caller::continuation
+    pub fn resume_with<T>(
+        self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        inputs: &[Value],
+        outputs: &mut [Value],
+    ) -> Result<ResumableCall, Error> {
+        self.engine
+            .resolve_func_type(self.host_func().ty_dedup(ctx.as_context()), |func_type| {
+                func_type.match_params(inputs)
+            })?;
+        self.engine
+            .resolve_func_type(self.func.ty_dedup(ctx.as_context()), |func_type| {
+                func_type.match_results(outputs, false)?;
+                func_type.prepare_outputs(outputs);
+                <Result<(), Error>>::Ok(())
+            })?;
+        let mode = self.reenter_mode();
+        self.engine
+            .clone()
+            .resume_func(ctx.as_context_mut(), self, mode, inputs, outputs)
+            .map(ResumableCall::new)
+    }
 }
 
 /// Returned by calling a [`TypedFunc`] in a resumable way.
@@ -306,12 +544,52 @@ impl<Results> TypedResumableInvocation<Results> {
             .resume_func(
                 ctx.as_context_mut(),
                 self.invocation,
+                ResumeMode::Results,
                 inputs,
                 <CallResultsTuple<Results>>::default(),
             )
             .map_err(Into::into)
             .map(TypedResumableCall::new)
     }
+
+    /// Resumes the call to the [`TypedFunc`] by re-entering the host function with the given inputs.
+    ///
+    /// The re-entered host function receives `inputs` as its parameters and may query
+    /// the continuation token of its [`HostYield`] via [`Caller::continuation`].
+    /// It may then either return its results to the Wasm caller or yield again.
+    ///
+    /// # Errors
+    ///
+    /// - If the function resumption returned a Wasm [`Error`].
+    /// - If the types of `Inputs` do not match the parameter types of the host function.
+    ///
+    /// [`TypedFunc`]: [`crate::TypedFunc`]
+    /// [`Caller::continuation`]: crate::Caller::continuation
+    pub fn resume_with<T, Inputs>(
+        self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        inputs: Inputs,
+    ) -> Result<TypedResumableCall<Results>, Error>
+    where
+        Inputs: WasmParams,
+        Results: WasmResults,
+    {
+        self.engine
+            .resolve_func_type(self.host_func().ty_dedup(ctx.as_context()), |func_type| {
+                func_type.match_params(<Inputs as WasmTypeList>::types().as_ref())
+            })?;
+        let mode = self.reenter_mode();
+        self.engine
+            .clone()
+            .resume_func(
+                ctx.as_context_mut(),
+                self.invocation,
+                mode,
+                inputs,
+                <CallResultsTuple<Results>>::default(),
+            )
+            .map(TypedResumableCall::new)
+    }
 }
 
 impl<Results> Deref for TypedResumableInvocation<Results> {
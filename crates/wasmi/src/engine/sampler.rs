@@ -0,0 +1,203 @@
+use super::{CompiledFunc, EngineIdx};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use std::{
+    sync::{
+        mpsc::{self, RecvTimeoutError, Sender},
+        Mutex,
+        MutexGuard,
+        PoisonError,
+        Weak,
+    },
+    thread::{self, JoinHandle, ThreadId},
+};
+use wasmi_arena::ArenaIndex;
+
+/// The callback of a [`Sampler`] installed via [`Engine::set_sampler`].
+///
+/// [`Engine::set_sampler`]: crate::Engine::set_sampler
+pub type SamplerCallback = Box<dyn FnMut(CompiledFunc) + Send + 'static>;
+
+/// The Wasm function currently executed by a thread.
+///
+/// # Note
+///
+/// Each thread owns a single [`CurrentFunc`] that is shared with all
+/// [`Engine`]s and updated by the executor upon calls and returns if
+/// [`Config::sampling`] is enabled for the executing [`Engine`].
+///
+/// [`Engine`]: crate::Engine
+/// [`Config::sampling`]: crate::Config::sampling
+#[derive(Debug)]
+pub struct CurrentFunc {
+    /// The [`EngineIdx`] in the upper and the [`CompiledFunc`] in the lower 32 bits.
+    ///
+    /// This is [`CurrentFunc::IDLE`] while no Wasm function is executed.
+    func: AtomicU64,
+}
+
+impl CurrentFunc {
+    /// The encoding of a thread that does not execute any Wasm function.
+    pub const IDLE: u64 = u64::MAX;
+
+    /// Returns the [`CurrentFunc`] of the current thread.
+    pub fn of_current_thread() -> Arc<Self> {
+        CURRENT_FUNC.with(Arc::clone)
+    }
+
+    /// Creates a new idle [`CurrentFunc`] and registers it for the current thread.
+    fn register() -> Arc<Self> {
+        let current = Arc::new(Self {
+            func: AtomicU64::new(Self::IDLE),
+        });
+        let mut threads = lock_threads();
+        threads.retain(|(_, current)| current.strong_count() != 0);
+        threads.push((thread::current().id(), Arc::downgrade(&current)));
+        current
+    }
+
+    /// Encodes the `func` of the `engine`.
+    fn encode(engine: EngineIdx, func: CompiledFunc) -> u64 {
+        ((engine.into_usize() as u64) << 32) | func.into_usize() as u64
+    }
+
+    /// Sets the currently executed Wasm function to the `func` of the `engine`.
+    #[inline]
+    pub fn set(&self, engine: EngineIdx, func: CompiledFunc) {
+        self.func
+            .store(Self::encode(engine, func), Ordering::Relaxed);
+    }
+
+    /// Replaces the encoded currently executed Wasm function with `func` and returns the previous.
+    ///
+    /// # Note
+    ///
+    /// This is used to restore the state of outer executions after nested executions.
+    pub fn replace(&self, func: u64) -> u64 {
+        self.func.swap(func, Ordering::Relaxed)
+    }
+
+    /// Returns the currently executed Wasm function if it belongs to the `engine`.
+    pub fn get(&self, engine: EngineIdx) -> Option<CompiledFunc> {
+        let func = self.func.load(Ordering::Relaxed);
+        if func == Self::IDLE || (func >> 32) as usize != engine.into_usize() {
+            return None;
+        }
+        let func = (func & u64::from(u32::MAX)) as usize;
+        Some(CompiledFunc::from_usize(func))
+    }
+}
+
+/// Tracks the Wasm functions of an [`Engine`] executed by the current thread.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug)]
+pub struct FuncTracker {
+    /// The [`EngineIdx`] of the executing [`Engine`].
+    ///
+    /// [`Engine`]: crate::Engine
+    engine: EngineIdx,
+    /// The [`CurrentFunc`] of the current thread.
+    current: Arc<CurrentFunc>,
+}
+
+impl FuncTracker {
+    /// Creates a new [`FuncTracker`] for the `engine` on the current thread.
+    pub fn new(engine: EngineIdx) -> Self {
+        Self {
+            engine,
+            current: CurrentFunc::of_current_thread(),
+        }
+    }
+
+    /// Records that the current thread now executes `func`.
+    #[inline]
+    pub fn enter(&self, func: CompiledFunc) {
+        self.current.set(self.engine, func)
+    }
+}
+
+std::thread_local! {
+    /// The [`CurrentFunc`] of the current thread.
+    static CURRENT_FUNC: Arc<CurrentFunc> = CurrentFunc::register();
+}
+
+/// The [`CurrentFunc`] of all threads that executed Wasm with [`Config::sampling`] enabled.
+///
+/// [`Config::sampling`]: crate::Config::sampling
+static THREADS: Mutex<Vec<(ThreadId, Weak<CurrentFunc>)>> = Mutex::new(Vec::new());
+
+/// Locks the [`THREADS`] registry.
+///
+/// # Note
+///
+/// The registry is never left inconsistent, therefore a poisoned lock is recovered.
+fn lock_threads() -> MutexGuard<'static, Vec<(ThreadId, Weak<CurrentFunc>)>> {
+    THREADS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Returns the Wasm function of the `engine` currently executed by `thread` if any.
+pub fn current_func_of(engine: EngineIdx, thread: ThreadId) -> Option<CompiledFunc> {
+    lock_threads()
+        .iter()
+        .filter(|(id, _)| *id == thread)
+        .find_map(|(_, current)| current.upgrade()?.get(engine))
+}
+
+/// Calls `f` with the Wasm function of the `engine` currently executed by each thread.
+fn sample(engine: EngineIdx, f: &mut SamplerCallback) {
+    let funcs = lock_threads()
+        .iter()
+        .filter_map(|(_, current)| current.upgrade()?.get(engine))
+        .collect::<Vec<_>>();
+    // Note: the callback is called without holding the lock of the registry
+    //       so that it may execute Wasm functions itself.
+    for func in funcs {
+        f(func);
+    }
+}
+
+/// A background thread that periodically samples the Wasm functions executed by an [`Engine`].
+///
+/// # Note
+///
+/// Dropping the [`Sampler`] stops its thread and waits until it finished.
+///
+/// [`Engine`]: crate::Engine
+#[derive(Debug)]
+pub struct Sampler {
+    /// Stops the sampler thread once dropped.
+    stop: Option<Sender<()>>,
+    /// The handle of the sampler thread.
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Sampler {
+    /// Spawns a new [`Sampler`] calling `callback` every `interval` for the `engine`.
+    pub fn spawn(engine: EngineIdx, interval: Duration, mut callback: SamplerCallback) -> Self {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = thread::spawn(move || loop {
+            match stopped.recv_timeout(interval) {
+                Err(RecvTimeoutError::Timeout) => sample(engine, &mut callback),
+                Ok(()) | Err(RecvTimeoutError::Disconnected) => return,
+            }
+        });
+        Self {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            // Note: a panicking callback only ends the sampling.
+            let _ = thread.join();
+        }
+    }
+}
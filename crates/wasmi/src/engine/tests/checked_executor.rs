@@ -0,0 +1,139 @@
+//! Tests to check that corrupted bytecode panics instead of causing undefined behavior.
+//!
+//! # Note
+//!
+//! These tests are only run with the `checked-executor` crate feature enabled.
+
+use crate::{
+    engine::{
+        bytecode::{BranchOffset, BranchOffset16, Instruction, Register},
+        CompiledFuncEntity,
+    },
+    Engine,
+    Error,
+    Linker,
+    Module,
+    Store,
+};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Executes the exported `(i32) -> i32` function whose bytecode has been replaced by `instrs`.
+fn execute_corrupted(
+    len_registers: u16,
+    instrs: impl IntoIterator<Item = Instruction>,
+) -> Result<i32, Error> {
+    let engine = Engine::default();
+    let wasm =
+        wat::parse_str(r#"(module (func (export "f") (param i32) (result i32) (local.get 0)))"#)
+            .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let (_, compiled_func) = module.internal_funcs().next().unwrap();
+    engine.replace_func(
+        compiled_func,
+        CompiledFuncEntity::new(len_registers, instrs, []),
+    );
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<i32, i32>(&store, "f")
+        .unwrap()
+        .call(&mut store, 1)
+}
+
+#[test]
+fn uncorrupted_works() {
+    let result = execute_corrupted(1, [Instruction::return_reg(Register::from_i16(0))]);
+    assert_eq!(result.unwrap(), 1);
+}
+
+#[test]
+#[should_panic(expected = "accesses the value stack out of bounds")]
+fn register_above_bounds() {
+    let _ = execute_corrupted(1, [Instruction::return_reg(Register::from_i16(i16::MAX))]);
+}
+
+#[test]
+#[should_panic(expected = "accesses the value stack out of bounds")]
+fn register_below_bounds() {
+    let _ = execute_corrupted(1, [Instruction::return_reg(Register::from_i16(i16::MIN))]);
+}
+
+#[test]
+#[should_panic(expected = "instruction pointer out of bounds")]
+fn branch_out_of_bounds() {
+    let _ = execute_corrupted(
+        1,
+        [
+            Instruction::branch(BranchOffset::from(100)),
+            Instruction::return_reg(Register::from_i16(0)),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "instruction pointer out of bounds")]
+fn missing_return() {
+    let _ = execute_corrupted(1, [Instruction::copy(0, 0)]);
+}
+
+/// A simple deterministic pseudo random number generator.
+struct XorShift(u64);
+
+impl XorShift {
+    /// Returns the next pseudo random number within `range`.
+    fn next_in(&mut self, range: core::ops::RangeInclusive<i32>) -> i32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        let len = (range.end() - range.start() + 1) as u64;
+        range.start() + (self.0 % len) as i32
+    }
+
+    /// Returns a pseudo random [`Register`] that may be out of bounds of the value stack.
+    fn register(&mut self) -> Register {
+        Register::from_i16(self.next_in(-300..=300) as i16)
+    }
+
+    /// Returns a pseudo random [`Instruction`].
+    ///
+    /// # Note
+    ///
+    /// Only forward branches are generated so that the execution always terminates.
+    fn instr(&mut self) -> Instruction {
+        match self.next_in(0..=6) {
+            0 => Instruction::return_reg(self.register()),
+            1 => Instruction::copy(self.register(), self.register()),
+            2 => Instruction::copy_imm32(self.register(), self.next_in(-10..=10)),
+            3 => Instruction::i32_add(self.register(), self.register(), self.register()),
+            4 => Instruction::i32_add_imm16(
+                self.register(),
+                self.register(),
+                self.next_in(-10..=10) as i16,
+            ),
+            5 => Instruction::branch(BranchOffset::from(self.next_in(1..=8))),
+            _ => Instruction::branch_i32_eq_imm(
+                self.register(),
+                self.next_in(-1..=1) as i16,
+                BranchOffset16::from(self.next_in(1..=8) as i16),
+            ),
+        }
+    }
+}
+
+#[test]
+fn corrupted_bytecode_fuzzing() {
+    let mut rng = XorShift(0x2545_F491_4F6C_DD1D);
+    for _ in 0..500 {
+        let len_registers = rng.next_in(1..=16) as u16;
+        let len_instrs = rng.next_in(1..=8);
+        let instrs = (0..len_instrs).map(|_| rng.instr()).collect::<Vec<_>>();
+        // Note: Corrupted bytecode may either execute, trap or panic but never cause UB.
+        let _ = catch_unwind(AssertUnwindSafe(|| {
+            execute_corrupted(len_registers, instrs)
+        }));
+    }
+}
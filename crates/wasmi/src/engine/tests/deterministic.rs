@@ -0,0 +1,83 @@
+//! Tests that [`Engine`]s with the same [`Config`] compile identical Wasmi bytecode.
+
+use crate::{
+    engine::{bytecode::Instruction, CompiledFunc},
+    Config,
+    Engine,
+    Module,
+};
+use wasmi_core::UntypedValue;
+
+/// A Wasm module with duplicate function types, imports and function local constants.
+const WAT: &str = r#"
+    (module
+        (type $a (func (param i32) (result i32)))
+        (type $b (func (param i64 f64)))
+        (type $a2 (func (param i32) (result i32)))
+        (import "env" "zeta" (func (type $b)))
+        (import "host" "alpha" (func (type $a2)))
+        (import "env" "beta" (func (type $a)))
+        (table 2 funcref)
+        (elem (i32.const 0) $id $indirect)
+        (func $id (type $a2)
+            (local.get 0)
+        )
+        (func $indirect (type $a)
+            (call_indirect (type $a2)
+                (i32.add (local.get 0) (i32.const 100000))
+                (i32.const 0)
+            )
+        )
+        (func (export "run") (param i64) (result i64)
+            (call 0 (i64.const 1) (f64.const 2.5))
+            (drop (call 1 (i32.const 3)))
+            (drop (call $indirect (call 2 (i32.const 4))))
+            (block $b
+                (br_table $b $b (i32.wrap_i64 (local.get 0)))
+            )
+            (i64.mul (local.get 0) (i64.const 1000000000000))
+        )
+    )
+"#;
+
+/// The compiled instructions and function local constants of a single function.
+type CompiledBytecode = (Vec<Instruction>, Vec<UntypedValue>);
+
+/// Returns the compiled instructions and function local constants of `func`.
+fn bytecode_of(engine: &Engine, func: CompiledFunc) -> CompiledBytecode {
+    let instrs = (0..)
+        .map_while(|index| engine.resolve_instr(func, index).unwrap())
+        .collect();
+    let consts = (0..)
+        .map_while(|index| engine.get_func_const(func, index).unwrap())
+        .collect();
+    (instrs, consts)
+}
+
+/// Compiles [`WAT`] with a fresh [`Engine`] using `config`.
+///
+/// Returns the [`Module`] and the compiled bytecode of all of its internal functions.
+fn compile(config: &Config) -> (Module, Vec<CompiledBytecode>) {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let bytecode = module
+        .internal_funcs()
+        .map(|(_, func)| bytecode_of(&engine, func))
+        .collect();
+    (module, bytecode)
+}
+
+#[test]
+fn same_config_same_bytecode() {
+    let mut config = Config::default();
+    config.deterministic_only(true).consume_fuel(true);
+    let (a, expected) = compile(&config);
+    assert!(expected.iter().all(|(instrs, _)| !instrs.is_empty()));
+    assert!(expected.iter().any(|(_, consts)| !consts.is_empty()));
+    for _ in 0..10 {
+        let (b, bytecode) = compile(&config);
+        assert!(!Engine::same(a.engine(), b.engine()));
+        assert!(Engine::same_config_fingerprint(a.engine(), b.engine()));
+        assert_eq!(bytecode, expected);
+    }
+}
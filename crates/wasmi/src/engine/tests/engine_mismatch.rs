@@ -0,0 +1,71 @@
+//! Tests for the rejection of Wasm functions of another [`Engine`] than the one of the [`Store`].
+//!
+//! # Note
+//!
+//! Modules compiled by another [`Engine`] cannot be instantiated into a [`Store`].
+//! Therefore the tests contrive such a Wasm function from its raw parts.
+
+use crate::{
+    core::ValueType,
+    errors::{ErrorKind, FuncError},
+    func::WasmFuncEntity,
+    Engine,
+    Error,
+    Func,
+    FuncType,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+
+/// Returns a Wasm function of `store` whose function type and body belong to another [`Engine`].
+fn foreign_func(store: &mut Store<()>) -> Func {
+    let wasm =
+        wat::parse_str(r#"(module (func (export "f") (result i32) (i32.const 7)))"#).unwrap();
+    let other = Engine::default();
+    let foreign = Module::new(&other, &wasm[..]).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(store.engine())
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap();
+    let ty = other.alloc_func_type(FuncType::new([], [ValueType::I32]));
+    let body = foreign.get_compiled_func(0).unwrap();
+    store
+        .inner
+        .alloc_func(WasmFuncEntity::new(ty, body, instance).into())
+}
+
+/// Returns `true` if `error` reports a [`FuncError::EngineMismatch`].
+fn is_engine_mismatch(error: &Error) -> bool {
+    matches!(error.kind(), ErrorKind::Func(FuncError::EngineMismatch))
+}
+
+#[test]
+fn foreign_func_is_rejected() {
+    let mut store = Store::new(&Engine::default(), ());
+    let func = foreign_func(&mut store);
+    let mut results = [Value::I32(0)];
+    let error = func.call(&mut store, &[], &mut results).unwrap_err();
+    assert!(is_engine_mismatch(&error));
+    assert_eq!(
+        error.to_string(),
+        "function does not belong to the engine of the store"
+    );
+    let error = func.typed::<(), i32>(&store).unwrap_err();
+    assert!(is_engine_mismatch(&error));
+}
+
+#[test]
+#[cfg(feature = "resumable")]
+fn foreign_func_is_rejected_for_resumable_calls() {
+    let mut store = Store::new(&Engine::default(), ());
+    let func = foreign_func(&mut store);
+    let mut results = [Value::I32(0)];
+    let error = func
+        .call_resumable(&mut store, &[], &mut results)
+        .unwrap_err();
+    assert!(is_engine_mismatch(&error));
+}
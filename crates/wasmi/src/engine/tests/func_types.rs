@@ -0,0 +1,103 @@
+//! Tests for the deterministic indices of deduplicated function types.
+
+use crate::{core::TrapCode, Engine, Func, Linker, Module, Store};
+
+/// A Wasm module with duplicate function types that calls its functions indirectly.
+///
+/// - The function types `$a` and `$a2` are structurally equal.
+/// - `call_a2` calls the function at `index` of the table with type `$a2`.
+const WAT: &str = r#"
+    (module
+        (type $a (func (param i32) (result i32)))
+        (type $b (func))
+        (type $a2 (func (param i32) (result i32)))
+        (type $c (func (result i64)))
+        (import "env" "f" (func (type $c)))
+        (table (export "table") 3 funcref)
+        (elem (i32.const 0) $id $nop $inc)
+        (func $id (type $a)
+            (local.get 0)
+        )
+        (func $nop (type $b))
+        (func $inc (type $a2)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func (export "call_a2") (param $value i32) (param $index i32) (result i32)
+            (call_indirect (type $a2) (local.get $value) (local.get $index))
+        )
+    )
+"#;
+
+/// Compiles [`WAT`] with a fresh [`Engine`].
+fn compile() -> Module {
+    let engine = Engine::default();
+    Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap()
+}
+
+/// Returns the indices of the function types of the type section of `module`
+/// followed by the indices of the function types of its internal functions.
+fn func_type_indices(module: &Module) -> (Vec<u32>, Vec<u32>) {
+    let engine = module.engine();
+    let func_types = (0..)
+        .map_while(|index| module.get_func_type(index))
+        .map(|func_type| engine.func_type_index(&func_type))
+        .collect();
+    let funcs = module
+        .internal_funcs()
+        .map(|(func_type, _)| engine.func_type_index(&func_type))
+        .collect();
+    (func_types, funcs)
+}
+
+#[test]
+fn func_type_indices_are_first_seen_order() {
+    let module = compile();
+    let (func_types, funcs) = func_type_indices(&module);
+    // The last function type is the implicit type of `call_a2`.
+    assert_eq!(func_types, [0, 1, 0, 2, 3]);
+    assert_eq!(funcs, [0, 1, 0, 3]);
+}
+
+#[test]
+fn func_type_indices_are_deterministic() {
+    let expected = func_type_indices(&compile());
+    for _ in 0..10 {
+        assert_eq!(func_type_indices(&compile()), expected);
+    }
+}
+
+#[test]
+fn call_indirect_with_deduplicated_func_types() {
+    for _ in 0..2 {
+        let module = compile();
+        let mut store = Store::new(module.engine(), ());
+        let mut linker = <Linker<()>>::new(module.engine());
+        linker.func_wrap("env", "f", || 0_i64).unwrap();
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let call_a2 = instance
+            .get_typed_func::<(i32, u32), i32>(&store, "call_a2")
+            .unwrap();
+        // Calls the same call site multiple times so that the cached callee is used.
+        for _ in 0..2 {
+            // `$id` is declared with `$a` which is structurally equal to `$a2`.
+            assert_eq!(call_a2.call(&mut store, (5, 0)).unwrap(), 5);
+            assert_eq!(call_a2.call(&mut store, (5, 2)).unwrap(), 6);
+            let error = call_a2.call(&mut store, (5, 1)).unwrap_err();
+            assert_eq!(error.as_trap_code(), Some(TrapCode::BadSignature));
+        }
+        // Replacing a cached callee with a function of another type fails the signature check.
+        let table = instance.get_table(&store, "table").unwrap();
+        let nop = table.get(&store, 1).unwrap();
+        table.set(&mut store, 0, nop).unwrap();
+        let error = call_a2.call(&mut store, (5, 0)).unwrap_err();
+        assert_eq!(error.as_trap_code(), Some(TrapCode::BadSignature));
+        // Host functions with structurally equal types pass the signature check.
+        let host = Func::wrap(&mut store, |value: i32| value * 2);
+        table.set(&mut store, 0, host.into()).unwrap();
+        assert_eq!(call_a2.call(&mut store, (5, 0)).unwrap(), 10);
+    }
+}
@@ -1 +1,9 @@
+#[cfg(feature = "checked-executor")]
+mod checked_executor;
+mod deterministic;
+mod engine_mismatch;
+mod func_types;
 mod host_calls;
+mod raw_host_calls;
+mod reentrance;
+mod translation_limits;
@@ -0,0 +1,194 @@
+//! Tests for raw host functions defined via [`Linker::func_wrap_raw`].
+//!
+//! # Note
+//!
+//! These tests are also run under `miri` to check the soundness of
+//! the pointer handoff between the executor and raw host functions.
+
+use crate::{
+    core::{TrapCode, UntypedValue, ValueType},
+    Engine,
+    FuncType,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Raw host function adding its two `i32` parameters.
+///
+/// # Safety
+///
+/// Must be called with a buffer holding 2 `i32` parameters.
+unsafe fn raw_add(params_results: *mut UntypedValue, len: usize) -> Result<(), TrapCode> {
+    assert_eq!(len, 2);
+    let lhs = i32::from(unsafe { *params_results });
+    let rhs = i32::from(unsafe { *params_results.add(1) });
+    unsafe { *params_results = UntypedValue::from(lhs.wrapping_add(rhs)) };
+    Ok(())
+}
+
+/// Raw host function returning its `i64` parameter times 1, 2 and 3.
+///
+/// # Safety
+///
+/// Must be called with a buffer large enough for 3 `i64` results.
+unsafe fn raw_spread(params_results: *mut UntypedValue, len: usize) -> Result<(), TrapCode> {
+    assert_eq!(len, 3);
+    let buffer = unsafe { core::slice::from_raw_parts_mut(params_results, len) };
+    let value = i64::from(buffer[0]);
+    buffer[0] = UntypedValue::from(value);
+    buffer[1] = UntypedValue::from(value * 2);
+    buffer[2] = UntypedValue::from(value * 3);
+    Ok(())
+}
+
+/// Raw host function that traps if its `i32` parameter is zero.
+///
+/// # Safety
+///
+/// Must be called with a buffer holding 1 `i32` parameter.
+unsafe fn raw_check(params_results: *mut UntypedValue, len: usize) -> Result<(), TrapCode> {
+    assert_eq!(len, 1);
+    if i32::from(unsafe { *params_results }) == 0 {
+        return Err(TrapCode::UnreachableCodeReached);
+    }
+    Ok(())
+}
+
+/// The Wasm module calling the raw host functions.
+const WAT: &str = r#"
+    (module
+        (import "env" "add" (func $add (param i32 i32) (result i32)))
+        (import "env" "spread" (func $spread (param i64) (result i64 i64 i64)))
+        (import "env" "check" (func $check (param i32)))
+        (export "spread_import" (func $spread))
+        (func (export "sum") (param $n i32) (result i32)
+            (local $acc i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $acc (call $add (local.get $acc) (local.get $n)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+        (func (export "spread") (param i64) (result i64 i64 i64)
+            (call $spread (local.get 0))
+        )
+        (func (export "check") (param i32)
+            (call $check (local.get 0))
+        )
+    )
+"#;
+
+/// Returns a [`Linker`] defining the raw host functions imported by [`WAT`].
+fn linker(engine: &Engine) -> Linker<()> {
+    let mut linker = <Linker<()>>::new(engine);
+    // Safety: The raw host functions uphold the safety contract for their function types.
+    unsafe {
+        linker
+            .func_wrap_raw(
+                "env",
+                "add",
+                FuncType::new([ValueType::I32; 2], [ValueType::I32]),
+                raw_add,
+            )
+            .unwrap()
+            .func_wrap_raw(
+                "env",
+                "spread",
+                FuncType::new([ValueType::I64], [ValueType::I64; 3]),
+                raw_spread,
+            )
+            .unwrap()
+            .func_wrap_raw("env", "check", FuncType::new([ValueType::I32], []), raw_check)
+            .unwrap();
+    }
+    linker
+}
+
+/// Instantiates [`WAT`] using `linker`.
+fn instantiate(linker: &Linker<()>) -> (Store<()>, Instance) {
+    let engine = linker.engine();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(engine, &wasm[..]).unwrap();
+    let mut store = Store::new(engine, ());
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Instantiates [`WAT`] with the raw host functions of [`linker`].
+fn setup() -> (Store<()>, Instance) {
+    instantiate(&linker(&Engine::default()))
+}
+
+#[test]
+fn raw_host_call_from_wasm() {
+    let (mut store, instance) = setup();
+    let sum = instance.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    assert_eq!(sum.call(&mut store, 0).unwrap(), 0);
+    assert_eq!(sum.call(&mut store, 10).unwrap(), 55);
+}
+
+#[test]
+fn raw_host_call_with_more_results_than_params() {
+    let (mut store, instance) = setup();
+    let spread = instance
+        .get_typed_func::<i64, (i64, i64, i64)>(&store, "spread")
+        .unwrap();
+    assert_eq!(spread.call(&mut store, 5).unwrap(), (5, 10, 15));
+    assert_eq!(spread.call(&mut store, -1).unwrap(), (-1, -2, -3));
+}
+
+#[test]
+fn raw_host_call_trap() {
+    let (mut store, instance) = setup();
+    let check = instance.get_typed_func::<i32, ()>(&store, "check").unwrap();
+    check.call(&mut store, 1).unwrap();
+    let error = check.call(&mut store, 0).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    // The store remains usable after the trap.
+    check.call(&mut store, 1).unwrap();
+}
+
+#[test]
+fn raw_host_call_from_host() {
+    let (mut store, instance) = setup();
+    // Note: The re-exported import calls the raw host function without a Wasm caller.
+    let spread = instance
+        .get_typed_func::<i64, (i64, i64, i64)>(&store, "spread_import")
+        .unwrap();
+    assert_eq!(spread.call(&mut store, 2).unwrap(), (2, 4, 6));
+}
+
+#[test]
+fn raw_host_call_intercepted() {
+    let engine = Engine::default();
+    let mut linker = linker(&engine);
+    let count = Arc::new(AtomicUsize::new(0));
+    linker.set_interceptor({
+        let count = count.clone();
+        move |_info, params, next, results| {
+            count.fetch_add(1, Ordering::SeqCst);
+            next(params, results)?;
+            if let [Value::I32(result)] = results {
+                *result += 1;
+            }
+            Ok(())
+        }
+    });
+    let (mut store, instance) = instantiate(&linker);
+    let sum = instance.get_typed_func::<i32, i32>(&store, "sum").unwrap();
+    assert_eq!(sum.call(&mut store, 10).unwrap(), 65);
+    assert_eq!(count.load(Ordering::SeqCst), 10);
+}
@@ -0,0 +1,62 @@
+//! Tests for the detection of improperly nested executions on the same [`Store`].
+//!
+//! # Note
+//!
+//! Safe Rust cannot call into a [`Store`] that is executing other than via the
+//! [`Caller`] of a host function. Therefore the tests contrive such an entry by
+//! registering an execution that is not calling a host function.
+//!
+//! [`Caller`]: crate::Caller
+
+use crate::{
+    errors::{ErrorKind, FuncError},
+    Engine,
+    Error,
+    Func,
+    Store,
+};
+
+/// Returns `true` if `error` reports a [`FuncError::StoreAlreadyExecuting`].
+fn is_already_executing(error: &Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::StoreAlreadyExecuting)
+    )
+}
+
+#[test]
+fn improper_entry_is_rejected() {
+    let mut store = Store::new(&Engine::default(), 0_i32);
+    let func = Func::wrap(&mut store, |x: i32| x + 1);
+    let typed = func.typed::<i32, i32>(&store).unwrap();
+    store.inner.enter_execution().unwrap();
+    assert!(store.is_executing());
+    let error = typed.call(&mut store, 1).unwrap_err();
+    assert!(is_already_executing(&error));
+    assert_eq!(
+        error.to_string(),
+        "store is already executing; use Caller to re-enter"
+    );
+    let error = store
+        .inner
+        .enter_execution()
+        .expect_err("nested execution must be rejected");
+    assert!(matches!(error, FuncError::StoreAlreadyExecuting));
+    store.inner.exit_execution();
+    assert!(!store.is_executing());
+    assert_eq!(typed.call(&mut store, 1).unwrap(), 2);
+}
+
+#[test]
+#[cfg(feature = "resumable")]
+fn improper_entry_is_rejected_for_resumable_calls() {
+    let mut store = Store::new(&Engine::default(), 0_i32);
+    let func = Func::wrap(&mut store, |x: i32| x + 1);
+    let typed = func.typed::<i32, i32>(&store).unwrap();
+    store.inner.enter_execution().unwrap();
+    let error = typed.call_resumable(&mut store, 1).unwrap_err();
+    assert!(is_already_executing(&error));
+    store.inner.exit_execution();
+    let result = typed.call_resumable(&mut store, 1).unwrap();
+    assert!(matches!(result, crate::TypedResumableCall::Finished(2)));
+}
@@ -0,0 +1,386 @@
+//! Tests for the translation limits of the [`Config`] and the reuse of translation allocations.
+
+use crate::{
+    engine::TranslationError,
+    errors::ErrorKind,
+    CompilationMode,
+    Config,
+    Engine,
+    Error,
+    Linker,
+    Module,
+    ModuleLimit,
+    ModuleLimits,
+    Store,
+};
+use assert_matches::assert_matches;
+
+/// Compiles the Wasm module given in `wat` format with the `engine`.
+fn compile(engine: &Engine, wat: &str) -> Result<Module, Error> {
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(engine, &wasm[..])
+}
+
+#[test]
+fn max_function_body_size() {
+    let mut config = Config::default();
+    // Note: the body of `(func)` consists of its empty locals declaration and its `end`.
+    config.max_function_body_size(2);
+    let engine = Engine::new(&config);
+    assert!(compile(&engine, "(module (func))").is_ok());
+    assert_matches!(
+        compile(&engine, "(module (func) (func nop))")
+            .unwrap_err()
+            .kind(),
+        ErrorKind::Translation(TranslationError::LimitExceeded {
+            which: ModuleLimit::FunctionBodyBytes,
+            limit: 2,
+            found: 3,
+        })
+    );
+}
+
+#[test]
+fn max_locals() {
+    let mut config = Config::default();
+    config.max_locals(3);
+    let engine = Engine::new(&config);
+    // Note: function parameters do not count as local variables.
+    assert!(compile(
+        &engine,
+        "(module (func (param i32 i32 i32 i32) (local i32 i64) (local f32)))"
+    )
+    .is_ok());
+    assert_matches!(
+        compile(&engine, "(module (func (local i32 i64) (local f32 f64)))")
+            .unwrap_err()
+            .kind(),
+        ErrorKind::Translation(TranslationError::LimitExceeded {
+            which: ModuleLimit::Locals,
+            limit: 3,
+            found: 4,
+        })
+    );
+}
+
+#[test]
+fn max_locals_lazy_translation() {
+    let mut config = Config::default();
+    config
+        .max_locals(1)
+        .compilation_mode(CompilationMode::LazyTranslation);
+    let engine = Engine::new(&config);
+    let module = compile(&engine, r#"(module (func (export "f") (local i32 i32)))"#).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func::<(), ()>(&store, "f").unwrap();
+    let error = func.call(&mut store, ()).unwrap_err();
+    let ErrorKind::LazyCompilationFailed { source, .. } = error.kind() else {
+        panic!("expected lazy compilation failure but found: {error:?}")
+    };
+    assert_matches!(
+        source.kind(),
+        ErrorKind::Translation(TranslationError::LimitExceeded {
+            which: ModuleLimit::Locals,
+            limit: 1,
+            found: 2,
+        })
+    );
+}
+
+/// Returns the default [`ModuleLimits`] with the limit for `which` set to `limit`.
+fn limits_with(which: ModuleLimit, limit: u32) -> ModuleLimits {
+    let mut limits = ModuleLimits::default();
+    let field = match which {
+        ModuleLimit::Types => &mut limits.max_types,
+        ModuleLimit::Functions => &mut limits.max_functions,
+        ModuleLimit::Imports => &mut limits.max_imports,
+        ModuleLimit::Globals => &mut limits.max_globals,
+        ModuleLimit::Tables => &mut limits.max_tables,
+        ModuleLimit::Memories => &mut limits.max_memories,
+        ModuleLimit::ElementSegments => &mut limits.max_element_segments,
+        ModuleLimit::DataSegments => &mut limits.max_data_segments,
+        ModuleLimit::BrTableTargets => &mut limits.max_br_table_targets,
+        ModuleLimit::Locals => &mut limits.max_locals,
+        ModuleLimit::FunctionBodyBytes => &mut limits.max_function_body_bytes,
+        ModuleLimit::NameLength => &mut limits.max_name_length,
+    };
+    *field = limit;
+    limits
+}
+
+/// Asserts that compiling `wasm` fails since it exceeds the limit of `which` set to `limit`.
+fn assert_limit_exceeded(wasm: &[u8], which: ModuleLimit, limit: u32, found: u32) {
+    let mut config = Config::default();
+    config.set_module_limits(limits_with(which, limit));
+    let error = Module::new(&Engine::new(&config), wasm).unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Translation(TranslationError::LimitExceeded {
+            which: actual_which,
+            limit: actual_limit,
+            found: actual_found,
+        }) if *actual_which == which && *actual_limit == limit && *actual_found == found,
+        "unexpected error for {which}: {error}"
+    );
+}
+
+#[test]
+fn module_limits_exceeded() {
+    let cases = [
+        (ModuleLimit::Types, "(type (func)) (type (func (param i32)))"),
+        (ModuleLimit::Functions, "(func) (func)"),
+        (
+            ModuleLimit::Imports,
+            r#"(import "env" "f" (func)) (import "env" "g" (global i32))"#,
+        ),
+        (
+            ModuleLimit::Globals,
+            "(global i32 (i32.const 0)) (global i64 (i64.const 0))",
+        ),
+        (ModuleLimit::Tables, "(table 0 funcref) (table 0 externref)"),
+        (
+            ModuleLimit::ElementSegments,
+            "(table 1 funcref) (elem (i32.const 0) func) (elem func)",
+        ),
+        (
+            ModuleLimit::DataSegments,
+            r#"(memory 1) (data (i32.const 0) "a") (data "b")"#,
+        ),
+        (
+            ModuleLimit::BrTableTargets,
+            "(func (block (br_table 0 0 0 (i32.const 0))))",
+        ),
+        (
+            ModuleLimit::BrTableTargets,
+            "(func (block (unreachable) (br_table 0 0 0)))",
+        ),
+    ];
+    for (which, wat) in cases {
+        let wasm = wat::parse_str(format!("(module {wat})")).unwrap();
+        let mut config = Config::default();
+        config.set_module_limits(limits_with(which, 2));
+        assert!(Module::new(&Engine::new(&config), &wasm[..]).is_ok());
+        assert_limit_exceeded(&wasm, which, 1, 2);
+    }
+    // Note: Wasmi does not support multiple linear memories.
+    let wasm = wat::parse_str("(module (memory 1))").unwrap();
+    assert_limit_exceeded(&wasm, ModuleLimit::Memories, 0, 1);
+}
+
+#[test]
+fn max_name_length() {
+    let cases = [
+        r#"(import "abcd" "f" (func))"#,
+        r#"(import "f" "abcd" (func))"#,
+        r#"(func (export "abcd"))"#,
+    ];
+    for wat in cases {
+        let wasm = wat::parse_str(format!("(module {wat})")).unwrap();
+        let mut config = Config::default();
+        config.max_name_length(4);
+        assert!(Module::new(&Engine::new(&config), &wasm[..]).is_ok());
+        assert_limit_exceeded(&wasm, ModuleLimit::NameLength, 3, 4);
+    }
+    // Note: the length of a name is measured in bytes of its UTF-8 encoding.
+    let wasm = wat::parse_str(r#"(module (func (export "äö")))"#).unwrap();
+    assert_limit_exceeded(&wasm, ModuleLimit::NameLength, 3, 4);
+    let error = Module::new(
+        &Engine::new(Config::default().max_name_length(3)),
+        &wasm[..],
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "encountered 4 name bytes exceeding the configured limit of 3"
+    );
+}
+
+/// Appends the LEB128 encoding of `value` to `bytes`.
+fn write_leb128(bytes: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Returns a Wasm module with a single section with `id` that declares `count` entries.
+///
+/// The section contains no entries regardless of `count`.
+fn section_with_count(id: u8, count: u32) -> Vec<u8> {
+    let mut payload = Vec::new();
+    write_leb128(&mut payload, count);
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    wasm.push(id);
+    write_leb128(&mut wasm, payload.len() as u32);
+    wasm.extend(payload);
+    wasm
+}
+
+#[test]
+fn module_limits_checked_before_allocation() {
+    // Note: allocating for `u32::MAX` entries aborts the process which would fail this test.
+    let cases = [
+        (1, ModuleLimit::Types),
+        (2, ModuleLimit::Imports),
+        (3, ModuleLimit::Functions),
+        (4, ModuleLimit::Tables),
+        (5, ModuleLimit::Memories),
+        (6, ModuleLimit::Globals),
+        (9, ModuleLimit::ElementSegments),
+        (11, ModuleLimit::DataSegments),
+        (12, ModuleLimit::DataSegments),
+    ];
+    for (id, which) in cases {
+        let wasm = section_with_count(id, u32::MAX);
+        assert_limit_exceeded(&wasm, which, 1, u32::MAX);
+    }
+}
+
+#[test]
+fn default_module_limits() {
+    let limits = ModuleLimits::default();
+    for which in [
+        ModuleLimit::Types,
+        ModuleLimit::Functions,
+        ModuleLimit::Imports,
+        ModuleLimit::Globals,
+        ModuleLimit::Tables,
+        ModuleLimit::Memories,
+        ModuleLimit::ElementSegments,
+        ModuleLimit::DataSegments,
+        ModuleLimit::BrTableTargets,
+        ModuleLimit::Locals,
+        ModuleLimit::FunctionBodyBytes,
+        ModuleLimit::NameLength,
+    ] {
+        assert!(limits.get(which) < u32::MAX, "{which} limit must be finite");
+    }
+    let wasm = section_with_count(1, limits.max_types + 1);
+    assert_matches!(
+        Module::new(&Engine::default(), &wasm[..]).unwrap_err().kind(),
+        ErrorKind::Translation(TranslationError::LimitExceeded {
+            which: ModuleLimit::Types,
+            ..
+        })
+    );
+}
+
+/// Returns a Wasm module where function 1 has `len_locals` local variables.
+fn many_locals_wat(len_locals: usize) -> String {
+    let locals = "i64 ".repeat(len_locals);
+    format!(
+        r#"
+        (module
+            (func (param i32) (result i32) (local.get 0))
+            (func (export "f") (local {locals})
+                (local.set 0 (i64.add (local.get 1) (local.get 2)))
+            )
+        )
+        "#
+    )
+}
+
+#[test]
+fn len_registers() {
+    let engine = Engine::default();
+    let module = compile(&engine, &many_locals_wat(100)).unwrap();
+    let func = module.get_compiled_func(0).unwrap();
+    assert_eq!(engine.len_registers(func).unwrap(), 1);
+    let func = module.get_compiled_func(1).unwrap();
+    assert!(engine.len_registers(func).unwrap() >= 100);
+}
+
+#[test]
+fn max_registers_per_function() {
+    let wat = many_locals_wat(100);
+    let len_registers = {
+        let engine = Engine::default();
+        let module = compile(&engine, &wat).unwrap();
+        let func = module.get_compiled_func(1).unwrap();
+        u32::from(engine.len_registers(func).unwrap())
+    };
+    let mut config = Config::default();
+    config.max_registers_per_function(len_registers);
+    assert!(compile(&Engine::new(&config), &wat).is_ok());
+    config.max_registers_per_function(len_registers - 1);
+    let error = compile(&Engine::new(&config), &wat).unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Translation(TranslationError::TooManyRegisters {
+            func: 1,
+            len_registers: actual,
+            limit,
+        }) if *actual == len_registers && *limit == len_registers - 1
+    );
+    let message = error.to_string();
+    assert!(message.contains("function 1"), "{message}");
+    assert!(message.contains(&len_registers.to_string()), "{message}");
+}
+
+#[test]
+fn max_registers_per_function_lazy_translation() {
+    let mut config = Config::default();
+    config
+        .max_registers_per_function(50)
+        .compilation_mode(CompilationMode::LazyTranslation);
+    let engine = Engine::new(&config);
+    let module = compile(&engine, &many_locals_wat(100)).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func::<(), ()>(&store, "f").unwrap();
+    let error = func.call(&mut store, ()).unwrap_err();
+    let ErrorKind::LazyCompilationFailed { source, .. } = error.kind() else {
+        panic!("expected lazy compilation failure but found: {error:?}")
+    };
+    assert_matches!(
+        source.kind(),
+        ErrorKind::Translation(TranslationError::TooManyRegisters {
+            func: 1,
+            limit: 50,
+            ..
+        })
+    );
+}
+
+#[test]
+fn translations_reuse_allocations() {
+    const WAT: &str = r#"
+        (module
+            (func (param i32) (result i32)
+                (block
+                    (br_if 0 (local.get 0))
+                    (local.set 0 (i32.add (local.get 0) (i32.const 1)))
+                )
+                (local.get 0)
+            )
+            (func (param i64 i64) (result i64)
+                (i64.mul (local.get 0) (local.get 1))
+            )
+            (func (result i32)
+                (return (i32.const 1))
+                (i32.const 2)
+            )
+        )
+    "#;
+    let mut config = Config::default();
+    config.optimization_level(1);
+    let engine = Engine::new(&config);
+    for _ in 0..3 {
+        compile(&engine, WAT).unwrap();
+    }
+    assert_eq!(engine.inner.allocs.lock().len_created_translation, 1);
+}
@@ -1,3 +1,5 @@
+use crate::{engine::ModuleLimit, ExternKind};
+use alloc::boxed::Box;
 use core::fmt::{self, Display};
 
 /// An error that may occur upon parsing, validating and translating Wasm.
@@ -27,8 +29,43 @@ pub enum TranslationError {
     TooManyFunctionResults,
     /// Tried to define a function with too many function parameters.
     TooManyFunctionParams,
-    /// The function failed to compiled lazily.
-    LazyCompilationFailed,
+    /// Encountered a Wasm module or function exceeding one of the configured [`ModuleLimits`].
+    ///
+    /// [`ModuleLimits`]: crate::ModuleLimits
+    LimitExceeded {
+        /// The kind of the exceeded limit.
+        which: ModuleLimit,
+        /// The configured value of the exceeded limit.
+        limit: u32,
+        /// The encountered number of entities or bytes.
+        found: u32,
+    },
+    /// Tried to translate a function with more registers than [`Config::max_registers_per_function`].
+    ///
+    /// [`Config::max_registers_per_function`]: crate::Config::max_registers_per_function
+    TooManyRegisters {
+        /// The index of the function within its Wasm module.
+        func: u32,
+        /// The number of registers required by the function.
+        len_registers: u32,
+        /// The configured maximum number of registers of a function.
+        limit: u32,
+    },
+    /// Encountered a Wasm module exporting two items under the same name.
+    DuplicateExport {
+        /// The duplicated export name.
+        name: Box<str>,
+        /// The kind and index of the item exported first under `name`.
+        first: (ExternKind, u32),
+        /// The kind and index of the item exported again under `name`.
+        second: (ExternKind, u32),
+    },
+    /// The translation has been cancelled before it finished.
+    Cancelled,
+    /// Tried to translate a function whose type is missing in its [`TranslationContext`].
+    ///
+    /// [`TranslationContext`]: crate::TranslationContext
+    UnknownFuncType,
 }
 
 impl TranslationError {
@@ -94,10 +131,45 @@ impl Display for TranslationError {
             Self::TooManyFunctionParams => {
                 write!(f, "encountered function with too many function parameters")
             }
-            Self::LazyCompilationFailed => {
+            Self::LimitExceeded {
+                which,
+                limit,
+                found,
+            } => {
                 write!(
                     f,
-                    "lazy function compilation encountered a Wasm validation or translation error"
+                    "encountered {found} {which} exceeding the configured limit of {limit}"
+                )
+            }
+            Self::TooManyRegisters {
+                func,
+                len_registers,
+                limit,
+            } => {
+                write!(
+                    f,
+                    "function {func} requires {len_registers} registers \
+                    exceeding the configured limit of {limit} registers"
+                )
+            }
+            Self::DuplicateExport {
+                name,
+                first: (first_kind, first_index),
+                second: (second_kind, second_index),
+            } => {
+                write!(
+                    f,
+                    "duplicate export name `{name}` of {second_kind} {second_index} \
+                    already used by {first_kind} {first_index}"
+                )
+            }
+            Self::Cancelled => {
+                write!(f, "translation has been cancelled")
+            }
+            Self::UnknownFuncType => {
+                write!(
+                    f,
+                    "encountered function type missing in the translation context"
                 )
             }
         }
@@ -0,0 +1,367 @@
+//! Optional inlining of calls to tiny internal functions during translation.
+//!
+//! # Note
+//!
+//! Inlining is enabled via [`Config::optimization_level`] of at least `2` and only applies
+//! to eagerly compiled Wasm modules. Calls to tiny leaf functions are replaced by a copy
+//! of the body of the callee with all of its registers renumbered into the caller:
+//!
+//! - Function parameters that the callee never writes map to the call parameters.
+//! - All other registers of the callee map to scratch registers of the caller.
+//! - Returns are rewritten into copies of the returned values to the call results.
+//!
+//! The [`Instruction::ConsumeFuel`] of the callee are inlined as well. Unlike adding the fuel
+//! of the callee to the block enclosing the call this does not alter fuel consumption if the
+//! call is not executed. Their recorded [`FuelBreakdown`] is inlined with them so that the
+//! inlined fuel can be repriced. Since functions are compiled in the order of the code section only
+//! calls to functions defined before the caller are inlined.
+//!
+//! [`Config::optimization_level`]: crate::Config::optimization_level
+
+use super::{
+    optimizer::{reads_register, writes_register},
+    stack::RegisterSpace,
+    visit_register::VisitInputRegisters,
+    FuelInfo,
+    FuncTranslator,
+    TypedProvider,
+};
+use crate::{
+    engine::{
+        bytecode::{Instruction, Register, RegisterSpan},
+        code_map::CompiledFuncEntity,
+        config::CompilationMode,
+        CompiledFunc,
+        FuelBreakdown,
+    },
+    module::ModuleHeader,
+    Error,
+};
+use alloc::vec::Vec;
+
+/// The maximum number of instruction words of an inlined function.
+const MAX_INSTRS: usize = 8;
+
+/// The maximum number of registers of an inlined function.
+///
+/// # Note
+///
+/// This must not exceed the number of bits of a [`RegisterSet`].
+const MAX_REGISTERS: u16 = 16;
+
+/// The maximum number of instruction words inlined into a single function.
+pub const INLINE_BUDGET: usize = 256;
+
+/// A set of the registers of an inlined function.
+type RegisterSet = u16;
+
+/// Returns the [`RegisterSet`] of all registers below `len_registers` for which `f` returns `true`.
+fn register_set(
+    len_registers: u16,
+    mut f: impl FnMut(Register) -> Result<bool, Error>,
+) -> Result<RegisterSet, Error> {
+    let mut set = 0;
+    for index in 0..len_registers {
+        if f(Register::from_i16(index as i16))? {
+            set |= 1 << index;
+        }
+    }
+    Ok(set)
+}
+
+/// Returns `true` if the register of an inlined function at `index` is contained in `set`.
+fn contains(set: RegisterSet, index: i16) -> bool {
+    (0..MAX_REGISTERS as i16).contains(&index) && set & (1 << index) != 0
+}
+
+/// A tiny leaf function that is inlined into its callers.
+#[derive(Debug)]
+struct InlinedFunc {
+    /// The instructions of the function without its final return.
+    body: Vec<Instruction>,
+    /// The final return instruction of the function.
+    ret: Instruction,
+    /// The [`FuelBreakdown`] of all [`Instruction::ConsumeFuel`] of the body in order.
+    fuel: Vec<FuelBreakdown>,
+    /// The number of registers of the function.
+    len_registers: u16,
+    /// The function parameters written by the body of the function.
+    written_params: RegisterSet,
+    /// The registers read by the body of the function.
+    read: RegisterSet,
+}
+
+impl InlinedFunc {
+    /// Returns the [`InlinedFunc`] for `func` with `len_params` parameters if it is inlinable.
+    ///
+    /// # Note
+    ///
+    /// Inlinable functions consist of a short straight-line sequence of single instruction
+    /// words followed by a return. They must neither call other functions nor grow a linear
+    /// memory and their registers other than their parameters must be written before use.
+    fn new(
+        func: &CompiledFuncEntity,
+        len_params: usize,
+        module: &ModuleHeader,
+    ) -> Result<Option<Self>, Error> {
+        let len_registers = func.len_cells();
+        if func.instrs().len() > MAX_INSTRS
+            || len_registers > MAX_REGISTERS
+            || !func.consts().is_empty()
+        {
+            // Note: functions with function local constant values are not inlined since
+            //       they would add constant values to the registers of the caller which
+            //       alters the fuel consumed upon calling the caller.
+            return Ok(None);
+        }
+        let Some((&ret, instrs)) = func.instrs().split_last() else {
+            return Ok(None);
+        };
+        if !matches!(
+            ret,
+            Instruction::Return
+                | Instruction::ReturnReg { .. }
+                | Instruction::ReturnReg2 { .. }
+                | Instruction::ReturnReg3 { .. }
+                | Instruction::ReturnImm32 { .. }
+                | Instruction::ReturnI64Imm32 { .. }
+                | Instruction::ReturnF64Imm32 { .. }
+        ) {
+            return Ok(None);
+        }
+        let params = ((1_u32 << len_params) - 1) as RegisterSet;
+        let reads = |instr| {
+            register_set(len_registers, |register| {
+                Ok(reads_register(instr, register))
+            })
+        };
+        let mut body = Vec::with_capacity(instrs.len());
+        let mut fuel = Vec::new();
+        let mut written: RegisterSet = 0;
+        let mut read: RegisterSet = 0;
+        for (index, &instr) in instrs.iter().enumerate() {
+            if instr.is_block_barrier()
+                || matches!(
+                    instr,
+                    Instruction::MemoryGrow { .. } | Instruction::MemoryGrowBy { .. }
+                )
+            {
+                return Ok(None);
+            }
+            if let Instruction::ConsumeFuel(_) = instr {
+                let Some(breakdown) = func.fuel_breakdown(index) else {
+                    // Note: the fuel of the function cannot be repriced after inlining.
+                    return Ok(None);
+                };
+                fuel.push(breakdown.clone());
+            }
+            let instr_reads = reads(instr)?;
+            if instr_reads & !(params | written) != 0 {
+                // Case: a local variable is read before it is written.
+                return Ok(None);
+            }
+            read |= instr_reads;
+            written |= register_set(len_registers, |register| {
+                writes_register(module, instr, register)
+            })?;
+            body.push(instr);
+        }
+        if reads(ret)? & !(params | written) != 0 {
+            // Case: a local variable is returned without being written.
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            body,
+            ret,
+            fuel,
+            len_registers,
+            written_params: written & params,
+            read,
+        }))
+    }
+
+    /// Returns the registers returned by the [`InlinedFunc`].
+    ///
+    /// Returns an empty slice if the [`InlinedFunc`] returns no registers.
+    fn returned_registers(&self) -> &[Register] {
+        match &self.ret {
+            Instruction::ReturnReg { value } => core::slice::from_ref(value),
+            Instruction::ReturnReg2 { values } => values,
+            Instruction::ReturnReg3 { values } => values,
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` if the returned registers can be written directly to the call `results`.
+    ///
+    /// # Note
+    ///
+    /// This is the case if all returned registers are distinct registers that are written
+    /// but never read by the body and if none of the call `params` is a call result.
+    fn returns_in_place(
+        &self,
+        len_params: usize,
+        params: &[Register],
+        results: &[Register],
+    ) -> bool {
+        let returned = self.returned_registers();
+        let is_local = |register: &Register| {
+            let index = register.to_i16();
+            index as usize >= len_params && !contains(self.read, index)
+        };
+        let is_distinct = |(n, register): (usize, &Register)| !returned[..n].contains(register);
+        !returned.is_empty()
+            && returned.iter().all(is_local)
+            && returned.iter().enumerate().all(is_distinct)
+            && !results.iter().any(|result| params.contains(result))
+    }
+}
+
+impl FuncTranslator {
+    /// Tries to inline the call to the internal `func` with `len_results` call `results`.
+    ///
+    /// # Note
+    ///
+    /// - The parameters of the call are expected in the reusable buffer of the [`FuncTranslator`].
+    /// - Returns `false` and encodes nothing if `func` is not inlined.
+    pub(super) fn try_inline_call(
+        &mut self,
+        func: CompiledFunc,
+        results: RegisterSpan,
+        len_results: usize,
+    ) -> Result<bool, Error> {
+        let config = self.engine().config();
+        if config.get_optimization_level() < 2
+            || !matches!(config.get_compilation_mode(), CompilationMode::Eager)
+        {
+            return Ok(false);
+        }
+        let len_params = self.alloc.buffer.len();
+        let module = &self.module;
+        let Some(inlined) = self
+            .engine()
+            .with_compiled_func(func, |func| InlinedFunc::new(func, len_params, module))
+            .transpose()?
+            .flatten()
+        else {
+            return Ok(false);
+        };
+        let Some(budget) = self.inline_budget.checked_sub(inlined.body.len()) else {
+            return Ok(false);
+        };
+        self.inline_budget = budget;
+        let results: Vec<Register> = results.iter(len_results).collect();
+        self.encode_inlined_call(&inlined, &results)?;
+        Ok(true)
+    }
+
+    /// Encodes the body of the [`InlinedFunc`] as if it was called with `results`.
+    fn encode_inlined_call(
+        &mut self,
+        inlined: &InlinedFunc,
+        results: &[Register],
+    ) -> Result<(), Error> {
+        let len_params = self.alloc.buffer.len();
+        let mut params = [Register::from_i16(0); MAX_REGISTERS as usize];
+        // Note: scratch registers must not overlap with the dynamically allocated call parameters.
+        let mut min_scratch = Register::from_i16(0);
+        for (param, provider) in params.iter_mut().zip(&self.alloc.buffer) {
+            // Note: constant call parameters are allocated as function local constant values
+            //       the same as for non-inlined calls so that fuel consumption is not altered.
+            *param = match *provider {
+                TypedProvider::Register(register) => register,
+                TypedProvider::Const(value) => self.alloc.stack.alloc_const(value)?,
+            };
+            if let RegisterSpace::Dynamic = self.alloc.stack.get_register_space(*param) {
+                min_scratch = min_scratch.max(param.next());
+            }
+        }
+        let params = &params[..len_params];
+        let scratch = self
+            .alloc
+            .stack
+            .peek_scratch_n(min_scratch, usize::from(inlined.len_registers))?
+            .head()
+            .to_i16();
+        let returned = inlined.returned_registers();
+        let in_place = inlined.returns_in_place(len_params, params, results);
+        let map = |register: Register| -> Register {
+            let index = register.to_i16();
+            if (index as usize) < len_params && !contains(inlined.written_params, index) {
+                return params[index as usize];
+            }
+            if in_place {
+                if let Some(n) = returned.iter().position(|&value| value == register) {
+                    return results[n];
+                }
+            }
+            Register::from_i16(scratch + index)
+        };
+        for (index, &param) in params.iter().enumerate() {
+            let register = Register::from_i16(index as i16);
+            if contains(inlined.written_params, register.to_i16()) {
+                self.alloc.instr_encoder.encode_copy(
+                    &mut self.alloc.stack,
+                    map(register),
+                    TypedProvider::Register(param),
+                    FuelInfo::None,
+                )?;
+            }
+        }
+        let mut fuel = inlined.fuel.iter();
+        for &instr in &inlined.body {
+            if let Instruction::ConsumeFuel(_) = instr {
+                let breakdown = fuel
+                    .next()
+                    .cloned()
+                    .expect("missing fuel breakdown for inlined Instruction::ConsumeFuel");
+                let costs = self
+                    .fuel_costs()
+                    .copied()
+                    .expect("fuel metering must be enabled for inlined Instruction::ConsumeFuel");
+                self.alloc
+                    .instr_encoder
+                    .push_fuel_instr(&costs, breakdown)?;
+                continue;
+            }
+            let instr = match instr {
+                Instruction::Copy { result, value } => Instruction::copy(map(result), map(value)),
+                mut instr => {
+                    instr.visit_input_registers(|input| *input = map(*input));
+                    for index in 0..inlined.len_registers as i16 {
+                        let register = Register::from_i16(index);
+                        if map(register) != register
+                            && instr.relink_result(&self.module, map(register), register)?
+                        {
+                            break;
+                        }
+                    }
+                    instr
+                }
+            };
+            self.alloc.instr_encoder.push_instr(instr)?;
+        }
+        let ret = match inlined.ret {
+            Instruction::ReturnImm32 { value } => Instruction::copy_imm32(results[0], value),
+            Instruction::ReturnI64Imm32 { value } => Instruction::copy_i64imm32(results[0], value),
+            Instruction::ReturnF64Imm32 { value } => Instruction::copy_f64imm32(results[0], value),
+            _ => {
+                if !in_place && !returned.is_empty() {
+                    let values: Vec<TypedProvider> = returned
+                        .iter()
+                        .map(|&value| TypedProvider::Register(map(value)))
+                        .collect();
+                    self.alloc.instr_encoder.encode_copies(
+                        &mut self.alloc.stack,
+                        RegisterSpan::new(results[0]).iter(results.len()),
+                        &values,
+                        FuelInfo::None,
+                    )?;
+                }
+                return Ok(());
+            }
+        };
+        self.alloc.instr_encoder.push_instr(ret)?;
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use super::{
+    optimizer::{self, OptimizerBuffers},
     visit_register::VisitInputRegisters,
     FuelInfo,
     LabelRef,
@@ -23,7 +24,9 @@ use crate::{
             RegisterSpanIter,
         },
         translator::{stack::RegisterSpace, ValueStack},
+        FuelBreakdown,
         FuelCosts,
+        FuelUsage,
     },
     module::ModuleHeader,
     Error,
@@ -88,6 +91,13 @@ pub struct InstrEncoder {
     labels: LabelRegistry,
     /// The last [`Instruction`] created via [`InstrEncoder::push_instr`].
     last_instr: Option<Instr>,
+    /// The number of results of [`InstrEncoder::last_instr`] if it is a call [`Instruction`].
+    ///
+    /// # Note
+    ///
+    /// This is used to encode an [`Instruction::ReturnForward`] for a `return`
+    /// that returns exactly the results of the call right before it.
+    last_call_results: Option<u16>,
     /// The first encoded [`Instr`] that is affected by a `local.set` preservation.
     ///
     /// # Note
@@ -96,6 +106,15 @@ pub struct InstrEncoder {
     /// defragmentation of the register space due to `local.set` register
     /// preservations.
     notified_preservation: Option<Instr>,
+    /// The last encoded copy [`Instruction`] that may be merged with adjacent copies.
+    ///
+    /// # Note
+    ///
+    /// This is reset whenever any other [`Instruction`] is encoded or a label
+    /// is pinned so that copies are never merged across branch targets.
+    last_copy: Option<Instr>,
+    /// The reusable buffers of the optimization pass.
+    optimizer: OptimizerBuffers,
 }
 
 /// The sequence of encoded [`Instruction`].
@@ -103,12 +122,86 @@ pub struct InstrEncoder {
 pub struct InstrSequence {
     /// Already encoded [`Instruction`] words.
     instrs: Vec<Instruction>,
+    /// The address map of the [`InstrSequence`] if enabled.
+    address_map: Option<AddressMap>,
+    /// The Wasm binary offset of the currently translated Wasm operator.
+    pos: u32,
+    /// The Wasm binary offsets of all encoded [`Instruction::Trap`] in the order of their encoding.
+    ///
+    /// # Note
+    ///
+    /// Unlike the address map this is always enabled since it only costs
+    /// a single entry per [`Instruction::Trap`]. Read [`InstrSequence::trap_sites`].
+    trap_offsets: Vec<u32>,
+    /// The [`FuelBreakdown`] of all encoded [`Instruction::ConsumeFuel`] sorted by their [`Instr`].
+    ///
+    /// # Note
+    ///
+    /// This is only populated if fuel metering is enabled. Read [`InstrSequence::fuel_sites`].
+    fuel_sites: Vec<(Instr, FuelBreakdown)>,
+}
+
+/// The Wasm binary offsets of the encoded [`Instruction`] words of an [`InstrSequence`].
+#[derive(Debug, Default)]
+pub struct AddressMap {
+    /// The Wasm binary offset of every encoded [`Instruction`] word.
+    offsets: Vec<u32>,
+    /// The Wasm binary offset of the currently translated Wasm operator.
+    ///
+    /// This is `None` until the first Wasm binary offset is known.
+    pos: Option<u32>,
+}
+
+impl AddressMap {
+    /// Returns the Wasm binary offset for newly encoded [`Instruction`] words.
+    fn pos(&self) -> u32 {
+        self.pos.unwrap_or_default()
+    }
+
+    /// Updates the Wasm binary offset of the currently translated Wasm operator.
+    ///
+    /// # Note
+    ///
+    /// [`Instruction`] words encoded before the first update are attributed to `pos`.
+    fn update_pos(&mut self, pos: u32) {
+        if self.pos.is_none() {
+            self.offsets.fill(pos);
+        }
+        self.pos = Some(pos);
+    }
+
+    /// Returns the compressed address map.
+    ///
+    /// Only contains an `(instr, offset)` entry for every [`Instruction`] index `instr`
+    /// that starts a sequence of [`Instruction`] words with the same Wasm binary `offset`.
+    fn finish(&self) -> Vec<(u32, u32)> {
+        let mut entries = Vec::<(u32, u32)>::new();
+        for (instr, &offset) in self.offsets.iter().enumerate() {
+            if entries.last().map(|&(_, last)| last) != Some(offset) {
+                entries.push((instr as u32, offset));
+            }
+        }
+        entries
+    }
 }
 
 impl InstrSequence {
     /// Resets the [`InstrSequence`].
-    pub fn reset(&mut self) {
+    ///
+    /// Enables its address map if `address_map` is `true`.
+    pub fn reset(&mut self, address_map: bool) {
         self.instrs.clear();
+        self.pos = 0;
+        self.trap_offsets.clear();
+        self.fuel_sites.clear();
+        match (address_map, &mut self.address_map) {
+            (true, Some(map)) => {
+                map.offsets.clear();
+                map.pos = None;
+            }
+            (true, map @ None) => *map = Some(AddressMap::default()),
+            (false, map) => *map = None,
+        }
     }
 
     /// Returns the next [`Instr`].
@@ -123,10 +216,45 @@ impl InstrSequence {
     /// If there are too many instructions in the instruction sequence.
     fn push(&mut self, instruction: Instruction) -> Result<Instr, Error> {
         let instr = self.next_instr();
+        if let Instruction::Trap(_) = instruction {
+            self.trap_offsets.push(self.pos);
+        }
         self.instrs.push(instruction);
+        if let Some(map) = &mut self.address_map {
+            map.offsets.push(map.pos());
+        }
         Ok(instr)
     }
 
+    /// Pushes an [`Instruction::ConsumeFuel`] charging `breakdown` for `costs` and returns its [`Instr`].
+    ///
+    /// # Errors
+    ///
+    /// - If the block fuel of `breakdown` is out of bounds for `costs`.
+    /// - If there are too many instructions in the instruction sequence.
+    fn push_fuel(&mut self, costs: &FuelCosts, breakdown: FuelBreakdown) -> Result<Instr, Error> {
+        let block_fuel = breakdown.block_fuel(costs)?;
+        let instr = self.push(Instruction::ConsumeFuel(block_fuel))?;
+        self.fuel_sites.push((instr, breakdown));
+        Ok(instr)
+    }
+
+    /// Bumps the fuel of the [`Instruction::ConsumeFuel`] at `instr` by `usage` for `costs`.
+    ///
+    /// # Errors
+    ///
+    /// If the block fuel is out of bounds after this operation.
+    fn bump_fuel(&mut self, instr: Instr, costs: &FuelCosts, usage: FuelUsage) -> Result<(), Error> {
+        self.get_mut(instr).bump_fuel_consumption(usage.fuel(costs))?;
+        if let Ok(index) = self
+            .fuel_sites
+            .binary_search_by_key(&instr, |&(instr, _)| instr)
+        {
+            self.fuel_sites[index].1.record(usage);
+        }
+        Ok(())
+    }
+
     /// Pushes an [`Instruction`] before the [`Instruction`] at [`Instr`].
     ///
     /// Returns the [`Instr`] of the [`Instruction`] that was at [`Instr`] before this operation.
@@ -143,7 +271,23 @@ impl InstrSequence {
     ///
     /// If there are too many instructions in the instruction sequence.
     fn push_before(&mut self, instr: Instr, instruction: Instruction) -> Result<Instr, Error> {
+        if let Instruction::Trap(_) = instruction {
+            let index = self.instrs[..instr.into_usize()]
+                .iter()
+                .filter(|instr| matches!(instr, Instruction::Trap(_)))
+                .count();
+            self.trap_offsets.insert(index, self.pos);
+        }
         self.instrs.insert(instr.into_usize(), instruction);
+        if let Some(map) = &mut self.address_map {
+            map.offsets.insert(instr.into_usize(), map.pos());
+        }
+        for (site, _) in self.fuel_sites.iter_mut().rev() {
+            if *site < instr {
+                break;
+            }
+            *site = Instr::from_u32(site.into_u32() + 1);
+        }
         let shifted_instr = instr
             .into_u32()
             .checked_add(1)
@@ -170,6 +314,23 @@ impl InstrSequence {
         &mut self.instrs[instr.into_usize()]
     }
 
+    /// Pops the last [`Instruction`] of the [`InstrSequence`] if any.
+    fn pop(&mut self) -> Option<Instruction> {
+        let instruction = self.instrs.pop()?;
+        if let Instruction::Trap(_) = instruction {
+            self.trap_offsets.pop();
+        }
+        if let Some(map) = &mut self.address_map {
+            map.offsets.pop();
+        }
+        if let Some((site, _)) = self.fuel_sites.last() {
+            if site.into_usize() == self.instrs.len() {
+                self.fuel_sites.pop();
+            }
+        }
+        Some(instruction)
+    }
+
     /// Return an iterator over the sequence of generated [`Instruction`].
     ///
     /// # Note
@@ -179,6 +340,60 @@ impl InstrSequence {
         self.instrs.drain(..)
     }
 
+    /// Returns the compressed address map of the [`InstrSequence`] if enabled.
+    ///
+    /// Read [`AddressMap::finish`] for more information.
+    pub fn address_map(&self) -> Option<Vec<(u32, u32)>> {
+        self.address_map.as_ref().map(AddressMap::finish)
+    }
+
+    /// Returns the `(instr, offset)` pairs of all [`Instruction::Trap`] of the [`InstrSequence`].
+    ///
+    /// The `instr` is the index of the [`Instruction::Trap`] and `offset` is the Wasm
+    /// binary offset of the Wasm operator it has been translated from.
+    pub fn trap_sites(&self) -> Vec<(u32, u32)> {
+        let traps = self
+            .instrs
+            .iter()
+            .enumerate()
+            .filter(|(_, instr)| matches!(instr, Instruction::Trap(_)))
+            .map(|(index, _)| index as u32);
+        debug_assert_eq!(traps.clone().count(), self.trap_offsets.len());
+        traps.zip(self.trap_offsets.iter().copied()).collect()
+    }
+
+    /// Returns the `(instr, breakdown)` pairs of all [`Instruction::ConsumeFuel`] of the [`InstrSequence`].
+    ///
+    /// The `instr` is the index of the [`Instruction::ConsumeFuel`] and `breakdown`
+    /// is the [`FuelBreakdown`] of the fuel it charges.
+    ///
+    /// # Note
+    ///
+    /// The [`FuelBreakdown`]s are taken out of the [`InstrSequence`] by this operation.
+    pub fn fuel_sites(&mut self) -> Vec<(u32, FuelBreakdown)> {
+        self.fuel_sites
+            .drain(..)
+            .map(|(instr, breakdown)| (instr.into_u32(), breakdown))
+            .collect()
+    }
+
+    /// Updates the [`Instr`] of all recorded [`Instruction::ConsumeFuel`] after `instrs` have been modified.
+    ///
+    /// # Note
+    ///
+    /// The recorded [`Instruction::ConsumeFuel`] are kept in the order of their encoding.
+    fn relocate_fuel_sites(&mut self) {
+        let fuel_instrs = self
+            .instrs
+            .iter()
+            .enumerate()
+            .filter(|(_, instr)| matches!(instr, Instruction::ConsumeFuel(_)))
+            .map(|(index, _)| Instr::from_usize(index));
+        for ((site, _), instr) in self.fuel_sites.iter_mut().zip(fuel_instrs) {
+            *site = instr;
+        }
+    }
+
     /// Returns a slice to the sequence of [`Instruction`] starting at `start`.
     ///
     /// # Panics
@@ -200,11 +415,14 @@ impl<'a> IntoIterator for &'a mut InstrSequence {
 
 impl InstrEncoder {
     /// Resets the [`InstrEncoder`].
-    pub fn reset(&mut self) {
-        self.instrs.reset();
+    ///
+    /// Enables the generation of an address map if `address_map` is `true`.
+    pub fn reset(&mut self, address_map: bool) {
+        self.instrs.reset(address_map);
         self.labels.reset();
         self.reset_last_instr();
         self.notified_preservation = None;
+        self.last_copy = None;
     }
 
     /// Resets the [`Instr`] last created via [`InstrEncoder::push_instr`].
@@ -220,6 +438,7 @@ impl InstrEncoder {
     /// not invalidly optimize across control flow boundaries.
     pub fn reset_last_instr(&mut self) {
         self.last_instr = None;
+        self.last_call_results = None;
     }
 
     /// Return an iterator over the sequence of generated [`Instruction`].
@@ -231,6 +450,65 @@ impl InstrEncoder {
         self.instrs.drain()
     }
 
+    /// Updates the Wasm binary offset of the currently translated Wasm operator.
+    ///
+    /// # Note
+    ///
+    /// The position is always tracked for the trap sites of the encoded [`Instruction`]
+    /// sequence whereas the address map is only updated if its generation is enabled.
+    pub fn update_pos(&mut self, pos: usize) {
+        let pos = u32::try_from(pos).unwrap_or(u32::MAX);
+        self.instrs.pos = pos;
+        if let Some(map) = &mut self.instrs.address_map {
+            map.update_pos(pos);
+        }
+    }
+
+    /// Returns the address map of the encoded [`Instruction`] sequence if enabled.
+    ///
+    /// # Note
+    ///
+    /// The address map is a sorted table of `(instr, offset)` entries where `offset`
+    /// is the Wasm binary offset of all [`Instruction`] words starting at index `instr`
+    /// up to the index of the next entry.
+    pub fn address_map(&self) -> Option<Vec<(u32, u32)>> {
+        self.instrs.address_map()
+    }
+
+    /// Returns the trap sites of the encoded [`Instruction`] sequence.
+    ///
+    /// Read [`InstrSequence::trap_sites`] for more information.
+    pub fn trap_sites(&self) -> Vec<(u32, u32)> {
+        self.instrs.trap_sites()
+    }
+
+    /// Returns the fuel sites of the encoded [`Instruction`] sequence.
+    ///
+    /// Read [`InstrSequence::fuel_sites`] for more information.
+    pub fn fuel_sites(&mut self) -> Vec<(u32, FuelBreakdown)> {
+        self.instrs.fuel_sites()
+    }
+
+    /// Applies the optional optimization pass to the encoded [`Instruction`] sequence.
+    ///
+    /// # Note
+    ///
+    /// This must be called after all branch offsets have been updated.
+    /// Read [`optimizer::optimize`] for more information.
+    pub fn optimize(&mut self, module: &ModuleHeader) -> Result<(), Error> {
+        let offsets = self.instrs.address_map.as_mut().map(|map| &mut map.offsets);
+        optimizer::optimize(
+            &mut self.instrs.instrs,
+            offsets,
+            &mut self.instrs.trap_offsets,
+            &mut self.instrs.fuel_sites,
+            module,
+            &mut self.optimizer,
+        )?;
+        self.instrs.relocate_fuel_sites();
+        Ok(())
+    }
+
     /// Creates a new unresolved label and returns its [`LabelRef`].
     pub fn new_label(&mut self) -> LabelRef {
         self.labels.new_label()
@@ -246,6 +524,7 @@ impl InstrEncoder {
     /// the given label can be resolved properly.
     /// This usually takes place when encountering the Wasm `End` operand for example.
     pub fn pin_label_if_unpinned(&mut self, label: LabelRef) {
+        self.last_copy = None;
         self.labels.try_pin_label(label, self.instrs.next_instr())
     }
 
@@ -261,6 +540,10 @@ impl InstrEncoder {
     ///
     /// If the label has already been resolved.
     pub fn pin_label(&mut self, label: LabelRef) {
+        self.last_copy = None;
+        // Instructions before a pinned label must not be fused with the ones
+        // after it since branches to the label would skip the fused instruction.
+        self.last_instr = None;
         self.labels
             .pin_label(label, self.instrs.next_instr())
             .unwrap_or_else(|err| panic!("failed to pin label: {err}"));
@@ -309,9 +592,22 @@ impl InstrEncoder {
     pub fn push_instr(&mut self, instr: Instruction) -> Result<Instr, Error> {
         let last_instr = self.instrs.push(instr)?;
         self.last_instr = Some(last_instr);
+        self.last_call_results = None;
+        self.last_copy = None;
         Ok(last_instr)
     }
 
+    /// Push the call [`Instruction`] with `len_results` results to the [`InstrEncoder`].
+    ///
+    /// # Note
+    ///
+    /// The parameters of the call must be appended without using [`InstrEncoder::push_instr`].
+    pub fn push_call(&mut self, instr: Instruction, len_results: usize) -> Result<Instr, Error> {
+        let call = self.push_instr(instr)?;
+        self.last_call_results = u16::try_from(len_results).ok();
+        Ok(call)
+    }
+
     /// Appends the [`Instruction`] to the last [`Instruction`] created via [`InstrEncoder::push_instr`].
     ///
     /// # Note
@@ -320,6 +616,7 @@ impl InstrEncoder {
     /// parameters for the [`Instruction`]. An example of this is [`Instruction::Const32`]
     /// carrying the `offset` parameter for [`Instruction::I32Load`].
     pub fn append_instr(&mut self, instr: Instruction) -> Result<Instr, Error> {
+        self.last_copy = None;
         self.instrs.push(instr)
     }
 
@@ -327,8 +624,10 @@ impl InstrEncoder {
     ///
     /// # Note
     ///
-    /// Applies optimizations for `copy x <- x` and properly selects the
-    /// most optimized `copy` instruction variant for the given `value`.
+    /// - Applies optimizations for `copy x <- x` and properly selects the
+    ///   most optimized `copy` instruction variant for the given `value`.
+    /// - Merges the copy into the previous copy instruction if possible.
+    ///   Read [`InstrEncoder::try_merge_copies`] for more information.
     pub fn encode_copy(
         &mut self,
         stack: &mut ValueStack,
@@ -351,6 +650,10 @@ impl InstrEncoder {
                     // Optimization: copying from register `x` into `x` is a no-op.
                     return Ok(None);
                 }
+                if let Some(merged) = self.try_merge_copies(stack, result, &[value]) {
+                    self.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
+                    return Ok(Some(merged));
+                }
                 Instruction::copy(result, value)
             }
             TypedProvider::Const(value) => match value.ty() {
@@ -368,8 +671,9 @@ impl InstrEncoder {
                 ValueType::ExternRef => copy_imm(stack, result, value)?,
             },
         };
-        self.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
+        self.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
         let instr = self.push_instr(instr)?;
+        self.last_copy = Some(instr);
         Ok(Some(instr))
     }
 
@@ -383,18 +687,19 @@ impl InstrEncoder {
         &mut self,
         stack: &mut ValueStack,
         mut results: RegisterSpanIter,
-        values: &[TypedProvider],
+        mut values: &[TypedProvider],
         fuel_info: FuelInfo,
     ) -> Result<(), Error> {
         assert_eq!(results.len(), values.len());
-        if let Some((TypedProvider::Register(value), rest)) = values.split_first() {
-            if results.span().head() == *value {
-                // Case: `result` and `value` are equal thus this is a no-op copy which we can avoid.
-                //       Applied recursively we thereby remove all no-op copies at the start of the
-                //       copy sequence until the first actual copy.
-                results.next();
-                return self.encode_copies(stack, results, rest, fuel_info);
+        while let Some((TypedProvider::Register(value), rest)) = values.split_first() {
+            if results.span().head() != *value {
+                break;
             }
+            // Case: `result` and `value` are equal thus this is a no-op copy which we can avoid.
+            //       Applied repeatedly we thereby remove all no-op copies at the start of the
+            //       copy sequence until the first actual copy.
+            results.next();
+            values = rest;
         }
         let result = results.span().head();
         match values {
@@ -415,19 +720,24 @@ impl InstrEncoder {
                 }
                 let reg0 = Self::provider2reg(stack, v0)?;
                 let reg1 = Self::provider2reg(stack, v1)?;
-                self.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
-                self.push_instr(Instruction::copy2(results.span(), reg0, reg1))?;
+                self.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
+                if self
+                    .try_merge_copies(stack, result, &[reg0, reg1])
+                    .is_some()
+                {
+                    return Ok(());
+                }
+                let instr = self.push_instr(Instruction::copy2(results.span(), reg0, reg1))?;
+                self.last_copy = Some(instr);
                 Ok(())
             }
             [v0, v1, rest @ ..] => {
                 debug_assert!(!rest.is_empty());
                 // Note: The fuel for copies might result in 0 charges if there aren't
                 //       enough copies to account for at least 1 fuel. Therefore we need
-                //       to also bump by `FuelCosts::base` to charge at least 1 fuel.
-                self.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
-                self.bump_fuel_consumption(fuel_info, |costs| {
-                    costs.fuel_for_copies(rest.len() as u64 + 3)
-                })?;
+                //       to also bump by `FuelUsage::Base` to charge at least 1 fuel.
+                self.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
+                self.bump_fuel_consumption(fuel_info, FuelUsage::Copies(rest.len() as u64 + 3))?;
                 if let Some(values) = RegisterSpanIter::from_providers(values) {
                     let make_instr = match Self::has_overlapping_copy_spans(
                         results.span(),
@@ -437,11 +747,12 @@ impl InstrEncoder {
                         true => Instruction::copy_span,
                         false => Instruction::copy_span_non_overlapping,
                     };
-                    self.push_instr(make_instr(
+                    let instr = self.push_instr(make_instr(
                         results.span(),
                         values.span(),
                         values.len_as_u16(),
                     ))?;
+                    self.last_copy = Some(instr);
                     return Ok(());
                 }
                 let make_instr = match Self::has_overlapping_copies(results, values) {
@@ -457,6 +768,131 @@ impl InstrEncoder {
         }
     }
 
+    /// Tries to merge the copies `result+i <- values[i]` into the last copy instruction.
+    ///
+    /// Returns the [`Instr`] of the merged copy instruction upon success.
+    ///
+    /// # Note
+    ///
+    /// - A single [`Instruction::Copy`] followed by another adjacent copy is
+    ///   merged into an [`Instruction::Copy2`].
+    /// - Otherwise copies of the form `copy r+i <- v+i` are merged into either
+    ///   [`Instruction::CopySpanNonOverlapping`] or [`Instruction::CopySpan`].
+    /// - The merged instruction reads all of its inputs before writing its results.
+    ///   Therefore copies are not merged if one of them reads a [`Register`]
+    ///   that has been written to by the preceding copies.
+    /// - Only registers of local variables and dynamically allocated registers are
+    ///   merged since preserved registers are relocated by register defragmentation.
+    fn try_merge_copies(
+        &mut self,
+        stack: &ValueStack,
+        result: Register,
+        values: &[Register],
+    ) -> Option<Instr> {
+        /// Returns the [`Register`] that is `n` registers after `register` if any.
+        fn offset(register: Register, n: u16) -> Option<Register> {
+            register
+                .to_i16()
+                .checked_add_unsigned(n)
+                .map(Register::from_i16)
+        }
+        /// Returns `true` if copies from and to `first..=last` may be merged.
+        fn is_mergeable(stack: &ValueStack, first: Register, last: Register) -> bool {
+            // Note: local and dynamic registers form a contiguous register space.
+            //       Therefore it is sufficient to check the bounds of the span.
+            [first, last].into_iter().all(|register| {
+                matches!(
+                    stack.get_register_space(register),
+                    RegisterSpace::Local | RegisterSpace::Dynamic
+                )
+            })
+        }
+        let last_copy = self.last_copy?;
+        debug_assert!(matches!(values.len(), 1 | 2));
+        if last_copy.distance(self.instrs.next_instr()) != 1 {
+            // The merged copies must directly follow the last copy instruction.
+            return None;
+        }
+        let (head, first, len) = match *self.instrs.get(last_copy) {
+            Instruction::Copy { result, value } => (result, value, 1),
+            Instruction::Copy2 {
+                results,
+                values: [value0, value1],
+            } => {
+                if value0.next() != value1 {
+                    // Only copies of contiguous values can be extended.
+                    return None;
+                }
+                (results.head(), value0, 2)
+            }
+            Instruction::CopySpan {
+                results,
+                values,
+                len,
+            }
+            | Instruction::CopySpanNonOverlapping {
+                results,
+                values,
+                len,
+            } => (results.head(), values.head(), len),
+            _ => return None,
+        };
+        let results = RegisterSpan::new(head);
+        if offset(head, len)? != result {
+            // The merged copies must write to the registers directly following the last copy.
+            return None;
+        }
+        if values
+            .iter()
+            .any(|value| results.iter_u16(len).contains(*value))
+        {
+            // The merged copies read registers written to by the last copy.
+            return None;
+        }
+        let merged = match (len, values) {
+            (1, [value]) => {
+                if !is_mergeable(stack, head, result)
+                    || !is_mergeable(stack, first, first)
+                    || !is_mergeable(stack, *value, *value)
+                {
+                    return None;
+                }
+                Instruction::copy2(results, first, *value)
+            }
+            _ => {
+                let [value, rest @ ..] = values else {
+                    return None;
+                };
+                if offset(first, len)? != *value
+                    || rest.iter().zip(values).any(|(rhs, lhs)| lhs.next() != *rhs)
+                {
+                    // The merged copies must read the registers directly following the last copy.
+                    return None;
+                }
+                let merged_len = len.checked_add(values.len() as u16)?;
+                let last_result = offset(head, merged_len - 1)?;
+                let last_value = offset(first, merged_len - 1)?;
+                if !is_mergeable(stack, head, last_result)
+                    || !is_mergeable(stack, first, last_value)
+                {
+                    return None;
+                }
+                let values = RegisterSpan::new(first);
+                let make_instr = match Self::has_overlapping_copy_spans(
+                    results,
+                    values,
+                    usize::from(merged_len),
+                ) {
+                    true => Instruction::copy_span,
+                    false => Instruction::copy_span_non_overlapping,
+                };
+                make_instr(results, values, merged_len)
+            }
+        };
+        *self.instrs.get_mut(last_copy) = merged;
+        Some(last_copy)
+    }
+
     /// Returns `true` if `copy_span results <- values` has overlapping copies.
     ///
     /// # Examples
@@ -499,24 +935,39 @@ impl InstrEncoder {
         false
     }
 
-    /// Bumps consumed fuel for [`Instruction::ConsumeFuel`] of `instr` by `delta`.
+    /// Bumps consumed fuel for [`Instruction::ConsumeFuel`] of `instr` by `usage`.
     ///
     /// # Errors
     ///
     /// If consumed fuel is out of bounds after this operation.
-    pub fn bump_fuel_consumption<F>(&mut self, fuel_info: FuelInfo, f: F) -> Result<(), Error>
-    where
-        F: FnOnce(&FuelCosts) -> u64,
-    {
+    pub fn bump_fuel_consumption(
+        &mut self,
+        fuel_info: FuelInfo,
+        usage: FuelUsage,
+    ) -> Result<(), Error> {
         let FuelInfo::Some { costs, instr } = fuel_info else {
             // Fuel metering is disabled so we can bail out.
             return Ok(());
         };
-        let fuel_consumed = f(&costs);
-        self.instrs
-            .get_mut(instr)
-            .bump_fuel_consumption(fuel_consumed)?;
-        Ok(())
+        self.instrs.bump_fuel(instr, &costs, usage)
+    }
+
+    /// Pushes an [`Instruction::ConsumeFuel`] charging `breakdown` for `costs` and returns its [`Instr`].
+    ///
+    /// # Errors
+    ///
+    /// - If the block fuel of `breakdown` is out of bounds for `costs`.
+    /// - If there are too many instructions in the instruction sequence.
+    pub fn push_fuel_instr(
+        &mut self,
+        costs: &FuelCosts,
+        breakdown: FuelBreakdown,
+    ) -> Result<Instr, Error> {
+        let instr = self.instrs.push_fuel(costs, breakdown)?;
+        self.last_instr = Some(instr);
+        self.last_call_results = None;
+        self.last_copy = None;
+        Ok(instr)
     }
 
     /// Encodes an unconditional `return` instruction.
@@ -526,6 +977,15 @@ impl InstrEncoder {
         values: &[TypedProvider],
         fuel_info: FuelInfo,
     ) -> Result<(), Error> {
+        if let Some(values) = self.forwarded_call_results(values) {
+            // Note: We charge the same fuel as for the return that is replaced.
+            self.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
+            if values.len() > 3 {
+                self.bump_fuel_consumption(fuel_info, FuelUsage::Copies(values.len() as u64))?;
+            }
+            self.push_instr(Instruction::return_forward(values))?;
+            return Ok(());
+        }
         let instr = match values {
             [] => Instruction::Return,
             [TypedProvider::Register(reg)] => Instruction::return_reg(*reg),
@@ -559,11 +1019,9 @@ impl InstrEncoder {
                 debug_assert!(!rest.is_empty());
                 // Note: The fuel for return values might result in 0 charges if there aren't
                 //       enough return values to account for at least 1 fuel. Therefore we need
-                //       to also bump by `FuelCosts::base` to charge at least 1 fuel.
-                self.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
-                self.bump_fuel_consumption(fuel_info, |costs| {
-                    costs.fuel_for_copies(rest.len() as u64 + 3)
-                })?;
+                //       to also bump by `FuelUsage::Base` to charge at least 1 fuel.
+                self.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
+                self.bump_fuel_consumption(fuel_info, FuelUsage::Copies(rest.len() as u64 + 3))?;
                 if let Some(span) = RegisterSpanIter::from_providers(values) {
                     self.push_instr(Instruction::return_span(span))?;
                     return Ok(());
@@ -576,12 +1034,46 @@ impl InstrEncoder {
                 return Ok(());
             }
         };
-        self.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
+        self.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
         self.push_instr(instr)?;
         Ok(())
     }
 
+    /// Returns the results of the call right before the next [`Instruction`] if they are exactly `values`.
+    ///
+    /// # Note
+    ///
+    /// In this case the `return` of `values` can be encoded as [`Instruction::ReturnForward`].
+    fn forwarded_call_results(&mut self, values: &[TypedProvider]) -> Option<RegisterSpanIter> {
+        let len_results = self.last_call_results?;
+        if usize::from(len_results) != values.len() {
+            return None;
+        }
+        let results = match *self.instrs.get(self.last_instr?) {
+            Instruction::CallInternal0 { results, .. }
+            | Instruction::CallInternal { results, .. }
+            | Instruction::CallImported0 { results, .. }
+            | Instruction::CallImported { results, .. }
+            | Instruction::CallIndirect0 { results, .. }
+            | Instruction::CallIndirect { results, .. } => results.iter_u16(len_results),
+            _ => return None,
+        };
+        let is_forwarded = results.zip(values).all(
+            |(result, value)| matches!(value, TypedProvider::Register(value) if *value == result),
+        );
+        is_forwarded.then_some(results)
+    }
+
     /// Encodes an conditional `return` instruction.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`InstrEncoder::encode_branch_nez`] this does not fuse a preceding comparison
+    /// instruction producing the `condition` since fused forms would be required for every
+    /// comparison times each of the [`Instruction::ReturnNez`] variants which would bloat
+    /// the instruction set for comparably rare conditional returns.
+    /// This applies to float comparisons as well so NaN inputs are handled
+    /// by the comparison instruction itself.
     pub fn encode_return_nez(
         &mut self,
         stack: &mut ValueStack,
@@ -621,11 +1113,9 @@ impl InstrEncoder {
                 debug_assert!(!rest.is_empty());
                 // Note: The fuel for return values might result in 0 charges if there aren't
                 //       enough return values to account for at least 1 fuel. Therefore we need
-                //       to also bump by `FuelCosts::base` to charge at least 1 fuel.
-                self.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
-                self.bump_fuel_consumption(fuel_info, |costs| {
-                    costs.fuel_for_copies(rest.len() as u64 + 3)
-                })?;
+                //       to also bump by `FuelUsage::Base` to charge at least 1 fuel.
+                self.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
+                self.bump_fuel_consumption(fuel_info, FuelUsage::Copies(rest.len() as u64 + 3))?;
                 if let Some(span) = RegisterSpanIter::from_providers(values) {
                     self.push_instr(Instruction::return_nez_span(condition, span))?;
                     return Ok(());
@@ -637,7 +1127,7 @@ impl InstrEncoder {
                 return Ok(());
             }
         };
-        self.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
+        self.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
         self.push_instr(instr)?;
         Ok(())
     }
@@ -734,7 +1224,7 @@ impl InstrEncoder {
             fuel_info: FuelInfo,
         ) -> Result<(), Error> {
             if let Some(preserved) = preserved {
-                this.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
+                this.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
                 let preserve_instr = this.push_instr(Instruction::copy(preserved, local))?;
                 this.notify_preserved_register(preserve_instr);
             }
@@ -784,7 +1274,7 @@ impl InstrEncoder {
             // We were able to apply the optimization.
             // Preservation requires the copy to be before the optimized last instruction.
             // Therefore we need to push the preservation `copy` instruction before it.
-            self.bump_fuel_consumption(fuel_info, FuelCosts::base)?;
+            self.bump_fuel_consumption(fuel_info, FuelUsage::Base)?;
             let shifted_last_instr = self
                 .instrs
                 .push_before(last_instr, Instruction::copy(preserved, local))?;
@@ -830,9 +1320,11 @@ impl InstrEncoder {
     /// Translates a Wasm `i32.eqz` instruction.
     ///
     /// Tries to fuse `i32.eqz` with a previous `i32.{and,or,xor}` instruction if possible.
+    /// If `invert_cmp` is `true` it also fuses with a previous `i32.{eq,ne}` instruction
+    /// by inverting the comparison which also folds `i32.eqz` chains.
     /// Returns `true` if it was possible to fuse the `i32.eqz` instruction.
-    pub fn fuse_i32_eqz(&mut self, stack: &mut ValueStack) -> bool {
-        /// Fuse a `i32.{and,or,xor}` instruction with `i32.eqz`.
+    pub fn fuse_i32_eqz(&mut self, stack: &mut ValueStack, invert_cmp: bool) -> bool {
+        /// Fuse a `i32.{and,or,xor,eq,ne}` instruction with `i32.eqz`.
         macro_rules! fuse {
             ($instr:ident, $stack:ident, $make_fuse:expr) => {{
                 if matches!(
@@ -845,7 +1337,7 @@ impl InstrEncoder {
             }};
         }
 
-        /// Fuse a `i32.{and,or,xor}` instruction with 16-bit encoded immediate parameter with `i32.eqz`.
+        /// Fuse a `i32.{and,or,xor,eq,ne}` instruction with 16-bit encoded immediate parameter with `i32.eqz`.
         macro_rules! fuse_imm16 {
             ($instr:ident, $stack:ident, $make_fuse:expr) => {{
                 if matches!(
@@ -876,12 +1368,58 @@ impl InstrEncoder {
             Instruction::I32XorImm16(instr) => {
                 fuse_imm16!(instr, stack, Instruction::i32_xor_eqz_imm16)
             }
+            Instruction::I32Eq(instr) if invert_cmp => fuse!(instr, stack, Instruction::i32_ne),
+            Instruction::I32EqImm16(instr) if invert_cmp => {
+                fuse_imm16!(instr, stack, Instruction::i32_ne_imm16)
+            }
+            Instruction::I32Ne(instr) if invert_cmp => fuse!(instr, stack, Instruction::i32_eq),
+            Instruction::I32NeImm16(instr) if invert_cmp => {
+                fuse_imm16!(instr, stack, Instruction::i32_eq_imm16)
+            }
             _ => return false,
         };
         _ = mem::replace(self.instrs.get_mut(last_instr), fused_instr);
         true
     }
 
+    /// Tries to fuse a `select` on `condition` with a previous `i32.eqz` instruction.
+    ///
+    /// Returns the input of the `i32.eqz` instruction and `true` if the `select` operands
+    /// must be swapped. Returns `Some((input, false))` for the `i32.ne x 0` that results
+    /// from a doubly negated condition.
+    ///
+    /// # Note
+    ///
+    /// - The fused `i32.eqz` instruction is removed from the instruction sequence
+    ///   and the caller is required to encode the `select` with the returned condition.
+    /// - The fuel costs of the removed `i32.eqz` instruction are not adjusted.
+    pub fn fuse_select_i32_eqz(
+        &mut self,
+        stack: &mut ValueStack,
+        condition: Register,
+    ) -> Option<(Register, bool)> {
+        let last_instr = self.last_instr?;
+        if last_instr.distance(self.instrs.next_instr()) != 1 {
+            // The `i32.eqz` instruction must be the last encoded instruction word.
+            return None;
+        }
+        let (instr, swap) = match *self.instrs.get(last_instr) {
+            Instruction::I32EqImm16(instr) if instr.imm_in.is_zero() => (instr, true),
+            Instruction::I32NeImm16(instr) if instr.imm_in.is_zero() => (instr, false),
+            _ => return None,
+        };
+        if instr.result != condition
+            || matches!(stack.get_register_space(instr.result), RegisterSpace::Local)
+        {
+            // Must not remove instructions that store to local registers since
+            // this behavior is observable and would not be semantics preserving.
+            return None;
+        }
+        self.instrs.pop();
+        self.last_instr = None;
+        Some((instr.reg_in, swap))
+    }
+
     /// Encodes a `branch_eqz` instruction and tries to fuse it with a previous comparison instruction.
     pub fn encode_branch_eqz(
         &mut self,
@@ -892,18 +1430,6 @@ impl InstrEncoder {
         type BranchCmpConstructor = fn(Register, Register, BranchOffset16) -> Instruction;
         type BranchCmpImmConstructor<T> = fn(Register, Const16<T>, BranchOffset16) -> Instruction;
 
-        /// Create an [`Instruction::BranchCmpFallback`].
-        fn make_branch_cmp_fallback(
-            stack: &mut ValueStack,
-            cmp: BranchComparator,
-            lhs: Register,
-            rhs: Register,
-            offset: BranchOffset,
-        ) -> Result<Instruction, Error> {
-            let params = stack.alloc_const(ComparatorOffsetParam::new(cmp, offset))?;
-            Ok(Instruction::branch_cmp_fallback(lhs, rhs, params))
-        }
-
         /// Encode an unoptimized `branch_eqz` instruction.
         ///
         /// This is used as fallback whenever fusing compare and branch instructions is not possible.
@@ -988,6 +1514,13 @@ impl InstrEncoder {
             };
             Ok(Some(instr))
         }
+
+        /// Returns `instr` with swapped `lhs` and `rhs` operands.
+        ///
+        /// This turns `a > b` into `b < a` and `a >= b` into `b <= a`.
+        fn swap_operands(instr: BinInstr) -> BinInstr {
+            BinInstr::new(instr.result, instr.rhs, instr.lhs)
+        }
         use BranchComparator as Cmp;
         use Instruction as I;
 
@@ -1025,7 +1558,18 @@ impl InstrEncoder {
             I::I64GeU(instr) => fuse(self, stack, last_instr, instr, label, Cmp::I64LtU, I::branch_i64_lt_u as _)?,
             I::F32Eq(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32Ne, I::branch_f32_ne as _)?,
             I::F32Ne(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32Eq, I::branch_f32_eq as _)?,
-            // Note: We cannot fuse cmp+branch for float comparison operators due to how NaN values are treated.
+            // Note: Negated float comparisons must branch for NaN operands, e.g. `!(a < b)` is not `a >= b`.
+            //       Therefore they are fused into `Not{Lt,Le}` branches with swapped operands for `{Gt,Ge}`.
+            I::F32Lt(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32NotLt, I::branch_f32_not_lt as _)?,
+            I::F32Le(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F32NotLe, I::branch_f32_not_le as _)?,
+            I::F32Gt(instr) => fuse(self, stack, last_instr, swap_operands(instr), label, Cmp::F32NotLt, I::branch_f32_not_lt as _)?,
+            I::F32Ge(instr) => fuse(self, stack, last_instr, swap_operands(instr), label, Cmp::F32NotLe, I::branch_f32_not_le as _)?,
+            I::F64Eq(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F64Ne, I::branch_f64_ne as _)?,
+            I::F64Ne(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F64Eq, I::branch_f64_eq as _)?,
+            I::F64Lt(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F64NotLt, I::branch_f64_not_lt as _)?,
+            I::F64Le(instr) => fuse(self, stack, last_instr, instr, label, Cmp::F64NotLe, I::branch_f64_not_le as _)?,
+            I::F64Gt(instr) => fuse(self, stack, last_instr, swap_operands(instr), label, Cmp::F64NotLt, I::branch_f64_not_lt as _)?,
+            I::F64Ge(instr) => fuse(self, stack, last_instr, swap_operands(instr), label, Cmp::F64NotLe, I::branch_f64_not_le as _)?,
             I::I32AndImm16(instr) => fuse_imm::<i32>(self, stack, last_instr, instr, label, Cmp::I32AndEqz, I::branch_i32_and_eqz_imm as _)?,
             I::I32OrImm16(instr) => fuse_imm(self, stack, last_instr, instr, label, Cmp::I32OrEqz, I::branch_i32_or_eqz_imm as _)?,
             I::I32XorImm16(instr) => fuse_imm(self, stack, last_instr, instr, label, Cmp::I32XorEqz, I::branch_i32_xor_eqz_imm as _)?,
@@ -1071,18 +1615,6 @@ impl InstrEncoder {
         type BranchCmpConstructor = fn(Register, Register, BranchOffset16) -> Instruction;
         type BranchCmpImmConstructor<T> = fn(Register, Const16<T>, BranchOffset16) -> Instruction;
 
-        /// Create an [`Instruction::BranchCmpFallback`].
-        fn make_branch_cmp_fallback(
-            stack: &mut ValueStack,
-            cmp: BranchComparator,
-            lhs: Register,
-            rhs: Register,
-            offset: BranchOffset,
-        ) -> Result<Instruction, Error> {
-            let params = stack.alloc_const(ComparatorOffsetParam::new(cmp, offset))?;
-            Ok(Instruction::branch_cmp_fallback(lhs, rhs, params))
-        }
-
         /// Encode an unoptimized `branch_nez` instruction.
         ///
         /// This is used as fallback whenever fusing compare and branch instructions is not possible.
@@ -1250,6 +1782,42 @@ impl InstrEncoder {
     }
 }
 
+/// Creates a cmp+branch [`Instruction`] for a branch `offset` that cannot be 16-bit encoded.
+///
+/// # Note
+///
+/// Uses a dedicated fallback instruction with the 32-bit `offset` stored as function
+/// local constant value for the most common comparators which avoids decoding a
+/// [`ComparatorOffsetParam`] upon execution. Otherwise falls back to the generic
+/// [`Instruction::BranchCmpFallback`].
+fn make_branch_cmp_fallback(
+    stack: &mut ValueStack,
+    cmp: BranchComparator,
+    lhs: Register,
+    rhs: Register,
+    offset: BranchOffset,
+) -> Result<Instruction, Error> {
+    use BranchComparator as Cmp;
+    let make_instr: fn(Register, Register, Register) -> Instruction = match cmp {
+        Cmp::I32Eq => Instruction::branch_i32_eq_fallback,
+        Cmp::I32Ne => Instruction::branch_i32_ne_fallback,
+        Cmp::I32LtS => Instruction::branch_i32_lt_s_fallback,
+        Cmp::I32LtU => Instruction::branch_i32_lt_u_fallback,
+        Cmp::I32LeS => Instruction::branch_i32_le_s_fallback,
+        Cmp::I32LeU => Instruction::branch_i32_le_u_fallback,
+        Cmp::I32GtS => Instruction::branch_i32_gt_s_fallback,
+        Cmp::I32GtU => Instruction::branch_i32_gt_u_fallback,
+        Cmp::I32GeS => Instruction::branch_i32_ge_s_fallback,
+        Cmp::I32GeU => Instruction::branch_i32_ge_u_fallback,
+        _ => {
+            let params = stack.alloc_const(ComparatorOffsetParam::new(cmp, offset))?;
+            return Ok(Instruction::branch_cmp_fallback(lhs, rhs, params));
+        }
+    };
+    let offset = stack.alloc_const(offset.to_i32())?;
+    Ok(make_instr(lhs, rhs, offset))
+}
+
 impl Instruction {
     /// Updates the [`BranchOffset`] for the branch [`Instruction].
     ///
@@ -1257,15 +1825,14 @@ impl Instruction {
     ///
     /// If `self` is not a branch [`Instruction`].
     #[rustfmt::skip]
-    pub fn update_branch_offset(&mut self, stack: &mut ValueStack, new_offset: BranchOffset) -> Result<(), Error> {
+    pub(crate) fn update_branch_offset(&mut self, stack: &mut ValueStack, new_offset: BranchOffset) -> Result<(), Error> {
         /// Initializes the 16-bit offset of `instr` if possible.
         /// 
         /// If `new_offset` cannot be encoded as 16-bit offset `self` is replaced with a fallback instruction.
         macro_rules! init_offset {
             ($instr:expr, $new_offset:expr, $cmp:expr) => {{
                 if let Err(_) = $instr.offset.init($new_offset) {
-                    let params = stack.alloc_const(ComparatorOffsetParam::new($cmp, $new_offset))?;
-                    *self = Instruction::branch_cmp_fallback($instr.lhs, $instr.rhs, params);
+                    *self = make_branch_cmp_fallback(stack, $cmp, $instr.lhs, $instr.rhs, $new_offset)?;
                 }
                 Ok(())
             }}
@@ -1275,8 +1842,7 @@ impl Instruction {
             ($ty:ty, $instr:expr, $new_offset:expr, $cmp:expr) => {{
                 if let Err(_) = $instr.offset.init($new_offset) {
                     let rhs = stack.alloc_const(<$ty>::from($instr.rhs))?;
-                    let params = stack.alloc_const(ComparatorOffsetParam::new($cmp, $new_offset))?;
-                    *self = Instruction::branch_cmp_fallback($instr.lhs, rhs, params);
+                    *self = make_branch_cmp_fallback(stack, $cmp, $instr.lhs, rhs, $new_offset)?;
                 }
                 Ok(())
             }};
@@ -1321,12 +1887,16 @@ impl Instruction {
             I::BranchF32Le(instr) => init_offset!(instr, new_offset, Cmp::F32Le),
             I::BranchF32Gt(instr) => init_offset!(instr, new_offset, Cmp::F32Gt),
             I::BranchF32Ge(instr) => init_offset!(instr, new_offset, Cmp::F32Ge),
+            I::BranchF32NotLt(instr) => init_offset!(instr, new_offset, Cmp::F32NotLt),
+            I::BranchF32NotLe(instr) => init_offset!(instr, new_offset, Cmp::F32NotLe),
             I::BranchF64Eq(instr) => init_offset!(instr, new_offset, Cmp::F64Eq),
             I::BranchF64Ne(instr) => init_offset!(instr, new_offset, Cmp::F64Ne),
             I::BranchF64Lt(instr) => init_offset!(instr, new_offset, Cmp::F64Lt),
             I::BranchF64Le(instr) => init_offset!(instr, new_offset, Cmp::F64Le),
             I::BranchF64Gt(instr) => init_offset!(instr, new_offset, Cmp::F64Gt),
             I::BranchF64Ge(instr) => init_offset!(instr, new_offset, Cmp::F64Ge),
+            I::BranchF64NotLt(instr) => init_offset!(instr, new_offset, Cmp::F64NotLt),
+            I::BranchF64NotLe(instr) => init_offset!(instr, new_offset, Cmp::F64NotLe),
             I::BranchI32AndImm(instr) => init_offset_imm!(i32, instr, new_offset, Cmp::I32And),
             I::BranchI32OrImm(instr) => init_offset_imm!(i32, instr, new_offset, Cmp::I32Or),
             I::BranchI32XorImm(instr) => init_offset_imm!(i32, instr, new_offset, Cmp::I32Xor),
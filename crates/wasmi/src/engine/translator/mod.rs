@@ -4,8 +4,10 @@ mod control_frame;
 mod control_stack;
 mod driver;
 mod error;
+mod inline;
 mod instr_encoder;
 mod labels;
+mod optimizer;
 mod relink_result;
 mod stack;
 mod typed_value;
@@ -53,16 +55,22 @@ use crate::{
             SignatureIdx,
         },
         config::FuelCosts,
+        fuel::{FuelBreakdown, FuelUsage},
+        register_types::{CallSiteTypes, RegisterTypes},
         BlockType,
         CompiledFunc,
+        DedupFuncType,
+        Intrinsic,
+        ModuleLimit,
+        INTRINSICS_MODULE,
     },
-    module::{FuncIdx, FuncTypeIdx, ModuleHeader},
+    module::{FuncIdx, FuncTypeIdx, ModuleHeader, WasmiValueType},
     Engine,
     Error,
     FuncType,
 };
 use alloc::vec::Vec;
-use core::fmt;
+use core::{fmt, iter};
 use wasmi_core::{TrapCode, UntypedValue, ValueType};
 use wasmparser::{
     BinaryReaderError,
@@ -71,6 +79,7 @@ use wasmparser::{
     MemArg,
     ValidatorResources,
     VisitOperator,
+    WasmModuleResources,
 };
 
 /// Reusable allocations of a [`FuncTranslator`].
@@ -86,28 +95,44 @@ pub struct FuncTranslatorAllocations {
     buffer: Vec<TypedProvider>,
     /// Buffer to temporarily store `br_table` target depths.
     br_table_targets: Vec<u32>,
+    /// The types of the function parameters and locals.
+    ///
+    /// # Note
+    ///
+    /// Only filled if [`Config::generate_register_types`] is enabled.
+    ///
+    /// [`Config::generate_register_types`]: crate::Config::generate_register_types
+    local_types: Vec<ValueType>,
 }
 
 impl FuncTranslatorAllocations {
     /// Resets the [`FuncTranslatorAllocations`].
-    fn reset(&mut self) {
+    ///
+    /// Enables the generation of an address map if `address_map` is `true`.
+    fn reset(&mut self, address_map: bool) {
         self.stack.reset();
-        self.instr_encoder.reset();
+        self.instr_encoder.reset(address_map);
         self.control_stack.reset();
         self.buffer.clear();
         self.br_table_targets.clear();
+        self.local_types.clear();
     }
 }
 
 /// The used function validator type.
-type FuncValidator = wasmparser::FuncValidator<wasmparser::ValidatorResources>;
+///
+/// # Note
+///
+/// The module resources `R` are [`ValidatorResources`] unless the function
+/// is validated outside of its Wasm module via [`Engine::translate_func`].
+type FuncValidator<R = ValidatorResources> = wasmparser::FuncValidator<R>;
 
 /// A Wasm to Wasmi IR function translator that also validates its input.
-pub struct ValidatingFuncTranslator<T> {
+pub struct ValidatingFuncTranslator<T, R = ValidatorResources> {
     /// The current position in the Wasm binary while parsing operators.
     pos: usize,
     /// The Wasm function validator.
-    validator: FuncValidator,
+    validator: FuncValidator<R>,
     /// The chosen function translator.
     translator: T,
 }
@@ -171,7 +196,10 @@ pub trait WasmTranslator<'parser>: VisitOperator<'parser, Output = Result<(), Er
     ///
     /// # Note
     ///
-    /// This information is mainly required for properly locating translation errors.
+    /// This information is mainly required for properly locating translation errors
+    /// and for generating address maps via [`Config::generate_address_map`].
+    ///
+    /// [`Config::generate_address_map`]: crate::Config::generate_address_map
     fn update_pos(&mut self, pos: usize);
 
     /// Finishes constructing the Wasm function translation.
@@ -183,9 +211,12 @@ pub trait WasmTranslator<'parser>: VisitOperator<'parser, Output = Result<(), Er
     fn finish(self, finalize: impl FnOnce(CompiledFuncEntity)) -> Result<Self::Allocations, Error>;
 }
 
-impl<T> ValidatingFuncTranslator<T> {
+impl<T, R> ValidatingFuncTranslator<T, R>
+where
+    R: WasmModuleResources,
+{
     /// Creates a new [`ValidatingFuncTranslator`].
-    pub fn new(validator: FuncValidator, translator: T) -> Result<Self, Error> {
+    pub fn new(validator: FuncValidator<R>, translator: T) -> Result<Self, Error> {
         Ok(Self {
             pos: 0,
             validator,
@@ -205,7 +236,7 @@ impl<T> ValidatingFuncTranslator<T> {
         translate: Translate,
     ) -> Result<(), Error>
     where
-        Validate: FnOnce(&mut FuncValidator) -> Result<(), BinaryReaderError>,
+        Validate: FnOnce(&mut FuncValidator<R>) -> Result<(), BinaryReaderError>,
         Translate: FnOnce(&mut T) -> Result<(), Error>,
     {
         validate(&mut self.validator)?;
@@ -214,9 +245,10 @@ impl<T> ValidatingFuncTranslator<T> {
     }
 }
 
-impl<'parser, T> WasmTranslator<'parser> for ValidatingFuncTranslator<T>
+impl<'parser, T, R> WasmTranslator<'parser> for ValidatingFuncTranslator<T, R>
 where
     T: WasmTranslator<'parser>,
+    R: WasmModuleResources,
 {
     type Allocations = ReusableAllocations<T::Allocations>;
 
@@ -245,6 +277,7 @@ where
 
     fn update_pos(&mut self, pos: usize) {
         self.pos = pos;
+        self.translator.update_pos(pos);
     }
 
     fn finish(
@@ -316,9 +349,10 @@ macro_rules! impl_visit_operator {
     () => {};
 }
 
-impl<'a, T> VisitOperator<'a> for ValidatingFuncTranslator<T>
+impl<'a, T, R> VisitOperator<'a> for ValidatingFuncTranslator<T, R>
 where
     T: WasmTranslator<'a>,
+    R: WasmModuleResources,
 {
     type Output = Result<(), Error>;
 
@@ -331,6 +365,8 @@ pub struct LazyFuncTranslator {
     func_idx: FuncIdx,
     /// The identifier of the to be compiled function.
     compiled_func: CompiledFunc,
+    /// The offset of the function body within the Wasm binary.
+    offset: usize,
     /// The Wasm module header information used for translation.
     module: ModuleHeader,
     /// Optional information about lazy Wasm validation.
@@ -342,6 +378,7 @@ impl fmt::Debug for LazyFuncTranslator {
         f.debug_struct("LazyFuncTranslator")
             .field("func_idx", &self.func_idx)
             .field("compiled_func", &self.compiled_func)
+            .field("offset", &self.offset)
             .field("module", &self.module)
             .field("validate", &self.func_to_validate.is_some())
             .finish()
@@ -353,12 +390,14 @@ impl LazyFuncTranslator {
     pub fn new(
         func_idx: FuncIdx,
         compiled_func: CompiledFunc,
+        offset: usize,
         module: ModuleHeader,
         func_to_validate: Option<FuncToValidate<ValidatorResources>>,
     ) -> Self {
         Self {
             func_idx,
             compiled_func,
+            offset,
             module,
             func_to_validate,
         }
@@ -381,6 +420,7 @@ impl<'parser> WasmTranslator<'parser> for LazyFuncTranslator {
             .init_lazy_func(
                 self.func_idx,
                 self.compiled_func,
+                self.offset,
                 bytes,
                 &self.module,
                 self.func_to_validate.take(),
@@ -435,6 +475,8 @@ impl<'a> VisitOperator<'a> for LazyFuncTranslator {
 pub struct FuncTranslator {
     /// The reference to the Wasm module function under construction.
     func: FuncIdx,
+    /// The function type of the Wasm module function under construction.
+    func_type: DedupFuncType,
     /// The engine for which the function is compiled.
     ///
     /// # Note
@@ -459,6 +501,10 @@ pub struct FuncTranslator {
     ///
     /// `None` if fuel metering is disabled.
     fuel_costs: Option<FuelCosts>,
+    /// The number of local variables registered so far, excluding function parameters.
+    len_locals: u32,
+    /// The remaining number of instruction words that may be inlined into the function.
+    inline_budget: usize,
     /// The reusable data structures of the [`FuncTranslator`].
     alloc: FuncTranslatorAllocations,
 }
@@ -466,16 +512,34 @@ pub struct FuncTranslator {
 impl<'parser> WasmTranslator<'parser> for FuncTranslator {
     type Allocations = FuncTranslatorAllocations;
 
-    fn setup(&mut self, _bytes: &[u8]) -> Result<bool, Error> {
+    fn setup(&mut self, bytes: &[u8]) -> Result<bool, Error> {
+        let len_bytes = u32::try_from(bytes.len()).unwrap_or(u32::MAX);
+        self.engine()
+            .config()
+            .module_limits()
+            .ensure(ModuleLimit::FunctionBodyBytes, len_bytes)?;
         Ok(false)
     }
 
     fn translate_locals(
         &mut self,
         amount: u32,
-        _value_type: wasmparser::ValType,
+        value_type: wasmparser::ValType,
     ) -> Result<(), Error> {
-        self.alloc.stack.register_locals(amount)
+        let len_locals = self.len_locals.saturating_add(amount);
+        self.engine()
+            .config()
+            .module_limits()
+            .ensure(ModuleLimit::Locals, len_locals)?;
+        self.len_locals = len_locals;
+        self.alloc.stack.register_locals(amount)?;
+        if self.engine().config().get_generate_register_types() {
+            let value_type = WasmiValueType::from(value_type).into_inner();
+            self.alloc
+                .local_types
+                .extend(iter::repeat_n(value_type, amount as usize));
+        }
+        Ok(())
     }
 
     fn finish_translate_locals(&mut self) -> Result<(), Error> {
@@ -483,7 +547,9 @@ impl<'parser> WasmTranslator<'parser> for FuncTranslator {
         Ok(())
     }
 
-    fn update_pos(&mut self, _pos: usize) {}
+    fn update_pos(&mut self, pos: usize) {
+        self.alloc.instr_encoder.update_pos(pos);
+    }
 
     fn finish(
         mut self,
@@ -496,24 +562,56 @@ impl<'parser> WasmTranslator<'parser> for FuncTranslator {
             .instr_encoder
             .update_branch_offsets(&mut self.alloc.stack)?;
         let len_registers = self.alloc.stack.len_registers();
+        let limit = self.engine().config().get_max_registers_per_function();
+        if u32::from(len_registers) > limit {
+            return Err(Error::from(TranslationError::TooManyRegisters {
+                func: self.func.into_u32(),
+                len_registers: u32::from(len_registers),
+                limit,
+            }));
+        }
         if let Some(fuel_costs) = self.fuel_costs() {
             // Note: Fuel metering is enabled so we need to bump the fuel
             //       of the function enclosing Wasm `block` by an amount
             //       that depends on the total number of registers used by
             //       the compiled function.
+            // Note: The scratch registers of inlined calls are not accounted
+            //       for since the fuel of inlined functions covers them.
+            let len_registers = self.alloc.stack.len_metered_registers();
             // Note: The function enclosing block fuel instruction is always
             //       the instruction at the 0th index if fuel metering is enabled.
             let fuel_instr = Instr::from_u32(0);
             let fuel_info = FuelInfo::some(*fuel_costs, fuel_instr);
             self.alloc
                 .instr_encoder
-                .bump_fuel_consumption(fuel_info, |costs| {
-                    costs.fuel_for_copies(u64::from(len_registers))
-                })?;
+                .bump_fuel_consumption(fuel_info, FuelUsage::Copies(u64::from(len_registers)))?;
+        }
+        if self.is_optimizing() {
+            self.alloc.instr_encoder.optimize(&self.module)?;
         }
         let func_consts = self.alloc.stack.func_local_consts();
+        let address_map = self.alloc.instr_encoder.address_map();
+        let trap_sites = self.alloc.instr_encoder.trap_sites();
+        let fuel_sites = self.alloc.instr_encoder.fuel_sites();
         let instrs = self.alloc.instr_encoder.drain_instrs();
-        finalize(CompiledFuncEntity::new(len_registers, instrs, func_consts));
+        let mut func = CompiledFuncEntity::new(len_registers, instrs, func_consts)
+            .with_func_index(self.func.into_u32())
+            .with_trap_sites(trap_sites);
+        if let Some(fuel_costs) = self.fuel_costs() {
+            func = func.with_fuel_sites(*fuel_costs, fuel_sites);
+        }
+        if let Some(address_map) = address_map {
+            func = func.with_address_map(address_map);
+        }
+        if self.engine().config().get_generate_register_types() {
+            let register_types = self.register_types(func.instrs());
+            func = func.with_register_types(register_types);
+        }
+        #[cfg(debug_assertions)]
+        if let Err(error) = crate::engine::bytecode::verify_instrs(func.instrs()) {
+            panic!("translated invalid Wasmi bytecode: {error}")
+        }
+        finalize(func);
         Ok(self.into_allocations())
     }
 }
@@ -563,6 +661,22 @@ impl FuncTranslator {
         func: FuncIdx,
         res: ModuleHeader,
         alloc: FuncTranslatorAllocations,
+    ) -> Result<Self, Error> {
+        let func_type = *res.get_type_of_func(func);
+        Self::new_with_type(func, func_type, res, alloc)
+    }
+
+    /// Creates a new [`FuncTranslator`] for a function of type `func_type`.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`FuncTranslator::new`] this does not require `func` to be part of `res`.
+    /// This is used to translate functions outside of their Wasm module.
+    pub fn new_with_type(
+        func: FuncIdx,
+        func_type: DedupFuncType,
+        res: ModuleHeader,
+        alloc: FuncTranslatorAllocations,
     ) -> Result<Self, Error> {
         let Some(engine) = res.engine().upgrade() else {
             panic!(
@@ -570,17 +684,19 @@ impl FuncTranslator {
                 res.engine()
             )
         };
-        let config = engine.config();
-        let fuel_costs = config
-            .get_consume_fuel()
-            .then(|| config.fuel_costs())
-            .copied();
+        let fuel_costs = engine
+            .config()
+            .get_fuel_checks()
+            .then(|| engine.fuel_costs());
         Self {
             func,
+            func_type,
             engine,
             module: res,
             reachable: true,
             fuel_costs,
+            len_locals: 0,
+            inline_budget: inline::INLINE_BUDGET,
             alloc,
         }
         .init()
@@ -591,9 +707,30 @@ impl FuncTranslator {
         &self.engine
     }
 
+    /// Returns `true` if the bytecode optimizations of [`Config::optimization_level`] are enabled.
+    ///
+    /// [`Config::optimization_level`]: crate::Config::optimization_level
+    fn is_optimizing(&self) -> bool {
+        self.engine().config().get_optimization_level() >= 1
+    }
+
+    /// Pushes the call `instr` with `len_results` results.
+    ///
+    /// # Note
+    ///
+    /// If optimizations are enabled a `return` of exactly the results of the call
+    /// is encoded as [`Instruction::ReturnForward`].
+    fn push_call(&mut self, instr: Instruction, len_results: usize) -> Result<Instr, Error> {
+        if self.is_optimizing() {
+            return self.alloc.instr_encoder.push_call(instr, len_results);
+        }
+        self.alloc.instr_encoder.push_instr(instr)
+    }
+
     /// Initializes a newly constructed [`FuncTranslator`].
     fn init(mut self) -> Result<Self, Error> {
-        self.alloc.reset();
+        let address_map = self.engine().config().get_generate_address_map();
+        self.alloc.reset(address_map);
         self.init_func_body_block()?;
         self.init_func_params()?;
         Ok(self)
@@ -601,8 +738,7 @@ impl FuncTranslator {
 
     /// Registers the `block` control frame surrounding the entire function body.
     fn init_func_body_block(&mut self) -> Result<(), Error> {
-        let func_type = self.module.get_type_of_func(self.func);
-        let block_type = BlockType::func_type(func_type);
+        let block_type = BlockType::func_type(&self.func_type);
         let end_label = self.alloc.instr_encoder.new_label();
         let consume_fuel = self.make_fuel_instr()?;
         // Note: we use a dummy `RegisterSpan` as placeholder.
@@ -623,12 +759,50 @@ impl FuncTranslator {
 
     /// Registers the function parameters in the emulated value stack.
     fn init_func_params(&mut self) -> Result<(), Error> {
-        for _param_type in self.func_type().params() {
+        let func_type = self.func_type();
+        for _param_type in func_type.params() {
             self.alloc.stack.register_locals(1)?;
         }
+        if self.engine().config().get_generate_register_types() {
+            self.alloc.local_types.extend_from_slice(func_type.params());
+        }
         Ok(())
     }
 
+    /// Returns the [`RegisterTypes`] of the translated function with `instrs`.
+    ///
+    /// # Note
+    ///
+    /// The result types of call instructions are resolved from the signatures of their callees.
+    fn register_types(&self, instrs: &[Instruction]) -> RegisterTypes {
+        let call_sites = instrs.iter().enumerate().filter_map(|(index, instr)| {
+            let (results, func_type) = match *instr {
+                Instruction::CallInternal0 { results, func }
+                | Instruction::CallInternal { results, func } => {
+                    let func_index = self.module.get_func_index(func)?;
+                    (results, self.func_type_of(func_index))
+                }
+                Instruction::CallImported0 { results, func }
+                | Instruction::CallImported { results, func } => {
+                    (results, self.func_type_of(FuncIdx::from(func.to_u32())))
+                }
+                Instruction::CallIndirect0 { results, func_type }
+                | Instruction::CallIndirect { results, func_type } => {
+                    (results, self.func_type_at(func_type))
+                }
+                _ => return None,
+            };
+            let instr = u32::try_from(index)
+                .unwrap_or_else(|error| panic!("out of bounds instruction index {index}: {error}"));
+            Some(CallSiteTypes::new(
+                instr,
+                results,
+                func_type.results().iter().copied(),
+            ))
+        });
+        RegisterTypes::new(self.alloc.local_types.iter().copied(), call_sites)
+    }
+
     /// Consumes `self` and returns the underlying reusable [`FuncTranslatorAllocations`].
     fn into_allocations(self) -> FuncTranslatorAllocations {
         self.alloc
@@ -636,9 +810,8 @@ impl FuncTranslator {
 
     /// Returns the [`FuncType`] of the function that is currently translated.
     fn func_type(&self) -> FuncType {
-        let dedup_func_type = self.module.get_type_of_func(self.func);
         self.engine()
-            .resolve_func_type(dedup_func_type, Clone::clone)
+            .resolve_func_type(&self.func_type, Clone::clone)
     }
 
     /// Resolves the [`FuncType`] of the given [`FuncTypeIdx`].
@@ -656,12 +829,39 @@ impl FuncTranslator {
             .resolve_func_type(dedup_func_type, Clone::clone)
     }
 
+    /// Returns the [`Intrinsic`] if `func_index` refers to an imported intrinsic function.
+    ///
+    /// Returns `None` if the signature of the imported function
+    /// does not match the signature of the [`Intrinsic`].
+    fn intrinsic_of(&self, func_index: FuncIdx) -> Option<Intrinsic> {
+        let name = self.module.get_import_name_of_func(func_index)?;
+        if name.module() != INTRINSICS_MODULE {
+            return None;
+        }
+        let intrinsic = Intrinsic::from_name(name.name())?;
+        (intrinsic.func_type() == self.func_type_of(func_index)).then_some(intrinsic)
+    }
+
+    /// Translates a call to an imported [`Intrinsic`] function as its dedicated [`Instruction`].
+    fn translate_intrinsic(&mut self, intrinsic: Intrinsic) -> Result<(), Error> {
+        let (lhs, rhs) = self.alloc.stack.pop2();
+        let lhs = match lhs {
+            TypedProvider::Register(lhs) => lhs,
+            TypedProvider::Const(lhs) => self.alloc.stack.alloc_const(lhs)?,
+        };
+        let rhs = match rhs {
+            TypedProvider::Register(rhs) => rhs,
+            TypedProvider::Const(rhs) => self.alloc.stack.alloc_const(rhs)?,
+        };
+        self.push_binary_instr(lhs, rhs, intrinsic.make_instr())
+    }
+
     /// Returns `true` if the code at the current translation position is reachable.
     fn is_reachable(&self) -> bool {
         self.reachable
     }
 
-    /// Returns the configured [`FuelCosts`] of the [`Engine`] if any.
+    /// Returns the [`FuelCosts`] of the [`Engine`] used for the translation if any.
     ///
     /// Returns `None` if fuel metering is disabled.
     fn fuel_costs(&self) -> Option<&FuelCosts> {
@@ -697,23 +897,22 @@ impl FuncTranslator {
             // Fuel metering is disabled so there is no need to create an `Instruction::ConsumeFuel`.
             return Ok(None);
         };
-        let fuel_instr = Instruction::consume_fuel(fuel_costs.base())
-            .expect("base fuel must be valid for creating `Instruction::ConsumeFuel`");
-        let instr = self.alloc.instr_encoder.push_instr(fuel_instr)?;
+        let fuel_costs = *fuel_costs;
+        let instr = self
+            .alloc
+            .instr_encoder
+            .push_fuel_instr(&fuel_costs, FuelBreakdown::from(FuelUsage::Base))?;
         Ok(Some(instr))
     }
 
     /// Bumps fuel consumption of the most recent [`Instruction::ConsumeFuel`] in the translation process.
     ///
     /// Does nothing if gas metering is disabled.
-    fn bump_fuel_consumption<F>(&mut self, f: F) -> Result<(), Error>
-    where
-        F: FnOnce(&FuelCosts) -> u64,
-    {
+    fn bump_fuel_consumption(&mut self, usage: FuelUsage) -> Result<(), Error> {
         let fuel_info = self.fuel_info();
         self.alloc
             .instr_encoder
-            .bump_fuel_consumption(fuel_info, f)?;
+            .bump_fuel_consumption(fuel_info, usage)?;
         Ok(())
     }
 
@@ -722,11 +921,8 @@ impl FuncTranslator {
     /// # Note
     ///
     /// Fuel metering is only encoded or adjusted if it is enabled.
-    fn push_fueled_instr<F>(&mut self, instr: Instruction, f: F) -> Result<Instr, Error>
-    where
-        F: FnOnce(&FuelCosts) -> u64,
-    {
-        self.bump_fuel_consumption(f)?;
+    fn push_fueled_instr(&mut self, instr: Instruction, usage: FuelUsage) -> Result<Instr, Error> {
+        self.bump_fuel_consumption(usage)?;
         self.alloc.instr_encoder.push_instr(instr)
     }
 
@@ -736,7 +932,46 @@ impl FuncTranslator {
     ///
     /// Fuel metering is only encoded or adjusted if it is enabled.
     fn push_base_instr(&mut self, instr: Instruction) -> Result<Instr, Error> {
-        self.push_fueled_instr(instr, FuelCosts::base)
+        self.push_fueled_instr(instr, FuelUsage::Base)
+    }
+
+    /// Pushes a `br_table` instruction for the `br_table_targets` buffer with `index`.
+    ///
+    /// # Note
+    ///
+    /// - Encodes an [`Instruction::BranchTableSparse`] if more than half of the
+    ///   targets are the `default_target` and an [`Instruction::BranchTable`] otherwise.
+    /// - Afterwards the `br_table_targets` buffer holds the targets to encode in order
+    ///   which always end with the `default_target`.
+    fn push_branch_table(&mut self, index: Register, default_target: u32) -> Result<(), Error> {
+        let targets = &self.alloc.br_table_targets;
+        let len_targets = u32::try_from(targets.len())
+            .map_err(|_| TranslationError::BranchTableTargetsOutOfBounds)?;
+        let len_default = targets
+            .iter()
+            .filter(|&&target| target == default_target)
+            .count() as u32;
+        // Note: the `default_target` itself is not a case.
+        let len_cases = len_targets - len_default;
+        if len_default - 1 <= len_cases {
+            self.push_base_instr(Instruction::branch_table(index, len_targets))?;
+            return Ok(());
+        }
+        self.push_base_instr(Instruction::branch_table_sparse(index, len_cases))?;
+        for case in 0..len_targets - 1 {
+            if self.alloc.br_table_targets[case as usize] != default_target {
+                self.alloc
+                    .instr_encoder
+                    .append_instr(Instruction::const32(case))?;
+            }
+        }
+        let len_targets = len_targets as usize;
+        let mut case = 0;
+        self.alloc.br_table_targets.retain(|&target| {
+            case += 1;
+            case == len_targets || target != default_target
+        });
+        Ok(())
     }
 
     /// Convenience function to copy the parameters when branching to a control frame.
@@ -1122,7 +1357,7 @@ impl FuncTranslator {
         make_instr: fn(result: Register, lhs: Register, rhs: Register) -> Instruction,
     ) -> Result<(), Error> {
         let result = self.alloc.stack.push_dynamic()?;
-        self.push_fueled_instr(make_instr(result, lhs, rhs), FuelCosts::base)?;
+        self.push_fueled_instr(make_instr(result, lhs, rhs), FuelUsage::Base)?;
         Ok(())
     }
 
@@ -1145,7 +1380,7 @@ impl FuncTranslator {
         if let Ok(rhs) = rhs.try_into() {
             // Optimization: We can use a compact instruction for small constants.
             let result = self.alloc.stack.push_dynamic()?;
-            self.push_fueled_instr(make_instr_imm16(result, lhs, rhs), FuelCosts::base)?;
+            self.push_fueled_instr(make_instr_imm16(result, lhs, rhs), FuelUsage::Base)?;
             return Ok(true);
         }
         Ok(false)
@@ -1164,7 +1399,7 @@ impl FuncTranslator {
         if let Ok(lhs) = lhs.try_into() {
             // Optimization: We can use a compact instruction for small constants.
             let result = self.alloc.stack.push_dynamic()?;
-            self.push_fueled_instr(make_instr_imm16(result, lhs, rhs), FuelCosts::base)?;
+            self.push_fueled_instr(make_instr_imm16(result, lhs, rhs), FuelUsage::Base)?;
             return Ok(true);
         }
         Ok(false)
@@ -1198,7 +1433,7 @@ impl FuncTranslator {
     {
         let result = self.alloc.stack.push_dynamic()?;
         let rhs = self.alloc.stack.alloc_const(rhs)?;
-        self.push_fueled_instr(make_instr(result, lhs, rhs), FuelCosts::base)?;
+        self.push_fueled_instr(make_instr(result, lhs, rhs), FuelUsage::Base)?;
         Ok(())
     }
 
@@ -1219,14 +1454,14 @@ impl FuncTranslator {
     {
         let result = self.alloc.stack.push_dynamic()?;
         let lhs = self.alloc.stack.alloc_const(lhs)?;
-        self.push_fueled_instr(make_instr(result, lhs, rhs), FuelCosts::base)?;
+        self.push_fueled_instr(make_instr(result, lhs, rhs), FuelUsage::Base)?;
         Ok(())
     }
 
     /// Translates a [`TrapCode`] as [`Instruction`].
     fn translate_trap(&mut self, trap_code: TrapCode) -> Result<(), Error> {
         bail_unreachable!(self);
-        self.push_fueled_instr(Instruction::Trap(trap_code), FuelCosts::base)?;
+        self.push_fueled_instr(Instruction::Trap(trap_code), FuelUsage::Base)?;
         self.reachable = false;
         Ok(())
     }
@@ -1577,7 +1812,7 @@ impl FuncTranslator {
                 let result = self.alloc.stack.push_dynamic()?;
                 self.push_fueled_instr(
                     make_instr_imm(result, lhs, <Const16<T>>::from(rhs)),
-                    FuelCosts::base,
+                    FuelUsage::Base,
                 )?;
                 Ok(())
             }
@@ -1694,7 +1929,7 @@ impl FuncTranslator {
         match self.alloc.stack.pop() {
             TypedProvider::Register(input) => {
                 let result = self.alloc.stack.push_dynamic()?;
-                self.push_fueled_instr(make_instr(result, input), FuelCosts::base)?;
+                self.push_fueled_instr(make_instr(result, input), FuelUsage::Base)?;
                 Ok(())
             }
             TypedProvider::Const(input) => {
@@ -1714,7 +1949,7 @@ impl FuncTranslator {
         match self.alloc.stack.pop() {
             TypedProvider::Register(input) => {
                 let result = self.alloc.stack.push_dynamic()?;
-                self.push_fueled_instr(make_instr(result, input), FuelCosts::base)?;
+                self.push_fueled_instr(make_instr(result, input), FuelUsage::Base)?;
                 Ok(())
             }
             TypedProvider::Const(input) => match consteval(input) {
@@ -1729,10 +1964,19 @@ impl FuncTranslator {
 
     /// Returns the 32-bit [`MemArg`] offset.
     ///
+    /// # Note
+    ///
+    /// All loads and stores access the default linear memory since Wasm validation
+    /// rejects [`MemArg`]s referring to other linear memories.
+    ///
     /// # Panics
     ///
     /// If the [`MemArg`] offset is not 32-bit.
     fn memarg_offset(memarg: MemArg) -> u32 {
+        debug_assert_eq!(
+            memarg.memory, 0,
+            "wasmi does not yet support the multi-memory Wasm proposal"
+        );
         u32::try_from(memarg.offset).unwrap_or_else(|_| {
             panic!(
                 "encountered 64-bit memory load/store offset: {}",
@@ -1789,12 +2033,12 @@ impl FuncTranslator {
                     let result = self.alloc.stack.push_dynamic()?;
                     self.push_fueled_instr(
                         make_instr_offset16(result, ptr, offset),
-                        FuelCosts::load,
+                        FuelUsage::Load,
                     )?;
                     return Ok(());
                 }
                 let result = self.alloc.stack.push_dynamic()?;
-                self.push_fueled_instr(make_instr(result, ptr), FuelCosts::load)?;
+                self.push_fueled_instr(make_instr(result, ptr), FuelUsage::Load)?;
                 self.alloc
                     .instr_encoder
                     .append_instr(Instruction::const32(offset))?;
@@ -1805,7 +2049,7 @@ impl FuncTranslator {
                     let result = this.alloc.stack.push_dynamic()?;
                     this.push_fueled_instr(
                         make_instr_at(result, Const32::from(address)),
-                        FuelCosts::load,
+                        FuelUsage::Load,
                     )?;
                     Ok(())
                 })
@@ -1845,13 +2089,13 @@ impl FuncTranslator {
                 if let Ok(offset) = u16::try_from(offset) {
                     self.push_fueled_instr(
                         make_instr_offset16(ptr, offset, value),
-                        FuelCosts::store,
+                        FuelUsage::Store,
                     )?;
                     Ok(())
                 } else {
                     self.push_fueled_instr(
                         make_instr(ptr, Const32::from(offset)),
-                        FuelCosts::store,
+                        FuelUsage::Store,
                     )?;
                     self.alloc
                         .instr_encoder
@@ -1866,7 +2110,7 @@ impl FuncTranslator {
                     (Ok(offset), Ok(value)) => {
                         self.push_fueled_instr(
                             make_instr_offset16_imm(ptr, offset, value),
-                            FuelCosts::store,
+                            FuelUsage::Store,
                         )?;
                         Ok(())
                     }
@@ -1874,14 +2118,14 @@ impl FuncTranslator {
                         let value = self.alloc.stack.alloc_const(value)?;
                         self.push_fueled_instr(
                             make_instr_offset16(ptr, offset, value),
-                            FuelCosts::store,
+                            FuelUsage::Store,
                         )?;
                         Ok(())
                     }
                     (Err(_), _) => {
                         self.push_fueled_instr(
                             make_instr(ptr, Const32::from(offset)),
-                            FuelCosts::store,
+                            FuelUsage::Store,
                         )?;
                         self.alloc
                             .instr_encoder
@@ -1896,7 +2140,7 @@ impl FuncTranslator {
                 .effective_address_and(ptr, offset, |this, address| {
                     this.push_fueled_instr(
                         make_instr_at(Const32::from(address), value),
-                        FuelCosts::store,
+                        FuelUsage::Store,
                     )?;
                     Ok(())
                 }),
@@ -1905,14 +2149,14 @@ impl FuncTranslator {
                     if let Ok(value) = U::try_from(T::from(value)) {
                         this.push_fueled_instr(
                             make_instr_at_imm(Const32::from(address), value),
-                            FuelCosts::store,
+                            FuelUsage::Store,
                         )?;
                         Ok(())
                     } else {
                         let value = this.alloc.stack.alloc_const(value)?;
                         this.push_fueled_instr(
                             make_instr_at(Const32::from(address), value),
-                            FuelCosts::store,
+                            FuelUsage::Store,
                         )?;
                         Ok(())
                     }
@@ -1947,13 +2191,13 @@ impl FuncTranslator {
                 if let Ok(offset) = u16::try_from(offset) {
                     self.push_fueled_instr(
                         make_instr_offset16(ptr, offset, value),
-                        FuelCosts::store,
+                        FuelUsage::Store,
                     )?;
                     Ok(())
                 } else {
                     self.push_fueled_instr(
                         make_instr(ptr, Const32::from(offset)),
-                        FuelCosts::store,
+                        FuelUsage::Store,
                     )?;
                     self.alloc
                         .instr_encoder
@@ -1968,14 +2212,14 @@ impl FuncTranslator {
                         let value = self.alloc.stack.alloc_const(value)?;
                         self.push_fueled_instr(
                             make_instr_offset16(ptr, offset, value),
-                            FuelCosts::store,
+                            FuelUsage::Store,
                         )?;
                         Ok(())
                     }
                     Err(_) => {
                         self.push_fueled_instr(
                             make_instr(ptr, Const32::from(offset)),
-                            FuelCosts::store,
+                            FuelUsage::Store,
                         )?;
                         self.alloc
                             .instr_encoder
@@ -1990,7 +2234,7 @@ impl FuncTranslator {
                 .effective_address_and(ptr, offset, |this, address| {
                     this.push_fueled_instr(
                         make_instr_at(Const32::from(address), value),
-                        FuelCosts::store,
+                        FuelUsage::Store,
                     )?;
                     Ok(())
                 }),
@@ -1999,7 +2243,7 @@ impl FuncTranslator {
                     let value = this.alloc.stack.alloc_const(value)?;
                     this.push_fueled_instr(
                         make_instr_at(Const32::from(address), value),
-                        FuelCosts::store,
+                        FuelUsage::Store,
                     )?;
                     Ok(())
                 })
@@ -2034,7 +2278,7 @@ impl FuncTranslator {
                 lhs_or_rhs: Register,
             ) -> Instruction,
         ) -> Result<(), Error> {
-            this.push_fueled_instr(make_instr(result, condition, reg_in), FuelCosts::base)?;
+            this.push_fueled_instr(make_instr(result, condition, reg_in), FuelUsage::Base)?;
             let rhs = this.alloc.stack.alloc_const(imm_in)?;
             this.alloc
                 .instr_encoder
@@ -2060,7 +2304,7 @@ impl FuncTranslator {
                 lhs_or_rhs: Register,
             ) -> Instruction,
         ) -> Result<(), Error> {
-            this.push_fueled_instr(make_instr(result, condition, reg_in), FuelCosts::base)?;
+            this.push_fueled_instr(make_instr(result, condition, reg_in), FuelUsage::Base)?;
             this.alloc
                 .instr_encoder
                 .append_instr(Instruction::const32(imm_in))?;
@@ -2092,7 +2336,7 @@ impl FuncTranslator {
         {
             match <Const32<T>>::try_from(imm_in) {
                 Ok(imm_in) => {
-                    this.push_fueled_instr(make_instr(result, condition, reg_in), FuelCosts::base)?;
+                    this.push_fueled_instr(make_instr(result, condition, reg_in), FuelUsage::Base)?;
                     this.alloc
                         .instr_encoder
                         .append_instr(make_instr_param(imm_in))?;
@@ -2123,6 +2367,21 @@ impl FuncTranslator {
                 }
             },
             TypedProvider::Register(condition) => {
+                // # Optimization
+                //
+                // A `select` on `i32.eqz(x)` is a `select` on `x` with swapped operands.
+                let fused = if self.is_optimizing() {
+                    self.alloc
+                        .instr_encoder
+                        .fuse_select_i32_eqz(&mut self.alloc.stack, condition)
+                } else {
+                    None
+                };
+                let (condition, lhs, rhs) = match fused {
+                    Some((condition, true)) => (condition, rhs, lhs),
+                    Some((condition, false)) => (condition, lhs, rhs),
+                    None => (condition, lhs, rhs),
+                };
                 match (lhs, rhs) {
                     (TypedProvider::Register(lhs), TypedProvider::Register(rhs)) => {
                         if lhs == rhs {
@@ -2136,7 +2395,7 @@ impl FuncTranslator {
                         let result = self.alloc.stack.push_dynamic()?;
                         self.push_fueled_instr(
                             Instruction::select(result, condition, lhs),
-                            FuelCosts::base,
+                            FuelUsage::Base,
                         )?;
                         self.alloc
                             .instr_encoder
@@ -2259,7 +2518,7 @@ impl FuncTranslator {
                         ) -> Result<(), Error> {
                             this.push_fueled_instr(
                                 Instruction::select_imm32(result, lhs),
-                                FuelCosts::base,
+                                FuelUsage::Base,
                             )?;
                             this.alloc
                                 .instr_encoder
@@ -2295,7 +2554,7 @@ impl FuncTranslator {
                                 (Some(lhs), Some(rhs)) => {
                                     this.push_fueled_instr(
                                         make_instr(result, lhs),
-                                        FuelCosts::base,
+                                        FuelUsage::Base,
                                     )?;
                                     this.alloc
                                         .instr_encoder
@@ -2306,7 +2565,7 @@ impl FuncTranslator {
                                     let rhs = this.alloc.stack.alloc_const(rhs)?;
                                     this.push_fueled_instr(
                                         Instruction::select_rev(result, condition, rhs),
-                                        FuelCosts::base,
+                                        FuelUsage::Base,
                                     )?;
                                     this.alloc.instr_encoder.append_instr(make_param(lhs))?;
                                     Ok(())
@@ -2315,7 +2574,7 @@ impl FuncTranslator {
                                     let lhs = this.alloc.stack.alloc_const(lhs)?;
                                     this.push_fueled_instr(
                                         Instruction::select(result, condition, lhs),
-                                        FuelCosts::base,
+                                        FuelUsage::Base,
                                     )?;
                                     this.alloc.instr_encoder.append_instr(make_param(rhs))?;
                                     Ok(())
@@ -2346,7 +2605,7 @@ impl FuncTranslator {
                             let rhs = this.alloc.stack.alloc_const(rhs)?;
                             this.push_fueled_instr(
                                 Instruction::select(result, condition, lhs),
-                                FuelCosts::base,
+                                FuelUsage::Base,
                             )?;
                             this.alloc
                                 .instr_encoder
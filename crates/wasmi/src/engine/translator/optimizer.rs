@@ -0,0 +1,799 @@
+//! Optional optimization pass over the translated Wasmi bytecode of a function.
+//!
+//! # Note
+//!
+//! The pass is enabled via [`Config::optimization_level`] and runs after a function
+//! has been fully translated and all of its branch offsets have been resolved.
+//! It applies the following optimizations within the basic blocks of the function:
+//!
+//! - Folds constant values copied via [`Instruction::CopyImm32`] or [`Instruction::CopyI64Imm32`]
+//!   into the 16-bit immediate variants of binary instructions using them.
+//! - Removes copies into registers that are overwritten before their next use.
+//! - Removes unreachable instructions after unconditional branches and returns.
+//!
+//! The fuel costs of blocks are not adjusted for the removed instructions.
+//! However, the [`FuelBreakdown`] of removed [`Instruction::ConsumeFuel`] are discarded.
+//!
+//! [`Config::optimization_level`]: crate::Config::optimization_level
+
+use super::{visit_register::VisitInputRegisters, Instr};
+use crate::{
+    engine::{
+        bytecode::{
+            verify_instr_starts,
+            BinInstr,
+            BranchOffset,
+            BranchOffset16,
+            Const16,
+            Encoding,
+            Instruction,
+            Register,
+        },
+        FuelBreakdown,
+    },
+    module::ModuleHeader,
+    Error,
+};
+use alloc::vec::Vec;
+
+/// Reusable buffers of the optimization pass.
+///
+/// # Note
+///
+/// Those are part of the reusable allocations of the function translator
+/// so that optimizing many functions does not allocate for every function.
+#[derive(Debug, Default)]
+pub struct OptimizerBuffers {
+    /// Stores `true` for all instruction words that begin an instruction.
+    is_instr: Vec<bool>,
+    /// Stores `true` for all instruction words that are targeted by a branch.
+    is_target: Vec<bool>,
+    /// Stores `true` for all instruction words that are going to be removed.
+    removed: Vec<bool>,
+    /// The positions of all instruction words after removing instruction words.
+    new_pos: Vec<i32>,
+}
+
+/// Applies the optimization pass to the `instrs` of a translated function.
+///
+/// # Note
+///
+/// - The `instrs` must have all their branch offsets resolved.
+/// - The Wasm binary `offsets` of the `instrs` are kept in sync if given.
+/// - The Wasm binary `trap_offsets` of the [`Instruction::Trap`] of `instrs` are kept in sync.
+/// - The `fuel_sites` of removed [`Instruction::ConsumeFuel`] of `instrs` are removed.
+///   The [`Instr`] of the kept `fuel_sites` refer to the `instrs` before this operation.
+///
+/// # Errors
+///
+/// If the results of the `instrs` cannot be queried.
+pub fn optimize(
+    instrs: &mut Vec<Instruction>,
+    offsets: Option<&mut Vec<u32>>,
+    trap_offsets: &mut Vec<u32>,
+    fuel_sites: &mut Vec<(Instr, FuelBreakdown)>,
+    module: &ModuleHeader,
+    buffers: &mut OptimizerBuffers,
+) -> Result<(), Error> {
+    let OptimizerBuffers {
+        is_instr,
+        is_target,
+        removed,
+        new_pos,
+    } = buffers;
+    if verify_instr_starts(instrs, is_instr).is_err() {
+        // Note: invalid bytecode is reported by the bytecode verifier instead.
+        return Ok(());
+    };
+    branch_targets(instrs, is_instr, is_target);
+    fold_consts(instrs, is_instr, is_target, module)?;
+    if instrs.iter().any(Instruction::is_branch_fallback) {
+        // Note: fallback branches store their branch offsets as function local
+        //       constant values which cannot be adjusted for removed instructions.
+        return Ok(());
+    }
+    removed.clear();
+    removed.resize(instrs.len(), false);
+    remove_dead_copies(instrs, is_instr, is_target, module, removed)?;
+    remove_unreachable(instrs, is_instr, is_target, removed);
+    retain_kept_traps(instrs, trap_offsets, removed);
+    fuel_sites.retain(|(instr, _)| !removed[instr.into_usize()]);
+    compact(instrs, offsets, is_instr, removed, new_pos);
+    Ok(())
+}
+
+/// Removes the `trap_offsets` of all `removed` [`Instruction::Trap`] of `instrs`.
+///
+/// # Note
+///
+/// The `trap_offsets` are in the same order as the [`Instruction::Trap`] of `instrs`.
+fn retain_kept_traps(instrs: &[Instruction], trap_offsets: &mut Vec<u32>, removed: &[bool]) {
+    let mut removed_traps = instrs
+        .iter()
+        .zip(removed)
+        .filter(|(instr, _)| matches!(instr, Instruction::Trap(_)))
+        .map(|(_, &removed)| removed);
+    trap_offsets.retain(|_| !removed_traps.next().unwrap_or(false));
+}
+
+/// Stores `true` in `is_target` for all instruction words of `instrs` that are targeted by a branch.
+fn branch_targets(instrs: &[Instruction], is_instr: &[bool], is_target: &mut Vec<bool>) {
+    is_target.clear();
+    is_target.resize(instrs.len(), false);
+    for (pos, instr) in instrs.iter().enumerate() {
+        if !is_instr[pos] {
+            continue;
+        }
+        if let Some(offset) = instr.branch_offset() {
+            is_target[branch_target(pos, offset)] = true;
+        }
+    }
+}
+
+/// Returns the position of the instruction targeted by the branch at `pos` with `offset`.
+fn branch_target(pos: usize, offset: BranchOffset) -> usize {
+    pos.checked_add_signed(offset.to_i32() as isize)
+        .unwrap_or_else(|| panic!("branch at {pos} with offset {offset:?} is out of bounds"))
+}
+
+/// Returns `true` if the instruction at `pos` is part of the basic block of its predecessor.
+///
+/// # Note
+///
+/// Basic blocks are conservatively ended before all branch targets and before all
+/// instructions that are not fully understood by the optimizer. Therefore all
+/// instructions within a basic block are single instruction words.
+fn continues_block(instrs: &[Instruction], is_target: &[bool], pos: usize) -> bool {
+    pos < instrs.len() && !is_target[pos] && !instrs[pos].is_block_barrier()
+}
+
+/// Returns `true` if `instr` reads the value of `register`.
+pub(super) fn reads_register(instr: Instruction, register: Register) -> bool {
+    if let Instruction::Copy { value, .. } = instr {
+        // Note: visiting the input registers of a copy also visits its result.
+        return value == register;
+    }
+    let mut instr = instr;
+    let mut reads = false;
+    instr.visit_input_registers(|input| reads |= *input == register);
+    reads
+}
+
+/// Returns `true` if `register` is the single result of `instr`.
+///
+/// # Note
+///
+/// This reuses result relinking to query the result of `instr` which
+/// is the same mechanism used by the `local.set` optimization.
+pub(super) fn writes_register(
+    module: &ModuleHeader,
+    instr: Instruction,
+    register: Register,
+) -> Result<bool, Error> {
+    let mut instr = instr;
+    let placeholder = Register::from_i16(register.to_i16().wrapping_add(1));
+    instr.relink_result(module, placeholder, register)
+}
+
+/// Folds constant values copied to registers into the binary instructions using them.
+fn fold_consts(
+    instrs: &mut [Instruction],
+    is_instr: &[bool],
+    is_target: &[bool],
+    module: &ModuleHeader,
+) -> Result<(), Error> {
+    for pos in 0..instrs.len() {
+        if !is_instr[pos] {
+            continue;
+        }
+        let (register, value) = match instrs[pos] {
+            Instruction::CopyImm32 { result, value } => (result, CopiedConst::I32(value.into())),
+            Instruction::CopyI64Imm32 { result, value } => (result, CopiedConst::I64(value.into())),
+            _ => continue,
+        };
+        let mut user = pos + 1;
+        while continues_block(instrs, is_target, user) {
+            if let Some(folded) = value.fold_into(&instrs[user], register) {
+                instrs[user] = folded;
+            }
+            if writes_register(module, instrs[user], register)? {
+                break;
+            }
+            user += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Marks all copies of `instrs` that are overwritten before their next use as `removed`.
+fn remove_dead_copies(
+    instrs: &[Instruction],
+    is_instr: &[bool],
+    is_target: &[bool],
+    module: &ModuleHeader,
+    removed: &mut [bool],
+) -> Result<(), Error> {
+    for pos in 0..instrs.len() {
+        if !is_instr[pos] {
+            continue;
+        }
+        let register = match instrs[pos] {
+            Instruction::Copy { result, .. }
+            | Instruction::CopyImm32 { result, .. }
+            | Instruction::CopyI64Imm32 { result, .. }
+            | Instruction::CopyF64Imm32 { result, .. } => result,
+            _ => continue,
+        };
+        let mut user = pos + 1;
+        while continues_block(instrs, is_target, user) {
+            if reads_register(instrs[user], register) {
+                break;
+            }
+            if writes_register(module, instrs[user], register)? {
+                removed[pos] = true;
+                break;
+            }
+            user += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Marks all unreachable instruction words of `instrs` as `removed`.
+///
+/// # Note
+///
+/// Instructions are unreachable if they follow an unconditional branch or
+/// return and are not reachable by any branch.
+fn remove_unreachable(
+    instrs: &[Instruction],
+    is_instr: &[bool],
+    is_target: &[bool],
+    removed: &mut [bool],
+) {
+    let mut reachable = true;
+    let mut remove = false;
+    // The number of remaining instructions that belong to a reachable branch table.
+    let mut len_table_entries = 0;
+    for (pos, instr) in instrs.iter().enumerate() {
+        if !is_instr[pos] {
+            // Note: parameter words are removed together with their instruction.
+            removed[pos] |= remove;
+            continue;
+        }
+        reachable |= is_target[pos];
+        if len_table_entries > 0 {
+            // Note: the optional copy and the targets of a branch table are
+            //       reached via the branch table and must be kept in place.
+            len_table_entries -= 1;
+            reachable = len_table_entries != 0;
+            remove = false;
+            continue;
+        }
+        remove = !reachable;
+        removed[pos] |= remove;
+        if remove {
+            continue;
+        }
+        match instr {
+            Instruction::BranchTable { len_targets, .. } => {
+                let has_copy = instrs
+                    .get(pos + 1)
+                    .is_some_and(Instruction::is_branch_table_copy);
+                len_table_entries = u32::from(*len_targets) as usize + usize::from(has_copy);
+            }
+            Instruction::BranchTableSparse { len_cases, .. } => {
+                let len_cases = u32::from(*len_cases) as usize;
+                let has_copy = instrs
+                    .get(pos + 1 + len_cases)
+                    .is_some_and(Instruction::is_branch_table_copy);
+                len_table_entries = len_cases + 1 + usize::from(has_copy);
+            }
+            instr if instr.is_unconditional_exit() => reachable = false,
+            _ => {}
+        }
+    }
+}
+
+/// Removes all `removed` instruction words from `instrs` and adjusts the branch offsets.
+///
+/// Also removes the Wasm binary `offsets` of all `removed` instruction words if given.
+fn compact(
+    instrs: &mut Vec<Instruction>,
+    offsets: Option<&mut Vec<u32>>,
+    is_instr: &[bool],
+    removed: &[bool],
+    new_pos: &mut Vec<i32>,
+) {
+    if !removed.contains(&true) {
+        return;
+    }
+    // The positions of all instruction words after removing the `removed` instruction words.
+    //
+    // Note: removed instruction words are mapped to the position of the next kept
+    //       instruction word which is where branches to them continue execution.
+    new_pos.clear();
+    let mut len_kept = 0_i32;
+    for &removed in removed {
+        new_pos.push(len_kept);
+        len_kept += i32::from(!removed);
+    }
+    for pos in 0..instrs.len() {
+        if !is_instr[pos] || removed[pos] {
+            continue;
+        }
+        if let Some(offset) = instrs[pos].branch_offset() {
+            let target = branch_target(pos, offset);
+            let new_offset = BranchOffset::from(new_pos[target] - new_pos[pos]);
+            instrs[pos].set_branch_offset(new_offset);
+        }
+    }
+    retain_kept(instrs, removed);
+    if let Some(offsets) = offsets {
+        retain_kept(offsets, removed);
+    }
+}
+
+/// Removes all `removed` items from `items`.
+fn retain_kept<T>(items: &mut Vec<T>, removed: &[bool]) {
+    let mut pos = 0;
+    items.retain(|_| {
+        let keep = !removed[pos];
+        pos += 1;
+        keep
+    });
+}
+
+/// A constant value copied to a register.
+#[derive(Debug, Copy, Clone)]
+enum CopiedConst {
+    /// Copied via [`Instruction::CopyImm32`].
+    I32(i32),
+    /// Copied via [`Instruction::CopyI64Imm32`].
+    I64(i64),
+}
+
+impl CopiedConst {
+    /// Returns `instr` with the constant value of `register` folded into it if possible.
+    fn fold_into(self, instr: &Instruction, register: Register) -> Option<Instruction> {
+        match self {
+            Self::I32(value) => fold_i32(instr, register, value),
+            Self::I64(value) => fold_i64(instr, register, value),
+        }
+    }
+}
+
+/// Returns `instr` with the `i32` constant `value` of `register` folded into it if possible.
+fn fold_i32(instr: &Instruction, register: Register, value: i32) -> Option<Instruction> {
+    use Instruction as I;
+    let imm = || <Const16<i32>>::try_from(value).ok();
+    let uimm = || <Const16<u32>>::try_from(value as u32).ok();
+    match *instr {
+        I::I32Add(instr) => fold_bin(instr, register, imm()?, I::i32_add_imm16, I::i32_add_imm16),
+        I::I32Mul(instr) => fold_bin(instr, register, imm()?, I::i32_mul_imm16, I::i32_mul_imm16),
+        I::I32And(instr) => fold_bin(instr, register, imm()?, I::i32_and_imm16, I::i32_and_imm16),
+        I::I32Or(instr) => fold_bin(instr, register, imm()?, I::i32_or_imm16, I::i32_or_imm16),
+        I::I32Xor(instr) => fold_bin(instr, register, imm()?, I::i32_xor_imm16, I::i32_xor_imm16),
+        I::I32Eq(instr) => fold_bin(instr, register, imm()?, I::i32_eq_imm16, I::i32_eq_imm16),
+        I::I32Ne(instr) => fold_bin(instr, register, imm()?, I::i32_ne_imm16, I::i32_ne_imm16),
+        I::I32Sub(instr) => fold_bin(
+            instr,
+            register,
+            imm()?,
+            I::i32_sub_imm16,
+            |result, input, imm| I::i32_sub_imm16_rev(result, imm, input),
+        ),
+        I::I32LtS(instr) => fold_bin(
+            instr,
+            register,
+            imm()?,
+            I::i32_lt_s_imm16,
+            I::i32_gt_s_imm16,
+        ),
+        I::I32LeS(instr) => fold_bin(
+            instr,
+            register,
+            imm()?,
+            I::i32_le_s_imm16,
+            I::i32_ge_s_imm16,
+        ),
+        I::I32GtS(instr) => fold_bin(
+            instr,
+            register,
+            imm()?,
+            I::i32_gt_s_imm16,
+            I::i32_lt_s_imm16,
+        ),
+        I::I32GeS(instr) => fold_bin(
+            instr,
+            register,
+            imm()?,
+            I::i32_ge_s_imm16,
+            I::i32_le_s_imm16,
+        ),
+        I::I32LtU(instr) => fold_bin(
+            instr,
+            register,
+            uimm()?,
+            I::i32_lt_u_imm16,
+            I::i32_gt_u_imm16,
+        ),
+        I::I32LeU(instr) => fold_bin(
+            instr,
+            register,
+            uimm()?,
+            I::i32_le_u_imm16,
+            I::i32_ge_u_imm16,
+        ),
+        I::I32GtU(instr) => fold_bin(
+            instr,
+            register,
+            uimm()?,
+            I::i32_gt_u_imm16,
+            I::i32_lt_u_imm16,
+        ),
+        I::I32GeU(instr) => fold_bin(
+            instr,
+            register,
+            uimm()?,
+            I::i32_ge_u_imm16,
+            I::i32_le_u_imm16,
+        ),
+        _ => None,
+    }
+}
+
+/// Returns `instr` with the `i64` constant `value` of `register` folded into it if possible.
+fn fold_i64(instr: &Instruction, register: Register, value: i64) -> Option<Instruction> {
+    use Instruction as I;
+    let imm = || <Const16<i64>>::try_from(value).ok();
+    let uimm = || <Const16<u64>>::try_from(value as u64).ok();
+    match *instr {
+        I::I64Add(instr) => fold_bin(instr, register, imm()?, I::i64_add_imm16, I::i64_add_imm16),
+        I::I64Mul(instr) => fold_bin(instr, register, imm()?, I::i64_mul_imm16, I::i64_mul_imm16),
+        I::I64And(instr) => fold_bin(instr, register, imm()?, I::i64_and_imm16, I::i64_and_imm16),
+        I::I64Or(instr) => fold_bin(instr, register, imm()?, I::i64_or_imm16, I::i64_or_imm16),
+        I::I64Xor(instr) => fold_bin(instr, register, imm()?, I::i64_xor_imm16, I::i64_xor_imm16),
+        I::I64Eq(instr) => fold_bin(instr, register, imm()?, I::i64_eq_imm16, I::i64_eq_imm16),
+        I::I64Ne(instr) => fold_bin(instr, register, imm()?, I::i64_ne_imm16, I::i64_ne_imm16),
+        I::I64Sub(instr) => fold_bin(
+            instr,
+            register,
+            imm()?,
+            I::i64_sub_imm16,
+            |result, input, imm| I::i64_sub_imm16_rev(result, imm, input),
+        ),
+        I::I64LtS(instr) => fold_bin(
+            instr,
+            register,
+            imm()?,
+            I::i64_lt_s_imm16,
+            I::i64_gt_s_imm16,
+        ),
+        I::I64LeS(instr) => fold_bin(
+            instr,
+            register,
+            imm()?,
+            I::i64_le_s_imm16,
+            I::i64_ge_s_imm16,
+        ),
+        I::I64GtS(instr) => fold_bin(
+            instr,
+            register,
+            imm()?,
+            I::i64_gt_s_imm16,
+            I::i64_lt_s_imm16,
+        ),
+        I::I64GeS(instr) => fold_bin(
+            instr,
+            register,
+            imm()?,
+            I::i64_ge_s_imm16,
+            I::i64_le_s_imm16,
+        ),
+        I::I64LtU(instr) => fold_bin(
+            instr,
+            register,
+            uimm()?,
+            I::i64_lt_u_imm16,
+            I::i64_gt_u_imm16,
+        ),
+        I::I64LeU(instr) => fold_bin(
+            instr,
+            register,
+            uimm()?,
+            I::i64_le_u_imm16,
+            I::i64_ge_u_imm16,
+        ),
+        I::I64GtU(instr) => fold_bin(
+            instr,
+            register,
+            uimm()?,
+            I::i64_gt_u_imm16,
+            I::i64_lt_u_imm16,
+        ),
+        I::I64GeU(instr) => fold_bin(
+            instr,
+            register,
+            uimm()?,
+            I::i64_ge_u_imm16,
+            I::i64_le_u_imm16,
+        ),
+        _ => None,
+    }
+}
+
+/// Folds the immediate value `imm` of `register` into the binary `instr`.
+///
+/// - `make_rhs` creates the folded instruction if `register` is the right-hand side operand.
+/// - `make_lhs` creates the folded instruction if `register` is the left-hand side operand.
+///
+/// Returns `None` if `register` is not exactly one of the operands of `instr`.
+fn fold_bin<T>(
+    instr: BinInstr,
+    register: Register,
+    imm: T,
+    make_rhs: fn(Register, Register, T) -> Instruction,
+    make_lhs: fn(Register, Register, T) -> Instruction,
+) -> Option<Instruction> {
+    match (instr.lhs == register, instr.rhs == register) {
+        (false, true) => Some(make_rhs(instr.result, instr.lhs, imm)),
+        (true, false) => Some(make_lhs(instr.result, instr.rhs, imm)),
+        _ => None,
+    }
+}
+
+impl Instruction {
+    /// Returns `true` if the [`Instruction`] ends the basic block of the optimizer.
+    ///
+    /// # Note
+    ///
+    /// These are all instructions that are not single instruction words, that
+    /// may transfer control or that read or write spans of registers.
+    pub(super) fn is_block_barrier(&self) -> bool {
+        if self.encoding() != Encoding::Single || self.branch_offset().is_some() {
+            return true;
+        }
+        self.is_unconditional_exit()
+            || self.is_branch_fallback()
+            || matches!(
+                self,
+                Self::ReturnNez { .. }
+                    | Self::ReturnNezReg { .. }
+                    | Self::ReturnNezReg2 { .. }
+                    | Self::ReturnNezImm32 { .. }
+                    | Self::ReturnNezI64Imm32 { .. }
+                    | Self::ReturnNezF64Imm32 { .. }
+                    | Self::ReturnNezSpan { .. }
+                    | Self::CallInternal0 { .. }
+                    | Self::CallImported0 { .. }
+                    | Self::Copy2 { .. }
+                    | Self::CopySpan { .. }
+                    | Self::CopySpanNonOverlapping { .. }
+                    | Self::I64MulWideS { .. }
+                    | Self::I64MulWideU { .. }
+            )
+    }
+
+    /// Returns `true` if execution never continues after the [`Instruction`].
+    fn is_unconditional_exit(&self) -> bool {
+        matches!(
+            self,
+            Self::Trap(_)
+                | Self::Branch { .. }
+                | Self::Return
+                | Self::ReturnReg { .. }
+                | Self::ReturnReg2 { .. }
+                | Self::ReturnReg3 { .. }
+                | Self::ReturnImm32 { .. }
+                | Self::ReturnI64Imm32 { .. }
+                | Self::ReturnF64Imm32 { .. }
+                | Self::ReturnSpan { .. }
+                | Self::ReturnMany { .. }
+                | Self::ReturnForward { .. }
+                | Self::ReturnCallInternal0 { .. }
+                | Self::ReturnCallInternal { .. }
+                | Self::ReturnCallImported0 { .. }
+                | Self::ReturnCallImported { .. }
+                | Self::ReturnCallIndirect0 { .. }
+                | Self::ReturnCallIndirect { .. }
+        )
+    }
+
+    /// Returns `true` if the [`Instruction`] is a branch with a fallback offset.
+    fn is_branch_fallback(&self) -> bool {
+        matches!(
+            self,
+            Self::BranchCmpFallback { .. }
+                | Self::BranchI32EqFallback(_)
+                | Self::BranchI32NeFallback(_)
+                | Self::BranchI32LtSFallback(_)
+                | Self::BranchI32LtUFallback(_)
+                | Self::BranchI32LeSFallback(_)
+                | Self::BranchI32LeUFallback(_)
+                | Self::BranchI32GtSFallback(_)
+                | Self::BranchI32GtUFallback(_)
+                | Self::BranchI32GeSFallback(_)
+                | Self::BranchI32GeUFallback(_)
+        )
+    }
+
+    /// Updates the [`BranchOffset`] of the already initialized branch [`Instruction`].
+    ///
+    /// # Panics
+    ///
+    /// - If `self` is not a branch [`Instruction`] with a static offset.
+    /// - If `new_offset` cannot be encoded by `self`.
+    fn set_branch_offset(&mut self, new_offset: BranchOffset) {
+        let set_offset16 = |offset: &mut BranchOffset16| {
+            *offset = BranchOffset16::try_from(new_offset).unwrap_or_else(|error| {
+                panic!("failed to encode adjusted branch offset {new_offset:?}: {error}")
+            });
+        };
+        match self {
+            Self::Branch { offset } => *offset = new_offset,
+            Self::BranchI32And(instr)
+            | Self::BranchI32Or(instr)
+            | Self::BranchI32Xor(instr)
+            | Self::BranchI32AndEqz(instr)
+            | Self::BranchI32OrEqz(instr)
+            | Self::BranchI32XorEqz(instr)
+            | Self::BranchI32Eq(instr)
+            | Self::BranchI32Ne(instr)
+            | Self::BranchI32LtS(instr)
+            | Self::BranchI32LtU(instr)
+            | Self::BranchI32LeS(instr)
+            | Self::BranchI32LeU(instr)
+            | Self::BranchI32GtS(instr)
+            | Self::BranchI32GtU(instr)
+            | Self::BranchI32GeS(instr)
+            | Self::BranchI32GeU(instr)
+            | Self::BranchI64Eq(instr)
+            | Self::BranchI64Ne(instr)
+            | Self::BranchI64LtS(instr)
+            | Self::BranchI64LtU(instr)
+            | Self::BranchI64LeS(instr)
+            | Self::BranchI64LeU(instr)
+            | Self::BranchI64GtS(instr)
+            | Self::BranchI64GtU(instr)
+            | Self::BranchI64GeS(instr)
+            | Self::BranchI64GeU(instr)
+            | Self::BranchF32Eq(instr)
+            | Self::BranchF32Ne(instr)
+            | Self::BranchF32Lt(instr)
+            | Self::BranchF32Le(instr)
+            | Self::BranchF32Gt(instr)
+            | Self::BranchF32Ge(instr)
+            | Self::BranchF32NotLt(instr)
+            | Self::BranchF32NotLe(instr)
+            | Self::BranchF64Eq(instr)
+            | Self::BranchF64Ne(instr)
+            | Self::BranchF64Lt(instr)
+            | Self::BranchF64Le(instr)
+            | Self::BranchF64Gt(instr)
+            | Self::BranchF64Ge(instr)
+            | Self::BranchF64NotLt(instr)
+            | Self::BranchF64NotLe(instr) => set_offset16(&mut instr.offset),
+            Self::BranchI32AndImm(instr)
+            | Self::BranchI32OrImm(instr)
+            | Self::BranchI32XorImm(instr)
+            | Self::BranchI32AndEqzImm(instr)
+            | Self::BranchI32OrEqzImm(instr)
+            | Self::BranchI32XorEqzImm(instr)
+            | Self::BranchI32EqImm(instr)
+            | Self::BranchI32NeImm(instr)
+            | Self::BranchI32LtSImm(instr)
+            | Self::BranchI32LeSImm(instr)
+            | Self::BranchI32GtSImm(instr)
+            | Self::BranchI32GeSImm(instr) => set_offset16(&mut instr.offset),
+            Self::BranchI32LtUImm(instr)
+            | Self::BranchI32LeUImm(instr)
+            | Self::BranchI32GtUImm(instr)
+            | Self::BranchI32GeUImm(instr) => set_offset16(&mut instr.offset),
+            Self::BranchI64EqImm(instr)
+            | Self::BranchI64NeImm(instr)
+            | Self::BranchI64LtSImm(instr)
+            | Self::BranchI64LeSImm(instr)
+            | Self::BranchI64GtSImm(instr)
+            | Self::BranchI64GeSImm(instr) => set_offset16(&mut instr.offset),
+            Self::BranchI64LtUImm(instr)
+            | Self::BranchI64LeUImm(instr)
+            | Self::BranchI64GtUImm(instr)
+            | Self::BranchI64GeUImm(instr) => set_offset16(&mut instr.offset),
+            _ => panic!("tried to set branch offset of a non-branch instruction: {self:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use wasmi_core::TrapCode;
+
+    /// Removes the unreachable instructions of `instrs` and returns the result.
+    fn without_unreachable(mut instrs: Vec<Instruction>) -> Vec<Instruction> {
+        let mut is_instr = Vec::new();
+        verify_instr_starts(&instrs, &mut is_instr).unwrap();
+        let mut is_target = Vec::new();
+        branch_targets(&instrs, &is_instr, &mut is_target);
+        let mut removed = vec![false; instrs.len()];
+        remove_unreachable(&instrs, &is_instr, &is_target, &mut removed);
+        compact(&mut instrs, None, &is_instr, &removed, &mut Vec::new());
+        instrs
+    }
+
+    fn reg(index: i16) -> Register {
+        Register::from_i16(index)
+    }
+
+    #[test]
+    fn remove_unreachable_works() {
+        let trap = Instruction::Trap(TrapCode::UnreachableCodeReached);
+        let instrs = vec![
+            Instruction::branch_i32_eq_imm(reg(0), 0_i16, BranchOffset16::from(4)),
+            Instruction::copy_imm32(reg(1), 1_i32),
+            Instruction::branch(BranchOffset::from(4)),
+            trap,
+            Instruction::copy_imm32(reg(1), 2_i32),
+            Instruction::return_reg(reg(0)),
+            Instruction::return_reg(reg(1)),
+        ];
+        assert_eq!(
+            without_unreachable(instrs),
+            [
+                Instruction::branch_i32_eq_imm(reg(0), 0_i16, BranchOffset16::from(3)),
+                Instruction::copy_imm32(reg(1), 1_i32),
+                Instruction::branch(BranchOffset::from(3)),
+                Instruction::copy_imm32(reg(1), 2_i32),
+                Instruction::return_reg(reg(0)),
+                Instruction::return_reg(reg(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_unreachable_backwards_branch() {
+        let trap = Instruction::Trap(TrapCode::UnreachableCodeReached);
+        let instrs = vec![
+            Instruction::branch(BranchOffset::from(2)),
+            trap,
+            Instruction::copy_imm32(reg(1), 1_i32),
+            Instruction::branch_i32_ne_imm(reg(0), 0_i16, BranchOffset16::from(-3)),
+            Instruction::return_reg(reg(1)),
+        ];
+        assert_eq!(
+            without_unreachable(instrs),
+            [
+                Instruction::branch(BranchOffset::from(1)),
+                Instruction::copy_imm32(reg(1), 1_i32),
+                Instruction::branch_i32_ne_imm(reg(0), 0_i16, BranchOffset16::from(-2)),
+                Instruction::return_reg(reg(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_unreachable_keeps_branch_table() {
+        let trap = Instruction::Trap(TrapCode::UnreachableCodeReached);
+        let instrs = vec![
+            Instruction::branch_table(reg(0), 3_u32),
+            Instruction::copy(reg(2), reg(1)),
+            Instruction::return_reg(reg(0)),
+            Instruction::return_reg(reg(1)),
+            Instruction::return_reg(reg(2)),
+            trap,
+            Instruction::return_reg(reg(0)),
+        ];
+        assert_eq!(
+            without_unreachable(instrs),
+            [
+                Instruction::branch_table(reg(0), 3_u32),
+                Instruction::copy(reg(2), reg(1)),
+                Instruction::return_reg(reg(0)),
+                Instruction::return_reg(reg(1)),
+                Instruction::return_reg(reg(2)),
+            ]
+        );
+    }
+}
@@ -23,7 +23,7 @@ use crate::{
 
 impl Instruction {
     #[rustfmt::skip]
-    pub fn relink_result(
+    pub(crate) fn relink_result(
         &mut self,
         module: &ModuleHeader,
         new_result: Register,
@@ -54,6 +54,7 @@ impl Instruction {
             | I::ReturnF64Imm32 { .. }
             | I::ReturnSpan { .. }
             | I::ReturnMany { .. }
+            | I::ReturnForward { .. }
             | I::ReturnNez { .. }
             | I::ReturnNezReg { .. }
             | I::ReturnNezReg2 { .. }
@@ -64,6 +65,16 @@ impl Instruction {
             | I::ReturnNezMany { .. }
             | I::Branch { .. }
             | I::BranchCmpFallback { .. }
+            | I::BranchI32EqFallback(_)
+            | I::BranchI32NeFallback(_)
+            | I::BranchI32LtSFallback(_)
+            | I::BranchI32LtUFallback(_)
+            | I::BranchI32LeSFallback(_)
+            | I::BranchI32LeUFallback(_)
+            | I::BranchI32GtSFallback(_)
+            | I::BranchI32GtUFallback(_)
+            | I::BranchI32GeSFallback(_)
+            | I::BranchI32GeUFallback(_)
             | I::BranchI32And(_)
             | I::BranchI32AndImm(_)
             | I::BranchI32Or(_)
@@ -77,6 +88,7 @@ impl Instruction {
             | I::BranchI32XorEqz(_)
             | I::BranchI32XorEqzImm(_)
             | I::BranchTable { .. }
+            | I::BranchTableSparse { .. }
             | I::BranchI32Eq(_)
             | I::BranchI32EqImm(_)
             | I::BranchI32Ne(_)
@@ -123,12 +135,16 @@ impl Instruction {
             | I::BranchF32Le(_)
             | I::BranchF32Gt(_)
             | I::BranchF32Ge(_)
+            | I::BranchF32NotLt(_)
+            | I::BranchF32NotLe(_)
             | I::BranchF64Eq(_)
             | I::BranchF64Ne(_)
             | I::BranchF64Lt(_)
             | I::BranchF64Le(_)
             | I::BranchF64Gt(_)
-            | I::BranchF64Ge(_) => Ok(false),
+            | I::BranchF64Ge(_)
+            | I::BranchF64NotLt(_)
+            | I::BranchF64NotLe(_) => Ok(false),
             I::Copy { result, .. }
             | I::CopyImm32 { result, .. }
             | I::CopyI64Imm32 { result, .. }
@@ -383,6 +399,9 @@ impl Instruction {
             I::I32DivU(instr) |
             I::I32RemS(instr) |
             I::I32RemU(instr) |
+            I::I32CheckedAdd(instr) |
+            I::I32CheckedSub(instr) |
+            I::I32CheckedMul(instr) |
             I::I32And(instr) |
             I::I32AndEqz(instr) |
             I::I32Or(instr) |
@@ -401,6 +420,9 @@ impl Instruction {
             I::I64DivU(instr) |
             I::I64RemS(instr) |
             I::I64RemU(instr) |
+            I::I64CheckedAdd(instr) |
+            I::I64CheckedSub(instr) |
+            I::I64CheckedMul(instr) |
             I::I64And(instr) |
             I::I64Or(instr) |
             I::I64Xor(instr) |
@@ -442,6 +464,8 @@ impl Instruction {
 
             I::F32CopysignImm(instr) |
             I::F64CopysignImm(instr) => relink_simple(instr, new_result, old_result),
+            I::I64MulWideS { .. } |
+            I::I64MulWideU { .. } => Ok(false),
 
             I::I32AddImm16(instr) |
             I::I32SubImm16(instr) |
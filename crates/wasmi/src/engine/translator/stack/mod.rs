@@ -132,6 +132,16 @@ impl ValueStack {
         self.consts.len_consts() + self.reg_alloc.len_registers()
     }
 
+    /// Returns the number of registers allocated by the [`RegisterAlloc`] excluding scratch registers.
+    ///
+    /// # Note
+    ///
+    /// This is the number of registers used by fuel metering.
+    pub fn len_metered_registers(&self) -> u16 {
+        // The addition won't overflow since both operands are in the range of `0..i16::MAX`.
+        self.consts.len_consts() + self.reg_alloc.len_metered_registers()
+    }
+
     /// Registers an `amount` of function inputs or local variables.
     ///
     /// # Errors
@@ -374,6 +384,21 @@ impl ValueStack {
         Ok(registers)
     }
 
+    /// Returns a [`RegisterSpan`] of `n` scratch registers above the dynamic registers and at or after `min_head`.
+    ///
+    /// # Note
+    ///
+    /// - This procedure does not push anything onto the [`ValueStack`].
+    /// - Scratch registers are not accounted for by [`ValueStack::len_metered_registers`].
+    /// - This is primarily used to translate inlined function calls.
+    ///
+    /// # Errors
+    ///
+    /// If this procedure would allocate more registers than are available.
+    pub fn peek_scratch_n(&mut self, min_head: Register, n: usize) -> Result<RegisterSpan, Error> {
+        self.reg_alloc.peek_scratch_n(min_head, n)
+    }
+
     /// Finalizes register allocation and allows to defragment the register space.
     pub fn finalize_alloc(&mut self) {
         self.reg_alloc.finalize_alloc()
@@ -80,6 +80,13 @@ pub struct RegisterAlloc {
     next_dynamic: i16,
     /// The maximum index registered for a dynamically allocated register.
     max_dynamic: i16,
+    /// The maximum index registered for a scratch register.
+    ///
+    /// # Note
+    ///
+    /// Scratch registers are reserved via [`RegisterAlloc::peek_scratch_n`] and
+    /// are excluded from [`RegisterAlloc::len_metered_registers`].
+    max_scratch: i16,
     /// The minimum index registered for a preservation allocated register.
     min_preserve: i16,
     /// The offset for the defragmentation register index.
@@ -137,6 +144,7 @@ impl RegisterAlloc {
         self.len_locals = 0;
         self.next_dynamic = 0;
         self.max_dynamic = 0;
+        self.max_scratch = 0;
         self.min_preserve = i16::MAX;
     }
 
@@ -183,9 +191,23 @@ impl RegisterAlloc {
 
     /// Returns the number of registers allocated by the [`RegisterAlloc`].
     pub fn len_registers(&self) -> u16 {
+        (i16::MAX as u16) - self.max_registers().abs_diff(self.min_preserve)
+    }
+
+    /// Returns the number of registers allocated by the [`RegisterAlloc`] excluding scratch registers.
+    ///
+    /// # Note
+    ///
+    /// This is the number of registers used by fuel metering.
+    pub fn len_metered_registers(&self) -> u16 {
         (i16::MAX as u16) - self.max_dynamic.abs_diff(self.min_preserve)
     }
 
+    /// Returns the maximum index of all dynamically allocated and scratch registers.
+    fn max_registers(&self) -> i16 {
+        max(self.max_dynamic, self.max_scratch)
+    }
+
     /// Registers an `amount` of function inputs or local variables.
     ///
     /// # Errors
@@ -278,6 +300,33 @@ impl RegisterAlloc {
             .ok_or_else(|| Error::from(TranslationError::AllocatedTooManyRegisters))
     }
 
+    /// Returns `n` scratch [`Register`] above the dynamic allocation stack and at or after `min_head`.
+    ///
+    /// # Note
+    ///
+    /// - Scratch registers are neither pushed onto the allocation stack nor
+    ///   accounted for by [`RegisterAlloc::len_metered_registers`].
+    /// - Scratch registers are only valid until the next register allocation.
+    ///
+    /// # Errors
+    ///
+    /// If too many registers have been registered.
+    ///
+    /// # Panics
+    ///
+    /// If the current [`AllocPhase`] is not [`AllocPhase::Alloc`].
+    pub fn peek_scratch_n(&mut self, min_head: Register, n: usize) -> Result<RegisterSpan, Error> {
+        self.assert_alloc_phase();
+        let head = max(self.next_dynamic, min_head.to_i16());
+        let end = i16::try_from(n)
+            .ok()
+            .and_then(|n| head.checked_add(n))
+            .filter(|&end| end < self.min_preserve)
+            .ok_or(TranslationError::AllocatedTooManyRegisters)?;
+        self.max_scratch = max(self.max_scratch, end);
+        Ok(RegisterSpan::new(Register::from_i16(head)))
+    }
+
     /// Pops the top-most dynamically allocated [`Register`] from the allocation stack.
     ///
     /// # Panics
@@ -434,7 +483,7 @@ impl RegisterAlloc {
     pub fn finalize_alloc(&mut self) {
         assert!(matches!(self.phase, AllocPhase::Alloc));
         self.phase = AllocPhase::Defrag;
-        self.defrag_offset = (self.min_preserve - self.max_dynamic).saturating_add(1);
+        self.defrag_offset = (self.min_preserve - self.max_registers()).saturating_add(1);
     }
 
     /// Returns the defragmented [`Register`].
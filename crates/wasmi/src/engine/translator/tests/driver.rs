@@ -180,6 +180,12 @@ impl TranslationTest {
         }
     }
 
+    /// Sets the optimization level of the [`Config`] used for the test case.
+    pub fn optimization_level(&mut self, level: u8) -> &mut Self {
+        self.config.optimization_level(level);
+        self
+    }
+
     /// Returns the [`Config`] used for the test case.
     fn config(&self) -> &Config {
         &self.config
@@ -455,3 +455,137 @@ fn imm_params_0() {
     test_with(3);
     test_with(1000);
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_same_targets() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param $index i32) (result i32)
+                (block
+                    (br_table 0 0 0 0 (local.get $index))
+                )
+                (return (i32.const 10))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::branch(BranchOffset::from(1)),
+            Instruction::return_imm32(10_i32),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_sparse_params_0() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param $index i32) (result i32)
+                (block
+                    (block
+                        (block
+                            (br_table 1 0 0 0 2 0 (local.get $index))
+                        )
+                        (return (i32.const 10))
+                    )
+                    (return (i32.const 20))
+                )
+                (return (i32.const 30))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::branch_table_sparse(Register::from_i16(0), 2),
+            Instruction::const32(0_u32),
+            Instruction::const32(4_u32),
+            Instruction::branch(BranchOffset::from(4)),
+            Instruction::branch(BranchOffset::from(4)),
+            Instruction::branch(BranchOffset::from(1)),
+            Instruction::return_imm32(10),
+            Instruction::return_imm32(20),
+            Instruction::return_imm32(30),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_sparse_params_1() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param $index i32) (param $value i32) (result i32)
+                (block (result i32)
+                    (block (result i32)
+                        (local.get $value) ;; param to br_table targets
+                        (br_table 0 0 0 1 0 (local.get $index))
+                    )
+                    (return (i32.add (i32.const 10)))
+                )
+                (return (i32.add (i32.const 20)))
+            )
+        )",
+    );
+    let index = Register::from_i16(0);
+    let value = Register::from_i16(1);
+    let result = Register::from_i16(2);
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::branch_table_sparse(index, 1),
+            Instruction::const32(3_u32),
+            Instruction::copy(result, value),
+            Instruction::branch(BranchOffset::from(4)),
+            Instruction::branch(BranchOffset::from(1)),
+            Instruction::i32_add_imm16(result, result, 10),
+            Instruction::return_reg(result),
+            Instruction::i32_add_imm16(result, result, 20),
+            Instruction::return_reg(result),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_sparse_many_targets() {
+    // Only every 1000th of the 10,000 targets differs from the default target.
+    let len_targets = 10_000_u32;
+    let targets = (0..len_targets)
+        .map(|case| if case % 1000 == 0 { "1" } else { "0" })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let wasm = wat2wasm(&format!(
+        r"
+        (module
+            (func (param $index i32) (result i32)
+                (block
+                    (block
+                        (br_table {targets} 0 (local.get $index))
+                    )
+                    (return (i32.const 10))
+                )
+                (return (i32.const 20))
+            )
+        )",
+    ));
+    let cases = (0..len_targets).step_by(1000);
+    let len_cases = cases.len() as i32;
+    let mut instrs = vec![Instruction::branch_table_sparse(
+        Register::from_i16(0),
+        len_cases as u32,
+    )];
+    instrs.extend(cases.map(Instruction::const32));
+    instrs.extend(
+        (0..len_cases).map(|case| Instruction::branch(BranchOffset::from(len_cases + 2 - case))),
+    );
+    instrs.extend([
+        Instruction::branch(BranchOffset::from(1)),
+        Instruction::return_imm32(10),
+        Instruction::return_imm32(20),
+    ]);
+    TranslationTest::new(wasm).expect_func_instrs(instrs).run()
+}
@@ -0,0 +1,136 @@
+use super::*;
+use crate::engine::{bytecode::FuncIdx, RegisterSpan};
+
+/// Returns a Wasm module importing the intrinsic `name` with `ty` operands.
+///
+/// The module contains a single function calling the imported intrinsic
+/// with its `lhs` and `rhs` operands.
+fn wasm_intrinsic_call(name: &str, ty: &str, lhs: &str, rhs: &str) -> Vec<u8> {
+    wat2wasm(&format!(
+        r#"
+        (module
+            (import "wasmi_intrinsics" "{name}" (func $f (param {ty} {ty}) (result {ty})))
+            (func (param {ty} {ty}) (result {ty})
+                (call $f {lhs} {rhs})
+            )
+        )
+    "#,
+    ))
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_reg() {
+    fn test_for(
+        name: &str,
+        ty: &str,
+        make_instr: fn(result: Register, lhs: Register, rhs: Register) -> Instruction,
+    ) {
+        let wasm = wasm_intrinsic_call(name, ty, "(local.get 0)", "(local.get 1)");
+        TranslationTest::new(wasm)
+            .expect_func_instrs([
+                make_instr(
+                    Register::from_i16(2),
+                    Register::from_i16(0),
+                    Register::from_i16(1),
+                ),
+                Instruction::return_reg(Register::from_i16(2)),
+            ])
+            .run();
+    }
+    test_for("i32.checked_add", "i32", Instruction::i32_checked_add);
+    test_for("i32.checked_sub", "i32", Instruction::i32_checked_sub);
+    test_for("i32.checked_mul", "i32", Instruction::i32_checked_mul);
+    test_for("i64.checked_add", "i64", Instruction::i64_checked_add);
+    test_for("i64.checked_sub", "i64", Instruction::i64_checked_sub);
+    test_for("i64.checked_mul", "i64", Instruction::i64_checked_mul);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_imm() {
+    let wasm = wasm_intrinsic_call("i32.checked_add", "i32", "(local.get 0)", "(i32.const 1)");
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::i32_checked_add(
+                    Register::from_i16(2),
+                    Register::from_i16(0),
+                    Register::from_i16(-1),
+                ),
+                Instruction::return_reg(Register::from_i16(2)),
+            ])
+            .consts([1_i32]),
+        )
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn imm_imm() {
+    // Note: constant operands are not evaluated at compile time
+    //       since the intrinsic must trap at runtime upon overflow.
+    let wasm = wasm_intrinsic_call(
+        "i64.checked_mul",
+        "i64",
+        "(i64.const 0x7FFF_FFFF_FFFF_FFFF)",
+        "(i64.const 2)",
+    );
+    TranslationTest::new(wasm)
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::i64_checked_mul(
+                    Register::from_i16(2),
+                    Register::from_i16(-1),
+                    Register::from_i16(-2),
+                ),
+                Instruction::return_reg(Register::from_i16(2)),
+            ])
+            .consts([i64::MAX, 2_i64]),
+        )
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn signature_mismatch() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (import "wasmi_intrinsics" "i32.checked_add" (func $f (param i64 i64) (result i64)))
+            (func (param i64 i64) (result i64)
+                (call $f (local.get 0) (local.get 1))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::call_imported(RegisterSpan::new(Register::from_i16(2)), FuncIdx::from(0)),
+            Instruction::register2(0, 1),
+            Instruction::return_reg(Register::from_i16(2)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn other_module() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (import "env" "i32.checked_add" (func $f (param i32 i32) (result i32)))
+            (func (param i32 i32) (result i32)
+                (call $f (local.get 0) (local.get 1))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::call_imported(RegisterSpan::new(Register::from_i16(2)), FuncIdx::from(0)),
+            Instruction::register2(0, 1),
+            Instruction::return_reg(Register::from_i16(2)),
+        ])
+        .run();
+}
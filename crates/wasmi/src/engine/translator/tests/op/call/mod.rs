@@ -3,3 +3,4 @@ use super::*;
 mod imported;
 mod indirect;
 mod internal;
+mod intrinsic;
@@ -393,6 +393,29 @@ fn if_forward_multi_value() {
     test_for(ValueType::I64, "gt_u", Instruction::branch_i64_le_u);
     test_for(ValueType::I64, "ge_s", Instruction::branch_i64_lt_s);
     test_for(ValueType::I64, "ge_u", Instruction::branch_i64_lt_u);
+
+    // Note: negated float comparisons must also branch for NaN operands.
+    test_for(ValueType::F32, "eq", Instruction::branch_f32_ne);
+    test_for(ValueType::F32, "ne", Instruction::branch_f32_eq);
+    test_for(ValueType::F32, "lt", Instruction::branch_f32_not_lt);
+    test_for(ValueType::F32, "le", Instruction::branch_f32_not_le);
+    test_for(ValueType::F32, "gt", |lhs, rhs, offset| {
+        Instruction::branch_f32_not_lt(rhs, lhs, offset)
+    });
+    test_for(ValueType::F32, "ge", |lhs, rhs, offset| {
+        Instruction::branch_f32_not_le(rhs, lhs, offset)
+    });
+
+    test_for(ValueType::F64, "eq", Instruction::branch_f64_ne);
+    test_for(ValueType::F64, "ne", Instruction::branch_f64_eq);
+    test_for(ValueType::F64, "lt", Instruction::branch_f64_not_lt);
+    test_for(ValueType::F64, "le", Instruction::branch_f64_not_le);
+    test_for(ValueType::F64, "gt", |lhs, rhs, offset| {
+        Instruction::branch_f64_not_lt(rhs, lhs, offset)
+    });
+    test_for(ValueType::F64, "ge", |lhs, rhs, offset| {
+        Instruction::branch_f64_not_le(rhs, lhs, offset)
+    });
 }
 
 #[test]
@@ -454,6 +477,29 @@ fn if_forward() {
     test_for(ValueType::I64, "gt_u", Instruction::branch_i64_le_u);
     test_for(ValueType::I64, "ge_s", Instruction::branch_i64_lt_s);
     test_for(ValueType::I64, "ge_u", Instruction::branch_i64_lt_u);
+
+    // Note: negated float comparisons must also branch for NaN operands.
+    test_for(ValueType::F32, "eq", Instruction::branch_f32_ne);
+    test_for(ValueType::F32, "ne", Instruction::branch_f32_eq);
+    test_for(ValueType::F32, "lt", Instruction::branch_f32_not_lt);
+    test_for(ValueType::F32, "le", Instruction::branch_f32_not_le);
+    test_for(ValueType::F32, "gt", |lhs, rhs, offset| {
+        Instruction::branch_f32_not_lt(rhs, lhs, offset)
+    });
+    test_for(ValueType::F32, "ge", |lhs, rhs, offset| {
+        Instruction::branch_f32_not_le(rhs, lhs, offset)
+    });
+
+    test_for(ValueType::F64, "eq", Instruction::branch_f64_ne);
+    test_for(ValueType::F64, "ne", Instruction::branch_f64_eq);
+    test_for(ValueType::F64, "lt", Instruction::branch_f64_not_lt);
+    test_for(ValueType::F64, "le", Instruction::branch_f64_not_le);
+    test_for(ValueType::F64, "gt", |lhs, rhs, offset| {
+        Instruction::branch_f64_not_lt(rhs, lhs, offset)
+    });
+    test_for(ValueType::F64, "ge", |lhs, rhs, offset| {
+        Instruction::branch_f64_not_le(rhs, lhs, offset)
+    });
 }
 
 #[test]
@@ -566,3 +612,123 @@ fn if_i64_eqz_fuse() {
         ])
         .run()
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn block_i32_eqz_local_fuse() {
+    fn test_for(condition: &str, expect_instr: fn(Register, BranchOffset16) -> Instruction) {
+        let wasm = wat2wasm(&format!(
+            r"
+            (module
+                (func (param i32)
+                    (block
+                        {condition}
+                        (br_if 0)
+                    )
+                )
+            )",
+        ));
+        TranslationTest::new(wasm)
+            .optimization_level(1)
+            .expect_func_instrs([
+                expect_instr(Register::from_i16(0), BranchOffset16::from(1)),
+                Instruction::Return,
+            ])
+            .run()
+    }
+    test_for("(i32.eqz (local.get 0))", Instruction::branch_i32_eqz);
+    test_for("(i32.eqz (i32.eqz (local.get 0)))", Instruction::branch_i32_nez);
+    test_for(
+        "(i32.eqz (i32.eqz (i32.eqz (local.get 0))))",
+        Instruction::branch_i32_eqz,
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn if_i32_eqz_local_fuse() {
+    fn test_for(condition: &str, expect_instr: fn(Register, BranchOffset16) -> Instruction) {
+        let wasm = wat2wasm(&format!(
+            r"
+            (module
+                (func (param i32)
+                    (if
+                        {condition}
+                        (then)
+                    )
+                )
+            )",
+        ));
+        TranslationTest::new(wasm)
+            .optimization_level(1)
+            .expect_func_instrs([
+                expect_instr(Register::from_i16(0), BranchOffset16::from(1)),
+                Instruction::Return,
+            ])
+            .run()
+    }
+    test_for("(i32.eqz (local.get 0))", Instruction::branch_i32_nez);
+    test_for("(i32.eqz (i32.eqz (local.get 0)))", Instruction::branch_i32_eqz);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn block_i32_eqz_cmp_fuse() {
+    fn test_for(op: &str, expect_instr: fn(Register, Register, BranchOffset16) -> Instruction) {
+        let wasm = wat2wasm(&format!(
+            r"
+            (module
+                (func (param i32 i32)
+                    (block
+                        (i32.eqz (i32.{op} (local.get 0) (local.get 1)))
+                        (br_if 0)
+                    )
+                )
+            )",
+        ));
+        TranslationTest::new(wasm)
+            .optimization_level(1)
+            .expect_func_instrs([
+                expect_instr(
+                    Register::from_i16(0),
+                    Register::from_i16(1),
+                    BranchOffset16::from(1),
+                ),
+                Instruction::Return,
+            ])
+            .run()
+    }
+    test_for("eq", Instruction::branch_i32_ne);
+    test_for("ne", Instruction::branch_i32_eq);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn loop_header_no_fuse() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32)
+                (i32.eq (local.get 0) (local.get 1))
+                (loop (param i32)
+                    (if
+                        (then (br 1 (i32.const 0)))
+                    )
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_eq(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::branch_i32_eqz(Register::from_i16(2), BranchOffset16::from(3)),
+            Instruction::copy_imm32(Register::from_i16(2), 0_i32),
+            Instruction::branch(BranchOffset::from(-2)),
+            Instruction::Return,
+        ])
+        .run()
+}
@@ -0,0 +1,181 @@
+//! Tests for merging adjacent copy instructions.
+
+use super::*;
+use crate::engine::bytecode::RegisterSpan;
+
+/// Creates a [`RegisterSpan`] starting at `index`.
+fn span(index: i16) -> RegisterSpan {
+    RegisterSpan::new(Register::from_i16(index))
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn merge_copy2() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32 i32) (local i32 i32)
+                (local.set 3 (local.get 2))
+                (local.set 4 (local.get 0))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([Instruction::copy2(span(3), 2, 0), Instruction::Return])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn merge_span_non_overlapping() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32 i32 i32) (local i32 i32 i32 i32)
+                (local.set 4 (local.get 0))
+                (local.set 5 (local.get 1))
+                (local.set 6 (local.get 2))
+                (local.set 7 (local.get 3))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy_span_non_overlapping(span(4), span(0), 4),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn merge_span_shift_down() {
+    // Note: each copy reads a register before it is written to by the next copy.
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32 i32 i32)
+                (local.set 0 (local.get 1))
+                (local.set 1 (local.get 2))
+                (local.set 2 (local.get 3))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy_span_non_overlapping(span(0), span(1), 3),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_merge_shift_up() {
+    // Note: the second copy reads the register written to by the first copy.
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32 i32)
+                (local.set 1 (local.get 0))
+                (local.set 2 (local.get 1))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy(1, 0),
+            Instruction::copy(2, 1),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_merge_non_adjacent_results() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32) (local i32 i32 i32)
+                (local.set 2 (local.get 0))
+                (local.set 4 (local.get 1))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy(2, 0),
+            Instruction::copy(4, 1),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_merge_non_contiguous_span() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32 i32 i32) (local i32 i32 i32)
+                (local.set 4 (local.get 0))
+                (local.set 5 (local.get 1))
+                (local.set 6 (local.get 3))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy2(span(4), 0, 1),
+            Instruction::copy(6, 3),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_merge_across_loop_header() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32) (local i32 i32)
+                (local.set 2 (local.get 0))
+                (loop
+                    (local.set 3 (local.get 1))
+                )
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy(2, 0),
+            Instruction::copy(3, 1),
+            Instruction::Return,
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_merge_preserved() {
+    // Note: the first copy preserves the value of local 0 on the stack.
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32 i32) (result i32)
+                (local.get 0)
+                (local.set 0 (local.get 1))
+                (local.set 1 (local.get 2))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy(3, 0),
+            Instruction::copy2(span(0), 1, 2),
+            Instruction::return_reg(3),
+        ])
+        .run()
+}
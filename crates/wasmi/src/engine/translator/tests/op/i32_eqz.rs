@@ -67,3 +67,111 @@ fn binop_imm_i32_eqz() {
     test_for("or", Instruction::i32_or_eqz_imm16);
     test_for("xor", Instruction::i32_xor_eqz_imm16);
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cmp_i32_eqz() {
+    fn test_for(
+        op: &str,
+        expect_instr: fn(result: Register, lhs: Register, rhs: Register) -> Instruction,
+    ) {
+        let wasm = wat2wasm(&format!(
+            r"
+            (module
+                (func (param i32 i32) (result i32)
+                    (local.get 0)
+                    (local.get 1)
+                    (i32.{op})
+                    (i32.eqz)
+                )
+            )",
+        ));
+        TranslationTest::new(wasm)
+            .optimization_level(1)
+            .expect_func_instrs([
+                expect_instr(
+                    Register::from_i16(2),
+                    Register::from_i16(0),
+                    Register::from_i16(1),
+                ),
+                Instruction::return_reg(2),
+            ])
+            .run()
+    }
+    test_for("eq", Instruction::i32_ne);
+    test_for("ne", Instruction::i32_eq);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cmp_imm_i32_eqz() {
+    fn test_for(
+        op: &str,
+        expect_instr: fn(result: Register, lhs: Register, rhs: Const16<i32>) -> Instruction,
+    ) {
+        let wasm = wat2wasm(&format!(
+            r"
+            (module
+                (func (param i32 i32) (result i32)
+                    (local.get 0)
+                    (i32.const 1)
+                    (i32.{op})
+                    (i32.eqz)
+                )
+            )",
+        ));
+        TranslationTest::new(wasm)
+            .optimization_level(1)
+            .expect_func_instrs([
+                expect_instr(
+                    Register::from_i16(2),
+                    Register::from_i16(0),
+                    Const16::from(1),
+                ),
+                Instruction::return_reg(2),
+            ])
+            .run()
+    }
+    test_for("eq", Instruction::i32_ne_imm16);
+    test_for("ne", Instruction::i32_eq_imm16);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn i32_eqz_eqz() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (i32.eqz (i32.eqz (local.get 0)))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::i32_ne_imm16(Register::from_i16(1), Register::from_i16(0), 0),
+            Instruction::return_reg(1),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn i32_eqz_eqz_unoptimized() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32)
+                (i32.eqz (i32.eqz (local.get 0)))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_eq_imm16(Register::from_i16(1), Register::from_i16(0), 0),
+            Instruction::i32_eq_imm16(Register::from_i16(1), Register::from_i16(1), 0),
+            Instruction::return_reg(1),
+        ])
+        .run()
+}
@@ -0,0 +1,333 @@
+//! Tests for the optional inlining of calls to tiny internal functions.
+
+use super::*;
+use crate::engine::{CompiledFunc, RegisterSpan};
+
+/// A Wasm module with the tiny function `$inc` that is called by the exported `call`.
+const INC: &str = r#"
+    (module
+        (func $inc (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func (param i32) (result i32)
+            (call $inc (local.get 0))
+        )
+    )
+"#;
+
+/// The instructions of `$inc` of [`INC`].
+fn inc_instrs() -> [Instruction; 2] {
+    [
+        Instruction::i32_add_imm16(Register::from_i16(1), Register::from_i16(0), 1_i16),
+        Instruction::return_reg(1),
+    ]
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn not_inlined_below_level_2() {
+    TranslationTest::new(wat2wasm(INC))
+        .optimization_level(1)
+        .expect_func_instrs(inc_instrs())
+        .expect_func_instrs([
+            Instruction::call_internal(
+                RegisterSpan::new(Register::from_i16(1)),
+                CompiledFunc::from_u32(0),
+            ),
+            Instruction::register(0),
+            Instruction::return_forward(RegisterSpan::new(Register::from_i16(1)).iter(1)),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn result_in_place() {
+    TranslationTest::new(wat2wasm(INC))
+        .optimization_level(2)
+        .expect_func_instrs(inc_instrs())
+        .expect_func_instrs([
+            Instruction::i32_add_imm16(Register::from_i16(1), Register::from_i16(0), 1_i16),
+            Instruction::return_reg(1),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn const_param() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $add (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+            (func (param i32) (result i32)
+                (call $add (local.get 0) (i32.const 100000))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(2)
+        .expect_func_instrs([
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(2),
+        ])
+        .expect_func(
+            ExpectedFunc::new([
+                Instruction::i32_add(
+                    Register::from_i16(1),
+                    Register::from_i16(0),
+                    Register::from_i16(-1),
+                ),
+                Instruction::return_reg(1),
+            ])
+            .consts([100_000_i32]),
+        )
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn written_param() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $f (param i32) (result i32)
+                (local.set 0 (i32.mul (local.get 0) (local.get 0)))
+                (i32.add (local.get 0) (i32.const 1))
+            )
+            (func (param i32) (result i32)
+                (call $f (local.get 0))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(2)
+        .expect_func_instrs([
+            Instruction::i32_mul(
+                Register::from_i16(0),
+                Register::from_i16(0),
+                Register::from_i16(0),
+            ),
+            Instruction::i32_add_imm16(Register::from_i16(1), Register::from_i16(0), 1_i16),
+            Instruction::return_reg(1),
+        ])
+        .expect_func_instrs([
+            // Note: the written parameter is copied into the scratch registers after the result.
+            Instruction::copy(2, 0),
+            Instruction::i32_mul(
+                Register::from_i16(2),
+                Register::from_i16(2),
+                Register::from_i16(2),
+            ),
+            Instruction::i32_add_imm16(Register::from_i16(1), Register::from_i16(2), 1_i16),
+            Instruction::return_reg(1),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn dynamic_params() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $f (param i32 i32) (result i32)
+                (local.set 0 (i32.sub (local.get 0) (local.get 1)))
+                (i32.mul (local.get 0) (local.get 1))
+            )
+            (func (param i32 i32) (result i32)
+                (call $f
+                    (i32.add (local.get 0) (local.get 1))
+                    (i32.xor (local.get 0) (local.get 1))
+                )
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(2)
+        .expect_func_instrs([
+            Instruction::i32_sub(
+                Register::from_i16(0),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::i32_mul(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(2),
+        ])
+        .expect_func_instrs([
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::i32_xor(
+                Register::from_i16(3),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            // Note: the scratch registers start after the call parameter in register 3
+            //       and the result is copied since register 2 is a call parameter.
+            Instruction::copy(4, 2),
+            Instruction::i32_sub(
+                Register::from_i16(4),
+                Register::from_i16(4),
+                Register::from_i16(3),
+            ),
+            Instruction::i32_mul(
+                Register::from_i16(6),
+                Register::from_i16(4),
+                Register::from_i16(3),
+            ),
+            Instruction::copy(2, 6),
+            Instruction::return_reg(2),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn returned_param() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $swap (param i32 i32) (result i32 i32)
+                (local.get 1)
+                (local.get 0)
+            )
+            (func (param i32 i32) (result i32 i32)
+                (call $swap (local.get 0) (local.get 1))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(2)
+        .expect_func_instrs([Instruction::return_reg2(1, 0)])
+        .expect_func_instrs([
+            Instruction::copy2(RegisterSpan::new(Register::from_i16(2)), 1, 0),
+            Instruction::return_reg2(2, 3),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn return_imm() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func $c (result i32)
+                (i32.const 42)
+            )
+            (func (result i32)
+                (i32.add (call $c) (call $c))
+            )
+        )
+    "#,
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(2)
+        .expect_func_instrs([Instruction::return_imm32(42_i32)])
+        .expect_func_instrs([
+            // Note: the optimization pass folds the first inlined constant into the `i32.add`.
+            Instruction::copy_imm32(Register::from_i16(1), 42_i32),
+            Instruction::i32_add_imm16(Register::from_i16(0), Register::from_i16(1), 42_i16),
+            Instruction::return_reg(0),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn not_inlined() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (memory 1)
+            (func $grow (param i32) (result i32)
+                (memory.grow (local.get 0))
+            )
+            (func $call (param i32) (result i32)
+                (call $grow (local.get 0))
+            )
+            (func (param i32) (result i32)
+                (call $call (call $later (local.get 0)))
+            )
+            (func $later (param i32) (result i32)
+                (local.get 0)
+            )
+        )
+    "#,
+    );
+    let call = |func: u32, param: i16| {
+        [
+            Instruction::call_internal(
+                RegisterSpan::new(Register::from_i16(1)),
+                CompiledFunc::from_u32(func),
+            ),
+            Instruction::register(param),
+        ]
+    };
+    TranslationTest::new(wasm)
+        .optimization_level(2)
+        .expect_func_instrs([
+            Instruction::memory_grow(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::return_reg(1),
+        ])
+        .expect_func_instrs(call(0, 0).into_iter().chain([Instruction::return_forward(
+            RegisterSpan::new(Register::from_i16(1)).iter(1),
+        )]))
+        .expect_func_instrs(call(3, 0).into_iter().chain(call(1, 1)).chain([
+            Instruction::return_forward(RegisterSpan::new(Register::from_i16(1)).iter(1)),
+        ]))
+        .expect_func_instrs([Instruction::return_reg(0)])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn budget() {
+    let calls = "(local.set 0 (call $inc (local.get 0)))".repeat(300);
+    let wasm = wat2wasm(&format!(
+        r#"
+        (module
+            (func $inc (param i32) (result i32)
+                (i32.add (local.get 0) (i32.const 1))
+            )
+            (func (param i32) (result i32)
+                {calls}
+                (local.get 0)
+            )
+        )
+    "#
+    ));
+    let inlined = Instruction::i32_add_imm16(Register::from_i16(0), Register::from_i16(0), 1_i16);
+    let called = [
+        Instruction::call_internal(
+            RegisterSpan::new(Register::from_i16(0)),
+            CompiledFunc::from_u32(0),
+        ),
+        Instruction::register(0),
+    ];
+    let instrs = core::iter::repeat_n(inlined, 256)
+        .chain(core::iter::repeat_n(called, 300 - 256).flatten())
+        .chain([Instruction::return_reg(0)]);
+    TranslationTest::new(wasm)
+        .optimization_level(2)
+        .expect_func_instrs(inc_instrs())
+        .expect_func_instrs(instrs)
+        .run();
+}
@@ -6,14 +6,17 @@ mod br_table;
 mod call;
 mod cmp;
 mod cmp_br;
+mod copy;
 mod global_get;
 mod global_set;
 mod i32_eqz;
 mod if_;
+mod inline;
 mod load;
 mod local_set;
 mod loop_;
 mod memory;
+mod optimize;
 mod return_;
 mod return_call;
 mod select;
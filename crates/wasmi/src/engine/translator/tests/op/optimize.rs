@@ -0,0 +1,306 @@
+//! Tests for the optional optimization pass over translated Wasmi bytecode.
+
+use super::*;
+use crate::engine::bytecode::BranchOffset16;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fold_const_rhs() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32) (local i32)
+                (local.set 1 (i32.const 5))
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::copy_imm32(Register::from_i16(1), 5_i32),
+            Instruction::i32_add_imm16(Register::from_i16(2), Register::from_i16(0), 5_i16),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fold_const_lhs() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32) (local i32)
+                (local.set 1 (i32.const 5))
+                (i32.sub (local.get 1) (local.get 0))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::copy_imm32(Register::from_i16(1), 5_i32),
+            Instruction::i32_sub_imm16_rev(Register::from_i16(2), 5_i16, Register::from_i16(0)),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fold_const_cmp_lhs() {
+    // Note: `5 < x` is folded into `x > 5`.
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32) (local i32)
+                (local.set 1 (i32.const 5))
+                (i32.lt_s (local.get 1) (local.get 0))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::copy_imm32(Register::from_i16(1), 5_i32),
+            Instruction::i32_gt_s_imm16(Register::from_i16(2), Register::from_i16(0), 5_i16),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fold_const_i64() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i64) (result i64) (local i64)
+                (local.set 1 (i64.const -10))
+                (i64.and (local.get 0) (local.get 1))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::copy_i64imm32(Register::from_i16(1), -10_i32),
+            Instruction::i64_and_imm16(Register::from_i16(2), Register::from_i16(0), -10_i16),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fold_const_removes_dead_copy() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32) (local i32)
+                (local.set 1 (i32.const 5))
+                (local.set 1 (i32.mul (local.get 0) (local.get 1)))
+                (local.get 1)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::i32_mul_imm16(Register::from_i16(1), Register::from_i16(0), 5_i16),
+            Instruction::return_reg(1),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_fold_without_optimizations() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32) (local i32)
+                (local.set 1 (i32.const 5))
+                (local.set 1 (i32.mul (local.get 0) (local.get 1)))
+                (local.get 1)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::copy_imm32(Register::from_i16(1), 5_i32),
+            Instruction::i32_mul(
+                Register::from_i16(1),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(1),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_fold_out_of_bounds_const() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32) (local i32)
+                (local.set 1 (i32.const 100000))
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::copy_imm32(Register::from_i16(1), 100_000_i32),
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_fold_after_overwrite() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32) (local i32)
+                (local.set 1 (i32.const 5))
+                (local.set 1 (local.get 0))
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::copy(Register::from_i16(1), Register::from_i16(0)),
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_fold_across_branch_target() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32) (result i32) (local i32)
+                (local.set 1 (i32.const 5))
+                (loop
+                    (local.set 0 (i32.add (local.get 0) (local.get 1)))
+                    (br_if 0 (local.get 0))
+                )
+                (local.get 0)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::copy_imm32(Register::from_i16(1), 5_i32),
+            Instruction::i32_add(
+                Register::from_i16(0),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::branch_i32_ne_imm(Register::from_i16(0), 0_i16, BranchOffset16::from(-1)),
+            Instruction::return_reg(0),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn remove_dead_copy() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32) (result i32) (local i32)
+                (local.set 2 (local.get 1))
+                (local.set 2 (i32.add (local.get 0) (local.get 1)))
+                (local.get 2)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_remove_copy_read_before_overwrite() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32) (result i32) (local i32)
+                (local.set 2 (local.get 1))
+                (local.set 2 (i32.add (local.get 0) (local.get 2)))
+                (local.get 2)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::copy(Register::from_i16(2), Register::from_i16(1)),
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(2),
+            ),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn no_remove_copy_live_at_branch() {
+    let wasm = wat2wasm(
+        r"
+        (module
+            (func (param i32 i32) (result i32) (local i32)
+                (local.set 2 (local.get 1))
+                (if (local.get 0)
+                    (then (return (local.get 2)))
+                )
+                (local.set 2 (local.get 0))
+                (local.get 2)
+            )
+        )",
+    );
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::copy(Register::from_i16(2), Register::from_i16(1)),
+            Instruction::branch_i32_eq_imm(Register::from_i16(0), 0_i16, BranchOffset16::from(2)),
+            Instruction::return_reg(2),
+            Instruction::copy(Register::from_i16(2), Register::from_i16(0)),
+            Instruction::return_reg(2),
+        ])
+        .run()
+}
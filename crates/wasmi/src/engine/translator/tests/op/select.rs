@@ -799,3 +799,143 @@ fn both_f64imm32() {
     test_for(f64::NEG_INFINITY, f64::INFINITY);
     test_for(f64::NAN, f64::EPSILON);
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_eqz() {
+    fn test_reg_eqz(kind: SelectKind, result_ty: ValueType) {
+        let display_ty = DisplayValueType::from(result_ty);
+        let display_select = DisplaySelect::new(kind, result_ty);
+        let wasm = wat2wasm(&format!(
+            r#"
+            (module
+                (func (param $condition i32)
+                      (param $lhs {display_ty})
+                      (param $rhs {display_ty})
+                      (result {display_ty})
+                    local.get $lhs
+                    local.get $rhs
+                    local.get $condition
+                    i32.eqz
+                    {display_select}
+                )
+            )
+        "#,
+        ));
+        let condition = Register::from_i16(0);
+        let lhs = Register::from_i16(1);
+        let rhs = Register::from_i16(2);
+        let result = Register::from_i16(3);
+        TranslationTest::new(wasm)
+            .optimization_level(1)
+            .expect_func_instrs([
+                Instruction::select(result, condition, rhs),
+                Instruction::Register(lhs),
+                Instruction::return_reg(result),
+            ])
+            .run();
+    }
+    fn test_for(kind: SelectKind) {
+        test_reg_eqz(kind, ValueType::I32);
+        test_reg_eqz(kind, ValueType::I64);
+        test_reg_eqz(kind, ValueType::F32);
+        test_reg_eqz(kind, ValueType::F64);
+    }
+    test_for(SelectKind::Select);
+    test_for(SelectKind::TypedSelect);
+    test_reg_eqz(SelectKind::TypedSelect, ValueType::FuncRef);
+    test_reg_eqz(SelectKind::TypedSelect, ValueType::ExternRef);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_eqz_eqz() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param $condition i32) (param $lhs i32) (param $rhs i32) (result i32)
+                local.get $lhs
+                local.get $rhs
+                local.get $condition
+                i32.eqz
+                i32.eqz
+                select
+            )
+        )
+    "#,
+    );
+    let condition = Register::from_i16(0);
+    let lhs = Register::from_i16(1);
+    let rhs = Register::from_i16(2);
+    let result = Register::from_i16(3);
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::select(result, condition, lhs),
+            Instruction::Register(rhs),
+            Instruction::return_reg(result),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_imm32_eqz() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param $condition i32) (param $lhs i32) (result i32)
+                local.get $lhs
+                i32.const 10
+                local.get $condition
+                i32.eqz
+                select
+            )
+        )
+    "#,
+    );
+    let condition = Register::from_i16(0);
+    let lhs = Register::from_i16(1);
+    let result = Register::from_i16(2);
+    TranslationTest::new(wasm)
+        .optimization_level(1)
+        .expect_func_instrs([
+            Instruction::select_rev(result, condition, lhs),
+            Instruction::const32(10_i32),
+            Instruction::return_reg(result),
+        ])
+        .run();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn reg_eqz_local_tee() {
+    let wasm = wat2wasm(
+        r#"
+        (module
+            (func (param $condition i32) (param $lhs i32) (param $rhs i32) (result i32)
+                (local $tmp i32)
+                local.get $lhs
+                local.get $rhs
+                local.get $condition
+                i32.eqz
+                local.tee $tmp
+                select
+            )
+        )
+    "#,
+    );
+    let condition = Register::from_i16(0);
+    let lhs = Register::from_i16(1);
+    let rhs = Register::from_i16(2);
+    let tmp = Register::from_i16(3);
+    let result = Register::from_i16(4);
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::i32_eq_imm16(tmp, condition, 0),
+            Instruction::select(result, tmp, lhs),
+            Instruction::Register(rhs),
+            Instruction::return_reg(result),
+        ])
+        .run();
+}
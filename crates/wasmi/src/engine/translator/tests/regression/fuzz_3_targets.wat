@@ -0,0 +1,11 @@
+;; Variant of `fuzz_3.wat` with distinct `br_table` targets so that it is not translated as `br`.
+(module
+  (func (;0;) (result i32 i32 i32)
+    block (result i32 i32 i32) ;; label = @1
+      call 0
+      call 0
+      br_table 0 (;@1;) 1 (;@0;)
+    end
+  )
+  (export "" (func 0))
+)
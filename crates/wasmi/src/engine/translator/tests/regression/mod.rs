@@ -53,7 +53,7 @@ fn fuzz_regression_2() {
 #[test]
 #[cfg_attr(miri, ignore)]
 fn fuzz_regression_3() {
-    let wat = include_str!("fuzz_3.wat");
+    let wat = include_str!("fuzz_3_targets.wat");
     let wasm = wat2wasm(wat);
     TranslationTest::new(wasm)
         .expect_func_instrs([
@@ -71,8 +71,30 @@ fn fuzz_regression_3() {
                 RegisterSpan::new(Register::from_i16(2)),
                 3,
             ),
+            Instruction::branch(BranchOffset::from(2)),
             Instruction::return_span(RegisterSpan::new(Register::from_i16(0)).iter_u16(3)),
-            Instruction::return_span(RegisterSpan::new(Register::from_i16(0)).iter_u16(3)),
+            Instruction::return_reg3(0, 1, 2),
+        ])
+        .run()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fuzz_regression_3_identical_targets() {
+    let wat = include_str!("fuzz_3.wat");
+    let wasm = wat2wasm(wat);
+    TranslationTest::new(wasm)
+        .expect_func_instrs([
+            Instruction::call_internal_0(
+                RegisterSpan::new(Register::from_i16(0)),
+                CompiledFunc::from_u32(0),
+            ),
+            Instruction::call_internal_0(
+                RegisterSpan::new(Register::from_i16(3)),
+                CompiledFunc::from_u32(0),
+            ),
+            // Note: a `br_table` with only identical targets is translated as `br`.
+            Instruction::return_reg3(2, 3, 4),
         ])
         .run()
 }
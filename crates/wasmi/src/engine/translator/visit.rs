@@ -21,7 +21,8 @@ use crate::{
         bytecode::{self, Const16, Instruction, Provider, Register, SignatureIdx},
         translator::AcquiredTarget,
         BlockType,
-        FuelCosts,
+        FuelUsage,
+        ModuleLimit,
     },
     module::{self, FuncIdx, WasmiValueType},
     Error,
@@ -482,6 +483,10 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     }
 
     fn visit_br_table(&mut self, targets: wasmparser::BrTable<'a>) -> Self::Output {
+        self.engine()
+            .config()
+            .module_limits()
+            .ensure(ModuleLimit::BrTableTargets, targets.len())?;
         bail_unreachable!(self);
         let engine = self.engine().clone();
         let fuel_info = self.fuel_info();
@@ -518,6 +523,18 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             self.alloc.br_table_targets.push(target?);
         }
         self.alloc.br_table_targets.push(default_target);
+        if self
+            .alloc
+            .br_table_targets
+            .iter()
+            .all(|&target| target == default_target)
+        {
+            // Case: all `br_table` targets are the default target.
+            //
+            // This means the `br_table` always branches to the default target
+            // independent of the `index` value and thus acts exactly like a `br`.
+            return self.visit_br(default_target);
+        }
         // We check if all `br_table` targets expect their results at the same
         // registers which allows us to encode the `br_table` more efficiently
         // by using a single copy instruction before branching instead of having
@@ -547,7 +564,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             // In both cases it is sufficient to copy values to the destination of
             // the default branch target and encode the `br_table` with a series of
             // simple direct branches without any further copy instructions.
-            self.push_base_instr(Instruction::branch_table(index, targets.len() + 1))?;
+            self.push_branch_table(index, default_target)?;
             self.translate_copy_branch_params(default_branch_params)?;
             let return_instr = match default_branch_params.len() {
                 0 => Instruction::Return,
@@ -580,7 +597,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         //
         // Since `br_table` target depths are often shared we use a btree-set to
         // share codegen for `br_table` arms that have the same branch target.
-        self.push_base_instr(Instruction::branch_table(index, targets.len() + 1))?;
+        self.push_branch_table(index, default_target)?;
         let mut shared_targets = <BTreeMap<u32, LabelRef>>::new();
         for target in self.alloc.br_table_targets.iter().copied() {
             let shared_label = *shared_targets
@@ -635,15 +652,24 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_call(&mut self, function_index: u32) -> Self::Output {
         bail_unreachable!(self);
-        self.bump_fuel_consumption(FuelCosts::call)?;
         let func_idx = FuncIdx::from(function_index);
+        if let Some(intrinsic) = self.intrinsic_of(func_idx) {
+            // Case: We are calling an imported intrinsic function and can
+            //       substitute the call with its dedicated instruction.
+            return self.translate_intrinsic(intrinsic);
+        }
+        self.bump_fuel_consumption(FuelUsage::Call)?;
         let func_type = self.func_type_of(func_idx);
         let (params, results) = func_type.params_results();
-        let provider_params = &mut self.alloc.buffer;
-        self.alloc.stack.pop_n(params.len(), provider_params);
-        let results = self.alloc.stack.push_dynamic_n(results.len())?;
+        self.alloc.stack.pop_n(params.len(), &mut self.alloc.buffer);
+        let len_results = results.len();
+        let results = self.alloc.stack.push_dynamic_n(len_results)?;
         let instr = match self.module.get_compiled_func(func_idx) {
             Some(compiled_func) => {
+                if self.try_inline_call(compiled_func, results, len_results)? {
+                    // Case: We are calling a tiny internal function that got inlined.
+                    return Ok(());
+                }
                 // Case: We are calling an internal function and can optimize
                 //       this case by using the special instruction for it.
                 match params.len() {
@@ -660,10 +686,10 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                 }
             }
         };
-        self.alloc.instr_encoder.push_instr(instr)?;
+        self.push_call(instr, len_results)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_register_list(&mut self.alloc.stack, &self.alloc.buffer)?;
         Ok(())
     }
 
@@ -674,13 +700,12 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         _table_byte: u8,
     ) -> Self::Output {
         bail_unreachable!(self);
-        self.bump_fuel_consumption(FuelCosts::call)?;
+        self.bump_fuel_consumption(FuelUsage::Call)?;
         let type_index = SignatureIdx::from(type_index);
         let func_type = self.func_type_at(type_index);
         let (params, results) = func_type.params_results();
         let index = self.alloc.stack.pop();
-        let provider_params = &mut self.alloc.buffer;
-        self.alloc.stack.pop_n(params.len(), provider_params);
+        self.alloc.stack.pop_n(params.len(), &mut self.alloc.buffer);
         let table_params = match index {
             TypedProvider::Const(index) => match <Const16<u32>>::try_from(u32::from(index)).ok() {
                 Some(index) => {
@@ -697,22 +722,23 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             },
             TypedProvider::Register(index) => Instruction::call_indirect_params(index, table_index),
         };
-        let results = self.alloc.stack.push_dynamic_n(results.len())?;
+        let len_results = results.len();
+        let results = self.alloc.stack.push_dynamic_n(len_results)?;
         let instr = match params.len() {
             0 => Instruction::call_indirect_0(results, type_index),
             _ => Instruction::call_indirect(results, type_index),
         };
-        self.alloc.instr_encoder.push_instr(instr)?;
+        self.push_call(instr, len_results)?;
         self.alloc.instr_encoder.append_instr(table_params)?;
         self.alloc
             .instr_encoder
-            .encode_register_list(&mut self.alloc.stack, provider_params)?;
+            .encode_register_list(&mut self.alloc.stack, &self.alloc.buffer)?;
         Ok(())
     }
 
     fn visit_return_call(&mut self, function_index: u32) -> Self::Output {
         bail_unreachable!(self);
-        self.bump_fuel_consumption(FuelCosts::call)?;
+        self.bump_fuel_consumption(FuelUsage::Call)?;
         let func_idx = FuncIdx::from(function_index);
         let func_type = self.func_type_of(func_idx);
         let params = func_type.params();
@@ -746,7 +772,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_return_call_indirect(&mut self, type_index: u32, table_index: u32) -> Self::Output {
         bail_unreachable!(self);
-        self.bump_fuel_consumption(FuelCosts::call)?;
+        self.bump_fuel_consumption(FuelUsage::Call)?;
         let type_index = SignatureIdx::from(type_index);
         let func_type = self.func_type_at(type_index);
         let params = func_type.params();
@@ -870,7 +896,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         let result = self.alloc.stack.push_dynamic()?;
         self.push_fueled_instr(
             Instruction::global_get(result, global_idx),
-            FuelCosts::entity,
+            FuelUsage::Entity,
         )?;
         Ok(())
     }
@@ -880,7 +906,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         let global = bytecode::GlobalIdx::from(global_index);
         match self.alloc.stack.pop() {
             TypedProvider::Register(input) => {
-                self.push_fueled_instr(Instruction::global_set(global, input), FuelCosts::entity)?;
+                self.push_fueled_instr(Instruction::global_set(global, input), FuelUsage::Entity)?;
                 Ok(())
             }
             TypedProvider::Const(input) => {
@@ -893,7 +919,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                         if let Ok(value) = Const16::try_from(i32::from(input)) {
                             self.push_fueled_instr(
                                 Instruction::global_set_i32imm16(global, value),
-                                FuelCosts::entity,
+                                FuelUsage::Entity,
                             )?;
                             return Ok(());
                         }
@@ -902,7 +928,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                         if let Ok(value) = Const16::try_from(i64::from(input)) {
                             self.push_fueled_instr(
                                 Instruction::global_set_i64imm16(global, value),
-                                FuelCosts::entity,
+                                FuelUsage::Entity,
                             )?;
                             return Ok(());
                         }
@@ -910,7 +936,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                     _ => {}
                 };
                 let cref = self.alloc.stack.alloc_const(input)?;
-                self.push_fueled_instr(Instruction::global_set(global, cref), FuelCosts::entity)?;
+                self.push_fueled_instr(Instruction::global_set(global, cref), FuelUsage::Entity)?;
                 Ok(())
             }
         }
@@ -1144,11 +1170,15 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         );
         bail_unreachable!(self);
         let result = self.alloc.stack.push_dynamic()?;
-        self.push_fueled_instr(Instruction::memory_size(result), FuelCosts::entity)?;
+        self.push_fueled_instr(Instruction::memory_size(result), FuelUsage::Entity)?;
         Ok(())
     }
 
-    fn visit_memory_grow(&mut self, _mem: u32, _mem_byte: u8) -> Self::Output {
+    fn visit_memory_grow(&mut self, mem: u32, _mem_byte: u8) -> Self::Output {
+        debug_assert_eq!(
+            mem, 0,
+            "wasmi does not yet support the multi-memory Wasm proposal"
+        );
         bail_unreachable!(self);
         let delta = self.alloc.stack.pop();
         let delta = <Provider<Const16<u32>>>::new(delta, &mut self.alloc.stack)?;
@@ -1165,7 +1195,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             }
             Provider::Const(delta) => Instruction::memory_grow_by(result, delta),
         };
-        self.push_fueled_instr(instr, FuelCosts::entity)?;
+        self.push_fueled_instr(instr, FuelUsage::Entity)?;
         Ok(())
     }
 
@@ -1221,14 +1251,19 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         let result = self.alloc.stack.push_dynamic()?;
         self.push_fueled_instr(
             Instruction::ref_func(result, function_index),
-            FuelCosts::entity,
+            FuelUsage::Entity,
         )?;
         Ok(())
     }
 
     fn visit_i32_eqz(&mut self) -> Self::Output {
         bail_unreachable!(self);
-        if self.alloc.instr_encoder.fuse_i32_eqz(&mut self.alloc.stack) {
+        let invert_cmp = self.is_optimizing();
+        if self
+            .alloc
+            .instr_encoder
+            .fuse_i32_eqz(&mut self.alloc.stack, invert_cmp)
+        {
             // Optimization of `i32.eqz` was applied so we can bail out.
             return Ok(());
         }
@@ -3204,7 +3239,11 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         )
     }
 
-    fn visit_memory_init(&mut self, data_index: u32, _mem: u32) -> Self::Output {
+    fn visit_memory_init(&mut self, data_index: u32, mem: u32) -> Self::Output {
+        debug_assert_eq!(
+            mem, 0,
+            "wasmi does not yet support the multi-memory Wasm proposal"
+        );
         bail_unreachable!(self);
         let (dst, src, len) = self.alloc.stack.pop3();
         let dst = <Provider<Const16<u32>>>::new(dst, &mut self.alloc.stack)?;
@@ -3236,7 +3275,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                 Instruction::memory_init_from_to_exact(dst, src, len)
             }
         };
-        self.push_fueled_instr(instr, FuelCosts::entity)?;
+        self.push_fueled_instr(instr, FuelUsage::Entity)?;
         self.alloc
             .instr_encoder
             .append_instr(Instruction::data_idx(data_index))?;
@@ -3245,11 +3284,19 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_data_drop(&mut self, data_index: u32) -> Self::Output {
         bail_unreachable!(self);
-        self.push_fueled_instr(Instruction::DataDrop(data_index.into()), FuelCosts::entity)?;
+        self.push_fueled_instr(Instruction::DataDrop(data_index.into()), FuelUsage::Entity)?;
         Ok(())
     }
 
-    fn visit_memory_copy(&mut self, _dst_mem: u32, _src_mem: u32) -> Self::Output {
+    fn visit_memory_copy(&mut self, dst_mem: u32, src_mem: u32) -> Self::Output {
+        debug_assert_eq!(
+            dst_mem, 0,
+            "wasmi does not yet support the multi-memory Wasm proposal"
+        );
+        debug_assert_eq!(
+            src_mem, 0,
+            "wasmi does not yet support the multi-memory Wasm proposal"
+        );
         bail_unreachable!(self);
         let (dst, src, len) = self.alloc.stack.pop3();
         let dst = <Provider<Const16<u32>>>::new(dst, &mut self.alloc.stack)?;
@@ -3281,11 +3328,15 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                 Instruction::memory_copy_from_to_exact(dst, src, len)
             }
         };
-        self.push_fueled_instr(instr, FuelCosts::entity)?;
+        self.push_fueled_instr(instr, FuelUsage::Entity)?;
         Ok(())
     }
 
-    fn visit_memory_fill(&mut self, _mem: u32) -> Self::Output {
+    fn visit_memory_fill(&mut self, mem: u32) -> Self::Output {
+        debug_assert_eq!(
+            mem, 0,
+            "wasmi does not yet support the multi-memory Wasm proposal"
+        );
         bail_unreachable!(self);
         let (dst, value, len) = self.alloc.stack.pop3();
         let dst = <Provider<Const16<u32>>>::new(dst, &mut self.alloc.stack)?;
@@ -3317,7 +3368,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                 Instruction::memory_fill_at_imm_exact(dst, value, len)
             }
         };
-        self.push_fueled_instr(instr, FuelCosts::entity)?;
+        self.push_fueled_instr(instr, FuelUsage::Entity)?;
         Ok(())
     }
 
@@ -3353,7 +3404,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                 Instruction::table_init_from_to_exact(dst, src, len)
             }
         };
-        self.push_fueled_instr(instr, FuelCosts::entity)?;
+        self.push_fueled_instr(instr, FuelUsage::Entity)?;
         self.alloc
             .instr_encoder
             .append_instr(Instruction::table_idx(table))?;
@@ -3365,7 +3416,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
 
     fn visit_elem_drop(&mut self, elem_index: u32) -> Self::Output {
         bail_unreachable!(self);
-        self.push_fueled_instr(Instruction::ElemDrop(elem_index.into()), FuelCosts::entity)?;
+        self.push_fueled_instr(Instruction::ElemDrop(elem_index.into()), FuelUsage::Entity)?;
         Ok(())
     }
 
@@ -3401,7 +3452,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                 Instruction::table_copy_from_to_exact(dst, src, len)
             }
         };
-        self.push_fueled_instr(instr, FuelCosts::entity)?;
+        self.push_fueled_instr(instr, FuelUsage::Entity)?;
         self.alloc
             .instr_encoder
             .append_instr(Instruction::table_idx(dst_table))?;
@@ -3434,7 +3485,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                 Instruction::table_fill_at_exact(dst, len, value)
             }
         };
-        self.push_fueled_instr(instr, FuelCosts::entity)?;
+        self.push_fueled_instr(instr, FuelUsage::Entity)?;
         self.alloc
             .instr_encoder
             .append_instr(Instruction::table_idx(table))?;
@@ -3447,12 +3498,12 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
         let result = self.alloc.stack.push_dynamic()?;
         match index {
             TypedProvider::Register(index) => {
-                self.push_fueled_instr(Instruction::table_get(result, index), FuelCosts::entity)?;
+                self.push_fueled_instr(Instruction::table_get(result, index), FuelUsage::Entity)?;
             }
             TypedProvider::Const(index) => {
                 self.push_fueled_instr(
                     Instruction::table_get_imm(result, u32::from(index)),
-                    FuelCosts::entity,
+                    FuelUsage::Entity,
                 )?;
             }
         }
@@ -3473,7 +3524,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             TypedProvider::Register(index) => Instruction::table_set(index, value),
             TypedProvider::Const(index) => Instruction::table_set_at(u32::from(index), value),
         };
-        self.push_fueled_instr(instr, FuelCosts::entity)?;
+        self.push_fueled_instr(instr, FuelUsage::Entity)?;
         self.alloc
             .instr_encoder
             .append_instr(Instruction::table_idx(table))?;
@@ -3491,7 +3542,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
                 // operation a `table.grow` with `delta` of 0 can be translated
                 // as `table.size` instruction instead.
                 let result = self.alloc.stack.push_dynamic()?;
-                self.push_fueled_instr(Instruction::table_size(result, table), FuelCosts::entity)?;
+                self.push_fueled_instr(Instruction::table_size(result, table), FuelUsage::Entity)?;
                 return Ok(());
             }
         }
@@ -3505,7 +3556,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
             Provider::Register(delta) => Instruction::table_grow(result, delta, value),
             Provider::Const(delta) => Instruction::table_grow_imm(result, delta, value),
         };
-        self.push_fueled_instr(instr, FuelCosts::entity)?;
+        self.push_fueled_instr(instr, FuelUsage::Entity)?;
         self.alloc
             .instr_encoder
             .append_instr(Instruction::table_idx(table))?;
@@ -3515,7 +3566,7 @@ impl<'a> VisitOperator<'a> for FuncTranslator {
     fn visit_table_size(&mut self, table: u32) -> Self::Output {
         bail_unreachable!(self);
         let result = self.alloc.stack.push_dynamic()?;
-        self.push_fueled_instr(Instruction::table_size(result, table), FuelCosts::entity)?;
+        self.push_fueled_instr(Instruction::table_size(result, table), FuelUsage::Entity)?;
         Ok(())
     }
 }
@@ -60,6 +60,9 @@ impl VisitInputRegisters for Instruction {
             Instruction::ReturnMany { values } => {
                 values.visit_input_registers(f);
             }
+            Instruction::ReturnForward { values } => {
+                values.visit_input_registers(f);
+            }
             Instruction::ReturnNez { condition } => f(condition),
             Instruction::ReturnNezReg { condition, value } => visit_registers!(f, condition, value),
             Instruction::ReturnNezReg2 { condition, values } => {
@@ -79,8 +82,19 @@ impl VisitInputRegisters for Instruction {
             }
             Instruction::Branch { .. } => {},
             Instruction::BranchTable { index, .. } => f(index),
+            Instruction::BranchTableSparse { index, .. } => f(index),
 
             Instruction::BranchCmpFallback { lhs, rhs, .. } => visit_registers!(f, lhs, rhs),
+            Instruction::BranchI32EqFallback(instr) => visit_registers!(f, &mut instr.lhs, &mut instr.rhs),
+            Instruction::BranchI32NeFallback(instr) => visit_registers!(f, &mut instr.lhs, &mut instr.rhs),
+            Instruction::BranchI32LtSFallback(instr) => visit_registers!(f, &mut instr.lhs, &mut instr.rhs),
+            Instruction::BranchI32LtUFallback(instr) => visit_registers!(f, &mut instr.lhs, &mut instr.rhs),
+            Instruction::BranchI32LeSFallback(instr) => visit_registers!(f, &mut instr.lhs, &mut instr.rhs),
+            Instruction::BranchI32LeUFallback(instr) => visit_registers!(f, &mut instr.lhs, &mut instr.rhs),
+            Instruction::BranchI32GtSFallback(instr) => visit_registers!(f, &mut instr.lhs, &mut instr.rhs),
+            Instruction::BranchI32GtUFallback(instr) => visit_registers!(f, &mut instr.lhs, &mut instr.rhs),
+            Instruction::BranchI32GeSFallback(instr) => visit_registers!(f, &mut instr.lhs, &mut instr.rhs),
+            Instruction::BranchI32GeUFallback(instr) => visit_registers!(f, &mut instr.lhs, &mut instr.rhs),
             Instruction::BranchI32And(instr) => instr.visit_input_registers(f),
             Instruction::BranchI32AndImm(instr) => instr.visit_input_registers(f),
             Instruction::BranchI32Or(instr) => instr.visit_input_registers(f),
@@ -139,12 +153,16 @@ impl VisitInputRegisters for Instruction {
             Instruction::BranchF32Le(instr) => instr.visit_input_registers(f),
             Instruction::BranchF32Gt(instr) => instr.visit_input_registers(f),
             Instruction::BranchF32Ge(instr) => instr.visit_input_registers(f),
+            Instruction::BranchF32NotLt(instr) => instr.visit_input_registers(f),
+            Instruction::BranchF32NotLe(instr) => instr.visit_input_registers(f),
             Instruction::BranchF64Eq(instr) => instr.visit_input_registers(f),
             Instruction::BranchF64Ne(instr) => instr.visit_input_registers(f),
             Instruction::BranchF64Lt(instr) => instr.visit_input_registers(f),
             Instruction::BranchF64Le(instr) => instr.visit_input_registers(f),
             Instruction::BranchF64Gt(instr) => instr.visit_input_registers(f),
             Instruction::BranchF64Ge(instr) => instr.visit_input_registers(f),
+            Instruction::BranchF64NotLt(instr) => instr.visit_input_registers(f),
+            Instruction::BranchF64NotLe(instr) => instr.visit_input_registers(f),
 
             Instruction::Copy { result, value } => {
                 // Note: for copy instruction unlike all other instructions
@@ -410,6 +428,8 @@ impl VisitInputRegisters for Instruction {
             Instruction::I64Mul(instr) => instr.visit_input_registers(f),
             Instruction::I32MulImm16(instr) => instr.visit_input_registers(f),
             Instruction::I64MulImm16(instr) => instr.visit_input_registers(f),
+            Instruction::I64MulWideS { results: _, lhs, rhs } |
+            Instruction::I64MulWideU { results: _, lhs, rhs } => visit_registers!(f, lhs, rhs),
             Instruction::I32DivS(instr) => instr.visit_input_registers(f),
             Instruction::I64DivS(instr) => instr.visit_input_registers(f),
             Instruction::I32DivSImm16(instr) => instr.visit_input_registers(f),
@@ -434,6 +454,12 @@ impl VisitInputRegisters for Instruction {
             Instruction::I64RemUImm16(instr) => instr.visit_input_registers(f),
             Instruction::I32RemUImm16Rev(instr) => instr.visit_input_registers(f),
             Instruction::I64RemUImm16Rev(instr) => instr.visit_input_registers(f),
+            Instruction::I32CheckedAdd(instr) => instr.visit_input_registers(f),
+            Instruction::I32CheckedSub(instr) => instr.visit_input_registers(f),
+            Instruction::I32CheckedMul(instr) => instr.visit_input_registers(f),
+            Instruction::I64CheckedAdd(instr) => instr.visit_input_registers(f),
+            Instruction::I64CheckedSub(instr) => instr.visit_input_registers(f),
+            Instruction::I64CheckedMul(instr) => instr.visit_input_registers(f),
             Instruction::I32And(instr) => instr.visit_input_registers(f),
             Instruction::I32AndEqz(instr) => instr.visit_input_registers(f),
             Instruction::I32AndEqzImm16(instr) => instr.visit_input_registers(f),
@@ -1,26 +1,135 @@
 use super::errors::{
+    BatchCallError,
+    EngineMismatchError,
+    ExportError,
     FuelError,
     FuncError,
     GlobalError,
     InstantiationError,
     LinkerError,
     MemoryError,
+    SnapshotError,
     TableError,
+    ValueError,
 };
 use crate::{
     core::{HostError, TrapCode},
-    engine::TranslationError,
+    engine::{BytecodeError, StackUsage, TranslationError},
     module::ReadError,
+    FuncType,
 };
-use alloc::{boxed::Box, string::String};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use core::{fmt, fmt::Display};
 use wasmparser::BinaryReaderError as WasmError;
 
+#[cfg(feature = "std")]
+use super::errors::MemoryIoError;
+#[cfg(feature = "wat")]
+use super::errors::WatError;
+
 /// The generic Wasmi root error type.
 #[derive(Debug)]
 pub struct Error {
+    /// The underlying kind of the error and its additional information.
+    inner: Box<ErrorInner>,
+}
+
+/// The boxed information of an [`Error`].
+#[derive(Debug)]
+struct ErrorInner {
     /// The underlying kind of the error and its specific information.
-    kind: Box<ErrorKind>,
+    kind: ErrorKind,
+    /// The Wasm binary offset of the Wasm operator that caused the error if known.
+    ///
+    /// Read [`Error::trap_wasm_offset`] for more information.
+    wasm_offset: Option<u32>,
+    /// The expected and actual function signatures if the error is caused by their mismatch.
+    ///
+    /// Read [`Error::signature_mismatch`] for more information.
+    signature_mismatch: Option<(FuncType, FuncType)>,
+    /// The peak usage of the Wasm stack if the error is caused by a stack overflow.
+    ///
+    /// Read [`Error::stack_usage`] for more information.
+    stack_usage: Option<StackUsage>,
+    /// The Wasm origin of the trapping Wasm operator if known.
+    ///
+    /// Read [`Error::trap_origin`] for more information.
+    trap_origin: Option<TrapOrigin>,
+    /// The trap messages of the [`Engine`] that raised the trap if any are overridden.
+    ///
+    /// Read [`Engine::set_trap_message_override`] for more information.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Engine::set_trap_message_override`]: crate::Engine::set_trap_message_override
+    trap_messages: Option<Arc<TrapMessages>>,
+}
+
+/// The [`TrapCode`] messages overridden via [`Engine::set_trap_message_override`].
+///
+/// [`Engine::set_trap_message_override`]: crate::Engine::set_trap_message_override
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TrapMessages {
+    /// The overridden messages of their [`TrapCode`]s.
+    overrides: Vec<(TrapCode, Box<str>)>,
+}
+
+impl TrapMessages {
+    /// Overrides the message of `trap_code` with `message`.
+    pub fn set(&mut self, trap_code: TrapCode, message: Box<str>) {
+        match self
+            .overrides
+            .iter_mut()
+            .find(|(code, _)| *code == trap_code)
+        {
+            Some((_, overridden)) => *overridden = message,
+            None => self.overrides.push((trap_code, message)),
+        }
+    }
+
+    /// Returns the overridden message of `trap_code` if any.
+    pub fn get(&self, trap_code: TrapCode) -> Option<&str> {
+        self.overrides
+            .iter()
+            .find(|(code, _)| *code == trap_code)
+            .map(|(_, message)| &**message)
+    }
+}
+
+/// The Wasm function and operator that caused a trap.
+///
+/// Read [`Error::trap_origin`] for more information.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TrapOrigin {
+    /// The index of the Wasm function within its Wasm module.
+    func_index: u32,
+    /// The Wasm binary offset of the trapping Wasm operator.
+    wasm_offset: u32,
+}
+
+impl TrapOrigin {
+    /// Creates a new [`TrapOrigin`].
+    pub(crate) fn new(func_index: u32, wasm_offset: u32) -> Self {
+        Self {
+            func_index,
+            wasm_offset,
+        }
+    }
+
+    /// Returns the index of the Wasm function within its Wasm module.
+    ///
+    /// # Note
+    ///
+    /// The index space includes imported functions.
+    pub fn func_index(&self) -> u32 {
+        self.func_index
+    }
+
+    /// Returns the Wasm binary offset of the trapping Wasm operator.
+    ///
+    /// The offset is relative to the start of the Wasm binary.
+    pub fn wasm_offset(&self) -> u32 {
+        self.wasm_offset
+    }
 }
 
 #[test]
@@ -33,7 +142,14 @@ impl Error {
     /// Creates a new [`Error`] from the [`ErrorKind`].
     fn from_kind(kind: ErrorKind) -> Self {
         Self {
-            kind: Box::new(kind),
+            inner: Box::new(ErrorInner {
+                kind,
+                wasm_offset: None,
+                signature_mismatch: None,
+                stack_usage: None,
+                trap_origin: None,
+                trap_messages: None,
+            }),
         }
     }
 
@@ -47,6 +163,28 @@ impl Error {
         Self::from_kind(ErrorKind::Message(message.into().into_boxed_str()))
     }
 
+    /// Creates a new [`Error`] described by a `message` that wraps its `source` [`Error`].
+    ///
+    /// # Note
+    ///
+    /// - Host functions use this to add context to a Wasmi [`Error`] that they received,
+    ///   e.g. from a nested Wasm call, without losing its identity.
+    /// - The `source` is exposed via [`std::error::Error::source`] and its [`TrapCode`]
+    ///   can be queried via [`Error::root_trap_code`].
+    /// - Like the wrapped `source` the [`Error`] cannot be resumed if its innermost
+    ///   [`Error`] is created via [`Error::exit`] or [`Error::host_trap`].
+    #[inline]
+    #[cold]
+    pub fn wrap<T>(message: T, source: Error) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::from_kind(ErrorKind::Wrapped {
+            message: message.into().into_boxed_str(),
+            source,
+        })
+    }
+
     /// Creates a custom [`HostError`].
     #[inline]
     #[cold]
@@ -68,16 +206,198 @@ impl Error {
         Self::from_kind(ErrorKind::I32ExitStatus(status))
     }
 
+    /// Creates a new `Error` representing a deliberate program exit with the given exit `status`.
+    ///
+    /// # Note
+    ///
+    /// - Host functions use this to terminate the execution of Wasm, e.g. upon
+    ///   a WASI `proc_exit` call, so that embedders can tell a deliberate exit
+    ///   apart from a trap via [`Error::exit_status`].
+    /// - Unlike other host errors the exit cannot be resumed, i.e. a resumable
+    ///   call that receives it returns the error instead of a resumable invocation.
+    #[inline]
+    #[cold]
+    pub fn exit(status: i32) -> Self {
+        Self::from_kind(ErrorKind::Exit(status))
+    }
+
+    /// Creates a new [`Error`] representing a host-defined trap with the given `code`.
+    ///
+    /// # Note
+    ///
+    /// - Host functions use this to raise domain specific traps, e.g. for exceeded
+    ///   quotas or illegal system calls, that are cheap to match on via [`Error::as_host_trap`].
+    /// - The meaning of `code` is up to the host.
+    /// - Like a Wasm trap a host trap cannot be resumed, i.e. a resumable call that
+    ///   receives it returns the error instead of a resumable invocation.
+    #[inline]
+    #[cold]
+    pub fn host_trap(code: u16) -> Self {
+        Self::from_kind(ErrorKind::HostTrap(code))
+    }
+
+    /// Creates a new [`Error`] indicating that the function at `func_index` failed to compile lazily.
+    #[cold]
+    pub(crate) fn lazy_compilation_failed(func_index: u32, source: Arc<Error>) -> Self {
+        Self::from_kind(ErrorKind::LazyCompilationFailed { func_index, source })
+    }
+
     /// Returns the [`ErrorKind`] of the [`Error`].
     pub fn kind(&self) -> &ErrorKind {
-        &self.kind
+        &self.inner.kind
+    }
+
+    /// Returns the Wasm binary offset of the Wasm operator that caused the trap if known.
+    ///
+    /// # Note
+    ///
+    /// This is only available for errors raised by the execution of Wasm operators
+    /// of functions that have been translated with [`Config::generate_address_map`]
+    /// enabled. The offset is relative to the start of the Wasm binary and thus can
+    /// be used to look up source locations, e.g. via DWARF debug information.
+    ///
+    /// Otherwise returns `None`.
+    ///
+    /// [`Config::generate_address_map`]: crate::Config::generate_address_map
+    pub fn trap_wasm_offset(&self) -> Option<u32> {
+        self.inner.wasm_offset
+    }
+
+    /// Sets the Wasm binary offset of the Wasm operator that caused the trap.
+    ///
+    /// # Note
+    ///
+    /// Does nothing if the [`Error`] already has a Wasm binary offset.
+    pub(crate) fn with_trap_wasm_offset(mut self, wasm_offset: u32) -> Self {
+        self.inner.wasm_offset.get_or_insert(wasm_offset);
+        self
+    }
+
+    /// Returns the [`TrapOrigin`] of the Wasm operator that caused the trap if known.
+    ///
+    /// # Note
+    ///
+    /// This is available for traps raised by Wasm `unreachable` operators as well as by
+    /// Wasm operators that Wasmi determined to always trap during translation, e.g. an
+    /// `i32.div_u` by a constant zero. Unlike [`Error::trap_wasm_offset`] this does not
+    /// require [`Config::generate_address_map`] to be enabled.
+    ///
+    /// Otherwise returns `None`.
+    ///
+    /// [`Config::generate_address_map`]: crate::Config::generate_address_map
+    pub fn trap_origin(&self) -> Option<TrapOrigin> {
+        self.inner.trap_origin
+    }
+
+    /// Attaches the [`TrapOrigin`] of the trapping Wasm operator to the [`Error`].
+    #[cold]
+    pub(crate) fn with_trap_origin(mut self, origin: TrapOrigin) -> Self {
+        self.inner.trap_origin = Some(origin);
+        self
+    }
+
+    /// Attaches the overridden `messages` of the [`Engine`] to the [`Error`] if it is a trap.
+    ///
+    /// # Note
+    ///
+    /// Does nothing if the [`Error`] already has overridden messages attached,
+    /// e.g. by the execution of a nested call that raised the trap.
+    ///
+    /// [`Engine`]: crate::Engine
+    pub(crate) fn with_trap_messages(mut self, messages: Option<Arc<TrapMessages>>) -> Self {
+        if self.as_trap_code().is_some() && self.inner.trap_messages.is_none() {
+            self.inner.trap_messages = messages;
+        }
+        self
+    }
+
+    /// Returns the expected and actual [`FuncType`] if the error is caused by their mismatch.
+    ///
+    /// # Note
+    ///
+    /// This is available for errors of:
+    ///
+    /// - `call_indirect` operators that trap with [`TrapCode::BadSignature`]
+    ///   where the expected [`FuncType`] is the one of the `call_indirect` operator
+    ///   and the actual [`FuncType`] is the one of the called function.
+    /// - [`Func::typed`] where the expected [`FuncType`] is the one of the requested
+    ///   static types and the actual [`FuncType`] is the one of the function.
+    ///
+    /// Otherwise returns `None`.
+    ///
+    /// [`Func::typed`]: crate::Func::typed
+    pub fn signature_mismatch(&self) -> Option<(&FuncType, &FuncType)> {
+        self.inner
+            .signature_mismatch
+            .as_ref()
+            .map(|(expected, actual)| (expected, actual))
+    }
+
+    /// Attaches the `expected` and `actual` mismatching [`FuncType`] to the [`Error`].
+    #[cold]
+    pub(crate) fn with_signature_mismatch(mut self, expected: FuncType, actual: FuncType) -> Self {
+        self.inner.signature_mismatch = Some((expected, actual));
+        self
+    }
+
+    /// Returns the peak [`StackUsage`] of the execution if the error is caused by a stack overflow.
+    ///
+    /// # Note
+    ///
+    /// This is available for [`TrapCode::StackOverflow`] errors raised by Wasm executions.
+    /// The [`StackUsage`] describes the Wasm stack right before it overflowed.
+    ///
+    /// Otherwise returns `None`.
+    pub fn stack_usage(&self) -> Option<StackUsage> {
+        self.inner.stack_usage
+    }
+
+    /// Attaches the peak [`StackUsage`] to the [`Error`] if it is a stack overflow.
+    pub(crate) fn with_stack_usage(mut self, usage: StackUsage) -> Self {
+        if let Some(TrapCode::StackOverflow) = self.as_trap_code() {
+            self.inner.stack_usage = Some(usage);
+        }
+        self
     }
 
     /// Returns a reference to [`TrapCode`] if [`Error`] is a [`TrapCode`].
+    ///
+    /// # Note
+    ///
+    /// An [`Error`] returned unchanged by a host function keeps its [`TrapCode`]
+    /// when it propagates through the calling Wasm functions to the embedder.
+    /// Use [`Error::root_trap_code`] for errors wrapped via [`Error::wrap`].
     pub fn as_trap_code(&self) -> Option<TrapCode> {
         self.kind().as_trap_code()
     }
 
+    /// Returns the [`TrapCode`] of the innermost [`Error`] wrapped via [`Error::wrap`] if any.
+    ///
+    /// This is the same as [`Error::as_trap_code`] if the [`Error`] does not wrap another [`Error`].
+    pub fn root_trap_code(&self) -> Option<TrapCode> {
+        self.root().as_trap_code()
+    }
+
+    /// Returns the innermost [`Error`] wrapped via [`Error::wrap`].
+    ///
+    /// Returns `self` if the [`Error`] does not wrap another [`Error`].
+    pub(crate) fn root(&self) -> &Error {
+        let mut error = self;
+        while let ErrorKind::Wrapped { source, .. } = error.kind() {
+            error = source;
+        }
+        error
+    }
+
+    /// Returns the code of a host-defined trap if the [`Error`] is one.
+    ///
+    /// This is the case for errors created via [`Error::host_trap`].
+    ///
+    /// Otherwise returns `None`.
+    pub fn as_host_trap(&self) -> Option<u16> {
+        self.kind().as_host_trap()
+    }
+
     /// Returns the classic `i32` exit program code of a `Trap` if any.
     ///
     /// Otherwise returns `None`.
@@ -85,15 +405,29 @@ impl Error {
         self.kind().as_i32_exit_status()
     }
 
+    /// Returns the exit status if the [`Error`] represents a program exit.
+    ///
+    /// This is the case for errors created via [`Error::exit`] or [`Error::i32_exit`].
+    ///
+    /// Otherwise returns `None`.
+    pub fn exit_status(&self) -> Option<i32> {
+        self.kind().as_exit_status()
+    }
+
     /// Downcasts the [`Error`] into the `T: HostError` if possible.
     ///
+    /// # Note
+    ///
+    /// This does not look through errors wrapped via [`Error::wrap`].
+    ///
     /// Returns `None` otherwise.
     #[inline]
     pub fn downcast_ref<T>(&self) -> Option<&T>
     where
         T: HostError,
     {
-        self.kind
+        self.inner
+            .kind
             .as_host()
             .and_then(<(dyn HostError + 'static)>::downcast_ref)
     }
@@ -106,7 +440,8 @@ impl Error {
     where
         T: HostError,
     {
-        self.kind
+        self.inner
+            .kind
             .as_host_mut()
             .and_then(<(dyn HostError + 'static)>::downcast_mut)
     }
@@ -119,7 +454,8 @@ impl Error {
     where
         T: HostError,
     {
-        self.kind
+        self.inner
+            .kind
             .into_host()
             .and_then(|error| error.downcast().ok())
             .map(|boxed| *boxed)
@@ -127,11 +463,34 @@ impl Error {
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.inner.kind)
+    }
+}
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Display::fmt(&self.kind, f)
+        let overridden = self
+            .as_trap_code()
+            .zip(self.inner.trap_messages.as_deref())
+            .and_then(|(trap_code, messages)| messages.get(trap_code));
+        match overridden {
+            Some(message) => f.write_str(message)?,
+            None => Display::fmt(&self.inner.kind, f)?,
+        }
+        if let Some(origin) = self.trap_origin() {
+            write!(
+                f,
+                " (function {} at offset {:#x})",
+                origin.func_index(),
+                origin.wasm_offset()
+            )?;
+        }
+        if let Some((expected, actual)) = self.signature_mismatch() {
+            write!(f, ": expected {expected:?} but found {actual:?}")?;
+        }
+        Ok(())
     }
 }
 
@@ -145,28 +504,88 @@ pub enum ErrorKind {
     Message(Box<str>),
     /// An `i32` exit status usually used by WASI applications.
     I32ExitStatus(i32),
+    /// A deliberate program exit with its exit status.
+    ///
+    /// # Note
+    ///
+    /// This unwinds the execution like a trap but cannot be resumed
+    /// and is not meant to be caught by Wasm exception handling.
+    Exit(i32),
     /// A trap as defined by the WebAssembly specification.
     Host(Box<dyn HostError>),
+    /// A host-defined trap with its host specific code.
+    ///
+    /// # Note
+    ///
+    /// This is created via [`Error::host_trap`] and cannot be resumed.
+    HostTrap(u16),
     /// A global variable error.
     Global(GlobalError),
+    /// A value error.
+    Value(ValueError),
     /// A linear memory error.
     Memory(MemoryError),
+    /// An I/O error while streaming data into or out of a linear memory.
+    #[cfg(feature = "std")]
+    MemoryIo(MemoryIoError),
     /// A table error.
     Table(TableError),
+    /// A memory or table growth error.
+    Grow(GrowError),
     /// A linker error.
     Linker(LinkerError),
     /// A module instantiation error.
     Instantiation(InstantiationError),
+    /// A module could not be cloned into another engine.
+    EngineMismatch(EngineMismatchError),
+    /// An export lookup error.
+    Export(ExportError),
     /// A fuel error.
     Fuel(FuelError),
+    /// A store snapshot error.
+    Snapshot(SnapshotError),
     /// A function error.
     Func(FuncError),
+    /// A `wasmi.batch_call` error.
+    BatchCall(BatchCallError),
     /// Encountered when there is a problem with the Wasm input stream.
     Read(ReadError),
     /// Encountered when there is a Wasm parsing or validation error.
     Wasm(WasmError),
+    /// Encountered when there is an error parsing the WebAssembly text format.
+    #[cfg(feature = "wat")]
+    Wat(WatError),
     /// Encountered when there is a Wasm to Wasmi translation error.
     Translation(TranslationError),
+    /// Encountered when translated Wasmi bytecode violates its encoding invariants.
+    Bytecode(BytecodeError),
+    /// Encountered when a function failed to compile lazily upon its first use.
+    ///
+    /// # Note
+    ///
+    /// This is only encountered with [`CompilationMode::Lazy`] or
+    /// [`CompilationMode::LazyTranslation`]. All subsequent uses of the
+    /// same function report the same `source` error.
+    ///
+    /// [`CompilationMode::Lazy`]: crate::CompilationMode::Lazy
+    /// [`CompilationMode::LazyTranslation`]: crate::CompilationMode::LazyTranslation
+    LazyCompilationFailed {
+        /// The index of the function within its Wasm module.
+        func_index: u32,
+        /// The Wasm validation or translation error that caused the failure.
+        source: Arc<Error>,
+    },
+    /// A message that wraps its `source` [`Error`].
+    ///
+    /// # Note
+    ///
+    /// This is created via [`Error::wrap`].
+    Wrapped {
+        /// The message describing the context of the `source` [`Error`].
+        message: Box<str>,
+        /// The wrapped [`Error`].
+        source: Error,
+    },
 }
 
 impl ErrorKind {
@@ -178,6 +597,14 @@ impl ErrorKind {
         }
     }
 
+    /// Returns the host trap code if [`ErrorKind`] is an [`ErrorKind::HostTrap`].
+    pub fn as_host_trap(&self) -> Option<u16> {
+        match self {
+            Self::HostTrap(code) => Some(*code),
+            _ => None,
+        }
+    }
+
     /// Returns a [`i32`] if [`ErrorKind`] is an [`ErrorKind::I32ExitStatus`].
     pub fn as_i32_exit_status(&self) -> Option<i32> {
         match self {
@@ -186,6 +613,14 @@ impl ErrorKind {
         }
     }
 
+    /// Returns the exit status if [`ErrorKind`] is an [`ErrorKind::Exit`] or [`ErrorKind::I32ExitStatus`].
+    pub fn as_exit_status(&self) -> Option<i32> {
+        match self {
+            Self::Exit(status) | Self::I32ExitStatus(status) => Some(*status),
+            _ => None,
+        }
+    }
+
     /// Returns a dynamic reference to [`HostError`] if [`ErrorKind`] is a [`HostError`].
     pub fn as_host(&self) -> Option<&dyn HostError> {
         match self {
@@ -212,25 +647,54 @@ impl ErrorKind {
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for ErrorKind {}
+impl std::error::Error for ErrorKind {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::LazyCompilationFailed { source, .. } => Some(&**source),
+            Self::Wrapped { source, .. } => Some(source),
+            Self::MemoryIo(error) => Some(error),
+            _ => None,
+        }
+    }
+}
 
 impl Display for ErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::TrapCode(error) => Display::fmt(error, f),
             Self::I32ExitStatus(status) => writeln!(f, "Exited with i32 exit status {status}"),
+            Self::Exit(status) => write!(f, "Exited with exit status {status}"),
             Self::Message(message) => Display::fmt(message, f),
             Self::Host(error) => Display::fmt(error, f),
+            Self::HostTrap(code) => write!(f, "host trap {code}"),
             Self::Global(error) => Display::fmt(error, f),
+            Self::Value(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
+            #[cfg(feature = "std")]
+            Self::MemoryIo(error) => Display::fmt(error, f),
             Self::Table(error) => Display::fmt(error, f),
+            Self::Grow(error) => Display::fmt(error, f),
             Self::Linker(error) => Display::fmt(error, f),
             Self::Func(error) => Display::fmt(error, f),
+            Self::BatchCall(error) => Display::fmt(error, f),
             Self::Instantiation(error) => Display::fmt(error, f),
+            Self::EngineMismatch(error) => Display::fmt(error, f),
+            Self::Export(error) => Display::fmt(error, f),
             Self::Fuel(error) => Display::fmt(error, f),
+            Self::Snapshot(error) => Display::fmt(error, f),
             Self::Read(error) => Display::fmt(error, f),
             Self::Wasm(error) => Display::fmt(error, f),
+            #[cfg(feature = "wat")]
+            Self::Wat(error) => Display::fmt(error, f),
             Self::Translation(error) => Display::fmt(error, f),
+            Self::Bytecode(error) => Display::fmt(error, f),
+            Self::LazyCompilationFailed { func_index, source } => {
+                write!(
+                    f,
+                    "failed to lazily compile function {func_index}: {source}"
+                )
+            }
+            Self::Wrapped { message, source } => write!(f, "{message}: {source}"),
         }
     }
 }
@@ -251,15 +715,30 @@ macro_rules! impl_from {
 impl_from! {
     impl From<TrapCode> for Error::TrapCode;
     impl From<GlobalError> for Error::Global;
+    impl From<ValueError> for Error::Value;
     impl From<MemoryError> for Error::Memory;
     impl From<TableError> for Error::Table;
+    impl From<GrowError> for Error::Grow;
     impl From<LinkerError> for Error::Linker;
     impl From<InstantiationError> for Error::Instantiation;
+    impl From<EngineMismatchError> for Error::EngineMismatch;
+    impl From<ExportError> for Error::Export;
     impl From<TranslationError> for Error::Translation;
+    impl From<BytecodeError> for Error::Bytecode;
     impl From<WasmError> for Error::Wasm;
     impl From<ReadError> for Error::Read;
     impl From<FuelError> for Error::Fuel;
+    impl From<SnapshotError> for Error::Snapshot;
     impl From<FuncError> for Error::Func;
+    impl From<BatchCallError> for Error::BatchCall;
+}
+#[cfg(feature = "std")]
+impl_from! {
+    impl From<MemoryIoError> for Error::MemoryIo;
+}
+#[cfg(feature = "wat")]
+impl_from! {
+    impl From<WatError> for Error::Wat;
 }
 
 /// An error that can occur upon `memory.grow` or `table.grow`.
@@ -268,7 +747,7 @@ pub enum EntityGrowError {
     /// Usually a [`TrapCode::OutOfFuel`] trap.
     TrapCode(TrapCode),
     /// Encountered when `memory.grow` or `table.grow` fails.
-    InvalidGrow,
+    InvalidGrow(GrowError),
 }
 
 impl EntityGrowError {
@@ -282,3 +761,38 @@ impl From<TrapCode> for EntityGrowError {
         Self::TrapCode(trap_code)
     }
 }
+
+/// The reason why growing a [`Memory`] or [`Table`] failed.
+///
+/// [`Memory`]: crate::Memory
+/// [`Table`]: crate::Table
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GrowError {
+    /// The growth exceeds the maximum of the entity type.
+    Maximum,
+    /// The growth has been denied by the [`ResourceLimiter`].
+    ///
+    /// [`ResourceLimiter`]: crate::ResourceLimiter
+    Limiter,
+    /// The allocation of the grown entity failed.
+    Allocation,
+    /// There was not enough fuel for the growth.
+    ///
+    /// # Note
+    ///
+    /// This is only encountered by growth initiated by Wasm executions with fuel metering.
+    OutOfFuel,
+}
+
+impl Display for GrowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            Self::Maximum => "growth exceeds the maximum of the entity type",
+            Self::Limiter => "growth denied by the resource limiter",
+            Self::Allocation => "allocation failed upon growth",
+            Self::OutOfFuel => "out of fuel upon growth",
+        };
+        f.write_str(message)
+    }
+}
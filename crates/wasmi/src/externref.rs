@@ -182,6 +182,11 @@ impl ExternRef {
         self.inner.is_none()
     }
 
+    /// Returns the underlying [`ExternObject`] if [`ExternRef`] is not `null`.
+    pub(crate) fn object(&self) -> Option<&ExternObject> {
+        self.inner.as_ref()
+    }
+
     /// Returns a shared reference to the underlying data for this [`ExternRef`].
     ///
     /// # Panics
@@ -0,0 +1,297 @@
+//! The `wasmi.batch_call` builtin function that dispatches a batch of host function calls.
+
+use super::{Caller, FuncEntity, TrampolineEntity};
+use crate::{
+    core::{TrapCode, UntypedValue, ValueType},
+    engine::{FuelCosts, FuncParams},
+    AsContext,
+    AsContextMut,
+    Error,
+    Instance,
+    Memory,
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{
+    fmt::{self, Display},
+    ops::Range,
+};
+
+/// Errors that may occur upon validating the records of a `wasmi.batch_call`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BatchCallError {
+    /// The caller of `wasmi.batch_call` has no linear memory.
+    ///
+    /// # Note
+    ///
+    /// This also occurs if `wasmi.batch_call` is not called by a Wasm function.
+    MissingMemory,
+    /// A record refers to an import that is not a host function.
+    NotAHostFunc {
+        /// The import index of the record.
+        import_index: u32,
+    },
+    /// A record refers to a host function with reference typed parameters or results.
+    UnsupportedSignature {
+        /// The import index of the record.
+        import_index: u32,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BatchCallError {}
+
+impl Display for BatchCallError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingMemory => {
+                write!(f, "batch call requires the linear memory of its caller")
+            }
+            Self::NotAHostFunc { import_index } => {
+                write!(
+                    f,
+                    "batch call record refers to import {import_index} which is not a host function"
+                )
+            }
+            Self::UnsupportedSignature { import_index } => {
+                write!(
+                    f,
+                    "batch call record refers to import {import_index} with reference typed parameters or results"
+                )
+            }
+        }
+    }
+}
+
+/// The `wasmi.batch_call` builtin function of type `[i32 i32 i32] -> []`.
+///
+/// Read [`Config::builtin_batch_call`] for the format of its records.
+///
+/// [`Config::builtin_batch_call`]: crate::Config::builtin_batch_call
+pub(crate) struct BatchCall;
+
+/// A host function called by the records of a [`BatchCall`].
+struct BatchTarget<T> {
+    /// The trampoline of the host function.
+    trampoline: TrampolineEntity<T>,
+    /// The number of parameters of the host function.
+    len_params: usize,
+    /// The number of results of the host function.
+    len_results: usize,
+}
+
+/// The validated records of a [`BatchCall`].
+struct BatchRecords<T> {
+    /// The distinct host functions called by the records.
+    targets: Vec<BatchTarget<T>>,
+    /// The index into `targets` of the host function called by each record.
+    calls: Vec<usize>,
+    /// The parameters of all records in order.
+    params: Vec<UntypedValue>,
+    /// The size of the results of all records in bytes.
+    results_size: usize,
+}
+
+impl BatchCall {
+    /// The name of the builtin batch call function within the `"wasmi"` module.
+    pub const NAME: &'static str = "batch_call";
+
+    /// The size of the import index of a record in bytes.
+    const INDEX_SIZE: usize = 4;
+
+    /// The size of a parameter or result value in bytes.
+    const VALUE_SIZE: usize = 8;
+
+    /// Dispatches the `len` records at `records` and writes their results to `results`.
+    ///
+    /// # Note
+    ///
+    /// All records are validated before the first host function is called.
+    /// Therefore host functions cannot alter the records of the ongoing batch.
+    ///
+    /// # Errors
+    ///
+    /// - If the records or results are out of bounds of the linear memory of the `caller`.
+    /// - If any record is invalid.
+    /// - If there is not enough fuel to dispatch the next host function call.
+    /// - If any of the called host functions returns an error.
+    ///   The remaining records are not dispatched in this case.
+    pub fn call<T>(
+        mut caller: Caller<T>,
+        records: u32,
+        len: u32,
+        results: u32,
+    ) -> Result<(), Error> {
+        let (instance, memory) = Self::resolve_memory(&caller)?;
+        let BatchRecords {
+            targets,
+            calls,
+            params,
+            results_size,
+        } = Self::validate(&caller, instance, memory, records, len)?;
+        let mut results = Self::range(results as usize, results_size, memory.data(&caller))?.start;
+        let mut params = params.into_iter();
+        let mut buffer = Vec::new();
+        // Note: The called host functions must not observe the continuation token of a
+        //       re-entered batch call. The executor restores it once the batch call returns.
+        #[cfg(feature = "resumable")]
+        caller
+            .as_context_mut()
+            .store
+            .inner
+            .replace_host_continuation(None);
+        for target in calls {
+            let BatchTarget {
+                trampoline,
+                len_params,
+                len_results,
+            } = &targets[target];
+            let store = &mut caller.as_context_mut().store.inner;
+            store.fuel_mut().consume_fuel_if(FuelCosts::call)?;
+            store.count_host_call();
+            buffer.clear();
+            buffer.extend(params.by_ref().take(*len_params));
+            buffer.resize((*len_params).max(*len_results), UntypedValue::default());
+            trampoline.call(
+                &mut caller,
+                Some(&instance),
+                FuncParams::new(&mut buffer, *len_params, *len_results),
+            )?;
+            // Note: host functions may grow the linear memory but never shrink it
+            //       so the results are still in bounds after the call.
+            let data = memory.data_mut(&mut caller);
+            for value in &buffer[..*len_results] {
+                data[results..][..Self::VALUE_SIZE].copy_from_slice(&value.to_bits().to_le_bytes());
+                results += Self::VALUE_SIZE;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the [`Instance`] of the `caller` and its default linear [`Memory`].
+    ///
+    /// # Errors
+    ///
+    /// If the `caller` is not a Wasm function or its [`Instance`] has no linear [`Memory`].
+    fn resolve_memory<T>(caller: &Caller<T>) -> Result<(Instance, Memory), BatchCallError> {
+        caller
+            .instance()
+            .and_then(|instance| {
+                let memory = caller
+                    .as_context()
+                    .store
+                    .inner
+                    .resolve_instance(&instance)
+                    .get_memory(0)?;
+                Some((instance, memory))
+            })
+            .ok_or(BatchCallError::MissingMemory)
+    }
+
+    /// Validates the `len` records at `records` in the `memory` of the `instance`.
+    ///
+    /// # Errors
+    ///
+    /// - If the records are out of bounds of the linear `memory`.
+    /// - If any record is invalid.
+    fn validate<T>(
+        caller: &Caller<T>,
+        instance: Instance,
+        memory: Memory,
+        records: u32,
+        len: u32,
+    ) -> Result<BatchRecords<T>, Error> {
+        let data = memory.data(caller);
+        let mut targets = Vec::new();
+        let mut targets_by_index = BTreeMap::new();
+        let mut calls = Vec::new();
+        let mut params = Vec::new();
+        let mut results_size = 0_usize;
+        let mut offset = records as usize;
+        for _ in 0..len {
+            let index = Self::range(offset, Self::INDEX_SIZE, data)?;
+            let import_index = u32::from_le_bytes(data[index.clone()].try_into().unwrap());
+            let target = match targets_by_index.get(&import_index) {
+                Some(&target) => target,
+                None => {
+                    targets.push(Self::resolve_target(caller, instance, import_index)?);
+                    targets_by_index.insert(import_index, targets.len() - 1);
+                    targets.len() - 1
+                }
+            };
+            let BatchTarget {
+                len_params,
+                len_results,
+                ..
+            } = targets[target];
+            let values = Self::range(index.end, len_params * Self::VALUE_SIZE, data)?;
+            params.extend(
+                data[values.clone()]
+                    .chunks_exact(Self::VALUE_SIZE)
+                    .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+                    .map(UntypedValue::from),
+            );
+            calls.push(target);
+            results_size += len_results * Self::VALUE_SIZE;
+            offset = values.end;
+        }
+        Ok(BatchRecords {
+            targets,
+            calls,
+            params,
+            results_size,
+        })
+    }
+
+    /// Resolves the [`BatchTarget`] of the import at `import_index` of the `instance`.
+    ///
+    /// # Errors
+    ///
+    /// - If the import is not a host function.
+    /// - If the host function has reference typed parameters or results.
+    fn resolve_target<T>(
+        caller: &Caller<T>,
+        instance: Instance,
+        import_index: u32,
+    ) -> Result<BatchTarget<T>, BatchCallError> {
+        let store = caller.as_context().store;
+        let func = store
+            .inner
+            .resolve_instance(&instance)
+            .get_func(import_index);
+        let Some(FuncEntity::Host(host_func)) = func.map(|func| store.inner.resolve_func(&func))
+        else {
+            return Err(BatchCallError::NotAHostFunc { import_index });
+        };
+        // Note: reference typed values cannot be forged from linear memory.
+        let (len_params, len_results) = store
+            .inner
+            .resolve_func_type_with(host_func.ty_dedup(), |func_type| {
+                let (params, results) = func_type.params_results();
+                params
+                    .iter()
+                    .chain(results)
+                    .all(ValueType::is_num)
+                    .then_some((params.len(), results.len()))
+            })
+            .ok_or(BatchCallError::UnsupportedSignature { import_index })?;
+        Ok(BatchTarget {
+            trampoline: store.resolve_trampoline(host_func.trampoline()).clone(),
+            len_params,
+            len_results,
+        })
+    }
+
+    /// Returns the range of `len` bytes at `offset` if it is in bounds of `data`.
+    ///
+    /// # Errors
+    ///
+    /// If the range is out of bounds of `data`.
+    fn range(offset: usize, len: usize, data: &[u8]) -> Result<Range<usize>, TrapCode> {
+        offset
+            .checked_add(len)
+            .filter(|&end| end <= data.len())
+            .map(|end| offset..end)
+            .ok_or(TrapCode::MemoryOutOfBounds)
+    }
+}
@@ -1,5 +1,14 @@
 use super::super::{AsContext, AsContextMut, StoreContext, StoreContextMut};
-use crate::{store::FuelError, Engine, Extern, Instance};
+use crate::{
+    global::GlobalError,
+    store::{FuelError, StoreInner},
+    Engine,
+    Extern,
+    Global,
+    Instance,
+    Memory,
+    Value,
+};
 
 /// Represents the caller’s context when creating a host function via [`Func::wrap`].
 ///
@@ -25,6 +34,21 @@ impl<'a, T> Caller<'a, T> {
         }
     }
 
+    /// Reborrows the [`Caller`] for a shorter lifetime.
+    ///
+    /// This is used to call a host function multiple times with the same [`Caller`].
+    pub(crate) fn reborrow(&mut self) -> Caller<'_, T> {
+        Caller {
+            ctx: self.ctx.as_context_mut(),
+            instance: self.instance,
+        }
+    }
+
+    /// Returns the module instance associated to the call if any.
+    pub(crate) fn instance(&self) -> Option<Instance> {
+        self.instance
+    }
+
     /// Queries the caller for an exported definition identifier by `name`.
     ///
     /// Returns `None` if there is no associated [`Instance`] of the caller
@@ -44,11 +68,46 @@ impl<'a, T> Caller<'a, T> {
         self.ctx.store.data_mut()
     }
 
+    /// Returns an exclusive reference to the user provided host data and the bytes of `memory`.
+    ///
+    /// # Panics
+    ///
+    /// If `memory` does not originate from the [`Store`](crate::Store) of the [`Caller`].
+    pub fn data_and_memory_mut(&mut self, memory: Memory) -> (&mut T, &mut [u8]) {
+        let (bytes, data) = memory.data_and_store_mut(self.ctx.as_context_mut());
+        (data, bytes)
+    }
+
+    /// Splits the [`Caller`] into the user provided host data and the entities of its store.
+    ///
+    /// This allows to access both at the same time, for example
+    /// to copy bytes of a linear memory into a buffer of the host data.
+    pub fn split(&mut self) -> (&mut T, CallerEntities<'_>) {
+        let (inner, data) = self.ctx.store.inner_and_data_mut();
+        let entities = CallerEntities {
+            inner,
+            instance: self.instance,
+        };
+        (data, entities)
+    }
+
     /// Returns a shared reference to the used [`Engine`].
     pub fn engine(&self) -> &Engine {
         self.ctx.store.engine()
     }
 
+    /// Returns the continuation token of the [`HostYield`] that paused this host function.
+    ///
+    /// Returns `None` unless the host function has been re-entered via
+    /// [`ResumableInvocation::resume_with`] after yielding a [`HostYield`].
+    ///
+    /// [`HostYield`]: crate::HostYield
+    /// [`ResumableInvocation::resume_with`]: crate::ResumableInvocation::resume_with
+    #[cfg(feature = "resumable")]
+    pub fn continuation(&self) -> Option<u64> {
+        self.ctx.store.inner.host_continuation()
+    }
+
     /// Adds `delta` quantity of fuel to the remaining fuel.
     ///
     /// # Panics
@@ -84,6 +143,71 @@ impl<'a, T> Caller<'a, T> {
     pub fn consume_fuel(&mut self, delta: u64) -> Result<u64, FuelError> {
         self.ctx.store.consume_fuel(delta)
     }
+
+    /// Fills `bytes` with random bytes drawn from the entropy source of the [`Store`](crate::Store).
+    ///
+    /// Returns `false` and leaves `bytes` untouched if no entropy source has been
+    /// installed via [`Store::set_entropy_source`](crate::Store::set_entropy_source).
+    pub fn fill_entropy(&mut self, bytes: &mut [u8]) -> bool {
+        self.ctx.store.fill_entropy(bytes)
+    }
+}
+
+/// The entities of the [`Store`] of a [`Caller`] borrowed separately from its host data.
+///
+/// Created via [`Caller::split`].
+///
+/// # Panics
+///
+/// All methods taking an entity panic if the entity does not originate from the [`Store`].
+///
+/// [`Store`]: crate::Store
+pub struct CallerEntities<'a> {
+    /// The entities of the [`Store`](crate::Store).
+    inner: &'a mut StoreInner,
+    /// The module instance associated to the call if any.
+    instance: Option<Instance>,
+}
+
+impl<'a> CallerEntities<'a> {
+    /// Returns a shared reference to the used [`Engine`].
+    pub fn engine(&self) -> &Engine {
+        self.inner.engine()
+    }
+
+    /// Queries the caller for an exported definition identifier by `name`.
+    ///
+    /// Returns `None` if there is no associated [`Instance`] of the caller
+    /// or if the caller does not provide an export under the name `name`.
+    pub fn get_export(&self, name: &str) -> Option<Extern> {
+        self.instance
+            .and_then(|instance| self.inner.resolve_instance(&instance).get_export(name))
+    }
+
+    /// Returns a shared slice to the bytes underlying the `memory`.
+    pub fn memory_data(&self, memory: Memory) -> &[u8] {
+        self.inner.resolve_memory(&memory).data()
+    }
+
+    /// Returns an exclusive slice to the bytes underlying the `memory`.
+    pub fn memory_data_mut(&mut self, memory: Memory) -> &mut [u8] {
+        self.inner.resolve_memory_mut(&memory).data_mut()
+    }
+
+    /// Returns the current value of the `global` variable.
+    pub fn global_get(&self, global: Global) -> Value {
+        self.inner.resolve_global(&global).get()
+    }
+
+    /// Sets a new value to the `global` variable.
+    ///
+    /// # Errors
+    ///
+    /// - If the `global` variable is immutable.
+    /// - If there is a type mismatch between the `global` variable and the new value.
+    pub fn global_set(&mut self, global: Global, new_value: Value) -> Result<(), GlobalError> {
+        self.inner.resolve_global_mut(&global).set(new_value)
+    }
 }
 
 impl<T> AsContext for Caller<'_, T> {
@@ -1,3 +1,4 @@
+use crate::core::ValueType;
 use core::{fmt, fmt::Display};
 
 /// Errors that can occur upon type checking function signatures.
@@ -13,6 +14,50 @@ pub enum FuncError {
     MismatchingResultType,
     /// Specified an incorrect number of results.
     MismatchingResultLen,
+    /// A host function created via [`Func::new`] wrote a result of the wrong type.
+    ///
+    /// [`Func::new`]: crate::Func::new
+    MismatchingHostResultType {
+        /// The index of the mismatching result.
+        index: usize,
+        /// The result type declared by the host function signature.
+        expected: ValueType,
+        /// The type of the result written by the host function.
+        actual: ValueType,
+    },
+    /// Tried to call a function of a [`Store`] that is already executing.
+    ///
+    /// Host functions must call back into the [`Store`] via their [`Caller`].
+    ///
+    /// [`Store`]: crate::Store
+    /// [`Caller`]: crate::Caller
+    StoreAlreadyExecuting,
+    /// A function index does not refer to an internal function of a [`Module`].
+    ///
+    /// [`Module`]: crate::Module
+    NotAnInternalFunc {
+        /// The function index.
+        func_index: u32,
+    },
+    /// A function does not belong to the [`Engine`] of the [`Store`] it is called with.
+    ///
+    /// # Note
+    ///
+    /// This occurs for Wasm functions of a [`Module`] compiled by another [`Engine`].
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Store`]: crate::Store
+    /// [`Module`]: crate::Module
+    EngineMismatch,
+    /// Tried to exclusively access an [`Engine`] that executes Wasm on the current thread.
+    ///
+    /// # Note
+    ///
+    /// This occurs if a host function calls an [`Engine`] API that would otherwise
+    /// wait for the Wasm execution that called the host function to finish.
+    ///
+    /// [`Engine`]: crate::Engine
+    EngineAlreadyExecuting,
 }
 
 impl Display for FuncError {
@@ -33,6 +78,34 @@ impl Display for FuncError {
             FuncError::MismatchingResultLen => {
                 write!(f, "encountered an incorrect number of results")
             }
+            FuncError::MismatchingHostResultType {
+                index,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "host function wrote result {index} of type {actual:?} but expected {expected:?}"
+                )
+            }
+            FuncError::StoreAlreadyExecuting => {
+                write!(f, "store is already executing; use Caller to re-enter")
+            }
+            FuncError::NotAnInternalFunc { func_index } => {
+                write!(
+                    f,
+                    "function index {func_index} does not refer to an internal function"
+                )
+            }
+            FuncError::EngineMismatch => {
+                write!(f, "function does not belong to the engine of the store")
+            }
+            FuncError::EngineAlreadyExecuting => {
+                write!(
+                    f,
+                    "engine cannot be accessed exclusively while it executes Wasm on the current thread"
+                )
+            }
         }
     }
 }
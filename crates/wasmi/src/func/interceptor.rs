@@ -0,0 +1,141 @@
+use super::{Caller, FuncType, TrampolineFn};
+use crate::{
+    engine::{FuncFinished, FuncParams},
+    value::WithType,
+    Error,
+    Value,
+};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::{cmp, fmt, fmt::Debug};
+use wasmi_core::UntypedValue;
+
+/// Information about an intercepted host function.
+///
+/// This is handed to the interceptor of a [`Linker`] for every intercepted host function call.
+///
+/// [`Linker`]: crate::Linker
+#[derive(Debug, Clone)]
+pub struct HostFuncInfo {
+    /// The module name under which the host function is defined.
+    module: String,
+    /// The name of the host function within its module.
+    name: String,
+    /// The function type of the host function.
+    ty: FuncType,
+}
+
+impl HostFuncInfo {
+    /// Creates a new [`HostFuncInfo`].
+    pub(crate) fn new(module: &str, name: &str, ty: FuncType) -> Self {
+        Self {
+            module: module.into(),
+            name: name.into(),
+            ty,
+        }
+    }
+
+    /// Returns the module name under which the host function is defined.
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    /// Returns the name of the host function within its module.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the [`FuncType`] of the host function.
+    pub fn ty(&self) -> &FuncType {
+        &self.ty
+    }
+}
+
+/// The intercepted host function handed to the interceptor of a [`Linker`].
+///
+/// See [`Linker::set_interceptor`] for more information.
+///
+/// [`Linker`]: crate::Linker
+/// [`Linker::set_interceptor`]: crate::Linker::set_interceptor
+///
+/// # Note
+///
+/// Calling it executes the intercepted host function with the given
+/// parameters and writes its results into the given results buffer.
+pub type HostFuncNext<'a> = dyn FnMut(&[Value], &mut [Value]) -> Result<(), Error> + 'a;
+
+/// A middleware that is invoked around host function calls.
+///
+/// It is called with information about the intercepted host function, its parameters,
+/// the intercepted host function itself and the results buffer. An interceptor
+/// may deny the host function call by returning an error without calling the
+/// intercepted host function which then traps the calling Wasm function.
+pub(crate) type HostInterceptor = dyn Fn(&HostFuncInfo, &[Value], &mut HostFuncNext, &mut [Value]) -> Result<(), Error>
+    + Send
+    + Sync
+    + 'static;
+
+/// An interceptor together with the host function it intercepts.
+#[derive(Clone)]
+pub struct Interception {
+    /// Information about the intercepted host function.
+    info: HostFuncInfo,
+    /// The interceptor called around the intercepted host function.
+    interceptor: Arc<HostInterceptor>,
+    /// The default initialized parameters and results of the intercepted host function.
+    params_results: Box<[Value]>,
+}
+
+impl Debug for Interception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interception")
+            .field("info", &self.info)
+            .finish()
+    }
+}
+
+impl Interception {
+    /// Creates a new [`Interception`] for the host function described by `info`.
+    pub fn new(info: HostFuncInfo, interceptor: Arc<HostInterceptor>) -> Self {
+        let (param_types, result_types) = info.ty().params_results();
+        let params_iter = param_types.iter().copied().map(Value::default);
+        let results_iter = result_types.iter().copied().map(Value::default);
+        let params_results = params_iter.chain(results_iter).collect();
+        Self {
+            info,
+            interceptor,
+            params_results,
+        }
+    }
+
+    /// Calls the interceptor around the host function `func` with the given inputs.
+    pub fn call<T>(
+        &self,
+        mut caller: Caller<T>,
+        args: FuncParams,
+        func: &TrampolineFn<T>,
+    ) -> Result<FuncFinished, Error> {
+        let ty = self.info.ty();
+        let len_params = ty.params().len();
+        let len_results = ty.results().len();
+        let mut params_results = self.params_results.clone();
+        let (params, results) = params_results.split_at_mut(len_params);
+        let func_results = args.decode_params_into_slice(params).unwrap();
+        let mut next = |params: &[Value], results: &mut [Value]| -> Result<(), Error> {
+            ty.match_params(params)?;
+            ty.match_results(results, false)?;
+            let mut buffer: Vec<UntypedValue> =
+                params.iter().cloned().map(UntypedValue::from).collect();
+            buffer.resize(cmp::max(len_params, len_results), UntypedValue::default());
+            func(
+                caller.reborrow(),
+                FuncParams::new(&mut buffer, len_params, len_results),
+            )?;
+            for ((result, value), ty) in results.iter_mut().zip(&buffer).zip(ty.results()) {
+                *result = value.with_type(*ty);
+            }
+            Ok(())
+        };
+        (self.interceptor)(&self.info, params, &mut next, results)?;
+        Ok(func_results.encode_results_from_slice(results).unwrap())
+    }
+}
@@ -1,19 +1,26 @@
+mod batch_call;
 mod caller;
 mod error;
 mod func_type;
 mod funcref;
+mod interceptor;
 mod into_func;
 mod typed_func;
 
-pub(crate) use self::typed_func::CallResultsTuple;
+use self::interceptor::Interception;
 pub use self::{
-    caller::Caller,
+    batch_call::BatchCallError,
+    caller::{Caller, CallerEntities},
     error::FuncError,
     func_type::FuncType,
     funcref::FuncRef,
+    interceptor::{HostFuncInfo, HostFuncNext},
     into_func::{IntoFunc, WasmRet, WasmType, WasmTypeList},
     typed_func::{TypedFunc, WasmParams, WasmResults},
 };
+pub(crate) use self::{batch_call::BatchCall, interceptor::HostInterceptor};
+#[cfg(feature = "resumable")]
+pub(crate) use self::typed_func::CallResultsTuple;
 use super::{
     engine::{CompiledFunc, DedupFuncType, FuncFinished, FuncParams},
     AsContext,
@@ -22,13 +29,30 @@ use super::{
     StoreContext,
     Stored,
 };
-use crate::{engine::ResumableCall, Engine, Error, Value};
+use crate::{
+    core::{TrapCode, UntypedValue},
+    CallLimits,
+    Engine,
+    Error,
+    Value,
+};
+#[cfg(feature = "resumable")]
+use crate::engine::ResumableCall;
 use alloc::{boxed::Box, sync::Arc};
 use core::{fmt, fmt::Debug, num::NonZeroU32};
 use wasmi_arena::ArenaIndex;
 
+/// A raw host function operating directly on the registers of its call.
+///
+/// The host function is called with a pointer to the buffer holding its `len`
+/// parameters and results.
+///
+/// For its safety contract see [`Linker::func_wrap_raw`](crate::Linker::func_wrap_raw).
+pub type RawHostFunc =
+    unsafe fn(params_results: *mut UntypedValue, len: usize) -> Result<(), TrapCode>;
+
 /// A raw index to a function entity.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FuncIdx(NonZeroU32);
 
 impl ArenaIndex for FuncIdx {
@@ -106,12 +130,31 @@ pub struct HostFuncEntity {
     ty: DedupFuncType,
     /// A reference to the trampoline of the host function.
     func: Trampoline,
+    /// The raw host function if any.
+    ///
+    /// Raw host functions are called directly by the executor when called from Wasm.
+    raw: Option<RawHostFunc>,
 }
 
 impl HostFuncEntity {
     /// Creates a new [`HostFuncEntity`].
     pub fn new(ty: DedupFuncType, func: Trampoline) -> Self {
-        Self { ty, func }
+        Self {
+            ty,
+            func,
+            raw: None,
+        }
+    }
+
+    /// Sets the raw host function of the [`HostFuncEntity`].
+    pub fn with_raw(mut self, raw: Option<RawHostFunc>) -> Self {
+        self.raw = raw;
+        self
+    }
+
+    /// Returns the raw host function if any.
+    pub fn raw(&self) -> Option<RawHostFunc> {
+        self.raw
     }
 
     /// Returns the signature of the host function.
@@ -172,12 +215,18 @@ impl WasmFuncEntity {
     }
 }
 
+/// The maximum number of parameters and results of a host function created via [`Func::new`]
+/// that are buffered on the stack upon invocation.
+const MAX_INLINE_VALUES: usize = 16;
+
 /// A host function instance.
 pub struct HostFuncTrampolineEntity<T> {
     /// The type of the associated host function.
     ty: DedupFuncType,
     /// The trampoline of the associated host function.
     trampoline: TrampolineEntity<T>,
+    /// The raw host function if the host function has been defined as such.
+    raw: Option<RawHostFunc>,
 }
 
 impl<T> Clone for HostFuncTrampolineEntity<T> {
@@ -185,6 +234,7 @@ impl<T> Clone for HostFuncTrampolineEntity<T> {
         Self {
             ty: self.ty,
             trampoline: self.trampoline.clone(),
+            raw: self.raw,
         }
     }
 }
@@ -214,25 +264,83 @@ impl<T> HostFuncTrampolineEntity<T> {
         let len_params = ty.params().len();
         let params_results: Box<[Value]> = params_iter.chain(results_iter).collect();
         let trampoline = <TrampolineEntity<T>>::new(move |caller, args| {
-            // We are required to clone the buffer because we are operating within a `Fn`.
-            // This way the trampoline closure only has to own a single slice buffer.
-            // Note: An alternative solution is to use interior mutability but that solution
-            //       comes with its own downsides.
-            let mut params_results = params_results.clone();
-            let (params, results) = params_results.split_at_mut(len_params);
+            // We are required to copy the buffer because we are operating within a `Fn`.
+            // For the common case of few parameters and results the copy is stored on
+            // the stack so that invoking the trampoline does not allocate.
+            let mut inline;
+            let mut boxed;
+            let buffer = match params_results.len() {
+                len if len <= MAX_INLINE_VALUES => {
+                    inline = core::array::from_fn::<_, MAX_INLINE_VALUES, _>(|_| Value::I32(0));
+                    inline[..len].clone_from_slice(&params_results);
+                    &mut inline[..len]
+                }
+                _ => {
+                    boxed = params_results.clone();
+                    &mut boxed[..]
+                }
+            };
+            let (params, results) = buffer.split_at_mut(len_params);
             let func_results = args.decode_params_into_slice(params).unwrap();
             func(caller, params, results)?;
+            let expected = &params_results[len_params..];
+            for (index, (result, expected)) in results.iter().zip(expected).enumerate() {
+                if result.ty() != expected.ty() {
+                    return Err(Error::from(FuncError::MismatchingHostResultType {
+                        index,
+                        expected: expected.ty(),
+                        actual: result.ty(),
+                    }));
+                }
+            }
             Ok(func_results.encode_results_from_slice(results).unwrap())
         });
         let ty = engine.alloc_func_type(ty.clone());
-        Self { ty, trampoline }
+        Self {
+            ty,
+            trampoline,
+            raw: None,
+        }
     }
 
     /// Creates a new host function trampoline from the given statically typed closure.
     pub fn wrap<Params, Results>(engine: &Engine, func: impl IntoFunc<T, Params, Results>) -> Self {
         let (signature, trampoline) = func.into_func();
         let ty = engine.alloc_func_type(signature);
-        Self { ty, trampoline }
+        Self {
+            ty,
+            trampoline,
+            raw: None,
+        }
+    }
+
+    /// Creates a new host function trampoline from the given raw host function.
+    ///
+    /// # Safety
+    ///
+    /// The caller must uphold the safety contract of [`Linker::func_wrap_raw`].
+    ///
+    /// [`Linker::func_wrap_raw`]: crate::Linker::func_wrap_raw
+    ///
+    /// # Note
+    ///
+    /// The trampoline is only used if the host function is not called from Wasm,
+    /// for example when called from the host side or when intercepted.
+    pub unsafe fn new_raw(engine: &Engine, ty: FuncType, func: RawHostFunc) -> Self {
+        let trampoline = <TrampolineEntity<T>>::new(move |_caller, args| {
+            let (params_results, finished) = args.into_raw();
+            // Safety: The buffer holds the parameters and results of the call as described
+            //         by the function type and is exclusively borrowed during the call.
+            //         The raw host function itself is trusted by the unsafe constructor.
+            unsafe { func(params_results.as_mut_ptr(), params_results.len()) }?;
+            Ok(finished)
+        });
+        let ty = engine.alloc_func_type(ty);
+        Self {
+            ty,
+            trampoline,
+            raw: Some(func),
+        }
     }
 
     /// Returns the signature of the host function.
@@ -244,6 +352,26 @@ impl<T> HostFuncTrampolineEntity<T> {
     pub fn trampoline(&self) -> &TrampolineEntity<T> {
         &self.trampoline
     }
+
+    /// Returns the raw host function if any.
+    pub fn raw(&self) -> Option<RawHostFunc> {
+        self.raw
+    }
+
+    /// Returns a copy of the host function that is intercepted by `interceptor`.
+    ///
+    /// The `interceptor` is called with `info` describing the host function.
+    ///
+    /// # Note
+    ///
+    /// Intercepted raw host functions are always called through their trampoline.
+    pub fn intercept(&self, info: HostFuncInfo, interceptor: Arc<HostInterceptor>) -> Self {
+        Self {
+            ty: self.ty,
+            trampoline: self.trampoline.intercept(info, interceptor),
+            raw: None,
+        }
+    }
 }
 
 type TrampolineFn<T> =
@@ -251,6 +379,8 @@ type TrampolineFn<T> =
 
 pub struct TrampolineEntity<T> {
     closure: Arc<TrampolineFn<T>>,
+    /// The optional interceptor called around `closure`.
+    interception: Option<Arc<Interception>>,
 }
 
 impl<T> Debug for TrampolineEntity<T> {
@@ -267,6 +397,17 @@ impl<T> TrampolineEntity<T> {
     {
         Self {
             closure: Arc::new(trampoline),
+            interception: None,
+        }
+    }
+
+    /// Returns a [`TrampolineEntity`] that calls `interceptor` around the host function.
+    ///
+    /// The `info` describes the host function of `self`.
+    pub fn intercept(&self, info: HostFuncInfo, interceptor: Arc<HostInterceptor>) -> Self {
+        Self {
+            closure: self.closure.clone(),
+            interception: Some(Arc::new(Interception::new(info, interceptor))),
         }
     }
 
@@ -280,7 +421,10 @@ impl<T> TrampolineEntity<T> {
         params: FuncParams,
     ) -> Result<FuncFinished, Error> {
         let caller = <Caller<T>>::new(&mut ctx, instance);
-        (self.closure)(caller, params)
+        match &self.interception {
+            Some(interception) => interception.call(caller, params, &*self.closure),
+            None => (self.closure)(caller, params),
+        }
     }
 }
 
@@ -288,12 +432,26 @@ impl<T> Clone for TrampolineEntity<T> {
     fn clone(&self) -> Self {
         Self {
             closure: self.closure.clone(),
+            interception: self.interception.clone(),
         }
     }
 }
 
 /// A Wasm or host function reference.
-#[derive(Debug, Copy, Clone)]
+///
+/// # Identity
+///
+/// Two [`Func`] compare equal and have the same hash if and only if they refer
+/// to the same function entity of the same [`Store`](crate::Store).
+///
+/// - A Wasm function entity is created once per [`Instance`] and function index
+///   upon instantiation. Therefore the identity of a Wasm [`Func`] does not depend
+///   on whether the function has already been compiled, e.g. when using
+///   [`CompilationMode::Lazy`](crate::CompilationMode::Lazy).
+/// - All [`Func`] obtained for the same function are equal, no matter if they
+///   originate from `ref.func`, a table element, an export or an import of another [`Instance`].
+/// - Distinct host functions are never equal, even if they wrap the same closure.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct Func(Stored<FuncIdx>);
 
@@ -323,11 +481,10 @@ impl Func {
     ///
     /// - The given [`FuncType`] `ty` must match the parameters and results otherwise
     ///   the resulting host [`Func`] might trap during execution.
-    /// - It is the responsibility of the caller of [`Func::new`] to guarantee that
-    ///   the correct amount and types of results are written into the results buffer
-    ///   from the `func` closure. If an incorrect amount of results or types of results
-    ///   is written into the buffer then the remaining computation may fail in unexpected
-    ///   ways. This footgun can be avoided by using the typed [`Func::wrap`] method instead.
+    /// - The `func` closure is provided a results buffer prefilled with default values
+    ///   of the result types of `ty`. If `func` writes a result of the wrong type into
+    ///   the buffer the call returns an [`Error`] identifying the index of the result.
+    ///   This check can be avoided by using the typed [`Func::wrap`] method instead.
     /// - Prefer using [`Func::wrap`] over this method if possible since [`Func`] instances
     ///   created using this constructor have runtime overhead for every invocation that
     ///   can be avoided by using [`Func::wrap`].
@@ -395,6 +552,9 @@ impl Func {
     ///   inputs required by the function signature of `self`.
     /// - If the number of output values does not match the expected number of
     ///   outputs required by the function signature of `self`.
+    /// - If `self` does not belong to the [`Engine`] of the [`Store`].
+    ///
+    /// [`Store`]: crate::Store
     pub fn call<T>(
         &self,
         mut ctx: impl AsContextMut<UserState = T>,
@@ -412,6 +572,42 @@ impl Func {
         Ok(())
     }
 
+    /// Calls the Wasm or host function with the given inputs restricted to the [`CallLimits`].
+    ///
+    /// The result is written back into the `outputs` buffer.
+    ///
+    /// # Note
+    ///
+    /// - This is useful to call untrusted Wasm callbacks from host functions with a
+    ///   smaller recursion budget than the Wasm execution that called the host function.
+    /// - Every call from the host to Wasm uses a separate Wasm stack. Therefore the `limits`
+    ///   apply on top of the stack usage of all outer calls and are only in effect for the
+    ///   duration of this call. The limits of the outer calls are not affected.
+    /// - The configured [`StackLimits`] still apply and cannot be raised via `limits`.
+    ///
+    /// # Errors
+    ///
+    /// - If the call exceeds the `limits` with a [`TrapCode::StackOverflow`].
+    /// - For the same reasons as [`Func::call`].
+    ///
+    /// [`StackLimits`]: crate::StackLimits
+    pub fn call_with_limits<T>(
+        &self,
+        mut ctx: impl AsContextMut<UserState = T>,
+        inputs: &[Value],
+        outputs: &mut [Value],
+        limits: CallLimits,
+    ) -> Result<(), Error> {
+        self.verify_and_prepare_inputs_outputs(ctx.as_context(), inputs, outputs)?;
+        // Note: Cloning an [`Engine`] is intentionally a cheap operation.
+        ctx.as_context()
+            .store
+            .engine()
+            .clone()
+            .execute_func_with_limits(ctx.as_context_mut(), self, inputs, outputs, limits)?;
+        Ok(())
+    }
+
     /// Calls the Wasm or host function with the given inputs.
     ///
     /// The result is written back into the `outputs` buffer.
@@ -435,6 +631,7 @@ impl Func {
     ///   inputs required by the function signature of `self`.
     /// - If the number of output values does not match the expected number of
     ///   outputs required by the function signature of `self`.
+    #[cfg(feature = "resumable")]
     pub fn call_resumable<T>(
         &self,
         mut ctx: impl AsContextMut<UserState = T>,
@@ -465,12 +662,14 @@ impl Func {
     /// - If the `inputs` value types do not match the function input types.
     /// - If the number of `inputs` do not match the function input types.
     /// - If the number of `outputs` do not match the function output types.
+    /// - If the function does not belong to the [`Engine`] of the store.
     fn verify_and_prepare_inputs_outputs(
         &self,
         ctx: impl AsContext,
         inputs: &[Value],
         outputs: &mut [Value],
     ) -> Result<(), FuncError> {
+        ctx.as_context().store.inner.check_func_engine(self)?;
         let fn_type = self.ty_dedup(ctx.as_context());
         ctx.as_context()
             .store
@@ -493,8 +692,11 @@ impl Func {
     ///
     /// # Errors
     ///
-    /// If the function signature of `self` does not match `Params` and `Results`
-    /// as parameter types and result types respectively.
+    /// - If the function signature of `self` does not match `Params` and `Results`
+    ///   as parameter types and result types respectively.
+    /// - If `self` does not belong to the [`Engine`] of the [`Store`].
+    ///
+    /// [`Store`]: crate::Store
     pub fn typed<Params, Results>(
         &self,
         ctx: impl AsContext,
@@ -5,10 +5,17 @@ use crate::{
     AsContext,
     AsContextMut,
     Error,
-    TypedResumableCall,
+    FuncType,
 };
+#[cfg(feature = "resumable")]
+use crate::TypedResumableCall;
 use core::{fmt, fmt::Debug, marker::PhantomData};
 
+/// The maximum number of results of a [`TypedFunc`].
+///
+/// This is the maximum tuple length for which [`WasmTypeList`] is implemented.
+const MAX_LEN_RESULTS: usize = 16;
+
 /// A typed [`Func`] instance.
 ///
 /// # Note
@@ -63,16 +70,28 @@ where
     ///
     /// # Errors
     ///
-    /// If the provided static types `Params` and `Results` for the parameters
-    /// and result types of `func` mismatch the signature of `func`.
+    /// - If the provided static types `Params` and `Results` for the parameters
+    ///   and result types of `func` mismatch the signature of `func`.
+    /// - If `func` does not belong to the [`Engine`] of the store.
+    ///
+    /// [`Engine`]: crate::Engine
     pub(crate) fn new(ctx: impl AsContext, func: Func) -> Result<Self, Error> {
+        ctx.as_context().store.inner.check_func_engine(&func)?;
         let func_type = func.ty(&ctx);
         let (actual_params, actual_results) = (
             <Params as WasmTypeList>::types(),
             <Results as WasmTypeList>::types(),
         );
-        func_type.match_params(actual_params.as_ref())?;
-        func_type.match_results(actual_results.as_ref(), true)?;
+        func_type
+            .match_params(actual_params.as_ref())
+            .and_then(|_| func_type.match_results(actual_results.as_ref(), true))
+            .map_err(|error| {
+                let expected = FuncType::new(
+                    actual_params.as_ref().iter().copied(),
+                    actual_results.as_ref().iter().copied(),
+                );
+                Error::from(error).with_signature_mismatch(expected, func_type.clone())
+            })?;
         Ok(Self {
             signature: PhantomData,
             func,
@@ -94,13 +113,19 @@ where
     ///
     /// If the execution of the called Wasm function traps.
     pub fn call(&self, mut ctx: impl AsContextMut, params: Params) -> Result<Results, Error> {
+        // Note: Only the encoding of `params` and the decoding of the results
+        //       is specific to the signature. The execution itself is shared by all
+        //       signatures in order to avoid monomorphizing it for each of them.
+        let params = <Params as WasmTypeList>::values(params);
+        let mut buffer = [UntypedValue::default(); MAX_LEN_RESULTS];
+        let results = &mut buffer[..<Results as WasmTypeList>::LEN];
         // Note: Cloning an [`Engine`] is intentionally a cheap operation.
-        ctx.as_context().store.engine().clone().execute_func(
-            ctx.as_context_mut(),
-            &self.func,
-            params,
-            <CallResultsTuple<Results>>::default(),
-        )
+        ctx.as_context()
+            .store
+            .engine()
+            .clone()
+            .execute_func_untyped(ctx.as_context_mut(), &self.func, params.as_ref(), results)?;
+        Ok(<CallResultsTuple<Results>>::default().call_results(results))
     }
 
     /// Calls this Wasm or host function with the specified parameters.
@@ -118,6 +143,7 @@ where
     /// # Errors
     ///
     /// If the function returned a [`Error`] originating from WebAssembly.
+    #[cfg(feature = "resumable")]
     pub fn call_resumable(
         &self,
         mut ctx: impl AsContextMut,
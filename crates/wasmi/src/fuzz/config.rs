@@ -0,0 +1,80 @@
+use crate::{CompilationMode, Config};
+use arbitrary::{Arbitrary, Unstructured};
+
+/// A random combination of Wasmi [`Config`] settings.
+///
+/// # Note
+///
+/// - Only settings that do not alter the observable behavior of Wasm executions
+///   are randomized so that results are comparable to other Wasm runtimes.
+///   Therefore fuel metering and [`CompilationMode::Lazy`] are never enabled.
+/// - Disabled Wasm proposals might cause some Wasm modules to fail validation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FuzzConfig {
+    /// Is `true` if the `mutable-global` Wasm proposal is enabled.
+    pub mutable_global: bool,
+    /// Is `true` if the `sign-extension` Wasm proposal is enabled.
+    pub sign_extension: bool,
+    /// Is `true` if the `saturating-float-to-int` Wasm proposal is enabled.
+    pub saturating_float_to_int: bool,
+    /// Is `true` if the `multi-value` Wasm proposal is enabled.
+    pub multi_value: bool,
+    /// Is `true` if the `bulk-memory` Wasm proposal is enabled.
+    pub bulk_memory: bool,
+    /// Is `true` if the `reference-types` Wasm proposal is enabled.
+    pub reference_types: bool,
+    /// Is `true` if the `tail-call` Wasm proposal is enabled.
+    pub tail_call: bool,
+    /// Is `true` if the `extended-const` Wasm proposal is enabled.
+    pub extended_const: bool,
+    /// Is `true` if `funcref` tables are initialized lazily.
+    pub lazy_table_init: bool,
+    /// The level of optimizations applied to the translated Wasmi bytecode.
+    pub optimization_level: u8,
+    /// Is `true` if Wasm functions are translated lazily on first use.
+    ///
+    /// This uses [`CompilationMode::LazyTranslation`] and [`CompilationMode::Eager`] otherwise.
+    pub lazy_translation: bool,
+}
+
+impl<'a> Arbitrary<'a> for FuzzConfig {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self {
+            mutable_global: u.arbitrary()?,
+            sign_extension: u.arbitrary()?,
+            saturating_float_to_int: u.arbitrary()?,
+            multi_value: u.arbitrary()?,
+            bulk_memory: u.arbitrary()?,
+            reference_types: u.arbitrary()?,
+            tail_call: u.arbitrary()?,
+            extended_const: u.arbitrary()?,
+            lazy_table_init: u.arbitrary()?,
+            optimization_level: u.int_in_range(0..=2)?,
+            lazy_translation: u.arbitrary()?,
+        })
+    }
+}
+
+impl FuzzConfig {
+    /// Returns the Wasmi [`Config`] for `self`.
+    pub fn config(&self) -> Config {
+        let compilation_mode = match self.lazy_translation {
+            true => CompilationMode::LazyTranslation,
+            false => CompilationMode::Eager,
+        };
+        let mut config = Config::default();
+        config
+            .wasm_mutable_global(self.mutable_global)
+            .wasm_sign_extension(self.sign_extension)
+            .wasm_saturating_float_to_int(self.saturating_float_to_int)
+            .wasm_multi_value(self.multi_value)
+            .wasm_bulk_memory(self.bulk_memory)
+            .wasm_reference_types(self.reference_types)
+            .wasm_tail_call(self.tail_call)
+            .wasm_extended_const(self.extended_const)
+            .lazy_table_init(self.lazy_table_init)
+            .optimization_level(self.optimization_level)
+            .compilation_mode(compilation_mode);
+        config
+    }
+}
@@ -0,0 +1,67 @@
+use super::FuzzValue;
+use crate::{ExternType, FuncType, Module};
+use alloc::{boxed::Box, vec::Vec};
+use arbitrary::Unstructured;
+
+/// The maximum number of invocations generated by [`FuzzInvocation::arbitrary_sequence`].
+const MAX_INVOCATIONS: usize = 100;
+
+/// An invocation of an exported function with type-correct parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzInvocation {
+    /// The name of the exported function.
+    pub func: Box<str>,
+    /// The parameters of the invocation.
+    pub params: Box<[FuzzValue]>,
+}
+
+impl FuzzInvocation {
+    /// Returns an arbitrary sequence of invocations of the exported functions of `module`.
+    ///
+    /// # Note
+    ///
+    /// Exported functions with reference typed parameters or results are never invoked.
+    ///
+    /// # Errors
+    ///
+    /// If `u` does not provide enough data.
+    pub fn arbitrary_sequence(
+        u: &mut Unstructured,
+        module: &Module,
+    ) -> arbitrary::Result<Vec<Self>> {
+        let funcs = module
+            .exports()
+            .filter_map(|export| match export.ty() {
+                ExternType::Func(ty) if Self::supports(ty) => Some((export.name(), ty.clone())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if funcs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let len = u.int_in_range(1..=MAX_INVOCATIONS)?;
+        let mut invocations = Vec::with_capacity(len);
+        for _ in 0..len {
+            let (name, ty) = u.choose(&funcs)?;
+            let params = ty
+                .params()
+                .iter()
+                .map(|ty| FuzzValue::arbitrary_of(u, *ty))
+                .collect::<arbitrary::Result<_>>()?;
+            invocations.push(Self {
+                func: (*name).into(),
+                params,
+            });
+        }
+        Ok(invocations)
+    }
+
+    /// Returns `true` if all parameters and results of `ty` are supported by [`FuzzValue`].
+    fn supports(ty: &FuncType) -> bool {
+        ty.params()
+            .iter()
+            .chain(ty.results())
+            .copied()
+            .all(FuzzValue::supports)
+    }
+}
@@ -0,0 +1,54 @@
+//! Building blocks for fuzzing Wasmi embeddings.
+//!
+//! These are the same building blocks that Wasmi uses to fuzz itself:
+//!
+//! - [`FuzzConfig`] produces random but valid Wasmi [`Config`]s via [`Arbitrary`].
+//! - [`FuzzInvocation`] produces call sequences with type-correct arbitrary
+//!   parameters for the exported functions of a [`Module`].
+//! - [`DifferentialOracle`] abstracts over the Wasm runtimes compared by [`differential`].
+//!   [`WasmiOracle`] implements it for Wasmi and embedders may implement it for their
+//!   own reference implementations.
+//!
+//! This module is only available if the `fuzz` crate feature is enabled.
+//!
+//! # Example
+//!
+//! ```
+//! # use wasmi::{fuzz::{differential, FuzzConfig, FuzzInvocation, WasmiOracle}, *};
+//! # use arbitrary::{Arbitrary, Unstructured};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let wasm = wat::parse_str(r#"
+//!     (module
+//!         (func (export "div") (param i32 i32) (result i32)
+//!             (i32.div_s (local.get 0) (local.get 1))
+//!         )
+//!     )
+//! "#)?;
+//! // Note: Fuzzers usually provide the random bytes.
+//! let bytes = (0..1024_u32).map(|n| n.wrapping_mul(0x9E37_79B9) as u8).collect::<Vec<_>>();
+//! let mut u = Unstructured::new(&bytes);
+//! let module = Module::new(&Engine::default(), &wasm[..])?;
+//! let invocations = FuzzInvocation::arbitrary_sequence(&mut u, &module)?;
+//! let mut lhs = WasmiOracle::new(&FuzzConfig::arbitrary(&mut u)?.config(), &wasm)?;
+//! let mut rhs = WasmiOracle::new(&Config::default(), &wasm)?;
+//! differential(&module, &invocations, &mut lhs, &mut rhs)?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod config;
+mod invocation;
+mod oracle;
+mod value;
+
+pub use self::{
+    config::FuzzConfig,
+    invocation::FuzzInvocation,
+    oracle::{differential, DifferentialOracle, FuzzError, Mismatch, WasmiOracle},
+    value::FuzzValue,
+};
+
+#[cfg(doc)]
+use crate::{Config, Module};
+#[cfg(doc)]
+use arbitrary::Arbitrary;
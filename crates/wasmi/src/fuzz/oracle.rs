@@ -0,0 +1,259 @@
+use super::{FuzzInvocation, FuzzValue};
+use crate::{
+    core::TrapCode,
+    Config,
+    Engine,
+    Error,
+    ExternType,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+    Value,
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt::{self, Display};
+
+/// The error of a function invocation of a [`DifferentialOracle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FuzzError {
+    /// The invocation trapped with the [`TrapCode`].
+    Trap(TrapCode),
+    /// The invocation failed for a reason other than a Wasm trap.
+    ///
+    /// # Note
+    ///
+    /// Errors of this kind never cause a [`Mismatch`] since Wasm runtimes
+    /// are free to differ in their resource limits.
+    Other(String),
+}
+
+impl Display for FuzzError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Trap(trap_code) => write!(f, "trap: {trap_code}"),
+            Self::Other(message) => write!(f, "error: {message}"),
+        }
+    }
+}
+
+impl From<Error> for FuzzError {
+    fn from(error: Error) -> Self {
+        match error.as_trap_code() {
+            Some(trap_code) => Self::Trap(trap_code),
+            None => Self::Other(error.to_string()),
+        }
+    }
+}
+
+/// A Wasm runtime that is compared by [`differential`].
+///
+/// An implementor represents a single instance of a Wasm module.
+pub trait DifferentialOracle {
+    /// Invokes the exported function named `func` with `params`.
+    ///
+    /// # Errors
+    ///
+    /// If the invocation traps or fails otherwise.
+    fn call(&mut self, func: &str, params: &[FuzzValue]) -> Result<Vec<FuzzValue>, FuzzError>;
+
+    /// Returns the value of the exported global variable named `name` if any.
+    fn get_global(&mut self, name: &str) -> Option<FuzzValue>;
+
+    /// Returns the bytes of the exported linear memory named `name` if any.
+    fn get_memory(&mut self, name: &str) -> Option<&[u8]>;
+}
+
+/// The first disagreement found by [`differential`].
+#[derive(Debug, Clone)]
+pub enum Mismatch {
+    /// The oracles disagree on the outcome of an invocation.
+    Call {
+        /// The invocation.
+        invocation: FuzzInvocation,
+        /// The outcome of the `lhs` oracle.
+        lhs: Result<Vec<FuzzValue>, FuzzError>,
+        /// The outcome of the `rhs` oracle.
+        rhs: Result<Vec<FuzzValue>, FuzzError>,
+    },
+    /// The oracles disagree on the value of an exported global variable.
+    Global {
+        /// The name of the global variable.
+        name: Box<str>,
+        /// The value of the `lhs` oracle.
+        lhs: Option<FuzzValue>,
+        /// The value of the `rhs` oracle.
+        rhs: Option<FuzzValue>,
+    },
+    /// The oracles disagree on the bytes of an exported linear memory.
+    Memory {
+        /// The name of the linear memory.
+        name: Box<str>,
+    },
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Call {
+                invocation,
+                lhs,
+                rhs,
+            } => write!(
+                f,
+                "mismatch for invocation of {:?} with {:?}: lhs = {lhs:?}, rhs = {rhs:?}",
+                invocation.func, invocation.params,
+            ),
+            Self::Global { name, lhs, rhs } => write!(
+                f,
+                "mismatch for global variable {name:?}: lhs = {lhs:?}, rhs = {rhs:?}"
+            ),
+            Self::Memory { name } => write!(f, "mismatch for linear memory {name:?}"),
+        }
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Invokes all `invocations` on both `lhs` and `rhs` and compares their outcomes.
+///
+/// After all invocations the exported global variables and afterwards
+/// the exported linear memories of `module` are compared as well.
+///
+/// # Note
+///
+/// - Traps agree if their [`TrapCode`]s are equal.
+/// - [`FuzzError::Other`] agrees with any other error.
+///
+/// # Errors
+///
+/// The first [`Mismatch`] between `lhs` and `rhs`.
+pub fn differential<L, R>(
+    module: &Module,
+    invocations: &[FuzzInvocation],
+    lhs: &mut L,
+    rhs: &mut R,
+) -> Result<(), Mismatch>
+where
+    L: DifferentialOracle + ?Sized,
+    R: DifferentialOracle + ?Sized,
+{
+    for invocation in invocations {
+        let lhs = lhs.call(&invocation.func, &invocation.params);
+        let rhs = rhs.call(&invocation.func, &invocation.params);
+        let agree = match (&lhs, &rhs) {
+            (Ok(lhs), Ok(rhs)) => lhs == rhs,
+            (Err(FuzzError::Trap(lhs)), Err(FuzzError::Trap(rhs))) => lhs == rhs,
+            (Err(FuzzError::Other(_)), Err(_)) | (Err(_), Err(FuzzError::Other(_))) => true,
+            (Ok(_), Err(_)) | (Err(_), Ok(_)) => false,
+        };
+        if !agree {
+            return Err(Mismatch::Call {
+                invocation: invocation.clone(),
+                lhs,
+                rhs,
+            });
+        }
+    }
+    for export in module.exports() {
+        let name = export.name();
+        if let ExternType::Global(ty) = export.ty() {
+            if !FuzzValue::supports(ty.content()) {
+                continue;
+            }
+            let lhs = lhs.get_global(name);
+            let rhs = rhs.get_global(name);
+            if lhs != rhs {
+                return Err(Mismatch::Global {
+                    name: name.into(),
+                    lhs,
+                    rhs,
+                });
+            }
+        }
+    }
+    for export in module.exports() {
+        let name = export.name();
+        if let ExternType::Memory(_) = export.ty() {
+            if lhs.get_memory(name) != rhs.get_memory(name) {
+                return Err(Mismatch::Memory { name: name.into() });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A [`DifferentialOracle`] for Wasmi.
+#[derive(Debug)]
+pub struct WasmiOracle {
+    store: Store<StoreLimits>,
+    instance: Instance,
+    params: Vec<Value>,
+    results: Vec<Value>,
+}
+
+impl WasmiOracle {
+    /// The maximum size of the linear memories of a [`WasmiOracle`] in bytes.
+    const MAX_MEMORY_SIZE: usize = 1000 * 0x10000;
+
+    /// Instantiates the `wasm` module with a Wasmi [`Engine`] using `config`.
+    ///
+    /// # Note
+    ///
+    /// The `wasm` module must not have imports.
+    ///
+    /// # Errors
+    ///
+    /// If the `wasm` module fails to compile or to instantiate.
+    pub fn new(config: &Config, wasm: &[u8]) -> Result<Self, Error> {
+        let engine = Engine::new(config);
+        let module = Module::new(&engine, wasm)?;
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(Self::MAX_MEMORY_SIZE)
+            .build();
+        let mut store = Store::new(&engine, limits);
+        store.limiter(|limits| limits);
+        let instance = <Linker<StoreLimits>>::new(&engine)
+            .instantiate(&mut store, &module)?
+            .start(&mut store)?;
+        Ok(Self {
+            store,
+            instance,
+            params: Vec::new(),
+            results: Vec::new(),
+        })
+    }
+}
+
+impl DifferentialOracle for WasmiOracle {
+    fn call(&mut self, func: &str, params: &[FuzzValue]) -> Result<Vec<FuzzValue>, FuzzError> {
+        let Some(func) = self.instance.get_func(&self.store, func) else {
+            return Err(FuzzError::Other(format!(
+                "missing exported function {func:?}"
+            )));
+        };
+        let ty = func.ty(&self.store);
+        self.params.clear();
+        self.params.extend(params.iter().copied().map(Value::from));
+        self.results.clear();
+        self.results
+            .extend(ty.results().iter().copied().map(Value::default));
+        func.call(&mut self.store, &self.params, &mut self.results)?;
+        Ok(self.results.iter().map(FuzzValue::from).collect())
+    }
+
+    fn get_global(&mut self, name: &str) -> Option<FuzzValue> {
+        let value = self
+            .instance
+            .get_global(&self.store, name)?
+            .get(&self.store);
+        Some(FuzzValue::from(&value))
+    }
+
+    fn get_memory(&mut self, name: &str) -> Option<&[u8]> {
+        let memory = self.instance.get_memory(&self.store, name)?;
+        Some(memory.data(&self.store))
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    core::{ValueType, F32, F64},
+    Value,
+};
+use arbitrary::Unstructured;
+
+/// A numeric Wasm value that is comparable across Wasm runtimes.
+///
+/// # Note
+///
+/// Two NaN values of the same type are considered equal regardless
+/// of their bits since Wasm does not specify NaN bits deterministically.
+#[derive(Debug, Copy, Clone)]
+pub enum FuzzValue {
+    /// A 32-bit integer value.
+    I32(i32),
+    /// A 64-bit integer value.
+    I64(i64),
+    /// A 32-bit float value.
+    F32(F32),
+    /// A 64-bit float value.
+    F64(F64),
+}
+
+impl PartialEq for FuzzValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::I32(lhs), Self::I32(rhs)) => lhs == rhs,
+            (Self::I64(lhs), Self::I64(rhs)) => lhs == rhs,
+            (Self::F32(lhs), Self::F32(rhs)) => {
+                (lhs.is_nan() && rhs.is_nan()) || lhs.to_bits() == rhs.to_bits()
+            }
+            (Self::F64(lhs), Self::F64(rhs)) => {
+                (lhs.is_nan() && rhs.is_nan()) || lhs.to_bits() == rhs.to_bits()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FuzzValue {
+    /// Returns `true` if [`FuzzValue`] can represent values of type `ty`.
+    pub fn supports(ty: ValueType) -> bool {
+        matches!(
+            ty,
+            ValueType::I32 | ValueType::I64 | ValueType::F32 | ValueType::F64
+        )
+    }
+
+    /// Returns an arbitrary [`FuzzValue`] of type `ty`.
+    ///
+    /// Boundary values such as zero, minimum, maximum, infinity and NaN
+    /// values are chosen more often than by uniform sampling.
+    ///
+    /// # Panics
+    ///
+    /// If `ty` is not supported by [`FuzzValue`].
+    ///
+    /// # Errors
+    ///
+    /// If `u` does not provide enough data.
+    pub fn arbitrary_of(u: &mut Unstructured, ty: ValueType) -> arbitrary::Result<Self> {
+        let special = u.ratio(1, 4)?;
+        let value = match ty {
+            ValueType::I32 => Self::I32(match special {
+                true => *u.choose(&[0, 1, -1, i32::MIN, i32::MAX])?,
+                false => u.arbitrary()?,
+            }),
+            ValueType::I64 => Self::I64(match special {
+                true => *u.choose(&[0, 1, -1, i64::MIN, i64::MAX])?,
+                false => u.arbitrary()?,
+            }),
+            ValueType::F32 => Self::F32(F32::from(match special {
+                true => *u.choose(&[0.0, -0.0, 1.0, f32::INFINITY, f32::NEG_INFINITY, f32::NAN])?,
+                false => f32::from_bits(u.arbitrary()?),
+            })),
+            ValueType::F64 => Self::F64(F64::from(match special {
+                true => *u.choose(&[0.0, -0.0, 1.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN])?,
+                false => f64::from_bits(u.arbitrary()?),
+            })),
+            unsupported => panic!("fuzzing does not support values of type {unsupported:?}"),
+        };
+        Ok(value)
+    }
+}
+
+impl From<&Value> for FuzzValue {
+    /// Converts the numeric [`Value`] into a [`FuzzValue`].
+    ///
+    /// # Panics
+    ///
+    /// If `value` is a reference value.
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::I32(value) => Self::I32(*value),
+            Value::I64(value) => Self::I64(*value),
+            Value::F32(value) => Self::F32(*value),
+            Value::F64(value) => Self::F64(*value),
+            unsupported => panic!("fuzzing does not support reference values: {unsupported:?}"),
+        }
+    }
+}
+
+impl From<FuzzValue> for Value {
+    fn from(value: FuzzValue) -> Self {
+        match value {
+            FuzzValue::I32(value) => Self::I32(value),
+            FuzzValue::I64(value) => Self::I64(value),
+            FuzzValue::F32(value) => Self::F32(value),
+            FuzzValue::F64(value) => Self::F64(value),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::{AsContext, AsContextMut, Stored};
-use crate::{core::ValueType, value::WithType, Value};
+use crate::{core::ValueType, value::WithType, Instance, Value};
+use alloc::vec::Vec;
 use core::{fmt, fmt::Display, ptr::NonNull};
 use wasmi_arena::ArenaIndex;
 use wasmi_core::UntypedValue;
@@ -34,6 +35,8 @@ pub enum GlobalError {
         /// The type of the new value that mismatches the type of the global variable.
         encountered: ValueType,
     },
+    /// Occurs when a value references an entity that is not owned by the store of the global variable.
+    ForeignReference,
     /// Occurs when a global type does not satisfy the constraints of another.
     UnsatisfyingGlobalType {
         /// The unsatisfying [`GlobalType`].
@@ -57,6 +60,12 @@ impl Display for GlobalError {
                     expected {expected:?} but encountered {encountered:?}.",
                 )
             }
+            Self::ForeignReference => {
+                write!(
+                    f,
+                    "tried to use a reference to an entity of another store as global variable value"
+                )
+            }
             Self::UnsatisfyingGlobalType {
                 unsatisfying,
                 required,
@@ -231,11 +240,48 @@ impl Global {
     }
 
     /// Creates a new global variable to the store.
+    ///
+    /// The type of the global variable is the type of the `initial_value`.
+    ///
+    /// # Panics
+    ///
+    /// If `initial_value` references an entity that is not owned by `ctx`.
+    /// Use [`Global::new_typed`] in order to handle this as an error instead.
     pub fn new(mut ctx: impl AsContextMut, initial_value: Value, mutability: Mutability) -> Self {
-        ctx.as_context_mut()
-            .store
-            .inner
-            .alloc_global(GlobalEntity::new(initial_value, mutability))
+        let store = &mut ctx.as_context_mut().store.inner;
+        assert!(
+            store.owns_value(&initial_value),
+            "initial value of global variable references an entity of another store: {initial_value:?}"
+        );
+        store.alloc_global(GlobalEntity::new(initial_value, mutability))
+    }
+
+    /// Creates a new global variable of type `ty` to the store.
+    ///
+    /// The `initial_value` is coerced to the content type of `ty` via [`Value::with_type_coercion`]
+    /// so that a `null` reference value can initialize global variables of any reference type.
+    ///
+    /// # Errors
+    ///
+    /// - If `initial_value` cannot be coerced to the content type of `ty`.
+    /// - If `initial_value` references an entity that is not owned by `ctx`.
+    pub fn new_typed(
+        mut ctx: impl AsContextMut,
+        ty: GlobalType,
+        initial_value: Value,
+    ) -> Result<Self, GlobalError> {
+        let encountered = initial_value.ty();
+        let initial_value = initial_value
+            .with_type_coercion(ty.content())
+            .map_err(|_| GlobalError::TypeMismatch {
+                expected: ty.content(),
+                encountered,
+            })?;
+        let store = &mut ctx.as_context_mut().store.inner;
+        if !store.owns_value(&initial_value) {
+            return Err(GlobalError::ForeignReference);
+        }
+        Ok(store.alloc_global(GlobalEntity::new(initial_value, ty.mutability())))
     }
 
     /// Returns the [`GlobalType`] of the global variable.
@@ -243,22 +289,40 @@ impl Global {
         ctx.as_context().store.inner.resolve_global(self).ty()
     }
 
+    /// Returns the instances that define or import the global variable in the order of their instantiation.
+    ///
+    /// Read [`Memory::instances`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Global`].
+    ///
+    /// [`Memory::instances`]: crate::Memory::instances
+    pub fn instances(&self, ctx: impl AsContext) -> Vec<Instance> {
+        ctx.as_context().store.inner.global_instances(self)
+    }
+
     /// Sets a new value to the global variable.
     ///
     /// # Errors
     ///
     /// - If the global variable is immutable.
     /// - If there is a type mismatch between the global variable and the new value.
+    /// - If the new value references an entity that is not owned by `ctx`.
     ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Global`].
     pub fn set(&self, mut ctx: impl AsContextMut, new_value: Value) -> Result<(), GlobalError> {
-        ctx.as_context_mut()
-            .store
-            .inner
-            .resolve_global_mut(self)
-            .set(new_value)
+        let store = &mut ctx.as_context_mut().store.inner;
+        let global = store.resolve_global(self);
+        if global.ty().mutability().is_mut()
+            && global.ty().content() == new_value.ty()
+            && !store.owns_value(&new_value)
+        {
+            return Err(GlobalError::ForeignReference);
+        }
+        store.resolve_global_mut(self).set(new_value)
     }
 
     /// Returns the current value of the global variable.
@@ -1,8 +1,8 @@
 use super::InstanceEntity;
 use crate::{
-    engine::DedupFuncType,
+    engine::{CodeOwner, DedupFuncType},
     memory::DataSegment,
-    module::FuncIdx,
+    module::{ExportMap, FuncIdx},
     ElementSegment,
     Extern,
     ExternType,
@@ -12,20 +12,22 @@ use crate::{
     Module,
     Table,
 };
-use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use alloc::{sync::Arc, vec::Vec};
 
 /// A module instance entity builder.
 #[derive(Debug)]
 pub struct InstanceEntityBuilder {
+    code_owner: CodeOwner,
     func_types: Arc<[DedupFuncType]>,
     tables: Vec<Table>,
     funcs: Vec<Func>,
     memories: Vec<Memory>,
     globals: Vec<Global>,
     start_fn: Option<FuncIdx>,
-    exports: BTreeMap<Box<str>, Extern>,
+    exports: ExportMap<Extern>,
     data_segments: Vec<DataSegment>,
     elem_segments: Vec<ElementSegment>,
+    imports: Vec<Extern>,
 }
 
 impl InstanceEntityBuilder {
@@ -57,15 +59,17 @@ impl InstanceEntityBuilder {
             }
         }
         Self {
+            code_owner: module.code_owner().clone(),
             func_types: module.func_types_cloned(),
             tables: vec_with_capacity_exact(len_tables),
             funcs: vec_with_capacity_exact(len_funcs),
             memories: vec_with_capacity_exact(len_memories),
             globals: vec_with_capacity_exact(len_globals),
             start_fn: None,
-            exports: BTreeMap::default(),
+            exports: ExportMap::default(),
             data_segments: Vec::new(),
             elem_segments: Vec::new(),
+            imports: vec_with_capacity_exact(module.imports().len()),
         }
     }
 
@@ -136,6 +140,20 @@ impl InstanceEntityBuilder {
             .unwrap_or_else(|| panic!("missing `Func` at index: {index}"))
     }
 
+    /// Returns the functions of the [`InstanceEntity`] under construction.
+    pub fn funcs(&self) -> &[Func] {
+        &self.funcs
+    }
+
+    /// Pushes a resolved import to the [`InstanceEntity`] under construction.
+    ///
+    /// # Note
+    ///
+    /// This only records the import. The imported entity must be pushed separately.
+    pub fn push_import(&mut self, import: Extern) {
+        self.imports.push(import);
+    }
+
     /// Pushes a new [`Memory`] to the [`InstanceEntity`] under construction.
     pub fn push_memory(&mut self, memory: Memory) {
         self.memories.push(memory);
@@ -185,6 +203,7 @@ impl InstanceEntityBuilder {
     pub fn finish(self) -> InstanceEntity {
         InstanceEntity {
             initialized: true,
+            _code_owner: Some(self.code_owner),
             func_types: self.func_types,
             tables: self.tables.into(),
             funcs: self.funcs.into(),
@@ -193,6 +212,7 @@ impl InstanceEntityBuilder {
             exports: self.exports,
             data_segments: self.data_segments.into(),
             elem_segments: self.elem_segments.into(),
+            imports: self.imports.into(),
         }
     }
 }
@@ -0,0 +1,61 @@
+use super::ExternKind;
+use crate::FuncType;
+use alloc::boxed::Box;
+use core::{fmt, fmt::Display};
+
+/// An error that may occur upon looking up an export of an [`Instance`].
+///
+/// [`Instance`]: crate::Instance
+#[derive(Debug, Clone)]
+pub enum ExportError {
+    /// There is no export with the name.
+    NotFound {
+        /// The name of the missing export.
+        name: Box<str>,
+    },
+    /// The export has a different kind than expected.
+    KindMismatch {
+        /// The name of the export.
+        name: Box<str>,
+        /// The expected kind of the export.
+        expected: ExternKind,
+        /// The actual kind of the export.
+        found: ExternKind,
+    },
+    /// The exported function has a different signature than expected.
+    SignatureMismatch {
+        /// The name of the exported function.
+        name: Box<str>,
+        /// The expected function signature.
+        expected: FuncType,
+        /// The actual function signature of the exported function.
+        found: FuncType,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ExportError {}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound { name } => write!(f, "could not find export named \"{name}\""),
+            Self::KindMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected export \"{name}\" to be a {expected} but found a {found}"
+            ),
+            Self::SignatureMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected exported function \"{name}\" to have signature {expected:?} but found {found:?}"
+            ),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{AsContext, Func, FuncType, Global, GlobalType, Memory, MemoryType, Table, TableType};
-use alloc::{boxed::Box, collections::btree_map};
-use core::iter::FusedIterator;
+use alloc::boxed::Box;
+use core::{fmt, iter::FusedIterator, slice};
 
 /// An external item to a WebAssembly module.
 ///
@@ -85,6 +85,16 @@ impl Extern {
         None
     }
 
+    /// Returns the [`ExternKind`] of this [`Extern`].
+    pub fn kind(&self) -> ExternKind {
+        match self {
+            Extern::Global(_) => ExternKind::Global,
+            Extern::Table(_) => ExternKind::Table,
+            Extern::Memory(_) => ExternKind::Memory,
+            Extern::Func(_) => ExternKind::Func,
+        }
+    }
+
     /// Returns the type associated with this [`Extern`].
     ///
     /// # Panics
@@ -100,6 +110,31 @@ impl Extern {
     }
 }
 
+/// The kind of an [`Extern`] item.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExternKind {
+    /// The kind of an [`Extern::Global`].
+    Global,
+    /// The kind of an [`Extern::Table`].
+    Table,
+    /// The kind of an [`Extern::Memory`].
+    Memory,
+    /// The kind of an [`Extern::Func`].
+    Func,
+}
+
+impl fmt::Display for ExternKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self {
+            Self::Global => "global",
+            Self::Table => "table",
+            Self::Memory => "memory",
+            Self::Func => "function",
+        };
+        f.write_str(kind)
+    }
+}
+
 /// The type of an [`Extern`] item.
 ///
 /// A list of all possible types which can be externally referenced from a WebAssembly module.
@@ -234,18 +269,18 @@ impl<'instance> Export<'instance> {
 /// An iterator over the [`Extern`] declarations of an [`Instance`](crate::Instance).
 #[derive(Debug)]
 pub struct ExportsIter<'instance> {
-    iter: btree_map::Iter<'instance, Box<str>, Extern>,
+    iter: slice::Iter<'instance, (Box<str>, Extern)>,
 }
 
 impl<'instance> ExportsIter<'instance> {
     /// Creates a new [`ExportsIter`].
-    pub(super) fn new(iter: btree_map::Iter<'instance, Box<str>, Extern>) -> Self {
+    pub(super) fn new(iter: slice::Iter<'instance, (Box<str>, Extern)>) -> Self {
         Self { iter }
     }
 
     /// Prepares an item to match the expected iterator `Item` signature.
     #[allow(clippy::borrowed_box)]
-    fn convert_item((name, export): (&'instance Box<str>, &'instance Extern)) -> Export {
+    fn convert_item((name, export): &'instance (Box<str>, Extern)) -> Export<'instance> {
         Export::new(name, *export)
     }
 }
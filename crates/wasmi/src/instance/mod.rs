@@ -1,8 +1,12 @@
 pub(crate) use self::builder::InstanceEntityBuilder;
-pub use self::exports::{Export, ExportsIter, Extern, ExternType};
+pub use self::{
+    error::ExportError,
+    exports::{Export, ExportsIter, Extern, ExternKind, ExternType},
+};
 use super::{
-    engine::DedupFuncType,
+    engine::{CodeOwner, DedupFuncType},
     AsContext,
+    AsContextMut,
     Func,
     Global,
     Memory,
@@ -12,18 +16,22 @@ use super::{
     Table,
 };
 use crate::{
-    func::FuncError,
+    func::FuncEntity,
     memory::DataSegment,
+    module::ExportMap,
     ElementSegment,
     Error,
+    FuncType,
     TypedFunc,
     WasmParams,
     WasmResults,
+    WasmTypeList,
 };
-use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use wasmi_arena::ArenaIndex;
 
 mod builder;
+mod error;
 mod exports;
 
 /// A raw index to a module instance entity.
@@ -47,14 +55,26 @@ impl ArenaIndex for InstanceIdx {
 #[derive(Debug)]
 pub struct InstanceEntity {
     initialized: bool,
+    /// Keeps the functions of the instantiated [`Module`] alive in the [`Engine`].
+    ///
+    /// # Note
+    ///
+    /// This is never read but only held to prevent [`Engine::purge_unused`]
+    /// from reclaiming the functions while the instance exists.
+    ///
+    /// [`Engine`]: crate::Engine
+    /// [`Engine::purge_unused`]: crate::Engine::purge_unused
+    _code_owner: Option<CodeOwner>,
     func_types: Arc<[DedupFuncType]>,
     tables: Box<[Table]>,
     funcs: Box<[Func]>,
     memories: Box<[Memory]>,
     globals: Box<[Global]>,
-    exports: BTreeMap<Box<str>, Extern>,
+    exports: ExportMap<Extern>,
     data_segments: Box<[DataSegment]>,
     elem_segments: Box<[ElementSegment]>,
+    /// The resolved imports the instance has been instantiated with.
+    imports: Box<[Extern]>,
 }
 
 impl InstanceEntity {
@@ -62,14 +82,16 @@ impl InstanceEntity {
     pub fn uninitialized() -> InstanceEntity {
         Self {
             initialized: false,
+            _code_owner: None,
             func_types: Arc::new([]),
             tables: [].into(),
             funcs: [].into(),
             memories: [].into(),
             globals: [].into(),
-            exports: BTreeMap::new(),
+            exports: ExportMap::default(),
             data_segments: [].into(),
             elem_segments: [].into(),
+            imports: [].into(),
         }
     }
 
@@ -83,6 +105,26 @@ impl InstanceEntity {
         self.initialized
     }
 
+    /// Returns the linear memories of the [`InstanceEntity`] including imported ones.
+    pub fn memories(&self) -> &[Memory] {
+        &self.memories
+    }
+
+    /// Returns the tables of the [`InstanceEntity`] including imported ones.
+    pub fn tables(&self) -> &[Table] {
+        &self.tables
+    }
+
+    /// Returns the global variables of the [`InstanceEntity`] including imported ones.
+    pub fn globals(&self) -> &[Global] {
+        &self.globals
+    }
+
+    /// Returns the resolved imports of the [`InstanceEntity`] in the order of [`Module::imports`].
+    pub fn imports(&self) -> &[Extern] {
+        &self.imports
+    }
+
     /// Returns the linear memory at the `index` if any.
     pub fn get_memory(&self, index: u32) -> Option<Memory> {
         self.memories.get(index as usize).copied()
@@ -123,9 +165,7 @@ impl InstanceEntity {
         self.exports.get(name).copied()
     }
 
-    /// Returns an iterator over the exports of the [`Instance`].
-    ///
-    /// The order of the yielded exports is not specified.
+    /// Returns an iterator over the exports of the [`Instance`] in declaration order.
     pub fn exports(&self) -> ExportsIter {
         ExportsIter::new(self.exports.iter())
     }
@@ -163,6 +203,28 @@ impl Instance {
         &self.0
     }
 
+    /// Creates a new [`Instance`] of `module` from `imports` and runs its `start` function.
+    ///
+    /// The `imports` are joined with the imports of `module` in the order in which they occurred.
+    ///
+    /// # Note
+    ///
+    /// Prefer [`Linker::instantiate`](crate::Linker::instantiate) which resolves imports by name.
+    ///
+    /// # Errors
+    ///
+    /// - If the `imports` do not satisfy the imports of `module` in number or type.
+    /// - If the instantiation of `module` fails or its `start` function traps.
+    pub fn new(
+        mut store: impl AsContextMut,
+        module: &Module,
+        imports: &[Extern],
+    ) -> Result<Self, Error> {
+        module
+            .instantiate(&mut store, imports.iter().copied())?
+            .start(&mut store)
+    }
+
     /// Returns the function at the `index` if any.
     ///
     /// # Panics
@@ -203,16 +265,13 @@ impl Instance {
         self.get_export(store, name)?.into_func()
     }
 
-    /// Looks up an exported [`Func`] value by `name`.
-    ///
-    /// Returns `None` if there was no export named `name`,
-    /// or if there was but it wasn’t a function.
+    /// Looks up an exported [`Func`] value by `name` and types it as [`TypedFunc`].
     ///
     /// # Errors
     ///
-    /// - If there is no export named `name`.
-    /// - If there is no exported function named `name`.
-    /// - If `Params` or `Results` do not match the exported function type.
+    /// - [`ExportError::NotFound`]: If there is no export named `name`.
+    /// - [`ExportError::KindMismatch`]: If the export named `name` is not a function.
+    /// - [`ExportError::SignatureMismatch`]: If `Params` or `Results` do not match the exported function type.
     ///
     /// # Panics
     ///
@@ -221,15 +280,83 @@ impl Instance {
         &self,
         store: impl AsContext,
         name: &str,
-    ) -> Result<TypedFunc<Params, Results>, Error>
+    ) -> Result<TypedFunc<Params, Results>, ExportError>
     where
         Params: WasmParams,
         Results: WasmResults,
     {
-        self.get_export(&store, name)
-            .and_then(Extern::into_func)
-            .ok_or_else(|| Error::from(FuncError::ExportedFuncNotFound))?
-            .typed::<Params, Results>(store)
+        let export = self
+            .get_export(&store, name)
+            .ok_or_else(|| ExportError::NotFound { name: name.into() })?;
+        let Extern::Func(func) = export else {
+            return Err(ExportError::KindMismatch {
+                name: name.into(),
+                expected: ExternKind::Func,
+                found: export.kind(),
+            });
+        };
+        func.typed::<Params, Results>(&store).map_err(|_| {
+            let expected = FuncType::new(
+                <Params as WasmTypeList>::types().as_ref().iter().copied(),
+                <Results as WasmTypeList>::types().as_ref().iter().copied(),
+            );
+            ExportError::SignatureMismatch {
+                name: name.into(),
+                expected,
+                found: func.ty(&store),
+            }
+        })
+    }
+
+    /// Compiles the exported Wasm functions named `names` ahead of their first call.
+    ///
+    /// If `transitive` is `true` all internal functions directly called by the
+    /// compiled functions are compiled as well, transitively.
+    ///
+    /// # Note
+    ///
+    /// - This is the same as [`Module::compile_functions`] for exported functions.
+    ///   Host functions are skipped since they do not require compilation.
+    /// - This is safe to call while other threads execute or compile the same functions.
+    /// - Just like [`Module::compile_functions`] this may be called by host functions
+    ///   during Wasm executions of the [`Engine`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ExportError::NotFound`]: If there is no export named after any of the `names`.
+    /// - [`ExportError::KindMismatch`]: If any of the exports named after the `names` is not a function.
+    /// - If the compilation of any of the functions fails.
+    ///
+    /// # Panics
+    ///
+    /// If `store` does not own this [`Instance`].
+    ///
+    /// [`Engine`]: crate::Engine
+    pub fn precompile_exports(
+        &self,
+        store: impl AsContext,
+        names: &[&str],
+        transitive: bool,
+    ) -> Result<(), Error> {
+        let store = store.as_context().store;
+        let mut funcs = Vec::with_capacity(names.len());
+        for &name in names {
+            let export = self
+                .get_export(store, name)
+                .ok_or_else(|| ExportError::NotFound { name: name.into() })?;
+            let Extern::Func(func) = export else {
+                return Err(ExportError::KindMismatch {
+                    name: name.into(),
+                    expected: ExternKind::Func,
+                    found: export.kind(),
+                }
+                .into());
+            };
+            if let FuncEntity::Wasm(wasm_func) = store.inner.resolve_func(&func) {
+                funcs.push(wasm_func.func_body());
+            }
+        }
+        store.engine().compile_funcs(funcs, transitive)
     }
 
     /// Looks up an exported [`Global`] value by `name`.
@@ -270,7 +397,8 @@ impl Instance {
 
     /// Returns an iterator over the exports of the [`Instance`].
     ///
-    /// The order of the yielded exports is not specified.
+    /// The exports are yielded in the order of their declaration in the instantiated [`Module`].
+    /// Use [`Export::name`] and [`Export::ty`] to query their names and types.
     ///
     /// # Panics
     ///
@@ -281,4 +409,21 @@ impl Instance {
     ) -> ExportsIter<'ctx> {
         store.into().store.inner.resolve_instance(self).exports()
     }
+
+    /// Returns the resolved imports the [`Instance`] has been instantiated with.
+    ///
+    /// The imports are returned in the order of [`Module::imports`] of the instantiated [`Module`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own this [`Instance`].
+    pub fn imports(&self, store: impl AsContext) -> Vec<Extern> {
+        store
+            .as_context()
+            .store
+            .inner
+            .resolve_instance(self)
+            .imports()
+            .to_vec()
+    }
 }
@@ -29,10 +29,13 @@
 //!             )
 //!         )
 //!     "#;
-//!     // Wasmi does not yet support parsing `.wat` so we have to convert
-//!     // out `.wat` into `.wasm` before we compile and validate it.
-//!     let wasm = wat::parse_str(&wat)?;
-//!     let module = Module::new(&engine, &mut &wasm[..])?;
+//!     // Parsing `.wat` requires the `wat` crate feature.
+//!     // Without it we have to convert our `.wat` into `.wasm` first,
+//!     // e.g. via the `wat` crate, and then use `Module::new`.
+//!     # #[cfg(feature = "wat")]
+//!     let module = Module::new_wat(&engine, wat)?;
+//!     # #[cfg(not(feature = "wat"))]
+//!     # let module = Module::new(&engine, &wat::parse_str(wat)?[..])?;
 //!
 //!     // All Wasm objects operate within the context of a `Store`.
 //!     // Each `Store` has a type parameter to store host-specific data,
@@ -102,43 +105,113 @@ mod store;
 mod table;
 mod value;
 
+pub mod build;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+#[cfg(feature = "spec-testing")]
+pub mod spec;
+
 /// Definitions from the `wasmi_core` crate.
 #[doc(inline)]
 pub use wasmi_core as core;
 
+/// Definitions of the Wasmi bytecode used to construct functions via [`build::IrFunc`].
+pub mod ir {
+    pub use super::engine::{
+        bytecode::{
+            AnyConst16,
+            AnyConst32,
+            BinInstr,
+            BinInstrImm,
+            BinInstrImm16,
+            BlockFuel,
+            BranchBinOpInstr,
+            BranchBinOpInstrFallback,
+            BranchBinOpInstrImm,
+            BranchBinOpInstrImm16,
+            BranchComparator,
+            BranchOffset,
+            BranchOffset16,
+            CallIndirectParams,
+            ComparatorOffsetParam,
+            Const16,
+            Const32,
+            DataSegmentIdx,
+            ElementSegmentIdx,
+            FuncIdx,
+            GlobalIdx,
+            Instruction,
+            LoadAtInstr,
+            LoadInstr,
+            LoadOffset16Instr,
+            Register,
+            RegisterSpan,
+            RegisterSpanIter,
+            Sign,
+            SignatureIdx,
+            StoreAtInstr,
+            StoreInstr,
+            StoreOffset16Instr,
+            TableIdx,
+            UnaryInstr,
+        },
+        CompiledFunc,
+        DedupFuncType,
+    };
+}
+
 /// Defines some errors that may occur upon interaction with Wasmi.
 pub mod errors {
+    #[cfg(feature = "std")]
+    pub use super::memory::MemoryIoError;
+    #[cfg(feature = "wat")]
+    pub use super::module::WatError;
     pub use super::{
-        error::ErrorKind,
-        func::FuncError,
+        engine::{BytecodeError, BytecodeErrorKind},
+        error::{ErrorKind, GrowError},
+        func::{BatchCallError, FuncError},
         global::GlobalError,
+        instance::ExportError,
         linker::LinkerError,
         memory::MemoryError,
-        module::InstantiationError,
-        store::FuelError,
+        module::{EngineMismatchError, InstantiationError},
+        store::{FuelError, SnapshotError},
         table::TableError,
+        value::ValueError,
     };
 }
 
 pub use self::{
     engine::{
+        CallLimits,
+        CallSiteTypes,
         CompilationMode,
         Config,
+        DigestFn,
         Engine,
-        ResumableCall,
-        ResumableInvocation,
+        EngineMemoryUsage,
+        ExecutionDigest,
+        FuelCosts,
+        FuncInfo,
+        ModuleLimit,
+        ModuleLimits,
+        RegisterReader,
+        RegisterTypes,
         StackLimits,
-        TypedResumableCall,
-        TypedResumableInvocation,
+        StackUsage,
     },
-    error::Error,
+    error::{Error, TrapOrigin},
     externref::ExternRef,
     func::{
         Caller,
+        CallerEntities,
         Func,
         FuncRef,
         FuncType,
+        HostFuncInfo,
+        HostFuncNext,
         IntoFunc,
+        RawHostFunc,
         TypedFunc,
         WasmParams,
         WasmResults,
@@ -147,23 +220,58 @@ pub use self::{
         WasmTypeList,
     },
     global::{Global, GlobalType, Mutability},
-    instance::{Export, ExportsIter, Extern, ExternType, Instance},
+    instance::{Export, ExportsIter, Extern, ExternKind, ExternType, Instance},
     limits::{ResourceLimiter, StoreLimits, StoreLimitsBuilder},
-    linker::Linker,
-    memory::{Memory, MemoryType},
+    linker::{Linker, PreparedInstance},
+    memory::{Memory, MemoryType, MemoryTypeBuilder, MemoryView, MemoryViewMut, Pod},
     module::{
+        CompiledFuncBytes,
         ExportType,
+        FeaturePrefix,
+        FeatureRequirement,
         ImportType,
         InstancePre,
         Module,
         ModuleExportsIter,
         ModuleImportsIter,
+        ParseWarning,
+        Producers,
+        ProducersField,
+        ProducersValue,
         Read,
+        TranslationContext,
+        TranslationContextBuilder,
     },
-    store::{AsContext, AsContextMut, Store, StoreContext, StoreContextMut},
-    table::{Table, TableType},
+    store::{
+        AsContext,
+        AsContextMut,
+        Store,
+        StoreContext,
+        StoreContextMut,
+        StoreSnapshot,
+        WatchpointHit,
+        WatchpointId,
+        YieldDecision,
+    },
+    table::{Table, TableType, TableTypeBuilder},
     value::Value,
 };
+#[cfg(feature = "std")]
+pub use self::engine::{CompilationHandle, CompilationJob, CompilationStatus};
+#[cfg(feature = "resumable")]
+pub use self::engine::{
+    DriverState,
+    GuestYield,
+    HostInterruption,
+    HostYield,
+    ResumableCall,
+    ResumableDriver,
+    ResumableInvocation,
+    TypedResumableCall,
+    TypedResumableInvocation,
+};
+#[cfg(feature = "metrics")]
+pub use self::store::{CallMetrics, InstructionCategory, InstructionCategoryCounts};
 use self::{
     func::{FuncEntity, FuncIdx},
     global::{GlobalEntity, GlobalIdx},
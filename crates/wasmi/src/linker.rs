@@ -1,6 +1,10 @@
+#[cfg(feature = "resumable")]
+use crate::GuestYield;
 use crate::{
-    func::{FuncEntity, HostFuncEntity, HostFuncTrampolineEntity},
+    engine::{Intrinsic, BUILTINS_MODULE, INTRINSICS_MODULE},
+    func::{BatchCall, FuncEntity, HostFuncEntity, HostFuncTrampolineEntity, HostInterceptor},
     module::{ImportName, ImportType},
+    value::WithType,
     AsContext,
     AsContextMut,
     Caller,
@@ -11,15 +15,20 @@ use crate::{
     Func,
     FuncType,
     GlobalType,
+    HostFuncInfo,
+    HostFuncNext,
     InstancePre,
     IntoFunc,
     MemoryType,
     Module,
+    RawHostFunc,
+    StoreContextMut,
     TableType,
     Value,
 };
 use alloc::{
     collections::{btree_map::Entry, BTreeMap},
+    format,
     sync::Arc,
     vec::Vec,
 };
@@ -90,6 +99,18 @@ pub enum LinkerError {
         /// The mismatching [`GlobalType`] found.
         found: GlobalType,
     },
+    /// Encountered when a [`Store`] tied definition is used for pre-instantiation.
+    ///
+    /// Only host functions defined via [`Linker::func_new`] or [`Linker::func_wrap`]
+    /// are [`Store`] independent and can be used by [`Linker::instantiate_pre`].
+    ///
+    /// [`Store`]: crate::Store
+    StoreTiedDefinition {
+        /// The name of the import with the [`Store`] tied definition.
+        ///
+        /// [`Store`]: crate::Store
+        name: ImportName,
+    },
 }
 
 impl LinkerError {
@@ -145,6 +166,15 @@ impl LinkerError {
             found: *found,
         }
     }
+
+    /// Create a new [`LinkerError`] for when an imported definition is tied to a [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    fn store_tied_definition(import: &ImportType) -> Self {
+        Self::StoreTiedDefinition {
+            name: import.import_name().clone(),
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -206,6 +236,12 @@ impl Display for LinkerError {
                     expected {expected:?} but found {found:?}",
                 )
             }
+            Self::StoreTiedDefinition { name } => {
+                write!(
+                    f,
+                    "cannot pre-instantiate import {name} since its definition is tied to a store"
+                )
+            }
         }
     }
 }
@@ -347,21 +383,22 @@ impl<T> Definition<T> {
     ///   defined host function.
     /// - This unifies handling of [`Definition::Extern(Extern::Func)`] and
     ///   [`Definition::HostFunc`].
-    pub fn as_func(&self, mut ctx: impl AsContextMut<UserState = T>) -> Option<Func> {
+    /// - The `interceptor` is called around calls to [`Definition::HostFunc`]
+    ///   and is described by its [`HostFuncInfo`] if any.
+    pub fn as_func(
+        &self,
+        ctx: impl AsContextMut<UserState = T>,
+        interceptor: Option<(&Arc<HostInterceptor>, HostFuncInfo)>,
+    ) -> Option<Func> {
         match self {
             Definition::Extern(Extern::Func(func)) => Some(*func),
             Definition::HostFunc(host_func) => {
-                let trampoline = ctx
-                    .as_context_mut()
-                    .store
-                    .alloc_trampoline(host_func.trampoline().clone());
-                let ty_dedup = host_func.ty_dedup();
-                let entity = HostFuncEntity::new(*ty_dedup, trampoline);
-                let func = ctx
-                    .as_context_mut()
-                    .store
-                    .inner
-                    .alloc_func(FuncEntity::Host(entity));
+                let func = match interceptor {
+                    Some((interceptor, info)) => {
+                        alloc_host_func(ctx, &host_func.intercept(info, interceptor.clone()))
+                    }
+                    None => alloc_host_func(ctx, host_func),
+                };
                 Some(func)
             }
             _ => None,
@@ -369,6 +406,53 @@ impl<T> Definition<T> {
     }
 }
 
+/// Allocates a new host [`Func`] for the [`Linker`] defined `host_func` on `ctx`.
+fn alloc_host_func<T>(
+    mut ctx: impl AsContextMut<UserState = T>,
+    host_func: &HostFuncTrampolineEntity<T>,
+) -> Func {
+    let trampoline = ctx
+        .as_context_mut()
+        .store
+        .alloc_trampoline(host_func.trampoline().clone());
+    let entity = HostFuncEntity::new(*host_func.ty_dedup(), trampoline).with_raw(host_func.raw());
+    ctx.as_context_mut()
+        .store
+        .inner
+        .alloc_func(FuncEntity::Host(entity))
+}
+
+/// Enters the tracing span of the instantiation of `module`.
+///
+/// Returns the entered span and the start time of the import resolution.
+///
+/// # Note
+///
+/// The start time is `None` if the [`Engine`] must not read the wall clock
+/// due to [`Config::deterministic_only`].
+///
+/// [`Config::deterministic_only`]: crate::Config::deterministic_only
+#[cfg(feature = "tracing")]
+fn enter_instantiate_span(
+    module: &Module,
+) -> (tracing::span::EnteredSpan, Option<std::time::Instant>) {
+    let span = tracing::info_span!(
+        "instantiate",
+        num_imports = module.imports().len(),
+        import_resolution_ns = tracing::field::Empty,
+    )
+    .entered();
+    let deterministic = module.engine().config().get_deterministic_only();
+    (span, (!deterministic).then(std::time::Instant::now))
+}
+
+/// Records the duration of the import resolution started at `started` into `span`.
+#[cfg(feature = "tracing")]
+fn record_import_resolution(span: &tracing::Span, started: Option<std::time::Instant>) {
+    let duration = started.map(|started| started.elapsed()).unwrap_or_default();
+    span.record("import_resolution_ns", duration.as_nanos() as u64);
+}
+
 /// [`Debug`]-wrapper for the definitions of a [`Linker`].
 pub struct DebugDefinitions<'a, T> {
     /// The [`Engine`] of the [`Linker`].
@@ -428,6 +512,13 @@ impl<'a, T> Debug for DebugHostFuncEntity<'a, T> {
     }
 }
 
+/// Handler called upon instantiation for imports without definition in a [`Linker`].
+///
+/// Receives the module name, item name and type of the import and returns
+/// the [`Extern`] to use for the import or `None` to leave it undefined.
+type UnknownImportsHandler<T> =
+    dyn Fn(StoreContextMut<T>, &str, &str, &ExternType) -> Option<Extern> + Send + Sync;
+
 /// A linker used to define module imports and instantiate module instances.
 pub struct Linker<T> {
     /// The underlying [`Engine`] for the [`Linker`].
@@ -441,6 +532,10 @@ pub struct Linker<T> {
     strings: StringInterner,
     /// Stores the definitions given their names.
     definitions: BTreeMap<ImportKey, Definition<T>>,
+    /// The optional interceptor called around all [`Linker`] defined host functions.
+    interceptor: Option<Arc<HostInterceptor>>,
+    /// The optional handler called for imports without definition upon instantiation.
+    unknown_imports: Option<Arc<UnknownImportsHandler<T>>>,
 }
 
 impl<T> Debug for Linker<T> {
@@ -458,6 +553,8 @@ impl<T> Clone for Linker<T> {
             engine: self.engine.clone(),
             strings: self.strings.clone(),
             definitions: self.definitions.clone(),
+            interceptor: self.interceptor.clone(),
+            unknown_imports: self.unknown_imports.clone(),
         }
     }
 }
@@ -475,6 +572,8 @@ impl<T> Linker<T> {
             engine: engine.clone(),
             strings: StringInterner::default(),
             definitions: BTreeMap::default(),
+            interceptor: None,
+            unknown_imports: None,
         }
     }
 
@@ -522,6 +621,59 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Creates a new named raw host [`Func`] of type `ty` for this [`Linker`].
+    ///
+    /// Raw host functions operate directly on the registers holding the parameters
+    /// and results of their call. When called from Wasm they are called without leaving
+    /// the execution of the calling Wasm function and without any conversions.
+    /// This is meant for extremely hot host calls that perform very little work.
+    ///
+    /// `func` is called with a pointer to a buffer of `len` [`UntypedValue`] where
+    /// `len` is the maximum of the number of parameters and results of `ty`.
+    /// Upon the call the buffer starts with the parameters of `ty`.
+    /// Upon returning `Ok` the buffer is expected to start with the results of `ty`.
+    /// The buffer is invalid after `func` returned and must not be retained.
+    ///
+    /// # Note
+    ///
+    /// - Raw host functions have no access to the [`Store`] or its data.
+    /// - A returned [`TrapCode`] traps the execution and cannot be resumed.
+    /// - Raw host functions are called through a regular trampoline when called
+    ///   from the host side or when intercepted via [`Linker::set_interceptor`].
+    ///
+    /// # Safety
+    ///
+    /// `func` must uphold all of the following:
+    ///
+    /// - It only accesses the buffer at indices below `len`.
+    /// - It reads parameters and writes results according to their types in `ty`.
+    ///   Writing a value that is invalid for its result type, for example an invalid
+    ///   `funcref` or `externref`, is undefined behavior.
+    /// - It writes all results of `ty` before returning `Ok`.
+    /// - It does not reenter the Wasmi [`Engine`], for example by calling a [`Func`]
+    ///   through a [`Store`] captured by other means, while it accesses the buffer.
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the same name for this [`Linker`].
+    ///
+    /// [`UntypedValue`]: crate::core::UntypedValue
+    /// [`TrapCode`]: crate::core::TrapCode
+    /// [`Store`]: crate::Store
+    pub unsafe fn func_wrap_raw(
+        &mut self,
+        module: &str,
+        name: &str,
+        ty: FuncType,
+        func: RawHostFunc,
+    ) -> Result<&mut Self, LinkerError> {
+        // Safety: The caller upholds the safety contract of `func`.
+        let func = unsafe { HostFuncTrampolineEntity::new_raw(&self.engine, ty, func) };
+        let key = self.import_key(module, name);
+        self.insert(key, Definition::HostFunc(func))?;
+        Ok(self)
+    }
+
     /// Creates a new named [`Func::new`]-style host [`Func`] for this [`Linker`].
     ///
     /// For information how to use this API see [`Func::wrap`].
@@ -555,6 +707,178 @@ impl<T> Linker<T> {
         Ok(self)
     }
 
+    /// Defines the Wasmi intrinsic functions under the reserved `"wasmi_intrinsics"` module name.
+    ///
+    /// The defined intrinsic functions compute signed integer arithmetic
+    /// and trap with [`TrapCode::IntegerOverflow`] upon overflow:
+    ///
+    /// - `i32.checked_add`, `i32.checked_sub` and `i32.checked_mul` of type `[i32, i32] -> [i32]`
+    /// - `i64.checked_add`, `i64.checked_sub` and `i64.checked_mul` of type `[i64, i64] -> [i64]`
+    ///
+    /// # Note
+    ///
+    /// Wasm `call` instructions to imported intrinsic functions with matching signatures are
+    /// translated to dedicated Wasmi instructions that execute without host function call overhead.
+    /// Those calls are therefore not intercepted via [`Linker::set_interceptor`].
+    /// The host functions defined here implement the same semantics and are used for all
+    /// other calls, for example via `call_indirect` or [`Func::call`].
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the name of an intrinsic function for this [`Linker`].
+    ///
+    /// [`TrapCode::IntegerOverflow`]: crate::core::TrapCode::IntegerOverflow
+    pub fn define_intrinsics(&mut self) -> Result<&mut Self, LinkerError> {
+        for &intrinsic in Intrinsic::ALL {
+            let func_type = intrinsic.func_type();
+            let result_type = func_type.results()[0];
+            self.func_new(
+                INTRINSICS_MODULE,
+                intrinsic.name(),
+                func_type,
+                move |_caller, params, results| {
+                    let value =
+                        intrinsic.eval(params[0].clone().into(), params[1].clone().into())?;
+                    results[0] = value.with_type(result_type);
+                    Ok(())
+                },
+            )?;
+        }
+        Ok(self)
+    }
+
+    /// Defines the Wasmi builtin functions enabled by the [`Config`] of the [`Engine`]
+    /// under the reserved `"wasmi"` module name.
+    ///
+    /// The following builtin functions are defined if enabled:
+    ///
+    /// - `yield` of type `[i32] -> []` via [`Config::builtin_yield`]: suspends the
+    ///   resumable call with a [`GuestYield`] carrying the `i32` parameter as user tag.
+    /// - `batch_call` of type `[i32 i32 i32] -> []` via [`Config::builtin_batch_call`]:
+    ///   dispatches a batch of host function calls described in linear memory.
+    ///
+    /// # Errors
+    ///
+    /// If there already is a definition under the name of an enabled builtin function for this [`Linker`].
+    ///
+    /// [`Config`]: crate::Config
+    /// [`Config::builtin_yield`]: crate::Config::builtin_yield
+    /// [`Config::builtin_batch_call`]: crate::Config::builtin_batch_call
+    /// [`GuestYield`]: crate::GuestYield
+    pub fn define_builtins(&mut self) -> Result<&mut Self, LinkerError> {
+        #[cfg(feature = "resumable")]
+        if self.engine.config().get_builtin_yield() {
+            self.func_wrap(
+                BUILTINS_MODULE,
+                GuestYield::NAME,
+                |tag: i32| -> Result<(), Error> { Err(Error::host(GuestYield::new(tag))) },
+            )?;
+        }
+        if self.engine.config().get_builtin_batch_call() {
+            self.func_wrap(
+                BUILTINS_MODULE,
+                BatchCall::NAME,
+                |caller: Caller<T>, records: u32, len: u32, results: u32| {
+                    BatchCall::call(caller, records, len, results)
+                },
+            )?;
+        }
+        Ok(self)
+    }
+
+    /// Sets the `interceptor` that is called around host function calls.
+    ///
+    /// The `interceptor` is called with the [`HostFuncInfo`] of the called host function,
+    /// its parameters, the intercepted host function itself and the buffer for its results.
+    /// It may call the intercepted host function any number of times, for example with
+    /// altered parameters, or deny the call entirely by returning an error without calling
+    /// the intercepted host function in which case the calling Wasm function traps.
+    ///
+    /// # Note
+    ///
+    /// - Only host functions defined via [`Linker::func_new`] or [`Linker::func_wrap`]
+    ///   are intercepted. Functions defined via [`Linker::define`] are not.
+    /// - This replaces the previous interceptor if any.
+    /// - The interceptor is applied to host functions upon instantiation and therefore
+    ///   does not affect instances that have already been instantiated.
+    pub fn set_interceptor(
+        &mut self,
+        interceptor: impl Fn(&HostFuncInfo, &[Value], &mut HostFuncNext, &mut [Value]) -> Result<(), Error>
+            + Send
+            + Sync
+            + 'static,
+    ) -> &mut Self {
+        self.interceptor = Some(Arc::new(interceptor));
+        self
+    }
+
+    /// Sets the `handler` that is called for imports without definition upon instantiation.
+    ///
+    /// The `handler` is called with the module name, item name and type of the import.
+    /// It either returns the [`Extern`] to use for the import, for example a host function
+    /// created via [`Func::new`] or a memory, table or global synthesized for the import type,
+    /// or `None` in which case the instantiation fails with a missing definition error.
+    ///
+    /// # Note
+    ///
+    /// - The returned [`Extern`] must satisfy the import type just like other definitions.
+    /// - This replaces the previous handler if any.
+    /// - Since the `handler` creates [`Store`] tied items it is not called by
+    ///   [`Linker::instantiate_pre`].
+    ///
+    /// [`Store`]: crate::Store
+    pub fn define_unknown_imports_with(
+        &mut self,
+        handler: impl Fn(StoreContextMut<T>, &str, &str, &ExternType) -> Option<Extern>
+            + Send
+            + Sync
+            + 'static,
+    ) -> &mut Self {
+        self.unknown_imports = Some(Arc::new(handler));
+        self
+    }
+
+    /// Defines all function imports of `module` that have no definition as trapping host functions.
+    ///
+    /// Calling any of the defined host functions returns an error naming its import.
+    ///
+    /// # Note
+    ///
+    /// Imports of other kinds than functions are not defined by this method.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `module` are not the same.
+    pub fn define_unknown_imports_as_traps(
+        &mut self,
+        module: &Module,
+    ) -> Result<&mut Self, LinkerError> {
+        assert!(Engine::same(self.engine(), module.engine()));
+        for import in module.imports() {
+            let ExternType::Func(func_type) = import.ty() else {
+                continue;
+            };
+            if self
+                .resolve_definition(import.module(), import.name())
+                .is_some()
+            {
+                continue;
+            }
+            let import_name = import.import_name().clone();
+            self.func_new(
+                import.module(),
+                import.name(),
+                func_type.clone(),
+                move |_caller, _params, _results| {
+                    Err(Error::new(format!(
+                        "called unknown import {import_name} that has been defined as trap"
+                    )))
+                },
+            )?;
+        }
+        Ok(self)
+    }
+
     /// Returns the import key for the module name and item name.
     fn import_key(&mut self, module: &str, name: &str) -> ImportKey {
         ImportKey {
@@ -628,6 +952,13 @@ impl<T> Linker<T> {
             context.as_context().store.engine(),
             self.engine()
         ));
+        self.resolve_definition(module, name)
+    }
+
+    /// Looks up a [`Definition`] by name in this [`Linker`] without a store context.
+    ///
+    /// Returns `None` if this name was not previously defined in this [`Linker`].
+    fn resolve_definition(&self, module: &str, name: &str) -> Option<&Definition<T>> {
         let key = ImportKey {
             module: self.strings.get(module)?,
             name: self.strings.get(name)?,
@@ -643,6 +974,7 @@ impl<T> Linker<T> {
     ///
     /// # Errors
     ///
+    /// - If the [`Module`] has been compiled by another [`Engine`] than the one of `context`.
     /// - If the linker does not define imports of the instantiated [`Module`].
     /// - If any imported item does not satisfy its type requirements.
     pub fn instantiate(
@@ -651,15 +983,96 @@ impl<T> Linker<T> {
         module: &Module,
     ) -> Result<InstancePre, Error> {
         assert!(Engine::same(self.engine(), context.as_context().engine()));
+        module.check_engine(self.engine())?;
+        #[cfg(feature = "tracing")]
+        let (span, started) = enter_instantiate_span(module);
         // TODO: possibly add further resource limtation here on number of externals.
         // Not clear that user can't import the same external lots of times to inflate this.
         let externals = module
             .imports()
             .map(|import| self.process_import(&mut context, import))
             .collect::<Result<Vec<Extern>, Error>>()?;
+        #[cfg(feature = "tracing")]
+        record_import_resolution(&span, started);
         module.instantiate(context, externals)
     }
 
+    /// Resolves and type checks the imports of the given [`Module`] once for many instantiations.
+    ///
+    /// The returned [`PreparedInstance`] is cheap to clone and can instantiate the [`Module`]
+    /// into any [`Store`] of the same [`Engine`] via [`PreparedInstance::instantiate`]
+    /// without resolving its imports again.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`Linker`] and `module` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// - If the linker does not define imports of the [`Module`].
+    /// - If any imported item does not satisfy its type requirements.
+    /// - If any imported item is defined via [`Linker::define`] since those definitions
+    ///   are tied to a specific [`Store`].
+    ///
+    /// [`Store`]: crate::Store
+    pub fn instantiate_pre(&self, module: &Module) -> Result<PreparedInstance<T>, Error> {
+        assert!(Engine::same(self.engine(), module.engine()));
+        let funcs = module
+            .imports()
+            .map(|import| self.prepare_import(import))
+            .collect::<Result<Arc<[_]>, Error>>()?;
+        Ok(PreparedInstance {
+            module: module.clone(),
+            funcs,
+        })
+    }
+
+    /// Resolves and type checks a single [`Module`] import for [`Linker::instantiate_pre`].
+    ///
+    /// Returns the resolved host function with the interceptor of the [`Linker`] applied if any.
+    ///
+    /// # Errors
+    ///
+    /// - If the imported item does not satisfy constraints set by the [`Module`].
+    /// - If the imported item is not a [`Store`] independent host function.
+    ///
+    /// [`Store`]: crate::Store
+    fn prepare_import(&self, import: ImportType) -> Result<HostFuncTrampolineEntity<T>, Error> {
+        let module_name = import.module();
+        let field_name = import.name();
+        let host_func = match self.resolve_definition(module_name, field_name) {
+            Some(Definition::HostFunc(host_func)) => host_func,
+            Some(Definition::Extern(_)) => {
+                return Err(Error::from(LinkerError::store_tied_definition(&import)))
+            }
+            None => return Err(Error::from(LinkerError::missing_definition(&import))),
+        };
+        let found_type = self
+            .engine
+            .resolve_func_type(host_func.ty_dedup(), FuncType::clone);
+        let ExternType::Func(expected_type) = import.ty() else {
+            return Err(Error::from(LinkerError::invalid_type_definition(
+                &import,
+                &ExternType::Func(found_type),
+            )));
+        };
+        if &found_type != expected_type {
+            return Err(Error::from(LinkerError::func_type_mismatch(
+                import.import_name(),
+                expected_type,
+                &found_type,
+            )));
+        }
+        let host_func = match &self.interceptor {
+            Some(interceptor) => {
+                let info = HostFuncInfo::new(module_name, field_name, found_type);
+                host_func.intercept(info, interceptor.clone())
+            }
+            None => host_func.clone(),
+        };
+        Ok(host_func)
+    }
+
     /// Processes a single [`Module`] import.
     ///
     /// # Panics
@@ -678,9 +1091,21 @@ impl<T> Linker<T> {
         let import_name = import.import_name();
         let module_name = import.module();
         let field_name = import.name();
-        let resolved = self
-            .get_definition(context.as_context(), module_name, field_name)
-            .ok_or_else(|| LinkerError::missing_definition(&import))?;
+        let synthesized;
+        let resolved = match self.get_definition(context.as_context(), module_name, field_name) {
+            Some(resolved) => resolved,
+            None => {
+                let item = self
+                    .unknown_imports
+                    .as_ref()
+                    .and_then(|handler| {
+                        handler(context.as_context_mut(), module_name, field_name, import.ty())
+                    })
+                    .ok_or_else(|| LinkerError::missing_definition(&import))?;
+                synthesized = Definition::Extern(item);
+                &synthesized
+            }
+        };
         let invalid_type = || LinkerError::invalid_type_definition(&import, &resolved.ty(&context));
         match import.ty() {
             ExternType::Func(expected_type) => {
@@ -696,8 +1121,12 @@ impl<T> Linker<T> {
                         &found_type,
                     )));
                 }
+                let interceptor = self.interceptor.as_ref().map(|interceptor| {
+                    let info = HostFuncInfo::new(module_name, field_name, found_type);
+                    (interceptor, info)
+                });
                 let func = resolved
-                    .as_func(&mut context)
+                    .as_func(&mut context, interceptor)
                     .expect("already asserted that `resolved` is a function");
                 Ok(Extern::Func(func))
             }
@@ -719,7 +1148,7 @@ impl<T> Linker<T> {
                     .copied()
                     .and_then(Extern::into_memory)
                     .ok_or_else(invalid_type)?;
-                let found_type = memory.dynamic_ty(context);
+                let found_type = memory.ty(context);
                 found_type.is_subtype_or_err(expected_type).map_err(|_| {
                     LinkerError::invalid_memory_subtype(import_name, expected_type, &found_type)
                 })?;
@@ -745,6 +1174,74 @@ impl<T> Linker<T> {
     }
 }
 
+/// A [`Module`] with imports resolved and type checked by [`Linker::instantiate_pre`].
+///
+/// # Note
+///
+/// - Cloning a [`PreparedInstance`] is cheap.
+/// - Instantiating a [`PreparedInstance`] only allocates the host functions and
+///   entities of the [`Module`] and initializes its segments.
+pub struct PreparedInstance<T> {
+    /// The [`Module`] to instantiate.
+    module: Module,
+    /// The resolved host functions for all imports of the [`Module`] in order.
+    funcs: Arc<[HostFuncTrampolineEntity<T>]>,
+}
+
+impl<T> Debug for PreparedInstance<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PreparedInstance")
+            .field("module", &self.module)
+            .field("funcs", &self.funcs)
+            .finish()
+    }
+}
+
+impl<T> Clone for PreparedInstance<T> {
+    fn clone(&self) -> Self {
+        Self {
+            module: self.module.clone(),
+            funcs: self.funcs.clone(),
+        }
+    }
+}
+
+impl<T> PreparedInstance<T> {
+    /// Returns the [`Module`] of the [`PreparedInstance`].
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+
+    /// Instantiates the [`Module`] of the [`PreparedInstance`] into `context`.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Engine`] of the [`PreparedInstance`] and `context` are not the same.
+    ///
+    /// # Errors
+    ///
+    /// If the instantiation of the [`Module`] fails, for example due to resource limits.
+    pub fn instantiate(
+        &self,
+        mut context: impl AsContextMut<UserState = T>,
+    ) -> Result<InstancePre, Error> {
+        assert!(Engine::same(
+            self.module.engine(),
+            context.as_context().engine()
+        ));
+        #[cfg(feature = "tracing")]
+        let (span, started) = enter_instantiate_span(&self.module);
+        let externals = self
+            .funcs
+            .iter()
+            .map(|host_func| Extern::Func(alloc_host_func(&mut context, host_func)))
+            .collect::<Vec<Extern>>();
+        #[cfg(feature = "tracing")]
+        record_import_resolution(&span, started);
+        self.module.instantiate(context, externals)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use wasmi_core::ValueType;
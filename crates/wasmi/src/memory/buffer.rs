@@ -1,4 +1,4 @@
-use alloc::{vec, vec::Vec};
+use alloc::{collections::TryReserveError, vec, vec::Vec};
 
 /// A `Vec`-based byte buffer implementation.
 ///
@@ -25,14 +25,31 @@ impl ByteBuffer {
         }
     }
 
-    /// Grows the byte buffer to the given `new_size`.
+    /// Tries to grow the byte buffer to the given `new_size`.
+    ///
+    /// # Errors
+    ///
+    /// If the allocation of the additional bytes failed.
+    /// In this case the [`ByteBuffer`] is left unchanged.
     ///
     /// # Panics
     ///
     /// If the current size of the [`ByteBuffer`] is larger than `new_size`.
-    pub fn grow(&mut self, new_size: usize) {
+    pub fn try_grow(&mut self, new_size: usize) -> Result<(), TryReserveError> {
         assert!(new_size >= self.len());
+        self.bytes.try_reserve_exact(new_size - self.len())?;
         self.bytes.resize(new_size, 0x00_u8);
+        Ok(())
+    }
+
+    /// Replaces the contents of the byte buffer with `bytes`.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`ByteBuffer::try_grow`] this may also shrink the byte buffer.
+    pub fn restore(&mut self, bytes: &[u8]) {
+        self.bytes.clear();
+        self.bytes.extend_from_slice(bytes);
     }
 
     /// Returns the length of the byte buffer in bytes.
@@ -53,16 +53,22 @@ impl DataSegment {
 /// With the `bulk-memory` Wasm proposal it is possible to interact
 /// with data segments at runtime. Therefore Wasm instances now have
 /// a need to have an instantiated representation of data segments.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DataSegmentEntity {
     /// The underlying bytes of the instance data segment.
     ///
     /// # Note
     ///
     /// These bytes are just readable after instantiation.
-    /// Using Wasm `data.drop` simply replaces the instance
-    /// with an empty one.
+    /// Using Wasm `data.drop` marks them as dropped but keeps them around
+    /// since they are shared with the [`Module`] anyway. This allows to restore
+    /// the dropped state of the data segment via [`Store::restore`].
+    ///
+    /// [`Module`]: crate::Module
+    /// [`Store::restore`]: crate::Store::restore
     bytes: Option<Arc<[u8]>>,
+    /// Is `true` if the data segment has been dropped.
+    dropped: bool,
 }
 
 impl From<&'_ module::DataSegment> for DataSegmentEntity {
@@ -70,6 +76,7 @@ impl From<&'_ module::DataSegment> for DataSegmentEntity {
         match segment.kind() {
             module::DataSegmentKind::Passive => Self {
                 bytes: Some(segment.clone_bytes()),
+                dropped: false,
             },
             module::DataSegmentKind::Active(_) => Self::empty(),
         }
@@ -79,19 +86,38 @@ impl From<&'_ module::DataSegment> for DataSegmentEntity {
 impl DataSegmentEntity {
     /// Create an empty [`DataSegmentEntity`] representing dropped data segments.
     fn empty() -> Self {
-        Self { bytes: None }
+        Self {
+            bytes: None,
+            dropped: true,
+        }
     }
 
     /// Returns the bytes of the [`DataSegmentEntity`].
+    ///
+    /// Returns no bytes if the [`DataSegmentEntity`] has been dropped.
     pub fn bytes(&self) -> &[u8] {
-        self.bytes
-            .as_ref()
-            .map(|bytes| &bytes[..])
-            .unwrap_or_else(|| &[])
+        match &self.bytes {
+            Some(bytes) if !self.dropped => &bytes[..],
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` if the [`DataSegmentEntity`] has been dropped.
+    pub fn is_dropped(&self) -> bool {
+        self.dropped
+    }
+
+    /// Sets the dropped state of the [`DataSegmentEntity`] to `dropped`.
+    ///
+    /// # Note
+    ///
+    /// Data segments of active kind have no bytes even if they are no longer dropped.
+    pub fn set_dropped(&mut self, dropped: bool) {
+        self.dropped = dropped;
     }
 
     /// Drops the bytes of the [`DataSegmentEntity`].
     pub fn drop_bytes(&mut self) {
-        self.bytes = None;
+        self.dropped = true;
     }
 }
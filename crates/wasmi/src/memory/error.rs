@@ -22,6 +22,16 @@ pub enum MemoryError {
     },
     /// Tried to create too many memories
     TooManyMemories,
+    /// Tried to create a linear memory type with pages greater than 64 KiB.
+    InvalidPageSize {
+        /// The `log2` of the invalid page size.
+        page_size_log2: u8,
+    },
+    /// Tried to create a linear memory with a custom page size while
+    /// the [`custom-page-sizes`] Wasm proposal is disabled.
+    ///
+    /// [`custom-page-sizes`]: https://github.com/WebAssembly/custom-page-sizes
+    CustomPageSizesDisabled,
 }
 
 impl Display for MemoryError {
@@ -45,6 +55,12 @@ impl Display for MemoryError {
             Self::TooManyMemories => {
                 write!(f, "too many memories")
             }
+            Self::InvalidPageSize { page_size_log2 } => {
+                write!(f, "invalid memory page size of 2^{page_size_log2} bytes")
+            }
+            Self::CustomPageSizesDisabled => {
+                write!(f, "custom memory page sizes are disabled")
+            }
         }
     }
 }
@@ -0,0 +1,169 @@
+use super::{Memory, MemoryError};
+use crate::{AsContext, AsContextMut, Error};
+use core::{fmt, fmt::Display};
+use std::io::{self, Read, Write};
+
+/// The maximum number of bytes transferred by a single `read` or `write` call.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// An I/O error that occurred while streaming data into or out of a [`Memory`].
+///
+/// # Note
+///
+/// Streaming is not atomic. The first [`MemoryIoError::transferred`] bytes
+/// of the requested range have already been transferred when this error occurs.
+#[derive(Debug)]
+pub struct MemoryIoError {
+    /// The number of bytes transferred before the error occurred.
+    transferred: u64,
+    /// The underlying I/O error.
+    source: io::Error,
+}
+
+impl MemoryIoError {
+    /// Creates a new [`MemoryIoError`] after `transferred` bytes.
+    fn new(transferred: usize, source: io::Error) -> Self {
+        Self {
+            transferred: transferred as u64,
+            source,
+        }
+    }
+
+    /// Returns the number of bytes transferred before the error occurred.
+    pub fn transferred(&self) -> u64 {
+        self.transferred
+    }
+
+    /// Returns the underlying I/O error.
+    pub fn io_error(&self) -> &io::Error {
+        &self.source
+    }
+
+    /// Consumes `self` and returns the underlying I/O error.
+    pub fn into_io_error(self) -> io::Error {
+        self.source
+    }
+}
+
+impl Display for MemoryIoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "memory I/O failed after {} bytes: {}",
+            self.transferred, self.source
+        )
+    }
+}
+
+impl std::error::Error for MemoryIoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Returns the range of `len` bytes at `offset` if it is within the `memory` bounds.
+fn checked_range(memory: &[u8], offset: u64, len: u64) -> Result<(usize, usize), MemoryError> {
+    let start = usize::try_from(offset).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+    let len = usize::try_from(len).map_err(|_| MemoryError::OutOfBoundsAccess)?;
+    let end = start
+        .checked_add(len)
+        .filter(|&end| end <= memory.len())
+        .ok_or(MemoryError::OutOfBoundsAccess)?;
+    Ok((start, end))
+}
+
+impl Memory {
+    /// Streams `len` bytes from `reader` into `memory[offset..offset+len]`.
+    ///
+    /// Returns the number of bytes written which is always `len` upon success.
+    ///
+    /// # Note
+    ///
+    /// - The bytes are read directly into the linear memory in chunks
+    ///   without buffering the whole range.
+    /// - Bounds are checked once before reading anything from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// - If `memory[offset..offset+len]` is out of bounds.
+    ///   In this case nothing is read from `reader` and the [`Memory`] is not modified.
+    /// - If `reader` fails or ends before `len` bytes have been read.
+    ///   In this case the bytes read so far have already been written to the
+    ///   [`Memory`] and their number is reported by [`MemoryIoError::transferred`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn write_from(
+        &self,
+        mut ctx: impl AsContextMut,
+        offset: u64,
+        reader: &mut impl Read,
+        len: u64,
+    ) -> Result<u64, Error> {
+        let data = self.data_mut(ctx.as_context_mut());
+        let (start, end) = checked_range(data, offset, len)?;
+        let dst = &mut data[start..end];
+        let mut written = 0;
+        while written < dst.len() {
+            let chunk_end = dst.len().min(written + CHUNK_SIZE);
+            match reader.read(&mut dst[written..chunk_end]) {
+                Ok(0) => {
+                    let error = io::Error::from(io::ErrorKind::UnexpectedEof);
+                    return Err(MemoryIoError::new(written, error).into());
+                }
+                Ok(n) => written += n,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(MemoryIoError::new(written, error).into()),
+            }
+        }
+        Ok(len)
+    }
+
+    /// Streams `len` bytes from `memory[offset..offset+len]` into `writer`.
+    ///
+    /// Returns the number of bytes read which is always `len` upon success.
+    ///
+    /// # Note
+    ///
+    /// - The bytes are written directly from the linear memory in chunks
+    ///   without buffering the whole range.
+    /// - Bounds are checked once before writing anything to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// - If `memory[offset..offset+len]` is out of bounds.
+    ///   In this case nothing is written to `writer`.
+    /// - If `writer` fails or stops accepting bytes before `len` bytes have been written.
+    ///   In this case the bytes written so far have already been passed to `writer`
+    ///   and their number is reported by [`MemoryIoError::transferred`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn read_into(
+        &self,
+        ctx: impl AsContext,
+        offset: u64,
+        writer: &mut impl Write,
+        len: u64,
+    ) -> Result<u64, Error> {
+        let data = self.data(ctx.as_context());
+        let (start, end) = checked_range(data, offset, len)?;
+        let src = &data[start..end];
+        let mut read = 0;
+        while read < src.len() {
+            let chunk_end = src.len().min(read + CHUNK_SIZE);
+            match writer.write(&src[read..chunk_end]) {
+                Ok(0) => {
+                    let error = io::Error::from(io::ErrorKind::WriteZero);
+                    return Err(MemoryIoError::new(read, error).into());
+                }
+                Ok(n) => read += n,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(MemoryIoError::new(read, error).into()),
+            }
+        }
+        Ok(len)
+    }
+}
@@ -1,20 +1,30 @@
 mod buffer;
 mod data;
 mod error;
+#[cfg(feature = "std")]
+mod io;
+mod pod;
+mod view;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "std")]
+pub use self::io::MemoryIoError;
 use self::buffer::ByteBuffer;
 pub use self::{
     data::{DataSegment, DataSegmentEntity, DataSegmentIdx},
     error::MemoryError,
+    pod::Pod,
+    view::{MemoryView, MemoryViewMut},
 };
 use super::{AsContext, AsContextMut, StoreContext, StoreContextMut, Stored};
 use crate::{
-    error::EntityGrowError,
+    error::{EntityGrowError, GrowError},
     store::{Fuel, ResourceLimiterRef},
+    Instance,
 };
+use alloc::vec::Vec;
 use wasmi_arena::ArenaIndex;
 use wasmi_core::{Pages, TrapCode};
 
@@ -40,6 +50,84 @@ impl ArenaIndex for MemoryIdx {
 pub struct MemoryType {
     initial_pages: Pages,
     maximum_pages: Option<Pages>,
+    page_size_log2: u8,
+}
+
+/// A builder for [`MemoryType`]s.
+///
+/// Constructed via [`MemoryType::builder`].
+#[derive(Debug, Copy, Clone)]
+pub struct MemoryTypeBuilder {
+    minimum: u32,
+    maximum: Option<u32>,
+    page_size_log2: u8,
+}
+
+impl Default for MemoryTypeBuilder {
+    fn default() -> Self {
+        Self {
+            minimum: 0,
+            maximum: None,
+            page_size_log2: Pages::DEFAULT_PAGE_SIZE_LOG2,
+        }
+    }
+}
+
+impl MemoryTypeBuilder {
+    /// Sets the minimum amount of pages of the [`MemoryType`].
+    ///
+    /// Defaults to 0.
+    pub fn min(&mut self, minimum: u32) -> &mut Self {
+        self.minimum = minimum;
+        self
+    }
+
+    /// Sets the optional maximum amount of pages of the [`MemoryType`].
+    ///
+    /// Defaults to `None`.
+    pub fn max(&mut self, maximum: Option<u32>) -> &mut Self {
+        self.maximum = maximum;
+        self
+    }
+
+    /// Sets the byte size of the pages of the [`MemoryType`] to `2^page_size_log2`.
+    ///
+    /// # Note
+    ///
+    /// - Defaults to 16, the 64 KiB pages mandated by the WebAssembly specification.
+    /// - Other page sizes are defined by the [`custom-page-sizes`] Wasm proposal
+    ///   and require [`Config::wasm_custom_page_sizes`] for creating a [`Memory`].
+    ///
+    /// [`custom-page-sizes`]: https://github.com/WebAssembly/custom-page-sizes
+    /// [`Config::wasm_custom_page_sizes`]: crate::Config::wasm_custom_page_sizes
+    pub fn page_size_log2(&mut self, page_size_log2: u8) -> &mut Self {
+        self.page_size_log2 = page_size_log2;
+        self
+    }
+
+    /// Builds the [`MemoryType`] from the configured values.
+    ///
+    /// # Errors
+    ///
+    /// - If the page size is greater than 64 KiB.
+    /// - If the minimum or maximum amount of pages exceeds the limits for the page size.
+    ///   The byte size of a linear memory cannot exceed 4 GiB.
+    pub fn build(&self) -> Result<MemoryType, MemoryError> {
+        let page_size_log2 = self.page_size_log2;
+        if page_size_log2 > Pages::DEFAULT_PAGE_SIZE_LOG2 {
+            return Err(MemoryError::InvalidPageSize { page_size_log2 });
+        }
+        let new_pages = |amount| {
+            Pages::new_for_page_size(amount, page_size_log2).ok_or(MemoryError::InvalidMemoryType)
+        };
+        let initial_pages = new_pages(self.minimum)?;
+        let maximum_pages = self.maximum.map(new_pages).transpose()?;
+        Ok(MemoryType {
+            initial_pages,
+            maximum_pages,
+            page_size_log2,
+        })
+    }
 }
 
 impl MemoryType {
@@ -50,17 +138,35 @@ impl MemoryType {
     /// If the linear memory type initial or maximum size exceeds the
     /// maximum limits of 2^16 pages.
     pub fn new(initial: u32, maximum: Option<u32>) -> Result<Self, MemoryError> {
-        let initial_pages = Pages::new(initial).ok_or(MemoryError::InvalidMemoryType)?;
-        let maximum_pages = match maximum {
-            Some(maximum) => Pages::new(maximum)
-                .ok_or(MemoryError::InvalidMemoryType)?
-                .into(),
-            None => None,
-        };
-        Ok(Self {
-            initial_pages,
-            maximum_pages,
-        })
+        Self::builder().min(initial).max(maximum).build()
+    }
+
+    /// Returns a [`MemoryTypeBuilder`] to construct a [`MemoryType`].
+    pub fn builder() -> MemoryTypeBuilder {
+        MemoryTypeBuilder::default()
+    }
+
+    /// Returns the `log2` of the byte size of the pages of the memory type.
+    pub fn page_size_log2(self) -> u8 {
+        self.page_size_log2
+    }
+
+    /// Returns the byte size of the pages of the memory type.
+    pub fn page_size(self) -> u32 {
+        1 << self.page_size_log2
+    }
+
+    /// Returns the maximum pages of the memory type or the absolute maximum for its page size.
+    pub(crate) fn maximum_pages_or_max(self) -> Pages {
+        self.maximum_pages()
+            .unwrap_or_else(|| Pages::max_for_page_size(self.page_size_log2))
+    }
+
+    /// Returns the amount of bytes of `pages` with the page size of the memory type.
+    ///
+    /// Returns `None` if the amount of bytes cannot be represented on the executing platform.
+    pub(crate) fn pages_to_bytes(self, pages: Pages) -> Option<usize> {
+        pages.to_bytes_for_page_size(self.page_size_log2)
     }
 
     /// Returns the initial pages of the memory type.
@@ -73,7 +179,7 @@ impl MemoryType {
     /// # Note
     ///
     /// - Returns `None` if there is no limit set.
-    /// - Maximum memory size cannot exceed `65536` pages or 4GiB.
+    /// - Maximum memory size cannot exceed 4GiB.
     pub fn maximum_pages(self) -> Option<Pages> {
         self.maximum_pages
     }
@@ -91,6 +197,7 @@ impl MemoryType {
     ///
     /// - If the `minimum` size of `self` is less than or equal to the `minimum` size of `other`.
     /// - If the `maximum` size of `self` is greater than the `maximum` size of `other`.
+    /// - If the page sizes of `self` and `other` differ.
     pub(crate) fn is_subtype_or_err(&self, other: &MemoryType) -> Result<(), MemoryError> {
         match self.is_subtype_of(other) {
             true => Ok(()),
@@ -110,6 +217,9 @@ impl MemoryType {
     /// [import subtyping]:
     /// https://webassembly.github.io/spec/core/valid/types.html#import-subtyping
     pub(crate) fn is_subtype_of(&self, other: &MemoryType) -> bool {
+        if self.page_size_log2() != other.page_size_log2() {
+            return false;
+        }
         if self.initial_pages() < other.initial_pages() {
             return false;
         }
@@ -127,6 +237,8 @@ pub struct MemoryEntity {
     bytes: ByteBuffer,
     memory_type: MemoryType,
     current_pages: Pages,
+    /// Is `true` if write watchpoints are registered for the linear memory.
+    watched: bool,
 }
 
 impl MemoryEntity {
@@ -136,9 +248,9 @@ impl MemoryEntity {
         limiter: &mut ResourceLimiterRef<'_>,
     ) -> Result<Self, MemoryError> {
         let initial_pages = memory_type.initial_pages();
-        let initial_len = initial_pages.to_bytes();
-        let maximum_pages = memory_type.maximum_pages().unwrap_or_else(Pages::max);
-        let maximum_len = maximum_pages.to_bytes();
+        let initial_len = memory_type.pages_to_bytes(initial_pages);
+        let maximum_pages = memory_type.maximum_pages_or_max();
+        let maximum_len = memory_type.pages_to_bytes(maximum_pages);
 
         if let Some(limiter) = limiter.as_resource_limiter() {
             if !limiter.memory_growing(0, initial_len.unwrap_or(usize::MAX), maximum_len)? {
@@ -154,6 +266,7 @@ impl MemoryEntity {
                 bytes: ByteBuffer::new(initial_len),
                 memory_type,
                 current_pages: initial_pages,
+                watched: false,
             };
             Ok(memory)
         } else {
@@ -177,9 +290,12 @@ impl MemoryEntity {
     /// This respects the current size of the [`MemoryEntity`] as
     /// its minimum size and is useful for import subtyping checks.
     pub fn dynamic_ty(&self) -> MemoryType {
-        let current_pages = self.current_pages().into();
-        let maximum_pages = self.ty().maximum_pages().map(Into::into);
-        MemoryType::new(current_pages, maximum_pages)
+        let ty = self.ty();
+        MemoryType::builder()
+            .min(self.current_pages().into())
+            .max(ty.maximum_pages().map(Into::into))
+            .page_size_log2(ty.page_size_log2())
+            .build()
             .unwrap_or_else(|_| panic!("must result in valid memory type due to invariants"))
     }
 
@@ -192,22 +308,33 @@ impl MemoryEntity {
     ///
     /// Returns the amount of pages before the operation upon success.
     ///
+    /// # Note
+    ///
+    /// The limits of the growth are checked before any `fuel` is consumed
+    /// and the consumed `fuel` is refunded if the allocation of the new pages fails.
+    ///
     /// # Errors
     ///
-    /// If the linear memory would grow beyond its maximum limit after
-    /// the grow operation.
+    /// - If the [`ResourceLimiter`] denies the grow operation.
+    /// - If the linear memory would grow beyond its maximum limit after
+    ///   the grow operation.
+    /// - If there is not enough `fuel` for the new pages.
+    /// - If the allocation of the new pages failed.
+    ///
+    /// [`ResourceLimiter`]: crate::ResourceLimiter
     pub fn grow(
         &mut self,
         additional: Pages,
-        fuel: Option<&mut Fuel>,
+        mut fuel: Option<&mut Fuel>,
         limiter: &mut ResourceLimiterRef<'_>,
     ) -> Result<Pages, EntityGrowError> {
         fn notify_limiter(
             limiter: &mut ResourceLimiterRef<'_>,
+            error: MemoryError,
             err: EntityGrowError,
         ) -> Result<Pages, EntityGrowError> {
             if let Some(limiter) = limiter.as_resource_limiter() {
-                limiter.memory_grow_failed(&MemoryError::OutOfBoundsGrowth)
+                limiter.memory_grow_failed(&error)
             }
             Err(err)
         }
@@ -218,52 +345,129 @@ impl MemoryEntity {
             return Ok(current_pages);
         }
 
-        let maximum_pages = self.ty().maximum_pages().unwrap_or_else(Pages::max);
-        let desired_pages = current_pages.checked_add(additional);
+        let ty = self.ty();
+        let maximum_pages = ty.maximum_pages_or_max();
+        let desired_pages = u32::from(current_pages)
+            .checked_add(u32::from(additional))
+            .and_then(|pages| Pages::new_for_page_size(pages, ty.page_size_log2()));
 
         // ResourceLimiter gets first look at the request.
         if let Some(limiter) = limiter.as_resource_limiter() {
-            let current_size = current_pages.to_bytes().unwrap_or(usize::MAX);
-            let desired_size = desired_pages
-                .unwrap_or_else(Pages::max)
-                .to_bytes()
-                .unwrap_or(usize::MAX);
-            let maximum_size = maximum_pages.to_bytes();
+            let current_size = ty.pages_to_bytes(current_pages).unwrap_or(usize::MAX);
+            let desired_pages =
+                desired_pages.unwrap_or_else(|| Pages::max_for_page_size(ty.page_size_log2()));
+            let desired_size = ty.pages_to_bytes(desired_pages).unwrap_or(usize::MAX);
+            let maximum_size = ty.pages_to_bytes(maximum_pages);
             match limiter.memory_growing(current_size, desired_size, maximum_size) {
                 Ok(true) => (),
-                Ok(false) => return Err(EntityGrowError::InvalidGrow),
+                Ok(false) => return Err(EntityGrowError::InvalidGrow(GrowError::Limiter)),
                 Err(_) => return Err(EntityGrowError::TrapCode(TrapCode::GrowthOperationLimited)),
             }
         }
 
+        let out_of_bounds = MemoryError::OutOfBoundsGrowth;
+        let exceeds_maximum = EntityGrowError::InvalidGrow(GrowError::Maximum);
         let Some(new_pages) = desired_pages else {
-            return notify_limiter(limiter, EntityGrowError::InvalidGrow);
+            return notify_limiter(limiter, out_of_bounds, exceeds_maximum);
         };
         if new_pages > maximum_pages {
-            return notify_limiter(limiter, EntityGrowError::InvalidGrow);
+            return notify_limiter(limiter, out_of_bounds, exceeds_maximum);
         }
-        let Some(new_size) = new_pages.to_bytes() else {
-            return notify_limiter(limiter, EntityGrowError::InvalidGrow);
+        let Some(new_size) = ty.pages_to_bytes(new_pages) else {
+            return notify_limiter(limiter, out_of_bounds, exceeds_maximum);
         };
-        if let Some(fuel) = fuel {
-            let additional_bytes = additional.to_bytes().unwrap_or(usize::MAX) as u64;
-            if fuel
-                .consume_fuel_if(|costs| costs.fuel_for_bytes(additional_bytes))
-                .is_err()
-            {
-                return notify_limiter(limiter, EntityGrowError::TrapCode(TrapCode::OutOfFuel));
-            }
-        }
-        // At this point all checks passed to grow the linear memory:
+        // At this point the limits of the growth have been validated:
         //
         // 1. The resource limiter validated the memory consumption.
         // 2. The growth is within bounds.
-        // 3. There is enough fuel for the operation.
-        self.bytes.grow(new_size);
+        //
+        // Only now we consume fuel for the new pages so that no fuel
+        // is consumed if the growth is denied by any of its limits.
+        let mut consumed_fuel = 0;
+        if let Some(fuel) = fuel.as_deref_mut() {
+            let additional_bytes = ty.pages_to_bytes(additional).unwrap_or(usize::MAX) as u64;
+            consumed_fuel = match fuel.consume_fuel_for_memory_grow(additional_bytes) {
+                Ok(consumed_fuel) => consumed_fuel,
+                Err(err) => return notify_limiter(limiter, out_of_bounds, err),
+            };
+        }
+        if self.bytes.try_grow(new_size).is_err() {
+            // Case: the allocation of the new pages failed after fuel
+            //       has been consumed for them so we refund the fuel.
+            if let Some(fuel) = fuel {
+                fuel.refund_fuel(consumed_fuel);
+            }
+            return notify_limiter(
+                limiter,
+                MemoryError::OutOfBoundsAllocation,
+                EntityGrowError::InvalidGrow(GrowError::Allocation),
+            );
+        }
         self.current_pages = new_pages;
         Ok(current_pages)
     }
 
+    /// Grows the linear memory to at least `min_pages` pages.
+    ///
+    /// # Note
+    ///
+    /// This does nothing if the linear memory already has at least `min_pages`
+    /// pages and otherwise grows it via a single [`MemoryEntity::grow`].
+    /// The maximum of the [`MemoryType`] is checked before the [`ResourceLimiter`]
+    /// is consulted so that the source of a failure is reported accurately.
+    ///
+    /// [`ResourceLimiter`]: crate::ResourceLimiter
+    ///
+    /// # Errors
+    ///
+    /// If the growth fails. In this case the linear memory is left unchanged.
+    pub fn grow_to(
+        &mut self,
+        min_pages: u64,
+        limiter: &mut ResourceLimiterRef<'_>,
+    ) -> Result<(), GrowError> {
+        let current_pages = u64::from(u32::from(self.current_pages()));
+        let additional = match min_pages.checked_sub(current_pages) {
+            None | Some(0) => return Ok(()),
+            Some(additional) => additional,
+        };
+        let maximum_pages = u64::from(u32::from(self.ty().maximum_pages_or_max()));
+        if min_pages > maximum_pages {
+            return Err(GrowError::Maximum);
+        }
+        let additional = u32::try_from(additional)
+            .ok()
+            .and_then(|additional| {
+                Pages::new_for_page_size(additional, self.ty().page_size_log2())
+            })
+            .ok_or(GrowError::Maximum)?;
+        match self.grow(additional, None, limiter) {
+            Ok(_) => Ok(()),
+            Err(EntityGrowError::InvalidGrow(error)) => Err(error),
+            Err(EntityGrowError::TrapCode(_)) => Err(GrowError::Limiter),
+        }
+    }
+
+    /// Restores the linear memory to `current_pages` with the contents of `bytes`.
+    ///
+    /// # Note
+    ///
+    /// This may also shrink the linear memory and does not consult the
+    /// [`ResourceLimiter`](crate::ResourceLimiter) since it only restores a previously observed state.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `bytes` does not match `current_pages`.
+    pub fn restore(&mut self, current_pages: Pages, bytes: &[u8]) {
+        assert_eq!(
+            self.ty().pages_to_bytes(current_pages),
+            Some(bytes.len()),
+            "the restored bytes must match the restored amount of pages",
+        );
+        self.bytes.restore(bytes);
+        self.current_pages = current_pages;
+    }
+
     /// Returns a shared slice to the bytes underlying to the byte buffer.
     pub fn data(&self) -> &[u8] {
         self.bytes.data()
@@ -274,6 +478,16 @@ impl MemoryEntity {
         self.bytes.data_mut()
     }
 
+    /// Returns `true` if write watchpoints are registered for the linear memory.
+    pub(crate) fn is_watched(&self) -> bool {
+        self.watched
+    }
+
+    /// Sets whether write watchpoints are registered for the linear memory.
+    pub(crate) fn set_watched(&mut self, watched: bool) {
+        self.watched = watched;
+    }
+
     /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
     /// where `n` is the length of `buffer`.
     ///
@@ -281,13 +495,7 @@ impl MemoryEntity {
     ///
     /// If this operation accesses out of bounds linear memory.
     pub fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), MemoryError> {
-        let len_buffer = buffer.len();
-        let slice = self
-            .data()
-            .get(offset..(offset + len_buffer))
-            .ok_or(MemoryError::OutOfBoundsAccess)?;
-        buffer.copy_from_slice(slice);
-        Ok(())
+        MemoryView::new(self.data()).read(offset, buffer)
     }
 
     /// Writes `n` bytes to `memory[offset..offset+n]` from `buffer`
@@ -297,13 +505,7 @@ impl MemoryEntity {
     ///
     /// If this operation accesses out of bounds linear memory.
     pub fn write(&mut self, offset: usize, buffer: &[u8]) -> Result<(), MemoryError> {
-        let len_buffer = buffer.len();
-        let slice = self
-            .data_mut()
-            .get_mut(offset..(offset + len_buffer))
-            .ok_or(MemoryError::OutOfBoundsAccess)?;
-        slice.copy_from_slice(buffer);
-        Ok(())
+        MemoryViewMut::new(self.data_mut()).write(offset, buffer)
     }
 }
 
@@ -327,12 +529,20 @@ impl Memory {
     ///
     /// # Errors
     ///
-    /// If more than [`u32::MAX`] much linear memory is allocated.
+    /// - If more than [`u32::MAX`] much linear memory is allocated.
+    /// - If `ty` has a custom page size but [`Config::wasm_custom_page_sizes`] is disabled.
+    ///
+    /// [`Config::wasm_custom_page_sizes`]: crate::Config::wasm_custom_page_sizes
     pub fn new(mut ctx: impl AsContextMut, ty: MemoryType) -> Result<Self, MemoryError> {
         let (inner, mut resource_limiter) = ctx
             .as_context_mut()
             .store
             .store_inner_and_resource_limiter_ref();
+        if ty.page_size_log2() != Pages::DEFAULT_PAGE_SIZE_LOG2
+            && !inner.engine().config().get_custom_page_sizes()
+        {
+            return Err(MemoryError::CustomPageSizesDisabled);
+        }
 
         let entity = MemoryEntity::new(ty, &mut resource_limiter)?;
         let memory = inner.alloc_memory(entity);
@@ -341,29 +551,37 @@ impl Memory {
 
     /// Returns the memory type of the linear memory.
     ///
+    /// # Note
+    ///
+    /// The minimum of the returned [`MemoryType`] is the current size of the [`Memory`]
+    /// and thus reflects all growth of the [`Memory`] since its creation.
+    /// This is the type that is used to check if the [`Memory`] can be imported.
+    ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Memory`].
     pub fn ty(&self, ctx: impl AsContext) -> MemoryType {
-        ctx.as_context().store.inner.resolve_memory(self).ty()
+        ctx.as_context()
+            .store
+            .inner
+            .resolve_memory(self)
+            .dynamic_ty()
     }
 
-    /// Returns the dynamic [`MemoryType`] of the [`Memory`].
+    /// Returns the instances that use the [`Memory`] in the order of their instantiation.
     ///
     /// # Note
     ///
-    /// This respects the current size of the [`Memory`] as
-    /// its minimum size and is useful for import subtyping checks.
+    /// - An instance uses the [`Memory`] if it defines or imports it.
+    /// - Instances are recorded upon instantiation and stay recorded for the lifetime of the
+    ///   [`Store`](crate::Store) since instances cannot be removed. The only exception are
+    ///   instances of failed instantiations that have been removed again.
     ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Memory`].
-    pub(crate) fn dynamic_ty(&self, ctx: impl AsContext) -> MemoryType {
-        ctx.as_context()
-            .store
-            .inner
-            .resolve_memory(self)
-            .dynamic_ty()
+    pub fn instances(&self, ctx: impl AsContext) -> Vec<Instance> {
+        ctx.as_context().store.inner.memory_instances(self)
     }
 
     /// Returns the amount of pages in use by the linear memory.
@@ -388,6 +606,10 @@ impl Memory {
     /// If the linear memory would grow beyond its maximum limit after
     /// the grow operation.
     ///
+    /// # Note
+    ///
+    /// The `additional` pages are of the page size of the [`MemoryType`] of the [`Memory`].
+    ///
     /// # Panics
     ///
     /// Panics if `ctx` does not own this [`Memory`].
@@ -406,6 +628,37 @@ impl Memory {
             .map_err(|_| MemoryError::OutOfBoundsGrowth)
     }
 
+    /// Grows the linear memory to at least `min_pages` pages.
+    ///
+    /// Does nothing if the linear memory already has at least `min_pages` pages.
+    ///
+    /// # Note
+    ///
+    /// - The `min_pages` are of the page size of the [`MemoryType`] of the [`Memory`].
+    /// - The linear memory is grown at once the same as via the `memory.grow` instruction
+    ///   and respects the [`ResourceLimiter`] of the [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If the linear memory cannot be grown to `min_pages` pages.
+    /// In this case the linear memory is left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    ///
+    /// [`ResourceLimiter`]: crate::ResourceLimiter
+    /// [`Store`]: crate::Store
+    pub fn grow_to(&self, mut ctx: impl AsContextMut, min_pages: u64) -> Result<(), GrowError> {
+        let (inner, mut limiter) = ctx
+            .as_context_mut()
+            .store
+            .store_inner_and_resource_limiter_ref();
+        inner
+            .resolve_memory_mut(self)
+            .grow_to(min_pages, &mut limiter)
+    }
+
     /// Returns a shared slice to the bytes underlying the [`Memory`].
     ///
     /// # Panics
@@ -438,6 +691,84 @@ impl Memory {
         (memory.data_mut(), store)
     }
 
+    /// Returns a shared [`MemoryView`] into the bytes of the [`Memory`].
+    ///
+    /// # Note
+    ///
+    /// This is a zero-copy alternative to [`Memory::read`] that
+    /// can also be created from the [`Caller`] of a host function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    ///
+    /// [`Caller`]: crate::Caller
+    pub fn view<'a, T: 'a>(&self, ctx: impl Into<StoreContext<'a, T>>) -> MemoryView<'a> {
+        MemoryView::new(self.data(ctx))
+    }
+
+    /// Returns an exclusive [`MemoryViewMut`] into the bytes of the [`Memory`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn view_mut<'a, T: 'a>(&self, ctx: impl Into<StoreContextMut<'a, T>>) -> MemoryViewMut<'a> {
+        MemoryViewMut::new(self.data_mut(ctx))
+    }
+
+    /// Returns an exclusive [`MemoryViewMut`] into the bytes of the [`Memory`], and an
+    /// exclusive reference to the user provided state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn view_and_store_mut<'a, T: 'a>(
+        &self,
+        ctx: impl Into<StoreContextMut<'a, T>>,
+    ) -> (MemoryViewMut<'a>, &'a mut T) {
+        let (data, store) = self.data_and_store_mut(ctx);
+        (MemoryViewMut::new(data), store)
+    }
+
+    /// Reads the little-endian encoded [`Pod`] value at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn read_pod<T: Pod>(&self, ctx: impl AsContext, offset: usize) -> Result<T, MemoryError> {
+        let data = ctx.as_context().store.inner.resolve_memory(self).data();
+        MemoryView::new(data).read_pod(offset)
+    }
+
+    /// Writes `value` little-endian encoded at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    /// In this case the [`Memory`] is not modified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Memory`].
+    pub fn write_pod<T: Pod>(
+        &self,
+        mut ctx: impl AsContextMut,
+        offset: usize,
+        value: T,
+    ) -> Result<(), MemoryError> {
+        let data = ctx
+            .as_context_mut()
+            .store
+            .inner
+            .resolve_memory_mut(self)
+            .data_mut();
+        MemoryViewMut::new(data).write_pod(offset, value)
+    }
+
     /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
     /// where `n` is the length of `buffer`.
     ///
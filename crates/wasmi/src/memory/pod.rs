@@ -0,0 +1,113 @@
+use wasmi_core::{F32, F64};
+
+/// Plain old data that can be read from and written to linear memory.
+///
+/// # Note
+///
+/// Values are always encoded in little-endian byte order as mandated by Wasm.
+///
+/// Compound types such as `#[repr(packed)]` structs can implement [`Pod`]
+/// by decoding and encoding their fields in order of their memory layout.
+///
+/// # Example
+///
+/// ```
+/// use wasmi::Pod;
+///
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl Pod for Point {
+///     const SIZE: usize = 8;
+///
+///     fn read_le(bytes: &[u8]) -> Self {
+///         let (x, y) = bytes.split_at(i32::SIZE);
+///         Self { x: i32::read_le(x), y: i32::read_le(y) }
+///     }
+///
+///     fn write_le(&self, bytes: &mut [u8]) {
+///         let (x, y) = bytes.split_at_mut(i32::SIZE);
+///         self.x.write_le(x);
+///         self.y.write_le(y);
+///     }
+/// }
+/// ```
+pub trait Pod: Sized {
+    /// The number of bytes of the encoded value.
+    const SIZE: usize;
+
+    /// Decodes a value from its little-endian `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `bytes` does not match [`Pod::SIZE`].
+    fn read_le(bytes: &[u8]) -> Self;
+
+    /// Encodes `self` into its little-endian `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `bytes` does not match [`Pod::SIZE`].
+    fn write_le(&self, bytes: &mut [u8]);
+}
+
+macro_rules! impl_pod_for_primitive {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            impl Pod for $ty {
+                const SIZE: usize = ::core::mem::size_of::<$ty>();
+
+                fn read_le(bytes: &[u8]) -> Self {
+                    let bytes = <[u8; ::core::mem::size_of::<$ty>()]>::try_from(bytes)
+                        .unwrap_or_else(|_| panic!("expected {} bytes but found {}", Self::SIZE, bytes.len()));
+                    <$ty>::from_le_bytes(bytes)
+                }
+
+                fn write_le(&self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+impl_pod_for_primitive!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+macro_rules! impl_pod_for_float {
+    ( $($ty:ty as $prim:ty),* $(,)? ) => {
+        $(
+            impl Pod for $ty {
+                const SIZE: usize = <$prim as Pod>::SIZE;
+
+                fn read_le(bytes: &[u8]) -> Self {
+                    Self::from_bits(<$prim as Pod>::read_le(bytes))
+                }
+
+                fn write_le(&self, bytes: &mut [u8]) {
+                    self.to_bits().write_le(bytes)
+                }
+            }
+        )*
+    };
+}
+impl_pod_for_float!(F32 as u32, F64 as u64);
+
+impl<T, const N: usize> Pod for [T; N]
+where
+    T: Pod,
+{
+    const SIZE: usize = T::SIZE * N;
+
+    fn read_le(bytes: &[u8]) -> Self {
+        assert_eq!(bytes.len(), Self::SIZE);
+        core::array::from_fn(|n| T::read_le(&bytes[n * T::SIZE..][..T::SIZE]))
+    }
+
+    fn write_le(&self, bytes: &mut [u8]) {
+        assert_eq!(bytes.len(), Self::SIZE);
+        for (n, value) in self.iter().enumerate() {
+            value.write_le(&mut bytes[n * T::SIZE..][..T::SIZE]);
+        }
+    }
+}
@@ -14,3 +14,69 @@ fn subtyping_works() {
     assert!(memory_type(0, 1).is_subtype_of(&memory_type(0, None)));
     assert!(!memory_type(0, None).is_subtype_of(&memory_type(0, 1)));
 }
+
+/// Asserts that [`Pod`] accesses of `T` at all offsets near the end of a linear memory
+/// of `len` bytes either succeed entirely or fail without accessing any bytes.
+fn assert_pod_boundaries<T>(len: usize, value: T)
+where
+    T: Pod + PartialEq + core::fmt::Debug + Copy,
+{
+    let original: Vec<u8> = (0..len).map(|n| n as u8).collect();
+    let offsets = (len.saturating_sub(2 * T::SIZE)..=len + T::SIZE).chain([
+        usize::MAX - T::SIZE,
+        usize::MAX - 1,
+        usize::MAX,
+    ]);
+    for offset in offsets {
+        let mut data = original.clone();
+        let in_bounds = offset
+            .checked_add(T::SIZE)
+            .map(|end| end <= len)
+            .unwrap_or(false);
+        let mut view = MemoryViewMut::new(&mut data);
+        match view.write_pod(offset, value) {
+            Ok(()) => {
+                assert!(in_bounds, "wrote out of bounds at offset {offset}");
+                assert_eq!(view.read_pod::<T>(offset).unwrap(), value);
+                assert_eq!(&data[..offset], &original[..offset]);
+                assert_eq!(&data[offset + T::SIZE..], &original[offset + T::SIZE..]);
+            }
+            Err(error) => {
+                assert!(!in_bounds, "failed to write in bounds at offset {offset}");
+                assert!(matches!(error, MemoryError::OutOfBoundsAccess));
+                assert!(view.read_pod::<T>(offset).is_err());
+                assert_eq!(data, original, "torn write at offset {offset}");
+            }
+        }
+    }
+}
+
+#[test]
+fn pod_boundaries_work() {
+    for len in [0, 1, 7, 8, 9, 64] {
+        assert_pod_boundaries(len, 0xA5_u8);
+        assert_pod_boundaries(len, -2_i16);
+        assert_pod_boundaries(len, 0xDEAD_BEEF_u32);
+        assert_pod_boundaries(len, i64::MIN + 1);
+        assert_pod_boundaries(len, 1.5_f32);
+        assert_pod_boundaries(len, -0.25_f64);
+        assert_pod_boundaries(len, [1_u16, 2, 3]);
+    }
+}
+
+#[test]
+fn pod_is_little_endian() {
+    let mut data = [0x00_u8; 8];
+    let mut view = MemoryViewMut::new(&mut data);
+    view.write_pod(0, 0x0102_0304_u32).unwrap();
+    view.write_pod(4, [0x05_u8, 0x06]).unwrap();
+    view.write_pod(6, -2_i16).unwrap();
+    assert_eq!(data, [0x04, 0x03, 0x02, 0x01, 0x05, 0x06, 0xFE, 0xFF]);
+    let view = MemoryView::new(&data);
+    assert_eq!(view.read_pod::<u64>(0).unwrap(), 0xFFFE_0605_0102_0304);
+    assert_eq!(view.read_pod::<[u16; 2]>(0).unwrap(), [0x0304, 0x0102]);
+    assert_eq!(
+        view.read_pod::<wasmi_core::F32>(0).unwrap(),
+        wasmi_core::F32::from_bits(0x0102_0304)
+    );
+}
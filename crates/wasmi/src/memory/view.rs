@@ -0,0 +1,167 @@
+use super::{MemoryError, Pod};
+
+/// Returns the `len` bytes of `data` starting at `offset`.
+///
+/// # Errors
+///
+/// If any of the accessed bytes are out of bounds of `data`.
+/// In this case no bytes are accessed at all.
+fn bytes(data: &[u8], offset: usize, len: usize) -> Result<&[u8], MemoryError> {
+    offset
+        .checked_add(len)
+        .and_then(|end| data.get(offset..end))
+        .ok_or(MemoryError::OutOfBoundsAccess)
+}
+
+/// Returns the `len` bytes of `data` starting at `offset`.
+///
+/// # Errors
+///
+/// If any of the accessed bytes are out of bounds of `data`.
+/// In this case no bytes are accessed at all.
+fn bytes_mut(data: &mut [u8], offset: usize, len: usize) -> Result<&mut [u8], MemoryError> {
+    offset
+        .checked_add(len)
+        .and_then(|end| data.get_mut(offset..end))
+        .ok_or(MemoryError::OutOfBoundsAccess)
+}
+
+/// A shared view into the bytes of a linear [`Memory`].
+///
+/// The view borrows the store that owns the [`Memory`] and therefore
+/// prevents any mutation of the [`Memory`] while it is alive.
+///
+/// [`Memory`]: crate::Memory
+#[derive(Debug, Copy, Clone)]
+pub struct MemoryView<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> MemoryView<'a> {
+    /// Creates a new [`MemoryView`] for the bytes of a linear memory.
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Returns the viewed bytes of the linear memory.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the number of viewed bytes.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the viewed linear memory is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
+    /// where `n` is the length of `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), MemoryError> {
+        buffer.copy_from_slice(bytes(self.data, offset, buffer.len())?);
+        Ok(())
+    }
+
+    /// Reads the little-endian encoded [`Pod`] value at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn read_pod<T: Pod>(&self, offset: usize) -> Result<T, MemoryError> {
+        bytes(self.data, offset, T::SIZE).map(T::read_le)
+    }
+}
+
+/// An exclusive view into the bytes of a linear [`Memory`].
+///
+/// The view exclusively borrows the store that owns the [`Memory`].
+/// Use [`Memory::view_and_store_mut`] to access the user provided
+/// store data alongside the [`MemoryViewMut`].
+///
+/// [`Memory`]: crate::Memory
+/// [`Memory::view_and_store_mut`]: crate::Memory::view_and_store_mut
+#[derive(Debug)]
+pub struct MemoryViewMut<'a> {
+    data: &'a mut [u8],
+}
+
+impl<'a> MemoryViewMut<'a> {
+    /// Creates a new [`MemoryViewMut`] for the bytes of a linear memory.
+    pub(crate) fn new(data: &'a mut [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Returns a shared [`MemoryView`] reborrowing `self`.
+    pub fn as_view(&self) -> MemoryView<'_> {
+        MemoryView::new(self.data)
+    }
+
+    /// Returns the viewed bytes of the linear memory.
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    /// Returns the viewed bytes of the linear memory.
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        self.data
+    }
+
+    /// Returns the number of viewed bytes.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the viewed linear memory is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Reads `n` bytes from `memory[offset..offset+n]` into `buffer`
+    /// where `n` is the length of `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), MemoryError> {
+        self.as_view().read(offset, buffer)
+    }
+
+    /// Reads the little-endian encoded [`Pod`] value at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    pub fn read_pod<T: Pod>(&self, offset: usize) -> Result<T, MemoryError> {
+        self.as_view().read_pod(offset)
+    }
+
+    /// Writes `n` bytes to `memory[offset..offset+n]` from `buffer`
+    /// where `n` is the length of `buffer`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    /// In this case the linear memory is not modified.
+    pub fn write(&mut self, offset: usize, buffer: &[u8]) -> Result<(), MemoryError> {
+        bytes_mut(self.data, offset, buffer.len())?.copy_from_slice(buffer);
+        Ok(())
+    }
+
+    /// Writes `value` little-endian encoded at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// If this operation accesses out of bounds linear memory.
+    /// In this case the linear memory is not modified.
+    pub fn write_pod<T: Pod>(&mut self, offset: usize, value: T) -> Result<(), MemoryError> {
+        value.write_le(bytes_mut(self.data, offset, T::SIZE)?);
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use super::{
-    export::ExternIdx,
+    export::{ExportMap, ExternIdx},
     import::FuncTypeIdx,
     ConstExpr,
     DataSegment,
@@ -7,16 +7,16 @@ use super::{
     ExternTypeIdx,
     FuncIdx,
     Global,
-    Import,
     ImportName,
     Imported,
     Module,
     ModuleHeader,
     ModuleHeaderInner,
     ModuleImports,
+    ModuleMetadata,
 };
 use crate::{
-    engine::{CompiledFunc, DedupFuncType},
+    engine::{CodeOwner, CompiledFunc, DedupFuncType, TranslationError},
     Engine,
     Error,
     FuncType,
@@ -24,7 +24,12 @@ use crate::{
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    vec::Vec,
+};
 
 /// A builder for a WebAssembly [`Module`].
 #[derive(Debug)]
@@ -37,6 +42,7 @@ pub struct ModuleBuilder {
 #[derive(Debug)]
 pub struct ModuleHeaderBuilder {
     engine: Engine,
+    code_owner: CodeOwner,
     pub func_types: Vec<DedupFuncType>,
     pub imports: ModuleImportsBuilder,
     pub funcs: Vec<DedupFuncType>,
@@ -44,18 +50,23 @@ pub struct ModuleHeaderBuilder {
     pub memories: Vec<MemoryType>,
     pub globals: Vec<GlobalType>,
     pub globals_init: Vec<ConstExpr>,
-    pub exports: BTreeMap<Box<str>, ExternIdx>,
+    pub exports: ExportMap<ExternIdx>,
     pub start: Option<FuncIdx>,
     pub compiled_funcs: Vec<CompiledFunc>,
     pub compiled_funcs_idx: BTreeMap<CompiledFunc, FuncIdx>,
     pub element_segments: Vec<ElementSegment>,
+    /// The interned import names shared by all imports of the [`Module`].
+    names: BTreeSet<Arc<str>>,
 }
 
 impl ModuleHeaderBuilder {
     /// Creates a new [`ModuleHeaderBuilder`] for the given [`Engine`].
-    pub fn new(engine: &Engine) -> Self {
+    ///
+    /// The functions of the [`Module`] are allocated on behalf of `code_owner`.
+    pub fn new(engine: &Engine, code_owner: &CodeOwner) -> Self {
         Self {
             engine: engine.clone(),
+            code_owner: code_owner.clone(),
             func_types: Vec::new(),
             imports: ModuleImportsBuilder::default(),
             funcs: Vec::new(),
@@ -63,12 +74,28 @@ impl ModuleHeaderBuilder {
             memories: Vec::new(),
             globals: Vec::new(),
             globals_init: Vec::new(),
-            exports: BTreeMap::new(),
+            exports: ExportMap::default(),
             start: None,
             compiled_funcs: Vec::new(),
             compiled_funcs_idx: BTreeMap::new(),
             element_segments: Vec::new(),
+            names: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the interned `name`.
+    ///
+    /// # Note
+    ///
+    /// This reduces the memory usage of Wasm modules with many similarly named imports,
+    /// e.g. thousands of imports from the same Wasm module namespace.
+    fn intern(&mut self, name: &str) -> Arc<str> {
+        if let Some(interned) = self.names.get(name) {
+            return interned.clone();
         }
+        let interned = Arc::<str>::from(name);
+        self.names.insert(interned.clone());
+        interned
     }
 
     /// Finishes construction of [`ModuleHeader`].
@@ -173,14 +200,16 @@ impl ModuleHeaderBuilder {
     /// # Panics
     ///
     /// If this function has already been called on the same [`ModuleBuilder`].
-    pub fn push_imports<T>(&mut self, imports: T) -> Result<(), Error>
+    pub fn push_imports<'a, T>(&mut self, imports: T) -> Result<(), Error>
     where
-        T: IntoIterator<Item = Result<Import, Error>>,
+        T: IntoIterator<Item = Result<wasmparser::Import<'a>, Error>>,
     {
         for import in imports {
             let import = import?;
-            let (name, kind) = import.into_name_and_type();
-            match kind {
+            let module = self.intern(import.module);
+            let field = self.intern(import.name);
+            let name = ImportName::from_shared(module, field);
+            match ExternTypeIdx::from(import.ty) {
                 ExternTypeIdx::Func(func_type_idx) => {
                     self.imports.funcs.push(name);
                     let func_type = self.func_types[func_type_idx.into_u32() as usize];
@@ -228,7 +257,7 @@ impl ModuleHeaderBuilder {
                 panic!("function index out of bounds: {}", self.funcs.len())
             };
             self.funcs.push(func_type);
-            let compiled_func = self.engine.alloc_func();
+            let compiled_func = self.engine.alloc_func(&self.code_owner);
             self.compiled_funcs.push(compiled_func);
             self.compiled_funcs_idx
                 .insert(compiled_func, FuncIdx::from(func_index));
@@ -317,7 +346,8 @@ impl ModuleHeaderBuilder {
     ///
     /// # Errors
     ///
-    /// If an export declaration fails to validate.
+    /// - If an export declaration fails to validate.
+    /// - If two exports share the same name.
     ///
     /// # Panics
     ///
@@ -330,7 +360,17 @@ impl ModuleHeaderBuilder {
             self.exports.is_empty(),
             "tried to initialize module export declarations twice"
         );
-        self.exports = exports.into_iter().collect::<Result<BTreeMap<_, _>, _>>()?;
+        for export in exports {
+            let (name, idx) = export?;
+            if let Some(first) = self.exports.get(&name) {
+                return Err(Error::from(TranslationError::DuplicateExport {
+                    name,
+                    first: first.kind_and_index(),
+                    second: idx.kind_and_index(),
+                }));
+            }
+            self.exports.insert(name, idx);
+        }
         Ok(())
     }
 
@@ -391,11 +431,23 @@ impl ModuleBuilder {
     }
 
     /// Finishes construction of the WebAssembly [`Module`].
-    pub fn finish(self, engine: &Engine) -> Module {
+    ///
+    /// The [`Module`] owns the functions allocated on behalf of `code_owner`.
+    pub fn finish(
+        self,
+        engine: &Engine,
+        code_owner: CodeOwner,
+        metadata: ModuleMetadata,
+        #[cfg(feature = "wat")] wasm: Vec<u8>,
+    ) -> Module {
         Module {
             engine: engine.clone(),
+            code_owner,
             header: self.header,
             data_segments: self.data_segments.into(),
+            metadata: Arc::new(metadata),
+            #[cfg(feature = "wat")]
+            wasm: wasm.into(),
         }
     }
 }
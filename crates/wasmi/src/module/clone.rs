@@ -0,0 +1,106 @@
+use super::{FuncIdx, Module, ModuleHeader, ModuleHeaderInner};
+use crate::{
+    engine::{CodeOwner, CompiledFunc, DedupFuncType},
+    Engine,
+    FuncType,
+};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
+use core::{fmt, fmt::Display};
+
+/// Error returned by [`Module::clone_into`] if the [`Config`] of both [`Engine`]s differ.
+///
+/// [`Config`]: crate::Config
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EngineMismatchError;
+
+#[cfg(feature = "std")]
+impl std::error::Error for EngineMismatchError {}
+
+impl Display for EngineMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cannot clone module into an engine with a different config"
+        )
+    }
+}
+
+impl Module {
+    /// Clones the [`Module`] into the `target` [`Engine`] without translating it again.
+    ///
+    /// # Note
+    ///
+    /// - The compiled functions and their function local constant values
+    ///   are copied and the function types are registered with `target`.
+    /// - Functions that have not yet been compiled due to lazy compilation
+    ///   are compiled by the [`Engine`] of the [`Module`] before being copied.
+    ///   Functions that failed to compile lazily fail the same way in `target`.
+    /// - The returned [`Module`] is independent of the [`Engine`] of `self`.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Config`] of `target` differs from the one of the [`Engine`] of the [`Module`].
+    ///
+    /// [`Config`]: crate::Config
+    pub fn clone_into(&self, target: &Engine) -> Result<Module, EngineMismatchError> {
+        if !Engine::same_config_fingerprint(&self.engine, target) {
+            return Err(EngineMismatchError);
+        }
+        let origin = &self.header.inner;
+        let rekey = |func_type: &DedupFuncType| {
+            let func_type = self.engine.resolve_func_type(func_type, FuncType::clone);
+            target.alloc_func_type(func_type)
+        };
+        let code_owner = CodeOwner::default();
+        let compiled_funcs: Box<[CompiledFunc]> = origin
+            .compiled_funcs
+            .iter()
+            .map(|_| target.alloc_func(&code_owner))
+            .collect();
+        let func_map: BTreeMap<CompiledFunc, CompiledFunc> = origin
+            .compiled_funcs
+            .iter()
+            .copied()
+            .zip(compiled_funcs.iter().copied())
+            .collect();
+        for (&origin_func, &func) in &func_map {
+            target.init_func_copy(func, &self.engine, origin_func, |callee| {
+                match func_map.get(&callee) {
+                    Some(callee) => *callee,
+                    None => panic!("encountered call to foreign function: {callee:?}"),
+                }
+            });
+        }
+        let compiled_funcs_idx: BTreeMap<CompiledFunc, FuncIdx> = origin
+            .compiled_funcs_idx
+            .iter()
+            .map(|(func, func_idx)| (func_map[func], *func_idx))
+            .collect();
+        let header = ModuleHeader {
+            inner: Arc::new(ModuleHeaderInner {
+                engine: target.downgrade(),
+                func_types: origin.func_types.iter().map(rekey).collect(),
+                imports: origin.imports.clone(),
+                funcs: origin.funcs.iter().map(rekey).collect(),
+                tables: origin.tables.clone(),
+                memories: origin.memories.clone(),
+                globals: origin.globals.clone(),
+                globals_init: origin.globals_init.clone(),
+                exports: origin.exports.clone(),
+                start: origin.start,
+                compiled_funcs,
+                compiled_funcs_idx,
+                element_segments: origin.element_segments.clone(),
+            }),
+        };
+        Ok(Module {
+            engine: target.clone(),
+            code_owner,
+            header,
+            data_segments: self.data_segments.clone(),
+            metadata: self.metadata.clone(),
+            #[cfg(feature = "wat")]
+            wasm: self.wasm.clone(),
+        })
+    }
+}
@@ -4,7 +4,7 @@ use alloc::sync::Arc;
 /// A Wasm [`Module`] data segment.
 ///
 /// [`Module`]: [`super::Module`]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DataSegment {
     /// The kind of the data segment.
     kind: DataSegmentKind,
@@ -13,7 +13,7 @@ pub struct DataSegment {
 }
 
 /// The kind of a Wasm module [`DataSegment`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum DataSegmentKind {
     /// A passive data segment from the `bulk-memory` Wasm proposal.
     Passive,
@@ -22,7 +22,7 @@ pub enum DataSegmentKind {
 }
 
 /// An active data segment.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ActiveDataSegment {
     /// The linear memory that is to be initialized with this active segment.
     memory_index: MemoryIdx,
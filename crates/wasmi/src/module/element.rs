@@ -6,7 +6,7 @@ use wasmi_core::ValueType;
 /// A table element segment within a [`Module`].
 ///
 /// [`Module`]: [`super::Module`]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ElementSegment {
     /// The kind of the [`ElementSegment`].
     kind: ElementSegmentKind,
@@ -57,7 +57,7 @@ impl ElementSegmentItems {
 }
 
 /// The kind of a Wasm [`ElementSegment`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ElementSegmentKind {
     /// A passive [`ElementSegment`] from the `bulk-memory` Wasm proposal.
     Passive,
@@ -68,7 +68,7 @@ pub enum ElementSegmentKind {
 }
 
 /// An active Wasm element segment.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ActiveElementSegment {
     /// The index of the Wasm table that is to be initialized.
     table_index: TableIdx,
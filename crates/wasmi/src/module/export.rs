@@ -1,6 +1,7 @@
 use super::GlobalIdx;
-use crate::{Error, ExternType, Module};
-use alloc::{boxed::Box, collections::btree_map::Iter as BTreeIter};
+use crate::{Error, ExternKind, ExternType, Module};
+use alloc::{boxed::Box, vec::Vec};
+use core::slice;
 
 /// The index of a function declaration within a [`Module`].
 ///
@@ -99,6 +100,96 @@ impl ExternIdx {
             }
         }
     }
+
+    /// Returns the [`ExternKind`] and the index of the exported item.
+    pub fn kind_and_index(self) -> (ExternKind, u32) {
+        match self {
+            ExternIdx::Func(index) => (ExternKind::Func, index.into_u32()),
+            ExternIdx::Table(index) => (ExternKind::Table, index.into_u32()),
+            ExternIdx::Memory(index) => (ExternKind::Memory, index.into_u32()),
+            ExternIdx::Global(index) => (ExternKind::Global, index.into_u32()),
+        }
+    }
+}
+
+/// The exports of a [`Module`] or an [`Instance`] that preserve their declaration order.
+///
+/// [`Instance`]: crate::Instance
+#[derive(Debug, Clone)]
+pub struct ExportMap<T> {
+    /// The exports in the order of their declaration.
+    exports: Vec<(Box<str>, T)>,
+    /// The indices into `exports` sorted by export name for lookups by name.
+    sorted: Vec<u32>,
+}
+
+impl<T> Default for ExportMap<T> {
+    fn default() -> Self {
+        Self {
+            exports: Vec::new(),
+            sorted: Vec::new(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Box<str>, T)> for ExportMap<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (Box<str>, T)>,
+    {
+        let mut exports = Self::default();
+        for (name, value) in iter {
+            exports.insert(name, value);
+        }
+        exports
+    }
+}
+
+impl<T> ExportMap<T> {
+    /// Returns `true` if the [`ExportMap`] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.exports.is_empty()
+    }
+
+    /// Searches `sorted` for the export with the given `name`.
+    ///
+    /// Returns the position within `sorted` at which the export is found or needs to be inserted.
+    fn search(&self, name: &str) -> Result<usize, usize> {
+        self.sorted
+            .binary_search_by(|&index| (*self.exports[index as usize].0).cmp(name))
+    }
+
+    /// Returns the export with the given `name` if any.
+    pub fn get(&self, name: &str) -> Option<&T> {
+        let position = self.search(name).ok()?;
+        let index = self.sorted[position] as usize;
+        Some(&self.exports[index].1)
+    }
+
+    /// Inserts the export `value` under `name`.
+    ///
+    /// Returns the replaced export if `name` was already in use.
+    /// A replaced export keeps its position in the declaration order.
+    pub fn insert(&mut self, name: Box<str>, value: T) -> Option<T> {
+        match self.search(&name) {
+            Ok(position) => {
+                let index = self.sorted[position] as usize;
+                Some(core::mem::replace(&mut self.exports[index].1, value))
+            }
+            Err(position) => {
+                let index = u32::try_from(self.exports.len())
+                    .unwrap_or_else(|_| panic!("out of bounds export index for: {name}"));
+                self.exports.push((name, value));
+                self.sorted.insert(position, index);
+                None
+            }
+        }
+    }
+
+    /// Returns an iterator over the exports in the order of their declaration.
+    pub fn iter(&self) -> slice::Iter<'_, (Box<str>, T)> {
+        self.exports.iter()
+    }
 }
 
 /// An iterator over the exports of a [`Module`].
@@ -106,7 +197,7 @@ impl ExternIdx {
 /// [`Module`]: [`super::Module`]
 #[derive(Debug)]
 pub struct ModuleExportsIter<'module> {
-    exports: BTreeIter<'module, Box<str>, ExternIdx>,
+    exports: slice::Iter<'module, (Box<str>, ExternIdx)>,
     module: &'module Module,
 }
 
@@ -1,30 +1,25 @@
 use crate::{GlobalType, MemoryType, TableType};
-use alloc::boxed::Box;
+use alloc::sync::Arc;
 use core::fmt::{self, Display};
 use wasmparser::TypeRef;
 
-/// A [`Module`] import item.
+/// The name or namespace of an imported item.
+///
+/// # Note
+///
+/// The names are shared so that the imports of a [`Module`] can intern them.
 ///
 /// [`Module`]: [`super::Module`]
-#[derive(Debug)]
-pub struct Import {
-    /// The name of the imported item.
-    name: ImportName,
-    /// The type of the imported item.
-    kind: ExternTypeIdx,
-}
-
-/// The name or namespace of an imported item.
 #[derive(Debug, Clone)]
 pub struct ImportName {
     /// The name of the [`Module`] that defines the imported item.
     ///
     /// [`Module`]: [`super::Module`]
-    module: Box<str>,
+    module: Arc<str>,
     /// The name of the imported item within the [`Module`] namespace.
     ///
     /// [`Module`]: [`super::Module`]
-    field: Box<str>,
+    field: Arc<str>,
 }
 
 impl Display for ImportName {
@@ -36,12 +31,14 @@ impl Display for ImportName {
 }
 
 impl ImportName {
-    /// Creates a new [`Import`] item.
+    /// Creates a new [`ImportName`].
     pub fn new(module: &str, field: &str) -> Self {
-        Self {
-            module: module.into(),
-            field: field.into(),
-        }
+        Self::from_shared(module.into(), field.into())
+    }
+
+    /// Creates a new [`ImportName`] from the shared `module` and `field` names.
+    pub(crate) fn from_shared(module: Arc<str>, field: Arc<str>) -> Self {
+        Self { module, field }
     }
 
     /// Returns the name of the [`Module`] that defines the imported item.
@@ -59,40 +56,6 @@ impl ImportName {
     }
 }
 
-impl From<wasmparser::Import<'_>> for Import {
-    fn from(import: wasmparser::Import) -> Self {
-        let kind = match import.ty {
-            TypeRef::Func(ty) => ExternTypeIdx::Func(ty.into()),
-            TypeRef::Table(ty) => ExternTypeIdx::Table(TableType::from_wasmparser(ty)),
-            TypeRef::Memory(ty) => ExternTypeIdx::Memory(MemoryType::from_wasmparser(ty)),
-            TypeRef::Global(ty) => ExternTypeIdx::Global(GlobalType::from_wasmparser(ty)),
-            TypeRef::Tag(tag) => panic!(
-                "wasmi does not support the `exception-handling` Wasm proposal but found: {tag:?}"
-            ),
-        };
-        Self::new(import.module, import.name, kind)
-    }
-}
-
-impl Import {
-    /// Creates a new [`Import`] item.
-    pub fn new(module: &str, field: &str, kind: ExternTypeIdx) -> Self {
-        Self {
-            name: ImportName::new(module, field),
-            kind,
-        }
-    }
-
-    /// Splits the [`Import`] into its raw parts.
-    ///
-    /// # Note
-    ///
-    /// This allows to reuse some allocations in certain cases.
-    pub fn into_name_and_type(self) -> (ImportName, ExternTypeIdx) {
-        (self.name, self.kind)
-    }
-}
-
 /// The kind of a [`Module`] import.
 ///
 /// [`Module`]: [`super::Module`]
@@ -108,6 +71,20 @@ pub enum ExternTypeIdx {
     Global(GlobalType),
 }
 
+impl From<TypeRef> for ExternTypeIdx {
+    fn from(ty: TypeRef) -> Self {
+        match ty {
+            TypeRef::Func(ty) => Self::Func(ty.into()),
+            TypeRef::Table(ty) => Self::Table(TableType::from_wasmparser(ty)),
+            TypeRef::Memory(ty) => Self::Memory(MemoryType::from_wasmparser(ty)),
+            TypeRef::Global(ty) => Self::Global(GlobalType::from_wasmparser(ty)),
+            TypeRef::Tag(tag) => panic!(
+                "wasmi does not support the `exception-handling` Wasm proposal but found: {tag:?}"
+            ),
+        }
+    }
+}
+
 /// A [`FuncType`] index.
 ///
 /// # Note
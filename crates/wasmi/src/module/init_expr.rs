@@ -8,7 +8,7 @@
 
 use super::FuncIdx;
 use crate::{ExternRef, FuncRef, Value};
-use alloc::boxed::Box;
+use alloc::sync::Arc;
 use core::fmt;
 use smallvec::SmallVec;
 use wasmi_core::{UntypedValue, F32, F64};
@@ -43,7 +43,7 @@ impl EvalContext for EmptyEvalContext {
 }
 
 /// An input parameter to a [`ConstExpr`] operator.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Op {
     /// A constant value.
     Const(ConstOp),
@@ -64,7 +64,7 @@ pub enum Op {
 /// - `f32.const`
 /// - `f64.const`
 /// - `ref.null`
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConstOp {
     /// The underlying precomputed untyped value.
     value: UntypedValue,
@@ -78,7 +78,7 @@ impl Eval for ConstOp {
 
 /// Represents a Wasm `global.get` operator.
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GlobalOp {
     /// The index of the global variable.
     global_index: u32,
@@ -92,7 +92,7 @@ impl Eval for GlobalOp {
 
 /// Represents a Wasm `func.ref` operator.
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FuncRefOp {
     /// The index of the function.
     function_index: u32,
@@ -114,10 +114,11 @@ impl Eval for FuncRefOp {
 /// - `i64.add`
 /// - `i64.sub`
 /// - `i64.mul`
+#[derive(Clone)]
 #[allow(clippy::type_complexity)]
 pub struct ExprOp {
     /// The underlying closure that implements the expression.
-    expr: Box<dyn Fn(&dyn EvalContext) -> Option<UntypedValue> + Send + Sync>,
+    expr: Arc<dyn Fn(&dyn EvalContext) -> Option<UntypedValue> + Send + Sync>,
 }
 
 impl fmt::Debug for ExprOp {
@@ -159,7 +160,7 @@ impl Op {
         T: Fn(&dyn EvalContext) -> Option<UntypedValue> + Send + Sync + 'static,
     {
         Self::Expr(ExprOp {
-            expr: Box::new(expr),
+            expr: Arc::new(expr),
         })
     }
 }
@@ -180,7 +181,7 @@ impl Eval for Op {
 /// These are used to determine the offsets of memory data
 /// and table element segments as well as the initial value
 /// of global variables.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConstExpr {
     /// The root operator of the [`ConstExpr`].
     op: Op,
@@ -50,6 +50,13 @@ pub enum InstantiationError {
         index: u32,
     },
     TooManyInstances,
+    /// Caused when the instantiated [`Module`] has been compiled by another [`Engine`]
+    /// than the one of the [`Store`].
+    ///
+    /// [`Module`]: crate::Module
+    /// [`Engine`]: crate::Engine
+    /// [`Store`]: crate::Store
+    EngineMismatch,
 }
 
 #[cfg(feature = "std")]
@@ -86,7 +93,10 @@ impl Display for InstantiationError {
             Self::Table(error) => Display::fmt(error, f),
             Self::Memory(error) => Display::fmt(error, f),
             Self::Global(error) => Display::fmt(error, f),
-            Self::TooManyInstances => write!(f, "too many instances")
+            Self::TooManyInstances => write!(f, "too many instances"),
+            Self::EngineMismatch => {
+                write!(f, "module and store do not use the same engine")
+            }
         }
     }
 }
@@ -13,9 +13,11 @@ use crate::{
     AsContext,
     AsContextMut,
     ElementSegment,
+    Engine,
     Error,
     Extern,
     ExternType,
+    Func,
     FuncRef,
     FuncType,
     Global,
@@ -26,7 +28,8 @@ use crate::{
     Table,
     Value,
 };
-use wasmi_core::UntypedValue;
+use alloc::sync::Arc;
+use wasmi_core::{UntypedValue, ValueType};
 
 impl Module {
     /// Instantiates a new [`Instance`] from the given compiled [`Module`].
@@ -41,11 +44,16 @@ impl Module {
     ///
     /// # Errors
     ///
-    /// If the given `externals` do not satisfy the required imports, e.g. if an externally
-    /// provided [`Func`] has a different function signature than required by the module import.
+    /// - If the given `externals` do not satisfy the required imports, e.g. if an externally
+    ///   provided [`Func`] has a different function signature than required by the module import.
+    /// - If the [`Module`] has been compiled by another [`Engine`] than the one of the `context`.
+    ///
+    /// Upon failure the entities allocated for the new [`Instance`] are removed from the
+    /// `context` again unless they are still reachable, e.g. through imported tables.
     ///
     /// [`Linker`]: struct.Linker.html
     /// [`Func`]: [`crate::Func`]
+    /// [`Engine`]: crate::Engine
     pub(crate) fn instantiate<I>(
         &self,
         mut context: impl AsContextMut,
@@ -54,27 +62,65 @@ impl Module {
     where
         I: IntoIterator<Item = Extern>,
     {
+        self.check_engine(context.as_context().engine())?;
         context
             .as_context_mut()
             .store
             .check_new_instances_limit(1)?;
+        let before = context.as_context().store.inner.checkpoint();
         let handle = context.as_context_mut().store.inner.alloc_instance();
         let mut builder = InstanceEntity::build(self);
+        let result = self.instantiate_entities(&mut context, &mut builder, handle, externals);
+        let store = &mut context.as_context_mut().store.inner;
+        let after = store.checkpoint();
+        if let Err(error) = result {
+            store.rollback(&before, &after);
+            return Err(error);
+        }
+        // At this point the module instantiation is nearly done.
+        // The only thing that is missing is to run the `start` function.
+        Ok(InstancePre::new(handle, builder, before, after))
+    }
 
-        self.extract_imports(&context, &mut builder, externals)?;
-        self.extract_functions(&mut context, &mut builder, handle);
-        self.extract_tables(&mut context, &mut builder)?;
-        self.extract_memories(&mut context, &mut builder)?;
-        self.extract_globals(&mut context, &mut builder);
-        self.extract_exports(&mut builder);
-        self.extract_start_fn(&mut builder);
+    /// Returns `Ok` if the [`Module`] has been compiled by `engine`.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Module`] has been compiled by another [`Engine`].
+    pub(crate) fn check_engine(&self, engine: &Engine) -> Result<(), InstantiationError> {
+        if !Engine::same(self.engine(), engine) {
+            return Err(InstantiationError::EngineMismatch);
+        }
+        Ok(())
+    }
 
-        self.initialize_table_elements(&mut context, &mut builder)?;
-        self.initialize_memory_data(&mut context, &mut builder)?;
+    /// Allocates and initializes the entities of the [`Instance`] under construction.
+    ///
+    /// # Errors
+    ///
+    /// - If the given `externals` do not satisfy the required imports.
+    /// - If resource limits are exceeded or segments are out of bounds.
+    fn instantiate_entities<I>(
+        &self,
+        mut context: impl AsContextMut,
+        builder: &mut InstanceEntityBuilder,
+        handle: Instance,
+        externals: I,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = Extern>,
+    {
+        self.extract_imports(&context, builder, externals)?;
+        self.extract_functions(&mut context, builder, handle);
+        self.extract_tables(&mut context, builder)?;
+        self.extract_memories(&mut context, builder)?;
+        self.extract_globals(&mut context, builder);
+        self.extract_exports(builder);
+        self.extract_start_fn(builder);
 
-        // At this point the module instantiation is nearly done.
-        // The only thing that is missing is to run the `start` function.
-        Ok(InstancePre::new(handle, builder))
+        self.initialize_table_elements(&mut context, builder)?;
+        self.initialize_memory_data(&mut context, builder)?;
+        Ok(())
     }
 
     /// Extract the Wasm imports from the module and zips them with the given external values.
@@ -114,6 +160,7 @@ impl Module {
                     return Err(InstantiationError::ImportsExternalsLenMismatch)
                 }
             };
+            builder.push_import(external);
             match (import.ty(), external) {
                 (ExternType::Func(expected_signature), Extern::Func(func)) => {
                     let actual_signature = func.ty_dedup(context.as_context());
@@ -138,7 +185,7 @@ impl Module {
                     builder.push_table(table);
                 }
                 (ExternType::Memory(required), Extern::Memory(memory)) => {
-                    let imported = memory.dynamic_ty(context.as_context());
+                    let imported = memory.ty(context.as_context());
                     imported.is_subtype_or_err(required)?;
                     builder.push_memory(memory);
                 }
@@ -263,7 +310,7 @@ impl Module {
 
     /// Extracts the Wasm exports from the module and registers them into the [`Instance`].
     fn extract_exports(&self, builder: &mut InstanceEntityBuilder) {
-        for (field, idx) in &self.header.inner.exports {
+        for (field, idx) in self.header.inner.exports.iter() {
             let external = match idx {
                 export::ExternIdx::Func(func_index) => {
                     let func_index = func_index.into_u32();
@@ -303,6 +350,9 @@ impl Module {
         mut context: &mut impl AsContextMut,
         builder: &mut InstanceEntityBuilder,
     ) -> Result<(), Error> {
+        let lazy_table_init = context.as_context().engine().config().get_lazy_table_init();
+        // The instance functions shared by all lazily initialized tables.
+        let mut lazy_funcs: Option<Arc<[Func]>> = None;
         for segment in &self.header.inner.element_segments[..] {
             let element = ElementSegment::new(context.as_context_mut(), segment);
             if let ElementSegmentKind::Active(active) = segment.kind() {
@@ -325,8 +375,20 @@ impl Module {
                         offset: dst_index,
                         amount: len_items,
                     })?;
-                // Finally do the actual initialization of the table elements.
-                {
+                if lazy_table_init && table.ty(&context).element() == ValueType::FuncRef {
+                    // Only record the element segment so that table elements
+                    // are initialized upon their first access.
+                    let funcs = lazy_funcs
+                        .get_or_insert_with(|| builder.funcs().into())
+                        .clone();
+                    context
+                        .as_context_mut()
+                        .store
+                        .inner
+                        .resolve_table_mut(&table)
+                        .init_lazy(dst_index, segment.items_cloned(), funcs)?;
+                } else {
+                    // Finally do the actual initialization of the table elements.
                     let (table, element) = context
                         .as_context_mut()
                         .store
@@ -1,5 +1,12 @@
 use super::InstantiationError;
-use crate::{module::FuncIdx, AsContextMut, Error, Instance, InstanceEntityBuilder};
+use crate::{
+    module::FuncIdx,
+    store::StoreCheckpoint,
+    AsContextMut,
+    Error,
+    Instance,
+    InstanceEntityBuilder,
+};
 
 /// A partially instantiated [`Instance`] where the `start` function has not yet been executed.
 ///
@@ -12,12 +19,26 @@ use crate::{module::FuncIdx, AsContextMut, Error, Instance, InstanceEntityBuilde
 pub struct InstancePre {
     handle: Instance,
     builder: InstanceEntityBuilder,
+    /// The entities of the store before the instantiation.
+    before: StoreCheckpoint,
+    /// The entities of the store after the instantiation.
+    after: StoreCheckpoint,
 }
 
 impl InstancePre {
     /// Creates a new [`InstancePre`].
-    pub(super) fn new(handle: Instance, builder: InstanceEntityBuilder) -> Self {
-        Self { handle, builder }
+    pub(super) fn new(
+        handle: Instance,
+        builder: InstanceEntityBuilder,
+        before: StoreCheckpoint,
+        after: StoreCheckpoint,
+    ) -> Self {
+        Self {
+            handle,
+            builder,
+            before,
+            after,
+        }
     }
 
     /// Returns the index of the `start` function if any.
@@ -37,6 +58,17 @@ impl InstancePre {
     ///
     /// If executing the `start` function traps.
     ///
+    /// In this case the [`Instance`] is not returned and the instantiation has failed:
+    ///
+    /// - Mutations of imported entities, for example writes to an imported linear memory,
+    ///   remain visible as required by the Wasm specification.
+    /// - The [`Instance`] and all of its non-imported functions, tables, linear memories,
+    ///   global variables and segments are removed from the store again unless any of
+    ///   them might still be reachable. This is the case if a host function has been called
+    ///   or entities have been allocated since the instantiation, or if a function of the
+    ///   [`Instance`] has been stored into a table or global variable that is not owned by it.
+    ///   Otherwise the entities remain in the store until it is dropped.
+    ///
     /// # Panics
     ///
     /// If the `start` function is invalid albeit successful validation.
@@ -54,7 +86,10 @@ impl InstancePre {
                 .unwrap_or_else(|| {
                     panic!("encountered invalid start function after validation: {start_index}")
                 });
-            start_func.call(context.as_context_mut(), &[], &mut [])?
+            if let Err(error) = start_func.call(context.as_context_mut(), &[], &mut []) {
+                rollback(context, &self.before, &self.after);
+                return Err(error);
+            }
         }
         Ok(self.handle)
     }
@@ -64,11 +99,14 @@ impl InstancePre {
     /// # Errors
     ///
     /// If a `start` function exists that needs to be called for conformant module instantiation.
+    /// In this case the entities of the [`Instance`] are removed from the store as described
+    /// in [`InstancePre::start`].
     pub fn ensure_no_start(
         self,
         mut context: impl AsContextMut,
     ) -> Result<Instance, InstantiationError> {
         if let Some(index) = self.start_fn() {
+            rollback(context, &self.before, &self.after);
             return Err(InstantiationError::FoundStartFn { index });
         }
         context
@@ -79,3 +117,8 @@ impl InstancePre {
         Ok(self.handle)
     }
 }
+
+/// Removes the entities allocated by a failed instantiation from the store if possible.
+fn rollback(mut context: impl AsContextMut, before: &StoreCheckpoint, after: &StoreCheckpoint) {
+    context.as_context_mut().store.inner.rollback(before, after);
+}
@@ -0,0 +1,418 @@
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+use wasmparser::{BinaryReader, BinaryReaderError, CustomSectionReader, ProducersSectionReader};
+
+/// The name of the `producers` custom section.
+const PRODUCERS: &str = "producers";
+
+/// The name of the `target_features` custom section.
+const TARGET_FEATURES: &str = "target_features";
+
+/// The typed contents of the `producers` custom section of a Wasm module.
+///
+/// Describes the languages, tools and SDKs that were used to produce the Wasm module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Producers {
+    /// The fields of the `producers` section in the order of their appearance.
+    fields: Box<[ProducersField]>,
+}
+
+impl Producers {
+    /// Returns all fields of the [`Producers`] in the order of their appearance.
+    pub fn fields(&self) -> &[ProducersField] {
+        &self.fields
+    }
+
+    /// Returns the values of the field with `name` if any.
+    pub fn get(&self, name: &str) -> Option<&[ProducersValue]> {
+        self.fields
+            .iter()
+            .find(|field| field.name() == name)
+            .map(ProducersField::values)
+    }
+
+    /// Returns the source languages of the `language` field.
+    pub fn language(&self) -> &[ProducersValue] {
+        self.get("language").unwrap_or_default()
+    }
+
+    /// Returns the tools of the `processed-by` field.
+    pub fn processed_by(&self) -> &[ProducersValue] {
+        self.get("processed-by").unwrap_or_default()
+    }
+
+    /// Returns the SDKs of the `sdk` field.
+    pub fn sdk(&self) -> &[ProducersValue] {
+        self.get("sdk").unwrap_or_default()
+    }
+}
+
+/// A field of the [`Producers`] of a Wasm module, e.g. `language` or `processed-by`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProducersField {
+    /// The name of the field.
+    name: Box<str>,
+    /// The values of the field.
+    values: Box<[ProducersValue]>,
+}
+
+impl ProducersField {
+    /// Returns the name of the [`ProducersField`].
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the values of the [`ProducersField`].
+    pub fn values(&self) -> &[ProducersValue] {
+        &self.values
+    }
+}
+
+/// A named and versioned value of a [`ProducersField`], e.g. `rustc 1.78.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProducersValue {
+    /// The name of the language, tool or SDK.
+    name: Box<str>,
+    /// The version of the language, tool or SDK.
+    version: Box<str>,
+}
+
+impl ProducersValue {
+    /// Returns the name of the language, tool or SDK.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the version of the language, tool or SDK.
+    ///
+    /// This is empty if the producer did not specify a version.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+/// The prefix of a [`FeatureRequirement`] of the `target_features` custom section.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FeaturePrefix {
+    /// The `+` prefix: the Wasm module uses the feature.
+    Used,
+    /// The `-` prefix: the Wasm module must not be run on hosts that support the feature.
+    Disallowed,
+    /// The legacy `=` prefix: the Wasm module requires the feature.
+    Required,
+}
+
+impl FeaturePrefix {
+    /// Returns the [`FeaturePrefix`] encoded as `byte` if any.
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'+' => Some(Self::Used),
+            b'-' => Some(Self::Disallowed),
+            b'=' => Some(Self::Required),
+            _ => None,
+        }
+    }
+
+    /// Returns the character representation of the [`FeaturePrefix`].
+    pub fn as_char(self) -> char {
+        match self {
+            Self::Used => '+',
+            Self::Disallowed => '-',
+            Self::Required => '=',
+        }
+    }
+}
+
+/// An entry of the `target_features` custom section of a Wasm module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureRequirement {
+    /// The prefix describing how the feature relates to the Wasm module.
+    prefix: FeaturePrefix,
+    /// The name of the feature, e.g. `bulk-memory`.
+    name: Box<str>,
+}
+
+impl FeatureRequirement {
+    /// Returns the [`FeaturePrefix`] of the [`FeatureRequirement`].
+    pub fn prefix(&self) -> FeaturePrefix {
+        self.prefix
+    }
+
+    /// Returns the name of the feature, e.g. `bulk-memory`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Display for FeatureRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.prefix.as_char(), self.name)
+    }
+}
+
+/// A non-fatal issue encountered while parsing the metadata of a Wasm module.
+///
+/// The affected metadata is ignored while the Wasm module itself is still created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /// A custom section is malformed and has been ignored entirely.
+    MalformedSection {
+        /// The name of the custom section.
+        section: &'static str,
+        /// The offset within the Wasm binary at which the issue was found.
+        offset: usize,
+        /// A description of the issue.
+        message: Box<str>,
+    },
+    /// A custom section appeared more than once and all but the first were ignored.
+    DuplicateSection {
+        /// The name of the custom section.
+        section: &'static str,
+        /// The offset within the Wasm binary of the ignored custom section.
+        offset: usize,
+    },
+    /// A field of the `producers` section appeared more than once and all but the first were ignored.
+    DuplicateField {
+        /// The name of the field.
+        field: Box<str>,
+        /// The offset within the Wasm binary of the ignored field.
+        offset: usize,
+    },
+}
+
+impl ParseWarning {
+    /// Returns the offset within the Wasm binary at which the [`ParseWarning`] was found.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::MalformedSection { offset, .. }
+            | Self::DuplicateSection { offset, .. }
+            | Self::DuplicateField { offset, .. } => *offset,
+        }
+    }
+
+    /// Creates a [`ParseWarning::MalformedSection`] for `section` from `error`.
+    fn malformed(section: &'static str, error: BinaryReaderError) -> Self {
+        Self::MalformedSection {
+            section,
+            offset: error.offset(),
+            message: error.message().into(),
+        }
+    }
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MalformedSection {
+                section,
+                offset,
+                message,
+            } => write!(
+                f,
+                "ignored malformed `{section}` custom section at offset {offset}: {message}"
+            ),
+            Self::DuplicateSection { section, offset } => write!(
+                f,
+                "ignored duplicate `{section}` custom section at offset {offset}"
+            ),
+            Self::DuplicateField { field, offset } => write!(
+                f,
+                "ignored duplicate `producers` field `{field}` at offset {offset}"
+            ),
+        }
+    }
+}
+
+/// The metadata of a Wasm module parsed from its standard custom sections.
+#[derive(Debug, Default)]
+pub struct ModuleMetadata {
+    /// The contents of the `producers` custom section if any.
+    pub producers: Option<Producers>,
+    /// The contents of the `target_features` custom section if any.
+    pub target_features: Option<Box<[FeatureRequirement]>>,
+    /// The non-fatal issues encountered while parsing the metadata.
+    pub warnings: Box<[ParseWarning]>,
+}
+
+/// Parses the [`ModuleMetadata`] of a Wasm module from its custom sections.
+#[derive(Debug, Default)]
+pub struct ModuleMetadataBuilder {
+    /// The contents of the `producers` custom section if any.
+    producers: Option<Producers>,
+    /// The contents of the `target_features` custom section if any.
+    target_features: Option<Box<[FeatureRequirement]>>,
+    /// Is `true` once a `producers` custom section has been encountered.
+    seen_producers: bool,
+    /// Is `true` once a `target_features` custom section has been encountered.
+    seen_target_features: bool,
+    /// The non-fatal issues encountered while parsing the metadata.
+    warnings: Vec<ParseWarning>,
+}
+
+impl ModuleMetadataBuilder {
+    /// Processes a custom section of the Wasm module.
+    ///
+    /// Custom sections other than `producers` and `target_features` are ignored.
+    pub fn process_custom(&mut self, section: CustomSectionReader) {
+        match section.name() {
+            PRODUCERS => {
+                if self.is_duplicate(PRODUCERS, &section) {
+                    return;
+                }
+                self.producers = self.parse_producers(&section);
+            }
+            TARGET_FEATURES => {
+                if self.is_duplicate(TARGET_FEATURES, &section) {
+                    return;
+                }
+                self.target_features = self.parse_target_features(&section);
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns `true` and records a warning if the custom section called `name` has been seen before.
+    fn is_duplicate(&mut self, name: &'static str, section: &CustomSectionReader) -> bool {
+        let seen = match name {
+            PRODUCERS => &mut self.seen_producers,
+            _ => &mut self.seen_target_features,
+        };
+        if *seen {
+            self.warnings.push(ParseWarning::DuplicateSection {
+                section: name,
+                offset: section.range().start,
+            });
+            return true;
+        }
+        *seen = true;
+        false
+    }
+
+    /// Parses the `producers` custom `section`.
+    ///
+    /// Returns `None` and records a warning if `section` is malformed.
+    fn parse_producers(&mut self, section: &CustomSectionReader) -> Option<Producers> {
+        match read_producers(section, &mut self.warnings) {
+            Ok(producers) => Some(producers),
+            Err(error) => {
+                self.warnings.push(ParseWarning::malformed(PRODUCERS, error));
+                None
+            }
+        }
+    }
+
+    /// Parses the `target_features` custom `section`.
+    ///
+    /// Returns `None` and records a warning if `section` is malformed.
+    fn parse_target_features(
+        &mut self,
+        section: &CustomSectionReader,
+    ) -> Option<Box<[FeatureRequirement]>> {
+        match read_target_features(section) {
+            Ok(features) => Some(features),
+            Err(warning) => {
+                self.warnings.push(warning);
+                None
+            }
+        }
+    }
+
+    /// Finishes construction of the [`ModuleMetadata`].
+    pub fn finish(self) -> ModuleMetadata {
+        ModuleMetadata {
+            producers: self.producers,
+            target_features: self.target_features,
+            warnings: self.warnings.into(),
+        }
+    }
+}
+
+/// Reads the [`Producers`] from the `producers` custom `section`.
+///
+/// Records a [`ParseWarning::DuplicateField`] in `warnings` for every ignored duplicate field.
+///
+/// # Errors
+///
+/// If `section` is malformed.
+fn read_producers(
+    section: &CustomSectionReader,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<Producers, BinaryReaderError> {
+    let reader = ProducersSectionReader::new(section.data(), section.data_offset())?;
+    let mut fields = Vec::<ProducersField>::new();
+    let mut duplicates = Vec::new();
+    for field in reader.into_iter_with_offsets() {
+        let (offset, field) = field?;
+        let values = field
+            .values
+            .into_iter()
+            .map(|value| {
+                value.map(|value| ProducersValue {
+                    name: value.name.into(),
+                    version: value.version.into(),
+                })
+            })
+            .collect::<Result<Box<[_]>, _>>()?;
+        if fields.iter().any(|known| known.name() == field.name) {
+            duplicates.push(ParseWarning::DuplicateField {
+                field: field.name.into(),
+                offset,
+            });
+            continue;
+        }
+        fields.push(ProducersField {
+            name: field.name.into(),
+            values,
+        });
+    }
+    // Note: duplicate fields are only reported if the section as a whole is well-formed.
+    warnings.extend(duplicates);
+    Ok(Producers {
+        fields: fields.into(),
+    })
+}
+
+/// Reads the [`FeatureRequirement`] entries from the `target_features` custom `section`.
+///
+/// # Errors
+///
+/// If `section` is malformed.
+fn read_target_features(
+    section: &CustomSectionReader,
+) -> Result<Box<[FeatureRequirement]>, ParseWarning> {
+    let malformed = |offset: usize, message: String| ParseWarning::MalformedSection {
+        section: TARGET_FEATURES,
+        offset,
+        message: message.into(),
+    };
+    let from_error = |error: BinaryReaderError| ParseWarning::malformed(TARGET_FEATURES, error);
+    let mut reader = BinaryReader::new_with_offset(section.data(), section.data_offset());
+    let len = reader.read_var_u32().map_err(from_error)?;
+    let mut features = Vec::new();
+    for _ in 0..len {
+        let offset = reader.original_position();
+        let byte = reader.read_u8().map_err(from_error)?;
+        let Some(prefix) = FeaturePrefix::from_byte(byte) else {
+            return Err(malformed(
+                offset,
+                format!("unknown feature prefix: 0x{byte:02X}"),
+            ));
+        };
+        let name = reader.read_string().map_err(from_error)?;
+        features.push(FeatureRequirement {
+            prefix,
+            name: name.into(),
+        });
+    }
+    if !reader.eof() {
+        let offset = reader.original_position();
+        return Err(malformed(offset, "unexpected trailing bytes".to_string()));
+    }
+    Ok(features.into())
+}
@@ -1,4 +1,5 @@
 mod builder;
+mod clone;
 mod data;
 mod element;
 mod export;
@@ -6,32 +7,54 @@ mod global;
 mod import;
 mod init_expr;
 mod instantiate;
+mod metadata;
 mod parser;
 mod read;
+mod translation;
 pub(crate) mod utils;
+#[cfg(feature = "wat")]
+mod wat;
 
+#[cfg(feature = "std")]
+use self::parser::parse_with_progress;
+#[cfg(feature = "wat")]
+pub use self::wat::WatError;
 use self::{
     builder::ModuleBuilder,
     export::ExternIdx,
     global::Global,
-    import::{ExternTypeIdx, Import},
-    parser::{parse, parse_unchecked},
-};
-pub(crate) use self::{
-    data::{DataSegment, DataSegmentKind},
-    element::{ElementSegment, ElementSegmentItems, ElementSegmentKind},
-    init_expr::ConstExpr,
-    utils::WasmiValueType,
+    import::ExternTypeIdx,
+    metadata::ModuleMetadata,
+    parser::{parse, parse_unchecked, parse_with_ir_funcs},
 };
 pub use self::{
+    clone::EngineMismatchError,
     export::{ExportType, FuncIdx, MemoryIdx, ModuleExportsIter, TableIdx},
     global::GlobalIdx,
     import::{FuncTypeIdx, ImportName},
     instantiate::{InstancePre, InstantiationError},
+    metadata::{
+        FeaturePrefix,
+        FeatureRequirement,
+        ParseWarning,
+        Producers,
+        ProducersField,
+        ProducersValue,
+    },
     read::{Read, ReadError},
+    translation::{CompiledFuncBytes, TranslationContext, TranslationContextBuilder},
+};
+pub(crate) use self::{
+    data::{DataSegment, DataSegmentKind},
+    element::{ElementSegment, ElementSegmentItems, ElementSegmentKind},
+    export::ExportMap,
+    init_expr::ConstExpr,
+    utils::WasmiValueType,
 };
 use crate::{
-    engine::{CompiledFunc, DedupFuncType, EngineWeak},
+    build::IrFunc,
+    engine::{CodeOwner, CompiledFunc, DedupFuncType, EngineWeak},
+    errors::FuncError,
     Engine,
     Error,
     ExternType,
@@ -40,16 +63,25 @@ use crate::{
     MemoryType,
     TableType,
 };
-use alloc::{boxed::Box, collections::BTreeMap, sync::Arc};
+use alloc::{boxed::Box, collections::BTreeMap, sync::Arc, vec::Vec};
 use core::{iter, slice::Iter as SliceIter};
 use wasmparser::{FuncValidatorAllocations, Parser, ValidPayload, Validator};
 
 /// A parsed and validated WebAssembly module.
-#[derive(Debug)]
+///
+/// Cloning a [`Module`] is cheap since all clones share the same underlying data.
+#[derive(Debug, Clone)]
 pub struct Module {
     engine: Engine,
+    /// Keeps the functions of the [`Module`] alive in the [`Engine`].
+    code_owner: CodeOwner,
     header: ModuleHeader,
-    data_segments: Box<[DataSegment]>,
+    data_segments: Arc<[DataSegment]>,
+    /// The metadata parsed from the standard custom sections of the [`Module`].
+    metadata: Arc<ModuleMetadata>,
+    /// The original Wasm binary of the [`Module`] used by [`Module::to_wat`].
+    #[cfg(feature = "wat")]
+    wasm: Arc<[u8]>,
 }
 
 /// A parsed and validated WebAssembly module header.
@@ -68,7 +100,7 @@ struct ModuleHeaderInner {
     memories: Box<[MemoryType]>,
     globals: Box<[GlobalType]>,
     globals_init: Box<[ConstExpr]>,
-    exports: BTreeMap<Box<str>, ExternIdx>,
+    exports: ExportMap<ExternIdx>,
     start: Option<FuncIdx>,
     compiled_funcs: Box<[CompiledFunc]>,
     compiled_funcs_idx: BTreeMap<CompiledFunc, FuncIdx>,
@@ -108,6 +140,13 @@ impl ModuleHeader {
         Some(self.inner.compiled_funcs[index])
     }
 
+    /// Returns the [`ImportName`] of the imported function at [`FuncIdx`].
+    ///
+    /// Returns `None` if [`FuncIdx`] refers to an internal function.
+    pub fn get_import_name_of_func(&self, func_idx: FuncIdx) -> Option<&ImportName> {
+        self.inner.imports.get_func_name(func_idx)
+    }
+
     /// Returns the [`FuncIdx`] for the given [`CompiledFunc`].
     pub fn get_func_index(&self, func: CompiledFunc) -> Option<FuncIdx> {
         self.inner.compiled_funcs_idx.get(&func).copied()
@@ -127,13 +166,43 @@ impl ModuleHeader {
             (global_type, Some(init_expr))
         }
     }
+
+    /// Returns the number of function types.
+    pub fn len_func_types(&self) -> usize {
+        self.inner.func_types.len()
+    }
+
+    /// Returns the number of imported and internal functions.
+    pub fn len_funcs(&self) -> usize {
+        self.inner.funcs.len()
+    }
+
+    /// Returns the number of imported and internal tables.
+    pub fn len_tables(&self) -> usize {
+        self.inner.tables.len()
+    }
+
+    /// Returns the number of imported and internal linear memories.
+    pub fn len_memories(&self) -> usize {
+        self.inner.memories.len()
+    }
+
+    /// Returns the number of imported and internal global variables.
+    pub fn len_globals(&self) -> usize {
+        self.inner.globals.len()
+    }
+
+    /// Returns the number of element segments.
+    pub fn len_element_segments(&self) -> usize {
+        self.inner.element_segments.len()
+    }
 }
 
 /// The index of the default Wasm linear memory.
 pub(crate) const DEFAULT_MEMORY_INDEX: u32 = 0;
 
 /// An imported item declaration in the [`Module`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Imported {
     /// The name of an imported [`Func`].
     ///
@@ -152,7 +221,7 @@ pub enum Imported {
 }
 
 /// The import names of the [`Module`] imports.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ModuleImports {
     /// All names and types of all imported items.
     items: Box<[Imported]>,
@@ -182,6 +251,20 @@ impl ModuleImports {
     pub fn len_funcs(&self) -> usize {
         self.len_funcs
     }
+
+    /// Returns the [`ImportName`] of the imported function at [`FuncIdx`].
+    ///
+    /// Returns `None` if [`FuncIdx`] does not refer to an imported function.
+    pub fn get_func_name(&self, func_idx: FuncIdx) -> Option<&ImportName> {
+        let index = func_idx.into_u32() as usize;
+        if index >= self.len_funcs {
+            return None;
+        }
+        match &self.items[index] {
+            Imported::Func(name) => Some(name),
+            imported => panic!("expected imported function at {index} but found: {imported:?}"),
+        }
+    }
 }
 
 impl Module {
@@ -230,6 +313,50 @@ impl Module {
         unsafe { parse_unchecked(engine, stream).map_err(Into::into) }
     }
 
+    /// Creates a new Wasm [`Module`] from the given byte stream and pre-built Wasmi bytecode.
+    ///
+    /// # Note
+    ///
+    /// Internal functions with an `ir_funcs` entry use the validated [`IrFunc`]
+    /// instead of translating their Wasm function body yielded by `stream`.
+    ///
+    /// # Errors
+    ///
+    /// - If the `stream` cannot be parsed as a valid Wasm module.
+    /// - If any of the `ir_funcs` fails to validate.
+    ///
+    pub(crate) fn new_with_ir_funcs(
+        engine: &Engine,
+        stream: impl Read,
+        ir_funcs: Vec<Option<IrFunc>>,
+    ) -> Result<Self, Error> {
+        parse_with_ir_funcs(engine, stream, ir_funcs)
+    }
+
+    /// Creates a new Wasm [`Module`] from the given byte stream and reports its translation `progress`.
+    ///
+    /// # Note
+    ///
+    /// - Before translating each function body and after translating all function bodies
+    ///   `progress` is called with the number of translated and total function bodies.
+    /// - Translation is cancelled as soon as `progress` returns `false`.
+    ///
+    /// # Errors
+    ///
+    /// - If the `stream` cannot be parsed as a valid Wasm module.
+    /// - If the translation has been cancelled by `progress`.
+    #[cfg(feature = "std")]
+    pub(crate) fn new_with_progress<P>(
+        engine: &Engine,
+        stream: impl Read,
+        progress: P,
+    ) -> Result<Self, Error>
+    where
+        P: FnMut(u32, u32) -> bool + 'static,
+    {
+        parse_with_progress(engine, stream, progress)
+    }
+
     /// Returns the [`Engine`] used during creation of the [`Module`].
     pub fn engine(&self) -> &Engine {
         &self.engine
@@ -268,6 +395,110 @@ impl Module {
         Ok(())
     }
 
+    /// Verifies that the translated Wasmi bytecode of all internal functions of the [`Module`] is well-formed.
+    ///
+    /// # Note
+    ///
+    /// - This is mostly useful for fuzzing since Wasmi bytecode translation
+    ///   is expected to always produce well-formed Wasmi bytecode.
+    /// - Functions that have not yet been compiled due to lazy compilation
+    ///   are compiled by this method before being verified.
+    ///
+    /// # Errors
+    ///
+    /// - If lazy compilation of any of the internal functions fails.
+    /// - If the Wasmi bytecode of any of the internal functions is malformed.
+    pub fn verify_bytecode(&self) -> Result<(), Error> {
+        for func in self.header.inner.compiled_funcs.iter().copied() {
+            self.engine.verify_func(func)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the [`CompiledFunc`] of the function at `func_index` of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// The [`CompiledFunc`] can be used to query the address map of the function
+    /// via [`Engine::address_map`].
+    ///
+    /// Returns `None` if `func_index` is out of bounds or refers to an imported function.
+    pub fn get_compiled_func(&self, func_index: u32) -> Option<CompiledFunc> {
+        if func_index as usize >= self.header.inner.funcs.len() {
+            return None;
+        }
+        self.header.get_compiled_func(FuncIdx::from(func_index))
+    }
+
+    /// Compiles the internal functions at `func_indices` of the [`Module`] ahead of their first call.
+    ///
+    /// If `transitive` is `true` all internal functions directly called by the
+    /// compiled functions are compiled as well, transitively.
+    ///
+    /// # Note
+    ///
+    /// - This is useful with [`CompilationMode::Lazy`] in order to avoid the latency
+    ///   of compiling a rarely used function upon its first call.
+    /// - Already compiled functions are skipped.
+    /// - Functions called indirectly via tables are not compiled transitively.
+    /// - This is safe to call while other threads execute or compile functions of the [`Module`].
+    ///   Each function is compiled at most once.
+    /// - Compiling only requires shared access to the [`Engine`] and thus never waits for
+    ///   Wasm executions. Therefore host functions may call this during Wasm executions,
+    ///   e.g. to compile the functions that their calling Wasm function is about to call.
+    /// - Use [`Engine::compiled_functions`] to query the compilation state of the functions.
+    ///
+    /// # Errors
+    ///
+    /// - If any of the `func_indices` does not refer to an internal function of the [`Module`].
+    /// - If the compilation of any of the functions fails.
+    ///
+    /// [`CompilationMode::Lazy`]: crate::CompilationMode::Lazy
+    pub fn compile_functions(&self, func_indices: &[u32], transitive: bool) -> Result<(), Error> {
+        let funcs = func_indices
+            .iter()
+            .map(|&func_index| {
+                self.get_compiled_func(func_index)
+                    .ok_or(FuncError::NotAnInternalFunc { func_index })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.engine.compile_funcs(funcs, transitive)
+    }
+
+    /// Returns the [`DedupFuncType`] at `func_type_index` of the type section of the [`Module`].
+    ///
+    /// # Note
+    ///
+    /// The [`DedupFuncType`] can be used to query the index of the function type
+    /// within the [`Engine`] via [`Engine::func_type_index`].
+    ///
+    /// Returns `None` if `func_type_index` is out of bounds.
+    pub fn get_func_type(&self, func_type_index: u32) -> Option<DedupFuncType> {
+        self.header
+            .inner
+            .func_types
+            .get(func_type_index as usize)
+            .copied()
+    }
+
+    /// Returns an estimate of the [`Engine`] memory used by the code of the [`Module`] in bytes.
+    ///
+    /// # Note
+    ///
+    /// - This includes the Wasmi bytecode and function local constant values of compiled
+    ///   functions as well as the Wasm function bodies of not yet lazily compiled functions.
+    /// - Functions that are currently compiled lazily on other threads are measured
+    ///   once their compilation has finished.
+    pub fn code_size_estimate(&self) -> usize {
+        self.engine
+            .code_size_of(&self.header.inner.compiled_funcs[..])
+    }
+
+    /// Returns the [`CodeOwner`] of the functions of the [`Module`].
+    pub(crate) fn code_owner(&self) -> &CodeOwner {
+        &self.code_owner
+    }
+
     /// Returns the number of non-imported functions of the [`Module`].
     pub(crate) fn len_funcs(&self) -> usize {
         self.header.inner.funcs.len()
@@ -324,6 +555,18 @@ impl Module {
         }
     }
 
+    /// Returns an iterator over the function indices and [`CompiledFunc`]s of the internal functions.
+    pub(crate) fn internal_compiled_funcs(&self) -> impl Iterator<Item = (u32, CompiledFunc)> + '_ {
+        let len_imported = self.header.inner.imports.len_funcs as u32;
+        self.header
+            .inner
+            .compiled_funcs
+            .iter()
+            .copied()
+            .zip(len_imported..)
+            .map(|(func, func_index)| (func_index, func))
+    }
+
     /// Returns an iterator over the [`MemoryType`] of internal linear memories.
     fn internal_memories(&self) -> SliceIter<MemoryType> {
         let len_imported = self.header.inner.imports.len_memories;
@@ -375,6 +618,31 @@ impl Module {
         Some(ty)
     }
 
+    /// Returns the contents of the `producers` custom section of the [`Module`] if any.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` if the `producers` custom section is missing or malformed.
+    /// Read [`Module::parse_warnings`] to find out why it might have been ignored.
+    pub fn producers(&self) -> Option<&Producers> {
+        self.metadata.producers.as_ref()
+    }
+
+    /// Returns the entries of the `target_features` custom section of the [`Module`] if any.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` if the `target_features` custom section is missing or malformed.
+    /// Read [`Module::parse_warnings`] to find out why it might have been ignored.
+    pub fn target_features(&self) -> Option<&[FeatureRequirement]> {
+        self.metadata.target_features.as_deref()
+    }
+
+    /// Returns the non-fatal issues encountered while parsing the custom sections of the [`Module`].
+    pub fn parse_warnings(&self) -> &[ParseWarning] {
+        &self.metadata.warnings
+    }
+
     /// Returns the [`ExternType`] for a given [`ExternIdx`].
     ///
     /// # Note
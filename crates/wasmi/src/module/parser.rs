@@ -2,7 +2,8 @@ use super::{
     builder::ModuleHeaderBuilder,
     export::ExternIdx,
     global::Global,
-    import::{FuncTypeIdx, Import},
+    import::FuncTypeIdx,
+    metadata::ModuleMetadataBuilder,
     DataSegment,
     ElementSegment,
     FuncIdx,
@@ -11,11 +12,20 @@ use super::{
     ModuleHeader,
     Read,
 };
-use crate::{engine::CompiledFunc, Engine, Error, FuncType, MemoryType, TableType};
+use crate::{
+    build::IrFunc,
+    engine::{CodeOwner, CompiledFunc, ModuleLimit, TranslationError},
+    Engine,
+    Error,
+    FuncType,
+    MemoryType,
+    TableType,
+};
 use alloc::{boxed::Box, vec::Vec};
 use core::ops::Range;
 use wasmparser::{
     Chunk,
+    CustomSectionReader,
     DataSectionReader,
     ElementSectionReader,
     Encoding,
@@ -56,18 +66,81 @@ pub unsafe fn parse_unchecked(engine: &Engine, stream: impl Read) -> Result<Modu
     unsafe { ModuleParser::new(engine).parse_unchecked(stream) }
 }
 
+/// Parse, validate and translate the Wasm bytecode stream into Wasm IR bytecode.
+///
+/// - Internal functions with an `ir_funcs` entry use the validated [`IrFunc`]
+///   instead of translating their Wasm function body.
+/// - Indices of `ir_funcs` refer to internal functions, excluding imported functions.
+///
+/// # Errors
+///
+/// - If the Wasm bytecode stream fails to parse, validate or translate.
+/// - If any of the `ir_funcs` fails to validate.
+pub fn parse_with_ir_funcs(
+    engine: &Engine,
+    stream: impl Read,
+    ir_funcs: Vec<Option<IrFunc>>,
+) -> Result<Module, Error> {
+    let mut parser = ModuleParser::new(engine);
+    parser.ir_funcs = ir_funcs;
+    parser.parse(stream)
+}
+
+/// Parse, validate and translate the Wasm bytecode stream into Wasm IR bytecode.
+///
+/// - Before processing each function body and after processing all function bodies
+///   `progress` is called with the number of processed and total function bodies.
+/// - Parsing is cancelled as soon as `progress` returns `false`.
+///
+/// # Errors
+///
+/// - If the Wasm bytecode stream fails to parse, validate or translate.
+/// - If parsing has been cancelled by `progress`.
+#[cfg(feature = "std")]
+pub fn parse_with_progress<P>(engine: &Engine, stream: impl Read, progress: P) -> Result<Module, Error>
+where
+    P: FnMut(u32, u32) -> bool + 'static,
+{
+    let mut parser = ModuleParser::new(engine);
+    parser.progress = Some(Box::new(progress));
+    parser.parse(stream)
+}
+
+/// Observes the number of processed function bodies out of all function bodies.
+///
+/// Returns `false` in order to cancel parsing.
+type ParseProgress = dyn FnMut(u32, u32) -> bool;
+
 /// Context used to construct a WebAssembly module from a stream of bytes.
 pub struct ModuleParser {
     /// The engine used for translation.
     engine: Engine,
+    /// The owner of all functions allocated for the parsed Wasm module.
+    code_owner: CodeOwner,
     /// The Wasm validator used throughout stream parsing.
     validator: Validator,
     /// The underlying Wasm parser.
     parser: WasmParser,
     /// The number of compiled or processed functions.
     compiled_funcs: u32,
+    /// Pre-built Wasmi bytecode replacing the Wasm function bodies of internal functions.
+    ir_funcs: Vec<Option<IrFunc>>,
+    /// The number of function bodies as declared by the code section.
+    len_func_bodies: u32,
+    /// Observes the progress of function body processing if any.
+    progress: Option<Box<ParseProgress>>,
+    /// The number of data segments as declared by the data count section.
+    len_data_segments: u32,
     /// Flag, `true` when `stream` is at the end.
     eof: bool,
+    /// The metadata parsed from the standard custom sections.
+    metadata: ModuleMetadataBuilder,
+    /// The original Wasm binary pulled from `stream` so far.
+    #[cfg(feature = "wat")]
+    wasm: Vec<u8>,
+    /// The number of bytes pulled from `stream` so far.
+    #[cfg(feature = "tracing")]
+    len_bytes: usize,
 }
 
 /// The mode of Wasm validation when parsing a Wasm module.
@@ -86,10 +159,20 @@ impl ModuleParser {
         let parser = WasmParser::new(0);
         Self {
             engine: engine.clone(),
+            code_owner: CodeOwner::default(),
             validator,
             parser,
             compiled_funcs: 0,
+            ir_funcs: Vec::new(),
+            len_func_bodies: 0,
+            progress: None,
+            len_data_segments: 0,
             eof: false,
+            metadata: ModuleMetadataBuilder::default(),
+            #[cfg(feature = "wat")]
+            wasm: Vec::new(),
+            #[cfg(feature = "tracing")]
+            len_bytes: 0,
         }
     }
 
@@ -126,13 +209,35 @@ impl ModuleParser {
         mut self,
         validation_mode: ValidationMode,
         mut stream: impl Read,
+    ) -> Result<Module, Error> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "parse_module",
+            size = tracing::field::Empty,
+            num_funcs = tracing::field::Empty,
+        )
+        .entered();
+        let module = self.parse_sections(validation_mode, &mut stream);
+        #[cfg(feature = "tracing")]
+        span.record("size", self.len_bytes)
+            .record("num_funcs", self.len_func_bodies);
+        module
+    }
+
+    /// Parses, validates and translates all sections of the Wasm bytecode `stream`.
+    ///
+    /// # Errors
+    ///
+    /// If the Wasm bytecode stream fails to parse, validate or translate.
+    fn parse_sections(
+        &mut self,
+        validation_mode: ValidationMode,
+        stream: &mut impl Read,
     ) -> Result<Module, Error> {
         let mut buffer = Vec::new();
-        let header = Self::parse_header(&mut self, &mut stream, &mut buffer)?;
-        let builder =
-            Self::parse_code(&mut self, validation_mode, &mut stream, &mut buffer, header)?;
-        let module = Self::parse_data(&mut self, &mut stream, &mut buffer, builder)?;
-        Ok(module)
+        let header = self.parse_header(stream, &mut buffer)?;
+        let builder = self.parse_code(validation_mode, stream, &mut buffer, header)?;
+        self.parse_data(stream, &mut buffer, builder)
     }
 
     /// Parse the Wasm module header.
@@ -150,11 +255,11 @@ impl ModuleParser {
         stream: &mut impl Read,
         buffer: &mut Vec<u8>,
     ) -> Result<ModuleHeader, Error> {
-        let mut header = ModuleHeaderBuilder::new(&self.engine);
+        let mut header = ModuleHeaderBuilder::new(&self.engine, &self.code_owner);
         loop {
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
-                    self.eof = Self::pull_bytes(buffer, hint, stream)?;
+                    self.eof = self.pull_bytes(buffer, hint, stream)?;
                     if self.eof {
                         break;
                     }
@@ -201,7 +306,7 @@ impl ModuleParser {
                         }
                         Payload::DataSection(_) => break,
                         Payload::End(_) => break,
-                        Payload::CustomSection { .. } => Ok(()),
+                        Payload::CustomSection(section) => self.process_custom(section),
                         Payload::UnknownSection { id, range, .. } => {
                             self.process_unknown(id, range)
                         }
@@ -236,7 +341,7 @@ impl ModuleParser {
         loop {
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
-                    self.eof = Self::pull_bytes(buffer, hint, stream)?;
+                    self.eof = self.pull_bytes(buffer, hint, stream)?;
                 }
                 Chunk::Parsed { consumed, payload } => {
                     match payload {
@@ -250,7 +355,7 @@ impl ModuleParser {
                             let bytes = &buffer[start..consumed];
                             self.process_code_entry(func_body, validation_mode, bytes, &header)?;
                         }
-                        Payload::CustomSection { .. } => {}
+                        Payload::CustomSection(section) => self.process_custom(section)?,
                         Payload::UnknownSection { id, range, .. } => {
                             self.process_unknown(id, range)?
                         }
@@ -261,6 +366,7 @@ impl ModuleParser {
                 }
             }
         }
+        self.report_progress()?;
         Ok(ModuleBuilder::new(header))
     }
 
@@ -273,7 +379,7 @@ impl ModuleParser {
         loop {
             match self.parser.parse(&buffer[..], self.eof)? {
                 Chunk::NeedMoreData(hint) => {
-                    self.eof = Self::pull_bytes(buffer, hint, stream)?;
+                    self.eof = self.pull_bytes(buffer, hint, stream)?;
                 }
                 Chunk::Parsed { consumed, payload } => {
                     match payload {
@@ -285,7 +391,7 @@ impl ModuleParser {
                             buffer.drain(..consumed);
                             break;
                         }
-                        Payload::CustomSection { .. } => {}
+                        Payload::CustomSection(section) => self.process_custom(section)?,
                         Payload::UnknownSection { id, range, .. } => {
                             self.process_unknown(id, range)?
                         }
@@ -298,7 +404,13 @@ impl ModuleParser {
                 }
             }
         }
-        Ok(builder.finish(&self.engine))
+        Ok(builder.finish(
+            &self.engine,
+            self.code_owner.clone(),
+            core::mem::take(&mut self.metadata).finish(),
+            #[cfg(feature = "wat")]
+            core::mem::take(&mut self.wasm),
+        ))
     }
 
     /// Pulls more bytes from the `stream` in order to produce Wasm payload.
@@ -308,7 +420,12 @@ impl ModuleParser {
     /// # Note
     ///
     /// Uses `hint` to efficiently preallocate enough space for the next payload.
-    fn pull_bytes(buffer: &mut Vec<u8>, hint: u64, stream: &mut impl Read) -> Result<bool, Error> {
+    fn pull_bytes(
+        &mut self,
+        buffer: &mut Vec<u8>,
+        hint: u64,
+        stream: &mut impl Read,
+    ) -> Result<bool, Error> {
         // Use the hint to preallocate more space, then read
         // some more data into the buffer.
         //
@@ -318,10 +435,27 @@ impl ModuleParser {
         buffer.extend((0..hint).map(|_| 0u8));
         let read_bytes = stream.read(&mut buffer[len..])?;
         buffer.truncate(len + read_bytes);
+        #[cfg(feature = "wat")]
+        self.wasm.extend_from_slice(&buffer[len..]);
+        #[cfg(feature = "tracing")]
+        {
+            self.len_bytes += read_bytes;
+        }
         let reached_end = read_bytes == 0;
         Ok(reached_end)
     }
 
+    /// Processes a Wasm custom section.
+    ///
+    /// # Note
+    ///
+    /// This extracts the `producers` and `target_features` metadata of the [`Module`].
+    /// Malformed metadata is ignored and reported via [`Module::parse_warnings`].
+    fn process_custom(&mut self, section: CustomSectionReader) -> Result<(), Error> {
+        self.metadata.process_custom(section);
+        Ok(())
+    }
+
     /// Processes the end of the Wasm binary.
     fn process_end(&mut self, offset: usize) -> Result<(), Error> {
         self.validator.end(offset)?;
@@ -340,6 +474,37 @@ impl ModuleParser {
             .map_err(Into::into)
     }
 
+    /// Ensures that `found` does not exceed the configured [`ModuleLimits`] for `which`.
+    ///
+    /// # Note
+    ///
+    /// This is checked before the entities of a section are validated or allocated.
+    ///
+    /// # Errors
+    ///
+    /// If `found` exceeds the configured limit.
+    ///
+    /// [`ModuleLimits`]: crate::ModuleLimits
+    fn ensure_limit(&self, which: ModuleLimit, found: u32) -> Result<(), Error> {
+        self.engine
+            .config()
+            .module_limits()
+            .ensure(which, found)
+            .map_err(Error::from)
+    }
+
+    /// Ensures that the import or export `name` does not exceed [`ModuleLimits::max_name_length`].
+    ///
+    /// # Errors
+    ///
+    /// If `name` is longer than the configured limit.
+    ///
+    /// [`ModuleLimits::max_name_length`]: crate::ModuleLimits::max_name_length
+    fn ensure_name_length(&self, name: &str) -> Result<(), Error> {
+        let len = u32::try_from(name.len()).unwrap_or(u32::MAX);
+        self.ensure_limit(ModuleLimit::NameLength, len)
+    }
+
     /// Processes the Wasm type section.
     ///
     /// # Note
@@ -354,6 +519,7 @@ impl ModuleParser {
         section: TypeSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        self.ensure_limit(ModuleLimit::Types, section.count())?;
         self.validator.type_section(&section)?;
         let func_types = section.into_iter().map(|result| match result? {
             wasmparser::Type::Func(ty) => Ok(FuncType::from_wasmparser(ty)),
@@ -377,10 +543,14 @@ impl ModuleParser {
         section: ImportSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        self.ensure_limit(ModuleLimit::Imports, section.count())?;
         self.validator.import_section(&section)?;
-        let imports = section
-            .into_iter()
-            .map(|import| import.map(Import::from).map_err(Error::from));
+        let imports = section.into_iter().map(|import| {
+            let import = import?;
+            self.ensure_name_length(import.module)?;
+            self.ensure_name_length(import.name)?;
+            Ok(import)
+        });
         header.push_imports(imports)?;
         Ok(())
     }
@@ -414,6 +584,7 @@ impl ModuleParser {
         section: FunctionSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        self.ensure_limit(ModuleLimit::Functions, section.count())?;
         self.validator.function_section(&section)?;
         let funcs = section
             .into_iter()
@@ -436,6 +607,7 @@ impl ModuleParser {
         section: TableSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        self.ensure_limit(ModuleLimit::Tables, section.count())?;
         self.validator.table_section(&section)?;
         let tables = section
             .into_iter()
@@ -458,6 +630,7 @@ impl ModuleParser {
         section: MemorySectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        self.ensure_limit(ModuleLimit::Memories, section.count())?;
         self.validator.memory_section(&section)?;
         let memories = section
             .into_iter()
@@ -490,6 +663,7 @@ impl ModuleParser {
         section: GlobalSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        self.ensure_limit(ModuleLimit::Globals, section.count())?;
         self.validator.global_section(&section)?;
         let globals = section
             .into_iter()
@@ -512,14 +686,17 @@ impl ModuleParser {
         section: ExportSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
-        self.validator.export_section(&section)?;
-        let exports = section.into_iter().map(|export| {
+        // Note: the exports are processed before they are validated so that
+        //       overlong and duplicate export names yield more informative errors.
+        let exports = section.clone().into_iter().map(|export| {
             let export = export?;
+            self.ensure_name_length(export.name)?;
             let field: Box<str> = export.name.into();
             let idx = ExternIdx::new(export.kind, export.index)?;
             Ok((field, idx))
         });
         header.push_exports(exports)?;
+        self.validator.export_section(&section)?;
         Ok(())
     }
 
@@ -557,6 +734,7 @@ impl ModuleParser {
         section: ElementSectionReader,
         header: &mut ModuleHeaderBuilder,
     ) -> Result<(), Error> {
+        self.ensure_limit(ModuleLimit::ElementSegments, section.count())?;
         self.validator.element_section(&section)?;
         let segments = section
             .into_iter()
@@ -572,9 +750,10 @@ impl ModuleParser {
     /// This is part of the bulk memory operations Wasm proposal and not yet supported
     /// by Wasmi.
     fn process_data_count(&mut self, count: u32, range: Range<usize>) -> Result<(), Error> {
-        self.validator
-            .data_count_section(count, &range)
-            .map_err(Into::into)
+        self.ensure_limit(ModuleLimit::DataSegments, count)?;
+        self.validator.data_count_section(count, &range)?;
+        self.len_data_segments = count;
+        Ok(())
     }
 
     /// Process module linear memory data segments.
@@ -591,6 +770,7 @@ impl ModuleParser {
         section: DataSectionReader,
         builder: &mut ModuleBuilder,
     ) -> Result<(), Error> {
+        self.ensure_limit(ModuleLimit::DataSegments, section.count())?;
         self.validator.data_section(&section)?;
         let segments = section
             .into_iter()
@@ -612,6 +792,22 @@ impl ModuleParser {
     /// If the code start section fails to validate.
     fn process_code_start(&mut self, count: u32, range: Range<usize>) -> Result<(), Error> {
         self.validator.code_section_start(count, &range)?;
+        self.len_func_bodies = count;
+        Ok(())
+    }
+
+    /// Reports the number of processed function bodies to the progress observer if any.
+    ///
+    /// # Errors
+    ///
+    /// If the progress observer cancelled parsing.
+    fn report_progress(&mut self) -> Result<(), Error> {
+        let Some(progress) = &mut self.progress else {
+            return Ok(());
+        };
+        if !progress(self.compiled_funcs, self.len_func_bodies) {
+            return Err(Error::from(TranslationError::Cancelled));
+        }
         Ok(())
     }
 
@@ -646,6 +842,11 @@ impl ModuleParser {
         bytes: &[u8],
         header: &ModuleHeader,
     ) -> Result<(), Error> {
+        self.report_progress()?;
+        let ir_func = self
+            .ir_funcs
+            .get_mut(self.compiled_funcs as usize)
+            .and_then(Option::take);
         let (func, compiled_func) = self.next_func(header);
         let module = header.clone();
         let offset = func_body.get_binary_reader().original_position();
@@ -653,8 +854,14 @@ impl ModuleParser {
             ValidationMode::All => Some(self.validator.code_section_entry(&func_body)?),
             ValidationMode::HeaderOnly => None,
         };
+        if let Some(ir_func) = ir_func {
+            let entity =
+                ir_func.into_func_entity(&self.engine, header, self.len_data_segments, func)?;
+            self.engine.init_func(compiled_func, entity);
+            return Ok(());
+        }
         self.engine
-            .translate_func(func, compiled_func, offset, bytes, module, func_to_validate)?;
+            .translate_wasm_func(func, compiled_func, offset, bytes, module, func_to_validate)?;
         Ok(())
     }
 
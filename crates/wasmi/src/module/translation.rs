@@ -0,0 +1,380 @@
+use super::{builder::ModuleHeaderBuilder, FuncIdx, ImportName, Module, ModuleHeader};
+use crate::{
+    engine::{CodeOwner, CompiledFunc, CompiledFuncEntity, DedupFuncType},
+    module::WasmiValueType,
+    Engine,
+    FuncType,
+    GlobalType,
+    MemoryType,
+    TableType,
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use wasmi_core::ValueType;
+use wasmparser::WasmModuleResources;
+
+/// The Wasm module resources for translating single Wasm functions via [`Engine::translate_func`].
+///
+/// # Note
+///
+/// - Use [`TranslationContext::from_module`] to translate functions for an existing [`Module`].
+/// - Use [`TranslationContext::builder`] to define the Wasm module resources manually.
+///
+/// Cloning a [`TranslationContext`] is cheap since all clones share the same underlying data.
+#[derive(Debug, Clone)]
+pub struct TranslationContext {
+    /// The module header used for the translation of Wasm functions.
+    header: ModuleHeader,
+    /// The module resources used for the validation of Wasm functions.
+    resources: Arc<TranslationResources>,
+}
+
+/// The module resources of a [`TranslationContext`] used for the validation of Wasm functions.
+///
+/// # Note
+///
+/// All functions are considered to be referenced since
+/// the declarations of function references are not part of it.
+#[derive(Debug)]
+pub struct TranslationResources {
+    /// The function types of the Wasm module.
+    func_types: Box<[wasmparser::FuncType]>,
+    /// The function type indices of all imported and internal functions.
+    funcs: Box<[u32]>,
+    /// The types of all imported and internal tables.
+    tables: Box<[wasmparser::TableType]>,
+    /// The types of all imported and internal linear memories.
+    memories: Box<[wasmparser::MemoryType]>,
+    /// The types of all imported and internal global variables.
+    globals: Box<[wasmparser::GlobalType]>,
+    /// The types of the elements of all element segments.
+    element_types: Box<[wasmparser::ValType]>,
+    /// The number of data segments.
+    data_count: u32,
+}
+
+impl TranslationContext {
+    /// Creates a new [`TranslationContext`] with the Wasm module resources of `module`.
+    ///
+    /// # Note
+    ///
+    /// Functions translated with the returned [`TranslationContext`] may be installed
+    /// into the [`CompiledFunc`] of any internal function of `module` with the same type.
+    pub fn from_module(module: &Module) -> Self {
+        let header = module.header.clone();
+        let engine = module.engine();
+        let inner = &header.inner;
+        let func_types = inner
+            .func_types
+            .iter()
+            .map(|func_type| engine.resolve_func_type(func_type, FuncType::to_wasmparser))
+            .collect();
+        let funcs = inner
+            .funcs
+            .iter()
+            .map(|func_type| {
+                let index = inner
+                    .func_types
+                    .iter()
+                    .position(|ty| ty == func_type)
+                    .unwrap_or_else(|| panic!("missing function type of function: {func_type:?}"));
+                index as u32
+            })
+            .collect();
+        let resources = TranslationResources {
+            func_types,
+            funcs,
+            tables: inner.tables.iter().map(|ty| ty.to_wasmparser()).collect(),
+            memories: inner.memories.iter().map(|ty| ty.to_wasmparser()).collect(),
+            globals: inner.globals.iter().map(|ty| ty.to_wasmparser()).collect(),
+            element_types: inner
+                .element_segments
+                .iter()
+                .map(|segment| WasmiValueType::from(segment.ty()).into())
+                .collect(),
+            data_count: module.data_segments.len() as u32,
+        };
+        Self {
+            header,
+            resources: Arc::new(resources),
+        }
+    }
+
+    /// Creates a new [`TranslationContextBuilder`] to define Wasm module resources for the `engine` manually.
+    pub fn builder(engine: &Engine) -> TranslationContextBuilder {
+        TranslationContextBuilder::new(engine)
+    }
+
+    /// Returns the [`ModuleHeader`] used for the translation of Wasm functions.
+    pub(crate) fn header(&self) -> &ModuleHeader {
+        &self.header
+    }
+
+    /// Returns the [`TranslationResources`] used for the validation of Wasm functions.
+    pub(crate) fn resources(&self) -> &Arc<TranslationResources> {
+        &self.resources
+    }
+
+    /// Returns the number of imported and internal functions.
+    pub(crate) fn len_funcs(&self) -> u32 {
+        self.resources.funcs.len() as u32
+    }
+
+    /// Returns the index of the first function type equal to `func_type` if any.
+    pub(crate) fn func_type_index(&self, func_type: &FuncType) -> Option<u32> {
+        let func_type = func_type.to_wasmparser();
+        self.resources
+            .func_types
+            .iter()
+            .position(|ty| *ty == func_type)
+            .map(|index| index as u32)
+    }
+}
+
+impl WasmModuleResources for TranslationResources {
+    type FuncType = wasmparser::FuncType;
+
+    fn table_at(&self, at: u32) -> Option<wasmparser::TableType> {
+        self.tables.get(at as usize).copied()
+    }
+
+    fn memory_at(&self, at: u32) -> Option<wasmparser::MemoryType> {
+        self.memories.get(at as usize).copied()
+    }
+
+    fn tag_at(&self, _at: u32) -> Option<&Self::FuncType> {
+        None
+    }
+
+    fn global_at(&self, at: u32) -> Option<wasmparser::GlobalType> {
+        self.globals.get(at as usize).copied()
+    }
+
+    fn func_type_at(&self, type_idx: u32) -> Option<&Self::FuncType> {
+        self.func_types.get(type_idx as usize)
+    }
+
+    fn type_of_function(&self, func_idx: u32) -> Option<&Self::FuncType> {
+        let type_idx = *self.funcs.get(func_idx as usize)?;
+        self.func_types.get(type_idx as usize)
+    }
+
+    fn element_type_at(&self, at: u32) -> Option<wasmparser::ValType> {
+        self.element_types.get(at as usize).copied()
+    }
+
+    fn element_count(&self) -> u32 {
+        self.element_types.len() as u32
+    }
+
+    fn data_count(&self) -> Option<u32> {
+        Some(self.data_count)
+    }
+
+    fn is_function_referenced(&self, idx: u32) -> bool {
+        (idx as usize) < self.funcs.len()
+    }
+}
+
+/// A builder for a [`TranslationContext`] with manually defined Wasm module resources.
+///
+/// # Note
+///
+/// - Entities of the same kind are indexed in the order they are pushed or imported.
+/// - All index spaces must mirror the ones of the Wasm module of the
+///   [`CompiledFunc`] into which translated functions are installed.
+#[derive(Debug)]
+pub struct TranslationContextBuilder {
+    /// The [`Engine`] of the built [`TranslationContext`].
+    engine: Engine,
+    /// The builder for the module header used for translation.
+    header: ModuleHeaderBuilder,
+    /// The function types of the Wasm module.
+    func_types: Vec<wasmparser::FuncType>,
+    /// The function type indices of all imported and internal functions.
+    funcs: Vec<u32>,
+    /// The types of the elements of all element segments.
+    element_types: Vec<wasmparser::ValType>,
+    /// The number of data segments.
+    data_count: u32,
+}
+
+/// Returns the index of the next entity in a list of `len` entities.
+fn next_index(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or_else(|_| panic!("too many definitions"))
+}
+
+impl TranslationContextBuilder {
+    /// Creates a new [`TranslationContextBuilder`] for the `engine`.
+    fn new(engine: &Engine) -> Self {
+        Self {
+            engine: engine.clone(),
+            header: ModuleHeaderBuilder::new(engine, &CodeOwner::default()),
+            func_types: Vec::new(),
+            funcs: Vec::new(),
+            element_types: Vec::new(),
+            data_count: 0,
+        }
+    }
+
+    /// Returns the [`DedupFuncType`] at type index `ty`.
+    ///
+    /// # Panics
+    ///
+    /// If there is no function type at `ty`.
+    fn dedup_func_type(&self, ty: u32) -> DedupFuncType {
+        match self.header.func_types.get(ty as usize) {
+            Some(func_type) => *func_type,
+            None => panic!("missing function type at index {ty}"),
+        }
+    }
+
+    /// Pushes the function type `ty` and returns its type index.
+    pub fn push_type(&mut self, ty: FuncType) -> u32 {
+        let index = next_index(self.func_types.len());
+        self.func_types.push(ty.to_wasmparser());
+        let ty = self.engine.alloc_func_type(ty);
+        self.header.func_types.push(ty);
+        index
+    }
+
+    /// Imports a function of type index `ty` and returns its function index.
+    ///
+    /// # Panics
+    ///
+    /// - If there is no function type at `ty`.
+    /// - If a function has already been defined.
+    pub fn import_func(&mut self, module: &str, name: &str, ty: u32) -> u32 {
+        assert!(
+            self.header.compiled_funcs.is_empty(),
+            "functions must be imported before they are defined"
+        );
+        let index = next_index(self.funcs.len());
+        let func_type = self.dedup_func_type(ty);
+        self.header
+            .imports
+            .funcs
+            .push(ImportName::new(module, name));
+        self.header.funcs.push(func_type);
+        self.funcs.push(ty);
+        index
+    }
+
+    /// Pushes a function of type index `ty` compiled to `func` and returns its function index.
+    ///
+    /// # Note
+    ///
+    /// Calls to the function are translated as calls to `func`.
+    ///
+    /// # Panics
+    ///
+    /// If there is no function type at `ty`.
+    pub fn push_func(&mut self, ty: u32, func: CompiledFunc) -> u32 {
+        let index = next_index(self.funcs.len());
+        let func_type = self.dedup_func_type(ty);
+        self.header.funcs.push(func_type);
+        self.header.compiled_funcs.push(func);
+        self.header
+            .compiled_funcs_idx
+            .insert(func, FuncIdx::from(index));
+        self.funcs.push(ty);
+        index
+    }
+
+    /// Pushes a table of type `ty` and returns its table index.
+    pub fn push_table(&mut self, ty: TableType) -> u32 {
+        let index = next_index(self.header.tables.len());
+        self.header.tables.push(ty);
+        index
+    }
+
+    /// Pushes a linear memory of type `ty` and returns its memory index.
+    pub fn push_memory(&mut self, ty: MemoryType) -> u32 {
+        let index = next_index(self.header.memories.len());
+        self.header.memories.push(ty);
+        index
+    }
+
+    /// Pushes a global variable of type `ty` and returns its global index.
+    ///
+    /// # Note
+    ///
+    /// Unlike with [`TranslationContext::from_module`] the values of
+    /// immutable global variables are never inlined by the translation.
+    pub fn push_global(&mut self, ty: GlobalType) -> u32 {
+        let index = next_index(self.header.globals.len());
+        // Note: global variables are registered as anonymous imports
+        //       since their initial values are unknown.
+        self.header.imports.globals.push(ImportName::new("", ""));
+        self.header.globals.push(ty);
+        index
+    }
+
+    /// Pushes an element segment with elements of type `ty` and returns its element segment index.
+    pub fn push_element_segment(&mut self, ty: ValueType) -> u32 {
+        let index = next_index(self.element_types.len());
+        self.element_types.push(WasmiValueType::from(ty).into());
+        index
+    }
+
+    /// Sets the number of data segments to `count`.
+    pub fn set_data_count(&mut self, count: u32) {
+        self.data_count = count;
+    }
+
+    /// Finishes building the [`TranslationContext`].
+    pub fn finish(self) -> TranslationContext {
+        let header = self.header.finish();
+        let inner = &header.inner;
+        let resources = TranslationResources {
+            func_types: self.func_types.into(),
+            funcs: self.funcs.into(),
+            tables: inner.tables.iter().map(|ty| ty.to_wasmparser()).collect(),
+            memories: inner.memories.iter().map(|ty| ty.to_wasmparser()).collect(),
+            globals: inner.globals.iter().map(|ty| ty.to_wasmparser()).collect(),
+            element_types: self.element_types.into(),
+            data_count: self.data_count,
+        };
+        TranslationContext {
+            header,
+            resources: Arc::new(resources),
+        }
+    }
+}
+
+/// A Wasm function translated via [`Engine::translate_func`].
+///
+/// # Note
+///
+/// The translated function can be installed via [`Engine::install_func`]
+/// any number of times into the [`Engine`] it has been translated by.
+#[derive(Debug, Clone)]
+pub struct CompiledFuncBytes {
+    /// The [`Engine`] that translated the function.
+    engine: Engine,
+    /// The translated function.
+    entity: Arc<CompiledFuncEntity>,
+}
+
+impl CompiledFuncBytes {
+    /// Creates a new [`CompiledFuncBytes`] for the `entity` translated by `engine`.
+    pub(crate) fn new(engine: &Engine, entity: CompiledFuncEntity) -> Self {
+        Self {
+            engine: engine.clone(),
+            entity: Arc::new(entity),
+        }
+    }
+
+    /// Returns the [`Engine`] that translated the function.
+    pub(crate) fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    /// Returns the translated [`CompiledFuncEntity`].
+    ///
+    /// # Note
+    ///
+    /// The [`CompiledFuncEntity`] is only cloned if it is shared with other [`CompiledFuncBytes`].
+    pub(crate) fn into_entity(self) -> CompiledFuncEntity {
+        Arc::try_unwrap(self.entity).unwrap_or_else(|entity| (*entity).clone())
+    }
+}
@@ -14,6 +14,15 @@ impl TableType {
         let maximum = table_type.maximum;
         Self::new(element, minimum, maximum)
     }
+
+    /// Converts the [`TableType`] into its `wasmparser` primitive.
+    pub(crate) fn to_wasmparser(self) -> wasmparser::TableType {
+        wasmparser::TableType {
+            element_type: WasmiValueType::from(self.element()).into(),
+            initial: self.minimum(),
+            maximum: self.maximum(),
+        }
+    }
 }
 
 impl MemoryType {
@@ -44,6 +53,18 @@ impl MemoryType {
         Self::new(initial, maximum)
             .expect("encountered invalid wasmparser::MemoryType after validation")
     }
+
+    /// Converts the [`MemoryType`] into its `wasmparser` primitive.
+    pub(crate) fn to_wasmparser(self) -> wasmparser::MemoryType {
+        wasmparser::MemoryType {
+            memory64: false,
+            shared: false,
+            initial: u64::from(u32::from(self.initial_pages())),
+            maximum: self
+                .maximum_pages()
+                .map(|pages| u64::from(u32::from(pages))),
+        }
+    }
 }
 
 impl GlobalType {
@@ -61,6 +82,14 @@ impl GlobalType {
         };
         Self::new(value_type, mutability)
     }
+
+    /// Converts the [`GlobalType`] into its `wasmparser` primitive.
+    pub(crate) fn to_wasmparser(self) -> wasmparser::GlobalType {
+        wasmparser::GlobalType {
+            content_type: WasmiValueType::from(self.content()).into(),
+            mutable: self.mutability().is_mut(),
+        }
+    }
 }
 
 impl FuncType {
@@ -83,6 +112,14 @@ impl FuncType {
         let results = func_type.results().iter().map(extract_value_type);
         Self::new(params, results)
     }
+
+    /// Converts the [`FuncType`] into its `wasmparser` primitive.
+    pub(crate) fn to_wasmparser(&self) -> wasmparser::FuncType {
+        let into_val_type = |value_type: &ValueType| WasmiValueType::from(*value_type).into();
+        let params = self.params().iter().map(into_val_type);
+        let results = self.results().iter().map(into_val_type);
+        wasmparser::FuncType::new(params, results)
+    }
 }
 
 /// A Wasmi [`ValueType`].
@@ -120,3 +157,16 @@ impl From<wasmparser::ValType> for WasmiValueType {
         }
     }
 }
+
+impl From<WasmiValueType> for wasmparser::ValType {
+    fn from(value_type: WasmiValueType) -> Self {
+        match value_type.into_inner() {
+            ValueType::I32 => Self::I32,
+            ValueType::I64 => Self::I64,
+            ValueType::F32 => Self::F32,
+            ValueType::F64 => Self::F64,
+            ValueType::FuncRef => Self::FuncRef,
+            ValueType::ExternRef => Self::ExternRef,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use super::Module;
+use crate::{Engine, Error};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, fmt::Display};
+use wast_text::{
+    parser::{self, ParseBuffer},
+    Wat,
+};
+
+/// An error encountered when parsing the WebAssembly text format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatError {
+    /// The message describing the error without its location.
+    message: String,
+    /// The 1-based line of the error within the parsed text.
+    line: usize,
+    /// The 1-based column of the error within the parsed text.
+    column: usize,
+    /// The error message rendered together with the source location.
+    rendered: String,
+}
+
+impl WatError {
+    /// Creates a new [`WatError`] from the `error` encountered when parsing `text`.
+    #[cold]
+    fn new(mut error: wast_text::Error, text: &str) -> Self {
+        let (line, column) = error.span().linecol_in(text);
+        error.set_text(text);
+        Self {
+            message: error.message(),
+            line: line + 1,
+            column: column + 1,
+            rendered: error.to_string(),
+        }
+    }
+
+    /// Returns the message describing the [`WatError`] without its location.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the 1-based line of the [`WatError`] within the parsed text.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the 1-based column of the [`WatError`] within the parsed text.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl std::error::Error for WatError {}
+
+impl Display for WatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.rendered, f)
+    }
+}
+
+impl Module {
+    /// Creates a new Wasm [`Module`] from the given WebAssembly text format.
+    ///
+    /// # Note
+    ///
+    /// This encodes `wat` into the Wasm binary format and then behaves like [`Module::new`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmi::{Engine, Module};
+    /// let engine = Engine::default();
+    /// let module = Module::new_wat(&engine, r#"(module (func (export "f")))"#)?;
+    /// assert!(module.get_export("f").is_some());
+    /// # Ok::<(), wasmi::Error>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - If `wat` is not valid WebAssembly text format.
+    ///   The returned [`WatError`] carries the line and column of the error.
+    /// - For all the reasons [`Module::new`] fails.
+    pub fn new_wat(engine: &Engine, wat: impl AsRef<str>) -> Result<Self, Error> {
+        let wat = wat.as_ref();
+        let wasm = Self::encode_wat(wat).map_err(|error| WatError::new(error, wat))?;
+        Self::new(engine, &wasm[..])
+    }
+
+    /// Encodes the WebAssembly text format `wat` into the Wasm binary format.
+    fn encode_wat(wat: &str) -> Result<Vec<u8>, wast_text::Error> {
+        let buffer = ParseBuffer::new(wat)?;
+        let mut wat = parser::parse::<Wat>(&buffer)?;
+        wat.encode()
+    }
+
+    /// Disassembles the Wasm binary of the [`Module`] into the WebAssembly text format.
+    ///
+    /// # Note
+    ///
+    /// - This prints the original Wasm binary that the [`Module`] has been created from
+    ///   and not the Wasmi bytecode that it has been translated to.
+    /// - Function, local and other names are printed if the Wasm binary has a name section.
+    /// - This is meant for diagnostics. The output format is not stable.
+    ///
+    /// # Panics
+    ///
+    /// If the Wasm binary of the [`Module`] cannot be printed.
+    /// This does not happen for Wasm binaries that Wasmi successfully parsed.
+    pub fn to_wat(&self) -> String {
+        wasmprinter::print_bytes(&self.wasm).unwrap_or_else(|error| {
+            panic!("failed to print the Wasm binary of the module: {error}")
+        })
+    }
+}
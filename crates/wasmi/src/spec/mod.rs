@@ -0,0 +1,94 @@
+//! A runner for `.wast` files of the WebAssembly spec testsuite.
+//!
+//! This allows embedders to check the conformance of Wasmi under their own [`Config`]:
+//!
+//! - [`run_directory`] and [`run_file`] execute `.wast` files and return a [`SpecReport`]
+//!   with the [`SpecOutcome`] of every directive instead of panicking upon the first failure.
+//! - [`define_spectest`] defines the `spectest` host module imported by spec tests
+//!   in a user provided [`Linker`] and [`Store`].
+//!
+//! The runner supports the `module`, `register`, `invoke`, `assert_return`, `assert_trap`,
+//! `assert_exhaustion`, `assert_invalid`, `assert_malformed` and `assert_unlinkable` directives.
+//! Directives using features that Wasmi does not support, such as components or `v128` values,
+//! are reported as [`SpecStatus::Skipped`].
+//!
+//! This module is only available if the `spec-testing` crate feature is enabled.
+//!
+//! # Example
+//!
+//! ```
+//! # use wasmi::{spec::run_directory, Config};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let dir = std::env::temp_dir().join("wasmi-spec-doc-example");
+//! # std::fs::create_dir_all(&dir)?;
+//! # std::fs::write(dir.join("add.wast"), r#"
+//! #     (module
+//! #         (func (export "add") (param i32 i32) (result i32)
+//! #             (i32.add (local.get 0) (local.get 1))
+//! #         )
+//! #     )
+//! #     (assert_return (invoke "add" (i32.const 1) (i32.const 2)) (i32.const 3))
+//! # "#)?;
+//! let mut config = Config::default();
+//! config.consume_fuel(true);
+//! let report = run_directory(&config, &dir);
+//! for failure in report.failed() {
+//!     eprintln!("{failure}");
+//! }
+//! assert!(report.is_success());
+//! # Ok(())
+//! # }
+//! ```
+
+mod report;
+mod runner;
+mod spectest;
+
+pub use self::{
+    report::{DirectiveKind, SpecLocation, SpecOutcome, SpecReport, SpecStatus},
+    spectest::define_spectest,
+};
+
+use crate::{Config, Engine};
+use std::{fs, path::Path, string::ToString, vec::Vec};
+
+#[cfg(doc)]
+use crate::{Linker, Store};
+
+/// Runs all `.wast` files in the directory at `path` using `config`.
+///
+/// The files are run in order of their file names and each file is run in its own
+/// [`Store`]. Subdirectories are not searched.
+///
+/// Failures to read the directory or any of its files are reported as
+/// failed outcomes of kind [`DirectiveKind::File`].
+pub fn run_directory(config: &Config, path: &Path) -> SpecReport {
+    let engine = Engine::new(config);
+    let mut report = SpecReport::default();
+    let mut files = match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "wast"))
+            .collect::<Vec<_>>(),
+        Err(error) => {
+            report.push(SpecOutcome::new(
+                SpecLocation::new(path, 1, 1),
+                DirectiveKind::File,
+                SpecStatus::Failed(error.to_string().into()),
+            ));
+            return report;
+        }
+    };
+    files.sort();
+    for file in files {
+        report.extend(runner::run_file(&engine, &file));
+    }
+    report
+}
+
+/// Runs the `.wast` file at `path` using `config`.
+///
+/// A failure to read or parse the file is reported as a failed outcome of kind [`DirectiveKind::File`].
+pub fn run_file(config: &Config, path: &Path) -> SpecReport {
+    runner::run_file(&Engine::new(config), path)
+}
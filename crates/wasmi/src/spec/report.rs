@@ -0,0 +1,243 @@
+use std::{
+    boxed::Box,
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+    vec::Vec,
+};
+
+/// The kind of a `.wast` directive that produced a [`SpecOutcome`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DirectiveKind {
+    /// Reading or parsing of the `.wast` file itself.
+    File,
+    /// A `module` directive.
+    Module,
+    /// A `register` directive.
+    Register,
+    /// A top-level `invoke` directive.
+    Invoke,
+    /// An `assert_return` directive.
+    AssertReturn,
+    /// An `assert_trap` directive.
+    AssertTrap,
+    /// An `assert_exhaustion` directive.
+    AssertExhaustion,
+    /// An `assert_invalid` directive.
+    AssertInvalid,
+    /// An `assert_malformed` directive.
+    AssertMalformed,
+    /// An `assert_unlinkable` directive.
+    AssertUnlinkable,
+    /// Any other directive that is not supported by the runner.
+    Unsupported,
+}
+
+impl DirectiveKind {
+    /// Returns the name of the directive as written in `.wast` files.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Module => "module",
+            Self::Register => "register",
+            Self::Invoke => "invoke",
+            Self::AssertReturn => "assert_return",
+            Self::AssertTrap => "assert_trap",
+            Self::AssertExhaustion => "assert_exhaustion",
+            Self::AssertInvalid => "assert_invalid",
+            Self::AssertMalformed => "assert_malformed",
+            Self::AssertUnlinkable => "assert_unlinkable",
+            Self::Unsupported => "unsupported",
+        }
+    }
+}
+
+impl Display for DirectiveKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The location of a directive within a `.wast` file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpecLocation {
+    /// The path of the `.wast` file.
+    file: PathBuf,
+    /// The 1-based line of the directive.
+    line: usize,
+    /// The 1-based column of the directive.
+    column: usize,
+}
+
+impl SpecLocation {
+    /// Creates a new [`SpecLocation`] from 1-based `line` and `column`.
+    pub(super) fn new(file: &Path, line: usize, column: usize) -> Self {
+        Self {
+            file: file.to_path_buf(),
+            line,
+            column,
+        }
+    }
+
+    /// Returns the path of the `.wast` file.
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+
+    /// Returns the 1-based line of the directive.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the 1-based column of the directive.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Display for SpecLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file.display(), self.line, self.column)
+    }
+}
+
+/// The status of a single [`SpecOutcome`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecStatus {
+    /// The directive behaved as expected.
+    Passed,
+    /// The directive did not behave as expected.
+    Failed(Box<str>),
+    /// The directive was not executed since the runner does not support it.
+    Skipped,
+}
+
+/// The outcome of a single directive of a `.wast` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecOutcome {
+    /// The location of the directive.
+    location: SpecLocation,
+    /// The kind of the directive.
+    kind: DirectiveKind,
+    /// The status of the directive.
+    status: SpecStatus,
+}
+
+impl SpecOutcome {
+    /// Creates a new [`SpecOutcome`].
+    pub(super) fn new(location: SpecLocation, kind: DirectiveKind, status: SpecStatus) -> Self {
+        Self {
+            location,
+            kind,
+            status,
+        }
+    }
+
+    /// Returns the location of the directive.
+    pub fn location(&self) -> &SpecLocation {
+        &self.location
+    }
+
+    /// Returns the kind of the directive.
+    pub fn kind(&self) -> DirectiveKind {
+        self.kind
+    }
+
+    /// Returns the status of the directive.
+    pub fn status(&self) -> &SpecStatus {
+        &self.status
+    }
+
+    /// Returns `true` if the directive behaved as expected.
+    pub fn is_passed(&self) -> bool {
+        matches!(self.status, SpecStatus::Passed)
+    }
+
+    /// Returns `true` if the directive did not behave as expected.
+    pub fn is_failed(&self) -> bool {
+        matches!(self.status, SpecStatus::Failed(_))
+    }
+
+    /// Returns the failure message if the directive did not behave as expected.
+    pub fn failure(&self) -> Option<&str> {
+        match &self.status {
+            SpecStatus::Failed(message) => Some(message),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SpecOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = &self.location;
+        let kind = self.kind;
+        match &self.status {
+            SpecStatus::Passed => write!(f, "{location}: {kind}: passed"),
+            SpecStatus::Failed(message) => write!(f, "{location}: {kind}: failed: {message}"),
+            SpecStatus::Skipped => write!(f, "{location}: {kind}: skipped"),
+        }
+    }
+}
+
+/// The report of a spec test run created by [`run_directory`] or [`run_file`].
+///
+/// [`run_directory`]: super::run_directory
+/// [`run_file`]: super::run_file
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SpecReport {
+    /// The outcomes of all directives in order of execution.
+    outcomes: Vec<SpecOutcome>,
+}
+
+impl SpecReport {
+    /// Pushes the `outcome` to the [`SpecReport`].
+    pub(super) fn push(&mut self, outcome: SpecOutcome) {
+        self.outcomes.push(outcome);
+    }
+
+    /// Appends all outcomes of `other` to `self`.
+    pub(super) fn extend(&mut self, other: SpecReport) {
+        self.outcomes.extend(other.outcomes);
+    }
+
+    /// Returns the outcomes of all directives in order of execution.
+    pub fn outcomes(&self) -> &[SpecOutcome] {
+        &self.outcomes
+    }
+
+    /// Returns an iterator over the outcomes of all passed directives.
+    pub fn passed(&self) -> impl Iterator<Item = &SpecOutcome> {
+        self.outcomes.iter().filter(|outcome| outcome.is_passed())
+    }
+
+    /// Returns an iterator over the outcomes of all failed directives.
+    pub fn failed(&self) -> impl Iterator<Item = &SpecOutcome> {
+        self.outcomes.iter().filter(|outcome| outcome.is_failed())
+    }
+
+    /// Returns an iterator over the outcomes of all skipped directives.
+    pub fn skipped(&self) -> impl Iterator<Item = &SpecOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| matches!(outcome.status, SpecStatus::Skipped))
+    }
+
+    /// Returns `true` if no directive failed.
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none()
+    }
+}
+
+impl Display for SpecReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for outcome in self.failed() {
+            writeln!(f, "{outcome}")?;
+        }
+        write!(
+            f,
+            "{} passed, {} failed, {} skipped",
+            self.passed().count(),
+            self.failed().count(),
+            self.skipped().count(),
+        )
+    }
+}
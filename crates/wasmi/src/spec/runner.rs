@@ -0,0 +1,502 @@
+use super::{define_spectest, DirectiveKind, SpecLocation, SpecOutcome, SpecReport, SpecStatus};
+use crate::{
+    core::{F32, F64},
+    Engine,
+    Error,
+    Extern,
+    ExternRef,
+    FuncRef,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+use std::{
+    collections::HashMap,
+    format,
+    fs,
+    path::Path,
+    string::{String, ToString},
+    vec::Vec,
+};
+use wast_text::{
+    core::{AbstractHeapType, HeapType, NanPattern, WastArgCore, WastRetCore},
+    lexer::Lexer,
+    parser::{self, ParseBuffer},
+    token::Id,
+    QuoteWat,
+    Wast,
+    WastArg,
+    WastDirective,
+    WastExecute,
+    WastInvoke,
+    WastRet,
+    Wat,
+};
+
+/// The reason why a directive did not pass.
+enum Failure {
+    /// The directive did not behave as expected.
+    Failed(String),
+    /// The directive uses features that are not supported by the runner.
+    Skipped,
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Self {
+        Self::Failed(message)
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(error: Error) -> Self {
+        Self::Failed(error.to_string())
+    }
+}
+
+/// Runs all directives of the `.wast` file at `path` using `engine`.
+pub fn run_file(engine: &Engine, path: &Path) -> SpecReport {
+    let mut report = SpecReport::default();
+    let file_failure = |message: String| {
+        SpecOutcome::new(
+            SpecLocation::new(path, 1, 1),
+            DirectiveKind::File,
+            SpecStatus::Failed(message.into()),
+        )
+    };
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => {
+            report.push(file_failure(format!("failed to read file: {error}")));
+            return report;
+        }
+    };
+    let parse_failure = |error: wast_text::Error| {
+        let (line, column) = error.span().linecol_in(&text);
+        SpecOutcome::new(
+            SpecLocation::new(path, line + 1, column + 1),
+            DirectiveKind::File,
+            SpecStatus::Failed(format!("failed to parse file: {}", error.message()).into()),
+        )
+    };
+    let mut lexer = Lexer::new(&text);
+    lexer.allow_confusing_unicode(true);
+    let buffer = match ParseBuffer::new_with_lexer(lexer) {
+        Ok(buffer) => buffer,
+        Err(error) => {
+            report.push(parse_failure(error));
+            return report;
+        }
+    };
+    let wast = match parser::parse::<Wast>(&buffer) {
+        Ok(wast) => wast,
+        Err(error) => {
+            report.push(parse_failure(error));
+            return report;
+        }
+    };
+    let mut runner = Runner::new(engine);
+    if let Err(error) = define_spectest(&mut runner.linker, &mut runner.store) {
+        report.push(file_failure(format!(
+            "failed to define `spectest` imports: {error}"
+        )));
+        return report;
+    }
+    for directive in wast.directives {
+        let (line, column) = directive.span().linecol_in(&text);
+        let (kind, result) = runner.execute_directive(directive);
+        let status = match result {
+            Ok(()) => SpecStatus::Passed,
+            Err(Failure::Failed(message)) => SpecStatus::Failed(message.into()),
+            Err(Failure::Skipped) => SpecStatus::Skipped,
+        };
+        report.push(SpecOutcome::new(
+            SpecLocation::new(path, line + 1, column + 1),
+            kind,
+            status,
+        ));
+    }
+    report
+}
+
+/// The state of a single `.wast` file run.
+struct Runner<'a> {
+    /// The engine used to compile all Wasm modules.
+    engine: &'a Engine,
+    /// The linker with the `spectest` imports and all registered instances.
+    linker: Linker<()>,
+    /// The store holding all instances of the run.
+    store: Store<()>,
+    /// The instances of all named modules.
+    instances: HashMap<String, Instance>,
+    /// The most recently instantiated module.
+    last_instance: Option<Instance>,
+}
+
+impl<'a> Runner<'a> {
+    /// Creates a new [`Runner`] using `engine`.
+    fn new(engine: &'a Engine) -> Self {
+        let mut store = Store::new(engine, ());
+        // Spec tests do not test fuel metering and fail if fuel is exhausted.
+        _ = store.add_fuel(u64::MAX);
+        Self {
+            engine,
+            linker: Linker::new(engine),
+            store,
+            instances: HashMap::new(),
+            last_instance: None,
+        }
+    }
+
+    /// Executes the `directive` and returns its kind and result.
+    fn execute_directive(
+        &mut self,
+        directive: WastDirective,
+    ) -> (DirectiveKind, Result<(), Failure>) {
+        match directive {
+            WastDirective::Module(module) => (DirectiveKind::Module, self.module(module)),
+            WastDirective::Register { name, module, .. } => {
+                (DirectiveKind::Register, self.register(name, module))
+            }
+            WastDirective::Invoke(invoke) => (
+                DirectiveKind::Invoke,
+                self.invoke(invoke)
+                    .and_then(|result| result.map(|_| ()).map_err(Failure::from)),
+            ),
+            WastDirective::AssertReturn { exec, results, .. } => (
+                DirectiveKind::AssertReturn,
+                self.assert_return(exec, &results),
+            ),
+            WastDirective::AssertTrap { exec, message, .. } => (
+                DirectiveKind::AssertTrap,
+                self.execute(exec)
+                    .and_then(|result| Self::assert_trap(result, message)),
+            ),
+            WastDirective::AssertExhaustion { call, message, .. } => (
+                DirectiveKind::AssertExhaustion,
+                self.invoke(call)
+                    .and_then(|result| Self::assert_trap(result, message)),
+            ),
+            WastDirective::AssertInvalid { module, .. } => {
+                (DirectiveKind::AssertInvalid, self.assert_invalid(module))
+            }
+            WastDirective::AssertMalformed { module, .. } => (
+                DirectiveKind::AssertMalformed,
+                self.assert_malformed(module),
+            ),
+            WastDirective::AssertUnlinkable { module, .. } => (
+                DirectiveKind::AssertUnlinkable,
+                self.assert_unlinkable(module),
+            ),
+            _ => (DirectiveKind::Unsupported, Err(Failure::Skipped)),
+        }
+    }
+
+    /// Encodes `module` into the Wasm binary format.
+    ///
+    /// # Errors
+    ///
+    /// - If `module` is a component which is not supported by Wasmi.
+    /// - If encoding `module` fails.
+    fn encode(module: &mut QuoteWat) -> Result<Vec<u8>, Failure> {
+        if matches!(
+            module,
+            QuoteWat::Wat(Wat::Component(_)) | QuoteWat::QuoteComponent(..)
+        ) {
+            return Err(Failure::Skipped);
+        }
+        module
+            .encode()
+            .map_err(|error| Failure::Failed(format!("failed to encode module: {error}")))
+    }
+
+    /// Compiles and instantiates the Wasm binary `wasm`.
+    ///
+    /// # Errors
+    ///
+    /// If compilation or instantiation fails.
+    fn instantiate(&mut self, wasm: &[u8]) -> Result<Instance, Error> {
+        let module = Module::new(self.engine, wasm)?;
+        let instance = self
+            .linker
+            .instantiate(&mut self.store, &module)?
+            .start(&mut self.store)?;
+        self.last_instance = Some(instance);
+        Ok(instance)
+    }
+
+    /// Executes a `module` directive.
+    fn module(&mut self, mut module: QuoteWat) -> Result<(), Failure> {
+        let name = module.name().map(|name| name.name().to_string());
+        let wasm = Self::encode(&mut module)?;
+        let instance = self.instantiate(&wasm)?;
+        if let Some(name) = name {
+            self.instances.insert(name, instance);
+        }
+        Ok(())
+    }
+
+    /// Returns the instance of the module called `name` or the most recent one if `name` is `None`.
+    fn instance(&self, name: Option<Id>) -> Result<Instance, Failure> {
+        let Some(name) = name else {
+            return self
+                .last_instance
+                .ok_or_else(|| Failure::Failed(String::from("no module instantiated so far")));
+        };
+        let name = name.name();
+        self.instances
+            .get(name)
+            .copied()
+            .ok_or_else(|| Failure::Failed(format!("missing module instance `{name}`")))
+    }
+
+    /// Executes a `register` directive.
+    fn register(&mut self, as_name: &str, module: Option<Id>) -> Result<(), Failure> {
+        let instance = self.instance(module)?;
+        let exports = instance
+            .exports(&self.store)
+            .map(|export| (export.name().to_string(), export.into_extern()))
+            .collect::<Vec<_>>();
+        for (name, export) in exports {
+            self.linker
+                .define(as_name, &name, export)
+                .map_err(Error::from)?;
+        }
+        Ok(())
+    }
+
+    /// Executes an `invoke` action.
+    ///
+    /// The outer [`Result`] is an error if the invocation cannot be performed.
+    /// The inner [`Result`] holds the outcome of the invocation.
+    fn invoke(&mut self, invoke: WastInvoke) -> Result<Result<Vec<Value>, Error>, Failure> {
+        let instance = self.instance(invoke.module)?;
+        let name = invoke.name;
+        let func = instance
+            .get_export(&self.store, name)
+            .and_then(Extern::into_func)
+            .ok_or_else(|| Failure::Failed(format!("missing exported function `{name}`")))?;
+        let params = invoke
+            .args
+            .iter()
+            .map(|arg| self.arg(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut results = func
+            .ty(&self.store)
+            .results()
+            .iter()
+            .copied()
+            .map(Value::default)
+            .collect::<Vec<_>>();
+        Ok(func
+            .call(&mut self.store, &params, &mut results)
+            .map(|()| results))
+    }
+
+    /// Executes an action of an assertion.
+    ///
+    /// See [`Runner::invoke`] for the meaning of the returned [`Result`]s.
+    fn execute(&mut self, exec: WastExecute) -> Result<Result<Vec<Value>, Error>, Failure> {
+        match exec {
+            WastExecute::Invoke(invoke) => self.invoke(invoke),
+            WastExecute::Wat(Wat::Module(module)) => {
+                let wasm = Self::encode(&mut QuoteWat::Wat(Wat::Module(module)))?;
+                Ok(self.instantiate(&wasm).map(|_| Vec::new()))
+            }
+            WastExecute::Get { module, global, .. } => {
+                let instance = self.instance(module)?;
+                let global = instance
+                    .get_export(&self.store, global)
+                    .and_then(Extern::into_global)
+                    .ok_or_else(|| {
+                        Failure::Failed(format!("missing exported global variable `{global}`"))
+                    })?;
+                Ok(Ok(Vec::from([global.get(&self.store)])))
+            }
+            _ => Err(Failure::Skipped),
+        }
+    }
+
+    /// Converts the `arg` of an action into a [`Value`].
+    fn arg(&mut self, arg: &WastArg) -> Result<Value, Failure> {
+        let WastArg::Core(arg) = arg else {
+            return Err(Failure::Skipped);
+        };
+        let value = match arg {
+            WastArgCore::I32(value) => Value::I32(*value),
+            WastArgCore::I64(value) => Value::I64(*value),
+            WastArgCore::F32(value) => Value::F32(F32::from_bits(value.bits)),
+            WastArgCore::F64(value) => Value::F64(F64::from_bits(value.bits)),
+            WastArgCore::RefNull(HeapType::Abstract {
+                ty: AbstractHeapType::Func,
+                ..
+            }) => Value::FuncRef(FuncRef::null()),
+            WastArgCore::RefNull(HeapType::Abstract {
+                ty: AbstractHeapType::Extern,
+                ..
+            }) => Value::ExternRef(ExternRef::null()),
+            WastArgCore::RefExtern(value) => {
+                Value::ExternRef(ExternRef::new(&mut self.store, *value))
+            }
+            _ => return Err(Failure::Skipped),
+        };
+        Ok(value)
+    }
+
+    /// Executes an `assert_return` directive.
+    fn assert_return(&mut self, exec: WastExecute, expected: &[WastRet]) -> Result<(), Failure> {
+        let results = self
+            .execute(exec)?
+            .map_err(|error| format!("expected results but found error: {error}"))?;
+        if results.len() != expected.len() {
+            return Err(Failure::Failed(format!(
+                "expected {} results but found {results:?}",
+                expected.len()
+            )));
+        }
+        for (result, expected) in results.iter().zip(expected) {
+            let WastRet::Core(expected) = expected else {
+                return Err(Failure::Skipped);
+            };
+            if !self.matches(result, expected)? {
+                return Err(Failure::Failed(format!(
+                    "expected {expected:?} but found {result:?}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if `result` matches the `expected` pattern.
+    fn matches(&self, result: &Value, expected: &WastRetCore) -> Result<bool, Failure> {
+        let matches = match (result, expected) {
+            (Value::I32(result), WastRetCore::I32(expected)) => result == expected,
+            (Value::I64(result), WastRetCore::I64(expected)) => result == expected,
+            (Value::F32(result), WastRetCore::F32(expected)) => {
+                let bits = result.to_bits();
+                match expected {
+                    NanPattern::CanonicalNan => bits & 0x7FFF_FFFF == 0x7FC0_0000,
+                    NanPattern::ArithmeticNan => bits & 0x7FC0_0000 == 0x7FC0_0000,
+                    NanPattern::Value(expected) => bits == expected.bits,
+                }
+            }
+            (Value::F64(result), WastRetCore::F64(expected)) => {
+                let bits = result.to_bits();
+                match expected {
+                    NanPattern::CanonicalNan => {
+                        bits & 0x7FFF_FFFF_FFFF_FFFF == 0x7FF8_0000_0000_0000
+                    }
+                    NanPattern::ArithmeticNan => {
+                        bits & 0x7FF8_0000_0000_0000 == 0x7FF8_0000_0000_0000
+                    }
+                    NanPattern::Value(expected) => bits == expected.bits,
+                }
+            }
+            (Value::FuncRef(result), WastRetCore::RefNull(None))
+            | (
+                Value::FuncRef(result),
+                WastRetCore::RefNull(Some(HeapType::Abstract {
+                    ty: AbstractHeapType::Func,
+                    ..
+                })),
+            ) => result.is_null(),
+            (Value::ExternRef(result), WastRetCore::RefNull(None))
+            | (
+                Value::ExternRef(result),
+                WastRetCore::RefNull(Some(HeapType::Abstract {
+                    ty: AbstractHeapType::Extern,
+                    ..
+                })),
+            ) => result.is_null(),
+            (Value::FuncRef(result), WastRetCore::RefFunc(_)) => !result.is_null(),
+            (Value::ExternRef(result), WastRetCore::RefExtern(expected)) => {
+                match (result.data(&self.store), expected) {
+                    (None, _) => false,
+                    (Some(_), None) => true,
+                    (Some(data), Some(expected)) => data.downcast_ref::<u32>() == Some(expected),
+                }
+            }
+            (_, WastRetCore::Either(expected)) => {
+                for expected in expected {
+                    if self.matches(result, expected)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            (_, WastRetCore::V128(_))
+            | (_, WastRetCore::RefNull(_))
+            | (_, WastRetCore::RefHost(_))
+            | (_, WastRetCore::RefAny)
+            | (_, WastRetCore::RefEq)
+            | (_, WastRetCore::RefArray)
+            | (_, WastRetCore::RefStruct)
+            | (_, WastRetCore::RefI31)
+            | (_, WastRetCore::RefI31Shared) => return Err(Failure::Skipped),
+            _ => false,
+        };
+        Ok(matches)
+    }
+
+    /// Asserts that `result` is an error with `message`.
+    ///
+    /// # Note
+    ///
+    /// The `message` only needs to be contained in the error message.
+    fn assert_trap(result: Result<Vec<Value>, Error>, message: &str) -> Result<(), Failure> {
+        match result {
+            Ok(results) => Err(Failure::Failed(format!(
+                "expected trap `{message}` but returned {results:?}"
+            ))),
+            Err(error) if error.to_string().contains(message) => Ok(()),
+            Err(error) => Err(Failure::Failed(format!(
+                "expected trap `{message}` but found: {error}"
+            ))),
+        }
+    }
+
+    /// Executes an `assert_invalid` directive.
+    ///
+    /// # Note
+    ///
+    /// Wasmi's validation error messages differ from the ones of the reference
+    /// interpreter and therefore only the failure itself is asserted.
+    fn assert_invalid(&mut self, mut module: QuoteWat) -> Result<(), Failure> {
+        let wasm = Self::encode(&mut module)?;
+        match Module::new(self.engine, &wasm[..]) {
+            Ok(_) => Err(Failure::Failed(String::from(
+                "expected validation to fail but it succeeded",
+            ))),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Executes an `assert_malformed` directive.
+    ///
+    /// A module in the text format that fails to encode counts as malformed.
+    fn assert_malformed(&mut self, mut module: QuoteWat) -> Result<(), Failure> {
+        let wasm = match Self::encode(&mut module) {
+            Ok(wasm) => wasm,
+            Err(Failure::Failed(_)) => return Ok(()),
+            Err(Failure::Skipped) => return Err(Failure::Skipped),
+        };
+        match Module::new(self.engine, &wasm[..]) {
+            Ok(_) => Err(Failure::Failed(String::from(
+                "expected decoding to fail but it succeeded",
+            ))),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Executes an `assert_unlinkable` directive.
+    fn assert_unlinkable(&mut self, module: Wat) -> Result<(), Failure> {
+        let wasm = Self::encode(&mut QuoteWat::Wat(module))?;
+        match self.instantiate(&wasm) {
+            Ok(_) => Err(Failure::Failed(String::from(
+                "expected linking to fail but it succeeded",
+            ))),
+            Err(_) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    core::{ValueType, F32, F64},
+    AsContextMut,
+    Error,
+    Func,
+    Global,
+    Linker,
+    Memory,
+    MemoryType,
+    Mutability,
+    Table,
+    TableType,
+    Value,
+};
+
+/// Defines the `spectest` host module imported by the Wasm spec testsuite in `linker`.
+///
+/// All definitions are allocated in `store` so that Wasm modules instantiated
+/// in `store` via `linker` can import them. The definitions are the same as the
+/// ones provided by the reference interpreter:
+///
+/// - `memory`: a linear memory with 1 to 2 pages
+/// - `table`: a `funcref` table with 10 to 20 elements
+/// - `global_i32`, `global_i64`, `global_f32`, `global_f64`: immutable global variables
+/// - `print`, `print_i32`, `print_i64`, `print_f32`, `print_f64`, `print_i32_f32`, `print_f64_f64`:
+///   host functions that discard their parameters
+///
+/// # Errors
+///
+/// If `linker` already contains a definition with the `spectest` module name.
+pub fn define_spectest<T>(
+    linker: &mut Linker<T>,
+    mut store: impl AsContextMut<UserState = T>,
+) -> Result<(), Error> {
+    let mut store = store.as_context_mut();
+    let memory = Memory::new(&mut store, MemoryType::new(1, Some(2))?)?;
+    let table = Table::new(
+        &mut store,
+        TableType::new(ValueType::FuncRef, 10, Some(20)),
+        Value::default(ValueType::FuncRef),
+    )?;
+    let global_i32 = Global::new(&mut store, Value::I32(666), Mutability::Const);
+    let global_i64 = Global::new(&mut store, Value::I64(666), Mutability::Const);
+    let global_f32 = Global::new(&mut store, Value::F32(F32::from(666.6)), Mutability::Const);
+    let global_f64 = Global::new(&mut store, Value::F64(F64::from(666.6)), Mutability::Const);
+    let print = Func::wrap(&mut store, || {});
+    let print_i32 = Func::wrap(&mut store, |_: i32| {});
+    let print_i64 = Func::wrap(&mut store, |_: i64| {});
+    let print_f32 = Func::wrap(&mut store, |_: F32| {});
+    let print_f64 = Func::wrap(&mut store, |_: F64| {});
+    let print_i32_f32 = Func::wrap(&mut store, |_: i32, _: F32| {});
+    let print_f64_f64 = Func::wrap(&mut store, |_: F64, _: F64| {});
+    linker
+        .define("spectest", "memory", memory)?
+        .define("spectest", "table", table)?
+        .define("spectest", "global_i32", global_i32)?
+        .define("spectest", "global_i64", global_i64)?
+        .define("spectest", "global_f32", global_f32)?
+        .define("spectest", "global_f64", global_f64)?
+        .define("spectest", "print", print)?
+        .define("spectest", "print_i32", print_i32)?
+        .define("spectest", "print_i64", print_i64)?
+        .define("spectest", "print_f32", print_f32)?
+        .define("spectest", "print_f64", print_f64)?
+        .define("spectest", "print_i32_f32", print_i32_f32)?
+        .define("spectest", "print_f64_f64", print_f64_f64)?;
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use super::StoreInner;
+use crate::{FuncIdx, Value};
+use wasmi_arena::ArenaIndex;
+
+/// The number of entities of a [`StoreInner`] at some point in time.
+///
+/// Used to remove the entities of failed instantiations from the [`StoreInner`] again.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StoreCheckpoint {
+    funcs: usize,
+    memories: usize,
+    tables: usize,
+    globals: usize,
+    instances: usize,
+    datas: usize,
+    elems: usize,
+    extern_objects: usize,
+    /// The number of host function calls at the time of the checkpoint.
+    host_calls: u64,
+}
+
+impl StoreInner {
+    /// Returns a [`StoreCheckpoint`] of the current number of entities.
+    pub fn checkpoint(&self) -> StoreCheckpoint {
+        StoreCheckpoint {
+            funcs: self.funcs.len(),
+            memories: self.memories.len(),
+            tables: self.tables.len(),
+            globals: self.globals.len(),
+            instances: self.instances.len(),
+            datas: self.datas.len(),
+            elems: self.elems.len(),
+            extern_objects: self.extern_objects.len(),
+            host_calls: self.host_calls,
+        }
+    }
+
+    /// Removes all entities allocated in between the `before` and `after` checkpoints.
+    ///
+    /// Returns `true` if the entities have been removed.
+    ///
+    /// # Note
+    ///
+    /// The entities are only removed if none of them can be reached anymore,
+    /// otherwise the [`StoreInner`] is left untouched and `false` is returned.
+    /// This is the case if
+    ///
+    /// - entities have been allocated after the `after` checkpoint,
+    /// - a host function has been called since the `before` checkpoint or
+    /// - a table or global variable allocated before the `before` checkpoint
+    ///   refers to a function allocated after it.
+    pub fn rollback(&mut self, before: &StoreCheckpoint, after: &StoreCheckpoint) -> bool {
+        if self.checkpoint() != *after || self.host_calls != before.host_calls {
+            return false;
+        }
+        let is_new_func = |value: Value| match value {
+            Value::FuncRef(funcref) => funcref.func().is_some_and(|func| {
+                let func_idx: FuncIdx = self.unwrap_stored(func.as_inner());
+                func_idx.into_usize() >= before.funcs
+            }),
+            _ => false,
+        };
+        let escaped_via_table = self.tables.iter().take(before.tables).any(|(_, table)| {
+            (0..table.size())
+                .filter_map(|index| table.get(index))
+                .any(&is_new_func)
+        });
+        let escaped_via_global = self
+            .globals
+            .iter()
+            .take(before.globals)
+            .any(|(_, global)| is_new_func(global.get()));
+        if escaped_via_table || escaped_via_global {
+            return false;
+        }
+        self.funcs.truncate(before.funcs);
+        self.memories.truncate(before.memories);
+        self.tables.truncate(before.tables);
+        self.globals.truncate(before.globals);
+        self.instances.truncate(before.instances);
+        self.entity_uses.truncate(before.instances);
+        self.datas.truncate(before.datas);
+        self.elems.truncate(before.elems);
+        self.extern_objects.truncate(before.extern_objects);
+        // Cached `call_indirect` callees may refer to the removed entities
+        // whose indices are going to be handed out again.
+        self.indirect_call_cache.clear();
+        true
+    }
+}
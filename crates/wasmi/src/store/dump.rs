@@ -0,0 +1,171 @@
+use super::{Store, StoreInner};
+use crate::{Extern, Func, Global, Instance, Memory, Table};
+use core::fmt::{self, Write};
+use wasmi_arena::ArenaIndex;
+
+impl StoreInner {
+    /// Returns an iterator over all [`Memory`] references of the [`StoreInner`].
+    pub fn memories(&self) -> impl ExactSizeIterator<Item = Memory> + '_ {
+        self.memories
+            .iter()
+            .map(|(idx, _)| Memory::from_inner(self.wrap_stored(idx)))
+    }
+
+    /// Returns an iterator over all [`Table`] references of the [`StoreInner`].
+    pub fn tables(&self) -> impl ExactSizeIterator<Item = Table> + '_ {
+        self.tables
+            .iter()
+            .map(|(idx, _)| Table::from_inner(self.wrap_stored(idx)))
+    }
+
+    /// Returns an iterator over all [`Global`] references of the [`StoreInner`].
+    pub fn globals(&self) -> impl ExactSizeIterator<Item = Global> + '_ {
+        self.globals
+            .iter()
+            .map(|(idx, _)| Global::from_inner(self.wrap_stored(idx)))
+    }
+
+    /// Returns an iterator over all [`Func`] references of the [`StoreInner`].
+    pub fn funcs(&self) -> impl ExactSizeIterator<Item = Func> + '_ {
+        self.funcs
+            .iter()
+            .map(|(idx, _)| Func::from_inner(self.wrap_stored(idx)))
+    }
+
+    /// Returns an iterator over all [`Instance`] references of the [`StoreInner`].
+    pub fn instances(&self) -> impl ExactSizeIterator<Item = Instance> + '_ {
+        self.instances
+            .iter()
+            .map(|(idx, _)| Instance::from_inner(self.wrap_stored(idx)))
+    }
+
+    /// Writes the kind and the index within the [`StoreInner`] of `item` to `f`.
+    fn write_extern(&self, f: &mut impl Write, item: &Extern) -> fmt::Result {
+        match item {
+            Extern::Global(global) => {
+                let idx = self.unwrap_stored(global.as_inner());
+                write!(f, "global {}", idx.into_usize())
+            }
+            Extern::Table(table) => {
+                let idx = self.unwrap_stored(table.as_inner());
+                write!(f, "table {}", idx.into_usize())
+            }
+            Extern::Memory(memory) => {
+                let idx = self.unwrap_stored(memory.as_inner());
+                write!(f, "memory {}", idx.into_usize())
+            }
+            Extern::Func(func) => {
+                let idx = self.unwrap_stored(func.as_inner());
+                write!(f, "func {}", idx.into_usize())
+            }
+        }
+    }
+}
+
+impl<T> Store<T> {
+    /// Returns an iterator over all [`Memory`] instances of the [`Store`] in allocation order.
+    ///
+    /// This includes host defined memories as well as memories of failed instantiations.
+    pub fn memories(&self) -> impl ExactSizeIterator<Item = Memory> + '_ {
+        self.inner.memories()
+    }
+
+    /// Returns an iterator over all [`Table`] instances of the [`Store`] in allocation order.
+    ///
+    /// This includes host defined tables as well as tables of failed instantiations.
+    pub fn tables(&self) -> impl ExactSizeIterator<Item = Table> + '_ {
+        self.inner.tables()
+    }
+
+    /// Returns an iterator over all [`Global`] variables of the [`Store`] in allocation order.
+    ///
+    /// This includes host defined globals as well as globals of failed instantiations.
+    pub fn globals(&self) -> impl ExactSizeIterator<Item = Global> + '_ {
+        self.inner.globals()
+    }
+
+    /// Returns an iterator over all Wasm and host [`Func`] instances of the [`Store`] in allocation order.
+    ///
+    /// This includes functions of failed instantiations.
+    pub fn funcs(&self) -> impl ExactSizeIterator<Item = Func> + '_ {
+        self.inner.funcs()
+    }
+
+    /// Returns an iterator over all [`Instance`]s of the [`Store`] in allocation order.
+    ///
+    /// This includes instances of failed instantiations which are reported
+    /// as uninitialized by [`Store::debug_dump`].
+    pub fn instances(&self) -> impl ExactSizeIterator<Item = Instance> + '_ {
+        self.inner.instances()
+    }
+
+    /// Writes a human readable summary of all entities of the [`Store`] to `writer`.
+    ///
+    /// The summary contains the number of instances, functions, memories, tables and globals,
+    /// the sizes of all memories, the lengths of all tables, the values of all globals
+    /// and the export names of all instances.
+    ///
+    /// # Note
+    ///
+    /// - Entities are referred to by their allocation order within the [`Store`] as
+    ///   yielded by [`Store::memories`], [`Store::tables`], [`Store::globals`],
+    ///   [`Store::funcs`] and [`Store::instances`].
+    /// - The format of the summary is meant for diagnostics and may change.
+    ///
+    /// # Errors
+    ///
+    /// If writing to `writer` fails.
+    pub fn debug_dump(&self, writer: &mut impl Write) -> fmt::Result {
+        let inner = &self.inner;
+        writeln!(
+            writer,
+            "store: {} instances, {} funcs, {} memories, {} tables, {} globals",
+            inner.instances.len(),
+            inner.funcs.len(),
+            inner.memories.len(),
+            inner.tables.len(),
+            inner.globals.len(),
+        )?;
+        for (idx, memory) in inner.memories.iter() {
+            writeln!(
+                writer,
+                "memory {}: {} pages ({} bytes)",
+                idx.into_usize(),
+                u32::from(memory.current_pages()),
+                memory.data().len(),
+            )?;
+        }
+        for (idx, table) in inner.tables.iter() {
+            writeln!(
+                writer,
+                "table {}: {} elements of {:?}",
+                idx.into_usize(),
+                table.size(),
+                table.ty().element(),
+            )?;
+        }
+        for (idx, global) in inner.globals.iter() {
+            writeln!(
+                writer,
+                "global {}: {:?} ({:?})",
+                idx.into_usize(),
+                global.get(),
+                global.ty().mutability(),
+            )?;
+        }
+        for (idx, instance) in inner.instances.iter() {
+            write!(writer, "instance {}:", idx.into_usize())?;
+            if !instance.is_initialized() {
+                writeln!(writer, " uninitialized")?;
+                continue;
+            }
+            writeln!(writer)?;
+            for export in instance.exports() {
+                write!(writer, "  export {:?}: ", export.name())?;
+                inner.write_extern(writer, &export.into_extern())?;
+                writeln!(writer)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{engine::bytecode::Instruction, FuncIdx};
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+
+#[cfg(doc)]
+use crate::Func;
+
+/// The metrics of the host initiated calls of a single [`Func`].
+///
+/// Returned by [`Store::metrics`].
+///
+/// [`Store::metrics`]: crate::Store::metrics
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CallMetrics {
+    /// The number of host initiated calls of the [`Func`].
+    pub calls: u64,
+    /// The cumulative duration of all calls of the [`Func`] in nanoseconds.
+    pub total_nanos: u128,
+    /// The number of calls of the [`Func`] that returned an error.
+    pub traps: u64,
+}
+
+/// A category of executed Wasmi bytecode instructions.
+///
+/// Read [`Store::instruction_category_counts`] for more information.
+///
+/// [`Store::instruction_category_counts`]: crate::Store::instruction_category_counts
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InstructionCategory {
+    /// Linear memory load instructions.
+    Load,
+    /// Linear memory store instructions.
+    Store,
+    /// Conditional branch instructions that branched to their target.
+    BranchTaken,
+    /// Conditional branch instructions that fell through to the next instruction.
+    BranchNotTaken,
+    /// Direct, indirect and tail call instructions.
+    Call,
+    /// Instructions operating on `f32` or `f64` values apart from loads and stores.
+    ///
+    /// # Note
+    ///
+    /// This includes fused compare and branch instructions on `f32` or `f64`
+    /// values which are additionally counted as [`InstructionCategory::BranchTaken`]
+    /// or [`InstructionCategory::BranchNotTaken`].
+    Float,
+}
+
+impl InstructionCategory {
+    /// The number of [`InstructionCategory`] variants.
+    const COUNT: usize = 6;
+
+    /// Returns the [`InstructionCategory`] of `instr` if any.
+    ///
+    /// # Note
+    ///
+    /// Conditional branches are not categorized by their [`Instruction`] since
+    /// [`InstructionCategory::BranchTaken`] and [`InstructionCategory::BranchNotTaken`]
+    /// depend on their execution.
+    fn of(instr: &Instruction) -> Option<Self> {
+        use Instruction as Instr;
+        match instr {
+            Instr::I32Load(_)
+            | Instr::I32LoadAt(_)
+            | Instr::I32LoadOffset16(_)
+            | Instr::I64Load(_)
+            | Instr::I64LoadAt(_)
+            | Instr::I64LoadOffset16(_)
+            | Instr::F32Load(_)
+            | Instr::F32LoadAt(_)
+            | Instr::F32LoadOffset16(_)
+            | Instr::F64Load(_)
+            | Instr::F64LoadAt(_)
+            | Instr::F64LoadOffset16(_)
+            | Instr::I32Load8s(_)
+            | Instr::I32Load8sAt(_)
+            | Instr::I32Load8sOffset16(_)
+            | Instr::I32Load8u(_)
+            | Instr::I32Load8uAt(_)
+            | Instr::I32Load8uOffset16(_)
+            | Instr::I32Load16s(_)
+            | Instr::I32Load16sAt(_)
+            | Instr::I32Load16sOffset16(_)
+            | Instr::I32Load16u(_)
+            | Instr::I32Load16uAt(_)
+            | Instr::I32Load16uOffset16(_)
+            | Instr::I64Load8s(_)
+            | Instr::I64Load8sAt(_)
+            | Instr::I64Load8sOffset16(_)
+            | Instr::I64Load8u(_)
+            | Instr::I64Load8uAt(_)
+            | Instr::I64Load8uOffset16(_)
+            | Instr::I64Load16s(_)
+            | Instr::I64Load16sAt(_)
+            | Instr::I64Load16sOffset16(_)
+            | Instr::I64Load16u(_)
+            | Instr::I64Load16uAt(_)
+            | Instr::I64Load16uOffset16(_)
+            | Instr::I64Load32s(_)
+            | Instr::I64Load32sAt(_)
+            | Instr::I64Load32sOffset16(_)
+            | Instr::I64Load32u(_)
+            | Instr::I64Load32uAt(_)
+            | Instr::I64Load32uOffset16(_) => Some(Self::Load),
+            Instr::I32Store(_)
+            | Instr::I32StoreOffset16(_)
+            | Instr::I32StoreOffset16Imm16(_)
+            | Instr::I32StoreAt(_)
+            | Instr::I32StoreAtImm16(_)
+            | Instr::I32Store8(_)
+            | Instr::I32Store8Offset16(_)
+            | Instr::I32Store8Offset16Imm(_)
+            | Instr::I32Store8At(_)
+            | Instr::I32Store8AtImm(_)
+            | Instr::I32Store16(_)
+            | Instr::I32Store16Offset16(_)
+            | Instr::I32Store16Offset16Imm(_)
+            | Instr::I32Store16At(_)
+            | Instr::I32Store16AtImm(_)
+            | Instr::I64Store(_)
+            | Instr::I64StoreOffset16(_)
+            | Instr::I64StoreOffset16Imm16(_)
+            | Instr::I64StoreAt(_)
+            | Instr::I64StoreAtImm16(_)
+            | Instr::I64Store8(_)
+            | Instr::I64Store8Offset16(_)
+            | Instr::I64Store8Offset16Imm(_)
+            | Instr::I64Store8At(_)
+            | Instr::I64Store8AtImm(_)
+            | Instr::I64Store16(_)
+            | Instr::I64Store16Offset16(_)
+            | Instr::I64Store16Offset16Imm(_)
+            | Instr::I64Store16At(_)
+            | Instr::I64Store16AtImm(_)
+            | Instr::I64Store32(_)
+            | Instr::I64Store32Offset16(_)
+            | Instr::I64Store32Offset16Imm16(_)
+            | Instr::I64Store32At(_)
+            | Instr::I64Store32AtImm16(_)
+            | Instr::F32Store(_)
+            | Instr::F32StoreOffset16(_)
+            | Instr::F32StoreAt(_)
+            | Instr::F64Store(_)
+            | Instr::F64StoreOffset16(_)
+            | Instr::F64StoreAt(_) => Some(Self::Store),
+            Instr::ReturnCallInternal0 { .. }
+            | Instr::ReturnCallInternal { .. }
+            | Instr::ReturnCallImported0 { .. }
+            | Instr::ReturnCallImported { .. }
+            | Instr::ReturnCallIndirect0 { .. }
+            | Instr::ReturnCallIndirect { .. }
+            | Instr::CallInternal0 { .. }
+            | Instr::CallInternal { .. }
+            | Instr::CallImported0 { .. }
+            | Instr::CallImported { .. }
+            | Instr::CallIndirect0 { .. }
+            | Instr::CallIndirect { .. } => Some(Self::Call),
+            Instr::BranchF32Eq(_)
+            | Instr::BranchF32Ne(_)
+            | Instr::BranchF32Lt(_)
+            | Instr::BranchF32Le(_)
+            | Instr::BranchF32Gt(_)
+            | Instr::BranchF32Ge(_)
+            | Instr::BranchF32NotLt(_)
+            | Instr::BranchF32NotLe(_)
+            | Instr::BranchF64Eq(_)
+            | Instr::BranchF64Ne(_)
+            | Instr::BranchF64Lt(_)
+            | Instr::BranchF64Le(_)
+            | Instr::BranchF64Gt(_)
+            | Instr::BranchF64Ge(_)
+            | Instr::BranchF64NotLt(_)
+            | Instr::BranchF64NotLe(_)
+            | Instr::F32Eq(_)
+            | Instr::F64Eq(_)
+            | Instr::F32Ne(_)
+            | Instr::F64Ne(_)
+            | Instr::F32Lt(_)
+            | Instr::F64Lt(_)
+            | Instr::F32Le(_)
+            | Instr::F64Le(_)
+            | Instr::F32Gt(_)
+            | Instr::F64Gt(_)
+            | Instr::F32Ge(_)
+            | Instr::F64Ge(_)
+            | Instr::F32Abs(_)
+            | Instr::F64Abs(_)
+            | Instr::F32Neg(_)
+            | Instr::F64Neg(_)
+            | Instr::F32Ceil(_)
+            | Instr::F64Ceil(_)
+            | Instr::F32Floor(_)
+            | Instr::F64Floor(_)
+            | Instr::F32Trunc(_)
+            | Instr::F64Trunc(_)
+            | Instr::F32Nearest(_)
+            | Instr::F64Nearest(_)
+            | Instr::F32Sqrt(_)
+            | Instr::F64Sqrt(_)
+            | Instr::F32Add(_)
+            | Instr::F64Add(_)
+            | Instr::F32Sub(_)
+            | Instr::F64Sub(_)
+            | Instr::F32Mul(_)
+            | Instr::F64Mul(_)
+            | Instr::F32Div(_)
+            | Instr::F64Div(_)
+            | Instr::F32Min(_)
+            | Instr::F64Min(_)
+            | Instr::F32Max(_)
+            | Instr::F64Max(_)
+            | Instr::F32Copysign(_)
+            | Instr::F64Copysign(_)
+            | Instr::F32CopysignImm(_)
+            | Instr::F64CopysignImm(_)
+            | Instr::I32TruncF32S(_)
+            | Instr::I32TruncF32U(_)
+            | Instr::I32TruncF64S(_)
+            | Instr::I32TruncF64U(_)
+            | Instr::I64TruncF32S(_)
+            | Instr::I64TruncF32U(_)
+            | Instr::I64TruncF64S(_)
+            | Instr::I64TruncF64U(_)
+            | Instr::I32TruncSatF32S(_)
+            | Instr::I32TruncSatF32U(_)
+            | Instr::I32TruncSatF64S(_)
+            | Instr::I32TruncSatF64U(_)
+            | Instr::I64TruncSatF32S(_)
+            | Instr::I64TruncSatF32U(_)
+            | Instr::I64TruncSatF64S(_)
+            | Instr::I64TruncSatF64U(_)
+            | Instr::F32DemoteF64(_)
+            | Instr::F64PromoteF32(_)
+            | Instr::F32ConvertI32S(_)
+            | Instr::F32ConvertI32U(_)
+            | Instr::F32ConvertI64S(_)
+            | Instr::F32ConvertI64U(_)
+            | Instr::F64ConvertI32S(_)
+            | Instr::F64ConvertI32U(_)
+            | Instr::F64ConvertI64S(_)
+            | Instr::F64ConvertI64U(_) => Some(Self::Float),
+            _ => None,
+        }
+    }
+}
+
+/// The number of executed instructions per [`InstructionCategory`].
+///
+/// Returned by [`Store::instruction_category_counts`].
+///
+/// [`Store::instruction_category_counts`]: crate::Store::instruction_category_counts
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct InstructionCategoryCounts {
+    /// The number of executed instructions indexed by [`InstructionCategory`].
+    counts: [u64; InstructionCategory::COUNT],
+}
+
+impl InstructionCategoryCounts {
+    /// Returns the number of executed instructions of the `category`.
+    pub fn get(&self, category: InstructionCategory) -> u64 {
+        self.counts[category as usize]
+    }
+
+    /// Counts an executed instruction of the `category`.
+    #[inline]
+    pub(crate) fn count(&mut self, category: InstructionCategory) {
+        self.counts[category as usize] += 1;
+    }
+
+    /// Counts the executed `instr` if it has an [`InstructionCategory`].
+    #[inline]
+    pub(crate) fn count_instr(&mut self, instr: &Instruction) {
+        if let Some(category) = InstructionCategory::of(instr) {
+            self.count(category);
+        }
+    }
+}
+
+/// The [`CallMetrics`] recorded by a [`Store`] per called [`Func`].
+///
+/// [`Store`]: crate::Store
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// Is `true` if calls are recorded.
+    enabled: bool,
+    /// The recorded [`CallMetrics`] per [`Func`] in allocation order of the [`Func`].
+    funcs: BTreeMap<FuncIdx, CallMetrics>,
+    /// The number of executed instructions per [`InstructionCategory`].
+    ///
+    /// # Note
+    ///
+    /// This is only recorded if [`Config::profiling`] is enabled.
+    ///
+    /// [`Config::profiling`]: crate::Config::profiling
+    instrs: InstructionCategoryCounts,
+}
+
+impl Metrics {
+    /// Returns `true` if calls are recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables recording of calls.
+    pub fn set_enabled(&mut self, enable: bool) {
+        self.enabled = enable;
+    }
+
+    /// Records a call of `func` that took `duration` and returned an error if `trapped` is `true`.
+    pub fn record(&mut self, func: FuncIdx, duration: Duration, trapped: bool) {
+        let metrics = self.funcs.entry(func).or_default();
+        metrics.calls += 1;
+        metrics.total_nanos += duration.as_nanos();
+        metrics.traps += u64::from(trapped);
+    }
+
+    /// Returns a shared reference to the [`InstructionCategoryCounts`].
+    pub fn instrs(&self) -> &InstructionCategoryCounts {
+        &self.instrs
+    }
+
+    /// Returns an exclusive reference to the [`InstructionCategoryCounts`].
+    pub fn instrs_mut(&mut self) -> &mut InstructionCategoryCounts {
+        &mut self.instrs
+    }
+
+    /// Returns an iterator over the recorded [`CallMetrics`] per [`Func`] in allocation order.
+    pub fn iter(&self) -> impl Iterator<Item = (FuncIdx, CallMetrics)> + '_ {
+        self.funcs.iter().map(|(func, metrics)| (*func, *metrics))
+    }
+}
@@ -1,18 +1,44 @@
+mod checkpoint;
+mod dump;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod snapshot;
+mod uses;
+mod watchpoint;
+mod yielding;
+
+pub(crate) use self::checkpoint::StoreCheckpoint;
+pub use self::{
+    snapshot::{SnapshotError, StoreSnapshot},
+    watchpoint::{WatchpointHit, WatchpointId},
+    yielding::YieldDecision,
+};
+#[cfg(feature = "metrics")]
+pub use self::metrics::{CallMetrics, InstructionCategory, InstructionCategoryCounts};
+#[cfg(feature = "metrics")]
+pub(crate) use self::metrics::Metrics;
+use self::{
+    uses::EntityUses,
+    watchpoint::Watchpoints,
+    yielding::{YieldCallback, YieldCounter},
+};
 use crate::{
-    engine::{DedupFuncType, FuelCosts},
+    engine::{DedupFuncType, EngineIdx, FuelCosts, IndirectCallCache, StackUsage},
+    error::{EntityGrowError, GrowError},
     externref::{ExternObject, ExternObjectEntity, ExternObjectIdx},
-    func::{Trampoline, TrampolineEntity, TrampolineIdx},
+    func::{FuncError, Trampoline, TrampolineEntity, TrampolineIdx},
     memory::{DataSegment, MemoryError},
     module::InstantiationError,
     table::TableError,
     DataSegmentEntity, DataSegmentIdx, ElementSegment, ElementSegmentEntity, ElementSegmentIdx,
     Engine, Func, FuncEntity, FuncIdx, FuncType, Global, GlobalEntity, GlobalIdx, Instance,
     InstanceEntity, InstanceIdx, Memory, MemoryEntity, MemoryIdx, ResourceLimiter, Table,
-    TableEntity, TableIdx,
+    TableEntity, TableIdx, Value,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::{
     fmt::{self, Debug},
+    mem,
     sync::atomic::{AtomicU32, Ordering},
 };
 use wasmi_arena::{Arena, ArenaIndex, GuardedEntity};
@@ -23,7 +49,7 @@ use wasmi_core::TrapCode;
 /// # Note
 ///
 /// Used to protect against invalid entity indices.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct StoreIdx(u32);
 
 impl ArenaIndex for StoreIdx {
@@ -82,6 +108,20 @@ impl<T> Debug for ResourceLimiterQuery<T> {
     }
 }
 
+/// A wrapper around a user provided source of random bytes.
+///
+/// Installed via [`Store::set_entropy_source`].
+struct EntropySource(Box<EntropyFn>);
+
+/// The type of the closure of an [`EntropySource`].
+type EntropyFn = dyn FnMut(&mut [u8]) + Send + Sync;
+
+impl Debug for EntropySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EntropySource(...)")
+    }
+}
+
 /// The store that owns all data associated to Wasm modules.
 #[derive(Debug)]
 pub struct Store<T> {
@@ -98,6 +138,10 @@ pub struct Store<T> {
     data: T,
     /// User provided hook to retrieve a [`ResourceLimiter`].
     limiter: Option<ResourceLimiterQuery<T>>,
+    /// User provided callback that is periodically invoked during Wasm executions.
+    yield_callback: Option<YieldCallback<T>>,
+    /// User provided source of random bytes.
+    entropy: Option<EntropySource>,
 }
 
 /// The inner store that owns all data not associated to the host state.
@@ -117,6 +161,8 @@ pub struct StoreInner {
     globals: Arena<GlobalIdx, GlobalEntity>,
     /// Stored module instances.
     instances: Arena<InstanceIdx, InstanceEntity>,
+    /// The instances using the stored linear memories, tables and global variables.
+    entity_uses: EntityUses,
     /// Stored data segments.
     datas: Arena<DataSegmentIdx, DataSegmentEntity>,
     /// Stored data segments.
@@ -129,10 +175,39 @@ pub struct StoreInner {
     ///
     /// Amongst others the [`Engine`] stores the Wasm function definitions.
     engine: Engine,
+    /// The [`EngineIdx`] of the [`Engine`] cached for cheap ownership checks.
+    engine_idx: EngineIdx,
     /// The fuel of the [`Store`].
     fuel: Fuel,
+    /// Counts the fuel until the yield callback of the [`Store`] is due.
+    yield_counter: YieldCounter,
     /// The runtime_signature of the [`Store`].
     runtime_signature: u64,
+    /// Caches the resolved callees of `call_indirect` call sites.
+    indirect_call_cache: IndirectCallCache,
+    /// The continuation token of a host function re-entered via [`ResumableInvocation::resume_with`].
+    ///
+    /// This is `None` while host functions run that are called by Wasm during the
+    /// re-entered host function, e.g. via [`Func::call`] on its [`Caller`].
+    ///
+    /// [`Func::call`]: crate::Func::call
+    /// [`Caller`]: crate::Caller
+    /// [`ResumableInvocation::resume_with`]: crate::ResumableInvocation::resume_with
+    #[cfg(feature = "resumable")]
+    host_continuation: Option<u64>,
+    /// The peak usage of the Wasm stack by executions since the last reset.
+    stack_usage: StackUsage,
+    /// The number of host function calls dispatched by the executor.
+    host_calls: u64,
+    /// The number of executions of the engine on the [`StoreInner`] that have not yet returned.
+    executions: u32,
+    /// The number of host function calls of the active executions that have not yet returned.
+    active_host_calls: u32,
+    /// The write watchpoints guarding ranges of the linear memories.
+    watchpoints: Watchpoints,
+    /// The [`CallMetrics`] of host initiated calls.
+    #[cfg(feature = "metrics")]
+    metrics: Metrics,
 }
 
 #[test]
@@ -194,11 +269,15 @@ pub struct Fuel {
     remaining: u64,
     /// The total amount of fuel so far.
     total: u64,
+    /// The fuel consumed for lazy compilation of Wasm functions so far.
+    compilation: u64,
     /// This is `true` if fuel metering is enabled for the [`Engine`].
     enabled: bool,
-    /// The fuel costs provided by the [`Engine`]'s [`Config`].
-    ///
-    /// [`Config`]: crate::Config
+    /// This is `true` if `memory.grow` traps when running out of fuel.
+    memory_grow_traps: bool,
+    /// This is `true` if lazy compilation of Wasm functions consumes fuel.
+    charge_compilation: bool,
+    /// The fuel costs of the [`Engine`] at the time the [`Fuel`] was created.
     costs: FuelCosts,
 }
 
@@ -207,17 +286,22 @@ impl Fuel {
     pub fn new(engine: &Engine) -> Self {
         let config = engine.config();
         let enabled = config.get_consume_fuel();
-        let costs = *config.fuel_costs();
+        let memory_grow_traps = config.get_memory_grow_traps_on_out_of_fuel();
+        let charge_compilation = config.get_charge_compilation_fuel();
+        let costs = engine.fuel_costs();
         Self {
             remaining: 0,
             total: 0,
+            compilation: 0,
             enabled,
+            memory_grow_traps,
+            charge_compilation,
             costs,
         }
     }
 
     /// Returns `true` if fuel metering is enabled.
-    fn is_fuel_metering_enabled(&self) -> bool {
+    pub(crate) fn is_fuel_metering_enabled(&self) -> bool {
         self.enabled
     }
 
@@ -258,12 +342,42 @@ impl Fuel {
     }
 
     /// Returns the amount of [`Fuel`] consumed by executions of the [`Store`] so far.
+    ///
+    /// This excludes the [`Fuel`] consumed for lazy compilation of Wasm functions.
     pub fn fuel_consumed(&self) -> Option<u64> {
         self.check_fuel_metering_enabled().ok()?;
-        let consumed = self.total.wrapping_sub(self.remaining);
+        let consumed = self
+            .total
+            .wrapping_sub(self.remaining)
+            .wrapping_sub(self.compilation);
         Some(consumed)
     }
 
+    /// Returns the amount of [`Fuel`] consumed for lazy compilation of Wasm functions so far.
+    pub fn compilation_fuel_consumed(&self) -> Option<u64> {
+        self.check_fuel_metering_enabled().ok()?;
+        Some(self.compilation)
+    }
+
+    /// Consumes the [`Fuel`] for the lazy compilation of `len_bytes` of Wasm bytecode.
+    ///
+    /// # Note
+    ///
+    /// This does nothing if fuel metering or charging for compilation is disabled.
+    ///
+    /// # Errors
+    ///
+    /// If out of fuel. In this case no fuel is consumed.
+    pub(crate) fn consume_compilation_fuel(&mut self, len_bytes: u64) -> Result<(), TrapCode> {
+        if !self.enabled || !self.charge_compilation {
+            return Ok(());
+        }
+        let delta = self.costs.fuel_for_bytes(len_bytes);
+        self.consume_fuel_unchecked(delta)?;
+        self.compilation += delta;
+        Ok(())
+    }
+
     /// Synthetically consumes an amount of [`Fuel`] from the [`Store`].
     ///
     /// Returns the remaining amount of [`Fuel`] after this operation.
@@ -301,6 +415,48 @@ impl Fuel {
             .map_err(|_| FuelError::OutOfFuel)
     }
 
+    /// Consumes the [`Fuel`] for growing a linear memory by `len_bytes` if fuel metering is enabled.
+    ///
+    /// Returns the amount of consumed fuel which must be refunded
+    /// via [`Fuel::refund_fuel`] if growing the linear memory fails.
+    ///
+    /// # Errors
+    ///
+    /// If out of fuel. In this case no fuel is consumed and the error either is a
+    /// [`TrapCode::OutOfFuel`] trap or an invalid grow as configured via
+    /// [`Config::memory_grow_traps_on_out_of_fuel`].
+    ///
+    /// [`Config::memory_grow_traps_on_out_of_fuel`]: crate::Config::memory_grow_traps_on_out_of_fuel
+    pub(crate) fn consume_fuel_for_memory_grow(
+        &mut self,
+        len_bytes: u64,
+    ) -> Result<u64, EntityGrowError> {
+        if !self.is_fuel_metering_enabled() {
+            return Ok(0);
+        }
+        let delta = self.costs.fuel_for_bytes(len_bytes);
+        match self.consume_fuel_unchecked(delta) {
+            Ok(_) => Ok(delta),
+            Err(trap_code) if self.memory_grow_traps => Err(EntityGrowError::TrapCode(trap_code)),
+            Err(_) => Err(EntityGrowError::InvalidGrow(GrowError::OutOfFuel)),
+        }
+    }
+
+    /// Refunds `delta` amount of [`Fuel`] that has previously been consumed.
+    ///
+    /// # Note
+    ///
+    /// This is used to refund the fuel of operations that failed after consuming fuel.
+    pub(crate) fn refund_fuel(&mut self, delta: u64) {
+        self.remaining = self.remaining.checked_add(delta).unwrap_or_else(|| {
+            panic!(
+                "encountered remaining fuel overflow: fuel = {}, delta = {delta}",
+                self.remaining
+            )
+        });
+        debug_assert!(self.remaining <= self.total);
+    }
+
     /// Synthetically consumes an amount of [`Fuel`] from the [`Store`] if fuel metering is enabled.
     ///
     /// # Note
@@ -327,17 +483,30 @@ impl StoreInner {
         let fuel = Fuel::new(engine);
         StoreInner {
             engine: engine.clone(),
+            engine_idx: engine.engine_idx(),
             store_idx: StoreIdx::new(),
             funcs: Arena::new(),
             memories: Arena::new(),
             tables: Arena::new(),
             globals: Arena::new(),
             instances: Arena::new(),
+            entity_uses: EntityUses::default(),
             datas: Arena::new(),
             elems: Arena::new(),
             extern_objects: Arena::new(),
             fuel,
             runtime_signature: 0x97b69fcae66984bf,
+            indirect_call_cache: IndirectCallCache::default(),
+            #[cfg(feature = "resumable")]
+            host_continuation: None,
+            stack_usage: StackUsage::default(),
+            yield_counter: YieldCounter::default(),
+            host_calls: 0,
+            executions: 0,
+            active_host_calls: 0,
+            watchpoints: Watchpoints::default(),
+            #[cfg(feature = "metrics")]
+            metrics: Metrics::default(),
         }
     }
 
@@ -346,11 +515,121 @@ impl StoreInner {
         &self.engine
     }
 
+    /// Returns a shared reference to the [`IndirectCallCache`].
+    pub fn indirect_call_cache(&self) -> &IndirectCallCache {
+        &self.indirect_call_cache
+    }
+
+    /// Returns an exclusive reference to the [`IndirectCallCache`].
+    pub fn indirect_call_cache_mut(&mut self) -> &mut IndirectCallCache {
+        &mut self.indirect_call_cache
+    }
+
+    /// Returns the continuation token of the currently re-entered host function if any.
+    #[cfg(feature = "resumable")]
+    pub fn host_continuation(&self) -> Option<u64> {
+        self.host_continuation
+    }
+
+    /// Replaces the continuation token of the currently running host function.
+    ///
+    /// Returns the continuation token of the host function running before if any.
+    #[cfg(feature = "resumable")]
+    pub fn replace_host_continuation(&mut self, continuation: Option<u64>) -> Option<u64> {
+        mem::replace(&mut self.host_continuation, continuation)
+    }
+
     /// Returns an exclusive reference to the [`Fuel`] counters.
     pub fn fuel_mut(&mut self) -> &mut Fuel {
         &mut self.fuel
     }
 
+    /// Merges the peak [`StackUsage`] of an execution into the [`StoreInner`].
+    pub fn record_stack_usage(&mut self, usage: StackUsage) {
+        self.stack_usage.merge(usage);
+    }
+
+    /// Returns a shared reference to the recorded [`Metrics`].
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Returns an exclusive reference to the recorded [`Metrics`].
+    #[cfg(feature = "metrics")]
+    pub fn metrics_mut(&mut self) -> &mut Metrics {
+        &mut self.metrics
+    }
+
+    /// Records a host initiated call of `func` that took `duration`.
+    ///
+    /// The call returned an error if `trapped` is `true`.
+    ///
+    /// # Panics
+    ///
+    /// If `func` does not originate from this [`StoreInner`].
+    #[cfg(feature = "metrics")]
+    pub fn record_call_metrics(
+        &mut self,
+        func: &Func,
+        duration: core::time::Duration,
+        trapped: bool,
+    ) {
+        let idx = self.unwrap_stored(func.as_inner());
+        self.metrics.record(idx, duration, trapped);
+    }
+
+    /// Counts a host function call dispatched by the executor.
+    pub fn count_host_call(&mut self) {
+        self.host_calls = self.host_calls.wrapping_add(1);
+    }
+
+    /// Returns `true` if the engine is executing a function of the [`StoreInner`].
+    ///
+    /// This includes executions that are currently calling a host function.
+    pub fn is_executing(&self) -> bool {
+        self.executions != 0
+    }
+
+    /// Registers the start of an execution of the engine on the [`StoreInner`].
+    ///
+    /// # Errors
+    ///
+    /// If an execution is in progress that is not currently calling a host function.
+    /// Nested executions are only valid if entered via the [`Caller`] of a host function.
+    ///
+    /// [`Caller`]: crate::Caller
+    pub fn enter_execution(&mut self) -> Result<(), FuncError> {
+        if self.executions != self.active_host_calls {
+            return Err(FuncError::StoreAlreadyExecuting);
+        }
+        self.executions += 1;
+        Ok(())
+    }
+
+    /// Registers the end of an execution of the engine on the [`StoreInner`].
+    pub fn exit_execution(&mut self) {
+        debug_assert!(self.executions > self.active_host_calls);
+        self.executions -= 1;
+    }
+
+    /// Registers the start of a host function call dispatched by the executor.
+    pub fn enter_host_call(&mut self) {
+        self.count_host_call();
+        self.active_host_calls += 1;
+    }
+
+    /// Registers the end of a host function call dispatched by the executor.
+    pub fn exit_host_call(&mut self) {
+        debug_assert!(self.active_host_calls > 0);
+        self.active_host_calls -= 1;
+    }
+
+    /// Returns an exclusive reference to the [`YieldCounter`].
+    pub fn yield_counter_mut(&mut self) -> &mut YieldCounter {
+        &mut self.yield_counter
+    }
+
     /// Wraps an entity `Idx` (index type) as a [`Stored<Idx>`] type.
     ///
     /// # Note
@@ -378,6 +657,34 @@ impl StoreInner {
         })
     }
 
+    /// Unwraps the indices of all `entities` via `as_inner`.
+    fn unwrap_all<T, Idx>(&self, entities: &[T], as_inner: fn(&T) -> &Stored<Idx>) -> Vec<Idx>
+    where
+        Idx: ArenaIndex + Debug,
+    {
+        entities
+            .iter()
+            .map(|entity| self.unwrap_stored(as_inner(entity)))
+            .collect()
+    }
+
+    /// Returns `true` if all entities referenced by `value` are owned by the [`StoreInner`].
+    ///
+    /// This is always `true` for numeric values and `null` references.
+    pub fn owns_value(&self, value: &Value) -> bool {
+        match value {
+            Value::FuncRef(funcref) => match funcref.func() {
+                Some(func) => func.as_inner().entity_index(self.store_idx).is_some(),
+                None => true,
+            },
+            Value::ExternRef(externref) => match externref.object() {
+                Some(object) => object.as_inner().entity_index(self.store_idx).is_some(),
+                None => true,
+            },
+            _ => true,
+        }
+    }
+
     /// Allocates a new [`GlobalEntity`] and returns a [`Global`] reference to it.
     pub fn alloc_global(&mut self, global: GlobalEntity) -> Global {
         let global = self.globals.alloc(global);
@@ -457,7 +764,37 @@ impl StoreInner {
             !uninit.is_initialized(),
             "encountered an already initialized instance: {uninit:?}",
         );
-        *uninit = init;
+        let memories = self.unwrap_all(init.memories(), Memory::as_inner);
+        let tables = self.unwrap_all(init.tables(), Table::as_inner);
+        let globals = self.unwrap_all(init.globals(), Global::as_inner);
+        self.entity_uses.record(idx, memories, tables, globals);
+        self.instances[idx] = init;
+    }
+
+    /// Returns the initialized instances using the `memory` in instantiation order.
+    pub fn memory_instances(&self, memory: &Memory) -> Vec<Instance> {
+        let memory = self.unwrap_stored(memory.as_inner());
+        self.wrap_instances(self.entity_uses.memory(memory))
+    }
+
+    /// Returns the initialized instances using the `table` in instantiation order.
+    pub fn table_instances(&self, table: &Table) -> Vec<Instance> {
+        let table = self.unwrap_stored(table.as_inner());
+        self.wrap_instances(self.entity_uses.table(table))
+    }
+
+    /// Returns the initialized instances using the `global` in instantiation order.
+    pub fn global_instances(&self, global: &Global) -> Vec<Instance> {
+        let global = self.unwrap_stored(global.as_inner());
+        self.wrap_instances(self.entity_uses.global(global))
+    }
+
+    /// Wraps the `instances` into [`Instance`] references of the [`StoreInner`].
+    fn wrap_instances(&self, instances: Vec<InstanceIdx>) -> Vec<Instance> {
+        instances
+            .into_iter()
+            .map(|instance| Instance::from_inner(self.wrap_stored(instance)))
+            .collect()
     }
 
     /// Returns a shared reference to the entity indexed by the given `idx`.
@@ -813,6 +1150,33 @@ impl StoreInner {
         })
     }
 
+    /// Returns `Ok` if the [`Func`] belongs to the [`Engine`] of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// This is a cheap check that guards executions against Wasm functions of
+    /// [`Module`]s compiled by another [`Engine`] than the one of the [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`Func`] belongs to another [`Engine`] than the one of the [`Store`].
+    ///
+    /// # Panics
+    ///
+    /// If the [`Func`] does not originate from this [`Store`].
+    ///
+    /// [`Module`]: crate::Module
+    pub fn check_func_engine(&self, func: &Func) -> Result<(), FuncError> {
+        match self
+            .resolve_func(func)
+            .ty_dedup()
+            .is_owned_by(self.engine_idx)
+        {
+            true => Ok(()),
+            false => Err(FuncError::EngineMismatch),
+        }
+    }
+
     pub fn get_runtime_signature(&self) -> u64 {
         self.runtime_signature
     }
@@ -830,6 +1194,8 @@ impl<T> Store<T> {
             trampolines: Arena::new(),
             data,
             limiter: None,
+            yield_callback: None,
+            entropy: None,
         }
     }
 
@@ -843,12 +1209,40 @@ impl<T> Store<T> {
         &self.data
     }
 
+    /// Returns exclusive references to the [`StoreInner`] and the user provided data.
+    ///
+    /// This allows to borrow both disjointly.
+    pub(crate) fn inner_and_data_mut(&mut self) -> (&mut StoreInner, &mut T) {
+        (&mut self.inner, &mut self.data)
+    }
+
     /// Returns an exclusive reference to the user provided data owned by this [`Store`].
     pub fn data_mut(&mut self) -> &mut T {
         &mut self.data
     }
 
+    /// Replaces the user provided data owned by this [`Store`] with `data` and returns the old data.
+    ///
+    /// # Note
+    ///
+    /// - All instances, functions, memories, tables and globals of the [`Store`] stay valid.
+    ///   Host functions do not capture the data but receive it through their [`Caller`]
+    ///   at call time so calls after the replacement observe the new `data`.
+    /// - Closures installed via [`Store::limiter`] and [`Store::set_yield_callback`] are kept
+    ///   and are invoked with the new `data` from now on. Therefore a [`ResourceLimiter`]
+    ///   returned by [`Store::limiter`] must be retrievable from the new `data` as well.
+    ///
+    /// [`Caller`]: crate::Caller
+    pub fn replace_data(&mut self, data: T) -> T {
+        mem::replace(&mut self.data, data)
+    }
+
     /// Consumes `self` and returns its user provided data.
+    ///
+    /// # Note
+    ///
+    /// This drops all instances and other entities of the [`Store`].
+    /// Use [`Store::replace_data`] to take out the data while keeping them.
     pub fn into_data(self) -> T {
         self.data
     }
@@ -863,6 +1257,52 @@ impl<T> Store<T> {
         self.limiter = Some(ResourceLimiterQuery(Box::new(limiter)))
     }
 
+    /// Installs a `callback` into the [`Store`] that is invoked with the user data
+    /// type `T` each time Wasm executions consumed `interval` amount of fuel.
+    ///
+    /// The `callback` decides whether the execution continues or traps.
+    /// This allows single-threaded embedders to periodically regain control,
+    /// e.g. to pump the events of a GUI main loop.
+    ///
+    /// # Note
+    ///
+    /// - The `callback` runs synchronously within the Wasm execution.
+    ///   It has access to the user data but not to the Wasm call stack.
+    /// - Fuel is counted at the sites that consume fuel for entire blocks of
+    ///   instructions. Therefore the `callback` is invoked at most once per
+    ///   such site and the actual fuel in between two invocations might
+    ///   slightly exceed `interval`.
+    /// - The `callback` is only invoked for functions that have been translated
+    ///   with either [`Config::consume_fuel`] or [`Config::cooperative_yield`] enabled.
+    ///   With fuel metering disabled the fuel is counted as if it was enabled.
+    /// - Installing a new `callback` replaces the previous one and resets the counted fuel.
+    ///
+    /// # Panics
+    ///
+    /// If `interval` is zero.
+    ///
+    /// [`Config::consume_fuel`]: crate::Config::consume_fuel
+    /// [`Config::cooperative_yield`]: crate::Config::cooperative_yield
+    pub fn set_yield_callback(
+        &mut self,
+        interval: u64,
+        callback: impl FnMut(&mut T) -> YieldDecision + Send + Sync + 'static,
+    ) {
+        assert_ne!(interval, 0, "the yield interval must not be zero");
+        self.inner.yield_counter.set_interval(interval);
+        self.yield_callback = Some(YieldCallback(Box::new(callback)));
+    }
+
+    /// Invokes the yield callback of the [`Store`] if any.
+    ///
+    /// Returns [`YieldDecision::Continue`] if no yield callback is installed.
+    pub(crate) fn invoke_yield_callback(&mut self) -> YieldDecision {
+        match &mut self.yield_callback {
+            Some(callback) => (callback.0)(&mut self.data),
+            None => YieldDecision::Continue,
+        }
+    }
+
     pub(crate) fn check_new_instances_limit(
         &mut self,
         num_new_instances: usize,
@@ -928,10 +1368,32 @@ impl<T> Store<T> {
     /// Returns the amount of fuel consumed by executions of the [`Store`] so far.
     ///
     /// Returns `None` if fuel metering is disabled.
+    ///
+    /// # Note
+    ///
+    /// The fuel consumed for lazy compilation of Wasm functions is not included
+    /// so that the result does not depend on the configured [`CompilationMode`].
+    /// Use [`Store::compilation_fuel_consumed`] to query it instead.
+    ///
+    /// [`CompilationMode`]: crate::CompilationMode
     pub fn fuel_consumed(&self) -> Option<u64> {
         self.inner.fuel.fuel_consumed()
     }
 
+    /// Returns the amount of fuel consumed for lazy compilation of Wasm functions so far.
+    ///
+    /// Returns `None` if fuel metering is disabled.
+    ///
+    /// # Note
+    ///
+    /// Compilation fuel is drawn from the remaining fuel of the [`Store`]
+    /// unless disabled via [`Config::charge_compilation_fuel`].
+    ///
+    /// [`Config::charge_compilation_fuel`]: crate::Config::charge_compilation_fuel
+    pub fn compilation_fuel_consumed(&self) -> Option<u64> {
+        self.inner.fuel.compilation_fuel_consumed()
+    }
+
     /// Synthetically consumes an amount of fuel for the [`Store`].
     ///
     /// Returns the remaining amount of fuel after this operation.
@@ -948,6 +1410,117 @@ impl<T> Store<T> {
         self.inner.fuel.consume_fuel(|_| delta)
     }
 
+    /// Returns the peak [`StackUsage`] of Wasm executions of the [`Store`] since the last reset.
+    ///
+    /// # Note
+    ///
+    /// - The usage is updated whenever an execution finishes, traps or yields
+    ///   back to the host via a resumable call.
+    /// - Executions that are nested via host functions use separate Wasm stacks.
+    ///   Their usage is not added up but merged with the usage of their callers.
+    pub fn stack_usage(&self) -> StackUsage {
+        self.inner.stack_usage
+    }
+
+    /// Returns `true` while the engine is executing a function of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// Executions can only be nested by calling functions via the [`Caller`]
+    /// of a host function. Calling into the [`Store`] while it is executing
+    /// by any other means fails with [`FuncError::StoreAlreadyExecuting`].
+    ///
+    /// [`Caller`]: crate::Caller
+    /// [`FuncError::StoreAlreadyExecuting`]: crate::errors::FuncError::StoreAlreadyExecuting
+    pub fn is_executing(&self) -> bool {
+        self.inner.is_executing()
+    }
+
+    /// Resets the peak [`StackUsage`] of the [`Store`].
+    pub fn reset_stack_usage(&mut self) {
+        self.inner.stack_usage = StackUsage::default();
+    }
+
+    /// Enables or disables recording of [`CallMetrics`] for calls of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// - Only calls initiated by the host via [`Func::call`], [`TypedFunc::call`] or
+    ///   their resumable variants are recorded. Calls between Wasm functions are not.
+    /// - Calls from host functions back into Wasm are recorded separately for the called
+    ///   [`Func`]. Their durations are also included in the durations of the outer call.
+    /// - The duration of a resumable call only covers its execution up to
+    ///   the first host trap and excludes resumed executions.
+    /// - Disabling keeps the metrics recorded so far.
+    ///
+    /// Disabled by default.
+    ///
+    /// [`TypedFunc::call`]: crate::TypedFunc::call
+    #[cfg(feature = "metrics")]
+    pub fn enable_metrics(&mut self, enable: bool) {
+        self.inner.metrics.set_enabled(enable);
+    }
+
+    /// Returns an iterator over the recorded [`CallMetrics`] per called [`Func`].
+    ///
+    /// The functions are iterated in their allocation order as yielded by [`Store::funcs`].
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> impl Iterator<Item = (Func, CallMetrics)> + '_ {
+        self.inner
+            .metrics
+            .iter()
+            .map(|(idx, metrics)| (Func::from_inner(self.inner.wrap_stored(idx)), metrics))
+    }
+
+    /// Returns the number of executed instructions per [`InstructionCategory`].
+    ///
+    /// # Note
+    ///
+    /// - Instructions are only counted if [`Config::profiling`] is enabled.
+    /// - Instructions of all Wasm executions of the [`Store`] are counted
+    ///   until [`Store::reset_instruction_category_counts`] is called.
+    ///
+    /// [`Config::profiling`]: crate::Config::profiling
+    #[cfg(feature = "metrics")]
+    pub fn instruction_category_counts(&self) -> InstructionCategoryCounts {
+        *self.inner.metrics.instrs()
+    }
+
+    /// Resets the number of executed instructions per [`InstructionCategory`] to zero.
+    #[cfg(feature = "metrics")]
+    pub fn reset_instruction_category_counts(&mut self) {
+        *self.inner.metrics.instrs_mut() = InstructionCategoryCounts::default();
+    }
+
+    /// Installs the `source` of random bytes used by [`Store::fill_entropy`].
+    ///
+    /// # Note
+    ///
+    /// - No behavior of Wasmi depends on ambient randomness. Wasmi itself currently
+    ///   does not need any randomness. Any future randomized behavior of Wasmi, as
+    ///   well as host functions that provide randomness to Wasm, e.g. WASI's `random_get`,
+    ///   are supposed to draw their random bytes via [`Store::fill_entropy`].
+    ///   This way embedders control all randomness and may replay executions
+    ///   deterministically by installing a seeded `source`.
+    /// - Installing a new `source` replaces the previous one.
+    pub fn set_entropy_source(&mut self, source: impl FnMut(&mut [u8]) + Send + Sync + 'static) {
+        self.entropy = Some(EntropySource(Box::new(source)));
+    }
+
+    /// Fills `bytes` with random bytes drawn from the entropy source of the [`Store`].
+    ///
+    /// Returns `false` and leaves `bytes` untouched if no entropy source has been
+    /// installed via [`Store::set_entropy_source`].
+    pub fn fill_entropy(&mut self, bytes: &mut [u8]) -> bool {
+        match &mut self.entropy {
+            Some(source) => {
+                (source.0)(bytes);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Allocates a new [`TrampolineEntity`] and returns a [`Trampoline`] reference to it.
     pub(super) fn alloc_trampoline(&mut self, func: TrampolineEntity<T>) -> Trampoline {
         let idx = self.trampolines.alloc(func);
@@ -986,6 +1559,16 @@ impl<T> Store<T> {
             .unwrap_or_else(|| panic!("failed to resolve stored host function: {entity_index:?}"))
     }
 
+    /// Returns the runtime signature of the [`Store`].
+    ///
+    /// The runtime signature digests the Wasmi executions of the [`Store`]
+    /// as configured via [`Config::execution_digest`].
+    ///
+    /// [`Config::execution_digest`]: crate::Config::execution_digest
+    pub fn runtime_signature(&self) -> u64 {
+        self.inner.get_runtime_signature()
+    }
+
     pub fn get_runtime_signature(&self) -> u64 {
         self.inner.get_runtime_signature()
     }
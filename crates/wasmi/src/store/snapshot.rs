@@ -0,0 +1,732 @@
+use super::Store;
+use crate::{
+    core::{Pages, UntypedValue, ValueType},
+    value::WithType,
+    Error,
+    ExternRef,
+    FuncIdx,
+    FuncRef,
+    GlobalType,
+    InstanceIdx,
+    MemoryType,
+    Mutability,
+    TableType,
+    Value,
+};
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use core::fmt::{self, Display};
+use wasmi_arena::ArenaIndex;
+
+/// The magic number at the start of the bytes of [`StoreSnapshot::to_bytes`].
+const MAGIC: [u8; 4] = *b"wsnp";
+
+/// The version of the format of [`StoreSnapshot::to_bytes`].
+const VERSION: u8 = 1;
+
+/// An error that may occur upon taking or restoring a [`StoreSnapshot`].
+#[derive(Debug, Clone)]
+pub enum SnapshotError {
+    /// Encountered a function reference that is not owned by any instance of the [`Store`].
+    ///
+    /// # Note
+    ///
+    /// Function references are snapshotted as pairs of instance and function indices.
+    /// Host functions that have not been imported by any instance cannot be represented.
+    UnresolvableFuncRef,
+    /// The [`Store`] does not have the same entities as the [`Store`] of the [`StoreSnapshot`].
+    MismatchingTopology,
+    /// The bytes given to [`StoreSnapshot::from_bytes`] do not encode a valid [`StoreSnapshot`].
+    MalformedBytes,
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnresolvableFuncRef => {
+                write!(f, "function reference is not owned by any instance")
+            }
+            Self::MismatchingTopology => {
+                write!(f, "store entities do not match the store snapshot")
+            }
+            Self::MalformedBytes => {
+                write!(f, "malformed store snapshot bytes")
+            }
+        }
+    }
+}
+
+/// A snapshot of the mutable state of a [`Store`].
+///
+/// Created via [`Store::snapshot`] and restored via [`Store::restore`].
+///
+/// # Note
+///
+/// The [`StoreSnapshot`] does not hold on to any entity of its [`Store`].
+/// Function references are represented by pairs of instance and function indices
+/// and external references by user provided identifiers.
+/// Therefore it can be persisted via [`StoreSnapshot::to_bytes`].
+#[derive(Debug, Clone)]
+pub struct StoreSnapshot {
+    /// The instances with their number of functions.
+    instances: Box<[u32]>,
+    /// The linear memories.
+    memories: Box<[MemorySnapshot]>,
+    /// The tables.
+    tables: Box<[TableSnapshot]>,
+    /// The global variables.
+    globals: Box<[GlobalSnapshot]>,
+    /// The dropped flags of the data segments.
+    datas: Box<[bool]>,
+    /// The element types and dropped flags of the element segments.
+    elems: Box<[(ValueType, bool)]>,
+    /// The remaining, total and compilation fuel.
+    fuel: (u64, u64, u64),
+}
+
+/// A snapshot of a linear memory.
+#[derive(Debug, Clone)]
+struct MemorySnapshot {
+    /// The type of the linear memory.
+    ty: MemoryType,
+    /// The number of pages of the linear memory.
+    pages: Pages,
+    /// The bytes of the linear memory.
+    bytes: Box<[u8]>,
+}
+
+/// A snapshot of a table.
+#[derive(Debug, Clone)]
+struct TableSnapshot {
+    /// The type of the table.
+    ty: TableType,
+    /// The elements of the table.
+    elements: Box<[ValueSnapshot]>,
+}
+
+/// A snapshot of a global variable.
+#[derive(Debug, Clone)]
+struct GlobalSnapshot {
+    /// The type of the global variable.
+    ty: GlobalType,
+    /// The value of the global variable.
+    value: ValueSnapshot,
+}
+
+/// A snapshot of a [`Value`] that does not refer to any entity of the [`Store`].
+#[derive(Debug, Copy, Clone)]
+enum ValueSnapshot {
+    /// A numeric value.
+    Num(UntypedValue),
+    /// A nullable function reference as pair of instance and function index.
+    FuncRef(Option<(u32, u32)>),
+    /// A nullable external reference as user provided identifier.
+    ExternRef(Option<u64>),
+}
+
+impl ValueSnapshot {
+    /// Returns `true` if the [`ValueSnapshot`] is a value of type `ty`.
+    fn is_of_type(&self, ty: ValueType) -> bool {
+        match self {
+            Self::Num(_) => !ty.is_ref(),
+            Self::FuncRef(_) => matches!(ty, ValueType::FuncRef),
+            Self::ExternRef(_) => matches!(ty, ValueType::ExternRef),
+        }
+    }
+
+    /// Encodes the [`ValueSnapshot`] into `writer`.
+    fn encode(&self, writer: &mut Writer) {
+        match *self {
+            Self::Num(value) => {
+                writer.u8(0);
+                writer.u64(value.to_bits());
+            }
+            Self::FuncRef(None) => writer.u8(1),
+            Self::FuncRef(Some((instance, func))) => {
+                writer.u8(2);
+                writer.u32(instance);
+                writer.u32(func);
+            }
+            Self::ExternRef(None) => writer.u8(3),
+            Self::ExternRef(Some(id)) => {
+                writer.u8(4);
+                writer.u64(id);
+            }
+        }
+    }
+
+    /// Decodes a [`ValueSnapshot`] of type `ty` from `reader`.
+    fn decode(reader: &mut Reader, ty: ValueType) -> Result<Self, SnapshotError> {
+        let value = match reader.u8()? {
+            0 => Self::Num(UntypedValue::from(reader.u64()?)),
+            1 => Self::FuncRef(None),
+            2 => Self::FuncRef(Some((reader.u32()?, reader.u32()?))),
+            3 => Self::ExternRef(None),
+            4 => Self::ExternRef(Some(reader.u64()?)),
+            _ => return Err(SnapshotError::MalformedBytes),
+        };
+        if !value.is_of_type(ty) {
+            return Err(SnapshotError::MalformedBytes);
+        }
+        Ok(value)
+    }
+}
+
+impl StoreSnapshot {
+    /// Encodes the [`StoreSnapshot`] into bytes.
+    ///
+    /// The bytes are decoded via [`StoreSnapshot::from_bytes`].
+    ///
+    /// # Note
+    ///
+    /// The format of the bytes is versioned and may change between Wasmi versions.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer::default();
+        writer.raw(&MAGIC);
+        writer.u8(VERSION);
+        writer.len(self.instances.len());
+        for &len_funcs in &self.instances[..] {
+            writer.u32(len_funcs);
+        }
+        writer.len(self.memories.len());
+        for memory in &self.memories[..] {
+            writer.u32(u32::from(memory.ty.initial_pages()));
+            writer.option_u32(memory.ty.maximum_pages().map(u32::from));
+            writer.u8(memory.ty.page_size_log2());
+            writer.u32(u32::from(memory.pages));
+            writer.len(memory.bytes.len());
+            writer.raw(&memory.bytes);
+        }
+        writer.len(self.tables.len());
+        for table in &self.tables[..] {
+            writer.value_type(table.ty.element());
+            writer.u32(table.ty.minimum());
+            writer.option_u32(table.ty.maximum());
+            writer.len(table.elements.len());
+            for element in &table.elements[..] {
+                element.encode(&mut writer);
+            }
+        }
+        writer.len(self.globals.len());
+        for global in &self.globals[..] {
+            writer.value_type(global.ty.content());
+            writer.bool(matches!(global.ty.mutability(), Mutability::Var));
+            global.value.encode(&mut writer);
+        }
+        writer.len(self.datas.len());
+        for &dropped in &self.datas[..] {
+            writer.bool(dropped);
+        }
+        writer.len(self.elems.len());
+        for &(ty, dropped) in &self.elems[..] {
+            writer.value_type(ty);
+            writer.bool(dropped);
+        }
+        let (remaining, total, compilation) = self.fuel;
+        writer.u64(remaining);
+        writer.u64(total);
+        writer.u64(compilation);
+        writer.bytes
+    }
+
+    /// Decodes a [`StoreSnapshot`] from the `bytes` of [`StoreSnapshot::to_bytes`].
+    ///
+    /// # Note
+    ///
+    /// The decoded [`StoreSnapshot`] is restored via [`Store::restore`] which checks
+    /// if its entities match those of the [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If `bytes` do not encode a valid [`StoreSnapshot`] of the current format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::decode(&mut Reader { bytes }).map_err(Error::from)
+    }
+
+    /// Decodes a [`StoreSnapshot`] from `reader`.
+    fn decode(reader: &mut Reader) -> Result<Self, SnapshotError> {
+        if reader.raw(MAGIC.len())? != MAGIC || reader.u8()? != VERSION {
+            return Err(SnapshotError::MalformedBytes);
+        }
+        let instances = reader.collect(Reader::u32)?;
+        let memories = reader.collect(|reader| {
+            let ty = MemoryType::builder()
+                .min(reader.u32()?)
+                .max(reader.option_u32()?)
+                .page_size_log2(reader.u8()?)
+                .build()
+                .map_err(|_| SnapshotError::MalformedBytes)?;
+            let pages = Pages::new_for_page_size(reader.u32()?, ty.page_size_log2())
+                .filter(|&pages| ty.initial_pages() <= pages && pages <= ty.maximum_pages_or_max())
+                .ok_or(SnapshotError::MalformedBytes)?;
+            let len_bytes = reader.len()?;
+            if ty.pages_to_bytes(pages) != Some(len_bytes) {
+                return Err(SnapshotError::MalformedBytes);
+            }
+            let bytes = reader.raw(len_bytes)?.into();
+            Ok(MemorySnapshot { ty, pages, bytes })
+        })?;
+        let tables = reader.collect(|reader| {
+            let element = reader.value_type()?;
+            let min = reader.u32()?;
+            let max = reader.option_u32()?;
+            if max.is_some_and(|max| min > max) {
+                return Err(SnapshotError::MalformedBytes);
+            }
+            let ty = TableType::new(element, min, max);
+            let elements: Box<[ValueSnapshot]> =
+                reader.collect(|reader| ValueSnapshot::decode(reader, element))?;
+            let len = u32::try_from(elements.len()).map_err(|_| SnapshotError::MalformedBytes)?;
+            if len < min || max.is_some_and(|max| len > max) {
+                return Err(SnapshotError::MalformedBytes);
+            }
+            Ok(TableSnapshot { ty, elements })
+        })?;
+        let globals = reader.collect(|reader| {
+            let content = reader.value_type()?;
+            let mutability = match reader.bool()? {
+                true => Mutability::Var,
+                false => Mutability::Const,
+            };
+            let value = ValueSnapshot::decode(reader, content)?;
+            Ok(GlobalSnapshot {
+                ty: GlobalType::new(content, mutability),
+                value,
+            })
+        })?;
+        let datas = reader.collect(Reader::bool)?;
+        let elems = reader.collect(|reader| Ok((reader.value_type()?, reader.bool()?)))?;
+        let fuel = (reader.u64()?, reader.u64()?, reader.u64()?);
+        if !reader.bytes.is_empty() {
+            return Err(SnapshotError::MalformedBytes);
+        }
+        let snapshot = Self {
+            instances,
+            memories,
+            tables,
+            globals,
+            datas,
+            elems,
+            fuel,
+        };
+        if !snapshot.funcrefs_are_valid() {
+            return Err(SnapshotError::MalformedBytes);
+        }
+        Ok(snapshot)
+    }
+
+    /// Returns `true` if all function references refer to functions of the instances of the [`StoreSnapshot`].
+    fn funcrefs_are_valid(&self) -> bool {
+        let tables = self.tables.iter().flat_map(|table| table.elements.iter());
+        let globals = self.globals.iter().map(|global| &global.value);
+        tables.chain(globals).all(|value| match value {
+            ValueSnapshot::FuncRef(Some((instance, func))) => self
+                .instances
+                .get(*instance as usize)
+                .is_some_and(|len_funcs| func < len_funcs),
+            _ => true,
+        })
+    }
+
+    /// Returns an iterator over the identifiers of all external references in the [`StoreSnapshot`].
+    fn externref_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        let tables = self.tables.iter().flat_map(|table| table.elements.iter());
+        let globals = self.globals.iter().map(|global| &global.value);
+        tables.chain(globals).filter_map(|value| match value {
+            ValueSnapshot::ExternRef(id) => *id,
+            _ => None,
+        })
+    }
+}
+
+impl<T> Store<T> {
+    /// Takes a [`StoreSnapshot`] of all linear memories, tables, global variables,
+    /// data and element segments as well as the fuel of the [`Store`].
+    ///
+    /// Non-null [`ExternRef`] values are mapped to stable identifiers via `externref_to_id`.
+    ///
+    /// # Note
+    ///
+    /// Snapshots must be taken in between calls since the state of ongoing
+    /// executions, e.g. of resumable calls, is not part of the [`StoreSnapshot`].
+    ///
+    /// # Errors
+    ///
+    /// If a table or global variable holds a function reference that is not
+    /// owned by any of the instances of the [`Store`].
+    pub fn snapshot(
+        &self,
+        mut externref_to_id: impl FnMut(&Self, ExternRef) -> u64,
+    ) -> Result<StoreSnapshot, Error> {
+        let inner = &self.inner;
+        let mut func_indices = BTreeMap::new();
+        let mut instances = Vec::with_capacity(inner.instances.len());
+        for (instance_idx, instance) in inner.instances.iter() {
+            let instance_idx = instance_idx.into_usize() as u32;
+            let mut len_funcs = 0;
+            while let Some(func) = instance.get_func(len_funcs) {
+                let func_idx = inner.unwrap_stored(func.as_inner());
+                func_indices
+                    .entry(func_idx)
+                    .or_insert((instance_idx, len_funcs));
+                len_funcs += 1;
+            }
+            instances.push(len_funcs);
+        }
+        let mut snapshot_value = |value: Value| -> Result<ValueSnapshot, Error> {
+            let snapshot = match value {
+                Value::FuncRef(funcref) => match funcref.func() {
+                    Some(func) => {
+                        let func_idx: FuncIdx = inner.unwrap_stored(func.as_inner());
+                        let Some(indices) = func_indices.get(&func_idx) else {
+                            return Err(Error::from(SnapshotError::UnresolvableFuncRef));
+                        };
+                        ValueSnapshot::FuncRef(Some(*indices))
+                    }
+                    None => ValueSnapshot::FuncRef(None),
+                },
+                Value::ExternRef(externref) if externref.is_null() => {
+                    ValueSnapshot::ExternRef(None)
+                }
+                Value::ExternRef(externref) => {
+                    ValueSnapshot::ExternRef(Some(externref_to_id(self, externref)))
+                }
+                value => ValueSnapshot::Num(UntypedValue::from(value)),
+            };
+            Ok(snapshot)
+        };
+        let memories = inner
+            .memories
+            .iter()
+            .map(|(_, memory)| MemorySnapshot {
+                ty: memory.ty(),
+                pages: memory.current_pages(),
+                bytes: memory.data().into(),
+            })
+            .collect();
+        let mut tables = Vec::with_capacity(inner.tables.len());
+        for (_, table) in inner.tables.iter() {
+            let elements = (0..table.size())
+                .filter_map(|index| table.get(index))
+                .map(&mut snapshot_value)
+                .collect::<Result<_, _>>()?;
+            tables.push(TableSnapshot {
+                ty: table.ty(),
+                elements,
+            });
+        }
+        let mut globals = Vec::with_capacity(inner.globals.len());
+        for (_, global) in inner.globals.iter() {
+            globals.push(GlobalSnapshot {
+                ty: global.ty(),
+                value: snapshot_value(global.get())?,
+            });
+        }
+        Ok(StoreSnapshot {
+            instances: instances.into(),
+            memories,
+            tables: tables.into(),
+            globals: globals.into(),
+            datas: inner
+                .datas
+                .iter()
+                .map(|(_, data)| data.is_dropped())
+                .collect(),
+            elems: inner
+                .elems
+                .iter()
+                .map(|(_, elem)| (elem.ty(), elem.is_dropped()))
+                .collect(),
+            fuel: (
+                inner.fuel.remaining,
+                inner.fuel.total,
+                inner.fuel.compilation,
+            ),
+        })
+    }
+
+    /// Restores the state of the [`Store`] to the given [`StoreSnapshot`].
+    ///
+    /// Identifiers of non-null [`ExternRef`] values are mapped back via `id_to_externref`.
+    ///
+    /// # Note
+    ///
+    /// The [`StoreSnapshot`] must have been taken from this [`Store`]. The [`Store`] is
+    /// left unchanged if restoration fails, apart from the effects of `id_to_externref`.
+    ///
+    /// # Errors
+    ///
+    /// If the instances, linear memories, tables, global variables or data and element
+    /// segments of the [`Store`] do not match those of the [`StoreSnapshot`].
+    pub fn restore(
+        &mut self,
+        snapshot: &StoreSnapshot,
+        mut id_to_externref: impl FnMut(&mut Self, u64) -> ExternRef,
+    ) -> Result<(), Error> {
+        self.check_topology(snapshot)?;
+        let mut externrefs = BTreeMap::new();
+        for id in snapshot.externref_ids() {
+            externrefs
+                .entry(id)
+                .or_insert_with(|| id_to_externref(self, id));
+        }
+        let inner = &mut self.inner;
+        let restore_value = |value: &ValueSnapshot, ty: ValueType| -> Value {
+            match *value {
+                ValueSnapshot::Num(value) => value.with_type(ty),
+                ValueSnapshot::FuncRef(None) => Value::from(FuncRef::null()),
+                ValueSnapshot::FuncRef(Some((instance, func))) => {
+                    let func = inner
+                        .instances
+                        .get(InstanceIdx::from_usize(instance as usize))
+                        .and_then(|instance| instance.get_func(func));
+                    Value::from(FuncRef::new(func))
+                }
+                ValueSnapshot::ExternRef(None) => Value::from(ExternRef::null()),
+                ValueSnapshot::ExternRef(Some(id)) => Value::from(externrefs[&id]),
+            }
+        };
+        let tables = snapshot
+            .tables
+            .iter()
+            .map(|table| {
+                table
+                    .elements
+                    .iter()
+                    .map(|element| restore_value(element, table.ty.element()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let globals = snapshot
+            .globals
+            .iter()
+            .map(|global| restore_value(&global.value, global.ty.content()))
+            .collect::<Vec<_>>();
+        for ((_, memory), snapshot) in inner.memories.iter_mut().zip(&snapshot.memories[..]) {
+            memory.restore(snapshot.pages, &snapshot.bytes[..]);
+        }
+        for ((_, table), elements) in inner.tables.iter_mut().zip(tables) {
+            table.restore(elements);
+        }
+        for ((_, global), value) in inner.globals.iter_mut().zip(globals) {
+            if let Mutability::Var = global.ty().mutability() {
+                global
+                    .set(value)
+                    .unwrap_or_else(|error| panic!("failed to restore global variable: {error}"));
+            }
+        }
+        for ((_, data), &dropped) in inner.datas.iter_mut().zip(&snapshot.datas[..]) {
+            data.set_dropped(dropped);
+        }
+        for ((_, elem), &(_, dropped)) in inner.elems.iter_mut().zip(&snapshot.elems[..]) {
+            elem.set_dropped(dropped);
+        }
+        (
+            inner.fuel.remaining,
+            inner.fuel.total,
+            inner.fuel.compilation,
+        ) = snapshot.fuel;
+        Ok(())
+    }
+
+    /// Checks if the entities of the [`Store`] match those of the `snapshot`.
+    ///
+    /// # Errors
+    ///
+    /// If the entities of the [`Store`] do not match those of the `snapshot`.
+    fn check_topology(&self, snapshot: &StoreSnapshot) -> Result<(), Error> {
+        let inner = &self.inner;
+        let instances_match = inner.instances.len() == snapshot.instances.len()
+            && inner.instances.iter().zip(&snapshot.instances[..]).all(
+                |((_, instance), &len_funcs)| {
+                    instance.get_func(len_funcs).is_none()
+                        && (len_funcs == 0 || instance.get_func(len_funcs - 1).is_some())
+                },
+            );
+        let memories_match = inner.memories.len() == snapshot.memories.len()
+            && inner
+                .memories
+                .iter()
+                .zip(&snapshot.memories[..])
+                .all(|((_, memory), snapshot)| memory.ty() == snapshot.ty);
+        let tables_match = inner.tables.len() == snapshot.tables.len()
+            && inner
+                .tables
+                .iter()
+                .zip(&snapshot.tables[..])
+                .all(|((_, table), snapshot)| table.ty() == snapshot.ty);
+        let globals_match = inner.globals.len() == snapshot.globals.len()
+            && inner
+                .globals
+                .iter()
+                .zip(&snapshot.globals[..])
+                .all(|((_, global), snapshot)| global.ty() == snapshot.ty);
+        let segments_match = inner.datas.len() == snapshot.datas.len()
+            && inner.elems.len() == snapshot.elems.len()
+            && inner
+                .elems
+                .iter()
+                .zip(&snapshot.elems[..])
+                .all(|((_, elem), (ty, _))| elem.ty() == *ty);
+        if !(instances_match && memories_match && tables_match && globals_match && segments_match) {
+            return Err(Error::from(SnapshotError::MismatchingTopology));
+        }
+        Ok(())
+    }
+}
+
+/// Encodes the bytes of [`StoreSnapshot::to_bytes`].
+///
+/// # Note
+///
+/// Integers are encoded in little-endian byte order.
+#[derive(Debug, Default)]
+struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    /// Encodes the raw `bytes` as is.
+    fn raw(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Encodes a single `value` byte.
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    /// Encodes a `bool` `value` as a single byte.
+    fn bool(&mut self, value: bool) {
+        self.u8(u8::from(value));
+    }
+
+    /// Encodes a `u32` `value`.
+    fn u32(&mut self, value: u32) {
+        self.raw(&value.to_le_bytes());
+    }
+
+    /// Encodes a `u64` `value`.
+    fn u64(&mut self, value: u64) {
+        self.raw(&value.to_le_bytes());
+    }
+
+    /// Encodes a length `len` as `u64`.
+    fn len(&mut self, len: usize) {
+        self.u64(len as u64);
+    }
+
+    /// Encodes an optional `u32` `value` prefixed by whether it is present.
+    fn option_u32(&mut self, value: Option<u32>) {
+        self.bool(value.is_some());
+        if let Some(value) = value {
+            self.u32(value);
+        }
+    }
+
+    /// Encodes the [`ValueType`] `ty` by its Wasm binary encoding.
+    fn value_type(&mut self, ty: ValueType) {
+        let byte = match ty {
+            ValueType::I32 => 0x7F,
+            ValueType::I64 => 0x7E,
+            ValueType::F32 => 0x7D,
+            ValueType::F64 => 0x7C,
+            ValueType::FuncRef => 0x70,
+            ValueType::ExternRef => 0x6F,
+        };
+        self.u8(byte);
+    }
+}
+
+/// Decodes the bytes encoded by the [`Writer`].
+#[derive(Debug)]
+struct Reader<'a> {
+    /// The bytes that have not yet been decoded.
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Decodes the next `len` raw bytes.
+    fn raw(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if len > self.bytes.len() {
+            return Err(SnapshotError::MalformedBytes);
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    /// Decodes the next `N` raw bytes as array.
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        let mut array = [0x00; N];
+        array.copy_from_slice(self.raw(N)?);
+        Ok(array)
+    }
+
+    /// Decodes a single byte.
+    fn u8(&mut self) -> Result<u8, SnapshotError> {
+        Ok(self.raw(1)?[0])
+    }
+
+    /// Decodes a `bool` from a single byte.
+    fn bool(&mut self) -> Result<bool, SnapshotError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(SnapshotError::MalformedBytes),
+        }
+    }
+
+    /// Decodes a `u32` value.
+    fn u32(&mut self) -> Result<u32, SnapshotError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    /// Decodes a `u64` value.
+    fn u64(&mut self) -> Result<u64, SnapshotError> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    /// Decodes a length encoded as `u64`.
+    fn len(&mut self) -> Result<usize, SnapshotError> {
+        usize::try_from(self.u64()?).map_err(|_| SnapshotError::MalformedBytes)
+    }
+
+    /// Decodes an optional `u32` value prefixed by whether it is present.
+    fn option_u32(&mut self) -> Result<Option<u32>, SnapshotError> {
+        match self.bool()? {
+            true => self.u32().map(Some),
+            false => Ok(None),
+        }
+    }
+
+    /// Decodes a [`ValueType`] from its Wasm binary encoding.
+    fn value_type(&mut self) -> Result<ValueType, SnapshotError> {
+        let ty = match self.u8()? {
+            0x7F => ValueType::I32,
+            0x7E => ValueType::I64,
+            0x7D => ValueType::F32,
+            0x7C => ValueType::F64,
+            0x70 => ValueType::FuncRef,
+            0x6F => ValueType::ExternRef,
+            _ => return Err(SnapshotError::MalformedBytes),
+        };
+        Ok(ty)
+    }
+
+    /// Decodes a length followed by as many items decoded via `f`.
+    ///
+    /// # Note
+    ///
+    /// Does not preallocate for the decoded length since it has not yet been validated.
+    fn collect<T, F>(&mut self, mut f: F) -> Result<Box<[T]>, SnapshotError>
+    where
+        F: FnMut(&mut Self) -> Result<T, SnapshotError>,
+    {
+        let len = self.len()?;
+        let mut items = Vec::new();
+        for _ in 0..len {
+            items.push(f(self)?);
+        }
+        Ok(items.into())
+    }
+}
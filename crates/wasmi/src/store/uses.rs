@@ -0,0 +1,84 @@
+use crate::{GlobalIdx, InstanceIdx, MemoryIdx, TableIdx};
+use alloc::vec::Vec;
+use wasmi_arena::ArenaIndex;
+
+/// Records which instances use the linear memories, tables and global variables of a [`StoreInner`].
+///
+/// # Note
+///
+/// - An instance uses an entity if the entity is part of its index space,
+///   i.e. if the instance defines, imports or re-exports it.
+/// - Uses are only ever appended upon instantiation in instantiation order
+///   and removed again if a failed instantiation is rolled back.
+///
+/// [`StoreInner`]: super::StoreInner
+#[derive(Debug, Default)]
+pub struct EntityUses {
+    /// The uses of linear memories.
+    memories: Vec<(MemoryIdx, InstanceIdx)>,
+    /// The uses of tables.
+    tables: Vec<(TableIdx, InstanceIdx)>,
+    /// The uses of global variables.
+    globals: Vec<(GlobalIdx, InstanceIdx)>,
+}
+
+impl EntityUses {
+    /// Records the uses of the `memories`, `tables` and `globals` by the new `instance`.
+    pub fn record(
+        &mut self,
+        instance: InstanceIdx,
+        memories: impl IntoIterator<Item = MemoryIdx>,
+        tables: impl IntoIterator<Item = TableIdx>,
+        globals: impl IntoIterator<Item = GlobalIdx>,
+    ) {
+        self.memories
+            .extend(memories.into_iter().map(|memory| (memory, instance)));
+        self.tables
+            .extend(tables.into_iter().map(|table| (table, instance)));
+        self.globals
+            .extend(globals.into_iter().map(|global| (global, instance)));
+    }
+
+    /// Removes all uses by instances with an index of `len_instances` or greater.
+    pub fn truncate(&mut self, len_instances: usize) {
+        let is_kept = |instance: &InstanceIdx| instance.into_usize() < len_instances;
+        self.memories.retain(|(_, instance)| is_kept(instance));
+        self.tables.retain(|(_, instance)| is_kept(instance));
+        self.globals.retain(|(_, instance)| is_kept(instance));
+    }
+
+    /// Returns the instances using the `memory` in instantiation order.
+    pub fn memory(&self, memory: MemoryIdx) -> Vec<InstanceIdx> {
+        users(&self.memories, memory)
+    }
+
+    /// Returns the instances using the `table` in instantiation order.
+    pub fn table(&self, table: TableIdx) -> Vec<InstanceIdx> {
+        users(&self.tables, table)
+    }
+
+    /// Returns the instances using the `global` in instantiation order.
+    pub fn global(&self, global: GlobalIdx) -> Vec<InstanceIdx> {
+        users(&self.globals, global)
+    }
+}
+
+/// Returns the instances of `uses` that use the entity at `idx`.
+///
+/// # Note
+///
+/// Instances that use the same entity multiple times, e.g. by importing
+/// the same table twice, are only returned once.
+fn users<Idx>(uses: &[(Idx, InstanceIdx)], idx: Idx) -> Vec<InstanceIdx>
+where
+    Idx: PartialEq,
+{
+    let mut instances = uses
+        .iter()
+        .filter(|(entity, _)| *entity == idx)
+        .map(|(_, instance)| *instance)
+        .collect::<Vec<_>>();
+    // Note: all uses of an instance are recorded at once and thus are adjacent.
+    instances.dedup();
+    instances
+}
@@ -0,0 +1,285 @@
+use super::{Store, StoreInner};
+use crate::{Memory, MemoryIdx};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    ops::Range,
+};
+use wasmi_core::TrapCode;
+
+/// The identifier of a write watchpoint registered with a [`Store`].
+///
+/// Created via [`Store::add_write_watchpoint`] or [`Store::add_write_watchpoint_callback`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct WatchpointId(u32);
+
+/// A write of a Wasm execution to a range of a linear memory guarded by a write watchpoint.
+///
+/// This is handed to the callbacks of write watchpoints before the write is applied.
+#[derive(Debug)]
+pub struct WatchpointHit<'a> {
+    /// The identifier of the hit write watchpoint.
+    id: WatchpointId,
+    /// The address of the first written byte.
+    address: u64,
+    /// The bytes of the linear memory before the write.
+    old_bytes: &'a [u8],
+    /// The bytes of the linear memory after the write.
+    new_bytes: &'a [u8],
+}
+
+impl<'a> WatchpointHit<'a> {
+    /// Returns the [`WatchpointId`] of the hit write watchpoint.
+    pub fn id(&self) -> WatchpointId {
+        self.id
+    }
+
+    /// Returns the address of the first written byte.
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Returns the number of written bytes.
+    pub fn len(&self) -> usize {
+        self.new_bytes.len()
+    }
+
+    /// Returns `true` if no bytes are written.
+    ///
+    /// # Note
+    ///
+    /// This is never the case since writes of zero bytes do not hit write watchpoints.
+    pub fn is_empty(&self) -> bool {
+        self.new_bytes.is_empty()
+    }
+
+    /// Returns the written bytes of the linear memory before the write.
+    pub fn old_bytes(&self) -> &'a [u8] {
+        self.old_bytes
+    }
+
+    /// Returns the written bytes of the linear memory after the write.
+    pub fn new_bytes(&self) -> &'a [u8] {
+        self.new_bytes
+    }
+}
+
+/// A callback of a write watchpoint installed via [`Store::add_write_watchpoint_callback`].
+struct WatchpointCallback(Box<dyn FnMut(&WatchpointHit) + Send + Sync>);
+
+impl Debug for WatchpointCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WatchpointCallback(...)")
+    }
+}
+
+/// A write watchpoint guarding a range of a linear memory.
+#[derive(Debug)]
+struct Watchpoint {
+    /// The identifier of the write watchpoint.
+    id: WatchpointId,
+    /// The guarded linear memory.
+    memory: MemoryIdx,
+    /// The guarded range of addresses of the linear memory.
+    range: Range<u64>,
+    /// The callback that is invoked upon hits.
+    ///
+    /// Hits trap with [`TrapCode::Watchpoint`] if this is `None`.
+    callback: Option<WatchpointCallback>,
+}
+
+impl Watchpoint {
+    /// Returns `true` if a write to `range` of `memory` hits the [`Watchpoint`].
+    fn is_hit(&self, memory: MemoryIdx, range: &Range<u64>) -> bool {
+        self.memory == memory
+            && !self.range.is_empty()
+            && !range.is_empty()
+            && self.range.start < range.end
+            && range.start < self.range.end
+    }
+}
+
+/// The write watchpoints registered with a [`Store`].
+#[derive(Debug, Default)]
+pub(super) struct Watchpoints {
+    /// The registered write watchpoints in the order of their registration.
+    watchpoints: Vec<Watchpoint>,
+    /// The identifier of the next registered write watchpoint.
+    next_id: u32,
+}
+
+impl Watchpoints {
+    /// Notifies the write watchpoints about a write of `new_bytes` at `address` of `memory`.
+    ///
+    /// # Errors
+    ///
+    /// If the write hits a write watchpoint without callback.
+    /// In this case no callbacks are invoked.
+    fn notify(
+        &mut self,
+        memory: MemoryIdx,
+        address: u64,
+        old_bytes: &[u8],
+        new_bytes: &[u8],
+    ) -> Result<(), TrapCode> {
+        let range = address..address + new_bytes.len() as u64;
+        let traps = self
+            .watchpoints
+            .iter()
+            .any(|watchpoint| watchpoint.callback.is_none() && watchpoint.is_hit(memory, &range));
+        if traps {
+            return Err(TrapCode::Watchpoint);
+        }
+        for watchpoint in &mut self.watchpoints {
+            if !watchpoint.is_hit(memory, &range) {
+                continue;
+            }
+            if let Some(callback) = &mut watchpoint.callback {
+                (callback.0)(&WatchpointHit {
+                    id: watchpoint.id,
+                    address,
+                    old_bytes,
+                    new_bytes,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StoreInner {
+    /// Registers a write watchpoint for `range` of `memory` and returns its [`WatchpointId`].
+    ///
+    /// # Panics
+    ///
+    /// If `memory` does not originate from this [`StoreInner`].
+    fn add_write_watchpoint(
+        &mut self,
+        memory: &Memory,
+        range: Range<u64>,
+        callback: Option<WatchpointCallback>,
+    ) -> WatchpointId {
+        let idx = self.unwrap_stored(memory.as_inner());
+        Self::resolve_mut(idx, &mut self.memories).set_watched(true);
+        let id = WatchpointId(self.watchpoints.next_id);
+        self.watchpoints.next_id = self
+            .watchpoints
+            .next_id
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("out of write watchpoint identifiers"));
+        self.watchpoints.watchpoints.push(Watchpoint {
+            id,
+            memory: idx,
+            range,
+            callback,
+        });
+        id
+    }
+
+    /// Removes the write watchpoint identified by `id`.
+    ///
+    /// Returns `true` if the write watchpoint has been removed.
+    fn remove_write_watchpoint(&mut self, id: WatchpointId) -> bool {
+        let watchpoints = &mut self.watchpoints.watchpoints;
+        let Some(position) = watchpoints
+            .iter()
+            .position(|watchpoint| watchpoint.id == id)
+        else {
+            return false;
+        };
+        let memory = watchpoints.remove(position).memory;
+        let is_watched = watchpoints
+            .iter()
+            .any(|watchpoint| watchpoint.memory == memory);
+        Self::resolve_mut(memory, &mut self.memories).set_watched(is_watched);
+        true
+    }
+
+    /// Writes `bytes` at `address` of `memory` respecting its write watchpoints.
+    ///
+    /// # Note
+    ///
+    /// The callbacks of hit write watchpoints are invoked before the write is applied.
+    ///
+    /// # Errors
+    ///
+    /// - If the write is out of bounds of `memory`.
+    /// - If the write hits a write watchpoint without callback.
+    ///
+    /// In both cases `memory` is left untouched.
+    ///
+    /// # Panics
+    ///
+    /// If `memory` does not originate from this [`StoreInner`].
+    pub fn write_watched(
+        &mut self,
+        memory: &Memory,
+        address: u64,
+        bytes: &[u8],
+    ) -> Result<(), TrapCode> {
+        let idx = self.unwrap_stored(memory.as_inner());
+        let data = Self::resolve_mut(idx, &mut self.memories).data_mut();
+        let written = usize::try_from(address)
+            .ok()
+            .and_then(|start| Some(start..start.checked_add(bytes.len())?))
+            .filter(|written| written.end <= data.len())
+            .ok_or(TrapCode::MemoryOutOfBounds)?;
+        let data = &mut data[written];
+        if !bytes.is_empty() {
+            self.watchpoints.notify(idx, address, data, bytes)?;
+        }
+        data.copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+impl<T> Store<T> {
+    /// Registers a write watchpoint for the addresses in `range` of `memory`.
+    ///
+    /// Wasm executions that write to any byte in `range` of `memory` trap with
+    /// [`TrapCode::Watchpoint`] without applying the write.
+    ///
+    /// # Note
+    ///
+    /// - Writes of the host via [`Memory::write`] or [`Memory::data_mut`] are not watched.
+    /// - Executions that write to a linear memory with write watchpoints take a slower path
+    ///   for all of its accesses. Linear memories without write watchpoints are unaffected.
+    ///
+    /// # Panics
+    ///
+    /// If `memory` does not originate from this [`Store`].
+    pub fn add_write_watchpoint(&mut self, memory: &Memory, range: Range<u64>) -> WatchpointId {
+        self.inner.add_write_watchpoint(memory, range, None)
+    }
+
+    /// Registers a write watchpoint for the addresses in `range` of `memory` with a `callback`.
+    ///
+    /// The `callback` is invoked with the [`WatchpointHit`] before a Wasm execution
+    /// writes to any byte in `range` of `memory`. The write is applied afterwards.
+    ///
+    /// # Note
+    ///
+    /// If a write also hits a write watchpoint registered via [`Store::add_write_watchpoint`]
+    /// it traps with [`TrapCode::Watchpoint`] without invoking any callbacks.
+    ///
+    /// # Panics
+    ///
+    /// If `memory` does not originate from this [`Store`].
+    pub fn add_write_watchpoint_callback(
+        &mut self,
+        memory: &Memory,
+        range: Range<u64>,
+        callback: impl FnMut(&WatchpointHit) + Send + Sync + 'static,
+    ) -> WatchpointId {
+        let callback = WatchpointCallback(Box::new(callback));
+        self.inner
+            .add_write_watchpoint(memory, range, Some(callback))
+    }
+
+    /// Removes the write watchpoint identified by `id`.
+    ///
+    /// Returns `false` if no such write watchpoint is registered with the [`Store`].
+    pub fn remove_write_watchpoint(&mut self, id: WatchpointId) -> bool {
+        self.inner.remove_write_watchpoint(id)
+    }
+}
@@ -0,0 +1,71 @@
+use alloc::boxed::Box;
+use core::fmt::{self, Debug};
+use wasmi_core::TrapCode;
+
+#[cfg(doc)]
+use super::Store;
+
+/// The decision of a yield callback installed via [`Store::set_yield_callback`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum YieldDecision {
+    /// Resumes the Wasm execution immediately.
+    Continue,
+    /// Aborts the Wasm execution with the given [`TrapCode`].
+    Abort(TrapCode),
+}
+
+/// A yield callback installed via [`Store::set_yield_callback`].
+pub(super) struct YieldCallback<T>(
+    pub(super) Box<dyn FnMut(&mut T) -> YieldDecision + Send + Sync>,
+);
+impl<T> Debug for YieldCallback<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "YieldCallback(...)")
+    }
+}
+
+/// Counts the fuel of executed instructions until the yield callback is due.
+///
+/// # Note
+///
+/// Without fuel metering the fuel is counted as if fuel metering was enabled.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct YieldCounter {
+    /// The amount of fuel in between two invocations of the yield callback.
+    ///
+    /// This is `0` if no yield callback is installed.
+    interval: u64,
+    /// The amount of fuel counted since the last invocation of the yield callback.
+    elapsed: u64,
+}
+
+impl YieldCounter {
+    /// Sets the `interval` in between two invocations of the yield callback.
+    ///
+    /// This also resets the amount of counted fuel.
+    pub(super) fn set_interval(&mut self, interval: u64) {
+        self.interval = interval;
+        self.elapsed = 0;
+    }
+
+    /// Counts `delta` amount of fuel.
+    ///
+    /// Returns `true` if the yield callback is due.
+    ///
+    /// # Note
+    ///
+    /// The yield callback is due at most once per call even if
+    /// `delta` spans multiple intervals.
+    #[inline]
+    pub(crate) fn tick(&mut self, delta: u64) -> bool {
+        if self.interval == 0 {
+            return false;
+        }
+        self.elapsed = self.elapsed.saturating_add(delta);
+        if self.elapsed < self.interval {
+            return false;
+        }
+        self.elapsed %= self.interval;
+        true
+    }
+}
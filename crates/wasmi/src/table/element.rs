@@ -80,7 +80,7 @@ impl ElementSegment {
 /// With the `bulk-memory` Wasm proposal it is possible to interact
 /// with element segments at runtime. Therefore Wasm instances now have
 /// a need to have an instantiated representation of data segments.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ElementSegmentEntity {
     /// The [`ValueType`] of elements of this [`ElementSegmentEntity`].
     ty: ValueType,
@@ -89,9 +89,15 @@ pub struct ElementSegmentEntity {
     /// # Note
     ///
     /// These items are just readable after instantiation.
-    /// Using Wasm `elem.drop` simply replaces the instance
-    /// with an empty one.
+    /// Using Wasm `elem.drop` marks them as dropped but keeps them around
+    /// since they are shared with the [`Module`] anyway. This allows to restore
+    /// the dropped state of the element segment via [`Store::restore`].
+    ///
+    /// [`Module`]: crate::Module
+    /// [`Store::restore`]: crate::Store::restore
     items: Option<ElementSegmentItems>,
+    /// Is `true` if the element segment has been dropped.
+    dropped: bool,
 }
 
 impl From<&'_ module::ElementSegment> for ElementSegmentEntity {
@@ -101,6 +107,7 @@ impl From<&'_ module::ElementSegment> for ElementSegmentEntity {
             module::ElementSegmentKind::Passive | module::ElementSegmentKind::Active(_) => Self {
                 ty,
                 items: Some(segment.items_cloned()),
+                dropped: false,
             },
             module::ElementSegmentKind::Declared => Self::empty(ty),
         }
@@ -110,7 +117,11 @@ impl From<&'_ module::ElementSegment> for ElementSegmentEntity {
 impl ElementSegmentEntity {
     /// Create an empty [`ElementSegmentEntity`] representing dropped element segments.
     fn empty(ty: ValueType) -> Self {
-        Self { ty, items: None }
+        Self {
+            ty,
+            items: None,
+            dropped: true,
+        }
     }
 
     /// Returns the [`ValueType`] of elements in the [`ElementSegmentEntity`].
@@ -124,15 +135,31 @@ impl ElementSegmentEntity {
     }
 
     /// Returns the items of the [`ElementSegmentEntity`].
+    ///
+    /// Returns no items if the [`ElementSegmentEntity`] has been dropped.
     pub fn items(&self) -> &[ConstExpr] {
-        self.items
-            .as_ref()
-            .map(ElementSegmentItems::items)
-            .unwrap_or(&[])
+        match &self.items {
+            Some(items) if !self.dropped => items.items(),
+            _ => &[],
+        }
     }
 
     /// Drops the items of the [`ElementSegmentEntity`].
     pub fn drop_items(&mut self) {
-        self.items = None;
+        self.dropped = true;
+    }
+
+    /// Returns `true` if the [`ElementSegmentEntity`] has been dropped.
+    pub fn is_dropped(&self) -> bool {
+        self.dropped
+    }
+
+    /// Sets the dropped state of the [`ElementSegmentEntity`] to `dropped`.
+    ///
+    /// # Note
+    ///
+    /// Element segments of declared kind have no items even if they are no longer dropped.
+    pub fn set_dropped(&mut self, dropped: bool) {
+        self.dropped = dropped;
     }
 }
@@ -39,6 +39,13 @@ pub enum TableError {
         other: TableType,
     },
     TooManyTables,
+    /// Occurs when creating a [`TableType`] with a minimum greater than its maximum.
+    InvalidTableType {
+        /// The minimum number of elements.
+        min: u32,
+        /// The maximum number of elements.
+        max: u32,
+    },
 }
 
 impl Display for TableError {
@@ -74,6 +81,12 @@ impl Display for TableError {
             Self::TooManyTables => {
                 write!(f, "too many tables")
             }
+            Self::InvalidTableType { min, max } => {
+                write!(
+                    f,
+                    "table type minimum {min} is greater than its maximum {max}"
+                )
+            }
         }
     }
 }
@@ -0,0 +1,147 @@
+use crate::{
+    module::{ElementSegmentItems, FuncIdx},
+    Func,
+    FuncRef,
+};
+use alloc::{sync::Arc, vec::Vec};
+use wasmi_core::UntypedValue;
+
+/// An active element segment whose items have not yet been written to its table.
+#[derive(Debug)]
+struct PendingSegment {
+    /// The index of the first table element initialized by the element segment.
+    dst_index: u32,
+    /// The items of the element segment.
+    items: ElementSegmentItems,
+    /// The functions of the instance used to resolve the function indices of the `items`.
+    funcs: Arc<[Func]>,
+}
+
+impl PendingSegment {
+    /// Returns the value that the element segment writes to the table element at `index`.
+    ///
+    /// Returns `None` if the element segment does not cover `index`.
+    fn get(&self, index: u32) -> Option<UntypedValue> {
+        let offset = index.checked_sub(self.dst_index)?;
+        let item = self.items.items().get(offset as usize)?;
+        let func = item
+            .funcref()
+            .map(FuncIdx::into_u32)
+            .map(|func_index| self.funcs[func_index as usize]);
+        Some(FuncRef::new(func).into())
+    }
+}
+
+/// The lazily initialized elements of a `funcref` table.
+///
+/// # Note
+///
+/// Keeps track of all table elements that have not yet been initialized
+/// by their active element segments via a bitmap with a set bit for every
+/// pending table element.
+#[derive(Debug, Default)]
+pub struct LazyInit {
+    /// The pending active element segments in the order of their initialization.
+    segments: Vec<PendingSegment>,
+    /// The bitmap with a set bit for every pending table element.
+    pending: Vec<u64>,
+    /// The number of pending table elements.
+    len_pending: usize,
+}
+
+impl LazyInit {
+    /// The number of table elements tracked by a single bitmap word.
+    const BITS: usize = u64::BITS as usize;
+
+    /// Returns `true` if no table element is pending initialization.
+    pub fn is_empty(&self) -> bool {
+        self.len_pending == 0
+    }
+
+    /// Records the active element segment `items` to lazily initialize the table at `dst_index`.
+    ///
+    /// The function indices of the `items` are resolved via `funcs`.
+    pub fn push_segment(&mut self, dst_index: u32, items: ElementSegmentItems, funcs: Arc<[Func]>) {
+        let start = dst_index as usize;
+        let end = start + items.items().len();
+        let len_words = end.div_ceil(Self::BITS);
+        if self.pending.len() < len_words {
+            self.pending.resize(len_words, 0);
+        }
+        self.update_range(start, end, true);
+        self.segments.push(PendingSegment {
+            dst_index,
+            items,
+            funcs,
+        });
+    }
+
+    /// Returns `true` if the table element at `index` is pending initialization.
+    fn is_pending(&self, index: u32) -> bool {
+        let index = index as usize;
+        let Some(word) = self.pending.get(index / Self::BITS) else {
+            return false;
+        };
+        word & (1 << (index % Self::BITS)) != 0
+    }
+
+    /// Returns the value of the table element at `index` if it is pending initialization.
+    ///
+    /// # Note
+    ///
+    /// Later active element segments overwrite the elements of earlier ones.
+    pub fn get(&self, index: u32) -> Option<UntypedValue> {
+        if !self.is_pending(index) {
+            return None;
+        }
+        self.segments
+            .iter()
+            .rev()
+            .find_map(|segment| segment.get(index))
+    }
+
+    /// Marks the `len` table elements starting at `index` as initialized.
+    pub fn mark_initialized(&mut self, index: u32, len: u32) {
+        let start = index as usize;
+        let end = start.saturating_add(len as usize);
+        let end = end.min(self.pending.len() * Self::BITS);
+        if start >= end {
+            return;
+        }
+        self.update_range(start, end, false);
+        if self.is_empty() {
+            // Free the pending element segments since they are no longer needed.
+            self.segments = Vec::new();
+            self.pending = Vec::new();
+        }
+    }
+
+    /// Sets or clears the bits of `start..end` and updates the number of pending elements.
+    ///
+    /// # Panics
+    ///
+    /// If `start..end` is out of bounds for the bitmap.
+    fn update_range(&mut self, start: usize, end: usize, pending: bool) {
+        let mut index = start;
+        while index < end {
+            let offset = index % Self::BITS;
+            let len = (end - index).min(Self::BITS - offset);
+            let mask = match len {
+                Self::BITS => u64::MAX,
+                _ => ((1_u64 << len) - 1) << offset,
+            };
+            let word = &mut self.pending[index / Self::BITS];
+            match pending {
+                true => {
+                    self.len_pending += (mask & !*word).count_ones() as usize;
+                    *word |= mask;
+                }
+                false => {
+                    self.len_pending -= (mask & *word).count_ones() as usize;
+                    *word &= !mask;
+                }
+            }
+            index += len;
+        }
+    }
+}
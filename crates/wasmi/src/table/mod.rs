@@ -1,24 +1,27 @@
+use self::lazy::LazyInit;
 pub use self::{
     element::{ElementSegment, ElementSegmentEntity, ElementSegmentIdx},
     error::TableError,
 };
 use super::{AsContext, AsContextMut, Stored};
 use crate::{
-    error::EntityGrowError,
-    module::FuncIdx,
+    error::{EntityGrowError, GrowError},
+    module::{ElementSegmentItems, FuncIdx},
     store::{Fuel, FuelError, ResourceLimiterRef},
     value::WithType,
     Func,
     FuncRef,
+    Instance,
     Value,
 };
-use alloc::vec::Vec;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
 use core::cmp::max;
 use wasmi_arena::ArenaIndex;
 use wasmi_core::{TrapCode, UntypedValue, ValueType};
 
 mod element;
 mod error;
+mod lazy;
 
 #[cfg(test)]
 mod tests;
@@ -53,6 +56,49 @@ pub struct TableType {
     max: Option<u32>,
 }
 
+/// A builder for [`TableType`]s.
+///
+/// Constructed via [`TableType::builder`].
+#[derive(Debug, Copy, Clone)]
+pub struct TableTypeBuilder {
+    element: ValueType,
+    min: u32,
+    max: Option<u32>,
+}
+
+impl TableTypeBuilder {
+    /// Sets the minimum number of elements of the [`TableType`].
+    ///
+    /// Defaults to 0.
+    pub fn min(&mut self, min: u32) -> &mut Self {
+        self.min = min;
+        self
+    }
+
+    /// Sets the optional maximum number of elements of the [`TableType`].
+    ///
+    /// Defaults to `None`.
+    pub fn max(&mut self, max: Option<u32>) -> &mut Self {
+        self.max = max;
+        self
+    }
+
+    /// Builds the [`TableType`] from the configured values.
+    ///
+    /// # Errors
+    ///
+    /// If the minimum is greater than the maximum.
+    pub fn build(&self) -> Result<TableType, TableError> {
+        let Self { element, min, max } = *self;
+        if let Some(max) = max {
+            if min > max {
+                return Err(TableError::InvalidTableType { min, max });
+            }
+        }
+        Ok(TableType { element, min, max })
+    }
+}
+
 impl TableType {
     /// Creates a new [`TableType`].
     ///
@@ -66,6 +112,15 @@ impl TableType {
         Self { element, min, max }
     }
 
+    /// Returns a [`TableTypeBuilder`] to construct a [`TableType`] with `element` type.
+    pub fn builder(element: ValueType) -> TableTypeBuilder {
+        TableTypeBuilder {
+            element,
+            min: 0,
+            max: None,
+        }
+    }
+
     /// Returns the [`ValueType`] of elements stored in the [`Table`].
     pub fn element(&self) -> ValueType {
         self.element
@@ -145,6 +200,17 @@ impl TableType {
 pub struct TableEntity {
     ty: TableType,
     elements: Vec<UntypedValue>,
+    /// Incremented upon every mutation of the table elements.
+    ///
+    /// Used to invalidate cached `call_indirect` resolutions.
+    generation: u64,
+    /// The table elements that are pending lazy initialization if any.
+    ///
+    /// # Note
+    ///
+    /// The `elements` that are pending lazy initialization are logically
+    /// replaced by the values of their pending active element segments.
+    lazy: Option<Box<LazyInit>>,
 }
 
 impl TableEntity {
@@ -174,7 +240,12 @@ impl TableEntity {
         }
 
         let elements = vec![init.into(); ty.minimum() as usize];
-        Ok(Self { ty, elements })
+        Ok(Self {
+            ty,
+            elements,
+            generation: 0,
+            lazy: None,
+        })
     }
 
     /// Returns the resizable limits of the table.
@@ -197,6 +268,22 @@ impl TableEntity {
         self.elements.len() as u32
     }
 
+    /// Returns the current generation of the [`TableEntity`].
+    ///
+    /// # Note
+    ///
+    /// The generation changes whenever the table elements are mutated
+    /// via `table.set`, `table.fill`, `table.copy`, `table.init` or `table.grow`
+    /// as well as their host side API counterparts.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Advances the generation of the [`TableEntity`] after a mutation.
+    fn bump_generation(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
     /// Grows the table by the given amount of elements.
     ///
     /// Returns the old size of the [`Table`] upon success.
@@ -215,11 +302,52 @@ impl TableEntity {
         init: Value,
         fuel: Option<&mut Fuel>,
         limiter: &mut ResourceLimiterRef<'_>,
-    ) -> Result<u32, EntityGrowError> {
-        self.ty()
-            .matches_element_type(init.ty())
-            .map_err(|_| EntityGrowError::InvalidGrow)?;
+    ) -> Result<u32, TableError> {
+        self.ty().matches_element_type(init.ty())?;
+        let current = self.size();
+        let maximum = self.ty().maximum().unwrap_or(u32::MAX);
         self.grow_untyped(delta, init.into(), fuel, limiter)
+            .map_err(|_| TableError::GrowOutOfBounds {
+                maximum,
+                current,
+                delta,
+            })
+    }
+
+    /// Grows the table to at least `min_len` elements.
+    ///
+    /// # Note
+    ///
+    /// This does nothing if the table already has at least `min_len` elements
+    /// and otherwise grows it via a single [`TableEntity::grow_untyped`].
+    /// The maximum of the [`TableType`] is checked before the [`ResourceLimiter`]
+    /// is consulted so that the source of a failure is reported accurately.
+    ///
+    /// The newly added elements are initialized to `null`.
+    ///
+    /// [`ResourceLimiter`]: crate::ResourceLimiter
+    ///
+    /// # Errors
+    ///
+    /// If the growth fails. In this case the table is left unchanged.
+    pub fn grow_to(
+        &mut self,
+        min_len: u32,
+        limiter: &mut ResourceLimiterRef<'_>,
+    ) -> Result<(), GrowError> {
+        let delta = match min_len.checked_sub(self.size()) {
+            None | Some(0) => return Ok(()),
+            Some(delta) => delta,
+        };
+        if min_len > self.ty().maximum().unwrap_or(u32::MAX) {
+            return Err(GrowError::Maximum);
+        }
+        let null = Value::default(self.ty().element());
+        match self.grow_untyped(delta, null.into(), None, limiter) {
+            Ok(_) => Ok(()),
+            Err(EntityGrowError::InvalidGrow(error)) => Err(error),
+            Err(EntityGrowError::TrapCode(_)) => Err(GrowError::Limiter),
+        }
     }
 
     /// Grows the table by the given amount of elements.
@@ -249,37 +377,42 @@ impl TableEntity {
         if let Some(limiter) = limiter.as_resource_limiter() {
             match limiter.table_growing(current, desired.unwrap_or(u32::MAX), maximum) {
                 Ok(true) => (),
-                Ok(false) => return Err(EntityGrowError::InvalidGrow),
+                Ok(false) => return Err(EntityGrowError::InvalidGrow(GrowError::Limiter)),
                 Err(_) => return Err(EntityGrowError::TrapCode(TrapCode::GrowthOperationLimited)),
             }
         }
 
         let maximum = maximum.unwrap_or(u32::MAX);
-        let notify_limiter =
-            |limiter: &mut ResourceLimiterRef<'_>| -> Result<u32, EntityGrowError> {
-                if let Some(limiter) = limiter.as_resource_limiter() {
-                    limiter.table_grow_failed(&TableError::GrowOutOfBounds {
-                        maximum,
-                        current,
-                        delta,
-                    });
-                }
-                Err(EntityGrowError::InvalidGrow)
-            };
+        let notify_limiter = |limiter: &mut ResourceLimiterRef<'_>,
+                              error: GrowError|
+         -> Result<u32, EntityGrowError> {
+            if let Some(limiter) = limiter.as_resource_limiter() {
+                limiter.table_grow_failed(&TableError::GrowOutOfBounds {
+                    maximum,
+                    current,
+                    delta,
+                });
+            }
+            Err(EntityGrowError::InvalidGrow(error))
+        };
 
         let Some(desired) = desired else {
-            return notify_limiter(limiter);
+            return notify_limiter(limiter, GrowError::Maximum);
         };
         if desired > maximum {
-            return notify_limiter(limiter);
+            return notify_limiter(limiter, GrowError::Maximum);
+        }
+        if self.elements.try_reserve_exact(delta as usize).is_err() {
+            return notify_limiter(limiter, GrowError::Allocation);
         }
         if let Some(fuel) = fuel {
             match fuel.consume_fuel(|costs| costs.fuel_for_copies(u64::from(delta))) {
                 Ok(_) | Err(FuelError::FuelMeteringDisabled) => {}
-                Err(FuelError::OutOfFuel) => return notify_limiter(limiter),
+                Err(FuelError::OutOfFuel) => return notify_limiter(limiter, GrowError::OutOfFuel),
             }
         }
         self.elements.resize(desired as usize, init);
+        self.bump_generation();
         Ok(current)
     }
 
@@ -305,7 +438,106 @@ impl TableEntity {
     /// This is a more efficient version of [`Table::get`] for
     /// internal use only.
     pub fn get_untyped(&self, index: u32) -> Option<UntypedValue> {
-        self.elements.get(index as usize).copied()
+        let value = self.elements.get(index as usize).copied()?;
+        if let Some(lazy) = &self.lazy {
+            if let Some(value) = lazy.get(index) {
+                return Some(value);
+            }
+        }
+        Some(value)
+    }
+
+    /// Returns the untyped [`Table`] element value at `index`.
+    ///
+    /// Initializes the [`Table`] element at `index` if it is pending lazy initialization.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn get_untyped_or_init(&mut self, index: u32) -> Option<UntypedValue> {
+        let element = self.elements.get_mut(index as usize)?;
+        let Some(lazy) = self.lazy.as_deref_mut() else {
+            return Some(*element);
+        };
+        if let Some(value) = lazy.get(index) {
+            *element = value;
+            self.mark_initialized(index, 1);
+            return Some(value);
+        }
+        Some(*element)
+    }
+
+    /// Lazily initializes `len` elements of the [`Table`] at `dst_index` with the `items`.
+    ///
+    /// Uses the `funcs` of the instance to resolve the function indices of the `items`.
+    ///
+    /// # Note
+    ///
+    /// The [`Table`] elements are initialized upon their first access.
+    /// The observable behavior is equal to [`TableEntity::init`] though.
+    ///
+    /// # Errors
+    ///
+    /// If the `items` are out of bounds for the [`Table`] at `dst_index`.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Table`] element type is not [`ValueType::FuncRef`].
+    pub fn init_lazy(
+        &mut self,
+        dst_index: u32,
+        items: ElementSegmentItems,
+        funcs: Arc<[Func]>,
+    ) -> Result<(), TrapCode> {
+        assert_eq!(
+            self.ty().element(),
+            ValueType::FuncRef,
+            "lazy table initialization only works on funcref tables"
+        );
+        let len = u32::try_from(items.items().len()).map_err(|_| TrapCode::TableOutOfBounds)?;
+        dst_index
+            .checked_add(len)
+            .filter(|&max_index| max_index <= self.size())
+            .ok_or(TrapCode::TableOutOfBounds)?;
+        if len == 0 {
+            return Ok(());
+        }
+        self.lazy
+            .get_or_insert_with(Box::default)
+            .push_segment(dst_index, items, funcs);
+        self.bump_generation();
+        Ok(())
+    }
+
+    /// Marks the `len` [`Table`] elements at `index` as no longer pending lazy initialization.
+    ///
+    /// # Note
+    ///
+    /// This must be called whenever [`Table`] elements are written to.
+    fn mark_initialized(&mut self, index: u32, len: u32) {
+        let Some(lazy) = self.lazy.as_deref_mut() else {
+            return;
+        };
+        lazy.mark_initialized(index, len);
+        if lazy.is_empty() {
+            self.lazy = None;
+        }
+    }
+
+    /// Initializes the `len` [`Table`] elements at `index` that are pending lazy initialization.
+    ///
+    /// # Note
+    ///
+    /// The range `index..index+len` must be in bounds for the [`Table`].
+    fn init_range(&mut self, index: u32, len: u32) {
+        let Some(lazy) = self.lazy.as_deref() else {
+            return;
+        };
+        let elements = &mut self.elements[index as usize..][..len as usize];
+        for (element_index, element) in (index..).zip(elements) {
+            if let Some(value) = lazy.get(element_index) {
+                *element = value;
+            }
+        }
+        self.mark_initialized(index, len);
     }
 
     /// Sets the [`Value`] of this [`Table`] at `index`.
@@ -334,9 +566,67 @@ impl TableEntity {
                     offset: index,
                 })?;
         *untyped = value;
+        self.mark_initialized(index, 1);
+        self.bump_generation();
+        Ok(())
+    }
+
+    /// Sets the elements of the [`Table`] starting at `dst_index` to `values`.
+    ///
+    /// # Note
+    ///
+    /// Either all `values` are written or none of them.
+    ///
+    /// # Errors
+    ///
+    /// - If any of the `values` does not match the [`Table`] element type.
+    /// - If the range of elements to be set is out of bounds.
+    pub fn set_many(&mut self, dst_index: u32, values: &[Value]) -> Result<(), TableError> {
+        let table_type = self.ty();
+        for value in values {
+            table_type.matches_element_type(value.ty())?;
+        }
+        let current = self.size();
+        let dst_items = self
+            .elements
+            .get_mut(dst_index as usize..)
+            .and_then(|items| items.get_mut(..values.len()))
+            .ok_or(TableError::AccessOutOfBounds {
+                current,
+                offset: dst_index,
+            })?;
+        for (dst, value) in dst_items.iter_mut().zip(values) {
+            *dst = value.clone().into();
+        }
+        self.mark_initialized(dst_index, values.len() as u32);
+        self.bump_generation();
         Ok(())
     }
 
+    /// Restores all elements of the [`Table`] to `elements`.
+    ///
+    /// # Note
+    ///
+    /// This may also shrink the [`Table`] since it only restores a previously observed state.
+    ///
+    /// # Panics
+    ///
+    /// If any of the `elements` does not match the [`Table`] element type.
+    pub fn restore(&mut self, elements: impl IntoIterator<Item = Value>) {
+        let element_ty = self.ty().element();
+        self.lazy = None;
+        self.elements.clear();
+        self.elements.extend(elements.into_iter().map(|element| {
+            assert_eq!(
+                element.ty(),
+                element_ty,
+                "restored table element type mismatch"
+            );
+            UntypedValue::from(element)
+        }));
+        self.bump_generation();
+    }
+
     /// Initialize `len` elements from `src_element[src_index..]` into
     /// `dst_table[dst_index..]`.
     ///
@@ -410,6 +700,8 @@ impl TableEntity {
             }
             _ => panic!("table.init currently only works on reftypes"),
         };
+        self.mark_initialized(dst_index as u32, len as u32);
+        self.bump_generation();
         Ok(())
     }
 
@@ -446,8 +738,17 @@ impl TableEntity {
         if let Some(fuel) = fuel {
             fuel.consume_fuel_if(|costs| costs.fuel_for_copies(len as u64))?;
         }
-        // Finally, copy elements in-place for the table.
-        dst_items.copy_from_slice(src_items);
+        // Finally, copy the logical elements of the source table.
+        match src_table.lazy.as_deref() {
+            None => dst_items.copy_from_slice(src_items),
+            Some(lazy) => {
+                for ((src_index, dst), src) in (src_index as u32..).zip(dst_items).zip(src_items) {
+                    *dst = lazy.get(src_index).unwrap_or(*src);
+                }
+            }
+        }
+        dst_table.mark_initialized(dst_index as u32, len as u32);
+        dst_table.bump_generation();
         Ok(())
     }
 
@@ -476,9 +777,13 @@ impl TableEntity {
         if let Some(fuel) = fuel {
             fuel.consume_fuel_if(|costs| costs.fuel_for_copies(len as u64))?;
         }
+        // Copying requires the pending source elements to be initialized.
+        self.init_range(src_index as u32, len as u32);
         // Finally, copy elements in-place for the table.
         self.elements
             .copy_within(src_index..src_index.wrapping_add(len), dst_index);
+        self.mark_initialized(dst_index as u32, len as u32);
+        self.bump_generation();
         Ok(())
     }
 
@@ -541,6 +846,8 @@ impl TableEntity {
             fuel.consume_fuel_if(|costs| costs.fuel_for_copies(len as u64))?;
         }
         dst.fill(val);
+        self.mark_initialized(dst_index as u32, len as u32);
+        self.bump_generation();
         Ok(())
     }
 }
@@ -585,6 +892,19 @@ impl Table {
         ctx.as_context().store.inner.resolve_table(self).ty()
     }
 
+    /// Returns the instances that define or import the [`Table`] in the order of their instantiation.
+    ///
+    /// Read [`Memory::instances`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    ///
+    /// [`Memory::instances`]: crate::Memory::instances
+    pub fn instances(&self, ctx: impl AsContext) -> Vec<Instance> {
+        ctx.as_context().store.inner.table_instances(self)
+    }
+
     /// Returns the dynamic [`TableType`] of the [`Table`].
     ///
     /// # Note
@@ -638,16 +958,40 @@ impl Table {
             .as_context_mut()
             .store
             .store_inner_and_resource_limiter_ref();
-        let table = inner.resolve_table_mut(self);
-        let current = table.size();
-        let maximum = table.ty().maximum().unwrap_or(u32::MAX);
-        table
+        inner
+            .resolve_table_mut(self)
             .grow(delta, init, None, &mut limiter)
-            .map_err(|_| TableError::GrowOutOfBounds {
-                maximum,
-                current,
-                delta,
-            })
+    }
+
+    /// Grows the table to at least `min_len` elements.
+    ///
+    /// Does nothing if the table already has at least `min_len` elements.
+    ///
+    /// # Note
+    ///
+    /// - The newly added elements are initialized to `null`.
+    /// - The table is grown at once the same as via the `table.grow` instruction
+    ///   and respects the [`ResourceLimiter`] of the [`Store`].
+    ///
+    /// # Errors
+    ///
+    /// If the table cannot be grown to `min_len` elements.
+    /// In this case the table is left unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    ///
+    /// [`ResourceLimiter`]: crate::ResourceLimiter
+    /// [`Store`]: crate::Store
+    pub fn grow_to(&self, mut ctx: impl AsContextMut, min_len: u32) -> Result<(), GrowError> {
+        let (inner, mut limiter) = ctx
+            .as_context_mut()
+            .store
+            .store_inner_and_resource_limiter_ref();
+        inner
+            .resolve_table_mut(self)
+            .grow_to(min_len, &mut limiter)
     }
 
     /// Returns the [`Table`] element value at `index`.
@@ -684,6 +1028,35 @@ impl Table {
             .set(index, value)
     }
 
+    /// Sets the elements of this [`Table`] starting at `index` to `values`.
+    ///
+    /// # Note
+    ///
+    /// - This is more efficient than calling [`Table::set`] for every element since
+    ///   the [`Table`] is resolved and bounds checked only once for all `values`.
+    /// - Either all `values` are written or none of them.
+    ///
+    /// # Errors
+    ///
+    /// - If the range of elements to be set is out of bounds.
+    /// - If any of the `values` does not match the [`Table`] element type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub fn set_many(
+        &self,
+        mut ctx: impl AsContextMut,
+        index: u32,
+        values: &[Value],
+    ) -> Result<(), TableError> {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_table_mut(self)
+            .set_many(index, values)
+    }
+
     /// Returns `true` if `lhs` and `rhs` [`Table`] refer to the same entity.
     ///
     /// # Note
@@ -741,8 +1114,70 @@ impl Table {
         }
     }
 
+    /// Copy `len` elements from `src_table[src_index..]` into `dst_table[dst_index..]`.
+    ///
+    /// # Note
+    ///
+    /// This is the same as [`Table::copy`] and follows the semantics of the Wasm
+    /// `table.copy` instruction, i.e. `dst_table` and `src_table` may be the same
+    /// [`Table`] and no element is copied if any of the ranges is out of bounds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range is out of bounds of either the source or
+    /// destination tables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` does not own either `dst_table` or `src_table`.
+    pub fn copy_between(
+        store: impl AsContextMut,
+        dst_table: &Table,
+        dst_index: u32,
+        src_table: &Table,
+        src_index: u32,
+        len: u32,
+    ) -> Result<(), TableError> {
+        Self::copy(store, dst_table, dst_index, src_table, src_index, len)
+    }
+
+    /// Copy `len` elements from `self[src_index..]` into `self[dst_index..]`.
+    ///
+    /// # Note
+    ///
+    /// This follows the semantics of the Wasm `table.copy` instruction, i.e. the
+    /// source and destination ranges may overlap and no element is copied if any
+    /// of the ranges is out of bounds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the range is out of bounds of the [`Table`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ctx` does not own this [`Table`].
+    pub fn copy_within(
+        &self,
+        mut ctx: impl AsContextMut,
+        dst_index: u32,
+        src_index: u32,
+        len: u32,
+    ) -> Result<(), TableError> {
+        ctx.as_context_mut()
+            .store
+            .inner
+            .resolve_table_mut(self)
+            .copy_within(dst_index, src_index, len, None)
+            .map_err(|_| TableError::CopyOutOfBounds)
+    }
+
     /// Fill `table[dst..(dst + len)]` with the given value.
     ///
+    /// # Note
+    ///
+    /// This follows the semantics of the Wasm `table.fill` instruction,
+    /// i.e. no element is written if the region is out of bounds.
+    ///
     /// # Errors
     ///
     /// - If `val` has a type mismatch with the element type of the [`Table`].
@@ -1,4 +1,5 @@
 use crate::{ExternRef, Func, FuncRef};
+use core::{fmt, fmt::Display};
 use wasmi_core::{UntypedValue, ValueType, F32, F64};
 
 /// Untyped instances that allow to be typed.
@@ -38,6 +39,35 @@ impl From<Value> for UntypedValue {
     }
 }
 
+/// An error that may occur upon operating on [`Value`]s.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValueError {
+    /// Occurs when a [`Value`] cannot be coerced to the expected [`ValueType`].
+    TypeMismatch {
+        /// The expected type of the [`Value`].
+        expected: ValueType,
+        /// The type of the [`Value`] that cannot be coerced to the `expected` type.
+        encountered: ValueType,
+    },
+}
+
+impl Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TypeMismatch {
+                expected,
+                encountered,
+            } => {
+                write!(
+                    f,
+                    "cannot coerce value of type {encountered:?} to type {expected:?}",
+                )
+            }
+        }
+    }
+}
+
 /// Runtime representation of a value.
 ///
 /// Wasm code manipulate values of the four basic value types:
@@ -75,6 +105,50 @@ impl Value {
         }
     }
 
+    /// Creates a `null` [`FuncRef`] value.
+    #[inline]
+    pub fn null_func() -> Self {
+        Self::FuncRef(FuncRef::null())
+    }
+
+    /// Creates a `null` [`ExternRef`] value.
+    #[inline]
+    pub fn null_extern() -> Self {
+        Self::ExternRef(ExternRef::null())
+    }
+
+    /// Returns `true` if `self` is a `null` [`FuncRef`] or [`ExternRef`] value.
+    pub fn is_null(&self) -> bool {
+        match self {
+            Self::FuncRef(funcref) => funcref.is_null(),
+            Self::ExternRef(externref) => externref.is_null(),
+            _ => false,
+        }
+    }
+
+    /// Coerces `self` to a [`Value`] of type `ty`.
+    ///
+    /// # Note
+    ///
+    /// - Returns `self` if it already is of type `ty`.
+    /// - A `null` reference value is coerced to the `null` value of any reference type.
+    ///
+    /// # Errors
+    ///
+    /// If `self` cannot be coerced to `ty`.
+    pub fn with_type_coercion(self, ty: ValueType) -> Result<Self, ValueError> {
+        if self.ty() == ty {
+            return Ok(self);
+        }
+        if self.is_null() && ty.is_ref() {
+            return Ok(Self::default(ty));
+        }
+        Err(ValueError::TypeMismatch {
+            expected: ty,
+            encountered: self.ty(),
+        })
+    }
+
     /// Get variable type for this value.
     #[inline]
     pub fn ty(&self) -> ValueType {
@@ -0,0 +1,150 @@
+//! Tests for the address maps of translated functions and the Wasm binary offsets of traps.
+
+use wasmi::{core::TrapCode, CompilationMode, Config, Engine, Error, Linker, Module, Store};
+
+/// A Wasm binary with a known structure.
+///
+/// - Function `a` calls function `b` which traps via `unreachable`.
+/// - The `unreachable` Wasm operator is located at [`UNREACHABLE_OFFSET`].
+#[rustfmt::skip]
+const WASM: &[u8] = &[
+    // Wasm binary header
+    0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00,
+    // Type section: (type (func (result i32)))
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7F,
+    // Function section: 2 functions of type 0
+    0x03, 0x03, 0x02, 0x00, 0x00,
+    // Export section: (export "a" (func 0)) (export "b" (func 1))
+    0x07, 0x09, 0x02, 0x01, 0x61, 0x00, 0x00, 0x01, 0x62, 0x00, 0x01,
+    // Code section: 2 function bodies
+    0x0A, 0x0D, 0x02,
+    // Function `a` at offset 34: (call 1)
+    0x04, 0x00, 0x10, 0x01, 0x0B,
+    // Function `b` at offset 39: (i32.const 1) (drop) (unreachable)
+    0x06, 0x00, 0x41, 0x01, 0x1A, 0x00, 0x0B,
+];
+
+/// The offset of the `unreachable` Wasm operator within [`WASM`].
+const UNREACHABLE_OFFSET: u32 = 44;
+
+/// Compiles [`WASM`] with `config` and returns the error of calling its export `name`.
+fn call_trap(config: &Config, name: &str) -> Error {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let error = instance
+        .get_typed_func::<(), i32>(&store, name)
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    error
+}
+
+/// Returns a [`Config`] with address map generation enabled.
+fn config(mode: CompilationMode) -> Config {
+    let mut config = Config::default();
+    config.generate_address_map(true);
+    config.compilation_mode(mode);
+    config
+}
+
+const MODES: [CompilationMode; 3] = [
+    CompilationMode::Eager,
+    CompilationMode::LazyTranslation,
+    CompilationMode::Lazy,
+];
+
+#[test]
+fn trap_wasm_offset() {
+    assert_eq!(WASM[UNREACHABLE_OFFSET as usize], 0x00);
+    for mode in MODES {
+        for name in ["a", "b"] {
+            let error = call_trap(&config(mode), name);
+            assert_eq!(
+                error.trap_wasm_offset(),
+                Some(UNREACHABLE_OFFSET),
+                "{mode:?}: {name}"
+            );
+        }
+    }
+}
+
+#[test]
+fn trap_wasm_offset_optimized_with_fuel() {
+    let mut config = config(CompilationMode::Eager);
+    config.optimization_level(1);
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, WASM).unwrap();
+    let mut store = Store::new(&engine, ());
+    store.add_fuel(1_000).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let error = instance
+        .get_typed_func::<(), i32>(&store, "a")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert_eq!(error.trap_wasm_offset(), Some(UNREACHABLE_OFFSET));
+}
+
+#[test]
+fn trap_wasm_offset_disabled() {
+    let error = call_trap(&Config::default(), "a");
+    assert_eq!(error.trap_wasm_offset(), None);
+}
+
+#[test]
+fn address_map() {
+    for mode in MODES {
+        let engine = Engine::new(&config(mode));
+        let module = Module::new(&engine, WASM).unwrap();
+        let func = module.get_compiled_func(1).unwrap();
+        let address_map = engine.address_map(func, <[_]>::to_vec).unwrap();
+        assert!(!address_map.is_empty(), "{mode:?}");
+        assert_eq!(address_map[0].0, 0, "{mode:?}");
+        assert!(
+            address_map.windows(2).all(|w| w[0].0 < w[1].0),
+            "{mode:?}: {address_map:?}"
+        );
+        // All offsets are within the function body of `b`.
+        assert!(
+            address_map
+                .iter()
+                .all(|&(_, offset)| (39..46).contains(&offset)),
+            "{mode:?}: {address_map:?}"
+        );
+        assert!(
+            address_map
+                .iter()
+                .any(|&(_, offset)| offset == UNREACHABLE_OFFSET),
+            "{mode:?}: {address_map:?}"
+        );
+    }
+}
+
+#[test]
+fn address_map_disabled() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    let func = module.get_compiled_func(1).unwrap();
+    assert!(engine.address_map(func, <[_]>::is_empty).unwrap());
+}
+
+#[test]
+fn get_compiled_func() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, WASM).unwrap();
+    assert!(module.get_compiled_func(0).is_some());
+    assert!(module.get_compiled_func(1).is_some());
+    assert!(module.get_compiled_func(2).is_none());
+}
@@ -0,0 +1,146 @@
+//! Tests for compiling Wasm modules in the background via [`Engine::compile_in_background`].
+
+use std::{cell::RefCell, fmt::Write as _, rc::Rc};
+use wasmi::{
+    CompilationHandle,
+    CompilationJob,
+    CompilationMode,
+    CompilationStatus,
+    Config,
+    Engine,
+    Linker,
+    Module,
+    Store,
+};
+
+/// The number of functions in the module returned by [`large_wasm`].
+const LEN_FUNCS: i32 = 2000;
+
+/// Returns a Wasm module with [`LEN_FUNCS`] functions where function `n` returns `n`.
+///
+/// The exported `sum` function calls the first and last function and returns their sum.
+fn large_wasm() -> Vec<u8> {
+    let mut wat = String::from("(module\n");
+    for n in 0..LEN_FUNCS {
+        writeln!(
+            wat,
+            "(func $f{n} (result i32) (i32.add (i32.const {n}) (i32.const 0)))"
+        )
+        .unwrap();
+    }
+    let last = LEN_FUNCS - 1;
+    writeln!(
+        wat,
+        "(func (export \"sum\") (result i32) (i32.add (call $f0) (call $f{last})))"
+    )
+    .unwrap();
+    wat.push(')');
+    wat::parse_str(wat).unwrap()
+}
+
+/// Returns an [`Engine`] that eagerly compiles all functions.
+fn eager_engine() -> Engine {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Eager);
+    Engine::new(&config)
+}
+
+/// Instantiates `module` and calls its exported `sum` function.
+fn call_sum(module: &Module) -> i32 {
+    let mut store = Store::new(module.engine(), ());
+    let instance = <Linker<()>>::new(module.engine())
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<(), i32>(&store, "sum")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap()
+}
+
+/// Compiles `wasm` in the background returning its [`CompilationHandle`] and the deferred job.
+fn deferred(engine: &Engine, wasm: Vec<u8>) -> (CompilationHandle, CompilationJob) {
+    let job = Rc::new(RefCell::new(None));
+    let handle = engine.compile_in_background_with(wasm, |spawned| {
+        *job.borrow_mut() = Some(spawned);
+    });
+    let job = job.borrow_mut().take().unwrap();
+    (handle, job)
+}
+
+#[test]
+fn poll_until_done() {
+    let engine = eager_engine();
+    let mut handle = engine.compile_in_background(large_wasm());
+    let mut last_progress = 0.0;
+    let module = loop {
+        match handle.poll() {
+            CompilationStatus::Pending(progress) => {
+                assert!((last_progress..=1.0).contains(&progress));
+                last_progress = progress;
+                std::thread::yield_now();
+            }
+            CompilationStatus::Done(module) => break module,
+            CompilationStatus::Failed(error) => panic!("unexpected compilation error: {error}"),
+        }
+    };
+    assert_eq!(call_sum(&module), LEN_FUNCS - 1);
+    // The module is the same as if compiled synchronously.
+    let expected = Module::new(&engine, &large_wasm()[..]).unwrap();
+    let names = |module: &Module| -> Vec<String> {
+        module.exports().map(|export| export.name().into()).collect()
+    };
+    assert_eq!(names(&module), names(&expected));
+    assert_eq!(
+        module.code_size_estimate(),
+        expected.code_size_estimate()
+    );
+}
+
+#[test]
+fn wait() {
+    let engine = eager_engine();
+    let module = engine.compile_in_background(large_wasm()).wait().unwrap();
+    assert_eq!(call_sum(&module), LEN_FUNCS - 1);
+}
+
+#[test]
+fn progress() {
+    let engine = eager_engine();
+    let (mut handle, job) = deferred(&engine, large_wasm());
+    assert!(matches!(handle.poll(), CompilationStatus::Pending(progress) if progress == 0.0));
+    job();
+    assert!(matches!(handle.poll(), CompilationStatus::Done(_)));
+}
+
+#[test]
+fn failed() {
+    let engine = Engine::default();
+    let mut handle = engine.compile_in_background(b"not a wasm binary".to_vec());
+    let error = loop {
+        match handle.poll() {
+            CompilationStatus::Pending(_) => std::thread::yield_now(),
+            CompilationStatus::Done(_) => panic!("unexpectedly compiled an invalid Wasm binary"),
+            CompilationStatus::Failed(error) => break error,
+        }
+    };
+    assert!(error.to_string().contains("magic header"), "{error}");
+}
+
+#[test]
+fn drop_cancels() {
+    let engine = eager_engine();
+    let (handle, job) = deferred(&engine, large_wasm());
+    drop(handle);
+    job();
+    // No function body has been translated after cancellation.
+    assert_eq!(engine.memory_usage().code_bytes, 0);
+    // Control: without cancellation all function bodies are translated.
+    let (handle, job) = deferred(&engine, large_wasm());
+    job();
+    let module = handle.wait().unwrap();
+    assert_ne!(engine.memory_usage().code_bytes, 0);
+    assert_eq!(call_sum(&module), LEN_FUNCS - 1);
+}
@@ -0,0 +1,397 @@
+//! Tests for batched host function calls via the `wasmi.batch_call` builtin function.
+
+use assert_matches::assert_matches;
+use wasmi::{
+    core::{TrapCode, F64},
+    errors::{BatchCallError, ErrorKind},
+    Caller,
+    Config,
+    Engine,
+    Error,
+    ExternRef,
+    Func,
+    Instance,
+    Linker,
+    Memory,
+    Module,
+    Store,
+    TypedFunc,
+    Value,
+};
+
+/// A Wasm module that forwards its exported `batch` function to the `wasmi.batch_call` builtin.
+///
+/// The import indices are:
+///
+/// - `0`: `env.add` of type `[i32 i32] -> [i32]`
+/// - `1`: `env.mul` of type `[i64 i64] -> [i64]`
+/// - `2`: `env.neg` of type `[f64] -> [f64]`
+/// - `3`: `env.log` of type `[i32] -> []` which logs its parameter
+/// - `4`: `env.fail` of type `[i32] -> []` which fails with its parameter as exit status
+/// - `5`: `env.drop` of type `[externref] -> []`
+/// - `6`: `wasmi.batch_call`
+///
+/// The exported `batch` function has the function index `7`.
+const WASM: &str = r#"
+    (module
+        (import "env" "add" (func $add (param i32 i32) (result i32)))
+        (import "env" "mul" (func $mul (param i64 i64) (result i64)))
+        (import "env" "neg" (func $neg (param f64) (result f64)))
+        (import "env" "log" (func $log (param i32)))
+        (import "env" "fail" (func $fail (param i32)))
+        (import "env" "drop" (func $drop (param externref)))
+        (import "wasmi" "batch_call" (func $batch_call (param i32 i32 i32)))
+        (memory (export "memory") 1)
+        (func (export "batch") (param $records i32) (param $len i32) (param $results i32)
+            (call $batch_call (local.get $records) (local.get $len) (local.get $results))
+        )
+        (export "add" (func $add))
+        (export "mul" (func $mul))
+        (export "neg" (func $neg))
+        (export "log" (func $log))
+    )
+"#;
+
+/// The address of the records within the linear memory of [`WASM`].
+const RECORDS: u32 = 0;
+
+/// The address of the results within the linear memory of [`WASM`].
+const RESULTS: u32 = 0x8000;
+
+/// A host function call of a batch.
+#[derive(Debug, Copy, Clone)]
+enum Call {
+    Add(i32, i32),
+    Mul(i64, i64),
+    Neg(f64),
+    Log(i32),
+    Fail(i32),
+}
+
+impl Call {
+    /// Returns the import index and the parameters of the [`Call`].
+    fn params(&self) -> (u32, Vec<Value>) {
+        match *self {
+            Self::Add(lhs, rhs) => (0, vec![Value::I32(lhs), Value::I32(rhs)]),
+            Self::Mul(lhs, rhs) => (1, vec![Value::I64(lhs), Value::I64(rhs)]),
+            Self::Neg(value) => (2, vec![Value::F64(value.into())]),
+            Self::Log(value) => (3, vec![Value::I32(value)]),
+            Self::Fail(status) => (4, vec![Value::I32(status)]),
+        }
+    }
+
+    /// Returns the name of the exported host function of the [`Call`].
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Add(..) => "add",
+            Self::Mul(..) => "mul",
+            Self::Neg(..) => "neg",
+            Self::Log(..) => "log",
+            Self::Fail(..) => panic!("unexpected call to the unexported env.fail"),
+        }
+    }
+
+    /// Returns an empty results buffer for the [`Call`].
+    fn results(&self) -> Vec<Value> {
+        match self {
+            Self::Add(..) => vec![Value::I32(0)],
+            Self::Mul(..) => vec![Value::I64(0)],
+            Self::Neg(..) => vec![Value::F64(0.0.into())],
+            Self::Log(..) | Self::Fail(..) => vec![],
+        }
+    }
+}
+
+/// Returns the raw bits of `value`.
+fn to_bits(value: &Value) -> u64 {
+    match value {
+        Value::I32(value) => *value as u32 as u64,
+        Value::I64(value) => *value as u64,
+        Value::F64(value) => value.to_bits(),
+        _ => panic!("unexpected value: {value:?}"),
+    }
+}
+
+/// Encodes the records of `calls` for the `wasmi.batch_call` builtin function.
+fn encode(calls: &[Call]) -> Vec<u8> {
+    let mut records = Vec::new();
+    for call in calls {
+        let (import_index, params) = call.params();
+        records.extend(import_index.to_le_bytes());
+        for param in &params {
+            records.extend(to_bits(param).to_le_bytes());
+        }
+    }
+    records
+}
+
+/// The instantiated [`WASM`] module.
+struct Batch {
+    store: Store<Vec<i32>>,
+    instance: Instance,
+    memory: Memory,
+    batch: TypedFunc<(u32, u32, u32), ()>,
+}
+
+impl Batch {
+    /// Instantiates [`WASM`] with the builtin functions of a [`Linker`] using `config`.
+    fn new(config: &mut Config) -> Self {
+        config.builtin_batch_call(true);
+        let engine = Engine::new(config);
+        let mut store = Store::new(&engine, Vec::new());
+        let wasm = wat::parse_str(WASM).unwrap();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut linker = <Linker<Vec<i32>>>::new(&engine);
+        linker.define_builtins().unwrap();
+        linker
+            .func_wrap("env", "add", |lhs: i32, rhs: i32| lhs.wrapping_add(rhs))
+            .unwrap()
+            .func_wrap("env", "mul", |lhs: i64, rhs: i64| lhs.wrapping_mul(rhs))
+            .unwrap()
+            .func_wrap("env", "neg", |value: F64| -value)
+            .unwrap()
+            .func_wrap("env", "log", |mut caller: Caller<Vec<i32>>, value: i32| {
+                caller.data_mut().push(value)
+            })
+            .unwrap()
+            .func_wrap("env", "fail", |status: i32| -> Result<(), Error> {
+                Err(Error::i32_exit(status))
+            })
+            .unwrap()
+            .func_wrap("env", "drop", |_: ExternRef| {})
+            .unwrap();
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let memory = instance.get_memory(&store, "memory").unwrap();
+        let batch = instance
+            .get_typed_func::<(u32, u32, u32), ()>(&store, "batch")
+            .unwrap();
+        Self {
+            store,
+            instance,
+            memory,
+            batch,
+        }
+    }
+
+    /// Dispatches `records` describing `len` calls via the `wasmi.batch_call` builtin function.
+    fn call_raw(&mut self, records: &[u8], len: u32, results: u32) -> Result<(), Error> {
+        self.memory
+            .write(&mut self.store, RECORDS as usize, records)
+            .unwrap();
+        self.batch.call(&mut self.store, (RECORDS, len, results))
+    }
+
+    /// Dispatches `calls` via the `wasmi.batch_call` builtin function and returns their results.
+    fn call(&mut self, calls: &[Call]) -> Result<Vec<u64>, Error> {
+        self.call_raw(&encode(calls), calls.len() as u32, RESULTS)?;
+        let len_results = calls.iter().map(|call| call.results().len()).sum::<usize>();
+        let mut results = vec![0_u8; len_results * 8];
+        self.memory
+            .read(&self.store, RESULTS as usize, &mut results)
+            .unwrap();
+        Ok(results
+            .chunks_exact(8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Calls each of the `calls` individually and returns their results.
+    fn call_each(&mut self, calls: &[Call]) -> Vec<u64> {
+        let mut results = Vec::new();
+        for call in calls {
+            let func: Func = self.instance.get_func(&self.store, call.name()).unwrap();
+            let (_, params) = call.params();
+            let mut outputs = call.results();
+            func.call(&mut self.store, &params, &mut outputs).unwrap();
+            results.extend(outputs.iter().map(to_bits));
+        }
+        results
+    }
+}
+
+/// Returns `len` pseudo random calls to the `add`, `mul`, `neg` and `log` host functions.
+fn mixed_calls(len: usize) -> Vec<Call> {
+    let mut state = 0x2545_f491_u64;
+    (0..len)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let value = (state >> 32) as i32;
+            match state % 4 {
+                0 => Call::Add(value, value >> 3),
+                1 => Call::Mul(i64::from(value), state as i64),
+                2 => Call::Neg(f64::from(value) / 7.0),
+                _ => Call::Log(value),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn batch_matches_individual_calls() {
+    let calls = mixed_calls(1000);
+    let mut batched = Batch::new(&mut Config::default());
+    let mut individual = Batch::new(&mut Config::default());
+    let results = batched.call(&calls).unwrap();
+    assert_eq!(results, individual.call_each(&calls));
+    assert_eq!(batched.store.data(), individual.store.data());
+    assert_eq!(
+        batched.store.data().len(),
+        calls
+            .iter()
+            .filter(|call| matches!(call, Call::Log(_)))
+            .count()
+    );
+}
+
+#[test]
+fn empty_batch() {
+    let mut batch = Batch::new(&mut Config::default());
+    assert_eq!(batch.call(&[]).unwrap(), []);
+    assert!(batch.store.data().is_empty());
+}
+
+#[test]
+fn fuel_is_consumed_per_call() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let mut batch = Batch::new(&mut config);
+    let call_cost = batch.store.engine().fuel_costs().call();
+    batch.store.add_fuel(1_000_000).unwrap();
+    batch.call(&[]).unwrap();
+    let base = batch.store.fuel_consumed().unwrap();
+    let calls = mixed_calls(100);
+    batch.call(&calls).unwrap();
+    let consumed = batch.store.fuel_consumed().unwrap();
+    assert_eq!(consumed - 2 * base, 100 * call_cost);
+}
+
+#[test]
+fn out_of_fuel_aborts_batch() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let mut batch = Batch::new(&mut config);
+    let call_cost = batch.store.engine().fuel_costs().call();
+    batch.store.add_fuel(1_000_000).unwrap();
+    batch.call(&[]).unwrap();
+    let base = batch.store.fuel_consumed().unwrap();
+    let mut batch = Batch::new(&mut config);
+    batch.store.add_fuel(base + 3 * call_cost).unwrap();
+    let error = batch.call(&[Call::Log(1); 5]).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert_eq!(batch.store.data(), &[1, 1, 1]);
+}
+
+#[test]
+fn host_error_aborts_batch() {
+    let mut batch = Batch::new(&mut Config::default());
+    let calls = [Call::Log(1), Call::Fail(42), Call::Log(2)];
+    let error = batch.call(&calls).unwrap_err();
+    assert_eq!(error.i32_exit_status(), Some(42));
+    assert_eq!(batch.store.data(), &[1]);
+}
+
+#[test]
+fn invalid_records_are_rejected() {
+    let mut batch = Batch::new(&mut Config::default());
+    for import_index in [7, 8, u32::MAX] {
+        let mut records = encode(&[Call::Log(1)]);
+        records.extend(import_index.to_le_bytes());
+        let error = batch.call_raw(&records, 2, RESULTS).unwrap_err();
+        assert_matches!(
+            error.kind(),
+            ErrorKind::BatchCall(BatchCallError::NotAHostFunc { import_index: index }) if *index == import_index
+        );
+    }
+    let mut records = encode(&[Call::Log(1)]);
+    records.extend(5_u32.to_le_bytes());
+    records.extend(0_u64.to_le_bytes());
+    let error = batch.call_raw(&records, 2, RESULTS).unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::BatchCall(BatchCallError::UnsupportedSignature { import_index: 5 })
+    );
+    // Note: no host function is called if any of the records is invalid.
+    assert!(batch.store.data().is_empty());
+}
+
+#[test]
+fn out_of_bounds_batch_traps() {
+    let mut batch = Batch::new(&mut Config::default());
+    let len_memory = batch.memory.data(&batch.store).len() as u32;
+    // Note: the parameters of the `add` record at the end of the linear memory are out of bounds.
+    let error = batch
+        .batch
+        .call(&mut batch.store, (len_memory - 4, 1, RESULTS))
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    let error = batch
+        .batch
+        .call(&mut batch.store, (len_memory, 1, RESULTS))
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    // Note: the result of the `add` record is out of bounds.
+    let records = encode(&[Call::Log(1), Call::Add(1, 2)]);
+    let error = batch.call_raw(&records, 2, len_memory - 4).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+    assert!(batch.store.data().is_empty());
+    batch.call_raw(&records, 2, len_memory - 8).unwrap();
+    assert_eq!(batch.store.data(), &[1]);
+    let mut result = [0_u8; 8];
+    batch
+        .memory
+        .read(&batch.store, len_memory as usize - 8, &mut result)
+        .unwrap();
+    assert_eq!(u64::from_le_bytes(result), 3);
+}
+
+#[test]
+fn batch_call_requires_memory() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "wasmi" "batch_call" (func $batch_call (param i32 i32 i32)))
+            (func (export "batch")
+                (call $batch_call (i32.const 0) (i32.const 0) (i32.const 0))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let mut config = Config::default();
+    config.builtin_batch_call(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define_builtins().unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let batch = instance.get_typed_func::<(), ()>(&store, "batch").unwrap();
+    let error = batch.call(&mut store, ()).unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::BatchCall(BatchCallError::MissingMemory)
+    );
+}
+
+#[test]
+fn batch_call_is_not_defined_by_default() {
+    let engine = Engine::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define_builtins().unwrap();
+    let wasm =
+        wat::parse_str(r#"(module (import "wasmi" "batch_call" (func (param i32 i32 i32))))"#)
+            .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    assert!(linker.instantiate(&mut store, &module).is_err());
+}
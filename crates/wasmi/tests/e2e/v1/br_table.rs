@@ -0,0 +1,132 @@
+//! Tests for the dense and sparse encodings of pathological Wasm `br_table` instructions.
+
+use wasmi::{Config, Engine, Linker, Module, Store};
+
+/// The results of the `run` function for each `br_table` label depth.
+///
+/// # Note
+///
+/// Label depth 4 is the maximum label index which refers to the function body.
+const RESULTS: [i32; 5] = [11, 21, 31, 41, 1];
+
+/// Returns a Wasm module with a `run` function that executes a `br_table` with `targets` and `default`.
+///
+/// If `diff` is `true` the `br_table` targets do not share their branch parameter registers.
+fn wat(targets: &[u32], default: u32, diff: bool) -> String {
+    let targets = targets
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    // Note: `global.get` allocates a dynamic register and thus
+    //       the label depths 0 and 1 use different branch parameters.
+    let (global_get, add) = match diff {
+        true => ("(global.get $zero)", "(i32.add)"),
+        false => ("", ""),
+    };
+    format!(
+        r#"
+        (module
+            (global $zero i32 (i32.const 0))
+            (func (export "run") (param $index i32) (result i32)
+                (block (result i32)
+                    (block (result i32)
+                        (block (result i32)
+                            {global_get}
+                            (block (result i32)
+                                (i32.const 1)
+                                (br_table {targets} {default} (local.get $index))
+                            )
+                            {add}
+                            (return (i32.add (i32.const 10)))
+                        )
+                        (return (i32.add (i32.const 20)))
+                    )
+                    (return (i32.add (i32.const 30)))
+                )
+                (i32.add (i32.const 40))
+            )
+        )
+    "#
+    )
+}
+
+/// Asserts that the `br_table` with `targets` and `default` branches as defined by the Wasm spec.
+fn assert_br_table(targets: &[u32], default: u32) {
+    let len_targets = targets.len() as u32;
+    let indices = (0..=len_targets + 1)
+        .chain([i32::MAX as u32, u32::MAX - 1, u32::MAX])
+        .collect::<Vec<_>>();
+    for diff in [false, true] {
+        let wasm = wat::parse_str(wat(targets, default, diff)).unwrap();
+        for optimization_level in [0, 1, 2] {
+            let mut config = Config::default();
+            config.optimization_level(optimization_level);
+            let engine = Engine::new(&config);
+            let module = Module::new(&engine, &wasm[..]).unwrap();
+            let mut store = Store::new(&engine, ());
+            let instance = <Linker<()>>::new(&engine)
+                .instantiate(&mut store, &module)
+                .unwrap()
+                .start(&mut store)
+                .unwrap();
+            let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+            for &index in &indices {
+                let depth = targets.get(index as usize).copied().unwrap_or(default);
+                assert_eq!(
+                    run.call(&mut store, index as i32).unwrap(),
+                    RESULTS[depth as usize],
+                    "index = {index}, diff = {diff}, optimization_level = {optimization_level}",
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn dense() {
+    assert_br_table(&[0, 1, 2, 3, 4, 3, 2, 1], 0);
+}
+
+#[test]
+fn sparse() {
+    let mut targets = [0; 64];
+    targets[0] = 4;
+    targets[7] = 1;
+    targets[31] = 2;
+    targets[63] = 3;
+    assert_br_table(&targets, 0);
+}
+
+#[test]
+fn duplicate_non_default_targets() {
+    // Note: most targets are duplicates but none of them is the default target.
+    assert_br_table(&[3, 3, 3, 1, 3, 3], 2);
+}
+
+#[test]
+fn all_targets_identical() {
+    assert_br_table(&[2; 16], 2);
+}
+
+#[test]
+fn duplicate_targets() {
+    assert_br_table(&[3, 3, 3, 1, 3, 3, 1], 3);
+}
+
+#[test]
+fn max_label_index() {
+    assert_br_table(&[0, 4, 4, 4, 4], 4);
+    assert_br_table(&[4, 0, 0, 0, 0], 0);
+}
+
+#[test]
+fn many_targets() {
+    let targets = (0..10_000_u32)
+        .map(|case| match case % 1000 {
+            0 => case / 1000 % 5,
+            _ => 4,
+        })
+        .collect::<Vec<_>>();
+    assert_br_table(&targets, 4);
+}
@@ -0,0 +1,183 @@
+//! Tests to check that fused compare and branch instructions behave the same
+//! independent of whether their branch offsets can be 16-bit encoded or not.
+
+use wasmi::{core::ValueType, Engine, Instance, Linker, Module, Store, Value};
+
+/// The number of `br_table` targets used to exceed 16-bit encodable branch offsets.
+const LARGE_PADDING: usize = i16::MAX as usize + 1;
+
+/// The names of the test functions of the module created by [`module_wat`].
+const FUNCS: [&str; 5] = ["forward", "forward_eqz", "forward_imm", "if_", "backward"];
+
+/// Returns a `br_table` with `len` targets that pads a function body with `len` instruction words.
+fn padding(len: usize) -> String {
+    format!("(block (br_table {} 0 (local.get $pad)))", "0 ".repeat(len))
+}
+
+/// Returns the Wasm text type name of `ty`.
+fn type_name(ty: ValueType) -> &'static str {
+    match ty {
+        ValueType::I32 => "i32",
+        ValueType::I64 => "i64",
+        ValueType::F32 => "f32",
+        ValueType::F64 => "f64",
+        _ => panic!("unsupported type: {ty:?}"),
+    }
+}
+
+/// Returns a Wasm module with functions branching conditionally on `{ty}.{op}`.
+///
+/// All branches of the returned functions skip `len_padding` instruction words.
+/// Each function returns `1` if its branch was taken and `0` otherwise.
+fn module_wat(ty: ValueType, op: &str, len_padding: usize) -> String {
+    let ty = type_name(ty);
+    let padding = padding(len_padding);
+    format!(
+        r#"
+        (module
+            (func (export "forward") (param $lhs {ty}) (param $rhs {ty}) (result i32)
+                (local $pad i32)
+                (block $taken
+                    (br_if $taken ({ty}.{op} (local.get $lhs) (local.get $rhs)))
+                    {padding}
+                    (return (i32.const 0))
+                )
+                (i32.const 1)
+            )
+            (func (export "forward_eqz") (param $lhs {ty}) (param $rhs {ty}) (result i32)
+                (local $pad i32)
+                (block $not_taken
+                    (br_if $not_taken (i32.eqz ({ty}.{op} (local.get $lhs) (local.get $rhs))))
+                    {padding}
+                    (return (i32.const 1))
+                )
+                (i32.const 0)
+            )
+            (func (export "forward_imm") (param $lhs {ty}) (param $rhs {ty}) (result i32)
+                (local $pad i32)
+                (block $taken
+                    (br_if $taken ({ty}.{op} (local.get $lhs) ({ty}.const 1)))
+                    {padding}
+                    (return (i32.const 0))
+                )
+                (i32.const 1)
+            )
+            (func (export "if_") (param $lhs {ty}) (param $rhs {ty}) (result i32)
+                (local $pad i32)
+                (if ({ty}.{op} (local.get $lhs) (local.get $rhs))
+                    (then
+                        {padding}
+                        (return (i32.const 1))
+                    )
+                )
+                (i32.const 0)
+            )
+            (func (export "backward") (param $lhs {ty}) (param $rhs {ty}) (result i32)
+                (local $pad i32)
+                (local $count i32)
+                (loop $continue
+                    (if (local.get $count)
+                        (then (return (i32.const 1)))
+                    )
+                    (local.set $count (i32.const 1))
+                    {padding}
+                    (br_if $continue ({ty}.{op} (local.get $lhs) (local.get $rhs)))
+                )
+                (i32.const 0)
+            )
+        )
+        "#,
+    )
+}
+
+/// Instantiates the Wasm module of [`module_wat`] for the given parameters.
+fn instantiate(store: &mut Store<()>, ty: ValueType, op: &str, len_padding: usize) -> Instance {
+    let wasm = wat::parse_str(module_wat(ty, op, len_padding)).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    Linker::new(store.engine())
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Calls the exported function `name` of `instance` with `lhs` and `rhs`.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, lhs: &Value, rhs: &Value) -> i32 {
+    let mut result = [Value::I32(0)];
+    instance
+        .get_func(&*store, name)
+        .unwrap()
+        .call(&mut *store, &[lhs.clone(), rhs.clone()], &mut result)
+        .unwrap();
+    result[0].i32().unwrap()
+}
+
+/// Returns the test inputs for comparisons of type `ty`.
+fn inputs(ty: ValueType) -> Vec<Value> {
+    match ty {
+        ValueType::I32 => [0, 1, 2, -1, i32::MIN, i32::MAX].map(Value::I32).to_vec(),
+        ValueType::I64 => [0, 1, 2, -1, i64::MIN, i64::MAX].map(Value::I64).to_vec(),
+        ValueType::F32 => [0.0, -0.0, 1.0, 2.0, -1.0, f32::NAN, f32::INFINITY]
+            .map(|value| Value::F32(value.into()))
+            .to_vec(),
+        ValueType::F64 => [0.0, -0.0, 1.0, 2.0, -1.0, f64::NAN, f64::INFINITY]
+            .map(|value| Value::F64(value.into()))
+            .to_vec(),
+        _ => panic!("unsupported type: {ty:?}"),
+    }
+}
+
+/// Asserts that `{ty}.{op}` branches identically with small and large branch offsets.
+fn assert_same_branching(ty: ValueType, op: &str) {
+    let mut store = <Store<()>>::new(&Engine::default(), ());
+    let small = instantiate(&mut store, ty, op, 1);
+    let large = instantiate(&mut store, ty, op, LARGE_PADDING);
+    let inputs = inputs(ty);
+    for lhs in &inputs {
+        for rhs in &inputs {
+            for func in FUNCS {
+                let expected = call(&mut store, &small, func, lhs, rhs);
+                let actual = call(&mut store, &large, func, lhs, rhs);
+                assert_eq!(
+                    actual,
+                    expected,
+                    "{func} with {}.{op} for lhs = {lhs:?} and rhs = {rhs:?}",
+                    type_name(ty)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn i32_branch_fallback() {
+    for op in [
+        "eq", "ne", "lt_s", "lt_u", "le_s", "le_u", "gt_s", "gt_u", "ge_s", "ge_u", "and", "or",
+        "xor",
+    ] {
+        assert_same_branching(ValueType::I32, op);
+    }
+}
+
+#[test]
+fn i64_branch_fallback() {
+    for op in [
+        "eq", "ne", "lt_s", "lt_u", "le_s", "le_u", "gt_s", "gt_u", "ge_s", "ge_u",
+    ] {
+        assert_same_branching(ValueType::I64, op);
+    }
+}
+
+#[test]
+fn f32_branch_fallback() {
+    for op in ["eq", "ne", "lt", "le", "gt", "ge"] {
+        assert_same_branching(ValueType::F32, op);
+    }
+}
+
+#[test]
+fn f64_branch_fallback() {
+    for op in ["eq", "ne", "lt", "le", "gt", "ge"] {
+        assert_same_branching(ValueType::F64, op);
+    }
+}
@@ -0,0 +1,175 @@
+//! Tests to check that the module builder constructs modules without Wasm bytes.
+
+use assert_matches::assert_matches;
+use wasmi::{
+    build::{IrFunc, ModuleBuilder},
+    core::{UntypedValue, ValueType},
+    errors::{BytecodeErrorKind, ErrorKind},
+    ir::{Instruction, Register},
+    Engine,
+    Error,
+    FuncType,
+    Instance,
+    Linker,
+    MemoryType,
+    Module,
+    Mutability,
+    Store,
+    Value,
+};
+
+/// Returns the `(i32, i32) -> i32` function type.
+fn binop_type() -> FuncType {
+    FuncType::new([ValueType::I32; 2], [ValueType::I32])
+}
+
+/// Returns an [`IrFunc`] adding its two `i32` parameters.
+fn ir_add() -> IrFunc {
+    IrFunc::new(
+        3,
+        [],
+        [
+            Instruction::i32_add(
+                Register::from_i16(2),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(Register::from_i16(2)),
+        ],
+    )
+}
+
+/// Builds a module with a single exported `add` function defined by `body`.
+fn build_add(engine: &Engine, body: IrFunc) -> Result<Module, Error> {
+    let mut builder = ModuleBuilder::new(engine);
+    let ty = builder.push_type(binop_type());
+    let add = builder.push_ir_func(ty, body);
+    builder.export_func("add", add);
+    builder.finish()
+}
+
+/// Instantiates `module` in `store`.
+fn instantiate(store: &mut Store<()>, module: &Module) -> Instance {
+    <Linker<()>>::new(store.engine())
+        .instantiate(&mut *store, module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Returns the [`BytecodeErrorKind`] of `error`.
+fn bytecode_error(error: &Error) -> BytecodeErrorKind {
+    match error.kind() {
+        ErrorKind::Bytecode(error) => error.kind(),
+        error => panic!("expected a bytecode error but found: {error:?}"),
+    }
+}
+
+#[test]
+fn build_ir_func() {
+    let engine = Engine::default();
+    let module = build_add(&engine, ir_add()).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = instantiate(&mut store, &module);
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+    assert_eq!(add.call(&mut store, (i32::MAX, 1)).unwrap(), i32::MIN);
+}
+
+#[test]
+fn build_mixed_funcs() {
+    let engine = Engine::default();
+    let mut builder = ModuleBuilder::new(&engine);
+    let binop = builder.push_type(binop_type());
+    let unop = builder.push_type(FuncType::new([ValueType::I32], [ValueType::I32]));
+    // Subtracts the function local constant `10` from the first parameter.
+    let sub = builder.push_ir_func(
+        binop,
+        IrFunc::new(
+            3,
+            [UntypedValue::from(10_i32)],
+            [
+                Instruction::i32_sub(
+                    Register::from_i16(2),
+                    Register::from_i16(0),
+                    Register::from_i16(-1),
+                ),
+                Instruction::return_reg(Register::from_i16(2)),
+            ],
+        ),
+    );
+    let global = builder.push_global(Value::I32(5), Mutability::Var);
+    // (func (param i32) (result i32)
+    //     (call $sub (local.get 0) (global.get $global))
+    // )
+    let sub = u8::try_from(sub).unwrap();
+    let global = u8::try_from(global).unwrap();
+    let call_sub = builder.push_wasm_func(unop, &[0x00, 0x20, 0x00, 0x23, global, 0x10, sub, 0x0B]);
+    let memory = builder.push_memory(MemoryType::new(1, Some(2)).unwrap());
+    builder.export_func("call_sub", call_sub);
+    builder.export_global("global", u32::from(global));
+    builder.export_memory("memory", memory);
+    let module = builder.finish().unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = instantiate(&mut store, &module);
+    let call_sub = instance
+        .get_typed_func::<i32, i32>(&store, "call_sub")
+        .unwrap();
+    assert_eq!(call_sub.call(&mut store, 42).unwrap(), 32);
+    let global = instance.get_global(&store, "global").unwrap();
+    assert_eq!(global.get(&store).i32(), Some(5));
+    assert!(instance.get_memory(&store, "memory").is_some());
+}
+
+#[test]
+fn build_rejects_invalid_register() {
+    let body = IrFunc::new(
+        3,
+        [],
+        [
+            Instruction::i32_add(
+                Register::from_i16(3),
+                Register::from_i16(0),
+                Register::from_i16(1),
+            ),
+            Instruction::return_reg(Register::from_i16(3)),
+        ],
+    );
+    let error = build_add(&Engine::default(), body).unwrap_err();
+    assert_eq!(
+        bytecode_error(&error),
+        BytecodeErrorKind::RegisterOutOfBounds
+    );
+}
+
+#[test]
+fn build_rejects_signature_mismatch() {
+    let body = IrFunc::new(3, [], [Instruction::Return]);
+    let error = build_add(&Engine::default(), body).unwrap_err();
+    assert_eq!(bytecode_error(&error), BytecodeErrorKind::SignatureMismatch);
+}
+
+#[test]
+fn build_rejects_unterminated_body() {
+    let body = IrFunc::new(
+        3,
+        [],
+        [Instruction::i32_add(
+            Register::from_i16(2),
+            Register::from_i16(0),
+            Register::from_i16(1),
+        )],
+    );
+    let error = build_add(&Engine::default(), body).unwrap_err();
+    assert_eq!(bytecode_error(&error), BytecodeErrorKind::UnterminatedBody);
+}
+
+#[test]
+fn build_rejects_invalid_module() {
+    let mut builder = ModuleBuilder::new(&Engine::default());
+    // There is no function type at index 0.
+    builder.push_ir_func(0, ir_add());
+    assert_matches!(builder.finish().unwrap_err().kind(), ErrorKind::Wasm(_));
+}
@@ -0,0 +1,245 @@
+//! Tests for the bounds checks of `memory.init` and `table.init` with dropped segments.
+//!
+//! The Wasm spec requires both the source and destination bounds to be checked even
+//! if nothing is initialized. Dropped segments behave as if they were empty.
+//!
+//! Each case is tested for all instruction variants with register and constant operands.
+
+use wasmi::{core::TrapCode, Engine, Error, Instance, Linker, Module, Store};
+
+/// The size of the single page linear memory in bytes.
+///
+/// # Note
+///
+/// Operands that do not fit into 16-bit constants are always passed via registers.
+const MEMORY_SIZE: u32 = 0x1_0000;
+
+/// The size of the table in elements.
+const TABLE_SIZE: u32 = 16;
+
+/// The length of the data and element segments before they are dropped.
+const SEGMENT_LEN: u32 = 4;
+
+/// The state of the initializing data or element segment.
+#[derive(Debug, Copy, Clone)]
+enum Segment {
+    /// A passive segment that has not been dropped.
+    Passive,
+    /// A passive segment dropped via `data.drop` or `elem.drop`.
+    Dropped,
+    /// An active segment that is dropped upon instantiation.
+    Active,
+    /// A declarative element segment that is dropped upon instantiation.
+    Declared,
+}
+
+impl Segment {
+    /// Returns the length of the segment as observed by `memory.init` and `table.init`.
+    fn len(self) -> u32 {
+        match self {
+            Self::Passive => SEGMENT_LEN,
+            Self::Dropped | Self::Active | Self::Declared => 0,
+        }
+    }
+}
+
+/// The tested `(dst, src, len)` operands for a destination of `size` items.
+fn operands(size: u32) -> impl Iterator<Item = (u32, u32, u32)> {
+    let dsts = [0, size - SEGMENT_LEN, size - 1, size, size + 1];
+    let srcs = [0, 1, SEGMENT_LEN - 1, SEGMENT_LEN, SEGMENT_LEN + 1];
+    let lens = [0, 1, SEGMENT_LEN];
+    dsts.into_iter().flat_map(move |dst| {
+        srcs.into_iter()
+            .flat_map(move |src| lens.into_iter().map(move |len| (dst, src, len)))
+    })
+}
+
+/// Returns `true` if initializing `len` items at `src` of `segment` to `dst` is within bounds.
+///
+/// The initialized memory or table consists of `size` items.
+fn in_bounds(segment: Segment, size: u32, dst: u32, src: u32, len: u32) -> bool {
+    src + len <= segment.len() && dst + len <= size
+}
+
+/// Returns the Wasm operand for `value` as constant if `is_const` or as local `name` otherwise.
+fn operand(name: &str, value: u32, is_const: bool) -> String {
+    match is_const {
+        true => format!("(i32.const {value})"),
+        false => format!("(local.get ${name})"),
+    }
+}
+
+/// Returns the exported `init_{n}` functions calling `instr` for each of the 8 operand variants.
+///
+/// The bits of `n` determine which of the `dst`, `src` and `len` operands are constants.
+fn init_funcs(instr: &str, dst: u32, src: u32, len: u32) -> String {
+    (0..8)
+        .map(|n| {
+            let dst = operand("dst", dst, n & 0b001 != 0);
+            let src = operand("src", src, n & 0b010 != 0);
+            let len = operand("len", len, n & 0b100 != 0);
+            format!(
+                r#"
+                (func (export "init_{n}") (param $dst i32) (param $src i32) (param $len i32)
+                    ({instr} {dst} {src} {len})
+                )"#
+            )
+        })
+        .collect()
+}
+
+/// Instantiates the Wasm module `wat` and drops its segment if `segment` is [`Segment::Dropped`].
+fn instantiate(wat: &str, segment: Segment) -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    if let Segment::Dropped = segment {
+        instance
+            .get_typed_func::<(), ()>(&store, "drop")
+            .unwrap()
+            .call(&mut store, ())
+            .unwrap();
+    }
+    (store, instance)
+}
+
+/// Calls `init_{n}` of `instance` with the `dst`, `src` and `len` operands.
+fn call_init(
+    store: &mut Store<()>,
+    instance: Instance,
+    n: u32,
+    dst: u32,
+    src: u32,
+    len: u32,
+) -> Result<(), Error> {
+    instance
+        .get_typed_func::<(u32, u32, u32), ()>(&*store, &format!("init_{n}"))
+        .unwrap()
+        .call(store, (dst, src, len))
+}
+
+/// Returns a Wasm module testing `memory.init` with a data segment in state `segment`.
+///
+/// The data segment consists of the bytes `1, 2, 3, 4`.
+fn memory_init_module(segment: Segment, dst: u32, src: u32, len: u32) -> String {
+    let data = match segment {
+        Segment::Passive | Segment::Dropped => "(data $seg \"\\01\\02\\03\\04\")",
+        Segment::Active => "(data $seg (i32.const 0) \"\\01\\02\\03\\04\")",
+        Segment::Declared => unreachable!("data segments cannot be declarative"),
+    };
+    let funcs = init_funcs("memory.init $seg", dst, src, len);
+    format!(
+        r#"
+        (module
+            (memory (export "mem") 1)
+            {data}
+            (func (export "drop") (data.drop $seg))
+            {funcs}
+        )"#
+    )
+}
+
+#[test]
+fn memory_init_bounds() {
+    for segment in [Segment::Passive, Segment::Dropped, Segment::Active] {
+        for (dst, src, len) in operands(MEMORY_SIZE) {
+            let wat = memory_init_module(segment, dst, src, len);
+            for n in 0..8 {
+                let (mut store, instance) = instantiate(&wat, segment);
+                let memory = instance.get_memory(&store, "mem").unwrap();
+                // Note: resets the bytes written by the active data segment.
+                memory.data_mut(&mut store).fill(0);
+                let result = call_init(&mut store, instance, n, dst, src, len);
+                let case = format!("{segment:?}: dst = {dst}, src = {src}, len = {len}, n = {n}");
+                let mut expected = vec![0_u8; MEMORY_SIZE as usize];
+                if in_bounds(segment, MEMORY_SIZE, dst, src, len) {
+                    assert!(result.is_ok(), "{case}: unexpected trap: {result:?}");
+                    for i in 0..len {
+                        expected[(dst + i) as usize] = (src + i + 1) as u8;
+                    }
+                } else {
+                    let trap_code = result.err().and_then(|error| error.as_trap_code());
+                    assert_eq!(trap_code, Some(TrapCode::MemoryOutOfBounds), "{case}");
+                }
+                assert_eq!(memory.data(&store), expected, "{case}");
+            }
+        }
+    }
+}
+
+/// Returns a Wasm module testing `table.init` with an element segment in state `segment`.
+///
+/// The element segment consists of the functions returning `1, 2, 3, 4`.
+/// The exported `read` function returns the result of the function at
+/// the given table index or `0` if the table element is `null`.
+fn table_init_module(segment: Segment, dst: u32, src: u32, len: u32) -> String {
+    let elem = match segment {
+        Segment::Passive | Segment::Dropped => "(elem $seg func $f1 $f2 $f3 $f4)",
+        // Note: the active segment is written outside of the tested table region.
+        Segment::Active => "(elem $seg (table $other) (i32.const 0) func $f1 $f2 $f3 $f4)",
+        Segment::Declared => "(elem $seg declare func $f1 $f2 $f3 $f4)",
+    };
+    let funcs = init_funcs("table.init $table $seg", dst, src, len);
+    format!(
+        r#"
+        (module
+            (type $result (func (result i32)))
+            (table $table {TABLE_SIZE} funcref)
+            (table $other {SEGMENT_LEN} funcref)
+            (func $f1 (result i32) (i32.const 1))
+            (func $f2 (result i32) (i32.const 2))
+            (func $f3 (result i32) (i32.const 3))
+            (func $f4 (result i32) (i32.const 4))
+            {elem}
+            (func (export "drop") (elem.drop $seg))
+            (func (export "read") (param $index i32) (result i32)
+                (if (result i32) (ref.is_null (table.get $table (local.get $index)))
+                    (then (i32.const 0))
+                    (else (call_indirect $table (type $result) (local.get $index)))
+                )
+            )
+            {funcs}
+        )"#
+    )
+}
+
+#[test]
+fn table_init_bounds() {
+    let segments = [
+        Segment::Passive,
+        Segment::Dropped,
+        Segment::Active,
+        Segment::Declared,
+    ];
+    for segment in segments {
+        for (dst, src, len) in operands(TABLE_SIZE) {
+            let wat = table_init_module(segment, dst, src, len);
+            for n in 0..8 {
+                let (mut store, instance) = instantiate(&wat, segment);
+                let result = call_init(&mut store, instance, n, dst, src, len);
+                let case = format!("{segment:?}: dst = {dst}, src = {src}, len = {len}, n = {n}");
+                let mut expected = [0_i32; TABLE_SIZE as usize];
+                if in_bounds(segment, TABLE_SIZE, dst, src, len) {
+                    assert!(result.is_ok(), "{case}: unexpected trap: {result:?}");
+                    for i in 0..len {
+                        expected[(dst + i) as usize] = (src + i + 1) as i32;
+                    }
+                } else {
+                    let trap_code = result.err().and_then(|error| error.as_trap_code());
+                    assert_eq!(trap_code, Some(TrapCode::TableOutOfBounds), "{case}");
+                }
+                let read = instance.get_typed_func::<u32, i32>(&store, "read").unwrap();
+                let elements = (0..TABLE_SIZE)
+                    .map(|index| read.call(&mut store, index).unwrap())
+                    .collect::<Vec<_>>();
+                assert_eq!(elements, expected, "{case}");
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Tests for the `memory.copy` and `memory.fill` instructions with and without fuel metering.
+
+use wasmi::{core::TrapCode, Config, Engine, Instance, Linker, Memory, Module, Store, TypedFunc};
+
+/// A Wasm module with a single page linear memory.
+///
+/// - `copy` copies `len` bytes from `src` to `dst`.
+/// - `fill` fills `len` bytes at `dst` with `value`.
+/// - `grow_and_copy` grows the memory by one page and then copies like `copy`.
+const WAT: &str = r#"
+    (module
+        (memory (export "mem") 1)
+        (func (export "copy") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "fill") (param $dst i32) (param $value i32) (param $len i32)
+            (memory.fill (local.get $dst) (local.get $value) (local.get $len))
+        )
+        (func (export "grow_and_copy") (param $dst i32) (param $src i32) (param $len i32)
+            (drop (memory.grow (i32.const 1)))
+            (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+        )
+    )
+"#;
+
+/// The size of a linear memory page in bytes.
+const PAGE_SIZE: u32 = 0x1_0000;
+
+/// The instantiated [`WAT`] module.
+struct Test {
+    store: Store<()>,
+    memory: Memory,
+    copy: TypedFunc<(u32, u32, u32), ()>,
+    fill: TypedFunc<(u32, u32, u32), ()>,
+    grow_and_copy: TypedFunc<(u32, u32, u32), ()>,
+}
+
+impl Test {
+    /// Instantiates [`WAT`] with fuel metering enabled if `fuel` is `Some`.
+    fn new(fuel: Option<u64>) -> Self {
+        let mut config = Config::default();
+        config.consume_fuel(fuel.is_some());
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        if let Some(fuel) = fuel {
+            store.add_fuel(fuel).unwrap();
+        }
+        let instance: Instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let memory = instance.get_memory(&store, "mem").unwrap();
+        let copy = instance.get_typed_func(&store, "copy").unwrap();
+        let fill = instance.get_typed_func(&store, "fill").unwrap();
+        let grow_and_copy = instance.get_typed_func(&store, "grow_and_copy").unwrap();
+        let mut test = Self {
+            store,
+            memory,
+            copy,
+            fill,
+            grow_and_copy,
+        };
+        for (n, byte) in test.bytes_mut().iter_mut().enumerate() {
+            *byte = n as u8;
+        }
+        test
+    }
+
+    /// Returns the bytes of the linear memory.
+    fn bytes(&self) -> &[u8] {
+        self.memory.data(&self.store)
+    }
+
+    /// Returns the bytes of the linear memory for modification.
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        self.memory.data_mut(&mut self.store)
+    }
+
+    /// Runs the exported `copy` function.
+    fn copy(&mut self, dst: u32, src: u32, len: u32) -> Result<(), wasmi::Error> {
+        self.copy.call(&mut self.store, (dst, src, len))
+    }
+
+    /// Runs the exported `fill` function.
+    fn fill(&mut self, dst: u32, value: u32, len: u32) -> Result<(), wasmi::Error> {
+        self.fill.call(&mut self.store, (dst, value, len))
+    }
+}
+
+/// Runs `f` with and without fuel metering.
+fn run(f: impl Fn(Test)) {
+    f(Test::new(None));
+    f(Test::new(Some(1_000_000)));
+}
+
+#[test]
+fn overlapping_copy_works() {
+    run(|mut test| {
+        // Copy towards higher addresses.
+        let mut expected = test.bytes().to_vec();
+        expected.copy_within(0..100, 10);
+        test.copy(10, 0, 100).unwrap();
+        assert_eq!(test.bytes(), &expected[..]);
+        // Copy towards lower addresses.
+        expected.copy_within(10..110, 0);
+        test.copy(0, 10, 100).unwrap();
+        assert_eq!(test.bytes(), &expected[..]);
+    })
+}
+
+#[test]
+fn fill_works() {
+    run(|mut test| {
+        let mut expected = test.bytes().to_vec();
+        expected[5..105].fill(0xAB);
+        test.fill(5, 0xFFAB, 100).unwrap();
+        assert_eq!(test.bytes(), &expected[..]);
+    })
+}
+
+#[test]
+fn out_of_bounds_traps_without_writes() {
+    run(|mut test| {
+        let expected = test.bytes().to_vec();
+        let oob_copies = [
+            (0, PAGE_SIZE - 10, 11),
+            (PAGE_SIZE - 10, 0, 11),
+            (PAGE_SIZE + 1, 0, 0),
+            (0, u32::MAX, 2),
+            (u32::MAX, 0, 2),
+        ];
+        for (dst, src, len) in oob_copies {
+            let error = test.copy(dst, src, len).unwrap_err();
+            assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+            assert_eq!(test.bytes(), &expected[..]);
+        }
+        let oob_fills = [(PAGE_SIZE - 10, 11), (PAGE_SIZE + 1, 0), (u32::MAX, 2)];
+        for (dst, len) in oob_fills {
+            let error = test.fill(dst, 0xFF, len).unwrap_err();
+            assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+            assert_eq!(test.bytes(), &expected[..]);
+        }
+        // Zero length operations at the end of the linear memory are in bounds.
+        test.copy(PAGE_SIZE, PAGE_SIZE, 0).unwrap();
+        test.fill(PAGE_SIZE, 0xFF, 0).unwrap();
+        assert_eq!(test.bytes(), &expected[..]);
+    })
+}
+
+#[test]
+fn copy_after_grow_works() {
+    run(|mut test| {
+        test.grow_and_copy
+            .call(&mut test.store, (PAGE_SIZE, 0, PAGE_SIZE))
+            .unwrap();
+        let (old, new) = test.bytes().split_at(PAGE_SIZE as usize);
+        assert_eq!(old, new);
+    })
+}
+
+#[test]
+fn out_of_fuel_traps_without_writes() {
+    let mut test = Test::new(Some(1_000));
+    let expected = test.bytes().to_vec();
+    let error = test.copy(0, 1, PAGE_SIZE - 1).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    let error = test.fill(0, 0xFF, PAGE_SIZE).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert_eq!(test.bytes(), &expected[..]);
+}
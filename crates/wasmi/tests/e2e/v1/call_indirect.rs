@@ -0,0 +1,182 @@
+//! Tests to check that cached `call_indirect` resolutions never become stale.
+
+use wasmi::{
+    core::{TrapCode, ValueType},
+    Engine,
+    FuncType,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+};
+
+/// A Wasm module with a `call_indirect` call site and exports that mutate its table.
+const WAT: &str = r#"
+    (module
+        (type $i32 (func (result i32)))
+        (table $t (export "table") 2 funcref)
+        (elem (i32.const 0) func $f10 $f10)
+        (elem $e func $f10 $f20 $f30)
+        (elem declare func $f20 $f64)
+        (func $f10 (result i32) (i32.const 10))
+        (func $f20 (result i32) (i32.const 20))
+        (func $f30 (result i32) (i32.const 30))
+        (func $f64 (result i64) (i64.const 64))
+        (func (export "call") (param $index i32) (result i32)
+            (call_indirect (type $i32) (local.get $index))
+        )
+        (func (export "set_f20") (param $index i32)
+            (table.set $t (local.get $index) (ref.func $f20))
+        )
+        (func (export "set_f64") (param $index i32)
+            (table.set $t (local.get $index) (ref.func $f64))
+        )
+        (func (export "fill_null") (param $index i32) (param $len i32)
+            (table.fill $t (local.get $index) (ref.null func) (local.get $len))
+        )
+        (func (export "copy") (param $dst i32) (param $src i32) (param $len i32)
+            (table.copy $t $t (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "init") (param $dst i32) (param $src i32) (param $len i32)
+            (table.init $t $e (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "grow_f30") (param $delta i32)
+            (drop (table.grow $t (ref.func $f30) (local.get $delta)))
+        )
+    )
+"#;
+
+/// Instantiates the [`WAT`] test module.
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Returns the exported `"call"` function.
+fn call_func(store: &Store<()>, instance: &Instance) -> TypedFunc<i32, i32> {
+    instance.get_typed_func::<i32, i32>(store, "call").unwrap()
+}
+
+/// Calls the exported function `name` that mutates the table.
+fn mutate<Params>(store: &mut Store<()>, instance: &Instance, name: &str, params: Params)
+where
+    Params: wasmi::WasmParams,
+{
+    instance
+        .get_typed_func::<Params, ()>(&*store, name)
+        .unwrap()
+        .call(store, params)
+        .unwrap();
+}
+
+/// Asserts that calling `call` with `index` traps with `expected`.
+fn assert_trap(store: &mut Store<()>, call: TypedFunc<i32, i32>, index: i32, expected: TrapCode) {
+    let error = call.call(store, index).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(expected));
+}
+
+#[test]
+fn call_indirect_stable_table() {
+    let (mut store, instance) = setup();
+    let call = call_func(&store, &instance);
+    for _ in 0..100 {
+        assert_eq!(call.call(&mut store, 0).unwrap(), 10);
+        assert_eq!(call.call(&mut store, 1).unwrap(), 10);
+    }
+}
+
+#[test]
+fn call_indirect_after_table_set() {
+    let (mut store, instance) = setup();
+    let call = call_func(&store, &instance);
+    assert_eq!(call.call(&mut store, 0).unwrap(), 10);
+    mutate(&mut store, &instance, "set_f20", 0);
+    assert_eq!(call.call(&mut store, 0).unwrap(), 20);
+    mutate(&mut store, &instance, "set_f64", 0);
+    assert_trap(&mut store, call, 0, TrapCode::BadSignature);
+}
+
+#[test]
+fn call_indirect_after_table_fill() {
+    let (mut store, instance) = setup();
+    let call = call_func(&store, &instance);
+    assert_eq!(call.call(&mut store, 1).unwrap(), 10);
+    mutate(&mut store, &instance, "fill_null", (0, 2));
+    assert_trap(&mut store, call, 1, TrapCode::IndirectCallToNull);
+}
+
+#[test]
+fn call_indirect_after_table_copy() {
+    let (mut store, instance) = setup();
+    let call = call_func(&store, &instance);
+    mutate(&mut store, &instance, "set_f20", 1);
+    assert_eq!(call.call(&mut store, 0).unwrap(), 10);
+    mutate(&mut store, &instance, "copy", (0, 1, 1));
+    assert_eq!(call.call(&mut store, 0).unwrap(), 20);
+}
+
+#[test]
+fn call_indirect_after_table_init() {
+    let (mut store, instance) = setup();
+    let call = call_func(&store, &instance);
+    assert_eq!(call.call(&mut store, 0).unwrap(), 10);
+    mutate(&mut store, &instance, "init", (0, 2, 1));
+    assert_eq!(call.call(&mut store, 0).unwrap(), 30);
+}
+
+#[test]
+fn call_indirect_after_table_grow() {
+    let (mut store, instance) = setup();
+    let call = call_func(&store, &instance);
+    assert_trap(&mut store, call, 2, TrapCode::TableOutOfBounds);
+    mutate(&mut store, &instance, "grow_f30", 1);
+    assert_eq!(call.call(&mut store, 2).unwrap(), 30);
+}
+
+#[test]
+fn call_indirect_after_host_table_set() {
+    let (mut store, instance) = setup();
+    let call = call_func(&store, &instance);
+    let table = instance.get_table(&store, "table").unwrap();
+    assert_eq!(call.call(&mut store, 0).unwrap(), 10);
+    mutate(&mut store, &instance, "set_f20", 1);
+    let f20 = table.get(&store, 1).unwrap();
+    table.set(&mut store, 0, f20).unwrap();
+    assert_eq!(call.call(&mut store, 0).unwrap(), 20);
+}
+
+#[test]
+fn call_indirect_reports_mismatching_signatures() {
+    let (mut store, instance) = setup();
+    let call = call_func(&store, &instance);
+    assert_eq!(call.call(&mut store, 0).unwrap(), 10);
+    mutate(&mut store, &instance, "set_f64", 0);
+    let error = call.call(&mut store, 0).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::BadSignature));
+    let (expected, actual) = error.signature_mismatch().unwrap();
+    assert_eq!(expected, &FuncType::new([], [ValueType::I32]));
+    assert_eq!(actual, &FuncType::new([], [ValueType::I64]));
+    let message = error.to_string();
+    assert!(
+        message.contains("I32") && message.contains("I64"),
+        "{message}"
+    );
+}
+
+#[test]
+fn call_indirect_other_traps_have_no_signatures() {
+    let (mut store, instance) = setup();
+    let call = call_func(&store, &instance);
+    let error = call.call(&mut store, 2).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::TableOutOfBounds));
+    assert!(error.signature_mismatch().is_none());
+}
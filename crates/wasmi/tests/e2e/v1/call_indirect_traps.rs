@@ -0,0 +1,180 @@
+//! Tests asserting that all `call_indirect` and `return_call_indirect` variants
+//! consistently distinguish out of bounds table accesses from uninitialized elements.
+//!
+//! # Note
+//!
+//! The callee index of an indirect call is either encoded in a register or as
+//! 16-bit immediate value if it is a constant that fits into 16 bits.
+//! Indirect calls without parameters are encoded differently from indirect calls
+//! with parameters. All combinations are tested at the 16-bit boundary index.
+
+use core::fmt::Write as _;
+use wasmi::{core::TrapCode, Config, Engine, Instance, Linker, Module, Store};
+
+/// The size of the `$big` table with uninitialized elements at the 16-bit boundary.
+const BIG: u32 = 65537;
+
+/// The size of the `$small` table for which the 16-bit boundary is out of bounds.
+const SMALL: u32 = 2;
+
+/// The constant callee indices tested for both tables.
+///
+/// - `1`: valid element
+/// - `2`: first uninitialized or out of bounds index
+/// - `65535`: largest index encodable as 16-bit immediate
+/// - `65536`: smallest index not encodable as 16-bit immediate
+/// - `65537`: out of bounds index for both tables
+const INDICES: [u32; 5] = [1, 2, 65535, 65536, 65537];
+
+/// The indirect call instructions under test.
+const KINDS: [&str; 2] = ["call_indirect", "return_call_indirect"];
+
+/// Returns the name of the exported function under test.
+///
+/// The function uses a constant callee `index` or a register callee index if `index` is `None`.
+fn name(kind: &str, table: &str, with_params: bool, index: Option<u32>) -> String {
+    let params = if with_params { "params" } else { "0" };
+    match index {
+        Some(index) => format!("{kind}_{table}_{params}_{index}"),
+        None => format!("{kind}_{table}_{params}"),
+    }
+}
+
+/// Returns the Wasm module with all exported indirect call functions under test.
+///
+/// Elements `0` and `1` of both tables are `$f1` so that indirect calls with
+/// parameters succeed while indirect calls without parameters fail the signature check.
+fn wasm() -> Vec<u8> {
+    let mut wat = String::from(
+        r#"
+        (module
+            (type $t0 (func (result i32)))
+            (type $t1 (func (param i32) (result i32)))
+            (table $big 65537 funcref)
+            (table $small 2 funcref)
+            (elem (table $big) (i32.const 0) func $f1 $f1)
+            (elem (table $small) (i32.const 0) func $f1 $f1)
+            (func $f1 (param i32) (result i32) (i32.add (local.get 0) (i32.const 1)))
+        "#,
+    );
+    for kind in KINDS {
+        for table in ["big", "small"] {
+            for with_params in [false, true] {
+                let (ty, params) = match with_params {
+                    false => ("$t0", ""),
+                    true => ("$t1", "(i32.const 41)"),
+                };
+                let index_name = name(kind, table, with_params, None);
+                writeln!(
+                    wat,
+                    r#"(func (export "{index_name}") (param $index i32) (result i32)
+                        ({kind} ${table} (type {ty}) {params} (local.get $index))
+                    )"#,
+                )
+                .unwrap();
+                for index in INDICES {
+                    let const_name = name(kind, table, with_params, Some(index));
+                    writeln!(
+                        wat,
+                        r#"(func (export "{const_name}") (param $unused i32) (result i32)
+                            ({kind} ${table} (type {ty}) {params} (i32.const {index}))
+                        )"#,
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    }
+    wat.push(')');
+    wat::parse_str(wat).unwrap()
+}
+
+/// Instantiates the Wasm module returned by [`wasm`].
+fn setup() -> (Store<()>, Instance) {
+    let mut config = Config::default();
+    config.wasm_tail_call(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm()[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Returns the expected outcome of an indirect call to `index` of a table with `size`.
+///
+/// Returns `Ok(42)` for a successful call of `$f1` with parameters.
+fn expected(size: u32, with_params: bool, index: u32) -> Result<i32, TrapCode> {
+    if index >= size {
+        return Err(TrapCode::TableOutOfBounds);
+    }
+    if index >= 2 {
+        return Err(TrapCode::IndirectCallToNull);
+    }
+    match with_params {
+        true => Ok(42),
+        false => Err(TrapCode::BadSignature),
+    }
+}
+
+/// Calls the exported function `name` with `input` and returns its result or trap code.
+fn call(
+    store: &mut Store<()>,
+    instance: &Instance,
+    name: &str,
+    input: u32,
+) -> Result<i32, TrapCode> {
+    instance
+        .get_typed_func::<i32, i32>(&*store, name)
+        .unwrap()
+        .call(&mut *store, input as i32)
+        .map_err(|error| {
+            error
+                .as_trap_code()
+                .unwrap_or_else(|| panic!("{name}: unexpected error: {error}"))
+        })
+}
+
+#[test]
+fn indirect_call_traps() {
+    let (mut store, instance) = setup();
+    for kind in KINDS {
+        for (table, size) in [("big", BIG), ("small", SMALL)] {
+            for with_params in [false, true] {
+                for index in INDICES {
+                    let expected = expected(size, with_params, index);
+                    let const_name = name(kind, table, with_params, Some(index));
+                    let result = call(&mut store, &instance, &const_name, 0);
+                    assert_eq!(result, expected, "{const_name}");
+                    let index_name = name(kind, table, with_params, None);
+                    let result = call(&mut store, &instance, &index_name, index);
+                    assert_eq!(result, expected, "{index_name}({index})");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn indirect_call_traps_are_not_cached() {
+    let (mut store, instance) = setup();
+    // Note: successful indirect calls are cached per call site which must not
+    //       affect the traps of subsequent calls through the same call site.
+    for kind in KINDS {
+        let name = name(kind, "big", true, None);
+        for _ in 0..3 {
+            assert_eq!(call(&mut store, &instance, &name, 1), Ok(42));
+            assert_eq!(
+                call(&mut store, &instance, &name, 65535),
+                Err(TrapCode::IndirectCallToNull)
+            );
+            assert_eq!(
+                call(&mut store, &instance, &name, BIG),
+                Err(TrapCode::TableOutOfBounds)
+            );
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Tests for restricting the stack usage of re-entrant calls via [`Func::call_with_limits`].
+
+use wasmi::{
+    core::TrapCode,
+    CallLimits,
+    Caller,
+    Engine,
+    Func,
+    FuncRef,
+    Instance,
+    Linker,
+    Module,
+    StackUsage,
+    Store,
+    Value,
+};
+
+/// A Wasm module that registers its `$callback` and calls it from deep within the `trusted` function.
+///
+/// - `$callback` recurses `n` times and returns `n`.
+/// - `trusted` recurses `depth` times before calling `$callback` with `n` through
+///   the `env.invoke` host function and adds `depth` to its result.
+const WASM: &str = r#"
+    (module
+        (import "env" "register" (func $register (param funcref)))
+        (import "env" "invoke" (func $invoke (param i32) (result i32)))
+        (elem declare func $callback)
+        (func $callback (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 0))
+                (else
+                    (i32.add
+                        (call $callback (i32.sub (local.get $n) (i32.const 1)))
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+        (func (export "register")
+            (call $register (ref.func $callback))
+        )
+        (func $trusted (export "trusted") (param $depth i32) (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $depth))
+                (then (call $invoke (local.get $n)))
+                (else
+                    (i32.add
+                        (call $trusted (i32.sub (local.get $depth) (i32.const 1)) (local.get $n))
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+    )
+"#;
+
+/// The host state of the [`Store`].
+struct HostState {
+    /// The callback registered by the Wasm module.
+    callback: Option<Func>,
+    /// The limits of calls to the `callback`.
+    limits: CallLimits,
+    /// The stack usages of all calls to the `callback` that overflowed the stack.
+    overflows: Vec<Option<StackUsage>>,
+}
+
+/// Instantiates [`WASM`] and registers its callback that is called with `limits`.
+fn setup(limits: CallLimits) -> (Store<HostState>, Instance) {
+    let engine = Engine::default();
+    let state = HostState {
+        callback: None,
+        limits,
+        overflows: Vec::new(),
+    };
+    let mut store = Store::new(&engine, state);
+    let mut linker = <Linker<HostState>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "register",
+            |mut caller: Caller<HostState>, callback: FuncRef| {
+                caller.data_mut().callback = callback.func().copied();
+            },
+        )
+        .unwrap()
+        .func_wrap("env", "invoke", |mut caller: Caller<HostState>, n: i32| {
+            let callback = caller.data().callback.unwrap();
+            let limits = caller.data().limits;
+            let mut results = [Value::I32(0)];
+            match callback.call_with_limits(&mut caller, &[Value::I32(n)], &mut results, limits) {
+                Ok(()) => results[0].i32().unwrap(),
+                Err(error) => {
+                    assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+                    caller.data_mut().overflows.push(error.stack_usage());
+                    -1
+                }
+            }
+        })
+        .unwrap();
+    let wasm = wat::parse_str(WASM).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<(), ()>(&store, "register")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the `trusted` function of [`WASM`] with `depth` and `n`.
+fn trusted(store: &mut Store<HostState>, instance: Instance, depth: i32, n: i32) -> i32 {
+    instance
+        .get_typed_func::<(i32, i32), i32>(&*store, "trusted")
+        .unwrap()
+        .call(store, (depth, n))
+        .unwrap()
+}
+
+/// Calls the registered callback of [`WASM`] with `n` without limits.
+fn callback(store: &mut Store<HostState>, n: i32) -> i32 {
+    let callback = store.data().callback.unwrap();
+    let mut results = [Value::I32(0)];
+    callback
+        .call(&mut *store, &[Value::I32(n)], &mut results)
+        .unwrap();
+    results[0].i32().unwrap()
+}
+
+#[test]
+fn callback_within_limits() {
+    let (mut store, instance) = setup(CallLimits {
+        max_additional_frames: 16,
+        max_additional_value_stack: 1024,
+    });
+    assert_eq!(trusted(&mut store, instance, 500, 15), 515);
+    assert!(store.data().overflows.is_empty());
+}
+
+#[test]
+fn recursing_callback_traps_at_frame_limit() {
+    let (mut store, instance) = setup(CallLimits {
+        max_additional_frames: 16,
+        max_additional_value_stack: 1024,
+    });
+    // Note: the callback itself plus 16 recursive calls exceed the limit.
+    assert_eq!(trusted(&mut store, instance, 500, 16), 499);
+    let overflows = &store.data().overflows;
+    assert_eq!(overflows.len(), 1);
+    assert_eq!(overflows[0].unwrap().peak_call_depth, 16);
+    // The outer call can still recurse deeper than the limit after the trap.
+    assert_eq!(trusted(&mut store, instance, 900, 5), 905);
+    // The limits are not in effect for calls without limits.
+    assert_eq!(callback(&mut store, 500), 500);
+    assert_eq!(store.data().overflows.len(), 1);
+}
+
+#[test]
+fn recursing_callback_traps_at_value_stack_limit() {
+    let (mut store, instance) = setup(CallLimits {
+        max_additional_frames: 1000,
+        max_additional_value_stack: 64,
+    });
+    assert_eq!(trusted(&mut store, instance, 10, 2), 12);
+    assert_eq!(trusted(&mut store, instance, 10, 500), 9);
+    let overflows = &store.data().overflows;
+    assert_eq!(overflows.len(), 1);
+    // Note: the value stack overflows long before the frame limit is reached.
+    let usage = overflows[0].unwrap();
+    assert!(usage.peak_call_depth < 64);
+    assert!(usage.peak_value_stack_bytes <= 64 * 8);
+    assert_eq!(callback(&mut store, 500), 500);
+}
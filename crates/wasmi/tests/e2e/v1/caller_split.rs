@@ -0,0 +1,195 @@
+//! Tests for the disjoint borrows of host data and store entities via [`Caller::split`].
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    ptr,
+};
+use wasmi::{
+    core::ValueType,
+    Caller,
+    Engine,
+    Extern,
+    Global,
+    Linker,
+    Memory,
+    Module,
+    Mutability,
+    Store,
+    Value,
+};
+
+/// A global allocator that counts the allocations of the current thread.
+///
+/// Allocations of the current thread larger than its [`ALLOCATION_LIMIT`] fail.
+struct CountingAllocator;
+
+thread_local! {
+    /// The number of allocations performed by the current thread.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    /// The maximum size in bytes of a single allocation of the current thread.
+    static ALLOCATION_LIMIT: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+/// Returns `true` if an allocation of `size` bytes exceeds the [`ALLOCATION_LIMIT`].
+fn exceeds_allocation_limit(size: usize) -> bool {
+    ALLOCATION_LIMIT
+        .try_with(|limit| size > limit.get())
+        .unwrap_or(false)
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if exceeds_allocation_limit(layout.size()) {
+            return ptr::null_mut();
+        }
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if exceeds_allocation_limit(new_size) {
+            return ptr::null_mut();
+        }
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `f` while all allocations of the current thread larger than `limit` bytes fail.
+pub(super) fn with_allocation_limit<R>(limit: usize, f: impl FnOnce() -> R) -> R {
+    let previous = ALLOCATION_LIMIT.with(|cell| cell.replace(limit));
+    let result = f();
+    ALLOCATION_LIMIT.with(|cell| cell.set(previous));
+    result
+}
+
+/// Returns the number of allocations performed by the current thread while running `f`.
+fn count_allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+/// A Wasm module that lets its host functions copy parts of its memory.
+const WAT: &str = r#"
+    (module
+        (import "env" "read" (func $read (param i32 i32)))
+        (import "env" "count" (func $count (param i32 i32)))
+        (import "env" "calls" (global $calls (mut i32)))
+        (export "calls" (global $calls))
+        (memory (export "mem") 1)
+        (data (i32.const 16) "hello wasmi")
+        (func (export "read") (param i32 i32)
+            (call $read (local.get 0) (local.get 1))
+        )
+        (func (export "count") (param i32 i32)
+            (call $count (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+/// The host data of the [`Store`].
+#[derive(Default)]
+struct HostState {
+    /// The bytes read from the Wasm memory.
+    buffer: Vec<u8>,
+}
+
+/// Returns the exported linear memory of the caller.
+fn memory(caller: &Caller<HostState>) -> Memory {
+    caller
+        .get_export("mem")
+        .and_then(Extern::into_memory)
+        .unwrap()
+}
+
+/// Instantiates [`WAT`] and returns the [`Store`] with its instance.
+fn setup() -> (Store<HostState>, wasmi::Instance, Global) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, HostState::default());
+    store.data_mut().buffer.reserve(64);
+    let calls = Global::new(&mut store, Value::I32(0), Mutability::Var);
+    let mut linker = <Linker<HostState>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "read",
+            |mut caller: Caller<HostState>, ptr: u32, len: u32| {
+                let memory = memory(&caller);
+                let (data, bytes) = caller.data_and_memory_mut(memory);
+                let bytes = &bytes[ptr as usize..][..len as usize];
+                data.buffer.clear();
+                data.buffer.extend_from_slice(bytes);
+            },
+        )
+        .unwrap();
+    linker
+        .func_wrap(
+            "env",
+            "count",
+            |mut caller: Caller<HostState>, ptr: u32, len: u32| {
+                let (data, mut entities) = caller.split();
+                let memory = entities
+                    .get_export("mem")
+                    .and_then(Extern::into_memory)
+                    .unwrap();
+                let bytes = &entities.memory_data(memory)[ptr as usize..][..len as usize];
+                data.buffer.clear();
+                data.buffer.extend_from_slice(bytes);
+                let calls = entities
+                    .get_export("calls")
+                    .and_then(Extern::into_global)
+                    .unwrap();
+                let count = entities.global_get(calls).i32().unwrap();
+                entities.global_set(calls, Value::I32(count + 1)).unwrap();
+                entities.memory_data_mut(memory)[ptr as usize] = b'H';
+            },
+        )
+        .unwrap();
+    linker.define("env", "calls", calls).unwrap();
+    let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance, calls)
+}
+
+#[test]
+fn data_and_memory_mut_without_allocations() {
+    let (mut store, instance, _) = setup();
+    let read = instance
+        .get_typed_func::<(u32, u32), ()>(&store, "read")
+        .unwrap();
+    // Warm up the engine so that its internal buffers are allocated.
+    read.call(&mut store, (16, 5)).unwrap();
+    let allocations = count_allocations(|| read.call(&mut store, (22, 5)).unwrap());
+    assert_eq!(allocations, 0);
+    assert_eq!(store.data().buffer, b"wasmi");
+}
+
+#[test]
+fn split_without_allocations() {
+    let (mut store, instance, calls) = setup();
+    let count = instance
+        .get_typed_func::<(u32, u32), ()>(&store, "count")
+        .unwrap();
+    count.call(&mut store, (16, 5)).unwrap();
+    assert_eq!(store.data().buffer, b"hello");
+    let allocations = count_allocations(|| count.call(&mut store, (16, 11)).unwrap());
+    assert_eq!(allocations, 0);
+    assert_eq!(store.data().buffer, b"Hello wasmi");
+    assert_eq!(calls.get(&store).i32(), Some(2));
+    let memory = instance.get_memory(&store, "mem").unwrap();
+    assert_eq!(&memory.data(&store)[16..27], b"Hello wasmi");
+    assert_eq!(calls.ty(&store).content(), ValueType::I32);
+}
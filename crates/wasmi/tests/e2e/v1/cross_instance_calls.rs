@@ -0,0 +1,210 @@
+//! Tests to check that calls alternating between two instances access the correct entities.
+//!
+//! The Wasmi executor keeps the cached entities of the previously used instance
+//! around while calling into another instance. These tests make sure that those
+//! cached entities are invalidated whenever a linear memory might have grown.
+
+use wasmi::{
+    core::Pages,
+    Caller,
+    Engine,
+    Func,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+};
+
+/// A Wasm module that exports its linear memory and may grow it.
+const CALLEE: &str = r#"
+    (module
+        (import "env" "host_grow" (func $host_grow (param i32)))
+        (memory (export "mem") 1)
+        (func (export "load") (param i32) (result i32)
+            (i32.load (local.get 0))
+        )
+        (func (export "store") (param i32 i32)
+            (i32.store (local.get 0) (local.get 1))
+        )
+        ;; Grows the memory and stores `value` at the first address of the new pages.
+        (func (export "grow_store") (param $delta i32) (param $value i32)
+            (i32.store
+                (i32.mul (memory.grow (local.get $delta)) (i32.const 65536))
+                (local.get $value)
+            )
+        )
+        ;; Grows the memory via a host function.
+        (func (export "host_grow") (param $delta i32)
+            (call $host_grow (local.get $delta))
+        )
+    )
+"#;
+
+/// A Wasm module that alternates between its own memory accesses and calls to `callee`.
+const CALLER: &str = r#"
+    (module
+        (type $load (func (param i32) (result i32)))
+        (type $store (func (param i32 i32)))
+        (type $grow (func (param i32 i32)))
+        (import "env" "mem" (memory 1))
+        (import "callee" "load" (func $load (type $load)))
+        (import "callee" "store" (func $store (type $store)))
+        (import "callee" "grow_store" (func $grow_store (type $grow)))
+        (import "callee" "host_grow" (func $host_grow (param i32)))
+        (table funcref (elem $load $store $grow_store))
+
+        ;; Stores `value` at `addr` of its own memory and returns the
+        ;; value at `addr` of the callee memory loaded via `call_indirect`.
+        (func (export "ping_pong") (param $addr i32) (param $value i32) (param $n i32) (result i32)
+            (local $result i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (i32.store (local.get $addr) (local.get $value))
+                    (local.set $result
+                        (call_indirect (type $load) (local.get $addr) (i32.const 0))
+                    )
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $result)
+        )
+
+        ;; Accesses its own memory, lets the callee grow the shared memory
+        ;; and afterwards accesses its own memory within the new pages.
+        (func (export "grow_in_callee") (param $delta i32) (param $value i32) (result i32)
+            (local $end i32)
+            (i32.store (i32.const 0) (i32.const 1))
+            (drop (call_indirect (type $load) (i32.const 0) (i32.const 0)))
+            (local.set $end (i32.mul (memory.size) (i32.const 65536)))
+            (call_indirect (type $grow) (local.get $delta) (local.get $value) (i32.const 2))
+            (i32.store (i32.add (local.get $end) (i32.const 4)) (i32.const 7))
+            (i32.add
+                (i32.load (local.get $end))
+                (i32.load (i32.add (local.get $end) (i32.const 4)))
+            )
+        )
+
+        ;; Same as `grow_in_callee` but the callee grows the memory via a host function.
+        (func (export "host_grow_in_callee") (param $delta i32) (result i32)
+            (local $end i32)
+            (i32.store (i32.const 0) (i32.const 1))
+            (drop (call $load (i32.const 0)))
+            (local.set $end (i32.mul (memory.size) (i32.const 65536)))
+            (call $host_grow (local.get $delta))
+            (i32.store (local.get $end) (i32.const 11))
+            (i32.load (local.get $end))
+        )
+
+        ;; Grows its memory while the cached entities of the callee are parked
+        ;; and then calls the callee to access the new pages.
+        (func (export "grow_in_caller") (param $delta i32) (result i32)
+            (local $end i32)
+            (drop (call_indirect (type $load) (i32.const 0) (i32.const 0)))
+            (local.set $end (i32.mul (memory.size) (i32.const 65536)))
+            (drop (memory.grow (local.get $delta)))
+            (call_indirect (type $store)
+                (local.get $end) (i32.const 13) (i32.const 1)
+            )
+            (i32.load (local.get $end))
+        )
+    )
+"#;
+
+/// Returns the `host_grow` host function that grows the memory stored in the [`Store`] data.
+fn host_grow(store: &mut Store<Option<Memory>>) -> Func {
+    Func::wrap(store, |mut caller: Caller<Option<Memory>>, delta: u32| {
+        let memory = caller.data().unwrap();
+        memory
+            .grow(&mut caller, Pages::new(delta).unwrap())
+            .unwrap();
+    })
+}
+
+/// Instantiates [`CALLER`] and [`CALLEE`].
+///
+/// If `shared` is `true` both share the same linear memory exported by the callee.
+/// Otherwise the caller uses a host defined linear memory.
+fn setup(shared: bool) -> (Store<Option<Memory>>, Instance, Memory) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, None);
+    let mut linker = <Linker<Option<Memory>>>::new(&engine);
+    let host_grow = host_grow(&mut store);
+    linker.define("env", "host_grow", host_grow).unwrap();
+    let callee = Module::new(&engine, &wat::parse_str(CALLEE).unwrap()[..]).unwrap();
+    let callee = linker
+        .instantiate(&mut store, &callee)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    *store.data_mut() = callee.get_memory(&store, "mem");
+    let caller_memory = match shared {
+        true => callee.get_memory(&store, "mem").unwrap(),
+        false => Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap(),
+    };
+    linker.define("env", "mem", caller_memory).unwrap();
+    for name in ["load", "store", "grow_store", "host_grow"] {
+        let func = callee.get_func(&store, name).unwrap();
+        linker.define("callee", name, func).unwrap();
+    }
+    let caller = Module::new(&engine, &wat::parse_str(CALLER).unwrap()[..]).unwrap();
+    let caller = linker
+        .instantiate(&mut store, &caller)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, caller, caller_memory)
+}
+
+#[test]
+fn ping_pong_separate_memories() {
+    let (mut store, caller, _) = setup(false);
+    let ping_pong = caller
+        .get_typed_func::<(i32, i32, i32), i32>(&store, "ping_pong")
+        .unwrap();
+    // The callee memory is never written so the callee must always load zero.
+    assert_eq!(ping_pong.call(&mut store, (8, 42, 100)).unwrap(), 0);
+}
+
+#[test]
+fn ping_pong_shared_memory() {
+    let (mut store, caller, _) = setup(true);
+    let ping_pong = caller
+        .get_typed_func::<(i32, i32, i32), i32>(&store, "ping_pong")
+        .unwrap();
+    assert_eq!(ping_pong.call(&mut store, (8, 42, 100)).unwrap(), 42);
+}
+
+#[test]
+fn grow_in_callee_while_caller_is_parked() {
+    let (mut store, caller, memory) = setup(true);
+    let grow_in_callee = caller
+        .get_typed_func::<(i32, i32), i32>(&store, "grow_in_callee")
+        .unwrap();
+    // Grow by many pages so that the linear memory is likely to be reallocated.
+    assert_eq!(grow_in_callee.call(&mut store, (100, 5)).unwrap(), 5 + 7);
+    assert_eq!(memory.current_pages(&store), Pages::new(101).unwrap());
+}
+
+#[test]
+fn host_grow_in_callee_while_caller_is_parked() {
+    let (mut store, caller, memory) = setup(true);
+    let host_grow_in_callee = caller
+        .get_typed_func::<i32, i32>(&store, "host_grow_in_callee")
+        .unwrap();
+    assert_eq!(host_grow_in_callee.call(&mut store, 100).unwrap(), 11);
+    assert_eq!(memory.current_pages(&store), Pages::new(101).unwrap());
+}
+
+#[test]
+fn grow_in_caller_while_callee_is_parked() {
+    let (mut store, caller, memory) = setup(true);
+    let grow_in_caller = caller
+        .get_typed_func::<i32, i32>(&store, "grow_in_caller")
+        .unwrap();
+    assert_eq!(grow_in_caller.call(&mut store, 100).unwrap(), 13);
+    assert_eq!(memory.current_pages(&store), Pages::new(101).unwrap());
+}
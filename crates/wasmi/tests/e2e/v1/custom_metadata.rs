@@ -0,0 +1,287 @@
+//! Tests for the metadata parsed from the `producers` and `target_features` custom sections.
+
+use wasmi::{Engine, FeaturePrefix, Module, ParseWarning};
+
+/// Appends `value` in its unsigned LEB128 encoding to `bytes`.
+fn push_leb128(bytes: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Appends the length prefixed `name` to `bytes`.
+fn push_name(bytes: &mut Vec<u8>, name: &str) {
+    push_leb128(bytes, name.len());
+    bytes.extend_from_slice(name.as_bytes());
+}
+
+/// Returns an encoded custom section called `name` with the `payload`.
+fn custom_section(name: &str, payload: &[u8]) -> Vec<u8> {
+    let mut contents = Vec::new();
+    push_name(&mut contents, name);
+    contents.extend_from_slice(payload);
+    let mut section = vec![0x00];
+    push_leb128(&mut section, contents.len());
+    section.extend(contents);
+    section
+}
+
+/// Returns the payload of a `producers` section with `fields`.
+fn producers(fields: &[(&str, &[(&str, &str)])]) -> Vec<u8> {
+    let mut payload = Vec::new();
+    push_leb128(&mut payload, fields.len());
+    for (name, values) in fields {
+        push_name(&mut payload, name);
+        push_leb128(&mut payload, values.len());
+        for (name, version) in *values {
+            push_name(&mut payload, name);
+            push_name(&mut payload, version);
+        }
+    }
+    payload
+}
+
+/// Returns the payload of a `target_features` section with `features`.
+fn target_features(features: &[(u8, &str)]) -> Vec<u8> {
+    let mut payload = Vec::new();
+    push_leb128(&mut payload, features.len());
+    for (prefix, name) in features {
+        payload.push(*prefix);
+        push_name(&mut payload, name);
+    }
+    payload
+}
+
+/// Creates a [`Module`] from a Wasm binary with all `sections` appended.
+///
+/// The Wasm binary has code and data sections so that the custom
+/// sections are parsed after all other sections.
+fn module_with(sections: &[Vec<u8>]) -> Module {
+    let mut wasm = wat::parse_str(
+        r#"
+        (module
+            (memory 1)
+            (func (export "f") (result i32) (i32.const 42))
+            (data (i32.const 0) "hello")
+        )
+        "#,
+    )
+    .unwrap();
+    for section in sections {
+        wasm.extend_from_slice(section);
+    }
+    Module::new(&Engine::default(), &wasm[..]).unwrap()
+}
+
+#[test]
+fn missing_sections() {
+    let module = module_with(&[]);
+    assert!(module.producers().is_none());
+    assert!(module.target_features().is_none());
+    assert!(module.parse_warnings().is_empty());
+}
+
+#[test]
+fn well_formed_producers() {
+    let module = module_with(&[custom_section(
+        "producers",
+        &producers(&[
+            ("language", &[("Rust", "")]),
+            ("processed-by", &[("rustc", "1.78.0"), ("wasm-opt", "117")]),
+        ]),
+    )]);
+    assert!(module.parse_warnings().is_empty());
+    let producers = module.producers().unwrap();
+    assert_eq!(producers.fields().len(), 2);
+    assert_eq!(producers.language().len(), 1);
+    assert_eq!(producers.language()[0].name(), "Rust");
+    assert_eq!(producers.language()[0].version(), "");
+    let processed_by = producers.processed_by();
+    assert_eq!(processed_by.len(), 2);
+    assert_eq!(processed_by[0].name(), "rustc");
+    assert_eq!(processed_by[0].version(), "1.78.0");
+    assert_eq!(processed_by[1].name(), "wasm-opt");
+    assert_eq!(processed_by[1].version(), "117");
+    assert!(producers.sdk().is_empty());
+    assert!(producers.get("unknown").is_none());
+}
+
+#[test]
+fn well_formed_target_features() {
+    let module = module_with(&[custom_section(
+        "target_features",
+        &target_features(&[(b'+', "bulk-memory"), (b'-', "atomics"), (b'=', "simd128")]),
+    )]);
+    assert!(module.parse_warnings().is_empty());
+    let features = module.target_features().unwrap();
+    assert_eq!(features.len(), 3);
+    assert_eq!(features[0].prefix(), FeaturePrefix::Used);
+    assert_eq!(features[0].name(), "bulk-memory");
+    assert_eq!(features[1].prefix(), FeaturePrefix::Disallowed);
+    assert_eq!(features[1].name(), "atomics");
+    assert_eq!(features[2].prefix(), FeaturePrefix::Required);
+    assert_eq!(features[2].to_string(), "=simd128");
+}
+
+#[test]
+fn sections_before_other_sections() {
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    wasm.extend(custom_section(
+        "target_features",
+        &target_features(&[(b'+', "mutable-globals")]),
+    ));
+    wasm.extend(custom_section(
+        "producers",
+        &producers(&[("sdk", &[("Emscripten", "3.1.0")])]),
+    ));
+    wasm.extend(wat::parse_str("(module (func))").unwrap()[8..].iter());
+    let module = Module::new(&Engine::default(), &wasm[..]).unwrap();
+    assert!(module.parse_warnings().is_empty());
+    assert_eq!(
+        module.target_features().unwrap()[0].name(),
+        "mutable-globals"
+    );
+    assert_eq!(module.producers().unwrap().sdk()[0].name(), "Emscripten");
+}
+
+#[test]
+fn duplicate_field() {
+    let module = module_with(&[custom_section(
+        "producers",
+        &producers(&[
+            ("language", &[("C", "")]),
+            ("sdk", &[("wasi-sdk", "21")]),
+            ("language", &[("Rust", "")]),
+        ]),
+    )]);
+    let producers = module.producers().unwrap();
+    assert_eq!(producers.fields().len(), 2);
+    assert_eq!(producers.language()[0].name(), "C");
+    assert_eq!(producers.sdk()[0].name(), "wasi-sdk");
+    let warnings = module.parse_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(
+        &warnings[0],
+        ParseWarning::DuplicateField { field, .. } if &**field == "language"
+    ));
+}
+
+#[test]
+fn duplicate_sections() {
+    let module = module_with(&[
+        custom_section("producers", &producers(&[("language", &[("C", "")])])),
+        custom_section("target_features", &target_features(&[(b'+', "simd128")])),
+        custom_section("producers", &producers(&[("language", &[("Rust", "")])])),
+        custom_section("target_features", &target_features(&[(b'-', "simd128")])),
+    ]);
+    assert_eq!(module.producers().unwrap().language()[0].name(), "C");
+    assert_eq!(
+        module.target_features().unwrap()[0].prefix(),
+        FeaturePrefix::Used
+    );
+    let warnings = module.parse_warnings();
+    assert_eq!(warnings.len(), 2);
+    assert!(matches!(
+        warnings[0],
+        ParseWarning::DuplicateSection {
+            section: "producers",
+            ..
+        }
+    ));
+    assert!(matches!(
+        warnings[1],
+        ParseWarning::DuplicateSection {
+            section: "target_features",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn truncated_producers() {
+    let payload = producers(&[("language", &[("Rust", "1.78.0")])]);
+    for len in 0..payload.len() {
+        let module = module_with(&[custom_section("producers", &payload[..len])]);
+        assert!(module.producers().is_none(), "truncated at {len}");
+        let warnings = module.parse_warnings();
+        assert_eq!(warnings.len(), 1, "truncated at {len}");
+        assert!(matches!(
+            warnings[0],
+            ParseWarning::MalformedSection {
+                section: "producers",
+                ..
+            }
+        ));
+        // The malformed section does not affect the rest of the module.
+        assert!(module.get_export("f").is_some());
+    }
+}
+
+#[test]
+fn truncated_target_features() {
+    let payload = target_features(&[(b'+', "bulk-memory"), (b'+', "sign-ext")]);
+    for len in 0..payload.len() {
+        let module = module_with(&[custom_section("target_features", &payload[..len])]);
+        assert!(module.target_features().is_none(), "truncated at {len}");
+        let warnings = module.parse_warnings();
+        assert_eq!(warnings.len(), 1, "truncated at {len}");
+        assert!(matches!(
+            warnings[0],
+            ParseWarning::MalformedSection {
+                section: "target_features",
+                ..
+            }
+        ));
+    }
+}
+
+#[test]
+fn malformed_target_features() {
+    // Unknown feature prefix.
+    let module = module_with(&[custom_section(
+        "target_features",
+        &target_features(&[(b'+', "simd128"), (b'?', "atomics")]),
+    )]);
+    assert!(module.target_features().is_none());
+    assert!(module.parse_warnings()[0]
+        .to_string()
+        .contains("unknown feature prefix"));
+    // Trailing bytes after the last feature.
+    let mut payload = target_features(&[(b'+', "simd128")]);
+    payload.push(0x00);
+    let module = module_with(&[custom_section("target_features", &payload)]);
+    assert!(module.target_features().is_none());
+    assert!(module.parse_warnings()[0]
+        .to_string()
+        .contains("trailing bytes"));
+}
+
+#[test]
+fn malformed_producers_then_well_formed() {
+    // A malformed section still counts as the first occurrence.
+    let module = module_with(&[
+        custom_section("producers", &[0x01]),
+        custom_section("producers", &producers(&[("language", &[("C", "")])])),
+    ]);
+    assert!(module.producers().is_none());
+    let warnings = module.parse_warnings();
+    assert_eq!(warnings.len(), 2);
+    assert!(matches!(warnings[0], ParseWarning::MalformedSection { .. }));
+    assert!(matches!(warnings[1], ParseWarning::DuplicateSection { .. }));
+}
+
+#[test]
+fn metadata_survives_clone_into() {
+    let module = module_with(&[custom_section(
+        "target_features",
+        &target_features(&[(b'+', "simd128")]),
+    )]);
+    let cloned = module.clone_into(&Engine::default()).unwrap();
+    assert_eq!(cloned.target_features(), module.target_features());
+}
@@ -0,0 +1,131 @@
+//! Tests for linear memories with custom page sizes of the `custom-page-sizes` Wasm proposal.
+
+use assert_matches::assert_matches;
+use wasmi::{
+    core::{Pages, ValueType},
+    errors::{MemoryError, TableError},
+    Config,
+    Engine,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+    TableType,
+};
+
+/// Creates a new [`Store`] with the `custom-page-sizes` proposal enabled or disabled.
+fn store(custom_page_sizes: bool) -> Store<()> {
+    let mut config = Config::default();
+    config.wasm_custom_page_sizes(custom_page_sizes);
+    Store::new(&Engine::new(&config), ())
+}
+
+/// Returns a [`MemoryType`] with pages of `2^page_size_log2` bytes.
+fn memory_type(min: u32, max: Option<u32>, page_size_log2: u8) -> MemoryType {
+    MemoryType::builder()
+        .min(min)
+        .max(max)
+        .page_size_log2(page_size_log2)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn grow_4kib_pages() {
+    let mut store = store(true);
+    let ty = memory_type(0, None, 12);
+    assert_eq!(ty.page_size(), 4096);
+    let memory = Memory::new(&mut store, ty).unwrap();
+    let old = memory.grow(&mut store, Pages::from(3)).unwrap();
+    assert_eq!(old, Pages::from(0));
+    assert_eq!(memory.current_pages(&store), Pages::from(3));
+    assert_eq!(memory.data(&store).len(), 3 * 4096);
+    assert_eq!(memory.ty(&store).page_size_log2(), 12);
+}
+
+#[test]
+fn grow_byte_pages_beyond_default_limit() {
+    let mut store = store(true);
+    let memory = Memory::new(&mut store, memory_type(1, None, 0)).unwrap();
+    let delta = Pages::new_for_page_size(70_000, 0).unwrap();
+    memory.grow(&mut store, delta).unwrap();
+    assert_eq!(u32::from(memory.current_pages(&store)), 70_001);
+    assert_eq!(memory.data(&store).len(), 70_001);
+}
+
+#[test]
+fn grow_respects_maximum() {
+    let mut store = store(true);
+    let memory = Memory::new(&mut store, memory_type(1, Some(2), 12)).unwrap();
+    memory.grow(&mut store, Pages::from(1)).unwrap();
+    assert_matches!(
+        memory.grow(&mut store, Pages::from(1)),
+        Err(MemoryError::OutOfBoundsGrowth)
+    );
+    assert_eq!(memory.data(&store).len(), 2 * 4096);
+}
+
+#[test]
+fn requires_config() {
+    let mut store = store(false);
+    assert_matches!(
+        Memory::new(&mut store, memory_type(1, None, 12)),
+        Err(MemoryError::CustomPageSizesDisabled)
+    );
+    // The default page size is always supported.
+    assert!(Memory::new(&mut store, memory_type(1, None, 16)).is_ok());
+}
+
+#[test]
+fn invalid_memory_types() {
+    assert_matches!(
+        MemoryType::builder().page_size_log2(17).build(),
+        Err(MemoryError::InvalidPageSize { page_size_log2: 17 })
+    );
+    // 2^20 pages of 4 KiB make up 4 GiB.
+    assert!(MemoryType::builder()
+        .min(1 << 20)
+        .page_size_log2(12)
+        .build()
+        .is_ok());
+    assert_matches!(
+        MemoryType::builder()
+            .min((1 << 20) + 1)
+            .page_size_log2(12)
+            .build(),
+        Err(MemoryError::InvalidMemoryType)
+    );
+    assert_eq!(
+        MemoryType::builder().min(1).max(Some(2)).build().unwrap(),
+        MemoryType::new(1, Some(2)).unwrap()
+    );
+}
+
+#[test]
+fn import_requires_matching_page_size() {
+    let mut store = store(true);
+    let memory = Memory::new(&mut store, memory_type(1, None, 12)).unwrap();
+    let mut linker = <Linker<()>>::new(store.engine());
+    linker.define("env", "mem", memory).unwrap();
+    let wasm = wat::parse_str(r#"(module (import "env" "mem" (memory 1)))"#).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    assert!(linker.instantiate(&mut store, &module).is_err());
+}
+
+#[test]
+fn table_type_builder() {
+    let ty = TableType::builder(ValueType::FuncRef)
+        .min(1)
+        .max(Some(10))
+        .build()
+        .unwrap();
+    assert_eq!(ty, TableType::new(ValueType::FuncRef, 1, Some(10)));
+    assert_matches!(
+        TableType::builder(ValueType::FuncRef)
+            .min(2)
+            .max(Some(1))
+            .build(),
+        Err(TableError::InvalidTableType { min: 2, max: 1 })
+    );
+}
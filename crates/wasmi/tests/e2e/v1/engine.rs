@@ -0,0 +1,118 @@
+//! Tests to check that [`Engine`]s with the same [`Config`] behave identically.
+
+use wasmi::{
+    errors::{ErrorKind, InstantiationError},
+    CompilationMode,
+    Config,
+    Engine,
+    FuelCosts,
+    Linker,
+    Module,
+    Store,
+};
+
+/// A Wasm module with multiple imports and exports.
+const WAT: &str = r#"
+    (module
+        (import "env" "zeta" (func (param i32)))
+        (import "host" "alpha" (global i32))
+        (import "env" "beta" (memory 1))
+        (import "env" "alpha" (func (result i64)))
+        (func (export "run") (param i32) (result i32)
+            (local.get 0)
+        )
+        (global (export "g") i32 (i32.const 0))
+        (export "mem" (memory 0))
+        (export "a" (func 0))
+    )
+"#;
+
+/// Compiles the [`WAT`] module for an [`Engine`] created with `config`.
+fn module(config: &Config) -> Module {
+    let engine = Engine::new(config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(&engine, &wasm[..]).unwrap()
+}
+
+/// Returns a description of the imports and exports of `module` in their iteration order.
+fn describe(module: &Module) -> Vec<String> {
+    let imports = module
+        .imports()
+        .map(|import| format!("{}::{}: {:?}", import.module(), import.name(), import.ty()));
+    let exports = module
+        .exports()
+        .map(|export| format!("{}: {:?}", export.name(), export.ty()));
+    imports.chain(exports).collect()
+}
+
+/// Returns the error message of instantiating `module` without any definitions.
+fn instantiation_error(module: &Module) -> String {
+    let mut store = Store::new(module.engine(), ());
+    <Linker<()>>::new(module.engine())
+        .instantiate(&mut store, module)
+        .unwrap_err()
+        .to_string()
+}
+
+#[test]
+fn same_config_fingerprint() {
+    let a = Engine::default();
+    let b = Engine::default();
+    assert!(!Engine::same(&a, &b));
+    assert!(Engine::same_config_fingerprint(&a, &b));
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let fuel = Engine::new(&config);
+    assert!(!Engine::same_config_fingerprint(&a, &fuel));
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let lazy = Engine::new(&config);
+    assert!(!Engine::same_config_fingerprint(&a, &lazy));
+    let mut config = Config::default();
+    config.deterministic_only(true);
+    let deterministic = Engine::new(&config);
+    assert!(!Engine::same_config_fingerprint(&a, &deterministic));
+}
+
+#[test]
+fn same_config_fingerprint_after_reprice() {
+    let a = Engine::default();
+    let b = Engine::default();
+    let mut costs = FuelCosts::default();
+    costs.set_base(2);
+    b.reprice_fuel(&costs).unwrap();
+    assert!(!Engine::same_config_fingerprint(&a, &b));
+    a.reprice_fuel(&costs).unwrap();
+    assert!(Engine::same_config_fingerprint(&a, &b));
+}
+
+#[test]
+fn same_config_same_behavior() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let a = module(&config);
+    let b = module(&config);
+    assert!(Engine::same_config_fingerprint(a.engine(), b.engine()));
+    assert_eq!(describe(&a), describe(&b));
+    assert_eq!(instantiation_error(&a), instantiation_error(&b));
+}
+
+#[test]
+fn module_of_other_engine_is_rejected() {
+    let a = module(&Config::default());
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let error = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &a)
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::EngineMismatch)
+    ));
+    // Note: the module can be instantiated once it has been cloned into the engine.
+    let b = a.clone_into(&engine).unwrap();
+    assert_ne!(
+        instantiation_error(&b),
+        "module and store do not use the same engine"
+    );
+}
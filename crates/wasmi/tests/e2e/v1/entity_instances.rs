@@ -0,0 +1,131 @@
+//! Tests for querying the instances that share a [`Memory`], [`Table`] or [`Global`].
+
+use wasmi::{
+    core::ValueType,
+    Engine,
+    Error,
+    Extern,
+    Global,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Mutability,
+    Store,
+    Table,
+    TableType,
+    Value,
+};
+
+/// Imports a linear memory, a table and a global variable.
+const IMPORTER: &str = r#"
+    (module
+        (import "env" "memory" (memory 1))
+        (import "env" "table" (table 1 funcref))
+        (import "env" "global" (global i32))
+    )
+"#;
+
+/// Defines and exports a linear memory, a table and a global variable.
+const EXPORTER: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (table (export "table") 1 funcref)
+        (global (export "global") i32 (i32.const 0))
+    )
+"#;
+
+/// Imports the entities like [`IMPORTER`] but traps in its `start` function.
+const TRAPPING: &str = r#"
+    (module
+        (import "env" "memory" (memory 1))
+        (import "env" "table" (table 1 funcref))
+        (import "env" "global" (global i32))
+        (func $start (unreachable))
+        (start $start)
+    )
+"#;
+
+/// Instantiates `wasm` with the entities defined in `linker`.
+fn instantiate(store: &mut Store<()>, linker: &Linker<()>, wasm: &str) -> Result<Instance, Error> {
+    let module = Module::new(store.engine(), &wat::parse_str(wasm).unwrap()[..]).unwrap();
+    linker.instantiate(&mut *store, &module)?.start(&mut *store)
+}
+
+/// Defines a host linear memory, table and global variable in a new [`Linker`].
+fn setup() -> (Store<()>, Linker<()>, Memory, Table, Global) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let table = Table::new(
+        &mut store,
+        TableType::new(ValueType::FuncRef, 1, None),
+        Value::default(ValueType::FuncRef),
+    )
+    .unwrap();
+    let global = Global::new(&mut store, Value::I32(0), Mutability::Const);
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .define("env", "memory", memory)
+        .unwrap()
+        .define("env", "table", table)
+        .unwrap()
+        .define("env", "global", global)
+        .unwrap();
+    (store, linker, memory, table, global)
+}
+
+#[test]
+fn importers_share_host_entities() {
+    let (mut store, linker, memory, table, global) = setup();
+    assert!(memory.instances(&store).is_empty());
+    let a = instantiate(&mut store, &linker, IMPORTER).unwrap();
+    let b = instantiate(&mut store, &linker, IMPORTER).unwrap();
+    assert_eq!(memory.instances(&store), [a, b]);
+    assert_eq!(table.instances(&store), [a, b]);
+    assert_eq!(global.instances(&store), [a, b]);
+}
+
+#[test]
+fn exporter_and_importer_share_entities() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let exporter = instantiate(&mut store, &Linker::new(&engine), EXPORTER).unwrap();
+    let memory = exporter.get_memory(&store, "memory").unwrap();
+    let table = exporter.get_table(&store, "table").unwrap();
+    let global = exporter.get_global(&store, "global").unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .define("env", "memory", memory)
+        .unwrap()
+        .define("env", "table", table)
+        .unwrap()
+        .define("env", "global", global)
+        .unwrap();
+    let importer = instantiate(&mut store, &linker, IMPORTER).unwrap();
+    assert_eq!(memory.instances(&store), [exporter, importer]);
+    assert_eq!(table.instances(&store), [exporter, importer]);
+    assert_eq!(global.instances(&store), [exporter, importer]);
+    assert!(exporter.imports(&store).is_empty());
+    // Note: imports are returned in the order of `Module::imports`.
+    let [Extern::Table(t), Extern::Memory(m), Extern::Global(g)] = importer.imports(&store)[..]
+    else {
+        panic!("unexpected imports: {:?}", importer.imports(&store))
+    };
+    // Note: the imported entities are the ones shared with the exporter.
+    assert_eq!(m.instances(&store), [exporter, importer]);
+    assert_eq!(t.instances(&store), [exporter, importer]);
+    assert_eq!(g.instances(&store), [exporter, importer]);
+}
+
+#[test]
+fn failed_instantiation_is_removed() {
+    let (mut store, linker, memory, table, global) = setup();
+    let a = instantiate(&mut store, &linker, IMPORTER).unwrap();
+    instantiate(&mut store, &linker, TRAPPING).unwrap_err();
+    let b = instantiate(&mut store, &linker, IMPORTER).unwrap();
+    assert_eq!(memory.instances(&store), [a, b]);
+    assert_eq!(table.instances(&store), [a, b]);
+    assert_eq!(global.instances(&store), [a, b]);
+}
@@ -0,0 +1,182 @@
+//! Tests to check that branches on float comparisons respect the IEEE 754 semantics for NaN inputs.
+//!
+//! # Note
+//!
+//! Branching on the negation of a float comparison is fused into a negated branch instruction
+//! such as `BranchF32NotLt` since `!(a < b)` is not the same as `a >= b` if any input is NaN.
+//! The tests check all fused branch forms with 16-bit encodable and with fallback branch offsets.
+
+use wasmi::{
+    core::{F32, F64},
+    Engine,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+
+/// The number of `br_table` targets used to exceed 16-bit encodable branch offsets.
+const LARGE_PADDING: usize = i16::MAX as usize + 1;
+
+/// A Wasm comparison operator name and its reference semantics in Rust.
+type Comparison<T> = (&'static str, fn(T, T) -> bool);
+
+/// The names of the test functions of the module created by [`module_wat`].
+///
+/// Each function returns `1` if its comparison holds and `0` otherwise.
+const FUNCS: [&str; 3] = ["forward", "forward_eqz", "if_"];
+
+/// Returns a Wasm module with functions branching conditionally on `{ty}.{op}`.
+///
+/// All branches of the returned functions skip `len_padding` instruction words.
+fn module_wat(ty: &str, op: &str, len_padding: usize) -> String {
+    let padding = format!(
+        "(block (br_table {} 0 (local.get $pad)))",
+        "0 ".repeat(len_padding)
+    );
+    format!(
+        r#"
+        (module
+            (func (export "forward") (param $lhs {ty}) (param $rhs {ty}) (result i32)
+                (local $pad i32)
+                (block $taken
+                    (br_if $taken ({ty}.{op} (local.get $lhs) (local.get $rhs)))
+                    {padding}
+                    (return (i32.const 0))
+                )
+                (i32.const 1)
+            )
+            (func (export "forward_eqz") (param $lhs {ty}) (param $rhs {ty}) (result i32)
+                (local $pad i32)
+                (block $not_taken
+                    (br_if $not_taken (i32.eqz ({ty}.{op} (local.get $lhs) (local.get $rhs))))
+                    {padding}
+                    (return (i32.const 1))
+                )
+                (i32.const 0)
+            )
+            (func (export "if_") (param $lhs {ty}) (param $rhs {ty}) (result i32)
+                (local $pad i32)
+                (if ({ty}.{op} (local.get $lhs) (local.get $rhs))
+                    (then
+                        {padding}
+                        (return (i32.const 1))
+                    )
+                )
+                (i32.const 0)
+            )
+        )
+        "#,
+    )
+}
+
+/// Instantiates the Wasm module of [`module_wat`] for the given parameters.
+fn instantiate(store: &mut Store<()>, ty: &str, op: &str, len_padding: usize) -> Instance {
+    let wasm = wat::parse_str(module_wat(ty, op, len_padding)).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    Linker::new(store.engine())
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Calls the exported function `name` of `instance` with `lhs` and `rhs`.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, lhs: &Value, rhs: &Value) -> i32 {
+    let mut result = [Value::I32(0)];
+    instance
+        .get_func(&*store, name)
+        .unwrap()
+        .call(&mut *store, &[lhs.clone(), rhs.clone()], &mut result)
+        .unwrap();
+    result[0].i32().unwrap()
+}
+
+/// Asserts that branches on `{ty}.{op}` agree with `expected` for all pairs of `inputs`.
+fn assert_branching<T, F>(ty: &str, op: &str, inputs: &[T], expected: fn(T, T) -> bool)
+where
+    T: Copy + Into<F>,
+    F: Into<Value>,
+{
+    let mut store = <Store<()>>::new(&Engine::default(), ());
+    for len_padding in [1, LARGE_PADDING] {
+        let instance = instantiate(&mut store, ty, op, len_padding);
+        for &lhs in inputs {
+            for &rhs in inputs {
+                let lhs_value: Value = Into::<F>::into(lhs).into();
+                let rhs_value: Value = Into::<F>::into(rhs).into();
+                for func in FUNCS {
+                    let actual = call(&mut store, &instance, func, &lhs_value, &rhs_value);
+                    assert_eq!(
+                        actual,
+                        i32::from(expected(lhs, rhs)),
+                        "{func} with {ty}.{op} for lhs = {lhs_value:?} and rhs = {rhs_value:?} and padding = {len_padding}",
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// The `f32` inputs of the tests including quiet, negative and signalling NaNs.
+const F32_INPUTS: [f32; 12] = [
+    0.0,
+    -0.0,
+    1.0,
+    -1.0,
+    f32::MIN_POSITIVE,
+    f32::MAX,
+    f32::INFINITY,
+    f32::NEG_INFINITY,
+    f32::NAN,
+    -f32::NAN,
+    f32::from_bits(0x7FA0_0000),
+    f32::from_bits(0xFFFF_FFFF),
+];
+
+/// The `f64` inputs of the tests including quiet, negative and signalling NaNs.
+const F64_INPUTS: [f64; 12] = [
+    0.0,
+    -0.0,
+    1.0,
+    -1.0,
+    f64::MIN_POSITIVE,
+    f64::MAX,
+    f64::INFINITY,
+    f64::NEG_INFINITY,
+    f64::NAN,
+    -f64::NAN,
+    f64::from_bits(0x7FF4_0000_0000_0000),
+    f64::from_bits(0xFFFF_FFFF_FFFF_FFFF),
+];
+
+#[test]
+fn f32_branch_nan() {
+    let ops: [Comparison<f32>; 6] = [
+        ("eq", |a, b| a == b),
+        ("ne", |a, b| a != b),
+        ("lt", |a, b| a < b),
+        ("le", |a, b| a <= b),
+        ("gt", |a, b| a > b),
+        ("ge", |a, b| a >= b),
+    ];
+    for (op, expected) in ops {
+        assert_branching::<_, F32>("f32", op, &F32_INPUTS, expected);
+    }
+}
+
+#[test]
+fn f64_branch_nan() {
+    let ops: [Comparison<f64>; 6] = [
+        ("eq", |a, b| a == b),
+        ("ne", |a, b| a != b),
+        ("lt", |a, b| a < b),
+        ("le", |a, b| a <= b),
+        ("gt", |a, b| a > b),
+        ("ge", |a, b| a >= b),
+    ];
+    for (op, expected) in ops {
+        assert_branching::<_, F64>("f64", op, &F64_INPUTS, expected);
+    }
+}
@@ -8,6 +8,8 @@ use wasmi::{
     Engine,
     Func,
     FuncType,
+    Linker,
+    Module,
     Store,
     Value,
 };
@@ -531,3 +533,114 @@ fn static_type_check_works() {
         ErrorKind::Func(FuncError::MismatchingResultType)
     );
 }
+
+#[test]
+fn static_type_check_reports_mismatching_signatures() {
+    let mut store = test_setup();
+    let identity = Func::wrap(&mut store, |value: i32| value);
+    let error = identity.typed::<(i64, F32), ()>(&mut store).unwrap_err();
+    let (expected, actual) = error.signature_mismatch().unwrap();
+    assert_eq!(
+        expected,
+        &FuncType::new([ValueType::I64, ValueType::F32], [])
+    );
+    assert_eq!(actual, &FuncType::new([ValueType::I32], [ValueType::I32]));
+    let error = identity.typed::<i32, i64>(&mut store).unwrap_err();
+    let (expected, actual) = error.signature_mismatch().unwrap();
+    assert_eq!(expected, &FuncType::new([ValueType::I32], [ValueType::I64]));
+    assert_eq!(actual, &FuncType::new([ValueType::I32], [ValueType::I32]));
+    assert!(identity.typed::<i32, i32>(&mut store).is_ok());
+}
+
+/// Instantiates a Wasm module that calls the imported `env.dynamic` host function.
+///
+/// The host function has the signature `(i32, f64) -> (i64, i32)`.
+/// The exported `run` function forwards its parameters to it and returns its results.
+fn instantiate_dynamic_caller(store: &mut Store<()>, dynamic: Func) -> Func {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "dynamic" (func $dynamic (param i32 f64) (result i64 i32)))
+            (func (export "run") (param i32 f64) (result i64 i32)
+                (call $dynamic (local.get 0) (local.get 1))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(store.engine());
+    linker.define("env", "dynamic", dynamic).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+        .get_func(&*store, "run")
+        .unwrap()
+}
+
+#[test]
+fn dynamic_multi_value_called_from_wasm() {
+    let mut store = test_setup();
+    let ty = FuncType::new(
+        [ValueType::I32, ValueType::F64],
+        [ValueType::I64, ValueType::I32],
+    );
+    let dynamic = Func::new(&mut store, ty.clone(), |_caller, params, results| {
+        let (Value::I32(lhs), Value::F64(rhs)) = (&params[0], &params[1]) else {
+            panic!("unexpected parameters: {params:?}")
+        };
+        results[0] = Value::I64(i64::from(*lhs) * 2);
+        results[1] = Value::I32(f64::from(*rhs) as i32);
+        Ok(())
+    });
+    assert_eq!(dynamic.ty(&store), ty);
+    assert_eq!(
+        dynamic.ty(&store).params(),
+        [ValueType::I32, ValueType::F64]
+    );
+    assert_eq!(
+        dynamic.ty(&store).results(),
+        [ValueType::I64, ValueType::I32]
+    );
+    let run = instantiate_dynamic_caller(&mut store, dynamic);
+    let (a, b) = run
+        .typed::<(i32, F64), (i64, i32)>(&store)
+        .unwrap()
+        .call(&mut store, (21, F64::from(7.5)))
+        .unwrap();
+    assert_eq!((a, b), (42, 7));
+}
+
+#[test]
+fn dynamic_wrong_result_type() {
+    let mut store = test_setup();
+    let ty = FuncType::new(
+        [ValueType::I32, ValueType::F64],
+        [ValueType::I64, ValueType::I32],
+    );
+    let dynamic = Func::new(&mut store, ty, |_caller, _params, results| {
+        results[0] = Value::I64(1);
+        results[1] = Value::F32(F32::from(1.0));
+        Ok(())
+    });
+    let run = instantiate_dynamic_caller(&mut store, dynamic);
+    let error = run
+        .typed::<(i32, F64), (i64, i32)>(&store)
+        .unwrap()
+        .call(&mut store, (1, F64::from(1.0)))
+        .unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::MismatchingHostResultType {
+            index: 1,
+            expected: ValueType::I32,
+            actual: ValueType::F32,
+        })
+    );
+    assert_eq!(
+        error.to_string(),
+        "host function wrote result 1 of type F32 but expected I32"
+    );
+}
@@ -0,0 +1,140 @@
+//! Tests asserting that the identity of [`Func`] is stable regardless of its compilation state.
+
+use std::collections::HashSet;
+use wasmi::{
+    CompilationMode,
+    Config,
+    Engine,
+    Extern,
+    Func,
+    FuncRef,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+
+/// A Wasm module exporting a function `f` that is also accessible via `ref.func` and a table.
+const WAT: &str = r#"
+    (module
+        (table (export "table") 1 funcref)
+        (elem (i32.const 0) $f)
+        (func $f (export "f") (result i32)
+            (i32.const 42)
+        )
+        (func (export "ref_f") (result funcref)
+            (ref.func $f)
+        )
+    )
+"#;
+
+/// A Wasm module importing and re-exporting `f` from the [`WAT`] module.
+const WAT_IMPORTER: &str = r#"
+    (module
+        (import "env" "f" (func $f (result i32)))
+        (export "f" (func $f))
+        (func (export "ref_f") (result funcref)
+            (ref.func $f)
+        )
+    )
+"#;
+
+/// Creates an [`Engine`] using the given [`CompilationMode`].
+fn engine(mode: CompilationMode) -> Engine {
+    let mut config = Config::default();
+    config.compilation_mode(mode);
+    Engine::new(&config)
+}
+
+/// Instantiates `wat` using `linker`.
+fn instantiate(store: &mut Store<()>, linker: &Linker<()>, wat: &str) -> Instance {
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Returns the [`Func`] returned by the exported `ref_f` function of `instance`.
+fn ref_f(store: &mut Store<()>, instance: &Instance) -> Func {
+    let funcref = instance
+        .get_typed_func::<(), FuncRef>(&*store, "ref_f")
+        .unwrap()
+        .call(&mut *store, ())
+        .unwrap();
+    *funcref.func().unwrap()
+}
+
+/// Returns the [`Func`] stored in the exported `table` of `instance` at index 0.
+fn table_f(store: &Store<()>, instance: &Instance) -> Func {
+    let table = instance.get_table(store, "table").unwrap();
+    match table.get(store, 0).unwrap() {
+        Value::FuncRef(funcref) => *funcref.func().unwrap(),
+        value => panic!("unexpected table element: {value:?}"),
+    }
+}
+
+/// Asserts that all [`Func`] obtained for `f` are equal before and after its first call.
+fn assert_stable_identity(mode: CompilationMode) {
+    let engine = engine(mode);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let instance = instantiate(&mut store, &linker, WAT);
+    let export = instance.get_func(&store, "f").unwrap();
+    linker.define("env", "f", export).unwrap();
+    let importer = instantiate(&mut store, &linker, WAT_IMPORTER);
+    let collect = |store: &mut Store<()>| {
+        [
+            instance.get_func(&*store, "f").unwrap(),
+            ref_f(store, &instance),
+            table_f(store, &instance),
+            importer.get_func(&*store, "f").unwrap(),
+            ref_f(store, &importer),
+        ]
+    };
+    let before = collect(&mut store);
+    assert!(before.iter().all(|func| *func == export), "{mode:?}");
+    let result = export
+        .typed::<(), i32>(&store)
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(result, 42);
+    let after = collect(&mut store);
+    assert_eq!(before, after, "{mode:?}");
+    // Equal functions have equal hashes.
+    let set = before.iter().chain(&after).copied().collect::<HashSet<Func>>();
+    assert_eq!(set.len(), 1, "{mode:?}");
+    // Control: functions with distinct identity are not equal.
+    let ref_f_export = instance.get_func(&store, "ref_f").unwrap();
+    assert_ne!(ref_f_export, export);
+}
+
+#[test]
+fn stable_identity_eager() {
+    assert_stable_identity(CompilationMode::Eager);
+}
+
+#[test]
+fn stable_identity_lazy_translation() {
+    assert_stable_identity(CompilationMode::LazyTranslation);
+}
+
+#[test]
+fn stable_identity_lazy() {
+    assert_stable_identity(CompilationMode::Lazy);
+}
+
+#[test]
+fn host_funcs_have_distinct_identity() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let a = Func::wrap(&mut store, || 1_i32);
+    let b = Func::wrap(&mut store, || 1_i32);
+    assert_eq!(a, a);
+    assert_ne!(a, b);
+    assert_eq!(Extern::from(a).into_func(), Some(a));
+}
@@ -0,0 +1,197 @@
+//! Tests for the fuzzing building blocks of the `wasmi::fuzz` module.
+
+use arbitrary::{Arbitrary, Unstructured};
+use wasmi::{
+    core::ValueType,
+    fuzz::{
+        differential,
+        DifferentialOracle,
+        FuzzConfig,
+        FuzzError,
+        FuzzInvocation,
+        FuzzValue,
+        Mismatch,
+        WasmiOracle,
+    },
+    Config,
+    Engine,
+    Module,
+};
+
+/// A Wasm module with functions that trap, mutate state and compute floats.
+const WAT: &str = r#"
+    (module
+        (memory (export "mem") 1)
+        (global $counter (export "counter") (mut i32) (i32.const 0))
+        (func (export "div") (param i32 i32) (result i32)
+            (i32.div_s (local.get 0) (local.get 1))
+        )
+        (func (export "store") (param i32 i64)
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+            (i64.store (local.get 0) (local.get 1))
+        )
+        (func (export "load") (param i32) (result i64)
+            (i64.load (local.get 0))
+        )
+        (func (export "fmix") (param f32 f64) (result f64 f32)
+            (f64.mul (f64.promote_f32 (local.get 0)) (local.get 1))
+            (f32.sqrt (local.get 0))
+        )
+        (func (export "sum") (param i32) (result i64)
+            (local $sum i64)
+            (local.set 0 (i32.and (local.get 0) (i32.const 0xFF)))
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get 0)))
+                    (local.set $sum (i64.add (local.get $sum) (i64.extend_i32_u (local.get 0))))
+                    (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $sum)
+        )
+        (func (export "null") (result funcref)
+            (ref.null func)
+        )
+    )
+"#;
+
+/// Returns `len` pseudo random bytes for `seed`.
+fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..len)
+        .map(|_| {
+            // Note: xorshift64 is good enough to feed `Unstructured`.
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+/// Returns the compiled [`WAT`] module and its Wasm bytes.
+fn module() -> (Module, Vec<u8>) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&Engine::default(), &wasm[..]).unwrap();
+    (module, wasm)
+}
+
+#[test]
+fn arbitrary_invocations_are_type_correct() {
+    let (module, _) = module();
+    for seed in 0..20 {
+        let bytes = random_bytes(seed, 4096);
+        let mut u = Unstructured::new(&bytes);
+        let invocations = FuzzInvocation::arbitrary_sequence(&mut u, &module).unwrap();
+        assert!(!invocations.is_empty());
+        for invocation in &invocations {
+            assert_ne!(&*invocation.func, "null");
+            let ty = module
+                .get_export(&invocation.func)
+                .and_then(|ty| ty.func().cloned())
+                .unwrap();
+            assert_eq!(ty.params().len(), invocation.params.len());
+            for (ty, param) in ty.params().iter().zip(&*invocation.params) {
+                let matches = matches!(
+                    (ty, param),
+                    (ValueType::I32, FuzzValue::I32(_))
+                        | (ValueType::I64, FuzzValue::I64(_))
+                        | (ValueType::F32, FuzzValue::F32(_))
+                        | (ValueType::F64, FuzzValue::F64(_))
+                );
+                assert!(matches, "{ty:?} does not match {param:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn differential_wasmi_configs() {
+    let (module, wasm) = module();
+    let mut compared = 0;
+    for seed in 0..50 {
+        let bytes = random_bytes(seed, 4096);
+        let mut u = Unstructured::new(&bytes);
+        let config = FuzzConfig::arbitrary(&mut u).unwrap().config();
+        let invocations = FuzzInvocation::arbitrary_sequence(&mut u, &module).unwrap();
+        let Ok(mut lhs) = WasmiOracle::new(&config, &wasm) else {
+            // Note: Some configurations disable Wasm proposals used by the module.
+            continue;
+        };
+        let mut rhs = WasmiOracle::new(&Config::default(), &wasm).unwrap();
+        if let Err(mismatch) = differential(&module, &invocations, &mut lhs, &mut rhs) {
+            panic!("seed {seed}: {mismatch}")
+        }
+        compared += 1;
+    }
+    assert!(compared > 0);
+}
+
+/// A [`DifferentialOracle`] that wraps a [`WasmiOracle`] with a bug in `i32.div_s`.
+struct FaultyOracle(WasmiOracle);
+
+impl DifferentialOracle for FaultyOracle {
+    fn call(&mut self, func: &str, params: &[FuzzValue]) -> Result<Vec<FuzzValue>, FuzzError> {
+        let mut results = self.0.call(func, params)?;
+        if func == "div" {
+            if let [FuzzValue::I32(result)] = &mut results[..] {
+                *result = result.wrapping_add(1);
+            }
+        }
+        Ok(results)
+    }
+
+    fn get_global(&mut self, name: &str) -> Option<FuzzValue> {
+        self.0.get_global(name)
+    }
+
+    fn get_memory(&mut self, name: &str) -> Option<&[u8]> {
+        self.0.get_memory(name)
+    }
+}
+
+#[test]
+fn differential_finds_mismatch() {
+    let (module, wasm) = module();
+    let invocations = [
+        FuzzInvocation {
+            func: "div".into(),
+            params: [FuzzValue::I32(1), FuzzValue::I32(0)].into(),
+        },
+        FuzzInvocation {
+            func: "div".into(),
+            params: [FuzzValue::I32(7), FuzzValue::I32(2)].into(),
+        },
+    ];
+    let mut lhs = WasmiOracle::new(&Config::default(), &wasm).unwrap();
+    let mut rhs = FaultyOracle(WasmiOracle::new(&Config::default(), &wasm).unwrap());
+    let mismatch = differential(&module, &invocations, &mut lhs, &mut rhs).unwrap_err();
+    match mismatch {
+        Mismatch::Call {
+            invocation,
+            lhs,
+            rhs,
+        } => {
+            assert_eq!(invocation, invocations[1]);
+            assert_eq!(lhs.unwrap(), [FuzzValue::I32(3)]);
+            assert_eq!(rhs.unwrap(), [FuzzValue::I32(4)]);
+        }
+        mismatch => panic!("unexpected mismatch: {mismatch}"),
+    }
+}
+
+#[test]
+fn differential_finds_state_mismatch() {
+    let (module, wasm) = module();
+    let store = FuzzInvocation {
+        func: "store".into(),
+        params: [FuzzValue::I32(8), FuzzValue::I64(42)].into(),
+    };
+    let mut lhs = WasmiOracle::new(&Config::default(), &wasm).unwrap();
+    let mut rhs = WasmiOracle::new(&Config::default(), &wasm).unwrap();
+    // Note: Only `lhs` executes the `store` invocation.
+    lhs.call(&store.func, &store.params).unwrap();
+    let mismatch = differential(&module, &[], &mut lhs, &mut rhs).unwrap_err();
+    assert!(matches!(mismatch, Mismatch::Global { .. }), "{mismatch}");
+}
@@ -0,0 +1,112 @@
+//! Tests for the caching of global variables accessed by `global.get` and `global.set`.
+
+use wasmi::{Caller, Engine, Global, Linker, Module, Mutability, Store, TypedFunc, Value};
+
+/// A Wasm module with more mutable global variables than there are cache slots.
+///
+/// The exported `run` function increments the mutable global variables in each of
+/// its `n` iterations and returns the sum of all global variables except `$base`.
+/// The host function `env.alloc` is called in every iteration.
+///
+/// # Note
+///
+/// The global variable `$g6` is not replaced with a constant upon translation since
+/// its initial value is the value of the imported global variable `$base`.
+const WASM: &str = r#"
+    (module
+        (import "env" "alloc" (func $alloc))
+        (import "env" "shared" (global $g0 (mut i32)))
+        (import "env" "base" (global $base i32))
+        (global $g1 (mut i32) (i32.const 0))
+        (global $g2 (mut i64) (i64.const 0))
+        (global $g3 (mut i32) (i32.const 0))
+        (global $g4 (mut i32) (i32.const 0))
+        (global $g5 (mut i64) (i64.const 0))
+        (global $g6 i32 (global.get $base))
+        (func (export "run") (param $n i32) (result i64)
+            (block $break
+                (loop $continue
+                    (br_if $break (i32.eqz (local.get $n)))
+                    (global.set $g0 (i32.add (global.get $g0) (i32.const 1)))
+                    (global.set $g4 (i32.add (global.get $g4) (i32.const 5)))
+                    (global.set $g1 (i32.add (global.get $g1) (i32.const 2)))
+                    (call $alloc)
+                    (global.set $g5 (i64.add (global.get $g5) (i64.const 6)))
+                    (global.set $g2 (i64.add (global.get $g2) (i64.const 3)))
+                    (global.set $g3 (i32.add (global.get $g3) (global.get $g6)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (i64.add
+                (i64.add
+                    (i64.extend_i32_u
+                        (i32.add
+                            (i32.add (global.get $g0) (global.get $g1))
+                            (i32.add (global.get $g3) (global.get $g4))
+                        )
+                    )
+                    (i64.add (global.get $g2) (global.get $g5))
+                )
+                (i64.extend_i32_u (global.get $g6))
+            )
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] twice sharing the `env.shared` and `env.base` global variables.
+///
+/// The `env.alloc` host function allocates `allocs` new global variables in the [`Store`].
+/// Returns the `run` functions of both instances and the shared global variable.
+fn setup(store: &mut Store<()>, allocs: usize) -> ([TypedFunc<i32, i64>; 2], Global) {
+    let engine = store.engine().clone();
+    let shared = Global::new(&mut *store, Value::I32(4), Mutability::Var);
+    let base = Global::new(&mut *store, Value::I32(4), Mutability::Const);
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "alloc", move |mut caller: Caller<()>| {
+            for _ in 0..allocs {
+                Global::new(&mut caller, Value::I32(0), Mutability::Var);
+            }
+        })
+        .unwrap();
+    linker.define("env", "shared", shared).unwrap();
+    linker.define("env", "base", base).unwrap();
+    let wasm = wat::parse_str(WASM).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let run = [(); 2].map(|_| {
+        linker
+            .instantiate(&mut *store, &module)
+            .unwrap()
+            .start(&mut *store)
+            .unwrap()
+            .get_typed_func::<i32, i64>(&*store, "run")
+            .unwrap()
+    });
+    (run, shared)
+}
+
+/// Returns the expected result of calling `run` with `n` after `before` iterations of the same instance.
+///
+/// The shared global variable has been incremented `total` times by all instances.
+fn expected(total: i64, before: i64, n: i64) -> i64 {
+    let iterations = before + n;
+    (4 + total) + iterations * (2 + 3 + 4 + 5 + 6) + 4
+}
+
+#[test]
+fn interleaved_globals() {
+    for allocs in [0, 1, 100] {
+        let mut store = Store::new(&Engine::default(), ());
+        let ([run_a, run_b], shared) = setup(&mut store, allocs);
+        assert_eq!(run_a.call(&mut store, 10).unwrap(), expected(10, 0, 10));
+        assert_eq!(run_b.call(&mut store, 3).unwrap(), expected(13, 0, 3));
+        assert_eq!(run_a.call(&mut store, 5).unwrap(), expected(18, 10, 5));
+        assert_eq!(shared.get(&store).i32(), Some(4 + 18));
+        shared.set(&mut store, Value::I32(100)).unwrap();
+        assert_eq!(
+            run_b.call(&mut store, 1).unwrap(),
+            101 + 4 * (2 + 3 + 4 + 5 + 6) + 4
+        );
+    }
+}
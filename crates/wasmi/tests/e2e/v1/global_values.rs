@@ -0,0 +1,192 @@
+//! Tests for creating and updating [`Global`] variables of all [`ValueType`]s from the host.
+
+use wasmi::{
+    core::{ValueType, F32, F64},
+    errors::{GlobalError, ValueError},
+    Engine,
+    ExternRef,
+    Func,
+    FuncRef,
+    Global,
+    GlobalType,
+    Mutability,
+    Store,
+    Value,
+};
+
+/// All [`ValueType`]s supported by Wasmi.
+const VALUE_TYPES: [ValueType; 6] = [
+    ValueType::I32,
+    ValueType::I64,
+    ValueType::F32,
+    ValueType::F64,
+    ValueType::FuncRef,
+    ValueType::ExternRef,
+];
+
+/// Returns a non-default [`Value`] of type `ty` owned by `store`.
+fn non_default(store: &mut Store<()>, ty: ValueType) -> Value {
+    match ty {
+        ValueType::I32 => Value::I32(42),
+        ValueType::I64 => Value::I64(42),
+        ValueType::F32 => Value::F32(F32::from_float(42.0)),
+        ValueType::F64 => Value::F64(F64::from_float(42.0)),
+        ValueType::FuncRef => {
+            let func = Func::wrap(&mut *store, || {});
+            Value::from(FuncRef::new(func))
+        }
+        ValueType::ExternRef => Value::from(ExternRef::new::<i32>(&mut *store, 42)),
+    }
+}
+
+/// Returns `true` if `lhs` and `rhs` are of the same type and value.
+///
+/// # Note
+///
+/// Reference values are compared by their `null`-ness only.
+fn same_value(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs, rhs) {
+        (Value::I32(lhs), Value::I32(rhs)) => lhs == rhs,
+        (Value::I64(lhs), Value::I64(rhs)) => lhs == rhs,
+        (Value::F32(lhs), Value::F32(rhs)) => lhs.to_bits() == rhs.to_bits(),
+        (Value::F64(lhs), Value::F64(rhs)) => lhs.to_bits() == rhs.to_bits(),
+        (Value::FuncRef(_), Value::FuncRef(_)) | (Value::ExternRef(_), Value::ExternRef(_)) => {
+            lhs.is_null() == rhs.is_null()
+        }
+        _ => false,
+    }
+}
+
+#[test]
+fn null_constructors() {
+    assert_eq!(Value::null_func().ty(), ValueType::FuncRef);
+    assert_eq!(Value::null_extern().ty(), ValueType::ExternRef);
+    assert!(Value::null_func().is_null());
+    assert!(Value::null_extern().is_null());
+    assert!(!Value::I32(0).is_null());
+}
+
+#[test]
+fn defaults_for_all_types() {
+    let mut store = Store::new(&Engine::default(), ());
+    for ty in VALUE_TYPES {
+        let default = Value::default(ty);
+        assert_eq!(default.ty(), ty);
+        assert_eq!(default.is_null(), ty.is_ref());
+        let global = Global::new(&mut store, default.clone(), Mutability::Const);
+        assert_eq!(global.ty(&store).content(), ty);
+        assert!(same_value(&global.get(&store), &default));
+    }
+}
+
+#[test]
+fn type_coercion() {
+    let mut store = Store::new(&Engine::default(), ());
+    for from in VALUE_TYPES {
+        let value = non_default(&mut store, from);
+        for to in VALUE_TYPES {
+            let coerced = value.clone().with_type_coercion(to);
+            if from == to {
+                assert!(same_value(&coerced.unwrap(), &value));
+            } else {
+                assert_eq!(
+                    coerced.unwrap_err(),
+                    ValueError::TypeMismatch {
+                        expected: to,
+                        encountered: from,
+                    }
+                );
+            }
+            // Note: only `null` references are coerced to other reference types.
+            let coerced = Value::default(from).with_type_coercion(to);
+            match from == to || (from.is_ref() && to.is_ref()) {
+                true => assert!(same_value(&coerced.unwrap(), &Value::default(to))),
+                false => assert!(coerced.is_err()),
+            }
+        }
+    }
+}
+
+#[test]
+fn new_typed() {
+    let mut store = Store::new(&Engine::default(), ());
+    for content in VALUE_TYPES {
+        let ty = GlobalType::new(content, Mutability::Var);
+        for value_ty in VALUE_TYPES {
+            let value = non_default(&mut store, value_ty);
+            let result = Global::new_typed(&mut store, ty, value.clone());
+            if content == value_ty {
+                let global = result.unwrap();
+                assert_eq!(global.ty(&store), ty);
+                assert!(same_value(&global.get(&store), &value));
+            } else {
+                assert!(matches!(
+                    result,
+                    Err(GlobalError::TypeMismatch { expected, encountered })
+                    if expected == content && encountered == value_ty
+                ));
+            }
+        }
+    }
+    let ty = GlobalType::new(ValueType::ExternRef, Mutability::Const);
+    let global = Global::new_typed(&mut store, ty, Value::null_func()).unwrap();
+    assert_eq!(global.ty(&store), ty);
+    assert!(same_value(&global.get(&store), &Value::null_extern()));
+}
+
+#[test]
+fn set() {
+    let mut store = Store::new(&Engine::default(), ());
+    for content in VALUE_TYPES {
+        let global = Global::new(&mut store, Value::default(content), Mutability::Var);
+        let constant = Global::new(&mut store, Value::default(content), Mutability::Const);
+        for value_ty in VALUE_TYPES {
+            let value = non_default(&mut store, value_ty);
+            let result = global.set(&mut store, value.clone());
+            if content == value_ty {
+                result.unwrap();
+                assert!(same_value(&global.get(&store), &value));
+            } else {
+                assert!(matches!(result, Err(GlobalError::TypeMismatch { .. })));
+            }
+            assert!(matches!(
+                constant.set(&mut store, value),
+                Err(GlobalError::ImmutableWrite)
+            ));
+        }
+    }
+}
+
+#[test]
+fn foreign_references() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut other = Store::new(&engine, ());
+    for ty in [ValueType::FuncRef, ValueType::ExternRef] {
+        let foreign = non_default(&mut other, ty);
+        let global_ty = GlobalType::new(ty, Mutability::Var);
+        assert!(matches!(
+            Global::new_typed(&mut store, global_ty, foreign.clone()),
+            Err(GlobalError::ForeignReference)
+        ));
+        let global = Global::new(&mut store, Value::default(ty), Mutability::Var);
+        assert!(matches!(
+            global.set(&mut store, foreign),
+            Err(GlobalError::ForeignReference)
+        ));
+        assert!(global.get(&store).is_null());
+        let owned = non_default(&mut store, ty);
+        global.set(&mut store, owned).unwrap();
+        assert!(!global.get(&store).is_null());
+    }
+}
+
+#[test]
+#[should_panic]
+fn new_with_foreign_reference() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut other = Store::new(&engine, ());
+    let foreign = non_default(&mut other, ValueType::ExternRef);
+    Global::new(&mut store, foreign, Mutability::Const);
+}
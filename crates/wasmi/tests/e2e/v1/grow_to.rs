@@ -0,0 +1,199 @@
+//! Tests for [`Memory::grow_to`] and [`Table::grow_to`].
+
+use super::caller_split::with_allocation_limit;
+use wasmi::{
+    core::{Pages, ValueType},
+    errors::GrowError,
+    Caller,
+    Engine,
+    Extern,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+    Table,
+    TableType,
+    Value,
+};
+
+/// The size of a linear memory page in bytes.
+const PAGE_SIZE: usize = 65536;
+
+/// Creates a [`Store`] with the resource `limits`.
+fn store(limits: StoreLimits) -> Store<StoreLimits> {
+    let mut store = Store::new(&Engine::default(), limits);
+    store.limiter(|limits| limits);
+    store
+}
+
+/// Creates a [`Memory`] with `min` and `max` pages.
+fn memory(store: &mut Store<StoreLimits>, min: u32, max: Option<u32>) -> Memory {
+    let ty = MemoryType::new(min, max).unwrap();
+    Memory::new(store, ty).unwrap()
+}
+
+/// Creates a `funcref` [`Table`] with `min` and `max` elements.
+fn table(store: &mut Store<StoreLimits>, min: u32, max: Option<u32>) -> Table {
+    let ty = TableType::new(ValueType::FuncRef, min, max);
+    Table::new(store, ty, Value::default(ValueType::FuncRef)).unwrap()
+}
+
+#[test]
+fn memory_grow_to() {
+    let mut store = store(StoreLimits::default());
+    let memory = memory(&mut store, 1, None);
+    memory.data_mut(&mut store)[0] = 42;
+    // Growing to a size that is already satisfied does nothing.
+    memory.grow_to(&mut store, 0).unwrap();
+    memory.grow_to(&mut store, 1).unwrap();
+    assert_eq!(memory.current_pages(&store), Pages::from(1));
+    memory.grow_to(&mut store, 3).unwrap();
+    assert_eq!(memory.current_pages(&store), Pages::from(3));
+    assert_eq!(memory.data(&store).len(), 3 * PAGE_SIZE);
+    assert_eq!(memory.data(&store)[0], 42);
+    memory.grow_to(&mut store, 2).unwrap();
+    assert_eq!(memory.current_pages(&store), Pages::from(3));
+}
+
+#[test]
+fn memory_grow_to_maximum() {
+    let mut store = store(StoreLimits::default());
+    let memory = memory(&mut store, 1, Some(2));
+    assert_eq!(memory.grow_to(&mut store, 3), Err(GrowError::Maximum));
+    assert_eq!(memory.grow_to(&mut store, u64::MAX), Err(GrowError::Maximum));
+    assert_eq!(memory.current_pages(&store), Pages::from(1));
+    memory.grow_to(&mut store, 2).unwrap();
+    assert_eq!(memory.current_pages(&store), Pages::from(2));
+}
+
+#[test]
+fn memory_grow_to_limiter() {
+    for trap in [false, true] {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(2 * PAGE_SIZE)
+            .trap_on_grow_failure(trap)
+            .build();
+        let mut store = store(limits);
+        let memory = memory(&mut store, 1, None);
+        assert_eq!(memory.grow_to(&mut store, 3), Err(GrowError::Limiter));
+        assert_eq!(memory.current_pages(&store), Pages::from(1));
+        memory.grow_to(&mut store, 2).unwrap();
+        assert_eq!(memory.current_pages(&store), Pages::from(2));
+    }
+}
+
+#[test]
+fn memory_grow_to_allocation() {
+    let mut store = store(StoreLimits::default());
+    let memory = memory(&mut store, 1, None);
+    let result = with_allocation_limit(16 * PAGE_SIZE, || memory.grow_to(&mut store, 32));
+    assert_eq!(result, Err(GrowError::Allocation));
+    assert_eq!(memory.current_pages(&store), Pages::from(1));
+    assert_eq!(memory.data(&store).len(), PAGE_SIZE);
+    memory.grow_to(&mut store, 32).unwrap();
+    assert_eq!(memory.current_pages(&store), Pages::from(32));
+}
+
+#[test]
+fn table_grow_to() {
+    let mut store = store(StoreLimits::default());
+    let table = table(&mut store, 1, None);
+    table.grow_to(&mut store, 0).unwrap();
+    table.grow_to(&mut store, 1).unwrap();
+    assert_eq!(table.size(&store), 1);
+    table.grow_to(&mut store, 10).unwrap();
+    assert_eq!(table.size(&store), 10);
+    let element = table.get(&store, 9).unwrap();
+    assert!(element.funcref().unwrap().is_null());
+    table.grow_to(&mut store, 5).unwrap();
+    assert_eq!(table.size(&store), 10);
+}
+
+#[test]
+fn table_grow_to_maximum() {
+    let mut store = store(StoreLimits::default());
+    let table = table(&mut store, 1, Some(2));
+    assert_eq!(table.grow_to(&mut store, 3), Err(GrowError::Maximum));
+    assert_eq!(table.size(&store), 1);
+    table.grow_to(&mut store, 2).unwrap();
+    assert_eq!(table.size(&store), 2);
+}
+
+#[test]
+fn table_grow_to_limiter() {
+    for trap in [false, true] {
+        let limits = StoreLimitsBuilder::new()
+            .table_elements(2)
+            .trap_on_grow_failure(trap)
+            .build();
+        let mut store = store(limits);
+        let table = table(&mut store, 1, None);
+        assert_eq!(table.grow_to(&mut store, 3), Err(GrowError::Limiter));
+        assert_eq!(table.size(&store), 1);
+        table.grow_to(&mut store, 2).unwrap();
+        assert_eq!(table.size(&store), 2);
+    }
+}
+
+#[test]
+fn table_grow_to_allocation() {
+    let mut store = store(StoreLimits::default());
+    let table = table(&mut store, 1, None);
+    let result = with_allocation_limit(1024, || table.grow_to(&mut store, 100_000));
+    assert_eq!(result, Err(GrowError::Allocation));
+    assert_eq!(table.size(&store), 1);
+    table.grow_to(&mut store, 100_000).unwrap();
+    assert_eq!(table.size(&store), 100_000);
+}
+
+#[test]
+fn grow_to_from_host_function() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "grow" (func $grow))
+            (memory (export "memory") 1)
+            (table (export "table") 1 funcref)
+            (func (export "run") (result i32 i32 i32)
+                ;; Use the linear memory before the growth so that it is cached.
+                (i32.store (i32.const 0) (i32.const 1))
+                (call $grow)
+                (i32.store (i32.const 65536) (i32.const 2))
+                (memory.size)
+                (table.size)
+                (i32.add (i32.load (i32.const 0)) (i32.load (i32.const 65536)))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let mut store = store(StoreLimits::default());
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let mut linker = <Linker<StoreLimits>>::new(store.engine());
+    linker
+        .func_wrap("env", "grow", |mut caller: Caller<StoreLimits>| {
+            let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+                panic!("missing exported linear memory")
+            };
+            let Some(Extern::Table(table)) = caller.get_export("table") else {
+                panic!("missing exported table")
+            };
+            memory.grow_to(&mut caller, 2).unwrap();
+            table.grow_to(&mut caller, 3).unwrap();
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let results = instance
+        .get_typed_func::<(), (i32, i32, i32)>(&store, "run")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(results, (2, 3, 3));
+}
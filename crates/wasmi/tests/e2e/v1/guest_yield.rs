@@ -0,0 +1,69 @@
+//! Tests for guest controlled yields via the `wasmi.yield` builtin function.
+
+use wasmi::{Config, Engine, GuestYield, Linker, Module, Store, TypedFunc, TypedResumableCall};
+
+/// A Wasm module that yields with the tags `1`, `2` and `3` via the `wasmi.yield` builtin function.
+///
+/// The exported `run` function returns the number of yields multiplied by `10`.
+const WASM: &str = r#"
+    (module
+        (import "wasmi" "yield" (func $yield (param i32)))
+        (func (export "run") (result i32)
+            (local $n i32)
+            (loop $continue
+                (local.set $n (i32.add (local.get $n) (i32.const 1)))
+                (call $yield (local.get $n))
+                (br_if $continue (i32.lt_u (local.get $n) (i32.const 3)))
+            )
+            (i32.mul (local.get $n) (i32.const 10))
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] with the builtin functions of a [`Linker`] using `builtin_yield`.
+fn setup(builtin_yield: bool) -> Result<(Store<()>, TypedFunc<(), i32>), wasmi::Error> {
+    let mut config = Config::default();
+    config.builtin_yield(builtin_yield);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(WASM).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define_builtins().unwrap();
+    let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+    let run = instance.get_typed_func::<(), i32>(&store, "run").unwrap();
+    Ok((store, run))
+}
+
+#[test]
+fn guest_yields_tags_in_order() {
+    let (mut store, run) = setup(true).unwrap();
+    let mut call = run.call_resumable(&mut store, ()).unwrap();
+    let mut tags = Vec::new();
+    let result = loop {
+        match call {
+            TypedResumableCall::Resumable(invocation) => {
+                tags.push(invocation.guest_yield().unwrap().tag());
+                assert!(invocation.host_yield().is_none());
+                call = invocation.resume(&mut store, &[]).unwrap();
+            }
+            TypedResumableCall::Finished(result) => break result,
+        }
+    };
+    assert_eq!(tags, [1, 2, 3]);
+    assert_eq!(result, 30);
+}
+
+#[test]
+fn non_resumable_call_reports_guest_yield() {
+    let (mut store, run) = setup(true).unwrap();
+    let error = run.call(&mut store, ()).unwrap_err();
+    let guest_yield = error.downcast_ref::<GuestYield>().unwrap();
+    assert_eq!(guest_yield.tag(), 1);
+    assert!(error.to_string().contains("Func::call_resumable"));
+}
+
+#[test]
+fn builtin_yield_is_disabled_by_default() {
+    assert!(setup(false).is_err());
+}
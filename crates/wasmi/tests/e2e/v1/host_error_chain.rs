@@ -0,0 +1,149 @@
+//! Tests asserting that Wasm traps keep their [`TrapCode`] when they propagate through host functions.
+
+use std::error::Error as _;
+#[cfg(feature = "resumable")]
+use wasmi::TypedResumableCall;
+use wasmi::{core::TrapCode, Caller, Engine, Error, Extern, Instance, Linker, Module, Store};
+
+/// A Wasm module that calls back into its `trap` function via the imported host functions.
+const WAT: &str = r#"
+    (module
+        (import "env" "forward" (func $forward))
+        (import "env" "wrap" (func $wrap))
+        (import "env" "wrap_exit" (func $wrap_exit))
+        (func (export "trap")
+            (unreachable)
+        )
+        (func (export "forward")
+            (call $forward)
+        )
+        (func (export "wrap")
+            (call $wrap)
+        )
+        (func (export "wrap_exit")
+            (call $wrap_exit)
+        )
+    )
+"#;
+
+/// Calls the exported `trap` function of the `caller` instance.
+fn call_trap(caller: &mut Caller<()>) -> Result<(), Error> {
+    caller
+        .get_export("trap")
+        .and_then(Extern::into_func)
+        .unwrap()
+        .typed::<(), ()>(&*caller)?
+        .call(caller, ())
+}
+
+/// Instantiates [`WAT`] with its host functions.
+///
+/// - `env.forward` returns the trap of the `trap` function unchanged.
+/// - `env.wrap` wraps the trap of the `trap` function via [`Error::wrap`].
+/// - `env.wrap_exit` wraps an [`Error::exit`] twice via [`Error::wrap`].
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "forward", |mut caller: Caller<()>| {
+            call_trap(&mut caller)
+        })
+        .unwrap()
+        .func_wrap("env", "wrap", |mut caller: Caller<()>| {
+            call_trap(&mut caller).map_err(|error| Error::wrap("nested call failed", error))
+        })
+        .unwrap()
+        .func_wrap("env", "wrap_exit", || -> Result<(), Error> {
+            Err(Error::wrap("outer", Error::wrap("inner", Error::exit(3))))
+        })
+        .unwrap();
+    let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` and returns its error.
+fn call_err(name: &str) -> Error {
+    let (mut store, instance) = setup();
+    instance
+        .get_typed_func::<(), ()>(&store, name)
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err()
+}
+
+#[test]
+fn unchanged_propagation_keeps_trap_code() {
+    let error = call_err("forward");
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    assert_eq!(
+        error.root_trap_code(),
+        Some(TrapCode::UnreachableCodeReached)
+    );
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn wrapped_trap_exposes_root_trap_code() {
+    let error = call_err("wrap");
+    assert_eq!(error.as_trap_code(), None);
+    assert_eq!(
+        error.root_trap_code(),
+        Some(TrapCode::UnreachableCodeReached)
+    );
+    let source = error
+        .source()
+        .and_then(|source| source.downcast_ref::<Error>())
+        .unwrap();
+    assert_eq!(
+        source.as_trap_code(),
+        Some(TrapCode::UnreachableCodeReached)
+    );
+    assert_eq!(error.to_string(), format!("nested call failed: {source}"));
+}
+
+#[test]
+fn wrapped_exit_is_not_resumable() {
+    let error = call_err("wrap_exit");
+    assert_eq!(error.exit_status(), None);
+    assert_eq!(error.root_trap_code(), None);
+    let inner = error
+        .source()
+        .and_then(|source| source.source())
+        .and_then(|source| source.downcast_ref::<Error>())
+        .unwrap();
+    assert_eq!(inner.exit_status(), Some(3));
+    // Note: the wrapped exit cannot be resumed just like an unwrapped exit.
+    #[cfg(feature = "resumable")]
+    {
+        let (mut store, instance) = setup();
+        let result = instance
+            .get_typed_func::<(), ()>(&store, "wrap_exit")
+            .unwrap()
+            .call_resumable(&mut store, ());
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+#[cfg(feature = "resumable")]
+fn wrapped_trap_is_resumable() {
+    let (mut store, instance) = setup();
+    let result = instance
+        .get_typed_func::<(), ()>(&store, "wrap")
+        .unwrap()
+        .call_resumable(&mut store, ())
+        .unwrap();
+    let TypedResumableCall::Resumable(invocation) = result else {
+        panic!("expected a resumable call but found: {result:?}")
+    };
+    assert_eq!(
+        invocation.host_error().root_trap_code(),
+        Some(TrapCode::UnreachableCodeReached)
+    );
+}
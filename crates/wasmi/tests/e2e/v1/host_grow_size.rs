@@ -0,0 +1,210 @@
+//! Tests asserting that `memory.size` and `table.size` observe growth performed by host functions.
+//!
+//! # Note
+//!
+//! Wasmi does not support the multi-memory Wasm proposal so only the default
+//! linear memory is tested whereas tables are tested for non-default indices.
+
+use wasmi::{
+    core::{Pages, ValueType},
+    Caller,
+    Config,
+    Engine,
+    Extern,
+    Instance,
+    Linker,
+    Memory,
+    Module,
+    Store,
+    Table,
+    Value,
+};
+
+/// Imports host functions that grow the memory and tables of their caller by the given delta.
+const WASM: &str = r#"
+    (module
+        (import "env" "grow_memory" (func $grow_memory (param i32)))
+        (import "env" "grow_table" (func $grow_table (param i32 i32)))
+        (import "env" "instantiate" (func $instantiate))
+        (memory (export "memory") 1)
+        (table $t0 (export "t0") 1 funcref)
+        (table $t1 (export "t1") 2 funcref)
+        (table $t2 (export "t2") 3 funcref)
+        (type $grow_memory_ty (func (param i32)))
+        (elem (table $t0) (i32.const 0) func $grow_memory)
+
+        (func (export "memory_size") (param $delta i32) (result i32 i32)
+            (memory.size)
+            (call $grow_memory (local.get $delta))
+            (memory.size)
+        )
+        (func (export "memory_size_indirect") (param $delta i32) (result i32 i32)
+            (memory.size)
+            (call_indirect $t0 (type $grow_memory_ty) (local.get $delta) (i32.const 0))
+            (memory.size)
+        )
+        (func (export "memory_load") (param $delta i32) (result i32)
+            ;; Loads from the cached linear memory before and after the growth.
+            (drop (i32.load (i32.const 0)))
+            (call $grow_memory (local.get $delta))
+            (i32.store (i32.sub (i32.mul (memory.size) (i32.const 65536)) (i32.const 4)) (i32.const 42))
+            (i32.load (i32.sub (i32.mul (memory.size) (i32.const 65536)) (i32.const 4)))
+        )
+        (func (export "memory_size_instantiate") (result i32 i32)
+            (memory.size)
+            (call $instantiate)
+            (memory.size)
+        )
+        (func (export "table_size") (param $table i32) (param $delta i32) (result i32 i32 i32 i32 i32 i32)
+            (table.size $t0)
+            (table.size $t1)
+            (table.size $t2)
+            (call $grow_table (local.get $table) (local.get $delta))
+            (table.size $t0)
+            (table.size $t1)
+            (table.size $t2)
+        )
+    )
+"#;
+
+/// A module sharing the linear memory of [`WASM`] that grows it upon instantiation.
+const SHARING_WASM: &str = r#"
+    (module
+        (import "env" "memory" (memory 1))
+        (func $start
+            (drop (memory.grow (i32.const 3)))
+        )
+        (start $start)
+    )
+"#;
+
+/// Returns the linear memory exported by the `caller` instance.
+fn caller_memory(caller: &Caller<()>) -> Memory {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .unwrap()
+}
+
+/// Returns the table exported by the `caller` instance at `index`.
+fn caller_table(caller: &Caller<()>, index: i32) -> Table {
+    caller
+        .get_export(&format!("t{index}"))
+        .and_then(Extern::into_table)
+        .unwrap()
+}
+
+/// Instantiates [`WASM`] with fuel metering enabled or disabled.
+fn setup(consume_fuel: bool) -> (Store<()>, Instance) {
+    let mut config = Config::default();
+    config.consume_fuel(consume_fuel);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    _ = store.add_fuel(1_000_000);
+    let mut linker = <Linker<()>>::new(&engine);
+    let sharing = Module::new(&engine, &wat::parse_str(SHARING_WASM).unwrap()[..]).unwrap();
+    linker
+        .func_wrap(
+            "env",
+            "grow_memory",
+            |mut caller: Caller<()>, delta: u32| {
+                let memory = caller_memory(&caller);
+                memory
+                    .grow(&mut caller, Pages::new(delta).unwrap())
+                    .unwrap();
+            },
+        )
+        .unwrap()
+        .func_wrap(
+            "env",
+            "grow_table",
+            |mut caller: Caller<()>, index: i32, delta: u32| {
+                let table = caller_table(&caller, index);
+                let init = Value::default(ValueType::FuncRef);
+                table.grow(&mut caller, delta, init).unwrap();
+            },
+        )
+        .unwrap()
+        .func_wrap("env", "instantiate", move |mut caller: Caller<()>| {
+            let memory = caller_memory(&caller);
+            let mut linker = <Linker<()>>::new(caller.engine());
+            linker.define("env", "memory", memory).unwrap();
+            linker
+                .instantiate(&mut caller, &sharing)
+                .unwrap()
+                .start(&mut caller)
+                .unwrap();
+        })
+        .unwrap();
+    let module = Module::new(&engine, &wat::parse_str(WASM).unwrap()[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn memory_size_after_host_grow() {
+    for consume_fuel in [false, true] {
+        let (mut store, instance) = setup(consume_fuel);
+        let memory_size = instance
+            .get_typed_func::<i32, (i32, i32)>(&store, "memory_size")
+            .unwrap();
+        assert_eq!(memory_size.call(&mut store, 2).unwrap(), (1, 3));
+        assert_eq!(memory_size.call(&mut store, 0).unwrap(), (3, 3));
+        let memory_size_indirect = instance
+            .get_typed_func::<i32, (i32, i32)>(&store, "memory_size_indirect")
+            .unwrap();
+        assert_eq!(memory_size_indirect.call(&mut store, 1).unwrap(), (3, 4));
+    }
+}
+
+#[test]
+fn memory_access_after_host_grow() {
+    for consume_fuel in [false, true] {
+        let (mut store, instance) = setup(consume_fuel);
+        let memory_load = instance
+            .get_typed_func::<i32, i32>(&store, "memory_load")
+            .unwrap();
+        assert_eq!(memory_load.call(&mut store, 1).unwrap(), 42);
+        assert_eq!(memory_load.call(&mut store, 2).unwrap(), 42);
+    }
+}
+
+#[test]
+fn memory_size_after_host_instantiate() {
+    for consume_fuel in [false, true] {
+        let (mut store, instance) = setup(consume_fuel);
+        let memory_size_instantiate = instance
+            .get_typed_func::<(), (i32, i32)>(&store, "memory_size_instantiate")
+            .unwrap();
+        assert_eq!(
+            memory_size_instantiate.call(&mut store, ()).unwrap(),
+            (1, 4)
+        );
+    }
+}
+
+#[test]
+fn table_size_after_host_grow() {
+    for consume_fuel in [false, true] {
+        let (mut store, instance) = setup(consume_fuel);
+        let table_size = instance
+            .get_typed_func::<(i32, i32), (i32, i32, i32, i32, i32, i32)>(&store, "table_size")
+            .unwrap();
+        assert_eq!(
+            table_size.call(&mut store, (0, 1)).unwrap(),
+            (1, 2, 3, 2, 2, 3)
+        );
+        assert_eq!(
+            table_size.call(&mut store, (1, 2)).unwrap(),
+            (2, 2, 3, 2, 4, 3)
+        );
+        assert_eq!(
+            table_size.call(&mut store, (2, 3)).unwrap(),
+            (2, 4, 3, 2, 4, 6)
+        );
+    }
+}
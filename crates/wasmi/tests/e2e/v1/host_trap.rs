@@ -0,0 +1,84 @@
+//! Tests to check that host-defined traps raised via [`Error::host_trap`] propagate as intended.
+
+use wasmi::{Caller, Engine, Error, Extern, Func, Instance, Linker, Module, Store};
+
+/// A Wasm module that raises host traps via its imported host functions.
+const WAT: &str = r#"
+    (module
+        (import "env" "trap" (func $trap (param i32)))
+        (import "env" "reenter" (func $reenter (param i32)))
+        (func (export "trap") (param i32) (result i32)
+            (call $trap (local.get 0))
+            (i32.const 0)
+        )
+        (func (export "reenter") (param i32) (result i32)
+            (call $reenter (local.get 0))
+            (i32.const 0)
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`] with its host functions.
+///
+/// - `env.trap` raises the host trap with the given code.
+/// - `env.reenter` calls back into the exported `trap` function.
+fn setup() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let trap = Func::wrap(&mut store, |code: i32| -> Result<(), Error> {
+        Err(Error::host_trap(code as u16))
+    });
+    let reenter = Func::wrap(
+        &mut store,
+        |mut caller: Caller<()>, code: i32| -> Result<(), Error> {
+            let trap = caller
+                .get_export("trap")
+                .and_then(Extern::into_func)
+                .unwrap()
+                .typed::<i32, i32>(&caller)?;
+            trap.call(&mut caller, code)?;
+            Ok(())
+        },
+    );
+    linker.define("env", "trap", trap).unwrap();
+    linker.define("env", "reenter", reenter).unwrap();
+    let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn host_trap_at_call_site() {
+    let (mut store, instance) = setup();
+    let error = instance
+        .get_typed_func::<i32, i32>(&store, "trap")
+        .unwrap()
+        .call(&mut store, 7)
+        .unwrap_err();
+    assert_eq!(error.as_host_trap(), Some(7));
+    assert_eq!(error.as_trap_code(), None);
+    assert_eq!(error.to_string(), "host trap 7");
+}
+
+#[test]
+fn host_trap_through_nested_calls() {
+    let (mut store, instance) = setup();
+    let error = instance
+        .get_typed_func::<i32, i32>(&store, "reenter")
+        .unwrap()
+        .call(&mut store, 7)
+        .unwrap_err();
+    assert_eq!(error.as_host_trap(), Some(7));
+}
+
+#[test]
+fn other_errors_are_no_host_traps() {
+    assert_eq!(Error::new("message").as_host_trap(), None);
+    assert_eq!(Error::exit(7).as_host_trap(), None);
+    assert_eq!(Error::host_trap(u16::MAX).as_host_trap(), Some(u16::MAX));
+}
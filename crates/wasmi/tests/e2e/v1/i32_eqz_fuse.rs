@@ -0,0 +1,129 @@
+//! Tests for `i32.eqz` conditions fused into branch and `select` instructions.
+//!
+//! Doubly negated conditions and `select` conditions are only fused with optimizations enabled.
+
+use wasmi::{Config, Engine, Instance, Linker, Module, Store};
+
+/// A Wasm module with negated conditions as they are commonly emitted by LLVM.
+const WAT: &str = r#"
+    (module
+        (func (export "br_if_eqz") (param i32) (result i32)
+            (block
+                (br_if 0 (i32.eqz (local.get 0)))
+                (return (i32.const 10))
+            )
+            (i32.const 20)
+        )
+        (func (export "br_if_eqz_eqz") (param i32) (result i32)
+            (block
+                (br_if 0 (i32.eqz (i32.eqz (local.get 0))))
+                (return (i32.const 10))
+            )
+            (i32.const 20)
+        )
+        (func (export "br_if_eqz_eq") (param i32) (result i32)
+            (block
+                (br_if 0 (i32.eqz (i32.eq (local.get 0) (i32.const 1))))
+                (return (i32.const 10))
+            )
+            (i32.const 20)
+        )
+        (func (export "if_eqz") (param i32) (result i32)
+            (if (result i32) (i32.eqz (local.get 0))
+                (then (i32.const 10))
+                (else (i32.const 20))
+            )
+        )
+        (func (export "if_eqz_eqz") (param i32) (result i32)
+            (if (result i32) (i32.eqz (i32.eqz (local.get 0)))
+                (then (i32.const 10))
+                (else (i32.const 20))
+            )
+        )
+        (func (export "select_eqz") (param i32) (result i32)
+            (select (i32.const 10) (i32.const 20) (i32.eqz (local.get 0)))
+        )
+        (func (export "select_eqz_eqz") (param i32) (result i32)
+            (select (i32.const 10) (i32.const 20) (i32.eqz (i32.eqz (local.get 0))))
+        )
+        (func (export "select_eqz_reg") (param i32) (result i32)
+            (select
+                (i32.add (local.get 0) (i32.const 10))
+                (i32.add (local.get 0) (i32.const 20))
+                (i32.eqz (local.get 0))
+            )
+        )
+        (func (export "loop_header") (param i32) (result i32)
+            (local $n i32)
+            (i32.eq (local.get 0) (i32.const 1))
+            (loop (param i32)
+                (if
+                    (then
+                        (local.set $n (i32.add (local.get $n) (i32.const 1)))
+                        (br 1 (i32.lt_u (local.get $n) (i32.const 3)))
+                    )
+                )
+            )
+            (local.get $n)
+        )
+    )
+"#;
+
+/// Instantiates the Wasm module under test with or without fuel metering at `optimization_level`.
+fn setup(consume_fuel: bool, optimization_level: u8) -> (Store<()>, Instance) {
+    let mut config = Config::default();
+    config
+        .consume_fuel(consume_fuel)
+        .optimization_level(optimization_level);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    if consume_fuel {
+        store.add_fuel(1_000).unwrap();
+    }
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Asserts that the exported function `name` behaves like `expected` for a set of inputs.
+fn assert_func(name: &str, expected: fn(i32) -> i32) {
+    for (consume_fuel, optimization_level) in [(false, 0), (true, 0), (false, 1), (true, 1)] {
+        let (mut store, instance) = setup(consume_fuel, optimization_level);
+        let func = instance.get_typed_func::<i32, i32>(&store, name).unwrap();
+        for input in [0, 1, -1, 2, i32::MIN, i32::MAX] {
+            let result = func.call(&mut store, input).unwrap();
+            assert_eq!(result, expected(input), "{name}({input})");
+        }
+    }
+}
+
+#[test]
+fn br_if() {
+    assert_func("br_if_eqz", |x| if x == 0 { 20 } else { 10 });
+    assert_func("br_if_eqz_eqz", |x| if x != 0 { 20 } else { 10 });
+    assert_func("br_if_eqz_eq", |x| if x != 1 { 20 } else { 10 });
+}
+
+#[test]
+fn if_() {
+    assert_func("if_eqz", |x| if x == 0 { 10 } else { 20 });
+    assert_func("if_eqz_eqz", |x| if x != 0 { 10 } else { 20 });
+}
+
+#[test]
+fn select() {
+    assert_func("select_eqz", |x| if x == 0 { 10 } else { 20 });
+    assert_func("select_eqz_eqz", |x| if x != 0 { 10 } else { 20 });
+    assert_func("select_eqz_reg", |x| {
+        x.wrapping_add(if x == 0 { 10 } else { 20 })
+    });
+}
+
+#[test]
+fn loop_header() {
+    assert_func("loop_header", |x| if x == 1 { 3 } else { 0 });
+}
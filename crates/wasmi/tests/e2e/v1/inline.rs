@@ -0,0 +1,116 @@
+//! Tests for the inlining of calls to tiny internal functions enabled by optimization level 2.
+
+use wasmi::{core::TrapCode, Config, Engine, Instance, Linker, Module, Store};
+
+/// A Wasm module with tiny functions that are inlined into their callers.
+///
+/// - `run` sums up the results of calling all tiny functions `n` times.
+/// - `div` traps if its divisor is zero.
+const WAT: &str = r#"
+    (module
+        (memory 1)
+        (global $counter (mut i32) (i32.const 0))
+        (func $inc (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func $square_inc (param i32) (result i32)
+            (local.set 0 (i32.mul (local.get 0) (local.get 0)))
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func $swap (param i32 i32) (result i32 i32)
+            (local.get 1)
+            (local.get 0)
+        )
+        (func $answer (result i32)
+            (i32.const 42)
+        )
+        (func $bump
+            (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+        )
+        (func $store (param i32 i32)
+            (i32.store (local.get 0) (local.get 1))
+        )
+        (func $load (param i32) (result i32)
+            (i32.load (local.get 0))
+        )
+        (func $div (param i32 i32) (result i32)
+            (i32.div_s (local.get 0) (local.get 1))
+        )
+        (func (export "run") (param $n i32) (result i32)
+            (local $sum i32)
+            (local $i i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.ge_u (local.get $i) (local.get $n)))
+                    (local.set $sum (call $inc (local.get $sum)))
+                    (local.set $sum (i32.add (local.get $sum) (call $square_inc (local.get $i))))
+                    (call $swap (local.get $sum) (local.get $i))
+                    (local.set $sum (i32.sub))
+                    (local.set $sum (i32.add (local.get $sum) (call $answer)))
+                    (call $bump)
+                    (call $store (i32.const 8) (local.get $sum))
+                    (local.set $sum (i32.xor (local.get $sum) (call $load (i32.const 8))))
+                    (local.set $sum (i32.add (local.get $sum) (call $div (local.get $i) (i32.const 3))))
+                    (local.set $i (call $inc (local.get $i)))
+                    (br $continue)
+                )
+            )
+            (i32.add (local.get $sum) (global.get $counter))
+        )
+        (func (export "div") (param i32 i32) (result i32)
+            (call $div (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`] with the given `optimization_level` and fuel metering enabled.
+fn instantiate(optimization_level: u8) -> (Store<()>, Instance) {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    config.optimization_level(optimization_level);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    store.add_fuel(u64::MAX).unwrap();
+    let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls `run` of [`WAT`] with `n` and returns its result and the consumed fuel.
+fn run(optimization_level: u8, n: i32) -> (i32, u64) {
+    let (mut store, instance) = instantiate(optimization_level);
+    let result = instance
+        .get_typed_func::<i32, i32>(&store, "run")
+        .unwrap()
+        .call(&mut store, n)
+        .unwrap();
+    (result, store.fuel_consumed().unwrap())
+}
+
+#[test]
+fn results_and_fuel_match_without_inlining() {
+    for n in [0, 1, 2, 10, 100] {
+        let expected = run(0, n);
+        assert_eq!(run(1, n), expected);
+        assert_eq!(run(2, n), expected);
+    }
+}
+
+#[test]
+fn trap_in_inlined_function() {
+    for optimization_level in [0, 2] {
+        let (mut store, instance) = instantiate(optimization_level);
+        let div = instance
+            .get_typed_func::<(i32, i32), i32>(&store, "div")
+            .unwrap();
+        assert_eq!(div.call(&mut store, (7, 2)).unwrap(), 3);
+        let error = div.call(&mut store, (7, 0)).unwrap_err();
+        assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+        let error = div.call(&mut store, (i32::MIN, -1)).unwrap_err();
+        assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerOverflow));
+    }
+}
@@ -0,0 +1,164 @@
+//! Tests for the typed export accessors of [`Instance`] and the order of its exports.
+
+use assert_matches::assert_matches;
+use wasmi::{
+    core::ValueType,
+    errors::{ErrorKind, ExportError},
+    Engine,
+    Error,
+    ExternKind,
+    FuncType,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+/// A Wasm module whose exports are not declared in alphabetical order.
+const WAT: &str = r#"
+    (module
+        (func $add (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (memory 1)
+        (global (mut i64) (i64.const 0))
+        (table 1 funcref)
+        (export "zeta" (func $add))
+        (export "mem" (memory 0))
+        (export "alpha" (global 0))
+        (export "table" (table 0))
+        (export "beta" (func $add))
+    )
+"#;
+
+/// The export names of [`WAT`] in declaration order.
+const EXPORTS: [&str; 5] = ["zeta", "mem", "alpha", "table", "beta"];
+
+fn setup() -> (Store<()>, Module, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, module, instance)
+}
+
+#[test]
+fn get_typed_func_works() {
+    let (mut store, _, instance) = setup();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "zeta")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+}
+
+#[test]
+fn get_typed_func_not_found() {
+    let (store, _, instance) = setup();
+    let error = instance
+        .get_typed_func::<(), ()>(&store, "missing")
+        .unwrap_err();
+    assert_matches!(&error, ExportError::NotFound { name } if &**name == "missing");
+    assert_eq!(
+        error.to_string(),
+        r#"could not find export named "missing""#
+    );
+}
+
+#[test]
+fn get_typed_func_kind_mismatch() {
+    let (store, _, instance) = setup();
+    for (name, found) in [
+        ("mem", ExternKind::Memory),
+        ("alpha", ExternKind::Global),
+        ("table", ExternKind::Table),
+    ] {
+        let error = instance.get_typed_func::<(), ()>(&store, name).unwrap_err();
+        assert_matches!(
+            error,
+            ExportError::KindMismatch {
+                expected: ExternKind::Func,
+                found: actual,
+                ..
+            } if actual == found
+        );
+    }
+    let error = instance
+        .get_typed_func::<(), ()>(&store, "mem")
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        r#"expected export "mem" to be a function but found a memory"#
+    );
+}
+
+#[test]
+fn get_typed_func_signature_mismatch() {
+    let (store, _, instance) = setup();
+    let error = instance
+        .get_typed_func::<i64, i32>(&store, "beta")
+        .unwrap_err();
+    let ExportError::SignatureMismatch {
+        name,
+        expected,
+        found,
+    } = error
+    else {
+        panic!("expected a signature mismatch but found: {error:?}")
+    };
+    assert_eq!(&*name, "beta");
+    assert_eq!(expected, FuncType::new([ValueType::I64], [ValueType::I32]));
+    assert_eq!(
+        found,
+        FuncType::new([ValueType::I32, ValueType::I32], [ValueType::I32])
+    );
+}
+
+#[test]
+fn export_error_into_error() {
+    let (store, _, instance) = setup();
+    let lookup = || -> Result<(), Error> {
+        instance.get_typed_func::<(), ()>(&store, "missing")?;
+        Ok(())
+    };
+    let error = lookup().unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Export(ExportError::NotFound { .. })
+    );
+}
+
+#[test]
+fn exports_in_declaration_order() {
+    let (store, module, instance) = setup();
+    let names: Vec<_> = instance
+        .exports(&store)
+        .map(|export| export.name())
+        .collect();
+    assert_eq!(names, EXPORTS);
+    let kinds: Vec<_> = instance
+        .exports(&store)
+        .map(|export| export.into_extern().kind())
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            ExternKind::Func,
+            ExternKind::Memory,
+            ExternKind::Global,
+            ExternKind::Table,
+            ExternKind::Func,
+        ]
+    );
+    let names: Vec<_> = module.exports().map(|export| export.name()).collect();
+    assert_eq!(names, EXPORTS);
+    // Lookups by name are unaffected by the declaration order.
+    for name in EXPORTS {
+        assert!(instance.get_export(&store, name).is_some());
+        assert!(module.get_export(name).is_some());
+    }
+    assert!(instance.get_export(&store, "gamma").is_none());
+}
@@ -0,0 +1,190 @@
+//! Tests for the [`PreparedInstance`] created via [`Linker::instantiate_pre`].
+
+use assert_matches::assert_matches;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use wasmi::{
+    core::ValueType,
+    errors::{ErrorKind, LinkerError},
+    Caller,
+    Engine,
+    Func,
+    FuncType,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    PreparedInstance,
+    Store,
+    Value,
+};
+
+/// A Wasm module importing host functions and initializing its own entities.
+const WAT: &str = r#"
+    (module
+        (import "env" "add" (func $add (param i32 i32) (result i32)))
+        (import "env" "double" (func $double (param i32) (result i32)))
+        (memory 1)
+        (global $offset (mut i32) (i32.const 10))
+        (data (i32.const 0) "\2a")
+        (func (export "run") (param i32) (result i32)
+            (global.set $offset
+                (i32.add (global.get $offset) (i32.const 1))
+            )
+            (call $double
+                (call $add
+                    (i32.add (local.get 0) (global.get $offset))
+                    (i32.load8_u (i32.const 0))
+                )
+            )
+        )
+    )
+"#;
+
+/// A Wasm module importing a linear memory.
+const WAT_MEMORY: &str = r#"
+    (module
+        (import "env" "mem" (memory 1))
+    )
+"#;
+
+/// Returns a [`Linker`] with [`Store`] independent definitions for the imports of [`WAT`].
+fn setup() -> (Engine, Linker<()>) {
+    let engine = Engine::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "add", |lhs: i32, rhs: i32| lhs.wrapping_add(rhs))
+        .unwrap();
+    linker
+        .func_new(
+            "env",
+            "double",
+            FuncType::new([ValueType::I32], [ValueType::I32]),
+            |_caller: Caller<()>, params: &[Value], results: &mut [Value]| {
+                results[0] = Value::I32(params[0].i32().unwrap().wrapping_mul(2));
+                Ok(())
+            },
+        )
+        .unwrap();
+    (engine, linker)
+}
+
+/// Compiles the Wasm module in WebAssembly text format `wat`.
+fn module(engine: &Engine, wat: &str) -> Module {
+    Module::new(engine, &wat::parse_str(wat).unwrap()[..]).unwrap()
+}
+
+/// Instantiates `pre` into `store` and calls its exported `run` function with `input`.
+fn run(store: &mut Store<()>, pre: &PreparedInstance<()>, input: i32) -> i32 {
+    let instance = pre
+        .instantiate(&mut *store)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap();
+    instance
+        .get_typed_func::<i32, i32>(&*store, "run")
+        .unwrap()
+        .call(&mut *store, input)
+        .unwrap()
+}
+
+#[test]
+fn instantiate_into_many_stores() {
+    let (engine, linker) = setup();
+    let pre = linker.instantiate_pre(&module(&engine, WAT)).unwrap();
+    let mut stores = [Store::new(&engine, ()), Store::new(&engine, ())];
+    for store in &mut stores {
+        assert_eq!(run(store, &pre, 1), (1 + 11 + 42) * 2);
+    }
+    // Every instance owns its own global variable and memory.
+    let mut store = Store::new(&engine, ());
+    let cloned = pre.clone();
+    assert_eq!(run(&mut store, &cloned, 1), (1 + 11 + 42) * 2);
+    assert_eq!(run(&mut store, &pre, 1), (1 + 11 + 42) * 2);
+}
+
+#[test]
+fn instantiate_pre_applies_interceptor() {
+    let (engine, mut linker) = setup();
+    let count = Arc::new(AtomicUsize::new(0));
+    linker.set_interceptor({
+        let count = count.clone();
+        move |_info, params, next, results| {
+            count.fetch_add(1, Ordering::SeqCst);
+            next(params, results)
+        }
+    });
+    let pre = linker.instantiate_pre(&module(&engine, WAT)).unwrap();
+    let mut store = Store::new(&engine, ());
+    assert_eq!(run(&mut store, &pre, 0), (11 + 42) * 2);
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn store_tied_func_is_rejected() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let add = Func::wrap(&mut store, |lhs: i32, rhs: i32| lhs.wrapping_add(rhs));
+    linker.define("env", "add", add).unwrap();
+    linker
+        .func_wrap("env", "double", |value: i32| value.wrapping_mul(2))
+        .unwrap();
+    let error = linker.instantiate_pre(&module(&engine, WAT)).unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::StoreTiedDefinition { name })
+            if name.module() == "env" && name.name() == "add"
+    );
+    // The same definitions are fine for a regular instantiation.
+    assert!(linker
+        .instantiate(&mut store, &module(&engine, WAT))
+        .is_ok());
+}
+
+#[test]
+fn store_tied_memory_is_rejected() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    linker.define("env", "mem", memory).unwrap();
+    let error = linker
+        .instantiate_pre(&module(&engine, WAT_MEMORY))
+        .unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::StoreTiedDefinition { .. })
+    );
+    assert_eq!(
+        error.to_string(),
+        "cannot pre-instantiate import env::mem since its definition is tied to a store"
+    );
+}
+
+#[test]
+fn unresolved_imports_are_rejected() {
+    let engine = Engine::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    let error = linker.instantiate_pre(&module(&engine, WAT)).unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::MissingDefinition { .. })
+    );
+    linker
+        .func_wrap("env", "add", |lhs: i64, rhs: i64| lhs.wrapping_add(rhs))
+        .unwrap();
+    let error = linker.instantiate_pre(&module(&engine, WAT)).unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::FuncTypeMismatch { .. })
+    );
+    linker.func_wrap("env", "mem", || {}).unwrap();
+    let error = linker
+        .instantiate_pre(&module(&engine, WAT_MEMORY))
+        .unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Linker(LinkerError::InvalidTypeDefinition { .. })
+    );
+}
@@ -0,0 +1,131 @@
+//! Tests for the [`InstructionCategoryCounts`] recorded by the [`Store`] with [`Config::profiling`].
+
+use wasmi::{
+    CompilationMode,
+    Config,
+    Engine,
+    InstructionCategory,
+    InstructionCategoryCounts,
+    Linker,
+    Module,
+    Store,
+};
+
+/// Loops `n` times over a load, a store, a call and a float operator.
+///
+/// # Note
+///
+/// The `br_if` at the end of the loop is taken `n - 1` times and not taken once.
+const WASM: &str = r#"
+    (module
+        (memory 1)
+        (func $double (param f32) (result f32)
+            (f32.add (local.get 0) (local.get 0))
+        )
+        (func (export "run") (param $n i32) (result i32)
+            (local $i i32)
+            (loop $continue
+                (i32.store (i32.const 0) (i32.add (i32.load (i32.const 0)) (local.get $i)))
+                (drop (call $double (f32.const 1.5)))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br_if $continue (i32.lt_u (local.get $i) (local.get $n)))
+            )
+            (i32.load (i32.const 0))
+        )
+    )
+"#;
+
+/// Returns all [`Config`]s under test with profiling set to `profiling`.
+///
+/// # Note
+///
+/// Optimization level 2 is not tested since it inlines the called function.
+fn configs(profiling: bool) -> Vec<Config> {
+    let mut configs = Vec::new();
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        for optimization_level in [0, 1] {
+            let mut config = Config::default();
+            config.compilation_mode(mode);
+            config.optimization_level(optimization_level);
+            config.profiling(profiling);
+            configs.push(config);
+        }
+    }
+    configs
+}
+
+/// Calls the exported `run` function of [`WASM`] compiled with `config` with `n`.
+///
+/// Returns the [`InstructionCategoryCounts`] of the call.
+fn run(config: &Config, n: i32) -> InstructionCategoryCounts {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, &wat::parse_str(WASM).unwrap()[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let result = instance
+        .get_typed_func::<i32, i32>(&store, "run")
+        .unwrap()
+        .call(&mut store, n)
+        .unwrap();
+    assert_eq!(result, (0..n).sum::<i32>());
+    store.instruction_category_counts()
+}
+
+/// Asserts the [`InstructionCategoryCounts`] of a `run` call with `n` loop iterations.
+fn assert_counts(counts: InstructionCategoryCounts, n: u64) {
+    assert_eq!(counts.get(InstructionCategory::Load), n + 1);
+    assert_eq!(counts.get(InstructionCategory::Store), n);
+    assert_eq!(counts.get(InstructionCategory::Call), n);
+    assert_eq!(counts.get(InstructionCategory::Float), n);
+    assert_eq!(counts.get(InstructionCategory::BranchTaken), n - 1);
+    assert_eq!(counts.get(InstructionCategory::BranchNotTaken), 1);
+}
+
+#[test]
+fn loop_with_known_trip_count() {
+    for config in configs(true) {
+        assert_counts(run(&config, 10), 10);
+        assert_counts(run(&config, 25), 25);
+    }
+}
+
+#[test]
+fn counts_accumulate_until_reset() {
+    for config in configs(true) {
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &wat::parse_str(WASM).unwrap()[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+        run.call(&mut store, 4).unwrap();
+        run.call(&mut store, 6).unwrap();
+        let counts = store.instruction_category_counts();
+        assert_eq!(counts.get(InstructionCategory::Store), 10);
+        assert_eq!(counts.get(InstructionCategory::BranchTaken), 8);
+        assert_eq!(counts.get(InstructionCategory::BranchNotTaken), 2);
+        store.reset_instruction_category_counts();
+        assert_eq!(
+            store.instruction_category_counts(),
+            InstructionCategoryCounts::default()
+        );
+    }
+}
+
+#[test]
+fn no_counts_without_profiling() {
+    for config in configs(false) {
+        assert_eq!(run(&config, 10), InstructionCategoryCounts::default());
+    }
+}
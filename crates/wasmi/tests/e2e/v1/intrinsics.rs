@@ -0,0 +1,157 @@
+//! Tests to check that Wasmi intrinsic functions trap upon overflow.
+
+use wasmi::{core::TrapCode, Engine, Error, Instance, Linker, Module, Store, Value};
+
+/// A Wasm module calling the imported intrinsic functions directly and indirectly.
+///
+/// - Direct calls are substituted with dedicated Wasmi instructions.
+/// - Indirect calls are dispatched to the host functions defined by the [`Linker`].
+const WAT: &str = r#"
+    (module
+        (import "wasmi_intrinsics" "i32.checked_add" (func $i32_add (param i32 i32) (result i32)))
+        (import "wasmi_intrinsics" "i32.checked_sub" (func $i32_sub (param i32 i32) (result i32)))
+        (import "wasmi_intrinsics" "i32.checked_mul" (func $i32_mul (param i32 i32) (result i32)))
+        (import "wasmi_intrinsics" "i64.checked_add" (func $i64_add (param i64 i64) (result i64)))
+        (import "wasmi_intrinsics" "i64.checked_sub" (func $i64_sub (param i64 i64) (result i64)))
+        (import "wasmi_intrinsics" "i64.checked_mul" (func $i64_mul (param i64 i64) (result i64)))
+        (type $i32_binop (func (param i32 i32) (result i32)))
+        (type $i64_binop (func (param i64 i64) (result i64)))
+        (table funcref (elem $i32_add $i32_sub $i32_mul $i64_add $i64_sub $i64_mul))
+        (func (export "i32.add") (param i32 i32) (result i32)
+            (call $i32_add (local.get 0) (local.get 1))
+        )
+        (func (export "i32.sub") (param i32 i32) (result i32)
+            (call $i32_sub (local.get 0) (local.get 1))
+        )
+        (func (export "i32.mul") (param i32 i32) (result i32)
+            (call $i32_mul (local.get 0) (local.get 1))
+        )
+        (func (export "i64.add") (param i64 i64) (result i64)
+            (call $i64_add (local.get 0) (local.get 1))
+        )
+        (func (export "i64.sub") (param i64 i64) (result i64)
+            (call $i64_sub (local.get 0) (local.get 1))
+        )
+        (func (export "i64.mul") (param i64 i64) (result i64)
+            (call $i64_mul (local.get 0) (local.get 1))
+        )
+        (func (export "i32.indirect") (param i32 i32 i32) (result i32)
+            (call_indirect (type $i32_binop) (local.get 1) (local.get 2) (local.get 0))
+        )
+        (func (export "i64.indirect") (param i32 i64 i64) (result i64)
+            (call_indirect (type $i64_binop) (local.get 1) (local.get 2) (local.get 0))
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`] with the intrinsic functions defined by the [`Linker`].
+fn instantiate() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define_intrinsics().unwrap();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` of `instance` with `params`.
+fn call(
+    store: &mut Store<()>,
+    instance: Instance,
+    name: &str,
+    params: &[Value],
+) -> Result<Value, Error> {
+    let func = instance.get_func(&*store, name).unwrap();
+    let mut results = [Value::I32(0)];
+    func.call(&mut *store, params, &mut results)?;
+    Ok(results[0].clone())
+}
+
+/// Asserts that `result` is an integer overflow trap.
+fn assert_overflow(result: Result<Value, Error>) {
+    assert_eq!(
+        result.unwrap_err().as_trap_code(),
+        Some(TrapCode::IntegerOverflow)
+    );
+}
+
+#[test]
+fn intrinsics_compute_results() {
+    let (mut store, instance) = instantiate();
+    let mut test_i32 = |name: &str, lhs: i32, rhs: i32, expected: i32| {
+        let params = [Value::I32(lhs), Value::I32(rhs)];
+        let result = call(&mut store, instance, name, &params).unwrap();
+        assert_eq!(result.i32(), Some(expected), "{name}({lhs}, {rhs})");
+    };
+    test_i32("i32.add", 1, 2, 3);
+    test_i32("i32.add", i32::MAX, -1, i32::MAX - 1);
+    test_i32("i32.sub", 1, 2, -1);
+    test_i32("i32.sub", i32::MIN, -1, i32::MIN + 1);
+    test_i32("i32.mul", -3, 7, -21);
+    test_i32("i32.mul", i32::MIN, 1, i32::MIN);
+    let mut test_i64 = |name: &str, lhs: i64, rhs: i64, expected: i64| {
+        let params = [Value::I64(lhs), Value::I64(rhs)];
+        let result = call(&mut store, instance, name, &params).unwrap();
+        assert_eq!(result.i64(), Some(expected), "{name}({lhs}, {rhs})");
+    };
+    test_i64("i64.add", 1, 2, 3);
+    test_i64("i64.add", i64::MAX, -1, i64::MAX - 1);
+    test_i64("i64.sub", 1, 2, -1);
+    test_i64("i64.sub", i64::MIN, -1, i64::MIN + 1);
+    test_i64("i64.mul", -3, 7, -21);
+    test_i64("i64.mul", i64::MIN, 1, i64::MIN);
+}
+
+#[test]
+fn intrinsics_trap_on_overflow() {
+    let (mut store, instance) = instantiate();
+    let mut test_i32 = |name: &str, lhs: i32, rhs: i32| {
+        let params = [Value::I32(lhs), Value::I32(rhs)];
+        assert_overflow(call(&mut store, instance, name, &params));
+    };
+    test_i32("i32.add", i32::MAX, 1);
+    test_i32("i32.sub", i32::MIN, 1);
+    test_i32("i32.mul", i32::MIN, -1);
+    test_i32("i32.mul", 0x1_0000, 0x1_0000);
+    let mut test_i64 = |name: &str, lhs: i64, rhs: i64| {
+        let params = [Value::I64(lhs), Value::I64(rhs)];
+        assert_overflow(call(&mut store, instance, name, &params));
+    };
+    test_i64("i64.add", i64::MAX, 1);
+    test_i64("i64.sub", i64::MIN, 1);
+    test_i64("i64.mul", i64::MIN, -1);
+    test_i64("i64.mul", 0x1_0000_0000, 0x1_0000_0000);
+}
+
+#[test]
+fn intrinsics_host_fallback() {
+    let (mut store, instance) = instantiate();
+    // Indices into the table of [`WAT`].
+    let [i32_add, i32_sub, i32_mul, i64_add, i64_sub, i64_mul] = [0, 1, 2, 3, 4, 5];
+    let mut test_i32 = |index: i32, lhs: i32, rhs: i32| {
+        let params = [Value::I32(index), Value::I32(lhs), Value::I32(rhs)];
+        call(&mut store, instance, "i32.indirect", &params)
+    };
+    assert_eq!(test_i32(i32_add, 1, 2).unwrap().i32(), Some(3));
+    assert_eq!(test_i32(i32_sub, 1, 2).unwrap().i32(), Some(-1));
+    assert_eq!(test_i32(i32_mul, -3, 7).unwrap().i32(), Some(-21));
+    assert_overflow(test_i32(i32_add, i32::MAX, 1));
+    assert_overflow(test_i32(i32_sub, i32::MIN, 1));
+    assert_overflow(test_i32(i32_mul, i32::MIN, -1));
+    let mut test_i64 = |index: i32, lhs: i64, rhs: i64| {
+        let params = [Value::I32(index), Value::I64(lhs), Value::I64(rhs)];
+        call(&mut store, instance, "i64.indirect", &params)
+    };
+    assert_eq!(test_i64(i64_add, 1, 2).unwrap().i64(), Some(3));
+    assert_eq!(test_i64(i64_sub, 1, 2).unwrap().i64(), Some(-1));
+    assert_eq!(test_i64(i64_mul, -3, 7).unwrap().i64(), Some(-21));
+    assert_overflow(test_i64(i64_add, i64::MAX, 1));
+    assert_overflow(test_i64(i64_sub, i64::MIN, 1));
+    assert_overflow(test_i64(i64_mul, i64::MIN, -1));
+}
@@ -0,0 +1,343 @@
+//! Tests to check if Wasmi's lazy function compilation works as intended.
+
+use assert_matches::assert_matches;
+use std::{num::NonZeroU64, thread};
+use wasmi::{
+    core::TrapCode,
+    errors::ErrorKind,
+    CompilationMode,
+    Config,
+    Engine,
+    Error,
+    FuelCosts,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+/// A Wasm module with a valid `"valid"` and an invalid `"invalid"` function.
+///
+/// # Note
+///
+/// The `"invalid"` function fails Wasm validation since it returns
+/// an `i64` value while its function type demands an `i32` result.
+const WAT: &str = r#"
+    (module
+        (func (export "valid") (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func (export "invalid") (result i32)
+            (i64.const 42)
+        )
+    )
+"#;
+
+/// Creates an [`Engine`] using the given [`CompilationMode`].
+fn engine(mode: CompilationMode) -> Engine {
+    let mut config = Config::default();
+    config.compilation_mode(mode);
+    Engine::new(&config)
+}
+
+/// Compiles the [`WAT`] into a [`Module`] for the `engine`.
+fn module(engine: &Engine) -> Result<Module, Error> {
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(engine, &wasm[..])
+}
+
+/// Calls the `(i32) -> i32` function exported as `name` in a new instance of `module`.
+fn call(module: &Module, name: &str, input: i32) -> Result<i32, Error> {
+    let mut store = Store::new(module.engine(), ());
+    let instance = <Linker<()>>::new(module.engine())
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    match name {
+        "invalid" => instance
+            .get_typed_func::<(), i32>(&store, name)
+            .unwrap()
+            .call(&mut store, ()),
+        _ => instance
+            .get_typed_func::<i32, i32>(&store, name)
+            .unwrap()
+            .call(&mut store, input),
+    }
+}
+
+/// Asserts that `error` reports a lazy compilation failure of the function at `expected_index`.
+fn assert_lazy_compilation_failed(error: Error, expected_index: u32) {
+    assert_matches!(
+        error.kind(),
+        ErrorKind::LazyCompilationFailed { func_index, source }
+        if *func_index == expected_index && matches!(source.kind(), ErrorKind::Wasm(_))
+    );
+}
+
+#[test]
+fn lazy_invalid_func_fails_upon_call() {
+    let engine = engine(CompilationMode::Lazy);
+    let module = module(&engine).unwrap();
+    assert_eq!(call(&module, "valid", 1).unwrap(), 2);
+    let error = call(&module, "invalid", 0).unwrap_err();
+    assert_lazy_compilation_failed(error, 1);
+    // Subsequent calls report the same error.
+    let error = call(&module, "invalid", 0).unwrap_err();
+    assert_lazy_compilation_failed(error, 1);
+    // The valid function is not affected by the failure.
+    assert_eq!(call(&module, "valid", 41).unwrap(), 42);
+}
+
+#[test]
+fn lazy_translation_invalid_func_fails_eagerly() {
+    for mode in [CompilationMode::Eager, CompilationMode::LazyTranslation] {
+        let engine = engine(mode);
+        let error = module(&engine).unwrap_err();
+        assert_matches!(error.kind(), ErrorKind::Wasm(_));
+    }
+}
+
+#[test]
+fn lazy_concurrent_first_calls() {
+    let engine = engine(CompilationMode::Lazy);
+    let module = module(&engine).unwrap();
+    thread::scope(|scope| {
+        let handles = (0..4)
+            .map(|n| {
+                let module = &module;
+                scope.spawn(move || {
+                    assert_eq!(call(module, "valid", n).unwrap(), n + 1);
+                    call(module, "invalid", 0).unwrap_err()
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            assert_lazy_compilation_failed(handle.join().unwrap(), 1);
+        }
+    });
+}
+
+/// The number of functions of the [`stress_module`].
+const STRESS_FUNCS: i32 = if cfg!(miri) { 4 } else { 100 };
+
+/// The number of threads concurrently executing the [`stress_module`].
+const STRESS_THREADS: i32 = if cfg!(miri) { 2 } else { 8 };
+
+/// Creates a lazily compiled [`Module`] with [`STRESS_FUNCS`] exported functions for `engine`.
+///
+/// The `(i32) -> i32` function exported as `"f{n}"` adds `n` to its parameter
+/// by calling the function `"f{n-1}"` and adding `1` to its result.
+fn stress_module(engine: &Engine) -> Module {
+    let mut wat = String::from("(module\n");
+    wat.push_str("(func $f0 (export \"f0\") (param i32) (result i32) (local.get 0))\n");
+    for n in 1..STRESS_FUNCS {
+        let prev = n - 1;
+        wat.push_str(&format!(
+            "(func $f{n} (export \"f{n}\") (param i32) (result i32)
+                (i32.add (call $f{prev} (local.get 0)) (i32.const 1))
+            )\n"
+        ));
+    }
+    wat.push(')');
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(engine, &wasm[..]).unwrap()
+}
+
+/// Instantiates `module` in a new [`Store`] with the given amount of `fuel` if any.
+fn stress_instance(module: &Module, fuel: Option<u64>) -> (Store<()>, Instance) {
+    let mut store = Store::new(module.engine(), ());
+    if let Some(fuel) = fuel {
+        store.add_fuel(fuel).unwrap();
+    }
+    let instance = <Linker<()>>::new(module.engine())
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Returns the order in which the thread `n` calls the exported functions of the [`stress_module`].
+///
+/// Threads start at different functions and alternate their direction
+/// so that they race for the lazy compilation of different functions.
+fn stress_order(n: i32) -> Vec<i32> {
+    let start = n * STRESS_FUNCS / STRESS_THREADS;
+    let order = (0..STRESS_FUNCS).map(|i| (start + i) % STRESS_FUNCS);
+    match n % 2 {
+        0 => order.collect(),
+        _ => order.rev().collect(),
+    }
+}
+
+#[test]
+fn lazy_concurrent_stress() {
+    let engine = engine(CompilationMode::Lazy);
+    let module = stress_module(&engine);
+    thread::scope(|scope| {
+        for n in 0..STRESS_THREADS {
+            let module = &module;
+            scope.spawn(move || {
+                let (mut store, instance) = stress_instance(module, None);
+                for index in stress_order(n) {
+                    let func = instance
+                        .get_typed_func::<i32, i32>(&store, &format!("f{index}"))
+                        .unwrap();
+                    assert_eq!(func.call(&mut store, n).unwrap(), n + index);
+                }
+            });
+        }
+    });
+}
+
+/// Returns the fuel consumed by `store` including the fuel consumed for lazy compilation.
+fn total_fuel_consumed(store: &Store<()>) -> u64 {
+    store.fuel_consumed().unwrap() + store.compilation_fuel_consumed().unwrap()
+}
+
+#[test]
+fn lazy_concurrent_stress_out_of_fuel() {
+    /// The fuel provided for every successful call.
+    const FUEL: u64 = 1_000_000;
+    let mut config = Config::default();
+    config
+        .compilation_mode(CompilationMode::Lazy)
+        .consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = stress_module(&engine);
+    thread::scope(|scope| {
+        for n in 0..STRESS_THREADS {
+            let module = &module;
+            scope.spawn(move || {
+                let (mut store, instance) = stress_instance(module, Some(0));
+                for index in stress_order(n) {
+                    let func = instance
+                        .get_typed_func::<i32, i32>(&store, &format!("f{index}"))
+                        .unwrap();
+                    // Running out of fuel aborts a lazy compilation without failing it.
+                    let error = func.call(&mut store, n).unwrap_err();
+                    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+                    let consumed = total_fuel_consumed(&store);
+                    store.add_fuel(FUEL).unwrap();
+                    assert_eq!(func.call(&mut store, n).unwrap(), n + index);
+                    // Drain the remaining fuel so that the next call runs out of fuel again.
+                    let remaining = FUEL - (total_fuel_consumed(&store) - consumed);
+                    store.consume_fuel(remaining).unwrap();
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn verify_bytecode_compiles_lazy_funcs() {
+    let engine = engine(CompilationMode::Lazy);
+    let module = module(&engine).unwrap();
+    let error = module.verify_bytecode().unwrap_err();
+    assert_lazy_compilation_failed(error, 1);
+    let wasm =
+        wat::parse_str(r#"(module (func (export "f") (result i32) (i32.const 1)))"#).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    module.verify_bytecode().unwrap();
+}
+
+/// A Wasm module with ten exported `(i32) -> i32` functions `"f0"` to `"f9"` of varying sizes.
+///
+/// Some of the functions call others so that lazy compilation also happens within executions.
+const FUEL_WAT: &str = r#"
+    (module
+        (func $f0 (export "f0") (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func $f1 (export "f1") (param i32) (result i32)
+            (i32.mul (call $f0 (local.get 0)) (i32.const 2))
+        )
+        (func $f2 (export "f2") (param i32) (result i32)
+            (if (result i32) (i32.eqz (local.get 0))
+                (then (i32.const 7))
+                (else (call $f1 (i32.sub (local.get 0) (i32.const 1))))
+            )
+        )
+        (func $f3 (export "f3") (param $n i32) (result i32)
+            (local $acc i32)
+            (block $done
+                (loop $continue
+                    (br_if $done (i32.eqz (local.get $n)))
+                    (local.set $acc (i32.add (local.get $acc) (local.get $n)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+        (func $f4 (export "f4") (param i32) (result i32)
+            (i32.add (call $f3 (local.get 0)) (call $f2 (local.get 0)))
+        )
+        (func $f5 (export "f5") (param i32) (result i32)
+            (i32.xor (local.get 0) (i32.const 0x55))
+        )
+        (func $f6 (export "f6") (param i32) (result i32)
+            (call $f5 (call $f4 (local.get 0)))
+        )
+        (func $f7 (export "f7") (param i32) (result i32)
+            (select (local.get 0) (i32.const 3) (i32.gt_s (local.get 0) (i32.const 3)))
+        )
+        (func $f8 (export "f8") (param i32) (result i32)
+            (i32.rotl (call $f7 (local.get 0)) (i32.const 5))
+        )
+        (func $f9 (export "f9") (param i32) (result i32)
+            (i32.sub (call $f8 (local.get 0)) (call $f6 (local.get 0)))
+        )
+    )
+"#;
+
+/// Calls the functions of [`FUEL_WAT`] in the given `order` using `config` with the `mode`.
+///
+/// Returns the results of all calls followed by the consumed execution and compilation fuel.
+fn run_fuel_module(mut config: Config, mode: CompilationMode, order: &[usize]) -> Vec<u64> {
+    config.consume_fuel(true).compilation_mode(mode);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(FUEL_WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let (mut store, instance) = stress_instance(&module, Some(1_000_000));
+    let mut results: Vec<u64> = order
+        .iter()
+        .map(|index| {
+            let func = instance
+                .get_typed_func::<i32, i32>(&store, &format!("f{index}"))
+                .unwrap();
+            func.call(&mut store, *index as i32).unwrap() as u32 as u64
+        })
+        .collect();
+    results.push(store.fuel_consumed().unwrap());
+    results.push(store.compilation_fuel_consumed().unwrap());
+    results
+}
+
+#[test]
+fn fuel_consumed_is_independent_of_compilation_mode() {
+    let orders: [&[usize]; 4] = [
+        &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+        &[9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+        &[4, 9, 0, 6, 2, 2, 7, 9, 1, 5, 3, 8],
+        &[6, 6, 6, 1, 3],
+    ];
+    let mut costs = FuelCosts::default();
+    // Note: charge compilation fuel for every byte so that small functions are charged as well.
+    costs.set_bytes_per_fuel(NonZeroU64::new(1).unwrap());
+    for charge in [true, false] {
+        let mut config = Config::default();
+        config.set_fuel_costs(costs).charge_compilation_fuel(charge);
+        for order in orders {
+            let mut expected = run_fuel_module(config, CompilationMode::Eager, order);
+            assert_eq!(expected.pop(), Some(0));
+            for mode in [CompilationMode::LazyTranslation, CompilationMode::Lazy] {
+                let mut results = run_fuel_module(config, mode, order);
+                let compilation_fuel = results.pop().unwrap();
+                assert_eq!(results, expected, "{mode:?} with order {order:?}");
+                assert_eq!(compilation_fuel > 0, charge);
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Tests to check that lazily initialized tables behave the same as eagerly initialized tables.
+
+use wasmi::{core::TrapCode, Config, Engine, Instance, Linker, Module, Store, Table, Value};
+
+/// The Wasm module under test.
+///
+/// - Table `$t0` is initialized by overlapping active element segments.
+/// - Table `$t1` is empty and used as destination for `table.copy`.
+const WAT: &str = r#"
+    (module
+        (type $ty (func (result i32)))
+        (table $t0 (export "t0") 8 funcref)
+        (table $t1 (export "t1") 8 funcref)
+        (func $f0 (result i32) (i32.const 0))
+        (func $f1 (result i32) (i32.const 1))
+        (func $f2 (result i32) (i32.const 2))
+        (func $f3 (result i32) (i32.const 3))
+        (elem (table $t0) (i32.const 0) func $f0 $f1 $f2 $f3)
+        ;; Overwrites some elements of the previous element segment.
+        (elem (table $t0) (i32.const 2) func $f3 $f2)
+        (elem (table $t0) (i32.const 5) funcref (ref.null func) (ref.func $f1))
+        (func (export "call0") (param i32) (result i32)
+            (call_indirect $t0 (type $ty) (local.get 0))
+        )
+        (func (export "call1") (param i32) (result i32)
+            (call_indirect $t1 (type $ty) (local.get 0))
+        )
+        (func (export "is_null0") (param i32) (result i32)
+            (ref.is_null (table.get $t0 (local.get 0)))
+        )
+        (func (export "copy") (param $dst i32) (param $src i32) (param $len i32)
+            (table.copy $t1 $t0 (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "copy_within") (param $dst i32) (param $src i32) (param $len i32)
+            (table.copy $t0 $t0 (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "fill_null") (param $dst i32) (param $len i32)
+            (table.fill $t0 (local.get $dst) (ref.null func) (local.get $len))
+        )
+        (func (export "set") (param $dst i32) (param $src i32)
+            (table.set $t0 (local.get $dst) (table.get $t1 (local.get $src)))
+        )
+        (func (export "grow") (param $delta i32) (result i32)
+            (table.grow $t0 (ref.null func) (local.get $delta))
+        )
+    )
+"#;
+
+/// The observed state of a table element.
+///
+/// - `Ok(n)` if the element is a function returning `n`.
+/// - `Err(trap_code)` if calling the element traps.
+type Element = Result<i32, TrapCode>;
+
+/// A test instance of [`WAT`].
+struct Test {
+    store: Store<()>,
+    instance: Instance,
+}
+
+impl Test {
+    /// Instantiates [`WAT`] with lazy table initialization set to `lazy`.
+    fn new(lazy: bool) -> Self {
+        let mut config = Config::default();
+        config.lazy_table_init(lazy);
+        let engine = Engine::new(&config);
+        let mut store = Store::new(&engine, ());
+        let wasm = wat::parse_str(WAT).unwrap();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        Self { store, instance }
+    }
+
+    /// Calls the exported function `name` with `params` and returns its `i32` result if any.
+    fn call(&mut self, name: &str, params: &[Value]) -> Result<Option<i32>, TrapCode> {
+        let func = self.instance.get_func(&self.store, name).unwrap();
+        let mut results = vec![Value::I32(0); func.ty(&self.store).results().len()];
+        func.call(&mut self.store, params, &mut results)
+            .map_err(|error| error.as_trap_code().unwrap())?;
+        Ok(results.first().and_then(Value::i32))
+    }
+
+    /// Returns the table exported as `name`.
+    fn table(&self, name: &str) -> Table {
+        self.instance.get_table(&self.store, name).unwrap()
+    }
+
+    /// Returns the observed elements of the table exported as `name` via the host API.
+    fn host_elements(&mut self, name: &str) -> Vec<Element> {
+        let table = self.table(name);
+        (0..table.size(&self.store))
+            .map(|index| {
+                let value = table.get(&self.store, index).unwrap();
+                let func = value.funcref().unwrap().func().copied();
+                let func = func.ok_or(TrapCode::IndirectCallToNull)?;
+                let func = func.typed::<(), i32>(&self.store).unwrap();
+                Ok(func.call(&mut self.store, ()).unwrap())
+            })
+            .collect()
+    }
+
+    /// Returns the observed elements of the table exported as `name` via `call_indirect`.
+    fn wasm_elements(&mut self, name: &str, call: &str) -> Vec<Element> {
+        let size = self.table(name).size(&self.store);
+        (0..size)
+            .map(|index| {
+                let result = self.call(call, &[Value::I32(index as i32)])?;
+                Ok(result.unwrap())
+            })
+            .collect()
+    }
+
+    /// Returns the observed elements of both tables.
+    ///
+    /// # Panics
+    ///
+    /// If the host API and `call_indirect` disagree on the observed elements.
+    fn elements(&mut self) -> [Vec<Element>; 2] {
+        let t0 = self.host_elements("t0");
+        let t1 = self.host_elements("t1");
+        assert_eq!(t0, self.wasm_elements("t0", "call0"));
+        assert_eq!(t1, self.wasm_elements("t1", "call1"));
+        [t0, t1]
+    }
+}
+
+/// Runs `ops` on an eagerly and a lazily initialized [`Test`] and compares their tables.
+///
+/// Returns the observed elements of both tables after running all `ops`.
+fn assert_same(ops: &[(&str, &[Value])]) -> [Vec<Element>; 2] {
+    let mut eager = Test::new(false);
+    let mut lazy = Test::new(true);
+    for (name, params) in ops {
+        assert_eq!(eager.call(name, params), lazy.call(name, params), "{name}");
+    }
+    let expected = eager.elements();
+    assert_eq!(lazy.elements(), expected);
+    expected
+}
+
+const NULL: Element = Err(TrapCode::IndirectCallToNull);
+
+#[test]
+fn instantiate() {
+    let [t0, t1] = assert_same(&[]);
+    assert_eq!(t0, [Ok(0), Ok(1), Ok(3), Ok(2), NULL, NULL, Ok(1), NULL]);
+    assert_eq!(t1, [NULL; 8]);
+}
+
+#[test]
+fn table_get() {
+    let is_null = |index: i32| ("is_null0", [Value::I32(index)]);
+    let ops = [is_null(0), is_null(3), is_null(4), is_null(5), is_null(6)];
+    let ops = ops
+        .iter()
+        .map(|(name, params)| (*name, &params[..]))
+        .collect::<Vec<_>>();
+    assert_same(&ops);
+    let mut lazy = Test::new(true);
+    let results = [0, 3, 4, 5, 6].map(|index| lazy.call("is_null0", &[Value::I32(index)]));
+    let results = results.map(|result| result.unwrap().unwrap());
+    assert_eq!(results, [0, 0, 1, 1, 0]);
+}
+
+#[test]
+fn table_copy() {
+    let [_, t1] = assert_same(&[("copy", &[Value::I32(1), Value::I32(1), Value::I32(7)])]);
+    assert_eq!(t1, [NULL, Ok(1), Ok(3), Ok(2), NULL, NULL, Ok(1), NULL]);
+    // Out of bounds copies trap without modifying the table.
+    let [_, t1] = assert_same(&[("copy", &[Value::I32(0), Value::I32(4), Value::I32(5)])]);
+    assert_eq!(t1, [NULL; 8]);
+}
+
+#[test]
+fn table_copy_within() {
+    // Copies overlapping uninitialized elements to higher indices.
+    let [t0, _] = assert_same(&[(
+        "copy_within",
+        &[Value::I32(1), Value::I32(0), Value::I32(4)],
+    )]);
+    assert_eq!(t0, [Ok(0), Ok(0), Ok(1), Ok(3), Ok(2), NULL, Ok(1), NULL]);
+    // Copies overlapping uninitialized elements to lower indices.
+    let [t0, _] = assert_same(&[(
+        "copy_within",
+        &[Value::I32(0), Value::I32(2), Value::I32(5)],
+    )]);
+    assert_eq!(t0, [Ok(3), Ok(2), NULL, NULL, Ok(1), NULL, Ok(1), NULL]);
+}
+
+#[test]
+fn table_fill() {
+    let [t0, _] = assert_same(&[("fill_null", &[Value::I32(1), Value::I32(2)])]);
+    assert_eq!(t0, [Ok(0), NULL, NULL, Ok(2), NULL, NULL, Ok(1), NULL]);
+}
+
+#[test]
+fn table_set() {
+    let [t0, _] = assert_same(&[
+        ("copy", &[Value::I32(0), Value::I32(0), Value::I32(1)]),
+        ("set", &[Value::I32(6), Value::I32(0)]),
+        ("set", &[Value::I32(2), Value::I32(7)]),
+    ]);
+    assert_eq!(t0, [Ok(0), Ok(1), NULL, Ok(2), NULL, NULL, Ok(0), NULL]);
+}
+
+#[test]
+fn table_grow() {
+    let [t0, _] = assert_same(&[
+        ("grow", &[Value::I32(2)]),
+        (
+            "copy_within",
+            &[Value::I32(8), Value::I32(2), Value::I32(2)],
+        ),
+    ]);
+    assert_eq!(
+        t0,
+        [
+            Ok(0),
+            Ok(1),
+            Ok(3),
+            Ok(2),
+            NULL,
+            NULL,
+            Ok(1),
+            NULL,
+            Ok(3),
+            Ok(2)
+        ]
+    );
+}
+
+#[test]
+fn large_table() {
+    // Element segments crossing multiple 64-bit words of the pending bitmap.
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (type $ty (func (result i32)))
+            (table $t 200 funcref)
+            (func $f0 (result i32) (i32.const 0))
+            (func $f1 (result i32) (i32.const 1))
+            (elem (table $t) (i32.const 60) func
+                $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0
+                $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0
+                $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0
+                $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0 $f0
+                $f0 $f0 $f0 $f0 $f0 $f0
+            )
+            (elem (table $t) (i32.const 127) func $f1 $f1 $f1)
+            (func (export "call") (param i32) (result i32)
+                (call_indirect $t (type $ty) (local.get 0))
+            )
+            (func (export "fill_null") (param $dst i32) (param $len i32)
+                (table.fill $t (local.get $dst) (ref.null func) (local.get $len))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    let mut config = Config::default();
+    config.lazy_table_init(true);
+    let engine = Engine::new(&config);
+    let mut store = Store::new(&engine, ());
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let call = instance.get_typed_func::<i32, i32>(&store, "call").unwrap();
+    let fill_null = instance
+        .get_typed_func::<(i32, i32), ()>(&store, "fill_null")
+        .unwrap();
+    fill_null.call(&mut store, (100, 10)).unwrap();
+    for index in 0..200 {
+        let expected = match index {
+            100..=109 => NULL,
+            127..=129 => Ok(1),
+            60..=129 => Ok(0),
+            _ => NULL,
+        };
+        let result = call
+            .call(&mut store, index)
+            .map_err(|error| error.as_trap_code().unwrap());
+        assert_eq!(result, expected, "index = {index}");
+    }
+}
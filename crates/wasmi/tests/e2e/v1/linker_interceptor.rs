@@ -0,0 +1,160 @@
+//! Tests to check that [`Linker`] interceptors are called around host function calls.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use wasmi::{
+    core::ValueType,
+    Caller,
+    Engine,
+    Error,
+    FuncType,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+    Value,
+};
+
+/// A Wasm module calling both of its imported host functions.
+const WAT: &str = r#"
+    (module
+        (import "env" "add" (func $add (param i32 i32) (result i32)))
+        (import "env" "double" (func $double (param i32) (result i32)))
+        (func (export "run") (param i32) (result i32)
+            (call $double
+                (call $add (local.get 0) (i32.const 1))
+            )
+        )
+    )
+"#;
+
+/// Returns a [`Linker`] defining the imports of [`WAT`].
+///
+/// - `add` is defined via [`Linker::func_wrap`].
+/// - `double` is defined via [`Linker::func_new`].
+fn test_setup() -> (Store<()>, Linker<()>) {
+    let engine = Engine::default();
+    let store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "add", |lhs: i32, rhs: i32| lhs.wrapping_add(rhs))
+        .unwrap();
+    linker
+        .func_new(
+            "env",
+            "double",
+            FuncType::new([ValueType::I32], [ValueType::I32]),
+            |_caller: Caller<()>, params: &[Value], results: &mut [Value]| {
+                results[0] = Value::I32(params[0].i32().unwrap().wrapping_mul(2));
+                Ok(())
+            },
+        )
+        .unwrap();
+    (store, linker)
+}
+
+/// Instantiates [`WAT`] and returns its exported `run` function.
+fn instantiate(store: &mut Store<()>, linker: &Linker<()>) -> TypedFunc<i32, i32> {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance: Instance = linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap();
+    instance.get_typed_func::<i32, i32>(&*store, "run").unwrap()
+}
+
+#[test]
+fn interceptor_counts_calls() {
+    let (mut store, mut linker) = test_setup();
+    let count = Arc::new(AtomicUsize::new(0));
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    linker.set_interceptor({
+        let count = count.clone();
+        let calls = calls.clone();
+        move |info, params, next, results| {
+            count.fetch_add(1, Ordering::SeqCst);
+            calls.lock().unwrap().push((
+                info.module().to_string(),
+                info.name().to_string(),
+                info.ty().clone(),
+            ));
+            next(params, results)
+        }
+    });
+    let run = instantiate(&mut store, &linker);
+    assert_eq!(run.call(&mut store, 5).unwrap(), 12);
+    assert_eq!(run.call(&mut store, 10).unwrap(), 22);
+    assert_eq!(count.load(Ordering::SeqCst), 4);
+    let calls = calls.lock().unwrap();
+    assert_eq!(calls[0].0, "env");
+    assert_eq!(calls[0].1, "add");
+    assert_eq!(
+        calls[0].2,
+        FuncType::new([ValueType::I32; 2], [ValueType::I32])
+    );
+    assert_eq!(calls[1].0, "env");
+    assert_eq!(calls[1].1, "double");
+    assert_eq!(
+        calls[1].2,
+        FuncType::new([ValueType::I32], [ValueType::I32])
+    );
+}
+
+#[test]
+fn interceptor_alters_params_and_results() {
+    let (mut store, mut linker) = test_setup();
+    linker.set_interceptor(|info, params, next, results| {
+        if info.name() != "add" {
+            return next(params, results);
+        }
+        let lhs = params[0].i32().unwrap();
+        next(&[Value::I32(lhs * 10), params[1].clone()], results)?;
+        results[0] = Value::I32(results[0].i32().unwrap() + 1);
+        Ok(())
+    });
+    let run = instantiate(&mut store, &linker);
+    // (5 * 10 + 1 + 1) * 2
+    assert_eq!(run.call(&mut store, 5).unwrap(), 104);
+}
+
+#[test]
+fn interceptor_denies_call() {
+    #[derive(Debug)]
+    struct Denied;
+
+    impl core::fmt::Display for Denied {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "denied")
+        }
+    }
+
+    impl wasmi::core::HostError for Denied {}
+
+    let (mut store, mut linker) = test_setup();
+    let double_called = Arc::new(AtomicUsize::new(0));
+    linker.set_interceptor({
+        let double_called = double_called.clone();
+        move |info, params, next, results| {
+            if info.name() == "double" {
+                double_called.fetch_add(1, Ordering::SeqCst);
+                return Err(Error::host(Denied));
+            }
+            next(params, results)
+        }
+    });
+    let run = instantiate(&mut store, &linker);
+    let error = run.call(&mut store, 5).unwrap_err();
+    assert!(error.downcast_ref::<Denied>().is_some());
+    assert_eq!(double_called.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn interceptor_rejects_mismatching_params() {
+    let (mut store, mut linker) = test_setup();
+    linker.set_interceptor(|_info, _params, next, results| next(&[], results));
+    let run = instantiate(&mut store, &linker);
+    assert!(run.call(&mut store, 5).is_err());
+}
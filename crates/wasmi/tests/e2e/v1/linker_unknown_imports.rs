@@ -0,0 +1,215 @@
+//! Tests for [`Linker`] definitions of imports that are otherwise unknown.
+
+use wasmi::{
+    Engine,
+    Extern,
+    ExternType,
+    Global,
+    Linker,
+    Memory,
+    Module,
+    Mutability,
+    Store,
+    Value,
+};
+
+/// A Wasm module with 5 function imports and an imported memory and global.
+const WAT: &str = r#"
+    (module
+        (import "env" "f0" (func $f0))
+        (import "env" "f1" (func $f1 (param i32)))
+        (import "env" "f2" (func $f2 (result i32)))
+        (import "host" "f3" (func $f3 (param i64 f32) (result f64)))
+        (import "host" "f4" (func $f4 (param i32 i32) (result i32 i32)))
+        (import "env" "memory" (memory 2 5))
+        (import "env" "offset" (global i32))
+        (func (export "call_f2") (result i32)
+            (call $f2)
+        )
+        (func (export "load") (result i32)
+            (i32.load (global.get 0))
+        )
+        (func (export "size") (result i32)
+            (memory.size)
+        )
+    )
+"#;
+
+/// Returns the [`Module`] of [`WAT`].
+fn module(engine: &Engine) -> Module {
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(engine, &wasm[..]).unwrap()
+}
+
+/// Defines the memory and global imports of [`WAT`] via the unknown imports handler.
+fn define_memory_and_global(linker: &mut Linker<()>) {
+    linker.define_unknown_imports_with(|mut store, module, name, ty| match (module, name, ty) {
+        ("env", "memory", ExternType::Memory(ty)) => {
+            Memory::new(&mut store, *ty).ok().map(Extern::from)
+        }
+        ("env", "offset", ExternType::Global(_)) => Some(Extern::from(Global::new(
+            &mut store,
+            Value::I32(8),
+            Mutability::Const,
+        ))),
+        _ => None,
+    });
+}
+
+#[test]
+fn define_unknown_imports_as_traps() {
+    let engine = Engine::default();
+    let module = module(&engine);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define_unknown_imports_as_traps(&module).unwrap();
+    define_memory_and_global(&mut linker);
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let error = instance
+        .get_typed_func::<(), i32>(&store, "call_f2")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert!(error.to_string().contains("env::f2"), "{error}");
+}
+
+#[test]
+fn explicit_definitions_take_precedence() {
+    let engine = Engine::default();
+    let module = module(&engine);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "f2", || 42_i32).unwrap();
+    linker.define_unknown_imports_as_traps(&module).unwrap();
+    linker.define_unknown_imports_with(|mut store, module, name, ty| {
+        assert_ne!((module, name), ("env", "f2"));
+        match ty {
+            ExternType::Memory(ty) => Memory::new(&mut store, *ty).ok().map(Extern::from),
+            ExternType::Global(_) => Some(Extern::from(Global::new(
+                &mut store,
+                Value::I32(0),
+                Mutability::Const,
+            ))),
+            _ => None,
+        }
+    });
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let result = instance
+        .get_typed_func::<(), i32>(&store, "call_f2")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(result, 42);
+}
+
+#[test]
+fn synthesized_memory_matches_limits() {
+    let engine = Engine::default();
+    let module = module(&engine);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define_unknown_imports_as_traps(&module).unwrap();
+    define_memory_and_global(&mut linker);
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let size = instance
+        .get_typed_func::<(), i32>(&store, "size")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(size, 2);
+    let load = instance
+        .get_typed_func::<(), i32>(&store, "load")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(load, 0);
+}
+
+#[test]
+fn handler_returning_none_fails() {
+    let engine = Engine::default();
+    let module = module(&engine);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define_unknown_imports_as_traps(&module).unwrap();
+    linker.define_unknown_imports_with(|_store, _module, _name, _ty| None);
+    let error = linker.instantiate(&mut store, &module).unwrap_err();
+    assert!(error.to_string().contains("env::memory"), "{error}");
+}
+
+#[test]
+fn handler_returning_mismatched_type_fails() {
+    let engine = Engine::default();
+    let module = module(&engine);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define_unknown_imports_as_traps(&module).unwrap();
+    linker.define_unknown_imports_with(|mut store, _module, _name, ty| match ty {
+        // The synthesized memory does not satisfy the minimum of 2 pages.
+        ExternType::Memory(ty) => {
+            let ty = wasmi::MemoryType::new(1, ty.maximum_pages().map(u32::from)).unwrap();
+            Memory::new(&mut store, ty).ok().map(Extern::from)
+        }
+        ExternType::Global(_) => Some(Extern::from(Global::new(
+            &mut store,
+            Value::I64(0),
+            Mutability::Const,
+        ))),
+        _ => None,
+    });
+    assert!(linker.instantiate(&mut store, &module).is_err());
+}
+
+#[test]
+fn unknown_imports_handler_synthesizes_funcs() {
+    let engine = Engine::default();
+    let module = module(&engine);
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    define_memory_and_global(&mut linker);
+    assert!(linker.instantiate(&mut store, &module).is_err());
+    linker.define_unknown_imports_with(|mut store, _module, _name, ty| match ty {
+        ExternType::Func(ty) => {
+            let len_results = ty.results().len();
+            let results = ty.results().to_vec();
+            let func = wasmi::Func::new(&mut store, ty.clone(), move |_caller, _params, out| {
+                assert_eq!(out.len(), len_results);
+                for (out, ty) in out.iter_mut().zip(&results) {
+                    *out = Value::default(*ty);
+                }
+                Ok(())
+            });
+            Some(Extern::from(func))
+        }
+        ExternType::Memory(ty) => Memory::new(&mut store, *ty).ok().map(Extern::from),
+        ExternType::Global(_) => Some(Extern::from(Global::new(
+            &mut store,
+            Value::I32(0),
+            Mutability::Const,
+        ))),
+        ExternType::Table(_) => None,
+    });
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let result = instance
+        .get_typed_func::<(), i32>(&store, "call_f2")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(result, 0);
+}
@@ -0,0 +1,190 @@
+//! Tests for functions and blocks with many results of mixed types.
+//!
+//! All functions in these tests return [`LEN`] results which exceeds the
+//! number of values that are handled by the specialized return and copy instructions.
+
+use wasmi::{
+    core::ValueType,
+    Caller,
+    Engine,
+    Func,
+    FuncType,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+
+/// The number of results of the tested functions.
+const LEN: usize = 64;
+
+/// Returns the [`ValueType`] of the result at `index`.
+fn result_type(index: usize) -> ValueType {
+    [
+        ValueType::I32,
+        ValueType::I64,
+        ValueType::F32,
+        ValueType::F64,
+    ][index % 4]
+}
+
+/// Returns the Wasm text name of the [`ValueType`] of the result at `index`.
+fn result_type_str(index: usize) -> &'static str {
+    ["i32", "i64", "f32", "f64"][index % 4]
+}
+
+/// Returns the expected result at `index` for input `x` of a function that returns
+/// its results rotated by `shift`.
+fn expected(index: usize, x: i32, shift: usize) -> Value {
+    let value = x + ((index + shift) % LEN) as i32;
+    match result_type(index) {
+        ValueType::I32 => Value::I32(value),
+        ValueType::I64 => Value::I64(i64::from(value)),
+        ValueType::F32 => Value::F32((value as f32).into()),
+        ValueType::F64 => Value::F64(f64::from(value).into()),
+        _ => unreachable!(),
+    }
+}
+
+/// Creates the Wasm module under test.
+///
+/// - `$many` returns `x + n` for each of its results `n` given input `x`.
+/// - `$rotate` returns its `n + 4`-th parameter as its `n`-th result.
+/// - `host` calls the imported `$host_many` with the same behavior as `$many`.
+fn wat() -> String {
+    let results = (0..LEN).map(result_type_str).collect::<Vec<_>>().join(" ");
+    let many_body = (0..LEN)
+        .map(|n| {
+            let value = format!("(i32.add (local.get 0) (i32.const {n}))");
+            match result_type_str(n) {
+                "i32" => value,
+                "i64" => format!("(i64.extend_i32_s {value})"),
+                ty => format!("({ty}.convert_i32_s {value})"),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let rotate_body = (0..LEN)
+        .map(|n| format!("(local.get {})", (n + 4) % LEN))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        r#"
+        (module
+            (type $many_t (func (param i32) (result {results})))
+            (import "host" "many" (func $host_many (type $many_t)))
+            (table funcref (elem $many))
+            (func $many (type $many_t)
+                {many_body}
+            )
+            (func $rotate (param {results}) (result {results})
+                {rotate_body}
+            )
+            (func (export "direct") (type $many_t)
+                (call $many (local.get 0))
+            )
+            (func (export "indirect") (type $many_t)
+                (call_indirect (type $many_t) (local.get 0) (i32.const 0))
+            )
+            (func (export "chain") (type $many_t)
+                (call $rotate (call $many (local.get 0)))
+            )
+            (func (export "block") (type $many_t)
+                (block $exit (result {results})
+                    (call $many (local.get 0))
+                    (br_if $exit (local.get 0))
+                    (call $rotate)
+                )
+            )
+            (func (export "host") (type $many_t)
+                (call $host_many (local.get 0))
+            )
+        )
+    "#
+    )
+}
+
+/// Instantiates the module returned by [`wat`].
+fn instantiate() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat()).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let ty = FuncType::new([ValueType::I32], (0..LEN).map(result_type));
+    let host_many = Func::new(
+        &mut store,
+        ty,
+        |_caller: Caller<()>, params: &[Value], results: &mut [Value]| {
+            let x = params[0].i32().unwrap();
+            for (n, result) in results.iter_mut().enumerate() {
+                *result = expected(n, x, 0);
+            }
+            Ok(())
+        },
+    );
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("host", "many", host_many).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` with `x` and asserts that its results
+/// are the results of `$many` rotated by `shift`.
+fn assert_call(store: &mut Store<()>, instance: &Instance, name: &str, x: i32, shift: usize) {
+    let func = instance.get_func(&*store, name).unwrap();
+    let mut results = (0..LEN)
+        .map(|n| Value::default(result_type(n)))
+        .collect::<Vec<_>>();
+    func.call(&mut *store, &[Value::I32(x)], &mut results)
+        .unwrap();
+    for (n, result) in results.iter().enumerate() {
+        let expected = expected(n, x, shift);
+        let matches = match (result, &expected) {
+            (Value::I32(lhs), Value::I32(rhs)) => lhs == rhs,
+            (Value::I64(lhs), Value::I64(rhs)) => lhs == rhs,
+            (Value::F32(lhs), Value::F32(rhs)) => lhs == rhs,
+            (Value::F64(lhs), Value::F64(rhs)) => lhs == rhs,
+            _ => false,
+        };
+        assert!(
+            matches,
+            "{name}: result {n} is {result:?} but expected {expected:?}"
+        );
+    }
+}
+
+#[test]
+fn direct_call() {
+    let (mut store, instance) = instantiate();
+    assert_call(&mut store, &instance, "direct", 10, 0);
+}
+
+#[test]
+fn indirect_call() {
+    let (mut store, instance) = instantiate();
+    assert_call(&mut store, &instance, "indirect", 10, 0);
+}
+
+#[test]
+fn chained_calls() {
+    let (mut store, instance) = instantiate();
+    assert_call(&mut store, &instance, "chain", 10, 4);
+}
+
+#[test]
+fn block_results() {
+    let (mut store, instance) = instantiate();
+    assert_call(&mut store, &instance, "block", 10, 0);
+    assert_call(&mut store, &instance, "block", 0, 4);
+}
+
+#[test]
+fn host_call() {
+    let (mut store, instance) = instantiate();
+    assert_call(&mut store, &instance, "host", 10, 0);
+}
@@ -0,0 +1,105 @@
+//! Tests for the bounds checks of linear memory loads and stores.
+
+use wasmi::{
+    core::{Pages, TrapCode},
+    Caller,
+    Engine,
+    Extern,
+    Func,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+/// A Wasm module with a single page linear memory.
+///
+/// - `load` loads an `i32` at its parameter.
+/// - `load_max_offset` loads an `i32` at its parameter with the maximum offset.
+/// - `grow_and_load` lets the host grow the memory and then loads an `i32` at its parameter.
+const WAT: &str = r#"
+    (module
+        (import "host" "grow" (func $grow))
+        (memory (export "mem") 1)
+        (func (export "load") (param i32) (result i32)
+            (i32.load (local.get 0))
+        )
+        (func (export "load_max_offset") (param i32) (result i32)
+            (i32.load offset=0xFFFFFFFF (local.get 0))
+        )
+        (func (export "grow_and_load") (param i32) (result i32)
+            (drop (i32.load (i32.const 0)))
+            (call $grow)
+            (i32.load (local.get 0))
+        )
+    )
+"#;
+
+/// Instantiates [`WAT`] with a host function that grows the memory by one page.
+fn instantiate() -> (Store<()>, Instance) {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let grow = Func::wrap(&mut store, |mut caller: Caller<()>| {
+        let memory = caller
+            .get_export("mem")
+            .and_then(Extern::into_memory)
+            .unwrap();
+        memory.grow(&mut caller, Pages::new(1).unwrap()).unwrap();
+    });
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("host", "grow", grow).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the `(i32) -> i32` export `name` of `instance` with `address`.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, address: u32) -> Option<i32> {
+    let result = instance
+        .get_typed_func::<i32, i32>(&*store, name)
+        .unwrap()
+        .call(&mut *store, address as i32);
+    match result {
+        Ok(value) => Some(value),
+        Err(error) => {
+            assert_eq!(error.as_trap_code(), Some(TrapCode::MemoryOutOfBounds));
+            None
+        }
+    }
+}
+
+const PAGE_SIZE: u32 = 65536;
+
+#[test]
+fn load_bounds() {
+    let (mut store, instance) = instantiate();
+    assert_eq!(call(&mut store, &instance, "load", 0), Some(0));
+    assert_eq!(call(&mut store, &instance, "load", PAGE_SIZE - 4), Some(0));
+    assert_eq!(call(&mut store, &instance, "load", PAGE_SIZE - 3), None);
+    assert_eq!(call(&mut store, &instance, "load", u32::MAX), None);
+    assert_eq!(call(&mut store, &instance, "load_max_offset", 0), None);
+    assert_eq!(
+        call(&mut store, &instance, "load_max_offset", u32::MAX),
+        None
+    );
+}
+
+#[test]
+fn host_grow_refreshes_memory_bounds() {
+    let (mut store, instance) = instantiate();
+    assert_eq!(call(&mut store, &instance, "load", PAGE_SIZE), None);
+    assert_eq!(
+        call(&mut store, &instance, "grow_and_load", 2 * PAGE_SIZE - 4),
+        Some(0)
+    );
+    assert_eq!(
+        call(&mut store, &instance, "load", 2 * PAGE_SIZE - 4),
+        Some(0)
+    );
+    assert_eq!(call(&mut store, &instance, "load", 2 * PAGE_SIZE - 3), None);
+}
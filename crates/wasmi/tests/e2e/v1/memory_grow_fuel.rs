@@ -0,0 +1,172 @@
+//! Tests to check the order in which `memory.grow` checks its limits and consumes fuel.
+
+use wasmi::{
+    core::TrapCode,
+    errors::{MemoryError, TableError},
+    Config,
+    Engine,
+    Linker,
+    Module,
+    ResourceLimiter,
+    Store,
+    TypedFunc,
+};
+
+/// The fuel consumed for growing the linear memory by a single page.
+///
+/// This is the size of a single page divided by the default bytes per fuel.
+const FUEL_PER_PAGE: u64 = 65536 / 64;
+
+/// A [`ResourceLimiter`] that may deny all `memory.grow` operations.
+#[derive(Default)]
+struct Limiter {
+    /// Is `true` if the [`Limiter`] denies all `memory.grow` operations.
+    deny: bool,
+    /// The number of failed `memory.grow` operations the [`Limiter`] has been notified about.
+    failed: usize,
+}
+
+impl ResourceLimiter for Limiter {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        _desired: usize,
+        _maximum: Option<usize>,
+    ) -> Result<bool, MemoryError> {
+        Ok(!self.deny)
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: u32,
+        _desired: u32,
+        _maximum: Option<u32>,
+    ) -> Result<bool, TableError> {
+        Ok(true)
+    }
+
+    fn memory_grow_failed(&mut self, _error: &MemoryError) {
+        self.failed += 1;
+    }
+}
+
+/// The test setup with a linear memory of 1 page minimum and 3 pages maximum.
+struct Test {
+    store: Store<Limiter>,
+    grow: TypedFunc<i32, i32>,
+    /// The fuel consumed by calling `grow` excluding the fuel for new pages.
+    base_fuel: u64,
+}
+
+impl Test {
+    /// Creates a new [`Test`] where `memory.grow` traps on out of fuel if `traps` is `true`.
+    fn new(traps: bool) -> Self {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        config.memory_grow_traps_on_out_of_fuel(traps);
+        let engine = Engine::new(&config);
+        let mut store = Store::new(&engine, Limiter::default());
+        store.limiter(|limiter| limiter);
+        let wasm = wat::parse_str(
+            r#"
+            (module
+                (memory 1 3)
+                (func (export "grow") (param i32) (result i32)
+                    (memory.grow (local.get 0))
+                )
+            )
+        "#,
+        )
+        .unwrap();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        store.add_fuel(1000).unwrap();
+        let instance = <Linker<Limiter>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let grow = instance.get_typed_func::<i32, i32>(&store, "grow").unwrap();
+        // Growing by 0 pages never consumes fuel for new pages.
+        let before = store.fuel_consumed().unwrap();
+        assert_eq!(grow.call(&mut store, 0).unwrap(), 1);
+        let base_fuel = store.fuel_consumed().unwrap() - before;
+        assert!(base_fuel > 0);
+        let mut test = Self {
+            store,
+            grow,
+            base_fuel,
+        };
+        test.set_remaining_fuel(0);
+        test
+    }
+
+    /// Sets the remaining fuel of the [`Store`] to `base_fuel + fuel`.
+    fn set_remaining_fuel(&mut self, fuel: u64) {
+        let remaining = self.store.consume_fuel(0).unwrap();
+        self.store.consume_fuel(remaining).unwrap();
+        self.store.add_fuel(self.base_fuel + fuel).unwrap();
+    }
+
+    /// Returns the remaining fuel of the [`Store`].
+    fn remaining_fuel(&mut self) -> u64 {
+        self.store.consume_fuel(0).unwrap()
+    }
+}
+
+#[test]
+fn grow_consumes_fuel_for_new_pages() {
+    let mut test = Test::new(true);
+    test.set_remaining_fuel(2 * FUEL_PER_PAGE + 10);
+    assert_eq!(test.grow.call(&mut test.store, 2).unwrap(), 1);
+    assert_eq!(test.remaining_fuel(), 10);
+    assert_eq!(test.store.data().failed, 0);
+}
+
+#[test]
+fn grow_denied_by_limiter_consumes_no_fuel() {
+    let mut test = Test::new(true);
+    test.store.data_mut().deny = true;
+    test.set_remaining_fuel(FUEL_PER_PAGE);
+    assert_eq!(test.grow.call(&mut test.store, 1).unwrap(), -1);
+    assert_eq!(test.remaining_fuel(), FUEL_PER_PAGE);
+}
+
+#[test]
+fn grow_beyond_maximum_consumes_no_fuel() {
+    let mut test = Test::new(true);
+    test.set_remaining_fuel(3 * FUEL_PER_PAGE);
+    assert_eq!(test.grow.call(&mut test.store, 3).unwrap(), -1);
+    assert_eq!(test.remaining_fuel(), 3 * FUEL_PER_PAGE);
+    assert_eq!(test.store.data().failed, 1);
+}
+
+#[test]
+fn grow_beyond_maximum_without_fuel_returns_error_code() {
+    // Note: the limits are checked before fuel so this does not trap.
+    let mut test = Test::new(true);
+    assert_eq!(test.grow.call(&mut test.store, 3).unwrap(), -1);
+    assert_eq!(test.remaining_fuel(), 0);
+}
+
+#[test]
+fn grow_out_of_fuel_traps() {
+    let mut test = Test::new(true);
+    test.set_remaining_fuel(FUEL_PER_PAGE - 1);
+    let error = test.grow.call(&mut test.store, 1).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::OutOfFuel));
+    assert_eq!(test.remaining_fuel(), FUEL_PER_PAGE - 1);
+    assert_eq!(test.store.data().failed, 1);
+}
+
+#[test]
+fn grow_out_of_fuel_returns_error_code() {
+    let mut test = Test::new(false);
+    test.set_remaining_fuel(FUEL_PER_PAGE - 1);
+    assert_eq!(test.grow.call(&mut test.store, 1).unwrap(), -1);
+    assert_eq!(test.remaining_fuel(), FUEL_PER_PAGE - 1);
+    assert_eq!(test.store.data().failed, 1);
+    // The linear memory is left unchanged and can still grow given enough fuel.
+    test.set_remaining_fuel(FUEL_PER_PAGE);
+    assert_eq!(test.grow.call(&mut test.store, 1).unwrap(), 1);
+    assert_eq!(test.remaining_fuel(), 0);
+}
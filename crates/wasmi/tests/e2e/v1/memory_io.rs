@@ -0,0 +1,234 @@
+//! Tests for streaming data into and out of a [`Memory`].
+//!
+//! This uses [`Memory::write_from`] and [`Memory::read_into`].
+
+use std::{
+    io::{self, Read, Write},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+use wasmi::{errors::MemoryIoError, Engine, Memory, MemoryType, Store};
+
+/// The number of bytes of a Wasm page.
+const PAGE_SIZE: u64 = 64 * 1024;
+
+/// Returns a [`Store`] and a [`Memory`] with `pages` pages.
+fn setup(pages: u32) -> (Store<()>, Memory) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let memory = Memory::new(&mut store, MemoryType::new(pages, None).unwrap()).unwrap();
+    (store, memory)
+}
+
+/// Returns the byte of the test pattern at `index`.
+fn pattern(index: usize) -> u8 {
+    (index % 251) as u8
+}
+
+/// The writing end of a [`pipe`].
+struct PipeWriter(Sender<Vec<u8>>);
+
+/// The reading end of a [`pipe`].
+struct PipeReader {
+    receiver: Receiver<Vec<u8>>,
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+/// Returns a unidirectional in-process pipe.
+fn pipe() -> (PipeReader, PipeWriter) {
+    let (sender, receiver) = mpsc::channel();
+    let reader = PipeReader {
+        receiver,
+        buffer: Vec::new(),
+        pos: 0,
+    };
+    (reader, PipeWriter(sender))
+}
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .send(buf.to_vec())
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.buffer.len() {
+            match self.receiver.recv() {
+                Ok(buffer) => {
+                    self.buffer = buffer;
+                    self.pos = 0;
+                }
+                // All writers have been dropped: end of stream.
+                Err(_) => return Ok(0),
+            }
+        }
+        let len = buf.len().min(self.buffer.len() - self.pos);
+        buf[..len].copy_from_slice(&self.buffer[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+/// A reader that yields `len` pattern bytes and then fails.
+struct FailingReader {
+    pos: usize,
+    len: usize,
+}
+
+impl Read for FailingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.len {
+            return Err(io::Error::other("reader failed"));
+        }
+        // Note: yields small reads to exercise multiple chunks.
+        let len = buf.len().min(self.len - self.pos).min(1000);
+        for (offset, byte) in buf[..len].iter_mut().enumerate() {
+            *byte = pattern(self.pos + offset);
+        }
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+/// A reader that panics when read.
+struct UnreachableReader;
+
+impl Read for UnreachableReader {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        panic!("must not read from reader")
+    }
+}
+
+/// Returns the [`MemoryIoError`] of `error`.
+fn as_io_error(error: &wasmi::Error) -> &MemoryIoError {
+    match error.kind() {
+        wasmi::errors::ErrorKind::MemoryIo(error) => error,
+        _ => panic!("expected a memory I/O error but found: {error}"),
+    }
+}
+
+#[test]
+fn round_trip_10mb_through_pipes() {
+    const LEN: usize = 10 * 1024 * 1024;
+    let (mut store, memory) = setup(((LEN as u64 + PAGE_SIZE) / PAGE_SIZE) as u32);
+    let offset = 100;
+    // Pipe the pattern into the memory.
+    let (mut reader, mut writer) = pipe();
+    let producer = thread::spawn(move || {
+        let pattern = (0..LEN).map(pattern).collect::<Vec<u8>>();
+        for chunk in pattern.chunks(12345) {
+            writer.write_all(chunk).unwrap();
+        }
+    });
+    let written = memory
+        .write_from(&mut store, offset, &mut reader, LEN as u64)
+        .unwrap();
+    producer.join().unwrap();
+    assert_eq!(written, LEN as u64);
+    // Pipe the memory back out.
+    let (mut reader, mut writer) = pipe();
+    let consumer = thread::spawn(move || {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).unwrap();
+        bytes
+    });
+    let read = memory
+        .read_into(&store, offset, &mut writer, LEN as u64)
+        .unwrap();
+    drop(writer);
+    assert_eq!(read, LEN as u64);
+    let bytes = consumer.join().unwrap();
+    assert_eq!(bytes.len(), LEN);
+    assert!(bytes.iter().enumerate().all(|(n, &byte)| byte == pattern(n)));
+    // The bytes surrounding the written range are untouched.
+    let data = memory.data(&store);
+    assert!(data[..offset as usize].iter().all(|&byte| byte == 0));
+    assert_eq!(data[offset as usize + LEN], 0);
+}
+
+#[test]
+fn out_of_bounds_fails_before_io() {
+    let (mut store, memory) = setup(1);
+    for (offset, len) in [
+        (0, PAGE_SIZE + 1),
+        (PAGE_SIZE, 1),
+        (1, PAGE_SIZE),
+        (u64::MAX, 2),
+        (2, u64::MAX),
+    ] {
+        let error = memory
+            .write_from(&mut store, offset, &mut UnreachableReader, len)
+            .unwrap_err();
+        assert!(error.to_string().contains("out of bounds"), "{error}");
+        let mut writer = Vec::new();
+        memory
+            .read_into(&store, offset, &mut writer, len)
+            .unwrap_err();
+        assert!(writer.is_empty());
+    }
+    // Zero length accesses at the end of memory are in bounds.
+    assert_eq!(
+        memory
+            .write_from(&mut store, PAGE_SIZE, &mut UnreachableReader, 0)
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn reader_error_reports_partial_count() {
+    let (mut store, memory) = setup(2);
+    let partial = 70_000;
+    let mut reader = FailingReader {
+        pos: 0,
+        len: partial,
+    };
+    let error = memory
+        .write_from(&mut store, 0, &mut reader, 2 * PAGE_SIZE)
+        .unwrap_err();
+    let io_error = as_io_error(&error);
+    assert_eq!(io_error.transferred(), partial as u64);
+    assert_eq!(io_error.io_error().to_string(), "reader failed");
+    // The partially transferred data has been written.
+    let data = memory.data(&store);
+    assert!(data[..partial]
+        .iter()
+        .enumerate()
+        .all(|(n, &byte)| byte == pattern(n)));
+    assert!(data[partial..].iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn reader_eof_reports_partial_count() {
+    let (mut store, memory) = setup(1);
+    let error = memory
+        .write_from(&mut store, 0, &mut &[1_u8, 2, 3][..], 10)
+        .unwrap_err();
+    let io_error = as_io_error(&error);
+    assert_eq!(io_error.transferred(), 3);
+    assert_eq!(io_error.io_error().kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(&memory.data(&store)[..4], &[1, 2, 3, 0]);
+}
+
+#[test]
+fn writer_error_reports_partial_count() {
+    let (mut store, memory) = setup(1);
+    memory.write(&mut store, 0, &[0xFF; 16]).unwrap();
+    let mut buffer = [0_u8; 5];
+    let error = memory
+        .read_into(&store, 0, &mut &mut buffer[..], 16)
+        .unwrap_err();
+    let io_error = as_io_error(&error);
+    assert_eq!(io_error.transferred(), 5);
+    assert_eq!(io_error.io_error().kind(), io::ErrorKind::WriteZero);
+    assert_eq!(buffer, [0xFF; 5]);
+}
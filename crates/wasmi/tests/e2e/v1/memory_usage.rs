@@ -0,0 +1,214 @@
+//! Tests for the memory usage statistics of [`Engine`] and reclaiming unused code.
+
+use assert_matches::assert_matches;
+use wasmi::{
+    errors::{ErrorKind, FuncError},
+    Caller,
+    CompilationMode,
+    Config,
+    Engine,
+    EngineMemoryUsage,
+    Error,
+    Linker,
+    Module,
+    Store,
+};
+
+/// A Wasm module with some functions and function local constant values.
+const WAT: &str = r#"
+    (module
+        (func (export "add") (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1000000))
+        )
+        (func (export "mul") (param i64) (result i64)
+            (i64.mul (local.get 0) (i64.const 1000000000000))
+        )
+    )
+"#;
+
+/// Compiles [`WAT`] for the `engine`.
+fn module(engine: &Engine) -> Module {
+    let wasm = wat::parse_str(WAT).unwrap();
+    Module::new(engine, &wasm[..]).unwrap()
+}
+
+/// Calls the exported `add` function of an instance of `module` within `store`.
+fn call_add(store: &mut Store<()>, module: &Module) -> i32 {
+    let instance = <Linker<()>>::new(module.engine())
+        .instantiate(&mut *store, module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap();
+    instance
+        .get_typed_func::<i32, i32>(&*store, "add")
+        .unwrap()
+        .call(&mut *store, 1)
+        .unwrap()
+}
+
+#[test]
+fn memory_usage() {
+    let engine = Engine::default();
+    assert_eq!(engine.memory_usage(), EngineMemoryUsage::default());
+    let module = module(&engine);
+    let usage = engine.memory_usage();
+    assert_eq!(usage.total_funcs, 2);
+    assert_eq!(usage.func_types, 2);
+    assert!(usage.code_bytes > 0);
+    assert!(usage.const_pool_bytes > 0);
+    assert_eq!(
+        module.code_size_estimate(),
+        usage.code_bytes + usage.const_pool_bytes
+    );
+}
+
+#[test]
+fn memory_usage_lazy() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    let module = module(&engine);
+    // Note: Uncompiled functions account for their Wasm function bodies.
+    let uncompiled = engine.memory_usage();
+    assert!(uncompiled.code_bytes > 0);
+    assert_eq!(uncompiled.const_pool_bytes, 0);
+    let mut store = Store::new(&engine, ());
+    assert_eq!(call_add(&mut store, &module), 1_000_001);
+    let compiled = engine.memory_usage();
+    assert!(compiled.const_pool_bytes > 0);
+    assert_eq!(compiled.total_funcs, uncompiled.total_funcs);
+}
+
+#[test]
+fn memory_usage_during_lazy_compilation() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    let modules: Vec<Module> = (0..50).map(|_| module(&engine)).collect();
+    let uncompiled = engine.memory_usage();
+    std::thread::scope(|scope| {
+        let compiling = scope.spawn(|| {
+            let mut store = Store::new(&engine, ());
+            for module in &modules {
+                assert_eq!(call_add(&mut store, module), 1_000_001);
+            }
+        });
+        // Note: measuring must neither block nor race with the lazy compilations.
+        while !compiling.is_finished() {
+            let usage = engine.memory_usage();
+            assert_eq!(usage.total_funcs, uncompiled.total_funcs);
+        }
+        compiling.join().unwrap();
+    });
+    let compiled = engine.memory_usage();
+    assert_eq!(compiled.total_funcs, uncompiled.total_funcs);
+    assert!(compiled.const_pool_bytes > 0);
+}
+
+#[test]
+fn purge_unused_bounds_code_bytes() {
+    let engine = Engine::default();
+    let baseline = module(&engine).code_size_estimate();
+    for _ in 0..100 {
+        let module = module(&engine);
+        assert_eq!(module.code_size_estimate(), baseline);
+        drop(module);
+        engine.purge_unused().unwrap();
+        let usage = engine.memory_usage();
+        assert_eq!(usage.total_funcs, 0);
+        assert_eq!(usage.code_bytes, 0);
+        assert_eq!(usage.const_pool_bytes, 0);
+    }
+}
+
+#[test]
+fn without_purge_code_is_kept() {
+    let engine = Engine::default();
+    for _ in 0..10 {
+        drop(module(&engine));
+    }
+    assert_eq!(engine.memory_usage().total_funcs, 20);
+    assert_eq!(engine.purge_unused().unwrap(), 20);
+    assert_eq!(engine.purge_unused().unwrap(), 0);
+    assert_eq!(engine.memory_usage().total_funcs, 0);
+}
+
+#[test]
+fn purge_unused_keeps_instantiated_code() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let module = module(&engine);
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let add = instance.get_typed_func::<i32, i32>(&store, "add").unwrap();
+    drop(module);
+    // Note: The instance keeps the code of its module alive.
+    assert_eq!(engine.purge_unused().unwrap(), 0);
+    // Note: Reclaimed slots of other modules must not affect the instance.
+    let other = self::module(&engine);
+    drop(other);
+    assert_eq!(engine.purge_unused().unwrap(), 2);
+    let reused = self::module(&engine);
+    assert_eq!(add.call(&mut store, 1).unwrap(), 1_000_001);
+    assert_eq!(call_add(&mut store, &reused), 1_000_001);
+    drop(reused);
+    drop(store);
+    assert_eq!(engine.purge_unused().unwrap(), 4);
+    assert_eq!(engine.memory_usage().total_funcs, 0);
+}
+
+/// Asserts that `error` reports an exclusive access to an executing [`Engine`].
+fn assert_engine_already_executing(error: Error) {
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::EngineAlreadyExecuting)
+    );
+}
+
+#[test]
+fn memory_usage_from_host_func() {
+    let engine = Engine::default();
+    let other = Engine::default();
+    let module = module(&engine);
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "inspect" (func $inspect))
+            (func (export "run") (call $inspect))
+        )
+        "#,
+    )
+    .unwrap();
+    let caller_module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let inspected = module.clone();
+    linker
+        .func_wrap("env", "inspect", move |caller: Caller<()>| {
+            // Note: read-only queries do not wait for the calling execution to finish.
+            let engine = caller.engine();
+            assert_eq!(engine.memory_usage().total_funcs, 3);
+            assert!(inspected.code_size_estimate() > 0);
+            // Note: this would otherwise wait forever for the calling execution to finish.
+            assert_engine_already_executing(engine.purge_unused().unwrap_err());
+            // Note: engines that do not execute on the current thread are not affected.
+            assert_eq!(other.memory_usage(), EngineMemoryUsage::default());
+            assert_eq!(other.purge_unused().unwrap(), 0);
+        })
+        .unwrap();
+    linker
+        .instantiate(&mut store, &caller_module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<(), ()>(&store, "run")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(engine.memory_usage().total_funcs, 3);
+    assert!(module.code_size_estimate() > 0);
+    assert_eq!(engine.purge_unused().unwrap(), 0);
+}
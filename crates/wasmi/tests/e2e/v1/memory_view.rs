@@ -0,0 +1,139 @@
+//! Tests to check that host functions can access guest memory via memory views.
+
+use wasmi::{Caller, Engine, Extern, Linker, Memory, Module, Pod, Store};
+
+/// A packed struct as laid out in guest memory by the Wasm module of [`WAT`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Packed {
+    tag: u8,
+    id: u32,
+    value: f64,
+    pair: [i16; 2],
+}
+
+impl Pod for Packed {
+    const SIZE: usize = 1 + 4 + 8 + 4;
+
+    fn read_le(bytes: &[u8]) -> Self {
+        let (tag, rest) = bytes.split_at(u8::SIZE);
+        let (id, rest) = rest.split_at(u32::SIZE);
+        let (value, pair) = rest.split_at(f64::SIZE);
+        Self {
+            tag: u8::read_le(tag),
+            id: u32::read_le(id),
+            value: f64::read_le(value),
+            pair: <[i16; 2]>::read_le(pair),
+        }
+    }
+
+    fn write_le(&self, bytes: &mut [u8]) {
+        let (tag, rest) = bytes.split_at_mut(u8::SIZE);
+        let (id, rest) = rest.split_at_mut(u32::SIZE);
+        let (value, pair) = rest.split_at_mut(f64::SIZE);
+        self.tag.write_le(tag);
+        self.id.write_le(id);
+        self.value.write_le(value);
+        self.pair.write_le(pair);
+    }
+}
+
+/// A Wasm module that writes a [`Packed`] struct to its memory and hands it to the host.
+///
+/// The struct is written to an unaligned address to assert that reads are not torn.
+const WAT: &str = r#"
+    (module
+        (import "env" "inspect" (func $inspect (param i32)))
+        (memory (export "memory") 1)
+        (func (export "run") (param $ptr i32)
+            (i32.store8 offset=0 (local.get $ptr) (i32.const 0x7F))
+            (i32.store offset=1 (local.get $ptr) (i32.const 0xDEADBEEF))
+            (f64.store offset=5 (local.get $ptr) (f64.const -1.5))
+            (i32.store16 offset=13 (local.get $ptr) (i32.const -2))
+            (i32.store16 offset=15 (local.get $ptr) (i32.const 300))
+            (call $inspect (local.get $ptr))
+        )
+        (func (export "read_id") (param $ptr i32) (result i32)
+            (i32.load offset=1 (local.get $ptr))
+        )
+    )
+"#;
+
+/// The [`Packed`] struct written by the `run` function of [`WAT`].
+const EXPECTED: Packed = Packed {
+    tag: 0x7F,
+    id: 0xDEAD_BEEF,
+    value: -1.5,
+    pair: [-2, 300],
+};
+
+/// The state of the [`Store`] used by the tests.
+#[derive(Default)]
+struct HostState {
+    /// The [`Packed`] structs read by the host.
+    inspected: Vec<Packed>,
+}
+
+/// Returns the exported `memory` of the calling instance.
+fn caller_memory(caller: &Caller<HostState>) -> Memory {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .unwrap()
+}
+
+#[test]
+fn memory_view_reads_packed_struct() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, HostState::default());
+    let mut linker = <Linker<HostState>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "inspect",
+            |mut caller: Caller<HostState>, ptr: u32| {
+                let memory = caller_memory(&caller);
+                let ptr = ptr as usize;
+                let view = memory.view(&caller);
+                let packed = view.read_pod::<Packed>(ptr).unwrap();
+                assert_eq!(view.read_pod::<u8>(ptr).unwrap(), packed.tag);
+                // Write back the incremented `id` while also mutating the store data.
+                let (mut view, state) = memory.view_and_store_mut(&mut caller);
+                state.inspected.push(packed);
+                view.write_pod(ptr + 1, packed.id.wrapping_add(1)).unwrap();
+            },
+        )
+        .unwrap();
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, ()>(&store, "run").unwrap();
+    let read_id = instance
+        .get_typed_func::<i32, i32>(&store, "read_id")
+        .unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    // The last address at which a `Packed` struct fits into the linear memory.
+    let last = (memory.data(&store).len() - Packed::SIZE) as i32;
+    for ptr in [0, 3, 1001, last] {
+        run.call(&mut store, ptr).unwrap();
+        assert_eq!(store.data().inspected.last(), Some(&EXPECTED));
+        assert_eq!(read_id.call(&mut store, ptr).unwrap() as u32, 0xDEAD_BEF0);
+        let packed = memory.read_pod::<Packed>(&store, ptr as usize).unwrap();
+        assert_eq!(packed.id, 0xDEAD_BEF0);
+    }
+    assert_eq!(store.data().inspected.len(), 4);
+    // Accesses crossing the end of the linear memory fail entirely.
+    assert!(memory
+        .read_pod::<Packed>(&store, last as usize + 1)
+        .is_err());
+    assert!(memory
+        .write_pod(&mut store, last as usize + 1, EXPECTED)
+        .is_err());
+    assert_eq!(
+        memory.read_pod::<Packed>(&store, last as usize).unwrap().id,
+        0xDEAD_BEF0
+    );
+}
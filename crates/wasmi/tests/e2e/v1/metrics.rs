@@ -0,0 +1,194 @@
+//! Tests for the [`CallMetrics`] recorded by the [`Store`] for host initiated calls.
+
+use wasmi::{
+    CallMetrics,
+    Caller,
+    Config,
+    Engine,
+    Extern,
+    Func,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    Value,
+};
+
+/// The `host` function import calls back into the `inner` export of its caller.
+const WASM: &str = r#"
+    (module
+        (import "env" "host" (func $host (param i32) (result i32)))
+        (func $add (export "add") (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (func (export "div") (param i32 i32) (result i32)
+            (i32.div_u (local.get 0) (local.get 1))
+        )
+        (func (export "outer") (param i32) (result i32)
+            ;; Calls between Wasm functions are not recorded.
+            (call $add (call $host (local.get 0)) (i32.const 1))
+        )
+        (func (export "inner") (param i32) (result i32)
+            (i32.mul (local.get 0) (i32.const 2))
+        )
+    )
+"#;
+
+/// Instantiates [`WASM`] and returns its [`Store`], [`Instance`] and the `host` [`Func`].
+fn setup() -> (Store<()>, Instance, Func) {
+    setup_with(&Config::default())
+}
+
+/// Instantiates [`WASM`] with an [`Engine`] created with `config`.
+///
+/// Returns the [`Store`], [`Instance`] and the `host` [`Func`].
+fn setup_with(config: &Config) -> (Store<()>, Instance, Func) {
+    let engine = Engine::new(config);
+    let mut store = Store::new(&engine, ());
+    let host = Func::wrap(&mut store, |mut caller: Caller<()>, value: i32| {
+        caller
+            .get_export("inner")
+            .and_then(Extern::into_func)
+            .unwrap()
+            .typed::<i32, i32>(&caller)
+            .unwrap()
+            .call(&mut caller, value)
+            .unwrap()
+    });
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.define("env", "host", host).unwrap();
+    let module = Module::new(&engine, &wat::parse_str(WASM).unwrap()[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance, host)
+}
+
+/// Returns the recorded [`CallMetrics`] of the exported function `name`.
+fn metrics_of(store: &Store<()>, instance: &Instance, name: &str) -> Option<CallMetrics> {
+    let func = instance.get_func(store, name).unwrap();
+    store
+        .metrics()
+        .find(|(recorded, _)| *recorded == func)
+        .map(|(_, metrics)| metrics)
+}
+
+#[test]
+fn calls_and_traps() {
+    let (mut store, instance, _) = setup();
+    store.enable_metrics(true);
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    for i in 0..3 {
+        assert_eq!(add.call(&mut store, (i, 1)).unwrap(), i + 1);
+    }
+    let div = instance.get_func(&store, "div").unwrap();
+    let mut result = [Value::I32(0)];
+    div.call(&mut store, &[Value::I32(4), Value::I32(2)], &mut result)
+        .unwrap();
+    div.call(&mut store, &[Value::I32(4), Value::I32(0)], &mut result)
+        .unwrap_err();
+    let add = metrics_of(&store, &instance, "add").unwrap();
+    assert_eq!((add.calls, add.traps), (3, 0));
+    let div = metrics_of(&store, &instance, "div").unwrap();
+    assert_eq!((div.calls, div.traps), (2, 1));
+    assert_eq!(store.metrics().count(), 2);
+}
+
+#[test]
+fn reentrant_calls() {
+    let (mut store, instance, host) = setup();
+    store.enable_metrics(true);
+    let outer = instance
+        .get_typed_func::<i32, i32>(&store, "outer")
+        .unwrap();
+    assert_eq!(outer.call(&mut store, 5).unwrap(), 11);
+    let outer = metrics_of(&store, &instance, "outer").unwrap();
+    let inner = metrics_of(&store, &instance, "inner").unwrap();
+    assert_eq!((outer.calls, outer.traps), (1, 0));
+    assert_eq!((inner.calls, inner.traps), (1, 0));
+    // The inner call is executed during the outer call.
+    assert!(outer.total_nanos >= inner.total_nanos);
+    // Neither the host function nor calls between Wasm functions are recorded.
+    assert!(metrics_of(&store, &instance, "add").is_none());
+    assert!(store.metrics().all(|(func, _)| func != host));
+}
+
+#[test]
+fn enable_and_disable() {
+    let (mut store, instance, _) = setup();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    // Metrics are disabled by default.
+    add.call(&mut store, (1, 2)).unwrap();
+    assert_eq!(store.metrics().count(), 0);
+    store.enable_metrics(true);
+    add.call(&mut store, (1, 2)).unwrap();
+    store.enable_metrics(false);
+    add.call(&mut store, (1, 2)).unwrap();
+    // Disabling keeps the metrics recorded so far.
+    assert_eq!(metrics_of(&store, &instance, "add").unwrap().calls, 1);
+}
+
+#[test]
+#[cfg(feature = "resumable")]
+fn resumable_calls() {
+    let (mut store, instance, _) = setup();
+    store.enable_metrics(true);
+    let div = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "div")
+        .unwrap();
+    div.call_resumable(&mut store, (4, 2)).unwrap();
+    div.call_resumable(&mut store, (4, 0)).unwrap_err();
+    let div = metrics_of(&store, &instance, "div").unwrap();
+    assert_eq!((div.calls, div.traps), (2, 1));
+}
+
+#[test]
+fn allocation_order() {
+    let (mut store, instance, _) = setup();
+    store.enable_metrics(true);
+    // Call the functions in reverse order of their allocation.
+    let calls: [(&str, &[Value]); 3] = [
+        ("inner", &[Value::I32(1)]),
+        ("div", &[Value::I32(1), Value::I32(1)]),
+        ("add", &[Value::I32(1), Value::I32(1)]),
+    ];
+    let mut result = [Value::I32(0)];
+    for (name, params) in calls {
+        let func = instance.get_func(&store, name).unwrap();
+        func.call(&mut store, params, &mut result).unwrap();
+    }
+    let recorded = store.metrics().map(|(func, _)| func).collect::<Vec<_>>();
+    let expected = store
+        .funcs()
+        .filter(|func| recorded.contains(func))
+        .collect::<Vec<_>>();
+    assert_eq!(recorded.len(), 3);
+    assert_eq!(recorded, expected);
+}
+
+#[test]
+fn deterministic_only() {
+    let mut config = Config::default();
+    config.deterministic_only(true);
+    let (mut store, instance, _) = setup_with(&config);
+    store.enable_metrics(true);
+    let outer = instance
+        .get_typed_func::<i32, i32>(&store, "outer")
+        .unwrap();
+    assert_eq!(outer.call(&mut store, 5).unwrap(), 11);
+    // The wall clock is not read and thus all durations are zero.
+    let metrics = store
+        .metrics()
+        .map(|(_, metrics)| metrics)
+        .collect::<Vec<_>>();
+    assert_eq!(metrics.len(), 2);
+    for metrics in metrics {
+        assert_eq!((metrics.calls, metrics.total_nanos), (1, 0));
+    }
+}
@@ -1,6 +1,89 @@
+mod address_map;
+mod background_compile;
+mod batch_call;
+mod br_table;
+mod branch_fallback;
+mod build;
+mod bulk_init;
+mod bulk_memory;
+mod call_limits;
+mod call_indirect;
+mod call_indirect_traps;
+mod caller_split;
+mod cross_instance_calls;
+mod custom_metadata;
+mod custom_page_sizes;
+mod engine;
+mod entity_instances;
+mod float_branch_nan;
 mod fuel_consumption;
-mod fuel_metering;
 mod func;
+mod func_identity;
+mod global_cache;
+mod global_values;
+mod grow_to;
+#[cfg(feature = "resumable")]
+mod guest_yield;
+#[cfg(feature = "fuzz")]
+mod fuzz;
 mod host_calls_wasm;
+mod host_error_chain;
+mod host_grow_size;
+mod host_trap;
+mod i32_eqz_fuse;
+mod inline;
+#[cfg(feature = "metrics")]
+mod instruction_categories;
+mod instance_exports;
+mod instantiate_pre;
+mod intrinsics;
+mod lazy_compilation;
+mod lazy_table_init;
+mod linker_interceptor;
+mod linker_unknown_imports;
+mod many_results;
+mod memory_bounds;
+mod memory_grow_fuel;
+mod memory_io;
+mod memory_usage;
+mod memory_view;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod module_clone;
+mod module_names;
+mod mul_wide;
+mod multi_memory;
+mod precompile;
+mod reentrance;
+mod register_types;
+mod replace_data;
+mod reprice_fuel;
 mod resource_limiter;
+mod return_forward;
+#[cfg(feature = "resumable")]
 mod resumable_call;
+#[cfg(feature = "resumable")]
+mod resumable_driver;
+mod runtime_signature;
+#[cfg(feature = "metrics")]
+mod sampler;
+mod select_aliasing;
+mod shared_memory;
+mod snapshot;
+#[cfg(feature = "spec-testing")]
+mod spec_testing;
+mod spectre_mitigations;
+mod stack_usage;
+mod start_trap;
+mod store_dump;
+mod table;
+mod tail_call_host;
+mod translate_func;
+#[cfg(feature = "tracing")]
+mod tracing_spans;
+mod trap_messages;
+mod trap_origin;
+#[cfg(feature = "wat")]
+mod wat;
+mod watchpoint;
+mod yield_callback;
@@ -0,0 +1,206 @@
+//! Tests to check that [`Module::clone_into`] transfers modules across [`Engine`]s.
+
+use assert_matches::assert_matches;
+use wasmi::{
+    errors::{EngineMismatchError, ErrorKind},
+    CompilationMode,
+    Config,
+    Engine,
+    Instance,
+    Linker,
+    Module,
+    Store,
+};
+
+/// A Wasm module with internal, recursive and indirect calls as well as data and element segments.
+const WAT: &str = r#"
+    (module
+        (type $unary (func (param i64) (result i64)))
+        (memory (export "mem") 1)
+        (data (i32.const 8) "\2A\00\00\00\00\00\00\00")
+        (global $base (mut i64) (i64.const 1000))
+        (table 2 funcref)
+        (elem (i32.const 0) $double $square)
+        (func $double (param i64) (result i64)
+            (i64.mul (local.get 0) (i64.const 2))
+        )
+        (func $square (param i64) (result i64)
+            (i64.mul (local.get 0) (local.get 0))
+        )
+        (func $fib (param i64) (result i64)
+            (if (result i64) (i64.lt_u (local.get 0) (i64.const 2))
+                (then (local.get 0))
+                (else
+                    (i64.add
+                        (call $fib (i64.sub (local.get 0) (i64.const 1)))
+                        (call $fib (i64.sub (local.get 0) (i64.const 2)))
+                    )
+                )
+            )
+        )
+        (func $tail (param i64) (result i64)
+            (return_call $double (local.get 0))
+        )
+        (func (export "run") (param i64 i32) (result i64)
+            (i64.add
+                (i64.add
+                    (global.get $base)
+                    (i64.load (i32.const 8))
+                )
+                (i64.add
+                    (call $fib (local.get 0))
+                    (i64.add
+                        (call $tail (local.get 0))
+                        (call_indirect (type $unary) (local.get 0) (local.get 1))
+                    )
+                )
+            )
+        )
+    )
+"#;
+
+/// A Wasm module with types that do not occur in [`WAT`].
+///
+/// Compiling it first shifts the function types and functions of an [`Engine`].
+const OTHER_WAT: &str = r#"
+    (module
+        (func (param f32 f64) (result i32) (i32.const 0))
+        (func (param i32 i32) (result f64) (f64.const 0))
+    )
+"#;
+
+/// Returns the [`Config`] used by all tests with the `tail-call` proposal enabled.
+fn config() -> Config {
+    let mut config = Config::default();
+    config.wasm_tail_call(true);
+    config
+}
+
+/// Compiles `wat` into a [`Module`] for the `engine`.
+fn compile(engine: &Engine, wat: &str) -> Module {
+    let wasm = wat::parse_str(wat).unwrap();
+    Module::new(engine, &wasm[..]).unwrap()
+}
+
+/// Instantiates `module` in a new [`Store`].
+fn instantiate(module: &Module) -> (Store<()>, Instance) {
+    let mut store = Store::new(module.engine(), ());
+    let instance = <Linker<()>>::new(module.engine())
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported `run` function of a new instance of `module`.
+fn run(module: &Module, n: i64, index: i32) -> i64 {
+    let (mut store, instance) = instantiate(module);
+    instance
+        .get_typed_func::<(i64, i32), i64>(&store, "run")
+        .unwrap()
+        .call(&mut store, (n, index))
+        .unwrap()
+}
+
+/// Returns the expected result of the `run` function of [`WAT`].
+fn expected(n: i64, index: i32) -> i64 {
+    fn fib(n: i64) -> i64 {
+        match n {
+            0 | 1 => n,
+            n => fib(n - 1) + fib(n - 2),
+        }
+    }
+    let indirect = match index {
+        0 => n * 2,
+        _ => n * n,
+    };
+    1000 + 42 + fib(n) + n * 2 + indirect
+}
+
+#[test]
+fn clone_into_runs_on_both_engines() {
+    let origin = Engine::new(&config());
+    let target = Engine::new(&config());
+    let _other = compile(&target, OTHER_WAT);
+    let module = compile(&origin, WAT);
+    let cloned = module.clone_into(&target).unwrap();
+    assert!(Engine::same(cloned.engine(), &target));
+    for (n, index) in [(0, 0), (1, 1), (10, 0), (15, 1)] {
+        assert_eq!(run(&module, n, index), expected(n, index));
+        assert_eq!(run(&cloned, n, index), expected(n, index));
+    }
+    assert_eq!(
+        format!("{:?}", module.exports().collect::<Vec<_>>()),
+        format!("{:?}", cloned.exports().collect::<Vec<_>>()),
+    );
+}
+
+#[test]
+fn clone_into_outlives_origin() {
+    let target = Engine::new(&config());
+    let cloned = {
+        let origin = Engine::new(&config());
+        let module = compile(&origin, WAT);
+        module.clone_into(&target).unwrap()
+    };
+    assert_eq!(run(&cloned, 12, 1), expected(12, 1));
+    assert_eq!(target.purge_unused().unwrap(), 0);
+    assert_eq!(run(&cloned, 12, 0), expected(12, 0));
+}
+
+#[test]
+fn clone_into_same_engine() {
+    let engine = Engine::new(&config());
+    let module = compile(&engine, WAT);
+    let cloned = module.clone_into(&engine).unwrap();
+    drop(module);
+    engine.purge_unused().unwrap();
+    assert_eq!(run(&cloned, 7, 0), expected(7, 0));
+}
+
+#[test]
+fn clone_into_compiles_lazy_funcs() {
+    let mut config = config();
+    config.compilation_mode(CompilationMode::Lazy);
+    let origin = Engine::new(&config);
+    let target = Engine::new(&config);
+    let module = compile(&origin, WAT);
+    let cloned = module.clone_into(&target).unwrap();
+    assert_eq!(run(&cloned, 10, 1), expected(10, 1));
+    assert_eq!(run(&module, 10, 1), expected(10, 1));
+}
+
+#[test]
+fn clone_into_keeps_lazy_compilation_failures() {
+    let mut config = config();
+    config.compilation_mode(CompilationMode::Lazy);
+    let origin = Engine::new(&config);
+    let target = Engine::new(&config);
+    let module = compile(
+        &origin,
+        r#"(module (func (export "invalid") (result i32) (i64.const 42)))"#,
+    );
+    let cloned = module.clone_into(&target).unwrap();
+    let (mut store, instance) = instantiate(&cloned);
+    let error = instance
+        .get_typed_func::<(), i32>(&store, "invalid")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::LazyCompilationFailed { func_index: 0, source }
+        if matches!(source.kind(), ErrorKind::Wasm(_))
+    );
+}
+
+#[test]
+fn clone_into_mismatching_config() {
+    let origin = Engine::new(&config());
+    let module = compile(&origin, WAT);
+    let target = Engine::new(config().consume_fuel(true));
+    assert_eq!(module.clone_into(&target).unwrap_err(), EngineMismatchError);
+    let target = Engine::new(config().compilation_mode(CompilationMode::Lazy));
+    assert_eq!(module.clone_into(&target).unwrap_err(), EngineMismatchError);
+}
@@ -0,0 +1,145 @@
+//! Tests for the validation and interning of the import and export names of a [`Module`].
+
+use std::collections::HashSet;
+use wasmi::{errors::ErrorKind, Engine, Error, Module};
+
+/// Appends the Wasm encoding of the `name` bytes to `bytes`.
+///
+/// The length of `name` must fit into a single LEB128 byte.
+fn write_name(bytes: &mut Vec<u8>, name: &[u8]) {
+    assert!(name.len() < 0x80, "name too long: {}", name.len());
+    bytes.push(name.len() as u8);
+    bytes.extend_from_slice(name);
+}
+
+/// Appends the section with `id` and `payload` to the Wasm `module`.
+///
+/// The length of `payload` must fit into a single LEB128 byte.
+fn write_section(module: &mut Vec<u8>, id: u8, payload: &[u8]) {
+    assert!(payload.len() < 0x80, "payload too long: {}", payload.len());
+    module.push(id);
+    module.push(payload.len() as u8);
+    module.extend_from_slice(payload);
+}
+
+/// Returns a Wasm module importing a global variable from `module` under `field`
+/// and re-exporting it under `export`.
+///
+/// The names are encoded as given without checking that they are valid UTF-8.
+fn module_with_names(module: &[u8], field: &[u8], export: &[u8]) -> Vec<u8> {
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    let mut imports = vec![1];
+    write_name(&mut imports, module);
+    write_name(&mut imports, field);
+    // Note: an immutable `i32` global variable.
+    imports.extend([0x03, 0x7F, 0x00]);
+    write_section(&mut wasm, 2, &imports);
+    let mut exports = vec![1];
+    write_name(&mut exports, export);
+    // Note: the global variable at index 0.
+    exports.extend([0x03, 0x00]);
+    write_section(&mut wasm, 7, &exports);
+    wasm
+}
+
+/// Compiles the given `wasm` with the default [`Engine`].
+fn compile(wasm: &[u8]) -> Result<Module, Error> {
+    Module::new(&Engine::default(), wasm)
+}
+
+#[test]
+fn valid_utf8_names() {
+    let wasm = module_with_names("env".as_bytes(), "π".as_bytes(), "🦀".as_bytes());
+    let module = compile(&wasm).unwrap();
+    let import = module.imports().next().unwrap();
+    assert_eq!((import.module(), import.name()), ("env", "π"));
+    assert_eq!(module.exports().next().unwrap().name(), "🦀");
+}
+
+#[test]
+fn invalid_utf8_names() {
+    let invalid: [&[u8]; 4] = [
+        // Note: overlong encoding of `/`.
+        b"\xC0\xAF",
+        // Note: overlong encoding of the null character.
+        b"\xE0\x80\x80",
+        // Note: encoded UTF-16 surrogate.
+        b"\xED\xA0\x80",
+        // Note: truncated encoding of a 2 byte character.
+        b"a\xC3",
+    ];
+    for name in invalid {
+        for wasm in [
+            module_with_names(name, b"g", b"g"),
+            module_with_names(b"env", name, b"g"),
+            module_with_names(b"env", b"g", name),
+        ] {
+            let error = compile(&wasm).unwrap_err();
+            assert!(
+                matches!(error.kind(), ErrorKind::Wasm(_)),
+                "expected Wasm error for {name:X?} but found: {error}"
+            );
+        }
+    }
+}
+
+#[test]
+fn duplicate_export_names() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (export "a"))
+            (func (export "f"))
+            (global (export "b") i32 (i32.const 0))
+            (global (export "f") i32 (i32.const 0))
+        )
+        "#,
+    )
+    .unwrap();
+    let error = compile(&wasm).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Translation(_)));
+    assert_eq!(
+        error.to_string(),
+        "duplicate export name `f` of global 1 already used by function 1"
+    );
+}
+
+#[test]
+fn overlong_names_are_rejected_by_default() {
+    let name = "a".repeat(64 * 1024 + 1);
+    let wasm = wat::parse_str(format!(r#"(module (import "env" "{name}" (func)))"#)).unwrap();
+    let error = compile(&wasm).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "encountered 65537 name bytes exceeding the configured limit of 65536"
+    );
+}
+
+/// Returns the total number of bytes of all distinct allocations of the import names of `module`.
+fn import_name_bytes(module: &Module) -> usize {
+    let mut names = HashSet::new();
+    for import in module.imports() {
+        for name in [import.module(), import.name()] {
+            names.insert((name.as_ptr(), name.len()));
+        }
+    }
+    names.into_iter().map(|(_, len)| len).sum()
+}
+
+#[test]
+fn import_names_are_interned() {
+    let mut wat = String::from("(module");
+    for i in 0..1000 {
+        let field = i % 10;
+        wat.push_str(&format!(r#" (import "environment" "func_{field}" (func))"#));
+    }
+    wat.push(')');
+    let module = compile(&wat::parse_str(wat).unwrap()).unwrap();
+    assert_eq!(module.imports().len(), 1000);
+    // Note: without interning the names of the imports would require
+    //       1000 * ("environment".len() + "func_0".len()) = 17000 bytes.
+    assert_eq!(
+        import_name_bytes(&module),
+        "environment".len() + 10 * "func_0".len()
+    );
+}
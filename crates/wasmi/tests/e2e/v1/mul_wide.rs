@@ -0,0 +1,87 @@
+//! Tests for the `i64` widening multiply instructions built via [`IrFunc`].
+
+use wasmi::{
+    build::{IrFunc, ModuleBuilder},
+    core::ValueType,
+    ir::{Instruction, Register, RegisterSpan},
+    Engine,
+    FuncType,
+    Linker,
+    Store,
+    TypedFunc,
+};
+
+/// Returns the `(i64, i64) -> (i64, i64)` function computing the wide product via `instr`.
+fn mul_wide(instr: fn(RegisterSpan, Register, Register) -> Instruction) -> IrFunc {
+    let results = RegisterSpan::new(Register::from_i16(2));
+    IrFunc::new(
+        4,
+        [],
+        [
+            instr(results, Register::from_i16(0), Register::from_i16(1)),
+            Instruction::return_reg2(Register::from_i16(2), Register::from_i16(3)),
+        ],
+    )
+}
+
+/// The exported `(i64, i64) -> (i64, i64)` widening multiply function.
+type MulWide = TypedFunc<(i64, i64), (i64, i64)>;
+
+/// Builds and instantiates a module exporting `body` as `mul_wide`.
+fn instantiate(body: IrFunc) -> (Store<()>, MulWide) {
+    let engine = Engine::default();
+    let mut builder = ModuleBuilder::new(&engine);
+    let ty = builder.push_type(FuncType::new([ValueType::I64; 2], [ValueType::I64; 2]));
+    let func = builder.push_ir_func(ty, body);
+    builder.export_func("mul_wide", func);
+    let module = builder.finish().unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func(&store, "mul_wide").unwrap();
+    (store, func)
+}
+
+/// Returns the `(low, high)` halves of `value`.
+fn split(value: u128) -> (i64, i64) {
+    (value as u64 as i64, (value >> 64) as u64 as i64)
+}
+
+#[test]
+fn i64_mul_wide_s() {
+    let (mut store, func) = instantiate(mul_wide(Instruction::i64_mul_wide_s));
+    let cases = [
+        (0, 0),
+        (1, -1),
+        (-1, -1),
+        (i64::MAX, i64::MAX),
+        (i64::MIN, i64::MIN),
+        (i64::MIN, -1),
+        (0x1234_5678_9abc_def0, -0x0fed_cba9_8765_4321),
+    ];
+    for (lhs, rhs) in cases {
+        let expected = split((i128::from(lhs) * i128::from(rhs)) as u128);
+        assert_eq!(func.call(&mut store, (lhs, rhs)).unwrap(), expected);
+    }
+}
+
+#[test]
+fn i64_mul_wide_u() {
+    let (mut store, func) = instantiate(mul_wide(Instruction::i64_mul_wide_u));
+    let cases = [
+        (0, 0),
+        (1, -1),
+        (-1, -1),
+        (i64::MAX, i64::MAX),
+        (i64::MIN, i64::MIN),
+        (i64::MIN, -1),
+        (0x1234_5678_9abc_def0, -0x0fed_cba9_8765_4321),
+    ];
+    for (lhs, rhs) in cases {
+        let expected = split(u128::from(lhs as u64) * u128::from(rhs as u64));
+        assert_eq!(func.call(&mut store, (lhs, rhs)).unwrap(), expected);
+    }
+}
@@ -0,0 +1,82 @@
+//! Tests asserting that instructions referring to non-default linear memories are rejected.
+//!
+//! # Note
+//!
+//! Wasmi does not support the multi-memory Wasm proposal. Therefore all memory
+//! instructions are translated for the default linear memory and modules that
+//! refer to any other linear memory must fail validation.
+
+use wasmi::{Engine, Module};
+
+/// Instructions of all instruction families that refer to the linear memory `MEM`.
+const MEMORY_INSTRS: &[&str] = &[
+    "(drop (i32.load MEM (i32.const 0)))",
+    "(drop (i64.load MEM (i32.const 0)))",
+    "(drop (f32.load MEM (i32.const 0)))",
+    "(drop (f64.load MEM (i32.const 0)))",
+    "(drop (i32.load8_s MEM (i32.const 0)))",
+    "(drop (i32.load8_u MEM (i32.const 0)))",
+    "(drop (i32.load16_s MEM (i32.const 0)))",
+    "(drop (i32.load16_u MEM (i32.const 0)))",
+    "(drop (i64.load8_s MEM (i32.const 0)))",
+    "(drop (i64.load8_u MEM (i32.const 0)))",
+    "(drop (i64.load16_s MEM (i32.const 0)))",
+    "(drop (i64.load16_u MEM (i32.const 0)))",
+    "(drop (i64.load32_s MEM (i32.const 0)))",
+    "(drop (i64.load32_u MEM (i32.const 0)))",
+    "(i32.store MEM (i32.const 0) (i32.const 1))",
+    "(i64.store MEM (i32.const 0) (i64.const 1))",
+    "(f32.store MEM (i32.const 0) (f32.const 1))",
+    "(f64.store MEM (i32.const 0) (f64.const 1))",
+    "(i32.store8 MEM (i32.const 0) (i32.const 1))",
+    "(i32.store16 MEM (i32.const 0) (i32.const 1))",
+    "(i64.store8 MEM (i32.const 0) (i64.const 1))",
+    "(i64.store16 MEM (i32.const 0) (i64.const 1))",
+    "(i64.store32 MEM (i32.const 0) (i64.const 1))",
+    "(drop (memory.size MEM))",
+    "(drop (memory.grow MEM (i32.const 1)))",
+    "(memory.copy MEM 0 (i32.const 0) (i32.const 0) (i32.const 1))",
+    "(memory.copy 0 MEM (i32.const 0) (i32.const 0) (i32.const 1))",
+    "(memory.fill MEM (i32.const 0) (i32.const 1) (i32.const 1))",
+    "(memory.init MEM 0 (i32.const 0) (i32.const 0) (i32.const 1))",
+];
+
+/// Returns the Wasm module with a function containing `instr` and `len_memories` linear memories.
+fn wasm(len_memories: usize, instr: &str) -> Vec<u8> {
+    let memories = "(memory 1)".repeat(len_memories);
+    let wat = format!(
+        r#"
+        (module
+            {memories}
+            (data "abc")
+            (func {instr})
+        )
+        "#
+    );
+    wat::parse_str(wat).unwrap()
+}
+
+#[test]
+fn multiple_memories_are_rejected() {
+    let engine = Engine::default();
+    let wasm = wasm(2, "");
+    assert!(Module::validate(&engine, &wasm).is_err());
+    assert!(Module::new(&engine, &wasm[..]).is_err());
+}
+
+#[test]
+fn instrs_referring_to_other_memories_are_rejected() {
+    let engine = Engine::default();
+    for instr in MEMORY_INSTRS {
+        let other = instr.replace("MEM", "1");
+        for len_memories in [1, 2] {
+            let wasm = wasm(len_memories, &other);
+            assert!(Module::validate(&engine, &wasm).is_err(), "{other}");
+            assert!(Module::new(&engine, &wasm[..]).is_err(), "{other}");
+        }
+        // Control: the same instruction referring to the default memory is accepted.
+        let default = instr.replace("MEM", "0");
+        let wasm = wasm(1, &default);
+        assert!(Module::new(&engine, &wasm[..]).is_ok(), "{default}");
+    }
+}
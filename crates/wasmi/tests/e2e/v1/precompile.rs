@@ -0,0 +1,230 @@
+//! Tests for compiling lazily compiled functions ahead of their first call.
+
+use assert_matches::assert_matches;
+use std::{sync::Barrier, thread};
+use wasmi::{
+    errors::{ErrorKind, ExportError, FuncError},
+    Caller,
+    CompilationMode,
+    Config,
+    Engine,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    TypedFunc,
+};
+
+/// A Wasm module with an exported call chain `entry` -> `helper` -> `leaf`.
+///
+/// The function indices are:
+///
+/// - `0`: the imported host function `env.host`
+/// - `1`: `entry`
+/// - `2`: `helper`
+/// - `3`: `leaf`
+/// - `4`: `other`
+/// - `5`: `indirect` which is only called indirectly by `entry`
+const WAT: &str = r#"
+    (module
+        (import "env" "host" (func $host (param i32) (result i32)))
+        (table funcref (elem $indirect))
+        (memory (export "memory") 1)
+        (func $entry (export "entry") (param i32) (result i32)
+            (call $helper (local.get 0))
+            (call_indirect (param i32) (result i32) (i32.const 0))
+        )
+        (func $helper (param i32) (result i32)
+            (call $leaf (i32.add (local.get 0) (i32.const 1)))
+        )
+        (func $leaf (param i32) (result i32)
+            (call $host (i32.mul (local.get 0) (i32.const 2)))
+        )
+        (func $other (export "other") (param i32) (result i32)
+            (local.get 0)
+        )
+        (func $indirect (param i32) (result i32)
+            (i32.sub (local.get 0) (i32.const 1))
+        )
+        (export "host" (func $host))
+    )
+"#;
+
+/// Compiles [`WAT`] with lazy compilation and instantiates it.
+fn setup() -> (Engine, Module, Store<()>, Instance) {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "host", |x: i32| x + 10).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (engine, module, store, instance)
+}
+
+/// Returns the function indices of all compiled internal functions of `module`.
+fn compiled(engine: &Engine, module: &Module) -> Vec<u32> {
+    engine
+        .compiled_functions(module)
+        .filter_map(|(func_index, is_compiled)| is_compiled.then_some(func_index))
+        .collect()
+}
+
+/// Returns the exported `entry` function of `instance`.
+fn entry(store: &Store<()>, instance: &Instance) -> TypedFunc<i32, i32> {
+    instance.get_typed_func::<i32, i32>(store, "entry").unwrap()
+}
+
+#[test]
+fn compiled_functions_reports_all_internal_funcs() {
+    let (engine, module, _store, _instance) = setup();
+    let states = engine.compiled_functions(&module).collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        [(1, false), (2, false), (3, false), (4, false), (5, false)]
+    );
+    assert_eq!(engine.lazy_compilations(), 0);
+}
+
+#[test]
+fn precompile_export() {
+    let (engine, module, mut store, instance) = setup();
+    instance
+        .precompile_exports(&store, &["entry", "host"], false)
+        .unwrap();
+    assert_eq!(compiled(&engine, &module), [1]);
+    assert_eq!(engine.lazy_compilations(), 1);
+    // Note: the callees of `entry` are still compiled upon their first call.
+    assert_eq!(entry(&store, &instance).call(&mut store, 1).unwrap(), 13);
+    assert_eq!(compiled(&engine, &module), [1, 2, 3, 5]);
+    assert_eq!(engine.lazy_compilations(), 4);
+}
+
+#[test]
+fn precompile_export_transitively() {
+    let (engine, module, mut store, instance) = setup();
+    instance
+        .precompile_exports(&store, &["entry"], true)
+        .unwrap();
+    // Note: indirectly called functions are not compiled transitively.
+    assert_eq!(compiled(&engine, &module), [1, 2, 3]);
+    assert_eq!(engine.lazy_compilations(), 3);
+    // Note: only the indirectly called function is compiled upon the call.
+    assert_eq!(entry(&store, &instance).call(&mut store, 1).unwrap(), 13);
+    assert_eq!(compiled(&engine, &module), [1, 2, 3, 5]);
+    assert_eq!(engine.lazy_compilations(), 4);
+    instance
+        .precompile_exports(&store, &["entry"], true)
+        .unwrap();
+    assert_eq!(engine.lazy_compilations(), 4);
+}
+
+#[test]
+fn compile_functions_by_index() {
+    let (engine, module, mut store, instance) = setup();
+    module.compile_functions(&[5, 1], true).unwrap();
+    assert_eq!(compiled(&engine, &module), [1, 2, 3, 5]);
+    assert_eq!(engine.lazy_compilations(), 4);
+    // Note: a call after warming performs no compilation.
+    assert_eq!(entry(&store, &instance).call(&mut store, 1).unwrap(), 13);
+    assert_eq!(engine.lazy_compilations(), 4);
+    module.compile_functions(&[4], false).unwrap();
+    assert_eq!(compiled(&engine, &module), [1, 2, 3, 4, 5]);
+    assert_eq!(engine.lazy_compilations(), 5);
+}
+
+#[test]
+fn precompile_errors() {
+    let (engine, module, store, instance) = setup();
+    for func_index in [0, 6] {
+        let error = module
+            .compile_functions(&[1, func_index], true)
+            .unwrap_err();
+        assert_matches!(
+            error.kind(),
+            ErrorKind::Func(FuncError::NotAnInternalFunc { func_index: index }) if *index == func_index
+        );
+    }
+    let error = instance
+        .precompile_exports(&store, &["entry", "missing"], true)
+        .unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Export(ExportError::NotFound { .. })
+    );
+    let error = instance
+        .precompile_exports(&store, &["memory"], true)
+        .unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Export(ExportError::KindMismatch { .. })
+    );
+    // Note: no function is compiled if any of the functions cannot be found.
+    assert_eq!(compiled(&engine, &module), []);
+}
+
+#[test]
+fn precompile_from_host_func() {
+    let mut config = Config::default();
+    config.compilation_mode(CompilationMode::Lazy);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let precompiled = module.clone();
+    linker
+        .func_wrap("env", "host", move |caller: Caller<()>, x: i32| {
+            // Note: compiling only requires shared access to the executing engine.
+            precompiled.compile_functions(&[4], false).unwrap();
+            assert!(caller
+                .engine()
+                .compiled_functions(&precompiled)
+                .any(|state| state == (4, true)));
+            x + 10
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    assert_eq!(entry(&store, &instance).call(&mut store, 1).unwrap(), 13);
+    assert_eq!(compiled(&engine, &module), [1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn precompile_concurrently_with_execution() {
+    const THREADS: usize = 4;
+    let (engine, module, _store, _instance) = setup();
+    let barrier = Barrier::new(THREADS * 2);
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                barrier.wait();
+                module.compile_functions(&[1, 4, 5], true).unwrap();
+            });
+            scope.spawn(|| {
+                let mut store = Store::new(&engine, ());
+                let mut linker = <Linker<()>>::new(&engine);
+                linker.func_wrap("env", "host", |x: i32| x + 10).unwrap();
+                let instance = linker
+                    .instantiate(&mut store, &module)
+                    .unwrap()
+                    .start(&mut store)
+                    .unwrap();
+                barrier.wait();
+                assert_eq!(entry(&store, &instance).call(&mut store, 1).unwrap(), 13);
+            });
+        }
+    });
+    // Note: each function is compiled exactly once.
+    assert_eq!(compiled(&engine, &module), [1, 2, 3, 4, 5]);
+    assert_eq!(engine.lazy_compilations(), 5);
+}
@@ -0,0 +1,84 @@
+//! Tests for re-entering a [`Store`] via the [`Caller`] of a host function.
+
+use wasmi::{Caller, Engine, Error, Extern, Linker, Module, Store, TypedFunc};
+
+/// A Wasm module that recursively calls itself through the imported host function `env.recurse`.
+///
+/// The exported `count` function returns the number of nested executions until `n` is zero.
+const WASM: &str = r#"
+    (module
+        (import "env" "recurse" (func $recurse (param i32) (result i32)))
+        (func (export "count") (param $n i32) (result i32)
+            (if (result i32) (i32.eqz (local.get $n))
+                (then (i32.const 0))
+                (else
+                    (i32.add
+                        (call $recurse (i32.sub (local.get $n) (i32.const 1)))
+                        (i32.const 1)
+                    )
+                )
+            )
+        )
+        (func (export "trap") (unreachable))
+    )
+"#;
+
+/// Instantiates [`WASM`] with `env.recurse` calling back into `count` via its [`Caller`].
+///
+/// `env.recurse` calls the exported `trap` function instead if its parameter is negative.
+fn setup() -> (Store<()>, TypedFunc<i32, i32>) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap(
+            "env",
+            "recurse",
+            |mut caller: Caller<()>, n: i32| -> Result<i32, Error> {
+                if n < 0 {
+                    let trap = caller
+                        .get_export("trap")
+                        .and_then(Extern::into_func)
+                        .unwrap();
+                    trap.typed::<(), ()>(&caller)?.call(&mut caller, ())?;
+                }
+                let count = caller
+                    .get_export("count")
+                    .and_then(Extern::into_func)
+                    .unwrap();
+                count.typed::<i32, i32>(&caller)?.call(&mut caller, n)
+            },
+        )
+        .unwrap();
+    let wasm = wat::parse_str(WASM).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let count = instance
+        .get_typed_func::<i32, i32>(&store, "count")
+        .unwrap();
+    (store, count)
+}
+
+#[test]
+fn reentrance_via_caller() {
+    let (mut store, count) = setup();
+    assert!(!store.is_executing());
+    for n in [0, 1, 10, 100] {
+        assert_eq!(count.call(&mut store, n).unwrap(), n);
+        assert!(!store.is_executing());
+    }
+}
+
+#[test]
+fn trapping_reentrance_via_caller() {
+    let (mut store, count) = setup();
+    let error = count.call(&mut store, -1).unwrap_err();
+    assert!(error.as_trap_code().is_some());
+    assert!(!store.is_executing());
+    // Note: the store can be called again after the nested execution trapped.
+    assert_eq!(count.call(&mut store, 5).unwrap(), 5);
+}
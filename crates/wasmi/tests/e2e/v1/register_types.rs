@@ -0,0 +1,141 @@
+//! Tests for the register type tables of translated functions.
+
+use wasmi::{
+    core::{UntypedValue, ValueType},
+    ir::Register,
+    CompilationMode,
+    Config,
+    Engine,
+    Module,
+    RegisterReader,
+    RegisterTypes,
+    Value,
+};
+
+/// A Wasm module with a function `f` that has mixed parameter and local types.
+///
+/// - Function `f` calls an imported, an internal and an indirect function.
+const WAT: &str = r#"
+    (module
+        (import "host" "g" (func $g (param i32) (result i64)))
+        (type $to_f32 (func (result f32)))
+        (table 1 funcref)
+        (func $f (param i32 f64 externref) (result i32)
+            (local i64 externref externref f32)
+            (local.set 3 (call $g (local.get 0)))
+            (call $h (local.get 2))
+            (local.set 4)
+            (drop)
+            (local.set 6 (call_indirect (type $to_f32) (local.get 0)))
+            (local.get 0)
+        )
+        (func $h (param externref) (result i32 externref)
+            (i32.const 1)
+            (local.get 0)
+        )
+    )
+"#;
+
+/// The types of the parameters and locals of function `f` of [`WAT`].
+const ENTRY_TYPES: [ValueType; 7] = [
+    ValueType::I32,
+    ValueType::F64,
+    ValueType::ExternRef,
+    ValueType::I64,
+    ValueType::ExternRef,
+    ValueType::ExternRef,
+    ValueType::F32,
+];
+
+/// Compiles [`WAT`] with `config` and returns the [`RegisterTypes`] of function `f`.
+fn register_types(config: &Config) -> RegisterTypes {
+    let engine = Engine::new(config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    // Note: function `f` is the first internal function.
+    let func = module.get_compiled_func(1).unwrap();
+    engine.register_types(func, Clone::clone).unwrap()
+}
+
+/// Returns a [`Config`] with register type generation enabled.
+fn config(mode: CompilationMode) -> Config {
+    let mut config = Config::default();
+    config.generate_register_types(true);
+    config.compilation_mode(mode);
+    config
+}
+
+/// A [`RegisterReader`] that reads the bits `n` from every register with index `n`.
+struct IndexReader;
+
+impl RegisterReader for IndexReader {
+    fn read_register(&self, register: Register) -> UntypedValue {
+        UntypedValue::from(i64::from(register.to_i16()))
+    }
+}
+
+#[test]
+fn entry_types() {
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        let register_types = register_types(&config(mode));
+        assert_eq!(register_types.entry(), &ENTRY_TYPES[..], "{mode:?}");
+    }
+}
+
+#[test]
+fn call_site_types() {
+    let register_types = register_types(&config(CompilationMode::Eager));
+    let call_sites = register_types.call_sites();
+    let types: Vec<&[ValueType]> = call_sites.iter().map(|site| site.types()).collect();
+    assert_eq!(
+        types,
+        [
+            &[ValueType::I64][..],
+            &[ValueType::I32, ValueType::ExternRef][..],
+            &[ValueType::F32][..],
+        ]
+    );
+    assert!(call_sites.windows(2).all(|w| w[0].instr() < w[1].instr()));
+    for site in call_sites {
+        assert_eq!(register_types.call_site(site.instr()), Some(site));
+    }
+    assert!(register_types.call_site(u32::MAX).is_none());
+}
+
+#[test]
+fn typed_reads() {
+    let register_types = register_types(&config(CompilationMode::Eager));
+    let local = |index| register_types.read_local(&IndexReader, Register::from_i16(index));
+    assert!(matches!(local(0), Some(Value::I32(0))));
+    assert!(matches!(local(1), Some(Value::F64(_))));
+    assert!(matches!(local(3), Some(Value::I64(3))));
+    assert!(matches!(local(6), Some(Value::F32(_))));
+    // Note: raw register bits of locals with reference type are never read as numbers.
+    for index in [2, 4, 5] {
+        assert!(matches!(local(index), Some(Value::ExternRef(_))), "{index}");
+    }
+    assert!(local(7).is_none());
+    assert!(local(-1).is_none());
+    let site = &register_types.call_sites()[1];
+    let results: Vec<Value> = register_types
+        .read_call_results(&IndexReader, site.instr())
+        .unwrap()
+        .collect();
+    assert_eq!(results.len(), 2);
+    assert!(matches!(results[0], Value::I32(_)));
+    assert!(matches!(results[1], Value::ExternRef(_)));
+    assert!(register_types
+        .read_call_results(&IndexReader, u32::MAX)
+        .is_none());
+}
+
+#[test]
+fn register_types_disabled() {
+    let register_types = register_types(&Config::default());
+    assert!(register_types.entry().is_empty());
+    assert!(register_types.call_sites().is_empty());
+}
@@ -0,0 +1,116 @@
+//! Tests for [`Store::replace_data`] and [`Store::into_data`].
+
+use wasmi::{
+    Caller,
+    Config,
+    Engine,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+    YieldDecision,
+};
+
+/// The user data of the [`Store`] under test.
+#[derive(Debug)]
+struct HostData {
+    /// The values pushed by the Wasm guest via host calls.
+    buffer: Vec<i32>,
+    /// The resource limits of the [`Store`].
+    limits: StoreLimits,
+    /// The number of invocations of the yield callback.
+    yields: usize,
+}
+
+impl HostData {
+    /// Creates a new [`HostData`] that allows for at most `pages` linear memory pages.
+    fn new(pages: usize) -> Self {
+        Self {
+            buffer: Vec::new(),
+            limits: StoreLimitsBuilder::new().memory_size(pages * 65536).build(),
+            yields: 0,
+        }
+    }
+}
+
+/// Creates a [`Store`] with limiter and yield callback installed and instantiates the test module.
+fn setup() -> (Store<HostData>, Instance) {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "push" (func $push (param i32)))
+            (memory 1)
+            (func (export "fill") (param $n i32) (result i32)
+                (local $i i32)
+                (block $exit
+                    (loop $continue
+                        (br_if $exit (i32.ge_u (local.get $i) (local.get $n)))
+                        (call $push (local.get $i))
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br $continue)
+                    )
+                )
+                (memory.grow (i32.const 1))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let mut config = Config::default();
+    config.cooperative_yield(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, HostData::new(2));
+    store.limiter(|data| &mut data.limits);
+    store.set_yield_callback(1, |data| {
+        data.yields += 1;
+        YieldDecision::Continue
+    });
+    let mut linker = <Linker<HostData>>::new(&engine);
+    linker
+        .func_wrap("env", "push", |mut caller: Caller<HostData>, value: i32| {
+            caller.data_mut().buffer.push(value);
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+#[test]
+fn replace_data_preserves_instances() {
+    let (mut store, instance) = setup();
+    let fill = instance.get_typed_func::<i32, i32>(&store, "fill").unwrap();
+    // The limiter allows growing the linear memory from 1 to 2 pages.
+    assert_eq!(fill.call(&mut store, 3).unwrap(), 1);
+    let first = store.replace_data(HostData::new(3));
+    assert_eq!(first.buffer, [0, 1, 2]);
+    assert!(first.yields > 0);
+    assert!(store.data().buffer.is_empty());
+    assert_eq!(store.data().yields, 0);
+    // The same instance is used with the new data and its limiter.
+    assert_eq!(fill.call(&mut store, 2).unwrap(), 2);
+    // The new data allows for 3 pages at most so this growth fails.
+    assert_eq!(fill.call(&mut store, 1).unwrap(), -1);
+    let second = store.into_data();
+    assert_eq!(second.buffer, [0, 1, 0]);
+    assert!(second.yields > 0);
+    // The old data is no longer affected by the store.
+    assert_eq!(first.buffer, [0, 1, 2]);
+}
+
+#[test]
+fn replace_data_limiter_uses_new_data() {
+    let (mut store, instance) = setup();
+    let fill = instance.get_typed_func::<i32, i32>(&store, "fill").unwrap();
+    // The new data denies any linear memory growth.
+    let old = store.replace_data(HostData::new(1));
+    assert!(old.buffer.is_empty());
+    assert_eq!(fill.call(&mut store, 1).unwrap(), -1);
+    assert_eq!(store.data().buffer, [0]);
+}
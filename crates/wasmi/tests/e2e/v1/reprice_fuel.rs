@@ -0,0 +1,278 @@
+//! Tests for repricing the fuel of already translated functions via [`Engine::reprice_fuel`].
+
+use assert_matches::assert_matches;
+use core::num::NonZeroU64;
+use wasmi::{
+    errors::{ErrorKind, FuncError},
+    Caller,
+    CompilationMode,
+    Config,
+    Engine,
+    FuelCosts,
+    Linker,
+    Module,
+    Store,
+};
+
+/// Loops `n` times over simple arithmetic operators.
+const ARITHMETIC: &str = r#"
+    (module
+        (func (export "run") (param $n i32) (result i32)
+            (local $acc i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $acc
+                        (i32.add (local.get $acc) (i32.mul (local.get $n) (i32.const 3)))
+                    )
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+    )
+"#;
+
+/// Uses loads, stores, bulk memory operators and global variables.
+const MEMORY: &str = r#"
+    (module
+        (memory 1)
+        (global $g (mut i32) (i32.const 0))
+        (func (export "run") (param $n i32) (result i32)
+            (memory.fill (i32.const 0) (i32.const 7) (i32.const 100))
+            (memory.copy (i32.const 200) (i32.const 0) (local.get $n))
+            (i32.store (i32.const 400) (i32.add (i32.load (i32.const 200)) (local.get $n)))
+            (global.set $g (i32.load8_u (i32.const 401)))
+            (i32.add (global.get $g) (i32.load (i32.const 400)))
+        )
+    )
+"#;
+
+/// Uses direct and indirect calls with many parameters and results.
+///
+/// The `$add` function is inlined into its caller with optimization level 2.
+const CALLS: &str = r#"
+    (module
+        (type $swap (func
+            (param i32 i32 i32 i32 i32 i32 i32 i32 i32 i32)
+            (result i32 i32 i32 i32 i32 i32 i32 i32 i32 i32)
+        ))
+        (table 1 funcref)
+        (elem (i32.const 0) $swap)
+        (func $add (param i32 i32) (result i32)
+            (i32.add (local.get 0) (local.get 1))
+        )
+        (func $swap (type $swap)
+            (local.get 9) (local.get 8) (local.get 7) (local.get 6) (local.get 5)
+            (local.get 4) (local.get 3) (local.get 2) (local.get 1) (local.get 0)
+        )
+        (func (export "run") (param $n i32) (result i32)
+            (call $add (local.get $n) (i32.const 1))
+            (call_indirect (type $swap)
+                (local.get $n) (local.get $n) (local.get $n) (local.get $n) (local.get $n)
+                (local.get $n) (local.get $n) (local.get $n) (local.get $n) (i32.const 1)
+                (i32.const 0)
+            )
+            (drop) (drop) (drop) (drop) (drop) (drop) (drop) (drop)
+            (i32.add)
+            (i32.add)
+        )
+    )
+"#;
+
+/// Uses nested blocks, `br_table` and `if` with results.
+const CONTROL: &str = r#"
+    (module
+        (func (export "run") (param $n i32) (result i32)
+            (local $i i32)
+            (local $acc i32)
+            (loop $continue
+                (block $b2
+                    (block $b1
+                        (block $b0
+                            (br_table $b0 $b1 $b2 (i32.rem_u (local.get $i) (i32.const 3)))
+                        )
+                        (local.set $acc (i32.add (local.get $acc) (i32.const 1)))
+                        (br $b2)
+                    )
+                    (local.set $acc
+                        (if (result i32) (i32.and (local.get $i) (i32.const 1))
+                            (then (i32.mul (local.get $acc) (i32.const 2)))
+                            (else (i32.sub (local.get $acc) (i32.const 1)))
+                        )
+                    )
+                )
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br_if $continue (i32.lt_u (local.get $i) (local.get $n)))
+            )
+            (local.get $acc)
+        )
+    )
+"#;
+
+/// All Wasm modules under test.
+const MODULES: [&str; 4] = [ARITHMETIC, MEMORY, CALLS, CONTROL];
+
+/// The parameter passed to the `run` function of all [`MODULES`].
+const PARAM: i32 = 10;
+
+/// Returns the default [`FuelCosts`].
+fn costs_a() -> FuelCosts {
+    FuelCosts::default()
+}
+
+/// Returns [`FuelCosts`] that differ from [`costs_a`] in every instruction class.
+fn costs_b() -> FuelCosts {
+    let mut costs = costs_a();
+    costs
+        .set_base(3)
+        .set_entity(5)
+        .set_load(7)
+        .set_store(11)
+        .set_call(13)
+        .set_copies_per_fuel(NonZeroU64::new(2).unwrap())
+        .set_bytes_per_fuel(NonZeroU64::new(4).unwrap());
+    costs
+}
+
+/// Returns all [`Config`]s under test with fuel metering for `costs`.
+fn configs(costs: FuelCosts) -> Vec<Config> {
+    let mut configs = Vec::new();
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        for optimization_level in [0, 1, 2] {
+            let mut config = Config::default();
+            config.consume_fuel(true);
+            config.compilation_mode(mode);
+            config.optimization_level(optimization_level);
+            config.set_fuel_costs(costs);
+            configs.push(config);
+        }
+    }
+    configs
+}
+
+/// Calls the exported `run` function of `module` in a new [`Store`] and returns the consumed fuel.
+fn consumed_fuel(module: &Module) -> u64 {
+    let mut store = Store::new(module.engine(), ());
+    store.add_fuel(u64::MAX / 2).unwrap();
+    let instance = <Linker<()>>::new(module.engine())
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<i32, i32>(&store, "run")
+        .unwrap()
+        .call(&mut store, PARAM)
+        .unwrap();
+    store.fuel_consumed().unwrap()
+}
+
+/// Returns the fuel consumed by the `run` function of `wasm` compiled with `config`.
+///
+/// - If `reprice_to` is `Some` the [`Engine`] is repriced after compiling `wasm`.
+/// - If `warm_up` is `true` the `run` function is called once before repricing
+///   so that lazily compiled functions are translated before repricing.
+fn run(wasm: &str, config: &Config, reprice_to: Option<FuelCosts>, warm_up: bool) -> u64 {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, &wat::parse_str(wasm).unwrap()[..]).unwrap();
+    if warm_up {
+        consumed_fuel(&module);
+    }
+    if let Some(costs) = reprice_to {
+        engine.reprice_fuel(&costs).unwrap();
+        assert_eq!(engine.fuel_costs(), costs);
+    }
+    consumed_fuel(&module)
+}
+
+#[test]
+fn reprice_matches_fresh_translation() {
+    for wasm in MODULES {
+        for (config_a, config_b) in configs(costs_a()).iter().zip(&configs(costs_b())) {
+            for warm_up in [false, true] {
+                let fresh_a = run(wasm, config_a, None, warm_up);
+                let fresh_b = run(wasm, config_b, None, warm_up);
+                assert_ne!(fresh_a, fresh_b);
+                assert_eq!(run(wasm, config_a, Some(costs_b()), warm_up), fresh_b);
+                assert_eq!(run(wasm, config_b, Some(costs_a()), warm_up), fresh_a);
+            }
+        }
+    }
+}
+
+#[test]
+fn reprice_round_trip() {
+    for wasm in MODULES {
+        for config in configs(costs_a()) {
+            let engine = Engine::new(&config);
+            let module = Module::new(&engine, &wat::parse_str(wasm).unwrap()[..]).unwrap();
+            // Note: lazily compiled functions consume fuel for their compilation upon first call.
+            consumed_fuel(&module);
+            let before = consumed_fuel(&module);
+            engine.reprice_fuel(&costs_b()).unwrap();
+            assert_ne!(consumed_fuel(&module), before);
+            engine.reprice_fuel(&costs_a()).unwrap();
+            assert_eq!(consumed_fuel(&module), before);
+        }
+    }
+}
+
+#[test]
+fn reprice_out_of_bounds() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wat::parse_str(ARITHMETIC).unwrap()[..]).unwrap();
+    let before = consumed_fuel(&module);
+    let mut costs = costs_a();
+    costs.set_base(u64::from(u32::MAX));
+    assert!(engine.reprice_fuel(&costs).is_err());
+    // Note: nothing is repriced if repricing fails.
+    assert_eq!(engine.fuel_costs(), costs_a());
+    assert_eq!(consumed_fuel(&module), before);
+}
+
+#[test]
+fn reprice_from_host_func() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "reprice" (func $reprice))
+            (func (export "run") (call $reprice))
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    store.add_fuel(1_000).unwrap();
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "reprice", |caller: Caller<()>| {
+            let error = caller.engine().reprice_fuel(&costs_b()).unwrap_err();
+            assert_matches!(
+                error.kind(),
+                ErrorKind::Func(FuncError::EngineAlreadyExecuting)
+            );
+        })
+        .unwrap();
+    linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<(), ()>(&store, "run")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    engine.reprice_fuel(&costs_b()).unwrap();
+}
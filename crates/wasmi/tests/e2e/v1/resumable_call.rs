@@ -3,13 +3,14 @@
 use core::slice;
 use wasmi::{
     core::{TrapCode, ValueType},
-    errors::ErrorKind,
+    errors::{ErrorKind, FuncError},
     Caller,
     Config,
     Engine,
     Error,
     Extern,
     Func,
+    HostYield,
     Linker,
     Module,
     ResumableCall,
@@ -365,3 +366,423 @@ fn run_test_typed(wasm_fn: Func, mut store: &mut Store<TestData>, wasm_trap: boo
         assert_eq!(call.unwrap().assert_finish(), 4);
     }
 }
+
+/// Sets up the `read_chunk` generator test returning the `"sum"` function.
+///
+/// # Note
+///
+/// The guest accumulates the chunks returned by `read_chunk` until it returns 0.
+/// Upon each call `read_chunk` yields to request a chunk from the embedder. Once
+/// re-entered it either returns the provided chunk or yields again if the embedder
+/// provided a negative chunk which signals that no chunk is ready, yet.
+fn resumable_call_read_chunk_setup(yielded: Value) -> (Store<TestData>, Func) {
+    let (mut store, mut linker) = test_setup(0);
+    linker
+        .func_wrap(
+            "host",
+            "read_chunk",
+            move |caller: Caller<'_, TestData>, input: i32| -> Result<i32, Error> {
+                match caller.continuation() {
+                    None => Err(Error::host(HostYield::new(0, [yielded.clone()]))),
+                    Some(token) if input < 0 => {
+                        Err(Error::host(HostYield::new(token + 1, [Value::I32(0)])))
+                    }
+                    Some(_) => Ok(input),
+                }
+            },
+        )
+        .unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "host" "read_chunk" (func $read_chunk (param i32) (result i32)))
+            (func (export "sum") (result i32)
+                (local $chunk i32)
+                (local $sum i32)
+                (loop $continue
+                    (local.set $chunk (call $read_chunk (i32.const 0)))
+                    (local.set $sum (i32.add (local.get $sum) (local.get $chunk)))
+                    (br_if $continue (local.get $chunk))
+                )
+                (local.get $sum)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let sum = instance.get_func(&store, "sum").unwrap();
+    (store, sum)
+}
+
+/// The chunks provided by the embedder to `read_chunk` where `-1` signals that no chunk is ready.
+const CHUNKS: [i32; 7] = [10, -1, 20, -1, -1, 30, 0];
+
+#[test]
+fn resumable_call_yield_typed() {
+    let (mut store, sum) = resumable_call_read_chunk_setup(Value::I32(0));
+    let sum = sum.typed::<(), i32>(&store).unwrap();
+    let mut invocation = sum.call_resumable(&mut store, ()).unwrap_resumable();
+    let mut tokens = Vec::new();
+    for (n, chunk) in CHUNKS.into_iter().enumerate() {
+        let host_yield = invocation.host_yield().unwrap();
+        assert!(matches!(host_yield.values(), [Value::I32(0)]));
+        tokens.push(host_yield.token());
+        match invocation.resume_with(&mut store, chunk).unwrap() {
+            TypedResumableCall::Resumable(next) => invocation = next,
+            TypedResumableCall::Finished(result) => {
+                assert_eq!(n, CHUNKS.len() - 1);
+                assert_eq!(result, 60);
+                assert_eq!(tokens, [0, 0, 1, 0, 1, 2, 0]);
+                return;
+            }
+        }
+    }
+    panic!("expected the resumable call to finish")
+}
+
+#[test]
+fn resumable_call_yield_untyped() {
+    let (mut store, sum) = resumable_call_read_chunk_setup(Value::I32(0));
+    let mut result = Value::I32(0);
+    let mut call = sum
+        .call_resumable(&mut store, &[], slice::from_mut(&mut result))
+        .unwrap();
+    for chunk in CHUNKS {
+        let ResumableCall::Resumable(invocation) = call else {
+            panic!("expected ResumableCall::Resumable")
+        };
+        assert!(invocation.host_yield().is_some());
+        call = invocation
+            .resume_with(
+                &mut store,
+                &[Value::I32(chunk)],
+                slice::from_mut(&mut result),
+            )
+            .unwrap();
+    }
+    assert!(matches!(call, ResumableCall::Finished));
+    assert_eq!(result.i32(), Some(60));
+}
+
+#[test]
+fn resumable_call_yield_resume_with_results() {
+    let (mut store, sum) = resumable_call_read_chunk_setup(Value::I32(0));
+    let sum = sum.typed::<(), i32>(&store).unwrap();
+    let mut invocation = sum.call_resumable(&mut store, ()).unwrap_resumable();
+    // Resuming with results bypasses the host function instead of re-entering it.
+    for chunk in [5, 0] {
+        match invocation.resume(&mut store, &[Value::I32(chunk)]).unwrap() {
+            TypedResumableCall::Resumable(next) => invocation = next,
+            TypedResumableCall::Finished(result) => {
+                assert_eq!(chunk, 0);
+                assert_eq!(result, 5);
+                return;
+            }
+        }
+    }
+    panic!("expected the resumable call to finish")
+}
+
+#[test]
+fn resumable_call_yield_mismatching_types() {
+    let (mut store, sum) = resumable_call_read_chunk_setup(Value::I64(0));
+    let error = sum
+        .typed::<(), i32>(&store)
+        .unwrap()
+        .call_resumable(&mut store, ())
+        .unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::Func(FuncError::MismatchingResultType)
+    ));
+}
+
+#[test]
+fn resumable_call_yield_nested_host_call() {
+    let (mut store, mut linker) = test_setup(0);
+    // Note: `nested` yields unless it has been re-entered itself.
+    linker
+        .func_wrap(
+            "host",
+            "nested",
+            |caller: Caller<'_, TestData>| -> Result<i32, Error> {
+                match caller.continuation() {
+                    None => Err(Error::host(HostYield::new(1, [Value::I32(0)]))),
+                    Some(token) => Ok(token as i32),
+                }
+            },
+        )
+        .unwrap();
+    // Note: Once re-entered `outer` calls `nested` via the exported `inner` Wasm function.
+    linker
+        .func_wrap(
+            "host",
+            "outer",
+            |mut caller: Caller<'_, TestData>| -> Result<i32, Error> {
+                let Some(token) = caller.continuation() else {
+                    return Err(Error::host(HostYield::new(7, [Value::I32(0)])));
+                };
+                let inner = caller
+                    .get_export("inner")
+                    .and_then(Extern::into_func)
+                    .unwrap();
+                let mut result = [Value::I32(0)];
+                let error = inner.call(&mut caller, &[], &mut result).unwrap_err();
+                // The nested host call does not observe the continuation token of `outer`.
+                let host_yield = error.downcast_ref::<HostYield>().unwrap();
+                assert_eq!(host_yield.token(), 1);
+                // The continuation token of `outer` is restored after the nested host call.
+                assert_eq!(caller.continuation(), Some(token));
+                Ok(token as i32)
+            },
+        )
+        .unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "host" "outer" (func $outer (result i32)))
+            (import "host" "nested" (func $nested (result i32)))
+            (func (export "run") (result i32)
+                (call $outer)
+            )
+            (func (export "inner") (result i32)
+                (call $nested)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<(), i32>(&store, "run").unwrap();
+    let invocation = run.call_resumable(&mut store, ()).unwrap_resumable();
+    assert_eq!(invocation.host_yield().map(HostYield::token), Some(7));
+    match invocation.resume_with(&mut store, ()).unwrap() {
+        TypedResumableCall::Finished(result) => assert_eq!(result, 7),
+        TypedResumableCall::Resumable(_) => panic!("expected the resumable call to finish"),
+    }
+}
+
+#[test]
+fn resumable_call_exit_is_final() {
+    let (mut store, mut linker) = test_setup(0);
+    let host_fn = Func::wrap(&mut store, || -> Result<(), Error> { Err(Error::exit(3)) });
+    linker.define("env", "exit", host_fn).unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "exit" (func $exit))
+            (func (export "wasm_fn") (result i32)
+                (call $exit)
+                (i32.const 0)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let wasm_fn = instance.get_func(&store, "wasm_fn").unwrap();
+    let assert_exit = |error: Error| {
+        assert!(matches!(error.kind(), ErrorKind::Exit(3)));
+        assert_eq!(error.exit_status(), Some(3));
+        assert_eq!(error.as_trap_code(), None);
+    };
+    let mut results = [Value::I32(0)];
+    assert_exit(wasm_fn.call(&mut store, &[], &mut results).unwrap_err());
+    // Unlike other host errors the exit cannot be resumed.
+    assert_exit(
+        wasm_fn
+            .call_resumable(&mut store, &[], &mut results)
+            .unwrap_err(),
+    );
+    assert_exit(
+        wasm_fn
+            .typed::<(), i32>(&store)
+            .unwrap()
+            .call_resumable(&mut store, ())
+            .unwrap_err(),
+    );
+}
+
+#[test]
+fn resumable_call_host_trap_is_final() {
+    let (mut store, mut linker) = test_setup(0);
+    let trap = Func::wrap(&mut store, || -> Result<(), Error> {
+        Err(Error::host_trap(7))
+    });
+    let other = Func::wrap(&mut store, || -> Result<(), Error> {
+        Err(Error::new("other"))
+    });
+    linker.define("env", "trap", trap).unwrap();
+    linker.define("env", "other", other).unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "trap" (func $trap))
+            (import "env" "other" (func $other))
+            (func (export "trap") (result i32)
+                (call $trap)
+                (i32.const 0)
+            )
+            (func (export "other") (result i32)
+                (call $other)
+                (i32.const 0)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let trap = instance.get_typed_func::<(), i32>(&store, "trap").unwrap();
+    let assert_host_trap = |error: Error| {
+        assert!(matches!(error.kind(), ErrorKind::HostTrap(7)));
+        assert_eq!(error.as_host_trap(), Some(7));
+        assert_eq!(error.as_trap_code(), None);
+    };
+    // Unlike other host errors host traps cannot be resumed.
+    assert_host_trap(trap.call_resumable(&mut store, ()).unwrap_err());
+    let mut results = [Value::I32(0)];
+    assert_host_trap(
+        trap.func()
+            .call_resumable(&mut store, &[], &mut results)
+            .unwrap_err(),
+    );
+    let other = instance.get_typed_func::<(), i32>(&store, "other").unwrap();
+    match other.call_resumable(&mut store, ()).unwrap() {
+        TypedResumableCall::Resumable(invocation) => {
+            assert_eq!(invocation.host_error().as_host_trap(), None);
+        }
+        TypedResumableCall::Finished(_) => panic!("expected a resumable call"),
+    }
+}
+
+#[test]
+fn resumable_call_stress_with_stack_reallocations() {
+    let (mut store, mut linker) = test_setup(0);
+    // Note: Every call to `pause` returns a resumable error carrying its input.
+    let pause = Func::wrap(&mut store, |input: i32| -> Result<i32, Error> {
+        Err(Error::i32_exit(input))
+    });
+    linker.define("env", "pause", pause).unwrap();
+    // The `deep` functions recurse `depth` times before pausing and have many
+    // locals so that varying recursion depths repeatedly grow the value stack.
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "pause" (func $pause (param i32) (result i32)))
+            (func $deep (param $depth i32) (param $x i32) (result i32)
+                (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+                (if (result i32) (i32.eqz (local.get $depth))
+                    (then (call $pause (local.get $x)))
+                    (else
+                        (i32.add
+                            (call $deep
+                                (i32.sub (local.get $depth) (i32.const 1))
+                                (local.get $x)
+                            )
+                            (i32.const 1)
+                        )
+                    )
+                )
+            )
+            (func $deep_tail (param $depth i32) (param $x i32) (result i32)
+                (local i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64 i64)
+                (if (i32.eqz (local.get $depth))
+                    (then (return_call $pause (local.get $x)))
+                )
+                (i32.add
+                    (call $deep_tail
+                        (i32.sub (local.get $depth) (i32.const 1))
+                        (local.get $x)
+                    )
+                    (i32.const 1)
+                )
+            )
+            (func (export "run") (param $n i32) (result i32)
+                (local $i i32)
+                (local $sum i32)
+                (loop $continue
+                    ;; sum += deep(i * 37 % 211, i) + pause(i) + deep_tail(i * 13 % 97, i)
+                    (local.set $sum
+                        (i32.add
+                            (local.get $sum)
+                            (i32.add
+                                (i32.add
+                                    (call $deep
+                                        (i32.rem_u
+                                            (i32.mul (local.get $i) (i32.const 37))
+                                            (i32.const 211)
+                                        )
+                                        (local.get $i)
+                                    )
+                                    (call $pause (local.get $i))
+                                )
+                                (call $deep_tail
+                                    (i32.rem_u
+                                        (i32.mul (local.get $i) (i32.const 13))
+                                        (i32.const 97)
+                                    )
+                                    (local.get $i)
+                                )
+                            )
+                        )
+                    )
+                    (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                    (br_if $continue (i32.lt_u (local.get $i) (local.get $n)))
+                )
+                (local.get $sum)
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(store.engine(), &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+    let n = 334;
+    let expected: i32 = (0..n)
+        .map(|i| (2 * i + i * 37 % 211) + 2 * i + (2 * i + i * 13 % 97))
+        .sum();
+    let mut pauses = 0;
+    let mut call = run.call_resumable(&mut store, n).unwrap();
+    let result = loop {
+        match call {
+            TypedResumableCall::Finished(result) => break result,
+            TypedResumableCall::Resumable(invocation) => {
+                pauses += 1;
+                let input = invocation.host_error().i32_exit_status().unwrap();
+                assert!(matches!(invocation.host_params(), &[Value::I32(param)] if param == input));
+                // The resumed results must end up in the registers of the live caller frame.
+                call = invocation
+                    .resume(&mut store, &[Value::I32(2 * input)])
+                    .unwrap();
+            }
+        }
+    };
+    assert_eq!(pauses, 3 * n);
+    assert_eq!(result, expected);
+}
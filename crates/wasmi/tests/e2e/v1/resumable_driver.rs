@@ -0,0 +1,193 @@
+//! Tests to check that the [`ResumableDriver`] deterministically drives resumable calls.
+
+use wasmi::{
+    core::F32,
+    DriverState,
+    Engine,
+    Error,
+    Func,
+    FuncType,
+    Linker,
+    Module,
+    ResumableDriver,
+    Store,
+    TypedResumableCall,
+    Value,
+};
+
+/// A Wasm module that calls three different host functions in sequence.
+const WAT: &str = r#"
+    (module
+        (import "env" "read" (func $read (param i32) (result i32)))
+        (import "env" "sum" (func $sum (param i64 i64) (result i64)))
+        (import "env" "log" (func $log (param f32)))
+        (func (export "run") (param $x i32) (result i64)
+            (local $sum i64)
+            (local.set $sum
+                (call $sum
+                    (i64.extend_i32_s (call $read (local.get $x)))
+                    (i64.const 5)
+                )
+            )
+            (call $log (f32.const 1.5))
+            (local.get $sum)
+        )
+    )
+"#;
+
+/// The host functions imported by [`WAT`].
+struct HostFuncs {
+    read: Func,
+    sum: Func,
+    log: Func,
+}
+
+/// Instantiates [`WAT`] with host functions that always pause the invocation.
+fn setup() -> (Store<()>, HostFuncs, ResumableDriver<i64>) {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let funcs = HostFuncs {
+        read: Func::wrap(&mut store, |_: i32| -> Result<i32, Error> {
+            Err(Error::new("read"))
+        }),
+        sum: Func::wrap(&mut store, |_: i64, _: i64| -> Result<i64, Error> {
+            Err(Error::new("sum"))
+        }),
+        log: Func::wrap(&mut store, |_: F32| -> Result<(), Error> {
+            Err(Error::new("log"))
+        }),
+    };
+    linker.define("env", "read", funcs.read).unwrap();
+    linker.define("env", "sum", funcs.sum).unwrap();
+    linker.define("env", "log", funcs.log).unwrap();
+    let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let run = instance.get_typed_func::<i32, i64>(&store, "run").unwrap();
+    let call = run.call_resumable(&mut store, 42).unwrap();
+    (store, funcs, ResumableDriver::new(call))
+}
+
+/// Returns the [`FuncType`] of all host functions that interrupted the `driver`.
+fn logged_types(store: &Store<()>, driver: &ResumableDriver<i64>) -> Vec<FuncType> {
+    driver
+        .log()
+        .iter()
+        .map(|interruption| interruption.func().ty(store))
+        .collect()
+}
+
+#[test]
+fn run_to_completion() {
+    let (mut store, funcs, mut driver) = setup();
+    driver
+        .push_inputs([Value::I32(10)])
+        .push_inputs([Value::I64(15)])
+        .push_inputs([]);
+    assert_eq!(
+        driver.run_until_stalled(&mut store, 10).unwrap(),
+        DriverState::Finished
+    );
+    assert_eq!(driver.results(), Some(&15));
+    let expected = [funcs.read, funcs.sum, funcs.log].map(|func| func.ty(&store));
+    assert_eq!(logged_types(&store, &driver), expected);
+    let params: Vec<_> = driver
+        .log()
+        .iter()
+        .map(|interruption| interruption.params().to_vec())
+        .collect();
+    assert_eq!(params.len(), 3);
+    assert_eq!(params[0][0].i32(), Some(42));
+    assert_eq!(params[1][0].i64(), Some(10));
+    assert_eq!(params[1][1].i64(), Some(5));
+    assert_eq!(params[2][0].f32(), Some(F32::from(1.5)));
+    assert!(driver.invocation().is_none());
+    assert_eq!(driver.into_results(), Some(15));
+}
+
+#[test]
+fn stalls_on_empty_queue() {
+    let (mut store, funcs, mut driver) = setup();
+    assert_eq!(
+        driver.run_until_stalled(&mut store, 10).unwrap(),
+        DriverState::Stalled
+    );
+    assert_eq!(logged_types(&store, &driver), [funcs.read.ty(&store)]);
+    driver.push_inputs([Value::I32(1)]);
+    assert_eq!(
+        driver.run_until_stalled(&mut store, 10).unwrap(),
+        DriverState::Stalled
+    );
+    let invocation = driver.invocation().unwrap();
+    assert_eq!(invocation.host_func().ty(&store), funcs.sum.ty(&store));
+    assert_eq!(invocation.host_params()[0].i64(), Some(1));
+    assert_eq!(driver.results(), None);
+}
+
+#[test]
+fn step_limit() {
+    let (mut store, _funcs, mut driver) = setup();
+    driver
+        .push_inputs([Value::I32(1)])
+        .push_inputs([Value::I64(2)])
+        .push_inputs([]);
+    assert_eq!(
+        driver.run_until_stalled(&mut store, 1).unwrap(),
+        DriverState::StepLimitReached
+    );
+    assert_eq!(driver.log().len(), 2);
+    assert_eq!(
+        driver.run_until_stalled(&mut store, 0).unwrap(),
+        DriverState::StepLimitReached
+    );
+    assert_eq!(
+        driver.run_until_stalled(&mut store, 2).unwrap(),
+        DriverState::Finished
+    );
+    assert_eq!(driver.results(), Some(&2));
+}
+
+#[test]
+fn trap_stops_driver() {
+    let (mut store, _funcs, mut driver) = setup();
+    // Resuming with mismatching types is an error.
+    driver.push_inputs([Value::I64(1)]);
+    assert!(driver.run_until_stalled(&mut store, 10).is_err());
+    assert_eq!(
+        driver.run_until_stalled(&mut store, 10).unwrap(),
+        DriverState::Trapped
+    );
+    assert_eq!(driver.results(), None);
+}
+
+#[test]
+fn host_params_after_resume_with() {
+    let (mut store, funcs, _driver) = setup();
+    let module = Module::new(store.engine(), &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let mut linker = <Linker<()>>::new(store.engine());
+    linker.define("env", "read", funcs.read).unwrap();
+    linker.define("env", "sum", funcs.sum).unwrap();
+    linker.define("env", "log", funcs.log).unwrap();
+    let run = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<i32, i64>(&store, "run")
+        .unwrap();
+    let TypedResumableCall::Resumable(invocation) = run.call_resumable(&mut store, 42).unwrap()
+    else {
+        panic!("expected the call to pause")
+    };
+    assert_eq!(invocation.host_params()[0].i32(), Some(42));
+    // Re-entering the host function updates the parameters.
+    let TypedResumableCall::Resumable(invocation) = invocation.resume_with(&mut store, 7).unwrap()
+    else {
+        panic!("expected the call to pause")
+    };
+    assert_eq!(invocation.host_params()[0].i32(), Some(7));
+}
@@ -0,0 +1,242 @@
+//! Tests for calls whose results are returned right after the call.
+//!
+//! With optimizations enabled Wasmi translates those returns to [`Instruction::ReturnForward`]
+//! which is skipped if the called Wasm function returns its results directly to
+//! the caller of the function.
+
+use std::cell::Cell;
+use wasmi::{
+    core::TrapCode, ir::Instruction, Config, Engine, ExecutionDigest, Instance, Linker, Module,
+    RegisterReader, Store, Value,
+};
+
+/// A Wasm module with call chains of depth 3 that forward the results of their calls.
+const WAT: &str = r#"
+    (module
+        (import "env" "host" (func $host (param i32) (result i32)))
+        (type $id (func (param i32) (result i32)))
+        (table funcref (elem $c1))
+
+        (func $c0)
+        (func $b0 (call $c0))
+        (func (export "a0") (call $b0))
+
+        (func $c1 (param i32) (result i32)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func $b1 (param i32) (result i32)
+            (call $c1 (i32.add (local.get 0) (i32.const 10)))
+        )
+        (func (export "a1") (param i32) (result i32)
+            (call $b1 (i32.add (local.get 0) (i32.const 100)))
+        )
+
+        (func $c2 (param i32) (result i32 i32)
+            (local.get 0)
+            (i32.add (local.get 0) (i32.const 1))
+        )
+        (func $b2 (param i32) (result i32 i32)
+            (return (call $c2 (local.get 0)))
+        )
+        (func (export "a2") (param i32) (result i32 i32)
+            (return (call $b2 (local.get 0)))
+        )
+
+        (func $c5 (param i32) (result i32 i32 i32 i32 i32)
+            (local.get 0)
+            (i32.add (local.get 0) (i32.const 1))
+            (i32.add (local.get 0) (i32.const 2))
+            (i32.add (local.get 0) (i32.const 3))
+            (i32.add (local.get 0) (i32.const 4))
+        )
+        (func $b5 (param i32) (result i32 i32 i32 i32 i32)
+            (call $c5 (local.get 0))
+        )
+        (func (export "a5") (param i32) (result i32 i32 i32 i32 i32)
+            (call $b5 (local.get 0))
+        )
+
+        (func $b_host (param i32) (result i32)
+            (call $host (local.get 0))
+        )
+        (func (export "a_host") (param i32) (result i32)
+            (call $b_host (local.get 0))
+        )
+
+        (func $b_indirect (param i32) (result i32)
+            (call_indirect (type $id) (local.get 0) (i32.const 0))
+        )
+        (func (export "a_indirect") (param i32) (result i32)
+            (call $b_indirect (local.get 0))
+        )
+
+        (func $b_tail (param i32) (result i32)
+            (return_call $c1 (local.get 0))
+        )
+        (func (export "a_tail") (param i32) (result i32)
+            (call $b_tail (local.get 0))
+        )
+
+        (func $b_not_forwarded (param i32) (result i32)
+            (i32.add (call $c1 (local.get 0)) (i32.const 1))
+        )
+        (func (export "a_not_forwarded") (param i32) (result i32)
+            (call $b_not_forwarded (local.get 0))
+        )
+
+        (func $rec (export "rec") (param i32) (result i32)
+            (if (result i32) (i32.eqz (local.get 0))
+                (then (i32.const 0))
+                (else (return (call $rec (i32.sub (local.get 0) (i32.const 1)))))
+            )
+        )
+    )
+"#;
+
+thread_local! {
+    /// The number of executed return instructions.
+    static RETURNS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Counts the executed return instructions in [`RETURNS`].
+fn count_returns(instr: &Instruction, _registers: &dyn RegisterReader) -> u64 {
+    if matches!(
+        instr,
+        Instruction::Return
+            | Instruction::ReturnReg { .. }
+            | Instruction::ReturnReg2 { .. }
+            | Instruction::ReturnReg3 { .. }
+            | Instruction::ReturnImm32 { .. }
+            | Instruction::ReturnI64Imm32 { .. }
+            | Instruction::ReturnF64Imm32 { .. }
+            | Instruction::ReturnSpan { .. }
+            | Instruction::ReturnMany { .. }
+            | Instruction::ReturnForward { .. }
+    ) {
+        RETURNS.with(|returns| returns.set(returns.get() + 1));
+    }
+    0
+}
+
+/// Returns the [`Config`] for the Wasm module under test.
+fn config() -> Config {
+    let mut config = Config::default();
+    config.wasm_tail_call(true).optimization_level(1);
+    config
+}
+
+/// Instantiates the Wasm module under test using `config`.
+fn setup(config: &Config) -> (Store<()>, Instance) {
+    let engine = Engine::new(config);
+    let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "host", |input: i32| input.wrapping_mul(2))
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Calls the exported function `name` with `input` and returns its results.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, input: &[Value]) -> Vec<i32> {
+    let func = instance.get_func(&*store, name).unwrap();
+    let len_results = func.ty(&*store).results().len();
+    let mut results = vec![Value::I32(0); len_results];
+    func.call(&mut *store, input, &mut results).unwrap();
+    results.iter().map(|value| value.i32().unwrap()).collect()
+}
+
+/// Calls the exported function `name` with `input` and returns its results
+/// alongside the number of executed return instructions.
+fn call_counted(name: &str, input: &[Value]) -> (Vec<i32>, u64) {
+    call_counted_with(config(), name, input)
+}
+
+/// Calls the exported function `name` with `input` using `config` and returns its
+/// results alongside the number of executed return instructions.
+fn call_counted_with(mut config: Config, name: &str, input: &[Value]) -> (Vec<i32>, u64) {
+    config.execution_digest(ExecutionDigest::Custom(count_returns));
+    let (mut store, instance) = setup(&config);
+    RETURNS.with(|returns| returns.set(0));
+    let results = call(&mut store, &instance, name, input);
+    (results, RETURNS.with(Cell::get))
+}
+
+#[test]
+fn forwarded_results() {
+    let (mut store, instance) = setup(&config());
+    let input = [Value::I32(5)];
+    assert_eq!(call(&mut store, &instance, "a0", &[]), []);
+    assert_eq!(call(&mut store, &instance, "a1", &input), [116]);
+    assert_eq!(call(&mut store, &instance, "a2", &input), [5, 6]);
+    assert_eq!(call(&mut store, &instance, "a5", &input), [5, 6, 7, 8, 9]);
+    assert_eq!(call(&mut store, &instance, "a_host", &input), [10]);
+    assert_eq!(call(&mut store, &instance, "a_indirect", &input), [6]);
+    assert_eq!(call(&mut store, &instance, "a_tail", &input), [6]);
+    assert_eq!(call(&mut store, &instance, "a_not_forwarded", &input), [7]);
+    assert_eq!(call(&mut store, &instance, "rec", &[Value::I32(1000)]), [0]);
+}
+
+#[test]
+fn forwarded_returns_are_skipped() {
+    let input = [Value::I32(5)];
+    // Only the innermost callee of a forwarding call chain executes its return.
+    assert_eq!(call_counted("a0", &[]), (vec![], 1));
+    assert_eq!(call_counted("a1", &input), (vec![116], 1));
+    assert_eq!(call_counted("a2", &input), (vec![5, 6], 1));
+    assert_eq!(call_counted("a5", &input), (vec![5, 6, 7, 8, 9], 1));
+    assert_eq!(call_counted("a_indirect", &input), (vec![6], 1));
+    assert_eq!(call_counted("a_tail", &input), (vec![6], 1));
+    assert_eq!(call_counted("rec", &[Value::I32(100)]), (vec![0], 1));
+    // Host functions do not forward their results so their Wasm caller executes its return.
+    assert_eq!(call_counted("a_host", &input), (vec![10], 1));
+    // Results that are not returned right after the call are not forwarded.
+    assert_eq!(call_counted("a_not_forwarded", &input), (vec![7], 2));
+}
+
+#[test]
+fn unoptimized_returns_are_not_skipped() {
+    let mut config = config();
+    config.optimization_level(0);
+    let input = [Value::I32(5)];
+    assert_eq!(call_counted_with(config, "a0", &[]), (vec![], 3));
+    assert_eq!(call_counted_with(config, "a1", &input), (vec![116], 3));
+    assert_eq!(
+        call_counted_with(config, "a5", &input),
+        (vec![5, 6, 7, 8, 9], 3)
+    );
+}
+
+#[test]
+fn forwarded_recursion_overflows_stack() {
+    let (mut store, instance) = setup(&config());
+    let error = instance
+        .get_typed_func::<i32, i32>(&store, "rec")
+        .unwrap()
+        .call(&mut store, 100_000)
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+    // The store is still usable after the trap.
+    assert_eq!(call(&mut store, &instance, "a1", &[Value::I32(5)]), [116]);
+}
+
+#[test]
+fn forwarded_results_consume_fuel() {
+    let mut config = config();
+    config.consume_fuel(true);
+    let (mut store, instance) = setup(&config);
+    store.add_fuel(1_000).unwrap();
+    let before = store.fuel_consumed().unwrap();
+    call(&mut store, &instance, "a1", &[Value::I32(5)]);
+    let consumed = store.fuel_consumed().unwrap() - before;
+    // The skipped returns of the forwarding callers are still charged.
+    assert!(consumed >= 3 * 2);
+    let before = store.fuel_consumed().unwrap();
+    call(&mut store, &instance, "a1", &[Value::I32(5)]);
+    assert_eq!(store.fuel_consumed().unwrap() - before, consumed);
+}
@@ -0,0 +1,164 @@
+//! Tests to check the runtime signature computed by Wasmi executions.
+
+use wasmi::{
+    ir::Instruction,
+    Config,
+    Engine,
+    ExecutionDigest,
+    Linker,
+    Module,
+    RegisterReader,
+    Store,
+};
+
+/// A Wasm module exercising unary, binary, immediate and control flow instructions.
+const WAT: &str = r#"
+    (module
+        (func (export "run") (param $n i32) (result i64)
+            (local $acc i64)
+            (local.set $acc (i64.const 1))
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $acc
+                        (i64.add
+                            (i64.mul (local.get $acc) (i64.const 31))
+                            (i64.extend_i32_u (i32.clz (local.get $n)))
+                        )
+                    )
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (i64.rem_u (local.get $acc) (i64.const 1000000007))
+        )
+    )
+"#;
+
+/// A Wasm module with a call whose results are returned right after the call
+/// and a doubly negated branch condition.
+///
+/// Optimizations translate both shapes to different Wasmi bytecode.
+const WAT_OPTIMIZED_SHAPES: &str = r#"
+    (module
+        (func $double (param i32) (result i32)
+            (i32.add (local.get 0) (local.get 0))
+        )
+        (func (export "run") (param $n i32) (result i64)
+            (block $exit
+                (br_if $exit (i32.eqz (i32.eqz (local.get $n))))
+                (return (i64.extend_i32_u (call $double (i32.const 1))))
+            )
+            (return (i64.extend_i32_u (call $forward (local.get $n))))
+        )
+        (func $forward (param i32) (result i32)
+            (return (call $double (local.get 0)))
+        )
+    )
+"#;
+
+/// Executes the `run` function of [`WAT`] with `n` and returns the resulting runtime signature.
+fn run(config: &Config, n: i32) -> u64 {
+    run_wat(WAT, config, n)
+}
+
+/// Executes the `run` function of `wat` with `n` and returns the resulting runtime signature.
+fn run_wat(wat: &str, config: &Config, n: i32) -> u64 {
+    let engine = Engine::new(config);
+    let mut store = Store::new(&engine, ());
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_typed_func::<i32, i64>(&store, "run").unwrap();
+    func.call(&mut store, n).unwrap();
+    store.runtime_signature()
+}
+
+/// The runtime signature of a [`Store`] that has not executed any digested instructions.
+const INITIAL_SIGNATURE: u64 = 0x97b6_9fca_e669_84bf;
+
+#[test]
+fn instruction_primes_signature() {
+    let mut config = Config::default();
+    config.execution_digest(ExecutionDigest::InstructionPrimes);
+    let expected = [
+        (0, 0x9aa2_aedb_b49e_1752),
+        (1, 0x315c_2fa5_473d_3abd),
+        (10, 0x001b_0d17_b798_958e),
+        (1000, 0xa465_ee35_6de8_4c51),
+    ];
+    for (n, signature) in expected {
+        assert_eq!(run(&config, n), signature, "signature mismatch for n = {n}");
+    }
+    // `Config::update_runtime_signature` is a shorthand for the same strategy.
+    let mut shorthand = Config::default();
+    shorthand.update_runtime_signature(true);
+    assert_eq!(shorthand, config);
+}
+
+#[test]
+fn instruction_primes_signature_of_optimized_shapes() {
+    let mut config = Config::default();
+    config.execution_digest(ExecutionDigest::InstructionPrimes);
+    // The signatures must not change unless optimizations are enabled.
+    let expected = [(0, 0xeb42_aa48_e7c0_8206), (1, 0x31e2_ad51_af8b_c8c1)];
+    for (n, signature) in expected {
+        assert_eq!(
+            run_wat(WAT_OPTIMIZED_SHAPES, &config, n),
+            signature,
+            "signature mismatch for n = {n}"
+        );
+    }
+    config.optimization_level(1);
+    for (n, signature) in expected {
+        assert_ne!(run_wat(WAT_OPTIMIZED_SHAPES, &config, n), signature);
+    }
+}
+
+#[test]
+fn no_signature() {
+    let mut config = Config::default();
+    assert_eq!(run(&config, 10), INITIAL_SIGNATURE);
+    config.execution_digest(ExecutionDigest::None);
+    assert_eq!(run(&config, 10), INITIAL_SIGNATURE);
+    config.update_runtime_signature(false);
+    assert_eq!(run(&config, 10), INITIAL_SIGNATURE);
+}
+
+#[test]
+fn custom_signature() {
+    /// Digests every instruction equally and ignores its operands.
+    fn count(_instr: &Instruction, _registers: &dyn RegisterReader) -> u64 {
+        1
+    }
+
+    /// Digests the operands of `i32.sub` instructions with a 16-bit immediate.
+    fn sub_operands(instr: &Instruction, registers: &dyn RegisterReader) -> u64 {
+        match instr {
+            Instruction::I32SubImm16(instr) => u64::from(registers.read_register(instr.reg_in)),
+            _ => 0,
+        }
+    }
+
+    /// Digests nothing.
+    fn zero(_instr: &Instruction, _registers: &dyn RegisterReader) -> u64 {
+        0
+    }
+
+    let mut config = Config::default();
+    config.execution_digest(ExecutionDigest::Custom(count));
+    let short = run(&config, 1);
+    let long = run(&config, 2);
+    assert_ne!(short, INITIAL_SIGNATURE);
+    assert_ne!(short, long);
+    // Custom digests are deterministic.
+    assert_eq!(run(&config, 2), long);
+    config.execution_digest(ExecutionDigest::Custom(zero));
+    let zero = run(&config, 10);
+    config.execution_digest(ExecutionDigest::Custom(sub_operands));
+    assert_ne!(run(&config, 10), zero);
+}
@@ -0,0 +1,122 @@
+//! Tests for sampling the executed Wasm functions via [`Engine::set_sampler`].
+
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+use wasmi::{ir::CompiledFunc, Caller, Config, Engine, Instance, Linker, Module, Store};
+
+/// A Wasm module with a `hot` function looping `n` times and a `cold` function.
+///
+/// The `cold` function calls the imported `env.current` host function.
+const WASM: &str = r#"
+    (module
+        (import "env" "current" (func $current))
+        (func $hot (export "hot") (param $n i32) (result i32)
+            (local $acc i32)
+            (loop $continue
+                (local.set $acc (i32.add (local.get $acc) (local.get $n)))
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br_if $continue (local.get $n))
+            )
+            (local.get $acc)
+        )
+        (func $cold (export "cold")
+            (call $current)
+        )
+    )
+"#;
+
+/// The results of [`Engine::current_func_of`] recorded by the `env.current` host function.
+type Recorded = Arc<Mutex<Vec<Option<CompiledFunc>>>>;
+
+/// Instantiates [`WASM`] with `sampling` and its `env.current` host function.
+///
+/// The host function records the [`CompiledFunc`] of its thread into the returned list.
+fn setup(sampling: bool) -> (Store<()>, Module, Instance, Recorded) {
+    let mut config = Config::default();
+    config.sampling(sampling);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wat::parse_str(WASM).unwrap()[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let current = Recorded::default();
+    let mut linker = <Linker<()>>::new(&engine);
+    let recorded = current.clone();
+    linker
+        .func_wrap("env", "current", move |caller: Caller<()>| {
+            let func = caller.engine().current_func_of(thread::current().id());
+            recorded.lock().unwrap().push(func);
+        })
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, module, instance, current)
+}
+
+#[test]
+fn samples_are_dominated_by_hot_func() {
+    let (mut store, module, instance, _) = setup(true);
+    let engine = store.engine().clone();
+    let hot_func = module.get_compiled_func(1).unwrap();
+    let cold_func = module.get_compiled_func(2).unwrap();
+    let samples = <Arc<Mutex<Vec<CompiledFunc>>>>::default();
+    let recorded = samples.clone();
+    engine.set_sampler(Duration::from_millis(1), move |func| {
+        recorded.lock().unwrap().push(func);
+    });
+    let hot = instance.get_typed_func::<i32, i32>(&store, "hot").unwrap();
+    let cold = instance.get_typed_func::<(), ()>(&store, "cold").unwrap();
+    while samples.lock().unwrap().len() < 50 {
+        cold.call(&mut store, ()).unwrap();
+        hot.call(&mut store, 100_000).unwrap();
+    }
+    engine.clear_sampler();
+    let samples = samples.lock().unwrap().clone();
+    let len_hot = samples.iter().filter(|func| **func == hot_func).count();
+    assert!(samples
+        .iter()
+        .all(|func| *func == hot_func || *func == cold_func));
+    assert!(
+        len_hot * 10 >= samples.len() * 9,
+        "expected samples to be dominated by the hot function: {len_hot} of {}",
+        samples.len(),
+    );
+    let info = engine.func_info(hot_func).unwrap();
+    assert_eq!(info.func_index, Some(1));
+    assert!(info.len_instrs > 0);
+}
+
+#[test]
+fn current_func_is_calling_wasm_func() {
+    let (mut store, module, instance, current) = setup(true);
+    let engine = store.engine().clone();
+    let cold_func = module.get_compiled_func(2).unwrap();
+    let thread = thread::current().id();
+    assert_eq!(engine.current_func_of(thread), None);
+    let cold = instance.get_typed_func::<(), ()>(&store, "cold").unwrap();
+    cold.call(&mut store, ()).unwrap();
+    assert_eq!(current.lock().unwrap()[..], [Some(cold_func)]);
+    // Note: the thread is idle again after returning to the host.
+    assert_eq!(engine.current_func_of(thread), None);
+    assert_eq!(engine.func_info(cold_func).unwrap().func_index, Some(2));
+}
+
+#[test]
+fn no_current_func_without_sampling() {
+    let (mut store, _, instance, current) = setup(false);
+    let cold = instance.get_typed_func::<(), ()>(&store, "cold").unwrap();
+    cold.call(&mut store, ()).unwrap();
+    assert_eq!(current.lock().unwrap()[..], [None]);
+}
+
+#[test]
+#[should_panic = "sampling cannot be enabled together with `Config::deterministic_only`"]
+fn deterministic_only_rejects_sampling() {
+    let mut config = Config::default();
+    config.deterministic_only(true).sampling(true);
+    Engine::new(&config);
+}
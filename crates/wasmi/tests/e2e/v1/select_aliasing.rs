@@ -0,0 +1,198 @@
+//! Tests for `select` instructions whose `result` register aliases one of their inputs.
+
+use wasmi::{
+    build::{IrFunc, ModuleBuilder},
+    core::{ValueType, F64},
+    ir::{Instruction, Register},
+    Engine,
+    FuncType,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    WasmResults,
+};
+
+/// Shorthand for [`Register::from_i16`].
+fn reg(index: i16) -> Register {
+    Register::from_i16(index)
+}
+
+/// Instantiates `module` in a new [`Store`].
+fn instantiate(engine: &Engine, module: &Module) -> (Store<()>, Instance) {
+    let mut store = Store::new(engine, ());
+    let instance = <Linker<()>>::new(engine)
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Builds a function of type `(i32, i32, i32) -> result` from `instrs` and calls it
+/// with `(condition, 10, 20)` for both `condition` values.
+///
+/// Returns the results for `condition` being `1` and `0` respectively.
+fn eval_ir<R>(result: ValueType, instrs: impl IntoIterator<Item = Instruction>) -> (R, R)
+where
+    R: WasmResults,
+{
+    let engine = Engine::default();
+    let mut builder = ModuleBuilder::new(&engine);
+    let ty = builder.push_type(FuncType::new([ValueType::I32; 3], [result]));
+    let func = builder.push_ir_func(ty, IrFunc::new(3, [], instrs));
+    builder.export_func("select", func);
+    let module = builder.finish().unwrap();
+    let (mut store, instance) = instantiate(&engine, &module);
+    let select = instance
+        .get_typed_func::<(i32, i32, i32), R>(&store, "select")
+        .unwrap();
+    let on_true = select.call(&mut store, (1, 10, 20)).unwrap();
+    let on_false = select.call(&mut store, (0, 10, 20)).unwrap();
+    (on_true, on_false)
+}
+
+/// Evaluates `instrs` via [`eval_ir`] for functions returning `i32`.
+fn eval_ir_i32<const N: usize>(instrs: [Instruction; N]) -> (i32, i32) {
+    eval_ir::<i32>(ValueType::I32, instrs)
+}
+
+#[test]
+fn select_result_aliases_lhs() {
+    let instrs = [
+        Instruction::select(reg(1), reg(0), reg(1)),
+        Instruction::register(reg(2)),
+        Instruction::return_reg(reg(1)),
+    ];
+    assert_eq!(eval_ir_i32(instrs), (10, 20));
+}
+
+#[test]
+fn select_result_aliases_rhs() {
+    let instrs = [
+        Instruction::select(reg(2), reg(0), reg(1)),
+        Instruction::register(reg(2)),
+        Instruction::return_reg(reg(2)),
+    ];
+    assert_eq!(eval_ir_i32(instrs), (10, 20));
+}
+
+#[test]
+fn select_result_aliases_condition() {
+    let instrs = [
+        Instruction::select(reg(0), reg(0), reg(1)),
+        Instruction::register(reg(2)),
+        Instruction::return_reg(reg(0)),
+    ];
+    assert_eq!(eval_ir_i32(instrs), (10, 20));
+}
+
+#[test]
+fn select_result_aliases_lhs_with_imm_rhs() {
+    let instrs = [
+        Instruction::select(reg(1), reg(0), reg(1)),
+        Instruction::const32(30),
+        Instruction::return_reg(reg(1)),
+    ];
+    assert_eq!(eval_ir_i32(instrs), (10, 30));
+}
+
+#[test]
+fn select_rev_result_aliases_rhs() {
+    let instrs = [
+        Instruction::select_rev(reg(1), reg(0), reg(1)),
+        Instruction::register(reg(2)),
+        Instruction::return_reg(reg(1)),
+    ];
+    assert_eq!(eval_ir_i32(instrs), (20, 10));
+}
+
+#[test]
+fn select_rev_result_aliases_lhs() {
+    let instrs = [
+        Instruction::select_rev(reg(2), reg(0), reg(1)),
+        Instruction::register(reg(2)),
+        Instruction::return_reg(reg(2)),
+    ];
+    assert_eq!(eval_ir_i32(instrs), (20, 10));
+}
+
+#[test]
+fn select_rev_result_aliases_condition_with_imm_lhs() {
+    let instrs = [
+        Instruction::select_rev(reg(0), reg(0), reg(1)),
+        Instruction::const32(30),
+        Instruction::return_reg(reg(0)),
+    ];
+    assert_eq!(eval_ir_i32(instrs), (30, 10));
+}
+
+#[test]
+fn select_imm_result_aliases_condition() {
+    let instrs = [
+        Instruction::select_imm32(reg(0), 30),
+        Instruction::select_imm32(reg(0), 40),
+        Instruction::return_reg(reg(0)),
+    ];
+    assert_eq!(eval_ir_i32(instrs), (30, 40));
+    let instrs = [
+        Instruction::select_i64imm32(reg(0), 30_i32),
+        Instruction::select_i64imm32(reg(0), -40_i32),
+        Instruction::return_reg(reg(0)),
+    ];
+    assert_eq!(eval_ir::<i64>(ValueType::I64, instrs), (30, -40));
+    let instrs = [
+        Instruction::select_f64imm32(reg(0), 0.5_f32),
+        Instruction::select_f64imm32(reg(0), -1.5_f32),
+        Instruction::return_reg(reg(0)),
+    ];
+    assert_eq!(
+        eval_ir::<F64>(ValueType::F64, instrs),
+        (F64::from(0.5), F64::from(-1.5))
+    );
+}
+
+/// Regression test for `select` results that are written into one of the
+/// locals that are also used as `select` operands.
+#[test]
+fn select_into_operand_local() {
+    let wat = r#"
+        (module
+            (func (export "lhs") (param i32 i32) (result i32)
+                (local.set 1 (select (local.get 1) (i32.const 30) (local.get 0)))
+                (local.get 1)
+            )
+            (func (export "rhs") (param i32 i32) (result i32)
+                (local.set 1 (select (i32.const 30) (local.get 1) (local.get 0)))
+                (local.get 1)
+            )
+            (func (export "condition") (param i32 i32) (result i32)
+                (local.set 0 (select (local.get 1) (i32.const 30) (local.get 0)))
+                (local.get 0)
+            )
+            (func (export "all") (param i32 i32) (result i32)
+                (local.set 0 (select (local.get 0) (local.get 1) (local.get 0)))
+                (local.get 0)
+            )
+        )
+    "#;
+    let engine = Engine::default();
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let (mut store, instance) = instantiate(&engine, &module);
+    let mut call = |name: &str, condition: i32, value: i32| {
+        instance
+            .get_typed_func::<(i32, i32), i32>(&store, name)
+            .unwrap()
+            .call(&mut store, (condition, value))
+            .unwrap()
+    };
+    assert_eq!(call("lhs", 1, 10), 10);
+    assert_eq!(call("lhs", 0, 10), 30);
+    assert_eq!(call("rhs", 1, 10), 30);
+    assert_eq!(call("rhs", 0, 10), 10);
+    assert_eq!(call("condition", 1, 10), 10);
+    assert_eq!(call("condition", 0, 10), 30);
+    assert_eq!(call("all", 2, 10), 2);
+    assert_eq!(call("all", 0, 10), 10);
+}
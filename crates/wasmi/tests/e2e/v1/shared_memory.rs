@@ -0,0 +1,222 @@
+//! Tests for a host created linear memory that is imported by multiple instances.
+
+use wasmi::{
+    core::{Pages, TrapCode, ValueType},
+    Engine,
+    Instance,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+    Table,
+    TableType,
+    Value,
+};
+
+/// The size of a linear memory page in bytes.
+const PAGE_SIZE: u32 = 0x1_0000;
+
+/// A Wasm module that reads from the shared memory.
+///
+/// - `load` loads an `i32` at its parameter.
+/// - `grow` grows the shared memory by one page.
+/// - `indirect_grow_and_load` calls the function at index 0 of the shared table
+///   in between two loads where the second load is at its parameter.
+const WAT_B: &str = r#"
+    (module
+        (import "env" "mem" (memory 1))
+        (import "env" "table" (table 1 funcref))
+        (func (export "load") (param i32) (result i32)
+            (i32.load (local.get 0))
+        )
+        (func (export "grow") (result i32)
+            (memory.grow (i32.const 1))
+        )
+        (func (export "indirect_grow_and_load") (param i32) (result i32)
+            (drop (i32.load (i32.const 0)))
+            (drop (call_indirect (result i32) (i32.const 0)))
+            (i32.load (local.get 0))
+        )
+    )
+"#;
+
+/// A Wasm module that writes to the shared memory.
+///
+/// - `store` stores an `i32` value at an address.
+/// - `grow_via_b_and_store` calls `grow` of [`WAT_B`] in between two stores
+///   where the second store is at its parameter.
+/// - Its own `grow` function is put into the shared table for [`WAT_B`] to call.
+const WAT_A: &str = r#"
+    (module
+        (import "env" "mem" (memory 1))
+        (import "env" "table" (table 1 funcref))
+        (import "b" "grow" (func $b_grow (result i32)))
+        (elem (i32.const 0) $grow)
+        (func $grow (result i32)
+            (memory.grow (i32.const 1))
+        )
+        (func (export "store") (param i32 i32)
+            (i32.store (local.get 0) (local.get 1))
+        )
+        (func (export "grow_via_b_and_store") (param i32 i32)
+            (i32.store (i32.const 0) (i32.const 0))
+            (drop (call $b_grow))
+            (i32.store (local.get 0) (local.get 1))
+        )
+    )
+"#;
+
+/// The store with the host created [`Memory`] and the two instances sharing it.
+struct Test {
+    store: Store<()>,
+    memory: Memory,
+    a: Instance,
+    b: Instance,
+}
+
+impl Test {
+    /// Creates the shared [`Memory`] and instantiates [`WAT_B`] and [`WAT_A`] over it.
+    fn new() -> Self {
+        let engine = Engine::default();
+        let mut store = Store::new(&engine, ());
+        let memory = Memory::new(&mut store, MemoryType::new(1, Some(4)).unwrap()).unwrap();
+        let table = Table::new(
+            &mut store,
+            TableType::new(ValueType::FuncRef, 1, None),
+            Value::default(ValueType::FuncRef),
+        )
+        .unwrap();
+        let mut linker = <Linker<()>>::new(&engine);
+        linker.define("env", "mem", memory).unwrap();
+        linker.define("env", "table", table).unwrap();
+        let b = instantiate(&mut store, &linker, WAT_B);
+        let b_grow = b.get_func(&store, "grow").unwrap();
+        linker.define("b", "grow", b_grow).unwrap();
+        let a = instantiate(&mut store, &linker, WAT_A);
+        Self {
+            store,
+            memory,
+            a,
+            b,
+        }
+    }
+
+    /// Calls `store` of [`WAT_A`].
+    fn a_store(&mut self, address: u32, value: i32) -> Result<(), wasmi::Error> {
+        self.a
+            .get_typed_func::<(u32, i32), ()>(&self.store, "store")
+            .unwrap()
+            .call(&mut self.store, (address, value))
+    }
+
+    /// Calls `load` of [`WAT_B`].
+    fn b_load(&mut self, address: u32) -> Result<i32, wasmi::Error> {
+        self.b
+            .get_typed_func::<u32, i32>(&self.store, "load")
+            .unwrap()
+            .call(&mut self.store, address)
+    }
+}
+
+/// Instantiates the Wasm module in WebAssembly text format `wat` using `linker`.
+fn instantiate(store: &mut Store<()>, linker: &Linker<()>, wat: &str) -> Instance {
+    let module = Module::new(store.engine(), &wat::parse_str(wat).unwrap()[..]).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(store)
+        .unwrap()
+}
+
+#[test]
+fn a_writes_b_reads() {
+    let mut test = Test::new();
+    test.a_store(8, 42).unwrap();
+    assert_eq!(test.b_load(8).unwrap(), 42);
+    assert_eq!(test.memory.data(&test.store)[8], 42);
+    // Writes of the host are visible to both instances as well.
+    test.memory.data_mut(&mut test.store)[8] = 7;
+    assert_eq!(test.b_load(8).unwrap(), 7);
+}
+
+#[test]
+fn grow_via_b_is_visible_to_a() {
+    let mut test = Test::new();
+    let address = PAGE_SIZE + 4;
+    assert_eq!(
+        test.a_store(address, 1).unwrap_err().as_trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+    // Instance `a` accesses the memory before and after `b` grows it.
+    test.a
+        .get_typed_func::<(u32, i32), ()>(&test.store, "grow_via_b_and_store")
+        .unwrap()
+        .call(&mut test.store, (address, 42))
+        .unwrap();
+    assert_eq!(test.b_load(address).unwrap(), 42);
+    assert_eq!(
+        test.memory.current_pages(&test.store),
+        Pages::new(2).unwrap()
+    );
+}
+
+#[test]
+fn grow_via_a_is_visible_to_b() {
+    let mut test = Test::new();
+    let address = PAGE_SIZE + 4;
+    // Instance `b` accesses the memory before and after `a` grows it.
+    let value = test
+        .b
+        .get_typed_func::<u32, i32>(&test.store, "indirect_grow_and_load")
+        .unwrap()
+        .call(&mut test.store, address)
+        .unwrap();
+    assert_eq!(value, 0);
+    test.a_store(address, 42).unwrap();
+    assert_eq!(test.b_load(address).unwrap(), 42);
+}
+
+#[test]
+fn grow_via_b_then_a_writes() {
+    let mut test = Test::new();
+    let address = 2 * PAGE_SIZE - 4;
+    test.a_store(0, 1).unwrap();
+    assert_eq!(test.b_load(0).unwrap(), 1);
+    let grow = test
+        .b
+        .get_typed_func::<(), i32>(&test.store, "grow")
+        .unwrap();
+    assert_eq!(grow.call(&mut test.store, ()).unwrap(), 1);
+    test.a_store(address, 42).unwrap();
+    assert_eq!(test.b_load(address).unwrap(), 42);
+    // Growth by the host is visible to both instances.
+    test.memory
+        .grow(&mut test.store, Pages::new(1).unwrap())
+        .unwrap();
+    test.a_store(address + PAGE_SIZE, 7).unwrap();
+    assert_eq!(test.b_load(address + PAGE_SIZE).unwrap(), 7);
+}
+
+#[test]
+fn ty_reflects_growth() {
+    let mut test = Test::new();
+    let ty = test.memory.ty(&test.store);
+    assert_eq!(ty.initial_pages(), Pages::new(1).unwrap());
+    assert_eq!(ty.maximum_pages(), Some(Pages::new(4).unwrap()));
+    test.memory
+        .grow(&mut test.store, Pages::new(2).unwrap())
+        .unwrap();
+    let ty = test.memory.ty(&test.store);
+    assert_eq!(ty.initial_pages(), Pages::new(3).unwrap());
+    assert_eq!(ty.maximum_pages(), Some(Pages::new(4).unwrap()));
+    // The grown memory satisfies imports that require its current size.
+    let mut linker = <Linker<()>>::new(test.store.engine());
+    linker.define("env", "mem", test.memory).unwrap();
+    let module =
+        |wat: &str| Module::new(test.store.engine(), &wat::parse_str(wat).unwrap()[..]).unwrap();
+    let fits = module(r#"(module (import "env" "mem" (memory 3 4)))"#);
+    let too_large = module(r#"(module (import "env" "mem" (memory 4 4)))"#);
+    linker.instantiate(&mut test.store, &fits).unwrap();
+    linker.instantiate(&mut test.store, &too_large).unwrap_err();
+}
@@ -0,0 +1,290 @@
+//! Tests to check that store snapshots restore the full store state.
+
+use assert_matches::assert_matches;
+use wasmi::{
+    core::TrapCode,
+    errors::{ErrorKind, SnapshotError},
+    Config,
+    Engine,
+    ExternRef,
+    Func,
+    FuncRef,
+    Instance,
+    Linker,
+    Module,
+    Store,
+    StoreSnapshot,
+    Value,
+};
+
+/// A Wasm module with exports that mutate all kinds of store state.
+const WAT: &str = r#"
+    (module
+        (type $rt (func (result i32)))
+        (memory (export "memory") 1 3)
+        (global $g (export "global") (mut i32) (i32.const 0))
+        (table $t (export "funcs") 2 funcref)
+        (table $e (export "externs") 1 externref)
+        (data $d "\01\02\03\04")
+        (elem $el func $f1 $f2)
+        (func $f1 (result i32) (i32.const 1))
+        (func $f2 (result i32) (i32.const 2))
+        (func (export "step") (result i32)
+            (global.set $g (i32.add (global.get $g) (i32.const 1)))
+            (i32.store (i32.const 0) (global.get $g))
+            (global.get $g)
+        )
+        (func (export "load") (result i32)
+            (i32.load (i32.const 0))
+        )
+        (func (export "load_data") (result i32)
+            (i32.load (i32.const 8))
+        )
+        (func (export "grow") (result i32)
+            (memory.grow (i32.const 1))
+        )
+        (func (export "init_memory") (result i32)
+            (memory.init $d (i32.const 8) (i32.const 0) (i32.const 4))
+            (i32.const 0)
+        )
+        (func (export "drop_data") (result i32)
+            (data.drop $d)
+            (i32.const 0)
+        )
+        (func (export "init_table") (result i32)
+            (table.init $t $el (i32.const 0) (i32.const 0) (i32.const 2))
+            (i32.const 0)
+        )
+        (func (export "drop_elem") (result i32)
+            (elem.drop $el)
+            (i32.const 0)
+        )
+        (func (export "call_0") (result i32)
+            (call_indirect $t (type $rt) (i32.const 0))
+        )
+        (func (export "call_1") (result i32)
+            (call_indirect $t (type $rt) (i32.const 1))
+        )
+    )
+"#;
+
+/// Instantiates the [`WAT`] test module in a new [`Store`] for `engine`.
+fn setup(engine: &Engine) -> (Store<()>, Module, Instance) {
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(engine, &wasm[..]).unwrap();
+    let mut store = Store::new(engine, ());
+    let instance = instantiate(&mut store, &module);
+    (store, module, instance)
+}
+
+/// Instantiates `module` in `store`.
+fn instantiate(store: &mut Store<()>, module: &Module) -> Instance {
+    <Linker<()>>::new(store.engine())
+        .instantiate(&mut *store, module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Calls the exported `() -> i32` function `name`.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str) -> Result<i32, TrapCode> {
+    instance
+        .get_typed_func::<(), i32>(&*store, name)
+        .unwrap()
+        .call(store, ())
+        .map_err(|error| error.as_trap_code().unwrap())
+}
+
+/// Runs a sequence of calls that observe and mutate the store state and returns their results.
+fn run(store: &mut Store<()>, instance: &Instance) -> Vec<Result<i32, TrapCode>> {
+    [
+        "step",
+        "load",
+        "grow",
+        "init_memory",
+        "load_data",
+        "drop_data",
+        "init_memory",
+        "call_0",
+        "call_1",
+        "drop_elem",
+        "init_table",
+        "step",
+        "grow",
+        "grow",
+    ]
+    .into_iter()
+    .map(|name| call(store, instance, name))
+    .collect()
+}
+
+/// Returns an [`ExternRef`] for `store` wrapping `value`.
+fn externref(store: &mut Store<()>, value: u64) -> ExternRef {
+    ExternRef::new::<u64>(&mut *store, value)
+}
+
+/// Maps `externref` to its wrapped `u64` value.
+fn externref_to_id(store: &Store<()>, externref: ExternRef) -> u64 {
+    *externref
+        .data(store)
+        .unwrap()
+        .downcast_ref::<u64>()
+        .unwrap()
+}
+
+#[test]
+fn snapshot_restore_replays_identically() {
+    let (mut store, _module, instance) = setup(&Engine::default());
+    assert_eq!(call(&mut store, &instance, "step"), Ok(1));
+    assert_eq!(call(&mut store, &instance, "init_table"), Ok(0));
+    let snapshot = store.snapshot(externref_to_id).unwrap();
+    let expected = run(&mut store, &instance);
+    assert_eq!(
+        expected,
+        [
+            Ok(2),
+            Ok(2),
+            Ok(1),
+            Ok(0),
+            Ok(0x04030201),
+            Ok(0),
+            Err(TrapCode::MemoryOutOfBounds),
+            Ok(1),
+            Ok(2),
+            Ok(0),
+            Err(TrapCode::TableOutOfBounds),
+            Ok(3),
+            Ok(2),
+            Ok(-1),
+        ]
+    );
+    store.restore(&snapshot, |_, _| unreachable!()).unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    assert_eq!(u32::from(memory.current_pages(&store)), 1);
+    assert_eq!(run(&mut store, &instance), expected);
+    // A snapshot can be restored multiple times.
+    store.restore(&snapshot, |_, _| unreachable!()).unwrap();
+    assert_eq!(run(&mut store, &instance), expected);
+}
+
+#[test]
+fn snapshot_restore_funcrefs_and_externrefs() {
+    let (mut store, _module, instance) = setup(&Engine::default());
+    assert_eq!(call(&mut store, &instance, "init_table"), Ok(0));
+    let funcs = instance.get_table(&store, "funcs").unwrap();
+    let externs = instance.get_table(&store, "externs").unwrap();
+    let value = externref(&mut store, 42);
+    externs.set(&mut store, 0, Value::from(value)).unwrap();
+    let snapshot = store.snapshot(externref_to_id).unwrap();
+    funcs
+        .set(&mut store, 0, Value::from(FuncRef::null()))
+        .unwrap();
+    externs
+        .set(&mut store, 0, Value::from(ExternRef::null()))
+        .unwrap();
+    assert_eq!(
+        call(&mut store, &instance, "call_0"),
+        Err(TrapCode::IndirectCallToNull)
+    );
+    store.restore(&snapshot, externref).unwrap();
+    assert_eq!(call(&mut store, &instance, "call_0"), Ok(1));
+    let Value::ExternRef(restored) = externs.get(&store, 0).unwrap() else {
+        panic!("expected an externref table element")
+    };
+    assert_eq!(externref_to_id(&store, restored), 42);
+}
+
+#[test]
+fn snapshot_restore_fuel() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let (mut store, _module, instance) = setup(&Engine::new(&config));
+    store.add_fuel(1_000).unwrap();
+    let snapshot = store.snapshot(externref_to_id).unwrap();
+    let consumed = store.fuel_consumed();
+    assert_eq!(call(&mut store, &instance, "step"), Ok(1));
+    assert_ne!(store.fuel_consumed(), consumed);
+    store.restore(&snapshot, externref).unwrap();
+    assert_eq!(store.fuel_consumed(), consumed);
+}
+
+#[test]
+fn snapshot_restore_mismatching_topology() {
+    let (mut store, module, instance) = setup(&Engine::default());
+    let snapshot = store.snapshot(externref_to_id).unwrap();
+    assert_eq!(call(&mut store, &instance, "step"), Ok(1));
+    instantiate(&mut store, &module);
+    let error = store.restore(&snapshot, externref).unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Snapshot(SnapshotError::MismatchingTopology)
+    );
+    // The store remains unchanged upon failed restoration.
+    assert_eq!(call(&mut store, &instance, "load"), Ok(1));
+}
+
+#[test]
+fn snapshot_unresolvable_funcref() {
+    let (mut store, _module, instance) = setup(&Engine::default());
+    let host = Func::wrap(&mut store, || 42_i32);
+    let funcs = instance.get_table(&store, "funcs").unwrap();
+    funcs
+        .set(&mut store, 0, Value::from(FuncRef::new(host)))
+        .unwrap();
+    let error = store.snapshot(externref_to_id).unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Snapshot(SnapshotError::UnresolvableFuncRef)
+    );
+}
+
+#[test]
+fn snapshot_bytes_round_trip() {
+    let (mut store, module, instance) = setup(&Engine::default());
+    assert_eq!(call(&mut store, &instance, "step"), Ok(1));
+    assert_eq!(call(&mut store, &instance, "init_table"), Ok(0));
+    let externs = instance.get_table(&store, "externs").unwrap();
+    let value = externref(&mut store, 42);
+    externs.set(&mut store, 0, Value::from(value)).unwrap();
+    let bytes = store.snapshot(externref_to_id).unwrap().to_bytes();
+    let snapshot = StoreSnapshot::from_bytes(&bytes).unwrap();
+    assert_eq!(snapshot.to_bytes(), bytes);
+    let expected = run(&mut store, &instance);
+    store.restore(&snapshot, externref).unwrap();
+    assert_eq!(run(&mut store, &instance), expected);
+    // The decoded snapshot can also be restored into another store with the same entities.
+    let mut other = Store::new(store.engine(), ());
+    let other_instance = instantiate(&mut other, &module);
+    other.restore(&snapshot, externref).unwrap();
+    let externs = other_instance.get_table(&other, "externs").unwrap();
+    let Value::ExternRef(restored) = externs.get(&other, 0).unwrap() else {
+        panic!("expected an externref table element")
+    };
+    assert_eq!(externref_to_id(&other, restored), 42);
+    assert_eq!(run(&mut other, &other_instance), expected);
+}
+
+#[test]
+fn snapshot_from_malformed_bytes() {
+    let (store, _module, _instance) = setup(&Engine::default());
+    let bytes = store.snapshot(externref_to_id).unwrap().to_bytes();
+    let mut trailing = bytes.clone();
+    trailing.push(0x00);
+    let mut version = bytes.clone();
+    version[4] += 1;
+    let malformed = [
+        &[][..],
+        &bytes[..4],
+        &bytes[..bytes.len() / 2],
+        &bytes[..bytes.len() - 1],
+        &trailing[..],
+        &version[..],
+    ];
+    for bytes in malformed {
+        let error = StoreSnapshot::from_bytes(bytes).unwrap_err();
+        assert_matches!(
+            error.kind(),
+            ErrorKind::Snapshot(SnapshotError::MalformedBytes)
+        );
+    }
+}
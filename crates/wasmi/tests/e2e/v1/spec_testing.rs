@@ -0,0 +1,225 @@
+//! Tests for the `.wast` runner of the `wasmi::spec` module.
+
+use std::path::PathBuf;
+use wasmi::{
+    spec::{define_spectest, run_directory, run_file, DirectiveKind, SpecStatus},
+    Config,
+    Engine,
+    Linker,
+    Module,
+    Store,
+};
+
+/// Creates an empty directory called `name` and writes all `files` into it.
+fn write_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join("spec_testing")
+        .join(name);
+    _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    for (file, contents) in files {
+        std::fs::write(dir.join(file), contents).unwrap();
+    }
+    dir
+}
+
+const LINKING: &str = r#"
+(module $Mf
+    (func (export "call") (result i32) (call $g))
+    (func $g (result i32) (i32.const 2))
+)
+(register "Mf" $Mf)
+
+(module
+    (import "Mf" "call" (func $f (result i32)))
+    (import "spectest" "global_i32" (global $g i32))
+    (import "spectest" "print_i32" (func $print (param i32)))
+    (import "spectest" "memory" (memory 1))
+    (func (export "sum") (result i32)
+        (call $print (global.get $g))
+        (i32.add (call $f) (global.get $g))
+    )
+    (func (export "div") (param i32 i32) (result i32)
+        (i32.div_s (local.get 0) (local.get 1))
+    )
+    (func (export "nan") (result f32 f64)
+        (f32.div (f32.const 0) (f32.const 0))
+        (f64.sqrt (f64.const -1))
+    )
+    (func $loop (export "loop") (call $loop))
+)
+(assert_return (invoke "sum") (i32.const 668))
+(assert_return (invoke $Mf "call") (i32.const 2))
+(assert_return (invoke "nan") (f32.const nan:arithmetic) (f64.const nan:arithmetic))
+(assert_trap (invoke "div" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_exhaustion (invoke "loop") "call stack exhausted")
+(assert_invalid
+    (module (func (result i32) (i64.const 0)))
+    "type mismatch"
+)
+(assert_malformed
+    (module quote "(func (i32.const))")
+    "unexpected token"
+)
+(assert_unlinkable
+    (module (import "Mf" "missing" (func)))
+    "unknown import"
+)
+"#;
+
+#[test]
+fn run_directory_passes() {
+    let dir = write_dir(
+        "passes",
+        &[("linking.wast", LINKING), ("ignored.txt", "(module")],
+    );
+    for consume_fuel in [false, true] {
+        let mut config = Config::default();
+        config.consume_fuel(consume_fuel);
+        let report = run_directory(&config, &dir);
+        assert!(report.is_success(), "{report}");
+        assert_eq!(report.passed().count(), 11);
+        assert_eq!(report.skipped().count(), 0);
+        let kinds = report
+            .outcomes()
+            .iter()
+            .map(|outcome| outcome.kind())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                DirectiveKind::Module,
+                DirectiveKind::Register,
+                DirectiveKind::Module,
+                DirectiveKind::AssertReturn,
+                DirectiveKind::AssertReturn,
+                DirectiveKind::AssertReturn,
+                DirectiveKind::AssertTrap,
+                DirectiveKind::AssertExhaustion,
+                DirectiveKind::AssertInvalid,
+                DirectiveKind::AssertMalformed,
+                DirectiveKind::AssertUnlinkable,
+            ]
+        );
+    }
+}
+
+#[test]
+fn failures_have_locations() {
+    let dir = write_dir(
+        "failures",
+        &[
+            (
+                "a.wast",
+                r#"(module
+    (func (export "f") (result i32) (i32.const 1))
+    (func (export "nan") (result f32)
+        (f32.reinterpret_i32 (i32.const 0x7FA0_0000))
+    )
+)
+(assert_return (invoke "f") (i32.const 1))
+(assert_return (invoke "f") (i32.const 2))
+(assert_trap (invoke "f") "unreachable")
+(assert_return (invoke "missing"))
+(assert_return (invoke "nan") (f32.const nan:canonical))
+(assert_invalid (module (func)) "type mismatch")
+"#,
+            ),
+            ("b.wast", "(module (func)\n  (assert_return"),
+        ],
+    );
+    let report = run_directory(&Config::default(), &dir);
+    assert!(!report.is_success());
+    assert_eq!(report.passed().count(), 2);
+    let failed = report
+        .failed()
+        .map(|outcome| {
+            let location = outcome.location();
+            let file = location.file().file_name().unwrap().to_str().unwrap();
+            (file, location.line(), location.column(), outcome.kind())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        failed,
+        [
+            ("a.wast", 8, 2, DirectiveKind::AssertReturn),
+            ("a.wast", 9, 2, DirectiveKind::AssertTrap),
+            ("a.wast", 10, 2, DirectiveKind::AssertReturn),
+            ("a.wast", 11, 2, DirectiveKind::AssertReturn),
+            ("a.wast", 12, 2, DirectiveKind::AssertInvalid),
+            ("b.wast", 2, 4, DirectiveKind::File),
+        ]
+    );
+    let message = report.failed().next().unwrap().failure().unwrap();
+    assert!(message.contains("I32(2)"), "{message}");
+    assert!(report
+        .failed()
+        .nth(2)
+        .unwrap()
+        .failure()
+        .unwrap()
+        .contains("missing exported function `missing`"));
+}
+
+#[test]
+fn unsupported_directives_are_skipped() {
+    let dir = write_dir(
+        "skipped",
+        &[(
+            "simd.wast",
+            r#"(module
+    (func (export "f") (param i32))
+    (func (export "g") (result i32) (i32.const 0))
+)
+(invoke "f" (v128.const i32x4 0 0 0 0))
+(assert_return (invoke "g") (v128.const i32x4 0 0 0 0))
+(component)
+"#,
+        )],
+    );
+    let report = run_file(&Config::default(), &dir.join("simd.wast"));
+    assert!(report.is_success(), "{report}");
+    assert_eq!(report.passed().count(), 1);
+    assert_eq!(report.skipped().count(), 3);
+}
+
+#[test]
+fn missing_directory() {
+    let dir = write_dir("missing", &[]).join("does-not-exist");
+    let report = run_directory(&Config::default(), &dir);
+    assert_eq!(report.outcomes().len(), 1);
+    assert_eq!(report.outcomes()[0].kind(), DirectiveKind::File);
+    assert!(matches!(
+        report.outcomes()[0].status(),
+        SpecStatus::Failed(_)
+    ));
+}
+
+#[test]
+fn define_spectest_in_user_store() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "spectest" "global_i64" (global i64))
+            (import "spectest" "table" (table 10 funcref))
+            (import "spectest" "print_f64_f64" (func (param f64 f64)))
+            (func (export "get") (result i64) (global.get 0))
+        )
+        "#,
+    )
+    .unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, 42_u32);
+    let mut linker = <Linker<u32>>::new(&engine);
+    define_spectest(&mut linker, &mut store).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let get = instance.get_typed_func::<(), i64>(&store, "get").unwrap();
+    assert_eq!(get.call(&mut store, ()).unwrap(), 666);
+    // Defining the `spectest` module twice is an error.
+    assert!(define_spectest(&mut linker, &mut store).is_err());
+}
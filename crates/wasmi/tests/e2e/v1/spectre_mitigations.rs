@@ -0,0 +1,296 @@
+//! Tests asserting that [`Config::spectre_mitigations`] does not alter the behavior of Wasm executions.
+
+use core::fmt::Write as _;
+use wasmi::{Caller, Config, Engine, Instance, Linker, Module, Store, Value};
+
+/// The size of the default linear memory of the test module in bytes.
+const MEMORY_SIZE: u32 = 65536;
+
+/// The Wasm load operators with their access width in bytes and result type.
+const LOADS: &[(&str, u32, &str)] = &[
+    ("i32.load", 4, "i32"),
+    ("i64.load", 8, "i64"),
+    ("f32.load", 4, "f32"),
+    ("f64.load", 8, "f64"),
+    ("i32.load8_s", 1, "i32"),
+    ("i32.load8_u", 1, "i32"),
+    ("i32.load16_s", 2, "i32"),
+    ("i32.load16_u", 2, "i32"),
+    ("i64.load8_s", 1, "i64"),
+    ("i64.load8_u", 1, "i64"),
+    ("i64.load16_s", 2, "i64"),
+    ("i64.load16_u", 2, "i64"),
+    ("i64.load32_s", 4, "i64"),
+    ("i64.load32_u", 4, "i64"),
+];
+
+/// The Wasm store operators with their access width in bytes and value type.
+const STORES: &[(&str, u32, &str)] = &[
+    ("i32.store", 4, "i32"),
+    ("i64.store", 8, "i64"),
+    ("f32.store", 4, "f32"),
+    ("f64.store", 8, "f64"),
+    ("i32.store8", 1, "i32"),
+    ("i32.store16", 2, "i32"),
+    ("i64.store8", 1, "i64"),
+    ("i64.store16", 2, "i64"),
+    ("i64.store32", 4, "i64"),
+];
+
+/// Returns the Wasm module under test.
+///
+/// For every load and store operator it exports functions with
+///
+/// - a dynamic address: `"{op}"`
+/// - a dynamic address and a 32-bit offset: `"{op}/offset"`
+/// - constant in bounds and out of bounds addresses: `"{op}/at/in"` and `"{op}/at/out"`
+///
+/// It also exports functions accessing the table with a dynamic index.
+fn wasm() -> Vec<u8> {
+    let mut wat = String::from(
+        r#"
+        (module
+            (type $f (func (result i32)))
+            (memory (export "memory") 1)
+            (table 2 funcref)
+            (elem (i32.const 0) $f1)
+            (elem declare func $f2)
+            (func $f1 (result i32) (i32.const 1))
+            (func $f2 (result i32) (i32.const 2))
+            (func (export "table.get") (param i32) (result funcref)
+                (table.get (local.get 0))
+            )
+            (func (export "table.set") (param i32)
+                (table.set (local.get 0) (ref.func $f2))
+            )
+            (func (export "call_indirect") (param i32) (result i32)
+                (call_indirect (type $f) (local.get 0))
+            )
+            (func (export "memory.grow") (param i32) (result i32)
+                (memory.grow (local.get 0))
+            )
+        "#,
+    );
+    let offset = 0x1_0000;
+    for &(op, width, ty) in LOADS {
+        let in_bounds = MEMORY_SIZE - width;
+        let out_of_bounds = in_bounds + 1;
+        writeln!(
+            wat,
+            r#"
+            (func (export "{op}") (param i32) (result {ty}) ({op} (local.get 0)))
+            (func (export "{op}/offset") (param i32) (result {ty}) ({op} offset={offset} (local.get 0)))
+            (func (export "{op}/at/in") (param i32) (result {ty}) ({op} (i32.const {in_bounds})))
+            (func (export "{op}/at/out") (param i32) (result {ty}) ({op} (i32.const {out_of_bounds})))
+            "#
+        )
+        .unwrap();
+    }
+    for &(op, width, ty) in STORES {
+        let in_bounds = MEMORY_SIZE - width;
+        let out_of_bounds = in_bounds + 1;
+        writeln!(
+            wat,
+            r#"
+            (func (export "{op}") (param i32 {ty}) ({op} (local.get 0) (local.get 1)))
+            (func (export "{op}/offset") (param i32 {ty}) ({op} offset={offset} (local.get 0) (local.get 1)))
+            (func (export "{op}/at/in") (param i32 {ty}) ({op} (i32.const {in_bounds}) (local.get 1)))
+            (func (export "{op}/at/out") (param i32 {ty}) ({op} (i32.const {out_of_bounds}) (local.get 1)))
+            "#
+        )
+        .unwrap();
+    }
+    wat.push(')');
+    wat::parse_str(wat).unwrap()
+}
+
+/// Instantiates the Wasm module under test with or without `mitigations`.
+fn setup(mitigations: bool) -> (Store<()>, Instance) {
+    let mut config = Config::default();
+    config.spectre_mitigations(mitigations);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm()[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    for (n, byte) in memory.data_mut(&mut store).iter_mut().enumerate() {
+        *byte = (n % 251) as u8;
+    }
+    (store, instance)
+}
+
+/// Returns the value of type `ty` used as input for store operators.
+fn value(ty: &str) -> Value {
+    match ty {
+        "i32" => Value::I32(0x7654_3210),
+        "i64" => Value::I64(0x7654_3210_FEDC_BA98),
+        "f32" => Value::F32(1.5.into()),
+        "f64" => Value::F64(2.5.into()),
+        _ => unreachable!("unexpected value type: {ty}"),
+    }
+}
+
+/// Calls the exported function `name` with `params` and returns a textual representation of its outcome.
+fn call(store: &mut Store<()>, instance: &Instance, name: &str, params: &[Value]) -> String {
+    let func = instance.get_func(&*store, name).unwrap();
+    let ty = func.ty(&*store);
+    let mut results = ty
+        .results()
+        .iter()
+        .copied()
+        .map(Value::default)
+        .collect::<Vec<_>>();
+    match func.call(&mut *store, params, &mut results) {
+        Ok(()) => match results.first() {
+            Some(Value::FuncRef(funcref)) => format!("funcref(null: {})", funcref.is_null()),
+            _ => format!("{results:?}"),
+        },
+        Err(error) => format!("trap: {:?}", error.as_trap_code()),
+    }
+}
+
+/// The addresses used for all memory accesses with a dynamic address.
+fn addresses(width: u32) -> [u32; 6] {
+    [
+        0,
+        1,
+        MEMORY_SIZE - width,
+        MEMORY_SIZE - width + 1,
+        MEMORY_SIZE,
+        u32::MAX,
+    ]
+}
+
+/// Runs all memory and table accesses and returns a textual representation of their outcomes.
+fn run_accesses(mitigations: bool) -> Vec<String> {
+    let (mut store, instance) = setup(mitigations);
+    let mut outcomes = Vec::new();
+    for &(op, width, _) in LOADS {
+        for address in addresses(width) {
+            let params = [Value::I32(address as i32)];
+            outcomes.push(call(&mut store, &instance, op, &params));
+            let offset = format!("{op}/offset");
+            outcomes.push(call(&mut store, &instance, &offset, &params));
+        }
+        for at in ["at/in", "at/out"] {
+            let name = format!("{op}/{at}");
+            outcomes.push(call(&mut store, &instance, &name, &[Value::I32(0)]));
+        }
+    }
+    for &(op, width, ty) in STORES {
+        for address in addresses(width) {
+            let params = [Value::I32(address as i32), value(ty)];
+            outcomes.push(call(&mut store, &instance, op, &params));
+            let offset = format!("{op}/offset");
+            outcomes.push(call(&mut store, &instance, &offset, &params));
+        }
+        for at in ["at/in", "at/out"] {
+            let name = format!("{op}/{at}");
+            outcomes.push(call(
+                &mut store,
+                &instance,
+                &name,
+                &[Value::I32(0), value(ty)],
+            ));
+        }
+    }
+    let memory = instance.get_memory(&store, "memory").unwrap();
+    outcomes.push(format!("{:?}", memory.data(&store)));
+    for index in [0, 1, 2, u32::MAX] {
+        let params = [Value::I32(index as i32)];
+        outcomes.push(call(&mut store, &instance, "call_indirect", &params));
+        outcomes.push(call(&mut store, &instance, "table.get", &params));
+        outcomes.push(call(&mut store, &instance, "table.set", &params));
+        outcomes.push(call(&mut store, &instance, "call_indirect", &params));
+    }
+    outcomes
+}
+
+#[test]
+fn mitigations_preserve_behavior() {
+    let plain = run_accesses(false);
+    let mitigated = run_accesses(true);
+    assert_eq!(plain.len(), mitigated.len());
+    for (plain, mitigated) in plain.iter().zip(&mitigated) {
+        assert_eq!(plain, mitigated);
+    }
+    // Control: the tests actually trigger both successful and trapping accesses.
+    assert!(mitigated
+        .iter()
+        .any(|outcome| outcome.contains("MemoryOutOfBounds")));
+    assert!(mitigated
+        .iter()
+        .any(|outcome| outcome.contains("TableOutOfBounds")));
+    assert!(mitigated.iter().any(|outcome| outcome.starts_with('[')));
+}
+
+#[test]
+fn mitigations_respect_memory_growth() {
+    let (mut store, instance) = setup(true);
+    let address = [Value::I32(MEMORY_SIZE as i32)];
+    assert_eq!(
+        call(&mut store, &instance, "i64.load", &address),
+        "trap: Some(MemoryOutOfBounds)"
+    );
+    assert_eq!(
+        call(&mut store, &instance, "memory.grow", &[Value::I32(1)]),
+        "[I32(1)]"
+    );
+    assert_eq!(
+        call(&mut store, &instance, "i64.load", &address),
+        "[I64(0)]"
+    );
+}
+
+#[test]
+fn entropy_source() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut bytes = [0xFF_u8; 4];
+    // No entropy source is installed by default.
+    assert!(!store.fill_entropy(&mut bytes));
+    assert_eq!(bytes, [0xFF; 4]);
+    // A seeded entropy source yields reproducible bytes.
+    let mut seed = 0_u8;
+    store.set_entropy_source(move |bytes| {
+        for byte in bytes {
+            seed = seed.wrapping_add(1);
+            *byte = seed;
+        }
+    });
+    assert!(store.fill_entropy(&mut bytes));
+    assert_eq!(bytes, [1, 2, 3, 4]);
+    // Host functions draw from the same entropy source.
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "random", |mut caller: Caller<()>| -> i32 {
+            let mut bytes = [0_u8; 4];
+            assert!(caller.fill_entropy(&mut bytes));
+            i32::from_le_bytes(bytes)
+        })
+        .unwrap();
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (import "env" "random" (func $random (result i32)))
+            (export "random" (func $random))
+        )
+        "#,
+    )
+    .unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let random = instance
+        .get_typed_func::<(), i32>(&store, "random")
+        .unwrap();
+    let expected = i32::from_le_bytes([5, 6, 7, 8]);
+    assert_eq!(random.call(&mut store, ()).unwrap(), expected);
+}
@@ -0,0 +1,151 @@
+//! Tests to check that the peak [`StackUsage`] of Wasm executions is tracked by the [`Store`].
+
+use wasmi::{core::TrapCode, Config, Engine, Instance, Linker, Module, StackUsage, Store};
+
+/// A Wasm module with a function that recurses `n` times.
+const RECURSIVE: &str = r#"
+    (module
+        (func $rec (export "rec") (param i32)
+            (if (local.get 0)
+                (then (call $rec (i32.sub (local.get 0) (i32.const 1))))
+            )
+        )
+    )
+"#;
+
+/// Instantiates the Wasm module `wat` in a new [`Store`].
+fn instantiate(wat: &str) -> (Store<()>, Instance) {
+    instantiate_with(&Config::default(), wat)
+}
+
+/// Instantiates the Wasm module `wat` in a new [`Store`] using `config`.
+fn instantiate_with(config: &Config, wat: &str) -> (Store<()>, Instance) {
+    let engine = Engine::new(config);
+    let wasm = wat::parse_str(wat).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    (store, instance)
+}
+
+/// Returns a Wasm module with an exported `wide` function that has `len_locals` locals.
+fn wide_wat(len_locals: usize) -> String {
+    let locals = "i64 ".repeat(len_locals);
+    format!(
+        r#"
+        (module
+            (func (export "wide") (result i64)
+                (local {locals})
+                (local.set {last} (i64.const 42))
+                (local.get {last})
+            )
+        )
+        "#,
+        last = len_locals - 1,
+    )
+}
+
+/// Returns the peak value stack bytes of a single call to a function with `len_locals` locals.
+fn wide_peak_bytes(len_locals: usize) -> usize {
+    let (mut store, instance) = instantiate(&wide_wat(len_locals));
+    let result = instance
+        .get_typed_func::<(), i64>(&store, "wide")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(result, 42);
+    let usage = store.stack_usage();
+    assert_eq!(usage.peak_call_depth, 1);
+    usage.peak_value_stack_bytes
+}
+
+#[test]
+fn recursion_depth() {
+    let (mut store, instance) = instantiate(RECURSIVE);
+    assert_eq!(store.stack_usage(), StackUsage::default());
+    let rec = instance.get_typed_func::<i32, ()>(&store, "rec").unwrap();
+    rec.call(&mut store, 99).unwrap();
+    // The root call plus 99 recursive calls.
+    assert_eq!(store.stack_usage().peak_call_depth, 100);
+    // Smaller executions do not lower the peak usage.
+    rec.call(&mut store, 9).unwrap();
+    assert_eq!(store.stack_usage().peak_call_depth, 100);
+    store.reset_stack_usage();
+    assert_eq!(store.stack_usage(), StackUsage::default());
+    rec.call(&mut store, 9).unwrap();
+    assert_eq!(store.stack_usage().peak_call_depth, 10);
+}
+
+#[test]
+fn value_stack_grows_with_locals() {
+    let small = wide_peak_bytes(10);
+    let large = wide_peak_bytes(1000);
+    assert!(small >= 10 * 8);
+    assert!(large >= 1000 * 8);
+    assert!(large > small);
+    // The function locals dominate the value stack usage.
+    assert!(large - small >= 990 * 8);
+}
+
+#[test]
+fn stack_overflow_reports_usage() {
+    let (mut store, instance) = instantiate(RECURSIVE);
+    let error = instance
+        .get_typed_func::<i32, ()>(&store, "rec")
+        .unwrap()
+        .call(&mut store, 100_000)
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+    let usage = error.stack_usage().unwrap();
+    assert_eq!(usage.peak_call_depth, 1024);
+    assert!(usage.peak_value_stack_bytes >= 1024 * 8);
+    assert_eq!(store.stack_usage(), usage);
+}
+
+#[test]
+fn other_traps_have_no_usage() {
+    let (mut store, instance) = instantiate(r#"(module (func (export "trap") (unreachable)))"#);
+    let error = instance
+        .get_typed_func::<(), ()>(&store, "trap")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    assert_eq!(error.stack_usage(), None);
+    assert_eq!(store.stack_usage().peak_call_depth, 1);
+}
+
+#[test]
+fn small_config_recursion_overflow() {
+    let (mut store, instance) = instantiate_with(&Config::small(), RECURSIVE);
+    let rec = instance.get_typed_func::<i32, ()>(&store, "rec").unwrap();
+    rec.call(&mut store, 127).unwrap();
+    let error = rec.call(&mut store, 128).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+    assert_eq!(error.stack_usage().unwrap().peak_call_depth, 128);
+}
+
+#[test]
+fn small_config_value_stack_overflow() {
+    // Note: the small config provides 16 KiB of value stack, that is 2048 cells.
+    let (mut store, instance) = instantiate_with(&Config::small(), &wide_wat(3000));
+    let error = instance
+        .get_typed_func::<(), i64>(&store, "wide")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::StackOverflow));
+    assert!(error.stack_usage().unwrap().peak_value_stack_bytes <= 16 * 1024);
+    // Note: functions fitting into the fixed-capacity value stack execute normally.
+    let (mut store, instance) = instantiate_with(&Config::small(), &wide_wat(1000));
+    let result = instance
+        .get_typed_func::<(), i64>(&store, "wide")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    assert_eq!(result, 42);
+}
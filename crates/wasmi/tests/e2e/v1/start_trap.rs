@@ -0,0 +1,240 @@
+//! Tests to check that failed instantiations do not leak their [`Instance`] or entities.
+
+use assert_matches::assert_matches;
+use wasmi::{
+    core::{TrapCode, ValueType},
+    errors::{ErrorKind, InstantiationError, MemoryError},
+    Caller,
+    Engine,
+    Error,
+    Extern,
+    FuncRef,
+    Linker,
+    Memory,
+    MemoryType,
+    Module,
+    Store,
+    StoreLimits,
+    StoreLimitsBuilder,
+    Table,
+    TableType,
+    Value,
+};
+
+/// A Wasm module that writes to its imported memory and then traps in its `start` function.
+const WAT: &str = r#"
+    (module
+        (import "env" "mem" (memory 1))
+        (import "env" "table" (table 1 funcref))
+        (table 1 funcref)
+        (global (mut i32) (i32.const 0))
+        (elem (table 1) (i32.const 0) func $start)
+        (func $start
+            (i32.store8 (i32.const 0) (i32.const 42))
+            (unreachable)
+        )
+        (start $start)
+    )
+"#;
+
+/// A Wasm module that owns a memory and a table and traps in its `start` function.
+const WAT_OWNED: &str = r#"
+    (module
+        (memory 1)
+        (table 1 funcref)
+        (data (i32.const 0) "\07")
+        (func $start
+            (unreachable)
+        )
+        (start $start)
+    )
+"#;
+
+/// Same as [`WAT_OWNED`] but stores its own function into the imported table.
+const WAT_ESCAPE: &str = r#"
+    (module
+        (import "env" "table" (table 1 funcref))
+        (memory 1)
+        (data (i32.const 0) "\07")
+        (elem (table 0) (i32.const 0) func $get)
+        (func $get (result i32)
+            (i32.load8_u (i32.const 0))
+        )
+        (func $start
+            (unreachable)
+        )
+        (start $start)
+    )
+"#;
+
+/// A Wasm module that calls a host function before trapping in its `start` function.
+const WAT_HOST: &str = r#"
+    (module
+        (import "env" "host" (func $host))
+        (memory (export "own") 1)
+        (func $start
+            (call $host)
+            (unreachable)
+        )
+        (start $start)
+    )
+"#;
+
+/// A Wasm module without `start` function whose active data segment is out of bounds.
+const WAT_OOB: &str = r#"
+    (module
+        (memory 1)
+        (table 1 funcref)
+        (data (i32.const 0) "\07")
+        (data (i32.const 65536) "\01")
+    )
+"#;
+
+/// The imported entities of the tested Wasm modules.
+struct Imports {
+    memory: Memory,
+    table: Table,
+}
+
+/// Creates a [`Store`] with an imported memory and table whose limits only allow for one instance.
+///
+/// The limits on memories and tables are exceeded by a few leaked instantiations.
+fn setup() -> (Store<StoreLimits>, Linker<StoreLimits>, Imports) {
+    let engine = Engine::default();
+    let limits = StoreLimitsBuilder::new()
+        .instances(1)
+        .memories(3)
+        .tables(3)
+        .build();
+    let mut store = Store::new(&engine, limits);
+    store.limiter(|limits| limits);
+    let memory = Memory::new(&mut store, MemoryType::new(1, None).unwrap()).unwrap();
+    let table = Table::new(
+        &mut store,
+        TableType::new(ValueType::FuncRef, 1, None),
+        Value::default(ValueType::FuncRef),
+    )
+    .unwrap();
+    let mut linker = <Linker<StoreLimits>>::new(&engine);
+    linker.define("env", "mem", memory).unwrap();
+    linker.define("env", "table", table).unwrap();
+    (store, linker, Imports { memory, table })
+}
+
+/// Compiles the Wasm module in WebAssembly text format `wat`.
+fn module(engine: &Engine, wat: &str) -> Module {
+    Module::new(engine, &wat::parse_str(wat).unwrap()[..]).unwrap()
+}
+
+#[test]
+fn start_trap_keeps_imported_mutations() {
+    let (mut store, linker, imports) = setup();
+    let module = module(store.engine(), WAT);
+    let error = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    assert_eq!(imports.memory.data(&store)[0], 42);
+}
+
+#[test]
+fn start_trap_does_not_accumulate_entities() {
+    let (mut store, linker, _imports) = setup();
+    for wat in [WAT, WAT_OWNED] {
+        let module = module(store.engine(), wat);
+        // Each failed instantiation would exceed the store limits
+        // if its instance, memory and table were kept around.
+        for _ in 0..10 {
+            let error = linker
+                .instantiate(&mut store, &module)
+                .unwrap()
+                .start(&mut store)
+                .unwrap_err();
+            assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+        }
+    }
+    // The released resources can be used by a successful instantiation.
+    let module = self::module(store.engine(), "(module (memory 1) (table 1 funcref))");
+    linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+}
+
+#[test]
+fn failed_instantiation_does_not_accumulate_entities() {
+    let (mut store, linker, _imports) = setup();
+    let module = module(store.engine(), WAT_OOB);
+    for _ in 0..10 {
+        let error = linker.instantiate(&mut store, &module).unwrap_err();
+        assert_matches!(
+            error.kind(),
+            ErrorKind::Memory(MemoryError::OutOfBoundsAccess)
+        );
+    }
+    // `ensure_no_start` releases the entities of the instance with a `start` function.
+    let module = self::module(store.engine(), WAT_OWNED);
+    for _ in 0..10 {
+        let error = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .ensure_no_start(&mut store)
+            .unwrap_err();
+        assert_matches!(error, InstantiationError::FoundStartFn { .. });
+    }
+}
+
+#[test]
+fn start_trap_keeps_escaped_entities() {
+    let (mut store, linker, imports) = setup();
+    let module = module(store.engine(), WAT_ESCAPE);
+    let error = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    // The function of the failed instance is still reachable via the imported table
+    // and keeps access to the memory of the failed instance.
+    let get = imports
+        .table
+        .get(&store, 0)
+        .and_then(|value| value.funcref().and_then(FuncRef::func).copied())
+        .unwrap()
+        .typed::<(), i32>(&store)
+        .unwrap();
+    assert_eq!(get.call(&mut store, ()).unwrap(), 7);
+    // The entities of the failed instance are still accounted for.
+    let error = linker.instantiate(&mut store, &module).unwrap_err();
+    assert_matches!(
+        error.kind(),
+        ErrorKind::Instantiation(InstantiationError::TooManyInstances)
+    );
+}
+
+#[test]
+fn start_trap_after_host_call_keeps_entities() {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, None);
+    let mut linker = <Linker<Option<Memory>>>::new(&engine);
+    linker
+        .func_wrap("env", "host", |mut caller: Caller<Option<Memory>>| {
+            let memory = caller.get_export("own").and_then(Extern::into_memory);
+            *caller.data_mut() = memory;
+        })
+        .unwrap();
+    let module = module(&engine, WAT_HOST);
+    let error: Error = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    // The host function obtained the memory of the failed instance which therefore is kept.
+    let memory = store.data().unwrap();
+    memory.data_mut(&mut store)[0] = 1;
+    assert_eq!(memory.data(&store)[0], 1);
+}
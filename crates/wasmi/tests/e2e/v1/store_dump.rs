@@ -0,0 +1,124 @@
+//! Tests for iterating the entities of a [`Store`] and [`Store::debug_dump`].
+
+use wasmi::{core::Pages, Engine, Linker, Module, Store, Value};
+
+/// A Wasm module exporting a memory, a table, a mutable global and two functions.
+const WASM_A: &str = r#"
+    (module
+        (memory (export "memory") 1)
+        (table (export "table") 2 funcref)
+        (global $counter (export "counter") (mut i32) (i32.const 0))
+        (func (export "bump") (param i32)
+            (global.set $counter (i32.add (global.get $counter) (local.get 0)))
+        )
+        (func (export "counter_value") (result i32)
+            (global.get $counter)
+        )
+    )
+"#;
+
+/// A Wasm module importing the memory and a function of [`WASM_A`].
+const WASM_B: &str = r#"
+    (module
+        (import "a" "memory" (memory 1))
+        (import "a" "bump" (func $bump (param i32)))
+        (global (export "answer") i64 (i64.const 42))
+        (func (export "bump_twice") (param i32)
+            (call $bump (local.get 0))
+            (call $bump (local.get 0))
+        )
+    )
+"#;
+
+/// Instantiates [`WASM_A`] and [`WASM_B`] and calls `bump_twice` with `5`.
+fn setup() -> Store<()> {
+    let engine = Engine::default();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let wasm_a = wat::parse_str(WASM_A).unwrap();
+    let module_a = Module::new(&engine, &wasm_a[..]).unwrap();
+    let instance_a = linker
+        .instantiate(&mut store, &module_a)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    for name in ["memory", "bump"] {
+        let export = instance_a.get_export(&store, name).unwrap();
+        linker.define("a", name, export).unwrap();
+    }
+    let wasm_b = wat::parse_str(WASM_B).unwrap();
+    let module_b = Module::new(&engine, &wasm_b[..]).unwrap();
+    let instance_b = linker
+        .instantiate(&mut store, &module_b)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance_b
+        .get_typed_func::<i32, ()>(&store, "bump_twice")
+        .unwrap()
+        .call(&mut store, 5)
+        .unwrap();
+    store
+}
+
+#[test]
+fn iterate_store_entities() {
+    let store = setup();
+    assert_eq!(store.instances().count(), 2);
+    // Note: imported functions and memories are not allocated again.
+    assert_eq!(store.funcs().count(), 3);
+    assert_eq!(store.memories().count(), 1);
+    assert_eq!(store.tables().count(), 1);
+    assert_eq!(store.globals().count(), 2);
+    let memory = store.memories().next().unwrap();
+    assert_eq!(memory.current_pages(&store), Pages::new(1).unwrap());
+    let table = store.tables().next().unwrap();
+    assert_eq!(table.size(&store), 2);
+    let values = store
+        .globals()
+        .map(|global| global.get(&store))
+        .collect::<Vec<_>>();
+    assert!(matches!(values[..], [Value::I32(10), Value::I64(42)]));
+    let exports = store
+        .instances()
+        .map(|instance| {
+            instance
+                .exports(&store)
+                .map(|export| export.name().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exports,
+        [
+            vec!["memory", "table", "counter", "bump", "counter_value"],
+            vec!["answer", "bump_twice"],
+        ]
+    );
+}
+
+#[test]
+fn debug_dump() {
+    let store = setup();
+    let mut dump = String::new();
+    store.debug_dump(&mut dump).unwrap();
+    assert_eq!(
+        dump,
+        "\
+store: 2 instances, 3 funcs, 1 memories, 1 tables, 2 globals
+memory 0: 1 pages (65536 bytes)
+table 0: 2 elements of FuncRef
+global 0: I32(10) (Var)
+global 1: I64(42) (Const)
+instance 0:
+  export \"memory\": memory 0
+  export \"table\": table 0
+  export \"counter\": global 0
+  export \"bump\": func 0
+  export \"counter_value\": func 1
+instance 1:
+  export \"answer\": global 1
+  export \"bump_twice\": func 2
+"
+    );
+}
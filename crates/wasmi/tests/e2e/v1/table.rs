@@ -0,0 +1,224 @@
+//! Tests for the host side bulk APIs of [`Table`].
+
+use wasmi::{
+    core::ValueType,
+    errors::TableError,
+    Config,
+    Engine,
+    ExternRef,
+    Func,
+    FuncRef,
+    Linker,
+    Module,
+    Store,
+    Table,
+    TableType,
+    Value,
+};
+
+/// Creates a new [`Store`] for the tests.
+fn test_store() -> Store<()> {
+    Store::new(&Engine::default(), ())
+}
+
+/// Creates a new `externref` [`Table`] with `len` elements set to `0..len`.
+fn test_table(store: &mut Store<()>, len: u32) -> Table {
+    let table = Table::new(
+        &mut *store,
+        TableType::new(ValueType::ExternRef, len, None),
+        Value::from(ExternRef::null()),
+    )
+    .unwrap();
+    let values = (0..len as i32).map(|i| value(store, i)).collect::<Vec<_>>();
+    table.set_many(&mut *store, 0, &values).unwrap();
+    table
+}
+
+/// Returns an `externref` [`Value`] wrapping `n`.
+fn value(store: &mut Store<()>, n: i32) -> Value {
+    Value::from(ExternRef::new::<i32>(store, n))
+}
+
+/// Returns the `i32` values wrapped by the `externref` elements of `table`.
+///
+/// Null elements are represented by `None`.
+fn elements(store: &Store<()>, table: &Table) -> Vec<Option<i32>> {
+    (0..table.size(store))
+        .map(|index| {
+            let value = table.get(store, index).unwrap();
+            value
+                .externref()
+                .unwrap()
+                .data(store)
+                .map(|data| *data.downcast_ref::<i32>().unwrap())
+        })
+        .collect()
+}
+
+/// Returns `expected` with all elements wrapped in `Some`.
+fn some<const N: usize>(expected: [i32; N]) -> Vec<Option<i32>> {
+    expected.map(Some).to_vec()
+}
+
+#[test]
+fn set_many() {
+    let mut store = test_store();
+    let table = test_table(&mut store, 6);
+    assert_eq!(elements(&store, &table), some([0, 1, 2, 3, 4, 5]));
+    let values = [value(&mut store, 10), value(&mut store, 11)];
+    table.set_many(&mut store, 4, &values).unwrap();
+    assert_eq!(elements(&store, &table), some([0, 1, 2, 3, 10, 11]));
+    table.set_many(&mut store, 6, &[]).unwrap();
+}
+
+#[test]
+fn set_many_out_of_bounds() {
+    let mut store = test_store();
+    let table = test_table(&mut store, 6);
+    let values = [value(&mut store, 10), value(&mut store, 11)];
+    assert!(matches!(
+        table.set_many(&mut store, 5, &values),
+        Err(TableError::AccessOutOfBounds { .. })
+    ));
+    assert!(table.set_many(&mut store, u32::MAX, &values).is_err());
+    // No element has been written partially.
+    assert_eq!(elements(&store, &table), some([0, 1, 2, 3, 4, 5]));
+}
+
+#[test]
+fn set_many_type_mismatch() {
+    let mut store = test_store();
+    let table = test_table(&mut store, 6);
+    let values = [value(&mut store, 10), Value::from(FuncRef::null())];
+    assert!(matches!(
+        table.set_many(&mut store, 0, &values),
+        Err(TableError::ElementTypeMismatch { .. })
+    ));
+    // No element has been written partially.
+    assert_eq!(elements(&store, &table), some([0, 1, 2, 3, 4, 5]));
+}
+
+#[test]
+fn fill() {
+    let mut store = test_store();
+    let table = test_table(&mut store, 6);
+    let val = value(&mut store, 10);
+    table.fill(&mut store, 1, val.clone(), 3).unwrap();
+    assert_eq!(elements(&store, &table), some([0, 10, 10, 10, 4, 5]));
+    // Out of bounds fills write no elements.
+    assert!(table.fill(&mut store, 4, val.clone(), 3).is_err());
+    assert!(table.fill(&mut store, u32::MAX, val, 2).is_err());
+    assert_eq!(elements(&store, &table), some([0, 10, 10, 10, 4, 5]));
+}
+
+#[test]
+fn copy_within_overlapping() {
+    let mut store = test_store();
+    // Copies overlapping elements to higher indices.
+    let table = test_table(&mut store, 6);
+    table.copy_within(&mut store, 2, 0, 4).unwrap();
+    assert_eq!(elements(&store, &table), some([0, 1, 0, 1, 2, 3]));
+    // Copies overlapping elements to lower indices.
+    let table = test_table(&mut store, 6);
+    table.copy_within(&mut store, 0, 2, 4).unwrap();
+    assert_eq!(elements(&store, &table), some([2, 3, 4, 5, 4, 5]));
+}
+
+#[test]
+fn copy_within_out_of_bounds() {
+    let mut store = test_store();
+    let table = test_table(&mut store, 6);
+    assert!(matches!(
+        table.copy_within(&mut store, 3, 0, 4),
+        Err(TableError::CopyOutOfBounds)
+    ));
+    assert!(table.copy_within(&mut store, 0, 3, 4).is_err());
+    assert!(table.copy_within(&mut store, 0, u32::MAX, 2).is_err());
+    // No element has been copied partially.
+    assert_eq!(elements(&store, &table), some([0, 1, 2, 3, 4, 5]));
+    // Zero length copies at the end of the table are in bounds.
+    table.copy_within(&mut store, 6, 6, 0).unwrap();
+}
+
+#[test]
+fn copy_between() {
+    let mut store = test_store();
+    let src = test_table(&mut store, 6);
+    let dst = Table::new(
+        &mut store,
+        TableType::new(ValueType::ExternRef, 4, None),
+        Value::from(ExternRef::null()),
+    )
+    .unwrap();
+    Table::copy_between(&mut store, &dst, 1, &src, 3, 3).unwrap();
+    assert_eq!(
+        elements(&store, &dst),
+        [None, Some(3), Some(4), Some(5)].to_vec()
+    );
+    // Out of bounds copies write no elements.
+    assert!(Table::copy_between(&mut store, &dst, 2, &src, 0, 3).is_err());
+    assert!(Table::copy_between(&mut store, &dst, 0, &src, 4, 3).is_err());
+    assert_eq!(
+        elements(&store, &dst),
+        [None, Some(3), Some(4), Some(5)].to_vec()
+    );
+    // Copies between the same table behave like `copy_within`.
+    Table::copy_between(&mut store, &src, 1, &src, 0, 5).unwrap();
+    assert_eq!(elements(&store, &src), some([0, 0, 1, 2, 3, 4]));
+}
+
+#[test]
+fn copy_between_type_mismatch() {
+    let mut store = test_store();
+    let src = test_table(&mut store, 2);
+    let dst = Table::new(
+        &mut store,
+        TableType::new(ValueType::FuncRef, 2, None),
+        Value::from(FuncRef::null()),
+    )
+    .unwrap();
+    assert!(matches!(
+        Table::copy_between(&mut store, &dst, 0, &src, 0, 2),
+        Err(TableError::ElementTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn set_many_funcrefs_lazy() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (type $ty (func (result i32)))
+            (table $t (export "t") 4 funcref)
+            (func $f0 (result i32) (i32.const 0))
+            (elem (table $t) (i32.const 0) func $f0 $f0 $f0 $f0)
+            (func (export "call") (param i32) (result i32)
+                (call_indirect $t (type $ty) (local.get 0))
+            )
+        )
+    "#,
+    )
+    .unwrap();
+    for lazy in [false, true] {
+        let mut config = Config::default();
+        config.lazy_table_init(lazy);
+        let engine = Engine::new(&config);
+        let mut store = Store::new(&engine, ());
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let table = instance.get_table(&store, "t").unwrap();
+        let call = instance.get_typed_func::<i32, i32>(&store, "call").unwrap();
+        let f2 = Func::wrap(&mut store, || 2_i32);
+        let values = [Value::from(FuncRef::new(f2)), Value::from(FuncRef::new(f2))];
+        table.set_many(&mut store, 1, &values).unwrap();
+        table.copy_within(&mut store, 2, 0, 2).unwrap();
+        let results = (0..4)
+            .map(|index| call.call(&mut store, index).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(results, [0, 2, 0, 2], "lazy = {lazy}");
+    }
+}
@@ -0,0 +1,202 @@
+//! Tests for tail calls from Wasm to host functions via `return_call`.
+
+use wasmi::{Caller, Config, Engine, Extern, Instance, Linker, Module, StackUsage, Store};
+
+/// Calls the imported `env.host` host function in various tail call positions.
+const WASM: &str = r#"
+    (module
+        (import "env" "host" (func $host (param i32) (result i32)))
+        (func $tail (param i32 i32) (result i32)
+            (return_call $host (local.get 1))
+        )
+        (func $forward (param i32) (result i32)
+            (call $tail (i32.const 0) (local.get 0))
+        )
+        (func (export "nested") (result i32)
+            (local i32 i32)
+            (local.set 0 (i32.const 7))
+            (i32.add (local.get 0) (call $tail (i32.const 1) (i32.const 2)))
+        )
+        (func (export "forwarded") (result i32)
+            (i32.add (i32.const 7) (call $forward (i32.const 2)))
+        )
+        (func (export "root") (param i32) (result i32)
+            (return_call $host (local.get 0))
+        )
+    )
+"#;
+
+/// Mutually tail calls between `$ping` and `$pong` until `$pong` tail calls the `env.step` host function.
+///
+/// The `run` function does this `n` times summing up the results.
+const STRESS: &str = r#"
+    (module
+        (import "env" "step" (func $step (param i64) (result i64)))
+        (func $ping (param $n i64) (result i64)
+            (return_call $pong (local.get $n))
+        )
+        (func $pong (param $n i64) (result i64)
+            (return_call $step (local.get $n))
+        )
+        (func (export "inc") (param i64) (result i64)
+            (i64.add (local.get 0) (i64.const 1))
+        )
+        (func (export "run") (param $n i64) (result i64)
+            (local $acc i64)
+            (block $done
+                (loop $continue
+                    (br_if $done (i64.eqz (local.get $n)))
+                    (local.set $acc (i64.add (local.get $acc) (call $ping (local.get $n))))
+                    (local.set $n (i64.sub (local.get $n) (i64.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $acc)
+        )
+    )
+"#;
+
+/// Instantiates `wasm` with `linker` and tail calls enabled.
+fn instantiate<T>(store: &mut Store<T>, linker: &Linker<T>, wasm: &str) -> Instance {
+    let module = Module::new(store.engine(), &wat::parse_str(wasm).unwrap()[..]).unwrap();
+    linker
+        .instantiate(&mut *store, &module)
+        .unwrap()
+        .start(&mut *store)
+        .unwrap()
+}
+
+/// Returns an [`Engine`] with the Wasm `tail-call` proposal enabled.
+fn engine() -> Engine {
+    let mut config = Config::default();
+    config.wasm_tail_call(true);
+    Engine::new(&config)
+}
+
+/// Instantiates [`WASM`] with an `env.host` host function multiplying its input by 10.
+fn setup() -> (Store<()>, Instance) {
+    let engine = engine();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "host", |_caller: Caller<()>, x: i32| x * 10)
+        .unwrap();
+    let instance = instantiate(&mut store, &linker, WASM);
+    (store, instance)
+}
+
+/// Calls the exported `name` function of `instance` with `params`.
+fn call<Params, Results>(
+    store: &mut Store<()>,
+    instance: Instance,
+    name: &str,
+    params: Params,
+) -> Results
+where
+    Params: wasmi::WasmParams,
+    Results: wasmi::WasmResults,
+{
+    instance
+        .get_typed_func::<Params, Results>(&*store, name)
+        .unwrap()
+        .call(store, params)
+        .unwrap()
+}
+
+#[test]
+fn results_are_returned_to_callers_caller() {
+    let (mut store, instance) = setup();
+    assert_eq!(call::<(), i32>(&mut store, instance, "nested", ()), 27);
+}
+
+#[test]
+fn results_are_returned_to_forwarding_caller() {
+    let (mut store, instance) = setup();
+    assert_eq!(call::<(), i32>(&mut store, instance, "forwarded", ()), 27);
+}
+
+#[test]
+fn results_are_returned_from_root() {
+    let (mut store, instance) = setup();
+    assert_eq!(call::<i32, i32>(&mut store, instance, "root", 4), 40);
+}
+
+#[test]
+fn host_func_uses_instance_of_tail_caller() {
+    let engine = engine();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "host", |caller: Caller<()>, _: i32| {
+            match caller.get_export("marker") {
+                Some(Extern::Global(global)) => global.get(&caller).i32().unwrap(),
+                _ => -1,
+            }
+        })
+        .unwrap();
+    let callee = instantiate(
+        &mut store,
+        &linker,
+        r#"
+        (module
+            (import "env" "host" (func $host (param i32) (result i32)))
+            (global (export "marker") i32 (i32.const 1))
+            (func (export "tail") (param i32) (result i32)
+                (return_call $host (local.get 0))
+            )
+        )
+        "#,
+    );
+    let tail = callee.get_func(&store, "tail").unwrap();
+    linker.define("callee", "tail", tail).unwrap();
+    let caller = instantiate(
+        &mut store,
+        &linker,
+        r#"
+        (module
+            (import "callee" "tail" (func $tail (param i32) (result i32)))
+            (global (export "marker") i32 (i32.const 2))
+            (func (export "run") (result i32)
+                (i32.add (i32.const 10) (call $tail (i32.const 0)))
+            )
+        )
+        "#,
+    );
+    assert_eq!(call::<(), i32>(&mut store, caller, "run", ()), 11);
+}
+
+/// Runs the `run` function of [`STRESS`] with `n` and returns its result and [`StackUsage`].
+///
+/// The `env.step` host function re-enters Wasm via the exported `inc` function.
+fn run_stress(n: i64) -> (i64, StackUsage) {
+    let engine = engine();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker
+        .func_wrap("env", "step", |mut caller: Caller<()>, n: i64| {
+            caller
+                .get_export("inc")
+                .and_then(Extern::into_func)
+                .unwrap()
+                .typed::<i64, i64>(&caller)
+                .unwrap()
+                .call(&mut caller, n)
+                .unwrap()
+        })
+        .unwrap();
+    let instance = instantiate(&mut store, &linker, STRESS);
+    store.reset_stack_usage();
+    let result = call::<i64, i64>(&mut store, instance, "run", n);
+    (result, store.stack_usage())
+}
+
+#[test]
+fn stress_tail_calls_to_host_keep_stack_flat() {
+    let n = 1_000_000;
+    let (result, usage) = run_stress(n);
+    // Note: the sum of `n + 1` for all `n` in `1..=n`.
+    assert_eq!(result, n * (n + 1) / 2 + n);
+    // Note: only `run` and either `$ping` or `$pong` are on the call stack at a time.
+    assert_eq!(usage.peak_call_depth, 2);
+    assert_eq!(usage, run_stress(1).1);
+}
@@ -0,0 +1,271 @@
+//! Tests for the `tracing` spans and events emitted by Wasmi.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event,
+    Metadata,
+    Subscriber,
+};
+use tracing_core::span::Current;
+use wasmi::{core::TrapCode, CompilationMode, Config, Engine, Linker, Module, Store};
+
+/// A span or event recorded by the [`Recorder`].
+#[derive(Debug, Clone)]
+struct Recorded {
+    /// The name of the span or the message of the event.
+    name: String,
+    /// The index of the parent span within [`Recording::spans`] if any.
+    parent: Option<usize>,
+    /// The recorded fields except for the message of an event.
+    fields: BTreeMap<String, String>,
+    /// The static metadata of the span or event.
+    metadata: &'static Metadata<'static>,
+}
+
+impl Recorded {
+    /// Returns the recorded value of the field `name` if any.
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+}
+
+/// The spans and events recorded by the [`Recorder`].
+#[derive(Debug, Default)]
+struct Recording {
+    /// All recorded spans in the order of their creation.
+    spans: Vec<Recorded>,
+    /// All recorded events in the order of their emission.
+    events: Vec<Recorded>,
+    /// The indices of the currently entered spans.
+    entered: Vec<usize>,
+}
+
+/// A [`Subscriber`] that records all spans and events of Wasmi.
+#[derive(Debug, Default, Clone)]
+struct Recorder(Arc<Mutex<Recording>>);
+
+/// Collects the fields of a span or event.
+struct FieldVisitor<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().into(), format!("{value:?}"));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target().starts_with("wasmi")
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut recording = self.0.lock().unwrap();
+        let mut fields = BTreeMap::new();
+        span.record(&mut FieldVisitor(&mut fields));
+        let parent = recording.entered.last().copied();
+        recording.spans.push(Recorded {
+            name: span.metadata().name().into(),
+            parent,
+            fields,
+            metadata: span.metadata(),
+        });
+        Id::from_u64(recording.spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut recording = self.0.lock().unwrap();
+        let span = &mut recording.spans[span.into_u64() as usize - 1];
+        values.record(&mut FieldVisitor(&mut span.fields));
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut recording = self.0.lock().unwrap();
+        let mut fields = BTreeMap::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        let name = fields.remove("message").unwrap_or_default();
+        let parent = recording.entered.last().copied();
+        recording.events.push(Recorded {
+            name,
+            parent,
+            fields,
+            metadata: event.metadata(),
+        });
+    }
+
+    fn enter(&self, span: &Id) {
+        let mut recording = self.0.lock().unwrap();
+        recording.entered.push(span.into_u64() as usize - 1);
+    }
+
+    fn exit(&self, span: &Id) {
+        let mut recording = self.0.lock().unwrap();
+        let index = recording.entered.pop();
+        assert_eq!(index, Some(span.into_u64() as usize - 1));
+    }
+
+    fn current_span(&self) -> Current {
+        let recording = self.0.lock().unwrap();
+        match recording.entered.last() {
+            Some(&index) => {
+                let metadata = recording.spans[index].metadata;
+                Current::new(Id::from_u64(index as u64 + 1), metadata)
+            }
+            None => Current::none(),
+        }
+    }
+}
+
+/// A Wasm module with an imported host function, a start function and a trapping function.
+const WASM: &str = r#"
+    (module
+        (import "env" "add" (func $add (param i32 i32) (result i32)))
+        (global $started (mut i32) (i32.const 0))
+        (func $start (global.set $started (i32.const 1)))
+        (func (export "run") (param i32) (result i32)
+            (call $add (local.get 0) (global.get $started))
+        )
+        (func (export "trap") (param i32) (result i32)
+            (i32.div_s (local.get 0) (i32.const 0))
+        )
+        (start $start)
+    )
+"#;
+
+/// Compiles, instantiates and calls [`WASM`] using `mode` while recording all spans and events.
+///
+/// Calls `run` with `41` and `trap` with `1`.
+fn record(mode: CompilationMode) -> Recording {
+    let mut config = Config::default();
+    config.compilation_mode(mode);
+    record_with(&config)
+}
+
+/// Compiles, instantiates and calls [`WASM`] using `config` while recording all spans and events.
+///
+/// Calls `run` with `41` and `trap` with `1`.
+fn record_with(config: &Config) -> Recording {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let engine = Engine::new(config);
+        let wasm = wat::parse_str(WASM).unwrap();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let mut linker = <Linker<()>>::new(&engine);
+        linker
+            .func_wrap("env", "add", |a: i32, b: i32| a + b)
+            .unwrap();
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let run = instance.get_typed_func::<i32, i32>(&store, "run").unwrap();
+        assert_eq!(run.call(&mut store, 41).unwrap(), 42);
+        let trap = instance.get_typed_func::<i32, i32>(&store, "trap").unwrap();
+        let error = trap.call(&mut store, 1).unwrap_err();
+        assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    });
+    let recording = recorder.0.lock().unwrap();
+    assert!(recording.entered.is_empty());
+    Recording {
+        spans: recording.spans.clone(),
+        events: recording.events.clone(),
+        entered: Vec::new(),
+    }
+}
+
+/// Returns the names of the `spans` with their parent indices.
+fn span_tree(spans: &[Recorded]) -> Vec<(&str, Option<usize>)> {
+    spans
+        .iter()
+        .map(|span| (span.name.as_str(), span.parent))
+        .collect()
+}
+
+#[test]
+fn compile_instantiate_and_call() {
+    let recording = record(CompilationMode::Eager);
+    let spans = &recording.spans;
+    assert_eq!(
+        span_tree(spans),
+        [
+            ("parse_module", None),
+            ("instantiate", None),
+            // Note: the start function is called when starting the instance.
+            ("call", None),
+            ("call", None),
+            ("call", None),
+        ]
+    );
+    let wasm = wat::parse_str(WASM).unwrap();
+    assert_eq!(spans[0].field("size"), Some(&*wasm.len().to_string()));
+    assert_eq!(spans[0].field("num_funcs"), Some("3"));
+    assert_eq!(spans[1].field("num_imports"), Some("1"));
+    assert!(spans[1].field("import_resolution_ns").is_some());
+    // Note: the function indices include the imported function.
+    for (span, func_index) in spans[2..].iter().zip(["1", "2", "3"]) {
+        assert_eq!(span.field("host"), Some("false"));
+        assert_eq!(span.field("func_index"), Some(func_index));
+    }
+    // Note: functions are not compiled lazily and only the last call traps.
+    assert_eq!(recording.events.len(), 1);
+    let event = &recording.events[0];
+    assert_eq!(event.name, "call trapped");
+    assert_eq!(event.parent, Some(4));
+    assert_eq!(event.field("trap_code"), Some("IntegerDivisionByZero"));
+}
+
+#[test]
+fn lazily_compiled_funcs() {
+    let recording = record(CompilationMode::Lazy);
+    let spans = &recording.spans;
+    assert_eq!(spans.len(), 5);
+    // Note: the function indices are recorded after compiling the functions.
+    for (span, func_index) in spans[2..].iter().zip(["1", "2", "3"]) {
+        assert_eq!(span.name, "call");
+        assert_eq!(span.field("func_index"), Some(func_index));
+    }
+    let compiled = recording
+        .events
+        .iter()
+        .filter(|event| event.name == "compiled function lazily")
+        .map(|event| {
+            assert!(event.field("duration_ns").is_some());
+            (event.field("func_index").unwrap(), event.parent)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(compiled, [("1", Some(2)), ("2", Some(3)), ("3", Some(4))]);
+    let trapped = recording.events.last().unwrap();
+    assert_eq!(trapped.name, "call trapped");
+    assert_eq!(trapped.parent, Some(4));
+}
+
+#[test]
+fn deterministic_only() {
+    let mut config = Config::default();
+    config
+        .compilation_mode(CompilationMode::Lazy)
+        .deterministic_only(true);
+    let recording = record_with(&config);
+    // The wall clock is not read and thus all durations are zero.
+    assert_eq!(recording.spans[1].field("import_resolution_ns"), Some("0"));
+    let durations = recording
+        .events
+        .iter()
+        .filter(|event| event.name == "compiled function lazily")
+        .map(|event| event.field("duration_ns").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(durations, ["0", "0", "0"]);
+}
@@ -0,0 +1,313 @@
+//! Tests for translating single Wasm functions via [`Engine::translate_func`].
+
+use wasmi::{
+    core::ValueType,
+    errors::{ErrorKind, FuncError},
+    Caller,
+    Config,
+    Engine,
+    FuncType,
+    GlobalType,
+    Linker,
+    MemoryType,
+    Module,
+    Mutability,
+    Store,
+    TableType,
+    TranslationContext,
+};
+
+/// A Wasm module whose `compute` function body is the `{BODY}` placeholder.
+///
+/// The `compute` function is the function at index 4.
+const WASM: &str = r#"
+    (module
+        (type $unop (func (param i32) (result i32)))
+        (import "env" "host" (func $host (param i32) (result i32)))
+        (memory 1)
+        (table 2 funcref)
+        (global $count (mut i32) (i32.const 0))
+        (global $base i32 (i32.const 100))
+        (elem (i32.const 0) $double $inc)
+        (func $double (type $unop) (i32.mul (local.get 0) (i32.const 2)))
+        (func $inc (type $unop) (i32.add (local.get 0) (i32.const 1)))
+        (func (export "count") (result i32) (global.get $count))
+        (func (export "compute") (param $n i32) (result i32)
+            {BODY}
+        )
+    )
+"#;
+
+/// The body of the `compute` function of [`WASM`].
+const BODY: &str = r#"
+    (local $acc i32) (local $i i32)
+    (local.set $acc (global.get $base))
+    (block $done
+        (loop $continue
+            (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+            (i32.store
+                (i32.mul (local.get $i) (i32.const 4))
+                (call $double (local.get $i))
+            )
+            (local.set $acc
+                (i32.add
+                    (local.get $acc)
+                    (call_indirect (type $unop)
+                        (i32.load (i32.mul (local.get $i) (i32.const 4)))
+                        (i32.and (local.get $i) (i32.const 1))
+                    )
+                )
+            )
+            (local.set $i (i32.add (local.get $i) (i32.const 1)))
+            (br $continue)
+        )
+    )
+    (global.set $count (i32.add (global.get $count) (i32.const 1)))
+    (call $host (local.get $acc))
+"#;
+
+/// The index of the `compute` function within [`WASM`].
+const COMPUTE: u32 = 4;
+
+/// Returns the Wasm binary of [`WASM`] with the `compute` function `body`.
+fn wasm(body: &str) -> Vec<u8> {
+    wat::parse_str(WASM.replace("{BODY}", body)).unwrap()
+}
+
+/// Reads an unsigned LEB128 encoded `u32` from `bytes` at `pos`.
+fn read_u32(bytes: &[u8], pos: &mut usize) -> u32 {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[*pos];
+        *pos += 1;
+        value |= u32::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+/// Returns the local variable declarations and operators of the last function body of `wasm`.
+fn last_func_body(wasm: &[u8]) -> (Vec<(u32, ValueType)>, Vec<u8>) {
+    let mut pos = 8;
+    while wasm[pos] != 10 {
+        pos += 1;
+        let len = read_u32(wasm, &mut pos) as usize;
+        pos += len;
+    }
+    pos += 1;
+    read_u32(wasm, &mut pos);
+    let len_bodies = read_u32(wasm, &mut pos);
+    let mut body = &wasm[..0];
+    for _ in 0..len_bodies {
+        let len = read_u32(wasm, &mut pos) as usize;
+        body = &wasm[pos..pos + len];
+        pos += len;
+    }
+    let mut pos = 0;
+    let len_locals = read_u32(body, &mut pos);
+    let locals = (0..len_locals)
+        .map(|_| {
+            let amount = read_u32(body, &mut pos);
+            let ty = match body[pos] {
+                0x7F => ValueType::I32,
+                0x7E => ValueType::I64,
+                0x7D => ValueType::F32,
+                0x7C => ValueType::F64,
+                0x70 => ValueType::FuncRef,
+                0x6F => ValueType::ExternRef,
+                byte => panic!("unexpected value type: {byte:#X}"),
+            };
+            pos += 1;
+            (amount, ty)
+        })
+        .collect();
+    (locals, body[pos..].to_vec())
+}
+
+/// The fuel given to a [`Store`] if fuel metering is enabled.
+const FUEL: u64 = 1_000_000;
+
+/// Calls `compute` of `module` with all `inputs` and returns its results followed by `count`.
+///
+/// If fuel metering is enabled the results end with the consumed fuel.
+fn run(engine: &Engine, module: &Module, inputs: &[i32]) -> Vec<i64> {
+    let mut store = Store::new(engine, ());
+    let metered = store.add_fuel(FUEL).is_ok();
+    let mut linker = <Linker<()>>::new(engine);
+    linker
+        .func_wrap("env", "host", |_caller: Caller<()>, x: i32| x + 1000)
+        .unwrap();
+    let instance = linker
+        .instantiate(&mut store, module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let compute = instance
+        .get_typed_func::<i32, i32>(&store, "compute")
+        .unwrap();
+    let count = instance.get_typed_func::<(), i32>(&store, "count").unwrap();
+    let mut results: Vec<i64> = inputs
+        .iter()
+        .map(|n| i64::from(compute.call(&mut store, *n).unwrap()))
+        .collect();
+    results.push(i64::from(count.call(&mut store, ()).unwrap()));
+    if metered {
+        results.push(store.fuel_consumed().unwrap() as i64);
+    }
+    results
+}
+
+/// The inputs used to compare the `compute` function translations.
+const INPUTS: [i32; 5] = [0, 1, 2, 7, 100];
+
+/// The type of the `compute` function.
+fn compute_type() -> FuncType {
+    FuncType::new([ValueType::I32], [ValueType::I32])
+}
+
+/// Translates the `compute` function of [`WASM`] with `ctx` and installs it into `stubbed`.
+fn install_compute(engine: &Engine, stubbed: &Module, ctx: &TranslationContext) {
+    let (locals, body) = last_func_body(&wasm(BODY));
+    let bytes = engine
+        .translate_func(&compute_type(), &locals, &body, ctx)
+        .unwrap();
+    let func = stubbed.get_compiled_func(COMPUTE).unwrap();
+    // Safety: `ctx` matches `stubbed` which has not yet been executed.
+    unsafe { engine.install_func(func, bytes) }.unwrap();
+}
+
+#[test]
+fn installed_func_matches_translated_module() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm(BODY)[..]).unwrap();
+    let expected = run(&engine, &module, &INPUTS);
+    let stubbed = Module::new(&engine, &wasm("(unreachable)")[..]).unwrap();
+    let ctx = TranslationContext::from_module(&stubbed);
+    install_compute(&engine, &stubbed, &ctx);
+    assert_eq!(run(&engine, &stubbed, &INPUTS), expected);
+    // Note: the installed function keeps its index for trap origins.
+    let func = stubbed.get_compiled_func(COMPUTE).unwrap();
+    assert_eq!(engine.func_info(func).unwrap().func_index, Some(COMPUTE));
+}
+
+#[test]
+fn install_from_host_func_is_rejected() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm(BODY)[..]).unwrap();
+    let expected = run(&engine, &module, &INPUTS);
+    let stubbed = Module::new(&engine, &wasm("(unreachable)")[..]).unwrap();
+    let ctx = TranslationContext::from_module(&stubbed);
+    let (locals, body) = last_func_body(&wasm(BODY));
+    let caller = wat::parse_str(
+        r#"
+        (module
+            (import "env" "install" (func $install))
+            (func (export "run") (call $install))
+        )
+        "#,
+    )
+    .unwrap();
+    let caller = Module::new(&engine, &caller[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    let func = stubbed.get_compiled_func(COMPUTE).unwrap();
+    linker
+        .func_wrap("env", "install", move |caller: Caller<()>| {
+            let engine = caller.engine();
+            let bytes = engine
+                .translate_func(&compute_type(), &locals, &body, &ctx)
+                .unwrap();
+            // Safety: `ctx` matches `stubbed` which has not yet been executed.
+            let error = unsafe { engine.install_func(func, bytes) }.unwrap_err();
+            assert!(matches!(
+                error.kind(),
+                ErrorKind::Func(FuncError::EngineAlreadyExecuting)
+            ));
+        })
+        .unwrap();
+    linker
+        .instantiate(&mut store, &caller)
+        .unwrap()
+        .start(&mut store)
+        .unwrap()
+        .get_typed_func::<(), ()>(&store, "run")
+        .unwrap()
+        .call(&mut store, ())
+        .unwrap();
+    // Note: installing outside of Wasm executions still succeeds afterwards.
+    install_compute(
+        &engine,
+        &stubbed,
+        &TranslationContext::from_module(&stubbed),
+    );
+    assert_eq!(run(&engine, &stubbed, &INPUTS), expected);
+}
+
+#[test]
+fn installed_func_matches_with_manual_context() {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, &wasm(BODY)[..]).unwrap();
+    let expected = run(&engine, &module, &INPUTS);
+    let stubbed = Module::new(&engine, &wasm("(unreachable)")[..]).unwrap();
+    let compiled = |index| stubbed.get_compiled_func(index).unwrap();
+    let mut builder = TranslationContext::builder(&engine);
+    let unop = builder.push_type(compute_type());
+    let nullop = builder.push_type(FuncType::new([], [ValueType::I32]));
+    builder.import_func("env", "host", unop);
+    builder.push_func(unop, compiled(1));
+    builder.push_func(unop, compiled(2));
+    builder.push_func(nullop, compiled(3));
+    builder.push_func(unop, compiled(COMPUTE));
+    builder.push_memory(MemoryType::new(1, None).unwrap());
+    builder.push_table(TableType::new(ValueType::FuncRef, 2, None));
+    builder.push_global(GlobalType::new(ValueType::I32, Mutability::Var));
+    builder.push_global(GlobalType::new(ValueType::I32, Mutability::Const));
+    builder.push_element_segment(ValueType::FuncRef);
+    install_compute(&engine, &stubbed, &builder.finish());
+    assert_eq!(run(&engine, &stubbed, &INPUTS), expected);
+}
+
+#[test]
+fn unknown_func_type_is_rejected() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm("(unreachable)")[..]).unwrap();
+    let ctx = TranslationContext::from_module(&module);
+    let func_type = FuncType::new([ValueType::I64], [ValueType::I32]);
+    let error = engine
+        .translate_func(&func_type, &[], &[0x00, 0x0B], &ctx)
+        .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Translation(_)));
+    assert_eq!(
+        error.to_string(),
+        "encountered function type missing in the translation context"
+    );
+}
+
+#[test]
+fn invalid_func_is_rejected() {
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm("(unreachable)")[..]).unwrap();
+    let ctx = TranslationContext::from_module(&module);
+    let invalid: [&[u8]; 3] = [
+        // Note: returns an `i64` instead of an `i32`.
+        &[0x42, 0x00, 0x0B],
+        // Note: calls the out of bounds function at index 5.
+        &[0x20, 0x00, 0x10, 0x05, 0x0B],
+        // Note: reads the out of bounds global variable at index 2.
+        &[0x23, 0x02, 0x0B],
+    ];
+    for body in invalid {
+        let error = engine
+            .translate_func(&compute_type(), &[], body, &ctx)
+            .unwrap_err();
+        assert!(
+            matches!(error.kind(), ErrorKind::Wasm(_)),
+            "expected Wasm error for {body:X?} but found: {error}"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Tests for overriding the messages of [`TrapCode`]s via [`Engine::set_trap_message_override`].
+
+use wasmi::{core::TrapCode, Engine, Error, Linker, Module, Store};
+
+/// A Wasm module with functions that trap with different [`TrapCode`]s.
+const WASM: &str = r#"
+    (module
+        (func (export "div") (param i32 i32) (result i32)
+            (i32.div_u (local.get 0) (local.get 1))
+        )
+        (func (export "div_by_zero") (param i32) (result i32)
+            (i32.div_u (local.get 0) (i32.const 0))
+        )
+        (func (export "unreachable") (param i32) (result i32)
+            (unreachable)
+        )
+    )
+"#;
+
+/// Calls the exported function `name` of [`WASM`] with `params` using `engine`.
+fn call(engine: &Engine, name: &str, params: &[i32]) -> Error {
+    let wasm = wat::parse_str(WASM).unwrap();
+    let module = Module::new(engine, &wasm[..]).unwrap();
+    let mut store = Store::new(engine, ());
+    let instance = <Linker<()>>::new(engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let func = instance.get_func(&store, name).unwrap();
+    let params = params.iter().copied().map(Into::into).collect::<Vec<_>>();
+    let mut results = [0_i32.into()];
+    func.call(&mut store, &params, &mut results).unwrap_err()
+}
+
+/// Returns the expected message of `error` with the `message` of its trap.
+fn with_origin(message: &str, error: &Error) -> String {
+    let origin = error.trap_origin().unwrap();
+    format!(
+        "{message} (function {} at offset {:#x})",
+        origin.func_index(),
+        origin.wasm_offset()
+    )
+}
+
+#[test]
+fn default_messages() {
+    let engine = Engine::default();
+    let error = call(&engine, "div", &[1, 0]);
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(
+        error.to_string(),
+        TrapCode::IntegerDivisionByZero.trap_message()
+    );
+    let error = call(&engine, "unreachable", &[0]);
+    assert_eq!(error.trap_origin().unwrap().func_index(), 2);
+    assert_eq!(
+        error.to_string(),
+        with_origin(TrapCode::UnreachableCodeReached.trap_message(), &error)
+    );
+}
+
+#[test]
+fn overridden_message() {
+    let engine = Engine::default();
+    engine.set_trap_message_override(TrapCode::IntegerDivisionByZero, "Division durch Null");
+    let error = call(&engine, "div", &[1, 0]);
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(error.to_string(), "Division durch Null");
+    let error = call(&engine, "div_by_zero", &[1]);
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert_eq!(
+        error.to_string(),
+        with_origin("Division durch Null", &error)
+    );
+    // Note: codes that are not overridden keep their default messages.
+    let error = call(&engine, "unreachable", &[0]);
+    assert_eq!(
+        error.to_string(),
+        with_origin(TrapCode::UnreachableCodeReached.trap_message(), &error)
+    );
+}
+
+#[test]
+fn overrides_are_snapshotted() {
+    let engine = Engine::default();
+    engine.set_trap_message_override(TrapCode::IntegerDivisionByZero, "first");
+    let first = call(&engine, "div", &[1, 0]);
+    engine.set_trap_message_override(TrapCode::IntegerDivisionByZero, "second");
+    engine.set_trap_message_override(TrapCode::UnreachableCodeReached, "unreachable");
+    let second = call(&engine, "div", &[1, 0]);
+    assert_eq!(first.to_string(), "first");
+    assert_eq!(second.to_string(), "second");
+    let error = call(&engine, "unreachable", &[0]);
+    assert_eq!(error.to_string(), with_origin("unreachable", &error));
+    // Note: overrides only apply to the traps raised by the same engine.
+    let error = call(&Engine::default(), "div", &[1, 0]);
+    assert_eq!(
+        error.to_string(),
+        TrapCode::IntegerDivisionByZero.trap_message()
+    );
+}
@@ -0,0 +1,141 @@
+//! Tests for the [`TrapOrigin`] of errors raised by trapping Wasm operators.
+
+use wasmi::{
+    core::TrapCode,
+    CompilationMode,
+    Config,
+    Engine,
+    Error,
+    Linker,
+    Module,
+    Store,
+    TrapOrigin,
+};
+
+/// A Wasm module with multiple `unreachable` operators in a single function.
+///
+/// The `host` function import shifts the indices of all Wasm functions by one.
+const WASM: &str = r#"
+    (module
+        (import "env" "host" (func))
+        (func (export "select") (param i32) (result i32)
+            (if (i32.eqz (local.get 0))
+                (then (unreachable))
+            )
+            (if (i32.eq (local.get 0) (i32.const 1))
+                (then (unreachable))
+            )
+            (local.get 0)
+        )
+        (func (export "div") (param i32) (result i32)
+            (i32.div_u (local.get 0) (i32.const 0))
+        )
+        (func (export "call") (param i32) (result i32)
+            (call 1 (local.get 0))
+        )
+    )
+"#;
+
+/// Calls the exported function `name` of [`WASM`] compiled with `config` with `param`.
+fn call(config: &Config, name: &str, param: i32) -> Result<i32, Error> {
+    let engine = Engine::new(config);
+    let wasm = wat::parse_str(WASM).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let mut linker = <Linker<()>>::new(&engine);
+    linker.func_wrap("env", "host", || {}).unwrap();
+    let instance = linker
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    instance
+        .get_typed_func::<i32, i32>(&store, name)
+        .unwrap()
+        .call(&mut store, param)
+}
+
+/// Returns the [`TrapOrigin`] of the trap caused by calling `name` with `param`.
+fn trap_origin(config: &Config, name: &str, param: i32, trap_code: TrapCode) -> TrapOrigin {
+    let error = call(config, name, param).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(trap_code));
+    let origin = error.trap_origin().unwrap();
+    if let Some(wasm_offset) = error.trap_wasm_offset() {
+        // The trap origin agrees with the address map if it is enabled.
+        assert_eq!(origin.wasm_offset(), wasm_offset);
+    }
+    origin
+}
+
+/// Returns all [`Config`]s under test.
+fn configs() -> Vec<Config> {
+    let mut configs = Vec::new();
+    for mode in [
+        CompilationMode::Eager,
+        CompilationMode::LazyTranslation,
+        CompilationMode::Lazy,
+    ] {
+        for address_map in [false, true] {
+            for optimization_level in [0, 1] {
+                let mut config = Config::default();
+                config.compilation_mode(mode);
+                config.generate_address_map(address_map);
+                config.optimization_level(optimization_level);
+                configs.push(config);
+            }
+        }
+    }
+    configs
+}
+
+#[test]
+fn distinct_unreachables() {
+    for config in configs() {
+        let trap = TrapCode::UnreachableCodeReached;
+        let first = trap_origin(&config, "select", 0, trap);
+        let second = trap_origin(&config, "select", 1, trap);
+        assert_eq!(first.func_index(), 1);
+        assert_eq!(second.func_index(), 1);
+        assert!(first.wasm_offset() < second.wasm_offset());
+        assert_eq!(call(&config, "select", 2).unwrap(), 2);
+        // The trap origin is the same for traps raised in a nested call.
+        assert_eq!(trap_origin(&config, "call", 1, trap), second);
+    }
+}
+
+#[test]
+fn constant_division_by_zero() {
+    for config in configs() {
+        let origin = trap_origin(&config, "div", 1, TrapCode::IntegerDivisionByZero);
+        assert_eq!(origin.func_index(), 2);
+    }
+}
+
+#[test]
+fn no_trap_origin_for_dynamic_traps() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+            (func (export "div") (param i32 i32) (result i32)
+                (i32.div_u (local.get 0) (local.get 1))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let error = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "div")
+        .unwrap()
+        .call(&mut store, (1, 0))
+        .unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::IntegerDivisionByZero));
+    assert!(error.trap_origin().is_none());
+}
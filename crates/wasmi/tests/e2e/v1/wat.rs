@@ -0,0 +1,84 @@
+//! Tests for [`Module::new_wat`] and [`Module::to_wat`] of the `wat` crate feature.
+
+use assert_matches::assert_matches;
+use wasmi::{errors::ErrorKind, Engine, Linker, Module, Store};
+
+#[test]
+fn new_wat_runs() {
+    let engine = Engine::default();
+    let module = Module::new_wat(
+        &engine,
+        r#"
+        (module
+            (func (export "add") (param i32 i32) (result i32)
+                (i32.add (local.get 0) (local.get 1))
+            )
+        )
+        "#,
+    )
+    .unwrap();
+    let mut store = Store::new(&engine, ());
+    let instance = <Linker<()>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let add = instance
+        .get_typed_func::<(i32, i32), i32>(&store, "add")
+        .unwrap();
+    assert_eq!(add.call(&mut store, (1, 2)).unwrap(), 3);
+}
+
+#[test]
+fn new_wat_syntax_error() {
+    let engine = Engine::default();
+    let wat = "(module\n  (func (result i32)\n    (i32.const)))";
+    let error = Module::new_wat(&engine, wat).unwrap_err();
+    let ErrorKind::Wat(error) = error.kind() else {
+        panic!("expected a WAT error but found: {error:?}")
+    };
+    assert_eq!(error.line(), 3);
+    assert_eq!(error.column(), 15);
+    assert!(!error.message().contains(":3:15"));
+    assert!(error.to_string().contains(":3:15"));
+}
+
+#[test]
+fn new_wat_invalid_module() {
+    let engine = Engine::default();
+    let error = Module::new_wat(&engine, "(module (func (result i32) (i64.const 0)))").unwrap_err();
+    assert_matches!(error.kind(), ErrorKind::Wasm(_));
+}
+
+#[test]
+fn to_wat_round_trip() {
+    let engine = Engine::default();
+    let wat = r#"
+        (module
+            (memory 1)
+            (func $double (export "double") (param $x i64) (result i64)
+                (i64.mul (local.get $x) (i64.const 2))
+            )
+        )
+    "#;
+    let module = Module::new_wat(&engine, wat).unwrap();
+    let printed = module.to_wat();
+    // The name section is retained and printed.
+    assert!(printed.contains("$double"), "{printed}");
+    assert!(printed.contains("$x"), "{printed}");
+    assert!(printed.contains("i64.mul"), "{printed}");
+    // Printing the module parsed from the printed text yields the same text.
+    let reparsed = Module::new_wat(&engine, &printed).unwrap();
+    assert_eq!(reparsed.to_wat(), printed);
+}
+
+#[test]
+fn to_wat_of_binary() {
+    let engine = Engine::default();
+    let wasm = wat::parse_str(r#"(module (func (export "f") (nop)))"#).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let printed = module.to_wat();
+    assert!(printed.contains(r#"(export "f" (func 0))"#), "{printed}");
+    // The binary printed by `to_wat` is the original binary and not the translated one.
+    assert_eq!(wat::parse_str(&printed).unwrap(), wasm);
+}
@@ -0,0 +1,325 @@
+//! Tests for write watchpoints guarding ranges of linear memories.
+
+use std::{
+    cell::Cell,
+    sync::{Arc, Mutex},
+};
+use wasmi::{
+    core::TrapCode,
+    ir::Instruction,
+    Config,
+    Engine,
+    ExecutionDigest,
+    Instance,
+    Linker,
+    Memory,
+    Module,
+    RegisterReader,
+    Store,
+    WatchpointHit,
+};
+
+/// A Wasm module with a single page linear memory.
+///
+/// - `store32` and `store8` store a value at an address.
+/// - `load` loads an `i32` at an address.
+/// - `fill`, `copy` and `init` execute the respective bulk memory instructions.
+/// - `run` stores and loads `n` times in a loop and returns the sum of the loaded values.
+const WAT: &str = r#"
+    (module
+        (memory (export "mem") 1)
+        (data $data "\01\02\03\04\05\06\07\08")
+        (func (export "store32") (param $address i32) (param $value i32)
+            (i32.store (local.get $address) (local.get $value))
+        )
+        (func (export "store8") (param $address i32) (param $value i32)
+            (i32.store8 (local.get $address) (local.get $value))
+        )
+        (func (export "load") (param $address i32) (result i32)
+            (i32.load (local.get $address))
+        )
+        (func (export "fill") (param $dst i32) (param $value i32) (param $len i32)
+            (memory.fill (local.get $dst) (local.get $value) (local.get $len))
+        )
+        (func (export "copy") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.copy (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "init") (param $dst i32) (param $src i32) (param $len i32)
+            (memory.init $data (local.get $dst) (local.get $src) (local.get $len))
+        )
+        (func (export "run") (param $n i32) (result i32)
+            (local $sum i32)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (i32.store (i32.shl (local.get $n) (i32.const 2)) (local.get $n))
+                    (local.set $sum
+                        (i32.add
+                            (local.get $sum)
+                            (i32.load (i32.shl (local.get $n) (i32.const 2)))
+                        )
+                    )
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $sum)
+        )
+    )
+"#;
+
+/// The instantiated [`WAT`] module.
+struct Test {
+    store: Store<()>,
+    instance: Instance,
+    memory: Memory,
+}
+
+impl Test {
+    /// Instantiates [`WAT`] with `config`.
+    fn new(config: &Config) -> Self {
+        let engine = Engine::new(config);
+        let module = Module::new(&engine, &wat::parse_str(WAT).unwrap()[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = <Linker<()>>::new(&engine)
+            .instantiate(&mut store, &module)
+            .unwrap()
+            .start(&mut store)
+            .unwrap();
+        let memory = instance.get_memory(&store, "mem").unwrap();
+        Self {
+            store,
+            instance,
+            memory,
+        }
+    }
+
+    /// Returns the bytes of the linear memory at `range`.
+    fn bytes(&self, range: std::ops::Range<usize>) -> &[u8] {
+        &self.memory.data(&self.store)[range]
+    }
+
+    /// Calls the exported function `name` with `params`.
+    fn call(&mut self, name: &str, params: (u32, u32)) -> Result<(), wasmi::Error> {
+        self.instance
+            .get_typed_func::<(u32, u32), ()>(&self.store, name)
+            .unwrap()
+            .call(&mut self.store, params)
+    }
+
+    /// Calls the exported bulk memory function `name` with `params`.
+    fn bulk(&mut self, name: &str, params: (u32, u32, u32)) -> Result<(), wasmi::Error> {
+        self.instance
+            .get_typed_func::<(u32, u32, u32), ()>(&self.store, name)
+            .unwrap()
+            .call(&mut self.store, params)
+    }
+
+    /// Calls the exported `load` function.
+    fn load(&mut self, address: u32) -> i32 {
+        self.instance
+            .get_typed_func::<u32, i32>(&self.store, "load")
+            .unwrap()
+            .call(&mut self.store, address)
+            .unwrap()
+    }
+}
+
+/// Asserts that `result` is a [`TrapCode::Watchpoint`] trap.
+fn assert_watchpoint_trap(result: Result<(), wasmi::Error>) {
+    assert_eq!(
+        result.unwrap_err().as_trap_code(),
+        Some(TrapCode::Watchpoint)
+    );
+}
+
+#[test]
+fn scalar_store_hit_traps() {
+    let mut test = Test::new(&Config::default());
+    test.store.add_write_watchpoint(&test.memory, 100..104);
+    assert_watchpoint_trap(test.call("store32", (100, 0x0403_0201)));
+    assert_watchpoint_trap(test.call("store8", (103, 0xFF)));
+    assert_eq!(test.bytes(100..104), [0; 4]);
+    // Writes outside of the watched range and loads within it are unaffected.
+    test.call("store8", (99, 0xAA)).unwrap();
+    test.call("store32", (104, 0x0403_0201)).unwrap();
+    assert_eq!(test.bytes(99..108), [0xAA, 0, 0, 0, 0, 1, 2, 3, 4]);
+    assert_eq!(test.load(100), 0);
+    assert_eq!(test.load(104), 0x0403_0201);
+    // Out of bounds stores still trap as such.
+    assert_eq!(
+        test.call("store32", (0xFFFF, 0))
+            .unwrap_err()
+            .as_trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+}
+
+#[test]
+fn straddling_store_at_range_edge_traps() {
+    let mut test = Test::new(&Config::default());
+    test.store.add_write_watchpoint(&test.memory, 100..104);
+    // Stores that partially overlap the start or the end of the watched range.
+    assert_watchpoint_trap(test.call("store32", (97, u32::MAX)));
+    assert_watchpoint_trap(test.call("store32", (103, u32::MAX)));
+    assert_eq!(test.bytes(96..108), [0; 12]);
+    // Stores that end or start right at the edges of the watched range.
+    test.call("store32", (96, u32::MAX)).unwrap();
+    test.call("store32", (104, u32::MAX)).unwrap();
+    assert_eq!(test.bytes(96..100), [0xFF; 4]);
+    assert_eq!(test.bytes(100..104), [0; 4]);
+    assert_eq!(test.bytes(104..108), [0xFF; 4]);
+}
+
+#[test]
+fn bulk_memory_overlapping_range_traps() {
+    let mut test = Test::new(&Config::default());
+    test.memory.data_mut(&mut test.store)[..8].copy_from_slice(&[9; 8]);
+    test.store.add_write_watchpoint(&test.memory, 100..104);
+    assert_watchpoint_trap(test.bulk("fill", (90, 0xFF, 11)));
+    assert_watchpoint_trap(test.bulk("fill", (103, 0xFF, 100)));
+    assert_watchpoint_trap(test.bulk("copy", (98, 0, 8)));
+    assert_watchpoint_trap(test.bulk("init", (101, 0, 1)));
+    assert_eq!(test.bytes(8..512), [0; 504]);
+    // Bulk writes next to the watched range and of zero length are applied.
+    test.bulk("fill", (90, 0xFF, 10)).unwrap();
+    test.bulk("fill", (100, 0xFF, 0)).unwrap();
+    test.bulk("copy", (104, 0, 4)).unwrap();
+    test.bulk("init", (108, 0, 4)).unwrap();
+    assert_eq!(test.bytes(90..100), [0xFF; 10]);
+    assert_eq!(test.bytes(100..112), [0, 0, 0, 0, 9, 9, 9, 9, 1, 2, 3, 4]);
+    // Out of bounds bulk writes still trap as such.
+    assert_eq!(
+        test.bulk("fill", (0xFFFF, 0, 2))
+            .unwrap_err()
+            .as_trap_code(),
+        Some(TrapCode::MemoryOutOfBounds)
+    );
+}
+
+/// A [`WatchpointHit`] recorded by a write watchpoint callback.
+#[derive(Debug, PartialEq, Eq)]
+struct Hit {
+    address: u64,
+    len: usize,
+    old_bytes: Vec<u8>,
+    new_bytes: Vec<u8>,
+}
+
+impl From<&WatchpointHit<'_>> for Hit {
+    fn from(hit: &WatchpointHit) -> Self {
+        Self {
+            address: hit.address(),
+            len: hit.len(),
+            old_bytes: hit.old_bytes().to_vec(),
+            new_bytes: hit.new_bytes().to_vec(),
+        }
+    }
+}
+
+#[test]
+fn callback_is_invoked_before_write() {
+    let mut test = Test::new(&Config::default());
+    test.memory.data_mut(&mut test.store)[96..104].copy_from_slice(&[7; 8]);
+    let hits = Arc::new(Mutex::new(Vec::new()));
+    let id = test
+        .store
+        .add_write_watchpoint_callback(&test.memory, 100..104, {
+            let hits = hits.clone();
+            move |hit| hits.lock().unwrap().push(Hit::from(hit))
+        });
+    test.call("store32", (98, 0x0403_0201)).unwrap();
+    test.bulk("fill", (0, 0xFF, 100)).unwrap();
+    test.bulk("fill", (102, 0xEE, 4)).unwrap();
+    assert_eq!(
+        *hits.lock().unwrap(),
+        [
+            Hit {
+                address: 98,
+                len: 4,
+                old_bytes: vec![7; 4],
+                new_bytes: vec![1, 2, 3, 4],
+            },
+            Hit {
+                address: 102,
+                len: 4,
+                old_bytes: vec![7, 7, 0, 0],
+                new_bytes: vec![0xEE; 4],
+            },
+        ]
+    );
+    assert_eq!(
+        test.bytes(98..106),
+        [0xFF, 0xFF, 3, 4, 0xEE, 0xEE, 0xEE, 0xEE]
+    );
+    // Trapping write watchpoints take precedence over callbacks.
+    test.store.add_write_watchpoint(&test.memory, 103..104);
+    assert_watchpoint_trap(test.call("store32", (100, 0)));
+    assert_eq!(hits.lock().unwrap().len(), 2);
+    assert!(test.store.remove_write_watchpoint(id));
+}
+
+#[test]
+fn removed_watchpoints_are_not_hit() {
+    let mut test = Test::new(&Config::default());
+    let a = test.store.add_write_watchpoint(&test.memory, 100..104);
+    let b = test.store.add_write_watchpoint(&test.memory, 200..204);
+    assert_ne!(a, b);
+    assert!(test.store.remove_write_watchpoint(a));
+    assert!(!test.store.remove_write_watchpoint(a));
+    test.call("store32", (100, 1)).unwrap();
+    assert_watchpoint_trap(test.call("store32", (200, 1)));
+    assert!(test.store.remove_write_watchpoint(b));
+    test.call("store32", (200, 1)).unwrap();
+    assert_eq!(test.load(100), 1);
+    assert_eq!(test.load(200), 1);
+    // Empty watched ranges are never hit.
+    test.store.add_write_watchpoint(&test.memory, 300..300);
+    test.call("store32", (298, 1)).unwrap();
+}
+
+thread_local! {
+    /// The number of executed instructions counted by [`count`].
+    static EXECUTED: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Counts every executed instruction in [`EXECUTED`].
+fn count(_instr: &Instruction, _registers: &dyn RegisterReader) -> u64 {
+    EXECUTED.with(|executed| executed.set(executed.get() + 1));
+    1
+}
+
+#[test]
+fn no_overhead_without_watchpoints() {
+    let mut config = Config::default();
+    config
+        .consume_fuel(true)
+        .execution_digest(ExecutionDigest::Custom(count));
+    // Returns the result, executed instructions and consumed fuel of the `run` function.
+    let run = |test: &mut Test| {
+        let run = test
+            .instance
+            .get_typed_func::<i32, i32>(&test.store, "run")
+            .unwrap();
+        test.store.add_fuel(1_000_000).unwrap();
+        EXECUTED.with(|executed| executed.set(0));
+        let result = run.call(&mut test.store, 100).unwrap();
+        let fuel = test.store.fuel_consumed().unwrap();
+        (result, EXECUTED.with(Cell::get), fuel)
+    };
+    let mut test = Test::new(&config);
+    let unwatched = run(&mut test);
+    assert_eq!(unwatched.0, 5050);
+    // Removed write watchpoints leave no trace behind.
+    let mut test = Test::new(&config);
+    let id = test
+        .store
+        .add_write_watchpoint(&test.memory, 0x8000..0x8004);
+    assert!(test.store.remove_write_watchpoint(id));
+    assert_eq!(run(&mut test), unwatched);
+    // Watched linear memories execute the same instructions on their slow path.
+    let mut test = Test::new(&config);
+    test.store
+        .add_write_watchpoint(&test.memory, 0x8000..0x8004);
+    assert_eq!(run(&mut test), unwatched);
+}
@@ -0,0 +1,102 @@
+//! Tests for the yield callback installed via [`Store::set_yield_callback`].
+
+use wasmi::{core::TrapCode, Config, Engine, Linker, Module, Store, TypedFunc, YieldDecision};
+
+/// A Wasm module with a `sum` function that sums up all numbers from `n` to `1`.
+const WAT: &str = r#"
+    (module
+        (func (export "sum") (param $n i32) (result i64)
+            (local $sum i64)
+            (block $exit
+                (loop $continue
+                    (br_if $exit (i32.eqz (local.get $n)))
+                    (local.set $sum
+                        (i64.add (local.get $sum) (i64.extend_i32_u (local.get $n)))
+                    )
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br $continue)
+                )
+            )
+            (local.get $sum)
+        )
+    )
+"#;
+
+/// Returns the `sum` function of [`WAT`] instantiated for `config`.
+///
+/// The [`Store`] data counts the invocations of the yield callback.
+fn setup(config: &Config) -> (Store<u64>, TypedFunc<i32, i64>) {
+    let engine = Engine::new(config);
+    let wasm = wat::parse_str(WAT).unwrap();
+    let module = Module::new(&engine, &wasm[..]).unwrap();
+    let mut store = Store::new(&engine, 0);
+    let instance = <Linker<u64>>::new(&engine)
+        .instantiate(&mut store, &module)
+        .unwrap()
+        .start(&mut store)
+        .unwrap();
+    let sum = instance.get_typed_func(&store, "sum").unwrap();
+    (store, sum)
+}
+
+/// Returns the fuel consumed by calling `sum` with `n`.
+fn fuel_of_sum(n: i32) -> u64 {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let (mut store, sum) = setup(&config);
+    store.add_fuel(u64::MAX / 2).unwrap();
+    sum.call(&mut store, n).unwrap();
+    store.fuel_consumed().unwrap()
+}
+
+/// Returns the number of yield callback invocations for calling `sum` with `n`
+/// and a yield `interval` for executions with `config`.
+fn count_yields(config: &Config, n: i32, interval: u64) -> u64 {
+    let (mut store, sum) = setup(config);
+    // Note: Adding fuel fails if fuel metering is disabled.
+    _ = store.add_fuel(u64::MAX / 2);
+    store.set_yield_callback(interval, |count| {
+        *count += 1;
+        YieldDecision::Continue
+    });
+    let expected = (1..=i64::from(n)).sum::<i64>();
+    assert_eq!(sum.call(&mut store, n).unwrap(), expected);
+    *store.data()
+}
+
+#[test]
+fn yield_count_is_predictable() {
+    let fuel_per_iteration = fuel_of_sum(2) - fuel_of_sum(1);
+    let fuel = fuel_of_sum(100);
+    let mut consume_fuel = Config::default();
+    consume_fuel.consume_fuel(true);
+    let mut cooperative_yield = Config::default();
+    cooperative_yield.cooperative_yield(true);
+    for config in [consume_fuel, cooperative_yield] {
+        for interval in [fuel_per_iteration, 5 * fuel_per_iteration] {
+            assert_eq!(count_yields(&config, 100, interval), fuel / interval);
+        }
+    }
+}
+
+#[test]
+fn no_yield_without_check_sites() {
+    assert_eq!(count_yields(&Config::default(), 100, 1), 0);
+}
+
+#[test]
+fn yield_callback_aborts() {
+    let mut config = Config::default();
+    config.cooperative_yield(true);
+    let (mut store, sum) = setup(&config);
+    store.set_yield_callback(10, |count| {
+        *count += 1;
+        if *count == 3 {
+            return YieldDecision::Abort(TrapCode::UnreachableCodeReached);
+        }
+        YieldDecision::Continue
+    });
+    let error = sum.call(&mut store, 1_000).unwrap_err();
+    assert_eq!(error.as_trap_code(), Some(TrapCode::UnreachableCodeReached));
+    assert_eq!(*store.data(), 3);
+}
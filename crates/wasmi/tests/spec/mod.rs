@@ -204,3 +204,73 @@ mod fueled {
         let runner = run::run_wasm_spec_test;
     }
 }
+
+mod lazy_table_init {
+    use super::*;
+
+    /// Create a [`Config`] with all Wasm features and lazy table initialization enabled.
+    fn lazy_table_init_config() -> Config {
+        let mut config = test_config(false);
+        config.lazy_table_init(true);
+        config
+    }
+
+    define_spec_tests! {
+        let config = lazy_table_init_config();
+        let runner = run::run_wasm_spec_test;
+
+        fn wasm_bulk("bulk");
+        fn wasm_call_indirect("call_indirect");
+        fn wasm_extended_const_elem("proposals/extended-const/elem");
+        fn wasm_return_call_indirect("proposals/tail-call/return_call_indirect");
+        fn wasm_elem("elem");
+        fn wasm_imports("imports");
+        fn wasm_linking("linking");
+        fn wasm_ref_func("ref_func");
+        fn wasm_table_sub("table-sub");
+        fn wasm_table("table");
+        fn wasm_table_copy("table_copy");
+        fn wasm_table_fill("table_fill");
+        fn wasm_table_get("table_get");
+        fn wasm_table_grow("table_grow");
+        fn wasm_table_init("table_init");
+        fn wasm_table_set("table_set");
+        fn wasm_table_size("table_size");
+    }
+}
+
+mod optimized {
+    use super::*;
+
+    /// Create a [`Config`] with all Wasm features and bytecode optimizations enabled.
+    fn optimized_config() -> Config {
+        let mut config = test_config(false);
+        config.optimization_level(1);
+        config
+    }
+
+    expand_tests! {
+        define_spec_tests,
+
+        let config = optimized_config();
+        let runner = run::run_wasm_spec_test;
+    }
+}
+
+mod inlined {
+    use super::*;
+
+    /// Create a [`Config`] with all Wasm features, bytecode optimizations and inlining enabled.
+    fn inlined_config() -> Config {
+        let mut config = test_config(false);
+        config.optimization_level(2);
+        config
+    }
+
+    expand_tests! {
+        define_spec_tests,
+
+        let config = inlined_config();
+        let runner = run::run_wasm_spec_test;
+    }
+}
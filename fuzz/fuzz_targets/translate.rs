@@ -5,5 +5,6 @@ use wasmi::{Engine, Module};
 fuzz_target!(|data: wasm_smith::Module| {
     let wasm = data.to_bytes();
     let engine = Engine::default();
-    Module::new(&engine, &mut &wasm[..]).unwrap();
+    let module = Module::new(&engine, &mut &wasm[..]).unwrap();
+    module.verify_bytecode().unwrap();
 });